@@ -751,6 +751,7 @@ def isTopLevelKeyword (trimmed : String) : Bool :=
     || startsWith trimmed "constraint "
     || startsWith trimmed "equation "
     || startsWith trimmed "rewrite "
+    || startsWith trimmed "view "
 
 def collectIndentedBlock (lines : Array String) (startIndex : Nat) : (String × Nat) :=
   Id.run do
@@ -1195,6 +1196,12 @@ partial def parseSchemaV1 (text : String) : Except ParseError SchemaV1Module :=
     -- ----------------------------------------------------------------------
     match state.currentSection with
     | .none =>
+        -- `import M` header lines are resolved on the Rust side
+        -- (`axi_module_graph`); certificates are checked against the
+        -- flattened module, so the checker only needs to skip them here.
+        if (stripPrefix? line "import ").isSome then
+          i := i + 1
+          continue
         return (← failAt lineNo s!"line outside any section: {line}")
 
     | .schema schemaIndex =>
@@ -1310,6 +1317,14 @@ partial def parseSchemaV1 (text : String) : Except ParseError SchemaV1Module :=
           i := nextIndex
           continue
 
+        -- `view name = <AxQL>` saved queries are executed on the Rust side and
+        -- carry no certificate obligations, so the checker skips them (and
+        -- their continuation lines).
+        if (stripPrefix? line "view ").isSome then
+          let (_, nextIndex) := collectIndentedBlock lines (i + 1)
+          i := nextIndex
+          continue
+
         return (← failAt lineNo s!"unrecognized theory line: {line}")
 
     | .instance instanceIndex =>
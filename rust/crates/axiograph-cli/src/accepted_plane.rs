@@ -451,7 +451,7 @@ fn read_head(accepted_dir: &Path) -> Result<Option<String>> {
     }
 }
 
-fn write_head(accepted_dir: &Path, snapshot_id: &str) -> Result<()> {
+pub(crate) fn write_head(accepted_dir: &Path, snapshot_id: &str) -> Result<()> {
     fs::write(
         accepted_dir.join(ACCEPTED_PLANE_HEAD_FILE),
         format!("{snapshot_id}\n"),
@@ -548,7 +548,7 @@ fn resolve_snapshot_id(accepted_dir: &Path, snapshot_id_or_latest: &str) -> Resu
     Ok(matches[0].clone())
 }
 
-fn snapshot_manifest_path(accepted_dir: &Path, snapshot_id: &str) -> PathBuf {
+pub(crate) fn snapshot_manifest_path(accepted_dir: &Path, snapshot_id: &str) -> PathBuf {
     let file = format!("{}.json", digest_to_filename(snapshot_id));
     accepted_dir.join(ACCEPTED_PLANE_SNAPSHOTS_DIR).join(file)
 }
@@ -632,8 +632,12 @@ fn store_module_if_needed(
     Ok(rel)
 }
 
+pub(crate) fn log_path(accepted_dir: &Path) -> PathBuf {
+    accepted_dir.join(ACCEPTED_PLANE_LOG_V1)
+}
+
 fn append_event(accepted_dir: &Path, event: &AcceptedPlaneEventV1) -> Result<()> {
-    let log_path = accepted_dir.join(ACCEPTED_PLANE_LOG_V1);
+    let log_path = log_path(accepted_dir);
     let mut f = OpenOptions::new()
         .create(true)
         .append(true)
@@ -39,6 +39,7 @@ use nom::sequence::{delimited, preceded, tuple};
 use nom::IResult;
 use roaring::RoaringBitmap;
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{Duration, Instant};
 
 use axiograph_dsl::schema_v1::{parse_path_expr_v3, PathExprV3};
 use axiograph_pathdb::axi_meta::{
@@ -49,12 +50,13 @@ use axiograph_pathdb::axi_meta::{
     ATTR_REWRITE_RULE_VARS, META_ATTR_ID, META_TYPE_REWRITE_RULE,
 };
 use axiograph_pathdb::axi_semantics::MetaPlaneIndex;
+use axiograph_pathdb::cardinality::Direction as EdgeDirection;
 use axiograph_pathdb::certificate::{
     CertificatePayloadV2, CertificateV2, FixedPointProbability, QueryAtomV1, QueryAtomWitnessV1,
     QueryBindingV1, QueryRegexV1, QueryResultProofV1, QueryResultProofV2, QueryRowV1, QueryRowV2,
     QueryTermV1, QueryV1, QueryV2, ReachabilityProofV2,
     QueryAtomV3, QueryAtomWitnessV3, QueryBindingV3, QueryRegexV3, QueryResultProofV3, QueryRowV3,
-    QueryTermV3, QueryV3, PathRewriteStepV3, RewriteDerivationProofV3,
+    QueryTermV3, QueryV3, PathRewriteStepV3, RewriteDerivationProofV3, TypedLiteralV1,
 };
 use axiograph_pathdb::witness;
 use axiograph_pathdb::{DbToken, DbTokenMismatch};
@@ -296,6 +298,25 @@ pub fn parse_axql_query(input: &str) -> Result<AxqlQuery> {
     Ok(q)
 }
 
+/// Expand a saved-query reference (`view <name>` / `view <Schema.name>`) into
+/// the AxQL text declared by that view in the `.axi` meta-plane.
+///
+/// Any other text is returned unchanged, so callers that accept AxQL (REPL,
+/// `/query`, report definitions) can take a view reference in its place.
+pub fn resolve_axql_view_reference(meta: Option<&MetaPlaneIndex>, text: &str) -> Result<String> {
+    let trimmed = text.trim();
+    let Some(name) = trimmed
+        .strip_prefix("view")
+        .filter(|rest| rest.starts_with(char::is_whitespace))
+        .map(str::trim)
+    else {
+        return Ok(text.to_string());
+    };
+    let meta =
+        meta.ok_or_else(|| anyhow!("cannot resolve view `{name}`: no `.axi` meta-plane loaded"))?;
+    Ok(meta.view(name)?.query.clone())
+}
+
 pub fn parse_axql_path_expr(input: &str) -> Result<AxqlPathExpr> {
     let (_, p) = all_consuming(ws(path_expr))(input)
         .map_err(|e| anyhow!("failed to parse axql path expr: {e:?}"))?;
@@ -783,6 +804,126 @@ impl PreparedAxqlQuery {
         })
     }
 
+    /// Execute while timing each planner/executor step.
+    ///
+    /// The plan is rebuilt with profiling on (the cached plan is not timed),
+    /// so estimates are the planner's and actual counts come from this run.
+    fn execute_profiled(
+        &mut self,
+        db: &axiograph_pathdb::PathDB,
+        meta: Option<&MetaPlaneIndex>,
+        profile: &mut Vec<OperatorProfile>,
+    ) -> Result<AxqlResult> {
+        let actual = db.db_token();
+        if self.db_token != actual {
+            return Err(anyhow!(DbTokenMismatch {
+                expected: self.db_token,
+                actual,
+            }));
+        }
+
+        let started = Instant::now();
+        if self.lowered.vars.is_empty() {
+            let ok = self.lowered.check_grounded(db, &mut self.rpq, meta)?;
+            profile.push(OperatorProfile {
+                operator: "grounded check".to_string(),
+                index: None,
+                missed: None,
+                estimated: Some(1),
+                actual: Some(u64::from(ok)),
+                elapsed: started.elapsed(),
+            });
+            let rows = if ok { vec![BTreeMap::new()] } else { vec![] };
+            return Ok(AxqlResult {
+                selected_vars: Vec::new(),
+                rows,
+                truncated: false,
+            });
+        }
+
+        if let Some(result) = self
+            .lowered
+            .fast_single_path_query(db, &mut self.rpq, meta)?
+        {
+            let path_atom = self
+                .lowered
+                .atoms
+                .iter()
+                .find(|a| matches!(a, LoweredAtom::Edge { .. } | LoweredAtom::Rpq { .. }));
+            let (index, missed) = path_atom
+                .map(|a| self.lowered.path_index_usage(a))
+                .unwrap_or((None, None));
+            profile.push(OperatorProfile {
+                operator: format!(
+                    "single-path fast path {}",
+                    path_atom
+                        .map(|a| self.lowered.render_atom_for_explain(a))
+                        .unwrap_or_default()
+                ),
+                index,
+                missed,
+                estimated: None,
+                actual: Some(result.rows.len() as u64),
+                elapsed: started.elapsed(),
+            });
+            return Ok(result);
+        }
+
+        let plan = self
+            .lowered
+            .plan_profiled(db, &mut self.rpq, meta, Some(profile))?;
+
+        let started = Instant::now();
+        let mut assigned: Vec<Option<u32>> = vec![None; self.lowered.vars.len()];
+        let mut rows: Vec<BTreeMap<String, u32>> = Vec::new();
+        let mut truncated = false;
+        self.lowered.search(
+            db,
+            &plan.candidates,
+            &plan.order,
+            &plan.atom_order,
+            0,
+            &mut assigned,
+            &mut rows,
+            &mut truncated,
+            &mut self.rpq,
+            meta,
+        )?;
+        let bindings = plan
+            .candidates
+            .iter()
+            .fold(1u64, |acc, c| acc.saturating_mul(c.len()));
+        profile.push(OperatorProfile {
+            operator: format!("backtracking search ({} var(s))", plan.order.len()),
+            index: None,
+            missed: None,
+            estimated: Some(bindings.min(self.lowered.limit as u64)),
+            actual: Some(rows.len() as u64),
+            elapsed: started.elapsed(),
+        });
+
+        Ok(AxqlResult {
+            selected_vars: self.lowered.select_vars.clone(),
+            rows,
+            truncated,
+        })
+    }
+
+    /// `q --explain-analyze`: run the query and report every step with its
+    /// index, estimated vs actual cardinality and time.
+    fn explain_analyze_lines(
+        &mut self,
+        db: &axiograph_pathdb::PathDB,
+        meta: Option<&MetaPlaneIndex>,
+        indent: Option<&str>,
+    ) -> Result<(AxqlResult, Vec<String>)> {
+        let started = Instant::now();
+        let mut profile: Vec<OperatorProfile> = Vec::new();
+        let result = self.execute_profiled(db, meta, &mut profile)?;
+        let lines = render_operator_profile(&profile, &result, started.elapsed(), indent);
+        Ok((result, lines))
+    }
+
     pub(crate) fn elaborated_query_text(&self) -> String {
         self.lowered.render_as_axql()
     }
@@ -793,92 +934,7 @@ impl PreparedAxqlQuery {
 
     fn explain_plan_lines(&self, indent: Option<&str>) -> Vec<String> {
         let indent = indent.unwrap_or("");
-
-        fn render_term(vars: &[String], term: &LoweredTerm) -> String {
-            match term {
-                LoweredTerm::Var(v) => vars.get(*v).cloned().unwrap_or_else(|| format!("?v{v}")),
-                LoweredTerm::Const(id) => id.to_string(),
-            }
-        }
-
-        fn render_regex(re: &AxqlRegex) -> String {
-            match re {
-                AxqlRegex::Epsilon => "ε".to_string(),
-                AxqlRegex::Rel(r) => r.clone(),
-                AxqlRegex::Seq(parts) => parts.iter().map(render_regex).collect::<Vec<_>>().join("/"),
-                AxqlRegex::Alt(parts) => {
-                    format!(
-                        "({})",
-                        parts.iter().map(render_regex).collect::<Vec<_>>().join("|")
-                    )
-                }
-                AxqlRegex::Star(inner) => format!("{}*", render_regex(inner)),
-                AxqlRegex::Plus(inner) => format!("{}+", render_regex(inner)),
-                AxqlRegex::Opt(inner) => format!("{}?", render_regex(inner)),
-            }
-        }
-
-        let render_atom = |atom: &LoweredAtom| -> String {
-            match atom {
-                LoweredAtom::Type { term, type_name } => {
-                    format!("{} : {}", render_term(&self.lowered.vars, term), type_name)
-                }
-                LoweredAtom::AttrEq { term, key, value } => format!(
-                    "attr({}, \"{}\", \"{}\")",
-                    render_term(&self.lowered.vars, term),
-                    key,
-                    value
-                ),
-                LoweredAtom::AttrContains { term, key, needle } => format!(
-                    "contains({}, \"{}\", \"{}\")",
-                    render_term(&self.lowered.vars, term),
-                    key,
-                    needle
-                ),
-                LoweredAtom::AttrFts { term, key, query } => format!(
-                    "fts({}, \"{}\", \"{}\")",
-                    render_term(&self.lowered.vars, term),
-                    key,
-                    query
-                ),
-                LoweredAtom::AttrFuzzy {
-                    term,
-                    key,
-                    needle,
-                    max_dist,
-                } => format!(
-                    "fuzzy({}, \"{}\", \"{}\", {})",
-                    render_term(&self.lowered.vars, term),
-                    key,
-                    needle,
-                    max_dist
-                ),
-                LoweredAtom::Edge { left, rel, right } => format!(
-                    "{} -{}-> {}",
-                    render_term(&self.lowered.vars, left),
-                    rel,
-                    render_term(&self.lowered.vars, right)
-                ),
-                LoweredAtom::Rpq {
-                    left,
-                    rpq_id,
-                    right,
-                } => {
-                    let rpq = self
-                        .lowered
-                        .rpqs
-                        .get(*rpq_id)
-                        .map(render_regex)
-                        .unwrap_or_else(|| format!("#{rpq_id}"));
-                    format!(
-                        "{} -{}-> {}",
-                        render_term(&self.lowered.vars, left),
-                        rpq,
-                        render_term(&self.lowered.vars, right)
-                    )
-                }
-            }
-        };
+        let render_atom = |atom: &LoweredAtom| self.lowered.render_atom_for_explain(atom);
 
         let mut lines: Vec<String> = Vec::new();
 
@@ -924,7 +980,15 @@ impl PreparedAxqlQuery {
                 .get(*atom_idx)
                 .map(render_atom)
                 .unwrap_or_else(|| format!("<missing atom {atom_idx}>"));
-            lines.push(format!("{indent}  {}. {}", i + 1, atom));
+            match self.plan.atom_costs.get(*atom_idx) {
+                Some(&cost) => lines.push(format!(
+                    "{indent}  {}. {}  (≈{})",
+                    i + 1,
+                    atom,
+                    format_approx_count(cost as u64)
+                )),
+                None => lines.push(format!("{indent}  {}. {}", i + 1, atom)),
+            }
         }
         if self.plan.atom_order.len() > max_atoms {
             lines.push(format!("{indent}  …"));
@@ -998,6 +1062,95 @@ impl PreparedAxqlQueryExpr {
     }
 }
 
+impl PreparedAxqlQueryExpr {
+    /// Run the query with per-step profiling (`q --explain-analyze`).
+    ///
+    /// Disjunctions are profiled branch by branch; the returned result is the
+    /// same as `execute`.
+    pub(crate) fn explain_analyze(
+        &mut self,
+        db: &axiograph_pathdb::PathDB,
+        meta: Option<&MetaPlaneIndex>,
+    ) -> Result<(AxqlResult, Vec<String>)> {
+        match &mut self.inner {
+            PreparedAxqlQueryExprInner::Conjunction(q) => q.explain_analyze_lines(db, meta, None),
+            PreparedAxqlQueryExprInner::Disjunction(q) => {
+                let mut out: Vec<String> = Vec::new();
+                out.push(format!("disjunction: {} branch(es)", q.disjuncts.len()));
+                for (i, d) in q.disjuncts.iter_mut().enumerate() {
+                    out.push(format!("branch {}:", i + 1));
+                    let (_, lines) = d.explain_analyze_lines(db, meta, Some("  "))?;
+                    out.extend(lines);
+                }
+                let result = q.execute(db, meta)?;
+                out.push(format!("rows: {}", result.rows.len()));
+                Ok((result, out))
+            }
+        }
+    }
+}
+
+/// Render `--explain-analyze` output: one line per operator, then the
+/// indexes that were used and the ones the query could not use.
+fn render_operator_profile(
+    profile: &[OperatorProfile],
+    result: &AxqlResult,
+    total: Duration,
+    indent: Option<&str>,
+) -> Vec<String> {
+    let indent = indent.unwrap_or("");
+    let mut lines: Vec<String> = Vec::new();
+    lines.push(format!("{indent}operators:"));
+    for (i, op) in profile.iter().enumerate() {
+        let index = match (op.index, op.missed) {
+            (Some(used), Some(missed)) => format!("[{used}, missed {missed}]"),
+            (Some(used), None) => format!("[{used}]"),
+            (None, Some(missed)) => format!("[scan, missed {missed}]"),
+            (None, None) => "[-]".to_string(),
+        };
+        let estimated = op
+            .estimated
+            .map(|n| format!("≈{}", format_approx_count(n)))
+            .unwrap_or_else(|| "?".to_string());
+        let actual = op
+            .actual
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| "?".to_string());
+        lines.push(format!(
+            "{indent}  {}. {}  {}  est {}  actual {}  {:?}",
+            i + 1,
+            op.operator,
+            index,
+            estimated,
+            actual,
+            op.elapsed
+        ));
+    }
+
+    let mut used: Vec<&str> = profile.iter().filter_map(|op| op.index).collect();
+    used.sort_unstable();
+    used.dedup();
+    let mut missed: Vec<&str> = profile.iter().filter_map(|op| op.missed).collect();
+    missed.sort_unstable();
+    missed.dedup();
+    let list = |names: &[&str]| {
+        if names.is_empty() {
+            "(none)".to_string()
+        } else {
+            names.join(", ")
+        }
+    };
+    lines.push(format!("{indent}indexes used: {}", list(&used)));
+    lines.push(format!("{indent}indexes missed: {}", list(&missed)));
+    lines.push(format!(
+        "{indent}rows: {}{}  total {:?}",
+        result.rows.len(),
+        if result.truncated { " (truncated)" } else { "" },
+        total
+    ));
+    lines
+}
+
 impl PreparedAxqlDisjunction {
     fn execute(
         &mut self,
@@ -2050,11 +2203,48 @@ enum LoweredAtom {
     },
 }
 
+/// Compact count for plan output: `950`, `12.3k`, `4.1M`.
+fn format_approx_count(n: u64) -> String {
+    match n {
+        0..=999 => n.to_string(),
+        1_000..=999_999 => format!("{:.1}k", n as f64 / 1e3),
+        _ if n >= usize::MAX as u64 => "∞".to_string(),
+        _ => format!("{:.1}M", n as f64 / 1e6),
+    }
+}
+
 #[derive(Debug, Clone)]
 struct QueryPlan {
     candidates: Vec<RoaringBitmap>,
     order: Vec<usize>,
     atom_order: Vec<usize>,
+    /// Estimated cost per atom (indexed like `LoweredQuery::atoms`), for `--explain`.
+    atom_costs: Vec<usize>,
+}
+
+/// One measured planner/executor step, for `q --explain-analyze`.
+#[derive(Debug, Clone)]
+struct OperatorProfile {
+    operator: String,
+    /// Index the step was answered from (`type`, `path`, `fact`, `key`, `text`, ...).
+    index: Option<&'static str>,
+    /// Index that would have helped but could not be used (the step scanned instead).
+    missed: Option<&'static str>,
+    estimated: Option<u64>,
+    /// `None` when counting would cost more than the step itself (large RPQ joins).
+    actual: Option<u64>,
+    elapsed: Duration,
+}
+
+/// What `apply_fact_index_constraints` managed to use.
+#[derive(Debug, Clone, Default)]
+struct FactIndexUsage {
+    /// Fact-node vars pruned through the FactIndex.
+    fact_vars: Vec<usize>,
+    /// Key constraints answered by a key lookup.
+    key_lookups: usize,
+    /// Key constraints that could not be used (not every key field bound to a constant).
+    key_misses: usize,
 }
 
 fn lower_query_disjunct(query: &AxqlQuery, disjunct: &[AxqlAtom]) -> Result<LoweredQuery> {
@@ -2958,6 +3148,94 @@ fn normalize_term(
 }
 
 impl LoweredQuery {
+    /// One-line rendering of `atom` for `--explain` output.
+    fn render_atom_for_explain(&self, atom: &LoweredAtom) -> String {
+        fn render_term(vars: &[String], term: &LoweredTerm) -> String {
+            match term {
+                LoweredTerm::Var(v) => vars.get(*v).cloned().unwrap_or_else(|| format!("?v{v}")),
+                LoweredTerm::Const(id) => id.to_string(),
+            }
+        }
+
+        fn render_regex(re: &AxqlRegex) -> String {
+            match re {
+                AxqlRegex::Epsilon => "ε".to_string(),
+                AxqlRegex::Rel(r) => r.clone(),
+                AxqlRegex::Seq(parts) => {
+                    parts.iter().map(render_regex).collect::<Vec<_>>().join("/")
+                }
+                AxqlRegex::Alt(parts) => {
+                    format!(
+                        "({})",
+                        parts.iter().map(render_regex).collect::<Vec<_>>().join("|")
+                    )
+                }
+                AxqlRegex::Star(inner) => format!("{}*", render_regex(inner)),
+                AxqlRegex::Plus(inner) => format!("{}+", render_regex(inner)),
+                AxqlRegex::Opt(inner) => format!("{}?", render_regex(inner)),
+            }
+        }
+
+        match atom {
+            LoweredAtom::Type { term, type_name } => {
+                format!("{} : {}", render_term(&self.vars, term), type_name)
+            }
+            LoweredAtom::AttrEq { term, key, value } => format!(
+                "attr({}, \"{}\", \"{}\")",
+                render_term(&self.vars, term),
+                key,
+                value
+            ),
+            LoweredAtom::AttrContains { term, key, needle } => format!(
+                "contains({}, \"{}\", \"{}\")",
+                render_term(&self.vars, term),
+                key,
+                needle
+            ),
+            LoweredAtom::AttrFts { term, key, query } => format!(
+                "fts({}, \"{}\", \"{}\")",
+                render_term(&self.vars, term),
+                key,
+                query
+            ),
+            LoweredAtom::AttrFuzzy {
+                term,
+                key,
+                needle,
+                max_dist,
+            } => format!(
+                "fuzzy({}, \"{}\", \"{}\", {})",
+                render_term(&self.vars, term),
+                key,
+                needle,
+                max_dist
+            ),
+            LoweredAtom::Edge { left, rel, right } => format!(
+                "{} -{}-> {}",
+                render_term(&self.vars, left),
+                rel,
+                render_term(&self.vars, right)
+            ),
+            LoweredAtom::Rpq {
+                left,
+                rpq_id,
+                right,
+            } => {
+                let rpq = self
+                    .rpqs
+                    .get(*rpq_id)
+                    .map(render_regex)
+                    .unwrap_or_else(|| format!("#{rpq_id}"));
+                format!(
+                    "{} -{}-> {}",
+                    render_term(&self.vars, left),
+                    rpq,
+                    render_term(&self.vars, right)
+                )
+            }
+        }
+    }
+
     fn render_as_axql(&self) -> String {
         fn render_term(vars: &[String], term: &LoweredTerm) -> String {
             match term {
@@ -4815,7 +5093,21 @@ impl LoweredQuery {
                             .sources_with_min_confidence(*t, rel_id, min)
                             .len() as usize,
                     },
-                    _ => db.relations.rel_type_count(rel_id),
+                    // Neither end bound: every edge is a candidate, unless the
+                    // domain on one side times the sketched fan-out is smaller.
+                    _ => {
+                        let edges = db.relations.rel_type_count(rel_id);
+                        let side = |term: &LoweredTerm, direction| {
+                            let domain = term_var(term)
+                                .and_then(|v| candidates.get(v))
+                                .map_or(usize::MAX, |c| c.len() as usize);
+                            let fanout = db.estimate_fanout(rel, direction);
+                            (domain as f64 * fanout).ceil() as usize
+                        };
+                        edges
+                            .min(side(left, EdgeDirection::Forward))
+                            .min(side(right, EdgeDirection::Backward))
+                    }
                 }
             }
             LoweredAtom::Rpq {
@@ -4865,15 +5157,21 @@ impl LoweredQuery {
         scores
     }
 
+    /// Cheap-first atom order, plus the cost estimate of each atom.
     fn atom_order(
         &self,
         db: &axiograph_pathdb::PathDB,
         rpq: &mut RpqContext,
         candidates: &[RoaringBitmap],
-    ) -> Vec<usize> {
+    ) -> (Vec<usize>, Vec<usize>) {
+        let costs: Vec<usize> = self
+            .atoms
+            .iter()
+            .map(|atom| self.estimate_atom_cost(db, rpq, candidates, atom))
+            .collect();
         let mut order: Vec<usize> = (0..self.atoms.len()).collect();
-        order.sort_by_key(|&idx| self.estimate_atom_cost(db, rpq, candidates, &self.atoms[idx]));
-        order
+        order.sort_by_key(|&idx| costs[idx]);
+        (order, costs)
     }
 
     fn plan(
@@ -4881,12 +5179,24 @@ impl LoweredQuery {
         db: &axiograph_pathdb::PathDB,
         rpq: &mut RpqContext,
         meta: Option<&MetaPlaneIndex>,
+    ) -> Result<QueryPlan> {
+        self.plan_profiled(db, rpq, meta, None)
+    }
+
+    /// `plan`, optionally recording each step (for `q --explain-analyze`).
+    fn plan_profiled(
+        &self,
+        db: &axiograph_pathdb::PathDB,
+        rpq: &mut RpqContext,
+        meta: Option<&MetaPlaneIndex>,
+        mut profile: Option<&mut Vec<OperatorProfile>>,
     ) -> Result<QueryPlan> {
         if self.vars.is_empty() {
             return Ok(QueryPlan {
                 candidates: Vec::new(),
                 order: Vec::new(),
                 atom_order: Vec::new(),
+                atom_costs: Vec::new(),
             });
         }
 
@@ -4897,12 +5207,13 @@ impl LoweredQuery {
 
         // Apply unary constraints first (type, attr).
         for atom in &self.atoms {
+            let started = Instant::now();
             match atom {
                 LoweredAtom::Type { term, type_name } => {
                     self.apply_type_constraint(db, &mut candidates, term, type_name, meta)?;
                 }
                 LoweredAtom::AttrEq { term, key, value } => {
-                    self.apply_attr_eq_constraint(db, &mut candidates, term, key, value)?;
+                    self.apply_attr_eq_constraint(db, &mut candidates, term, key, value, meta)?;
                 }
                 LoweredAtom::AttrContains { term, key, needle } => {
                     self.apply_attr_contains_constraint(db, &mut candidates, term, key, needle)?;
@@ -4925,16 +5236,61 @@ impl LoweredQuery {
                         *max_dist,
                     )?;
                 }
-                LoweredAtom::Edge { .. } | LoweredAtom::Rpq { .. } => {}
+                LoweredAtom::Edge { .. } | LoweredAtom::Rpq { .. } => continue,
+            }
+            if let Some(profile) = &mut profile {
+                profile.push(self.unary_profile(db, atom, &candidates, meta, started.elapsed()));
             }
         }
 
         // FactIndex-driven pruning: use `(axi_schema, axi_relation)` and (when available)
         // key constraints to reduce the candidate set for fact-node variables.
-        self.apply_fact_index_constraints(db, &mut candidates, meta)?;
+        let started = Instant::now();
+        let usage = self.apply_fact_index_constraints(db, &mut candidates, meta)?;
+        if let Some(profile) = &mut profile {
+            if !usage.fact_vars.is_empty() || usage.key_misses > 0 {
+                profile.push(OperatorProfile {
+                    operator: format!(
+                        "fact-index pruning ({} var(s), {} key lookup(s))",
+                        usage.fact_vars.len(),
+                        usage.key_lookups
+                    ),
+                    index: Some(if usage.key_lookups > 0 { "key" } else { "fact" }),
+                    missed: (usage.key_misses > 0).then_some("key"),
+                    estimated: None,
+                    actual: Some(usage.fact_vars.iter().map(|&v| candidates[v].len()).sum()),
+                    elapsed: started.elapsed(),
+                });
+            }
+        }
 
         // Arc-consistency-like propagation (best effort pruning).
-        self.propagate_edges(db, &mut candidates, rpq)?;
+        let estimates: Vec<usize> = if profile.is_some() {
+            self.atoms
+                .iter()
+                .map(|atom| self.estimate_atom_cost(db, rpq, &candidates, atom))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let mut atom_times = vec![Duration::ZERO; self.atoms.len()];
+        self.propagate_edges(db, &mut candidates, rpq, &mut atom_times)?;
+        if let Some(profile) = &mut profile {
+            for (atom_idx, atom) in self.atoms.iter().enumerate() {
+                let (LoweredAtom::Edge { .. } | LoweredAtom::Rpq { .. }) = atom else {
+                    continue;
+                };
+                let (index, missed) = self.path_index_usage(atom);
+                profile.push(OperatorProfile {
+                    operator: format!("join {}", self.render_atom_for_explain(atom)),
+                    index,
+                    missed,
+                    estimated: Some(estimates[atom_idx] as u64),
+                    actual: self.count_atom_matches(db, rpq, &candidates, atom)?,
+                    elapsed: atom_times[atom_idx],
+                });
+            }
+        }
 
         // Backtracking search order (smallest domain first, biased by constraint counts).
         let mut order: Vec<usize> = (0..self.vars.len()).collect();
@@ -4949,15 +5305,128 @@ impl LoweredQuery {
             )
         });
 
-        let atom_order = self.atom_order(db, rpq, &candidates);
+        let (atom_order, atom_costs) = self.atom_order(db, rpq, &candidates);
 
         Ok(QueryPlan {
             candidates,
             order,
             atom_order,
+            atom_costs,
         })
     }
 
+    /// Profile row for a unary (type/attr) atom that was just applied.
+    fn unary_profile(
+        &self,
+        db: &axiograph_pathdb::PathDB,
+        atom: &LoweredAtom,
+        candidates: &[RoaringBitmap],
+        meta: Option<&MetaPlaneIndex>,
+        elapsed: Duration,
+    ) -> OperatorProfile {
+        let (term, index, missed, estimated) = match atom {
+            LoweredAtom::Type { term, type_name } => (
+                term,
+                Some("type"),
+                None,
+                Some(db.estimate_type_count(type_name)),
+            ),
+            LoweredAtom::AttrEq { term, key, value } => {
+                let index = if key == ATTR_AXI_RELATION {
+                    "fact"
+                } else if self.composite_attr_eq(db, term, key, value, meta).is_some() {
+                    "composite"
+                } else {
+                    "attr"
+                };
+                (term, Some(index), None, None)
+            }
+            // `contains`/`fuzzy` scan the attribute column; only `fts` is text-indexed.
+            LoweredAtom::AttrContains { term, .. } | LoweredAtom::AttrFuzzy { term, .. } => {
+                (term, None, Some("text"), None)
+            }
+            LoweredAtom::AttrFts { term, .. } => (term, Some("text"), None, None),
+            LoweredAtom::Edge { left, .. } | LoweredAtom::Rpq { left, .. } => {
+                (left, None, None, None)
+            }
+        };
+        let actual = match term {
+            LoweredTerm::Var(v) => candidates[*v].len(),
+            LoweredTerm::Const(_) => u64::from(candidates.iter().any(|c| !c.is_empty())),
+        };
+        OperatorProfile {
+            operator: format!("filter {}", self.render_atom_for_explain(atom)),
+            index,
+            missed,
+            estimated,
+            actual: Some(actual),
+            elapsed,
+        }
+    }
+
+    /// Index used (and missed) by an edge/RPQ atom: plain edges and short
+    /// chains walk the adjacency lists, long chains use the path index, and
+    /// other RPQs run the automaton.
+    fn path_index_usage(&self, atom: &LoweredAtom) -> (Option<&'static str>, Option<&'static str>) {
+        match atom {
+            LoweredAtom::Rpq { rpq_id, .. } => {
+                match self.rpqs.get(*rpq_id).and_then(simple_chain) {
+                    Some(chain) if chain.len() >= PATH_INDEX_MIN_LEN => (Some("path"), None),
+                    Some(_) => (Some("adjacency"), None),
+                    None => (Some("rpq"), Some("path")),
+                }
+            }
+            _ => (Some("adjacency"), None),
+        }
+    }
+
+    /// Number of `(left, right)` pairs satisfying an edge/RPQ atom within the
+    /// current domains. `None` for RPQs whose driving side is too large to
+    /// enumerate.
+    fn count_atom_matches(
+        &self,
+        db: &axiograph_pathdb::PathDB,
+        rpq: &mut RpqContext,
+        candidates: &[RoaringBitmap],
+        atom: &LoweredAtom,
+    ) -> Result<Option<u64>> {
+        const MAX_STARTS: u64 = 1024;
+        match atom {
+            LoweredAtom::Edge { left, rel, right } => {
+                let Some(rel_id) = db.interner.id_of(rel) else {
+                    return Ok(Some(0));
+                };
+                let right = term_domain(right, candidates);
+                let mut count = 0u64;
+                for_each_value(left, candidates, |s| {
+                    let targets = match self.min_confidence {
+                        None => db.relations.targets(s, rel_id),
+                        Some(min) => db.relations.targets_with_min_confidence(s, rel_id, min),
+                    };
+                    count += targets.intersection_len(&right);
+                });
+                Ok(Some(count))
+            }
+            LoweredAtom::Rpq {
+                left,
+                rpq_id,
+                right,
+            } => {
+                let starts = term_domain(left, candidates);
+                if starts.len() > MAX_STARTS {
+                    return Ok(None);
+                }
+                let right = term_domain(right, candidates);
+                let mut count = 0u64;
+                for s in starts.iter() {
+                    count += rpq.reachable_set(db, *rpq_id, s)?.intersection_len(&right);
+                }
+                Ok(Some(count))
+            }
+            _ => Ok(None),
+        }
+    }
+
     fn execute_assignments(
         &self,
         db: &axiograph_pathdb::PathDB,
@@ -5092,6 +5561,7 @@ impl LoweredQuery {
                         entity,
                         key_id: key_id.raw(),
                         value_id: value_id.raw(),
+                        literal: TypedLiteralV1::typed(value),
                     }
                 }
                 LoweredAtom::AttrContains { .. } => {
@@ -5203,6 +5673,7 @@ impl LoweredQuery {
                         entity: witness::stable_entity_id_v1(db, entity)?,
                         key: key.clone(),
                         value: value.clone(),
+                        literal: TypedLiteralV1::typed(value),
                     }
                 }
                 LoweredAtom::AttrContains { .. } => {
@@ -5781,6 +6252,7 @@ impl LoweredQuery {
         term: &LoweredTerm,
         key: &str,
         value: &str,
+        meta: Option<&MetaPlaneIndex>,
     ) -> Result<()> {
         match term {
             LoweredTerm::Var(v) => {
@@ -5790,6 +6262,12 @@ impl LoweredQuery {
                     candidates[*v] &= db.fact_nodes_by_axi_relation(value);
                     return Ok(());
                 }
+                // `?x : T` plus a composite `(T, key)` index: look the value up
+                // directly instead of scanning the column.
+                if let Some(ids) = self.composite_attr_eq(db, term, key, value, meta) {
+                    candidates[*v] &= ids;
+                    return Ok(());
+                }
 
                 let Some(key_id) = db.interner.id_of(key) else {
                     candidates[*v] = RoaringBitmap::new();
@@ -5812,14 +6290,37 @@ impl LoweredQuery {
         Ok(())
     }
 
+    /// Entities satisfying `attr(term, key, value)` and one of `term`'s type
+    /// atoms, from a composite index on that type (if any).
+    fn composite_attr_eq(
+        &self,
+        db: &axiograph_pathdb::PathDB,
+        term: &LoweredTerm,
+        key: &str,
+        value: &str,
+        meta: Option<&MetaPlaneIndex>,
+    ) -> Option<RoaringBitmap> {
+        let LoweredTerm::Var(v) = term else {
+            return None;
+        };
+        self.atoms.iter().find_map(|atom| match atom {
+            LoweredAtom::Type {
+                term: LoweredTerm::Var(tv),
+                type_name,
+            } if tv == v => composite_type_attr_eq(db, type_name, key, value, meta),
+            _ => None,
+        })
+    }
+
     fn apply_fact_index_constraints(
         &self,
         db: &axiograph_pathdb::PathDB,
         candidates: &mut [RoaringBitmap],
         meta: Option<&MetaPlaneIndex>,
-    ) -> Result<()> {
+    ) -> Result<FactIndexUsage> {
+        let mut usage = FactIndexUsage::default();
         if candidates.is_empty() {
-            return Ok(());
+            return Ok(usage);
         }
 
         // Optional context scoping.
@@ -5898,7 +6399,7 @@ impl LoweredQuery {
         }
 
         if relation_by_fact_var.is_empty() {
-            return Ok(());
+            return Ok(usage);
         }
 
         // relation_name -> candidate schemas containing it (from the meta-plane).
@@ -5937,11 +6438,13 @@ impl LoweredQuery {
                         scoped |= db.fact_nodes_by_context(*ctx);
                     }
                     candidates[fact_var] &= scoped;
+                    usage.fact_vars.push(fact_var);
                 }
                 continue;
             };
 
             // Tighten to (schema, relation) candidates using the FactIndex.
+            usage.fact_vars.push(fact_var);
             if resolved_context_ids.is_empty() {
                 candidates[fact_var] &=
                     db.fact_nodes_by_axi_schema_relation(schema_name, relation_name);
@@ -5969,9 +6472,10 @@ impl LoweredQuery {
                 continue;
             };
 
-            let Some(const_fields) = const_fields_by_fact_var.get(&fact_var) else {
-                continue;
-            };
+            let no_const_fields = HashMap::new();
+            let const_fields = const_fields_by_fact_var
+                .get(&fact_var)
+                .unwrap_or(&no_const_fields);
 
             for c in constraints {
                 let axiograph_pathdb::axi_semantics::ConstraintDecl::Key { fields, .. } = c else {
@@ -5991,6 +6495,7 @@ impl LoweredQuery {
                     values.push(*v);
                 }
                 if !ok {
+                    usage.key_misses += 1;
                     continue;
                 }
 
@@ -6006,10 +6511,11 @@ impl LoweredQuery {
                     keyed.insert(id);
                 }
                 candidates[fact_var] &= keyed;
+                usage.key_lookups += 1;
             }
         }
 
-        Ok(())
+        Ok(usage)
     }
 
     fn apply_attr_contains_constraint(
@@ -6082,13 +6588,15 @@ impl LoweredQuery {
         Ok(())
     }
 
+    /// Fixed-point propagation over edge constraints; the time spent on each
+    /// atom is added to `atom_times` (indexed like `atoms`).
     fn propagate_edges(
         &self,
         db: &axiograph_pathdb::PathDB,
         candidates: &mut [RoaringBitmap],
         rpq: &mut RpqContext,
+        atom_times: &mut [Duration],
     ) -> Result<()> {
-        // Fixed-point propagation over edge constraints.
         // This is best-effort pruning; correctness comes from the final search checks.
         let mut changed = true;
         let mut iter = 0usize;
@@ -6096,118 +6604,129 @@ impl LoweredQuery {
             iter += 1;
             changed = false;
 
-            for atom in &self.atoms {
-                match atom {
-                    LoweredAtom::Edge { left, rel, right } => {
-                        let Some(rel_id) = db.interner.id_of(rel) else {
-                            // Relation name not present => no edges.
-                            if let LoweredTerm::Var(v) = left {
-                                if !candidates[*v].is_empty() {
-                                    candidates[*v].clear();
-                                    changed = true;
-                                }
-                            }
-                            if let LoweredTerm::Var(v) = right {
-                                if !candidates[*v].is_empty() {
-                                    candidates[*v].clear();
-                                    changed = true;
-                                }
-                            }
-                            continue;
-                        };
+            for (atom_idx, atom) in self.atoms.iter().enumerate() {
+                let started = Instant::now();
+                changed |= self.propagate_atom(db, candidates, rpq, atom)?;
+                atom_times[atom_idx] += started.elapsed();
+            }
+        }
+        Ok(())
+    }
 
-                        // left -> right
-                        if let Some(right_var) = term_var(right) {
-                            let mut reach = RoaringBitmap::new();
-                            for_each_value(left, candidates, |s| {
-                                reach |= match self.min_confidence {
-                                    None => db.relations.targets(s, rel_id),
-                                    Some(min) => {
-                                        db.relations.targets_with_min_confidence(s, rel_id, min)
-                                    }
-                                };
-                            });
-                            let before = candidates[right_var].len();
-                            candidates[right_var] &= reach;
-                            if candidates[right_var].len() != before {
-                                changed = true;
-                            }
+    /// One propagation pass of a single edge/RPQ atom; whether any domain shrank.
+    fn propagate_atom(
+        &self,
+        db: &axiograph_pathdb::PathDB,
+        candidates: &mut [RoaringBitmap],
+        rpq: &mut RpqContext,
+        atom: &LoweredAtom,
+    ) -> Result<bool> {
+        let mut changed = false;
+        match atom {
+            LoweredAtom::Edge { left, rel, right } => {
+                let Some(rel_id) = db.interner.id_of(rel) else {
+                    // Relation name not present => no edges.
+                    if let LoweredTerm::Var(v) = left {
+                        if !candidates[*v].is_empty() {
+                            candidates[*v].clear();
+                            changed = true;
                         }
+                    }
+                    if let LoweredTerm::Var(v) = right {
+                        if !candidates[*v].is_empty() {
+                            candidates[*v].clear();
+                            changed = true;
+                        }
+                    }
+                    return Ok(changed);
+                };
 
-                        // right -> left (reverse)
-                        if let Some(left_var) = term_var(left) {
-                            let mut reach = RoaringBitmap::new();
-                            for_each_value(right, candidates, |t| {
-                                reach |= match self.min_confidence {
-                                    None => db.relations.sources(t, rel_id),
-                                    Some(min) => {
-                                        db.relations.sources_with_min_confidence(t, rel_id, min)
-                                    }
-                                };
-                            });
-                            let before = candidates[left_var].len();
-                            candidates[left_var] &= reach;
-                            if candidates[left_var].len() != before {
-                                changed = true;
+                // left -> right
+                if let Some(right_var) = term_var(right) {
+                    let mut reach = RoaringBitmap::new();
+                    for_each_value(left, candidates, |s| {
+                        reach |= match self.min_confidence {
+                            None => db.relations.targets(s, rel_id),
+                            Some(min) => db.relations.targets_with_min_confidence(s, rel_id, min),
+                        };
+                    });
+                    let before = candidates[right_var].len();
+                    candidates[right_var] &= reach;
+                    if candidates[right_var].len() != before {
+                        changed = true;
+                    }
+                }
+
+                // right -> left (reverse)
+                if let Some(left_var) = term_var(left) {
+                    let mut reach = RoaringBitmap::new();
+                    for_each_value(right, candidates, |t| {
+                        reach |= match self.min_confidence {
+                            None => db.relations.sources(t, rel_id),
+                            Some(min) => db.relations.sources_with_min_confidence(t, rel_id, min),
+                        };
+                    });
+                    let before = candidates[left_var].len();
+                    candidates[left_var] &= reach;
+                    if candidates[left_var].len() != before {
+                        changed = true;
+                    }
+                }
+            }
+            LoweredAtom::Rpq {
+                left,
+                rpq_id,
+                right,
+            } => {
+                // Best-effort pruning for RPQs: only run when the driving side
+                // is small enough to avoid exploding work.
+                const MAX_STARTS: u64 = 128;
+
+                // left -> right
+                if let Some(right_var) = term_var(right) {
+                    let mut reach = RoaringBitmap::new();
+                    if let LoweredTerm::Const(s) = left {
+                        reach |= rpq.reachable_set(db, *rpq_id, *s)?;
+                    } else if let Some(left_var) = term_var(left) {
+                        if candidates[left_var].len() <= MAX_STARTS {
+                            for s in candidates[left_var].iter() {
+                                reach |= rpq.reachable_set(db, *rpq_id, s)?;
                             }
                         }
                     }
-                    LoweredAtom::Rpq {
-                        left,
-                        rpq_id,
-                        right,
-                    } => {
-                        // Best-effort pruning for RPQs: only run when the driving side
-                        // is small enough to avoid exploding work.
-                        const MAX_STARTS: u64 = 128;
-
-                        // left -> right
-                        if let Some(right_var) = term_var(right) {
-                            let mut reach = RoaringBitmap::new();
-                            if let LoweredTerm::Const(s) = left {
-                                reach |= rpq.reachable_set(db, *rpq_id, *s)?;
-                            } else if let Some(left_var) = term_var(left) {
-                                if candidates[left_var].len() <= MAX_STARTS {
-                                    for s in candidates[left_var].iter() {
-                                        reach |= rpq.reachable_set(db, *rpq_id, s)?;
-                                    }
-                                }
-                            }
-                            if !reach.is_empty() {
-                                let before = candidates[right_var].len();
-                                candidates[right_var] &= reach;
-                                if candidates[right_var].len() != before {
-                                    changed = true;
-                                }
-                            }
+                    if !reach.is_empty() {
+                        let before = candidates[right_var].len();
+                        candidates[right_var] &= reach;
+                        if candidates[right_var].len() != before {
+                            changed = true;
                         }
+                    }
+                }
 
-                        // right -> left (reverse reachability)
-                        if let Some(left_var) = term_var(left) {
-                            let mut reach = RoaringBitmap::new();
-                            if let LoweredTerm::Const(t) = right {
-                                reach |= rpq.reachable_set_reverse(db, *rpq_id, *t)?;
-                            } else if let Some(right_var) = term_var(right) {
-                                if candidates[right_var].len() <= MAX_STARTS {
-                                    for t in candidates[right_var].iter() {
-                                        reach |= rpq.reachable_set_reverse(db, *rpq_id, t)?;
-                                    }
-                                }
-                            }
-                            if !reach.is_empty() {
-                                let before = candidates[left_var].len();
-                                candidates[left_var] &= reach;
-                                if candidates[left_var].len() != before {
-                                    changed = true;
-                                }
+                // right -> left (reverse reachability)
+                if let Some(left_var) = term_var(left) {
+                    let mut reach = RoaringBitmap::new();
+                    if let LoweredTerm::Const(t) = right {
+                        reach |= rpq.reachable_set_reverse(db, *rpq_id, *t)?;
+                    } else if let Some(right_var) = term_var(right) {
+                        if candidates[right_var].len() <= MAX_STARTS {
+                            for t in candidates[right_var].iter() {
+                                reach |= rpq.reachable_set_reverse(db, *rpq_id, t)?;
                             }
                         }
                     }
-                    _ => {}
+                    if !reach.is_empty() {
+                        let before = candidates[left_var].len();
+                        candidates[left_var] &= reach;
+                        if candidates[left_var].len() != before {
+                            changed = true;
+                        }
+                    }
                 }
             }
+            _ => {}
         }
-        Ok(())
+        Ok(changed)
     }
 
     fn search(
@@ -6506,6 +7025,14 @@ fn for_each_value<F: FnMut(u32)>(term: &LoweredTerm, candidates: &[RoaringBitmap
     }
 }
 
+/// The values `term` can take: its domain, or the constant itself.
+fn term_domain(term: &LoweredTerm, candidates: &[RoaringBitmap]) -> RoaringBitmap {
+    match term {
+        LoweredTerm::Const(id) => std::iter::once(*id).collect(),
+        LoweredTerm::Var(v) => candidates[*v].clone(),
+    }
+}
+
 fn resolve_term(t: &LoweredTerm, assigned: &[Option<u32>]) -> Option<u32> {
     match t {
         LoweredTerm::Const(id) => Some(*id),
@@ -6537,6 +7064,20 @@ fn type_bitmap_including_subtypes(
     type_name: &str,
     meta: Option<&MetaPlaneIndex>,
 ) -> RoaringBitmap {
+    let mut out = RoaringBitmap::new();
+    for ty in types_including_subtypes(type_name, meta) {
+        if let Some(bm) = db.find_by_type(ty) {
+            out |= bm.clone();
+        }
+    }
+    out
+}
+
+/// The stored types `?x : T` matches (see `type_bitmap_including_subtypes`).
+fn types_including_subtypes<'a>(
+    type_name: &'a str,
+    meta: Option<&'a MetaPlaneIndex>,
+) -> Vec<&'a str> {
     // If a meta-plane is present, interpret `?x : T` as:
     //   “x has type T *or any subtype of T* (in any imported schema)”.
     //
@@ -6545,7 +7086,7 @@ fn type_bitmap_including_subtypes(
     //
     // If no meta-plane exists, fall back to exact type matching.
     let Some(meta) = meta else {
-        return db.find_by_type(type_name).cloned().unwrap_or_default();
+        return vec![type_name];
     };
 
     let mut candidate_types: HashSet<&str> = HashSet::new();
@@ -6560,14 +7101,26 @@ fn type_bitmap_including_subtypes(
     if candidate_types.is_empty() {
         candidate_types.insert(type_name);
     }
+    candidate_types.into_iter().collect()
+}
 
+/// `?x : T, attr(?x, key, value)` answered from composite `(type, key)`
+/// indexes; `None` unless every type `?x : T` matches is indexed on `key`.
+fn composite_type_attr_eq(
+    db: &axiograph_pathdb::PathDB,
+    type_name: &str,
+    key: &str,
+    value: &str,
+    meta: Option<&MetaPlaneIndex>,
+) -> Option<RoaringBitmap> {
+    if db.composite_indexes().is_empty() {
+        return None;
+    }
     let mut out = RoaringBitmap::new();
-    for ty in candidate_types {
-        if let Some(bm) = db.find_by_type(ty) {
-            out |= bm.clone();
-        }
+    for ty in types_including_subtypes(type_name, meta) {
+        out |= db.composite_lookup(ty, key, value)?;
     }
-    out
+    Some(out)
 }
 
 fn entity_has_attr(
@@ -7021,13 +7574,15 @@ impl RpqContext {
     }
 }
 
+/// Chains at least this long are answered from the PathDB path index.
+const PATH_INDEX_MIN_LEN: usize = 3;
+
 fn follow_simple_chain_forward(
     db: &axiograph_pathdb::PathDB,
     chain: &[String],
     start: u32,
     min_confidence: Option<f32>,
 ) -> RoaringBitmap {
-    const PATH_INDEX_MIN_LEN: usize = 3;
     if chain.is_empty() {
         let mut out = RoaringBitmap::new();
         out.insert(start);
@@ -7085,14 +7640,13 @@ fn follow_chain_direct(
         let Some(rel_id) = db.interner.id_of(rel) else {
             return RoaringBitmap::new();
         };
-        let mut next = RoaringBitmap::new();
-        for entity in current.iter() {
-            next |= match min_confidence {
-                None => db.relations.targets(entity, rel_id),
-                Some(min) => db.relations.targets_with_min_confidence(entity, rel_id, min),
-            };
-        }
-        current = next;
+        current = match min_confidence {
+            None => db.relations.expand_frontier(&current, rel_id),
+            Some(min) => current
+                .iter()
+                .map(|entity| db.relations.targets_with_min_confidence(entity, rel_id, min))
+                .fold(RoaringBitmap::new(), |acc, reached| acc | reached),
+        };
         if current.is_empty() {
             break;
         }
@@ -7504,6 +8058,72 @@ instance CensusInst of Census:
         Ok(())
     }
 
+    #[test]
+    fn axql_explain_analyze_reports_operators_and_index_usage() -> Result<()> {
+        let db = db_with_axi_meta_plane();
+        let meta = MetaPlaneIndex::from_db(&db)?;
+
+        let q = parse_axql_query(
+            r#"select ?dst where ?f = Flow(from=a, to=?dst), ?dst : Supplier, contains(?dst, "name", "b") limit 5"#,
+        )?;
+        let mut prepared = prepare_axql_query_with_meta(&db, &q, Some(&meta))?;
+        let expected = prepared.execute(&db, Some(&meta))?;
+        let (res, lines) = prepared.explain_analyze(&db, Some(&meta))?;
+        assert_eq!(res.rows, expected.rows);
+        assert_eq!(res.rows.len(), 1);
+
+        let text = lines.join("\n");
+        assert!(text.contains("filter ?dst : Supplier  [type]"), "{text}");
+        assert!(text.contains("[scan, missed text]"), "{text}");
+        assert!(text.contains("fact-index pruning"), "{text}");
+        assert!(text.contains("backtracking search"), "{text}");
+        assert!(lines.iter().any(|l| l.starts_with("indexes used: ")
+            && l.contains("fact")
+            && l.contains("type")));
+        assert!(lines.contains(&"indexes missed: text".to_string()), "{text}");
+        assert!(lines.iter().any(|l| l.starts_with("rows: 1")), "{text}");
+        Ok(())
+    }
+
+    #[test]
+    fn axql_view_reference_runs_the_saved_query() -> Result<()> {
+        let mut db = axiograph_pathdb::PathDB::new();
+        let axi = r#"
+module Demo
+
+schema Demo:
+  object Supplier
+  relation Flow(from: Supplier, to: Supplier)
+
+theory Reports on Demo:
+  view downstream_of_a =
+    select ?dst where ?f = Flow(from=a, to=?dst) limit 10
+
+instance DemoInst of Demo:
+  Supplier = {a, b, c}
+  Flow = {(from=a, to=b), (from=b, to=c)}
+"#;
+        axiograph_pathdb::axi_module_import::import_axi_schema_v1_into_pathdb(&mut db, axi)?;
+        db.build_indexes();
+        let meta = MetaPlaneIndex::from_db(&db)?;
+
+        let text = resolve_axql_view_reference(Some(&meta), "view Demo.downstream_of_a")?;
+        assert_eq!(
+            text,
+            "select ?dst where ?f = Flow(from=a, to=?dst) limit 10"
+        );
+        let q = parse_axql_query(&text)?;
+        let res = prepare_axql_query_with_meta(&db, &q, Some(&meta))?.execute(&db, Some(&meta))?;
+        assert_eq!(res.rows.len(), 1);
+
+        // Plain AxQL passes through; unknown views and a missing meta-plane are errors.
+        let plain = "select ?x where ?x is Supplier";
+        assert_eq!(resolve_axql_view_reference(None, plain)?, plain);
+        assert!(resolve_axql_view_reference(Some(&meta), "view nope").is_err());
+        assert!(resolve_axql_view_reference(None, "view downstream_of_a").is_err());
+        Ok(())
+    }
+
     #[test]
     fn axql_schema_qualified_type_filters_axi_schema() -> Result<()> {
         let db = db_with_multi_schema_parent_collision();
@@ -7801,6 +8421,21 @@ instance I2 of S2:
         Ok(())
     }
 
+    #[test]
+    fn query_attr_filters_use_composite_index() -> Result<()> {
+        let mut db = tiny_db();
+        db.add_entity("Other", vec![("name", "b")]);
+        db.declare_composite_index("Node", "name");
+        let q = parse_axql_query(r#"select ?x where ?x : Node, attr(?x, "name", "b")"#)?;
+        let mut prepared = prepare_axql_query_with_meta(&db, &q, None)?;
+        let (res, lines) = prepared.explain_analyze(&db, None)?;
+        assert_eq!(res.rows.len(), 1);
+        assert_eq!(res.rows[0].get("?x").copied(), Some(1));
+        let text = lines.join("\n");
+        assert!(text.contains("[composite]"), "{text}");
+        Ok(())
+    }
+
     #[test]
     fn query_rel_star_includes_reflexive() -> Result<()> {
         let db = tiny_db();
@@ -0,0 +1,213 @@
+//! `axiograph ingest calibrate`: rescale proposal confidences using review
+//! outcomes from a storage changelog (see `axiograph_storage::calibration`).
+//!
+//! The original value is kept in proposal metadata as `raw_confidence`, so
+//! calibration is auditable and idempotent (re-running calibrates from the raw
+//! value, not from a previously calibrated one).
+
+use anyhow::{anyhow, Result};
+use axiograph_ingest_docs::{ProposalV1, ProposalsFileV1};
+use axiograph_storage::calibration::{self, CalibrationModel, DEFAULT_PRIOR_STRENGTH};
+use axiograph_storage::Change;
+use clap::Args;
+use colored::Colorize;
+use std::fs;
+use std::path::PathBuf;
+
+/// Proposal metadata key holding the extractor's original confidence.
+pub const RAW_CONFIDENCE_KEY: &str = "raw_confidence";
+
+/// Proposal metadata key selecting the calibration source for one proposal.
+pub const CALIBRATION_SOURCE_KEY: &str = "calibration_source";
+
+#[derive(Args, Debug, Clone)]
+pub struct CalibrateArgs {
+    /// Input proposals JSON (Evidence/Proposals schema).
+    pub proposals: PathBuf,
+
+    /// Storage changelog (`changelog.json`) to fit the calibration from.
+    #[arg(long, conflicts_with = "model", required_unless_present = "model")]
+    pub changelog: Option<PathBuf>,
+
+    /// Previously saved calibration model (`--model-out`) instead of a changelog.
+    #[arg(long)]
+    pub model: Option<PathBuf>,
+
+    /// Calibration source key (e.g. `llm:gpt-4o`, `file:sql`). Default: each
+    /// proposal's `calibration_source` metadata, else the file's `source_type`.
+    #[arg(long)]
+    pub source: Option<String>,
+
+    /// Weight of the raw confidence, in pseudo-reviews.
+    #[arg(long, default_value_t = DEFAULT_PRIOR_STRENGTH)]
+    pub prior_strength: f32,
+
+    /// Output calibrated proposals JSON.
+    #[arg(short, long)]
+    pub out: PathBuf,
+
+    /// Also write the fitted calibration model as JSON.
+    #[arg(long)]
+    pub model_out: Option<PathBuf>,
+}
+
+/// Rescale every proposal's confidence in place. Returns how many changed.
+pub fn calibrate_proposals(
+    file: &mut ProposalsFileV1,
+    model: &CalibrationModel,
+    source: Option<&str>,
+) -> usize {
+    let mut changed = 0;
+    for proposal in &mut file.proposals {
+        let meta = match proposal {
+            ProposalV1::Entity { meta, .. } | ProposalV1::Relation { meta, .. } => meta,
+        };
+        let key = source
+            .or(meta
+                .metadata
+                .get(CALIBRATION_SOURCE_KEY)
+                .map(String::as_str))
+            .unwrap_or(&file.source.source_type)
+            .to_string();
+        let raw = meta
+            .metadata
+            .get(RAW_CONFIDENCE_KEY)
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(meta.confidence);
+        // Keep the exact f64 when the model leaves the value alone.
+        let calibrated = match model.calibrate(&key, raw as f32) {
+            c if c == raw as f32 => raw,
+            c => f64::from(c),
+        };
+        meta.metadata
+            .insert(RAW_CONFIDENCE_KEY.to_string(), raw.to_string());
+        if calibrated != meta.confidence {
+            meta.confidence = calibrated;
+            changed += 1;
+        }
+    }
+    changed
+}
+
+pub fn cmd_calibrate(args: &CalibrateArgs) -> Result<()> {
+    let model = match (&args.changelog, &args.model) {
+        (Some(path), _) => {
+            let text = fs::read_to_string(path)
+                .map_err(|e| anyhow!("failed to read {}: {e}", path.display()))?;
+            let changelog: Vec<Change> = serde_json::from_str(&text)
+                .map_err(|e| anyhow!("failed to parse changelog {}: {e}", path.display()))?;
+            CalibrationModel::fit(
+                &calibration::review_outcomes(&changelog),
+                args.prior_strength,
+            )
+        }
+        (None, Some(path)) => serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| anyhow!("failed to parse calibration model {}: {e}", path.display()))?,
+        (None, None) => return Err(anyhow!("either --changelog or --model is required")),
+    };
+
+    println!(
+        "{} {}",
+        "Calibrating".green().bold(),
+        args.proposals.display()
+    );
+    for (source, stats) in &model.sources {
+        println!(
+            "  {source}: reviewed={} accepted={} reliability={:.2}",
+            stats.reviewed,
+            stats.accepted,
+            stats.reliability().unwrap_or(0.0)
+        );
+    }
+
+    let mut file: ProposalsFileV1 = serde_json::from_str(&fs::read_to_string(&args.proposals)?)?;
+    let changed = calibrate_proposals(&mut file, &model, args.source.as_deref());
+    fs::write(&args.out, serde_json::to_string_pretty(&file)?)?;
+    println!(
+        "  {} {} (proposals={}, recalibrated={changed})",
+        "→".cyan(),
+        args.out.display(),
+        file.proposals.len()
+    );
+
+    if let Some(path) = &args.model_out {
+        fs::write(path, serde_json::to_string_pretty(&model)?)?;
+        println!("  {} {}", "→".cyan(), path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axiograph_ingest_docs::{ProposalMetaV1, ProposalSourceV1, PROPOSALS_VERSION_V1};
+    use axiograph_storage::ReviewOutcome;
+    use std::collections::HashMap;
+
+    fn entity(id: &str, confidence: f64, metadata: HashMap<String, String>) -> ProposalV1 {
+        ProposalV1::Entity {
+            meta: ProposalMetaV1 {
+                proposal_id: id.to_string(),
+                confidence,
+                evidence: Vec::new(),
+                public_rationale: String::new(),
+                metadata,
+                schema_hint: None,
+            },
+            entity_id: id.to_string(),
+            entity_type: "Table".to_string(),
+            name: id.to_string(),
+            attributes: HashMap::new(),
+            description: None,
+        }
+    }
+
+    fn confidence(p: &ProposalV1) -> f64 {
+        match p {
+            ProposalV1::Entity { meta, .. } | ProposalV1::Relation { meta, .. } => meta.confidence,
+        }
+    }
+
+    #[test]
+    fn rescales_by_source_and_keeps_raw_confidence() {
+        // `file:sql` facts at 0.98 were accepted 1 time in 4.
+        let outcomes: Vec<ReviewOutcome> = (0..4)
+            .map(|i| ReviewOutcome {
+                source: "file:sql".to_string(),
+                confidence: 0.98,
+                accepted: i == 0,
+            })
+            .collect();
+        let model = CalibrationModel::fit(&outcomes, 1.0);
+
+        let override_meta =
+            HashMap::from([(CALIBRATION_SOURCE_KEY.to_string(), "llm:other".to_string())]);
+        let mut file = ProposalsFileV1 {
+            version: PROPOSALS_VERSION_V1,
+            generated_at: "0".to_string(),
+            source: ProposalSourceV1 {
+                source_type: "file:sql".to_string(),
+                locator: "schema.sql".to_string(),
+            },
+            schema_hint: None,
+            proposals: vec![
+                entity("a", 0.98, HashMap::new()),
+                entity("b", 0.98, override_meta),
+            ],
+        };
+
+        assert_eq!(calibrate_proposals(&mut file, &model, None), 1);
+        // (1 accepted + 1 * 0.98) / (4 reviewed + 1)
+        assert!((confidence(&file.proposals[0]) - 0.396).abs() < 1e-6);
+        // Unknown source: unchanged.
+        assert!((confidence(&file.proposals[1]) - 0.98).abs() < 1e-6);
+
+        // Re-running starts from the raw value, so the result is stable.
+        assert_eq!(calibrate_proposals(&mut file, &model, None), 0);
+        assert!((confidence(&file.proposals[0]) - 0.396).abs() < 1e-6);
+        let ProposalV1::Entity { meta, .. } = &file.proposals[0] else {
+            unreachable!()
+        };
+        assert_eq!(meta.metadata[RAW_CONFIDENCE_KEY], "0.98");
+    }
+}
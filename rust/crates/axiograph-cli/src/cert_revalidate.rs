@@ -0,0 +1,287 @@
+//! `axiograph cert revalidate`: carry stored certificates over to a new
+//! snapshot (see `axiograph_pathdb::revalidation`).
+//!
+//! Every certificate under the given files/directories is checked against the
+//! new `.axi` input. Those already anchored to it or whose witnesses still
+//! hold are written (re-anchored) to `--out-dir`; the report lists the ones
+//! the delta invalidated, with the witnesses that no longer hold.
+
+use anyhow::{anyhow, Context, Result};
+use axiograph_dsl::schema_v1::SchemaV1Module;
+use axiograph_pathdb::certificate::{AxiAnchorV1, CertificateV2};
+use axiograph_pathdb::{PathDB, RevalidationStatus, Revalidator};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Args, Debug, Clone)]
+pub struct CertRevalidateArgs {
+    /// The new `.axi` input (`PathDBExportV1` snapshot export or canonical module).
+    pub input: PathBuf,
+
+    /// Certificate JSON files, or directories of them.
+    #[arg(required = true)]
+    pub certs: Vec<PathBuf>,
+
+    /// Write current and re-derived certificates here (same file names).
+    #[arg(long)]
+    pub out_dir: Option<PathBuf>,
+
+    /// Write the revalidation report as JSON.
+    #[arg(long)]
+    pub report: Option<PathBuf>,
+
+    /// Exit with an error if any certificate was invalidated.
+    #[arg(long)]
+    pub fail_on_invalid: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CertRevalidationEntry {
+    pub path: PathBuf,
+    /// Certificate kind (`query_result_v3`, ...), if it parsed.
+    pub kind: Option<String>,
+    pub status: RevalidationStatus,
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CertRevalidationReport {
+    pub anchor: AxiAnchorV1,
+    pub counts: BTreeMap<String, usize>,
+    pub certificates: Vec<CertRevalidationEntry>,
+}
+
+/// Parse `input` and load it as a PathDB, the way `cert query` does.
+fn load_anchor(input: &Path) -> Result<(SchemaV1Module, PathDB, AxiAnchorV1)> {
+    let text =
+        fs::read_to_string(input).with_context(|| format!("failed to read {}", input.display()))?;
+    let anchor = AxiAnchorV1 {
+        axi_digest_v1: axiograph_dsl::digest::axi_digest_v1(&text),
+    };
+    let module = axiograph_dsl::axi_v1::parse_axi_v1(&text)?;
+    let db = if crate::is_pathdb_export_v1_module(&module) {
+        axiograph_pathdb::axi_export::import_pathdb_from_axi_v1_module(&module)?
+    } else {
+        let mut db = PathDB::new();
+        axiograph_pathdb::axi_module_import::import_axi_schema_v1_module_into_pathdb(
+            &mut db, &module,
+        )?;
+        db.build_indexes();
+        db
+    };
+    Ok((module, db, anchor))
+}
+
+fn collect_cert_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut entries: Vec<PathBuf> = fs::read_dir(path)
+                .with_context(|| format!("failed to read {}", path.display()))?
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|e| e == "json"))
+                .collect();
+            entries.sort();
+            files.extend(entries);
+        } else {
+            files.push(path.clone());
+        }
+    }
+    Ok(files)
+}
+
+fn cert_kind(cert: &CertificateV2) -> Option<String> {
+    serde_json::to_value(&cert.payload)
+        .ok()?
+        .get("kind")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Revalidate every certificate under `args.certs`, writing survivors to
+/// `args.out_dir` if set.
+pub fn revalidate_certificates(args: &CertRevalidateArgs) -> Result<CertRevalidationReport> {
+    let (module, db, anchor) = load_anchor(&args.input)?;
+    let revalidator = Revalidator::new(anchor.clone(), &db, Some(&module))?;
+    if let Some(dir) = &args.out_dir {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut report = CertRevalidationReport {
+        anchor,
+        counts: BTreeMap::new(),
+        certificates: Vec::new(),
+    };
+    for path in collect_cert_files(&args.certs)? {
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let entry = match serde_json::from_str::<CertificateV2>(&text) {
+            Ok(cert) if cert.version == axiograph_pathdb::CERTIFICATE_VERSION_V2 => {
+                let outcome = revalidator.revalidate(&cert);
+                if let (Some(dir), Some(new_cert)) = (&args.out_dir, &outcome.certificate) {
+                    let name = path.file_name().unwrap_or_default();
+                    fs::write(dir.join(name), serde_json::to_string_pretty(new_cert)?)?;
+                }
+                CertRevalidationEntry {
+                    path,
+                    kind: cert_kind(&cert),
+                    status: outcome.status,
+                    reasons: outcome.reasons,
+                }
+            }
+            Ok(cert) => CertRevalidationEntry {
+                path,
+                kind: cert_kind(&cert),
+                status: RevalidationStatus::Unsupported,
+                reasons: vec![format!("unsupported certificate version {}", cert.version)],
+            },
+            Err(e) => CertRevalidationEntry {
+                path,
+                kind: None,
+                status: RevalidationStatus::Unsupported,
+                reasons: vec![format!("not a v2 certificate: {e}")],
+            },
+        };
+        let status = serde_json::to_value(entry.status)?
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        *report.counts.entry(status).or_default() += 1;
+        report.certificates.push(entry);
+    }
+    Ok(report)
+}
+
+pub fn cmd_cert_revalidate(args: &CertRevalidateArgs) -> Result<()> {
+    let report = revalidate_certificates(args)?;
+    println!(
+        "{} {} certificate(s) against {}",
+        "Revalidated".green().bold(),
+        report.certificates.len(),
+        report.anchor.axi_digest_v1
+    );
+    for entry in &report.certificates {
+        let status = match entry.status {
+            RevalidationStatus::Current => "current".dimmed(),
+            RevalidationStatus::Rederived => "rederived".green(),
+            RevalidationStatus::Invalidated => "invalidated".red(),
+            RevalidationStatus::Unsupported => "skipped".yellow(),
+        };
+        println!(
+            "  {status} {} ({})",
+            entry.path.display(),
+            entry.kind.as_deref().unwrap_or("?")
+        );
+        for reason in &entry.reasons {
+            println!("      {reason}");
+        }
+    }
+    if let Some(path) = &args.report {
+        fs::write(path, serde_json::to_string_pretty(&report)?)?;
+        println!("  {} {}", "→".cyan(), path.display());
+    }
+
+    let invalidated = report
+        .certificates
+        .iter()
+        .filter(|e| e.status == RevalidationStatus::Invalidated)
+        .count();
+    if args.fail_on_invalid && invalidated > 0 {
+        return Err(anyhow!(
+            "{invalidated} certificate(s) invalidated by the new snapshot"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BEFORE: &str = "module Tiny\n\n\
+        schema S:\n  object Person\n  relation Knows(from: Person, to: Person)\n\n\
+        instance I of S:\n  Person = {Alice, Bob, Carol}\n  \
+        Knows = {(from=Alice, to=Bob), (from=Bob, to=Carol)}\n";
+
+    const AFTER: &str = "module Tiny\n\n\
+        schema S:\n  object Person\n  relation Knows(from: Person, to: Person)\n\n\
+        instance I of S:\n  Person = {Alice, Bob, Carol}\n  \
+        Knows = {(from=Alice, to=Carol), (from=Bob, to=Carol)}\n";
+
+    fn query_cert(input: &Path, query: &str) -> CertificateV2 {
+        let (_, db, anchor) = load_anchor(input).unwrap();
+        let meta = axiograph_pathdb::axi_semantics::MetaPlaneIndex::from_db(&db).unwrap();
+        let query = crate::axql::parse_axql_query(query).unwrap();
+        crate::axql::certify_axql_query_v3_with_meta(
+            &db,
+            &query,
+            Some(&meta),
+            &anchor.axi_digest_v1,
+        )
+        .unwrap()
+        .with_anchor(anchor)
+    }
+
+    #[test]
+    fn revalidation_rederives_surviving_certs_and_reports_invalidated_ones() {
+        let dir =
+            std::env::temp_dir().join(format!("axiograph_cert_revalidate_{}", std::process::id()));
+        let certs = dir.join("certs");
+        fs::create_dir_all(&certs).unwrap();
+        let before = dir.join("before.axi");
+        let after = dir.join("after.axi");
+        fs::write(&before, BEFORE).unwrap();
+        fs::write(&after, AFTER).unwrap();
+
+        let write = |name: &str, cert: &CertificateV2| {
+            fs::write(certs.join(name), serde_json::to_string(cert).unwrap()).unwrap();
+        };
+        write(
+            "alice.json",
+            &query_cert(&before, "select ?to where name(\"Alice\") -Knows-> ?to"),
+        );
+        write(
+            "bob.json",
+            &query_cert(&before, "select ?to where name(\"Bob\") -Knows-> ?to"),
+        );
+        write(
+            "current.json",
+            &query_cert(&after, "select ?to where name(\"Alice\") -Knows-> ?to"),
+        );
+        fs::write(certs.join("notes.json"), "{}").unwrap();
+
+        let args = CertRevalidateArgs {
+            input: after.clone(),
+            certs: vec![certs.clone()],
+            out_dir: Some(dir.join("out")),
+            report: None,
+            fail_on_invalid: true,
+        };
+        let report = revalidate_certificates(&args).unwrap();
+        let status: Vec<RevalidationStatus> =
+            report.certificates.iter().map(|e| e.status).collect();
+        assert_eq!(
+            status,
+            vec![
+                RevalidationStatus::Invalidated,
+                RevalidationStatus::Rederived,
+                RevalidationStatus::Current,
+                RevalidationStatus::Unsupported,
+            ]
+        );
+        assert!(report.certificates[0].reasons[0].contains("Knows"));
+
+        let rederived: CertificateV2 =
+            serde_json::from_str(&fs::read_to_string(dir.join("out/bob.json")).unwrap()).unwrap();
+        assert_eq!(rederived.anchor, Some(report.anchor.clone()));
+        assert!(!dir.join("out/alice.json").exists());
+        assert!(dir.join("out/current.json").exists());
+        assert!(cmd_cert_revalidate(&args).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -41,12 +41,20 @@ use tokio::sync::Semaphore;
 use url::form_urlencoded;
 
 use axiograph_pathdb::axi_semantics::MetaPlaneIndex;
+use axiograph_pathdb::pagination::{page_rows, PageCursor};
 use axiograph_pathdb::{read_sidecar_file, IndexSidecarWriter, PathDB};
 
 use crate::accepted_plane::{AcceptedPlaneEventV1, AcceptedPlaneSnapshotV1};
 use crate::llm::{GeneratedQuery, LlmBackend, LlmState, ToolLoopOptions};
 use crate::world_model::{WorldModelBackend, WorldModelState};
 use crate::pathdb_wal::{PathDbSnapshotV1, PathDbWalEventV1};
+use crate::replication::{LogTips, VersionVector};
+
+/// Request header: serve only if the loaded snapshot is at least this version
+/// (`accepted=N,pathdb=M`); otherwise `503` + `Retry-After`.
+const MIN_VERSION_HEADER: &str = "x-axiograph-min-version";
+/// Response header: version of the snapshot that served the request.
+const VERSION_HEADER: &str = "x-axiograph-version";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ServerRole {
@@ -86,6 +94,8 @@ struct ServerConfig {
     source: SnapshotSource,
     watch_head: bool,
     poll_interval: Duration,
+    /// Upstream `db serve` base URL this replica pulls deltas from.
+    replicate_from: Option<String>,
     admin_token: Option<String>,
     ready_file: Option<PathBuf>,
     cert_verify: CertVerifyConfig,
@@ -95,6 +105,14 @@ struct ServerConfig {
     path_index_lru_capacity: usize,
     path_index_lru_async: bool,
     path_index_lru_queue: usize,
+    metrics: bool,
+    /// Run `PathDB::verify_integrity` on every loaded snapshot and refuse
+    /// inconsistent ones.
+    verify_integrity: bool,
+    /// Serve only what this role may see (`--access-policy`/`--access-role`).
+    access: Option<axiograph_storage::RolePolicy>,
+    /// Where served queries are recorded (`--audit-log`).
+    audit: Option<crate::QueryAudit>,
 }
 
 #[derive(Debug, Clone)]
@@ -118,6 +136,8 @@ struct LoadedSnapshot {
     /// For store-based loads, the resolved PathDB WAL snapshot id.
     pathdb_snapshot_id: Option<String>,
     loaded_at_unix_secs: u64,
+    /// For store-based loads, the log positions of the loaded snapshots.
+    version: Option<VersionVector>,
     entities: usize,
     relations: usize,
     db: Arc<PathDB>,
@@ -208,6 +228,32 @@ struct ServerState {
     loaded: RwLock<LoadedSnapshot>,
     query_cache: Mutex<QueryPlanCache>,
     world_model_executor: WorldModelExecutor,
+    replication: Mutex<ReplicationState>,
+}
+
+/// Progress of `--replicate-from` (reported under `/status`).
+#[derive(Debug, Clone, Default)]
+struct ReplicationState {
+    local: Option<VersionVector>,
+    upstream: Option<VersionVector>,
+    events_applied: u64,
+    last_sync_unix_secs: Option<u64>,
+    last_error: Option<String>,
+}
+
+impl ReplicationState {
+    fn record(&mut self, outcome: Result<crate::replication::SyncOutcomeV1>) {
+        self.last_sync_unix_secs = Some(now_unix_secs());
+        match outcome {
+            Ok(outcome) => {
+                self.local = Some(outcome.local);
+                self.upstream = Some(outcome.upstream);
+                self.events_applied += outcome.events_applied;
+                self.last_error = None;
+            }
+            Err(e) => self.last_error = Some(e.to_string()),
+        }
+    }
 }
 
 fn now_unix_secs() -> u64 {
@@ -499,6 +545,12 @@ pub(crate) fn cmd_db_serve(args: crate::DbServeArgs) -> Result<()> {
     }
     world_model.model = args.world_model_model.clone();
 
+    if args.replicate_from.is_some() && (role != ServerRole::Replica || args.dir.is_none()) {
+        return Err(anyhow!(
+            "db serve: `--replicate-from` requires `--role replica --dir <local_dir>`"
+        ));
+    }
+
     let source = match (&args.axpd, &args.dir) {
         (Some(_), Some(_)) => {
             return Err(anyhow!("db serve: pass only one of --axpd or --dir"));
@@ -520,6 +572,7 @@ pub(crate) fn cmd_db_serve(args: crate::DbServeArgs) -> Result<()> {
         source,
         watch_head: args.watch_head || role == ServerRole::Replica,
         poll_interval,
+        replicate_from: args.replicate_from.clone(),
         admin_token: args.admin_token.clone(),
         ready_file: args.ready_file.clone(),
         cert_verify: CertVerifyConfig {
@@ -536,6 +589,10 @@ pub(crate) fn cmd_db_serve(args: crate::DbServeArgs) -> Result<()> {
         path_index_lru_capacity: args.path_index_lru_capacity,
         path_index_lru_async: args.path_index_lru_async,
         path_index_lru_queue: args.path_index_lru_queue,
+        metrics: args.metrics,
+        verify_integrity: args.verify_integrity,
+        access: args.access.role_policy()?,
+        audit: args.audit.open()?,
     };
 
     let rt = tokio::runtime::Builder::new_multi_thread()
@@ -547,6 +604,21 @@ pub(crate) fn cmd_db_serve(args: crate::DbServeArgs) -> Result<()> {
 }
 
 async fn serve_async(config: ServerConfig) -> Result<()> {
+    // A fresh replica has nothing to load until the first delta lands.
+    let mut replication = ReplicationState::default();
+    if let (Some(upstream), SnapshotSource::Store { dir, .. }) =
+        (config.replicate_from.clone(), &config.source)
+    {
+        let dir = dir.clone();
+        let outcome = tokio::task::spawn_blocking(move || {
+            crate::replication::sync_from_upstream(&upstream, &dir)
+        })
+        .await
+        .map_err(|e| anyhow!("db serve: failed to join replication task: {e}"))?
+        .map_err(|e| anyhow!("db serve: initial replication failed: {e}"))?;
+        replication.record(Ok(outcome));
+    }
+
     let initial = tokio::task::spawn_blocking({
         let config = config.clone();
         move || load_snapshot(&config)
@@ -559,9 +631,19 @@ async fn serve_async(config: ServerConfig) -> Result<()> {
         loaded: RwLock::new(initial),
         query_cache: Mutex::new(QueryPlanCache::default()),
         world_model_executor: WorldModelExecutor::new(config.world_model_workers),
+        replication: Mutex::new(replication),
     });
 
-    if config.watch_head {
+    if let Some(upstream) = config.replicate_from.clone() {
+        let state = state.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(config.poll_interval);
+            loop {
+                ticker.tick().await;
+                replicate_once(&state, &upstream).await;
+            }
+        });
+    } else if config.watch_head {
         let state = state.clone();
         tokio::spawn(async move {
             let mut ticker = tokio::time::interval(config.poll_interval);
@@ -622,6 +704,27 @@ async fn handle_request(
     let method = req.method().clone();
     let path = req.uri().path().to_string();
 
+    if let Some(raw) = req.headers().get(MIN_VERSION_HEADER) {
+        if let Some(resp) = min_version_rejection(&state, raw.to_str().unwrap_or("")) {
+            return Ok(resp);
+        }
+    }
+    let mut resp = route_request(req, &state, method, path).await?;
+    if let Some(version) = loaded_version(&state) {
+        if let Ok(value) = hyper::header::HeaderValue::from_str(&version.to_string()) {
+            resp.headers_mut().insert(VERSION_HEADER, value);
+        }
+    }
+    Ok(resp)
+}
+
+async fn route_request(
+    req: Request<Incoming>,
+    state: &Arc<ServerState>,
+    method: Method,
+    path: String,
+) -> Result<Response<Full<Bytes>>, hyper::Error> {
+    let state = state.clone();
     if method == Method::GET && path.starts_with("/viz/") {
         if path == "/viz/" || path == "/viz/index.html" {
             return match handle_viz_get(&state, req.uri().query()).await {
@@ -637,6 +740,13 @@ async fn handle_request(
 
     let resp = match (method, path.as_str()) {
         (Method::GET, "/healthz") => text_response(StatusCode::OK, "ok\n"),
+        (Method::GET, "/metrics") if state.config.metrics => Response::builder()
+            .status(StatusCode::OK)
+            .header(CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")
+            .body(Full::new(Bytes::from(
+                axiograph_pathdb::metrics::global().render_prometheus(),
+            )))
+            .unwrap_or_else(|_| text_response(StatusCode::INTERNAL_SERVER_ERROR, "metrics failed\n")),
         (Method::GET, "/status") => match status_payload(&state) {
             Ok(v) => json_response(StatusCode::OK, &v),
             Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
@@ -645,6 +755,12 @@ async fn handle_request(
             Ok(v) => json_response(StatusCode::OK, &v),
             Err(e) => json_error(StatusCode::BAD_REQUEST, &e.to_string()),
         },
+        (Method::GET, "/replication/version") => match replication_version_payload(&state) {
+            Ok(v) => json_response(StatusCode::OK, &v),
+            Err(e) => json_error(StatusCode::BAD_REQUEST, &e.to_string()),
+        },
+        (Method::GET, "/replication/delta") => handle_replication_delta(&state, req.uri().query()).await,
+        (Method::GET, "/replication/object") => handle_replication_object(&state, req.uri().query()).await,
         (Method::GET, "/anchor.axi") => match handle_anchor_get(&state, req.uri().query()).await {
             Ok(r) => r,
             Err(e) => json_error(StatusCode::BAD_REQUEST, &e.to_string()),
@@ -661,6 +777,10 @@ async fn handle_request(
             Ok(v) => json_response(StatusCode::OK, &v),
             Err(e) => json_error(StatusCode::BAD_REQUEST, &e.to_string()),
         },
+        (Method::GET, "/views") => match handle_views_get(&state).await {
+            Ok(v) => json_response(StatusCode::OK, &v),
+            Err(e) => json_error(StatusCode::BAD_REQUEST, &e.to_string()),
+        },
         (Method::GET, "/viz") => {
             let mut location = String::from("/viz/");
             if let Some(q) = req.uri().query() {
@@ -1030,9 +1150,12 @@ fn status_payload(state: &ServerState) -> Result<serde_json::Value> {
             "accepted_snapshot_id": loaded.accepted_snapshot_id,
             "pathdb_snapshot_id": loaded.pathdb_snapshot_id,
             "loaded_at_unix_secs": loaded.loaded_at_unix_secs,
+            "version": loaded.version,
             "entities": loaded.entities,
             "relations": loaded.relations,
+            "adjacency": loaded.db.adjacency_layout(),
         },
+        "replication": replication_status(state),
         "llm": {
             "enabled": !matches!(state.config.llm.backend, LlmBackend::Disabled),
             "backend": llm_backend,
@@ -1206,6 +1329,9 @@ fn snapshots_payload(state: &ServerState, query: Option<&str>) -> Result<serde_j
     }))
 }
 
+/// Page size for `/query` requests that send a `cursor` without a `limit`.
+const DEFAULT_QUERY_PAGE_ROWS: usize = 100;
+
 #[derive(Debug, Clone, Deserialize)]
 struct QueryRequestV1 {
     query: String,
@@ -1214,6 +1340,10 @@ struct QueryRequestV1 {
     /// Include elaboration output (inferred types + notes + elaborated query text).
     #[serde(default)]
     show_elaboration: bool,
+    /// Profile the execution: per-operator timings, estimated vs actual
+    /// cardinalities and index usage (returned as `profile`).
+    #[serde(default)]
+    explain_analyze: bool,
     /// Optional default contexts/worlds (applied only when the query text has no explicit `in ...`).
     ///
     /// Values may be numeric entity ids ("123") or context `name` values.
@@ -1242,6 +1372,12 @@ struct QueryRequestV1 {
     /// (does not affect the currently loaded snapshot for other requests).
     #[serde(default)]
     snapshot: Option<String>,
+    /// Page size: return at most this many rows (plus `next_cursor` when more remain).
+    #[serde(default)]
+    limit: Option<usize>,
+    /// Resume point from a previous response's `next_cursor`.
+    #[serde(default)]
+    cursor: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -1462,7 +1598,7 @@ struct ChatMessageV1 {
 
 #[derive(Debug, Clone, Deserialize)]
 struct VizRequestV1 {
-    /// html|json|dot
+    /// html|json|dot|evidence
     #[serde(default)]
     format: Option<String>,
     /// data|meta|both
@@ -1475,6 +1611,9 @@ struct VizRequestV1 {
     focus_name: Option<String>,
     #[serde(default)]
     focus_type: Option<String>,
+    /// Focus on every entity imported from this proposal id.
+    #[serde(default)]
+    focus_proposal: Option<String>,
     #[serde(default)]
     focus_id: Option<u32>,
     #[serde(default)]
@@ -1554,6 +1693,12 @@ struct QueryResponseV1 {
     rows: Vec<BTreeMap<String, EntityViewV1>>,
     truncated: bool,
     elapsed_ms: u128,
+    /// Pass back as `cursor` to fetch the next page (paged requests only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<String>,
+    /// Row count across all pages (paged requests only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_rows: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     elaborated_query: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1563,6 +1708,8 @@ struct QueryResponseV1 {
     #[serde(skip_serializing_if = "Option::is_none")]
     plan: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    profile: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     anchor_digest: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     anchor_axi: Option<String>,
@@ -1593,7 +1740,7 @@ impl EntityViewV1 {
         Self {
             id,
             entity_type: Some(view.entity_type),
-            name: view.attrs.get("name").cloned(),
+            name: view.label,
         }
     }
 }
@@ -1616,14 +1763,23 @@ async fn handle_query(state: &Arc<ServerState>, body: &[u8]) -> Result<QueryResp
 
     let query_text = req.query.clone();
     let show_elaboration = req.show_elaboration;
+    let explain_analyze = req.explain_analyze;
     let contexts_raw = req.contexts.clone();
     let want_cert = req.certify || req.verify;
     let want_verify = req.verify;
     let include_anchor = req.include_anchor;
     let snapshot_override = req.snapshot.clone();
+    let cursor = req
+        .cursor
+        .as_deref()
+        .map(PageCursor::decode)
+        .transpose()?;
+    let paged = req.limit.is_some() || cursor.is_some();
+    let limit = req.limit.unwrap_or(DEFAULT_QUERY_PAGE_ROWS).max(1);
     let state = state.clone();
 
     tokio::task::spawn_blocking(move || {
+        let _span = tracing::info_span!("db_server.query", certify = want_cert).entered();
         let (db, meta, snapshot_key) = if let Some(snapshot) = snapshot_override.as_deref() {
             let SnapshotSource::Store { dir, layer, .. } = &state.config.source else {
                 return Err(anyhow!(
@@ -1640,6 +1796,7 @@ async fn handle_query(state: &Arc<ServerState>, body: &[u8]) -> Result<QueryResp
             (loaded.db.clone(), loaded.meta.clone(), loaded.snapshot_key.clone())
         };
 
+        let query_text = crate::axql::resolve_axql_view_reference(meta.as_ref(), &query_text)?;
         let mut parsed = crate::axql::parse_axql_query(&query_text)?;
         if parsed.contexts.is_empty() && !contexts_raw.is_empty() {
             let mut contexts: Vec<crate::axql::AxqlContextSpec> = Vec::new();
@@ -1668,7 +1825,9 @@ async fn handle_query(state: &Arc<ServerState>, body: &[u8]) -> Result<QueryResp
                 .query_cache
                 .lock()
                 .map_err(|_| anyhow!("query cache lock poisoned"))?;
-            if let Some(p) = cache.get(&cache_key) {
+            let cached = cache.get(&cache_key);
+            axiograph_pathdb::metrics::record_cache("axql_plan", cached.is_some());
+            if let Some(p) = cached {
                 p
             } else {
                 let prepared =
@@ -1685,12 +1844,37 @@ async fn handle_query(state: &Arc<ServerState>, body: &[u8]) -> Result<QueryResp
         let elaborated_query = show_elaboration.then(|| prepared.elaborated_query_text());
         let elaboration = show_elaboration.then(|| prepared.elaboration_report().clone());
         let plan = show_elaboration.then(|| prepared.explain_plan_lines());
-        let res = prepared.execute(&db, meta.as_ref())?;
-        let elapsed_ms = start.elapsed().as_millis();
+        let (res, profile) = if explain_analyze {
+            let (res, lines) = prepared.explain_analyze(&db, meta.as_ref())?;
+            (res, Some(lines))
+        } else {
+            (prepared.execute(&db, meta.as_ref())?, None)
+        };
+        let elapsed = start.elapsed();
+        if let Some(audit) = &state.config.audit {
+            audit.record(
+                axiograph_storage::audit::AuditedQuery::Axql {
+                    query: query_text.clone(),
+                },
+                &cache_key.snapshot,
+                Some(res.rows.len() as u64),
+            )?;
+        }
+        let elapsed_ms = elapsed.as_millis();
+        axiograph_pathdb::metrics::global()
+            .histogram(
+                "axiograph_server_query_seconds",
+                "AxQL query latency in `db serve` (prepare + execute), in seconds.",
+                &[],
+            )
+            .observe_duration(elapsed);
 
         let vars = res.selected_vars.clone();
+        // Only the requested page is resolved into entity views.
+        let page = paged.then(|| page_rows(&res.rows, cursor.as_ref(), limit));
+        let selected = page.as_ref().map_or(&res.rows[..], |p| &p.items[..]);
         let mut rows: Vec<BTreeMap<String, EntityViewV1>> = Vec::new();
-        for row in &res.rows {
+        for row in selected {
             let mut out: BTreeMap<String, EntityViewV1> = BTreeMap::new();
             for (k, id) in row {
                 out.insert(k.clone(), EntityViewV1::from_id(&db, *id));
@@ -1731,10 +1915,13 @@ async fn handle_query(state: &Arc<ServerState>, body: &[u8]) -> Result<QueryResp
             rows,
             truncated: res.truncated,
             elapsed_ms,
+            next_cursor: page.as_ref().and_then(|p| p.next).map(|c| c.encode()),
+            total_rows: page.as_ref().map(|p| p.total),
             elaborated_query,
             inferred_types: elaboration.as_ref().map(|e| e.inferred_types.clone()),
             notes: elaboration.as_ref().map(|e| e.notes.clone()),
             plan,
+            profile,
             anchor_digest,
             anchor_axi,
             certificate,
@@ -1788,6 +1975,9 @@ async fn handle_anchor_get(
 struct ReachabilityCertRequestV1 {
     start: u32,
     relation_ids: Vec<u32>,
+    /// Confidence-combination policy recorded in the certificate.
+    #[serde(default)]
+    combiner: axiograph_pathdb::ConfidenceCombiner,
     #[serde(default)]
     verify: bool,
     #[serde(default)]
@@ -1850,11 +2040,13 @@ async fn handle_reachability_cert(
         .into_inner_in_db(&db)
         .map_err(|e| anyhow!(e))?;
 
-        let cert = axiograph_pathdb::certificate::CertificateV2::reachability(proof).with_anchor(
-            axiograph_pathdb::certificate::AxiAnchorV1 {
-                axi_digest_v1: digest.clone(),
-            },
-        );
+        let cert = axiograph_pathdb::certificate::CertificateV2::reachability_with_combiner(
+            proof,
+            req.combiner,
+        )
+        .with_anchor(axiograph_pathdb::certificate::AxiAnchorV1 {
+            axi_digest_v1: digest.clone(),
+        });
 
         let cert_json = serde_json::to_value(&cert)?;
         let (verified, verify_out) = if verify {
@@ -3106,6 +3298,41 @@ async fn handle_contexts_get(state: &Arc<ServerState>) -> Result<serde_json::Val
     .map_err(|e| anyhow!("contexts task join failed: {e}"))?
 }
 
+async fn handle_views_get(state: &Arc<ServerState>) -> Result<serde_json::Value> {
+    let state = state.clone();
+    tokio::task::spawn_blocking(move || {
+        let loaded = state
+            .loaded
+            .read()
+            .map_err(|_| anyhow!("loaded snapshot lock poisoned"))?;
+
+        let views: Vec<serde_json::Value> = loaded
+            .meta
+            .as_ref()
+            .map(|meta| {
+                meta.views()
+                    .into_iter()
+                    .map(|v| {
+                        serde_json::json!({
+                            "schema": v.schema_name,
+                            "theory": v.theory_name,
+                            "name": v.name,
+                            "query": v.query,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok::<_, anyhow::Error>(serde_json::json!({
+            "version": "axiograph_views_v1",
+            "views": views,
+        }))
+    })
+    .await
+    .map_err(|e| anyhow!("views task join failed: {e}"))?
+}
+
 async fn handle_discover_draft_axi(
     _state: &Arc<ServerState>,
     body: &[u8],
@@ -3159,6 +3386,7 @@ fn viz_request_from_query(query: Option<&str>) -> Result<VizRequestV1> {
         all: p.get("all").and_then(|s| parse_bool(Some(s.as_str()))),
         focus_name: p.get("focus_name").cloned(),
         focus_type: p.get("focus_type").cloned(),
+        focus_proposal: p.get("focus_proposal").cloned(),
         focus_id,
         hops,
         max_nodes,
@@ -3301,6 +3529,11 @@ async fn handle_viz_request(
         if !all_nodes {
             if let Some(id) = req.focus_id {
                 focus_ids.push(id);
+            } else if let Some(proposal_id) = req.focus_proposal.as_deref() {
+                focus_ids = crate::viz::resolve_focus_by_proposal_id(&db, proposal_id);
+                if focus_ids.is_empty() {
+                    return Err(anyhow!("no entities found with proposal_id `{proposal_id}`"));
+                }
             } else if let Some(name) = req.focus_name.as_deref() {
                 let id = crate::viz::resolve_focus_by_name_and_type(
                     &db,
@@ -3337,6 +3570,11 @@ async fn handle_viz_request(
                 let html = inject_meta_refresh(html, refresh_secs);
                 ("text/html; charset=utf-8", html.into_bytes())
             }
+            crate::viz::VizFormat::Evidence => {
+                let html = crate::viz::render_evidence_html(&db, &g)?;
+                let html = inject_meta_refresh(html, refresh_secs);
+                ("text/html; charset=utf-8", html.into_bytes())
+            }
             crate::viz::VizFormat::Json => ("application/json", crate::viz::render_json(&g)?.into_bytes()),
             crate::viz::VizFormat::Dot => ("text/vnd.graphviz; charset=utf-8", crate::viz::render_dot(&db, &g).into_bytes()),
         };
@@ -3370,6 +3608,8 @@ struct PromoteRequestV1 {
 #[derive(Debug, Clone, Serialize)]
 struct PromoteResponseV1 {
     snapshot_id: String,
+    /// Store version after the promotion (use with `X-Axiograph-Min-Version`).
+    version: Option<VersionVector>,
 }
 
 async fn handle_promote(state: &Arc<ServerState>, body: &[u8]) -> Result<PromoteResponseV1> {
@@ -3392,7 +3632,7 @@ async fn handle_promote(state: &Arc<ServerState>, body: &[u8]) -> Result<Promote
     let message = req.message.clone();
     let axi_text = req.axi_text.clone();
 
-    let snapshot_id = tokio::task::spawn_blocking(move || {
+    let (snapshot_id, version) = tokio::task::spawn_blocking(move || {
         let tmp = write_temp_file("axi", &axi_text)?;
         let out = crate::accepted_plane::promote_reviewed_module(
             &tmp,
@@ -3401,7 +3641,8 @@ async fn handle_promote(state: &Arc<ServerState>, body: &[u8]) -> Result<Promote
             &quality,
         )?;
         let _ = std::fs::remove_file(&tmp);
-        Ok::<_, anyhow::Error>(out)
+        let version = crate::replication::store_version(&dir).ok();
+        Ok::<_, anyhow::Error>((out, version))
     })
     .await
     .map_err(|e| anyhow!("promote task join failed: {e}"))??;
@@ -3418,7 +3659,7 @@ async fn handle_promote(state: &Arc<ServerState>, body: &[u8]) -> Result<Promote
         let _ = reload_now(state).await;
     }
 
-    Ok(PromoteResponseV1 { snapshot_id })
+    Ok(PromoteResponseV1 { snapshot_id, version })
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -3449,6 +3690,8 @@ struct PathdbCommitResponseV1 {
     snapshot_id: String,
     accepted_snapshot_id: String,
     ops_added: usize,
+    /// Store version after the commit (use with `X-Axiograph-Min-Version`).
+    version: Option<VersionVector>,
 }
 
 async fn handle_pathdb_commit(
@@ -3542,7 +3785,7 @@ async fn handle_pathdb_commit_req(
         }
     }
 
-    let result = tokio::task::spawn_blocking(move || {
+    let (result, version) = tokio::task::spawn_blocking(move || {
         let mut chunk_paths: Vec<PathBuf> = Vec::new();
         if !chunks.is_empty() {
             let tmp = write_temp_file(
@@ -3572,7 +3815,8 @@ async fn handle_pathdb_commit_req(
         for p in chunk_paths.into_iter().chain(proposal_paths.into_iter()) {
             let _ = std::fs::remove_file(&p);
         }
-        Ok::<_, anyhow::Error>(res)
+        let version = crate::replication::store_version(&dir).ok();
+        Ok::<_, anyhow::Error>((res, version))
     })
     .await
     .map_err(|e| anyhow!("pathdb-commit task join failed: {e}"))??;
@@ -3593,6 +3837,7 @@ async fn handle_pathdb_commit_req(
         snapshot_id: result.snapshot_id,
         accepted_snapshot_id: result.accepted_snapshot_id,
         ops_added: result.ops_added,
+        version,
     })
 }
 
@@ -3789,6 +4034,187 @@ async fn reload_if_head_changed(state: &Arc<ServerState>) -> Result<()> {
     Ok(())
 }
 
+async fn replicate_once(state: &Arc<ServerState>, upstream: &str) {
+    let SnapshotSource::Store { dir, .. } = &state.config.source else {
+        return;
+    };
+    let (upstream, dir) = (upstream.to_string(), dir.clone());
+    let outcome = tokio::task::spawn_blocking(move || {
+        crate::replication::sync_from_upstream(&upstream, &dir)
+    })
+    .await
+    .unwrap_or_else(|e| Err(anyhow!("replication task join failed: {e}")));
+    let applied = outcome.as_ref().map(|o| o.events_applied).unwrap_or(0);
+    if let Err(e) = outcome.as_ref() {
+        eprintln!("db serve: replication failed: {e}");
+    }
+    if let Ok(mut replication) = state.replication.lock() {
+        replication.record(outcome);
+    }
+    if applied > 0 {
+        if let Err(e) = reload_if_head_changed(state).await {
+            eprintln!("db serve: replica reload failed: {e}");
+        }
+    }
+}
+
+fn loaded_version(state: &ServerState) -> Option<VersionVector> {
+    state.loaded.read().ok()?.version
+}
+
+/// The response refusing a request whose `X-Axiograph-Min-Version` the loaded
+/// snapshot does not satisfy (`None` = serve it).
+fn min_version_rejection(state: &ServerState, raw: &str) -> Option<Response<Full<Bytes>>> {
+    let required = match VersionVector::parse(raw) {
+        Ok(v) => v,
+        Err(e) => {
+            return Some(json_error(
+                StatusCode::BAD_REQUEST,
+                &format!("invalid {MIN_VERSION_HEADER}: {e}"),
+            ))
+        }
+    };
+    let Some(loaded) = loaded_version(state) else {
+        return Some(json_error(
+            StatusCode::BAD_REQUEST,
+            &format!("{MIN_VERSION_HEADER} requires a store-backed server (`--dir`)"),
+        ));
+    };
+    if loaded.dominates(&required) {
+        return None;
+    }
+    let body = serde_json::json!({
+        "error": format!("snapshot version {loaded} is behind required {required}"),
+        "required": required,
+        "loaded": loaded,
+    });
+    let mut resp = json_response(StatusCode::SERVICE_UNAVAILABLE, &body);
+    resp.headers_mut().insert(
+        hyper::header::RETRY_AFTER,
+        hyper::header::HeaderValue::from(state.config.poll_interval.as_secs().max(1)),
+    );
+    Some(resp)
+}
+
+fn replication_status(state: &ServerState) -> serde_json::Value {
+    let Some(upstream_url) = state.config.replicate_from.as_ref() else {
+        return serde_json::Value::Null;
+    };
+    let Ok(replication) = state.replication.lock() else {
+        return serde_json::Value::Null;
+    };
+    let lag = match (replication.local, replication.upstream) {
+        (Some(local), Some(upstream)) => Some(local.lag_behind(&upstream)),
+        _ => None,
+    };
+    serde_json::json!({
+        "upstream": upstream_url,
+        "local_version": replication.local,
+        "upstream_version": replication.upstream,
+        "lag": lag,
+        "events_applied": replication.events_applied,
+        "last_sync_unix_secs": replication.last_sync_unix_secs,
+        "last_error": replication.last_error,
+    })
+}
+
+fn replication_dir(state: &ServerState) -> Result<&Path> {
+    match &state.config.source {
+        SnapshotSource::Store { dir, .. } => Ok(dir),
+        SnapshotSource::Axpd(_) => Err(anyhow!(
+            "replication requires `db serve --dir <accepted_plane_dir>` (store-backed server)"
+        )),
+    }
+}
+
+fn replication_version_payload(state: &ServerState) -> Result<serde_json::Value> {
+    let dir = replication_dir(state)?;
+    let tips = crate::replication::log_tips(dir)?;
+    Ok(serde_json::json!({
+        "version": "axiograph_replication_version_v1",
+        "store": crate::replication::store_version(dir)?,
+        "loaded": loaded_version(state),
+        "accepted_tip": tips.accepted,
+        "pathdb_tip": tips.pathdb,
+    }))
+}
+
+async fn handle_replication_delta(
+    state: &Arc<ServerState>,
+    query: Option<&str>,
+) -> Response<Full<Bytes>> {
+    let dir = match replication_dir(state) {
+        Ok(dir) => dir.to_path_buf(),
+        Err(e) => return json_error(StatusCode::BAD_REQUEST, &e.to_string()),
+    };
+    let params = parse_query_params(query);
+    let number = |key: &str| -> Result<u64> {
+        params
+            .get(key)
+            .map(|v| {
+                v.trim()
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("invalid `{key}`: {v}"))
+            })
+            .transpose()
+            .map(|v| v.unwrap_or(0))
+    };
+    let (since, max_events) = match (number("accepted"), number("pathdb"), number("max_events")) {
+        (Ok(accepted), Ok(pathdb), Ok(max_events)) => (
+            VersionVector { accepted, pathdb },
+            if max_events == 0 {
+                crate::replication::DEFAULT_MAX_EVENTS
+            } else {
+                max_events as usize
+            },
+        ),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            return json_error(StatusCode::BAD_REQUEST, &e.to_string())
+        }
+    };
+    let tips = LogTips {
+        accepted: params.get("accepted_tip").cloned(),
+        pathdb: params.get("pathdb_tip").cloned(),
+    };
+    let built = tokio::task::spawn_blocking(move || {
+        crate::replication::build_delta(&dir, since, &tips, max_events)
+    })
+    .await;
+    match built {
+        Ok(Ok(delta)) => json_response(StatusCode::OK, &delta),
+        Ok(Err(e)) => json_error(StatusCode::CONFLICT, &e.to_string()),
+        Err(e) => json_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("replication task join failed: {e}"),
+        ),
+    }
+}
+
+async fn handle_replication_object(
+    state: &Arc<ServerState>,
+    query: Option<&str>,
+) -> Response<Full<Bytes>> {
+    let dir = match replication_dir(state) {
+        Ok(dir) => dir.to_path_buf(),
+        Err(e) => return json_error(StatusCode::BAD_REQUEST, &e.to_string()),
+    };
+    let Some(path) = parse_query_params(query).remove("path") else {
+        return json_error(StatusCode::BAD_REQUEST, "missing `path`");
+    };
+    match tokio::task::spawn_blocking(move || crate::replication::read_object(&dir, &path)).await {
+        Ok(Ok(bytes)) => Response::builder()
+            .status(StatusCode::OK)
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .body(Full::new(Bytes::from(bytes)))
+            .unwrap_or_else(|_| text_response(StatusCode::INTERNAL_SERVER_ERROR, "object failed\n")),
+        Ok(Err(e)) => json_error(StatusCode::NOT_FOUND, &e.to_string()),
+        Err(e) => json_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("replication task join failed: {e}"),
+        ),
+    }
+}
+
 fn load_snapshot(config: &ServerConfig) -> Result<LoadedSnapshot> {
     match &config.source {
         SnapshotSource::Axpd(path) => load_from_axpd(path, config),
@@ -3800,7 +4226,29 @@ fn load_snapshot(config: &ServerConfig) -> Result<LoadedSnapshot> {
     }
 }
 
-fn configure_path_index(db: &mut PathDB, config: &ServerConfig) {
+/// With `--verify-integrity`, reject snapshots whose indexes disagree with the
+/// primary columns (a failed reload keeps serving the previous snapshot).
+fn verify_loaded_integrity(db: &PathDB, snapshot_key: &str, config: &ServerConfig) -> Result<()> {
+    if !config.verify_integrity {
+        return Ok(());
+    }
+    let report = db.verify_integrity();
+    if !report.is_ok() {
+        return Err(anyhow!(
+            "snapshot {snapshot_key} failed integrity verification: {report}"
+        ));
+    }
+    eprintln!("db serve: integrity verified for snapshot {snapshot_key}: {report}");
+    Ok(())
+}
+
+fn configure_loaded_db(db: &mut PathDB, config: &ServerConfig) {
+    if config.role != ServerRole::Master {
+        // Read-only roles never append relations: freeze adjacency into CSR
+        // and the interner into its lock-free table.
+        db.freeze_adjacency();
+        db.interner.freeze();
+    }
     if config.path_index_lru_async || config.path_index_lru_capacity > 0 {
         let queue = if config.path_index_lru_async {
             config.path_index_lru_queue
@@ -3820,18 +4268,27 @@ fn load_from_axpd(path: &Path, config: &ServerConfig) -> Result<LoadedSnapshot>
     let bytes = std::fs::read(path)
         .map_err(|e| anyhow!("failed to read .axpd `{}`: {e}", path.display()))?;
     let snapshot_key = axiograph_dsl::digest::fnv1a64_digest_bytes(&bytes);
-    let mut db = PathDB::from_bytes(&bytes)?;
-    configure_path_index(&mut db, config);
-    let sidecar_path = sidecar_path_for_axpd(path);
-    if sidecar_path.exists() {
-        if let Ok(sidecar) = read_sidecar_file(&sidecar_path) {
+    let db = PathDB::from_bytes(&bytes)?;
+    verify_loaded_integrity(&db, &snapshot_key, config)?;
+    let mut db = crate::restrict_to_role(db, config.access.as_ref());
+    configure_loaded_db(&mut db, config);
+    // Index sidecars describe the full snapshot, not a role's redacted copy.
+    let sidecar_path = config.access.is_none().then(|| sidecar_path_for_axpd(path));
+    if let Some(sidecar) = sidecar_path.as_deref().filter(|p| p.exists()) {
+        if let Ok(sidecar) = read_sidecar_file(sidecar) {
             db.load_index_sidecar(sidecar);
         }
     }
     let db = Arc::new(db);
     db.attach_async_index_source(Arc::downgrade(&db));
-    let writer = IndexSidecarWriter::new(sidecar_path, Arc::downgrade(&db), Some(snapshot_key.clone()));
-    db.attach_index_sidecar_writer(Arc::new(writer));
+    if let Some(sidecar_path) = sidecar_path {
+        let writer = IndexSidecarWriter::new(
+            sidecar_path,
+            Arc::downgrade(&db),
+            Some(snapshot_key.clone()),
+        );
+        db.attach_index_sidecar_writer(Arc::new(writer));
+    }
     let meta = MetaPlaneIndex::from_db(&db).ok();
     Ok(LoadedSnapshot {
         snapshot_key: snapshot_key.clone(),
@@ -3839,6 +4296,7 @@ fn load_from_axpd(path: &Path, config: &ServerConfig) -> Result<LoadedSnapshot>
         accepted_snapshot_id: None,
         pathdb_snapshot_id: None,
         loaded_at_unix_secs: now_unix_secs(),
+        version: None,
         entities: db.entities.len(),
         relations: db.relations.len(),
         db,
@@ -3905,9 +4363,15 @@ fn load_from_store(
             .to_string()
     };
 
-    let mut db = PathDB::from_bytes(&bytes)?;
-    configure_path_index(&mut db, config);
-    if let Some(pathdb_snapshot_id) = pathdb_snapshot_id.as_deref() {
+    let db = PathDB::from_bytes(&bytes)?;
+    verify_loaded_integrity(&db, &snapshot_key, config)?;
+    let mut db = crate::restrict_to_role(db, config.access.as_ref());
+    configure_loaded_db(&mut db, config);
+    // Index sidecars describe the full snapshot, not a role's redacted copy.
+    let sidecar_snapshot_id = pathdb_snapshot_id
+        .as_deref()
+        .filter(|_| config.access.is_none());
+    if let Some(pathdb_snapshot_id) = sidecar_snapshot_id {
         let sidecar_path = crate::pathdb_wal::checkpoint_sidecar_path(dir, pathdb_snapshot_id);
         if sidecar_path.exists() {
             if let Ok(sidecar) = read_sidecar_file(&sidecar_path) {
@@ -3917,7 +4381,7 @@ fn load_from_store(
     }
     let db = Arc::new(db);
     db.attach_async_index_source(Arc::downgrade(&db));
-    if let Some(pathdb_snapshot_id) = pathdb_snapshot_id.as_deref() {
+    if let Some(pathdb_snapshot_id) = sidecar_snapshot_id {
         let sidecar_path = crate::pathdb_wal::checkpoint_sidecar_path(dir, pathdb_snapshot_id);
         let writer = IndexSidecarWriter::new(
             sidecar_path,
@@ -3958,6 +4422,12 @@ fn load_from_store(
             layer,
             snapshot_key
         ),
+        version: crate::replication::snapshot_version(
+            dir,
+            accepted_snapshot_id.as_deref(),
+            pathdb_snapshot_id.as_deref(),
+        )
+        .ok(),
         accepted_snapshot_id,
         pathdb_snapshot_id,
         loaded_at_unix_secs: now_unix_secs(),
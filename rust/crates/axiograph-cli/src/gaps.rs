@@ -0,0 +1,663 @@
+//! `axiograph ingest gaps`: find what users ask for that the graph lacks.
+//!
+//! Reads a query audit log (see `axiograph_storage::audit`) and compares the
+//! entity types, relation types and attribute lookups its queries mention
+//! against a snapshot: types and relations with no instances, and lookups that
+//! match nothing, are gaps. Relation proposals whose endpoints resolve to
+//! neither a proposed entity nor a graph entity are dangling references.
+//!
+//! Gaps are then matched against the given proposals files: a file proposing
+//! an entity of a missing type, or one named like a missing lookup or dangling
+//! endpoint, is suggested for ingestion, ranked by how many logged queries it
+//! would answer.
+
+use anyhow::{anyhow, Result};
+use axiograph_ingest_docs::{ProposalV1, ProposalsFileV1};
+use axiograph_pathdb::{PathDB, PathQuery};
+use axiograph_storage::audit::{AuditedQuery, QueryAuditRecord};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::axql::{parse_axql_query, AxqlAtom, AxqlRegex, AxqlTerm};
+use crate::sqlish::parse_sqlish_query;
+
+#[derive(Args, Debug, Clone)]
+pub struct GapsArgs {
+    /// Snapshot to check against (`.axpd` or `.axi`).
+    pub input: PathBuf,
+
+    /// Query audit log (JSONL written by `UnifiedStorage::enable_query_audit`).
+    #[arg(long)]
+    pub audit: PathBuf,
+
+    /// Proposals JSON files to check for dangling references and to suggest
+    /// from (repeatable).
+    #[arg(long = "proposals")]
+    pub proposals: Vec<PathBuf>,
+
+    /// Show at most this many gaps per section.
+    #[arg(long, default_value_t = 20)]
+    pub top: usize,
+
+    /// Also write the full report as JSON.
+    #[arg(short, long)]
+    pub out: Option<PathBuf>,
+}
+
+/// A queried entity type, relation type or attribute lookup with no matches.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueryGap {
+    /// Type name, relation name, or `key=value` for lookups.
+    pub name: String,
+    /// Logged queries mentioning it.
+    pub queries: usize,
+    /// Of those, queries that returned no results.
+    pub failed: usize,
+}
+
+/// A relation proposal endpoint that resolves to nothing.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DanglingReference {
+    pub proposal_id: String,
+    pub endpoint: String,
+    /// Locator of the proposals file it came from.
+    pub source: String,
+}
+
+/// A proposals source that would fill some gaps if ingested.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IngestSuggestion {
+    /// Proposals file locator (`source.locator`).
+    pub source: String,
+    pub source_type: String,
+    /// Logged queries it would answer plus dangling references it resolves.
+    pub score: usize,
+    /// Gaps it fills (`type:Material`, `relation:usesTool`, `name=Ti-6Al-4V`).
+    pub fills: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GapsReport {
+    pub queries_analyzed: usize,
+    pub failed_queries: usize,
+    /// Queries that could not be parsed (not counted as mentioning anything).
+    pub unparsed_queries: usize,
+    pub missing_types: Vec<QueryGap>,
+    pub missing_relations: Vec<QueryGap>,
+    pub failed_lookups: Vec<QueryGap>,
+    pub dangling_references: Vec<DanglingReference>,
+    pub suggestions: Vec<IngestSuggestion>,
+    /// Gaps no given source covers, most queried first.
+    pub uncovered: Vec<String>,
+}
+
+/// Entity types, relation types and attribute lookups a query mentions.
+#[derive(Debug, Default, PartialEq)]
+struct Mentions {
+    types: BTreeSet<String>,
+    relations: BTreeSet<String>,
+    lookups: BTreeSet<(String, String)>,
+}
+
+impl Mentions {
+    fn term(&mut self, term: &AxqlTerm) {
+        if let AxqlTerm::Lookup { key, value } = term {
+            self.lookups.insert((key.clone(), value.clone()));
+        }
+    }
+
+    fn regex(&mut self, regex: &AxqlRegex) {
+        match regex {
+            AxqlRegex::Epsilon => {}
+            AxqlRegex::Rel(r) => {
+                self.relations.insert(r.clone());
+            }
+            AxqlRegex::Seq(parts) | AxqlRegex::Alt(parts) => {
+                parts.iter().for_each(|p| self.regex(p));
+            }
+            AxqlRegex::Star(r) | AxqlRegex::Plus(r) | AxqlRegex::Opt(r) => self.regex(r),
+        }
+    }
+
+    fn path_query(&mut self, query: &PathQuery) {
+        match query {
+            PathQuery::SelectByType(type_name) => {
+                self.types.insert(type_name.clone());
+            }
+            PathQuery::SelectRelated(_, rel_type) => {
+                self.relations.insert(rel_type.clone());
+            }
+            PathQuery::FollowPath { path, .. } => self.relations.extend(path.iter().cloned()),
+            PathQuery::FindPaths { .. } => {}
+            PathQuery::Join(left, right) | PathQuery::Union(left, right) => {
+                self.path_query(left);
+                self.path_query(right);
+            }
+            PathQuery::WithConfidence { base, .. } | PathQuery::WithPathConfidence { base, .. } => {
+                self.path_query(base)
+            }
+        }
+    }
+
+    fn atom(&mut self, atom: &AxqlAtom) {
+        match atom {
+            AxqlAtom::Type { term, type_name } => {
+                self.term(term);
+                self.types.insert(type_name.clone());
+            }
+            AxqlAtom::Edge { left, path, right } => {
+                self.term(left);
+                self.term(right);
+                self.regex(&path.regex);
+            }
+            AxqlAtom::AttrEq { term, key, value } => {
+                self.term(term);
+                self.lookups.insert((key.clone(), value.clone()));
+            }
+            AxqlAtom::AttrContains { term, .. }
+            | AxqlAtom::AttrFts { term, .. }
+            | AxqlAtom::AttrFuzzy { term, .. } => self.term(term),
+            AxqlAtom::Fact { fact, fields, .. } => {
+                fact.iter().for_each(|t| self.term(t));
+                fields.iter().for_each(|(_, t)| self.term(t));
+            }
+            AxqlAtom::HasOut { term, rels } => {
+                self.term(term);
+                self.relations.extend(rels.iter().cloned());
+            }
+            AxqlAtom::Attrs { term, pairs } => {
+                self.term(term);
+                self.lookups.extend(pairs.iter().cloned());
+            }
+            AxqlAtom::Shape {
+                term,
+                type_name,
+                rels,
+                attrs,
+            } => {
+                self.term(term);
+                self.types.extend(type_name.iter().cloned());
+                self.relations.extend(rels.iter().cloned());
+                self.lookups.extend(attrs.iter().cloned());
+            }
+        }
+    }
+}
+
+/// What a logged query mentions, or `None` if its text does not parse.
+fn query_mentions(query: &AuditedQuery) -> Option<Mentions> {
+    let mut m = Mentions::default();
+    match query {
+        AuditedQuery::Axql { query } => {
+            let q = parse_axql_query(query).ok()?;
+            q.disjuncts.iter().flatten().for_each(|a| m.atom(a));
+        }
+        AuditedQuery::Sql { query } => {
+            let q = parse_sqlish_query(query).ok()?;
+            q.disjuncts.iter().flatten().for_each(|a| m.atom(a));
+        }
+        AuditedQuery::PathQuery { query } => m.path_query(query),
+    }
+    Some(m)
+}
+
+fn has_type(db: &PathDB, name: &str) -> bool {
+    db.find_by_type(name).is_some_and(|ids| !ids.is_empty())
+}
+
+fn has_relation(db: &PathDB, name: &str) -> bool {
+    db.interner
+        .id_of(name)
+        .is_some_and(|id| db.relations.rel_type_count(id) > 0)
+}
+
+fn has_attr(db: &PathDB, key: &str, value: &str) -> bool {
+    !db.entities_with_attr_fuzzy(key, value, 0).is_empty()
+}
+
+#[derive(Default)]
+struct Tally(BTreeMap<String, (usize, usize)>);
+
+impl Tally {
+    fn add(&mut self, name: String, failed: bool) {
+        let entry = self.0.entry(name).or_default();
+        entry.0 += 1;
+        entry.1 += usize::from(failed);
+    }
+
+    /// Most queried first, then by name.
+    fn into_gaps(self) -> Vec<QueryGap> {
+        let mut gaps: Vec<QueryGap> = self
+            .0
+            .into_iter()
+            .map(|(name, (queries, failed))| QueryGap {
+                name,
+                queries,
+                failed,
+            })
+            .collect();
+        gaps.sort_by(|a, b| b.queries.cmp(&a.queries).then_with(|| a.name.cmp(&b.name)));
+        gaps
+    }
+}
+
+/// Compare logged queries and proposals against `db`.
+pub fn gaps_report(
+    db: &PathDB,
+    records: &[QueryAuditRecord],
+    proposals: &[ProposalsFileV1],
+) -> GapsReport {
+    let mut report = GapsReport::default();
+    let (mut types, mut relations, mut lookups) =
+        (Tally::default(), Tally::default(), Tally::default());
+
+    for record in records {
+        report.queries_analyzed += 1;
+        let failed = record.result_count == Some(0);
+        report.failed_queries += usize::from(failed);
+        let Some(m) = query_mentions(&record.query) else {
+            report.unparsed_queries += 1;
+            continue;
+        };
+        for t in m.types.into_iter().filter(|t| !has_type(db, t)) {
+            types.add(t, failed);
+        }
+        for r in m.relations.into_iter().filter(|r| !has_relation(db, r)) {
+            relations.add(r, failed);
+        }
+        for (k, v) in m.lookups {
+            if !has_attr(db, &k, &v) {
+                lookups.add(format!("{k}={v}"), failed);
+            }
+        }
+    }
+    report.missing_types = types.into_gaps();
+    report.missing_relations = relations.into_gaps();
+    report.failed_lookups = lookups.into_gaps();
+
+    // Endpoints may name any proposed entity (by id or name) or a graph entity.
+    let proposed: BTreeSet<&str> = proposals
+        .iter()
+        .flat_map(|f| &f.proposals)
+        .filter_map(|p| match p {
+            ProposalV1::Entity {
+                entity_id, name, ..
+            } => Some([entity_id.as_str(), name.as_str()]),
+            ProposalV1::Relation { .. } => None,
+        })
+        .flatten()
+        .collect();
+    for file in proposals {
+        for p in &file.proposals {
+            let ProposalV1::Relation {
+                meta,
+                source,
+                target,
+                ..
+            } = p
+            else {
+                continue;
+            };
+            for endpoint in [source, target] {
+                if !proposed.contains(endpoint.as_str()) && !has_attr(db, "name", endpoint) {
+                    report.dangling_references.push(DanglingReference {
+                        proposal_id: meta.proposal_id.clone(),
+                        endpoint: endpoint.clone(),
+                        source: file.source.locator.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    report.suggestions = suggest_sources(&report, proposals);
+    let covered: BTreeSet<&String> = report.suggestions.iter().flat_map(|s| &s.fills).collect();
+    report.uncovered = gap_keys(&report)
+        .into_iter()
+        .map(|(key, _)| key)
+        .filter(|key| !covered.contains(key))
+        .collect();
+    report
+}
+
+/// Every gap as `(key, weight)`, most queried first.
+fn gap_keys(report: &GapsReport) -> Vec<(String, usize)> {
+    let mut keys: Vec<(String, usize)> = report
+        .missing_types
+        .iter()
+        .map(|g| (format!("type:{}", g.name), g.queries))
+        .chain(
+            report
+                .missing_relations
+                .iter()
+                .map(|g| (format!("relation:{}", g.name), g.queries)),
+        )
+        .chain(
+            report
+                .failed_lookups
+                .iter()
+                .map(|g| (g.name.clone(), g.queries)),
+        )
+        .collect();
+    let mut dangling: BTreeMap<String, usize> = BTreeMap::new();
+    for d in &report.dangling_references {
+        *dangling.entry(format!("name={}", d.endpoint)).or_default() += 1;
+    }
+    for (key, n) in dangling {
+        match keys.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, w)) => *w += n,
+            None => keys.push((key, n)),
+        }
+    }
+    keys.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    keys
+}
+
+/// Rank proposals files by the weight of the gaps their proposals fill.
+fn suggest_sources(report: &GapsReport, proposals: &[ProposalsFileV1]) -> Vec<IngestSuggestion> {
+    let gaps: BTreeMap<String, usize> = gap_keys(report).into_iter().collect();
+    let mut suggestions: Vec<IngestSuggestion> = Vec::new();
+    for file in proposals {
+        let mut fills = BTreeSet::new();
+        for p in &file.proposals {
+            let keys: Vec<String> = match p {
+                ProposalV1::Entity {
+                    entity_type,
+                    name,
+                    attributes,
+                    ..
+                } => std::iter::once(format!("type:{entity_type}"))
+                    .chain(std::iter::once(format!("name={name}")))
+                    .chain(attributes.iter().map(|(k, v)| format!("{k}={v}")))
+                    .collect(),
+                ProposalV1::Relation { rel_type, .. } => vec![format!("relation:{rel_type}")],
+            };
+            fills.extend(keys.into_iter().filter(|k| gaps.contains_key(k)));
+        }
+        if fills.is_empty() {
+            continue;
+        }
+        // The same locator may appear in several files; merge them.
+        let suggestion = match suggestions
+            .iter_mut()
+            .find(|s| s.source == file.source.locator)
+        {
+            Some(s) => s,
+            None => {
+                suggestions.push(IngestSuggestion {
+                    source: file.source.locator.clone(),
+                    source_type: file.source.source_type.clone(),
+                    score: 0,
+                    fills: Vec::new(),
+                });
+                suggestions.last_mut().expect("just pushed")
+            }
+        };
+        for key in fills {
+            if !suggestion.fills.contains(&key) {
+                suggestion.score += gaps[&key];
+                suggestion.fills.push(key);
+            }
+        }
+    }
+    suggestions.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.source.cmp(&b.source)));
+    suggestions
+}
+
+pub fn cmd_gaps(args: &GapsArgs) -> Result<()> {
+    let db = crate::load_pathdb_for_cli(&args.input)?;
+    let text = fs::read_to_string(&args.audit)
+        .map_err(|e| anyhow!("failed to read {}: {e}", args.audit.display()))?;
+    let records = text
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(serde_json::from_str)
+        .collect::<std::result::Result<Vec<QueryAuditRecord>, _>>()
+        .map_err(|e| anyhow!("failed to parse audit log {}: {e}", args.audit.display()))?;
+    let proposals = args
+        .proposals
+        .iter()
+        .map(|path| -> Result<ProposalsFileV1> {
+            serde_json::from_str(&fs::read_to_string(path)?)
+                .map_err(|e| anyhow!("failed to parse proposals {}: {e}", path.display()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let report = gaps_report(&db, &records, &proposals);
+
+    println!(
+        "{} {} (queries={}, failed={}, unparsed={})",
+        "Gaps".green().bold(),
+        args.input.display(),
+        report.queries_analyzed,
+        report.failed_queries,
+        report.unparsed_queries
+    );
+    for (title, gaps) in [
+        ("Missing entity types", &report.missing_types),
+        ("Missing relation types", &report.missing_relations),
+        ("Failed lookups", &report.failed_lookups),
+    ] {
+        if gaps.is_empty() {
+            continue;
+        }
+        println!("  {}", title.yellow());
+        for g in gaps.iter().take(args.top) {
+            println!(
+                "    {} (queries={}, failed={})",
+                g.name, g.queries, g.failed
+            );
+        }
+    }
+    if !report.dangling_references.is_empty() {
+        println!("  {}", "Dangling references".yellow());
+        for d in report.dangling_references.iter().take(args.top) {
+            println!("    {} → {} ({})", d.proposal_id, d.endpoint, d.source);
+        }
+    }
+    if !report.suggestions.is_empty() {
+        println!("  {}", "Ingest next".cyan());
+        for s in report.suggestions.iter().take(args.top) {
+            println!(
+                "    {} [{}] score={}: {}",
+                s.source,
+                s.source_type,
+                s.score,
+                s.fills.join(", ")
+            );
+        }
+    }
+    if !report.uncovered.is_empty() {
+        println!(
+            "  {} {}",
+            "No known source for:".red(),
+            report
+                .uncovered
+                .iter()
+                .take(args.top)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    if let Some(path) = &args.out {
+        fs::write(path, serde_json::to_string_pretty(&report)?)?;
+        println!("  {} {}", "→".cyan(), path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axiograph_ingest_docs::{ProposalMetaV1, ProposalSourceV1, PROPOSALS_VERSION_V1};
+    use std::collections::HashMap;
+
+    fn record(query: AuditedQuery, result_count: u64) -> QueryAuditRecord {
+        serde_json::from_value(serde_json::json!({
+            "seq": 0,
+            "timestamp": "2026-01-01T00:00:00Z",
+            "principal": "analyst",
+            "query": query,
+            "snapshot": "genesis",
+            "result_count": result_count,
+        }))
+        .unwrap()
+    }
+
+    fn axql(query: &str, result_count: u64) -> QueryAuditRecord {
+        record(
+            AuditedQuery::Axql {
+                query: query.to_string(),
+            },
+            result_count,
+        )
+    }
+
+    fn meta(id: &str) -> ProposalMetaV1 {
+        ProposalMetaV1 {
+            proposal_id: id.to_string(),
+            confidence: 0.9,
+            evidence: Vec::new(),
+            public_rationale: String::new(),
+            metadata: HashMap::new(),
+            schema_hint: None,
+        }
+    }
+
+    fn entity(entity_type: &str, name: &str) -> ProposalV1 {
+        ProposalV1::Entity {
+            meta: meta(name),
+            entity_id: format!("e::{name}"),
+            entity_type: entity_type.to_string(),
+            name: name.to_string(),
+            attributes: HashMap::new(),
+            description: None,
+        }
+    }
+
+    fn relation(id: &str, rel_type: &str, source: &str, target: &str) -> ProposalV1 {
+        ProposalV1::Relation {
+            meta: meta(id),
+            relation_id: id.to_string(),
+            rel_type: rel_type.to_string(),
+            source: source.to_string(),
+            target: target.to_string(),
+            attributes: HashMap::new(),
+        }
+    }
+
+    fn file(locator: &str, proposals: Vec<ProposalV1>) -> ProposalsFileV1 {
+        ProposalsFileV1 {
+            version: PROPOSALS_VERSION_V1,
+            generated_at: "0".to_string(),
+            source: ProposalSourceV1 {
+                source_type: "doc".to_string(),
+                locator: locator.to_string(),
+            },
+            schema_hint: None,
+            proposals,
+        }
+    }
+
+    #[test]
+    fn logged_pathqueries_yield_types_and_relations() {
+        let q = axiograph_pathdb::PathQuery::Join(
+            Box::new(axiograph_pathdb::PathQuery::SelectByType(
+                "Coolant \"X\"".to_string(),
+            )),
+            Box::new(axiograph_pathdb::PathQuery::FollowPath {
+                start: 1,
+                path: vec!["usesTool".to_string(), "madeOf".to_string()],
+            }),
+        );
+        let m = query_mentions(&AuditedQuery::PathQuery { query: q }).unwrap();
+        assert_eq!(m.types, BTreeSet::from(["Coolant \"X\"".to_string()]));
+        assert_eq!(
+            m.relations,
+            BTreeSet::from(["madeOf".to_string(), "usesTool".to_string()])
+        );
+    }
+
+    #[test]
+    fn reports_missing_types_lookups_and_dangling_references() {
+        let mut db = PathDB::new();
+        let ti = db.add_entity("Material", vec![("name", "Titanium")]);
+        let op = db.add_entity("Operation", vec![("name", "Roughing")]);
+        db.add_relation("hasMaterial", op, ti, 1.0, vec![]);
+        db.build_indexes();
+
+        let records = vec![
+            axql("select ?x where ?x is Coolant", 0),
+            axql("select ?x where ?x is Coolant, ?x -hasMaterial-> ?m", 0),
+            axql("select ?x where ?x is Material, ?x.name = \"Inconel\"", 0),
+            axql("select ?x where ?x is Material", 1),
+            axql("select ?x where ?x -coolsWith-> ?c", 0),
+            axql("not axql", 0),
+        ];
+        let proposals = vec![
+            file(
+                "manuals/coolants.pdf",
+                vec![
+                    entity("Coolant", "Flood"),
+                    relation("r1", "coolsWith", "Roughing", "Flood"),
+                ],
+            ),
+            file(
+                "notes/alloys.md",
+                vec![
+                    entity("Material", "Inconel"),
+                    relation("r2", "hasMaterial", "Milling", "Inconel"),
+                ],
+            ),
+        ];
+
+        let report = gaps_report(&db, &records, &proposals);
+        assert_eq!(
+            (
+                report.queries_analyzed,
+                report.failed_queries,
+                report.unparsed_queries
+            ),
+            (6, 5, 1)
+        );
+        assert_eq!(
+            report.missing_types,
+            vec![QueryGap {
+                name: "Coolant".to_string(),
+                queries: 2,
+                failed: 2
+            }]
+        );
+        assert_eq!(report.missing_relations[0].name, "coolsWith");
+        assert_eq!(report.failed_lookups[0].name, "name=Inconel");
+        assert_eq!(
+            report.dangling_references,
+            vec![DanglingReference {
+                proposal_id: "r2".to_string(),
+                endpoint: "Milling".to_string(),
+                source: "notes/alloys.md".to_string(),
+            }]
+        );
+
+        let ranked: Vec<(&str, usize)> = report
+            .suggestions
+            .iter()
+            .map(|s| (s.source.as_str(), s.score))
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![("manuals/coolants.pdf", 3), ("notes/alloys.md", 1)]
+        );
+        assert_eq!(
+            report.suggestions[0].fills,
+            vec!["relation:coolsWith", "type:Coolant"]
+        );
+        assert_eq!(report.uncovered, vec!["name=Milling"]);
+    }
+}
@@ -0,0 +1,264 @@
+//! Ingestion provenance as graph structure (PROV-inspired).
+//!
+//! Importing a `proposals.json` already creates a `ProposalRun` node linked
+//! to every imported proposal (`run_has_proposal` / `proposal_in_run`). This
+//! module gives that run the rest of a provenance record, so "where did this
+//! fact come from, which run, which tool version" is an ordinary path query:
+//!
+//! | PROV                  | PathDB                                                  |
+//! |-----------------------|---------------------------------------------------------|
+//! | `prov:Activity`       | the `ProposalRun` node, also typed `IngestRun`           |
+//! | `prov:Entity` (input) | `IngestSource` (`source_type` + `locator`)              |
+//! | `prov:SoftwareAgent`  | `IngestTool` (`tool` + `tool_version`)                  |
+//! | `prov:used`           | `run_used_source` / `source_used_by_run`                |
+//! | `prov:wasAssociatedWith` | `run_used_tool` / `tool_used_by_run`                 |
+//! | `prov:wasGeneratedBy` | `run_produced_fact` / `fact_produced_by_run` (relation facts), |
+//! |                       | `run_produced_chunk` / `chunk_produced_by_run` (evidence chunks) |
+//!
+//! ```text
+//! q select ?f ?tool where ?f : ProposalFact, ?f -fact_produced_by_run/run_used_tool-> ?tool
+//! ```
+//!
+//! Sources and tools are shared across runs (keyed by `external_id`), so
+//! "every run over this file" is `source_used_by_run` from its source. The
+//! tool is the `axiograph` build that imported the run (the ingesters ship in
+//! the same binary).
+//!
+//! Like the rest of the evidence plane this is an extension layer: it never
+//! touches the accepted `.axi` plane.
+
+use anyhow::Result;
+
+use axiograph_ingest_docs::ProposalsFileV1;
+use axiograph_pathdb::axi_meta::META_ATTR_NAME;
+use axiograph_pathdb::PathDB;
+
+pub(crate) const TYPE_INGEST_RUN: &str = "IngestRun";
+pub(crate) const TYPE_INGEST_SOURCE: &str = "IngestSource";
+pub(crate) const TYPE_INGEST_TOOL: &str = "IngestTool";
+
+pub(crate) const TOOL_NAME: &str = "axiograph";
+pub(crate) const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Attach the run's source and tool (idempotent).
+pub(crate) fn record_run_provenance(
+    db: &mut PathDB,
+    run_id: u32,
+    file: &ProposalsFileV1,
+) -> Result<()> {
+    db.mark_virtual_type(run_id, TYPE_INGEST_RUN)?;
+    if find_attr(db, run_id, "tool").is_none() {
+        db.upsert_entity_attr(run_id, "tool", TOOL_NAME)?;
+        db.upsert_entity_attr(run_id, "tool_version", TOOL_VERSION)?;
+    }
+
+    let source_type = file.source.source_type.trim();
+    let locator = file.source.locator.trim();
+    let source_id = get_or_create(
+        db,
+        TYPE_INGEST_SOURCE,
+        &format!("ingest_source::{source_type}::{locator}"),
+        &format!("{source_type}:{locator}"),
+        &[("source_type", source_type), ("locator", locator)],
+    )?;
+    add_edge_pair(
+        db,
+        "run_used_source",
+        "source_used_by_run",
+        run_id,
+        source_id,
+    )?;
+
+    let tool_id = get_or_create(
+        db,
+        TYPE_INGEST_TOOL,
+        &format!("ingest_tool::{TOOL_NAME}::{TOOL_VERSION}"),
+        &format!("{TOOL_NAME} {TOOL_VERSION}"),
+        &[("tool", TOOL_NAME), ("tool_version", TOOL_VERSION)],
+    )?;
+    add_edge_pair(db, "run_used_tool", "tool_used_by_run", run_id, tool_id)
+}
+
+/// `run` generated the relation fact node `fact`.
+pub(crate) fn link_run_to_fact(db: &mut PathDB, run_id: u32, fact_id: u32) -> Result<()> {
+    add_edge_pair(
+        db,
+        "run_produced_fact",
+        "fact_produced_by_run",
+        run_id,
+        fact_id,
+    )
+}
+
+/// `run` produced (cites) the evidence chunk `chunk`.
+pub(crate) fn link_run_to_chunk(db: &mut PathDB, run_id: u32, chunk_id: u32) -> Result<()> {
+    add_edge_pair(
+        db,
+        "run_produced_chunk",
+        "chunk_produced_by_run",
+        run_id,
+        chunk_id,
+    )
+}
+
+fn get_or_create(
+    db: &mut PathDB,
+    entity_type: &str,
+    external_id: &str,
+    name: &str,
+    attrs: &[(&str, &str)],
+) -> Result<u32> {
+    let key = db.interner.intern("external_id");
+    let value = db.interner.intern(external_id);
+    if let Some(id) = db.find_by_type(entity_type).and_then(|ids| {
+        ids.iter()
+            .find(|&id| db.entities.get_attr(id, key) == Some(value))
+    }) {
+        return Ok(id);
+    }
+    let mut all: Vec<(&str, &str)> = vec![(META_ATTR_NAME, name), ("external_id", external_id)];
+    all.extend_from_slice(attrs);
+    Ok(db.add_entity(entity_type, all))
+}
+
+fn add_edge_pair(
+    db: &mut PathDB,
+    forward: &str,
+    backward: &str,
+    source: u32,
+    target: u32,
+) -> Result<()> {
+    for (rel, from, to) in [(forward, source, target), (backward, target, source)] {
+        let rel_id = db.interner.intern(rel);
+        if !db.relations.has_edge(from, rel_id, to) {
+            db.add_relation(rel, from, to, 1.0, vec![]);
+        }
+    }
+    Ok(())
+}
+
+fn find_attr(db: &PathDB, entity_id: u32, key: &str) -> Option<String> {
+    db.get_entity(entity_id)?.attrs.get(key).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axiograph_ingest_docs::{
+        Chunk, EvidencePointer, ProposalMetaV1, ProposalSourceV1, ProposalV1, PROPOSALS_VERSION_V1,
+    };
+    use std::collections::HashMap;
+
+    fn meta(id: &str) -> ProposalMetaV1 {
+        ProposalMetaV1 {
+            proposal_id: id.to_string(),
+            confidence: 0.9,
+            evidence: vec![EvidencePointer {
+                chunk_id: "doc_0_0".to_string(),
+                locator: None,
+                span_id: None,
+            }],
+            public_rationale: String::new(),
+            metadata: HashMap::new(),
+            schema_hint: None,
+        }
+    }
+
+    fn entity(id: &str, name: &str) -> ProposalV1 {
+        ProposalV1::Entity {
+            meta: meta(id),
+            entity_id: id.to_string(),
+            entity_type: "Supplier".to_string(),
+            name: name.to_string(),
+            attributes: HashMap::new(),
+            description: None,
+        }
+    }
+
+    fn proposals(locator: &str) -> ProposalsFileV1 {
+        ProposalsFileV1 {
+            version: PROPOSALS_VERSION_V1,
+            generated_at: "0".to_string(),
+            source: ProposalSourceV1 {
+                source_type: "doc".to_string(),
+                locator: locator.to_string(),
+            },
+            schema_hint: None,
+            proposals: vec![
+                entity("acme", "Acme"),
+                entity("bolt", "Bolt"),
+                ProposalV1::Relation {
+                    meta: meta("acme_supplies_bolt"),
+                    relation_id: "acme_supplies_bolt".to_string(),
+                    rel_type: "supplies".to_string(),
+                    source: "acme".to_string(),
+                    target: "bolt".to_string(),
+                    attributes: HashMap::new(),
+                },
+            ],
+        }
+    }
+
+    fn only(ids: roaring::RoaringBitmap) -> u32 {
+        assert_eq!(ids.len(), 1, "expected one entity");
+        ids.min().unwrap()
+    }
+
+    #[test]
+    fn facts_trace_back_to_run_source_and_tool() -> Result<()> {
+        let mut db = PathDB::new();
+        crate::doc_chunks::import_chunks_into_pathdb(
+            &mut db,
+            &[Chunk {
+                chunk_id: "doc_0_0".to_string(),
+                document_id: "notes.md".to_string(),
+                page: None,
+                span_id: "p0".to_string(),
+                text: "Acme supplies bolts.".to_string(),
+                bbox: None,
+                metadata: HashMap::new(),
+            }],
+        )?;
+        crate::proposals_import::import_proposals_file_into_pathdb(
+            &mut db,
+            &proposals("notes.md"),
+            "d1",
+        )?;
+
+        let run = only(
+            db.find_by_type(TYPE_INGEST_RUN)
+                .cloned()
+                .unwrap_or_default(),
+        );
+        let fact = only(db.follow_path(run, &["run_produced_fact"]));
+        assert_eq!(
+            db.follow_path(fact, &["fact_produced_by_run"]).min(),
+            Some(run)
+        );
+
+        let tool = only(db.follow_path(fact, &["fact_produced_by_run", "run_used_tool"]));
+        let tool_view = db.get_entity(tool).unwrap();
+        assert_eq!(tool_view.entity_type, TYPE_INGEST_TOOL);
+        assert_eq!(tool_view.attrs["tool_version"], TOOL_VERSION);
+
+        let source = only(db.follow_path(fact, &["fact_produced_by_run", "run_used_source"]));
+        assert_eq!(db.get_entity(source).unwrap().attrs["locator"], "notes.md");
+
+        let chunk = only(db.follow_path(run, &["run_produced_chunk"]));
+        assert_eq!(db.get_entity(chunk).unwrap().entity_type, "DocChunk");
+        assert!(db
+            .follow_path(chunk, &["chunk_produced_by_run"])
+            .contains(run));
+
+        // A second run over the same source reuses its Source and Tool nodes.
+        crate::proposals_import::import_proposals_file_into_pathdb(
+            &mut db,
+            &proposals("notes.md"),
+            "d2",
+        )?;
+        assert_eq!(db.find_by_type(TYPE_INGEST_SOURCE).unwrap().len(), 1);
+        assert_eq!(db.find_by_type(TYPE_INGEST_TOOL).unwrap().len(), 1);
+        assert_eq!(db.follow_path(source, &["source_used_by_run"]).len(), 2);
+        Ok(())
+    }
+}
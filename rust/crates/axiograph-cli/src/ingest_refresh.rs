@@ -0,0 +1,651 @@
+//! Scheduled re-ingestion with freshness tracking (`axiograph ingest refresh`).
+//!
+//! `ingest run` ingests every source every time. `ingest refresh` runs the
+//! same `ingest_plan_v1` plan incrementally against a freshness state file
+//! (`ingest_freshness_v1`) that records, per source, when it was last checked
+//! and ingested, the content digest it was ingested at (its version / ETag),
+//! and the digest of every proposal it produced.
+//!
+//! Each pass:
+//!
+//! - re-ingests sources that are **due** (their `refresh` schedule has elapsed
+//!   since the last check, or they were never ingested) **and** whose content
+//!   changed,
+//! - writes only the **delta**: proposals that are new or changed since the
+//!   source's last ingestion (removed ids are listed in the report), each
+//!   tagged with `source_version` / `last_ingested` metadata, and
+//! - flags as **stale** the proposals of sources that changed but are not due
+//!   yet, that can no longer be read, or whose re-ingestion failed.
+//!
+//! Schedules are cron-like shorthands: `@hourly`, `@daily`, `@weekly`, or an
+//! interval such as `30m`, `6h`, `2d`. A source without a schedule is due on
+//! every pass. `--watch` keeps running, sleeping until the next source is due.
+
+use anyhow::{anyhow, Context, Result};
+use axiograph_ingest_docs::ProposalV1;
+use clap::Args;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::ingest_run::{self, IngestJob, IngestPlanV1};
+
+pub const INGEST_FRESHNESS_VERSION_V1: &str = "ingest_freshness_v1";
+
+#[derive(Args, Debug, Clone)]
+pub struct IngestRefreshArgs {
+    /// Ingestion plan JSON (`ingest_plan_v1`); sources may carry a `refresh` schedule.
+    #[arg(long)]
+    pub plan: PathBuf,
+
+    /// Freshness state JSON (`ingest_freshness_v1`); created if missing.
+    #[arg(long)]
+    pub state: PathBuf,
+
+    /// Output delta proposals JSON (written only when something changed).
+    #[arg(short, long)]
+    pub out: PathBuf,
+
+    /// Output refresh report JSON (per-source status and stale facts).
+    #[arg(long)]
+    pub report: Option<PathBuf>,
+
+    /// Treat every source as due, ignoring schedules.
+    #[arg(long)]
+    pub force: bool,
+
+    /// Keep running, re-checking sources as their schedules come due.
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Maximum number of sources ingested concurrently.
+    #[arg(short = 'j', long)]
+    pub parallelism: Option<usize>,
+}
+
+/// When to re-check a source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RefreshPolicy {
+    /// `@hourly`, `@daily`, `@weekly`, or `<n><s|m|h|d|w>` (e.g. `6h`).
+    pub every: String,
+}
+
+impl RefreshPolicy {
+    pub fn interval(&self) -> Result<Duration> {
+        parse_schedule(&self.every)
+    }
+}
+
+pub(crate) fn parse_schedule(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    let secs = match s {
+        "@hourly" => 3_600,
+        "@daily" => 86_400,
+        "@weekly" => 604_800,
+        _ => {
+            let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            let (n, unit) = s.split_at(split);
+            let n: u64 = n
+                .parse()
+                .map_err(|_| anyhow!("invalid refresh schedule `{s}`"))?;
+            let unit = match unit {
+                "s" => 1,
+                "m" => 60,
+                "h" => 3_600,
+                "d" => 86_400,
+                "w" => 604_800,
+                _ => {
+                    return Err(anyhow!(
+                        "invalid refresh schedule `{s}` (unit must be s/m/h/d/w)"
+                    ))
+                }
+            };
+            n.saturating_mul(unit)
+        }
+    };
+    if secs == 0 {
+        return Err(anyhow!("refresh schedule `{s}` must be positive"));
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+/// Freshness of one source, as of its last check.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SourceFreshness {
+    pub path: PathBuf,
+    /// Unix seconds of the last pass that looked at this source.
+    pub last_checked: u64,
+    /// Unix seconds of the last successful ingestion.
+    pub last_ingested: u64,
+    /// Content digest the source was last ingested at.
+    pub version: String,
+    /// Proposal id -> digest, from the last successful ingestion.
+    #[serde(default)]
+    pub proposals: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FreshnessStateV1 {
+    pub version: String,
+    /// Keyed by source name.
+    #[serde(default)]
+    pub sources: BTreeMap<String, SourceFreshness>,
+}
+
+impl Default for FreshnessStateV1 {
+    fn default() -> Self {
+        Self {
+            version: INGEST_FRESHNESS_VERSION_V1.to_string(),
+            sources: BTreeMap::new(),
+        }
+    }
+}
+
+impl FreshnessStateV1 {
+    pub fn load_or_default(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read freshness state {}", path.display()))?;
+        let state: Self = serde_json::from_str(&text)
+            .with_context(|| format!("failed to parse freshness state {}", path.display()))?;
+        if state.version != INGEST_FRESHNESS_VERSION_V1 {
+            return Err(anyhow!(
+                "unsupported freshness state version `{}` (expected `{INGEST_FRESHNESS_VERSION_V1}`)",
+                state.version
+            ));
+        }
+        Ok(state)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefreshStatus {
+    /// Re-ingested; its delta is in the output.
+    Ingested,
+    /// Due, but its content has not changed.
+    Unchanged,
+    /// Not due yet, and unchanged.
+    NotDue,
+    /// Changed since it was ingested, but not due yet.
+    Pending,
+    /// Could not be read or re-ingested.
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaleReason {
+    /// The source changed and has not been re-ingested yet.
+    SourceChanged,
+    /// The source can no longer be read.
+    SourceMissing,
+    /// Re-ingesting the changed source failed.
+    IngestFailed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceRefresh {
+    pub name: String,
+    pub status: RefreshStatus,
+    pub version: Option<String>,
+    pub last_ingested: Option<u64>,
+    /// Unix seconds at which the source is next due.
+    pub next_due: Option<u64>,
+    pub added: usize,
+    pub changed: usize,
+    /// Proposal ids the source no longer produces.
+    pub removed: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// A proposal whose source has moved on since it was ingested.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct StaleFact {
+    pub proposal_id: String,
+    pub source: String,
+    pub reason: StaleReason,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RefreshReport {
+    pub checked_at: u64,
+    pub sources: Vec<SourceRefresh>,
+    pub stale: Vec<StaleFact>,
+}
+
+impl RefreshReport {
+    /// Earliest `next_due` over scheduled sources.
+    pub fn next_due(&self) -> Option<u64> {
+        self.sources.iter().filter_map(|s| s.next_due).min()
+    }
+}
+
+fn proposal_id(proposal: &ProposalV1) -> &str {
+    match proposal {
+        ProposalV1::Entity { meta, .. } | ProposalV1::Relation { meta, .. } => &meta.proposal_id,
+    }
+}
+
+fn proposal_digest(proposal: &ProposalV1) -> String {
+    // `Value` maps are sorted, so the digest does not depend on `HashMap` order.
+    let value = serde_json::to_value(proposal).unwrap_or_default();
+    axiograph_dsl::digest::fnv1a64_digest_bytes(value.to_string().as_bytes())
+}
+
+fn tag_freshness(proposal: &mut ProposalV1, version: &str, now: u64) {
+    let (ProposalV1::Entity { meta, .. } | ProposalV1::Relation { meta, .. }) = proposal;
+    meta.metadata
+        .insert("source_version".to_string(), version.to_string());
+    meta.metadata
+        .insert("last_ingested".to_string(), now.to_string());
+}
+
+/// Run one refresh pass at `now` (unix seconds), updating `state`.
+/// Returns the report and the delta proposals, in plan order.
+pub async fn refresh_pass(
+    sources: &[(IngestJob, Option<RefreshPolicy>)],
+    state: &mut FreshnessStateV1,
+    now: u64,
+    force: bool,
+    parallelism: usize,
+) -> Result<(RefreshReport, Vec<ProposalV1>)> {
+    let mut report = RefreshReport {
+        checked_at: now,
+        sources: Vec::new(),
+        stale: Vec::new(),
+    };
+    let mut to_ingest: Vec<(usize, String)> = Vec::new();
+
+    for (index, (job, policy)) in sources.iter().enumerate() {
+        let interval = policy.as_ref().map(|p| p.interval()).transpose()?;
+        let previous = state.sources.get(&job.name);
+        let due = force
+            || previous.is_none_or(|p| {
+                interval.is_none_or(|i| now >= p.last_checked.saturating_add(i.as_secs()))
+            });
+        let mut entry = SourceRefresh {
+            name: job.name.clone(),
+            status: RefreshStatus::NotDue,
+            version: previous.map(|p| p.version.clone()),
+            last_ingested: previous.map(|p| p.last_ingested),
+            next_due: None,
+            added: 0,
+            changed: 0,
+            removed: Vec::new(),
+            error: None,
+        };
+
+        let version = match fs::read(&job.path) {
+            Ok(bytes) => axiograph_dsl::digest::fnv1a64_digest_bytes(&bytes),
+            Err(e) => {
+                entry.status = RefreshStatus::Failed;
+                entry.error = Some(format!("failed to read {}: {e}", job.path.display()));
+                report.sources.push(entry);
+                continue;
+            }
+        };
+        let changed = previous.is_none_or(|p| p.version != version);
+        entry.status = match (due, changed) {
+            (true, true) => {
+                to_ingest.push((index, version));
+                RefreshStatus::Ingested
+            }
+            (true, false) => RefreshStatus::Unchanged,
+            (false, false) => RefreshStatus::NotDue,
+            (false, true) => RefreshStatus::Pending,
+        };
+        if due && !changed {
+            if let Some(p) = state.sources.get_mut(&job.name) {
+                p.last_checked = now;
+            }
+        }
+        report.sources.push(entry);
+    }
+
+    let jobs: Vec<IngestJob> = to_ingest
+        .iter()
+        .map(|(index, _)| sources[*index].0.clone())
+        .collect();
+    let outcomes = if jobs.is_empty() {
+        Vec::new()
+    } else {
+        let rx = ingest_run::spawn_ingest(jobs.clone(), parallelism);
+        ingest_run::collect_events(&jobs, rx).await
+    };
+
+    let mut delta = Vec::new();
+    for ((index, version), outcome) in to_ingest.into_iter().zip(outcomes) {
+        let job = &sources[index].0;
+        let entry = &mut report.sources[index];
+        let output = match outcome {
+            Ok(output) => output,
+            Err(e) => {
+                entry.status = RefreshStatus::Failed;
+                entry.error = Some(e);
+                continue;
+            }
+        };
+        let previous = state
+            .sources
+            .get(&job.name)
+            .map(|p| p.proposals.clone())
+            .unwrap_or_default();
+        let mut proposals = BTreeMap::new();
+        for mut proposal in output.proposals {
+            let id = proposal_id(&proposal).to_string();
+            let digest = proposal_digest(&proposal);
+            match previous.get(&id) {
+                Some(old) if *old == digest => {}
+                old => {
+                    if old.is_some() {
+                        entry.changed += 1;
+                    } else {
+                        entry.added += 1;
+                    }
+                    tag_freshness(&mut proposal, &version, now);
+                    delta.push(proposal);
+                }
+            }
+            proposals.insert(id, digest);
+        }
+        entry.removed = previous
+            .keys()
+            .filter(|id| !proposals.contains_key(*id))
+            .cloned()
+            .collect();
+        entry.version = Some(version.clone());
+        entry.last_ingested = Some(now);
+        state.sources.insert(
+            job.name.clone(),
+            SourceFreshness {
+                path: job.path.clone(),
+                last_checked: now,
+                last_ingested: now,
+                version,
+                proposals,
+            },
+        );
+    }
+
+    for ((job, policy), entry) in sources.iter().zip(&mut report.sources) {
+        let Some(fresh) = state.sources.get(&job.name) else {
+            continue;
+        };
+        if let Some(policy) = policy {
+            entry.next_due = Some(fresh.last_checked + policy.interval()?.as_secs());
+        }
+        let reason = match entry.status {
+            RefreshStatus::Pending => StaleReason::SourceChanged,
+            RefreshStatus::Failed if !job.path.exists() => StaleReason::SourceMissing,
+            RefreshStatus::Failed => StaleReason::IngestFailed,
+            _ => continue,
+        };
+        report
+            .stale
+            .extend(fresh.proposals.keys().map(|id| StaleFact {
+                proposal_id: id.clone(),
+                source: job.name.clone(),
+                reason,
+            }));
+    }
+
+    Ok((report, delta))
+}
+
+fn print_report(report: &RefreshReport) {
+    for s in &report.sources {
+        let status = match s.status {
+            RefreshStatus::Ingested => "ingested".green(),
+            RefreshStatus::Unchanged => "unchanged".dimmed(),
+            RefreshStatus::NotDue => "not due".dimmed(),
+            RefreshStatus::Pending => "pending".yellow(),
+            RefreshStatus::Failed => "failed".red(),
+        };
+        match s.status {
+            RefreshStatus::Ingested => println!(
+                "  {status} {} (+{} ~{} -{})",
+                s.name,
+                s.added,
+                s.changed,
+                s.removed.len()
+            ),
+            RefreshStatus::Failed => println!(
+                "  {status} {}: {}",
+                s.name,
+                s.error.as_deref().unwrap_or("unknown error")
+            ),
+            _ => println!("  {status} {}", s.name),
+        }
+    }
+    if !report.stale.is_empty() {
+        let sources: BTreeSet<&str> = report.stale.iter().map(|f| f.source.as_str()).collect();
+        println!(
+            "  {} {} fact(s) from {} source(s) are stale",
+            "warning:".yellow().bold(),
+            report.stale.len(),
+            sources.len()
+        );
+    }
+}
+
+fn write_pass(
+    args: &IngestRefreshArgs,
+    report: &RefreshReport,
+    delta: Vec<ProposalV1>,
+    state: &FreshnessStateV1,
+) -> Result<()> {
+    if delta.is_empty() {
+        println!("  {} no new or changed proposals", "→".cyan());
+    } else {
+        let file = axiograph_ingest_docs::ProposalsFileV1 {
+            version: axiograph_ingest_docs::PROPOSALS_VERSION_V1,
+            generated_at: report.checked_at.to_string(),
+            source: axiograph_ingest_docs::ProposalSourceV1 {
+                source_type: "ingest_refresh".to_string(),
+                locator: args.plan.display().to_string(),
+            },
+            schema_hint: None,
+            proposals: delta,
+        };
+        fs::create_dir_all(args.out.parent().unwrap_or(Path::new(".")))?;
+        fs::write(&args.out, serde_json::to_string_pretty(&file)?)?;
+        println!(
+            "  {} {} (delta proposals={})",
+            "→".cyan(),
+            args.out.display(),
+            file.proposals.len()
+        );
+    }
+    if let Some(path) = &args.report {
+        fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))?;
+        fs::write(path, serde_json::to_string_pretty(report)?)?;
+    }
+    fs::create_dir_all(args.state.parent().unwrap_or(Path::new(".")))?;
+    fs::write(&args.state, serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+pub fn cmd_ingest_refresh(args: &IngestRefreshArgs) -> Result<()> {
+    let plan = IngestPlanV1::load(&args.plan)?;
+    let parallelism = args
+        .parallelism
+        .or(plan.parallelism)
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(4, |n| n.get()));
+    let sources = plan.jobs();
+    if sources.is_empty() {
+        return Err(anyhow!("ingest refresh: the plan has no sources"));
+    }
+    if args.watch && sources.iter().all(|(_, policy)| policy.is_none()) {
+        return Err(anyhow!(
+            "ingest refresh --watch: no source has a `refresh` schedule"
+        ));
+    }
+    let mut state = FreshnessStateV1::load_or_default(&args.state)?;
+
+    let rt = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| anyhow!("failed to initialize tokio runtime: {e}"))?;
+    let mut force = args.force;
+    loop {
+        let now = unix_now();
+        println!(
+            "{} {} source(s)",
+            "Refreshing".green().bold(),
+            sources.len()
+        );
+        let (report, delta) =
+            rt.block_on(refresh_pass(&sources, &mut state, now, force, parallelism))?;
+        print_report(&report);
+        write_pass(args, &report, delta, &state)?;
+
+        if !args.watch {
+            return Ok(());
+        }
+        force = false;
+        // Unscheduled sources are due every pass; they ride along with the
+        // scheduled ones rather than forcing a busy loop.
+        let next = report.next_due().unwrap_or(now + 60).max(unix_now() + 1);
+        println!("  {} next check at {next}", "…".dimmed());
+        std::thread::sleep(Duration::from_secs(next.saturating_sub(unix_now())));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest_run::{IngestSourceKind, RetryPolicy};
+
+    fn job(name: &str, path: PathBuf) -> IngestJob {
+        IngestJob {
+            name: name.to_string(),
+            kind: IngestSourceKind::Sql,
+            path,
+            schema_hint: None,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    fn pass(
+        sources: &[(IngestJob, Option<RefreshPolicy>)],
+        state: &mut FreshnessStateV1,
+        now: u64,
+    ) -> (RefreshReport, Vec<ProposalV1>) {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(refresh_pass(sources, state, now, false, 2))
+            .unwrap()
+    }
+
+    #[test]
+    fn schedules_parse_cron_shorthands_and_intervals() {
+        assert_eq!(
+            parse_schedule("@daily").unwrap(),
+            Duration::from_secs(86_400)
+        );
+        assert_eq!(parse_schedule("6h").unwrap(), Duration::from_secs(21_600));
+        assert_eq!(parse_schedule("30m").unwrap(), Duration::from_secs(1_800));
+        assert!(parse_schedule("0h").is_err());
+        assert!(parse_schedule("6 hours").is_err());
+        assert!(parse_schedule("@often").is_err());
+    }
+
+    #[test]
+    fn refresh_emits_deltas_on_schedule_and_flags_stale_facts() {
+        let dir = std::env::temp_dir().join(format!(
+            "axiograph_ingest_refresh_{}_{}",
+            std::process::id(),
+            unix_now()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let users = dir.join("users.sql");
+        let orders = dir.join("orders.sql");
+        fs::write(&users, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+        fs::write(&orders, "CREATE TABLE orders (id INT PRIMARY KEY);").unwrap();
+        let hourly = Some(RefreshPolicy {
+            every: "1h".to_string(),
+        });
+        let sources = vec![
+            (job("users", users.clone()), hourly.clone()),
+            (job("orders", orders.clone()), None),
+        ];
+        let mut state = FreshnessStateV1::default();
+
+        // First pass ingests everything.
+        let (report, delta) = pass(&sources, &mut state, 1_000);
+        assert!(report
+            .sources
+            .iter()
+            .all(|s| s.status == RefreshStatus::Ingested));
+        assert_eq!(report.next_due(), Some(4_600));
+        assert!(report.stale.is_empty());
+        let first = delta.len();
+        assert!(first > 0);
+        let (ProposalV1::Entity { meta, .. } | ProposalV1::Relation { meta, .. }) = &delta[0];
+        assert_eq!(meta.metadata.get("last_ingested").unwrap(), "1000");
+        assert!(meta.metadata.contains_key("source_version"));
+
+        // Nothing changed: nothing to emit.
+        let (report, delta) = pass(&sources, &mut state, 1_100);
+        assert!(delta.is_empty());
+        assert_eq!(report.sources[0].status, RefreshStatus::NotDue);
+        assert_eq!(report.sources[1].status, RefreshStatus::Unchanged);
+
+        // Both change; only the unscheduled source is due, so users' facts go stale.
+        fs::write(
+            &users,
+            "CREATE TABLE users (id INT PRIMARY KEY, email TEXT);",
+        )
+        .unwrap();
+        fs::write(&orders, "CREATE TABLE invoices (id INT PRIMARY KEY);").unwrap();
+        let (report, delta) = pass(&sources, &mut state, 1_200);
+        assert_eq!(report.sources[0].status, RefreshStatus::Pending);
+        assert_eq!(report.sources[1].status, RefreshStatus::Ingested);
+        assert!(!report.sources[1].removed.is_empty());
+        assert!(report.sources[1].added > 0);
+        assert!(!delta.is_empty());
+        assert!(!report.stale.is_empty());
+        assert!(report
+            .stale
+            .iter()
+            .all(|f| f.source == "users" && f.reason == StaleReason::SourceChanged));
+
+        // Once due, users is re-ingested and only its new/changed facts are emitted.
+        let (report, delta) = pass(&sources, &mut state, 4_600);
+        assert_eq!(report.sources[0].status, RefreshStatus::Ingested);
+        assert!(report.stale.is_empty());
+        assert!(!delta.is_empty());
+        assert_eq!(
+            delta.len(),
+            report.sources[0].added + report.sources[0].changed
+        );
+        assert_eq!(state.sources["users"].last_ingested, 4_600);
+
+        // A vanished source flags its facts.
+        fs::remove_file(&orders).unwrap();
+        let (report, _) = pass(&sources, &mut state, 4_700);
+        assert_eq!(report.sources[1].status, RefreshStatus::Failed);
+        assert!(report
+            .stale
+            .iter()
+            .all(|f| f.source == "orders" && f.reason == StaleReason::SourceMissing));
+        assert!(!report.stale.is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,1119 @@
+//! Concurrent ingestion orchestrator (`axiograph ingest run`).
+//!
+//! The single-source `ingest <kind>` commands run one ingester synchronously.
+//! This module runs many sources at once:
+//!
+//! - bounded parallelism (a tokio semaphore; ingesters themselves are blocking
+//!   and run on the blocking pool),
+//! - a per-source [`RetryPolicy`] with exponential backoff,
+//! - progress reporting via a stream of [`IngestEvent`]s,
+//! - a combined `proposals.json` (+ `chunks.json`) built from that stream, and
+//! - optionally (`--storage`), each source's proposals applied to unified
+//!   storage as it completes, through a bounded
+//!   [`IngestBridge`](axiograph_storage::IngestBridge).
+//!
+//! The merged output is deterministic: per-source results are concatenated in
+//! plan order (not completion order) and deduplicated by stable id.
+//!
+//! Sources come from `--source kind=path` flags and/or a JSON plan file:
+//!
+//! ```json
+//! {
+//!   "version": "ingest_plan_v1",
+//!   "parallelism": 4,
+//!   "retry": { "max_attempts": 2, "backoff_ms": 250 },
+//!   "sources": [
+//!     { "kind": "sql", "path": "schema.sql" },
+//!     { "kind": "rdf", "path": "onto.ttl", "schema_hint": "onto",
+//!       "retry": { "max_attempts": 3, "backoff_ms": 500 } }
+//!   ]
+//! }
+//! ```
+
+use anyhow::{anyhow, Context, Result};
+use axiograph_ingest_docs::{Chunk, ProposalV1};
+use axiograph_storage::{ChangeSource, IngestBridge, IngestBridgeConfig, IngestBridgeSender};
+use clap::Args;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{mpsc, Semaphore};
+
+use crate::ingest_refresh::RefreshPolicy;
+
+pub const INGEST_PLAN_VERSION_V1: &str = "ingest_plan_v1";
+
+#[derive(Args, Debug, Clone)]
+pub struct IngestRunArgs {
+    /// Ingestion plan JSON (`ingest_plan_v1`); paths are relative to the plan file.
+    #[arg(long)]
+    pub plan: Option<PathBuf>,
+
+    /// Extra source as `kind=path` (kinds: sql, doc, json, rdf, proto, proposals). Repeatable.
+    #[arg(long = "source")]
+    pub sources: Vec<String>,
+
+    /// Output merged proposals JSON (Evidence/Proposals schema).
+    #[arg(short, long)]
+    pub out: PathBuf,
+
+    /// Output merged chunks JSON (default: `chunks.json` next to `--out`).
+    #[arg(long)]
+    pub chunks: Option<PathBuf>,
+
+    /// Maximum number of sources ingested concurrently (overrides the plan).
+    #[arg(short = 'j', long)]
+    pub parallelism: Option<usize>,
+
+    /// Default attempts per source (overrides the plan default; per-source policies win).
+    #[arg(long)]
+    pub max_attempts: Option<u32>,
+
+    /// Default initial retry backoff in milliseconds (doubles per attempt).
+    #[arg(long)]
+    pub backoff_ms: Option<u64>,
+
+    /// Schema hint recorded in the merged proposals file.
+    #[arg(long)]
+    pub schema_hint: Option<String>,
+
+    /// Write outputs even if some sources failed (failures are reported as warnings).
+    #[arg(long)]
+    pub allow_partial: bool,
+
+    /// Ingest and report what would be written (types, counts, dangling
+    /// relations) without writing `--out` or `--chunks`.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Also apply proposals to the unified storage in this knowledge
+    /// directory as each source completes (bounded queue with backpressure;
+    /// everything queued is stored before the command exits). Relations
+    /// resolve against entities streamed earlier or already stored.
+    #[arg(long)]
+    pub storage: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestSourceKind {
+    /// SQL DDL.
+    Sql,
+    /// Markdown / plain-text document.
+    Doc,
+    /// Arbitrary JSON (schema inference).
+    Json,
+    /// RDF/OWL (format chosen by file extension).
+    Rdf,
+    /// Buf descriptor set JSON.
+    Proto,
+    /// An existing `proposals.json` or NDJSON file (strictly validated pass-through).
+    Proposals,
+}
+
+impl IngestSourceKind {
+    fn parse(s: &str) -> Result<Self> {
+        Ok(match s.trim().to_ascii_lowercase().as_str() {
+            "sql" => Self::Sql,
+            "doc" | "md" | "txt" => Self::Doc,
+            "json" => Self::Json,
+            "rdf" | "owl" => Self::Rdf,
+            "proto" => Self::Proto,
+            "proposals" => Self::Proposals,
+            other => return Err(anyhow!("unknown ingest source kind `{other}`")),
+        })
+    }
+}
+
+/// How often (and how patiently) to retry a failing source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first (`1` = no retries).
+    #[serde(default = "RetryPolicy::default_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on each subsequent retry.
+    #[serde(default)]
+    pub backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: Self::default_max_attempts(),
+            backoff_ms: 0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn default_max_attempts() -> u32 {
+        1
+    }
+
+    /// Delay before attempt `next_attempt` (2-based: the first retry).
+    fn delay_before(&self, next_attempt: u32) -> Duration {
+        let exp = next_attempt.saturating_sub(2).min(16);
+        Duration::from_millis(self.backoff_ms.saturating_mul(1u64 << exp))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestSourceSpecV1 {
+    /// Display name (default: the path).
+    #[serde(default)]
+    pub name: Option<String>,
+    pub kind: IngestSourceKind,
+    pub path: PathBuf,
+    /// Schema hint / domain passed to the ingester.
+    #[serde(default)]
+    pub schema_hint: Option<String>,
+    /// Overrides the plan-level retry policy.
+    #[serde(default)]
+    pub retry: Option<RetryPolicy>,
+    /// Re-ingestion schedule for `ingest refresh` (overrides the plan-level one).
+    #[serde(default)]
+    pub refresh: Option<RefreshPolicy>,
+}
+
+impl IngestSourceSpecV1 {
+    fn into_job(self, default_retry: RetryPolicy) -> IngestJob {
+        IngestJob {
+            name: self.name.unwrap_or_else(|| self.path.display().to_string()),
+            kind: self.kind,
+            path: self.path,
+            schema_hint: self.schema_hint,
+            retry: self.retry.unwrap_or(default_retry),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestPlanV1 {
+    pub version: String,
+    #[serde(default)]
+    pub parallelism: Option<usize>,
+    /// Default retry policy for sources without their own.
+    #[serde(default)]
+    pub retry: Option<RetryPolicy>,
+    #[serde(default)]
+    pub schema_hint: Option<String>,
+    /// Default re-ingestion schedule for sources without their own.
+    #[serde(default)]
+    pub refresh: Option<RefreshPolicy>,
+    pub sources: Vec<IngestSourceSpecV1>,
+}
+
+impl IngestPlanV1 {
+    /// Read and check a plan file, resolving source paths against its directory.
+    pub fn load(plan_path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(plan_path)
+            .with_context(|| format!("failed to read plan {}", plan_path.display()))?;
+        let mut plan: IngestPlanV1 = serde_json::from_str(&text)
+            .with_context(|| format!("failed to parse plan {}", plan_path.display()))?;
+        if plan.version != INGEST_PLAN_VERSION_V1 {
+            return Err(anyhow!(
+                "unsupported ingest plan version `{}` (expected `{INGEST_PLAN_VERSION_V1}`)",
+                plan.version
+            ));
+        }
+        let base = plan_path.parent().unwrap_or(Path::new("."));
+        for source in &mut plan.sources {
+            if source.path.is_relative() {
+                source.path = base.join(&source.path);
+            }
+        }
+        Ok(plan)
+    }
+
+    /// The plan's sources as jobs, each paired with its refresh schedule.
+    pub fn jobs(self) -> Vec<(IngestJob, Option<RefreshPolicy>)> {
+        let retry = self.retry.unwrap_or_default();
+        let refresh = self.refresh;
+        self.sources
+            .into_iter()
+            .map(|s| {
+                let policy = s.refresh.clone().or_else(|| refresh.clone());
+                (s.into_job(retry), policy)
+            })
+            .collect()
+    }
+}
+
+/// One resolved unit of work.
+#[derive(Debug, Clone)]
+pub struct IngestJob {
+    pub name: String,
+    pub kind: IngestSourceKind,
+    pub path: PathBuf,
+    pub schema_hint: Option<String>,
+    pub retry: RetryPolicy,
+}
+
+/// Proposals + evidence chunks produced by one source.
+#[derive(Debug, Clone, Default)]
+pub struct SourceOutput {
+    pub proposals: Vec<ProposalV1>,
+    pub chunks: Vec<Chunk>,
+}
+
+/// Progress / output events, in completion order. `index` is the job's
+/// position in the input list.
+#[derive(Debug)]
+pub enum IngestEvent {
+    Started {
+        index: usize,
+        attempt: u32,
+    },
+    Retrying {
+        index: usize,
+        attempt: u32,
+        error: String,
+        delay: Duration,
+    },
+    Completed {
+        index: usize,
+        output: SourceOutput,
+        attempts: u32,
+        elapsed: Duration,
+    },
+    Failed {
+        index: usize,
+        error: String,
+        attempts: u32,
+    },
+}
+
+/// Start ingesting `jobs` with at most `parallelism` running at once and
+/// return the event stream. The stream closes once every job has completed or
+/// failed. Must be called from within a tokio runtime.
+pub fn spawn_ingest(jobs: Vec<IngestJob>, parallelism: usize) -> mpsc::Receiver<IngestEvent> {
+    let (tx, rx) = mpsc::channel(jobs.len().max(1) * 2);
+    let semaphore = Arc::new(Semaphore::new(parallelism.max(1)));
+    for (index, job) in jobs.into_iter().enumerate() {
+        let tx = tx.clone();
+        let semaphore = semaphore.clone();
+        tokio::spawn(async move {
+            let Ok(_permit) = semaphore.acquire_owned().await else {
+                return;
+            };
+            run_job(index, job, tx).await;
+        });
+    }
+    rx
+}
+
+async fn run_job(index: usize, job: IngestJob, tx: mpsc::Sender<IngestEvent>) {
+    let started = Instant::now();
+    let max_attempts = job.retry.max_attempts.max(1);
+    let job = Arc::new(job);
+    let mut attempt = 1;
+    loop {
+        let _ = tx.send(IngestEvent::Started { index, attempt }).await;
+        let task_job = job.clone();
+        let result = tokio::task::spawn_blocking(move || ingest_source(&task_job))
+            .await
+            .map_err(|e| anyhow!("ingester panicked: {e}"))
+            .and_then(|r| r);
+        match result {
+            Ok(output) => {
+                let _ = tx
+                    .send(IngestEvent::Completed {
+                        index,
+                        output,
+                        attempts: attempt,
+                        elapsed: started.elapsed(),
+                    })
+                    .await;
+                return;
+            }
+            Err(e) if attempt < max_attempts => {
+                let delay = job.retry.delay_before(attempt + 1);
+                let _ = tx
+                    .send(IngestEvent::Retrying {
+                        index,
+                        attempt,
+                        error: format!("{e:#}"),
+                        delay,
+                    })
+                    .await;
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                let _ = tx
+                    .send(IngestEvent::Failed {
+                        index,
+                        error: format!("{e:#}"),
+                        attempts: attempt,
+                    })
+                    .await;
+                return;
+            }
+        }
+    }
+}
+
+/// Run a single source synchronously.
+pub fn ingest_source(job: &IngestJob) -> Result<SourceOutput> {
+    let _span = tracing::info_span!("ingest.source", name = %job.name, kind = ?job.kind).entered();
+    let locator = job.path.to_string_lossy().to_string();
+    match job.kind {
+        IngestSourceKind::Sql => {
+            let text = fs::read_to_string(&job.path)
+                .with_context(|| format!("failed to read {}", job.path.display()))?;
+            let chunks = sql_statement_chunks(&text, &locator);
+            let schema = axiograph_ingest_sql::parse_sql_ddl(&text)?;
+            let proposals = crate::proposals_from_sql_schema(&schema, Some(locator), &chunks);
+            Ok(SourceOutput { proposals, chunks })
+        }
+        IngestSourceKind::Doc => {
+            let text = fs::read_to_string(&job.path)
+                .with_context(|| format!("failed to read {}", job.path.display()))?;
+            let stem = job.path.file_stem().unwrap_or_default().to_string_lossy();
+            let domain = job.schema_hint.as_deref().unwrap_or("general");
+            let result = axiograph_ingest_docs::extract_knowledge_full(&text, &stem, domain);
+            let proposals = axiograph_ingest_docs::proposals_from_extracted_facts_v1(
+                &result.facts,
+                Some(locator),
+                Some(domain.to_string()),
+            );
+            Ok(SourceOutput {
+                proposals,
+                chunks: result.extraction.chunks,
+            })
+        }
+        IngestSourceKind::Json => {
+            let text = fs::read_to_string(&job.path)
+                .with_context(|| format!("failed to read {}", job.path.display()))?;
+            let value: serde_json::Value = serde_json::from_str(&text)
+                .with_context(|| format!("failed to parse JSON {}", job.path.display()))?;
+            let schema = axiograph_ingest_json::infer_schema(&value, "Root");
+            let pretty = serde_json::to_string_pretty(&value).unwrap_or(text);
+            let chunks = line_chunks(&pretty, &locator, "json");
+            let proposals = crate::proposals_from_json_schema(&schema, Some(locator), &chunks);
+            Ok(SourceOutput { proposals, chunks })
+        }
+        IngestSourceKind::Rdf => {
+            let proposals = axiograph_ingest_rdfowl::proposals_from_rdf_file_v1(
+                &job.path,
+                Some(locator.clone()),
+                job.schema_hint.clone(),
+            )?;
+            let chunks = match fs::read_to_string(&job.path) {
+                Ok(text) => line_chunks(&text, &locator, "rdf"),
+                Err(_) => Vec::new(),
+            };
+            Ok(SourceOutput { proposals, chunks })
+        }
+        IngestSourceKind::Proto => {
+            let text = fs::read_to_string(&job.path)
+                .with_context(|| format!("failed to read {}", job.path.display()))?;
+            let result = axiograph_ingest_proto::ingest_descriptor_set_json(
+                &text,
+                Some(locator),
+                job.schema_hint.clone(),
+            )?;
+            Ok(SourceOutput {
+                proposals: result.proposals,
+                chunks: result.chunks,
+            })
+        }
+        IngestSourceKind::Proposals => Ok(SourceOutput {
+            proposals: crate::load_proposals_strict(&job.path, false)?,
+            chunks: Vec::new(),
+        }),
+    }
+}
+
+fn sql_statement_chunks(text: &str, locator: &str) -> Vec<Chunk> {
+    let doc_digest = axiograph_dsl::digest::fnv1a64_digest_bytes(locator.as_bytes());
+    let mut chunks = Vec::new();
+    for (i, stmt) in text.split(';').enumerate() {
+        let stmt = stmt.trim();
+        if stmt.is_empty() {
+            continue;
+        }
+        let mut metadata = std::collections::HashMap::new();
+        metadata.insert("kind".to_string(), "sql_ddl".to_string());
+        metadata.insert("source_path".to_string(), locator.to_string());
+        chunks.push(Chunk {
+            chunk_id: format!("sql_{doc_digest}_{i}"),
+            document_id: locator.to_string(),
+            page: None,
+            span_id: format!("stmt_{i}"),
+            text: format!("{stmt};"),
+            bbox: None,
+            metadata,
+        });
+    }
+    chunks
+}
+
+/// Split `text` into ~2.5k-char line-aligned chunks (same scheme as `ingest dir`).
+fn line_chunks(text: &str, locator: &str, kind: &str) -> Vec<Chunk> {
+    let doc_digest = axiograph_dsl::digest::fnv1a64_digest_bytes(locator.as_bytes());
+    let mut parts: Vec<String> = Vec::new();
+    let mut cur = String::new();
+    for line in text.lines() {
+        let line = line.trim_end();
+        if cur.len().saturating_add(line.len() + 1) > 2_500 && !cur.is_empty() {
+            parts.push(std::mem::take(&mut cur));
+        }
+        if !cur.is_empty() {
+            cur.push('\n');
+        }
+        cur.push_str(line);
+    }
+    if !cur.trim().is_empty() {
+        parts.push(cur);
+    }
+
+    parts
+        .into_iter()
+        .enumerate()
+        .map(|(i, part)| {
+            let mut metadata = std::collections::HashMap::new();
+            metadata.insert("kind".to_string(), kind.to_string());
+            metadata.insert("source_path".to_string(), locator.to_string());
+            Chunk {
+                chunk_id: format!("{kind}_{doc_digest}_{i}"),
+                document_id: locator.to_string(),
+                page: None,
+                span_id: format!("part_{i}"),
+                text: part,
+                bbox: None,
+                metadata,
+            }
+        })
+        .collect()
+}
+
+fn resolve_jobs(args: &IngestRunArgs) -> Result<(Vec<IngestJob>, usize, Option<String>)> {
+    let mut plan_retry = None;
+    let mut plan_parallelism = None;
+    let mut plan_schema_hint = None;
+    let mut specs: Vec<IngestSourceSpecV1> = Vec::new();
+
+    if let Some(plan_path) = &args.plan {
+        let plan = IngestPlanV1::load(plan_path)?;
+        plan_retry = plan.retry;
+        plan_parallelism = plan.parallelism;
+        plan_schema_hint = plan.schema_hint;
+        specs.extend(plan.sources);
+    }
+
+    for raw in &args.sources {
+        let (kind, path) = raw
+            .split_once('=')
+            .ok_or_else(|| anyhow!("--source expects `kind=path`, got `{raw}`"))?;
+        specs.push(IngestSourceSpecV1 {
+            name: None,
+            kind: IngestSourceKind::parse(kind)?,
+            path: PathBuf::from(path),
+            schema_hint: None,
+            retry: None,
+            refresh: None,
+        });
+    }
+
+    if specs.is_empty() {
+        return Err(anyhow!(
+            "ingest run: no sources (pass --plan <plan.json> and/or --source kind=path)"
+        ));
+    }
+
+    let mut default_retry = plan_retry.unwrap_or_default();
+    if let Some(n) = args.max_attempts {
+        default_retry.max_attempts = n;
+    }
+    if let Some(ms) = args.backoff_ms {
+        default_retry.backoff_ms = ms;
+    }
+
+    let jobs = specs
+        .into_iter()
+        .map(|s| s.into_job(default_retry))
+        .collect();
+    let parallelism = args
+        .parallelism
+        .or(plan_parallelism)
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(4, |n| n.get()));
+    let schema_hint = args.schema_hint.clone().or(plan_schema_hint);
+    Ok((jobs, parallelism, schema_hint))
+}
+
+/// Stream each completed source's proposals into `bridge` before passing its
+/// event on. A full bridge stalls this task, and through the bounded event
+/// channel, the ingesters.
+fn forward_to_bridge(
+    mut rx: mpsc::Receiver<IngestEvent>,
+    bridge: IngestBridgeSender,
+) -> mpsc::Receiver<IngestEvent> {
+    let (tx, forwarded) = mpsc::channel(1);
+    tokio::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            if let IngestEvent::Completed { output, .. } = &event {
+                for proposal in &output.proposals {
+                    // A stopped writer reports its error from `shutdown`.
+                    if bridge.send(proposal.clone()).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            if tx.send(event).await.is_err() {
+                return;
+            }
+        }
+    });
+    forwarded
+}
+
+/// Drain the event stream, print progress, and collect per-job outcomes in
+/// input order.
+pub(crate) async fn collect_events(
+    jobs: &[IngestJob],
+    mut rx: mpsc::Receiver<IngestEvent>,
+) -> Vec<Result<SourceOutput, String>> {
+    let total = jobs.len();
+    let mut outcomes: Vec<Option<Result<SourceOutput, String>>> = vec![None; total];
+    let mut done = 0usize;
+    while let Some(event) = rx.recv().await {
+        match event {
+            IngestEvent::Started { index, attempt } => {
+                if attempt == 1 {
+                    println!("  {} {}", "start".dimmed(), jobs[index].name);
+                }
+            }
+            IngestEvent::Retrying {
+                index,
+                attempt,
+                error,
+                delay,
+            } => {
+                println!(
+                    "  {} {} (attempt {attempt} failed: {error}; retrying in {}ms)",
+                    "retry".yellow(),
+                    jobs[index].name,
+                    delay.as_millis()
+                );
+            }
+            IngestEvent::Completed {
+                index,
+                output,
+                attempts,
+                elapsed,
+            } => {
+                done += 1;
+                println!(
+                    "  [{done}/{total}] {} {} proposals={} chunks={} attempts={attempts} ({}ms)",
+                    "ok".green(),
+                    jobs[index].name,
+                    output.proposals.len(),
+                    output.chunks.len(),
+                    elapsed.as_millis()
+                );
+                outcomes[index] = Some(Ok(output));
+            }
+            IngestEvent::Failed {
+                index,
+                error,
+                attempts,
+            } => {
+                done += 1;
+                println!(
+                    "  [{done}/{total}] {} {} after {attempts} attempt(s): {error}",
+                    "failed".red(),
+                    jobs[index].name
+                );
+                outcomes[index] = Some(Err(error));
+            }
+        }
+    }
+    outcomes
+        .into_iter()
+        .map(|o| o.unwrap_or_else(|| Err("ingest task did not report".to_string())))
+        .collect()
+}
+
+/// Concatenate outputs in input order, keeping the first proposal/chunk per id.
+pub fn merge_outputs(outputs: Vec<SourceOutput>) -> SourceOutput {
+    let mut seen_proposals: HashSet<String> = HashSet::new();
+    let mut seen_chunks: HashSet<String> = HashSet::new();
+    let mut merged = SourceOutput::default();
+    for output in outputs {
+        for p in output.proposals {
+            let id = match &p {
+                ProposalV1::Entity { meta, .. } | ProposalV1::Relation { meta, .. } => {
+                    meta.proposal_id.clone()
+                }
+            };
+            if seen_proposals.insert(id) {
+                merged.proposals.push(p);
+            }
+        }
+        for c in output.chunks {
+            if seen_chunks.insert(c.chunk_id.clone()) {
+                merged.chunks.push(c);
+            }
+        }
+    }
+    merged
+}
+
+/// What a merged run would contribute (`ingest run --dry-run`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct IngestDryRunReport {
+    /// Entity proposals per entity type.
+    pub entity_types: BTreeMap<String, usize>,
+    /// Relation proposals per relation type.
+    pub relation_types: BTreeMap<String, usize>,
+    pub chunks: usize,
+    /// Ids of relation proposals whose source or target is not an entity
+    /// proposed by this run.
+    pub dangling_relations: Vec<String>,
+}
+
+pub fn dry_run_report(merged: &SourceOutput) -> IngestDryRunReport {
+    let mut report = IngestDryRunReport {
+        chunks: merged.chunks.len(),
+        ..Default::default()
+    };
+    let mut entity_ids: HashSet<&str> = HashSet::new();
+    for proposal in &merged.proposals {
+        if let ProposalV1::Entity {
+            entity_id,
+            entity_type,
+            ..
+        } = proposal
+        {
+            entity_ids.insert(entity_id);
+            *report.entity_types.entry(entity_type.clone()).or_default() += 1;
+        }
+    }
+    for proposal in &merged.proposals {
+        if let ProposalV1::Relation {
+            relation_id,
+            rel_type,
+            source,
+            target,
+            ..
+        } = proposal
+        {
+            *report.relation_types.entry(rel_type.clone()).or_default() += 1;
+            if !entity_ids.contains(source.as_str()) || !entity_ids.contains(target.as_str()) {
+                report.dangling_relations.push(relation_id.clone());
+            }
+        }
+    }
+    report
+}
+
+fn print_dry_run(report: &IngestDryRunReport, out: &Path, chunks_out: &Path) {
+    println!("{}", "Dry run: nothing written".yellow().bold());
+    println!(
+        "  {} would write {} (entities={}, relations={})",
+        "→".cyan(),
+        out.display(),
+        report.entity_types.values().sum::<usize>(),
+        report.relation_types.values().sum::<usize>()
+    );
+    println!(
+        "  {} would write {} (chunks={})",
+        "→".cyan(),
+        chunks_out.display(),
+        report.chunks
+    );
+    for (ty, n) in &report.entity_types {
+        println!("    entity   {ty}: {n}");
+    }
+    for (ty, n) in &report.relation_types {
+        println!("    relation {ty}: {n}");
+    }
+    if !report.dangling_relations.is_empty() {
+        println!(
+            "  {} {} relation(s) reference entities not proposed by this run",
+            "warning:".yellow().bold(),
+            report.dangling_relations.len()
+        );
+    }
+}
+
+pub fn cmd_ingest_run(args: &IngestRunArgs) -> Result<()> {
+    let (jobs, parallelism, schema_hint) = resolve_jobs(args)?;
+    println!(
+        "{} {} source(s), parallelism={parallelism}",
+        "Ingesting".green().bold(),
+        jobs.len()
+    );
+
+    let rt = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| anyhow!("failed to initialize tokio runtime: {e}"))?;
+    let storage = match &args.storage {
+        Some(dir) if !args.dry_run => Some(Arc::new(axiograph_storage::open_storage(
+            &dir.display().to_string(),
+        )?)),
+        _ => None,
+    };
+    let (outcomes, stored) = rt.block_on(async {
+        let rx = spawn_ingest(jobs.clone(), parallelism);
+        let Some(storage) = storage else {
+            return Ok::<_, anyhow::Error>((collect_events(&jobs, rx).await, None));
+        };
+        let bridge = IngestBridge::spawn(
+            storage,
+            IngestBridgeConfig {
+                source: ChangeSource::System {
+                    reason: "ingest run".to_string(),
+                },
+                ..Default::default()
+            },
+        );
+        let rx = forward_to_bridge(rx, bridge.sender());
+        let outcomes = collect_events(&jobs, rx).await;
+        Ok((outcomes, Some(bridge.shutdown().await?)))
+    })?;
+    if let (Some(report), Some(dir)) = (&stored, &args.storage) {
+        println!(
+            "  {} storage {} (accepted={} rejected={} changes={})",
+            "→".cyan(),
+            dir.display(),
+            report.accepted,
+            report.rejected,
+            report.batches.len()
+        );
+        for error in &report.errors {
+            println!(
+                "  {} storage rejected {}: {}",
+                "warning:".yellow().bold(),
+                error.proposal_id.as_deref().unwrap_or("proposal"),
+                error.message
+            );
+        }
+    }
+
+    let mut outputs = Vec::new();
+    let mut failed = Vec::new();
+    for (job, outcome) in jobs.iter().zip(outcomes) {
+        match outcome {
+            Ok(output) => outputs.push(output),
+            Err(e) => failed.push(format!("{}: {e}", job.name)),
+        }
+    }
+    if !failed.is_empty() && !args.allow_partial {
+        return Err(anyhow!(
+            "ingest run: {} source(s) failed (use --allow-partial to write the rest):\n  {}",
+            failed.len(),
+            failed.join("\n  ")
+        ));
+    }
+
+    let merged = merge_outputs(outputs);
+    let out_dir = args.out.parent().unwrap_or(Path::new("."));
+    let chunks_out = args
+        .chunks
+        .clone()
+        .unwrap_or_else(|| out_dir.join("chunks.json"));
+    if args.dry_run {
+        print_dry_run(&dry_run_report(&merged), &args.out, &chunks_out);
+        for f in &failed {
+            println!("  {} {f}", "warning:".yellow().bold());
+        }
+        return Ok(());
+    }
+
+    let generated_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        .to_string();
+    let locator = args
+        .plan
+        .as_ref()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| {
+            jobs.iter()
+                .map(|j| j.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        });
+    let file = axiograph_ingest_docs::ProposalsFileV1 {
+        version: axiograph_ingest_docs::PROPOSALS_VERSION_V1,
+        generated_at,
+        source: axiograph_ingest_docs::ProposalSourceV1 {
+            source_type: "ingest_run".to_string(),
+            locator,
+        },
+        schema_hint,
+        proposals: merged.proposals,
+    };
+
+    fs::create_dir_all(out_dir)?;
+    fs::write(&args.out, serde_json::to_string_pretty(&file)?)?;
+    println!(
+        "  {} {} (proposals={})",
+        "→".cyan(),
+        args.out.display(),
+        file.proposals.len()
+    );
+
+    fs::create_dir_all(chunks_out.parent().unwrap_or(Path::new(".")))?;
+    fs::write(&chunks_out, serde_json::to_string_pretty(&merged.chunks)?)?;
+    println!(
+        "  {} {} (chunks={})",
+        "→".cyan(),
+        chunks_out.display(),
+        merged.chunks.len()
+    );
+
+    for f in &failed {
+        println!("  {} {f}", "warning:".yellow().bold());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempDirGuard {
+        path: PathBuf,
+    }
+
+    impl TempDirGuard {
+        fn new(prefix: &str) -> Self {
+            let ts = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            let path = std::env::temp_dir().join(format!("{prefix}_{}_{ts}", std::process::id()));
+            fs::create_dir_all(&path).expect("create temp dir");
+            Self { path }
+        }
+    }
+
+    impl Drop for TempDirGuard {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+
+    fn job(kind: IngestSourceKind, path: PathBuf, retry: RetryPolicy) -> IngestJob {
+        IngestJob {
+            name: path.display().to_string(),
+            kind,
+            path,
+            schema_hint: None,
+            retry,
+        }
+    }
+
+    #[test]
+    fn runs_sources_concurrently_and_retries_failures() {
+        let tmp = TempDirGuard::new("axiograph_ingest_run");
+        let sql = tmp.path.join("schema.sql");
+        fs::write(
+            &sql,
+            "CREATE TABLE users (id INT PRIMARY KEY, name TEXT);\n\
+             CREATE TABLE orders (id INT PRIMARY KEY, user_id INT REFERENCES users(id));",
+        )
+        .unwrap();
+        let json = tmp.path.join("data.json");
+        fs::write(&json, r#"{"user": {"id": 1, "name": "a"}}"#).unwrap();
+        let missing = tmp.path.join("missing.sql");
+
+        let retry = RetryPolicy {
+            max_attempts: 3,
+            backoff_ms: 1,
+        };
+        let jobs = vec![
+            job(IngestSourceKind::Sql, sql, RetryPolicy::default()),
+            job(IngestSourceKind::Sql, missing, retry),
+            job(IngestSourceKind::Json, json, RetryPolicy::default()),
+        ];
+
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let events: Vec<IngestEvent> = rt.block_on(async {
+            let mut rx = spawn_ingest(jobs, 2);
+            let mut events = Vec::new();
+            while let Some(e) = rx.recv().await {
+                events.push(e);
+            }
+            events
+        });
+
+        let retries = events
+            .iter()
+            .filter(|e| matches!(e, IngestEvent::Retrying { index: 1, .. }))
+            .count();
+        assert_eq!(retries, 2);
+        assert!(events.iter().any(|e| matches!(
+            e,
+            IngestEvent::Failed {
+                index: 1,
+                attempts: 3,
+                ..
+            }
+        )));
+
+        let mut completed: Vec<(usize, SourceOutput)> = events
+            .into_iter()
+            .filter_map(|e| match e {
+                IngestEvent::Completed { index, output, .. } => Some((index, output)),
+                _ => None,
+            })
+            .collect();
+        completed.sort_by_key(|(i, _)| *i);
+        assert_eq!(
+            completed.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        assert!(completed.iter().all(|(_, o)| !o.proposals.is_empty()));
+
+        let outputs: Vec<SourceOutput> = completed.into_iter().map(|(_, o)| o).collect();
+        let n: usize = outputs.iter().map(|o| o.proposals.len()).sum();
+        let twice = merge_outputs(vec![outputs[0].clone(), outputs[0].clone()]);
+        assert_eq!(twice.proposals.len(), outputs[0].proposals.len());
+        assert_eq!(merge_outputs(outputs).proposals.len(), n);
+    }
+
+    #[test]
+    fn retry_backoff_doubles() {
+        let policy = RetryPolicy {
+            max_attempts: 4,
+            backoff_ms: 100,
+        };
+        assert_eq!(policy.delay_before(2), Duration::from_millis(100));
+        assert_eq!(policy.delay_before(3), Duration::from_millis(200));
+        assert_eq!(policy.delay_before(4), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn plan_paths_resolve_relative_to_plan_file() {
+        let tmp = TempDirGuard::new("axiograph_ingest_plan");
+        let plan = tmp.path.join("plan.json");
+        fs::write(
+            &plan,
+            r#"{
+              "version": "ingest_plan_v1",
+              "parallelism": 3,
+              "retry": { "max_attempts": 2 },
+              "sources": [
+                { "kind": "sql", "path": "a.sql" },
+                { "kind": "rdf", "path": "b.ttl", "retry": { "max_attempts": 5, "backoff_ms": 10 } }
+              ]
+            }"#,
+        )
+        .unwrap();
+        let args = IngestRunArgs {
+            plan: Some(plan),
+            sources: vec!["json=c.json".to_string()],
+            out: tmp.path.join("out.json"),
+            chunks: None,
+            parallelism: None,
+            max_attempts: None,
+            backoff_ms: None,
+            schema_hint: None,
+            allow_partial: false,
+            dry_run: false,
+            storage: None,
+        };
+        let (jobs, parallelism, _) = resolve_jobs(&args).unwrap();
+        assert_eq!(parallelism, 3);
+        assert_eq!(jobs.len(), 3);
+        assert_eq!(jobs[0].path, tmp.path.join("a.sql"));
+        assert_eq!(jobs[0].retry.max_attempts, 2);
+        assert_eq!(jobs[1].retry.max_attempts, 5);
+        assert_eq!(jobs[2].kind, IngestSourceKind::Json);
+        assert_eq!(jobs[2].path, PathBuf::from("c.json"));
+    }
+
+    #[test]
+    fn storage_receives_proposals_through_the_bridge() {
+        let tmp = TempDirGuard::new("axiograph_ingest_storage");
+        let sql = tmp.path.join("schema.sql");
+        fs::write(
+            &sql,
+            "CREATE TABLE users (id INT PRIMARY KEY, name TEXT);\n\
+             CREATE TABLE orders (id INT PRIMARY KEY, user_id INT REFERENCES users(id));",
+        )
+        .unwrap();
+        let knowledge = tmp.path.join("knowledge");
+        fs::create_dir_all(&knowledge).unwrap();
+        let args = IngestRunArgs {
+            plan: None,
+            sources: vec![format!("sql={}", sql.display())],
+            out: tmp.path.join("out/proposals.json"),
+            chunks: None,
+            parallelism: None,
+            max_attempts: None,
+            backoff_ms: None,
+            schema_hint: None,
+            allow_partial: false,
+            dry_run: false,
+            storage: Some(knowledge.clone()),
+        };
+        cmd_ingest_run(&args).unwrap();
+
+        let (jobs, _, _) = resolve_jobs(&args).unwrap();
+        let proposals = ingest_source(&jobs[0]).unwrap().proposals;
+        let storage = axiograph_storage::open_storage(&knowledge.display().to_string()).unwrap();
+        let stored: usize = storage.changelog().iter().map(|c| c.facts.len()).sum();
+        assert!(stored > 0);
+        assert_eq!(stored, proposals.len());
+        assert!(knowledge.join("knowledge.axpd").exists());
+    }
+
+    #[test]
+    fn dry_run_reports_without_writing() {
+        let tmp = TempDirGuard::new("axiograph_ingest_dry_run");
+        let sql = tmp.path.join("schema.sql");
+        fs::write(
+            &sql,
+            "CREATE TABLE users (id INT PRIMARY KEY, name TEXT);\n\
+             CREATE TABLE orders (id INT PRIMARY KEY, user_id INT REFERENCES users(id));",
+        )
+        .unwrap();
+        let out = tmp.path.join("out/proposals.json");
+        let args = IngestRunArgs {
+            plan: None,
+            sources: vec![format!("sql={}", sql.display())],
+            out: out.clone(),
+            chunks: None,
+            parallelism: None,
+            max_attempts: None,
+            backoff_ms: None,
+            schema_hint: None,
+            allow_partial: false,
+            dry_run: true,
+            storage: None,
+        };
+        cmd_ingest_run(&args).unwrap();
+        assert!(!out.exists());
+        assert!(!tmp.path.join("out/chunks.json").exists());
+
+        let (jobs, _, _) = resolve_jobs(&args).unwrap();
+        let output = ingest_source(&jobs[0]).unwrap();
+        let report = dry_run_report(&output);
+        assert!(report.entity_types.values().sum::<usize>() > 0);
+        assert!(report.relation_types.values().sum::<usize>() > 0);
+        assert!(report.dangling_relations.is_empty());
+
+        let mut dangling = output.clone();
+        dangling
+            .proposals
+            .retain(|p| matches!(p, ProposalV1::Relation { .. }));
+        assert_eq!(
+            dry_run_report(&dangling).dangling_relations.len(),
+            report.relation_types.values().sum::<usize>()
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! `axiograph ingest sandbox`: check a new source's proposals against the
+//! `.axi` schemas before it goes live (see `axiograph_storage::sandbox`).
+//!
+//! Nothing is written to any snapshot; the command only reports novel entity
+//! and relation types, candidate mappings onto declared types, and coverage.
+
+use anyhow::{anyhow, Result};
+use axiograph_ingest_docs::ProposalsFileV1;
+use axiograph_storage::sandbox::{self, SandboxConfig, TypeCoverage};
+use axiograph_storage::AxiSchemaIndex;
+use clap::Args;
+use colored::Colorize;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Args, Debug, Clone)]
+pub struct SandboxArgs {
+    /// Input proposals JSON (Evidence/Proposals schema).
+    pub proposals: PathBuf,
+
+    /// Directory of `.axi` schemas to validate against.
+    #[arg(long)]
+    pub axi_dir: PathBuf,
+
+    /// Minimum name similarity for a candidate mapping.
+    #[arg(long, default_value_t = 0.75)]
+    pub min_similarity: f64,
+
+    /// Show at most this many novel types per section.
+    #[arg(long, default_value_t = 20)]
+    pub top: usize,
+
+    /// Also write the full report as JSON.
+    #[arg(short, long)]
+    pub out: Option<PathBuf>,
+}
+
+pub fn cmd_sandbox(args: &SandboxArgs) -> Result<()> {
+    let file: ProposalsFileV1 = serde_json::from_str(&fs::read_to_string(&args.proposals)?)
+        .map_err(|e| anyhow!("failed to parse {}: {e}", args.proposals.display()))?;
+    let schema = AxiSchemaIndex::load_dir(&args.axi_dir)?;
+    let config = SandboxConfig {
+        min_similarity: args.min_similarity,
+        ..Default::default()
+    };
+    let report = sandbox::validate_proposals(&file.proposals, &schema, &config);
+
+    println!(
+        "{} {} against {} (entity types={}, relation types={}, coverage={:.1}%)",
+        "Sandbox".green().bold(),
+        args.proposals.display(),
+        args.axi_dir.display(),
+        schema.entity_types.len(),
+        schema.relation_types.len(),
+        report.coverage_percent()
+    );
+    for (title, coverage) in [
+        ("Entity types", &report.entity_types),
+        ("Relation types", &report.relation_types),
+    ] {
+        print_coverage(title, coverage, args.top);
+    }
+
+    if let Some(path) = &args.out {
+        fs::write(path, serde_json::to_string_pretty(&report)?)?;
+        println!("  {} {}", "→".cyan(), path.display());
+    }
+    Ok(())
+}
+
+fn print_coverage(title: &str, coverage: &TypeCoverage, top: usize) {
+    println!(
+        "  {} proposals={} known={} coverage={:.1}%",
+        title.yellow(),
+        coverage.proposals,
+        coverage.known_proposals(),
+        coverage.coverage_percent()
+    );
+    for novel in coverage.novel.iter().take(top) {
+        let candidates = novel
+            .candidates
+            .iter()
+            .map(|c| format!("{} ({:.2})", c.schema_type, c.similarity))
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            println!("    {} (proposals={})", novel.name.red(), novel.proposals);
+        } else {
+            println!(
+                "    {} (proposals={}) → {}",
+                novel.name.red(),
+                novel.proposals,
+                candidates.join(", ")
+            );
+        }
+    }
+}
@@ -5329,7 +5329,7 @@ fn tool_snapshot_diff(store: Option<&ToolLoopStoreContext>, args: &serde_json::V
                 .map(|s| s.to_string())
                 .unwrap_or_else(|| "?".to_string());
             let target = db.get_entity(rel.target);
-            let target_name = target.as_ref().and_then(|v| v.attrs.get("name").cloned());
+            let target_name = target.as_ref().and_then(|v| v.label.clone());
             let target_type = target.as_ref().map(|v| v.entity_type.clone());
             outgoing.push(serde_json::json!({
                 "rel": rel_name,
@@ -6101,7 +6101,7 @@ pub(crate) fn describe_entity_v1(db: &PathDB, args: &serde_json::Value) -> Resul
         "entity": {
             "id": entity_id,
             "entity_type": view.entity_type,
-            "name": view.attrs.get("name").cloned(),
+            "name": view.label,
         },
         "summary": summary,
         "attrs": attrs,
@@ -6702,9 +6702,9 @@ fn build_entity_graph_text_for_token_hash(db: &PathDB, id: u32) -> Option<String
 
     let mut text = String::new();
     text.push_str(&view.entity_type);
-    if let Some(name) = view.attrs.get("name") {
+    if let Some(label) = &view.label {
         text.push(' ');
-        text.push_str(name);
+        text.push_str(label);
     }
     for k in ["search_text", "description", "comment", "iri"] {
         if let Some(v) = view.attrs.get(k) {
@@ -9565,7 +9565,7 @@ impl EntityViewV1 {
         Self {
             id,
             entity_type: Some(view.entity_type),
-            name: view.attrs.get("name").cloned(),
+            name: view.label,
         }
     }
 }
@@ -14,7 +14,7 @@ use std::collections::BTreeSet;
 use std::env;
 use std::fs;
 use std::io::{self, Read};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
@@ -22,13 +22,21 @@ mod accepted_plane;
 mod analyze;
 mod axi_fmt;
 mod axql;
+mod calibration;
+mod cert_revalidate;
 mod competency_questions;
 mod db_server;
 mod doc_chunks;
 mod embeddings;
+mod gaps;
 mod github;
+mod ingest_provenance;
+mod ingest_refresh;
+mod ingest_run;
+mod ingest_sandbox;
 mod llm;
 mod nlq;
+mod ontology_docs;
 mod pathdb_wal;
 mod perf;
 mod profiling;
@@ -38,12 +46,16 @@ mod proposals_validate;
 mod proto;
 mod query_ir;
 mod quality;
+mod quarantine;
 mod relation_resolution;
 mod repl;
+mod replication;
+mod reports;
 mod schema_discovery;
 mod sqlish;
 mod store_sync;
 mod synthetic_pathdb;
+mod verify;
 mod viz;
 mod web;
 mod world_model;
@@ -84,6 +96,17 @@ enum Commands {
         command: CheckCommands,
     },
 
+    /// Format `.axi` files (comment-preserving; `--check` for CI).
+    Fmt(axi_fmt::FmtArgs),
+
+    /// Lint `.axi` files: unknown types, duplicate names, unused objects and
+    /// suspicious constraints. Exits non-zero on errors.
+    Lint(axi_fmt::LintArgs),
+
+    /// Semantic diff of two `.axi` modules: added/removed/changed
+    /// declarations and renamed objects, independent of ordering and layout.
+    Diff(axi_fmt::DiffArgs),
+
     /// Emit certificates (Rust computes, Lean verifies).
     ///
     /// Certificates are untrusted proof objects emitted by the Rust engine
@@ -308,6 +331,10 @@ enum Commands {
         /// Do not echo commands while running a script / `--cmd`.
         #[arg(long)]
         quiet: bool,
+        #[command(flatten)]
+        access: AccessArgs,
+        #[command(flatten)]
+        audit: AuditArgs,
     },
 
     /// Run an AxQL/SQL-ish query over a `PathDBExportV1` `.axi` snapshot and emit a certificate.
@@ -348,6 +375,8 @@ enum Commands {
         /// currently anchors query-result certificates to snapshot exports).
         #[arg(long)]
         anchor_out: Option<PathBuf>,
+        #[command(flatten)]
+        audit: AuditArgs,
     },
 
     /// Typecheck a canonical `.axi` module and emit an `axi_well_typed_v1` certificate.
@@ -432,14 +461,24 @@ enum Commands {
 #[derive(Subcommand)]
 enum CheckCommands {
     /// Validate a canonical `.axi` module (parse + typecheck).
+    ///
+    /// Modules with `import` lines are resolved as a module graph (imports
+    /// next to the input, then under `--path`), checked for cycles and schema
+    /// visibility, and the combined module is typechecked.
     Validate {
         /// Input `.axi` file.
         input: PathBuf,
+        /// Extra directories to search for imported `<Module>.axi` files.
+        #[arg(short = 'I', long = "path")]
+        path: Vec<PathBuf>,
+        /// Write the combined (import-free) module here, e.g. for certificates.
+        #[arg(long)]
+        emit_combined: Option<PathBuf>,
     },
 
     /// Format a canonical `.axi` module (surgically; preserves comments).
     ///
-    /// Today this focuses on canonicalizing `constraint ...` syntax so
+    /// Same rules as `axiograph fmt`: canonicalizes `constraint ...` syntax so
     /// unknown/dialect-ish constraint forms are fixable now that the
     /// certificate/promote gates fail closed.
     Fmt {
@@ -477,6 +516,27 @@ enum CheckCommands {
         #[arg(long)]
         no_fail: bool,
     },
+
+    /// Strictly validate a `proposals.json` (or NDJSON) file produced by an
+    /// external tool: unknown fields, missing/empty ids, confidence range,
+    /// duplicate `proposal_id`s. Every problem is reported with its JSON path.
+    Proposals {
+        /// Input `proposals.json`, or `.ndjson`/`.jsonl` (one proposal per line).
+        input: PathBuf,
+        /// Treat the input as NDJSON regardless of extension.
+        #[arg(long)]
+        ndjson: bool,
+    },
+
+    /// Print the JSON Schema for `proposals.json` (generated from the Rust types).
+    ProposalsSchema {
+        /// Schema for a single proposal (one NDJSON line) instead of a whole file.
+        #[arg(long)]
+        line: bool,
+        /// Write the schema to this file (defaults to stdout).
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -495,6 +555,10 @@ enum ToolsCommands {
         #[command(subcommand)]
         command: perf::PerfCommands,
     },
+
+    /// Generate browsable Markdown/HTML ontology docs from the `.axi` schemas
+    /// in a snapshot, with per-type statistics, examples and constraints.
+    Docs(ontology_docs::DocsArgs),
 }
 
 #[derive(Subcommand)]
@@ -515,6 +579,7 @@ enum DbCommands {
     ///
     /// This keeps a `.axpd` snapshot loaded in memory and serves:
     /// - `/healthz`
+    /// - `/metrics` (with `--metrics`; Prometheus text format)
     /// - `/status`
     /// - `/snapshots` (store-backed only; list snapshots for time-travel)
     /// - `/query` (AxQL)
@@ -528,6 +593,20 @@ enum DbCommands {
     /// this as an **untrusted** runtime surface: trusted correctness remains
     /// certificate checking in Lean.
     Serve(DbServeArgs),
+
+    /// Run scheduled reports (saved queries) against a snapshot.
+    ///
+    /// Reports are declared in a `report_plan_v1` JSON file. Each due report
+    /// writes `<out_dir>/<report>/<snapshot_id>.{md,json}` with its result
+    /// rows, their evidence citations, and the snapshot version it ran against.
+    Report(reports::ReportArgs),
+
+    /// Record that a reviewer checked a stored fact.
+    ///
+    /// Raises the fact's confidence by a rule (default `corroborate:0.9`),
+    /// appends the act to the storage changelog under the reviewer's id, and
+    /// exempts the fact from confidence decay.
+    Verify(verify::VerifyArgs),
 }
 
 #[derive(Args, Debug, Clone)]
@@ -562,10 +641,17 @@ struct DbServeArgs {
     #[arg(long)]
     watch_head: bool,
 
-    /// Polling interval for `--watch-head`.
+    /// Polling interval for `--watch-head` (and `--replicate-from`).
     #[arg(long, default_value_t = 2)]
     poll_interval_secs: u64,
 
+    /// Replicate from an upstream `db serve` (base URL, e.g. `http://ingest:7878`).
+    ///
+    /// Requires `--role replica --dir <local_dir>`: the replica pulls accepted-plane
+    /// and PathDB WAL deltas into its own directory and reloads when `HEAD` moves.
+    #[arg(long)]
+    replicate_from: Option<String>,
+
     /// Optional admin token required for write endpoints (recommended for `--role master`).
     #[arg(long)]
     admin_token: Option<String>,
@@ -681,6 +767,120 @@ struct DbServeArgs {
     /// Async queue size for deeper-path LRU updates (ignored unless async is enabled).
     #[arg(long, default_value_t = 1024)]
     path_index_lru_queue: usize,
+
+    /// Expose Prometheus metrics at `GET /metrics` (query latency, cache hit
+    /// counters, index build times, pending-change gauge).
+    #[arg(long)]
+    metrics: bool,
+    /// Verify index/primary consistency of every loaded snapshot (startup and
+    /// reloads) and refuse to serve one that fails.
+    #[arg(long)]
+    verify_integrity: bool,
+
+    #[command(flatten)]
+    access: AccessArgs,
+
+    #[command(flatten)]
+    audit: AuditArgs,
+}
+
+/// Role-based read restrictions (see `axiograph_storage::access`).
+#[derive(Args, Debug, Clone, Default)]
+struct AccessArgs {
+    /// JSON access policy: `{"roles": {"<role>": {"deny_types": [..], "redact_attrs": [..], "redact_pii": bool}}}`.
+    #[arg(long, requires = "access_role")]
+    access_policy: Option<PathBuf>,
+
+    /// Role from `--access-policy` to enforce: only the role's redacted snapshot
+    /// is loaded (denied types and every path through them are gone; entity ids
+    /// are renumbered).
+    #[arg(long, requires = "access_policy")]
+    access_role: Option<String>,
+}
+
+impl AccessArgs {
+    fn role_policy(&self) -> Result<Option<axiograph_storage::RolePolicy>> {
+        let (Some(path), Some(role)) = (&self.access_policy, &self.access_role) else {
+            return Ok(None);
+        };
+        let policy: axiograph_storage::AccessPolicy = serde_json::from_slice(&fs::read(path)?)
+            .map_err(|e| anyhow!("invalid access policy `{}`: {e}", path.display()))?;
+        Ok(Some(policy.role(role)?.clone()))
+    }
+}
+
+/// Read-query auditing (see `axiograph_storage::audit`).
+#[derive(Args, Debug, Clone, Default)]
+struct AuditArgs {
+    /// Append the queries this command runs to a JSONL audit log.
+    #[arg(long)]
+    audit_log: Option<PathBuf>,
+
+    /// Fraction of queries recorded, in `[0, 1]`.
+    #[arg(long, default_value_t = 1.0, requires = "audit_log")]
+    audit_sample_rate: f64,
+
+    /// Principal recorded with each query (default: `$USER`).
+    #[arg(long, requires = "audit_log")]
+    audit_principal: Option<String>,
+}
+
+impl AuditArgs {
+    fn open(&self) -> Result<Option<QueryAudit>> {
+        let Some(path) = &self.audit_log else {
+            return Ok(None);
+        };
+        let mut config = axiograph_storage::QueryAuditConfig::new(path);
+        config.sample_rate = self.audit_sample_rate;
+        let principal = self
+            .audit_principal
+            .clone()
+            .or_else(|| env::var("USER").ok())
+            .unwrap_or_else(|| "unknown".to_string());
+        Ok(Some(QueryAudit {
+            log: std::sync::Arc::new(axiograph_storage::QueryAuditLog::open(config)?),
+            principal,
+        }))
+    }
+}
+
+/// The audit log a CLI entry point records its queries into.
+#[derive(Clone)]
+struct QueryAudit {
+    log: std::sync::Arc<axiograph_storage::QueryAuditLog>,
+    principal: String,
+}
+
+impl std::fmt::Debug for QueryAudit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueryAudit")
+            .field("principal", &self.principal)
+            .finish_non_exhaustive()
+    }
+}
+
+impl QueryAudit {
+    fn record(
+        &self,
+        query: axiograph_storage::audit::AuditedQuery,
+        snapshot: &str,
+        result_count: Option<u64>,
+    ) -> Result<()> {
+        self.log
+            .record(&self.principal, query, snapshot, result_count)?;
+        Ok(())
+    }
+}
+
+/// What `policy` lets a role see of `db` (all of it without a policy).
+fn restrict_to_role(
+    db: axiograph_pathdb::PathDB,
+    policy: Option<&axiograph_storage::RolePolicy>,
+) -> axiograph_pathdb::PathDB {
+    match policy {
+        Some(policy) => axiograph_storage::AccessView::new(&db, policy).redacted_pathdb(),
+        None => db,
+    }
 }
 
 #[derive(Subcommand)]
@@ -715,6 +915,8 @@ enum CertCommands {
         /// currently anchors query-result certificates to snapshot exports).
         #[arg(long)]
         anchor_out: Option<PathBuf>,
+        #[command(flatten)]
+        audit: AuditArgs,
     },
 
     /// Typecheck a canonical `.axi` module and emit an `axi_well_typed_v1` certificate.
@@ -736,6 +938,13 @@ enum CertCommands {
         #[arg(short, long)]
         out: Option<PathBuf>,
     },
+
+    /// Revalidate stored certificates against a new `.axi` snapshot/module.
+    ///
+    /// Certificates whose witnesses still hold are re-anchored to the new
+    /// digest (`--out-dir`); the ones the change invalidated are reported with
+    /// the witnesses that no longer hold.
+    Revalidate(cert_revalidate::CertRevalidateArgs),
 }
 
 #[derive(Args)]
@@ -745,7 +954,10 @@ struct VizArgs {
     /// Output file (extension does not matter; use `--format`).
     #[arg(short, long)]
     out: PathBuf,
-    /// Output format: dot|html|json
+    /// Output format: dot|html|json|evidence
+    ///
+    /// `evidence` writes a single self-contained HTML file with the cited
+    /// evidence chunks embedded (click a node/edge to inspect them).
     #[arg(long, default_value = "dot")]
     format: String,
     /// Plane selection: data|meta|both.
@@ -769,6 +981,9 @@ struct VizArgs {
     /// Useful when both the meta-plane and data-plane contain the same `name`.
     #[arg(long)]
     focus_type: Option<String>,
+    /// Focus on every entity imported from proposal `<id>` (`attr(proposal_id)`).
+    #[arg(long)]
+    focus_proposal: Option<String>,
     /// BFS radius around focus nodes.
     #[arg(long, default_value_t = 2)]
     hops: usize,
@@ -971,6 +1186,42 @@ enum IngestCommands {
         schema_hint: Option<String>,
     },
 
+    /// Ingest several sources concurrently into one merged `proposals.json` (+ `chunks.json`).
+    ///
+    /// Sources come from `--plan <plan.json>` (`ingest_plan_v1`, with per-source
+    /// retry policies) and/or repeated `--source kind=path` flags. Sources run
+    /// with bounded parallelism; the merged output is in plan order.
+    Run(ingest_run::IngestRunArgs),
+
+    /// Re-ingest plan sources on their schedules and emit only what changed.
+    ///
+    /// Tracks per-source freshness (last ingestion, content version, proposal
+    /// digests) in a state file, re-runs sources whose `refresh` schedule is
+    /// due and whose content changed, writes delta proposals, and flags facts
+    /// from sources that changed but were not (or could not be) re-ingested.
+    Refresh(ingest_refresh::IngestRefreshArgs),
+
+    /// Rescale proposal confidences from human review outcomes.
+    ///
+    /// Fits per-source reliability from a storage changelog (accepted vs.
+    /// rejected/rolled-back changes, binned by confidence) and rewrites each
+    /// proposal's confidence; the original is kept as `raw_confidence` metadata.
+    Calibrate(calibration::CalibrateArgs),
+
+    /// Check proposals against the `.axi` schemas before a source goes live.
+    ///
+    /// Reports entity and relation types no schema declares, candidate
+    /// mappings onto similarly named declared types, and coverage percentages.
+    /// Nothing is imported.
+    Sandbox(ingest_sandbox::SandboxArgs),
+
+    /// Report knowledge gaps and which sources to ingest next.
+    ///
+    /// Compares a query audit log against a snapshot (queried types, relations
+    /// and lookups with no matches), finds dangling references in proposals,
+    /// and ranks proposals sources by how many logged queries they would answer.
+    Gaps(gaps::GapsArgs),
+
     /// Run a world model plugin to propose new facts/relations (evidence plane).
     WorldModel(WorldModelProposeArgs),
 
@@ -1039,6 +1290,15 @@ enum PathdbCommands {
         /// Output `.axi` file
         #[arg(short, long)]
         out: PathBuf,
+        /// Rewrite registered inverse pairs before exporting:
+        /// `relation`, `inverse` or `both` (default: edges as stored)
+        #[arg(long)]
+        inverses: Option<axiograph_pathdb::InverseDirection>,
+        /// Also export the payloads of referenced blobs (`blob_content`)
+        #[arg(long)]
+        include_blobs: bool,
+        #[command(flatten)]
+        access: AccessArgs,
     },
 
     /// Export a canonical `.axi` module from a `.axpd` file (schema/theory/instance).
@@ -1059,6 +1319,60 @@ enum PathdbCommands {
         module: Option<String>,
     },
 
+    /// Regenerate declarations of a hand-authored `.axi` file from a `.axpd`
+    /// file (round-trip authoring).
+    ///
+    /// Selected `schema` / `theory` / `instance` blocks are re-rendered from the
+    /// PathDB meta-plane; everything else in the file is kept as written, and
+    /// comments inside the regenerated blocks stay attached to the declaration
+    /// they precede. Prints the result unless `--write` or `--check` is given;
+    /// `--write` leaves a file that already matches the graph untouched.
+    RegenAxi {
+        /// Input `.axpd` file
+        input: PathBuf,
+        /// The `.axi` file to bring in sync
+        #[arg(long)]
+        axi: PathBuf,
+        /// Module name (required if multiple modules are present).
+        #[arg(long)]
+        module: Option<String>,
+        /// Declarations to regenerate: `schema:Name`, `theory:Name`,
+        /// `instance:Name` or `Name` (repeatable; default: all of the module)
+        #[arg(long = "select")]
+        select: Vec<String>,
+        /// Overwrite `--axi` in place
+        #[arg(long, conflicts_with = "check")]
+        write: bool,
+        /// Write nothing; exit non-zero if `--axi` is out of sync (CI)
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Split a `.axpd` snapshot at a confidence threshold into two reversible
+    /// `.axi` exports (`PathDBExportV1`): the certified core (relations at or
+    /// above the threshold, for the trusted checker) and the evidence plane
+    /// (everything else).
+    ///
+    /// Both exports keep every entity, so ids line up and their union is the
+    /// input. The `confidence_partition_v1` certificate records the threshold
+    /// and the digests of the input and both sides, and is anchored to the core.
+    ExportPartition {
+        /// Input `.axpd` file
+        input: PathBuf,
+        /// Minimum relation confidence for the certified core
+        #[arg(long)]
+        threshold: f32,
+        /// Output `.axi` file for the certified core
+        #[arg(long)]
+        core_out: PathBuf,
+        /// Output `.axi` file for the evidence plane
+        #[arg(long)]
+        evidence_out: PathBuf,
+        /// Write the partition certificate JSON to this path
+        #[arg(long)]
+        cert: Option<PathBuf>,
+    },
+
     /// Import a `.axi` file into a `.axpd` PathDB file
     ///
     /// Accepts either:
@@ -1087,6 +1401,29 @@ enum PathdbCommands {
         #[arg(short, long)]
         out: PathBuf,
     },
+
+    /// Merge parallel edges (same source, relation and target) left by
+    /// repeated ingest runs into one edge per group.
+    ///
+    /// Confidences are combined with `--combiner`; attributes are unioned, so
+    /// every run's provenance values are kept. Relation ids are compacted; the
+    /// `relation_collapse_v1` certificate maps old ids to new ones.
+    CollapseDuplicates {
+        /// Input `.axpd` file
+        input: PathBuf,
+        /// Output `.axpd` file
+        #[arg(short, long)]
+        out: PathBuf,
+        /// `min`, `product`, `noisy_or` or `dempster_shafer`
+        #[arg(long, default_value = "noisy_or")]
+        combiner: axiograph_pathdb::ConfidenceCombiner,
+        /// Only collapse these relation types (comma-separated; default: all)
+        #[arg(long, value_delimiter = ',')]
+        relations: Vec<String>,
+        /// Write the rewrite certificate JSON to this path
+        #[arg(long)]
+        cert: Option<PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -1690,6 +2027,74 @@ enum AcceptedCommands {
         /// Override path index depth for this commit (0 disables path indexing).
         #[arg(long)]
         path_index_depth: Option<usize>,
+
+        /// Screen `--proposals` record by record: commit the valid ones and move
+        /// the rest to the quarantine store (see `quarantine-list`) instead of
+        /// failing the whole batch.
+        #[arg(long)]
+        quarantine: bool,
+
+        /// With `--quarantine`, also quarantine valid proposals whose
+        /// confidence is below this floor.
+        #[arg(long, requires = "quarantine")]
+        quarantine_min_confidence: Option<f64>,
+    },
+
+    /// List proposals held in the quarantine store (failed or suspicious records).
+    QuarantineList {
+        /// Accepted-plane directory.
+        #[arg(long, default_value = "build/accepted_plane")]
+        dir: PathBuf,
+        /// Print the records as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print one quarantined record (reasons + the proposal as submitted).
+    QuarantineShow {
+        /// Accepted-plane directory.
+        #[arg(long, default_value = "build/accepted_plane")]
+        dir: PathBuf,
+        /// Record id (or a unique prefix).
+        id: String,
+    },
+
+    /// Re-screen quarantined records and commit the ones that now pass.
+    ///
+    /// Fix a record either by editing its stored `proposal` under
+    /// `<dir>/quarantine/`, or by passing a corrected proposal with `--proposal`.
+    QuarantineResubmit {
+        /// Accepted-plane directory.
+        #[arg(long, default_value = "build/accepted_plane")]
+        dir: PathBuf,
+        /// Accepted-plane snapshot id (or `latest` / `head`).
+        #[arg(long, default_value = "latest")]
+        accepted_snapshot: String,
+        /// Record ids (or unique prefixes).
+        #[arg(required_unless_present = "all")]
+        ids: Vec<String>,
+        /// Resubmit every quarantined record.
+        #[arg(long, conflicts_with = "ids")]
+        all: bool,
+        /// Replacement proposal (one JSON object) for a single record.
+        #[arg(long)]
+        proposal: Option<PathBuf>,
+        /// Confidence floor applied on re-screening.
+        #[arg(long)]
+        min_confidence: Option<f64>,
+        /// Optional message (for human audit trail).
+        #[arg(long)]
+        message: Option<String>,
+    },
+
+    /// Discard quarantined records without committing them.
+    QuarantineDrop {
+        /// Accepted-plane directory.
+        #[arg(long, default_value = "build/accepted_plane")]
+        dir: PathBuf,
+        /// Record ids (or unique prefixes).
+        #[arg(required = true)]
+        ids: Vec<String>,
     },
 
     /// Compute and commit snapshot-scoped embeddings into the PathDB WAL (extension layer).
@@ -1807,8 +2212,21 @@ enum AcceptedCommands {
     },
 }
 
+/// Install a stderr `tracing` subscriber when `AXIOGRAPH_LOG` is set
+/// (same filter syntax as `RUST_LOG`, e.g. `axiograph=debug,info`).
+fn init_tracing() {
+    let Ok(filter) = std::env::var("AXIOGRAPH_LOG") else {
+        return;
+    };
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::new(filter))
+        .with_writer(std::io::stderr)
+        .try_init();
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    init_tracing();
     let profiler = profiling::Profiler::start(&cli.profile)?;
 
     let result = (|| {
@@ -1943,6 +2361,21 @@ fn main() -> Result<()> {
                     schema_hint.as_deref(),
                 )?;
             }
+            IngestCommands::Run(args) => {
+                ingest_run::cmd_ingest_run(&args)?;
+            }
+            IngestCommands::Refresh(args) => {
+                ingest_refresh::cmd_ingest_refresh(&args)?;
+            }
+            IngestCommands::Calibrate(args) => {
+                calibration::cmd_calibrate(&args)?;
+            }
+            IngestCommands::Sandbox(args) => {
+                ingest_sandbox::cmd_sandbox(&args)?;
+            }
+            IngestCommands::Gaps(args) => {
+                gaps::cmd_gaps(&args)?;
+            }
             IngestCommands::WorldModel(args) => {
                 cmd_world_model_propose(&args)?;
             }
@@ -1950,9 +2383,22 @@ fn main() -> Result<()> {
                 cmd_world_model_plugin_llm(&args)?;
             }
         },
+        Commands::Fmt(args) => {
+            axi_fmt::cmd_fmt(&args)?;
+        }
+        Commands::Lint(args) => {
+            axi_fmt::cmd_lint(&args)?;
+        }
+        Commands::Diff(args) => {
+            axi_fmt::cmd_diff(&args)?;
+        }
         Commands::Check { command } => match command {
-            CheckCommands::Validate { input } => {
-                cmd_validate(&input)?;
+            CheckCommands::Validate {
+                input,
+                path,
+                emit_combined,
+            } => {
+                cmd_validate(&input, &path, emit_combined.as_deref())?;
             }
             CheckCommands::Fmt { input, out, write } => {
                 axi_fmt::cmd_fmt_axi(&input, out.as_deref(), write)?;
@@ -1967,6 +2413,12 @@ fn main() -> Result<()> {
             } => {
                 quality::cmd_quality(&input, out.as_ref(), &format, &profile, &plane, no_fail)?;
             }
+            CheckCommands::Proposals { input, ndjson } => {
+                cmd_check_proposals(&input, ndjson)?;
+            }
+            CheckCommands::ProposalsSchema { line, out } => {
+                cmd_proposals_schema(line, out.as_ref())?;
+            }
         },
         Commands::Cert { command } => match command {
             CertCommands::Query {
@@ -1975,8 +2427,16 @@ fn main() -> Result<()> {
                 query,
                 out,
                 anchor_out,
+                audit,
             } => {
-                cmd_query_cert(&input, &lang, &query, out.as_ref(), anchor_out.as_ref())?;
+                cmd_query_cert(
+                    &input,
+                    &lang,
+                    &query,
+                    out.as_ref(),
+                    anchor_out.as_ref(),
+                    audit.open()?.as_ref(),
+                )?;
             }
             CertCommands::Typecheck { input, out } => {
                 cmd_typecheck_cert(&input, out.as_ref())?;
@@ -1984,6 +2444,9 @@ fn main() -> Result<()> {
             CertCommands::Constraints { input, out } => {
                 cmd_constraints_cert(&input, out.as_ref())?;
             }
+            CertCommands::Revalidate(args) => {
+                cert_revalidate::cmd_cert_revalidate(&args)?;
+            }
         },
         Commands::Tools { command } => match command {
             ToolsCommands::Viz(args) => {
@@ -1995,6 +2458,9 @@ fn main() -> Result<()> {
             ToolsCommands::Perf { command } => {
                 perf::cmd_perf(command)?;
             }
+            ToolsCommands::Docs(args) => {
+                ontology_docs::cmd_docs(&args)?;
+            }
         },
         Commands::Db { command } => match command {
             DbCommands::Accept { command } => {
@@ -2006,6 +2472,12 @@ fn main() -> Result<()> {
             DbCommands::Serve(args) => {
                 db_server::cmd_db_serve(args)?;
             }
+            DbCommands::Report(args) => {
+                reports::cmd_db_report(&args)?;
+            }
+            DbCommands::Verify(args) => {
+                verify::cmd_verify(&args)?;
+            }
         },
         Commands::Sql { input, out } => {
             cmd_sql(&input, &out, None)?;
@@ -2060,7 +2532,7 @@ fn main() -> Result<()> {
             cmd_pathdb(command)?;
         }
         Commands::Validate { input } => {
-            cmd_validate(&input)?;
+            cmd_validate(&input, &[], None)?;
         }
         Commands::Repo { command } => match command {
             RepoCommands::Index {
@@ -2383,7 +2855,11 @@ fn main() -> Result<()> {
             cmd,
             continue_on_error,
             quiet,
+            access,
+            audit,
         } => {
+            let access = access.role_policy()?;
+            let audit = audit.open()?;
             if script.is_some() || !cmd.is_empty() {
                 repl::cmd_repl_script(
                     axpd.as_ref(),
@@ -2391,9 +2867,11 @@ fn main() -> Result<()> {
                     &cmd,
                     continue_on_error,
                     quiet,
+                    access,
+                    audit,
                 )?;
             } else {
-                repl::cmd_repl(axpd.as_ref())?;
+                repl::cmd_repl(axpd.as_ref(), access, audit)?;
             }
         }
         Commands::QueryCert {
@@ -2402,8 +2880,16 @@ fn main() -> Result<()> {
             query,
             out,
             anchor_out,
+            audit,
         } => {
-            cmd_query_cert(&input, &lang, &query, out.as_ref(), anchor_out.as_ref())?;
+            cmd_query_cert(
+                &input,
+                &lang,
+                &query,
+                out.as_ref(),
+                anchor_out.as_ref(),
+                audit.open()?.as_ref(),
+            )?;
         }
         Commands::TypecheckCert { input, out } => {
             cmd_typecheck_cert(&input, out.as_ref())?;
@@ -2429,18 +2915,59 @@ fn main() -> Result<()> {
 
 fn cmd_pathdb(command: PathdbCommands) -> Result<()> {
     match command {
-        PathdbCommands::ExportAxi { input, out } => {
-            cmd_pathdb_export_axi(&input, &out)?;
+        PathdbCommands::ExportAxi {
+            input,
+            out,
+            inverses,
+            include_blobs,
+            access,
+        } => {
+            let access = access.role_policy()?;
+            cmd_pathdb_export_axi(&input, &out, inverses, include_blobs, access.as_ref())?;
         }
         PathdbCommands::ExportModule { input, out, module } => {
             cmd_pathdb_export_module(&input, &out, module.as_deref())?;
         }
+        PathdbCommands::RegenAxi {
+            input,
+            axi,
+            module,
+            select,
+            write,
+            check,
+        } => {
+            cmd_pathdb_regen_axi(&input, &axi, module.as_deref(), &select, write, check)?;
+        }
+        PathdbCommands::ExportPartition {
+            input,
+            threshold,
+            core_out,
+            evidence_out,
+            cert,
+        } => {
+            cmd_pathdb_export_partition(
+                &input,
+                threshold,
+                &core_out,
+                &evidence_out,
+                cert.as_ref(),
+            )?;
+        }
         PathdbCommands::ImportAxi { input, out } => {
             cmd_pathdb_import_axi(&input, &out)?;
         }
         PathdbCommands::ImportChunks { input, chunks, out } => {
             cmd_pathdb_import_chunks(&input, &chunks, &out)?;
         }
+        PathdbCommands::CollapseDuplicates {
+            input,
+            out,
+            combiner,
+            relations,
+            cert,
+        } => {
+            cmd_pathdb_collapse_duplicates(&input, &out, combiner, relations, cert.as_ref())?;
+        }
     }
     Ok(())
 }
@@ -2532,12 +3059,31 @@ fn cmd_accept(command: AcceptedCommands) -> Result<()> {
             timings,
             timings_json,
             path_index_depth,
+            quarantine,
+            quarantine_min_confidence,
         } => {
             if chunks.is_empty() && proposals.is_empty() {
                 return Err(anyhow!(
                     "pathdb-commit requires at least one --chunks <file.json> or --proposals <file.json>"
                 ));
             }
+            if quarantine {
+                let outcome = quarantine::commit_with_quarantine(
+                    &dir,
+                    &accepted_snapshot,
+                    &chunks,
+                    &proposals,
+                    message.as_deref(),
+                    pathdb_wal::PathdbCommitOptions {
+                        timings,
+                        timings_json,
+                        path_index_depth,
+                    },
+                    quarantine_min_confidence,
+                )?;
+                print_quarantine_outcome(&dir, &outcome);
+                return Ok(());
+            }
             let result = pathdb_wal::commit_pathdb_snapshot_with_overlays_with_options(
                 &dir,
                 &accepted_snapshot,
@@ -2619,6 +3165,73 @@ fn cmd_accept(command: AcceptedCommands) -> Result<()> {
                 message.as_deref(),
             )?;
         }
+        AcceptedCommands::QuarantineList { dir, json } => {
+            let records = quarantine::list_records(&dir)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&records)?);
+            } else if records.is_empty() {
+                println!("quarantine is empty");
+            } else {
+                for record in &records {
+                    println!(
+                        "{}  {:<10}  {}  {}",
+                        record.id.yellow(),
+                        format!("{:?}", record.stage).to_ascii_lowercase(),
+                        format_age_ago(record.quarantined_at_unix_secs).dimmed(),
+                        record.source
+                    );
+                    if let Some(reason) = record.reasons.first() {
+                        let more = record.reasons.len() - 1;
+                        if more > 0 {
+                            println!("    {reason} (+{more} more)");
+                        } else {
+                            println!("    {reason}");
+                        }
+                    }
+                }
+                println!("{} record(s)", records.len());
+            }
+        }
+        AcceptedCommands::QuarantineShow { dir, id } => {
+            let record = quarantine::show_record(&dir, &id)?;
+            println!("{}", serde_json::to_string_pretty(&record)?);
+        }
+        AcceptedCommands::QuarantineResubmit {
+            dir,
+            accepted_snapshot,
+            ids,
+            all,
+            proposal,
+            min_confidence,
+            message,
+        } => {
+            let ids = if all {
+                quarantine::list_records(&dir)?
+                    .into_iter()
+                    .map(|r| r.id)
+                    .collect()
+            } else {
+                ids
+            };
+            if ids.is_empty() {
+                println!("quarantine is empty");
+                return Ok(());
+            }
+            let outcome = quarantine::resubmit(
+                &dir,
+                &accepted_snapshot,
+                &ids,
+                proposal.as_deref(),
+                message.as_deref(),
+                min_confidence,
+            )?;
+            print_quarantine_outcome(&dir, &outcome);
+        }
+        AcceptedCommands::QuarantineDrop { dir, ids } => {
+            for id in quarantine::drop_records(&dir, &ids)? {
+                eprintln!("{} dropped {id}", "ok".green().bold());
+            }
+        }
         AcceptedCommands::Status { dir } => {
             cmd_accept_status(&dir)?;
         }
@@ -2642,6 +3255,38 @@ fn now_unix_secs() -> u64 {
         .as_secs()
 }
 
+fn print_quarantine_outcome(dir: &Path, outcome: &quarantine::QuarantineOutcome) {
+    if !outcome.released.is_empty() {
+        eprintln!(
+            "{} released {} record(s) from quarantine",
+            "ok".green().bold(),
+            outcome.released.len()
+        );
+    }
+    if !outcome.quarantined.is_empty() {
+        eprintln!(
+            "{} {} record(s) quarantined (see `axiograph db accept quarantine-list --dir {}`)",
+            "warn".yellow().bold(),
+            outcome.quarantined.len(),
+            dir.display()
+        );
+    }
+    match &outcome.commit {
+        Some(result) => {
+            eprintln!(
+                "{} committed {} proposal(s) in {} WAL op(s) on accepted snapshot {} → pathdb snapshot {}",
+                "ok".green().bold(),
+                outcome.accepted,
+                result.ops_added,
+                result.accepted_snapshot_id,
+                result.snapshot_id
+            );
+            println!("{}", result.snapshot_id);
+        }
+        None => eprintln!("nothing passed screening; no snapshot committed"),
+    }
+}
+
 fn format_age_ago(created_at_unix_secs: u64) -> String {
     let now = now_unix_secs();
     let delta = if created_at_unix_secs > now {
@@ -3597,6 +4242,7 @@ fn cmd_query_cert(
     query_text: &str,
     out: Option<&PathBuf>,
     anchor_out: Option<&PathBuf>,
+    audit: Option<&QueryAudit>,
 ) -> Result<()> {
     let axi_text = fs::read_to_string(input)?;
     let digest = axiograph_dsl::digest::axi_digest_v1(&axi_text);
@@ -3657,8 +4303,20 @@ fn cmd_query_cert(
         crate::axql::certify_axql_query_v3_with_meta(&db, &query, Some(&meta), &anchor_digest)?
     }
     .with_anchor(axiograph_pathdb::certificate::AxiAnchorV1 {
-        axi_digest_v1: anchor_digest,
+        axi_digest_v1: anchor_digest.clone(),
     });
+    if let Some(audit) = audit {
+        let query = if lang == "sql" {
+            axiograph_storage::audit::AuditedQuery::Sql {
+                query: query_text.to_string(),
+            }
+        } else {
+            axiograph_storage::audit::AuditedQuery::Axql {
+                query: query_text.to_string(),
+            }
+        };
+        audit.record(query, &anchor_digest, None)?;
+    }
 
     let json = serde_json::to_string_pretty(&cert)?;
     match out {
@@ -3775,6 +4433,7 @@ fn cmd_viz_from_args(args: &VizArgs) -> Result<()> {
         &args.focus_id,
         args.focus_name.as_deref(),
         args.focus_type.as_deref(),
+        args.focus_proposal.as_deref(),
         args.all,
         args.hops,
         args.max_nodes,
@@ -3794,6 +4453,7 @@ fn cmd_viz(
     focus_id: &[u32],
     focus_name: Option<&str>,
     focus_type: Option<&str>,
+    focus_proposal: Option<&str>,
     all: bool,
     hops: usize,
     max_nodes: usize,
@@ -3818,6 +4478,13 @@ fn cmd_viz(
 
     let mut focus: Vec<u32> = focus_id.to_vec();
     if !all {
+        if let Some(proposal_id) = focus_proposal {
+            let ids = crate::viz::resolve_focus_by_proposal_id(&db, proposal_id);
+            if ids.is_empty() {
+                return Err(anyhow!("no entities found with proposal_id `{proposal_id}`"));
+            }
+            focus.extend(ids);
+        }
         if focus.is_empty() {
             if let Some(name) = focus_name {
                 if let Some(id) = crate::viz::resolve_focus_by_name_and_type(&db, name, focus_type)? {
@@ -3854,6 +4521,7 @@ fn cmd_viz(
         crate::viz::VizFormat::Dot => crate::viz::render_dot(&db, &g),
         crate::viz::VizFormat::Json => crate::viz::render_json(&g)?,
         crate::viz::VizFormat::Html => crate::viz::render_html(&db, &g)?,
+        crate::viz::VizFormat::Evidence => crate::viz::render_evidence_html(&db, &g)?,
     };
 
     if matches!(format, crate::viz::VizFormat::Html) {
@@ -4404,11 +5072,21 @@ fn cmd_doc(
         .unwrap_or_default()
         .as_secs()
         .to_string();
-    let proposals = axiograph_ingest_docs::proposals_from_extracted_facts_v1(
+    let mut proposals = axiograph_ingest_docs::proposals_from_extracted_facts_v1(
         &result.facts,
         Some(input.to_string_lossy().to_string()),
         Some(domain.to_string()),
     );
+    let plans = if domain == "machining" {
+        axiograph_ingest_docs::build_process_plans_v1(&result.extraction.chunks, &result.facts)
+    } else {
+        Vec::new()
+    };
+    proposals.extend(axiograph_ingest_docs::proposals_from_process_plans_v1(
+        &plans,
+        Some(input.to_string_lossy().to_string()),
+        Some(domain.to_string()),
+    ));
     let file = axiograph_ingest_docs::ProposalsFileV1 {
         version: axiograph_ingest_docs::PROPOSALS_VERSION_V1,
         generated_at,
@@ -4423,6 +5101,9 @@ fn cmd_doc(
     fs::write(out, &json)?;
     println!("  {} {}", "→".cyan(), out.display());
     println!("  {} {} facts extracted", "→".yellow(), result.facts.len());
+    if !plans.is_empty() {
+        println!("  {} {} process plan(s)", "→".yellow(), plans.len());
+    }
 
     let chunks_out = chunks_path
         .cloned()
@@ -4760,7 +5441,13 @@ fn cmd_readings(
     Ok(())
 }
 
-fn cmd_pathdb_export_axi(input: &PathBuf, out: &PathBuf) -> Result<()> {
+fn cmd_pathdb_export_axi(
+    input: &PathBuf,
+    out: &PathBuf,
+    inverses: Option<axiograph_pathdb::InverseDirection>,
+    include_blobs: bool,
+    access: Option<&axiograph_storage::RolePolicy>,
+) -> Result<()> {
     println!(
         "{} {}",
         "Exporting PathDB (.axpd → .axi)".green().bold(),
@@ -4768,14 +5455,79 @@ fn cmd_pathdb_export_axi(input: &PathBuf, out: &PathBuf) -> Result<()> {
     );
 
     let bytes = fs::read(input)?;
-    let db = axiograph_pathdb::PathDB::from_bytes(&bytes)?;
-    let axi = axiograph_pathdb::axi_export::export_pathdb_to_axi_v1(&db)?;
+    let mut db = restrict_to_role(axiograph_pathdb::PathDB::from_bytes(&bytes)?, access);
+    if let Some(direction) = inverses {
+        let added = db.materialize_inverses(direction);
+        println!("  {} {added} inverse edge(s) materialized", "→".cyan());
+    }
+    let axi = if include_blobs {
+        axiograph_pathdb::axi_export::export_pathdb_to_axi_v1_with_blobs(&db)?
+    } else {
+        axiograph_pathdb::axi_export::export_pathdb_to_axi_v1(&db)?
+    };
     fs::write(out, &axi)?;
+    let missing = db.missing_blobs().len();
+    if include_blobs && missing > 0 {
+        eprintln!(
+            "{} {missing} referenced blob(s) are not in the snapshot",
+            "warn".yellow().bold()
+        );
+    }
 
     println!("  {} {}", "→".cyan(), out.display());
     Ok(())
 }
 
+fn cmd_pathdb_export_partition(
+    input: &PathBuf,
+    threshold: f32,
+    core_out: &PathBuf,
+    evidence_out: &PathBuf,
+    cert: Option<&PathBuf>,
+) -> Result<()> {
+    println!(
+        "{} {}",
+        "Partitioning PathDB by confidence".green().bold(),
+        input.display()
+    );
+
+    let bytes = fs::read(input)?;
+    let db = axiograph_pathdb::PathDB::from_bytes(&bytes)?;
+    let partition = db.partition_by_confidence(threshold)?;
+    fs::write(core_out, &partition.core_axi)?;
+    fs::write(evidence_out, &partition.evidence_axi)?;
+
+    let proof = &partition.proof;
+    println!(
+        "  {} threshold={} core_relations={} evidence_relations={} entities={}",
+        "→".cyan(),
+        threshold,
+        proof.core.relation_count,
+        proof.evidence.relation_count,
+        proof.entity_count
+    );
+    if let Some(cert) = cert {
+        fs::write(
+            cert,
+            serde_json::to_string_pretty(&partition.certificate())?,
+        )?;
+        println!("  {} certificate {}", "→".cyan(), cert.display());
+    }
+    println!(
+        "  {} core {} ({})",
+        "→".cyan(),
+        core_out.display(),
+        proof.core.axi_digest_v1
+    );
+    println!(
+        "  {} evidence {} ({})",
+        "→".cyan(),
+        evidence_out.display(),
+        proof.evidence.axi_digest_v1
+    );
+    Ok(())
+}
+
 fn cmd_pathdb_export_module(input: &PathBuf, out: &PathBuf, module: Option<&str>) -> Result<()> {
     println!(
         "{} {}",
@@ -4807,6 +5559,79 @@ fn cmd_pathdb_export_module(input: &PathBuf, out: &PathBuf, module: Option<&str>
     Ok(())
 }
 
+fn cmd_pathdb_regen_axi(
+    input: &PathBuf,
+    axi: &PathBuf,
+    module: Option<&str>,
+    select: &[String],
+    write: bool,
+    check: bool,
+) -> Result<()> {
+    let bytes = fs::read(input)?;
+    let db = axiograph_pathdb::PathDB::from_bytes(&bytes)?;
+    let module_name = match module {
+        Some(m) => m.to_string(),
+        None => infer_single_meta_module_name(&db)?,
+    };
+    let select = select
+        .iter()
+        .map(|s| s.parse().map_err(|e: String| anyhow!(e)))
+        .collect::<Result<Vec<axiograph_dsl::regen::DeclRef>>>()?;
+
+    let existing = fs::read_to_string(axi)?;
+    let regen = axiograph_pathdb::axi_module_export::regenerate_axi_from_pathdb(
+        &db,
+        &module_name,
+        &existing,
+        &select,
+    )?;
+    if !write && !check {
+        print!("{}", regen.text);
+        return Ok(());
+    }
+
+    // Drift is semantic: layout-only differences from the export do not count.
+    let drift = axiograph_dsl::diff::diff_axi(&existing, &regen.text)?;
+    let in_sync = drift.is_empty();
+    if check {
+        if !in_sync {
+            for change in &drift.changes {
+                println!("  {change}");
+            }
+            return Err(anyhow!(
+                "{} is out of sync with {} (run `axiograph db pathdb regen-axi --write`)",
+                axi.display(),
+                input.display()
+            ));
+        }
+        println!("{} {} is in sync", "✓".green(), axi.display());
+        return Ok(());
+    }
+    if !in_sync {
+        fs::write(axi, &regen.text)?;
+    }
+    println!(
+        "{} {} (module={})",
+        if in_sync { "Unchanged" } else { "Regenerated" }.green().bold(),
+        axi.display(),
+        module_name.cyan()
+    );
+    for label in &regen.replaced {
+        println!("  {} {label}", "→".cyan());
+    }
+    for label in &regen.added {
+        println!("  {} {label} (added)", "→".cyan());
+    }
+    if regen.orphaned_comments > 0 {
+        println!(
+            "  {} {} comment line(s) lost their declaration; kept at the end of the block",
+            "!".yellow(),
+            regen.orphaned_comments
+        );
+    }
+    Ok(())
+}
+
 fn cmd_pathdb_import_axi(input: &PathBuf, out: &PathBuf) -> Result<()> {
     println!(
         "{} {}",
@@ -4897,6 +5722,43 @@ fn cmd_pathdb_import_chunks(input: &PathBuf, chunks: &PathBuf, out: &PathBuf) ->
     Ok(())
 }
 
+fn cmd_pathdb_collapse_duplicates(
+    input: &PathBuf,
+    out: &PathBuf,
+    combiner: axiograph_pathdb::ConfidenceCombiner,
+    relations: Vec<String>,
+    cert: Option<&PathBuf>,
+) -> Result<()> {
+    println!(
+        "{} {}",
+        "Collapsing duplicate relations".green().bold(),
+        input.display()
+    );
+
+    let bytes = fs::read(input)?;
+    let mut db = axiograph_pathdb::PathDB::from_bytes(&bytes)?;
+    let collapse = db.collapse_duplicate_relations(&axiograph_pathdb::CollapseConfig {
+        combiner,
+        relations,
+    });
+    db.build_indexes();
+    fs::write(out, db.to_bytes()?)?;
+
+    println!(
+        "  {} merged_edges={} removed={} combiner={}",
+        "→".cyan(),
+        collapse.edges.len(),
+        collapse.removed,
+        combiner.as_str()
+    );
+    if let Some(cert) = cert {
+        fs::write(cert, serde_json::to_string_pretty(&collapse.certificate())?)?;
+        println!("  {} certificate {}", "→".cyan(), cert.display());
+    }
+    println!("  {} {}", "→".cyan(), out.display());
+    Ok(())
+}
+
 fn infer_single_meta_module_name(db: &axiograph_pathdb::PathDB) -> Result<String> {
     let Some(mods) = db.find_by_type(axiograph_pathdb::axi_meta::META_TYPE_MODULE) else {
         return Err(anyhow::anyhow!(
@@ -4930,7 +5792,11 @@ fn infer_single_meta_module_name(db: &axiograph_pathdb::PathDB) -> Result<String
     Ok(names[0].clone())
 }
 
-fn cmd_validate(input: &PathBuf) -> Result<()> {
+fn cmd_validate(
+    input: &PathBuf,
+    search_paths: &[PathBuf],
+    emit_combined: Option<&Path>,
+) -> Result<()> {
     println!("{} {}", "Validating".green().bold(), input.display());
 
     let text = fs::read_to_string(input)?;
@@ -4951,10 +5817,86 @@ fn cmd_validate(input: &PathBuf) -> Result<()> {
         );
     }
 
+    if !m.imports.is_empty() || emit_combined.is_some() {
+        let graph = axiograph_pathdb::axi_module_graph::ModuleGraph::load(input, search_paths)?;
+        println!("  Imports: {}", m.imports.join(", "));
+        println!("  Module graph: {}", graph.order().join(" -> ").cyan());
+        let typed = graph.typecheck()?;
+        let combined = typed.module();
+        println!(
+            "  Combined: {} schemas, {} theories, {} instances (typechecked)",
+            combined.schemas.len(),
+            combined.theories.len(),
+            combined.instances.len()
+        );
+        if let Some(out) = emit_combined {
+            fs::write(out, axiograph_dsl::printer::print_axi_v1(combined)?)?;
+            println!("  Wrote combined module to {}", out.display());
+        }
+    }
+
     println!("{}", "Valid.".green());
     Ok(())
 }
 
+/// Strictly load proposals from a `proposals.json` or NDJSON file.
+fn load_proposals_strict(
+    input: &Path,
+    ndjson: bool,
+) -> Result<Vec<axiograph_ingest_docs::ProposalV1>> {
+    let validated = if ndjson || axiograph_ingest_docs::is_ndjson_path(input) {
+        let file = fs::File::open(input)
+            .map_err(|e| anyhow!("failed to open {}: {e}", input.display()))?;
+        axiograph_ingest_docs::validate_proposals_ndjson(io::BufReader::new(file))
+    } else {
+        let text = fs::read_to_string(input)
+            .map_err(|e| anyhow!("failed to read {}: {e}", input.display()))?;
+        axiograph_ingest_docs::validate_proposals_json(&text).map(|file| file.proposals)
+    };
+    validated.map_err(|issues| {
+        let lines: Vec<String> = issues.iter().map(|i| format!("  {i}")).collect();
+        anyhow!(
+            "{}: {} problem(s):\n{}",
+            input.display(),
+            issues.len(),
+            lines.join("\n")
+        )
+    })
+}
+
+fn cmd_check_proposals(input: &Path, ndjson: bool) -> Result<()> {
+    println!("{} {}", "Validating".green().bold(), input.display());
+    let proposals = load_proposals_strict(input, ndjson)?;
+    let relations = proposals
+        .iter()
+        .filter(|p| matches!(p, axiograph_ingest_docs::ProposalV1::Relation { .. }))
+        .count();
+    println!(
+        "  Proposals: {} (entities={}, relations={relations})",
+        proposals.len(),
+        proposals.len() - relations
+    );
+    println!("{}", "Valid.".green());
+    Ok(())
+}
+
+fn cmd_proposals_schema(line: bool, out: Option<&PathBuf>) -> Result<()> {
+    let schema = if line {
+        axiograph_ingest_docs::proposal_json_schema()
+    } else {
+        axiograph_ingest_docs::proposals_file_json_schema()
+    };
+    let text = format!("{}\n", serde_json::to_string_pretty(&schema)?);
+    match out {
+        Some(path) => {
+            fs::write(path, text)?;
+            println!("  {} {}", "→".cyan(), path.display());
+        }
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
 fn cmd_repo_index(
     root: &PathBuf,
     out: &PathBuf,
@@ -0,0 +1,598 @@
+//! `axiograph tools docs`: browsable ontology documentation.
+//!
+//! Renders the `.axi` schemas in a snapshot's meta-plane, joined with PathDB
+//! statistics, as one page per schema plus an index:
+//!
+//! - per object type: instance count (subtypes included), declared
+//!   super/subtypes, attributes observed on instances (with coverage), the
+//!   relations whose fields take the type (with stored fact counts), a few
+//!   example instances, and the constraints on those relations;
+//! - per relation: fields, stored fact count and constraints.
+//!
+//! Output is Markdown (`index.md`, `<schema>.md`), static HTML (`index.html`,
+//! `<schema>.html`) or both. Everything is regenerated from the snapshot, so
+//! the pages can replace hand-maintained wiki tables.
+
+use anyhow::{anyhow, Result};
+use axiograph_pathdb::axi_semantics::{ConstraintDecl, MetaPlaneIndex, SchemaIndex};
+use axiograph_pathdb::PathDB;
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Args, Debug, Clone)]
+pub struct DocsArgs {
+    /// Snapshot to document (`.axpd` or `.axi`).
+    pub input: PathBuf,
+
+    /// Output directory.
+    #[arg(short, long)]
+    pub out: PathBuf,
+
+    /// `md`, `html` or `both`.
+    #[arg(long, default_value = "both")]
+    pub format: String,
+
+    /// Example instances listed per type.
+    #[arg(long, default_value_t = 3)]
+    pub examples: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OntologyDoc {
+    pub schemas: Vec<SchemaDoc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SchemaDoc {
+    pub name: String,
+    pub module: Option<String>,
+    pub types: Vec<TypeDoc>,
+    pub relations: Vec<RelationDoc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TypeDoc {
+    pub name: String,
+    /// Instances, subtypes included.
+    pub instances: u64,
+    pub supertypes: Vec<String>,
+    pub subtypes: Vec<String>,
+    /// Attribute key and how many instances carry it.
+    pub attributes: Vec<(String, u64)>,
+    pub usages: Vec<RelationUsage>,
+    /// Example instance names (or ids).
+    pub examples: Vec<String>,
+    /// Constraints on the relations in `usages`, as `Rel: constraint`.
+    pub constraints: Vec<String>,
+}
+
+/// A relation field that takes the type (or one of its supertypes).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RelationUsage {
+    pub relation: String,
+    pub field: String,
+    pub facts: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RelationDoc {
+    pub name: String,
+    /// `(field, type)` in declaration order.
+    pub fields: Vec<(String, String)>,
+    pub facts: u64,
+    pub constraints: Vec<String>,
+}
+
+fn constraint_text(c: &ConstraintDecl) -> String {
+    let on = |carriers: &Option<(String, String)>| {
+        carriers
+            .as_ref()
+            .map(|(l, r)| format!(" on ({l}, {r})"))
+            .unwrap_or_default()
+    };
+    let param = |params: &Option<Vec<String>>| match params {
+        Some(ps) if !ps.is_empty() => format!(" param ({})", ps.join(", ")),
+        _ => String::new(),
+    };
+    match c {
+        ConstraintDecl::Key { fields, .. } => format!("key({})", fields.join(", ")),
+        ConstraintDecl::Functional {
+            src_field,
+            dst_field,
+            ..
+        } => format!("functional({src_field} -> {dst_field})"),
+        ConstraintDecl::AtMost {
+            src_field,
+            dst_field,
+            max,
+            params,
+            ..
+        } => format!("at_most {max} {src_field} -> {dst_field}{}", param(params)),
+        ConstraintDecl::Typing { rule, .. } => format!("typing({rule})"),
+        ConstraintDecl::SymmetricWhereIn {
+            field,
+            values,
+            carriers,
+            params,
+            ..
+        } => format!(
+            "symmetric where {field} in {{{}}}{}{}",
+            values.join(", "),
+            on(carriers),
+            param(params)
+        ),
+        ConstraintDecl::Symmetric {
+            carriers, params, ..
+        } => format!("symmetric{}{}", on(carriers), param(params)),
+        ConstraintDecl::Transitive {
+            carriers, params, ..
+        } => format!("transitive{}{}", on(carriers), param(params)),
+        ConstraintDecl::NamedBlock { name, .. } => format!("named_block({name})"),
+        ConstraintDecl::Unknown { text, .. } => format!("unknown({text})"),
+    }
+}
+
+fn relation_doc(db: &PathDB, schema_name: &str, schema: &SchemaIndex, name: &str) -> RelationDoc {
+    let decl = &schema.relation_decls[name];
+    let mut fields: Vec<_> = decl.fields.iter().collect();
+    fields.sort_by_key(|f| f.field_index);
+    RelationDoc {
+        name: name.to_string(),
+        fields: fields
+            .iter()
+            .map(|f| (f.field_name.clone(), f.field_type.clone()))
+            .collect(),
+        facts: db
+            .fact_nodes_by_axi_schema_relation(schema_name, name)
+            .len(),
+        constraints: schema
+            .constraints_by_relation
+            .get(name)
+            .into_iter()
+            .flatten()
+            .map(constraint_text)
+            .collect(),
+    }
+}
+
+fn type_doc(
+    db: &PathDB,
+    schema: &SchemaIndex,
+    relations: &[RelationDoc],
+    name: &str,
+    examples: usize,
+) -> TypeDoc {
+    let instances = db.find_by_type(name).cloned().unwrap_or_default();
+    let mut attributes: BTreeMap<String, u64> = BTreeMap::new();
+    let mut example_names = Vec::new();
+    for id in instances.iter() {
+        let Some(view) = db.get_entity(id) else {
+            continue;
+        };
+        if example_names.len() < examples {
+            example_names.push(
+                view.attrs
+                    .get("name")
+                    .cloned()
+                    .unwrap_or_else(|| format!("#{id}")),
+            );
+        }
+        for key in view.attrs.into_keys() {
+            *attributes.entry(key).or_default() += 1;
+        }
+    }
+    let mut supertypes: Vec<String> = schema
+        .supertypes_of
+        .get(name)
+        .into_iter()
+        .flatten()
+        .filter(|t| *t != name)
+        .cloned()
+        .collect();
+    supertypes.sort();
+    let mut subtypes: Vec<String> = schema
+        .object_types
+        .iter()
+        .filter(|t| *t != name && schema.is_subtype(t, name))
+        .cloned()
+        .collect();
+    subtypes.sort();
+
+    let mut usages = Vec::new();
+    let mut constraints = Vec::new();
+    for rel in relations {
+        let mut used = false;
+        for (field, field_type) in &rel.fields {
+            if schema.is_subtype(name, field_type) {
+                used = true;
+                usages.push(RelationUsage {
+                    relation: rel.name.clone(),
+                    field: field.clone(),
+                    facts: rel.facts,
+                });
+            }
+        }
+        if used {
+            constraints.extend(rel.constraints.iter().map(|c| format!("{}: {c}", rel.name)));
+        }
+    }
+
+    TypeDoc {
+        name: name.to_string(),
+        instances: instances.len(),
+        supertypes,
+        subtypes,
+        attributes: attributes.into_iter().collect(),
+        usages,
+        examples: example_names,
+        constraints,
+    }
+}
+
+/// Collect documentation for every schema in `db`'s meta-plane.
+pub fn ontology_doc(db: &PathDB, examples: usize) -> Result<OntologyDoc> {
+    let meta = MetaPlaneIndex::from_db(db)?;
+    let mut names: Vec<&String> = meta.schemas.keys().collect();
+    names.sort();
+    let schemas = names
+        .into_iter()
+        .map(|schema_name| {
+            let schema = &meta.schemas[schema_name];
+            let mut rel_names: Vec<&String> = schema.relation_decls.keys().collect();
+            rel_names.sort();
+            let relations: Vec<RelationDoc> = rel_names
+                .into_iter()
+                .map(|r| relation_doc(db, schema_name, schema, r))
+                .collect();
+            let mut type_names: Vec<&String> = schema.object_types.iter().collect();
+            type_names.sort();
+            let types = type_names
+                .into_iter()
+                .map(|t| type_doc(db, schema, &relations, t, examples))
+                .collect();
+            SchemaDoc {
+                name: schema_name.clone(),
+                module: schema.module_name.clone(),
+                types,
+                relations,
+            }
+        })
+        .collect();
+    Ok(OntologyDoc { schemas })
+}
+
+fn md_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+pub fn render_index_markdown(doc: &OntologyDoc) -> String {
+    let mut out =
+        String::from("# Ontology\n\n| Schema | Module | Types | Relations |\n|---|---|---|---|\n");
+    for s in &doc.schemas {
+        let _ = writeln!(
+            out,
+            "| [{0}]({0}.md) | {1} | {2} | {3} |",
+            md_cell(&s.name),
+            md_cell(s.module.as_deref().unwrap_or("")),
+            s.types.len(),
+            s.relations.len()
+        );
+    }
+    out
+}
+
+pub fn render_schema_markdown(schema: &SchemaDoc) -> String {
+    let mut out = format!("# Schema `{}`\n\n", schema.name);
+    if let Some(module) = &schema.module {
+        let _ = writeln!(out, "Module: `{module}`\n");
+    }
+    out.push_str("## Types\n");
+    for t in &schema.types {
+        let _ = writeln!(out, "\n### {}\n", t.name);
+        let _ = writeln!(out, "Instances: {}", t.instances);
+        if !t.supertypes.is_empty() {
+            let _ = writeln!(out, "\nSupertypes: {}", t.supertypes.join(", "));
+        }
+        if !t.subtypes.is_empty() {
+            let _ = writeln!(out, "\nSubtypes: {}", t.subtypes.join(", "));
+        }
+        if !t.attributes.is_empty() {
+            out.push_str("\n| Attribute | Instances |\n|---|---|\n");
+            for (key, n) in &t.attributes {
+                let _ = writeln!(out, "| `{}` | {n} |", md_cell(key));
+            }
+        }
+        if !t.usages.is_empty() {
+            out.push_str("\n| Relation | Field | Facts |\n|---|---|---|\n");
+            for u in &t.usages {
+                let _ = writeln!(
+                    out,
+                    "| [{0}](#relation-{0}) | `{1}` | {2} |",
+                    md_cell(&u.relation),
+                    md_cell(&u.field),
+                    u.facts
+                );
+            }
+        }
+        if !t.examples.is_empty() {
+            let _ = writeln!(out, "\nExamples: {}", t.examples.join(", "));
+        }
+        if !t.constraints.is_empty() {
+            out.push_str("\nConstraints:\n\n");
+            for c in &t.constraints {
+                let _ = writeln!(out, "- `{c}`");
+            }
+        }
+    }
+    out.push_str("\n## Relations\n");
+    for r in &schema.relations {
+        let fields: Vec<String> = r.fields.iter().map(|(f, t)| format!("{f}: {t}")).collect();
+        let _ = writeln!(
+            out,
+            "\n<a id=\"relation-{0}\"></a>\n### {0}\n\n`{0}({1})` — {2} facts",
+            r.name,
+            fields.join(", "),
+            r.facts
+        );
+        for c in &r.constraints {
+            let _ = writeln!(out, "- `{c}`");
+        }
+    }
+    out
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html_page(title: &str, body: &str) -> String {
+    format!(
+        "<!doctype html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>body{{font-family:sans-serif;max-width:60rem;margin:2rem auto}}\
+         table{{border-collapse:collapse}}td,th{{border:1px solid #ccc;padding:.2rem .5rem}}</style>\n\
+         </head>\n<body>\n{body}</body>\n</html>\n",
+        html_escape(title)
+    )
+}
+
+fn html_table(out: &mut String, headers: &[&str], rows: Vec<Vec<String>>) {
+    out.push_str("<table>\n<tr>");
+    for h in headers {
+        let _ = write!(out, "<th>{h}</th>");
+    }
+    out.push_str("</tr>\n");
+    for row in rows {
+        out.push_str("<tr>");
+        for cell in row {
+            let _ = write!(out, "<td>{cell}</td>");
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n");
+}
+
+pub fn render_index_html(doc: &OntologyDoc) -> String {
+    let mut body = String::from("<h1>Ontology</h1>\n");
+    let rows = doc
+        .schemas
+        .iter()
+        .map(|s| {
+            vec![
+                format!("<a href=\"{0}.html\">{0}</a>", html_escape(&s.name)),
+                html_escape(s.module.as_deref().unwrap_or("")),
+                s.types.len().to_string(),
+                s.relations.len().to_string(),
+            ]
+        })
+        .collect();
+    html_table(&mut body, &["Schema", "Module", "Types", "Relations"], rows);
+    html_page("Ontology", &body)
+}
+
+pub fn render_schema_html(schema: &SchemaDoc) -> String {
+    let e = html_escape;
+    let mut body = format!(
+        "<p><a href=\"index.html\">Ontology</a></p>\n<h1>Schema {}</h1>\n",
+        e(&schema.name)
+    );
+    if let Some(module) = &schema.module {
+        let _ = writeln!(body, "<p>Module: <code>{}</code></p>", e(module));
+    }
+    body.push_str("<h2>Types</h2>\n");
+    for t in &schema.types {
+        let _ = writeln!(body, "<h3 id=\"type-{0}\">{0}</h3>", e(&t.name));
+        let _ = writeln!(body, "<p>Instances: {}</p>", t.instances);
+        for (label, types) in [("Supertypes", &t.supertypes), ("Subtypes", &t.subtypes)] {
+            if !types.is_empty() {
+                let links: Vec<String> = types
+                    .iter()
+                    .map(|n| format!("<a href=\"#type-{0}\">{0}</a>", e(n)))
+                    .collect();
+                let _ = writeln!(body, "<p>{label}: {}</p>", links.join(", "));
+            }
+        }
+        if !t.attributes.is_empty() {
+            let rows = t
+                .attributes
+                .iter()
+                .map(|(k, n)| vec![format!("<code>{}</code>", e(k)), n.to_string()])
+                .collect();
+            html_table(&mut body, &["Attribute", "Instances"], rows);
+        }
+        if !t.usages.is_empty() {
+            let rows = t
+                .usages
+                .iter()
+                .map(|u| {
+                    vec![
+                        format!("<a href=\"#relation-{0}\">{0}</a>", e(&u.relation)),
+                        format!("<code>{}</code>", e(&u.field)),
+                        u.facts.to_string(),
+                    ]
+                })
+                .collect();
+            html_table(&mut body, &["Relation", "Field", "Facts"], rows);
+        }
+        if !t.examples.is_empty() {
+            let examples: Vec<String> = t.examples.iter().map(|x| e(x)).collect();
+            let _ = writeln!(body, "<p>Examples: {}</p>", examples.join(", "));
+        }
+        if !t.constraints.is_empty() {
+            body.push_str("<ul>\n");
+            for c in &t.constraints {
+                let _ = writeln!(body, "<li><code>{}</code></li>", e(c));
+            }
+            body.push_str("</ul>\n");
+        }
+    }
+    body.push_str("<h2>Relations</h2>\n");
+    for r in &schema.relations {
+        let fields: Vec<String> = r
+            .fields
+            .iter()
+            .map(|(f, t)| format!("{}: <a href=\"#type-{1}\">{1}</a>", e(f), e(t)))
+            .collect();
+        let _ = writeln!(
+            body,
+            "<h3 id=\"relation-{0}\">{0}</h3>\n<p><code>{0}({1})</code> — {2} facts</p>",
+            e(&r.name),
+            fields.join(", "),
+            r.facts
+        );
+        if !r.constraints.is_empty() {
+            body.push_str("<ul>\n");
+            for c in &r.constraints {
+                let _ = writeln!(body, "<li><code>{}</code></li>", e(c));
+            }
+            body.push_str("</ul>\n");
+        }
+    }
+    html_page(&format!("Schema {}", schema.name), &body)
+}
+
+pub fn cmd_docs(args: &DocsArgs) -> Result<()> {
+    let (md, html) = match args.format.as_str() {
+        "md" | "markdown" => (true, false),
+        "html" => (false, true),
+        "both" => (true, true),
+        other => {
+            return Err(anyhow!(
+                "unknown format `{other}` (expected md, html or both)"
+            ))
+        }
+    };
+    let db = crate::load_pathdb_for_cli(&args.input)?;
+    let doc = ontology_doc(&db, args.examples)?;
+    if doc.schemas.is_empty() {
+        return Err(anyhow!(
+            "{} has no `.axi` schemas in its meta-plane",
+            args.input.display()
+        ));
+    }
+
+    fs::create_dir_all(&args.out)?;
+    let mut written = 0;
+    let mut write = |name: String, text: String| -> Result<()> {
+        fs::write(args.out.join(name), text)?;
+        written += 1;
+        Ok(())
+    };
+    if md {
+        write("index.md".to_string(), render_index_markdown(&doc))?;
+        for s in &doc.schemas {
+            write(format!("{}.md", s.name), render_schema_markdown(s))?;
+        }
+    }
+    if html {
+        write("index.html".to_string(), render_index_html(&doc))?;
+        for s in &doc.schemas {
+            write(format!("{}.html", s.name), render_schema_html(s))?;
+        }
+    }
+    println!(
+        "{} {} schema(s) → {} ({written} files)",
+        "Documented".green().bold(),
+        doc.schemas.len(),
+        args.out.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODULE: &str = r#"
+module DocsTest
+
+schema Shop:
+  object Material
+  object Metal
+  object Tool
+  subtype Metal < Material
+  relation Cuts(tool: Tool, material: Material)
+
+theory Rules on Shop:
+  constraint key Cuts(tool, material)
+
+instance I of Shop:
+  Metal = {Steel, Titanium}
+  Tool = {EndMill}
+  Cuts = {
+    (tool=EndMill, material=Steel)
+  }
+"#;
+
+    #[test]
+    fn documents_types_relations_and_constraints() {
+        let m = axiograph_dsl::axi_v1::parse_axi_v1(MODULE).unwrap();
+        let mut db = PathDB::new();
+        axiograph_pathdb::axi_module_import::import_axi_schema_v1_module_into_pathdb(&mut db, &m)
+            .unwrap();
+        db.build_indexes();
+
+        let doc = ontology_doc(&db, 1).unwrap();
+        let [shop] = &doc.schemas[..] else {
+            panic!("one schema");
+        };
+        assert_eq!(shop.module.as_deref(), Some("DocsTest"));
+        let material = shop.types.iter().find(|t| t.name == "Material").unwrap();
+        assert_eq!(material.instances, 2);
+        assert_eq!(material.subtypes, vec!["Metal".to_string()]);
+        assert_eq!(material.examples.len(), 1);
+        assert!(material
+            .attributes
+            .iter()
+            .any(|(k, n)| k == "name" && *n == 2));
+        // Metal takes `Cuts.material` through its supertype.
+        let metal = shop.types.iter().find(|t| t.name == "Metal").unwrap();
+        assert_eq!(
+            metal.usages,
+            vec![RelationUsage {
+                relation: "Cuts".to_string(),
+                field: "material".to_string(),
+                facts: 1,
+            }]
+        );
+        assert_eq!(
+            metal.constraints,
+            vec!["Cuts: key(tool, material)".to_string()]
+        );
+
+        let md = render_schema_markdown(shop);
+        assert!(md.contains("### Metal"));
+        assert!(md.contains("`Cuts(tool: Tool, material: Material)` — 1 facts"));
+        assert!(render_index_markdown(&doc).contains("[Shop](Shop.md)"));
+        let html = render_schema_html(shop);
+        assert!(html.contains("<h3 id=\"relation-Cuts\">Cuts</h3>"));
+        assert_eq!(html_escape("a<b & \"c\""), "a&lt;b &amp; &quot;c&quot;");
+    }
+}
@@ -757,7 +757,7 @@ fn read_pathdb_head(accepted_dir: &Path) -> Result<Option<String>> {
     }
 }
 
-fn write_pathdb_head(accepted_dir: &Path, snapshot_id: &str) -> Result<()> {
+pub(crate) fn write_pathdb_head(accepted_dir: &Path, snapshot_id: &str) -> Result<()> {
     fs::write(
         pathdb_dir(accepted_dir).join(PATHDB_WAL_HEAD_FILE),
         format!("{snapshot_id}\n"),
@@ -852,7 +852,7 @@ fn resolve_pathdb_snapshot_id(accepted_dir: &Path, snapshot_id_or_latest: &str)
     Ok(matches[0].clone())
 }
 
-fn snapshot_manifest_path(accepted_dir: &Path, snapshot_id: &str) -> PathBuf {
+pub(crate) fn snapshot_manifest_path(accepted_dir: &Path, snapshot_id: &str) -> PathBuf {
     let file = format!("{}.json", digest_to_filename(snapshot_id));
     pathdb_dir(accepted_dir)
         .join(PATHDB_WAL_SNAPSHOTS_DIR)
@@ -942,8 +942,12 @@ fn try_load_checkpoint(
     Ok(Some(db))
 }
 
+pub(crate) fn log_path(accepted_dir: &Path) -> PathBuf {
+    pathdb_dir(accepted_dir).join(PATHDB_WAL_LOG_V1)
+}
+
 fn append_event(accepted_dir: &Path, event: &PathDbWalEventV1) -> Result<()> {
-    let log_path = pathdb_dir(accepted_dir).join(PATHDB_WAL_LOG_V1);
+    let log_path = log_path(accepted_dir);
     let mut f = OpenOptions::new()
         .create(true)
         .append(true)
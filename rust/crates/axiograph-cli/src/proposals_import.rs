@@ -19,8 +19,9 @@ use axiograph_pathdb::axi_meta::{
     ATTR_AXI_RELATION, ATTR_AXI_SCHEMA, META_ATTR_NAME, REL_AXI_FACT_IN_CONTEXT,
 };
 use axiograph_pathdb::axi_semantics::{MetaPlaneIndex, RelationDecl};
+use axiograph_pathdb::lang_attrs::split_lang_key;
 use axiograph_pathdb::CheckedDbMut;
-use axiograph_pathdb::PathDB;
+use axiograph_pathdb::{OnMultiplicityConflict, PathDB};
 
 use crate::relation_resolution::EndpointOrientation;
 
@@ -32,6 +33,10 @@ pub(crate) struct ImportProposalsSummary {
     pub relation_facts_added: usize,
     pub relation_facts_reused: usize,
     pub derived_edges_added: usize,
+    /// Derived edges skipped because they would exceed a declared relation
+    /// multiplicity (e.g. a second target for a `functional` relation).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub multiplicity_violations: Vec<String>,
     pub contexts_created: usize,
     pub evidence_links_added: usize,
 }
@@ -60,6 +65,7 @@ pub(crate) fn import_proposals_file_into_pathdb(
     // Represent the proposals file itself as a run node, so evidence-plane data
     // can be traced back to its source (cross-domain provenance).
     let run_id = get_or_create_proposal_run(db, file, proposals_digest)?;
+    crate::ingest_provenance::record_run_provenance(db, run_id, file)?;
 
     let mut id_map: HashMap<String, u32> = HashMap::new();
 
@@ -145,9 +151,10 @@ pub(crate) fn import_proposals_file_into_pathdb(
             }
         };
 
+        import_lang_attrs(db, id, attributes)?;
         link_run_to_proposal(db, run_id, id)?;
         id_map.insert(entity_id.to_string(), id);
-        summary.evidence_links_added += link_evidence(db, id, &proposal_meta.evidence)?;
+        summary.evidence_links_added += link_evidence(db, run_id, id, &proposal_meta.evidence)?;
     }
 
     // Pass 2: import relation proposals as fact nodes + derived binary edges.
@@ -246,9 +253,10 @@ pub(crate) fn import_proposals_file_into_pathdb(
                     };
 
                 link_run_to_proposal(db, run_id, fact_id)?;
+                crate::ingest_provenance::link_run_to_fact(db, run_id, fact_id)?;
                 summary
                     .evidence_links_added
-                    += link_evidence(db, fact_id, &proposal_meta.evidence)?;
+                    += link_evidence(db, run_id, fact_id, &proposal_meta.evidence)?;
 
                 add_edge_if_missing(db, "from", fact_id, src, 1.0)?;
                 add_edge_if_missing(db, "to", fact_id, dst, 1.0)?;
@@ -283,11 +291,7 @@ pub(crate) fn import_proposals_file_into_pathdb(
                         rel_type.clone()
                     };
 
-                    let rel_id = db.interner.intern(&derived_label);
-                    if !db.relations.has_edge(src, rel_id, dst) {
-                        db.add_relation(&derived_label, src, dst, confidence, vec![]);
-                        summary.derived_edges_added += 1;
-                    }
+                    add_derived_edge(db, &mut summary, &derived_label, src, dst, confidence)?;
                 }
 
                 continue;
@@ -465,7 +469,9 @@ pub(crate) fn import_proposals_file_into_pathdb(
             summary.relation_facts_reused += 1;
         }
         link_run_to_proposal(db, run_id, fact_id)?;
-        summary.evidence_links_added += link_evidence(db, fact_id, &proposal_meta.evidence)?;
+        crate::ingest_provenance::link_run_to_fact(db, run_id, fact_id)?;
+        summary.evidence_links_added +=
+            link_evidence(db, run_id, fact_id, &proposal_meta.evidence)?;
 
         // Uniform context scoping: treat `attributes.ctx/context` as a request to
         // scope the fact to a world/context, even if the relation signature does
@@ -490,11 +496,7 @@ pub(crate) fn import_proposals_file_into_pathdb(
                 rel_type.clone()
             };
 
-            let rel_id = db.interner.intern(&derived_label);
-            if !db.relations.has_edge(src, rel_id, dst) {
-                db.add_relation(&derived_label, src, dst, confidence, vec![]);
-                summary.derived_edges_added += 1;
-            }
+            add_derived_edge(db, &mut summary, &derived_label, src, dst, confidence)?;
         }
     }
 
@@ -702,6 +704,10 @@ fn build_entity_attrs(
     .collect();
 
     for (k, v) in attributes {
+        if split_lang_key(k).is_some() {
+            // Stored as language-tagged attributes (`import_lang_attrs`).
+            continue;
+        }
         if reserved.contains(k.as_str()) {
             attrs.push((format!("attr_{k}"), v.clone()));
         } else {
@@ -751,6 +757,9 @@ fn enrich_entity_from_proposal(
         }
     }
     for (k, v) in attributes {
+        if split_lang_key(k).is_some() {
+            continue;
+        }
         if k == META_ATTR_NAME {
             upsert_if_missing(db, entity_id, "attr_name", v)?;
         } else {
@@ -1154,7 +1163,14 @@ fn attach_evidence_attrs(db: &mut PathDB, entity_id: u32, evidence: &[EvidencePo
     Ok(())
 }
 
-fn link_evidence(db: &mut PathDB, proposal_entity_id: u32, evidence: &[EvidencePointer]) -> Result<usize> {
+/// Link a proposal to its evidence chunks (and record them as produced by
+/// `run_id`). Returns the number of proposal↔chunk edges added.
+fn link_evidence(
+    db: &mut PathDB,
+    run_id: u32,
+    proposal_entity_id: u32,
+    evidence: &[EvidencePointer],
+) -> Result<usize> {
     let mut added = 0usize;
     for ev in evidence {
         let Some(chunk_id) = find_doc_chunk_by_chunk_id(db, &ev.chunk_id)? else {
@@ -1162,6 +1178,7 @@ fn link_evidence(db: &mut PathDB, proposal_entity_id: u32, evidence: &[EvidenceP
         };
         add_edge_if_missing(db, "has_evidence_chunk", proposal_entity_id, chunk_id, 1.0)?;
         add_edge_if_missing(db, "evidence_for", chunk_id, proposal_entity_id, 1.0)?;
+        crate::ingest_provenance::link_run_to_chunk(db, run_id, chunk_id)?;
         added += 2;
     }
     Ok(added)
@@ -1233,7 +1250,62 @@ fn upsert_if_missing(db: &mut PathDB, entity_id: u32, key: &str, value: &str) ->
     if db.entities.get_attr(entity_id, key_id).is_some() {
         return Ok(());
     }
-    db.upsert_entity_attr(entity_id, key, value)
+    Ok(db.upsert_entity_attr(entity_id, key, value)?)
+}
+
+/// Store `key@lang` proposal attributes as language-tagged attributes,
+/// keeping values already present for that key and language.
+fn import_lang_attrs(
+    db: &mut PathDB,
+    entity_id: u32,
+    attributes: &HashMap<String, String>,
+) -> Result<()> {
+    for (k, v) in attributes {
+        let Some((key, lang)) = split_lang_key(k) else {
+            continue;
+        };
+        if db
+            .attr_langs(entity_id, key)
+            .iter()
+            .any(|(l, _)| l.eq_ignore_ascii_case(lang))
+        {
+            continue;
+        }
+        db.set_attr_lang(entity_id, key, lang, v)?;
+    }
+    Ok(())
+}
+
+/// Derived traversal edges respect declared multiplicities: an edge that
+/// would give a `functional` relation a second target is skipped and
+/// reported instead of accumulating next to the first.
+fn add_derived_edge(
+    db: &mut PathDB,
+    summary: &mut ImportProposalsSummary,
+    rel: &str,
+    source: u32,
+    target: u32,
+    confidence: f32,
+) -> Result<()> {
+    let rel_id = db.interner.intern(rel);
+    if db.relations.has_edge(source, rel_id, target) {
+        return Ok(());
+    }
+    let insert = db.insert_relation(
+        rel,
+        source,
+        target,
+        confidence,
+        vec![],
+        OnMultiplicityConflict::Skip,
+    )?;
+    if insert.added {
+        summary.derived_edges_added += 1;
+    }
+    summary
+        .multiplicity_violations
+        .extend(insert.violations.iter().map(ToString::to_string));
+    Ok(())
 }
 
 fn add_edge_if_missing(db: &mut PathDB, rel: &str, source: u32, target: u32, confidence: f32) -> Result<()> {
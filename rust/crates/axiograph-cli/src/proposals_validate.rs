@@ -51,7 +51,7 @@ fn proposals_digest(file: &ProposalsFileV1) -> Result<String> {
 
 fn clone_db(db: &PathDB) -> Result<PathDB> {
     let bytes = db.to_bytes()?;
-    PathDB::from_bytes(&bytes)
+    Ok(PathDB::from_bytes(&bytes)?)
 }
 
 fn typecheck_preview(db: &PathDB) -> ProposalAxiTypecheckReportV1 {
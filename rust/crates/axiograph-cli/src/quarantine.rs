@@ -0,0 +1,577 @@
+//! Quarantine lane for proposals that fail validation or look suspicious.
+//!
+//! Without it, a `pathdb-commit --proposals` batch is all-or-nothing: one
+//! malformed record in a 100k-proposal file means nothing lands. With
+//! `--quarantine`, each record is screened on its own:
+//!
+//! - records that fail strict validation (`proposal_schema` checks: shape,
+//!   unknown fields, empty ids, confidence range, duplicates) are quarantined
+//!   at stage `validation`;
+//! - records that validate but fall below `--quarantine-min-confidence` are
+//!   quarantined at stage `guardrail`;
+//! - everything else is committed as usual.
+//!
+//! Layout (under the accepted-plane directory):
+//!
+//! ```text
+//! quarantine/
+//!   q_<digest>.json     one QuarantineRecordV1 per record (raw JSON + reasons)
+//! ```
+//!
+//! Record ids are content-derived (digest of the raw record), so quarantining
+//! the same bad record twice updates one entry. After a fix (edit the stored
+//! `proposal`, or pass a replacement file), `quarantine-resubmit` re-screens
+//! the record: if it passes it is committed and the entry removed, otherwise
+//! the entry is updated with the new reasons.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use axiograph_ingest_docs::{ProposalSourceV1, ProposalV1, ProposalsFileV1, RejectedProposal};
+
+use crate::pathdb_wal::{PathDbCommitResult, PathdbCommitOptions};
+
+const QUARANTINE_DIR: &str = "quarantine";
+const QUARANTINE_RECORD_VERSION_V1: &str = "quarantine_record_v1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuarantineStage {
+    /// Failed strict validation.
+    Validation,
+    /// Valid, but flagged as suspicious (e.g. below the confidence floor).
+    Guardrail,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantineRecordV1 {
+    pub version: String,
+    pub id: String,
+    pub quarantined_at_unix_secs: u64,
+    /// Input file, plus the position for validation failures
+    /// (`proposals.json#proposals[3]`, `x.ndjson#line 12`).
+    pub source: String,
+    pub stage: QuarantineStage,
+    pub reasons: Vec<String>,
+    /// The record as submitted (a JSON string if it was not valid JSON).
+    pub proposal: Value,
+    /// Failed resubmissions so far.
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+/// A record that did not make it into the commit.
+#[derive(Debug, Clone)]
+pub struct Rejection {
+    pub source: String,
+    pub stage: QuarantineStage,
+    pub reasons: Vec<String>,
+    pub proposal: Value,
+}
+
+/// Outcome of a quarantining commit or a resubmission.
+pub struct QuarantineOutcome {
+    /// `None` when nothing passed screening (no commit was made).
+    pub commit: Option<PathDbCommitResult>,
+    pub accepted: usize,
+    /// Ids of records (newly or still) in quarantine.
+    pub quarantined: Vec<String>,
+    /// Ids removed from quarantine (resubmission only).
+    pub released: Vec<String>,
+}
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+pub fn quarantine_dir(accepted_dir: &Path) -> PathBuf {
+    accepted_dir.join(QUARANTINE_DIR)
+}
+
+fn record_path(accepted_dir: &Path, id: &str) -> PathBuf {
+    quarantine_dir(accepted_dir).join(format!("{id}.json"))
+}
+
+fn record_id(proposal: &Value) -> String {
+    let bytes = serde_json::to_vec(proposal).unwrap_or_default();
+    let digest = axiograph_dsl::digest::fnv1a64_digest_bytes(&bytes);
+    format!("q_{}", digest.trim_start_matches("fnv1a64:"))
+}
+
+fn confidence(proposal: &ProposalV1) -> f64 {
+    match proposal {
+        ProposalV1::Entity { meta, .. } | ProposalV1::Relation { meta, .. } => meta.confidence,
+    }
+}
+
+fn from_rejected(source: &str, rejected: RejectedProposal) -> Rejection {
+    Rejection {
+        source: format!("{source}#{}", rejected.path),
+        stage: QuarantineStage::Validation,
+        reasons: rejected.issues.iter().map(ToString::to_string).collect(),
+        proposal: rejected.raw,
+    }
+}
+
+/// Split a proposals batch (`proposals.json` or NDJSON) into the proposals to
+/// commit and the records to quarantine.
+pub fn screen_proposals_file(
+    input: &Path,
+    ndjson: bool,
+    min_confidence: Option<f64>,
+) -> Result<(ProposalsFileV1, Vec<Rejection>)> {
+    let source = input.display().to_string();
+    let document_error = |issues: Vec<axiograph_ingest_docs::ProposalIssue>| {
+        let lines: Vec<String> = issues.iter().map(|i| format!("  {i}")).collect();
+        anyhow!(
+            "{source}: unusable proposals document:\n{}",
+            lines.join("\n")
+        )
+    };
+    let (mut file, rejected) = if ndjson || axiograph_ingest_docs::is_ndjson_path(input) {
+        let reader = fs::File::open(input)
+            .map(std::io::BufReader::new)
+            .map_err(|e| anyhow!("failed to open {source}: {e}"))?;
+        let screened =
+            axiograph_ingest_docs::screen_proposals_ndjson(reader).map_err(document_error)?;
+        let file = ProposalsFileV1 {
+            version: axiograph_ingest_docs::PROPOSALS_VERSION_V1,
+            generated_at: now_unix_secs().to_string(),
+            source: ProposalSourceV1 {
+                source_type: "ndjson".to_string(),
+                locator: source.clone(),
+            },
+            schema_hint: None,
+            proposals: screened.accepted,
+        };
+        (file, screened.rejected)
+    } else {
+        let text =
+            fs::read_to_string(input).map_err(|e| anyhow!("failed to read {source}: {e}"))?;
+        axiograph_ingest_docs::screen_proposals_json(&text).map_err(document_error)?
+    };
+
+    let mut rejections: Vec<Rejection> = rejected
+        .into_iter()
+        .map(|r| from_rejected(&source, r))
+        .collect();
+    if let Some(floor) = min_confidence {
+        let (kept, flagged): (Vec<_>, Vec<_>) = std::mem::take(&mut file.proposals)
+            .into_iter()
+            .partition(|p| confidence(p) >= floor);
+        file.proposals = kept;
+        for proposal in flagged {
+            rejections.push(Rejection {
+                source: source.clone(),
+                stage: QuarantineStage::Guardrail,
+                reasons: vec![format!(
+                    "confidence {} is below the quarantine floor {floor}",
+                    confidence(&proposal)
+                )],
+                proposal: serde_json::to_value(&proposal)?,
+            });
+        }
+    }
+    Ok((file, rejections))
+}
+
+/// Store rejections, returning their ids. Re-quarantining a known record
+/// refreshes its reasons and keeps its attempt count.
+pub fn quarantine(accepted_dir: &Path, rejections: Vec<Rejection>) -> Result<Vec<String>> {
+    fs::create_dir_all(quarantine_dir(accepted_dir))?;
+    let mut ids = Vec::with_capacity(rejections.len());
+    for rejection in rejections {
+        let id = record_id(&rejection.proposal);
+        let attempts = read_record(accepted_dir, &id)
+            .map(|r| r.attempts)
+            .unwrap_or(0);
+        write_record(
+            accepted_dir,
+            &QuarantineRecordV1 {
+                version: QUARANTINE_RECORD_VERSION_V1.to_string(),
+                id: id.clone(),
+                quarantined_at_unix_secs: now_unix_secs(),
+                source: rejection.source,
+                stage: rejection.stage,
+                reasons: rejection.reasons,
+                proposal: rejection.proposal,
+                attempts,
+            },
+        )?;
+        ids.push(id);
+    }
+    Ok(ids)
+}
+
+fn read_record(accepted_dir: &Path, id: &str) -> Result<QuarantineRecordV1> {
+    let path = record_path(accepted_dir, id);
+    let bytes = fs::read(&path)
+        .map_err(|e| anyhow!("failed to read quarantine record `{}`: {e}", path.display()))?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+fn write_record(accepted_dir: &Path, record: &QuarantineRecordV1) -> Result<()> {
+    let path = record_path(accepted_dir, &record.id);
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(record)?)?;
+    fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+/// All quarantined records, oldest first.
+pub fn list_records(accepted_dir: &Path) -> Result<Vec<QuarantineRecordV1>> {
+    let dir = quarantine_dir(accepted_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut records = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|e| e == "json") {
+            records.push(serde_json::from_slice::<QuarantineRecordV1>(&fs::read(
+                &path,
+            )?)?);
+        }
+    }
+    records.sort_by(|a, b| {
+        (a.quarantined_at_unix_secs, &a.id).cmp(&(b.quarantined_at_unix_secs, &b.id))
+    });
+    Ok(records)
+}
+
+/// Resolve a record id or unique prefix.
+pub fn resolve_record_id(accepted_dir: &Path, id_or_prefix: &str) -> Result<String> {
+    let wanted = id_or_prefix.trim();
+    if record_path(accepted_dir, wanted).exists() {
+        return Ok(wanted.to_string());
+    }
+    let matches: Vec<String> = list_records(accepted_dir)?
+        .into_iter()
+        .map(|r| r.id)
+        .filter(|id| !wanted.is_empty() && id.starts_with(wanted))
+        .collect();
+    match matches.as_slice() {
+        [id] => Ok(id.clone()),
+        [] => Err(anyhow!("no quarantined record `{wanted}`")),
+        _ => Err(anyhow!(
+            "ambiguous quarantine id `{wanted}` ({} matches)",
+            matches.len()
+        )),
+    }
+}
+
+pub fn show_record(accepted_dir: &Path, id_or_prefix: &str) -> Result<QuarantineRecordV1> {
+    read_record(
+        accepted_dir,
+        &resolve_record_id(accepted_dir, id_or_prefix)?,
+    )
+}
+
+/// Remove records without committing them.
+pub fn drop_records(accepted_dir: &Path, ids: &[String]) -> Result<Vec<String>> {
+    let mut dropped = Vec::new();
+    for id in ids {
+        let id = resolve_record_id(accepted_dir, id)?;
+        fs::remove_file(record_path(accepted_dir, &id))?;
+        dropped.push(id);
+    }
+    Ok(dropped)
+}
+
+fn write_staged(accepted_dir: &Path, file: &ProposalsFileV1, label: &str) -> Result<PathBuf> {
+    let path = quarantine_dir(accepted_dir)
+        .join(format!(".staged_{}_{label}.proposals", std::process::id()));
+    fs::create_dir_all(quarantine_dir(accepted_dir))?;
+    fs::write(&path, serde_json::to_vec_pretty(file)?)?;
+    Ok(path)
+}
+
+/// `pathdb-commit --quarantine`: screen every proposals file, quarantine the
+/// rejects, and commit the rest (plus any chunks).
+pub fn commit_with_quarantine(
+    accepted_dir: &Path,
+    accepted_snapshot: &str,
+    chunks: &[PathBuf],
+    proposals: &[PathBuf],
+    message: Option<&str>,
+    options: PathdbCommitOptions,
+    min_confidence: Option<f64>,
+) -> Result<QuarantineOutcome> {
+    let mut screened = Vec::new();
+    let mut rejections = Vec::new();
+    for input in proposals {
+        let (file, rejected) = screen_proposals_file(input, false, min_confidence)?;
+        rejections.extend(rejected);
+        screened.push(file);
+    }
+    let accepted = screened.iter().map(|f| f.proposals.len()).sum();
+    let quarantined = quarantine(accepted_dir, rejections)?;
+
+    let mut staged = Vec::new();
+    for (i, file) in screened.iter().enumerate() {
+        if !file.proposals.is_empty() {
+            staged.push(write_staged(accepted_dir, file, &i.to_string())?);
+        }
+    }
+    let commit = if staged.is_empty() && chunks.is_empty() {
+        Ok(None)
+    } else {
+        crate::pathdb_wal::commit_pathdb_snapshot_with_overlays_with_options(
+            accepted_dir,
+            accepted_snapshot,
+            chunks,
+            &staged,
+            message,
+            options,
+        )
+        .map(Some)
+    };
+    for path in &staged {
+        let _ = fs::remove_file(path);
+    }
+    Ok(QuarantineOutcome {
+        commit: commit?,
+        accepted,
+        quarantined,
+        released: Vec::new(),
+    })
+}
+
+/// Re-screen quarantined records and commit the ones that now pass.
+///
+/// `replacement` (a single proposal JSON object) replaces the stored record
+/// before screening; it requires exactly one id.
+pub fn resubmit(
+    accepted_dir: &Path,
+    accepted_snapshot: &str,
+    ids: &[String],
+    replacement: Option<&Path>,
+    message: Option<&str>,
+    min_confidence: Option<f64>,
+) -> Result<QuarantineOutcome> {
+    if replacement.is_some() && ids.len() != 1 {
+        return Err(anyhow!(
+            "--proposal replaces exactly one quarantined record"
+        ));
+    }
+    let replacement = match replacement {
+        Some(path) => Some(serde_json::from_slice::<Value>(
+            &fs::read(path).map_err(|e| anyhow!("failed to read {}: {e}", path.display()))?,
+        )?),
+        None => None,
+    };
+
+    let mut passed: Vec<(String, ProposalV1)> = Vec::new();
+    let mut quarantined = Vec::new();
+    for id in ids {
+        let mut record = read_record(accepted_dir, &resolve_record_id(accepted_dir, id)?)?;
+        if let Some(proposal) = replacement.clone() {
+            record.proposal = proposal;
+        }
+        let document = serde_json::json!({
+            "version": axiograph_ingest_docs::PROPOSALS_VERSION_V1,
+            "generated_at": now_unix_secs().to_string(),
+            "source": { "source_type": "quarantine", "locator": record.id },
+            "proposals": [record.proposal],
+        });
+        let (file, rejected) = axiograph_ingest_docs::screen_proposals_json(&document.to_string())
+            .map_err(|issues| anyhow!("{}: {}", record.id, issues[0]))?;
+
+        let failure = if let Some(rejected) = rejected.into_iter().next() {
+            Some((
+                QuarantineStage::Validation,
+                rejected.issues.iter().map(ToString::to_string).collect(),
+            ))
+        } else {
+            let proposal = &file.proposals[0];
+            min_confidence
+                .filter(|floor| confidence(proposal) < *floor)
+                .map(|floor| {
+                    (
+                        QuarantineStage::Guardrail,
+                        vec![format!(
+                            "confidence {} is below the quarantine floor {floor}",
+                            confidence(proposal)
+                        )],
+                    )
+                })
+        };
+        match failure {
+            Some((stage, reasons)) => {
+                record.stage = stage;
+                record.reasons = reasons;
+                record.attempts += 1;
+                write_record(accepted_dir, &record)?;
+                quarantined.push(record.id);
+            }
+            None => passed.push((record.id, file.proposals.into_iter().next().expect("one"))),
+        }
+    }
+
+    let accepted = passed.len();
+    let mut commit = None;
+    let mut released = Vec::new();
+    if !passed.is_empty() {
+        let (released_ids, proposals): (Vec<String>, Vec<ProposalV1>) = passed.into_iter().unzip();
+        let file = ProposalsFileV1 {
+            version: axiograph_ingest_docs::PROPOSALS_VERSION_V1,
+            generated_at: now_unix_secs().to_string(),
+            source: ProposalSourceV1 {
+                source_type: "quarantine_resubmit".to_string(),
+                locator: released_ids.join(","),
+            },
+            schema_hint: None,
+            proposals,
+        };
+        let staged = write_staged(accepted_dir, &file, "resubmit")?;
+        let result = crate::pathdb_wal::commit_pathdb_snapshot_with_overlays_with_options(
+            accepted_dir,
+            accepted_snapshot,
+            &[],
+            std::slice::from_ref(&staged),
+            message,
+            PathdbCommitOptions::default(),
+        );
+        let _ = fs::remove_file(&staged);
+        commit = Some(result?);
+        for id in &released_ids {
+            fs::remove_file(record_path(accepted_dir, id))?;
+        }
+        released = released_ids;
+    }
+    Ok(QuarantineOutcome {
+        commit,
+        accepted,
+        quarantined,
+        released,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempDirGuard {
+        path: PathBuf,
+    }
+
+    impl TempDirGuard {
+        fn new(prefix: &str) -> Self {
+            let ts = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            let pid = std::process::id();
+            let path = std::env::temp_dir().join(format!("{prefix}_{pid}_{ts}"));
+            fs::create_dir_all(&path).expect("create temp dir");
+            Self { path }
+        }
+    }
+
+    impl Drop for TempDirGuard {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+
+    const ENTITY: &str = r#"{"kind":"Entity","proposal_id":"p1","confidence":0.9,"evidence":[],"public_rationale":"","entity_id":"e1","entity_type":"Person","name":"Ada"}"#;
+
+    fn batch(records: &[&str]) -> String {
+        format!(
+            r#"{{"version":1,"generated_at":"0","source":{{"source_type":"t","locator":"x"}},"proposals":[{}]}}"#,
+            records.join(",")
+        )
+    }
+
+    #[test]
+    fn screening_quarantines_invalid_and_low_confidence_records() -> Result<()> {
+        let tmp = TempDirGuard::new("axiograph_quarantine_screen_test");
+        let input = tmp.path.join("proposals.json");
+        let low = ENTITY.replace("\"p1\"", "\"p2\"").replace("0.9", "0.2");
+        let bad = ENTITY
+            .replace("\"p1\"", "\"p3\"")
+            .replace("\"Ada\"", "\"\"");
+        fs::write(&input, batch(&[ENTITY, &low, &bad]))?;
+
+        let (file, rejections) = screen_proposals_file(&input, false, Some(0.5))?;
+        assert_eq!(file.proposals.len(), 1);
+        let stages: Vec<_> = rejections.iter().map(|r| r.stage).collect();
+        assert_eq!(
+            stages,
+            vec![QuarantineStage::Validation, QuarantineStage::Guardrail]
+        );
+        assert!(rejections[0].source.ends_with("#proposals[2]"));
+        assert!(rejections[0].reasons[0].contains("proposals[2].name"));
+
+        let store = tmp.path.join("store");
+        let ids = quarantine(&store, rejections.clone())?;
+        // Content-derived ids: quarantining again does not duplicate.
+        assert_eq!(quarantine(&store, rejections)?, ids);
+        assert_eq!(list_records(&store)?.len(), 2);
+
+        assert_eq!(show_record(&store, &ids[0][..10])?.id, ids[0]);
+        assert_eq!(drop_records(&store, &ids[..1])?, ids[..1].to_vec());
+        assert_eq!(list_records(&store)?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_records_do_not_block_the_batch_and_resubmit_after_fix() -> Result<()> {
+        let tmp = TempDirGuard::new("axiograph_quarantine_commit_test");
+        let dir = &tmp.path;
+        let axi_path = dir.join("Test.axi");
+        fs::write(
+            &axi_path,
+            "module Test\n\nschema S:\n  object Person\n\ninstance I of S:\n  Person = {Alice}\n",
+        )?;
+        let accepted =
+            crate::accepted_plane::promote_reviewed_module(&axi_path, dir, Some("test"), "off")?;
+
+        let bad = ENTITY
+            .replace("\"p1\"", "\"p2\"")
+            .replace("\"Ada\"", "\"\"");
+        let input = dir.join("proposals.json");
+        fs::write(&input, batch(&[ENTITY, &bad]))?;
+        let outcome = commit_with_quarantine(
+            dir,
+            &accepted,
+            &[],
+            &[input],
+            None,
+            PathdbCommitOptions::default(),
+            None,
+        )?;
+        assert_eq!(outcome.accepted, 1);
+        assert_eq!(outcome.quarantined.len(), 1);
+        assert!(outcome.commit.is_some());
+        let id = outcome.quarantined[0].clone();
+
+        // Unchanged, it fails again.
+        let retry = resubmit(dir, &accepted, std::slice::from_ref(&id), None, None, None)?;
+        assert!(retry.commit.is_none());
+        assert_eq!(show_record(dir, &id)?.attempts, 1);
+
+        let fixed = dir.join("fixed.json");
+        fs::write(&fixed, bad.replace("\"name\":\"\"", "\"name\":\"Grace\""))?;
+        let released = resubmit(
+            dir,
+            &accepted,
+            std::slice::from_ref(&id),
+            Some(&fixed),
+            None,
+            None,
+        )?;
+        assert_eq!(released.released, vec![id]);
+        assert_eq!(released.commit.map(|c| c.ops_added), Some(1));
+        assert!(list_records(dir)?.is_empty());
+        Ok(())
+    }
+}
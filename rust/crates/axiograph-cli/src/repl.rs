@@ -16,14 +16,18 @@ use std::path::PathBuf;
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use roaring::RoaringBitmap;
 
-pub fn cmd_repl(initial_axpd: Option<&PathBuf>) -> Result<()> {
+pub fn cmd_repl(
+    initial_axpd: Option<&PathBuf>,
+    access: Option<axiograph_storage::RolePolicy>,
+    audit: Option<crate::QueryAudit>,
+) -> Result<()> {
     #[cfg(feature = "repl-rustyline")]
     {
-        return cmd_repl_rustyline(initial_axpd);
+        return cmd_repl_rustyline(initial_axpd, access, audit);
     }
     #[cfg(not(feature = "repl-rustyline"))]
     {
-        return cmd_repl_simple(initial_axpd);
+        return cmd_repl_simple(initial_axpd, access, audit);
     }
 }
 
@@ -33,8 +37,14 @@ pub fn cmd_repl_script(
     commands: &[String],
     continue_on_error: bool,
     quiet: bool,
+    access: Option<axiograph_storage::RolePolicy>,
+    audit: Option<crate::QueryAudit>,
 ) -> Result<()> {
-    let mut state = ReplState::default();
+    let mut state = ReplState {
+        access,
+        audit,
+        ..ReplState::default()
+    };
 
     if let Some(path) = initial_axpd {
         cmd_load_axpd(&mut state, path)?;
@@ -90,8 +100,16 @@ pub fn cmd_repl_script(
 }
 
 #[cfg(not(feature = "repl-rustyline"))]
-fn cmd_repl_simple(initial_axpd: Option<&PathBuf>) -> Result<()> {
-    let mut state = ReplState::default();
+fn cmd_repl_simple(
+    initial_axpd: Option<&PathBuf>,
+    access: Option<axiograph_storage::RolePolicy>,
+    audit: Option<crate::QueryAudit>,
+) -> Result<()> {
+    let mut state = ReplState {
+        access,
+        audit,
+        ..ReplState::default()
+    };
 
     println!("{}", "Axiograph REPL".green().bold());
     println!("Type `help` for commands. Type `exit` to quit.\n");
@@ -133,11 +151,19 @@ fn cmd_repl_simple(initial_axpd: Option<&PathBuf>) -> Result<()> {
 }
 
 #[cfg(feature = "repl-rustyline")]
-fn cmd_repl_rustyline(initial_axpd: Option<&PathBuf>) -> Result<()> {
+fn cmd_repl_rustyline(
+    initial_axpd: Option<&PathBuf>,
+    access: Option<axiograph_storage::RolePolicy>,
+    audit: Option<crate::QueryAudit>,
+) -> Result<()> {
     use rustyline::error::ReadlineError;
     use rustyline::Editor;
 
-    let mut state = ReplState::default();
+    let mut state = ReplState {
+        access,
+        audit,
+        ..ReplState::default()
+    };
 
     println!("{}", "Axiograph REPL".green().bold());
     println!("Tab-completion enabled. Type `help` for commands. Type `exit` to quit.\n");
@@ -285,6 +311,16 @@ fn dispatch_repl_line_result(state: &mut ReplState, tokens: &[String]) -> Result
             cmd_rules(state, args)?;
             Ok(ReplControl::Continue)
         }
+        "views" => {
+            cmd_views(state, args)?;
+            Ok(ReplControl::Continue)
+        }
+        "view" => {
+            let mut q_args = vec!["view".to_string()];
+            q_args.extend(args.iter().cloned());
+            cmd_axql(state, &q_args)?;
+            Ok(ReplControl::Continue)
+        }
         "validate_axi" => {
             cmd_validate_axi(state, args)?;
             Ok(ReplControl::Continue)
@@ -325,6 +361,10 @@ fn dispatch_repl_line_result(state: &mut ReplState, tokens: &[String]) -> Result
             cmd_find_paths(state, args)?;
             Ok(ReplControl::Continue)
         }
+        "why_not" => {
+            cmd_why_not(state, args)?;
+            Ok(ReplControl::Continue)
+        }
         "gen" => {
             cmd_gen(state, args)?;
             Ok(ReplControl::Continue)
@@ -370,6 +410,20 @@ struct ReplState {
     snapshot_key: String,
     contexts: Vec<crate::axql::AxqlContextSpec>,
     query_cache: crate::axql::AxqlPreparedQueryCache,
+    /// Role enforced on every loaded or imported snapshot (`--access-role`).
+    access: Option<axiograph_storage::RolePolicy>,
+    /// Where `q`/`sql` queries are recorded (`--audit-log`).
+    audit: Option<crate::QueryAudit>,
+}
+
+/// Replace the loaded snapshot by what the REPL's role may see.
+fn enforce_access_policy(state: &mut ReplState) {
+    let Some(policy) = state.access.as_ref() else {
+        return;
+    };
+    if let Some(db) = state.db.take() {
+        state.db = Some(crate::restrict_to_role(db, Some(policy)));
+    }
 }
 
 fn refresh_meta_plane_index(state: &mut ReplState) -> Result<()> {
@@ -476,6 +530,8 @@ fn refresh_completion_data(
         "schema".to_string(),
         "constraints".to_string(),
         "rules".to_string(),
+        "views".to_string(),
+        "view".to_string(),
         "validate_axi".to_string(),
         "learning_graph".to_string(),
         "show".to_string(),
@@ -486,6 +542,7 @@ fn refresh_completion_data(
         "follow".to_string(),
         "find_by_type".to_string(),
         "find_paths".to_string(),
+        "why_not".to_string(),
         "gen".to_string(),
         "q".to_string(),
         "axql".to_string(),
@@ -712,6 +769,8 @@ fn print_help() {
   constraints <schema> [relation]
                                  Show imported theory constraints (keys/functionals/etc) for a schema/relation
   rules [theory] [rule]          Show imported theory rewrite rules (meta-plane)
+  views [name]                   List saved queries (`view name = ...` in `.axi` theories), or show one
+  view <name>                    Run a saved query (same as `q view <name>`)
   validate_axi                   Type-check imported canonical `.axi` instance data against the meta-plane schema
   learning_graph <schema>        Extract a typed learning graph (Concept prerequisites + links) from the current DB
 
@@ -732,6 +791,8 @@ fn print_help() {
                                  Mutate the current DB by adding an equivalence (dashed in viz)
   stats                          Print current DB stats
   analyze network [out] [opts]   Network analysis over the current DB (tooling)
+  analyze sample <start> [opts]  Sampled path count / neighborhood size with error bounds
+                                 (evidence-plane estimate, not certified)
   quality [out] [opts]           Quality checks over the current DB (tooling)
 
   show <entity_id>               Show an entity (type + attributes)
@@ -744,11 +805,17 @@ fn print_help() {
   follow <start_id> <path_expr>  Follow an RPQ path expression (e.g. `rel_0/rel_1`, `(a|b)*`)
                                  Optional: `max_hops N`
   find_paths <from> <to> <depth> Find paths between entities (prints first 10)
+  why_not <from> <to> [opts]     Propose the fewest plausible missing edges that would make
+                                 <to> reachable (untrusted, low confidence)
+                                 Optional: `max_missing N`, `via rel,rel`, `out proposals.json`
 
   q <AxQL query>                 Pattern-match query language (datalog-ish)
                                  Prints cache hit/miss + elapsed time
   q --elaborate <AxQL query>     Typecheck + show elaborated query (inferred types)
   q --typecheck <AxQL query>     Typecheck only (no execution)
+  q --explain-analyze <AxQL query>
+                                 Run with per-step timings, estimated vs actual
+                                 cardinalities, and index usage
   sql <SQL query>                SQL-ish dialect compiled into the same query core
   ask <query>                    Natural-language-ish templates compiled into AxQL
   llm <subcommand>               LLM-assisted query translation / answering
@@ -756,7 +823,7 @@ fn print_help() {
   match_proto_enterprise          Add heuristic links from `Service` → `ProtoService` (and reverse), so enterprise graphs can traverse into imported proto surfaces
   viz <out> [options...]         Export a neighborhood visualization (dot/html/json)
                                  Options:
-                                   format dot|html|json
+                                   format dot|html|json|evidence
                                    plane data|meta|both
                                    focus <entity_id> | focus_name <name>
                                    focus_type <TypeName>
@@ -959,6 +1026,7 @@ fn cmd_load_axpd(state: &mut ReplState, path: &PathBuf) -> Result<()> {
     let db = axiograph_pathdb::PathDB::from_bytes(&bytes)?;
     state.db = Some(db);
     set_snapshot_key(state, snapshot_key);
+    enforce_access_policy(state);
     refresh_meta_plane_index(state)?;
     println!("loaded {}", path.display());
     Ok(())
@@ -991,6 +1059,7 @@ fn cmd_import_axi(state: &mut ReplState, path: &PathBuf) -> Result<()> {
         let db = axiograph_pathdb::axi_export::import_pathdb_from_axi_v1_module(&m)?;
         state.db = Some(db);
         set_snapshot_key(state, module_digest);
+        enforce_access_policy(state);
         refresh_meta_plane_index(state)?;
         println!("imported PathDB snapshot {}", path.display());
         return Ok(());
@@ -1009,6 +1078,7 @@ fn cmd_import_axi(state: &mut ReplState, path: &PathBuf) -> Result<()> {
         chain_snapshot_key(&state.snapshot_key, "import_axi", &module_digest)
     };
     set_snapshot_key(state, next_key);
+    enforce_access_policy(state);
     refresh_meta_plane_index(state)?;
     println!(
         "imported axi_schema_v1 module {} (meta_entities={} meta_relations={} instances={} entities={} upgraded_types={} tuple_entities={} relations={} derived_edges={})",
@@ -1127,12 +1197,16 @@ fn cmd_import_proto(state: &mut ReplState, args: &[String]) -> Result<()> {
         proposals_summary.evidence_links_added,
         start.elapsed()
     );
+    for violation in &proposals_summary.multiplicity_violations {
+        println!("  skipped derived edge: {violation}");
+    }
     let next_key = if state.snapshot_key.is_empty() {
         ingest_digest
     } else {
         chain_snapshot_key(&state.snapshot_key, "import_proto", &ingest_digest)
     };
     set_snapshot_key(state, next_key);
+    enforce_access_policy(state);
     refresh_meta_plane_index(state)?;
     Ok(())
 }
@@ -1302,7 +1376,7 @@ fn cmd_viz(state: &ReplState, args: &[String]) -> Result<()> {
         return Err(anyhow!(
             "usage: viz <out_path> [options...]\n\
              options:\n\
-               format dot|html|json\n\
+               format dot|html|json|evidence\n\
                plane data|meta|both\n\
                focus <entity_id> | focus_name <name>\n\
                focus_type <TypeName>\n\
@@ -1458,6 +1532,7 @@ fn cmd_viz(state: &ReplState, args: &[String]) -> Result<()> {
         crate::viz::VizFormat::Dot => crate::viz::render_dot(db, &g),
         crate::viz::VizFormat::Json => crate::viz::render_json(&g)?,
         crate::viz::VizFormat::Html => crate::viz::render_html(db, &g)?,
+        crate::viz::VizFormat::Evidence => crate::viz::render_evidence_html(db, &g)?,
     };
     if matches!(format, crate::viz::VizFormat::Html) {
         let json = crate::viz::render_json(&g)?;
@@ -1493,14 +1568,18 @@ fn cmd_analyze(state: &ReplState, args: &[String]) -> Result<()> {
                include_equivalences | skip_facts | communities\n\
                pagerank_iters <n> | pagerank_damping <d>\n\
                betweenness_sources <n> | seed <n>\n\
-               max_heavy_nodes <n> | top <n>"
+               max_heavy_nodes <n> | top <n>\n\
+             usage: analyze sample <start_id> [path <rel...> | hops <n>] [options...]\n\
+             options:\n\
+               walks <n> | ms <n> | seed <n> | delta <d> | min_confidence <c>"
         ));
     }
 
     match args[0].as_str() {
         "network" => cmd_analyze_network(state, &args[1..]),
+        "sample" => cmd_analyze_sample(state, &args[1..]),
         other => Err(anyhow!(
-            "unknown analyze subcommand `{other}` (try: analyze network)"
+            "unknown analyze subcommand `{other}` (try: analyze network | analyze sample)"
         )),
     }
 }
@@ -1665,6 +1744,92 @@ fn cmd_analyze_network(state: &ReplState, args: &[String]) -> Result<()> {
     Ok(())
 }
 
+/// `analyze sample`: random-walk estimates (evidence plane; never certified).
+fn cmd_analyze_sample(state: &ReplState, args: &[String]) -> Result<()> {
+    let usage = "usage: analyze sample <start_id> [path <rel...> | hops <n>] [walks <n>] [ms <n>] [seed <n>] [delta <d>] [min_confidence <c>]";
+    let Some(start) = args.first() else {
+        return Err(anyhow!(usage));
+    };
+    let start: u32 = start.parse()?;
+    let mut path: Vec<String> = Vec::new();
+    let mut hops: usize = 2;
+    let mut config = axiograph_pathdb::SamplingConfig::default();
+
+    let mut i = 1usize;
+    while i < args.len() {
+        let value = |i: usize| {
+            args.get(i + 1)
+                .ok_or_else(|| anyhow!("analyze sample: missing value for `{}`", args[i]))
+        };
+        match args[i].as_str() {
+            "path" => {
+                while i + 1 < args.len()
+                    && !matches!(
+                        args[i + 1].as_str(),
+                        "hops" | "walks" | "ms" | "seed" | "delta" | "min_confidence"
+                    )
+                {
+                    path.push(args[i + 1].clone());
+                    i += 1;
+                }
+                i += 1;
+                continue;
+            }
+            "hops" => hops = value(i)?.parse()?,
+            "walks" => config.walks = value(i)?.parse()?,
+            "ms" => config.budget = config.budget.with_max_ms(value(i)?.parse()?),
+            "seed" => config.seed = value(i)?.parse()?,
+            "delta" => config.delta = value(i)?.parse()?,
+            "min_confidence" => config.min_confidence = Some(value(i)?.parse()?),
+            other => return Err(anyhow!("analyze sample: unexpected token `{other}`")),
+        }
+        i += 2;
+    }
+
+    let db = require_db(state)?;
+    let fmt_upper = |upper: Option<f64>| upper.map_or("unbounded".to_string(), |u| format!("{u:.1}"));
+    if path.is_empty() {
+        let sample = db.sample_neighborhood(start, None, hops, &config);
+        let v = &sample.value;
+        println!(
+            "≈{:.1} entities within {hops} hops of {} (≥{:.0}, {:.0}% confidence; missing mass ≤ {:.3})",
+            v.size.value,
+            describe_entity(db, start),
+            v.size.lower,
+            v.size.confidence * 100.0,
+            v.missing_mass
+        );
+        println!(
+            "walks={} reached={} status={:?} elapsed={:?} [evidence plane: not certified]",
+            v.walks,
+            v.reached.len(),
+            sample.status,
+            sample.elapsed
+        );
+    } else {
+        let rels: Vec<&str> = path.iter().map(String::as_str).collect();
+        let sample = db.sample_path_count(start, &rels, &config);
+        let v = &sample.value;
+        println!(
+            "≈{:.1} paths {} from {} ([{:.1}, {}] at {:.0}% confidence)",
+            v.count.value,
+            rels.join("/"),
+            describe_entity(db, start),
+            v.count.lower,
+            fmt_upper(v.count.upper),
+            v.count.confidence * 100.0
+        );
+        println!(
+            "walks={} reached={} status={:?} elapsed={:?} [evidence plane: not certified]",
+            v.walks,
+            v.reached.len(),
+            sample.status,
+            sample.elapsed
+        );
+    }
+    Ok(())
+}
+
 fn cmd_quality(state: &ReplState, args: &[String]) -> Result<()> {
     let mut out: Option<PathBuf> = None;
     let mut format = "text".to_string();
@@ -2425,9 +2590,7 @@ fn cmd_learning_graph(state: &ReplState, args: &[String]) -> Result<()> {
     );
 
     fn name_of(db: &axiograph_pathdb::PathDB, entity: u32) -> String {
-        db.get_entity(entity)
-            .and_then(|v| v.attrs.get("name").cloned())
-            .unwrap_or_else(|| entity.to_string())
+        db.entity_label(entity).unwrap_or_else(|| entity.to_string())
     }
 
     let limit = 20usize;
@@ -2507,6 +2670,8 @@ fn entity_plane(view: &axiograph_pathdb::EntityView) -> &'static str {
     if view.entity_type == "DocChunk"
         || view.entity_type == "Document"
         || view.entity_type == "ProposalRun"
+        || view.entity_type == crate::ingest_provenance::TYPE_INGEST_SOURCE
+        || view.entity_type == crate::ingest_provenance::TYPE_INGEST_TOOL
         || view.attrs.contains_key("proposal_id")
         || view.attrs.contains_key("proposals_digest")
     {
@@ -2729,7 +2894,7 @@ fn cmd_describe(state: &ReplState, args: &[String]) -> Result<()> {
 fn cmd_neigh(state: &ReplState, args: &[String]) -> Result<()> {
     if args.is_empty() {
         return Err(anyhow!(
-            "usage: neigh <entity_id|name> [--hops N] [--plane data|meta|both] [--out <path>] [--format dot|html|json] [--typed_overlay] [--max_nodes N] [--max_edges N] [--direction out|in|both]"
+            "usage: neigh <entity_id|name> [--hops N] [--plane data|meta|both] [--out <path>] [--format dot|html|json|evidence] [--typed_overlay] [--max_nodes N] [--max_edges N] [--direction out|in|both]"
         ));
     }
 
@@ -2871,6 +3036,7 @@ fn cmd_neigh(state: &ReplState, args: &[String]) -> Result<()> {
             crate::viz::VizFormat::Dot => crate::viz::render_dot(db, &g),
             crate::viz::VizFormat::Json => crate::viz::render_json(&g)?,
             crate::viz::VizFormat::Html => crate::viz::render_html(db, &g)?,
+            crate::viz::VizFormat::Evidence => crate::viz::render_evidence_html(db, &g)?,
         };
         if matches!(format, crate::viz::VizFormat::Html) {
             let json = crate::viz::render_json(&g)?;
@@ -3246,6 +3412,133 @@ fn cmd_find_paths(state: &ReplState, args: &[String]) -> Result<()> {
     Ok(())
 }
 
+fn cmd_why_not(state: &ReplState, args: &[String]) -> Result<()> {
+    const USAGE: &str =
+        "usage: why_not <from> <to> [max_missing N] [via rel,rel...] [out proposals.json]";
+    if args.len() < 2 || !args.len().is_multiple_of(2) {
+        return Err(anyhow!(USAGE));
+    }
+    let db = require_db(state)?;
+    let from = resolve_entity_ref(db, &args[0])?;
+    let to = resolve_entity_ref(db, &args[1])?;
+
+    let mut config = axiograph_pathdb::AbductionConfig::default();
+    let mut out: Option<PathBuf> = None;
+    for option in args[2..].chunks(2) {
+        match option[0].as_str() {
+            "max_missing" => config.max_missing = option[1].parse()?,
+            "via" => {
+                config.relations = option[1]
+                    .split(',')
+                    .map(|r| r.trim().to_string())
+                    .filter(|r| !r.is_empty())
+                    .collect()
+            }
+            "out" => out = Some(PathBuf::from(&option[1])),
+            _ => return Err(anyhow!(USAGE)),
+        }
+    }
+
+    let start = Instant::now();
+    let Some(abduction) = db.abduce_reachability(from, to, &config) else {
+        println!(
+            "no plausible path with at most {} missing edge(s) ({:?})",
+            config.max_missing,
+            start.elapsed()
+        );
+        return Ok(());
+    };
+    if abduction.already_reachable() {
+        println!("already reachable ({:?})", start.elapsed());
+    } else {
+        println!(
+            "{} missing edge(s) would connect them ({:?})",
+            abduction.proposed.len(),
+            start.elapsed()
+        );
+    }
+    for step in &abduction.path {
+        let marker = if step.proposed {
+            "proposed".yellow().to_string()
+        } else {
+            "existing".to_string()
+        };
+        println!(
+            "  {marker} {} -{}-> {} (conf={:.2})",
+            describe_entity(db, step.source),
+            step.rel_type,
+            describe_entity(db, step.target),
+            step.confidence
+        );
+    }
+
+    if let Some(path) = out {
+        let file = abduction_proposals(db, &abduction);
+        fs::write(&path, serde_json::to_string_pretty(&file)?)?;
+        println!(
+            "wrote {} proposal(s) to {} (review before import)",
+            file.proposals.len(),
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// The proposed edges of an abduction as relation proposals, endpoints by name.
+fn abduction_proposals(
+    db: &axiograph_pathdb::PathDB,
+    abduction: &axiograph_pathdb::Abduction,
+) -> axiograph_ingest_docs::ProposalsFileV1 {
+    let name = |id: u32| {
+        db.get_entity(id)
+            .and_then(|view| view.attrs.get("name").cloned())
+            .unwrap_or_else(|| id.to_string())
+    };
+    let (from, to) = (name(abduction.from), name(abduction.to));
+    let proposals = abduction
+        .proposed
+        .iter()
+        .map(|edge| {
+            let (source, target) = (name(edge.source), name(edge.target));
+            let relation_id = format!("abduction::{source}::{}::{target}", edge.rel_type);
+            axiograph_ingest_docs::ProposalV1::Relation {
+                meta: axiograph_ingest_docs::ProposalMetaV1 {
+                    proposal_id: relation_id.clone(),
+                    confidence: edge.confidence as f64,
+                    evidence: Vec::new(),
+                    public_rationale: format!(
+                        "would make `{to}` reachable from `{from}`; {} existing `{}` edge(s) share this type signature",
+                        edge.support, edge.rel_type
+                    ),
+                    metadata: [("abduction_support".to_string(), edge.support.to_string())]
+                        .into(),
+                    schema_hint: None,
+                },
+                relation_id,
+                rel_type: edge.rel_type.clone(),
+                source,
+                target,
+                attributes: Default::default(),
+            }
+        })
+        .collect();
+    let generated_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        .to_string();
+    axiograph_ingest_docs::ProposalsFileV1 {
+        version: axiograph_ingest_docs::PROPOSALS_VERSION_V1,
+        generated_at,
+        source: axiograph_ingest_docs::ProposalSourceV1 {
+            source_type: "abduction".to_string(),
+            locator: format!("why_not {from} {to}"),
+        },
+        schema_hint: None,
+        proposals,
+    }
+}
+
 fn cmd_gen(state: &mut ReplState, args: &[String]) -> Result<()> {
     if args.is_empty() {
         return Err(anyhow!(
@@ -3294,6 +3587,7 @@ fn cmd_gen(state: &mut ReplState, args: &[String]) -> Result<()> {
         let gen_key = axiograph_dsl::digest::axi_digest_v1(&gen_spec);
         state.db = Some(db);
         set_snapshot_key(state, gen_key);
+        enforce_access_policy(state);
         refresh_meta_plane_index(state)?;
         return Ok(());
     }
@@ -3362,16 +3656,20 @@ fn cmd_gen(state: &mut ReplState, args: &[String]) -> Result<()> {
     let gen_key = axiograph_dsl::digest::axi_digest_v1(&gen_spec);
     state.db = Some(db);
     set_snapshot_key(state, gen_key);
+    enforce_access_policy(state);
     refresh_meta_plane_index(state)?;
     Ok(())
 }
 
 fn cmd_axql(state: &mut ReplState, args: &[String]) -> Result<()> {
     if args.is_empty() {
-        return Err(anyhow!("usage: q [--elaborate|--typecheck] <AxQL query>"));
+        return Err(anyhow!(
+            "usage: q [--elaborate|--typecheck|--explain-analyze] <AxQL query>"
+        ));
     }
     let mut show_elaboration = false;
     let mut typecheck_only = false;
+    let mut explain_analyze = false;
 
     let mut idx = 0usize;
     while idx < args.len() {
@@ -3385,20 +3683,27 @@ fn cmd_axql(state: &mut ReplState, args: &[String]) -> Result<()> {
                 typecheck_only = true;
                 idx += 1;
             }
+            "--explain-analyze" | "--analyze" => {
+                show_elaboration = true;
+                explain_analyze = true;
+                idx += 1;
+            }
             _ => break,
         }
     }
 
     if idx >= args.len() {
-        return Err(anyhow!("usage: q [--elaborate|--typecheck] <AxQL query>"));
+        return Err(anyhow!(
+            "usage: q [--elaborate|--typecheck|--explain-analyze] <AxQL query>"
+        ));
     }
 
-    let query_text = args[idx..].join(" ");
     let db = state
         .db
         .as_ref()
         .ok_or_else(|| anyhow!("no database loaded (use `load`, `import_axi`, or `gen`)"))?;
     let meta = state.meta.as_ref();
+    let query_text = crate::axql::resolve_axql_view_reference(meta, &args[idx..].join(" "))?;
 
     let mut query = crate::axql::parse_axql_query(&query_text)?;
     if query.contexts.is_empty() && !state.contexts.is_empty() {
@@ -3436,7 +3741,7 @@ fn cmd_axql(state: &mut ReplState, args: &[String]) -> Result<()> {
                 return Ok(());
             }
         }
-        prepared.execute(db, meta)?
+        execute_prepared_axql(prepared, db, meta, explain_analyze)?
     } else {
         let prepared = crate::axql::prepare_axql_query_with_meta(db, &query, meta)?;
         state.query_cache.insert(key.clone(), prepared);
@@ -3467,7 +3772,7 @@ fn cmd_axql(state: &mut ReplState, args: &[String]) -> Result<()> {
                 return Ok(());
             }
         }
-        prepared.execute(db, meta)?
+        execute_prepared_axql(prepared, db, meta, explain_analyze)?
     };
     let dt = start.elapsed();
     println!(
@@ -3475,6 +3780,13 @@ fn cmd_axql(state: &mut ReplState, args: &[String]) -> Result<()> {
         if cache_hit { "hit" } else { "miss" },
         dt
     );
+    record_query(
+        state,
+        axiograph_storage::audit::AuditedQuery::Axql {
+            query: query_text.clone(),
+        },
+        result.rows.len(),
+    )?;
 
     let vars = if result.selected_vars.is_empty() {
         "(no selected vars)".to_string()
@@ -3506,6 +3818,37 @@ fn cmd_axql(state: &mut ReplState, args: &[String]) -> Result<()> {
     Ok(())
 }
 
+/// Append an executed query to the `--audit-log`, if any.
+fn record_query(
+    state: &ReplState,
+    query: axiograph_storage::audit::AuditedQuery,
+    rows: usize,
+) -> Result<()> {
+    match &state.audit {
+        Some(audit) => audit.record(query, &state.snapshot_key, Some(rows as u64)),
+        None => Ok(()),
+    }
+}
+
+/// Execute a prepared query; with `explain_analyze`, also print the
+/// per-operator profile.
+fn execute_prepared_axql(
+    prepared: &mut crate::axql::PreparedAxqlQueryExpr,
+    db: &axiograph_pathdb::PathDB,
+    meta: Option<&axiograph_pathdb::axi_semantics::MetaPlaneIndex>,
+    explain_analyze: bool,
+) -> Result<crate::axql::AxqlResult> {
+    if !explain_analyze {
+        return prepared.execute(db, meta);
+    }
+    let (result, lines) = prepared.explain_analyze(db, meta)?;
+    println!("analyze:");
+    for l in lines {
+        println!("  {l}");
+    }
+    Ok(result)
+}
+
 fn cmd_schema_constraints(state: &ReplState, args: &[String]) -> Result<()> {
     let Some(meta) = state.meta.as_ref() else {
         return Err(anyhow!(
@@ -3669,6 +4012,36 @@ fn cmd_schema_constraints(state: &ReplState, args: &[String]) -> Result<()> {
     Ok(())
 }
 
+fn cmd_views(state: &ReplState, args: &[String]) -> Result<()> {
+    if args.len() > 1 {
+        return Err(anyhow!("usage: views [name]"));
+    }
+    let Some(meta) = state.meta.as_ref() else {
+        return Err(anyhow!(
+            "no `.axi` meta-plane loaded (import a canonical `.axi` module first)"
+        ));
+    };
+
+    if let Some(name) = args.first() {
+        let view = meta.view(name)?;
+        println!("view {}.{}", view.schema_name, view.name);
+        println!("  theory: {}", view.theory_name);
+        println!("  query: {}", view.query);
+        return Ok(());
+    }
+
+    let views = meta.views();
+    if views.is_empty() {
+        println!("(no views declared)");
+        return Ok(());
+    }
+    println!("views");
+    for view in views {
+        println!("  {}.{} = {}", view.schema_name, view.name, view.query);
+    }
+    Ok(())
+}
+
 fn cmd_rules(state: &ReplState, args: &[String]) -> Result<()> {
     if args.len() > 2 {
         return Err(anyhow!("usage: rules [theory] [rule]"));
@@ -3825,6 +4198,11 @@ fn cmd_sqlish(state: &mut ReplState, args: &[String]) -> Result<()> {
             .expect("query cache insert")
             .execute(db, meta)?
     };
+    record_query(
+        state,
+        axiograph_storage::audit::AuditedQuery::Sql { query: query_text },
+        result.rows.len(),
+    )?;
 
     let vars = if result.selected_vars.is_empty() {
         "(no selected vars)".to_string()
@@ -3885,6 +4263,13 @@ fn cmd_ask(state: &mut ReplState, args: &[String]) -> Result<()> {
             .expect("query cache insert")
             .execute(db, meta)?
     };
+    record_query(
+        state,
+        axiograph_storage::audit::AuditedQuery::Axql {
+            query: crate::nlq::render_axql_query(&query),
+        },
+        result.rows.len(),
+    )?;
 
     let vars = if result.selected_vars.is_empty() {
         "(no selected vars)".to_string()
@@ -4273,8 +4658,8 @@ fn describe_entity(db: &axiograph_pathdb::PathDB, entity_id: u32) -> String {
         return format!("{entity_id} (missing)");
     };
 
-    if let Some(name) = view.attrs.get("name") {
-        return format!("{entity_id} ({}, name={})", view.entity_type, name);
+    if let Some(label) = &view.label {
+        return format!("{entity_id} ({}, {label})", view.entity_type);
     }
 
     format!("{entity_id} ({})", view.entity_type)
@@ -4323,7 +4708,7 @@ fn tokenize_repl_line(line: &str) -> Vec<String> {
     // quotes changes the meaning (and can make URLs/IRIs unparsable).
     //
     // So we parse:
-    //   q [--elaborate|--typecheck] <raw query...>
+    //   q [--elaborate|--typecheck|--explain-analyze] <raw query...>
     // as:
     //   ["q", "--elaborate", "<raw query...>"]
     //
@@ -5067,6 +5452,41 @@ fn cmd_world_model_plan_repl(state: &mut ReplState, args: &[String]) -> Result<(
     Ok(())
 }
 
+#[cfg(test)]
+mod repl_why_not_tests {
+    use super::*;
+
+    #[test]
+    fn abduction_proposals_name_endpoints_and_keep_low_confidence() {
+        let mut db = axiograph_pathdb::PathDB::new();
+        let alice = db.add_entity("Person", vec![("name", "alice")]);
+        let bob = db.add_entity("Person", vec![("name", "bob")]);
+        let acme = db.add_entity("Company", vec![("name", "acme")]);
+        db.add_relation("worksAt", alice, acme, 1.0, Vec::new());
+        db.build_indexes();
+
+        let config = axiograph_pathdb::AbductionConfig::default();
+        let abduction = db.abduce_reachability(bob, acme, &config).unwrap();
+        let file = abduction_proposals(&db, &abduction);
+        assert_eq!(file.source.source_type, "abduction");
+        let [axiograph_ingest_docs::ProposalV1::Relation {
+            meta,
+            rel_type,
+            source,
+            target,
+            ..
+        }] = &file.proposals[..]
+        else {
+            panic!("expected one relation proposal");
+        };
+        assert_eq!(
+            (rel_type.as_str(), source.as_str(), target.as_str()),
+            ("worksAt", "bob", "acme")
+        );
+        assert_eq!(meta.confidence, config.confidence as f64);
+    }
+}
+
 #[cfg(test)]
 mod repl_tokenize_tests {
     use super::*;
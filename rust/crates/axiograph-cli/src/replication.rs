@@ -0,0 +1,824 @@
+//! Read-replica synchronization by shipping accepted-plane / PathDB WAL deltas.
+//!
+//! Motivation
+//! ----------
+//! `db serve --role replica` originally assumed the replica could see the
+//! master's store directory (shared filesystem, or `db accept sync` copies) and
+//! simply watched `HEAD`.
+//! For replicas on other machines, the ingest node instead *ships* what
+//! changed: both logs are append-only, and everything they reference is
+//! content-addressed, so a delta is just
+//! - the new log lines (`accepted_plane.log.jsonl`, `pathdb/pathdb_wal.log.jsonl`), and
+//! - the immutable objects those events introduced (snapshot manifests,
+//!   `.axi` modules, WAL blobs, quality reports, constraint certificates).
+//!
+//! Versions
+//! --------
+//! A store's version is a [`VersionVector`]: the number of events in each log.
+//! Replicas apply deltas in order (objects, then `HEAD`, then log lines), so a
+//! replica directory is always a prefix of the master's and a partially
+//! applied delta is simply re-shipped. The version of a *loaded* snapshot is
+//! the log position of the snapshots it was built from, which is what
+//! "at least version N" reads compare against.
+//!
+//! Derived state (`.axpd` checkpoints, index sidecars) is never shipped;
+//! replicas rebuild it from the accepted plane + WAL ops.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Component, Path};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::accepted_plane::AcceptedPlaneEventV1;
+use crate::pathdb_wal::{PathDbWalEventV1, PathDbWalOpV1};
+
+pub(crate) const REPLICATION_DELTA_VERSION_V1: &str = "axiograph_replication_delta_v1";
+
+/// Default cap on events shipped per log in one delta.
+pub(crate) const DEFAULT_MAX_EVENTS: usize = 256;
+
+/// Top-level directories replicas may fetch objects from.
+const SHIPPABLE_ROOTS: &[&str] = &["modules", "snapshots", "quality", "certs", "pathdb"];
+const SHIPPABLE_PATHDB_DIRS: &[&str] = &["snapshots", "blobs"];
+
+/// Per-log event counts of a store (or log positions of a loaded snapshot).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct VersionVector {
+    pub accepted: u64,
+    pub pathdb: u64,
+}
+
+impl VersionVector {
+    /// Whether `self` is at least `other` in every component.
+    pub(crate) fn dominates(&self, other: &VersionVector) -> bool {
+        self.accepted >= other.accepted && self.pathdb >= other.pathdb
+    }
+
+    /// Events `self` is missing relative to `upstream`, per log.
+    pub(crate) fn lag_behind(&self, upstream: &VersionVector) -> VersionVector {
+        VersionVector {
+            accepted: upstream.accepted.saturating_sub(self.accepted),
+            pathdb: upstream.pathdb.saturating_sub(self.pathdb),
+        }
+    }
+
+    /// Parse `accepted=N,pathdb=M` (either component may be omitted, meaning 0).
+    pub(crate) fn parse(s: &str) -> Result<Self> {
+        let mut out = VersionVector::default();
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| anyhow!("invalid version `{s}` (expected accepted=N,pathdb=M)"))?;
+            let value: u64 = value
+                .trim()
+                .parse()
+                .map_err(|_| anyhow!("invalid version component `{part}`"))?;
+            match key.trim() {
+                "accepted" => out.accepted = value,
+                "pathdb" => out.pathdb = value,
+                other => return Err(anyhow!("unknown version component `{other}`")),
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl fmt::Display for VersionVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "accepted={},pathdb={}", self.accepted, self.pathdb)
+    }
+}
+
+/// An immutable file a delta depends on (path relative to the store dir).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ReplicationObjectV1 {
+    pub path: String,
+    /// `fnv1a64` digest of the file bytes (transfer integrity check).
+    pub digest: String,
+    pub size: u64,
+}
+
+/// Everything a replica at `from` needs to reach `to`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ReplicationDeltaV1 {
+    pub version: String,
+    pub from: VersionVector,
+    pub to: VersionVector,
+    /// The upstream store version when the delta was built (`to` may trail it
+    /// when `max_events` truncated the delta).
+    pub head: VersionVector,
+    /// Raw `accepted_plane.log.jsonl` lines, in order.
+    pub accepted_events: Vec<String>,
+    /// Raw `pathdb_wal.log.jsonl` lines, in order.
+    pub pathdb_events: Vec<String>,
+    pub objects: Vec<ReplicationObjectV1>,
+}
+
+impl ReplicationDeltaV1 {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.accepted_events.is_empty() && self.pathdb_events.is_empty()
+    }
+}
+
+/// Last-event snapshot ids a replica sends so the master can detect divergence.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct LogTips {
+    pub accepted: Option<String>,
+    pub pathdb: Option<String>,
+}
+
+fn read_log_lines(path: &Path) -> Result<Vec<String>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let text = fs::read_to_string(path)
+        .map_err(|e| anyhow!("failed to read log `{}`: {e}", path.display()))?;
+    Ok(text
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.to_string())
+        .collect())
+}
+
+fn event_snapshot_id(line: &str) -> Result<String> {
+    let v: serde_json::Value =
+        serde_json::from_str(line).map_err(|e| anyhow!("invalid log line: {e}"))?;
+    v.get("snapshot_id")
+        .and_then(|s| s.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow!("log line has no `snapshot_id`"))
+}
+
+/// The current version of a store directory.
+pub(crate) fn store_version(dir: &Path) -> Result<VersionVector> {
+    Ok(VersionVector {
+        accepted: read_log_lines(&crate::accepted_plane::log_path(dir))?.len() as u64,
+        pathdb: read_log_lines(&crate::pathdb_wal::log_path(dir))?.len() as u64,
+    })
+}
+
+/// Snapshot ids of the last event in each log.
+pub(crate) fn log_tips(dir: &Path) -> Result<LogTips> {
+    let last = |lines: Vec<String>| lines.last().map(|l| event_snapshot_id(l)).transpose();
+    Ok(LogTips {
+        accepted: last(read_log_lines(&crate::accepted_plane::log_path(dir))?)?,
+        pathdb: last(read_log_lines(&crate::pathdb_wal::log_path(dir))?)?,
+    })
+}
+
+/// Log positions (1-based, 0 = absent) of the snapshots a server loaded.
+pub(crate) fn snapshot_version(
+    dir: &Path,
+    accepted_snapshot_id: Option<&str>,
+    pathdb_snapshot_id: Option<&str>,
+) -> Result<VersionVector> {
+    fn position(lines: &[String], id: Option<&str>) -> u64 {
+        let Some(id) = id else {
+            return 0;
+        };
+        lines
+            .iter()
+            .rposition(|l| event_snapshot_id(l).is_ok_and(|s| s == id))
+            .map(|i| i as u64 + 1)
+            .unwrap_or(0)
+    }
+    Ok(VersionVector {
+        accepted: position(
+            &read_log_lines(&crate::accepted_plane::log_path(dir))?,
+            accepted_snapshot_id,
+        ),
+        pathdb: position(
+            &read_log_lines(&crate::pathdb_wal::log_path(dir))?,
+            pathdb_snapshot_id,
+        ),
+    })
+}
+
+/// Resolve a shippable object path, rejecting anything outside the store's
+/// immutable directories.
+fn object_path(dir: &Path, rel: &str) -> Result<std::path::PathBuf> {
+    let rel_path = Path::new(rel);
+    let parts: Vec<&str> = rel_path
+        .components()
+        .map(|c| match c {
+            Component::Normal(s) => s.to_str().ok_or_else(|| anyhow!("non-utf8 path")),
+            _ => Err(anyhow!(
+                "object path `{rel}` must be relative and normalized"
+            )),
+        })
+        .collect::<Result<_>>()?;
+    let allowed = match parts.as_slice() {
+        ["pathdb", sub, _, ..] => SHIPPABLE_PATHDB_DIRS.contains(sub),
+        ["pathdb", ..] => false,
+        [root, _, ..] => SHIPPABLE_ROOTS.contains(root),
+        _ => false,
+    };
+    if !allowed {
+        return Err(anyhow!("object path `{rel}` is not replicated"));
+    }
+    Ok(dir.join(rel_path))
+}
+
+/// Read a shippable object from a store directory.
+pub(crate) fn read_object(dir: &Path, rel: &str) -> Result<Vec<u8>> {
+    let path = object_path(dir, rel)?;
+    fs::read(&path).map_err(|e| anyhow!("failed to read object `{rel}`: {e}"))
+}
+
+fn rel_to(dir: &Path, path: &Path) -> String {
+    path.strip_prefix(dir)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Objects introduced by an accepted-plane event (`required`, `optional`).
+fn accepted_event_objects(dir: &Path, line: &str) -> Result<(Vec<String>, Vec<String>)> {
+    let ev: AcceptedPlaneEventV1 =
+        serde_json::from_str(line).map_err(|e| anyhow!("invalid accepted-plane log line: {e}"))?;
+    let manifest = crate::accepted_plane::snapshot_manifest_path(dir, &ev.snapshot_id);
+    let mut required = vec![rel_to(dir, &manifest), ev.stored_module_path];
+    required.extend(ev.quality_report_path);
+    required.extend(ev.constraints_cert_path);
+    Ok((required, Vec::new()))
+}
+
+/// Objects introduced by a PathDB WAL event (`required`, `optional`).
+fn pathdb_event_objects(dir: &Path, line: &str) -> Result<(Vec<String>, Vec<String>)> {
+    let ev: PathDbWalEventV1 =
+        serde_json::from_str(line).map_err(|e| anyhow!("invalid pathdb wal log line: {e}"))?;
+    let manifest = crate::pathdb_wal::snapshot_manifest_path(dir, &ev.snapshot_id);
+    let mut required = vec![rel_to(dir, &manifest)];
+    let mut optional = Vec::new();
+    for op in ev.ops_appended {
+        let stored_path = match op {
+            PathDbWalOpV1::ImportChunksV1 { stored_path, .. }
+            | PathDbWalOpV1::ImportEmbeddingsV1 { stored_path, .. }
+            | PathDbWalOpV1::ImportProposalsV1 { stored_path, .. } => stored_path,
+        };
+        // Derived CBOR replay sidecars (`<digest>.chunks.cbor`, ...), if any.
+        let sidecar = Path::new(&stored_path).with_extension("cbor");
+        if sidecar != Path::new(&stored_path) {
+            optional.push(sidecar.to_string_lossy().to_string());
+        }
+        required.push(stored_path);
+    }
+    Ok((required, optional))
+}
+
+/// Build the delta taking a replica at `since` toward the current store head.
+///
+/// `tips` (the replica's last-event snapshot ids) guard against divergence: a
+/// replica whose logs are not a prefix of ours gets an error, not a delta.
+pub(crate) fn build_delta(
+    dir: &Path,
+    since: VersionVector,
+    tips: &LogTips,
+    max_events: usize,
+) -> Result<ReplicationDeltaV1> {
+    let accepted_lines = read_log_lines(&crate::accepted_plane::log_path(dir))?;
+    let pathdb_lines = read_log_lines(&crate::pathdb_wal::log_path(dir))?;
+    let head = VersionVector {
+        accepted: accepted_lines.len() as u64,
+        pathdb: pathdb_lines.len() as u64,
+    };
+    if !head.dominates(&since) {
+        return Err(anyhow!(
+            "replica version ({since}) is ahead of upstream ({head}); the stores have diverged"
+        ));
+    }
+    check_tip(
+        "accepted",
+        &accepted_lines,
+        since.accepted,
+        tips.accepted.as_deref(),
+    )?;
+    check_tip(
+        "pathdb",
+        &pathdb_lines,
+        since.pathdb,
+        tips.pathdb.as_deref(),
+    )?;
+
+    let max_events = max_events.max(1);
+    let take = |lines: &[String], from: u64| -> Vec<String> {
+        lines
+            .iter()
+            .skip(from as usize)
+            .take(max_events)
+            .cloned()
+            .collect()
+    };
+    let accepted_events = take(&accepted_lines, since.accepted);
+    let pathdb_events = take(&pathdb_lines, since.pathdb);
+
+    let mut required: BTreeSet<String> = BTreeSet::new();
+    let mut optional: BTreeSet<String> = BTreeSet::new();
+    for line in &accepted_events {
+        let (r, o) = accepted_event_objects(dir, line)?;
+        required.extend(r);
+        optional.extend(o);
+    }
+    for line in &pathdb_events {
+        let (r, o) = pathdb_event_objects(dir, line)?;
+        required.extend(r);
+        optional.extend(o);
+    }
+
+    let mut objects = Vec::new();
+    for rel in &required {
+        let path = object_path(dir, rel)?;
+        let bytes = fs::read(&path)
+            .map_err(|e| anyhow!("replicated object `{rel}` is missing upstream: {e}"))?;
+        objects.push(describe_object(rel, &bytes));
+    }
+    for rel in optional.difference(&required) {
+        if let Ok(bytes) = object_path(dir, rel).and_then(|p| Ok(fs::read(p)?)) {
+            objects.push(describe_object(rel, &bytes));
+        }
+    }
+
+    Ok(ReplicationDeltaV1 {
+        version: REPLICATION_DELTA_VERSION_V1.to_string(),
+        from: since,
+        to: VersionVector {
+            accepted: since.accepted + accepted_events.len() as u64,
+            pathdb: since.pathdb + pathdb_events.len() as u64,
+        },
+        head,
+        accepted_events,
+        pathdb_events,
+        objects,
+    })
+}
+
+fn check_tip(log: &str, lines: &[String], position: u64, tip: Option<&str>) -> Result<()> {
+    let (Some(tip), Some(index)) = (tip, (position as usize).checked_sub(1)) else {
+        return Ok(());
+    };
+    let ours = event_snapshot_id(&lines[index])?;
+    if ours != tip {
+        return Err(anyhow!(
+            "replica {log} log diverged at event {position}: replica has `{tip}`, upstream has `{ours}`"
+        ));
+    }
+    Ok(())
+}
+
+fn describe_object(rel: &str, bytes: &[u8]) -> ReplicationObjectV1 {
+    ReplicationObjectV1 {
+        path: rel.to_string(),
+        digest: axiograph_dsl::digest::fnv1a64_digest_bytes(bytes),
+        size: bytes.len() as u64,
+    }
+}
+
+/// Apply `delta` to a replica store directory, fetching missing objects via
+/// `fetch(path)`. Returns the new store version.
+///
+/// Objects land first, then `HEAD`, then the log lines, so an interrupted
+/// apply leaves the store at its old version and the delta can be re-shipped.
+pub(crate) fn apply_delta(
+    dir: &Path,
+    delta: &ReplicationDeltaV1,
+    fetch: &mut dyn FnMut(&str) -> Result<Vec<u8>>,
+) -> Result<VersionVector> {
+    if delta.version != REPLICATION_DELTA_VERSION_V1 {
+        return Err(anyhow!(
+            "unsupported replication delta version `{}`",
+            delta.version
+        ));
+    }
+    crate::accepted_plane::init_accepted_plane_dir(dir)?;
+    crate::pathdb_wal::init_pathdb_wal_dir(dir)?;
+    let local = store_version(dir)?;
+    if local != delta.from {
+        return Err(anyhow!(
+            "replica is at {local}, but the delta starts at {}",
+            delta.from
+        ));
+    }
+    let expected_to = VersionVector {
+        accepted: delta.from.accepted + delta.accepted_events.len() as u64,
+        pathdb: delta.from.pathdb + delta.pathdb_events.len() as u64,
+    };
+    if expected_to != delta.to {
+        return Err(anyhow!(
+            "malformed delta: {} events from {} do not reach {}",
+            delta.accepted_events.len() + delta.pathdb_events.len(),
+            delta.from,
+            delta.to
+        ));
+    }
+
+    for object in &delta.objects {
+        let path = object_path(dir, &object.path)?;
+        if path.exists() {
+            let existing = fs::read(&path)?;
+            if axiograph_dsl::digest::fnv1a64_digest_bytes(&existing) != object.digest {
+                return Err(anyhow!(
+                    "replica object `{}` exists with different contents",
+                    object.path
+                ));
+            }
+            continue;
+        }
+        let bytes = fetch(&object.path)?;
+        let digest = axiograph_dsl::digest::fnv1a64_digest_bytes(&bytes);
+        if digest != object.digest || bytes.len() as u64 != object.size {
+            return Err(anyhow!(
+                "object `{}` failed integrity check (expected {}, got {digest})",
+                object.path,
+                object.digest
+            ));
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("replica.tmp");
+        fs::write(&tmp, &bytes)?;
+        fs::rename(&tmp, &path)?;
+    }
+
+    if let Some(line) = delta.accepted_events.last() {
+        crate::accepted_plane::write_head(dir, &event_snapshot_id(line)?)?;
+    }
+    if let Some(line) = delta.pathdb_events.last() {
+        crate::pathdb_wal::write_pathdb_head(dir, &event_snapshot_id(line)?)?;
+    }
+    append_lines(
+        &crate::accepted_plane::log_path(dir),
+        &delta.accepted_events,
+    )?;
+    append_lines(&crate::pathdb_wal::log_path(dir), &delta.pathdb_events)?;
+
+    store_version(dir)
+}
+
+fn append_lines(path: &Path, lines: &[String]) -> Result<()> {
+    if lines.is_empty() {
+        return Ok(());
+    }
+    let mut f = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| anyhow!("failed to open log `{}`: {e}", path.display()))?;
+    let mut buf = String::new();
+    for line in lines {
+        buf.push_str(line);
+        buf.push('\n');
+    }
+    f.write_all(buf.as_bytes())?;
+    Ok(())
+}
+
+/// Result of one [`sync_from_upstream`] round.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct SyncOutcomeV1 {
+    pub events_applied: u64,
+    pub objects_fetched: usize,
+    pub local: VersionVector,
+    pub upstream: VersionVector,
+}
+
+/// Pull deltas from `upstream` (a `db serve` base URL) until `dir` reaches
+/// the upstream head observed by the first request.
+#[cfg(feature = "replication")]
+pub(crate) fn sync_from_upstream(upstream: &str, dir: &Path) -> Result<SyncOutcomeV1> {
+    let base = upstream.trim_end_matches('/');
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(120))
+        .build()
+        .map_err(|e| anyhow!("failed to build http client: {e}"))?;
+
+    crate::accepted_plane::init_accepted_plane_dir(dir)?;
+    crate::pathdb_wal::init_pathdb_wal_dir(dir)?;
+    let mut outcome = SyncOutcomeV1 {
+        events_applied: 0,
+        objects_fetched: 0,
+        local: store_version(dir)?,
+        upstream: VersionVector::default(),
+    };
+    loop {
+        let tips = log_tips(dir)?;
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        query.append_pair("accepted", &outcome.local.accepted.to_string());
+        query.append_pair("pathdb", &outcome.local.pathdb.to_string());
+        if let Some(tip) = tips.accepted.as_deref() {
+            query.append_pair("accepted_tip", tip);
+        }
+        if let Some(tip) = tips.pathdb.as_deref() {
+            query.append_pair("pathdb_tip", tip);
+        }
+        let url = format!("{base}/replication/delta?{}", query.finish());
+        let resp = client
+            .get(&url)
+            .send()
+            .map_err(|e| anyhow!("replication delta request failed: {e}"))?;
+        let status = resp.status();
+        if !status.is_success() {
+            let text = resp.text().unwrap_or_default();
+            return Err(anyhow!("upstream returned {status} for delta: {text}"));
+        }
+        let delta: ReplicationDeltaV1 = resp
+            .json()
+            .map_err(|e| anyhow!("invalid replication delta: {e}"))?;
+        outcome.upstream = delta.head;
+        if delta.is_empty() {
+            return Ok(outcome);
+        }
+
+        let mut fetched = 0usize;
+        let local = apply_delta(dir, &delta, &mut |path| {
+            let mut query = url::form_urlencoded::Serializer::new(String::new());
+            query.append_pair("path", path);
+            let url = format!("{base}/replication/object?{}", query.finish());
+            let resp = client
+                .get(&url)
+                .send()
+                .map_err(|e| anyhow!("replication object request failed: {e}"))?;
+            let status = resp.status();
+            if !status.is_success() {
+                return Err(anyhow!("upstream returned {status} for object `{path}`"));
+            }
+            fetched += 1;
+            Ok(resp
+                .bytes()
+                .map_err(|e| anyhow!("failed to read object `{path}`: {e}"))?
+                .to_vec())
+        })?;
+        outcome.events_applied +=
+            (local.accepted - outcome.local.accepted) + (local.pathdb - outcome.local.pathdb);
+        outcome.objects_fetched += fetched;
+        outcome.local = local;
+        if local.dominates(&delta.head) {
+            return Ok(outcome);
+        }
+    }
+}
+
+#[cfg(not(feature = "replication"))]
+pub(crate) fn sync_from_upstream(_upstream: &str, _dir: &Path) -> Result<SyncOutcomeV1> {
+    Err(anyhow!(
+        "replication support not compiled (enable `axiograph-cli` feature `replication`)"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::*;
+
+    struct TempDirGuard {
+        path: PathBuf,
+    }
+
+    impl TempDirGuard {
+        fn new(prefix: &str) -> Self {
+            let ts = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            let pid = std::process::id();
+            let path = std::env::temp_dir().join(format!("{prefix}_{pid}_{ts}"));
+            fs::create_dir_all(&path).expect("create temp dir");
+            Self { path }
+        }
+    }
+
+    impl Drop for TempDirGuard {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+
+    const MODULE_V1: &str = r#"module Test
+
+schema S:
+  object Person
+  relation Parent(parent: Person, child: Person)
+
+instance I of S:
+  Person = {Alice, Bob}
+  Parent = {
+    (parent=Alice, child=Bob)
+  }
+"#;
+
+    /// Master store with two accepted promotions and one PathDB commit.
+    fn seed_master(dir: &Path, scratch: &Path) {
+        let axi_path = scratch.join("Test.axi");
+        fs::write(&axi_path, MODULE_V1).expect("write .axi");
+        let accepted = crate::accepted_plane::promote_reviewed_module(
+            &axi_path,
+            dir,
+            Some("test: promote v1"),
+            "off",
+        )
+        .expect("promote v1");
+
+        let chunks_path = scratch.join("chunks.json");
+        fs::write(
+            &chunks_path,
+            r#"[{"chunk_id":"doc_0","document_id":"Test.axi","page":null,"span_id":"p0",
+                 "text":"Alice is Bob's parent.","bbox":null,"metadata":{}}]"#,
+        )
+        .expect("write chunks.json");
+        crate::pathdb_wal::commit_pathdb_snapshot_with_overlays(
+            dir,
+            &accepted,
+            &[chunks_path],
+            &[],
+            Some("test: commit chunks"),
+        )
+        .expect("commit chunks");
+
+        fs::write(
+            &axi_path,
+            MODULE_V1.replace("{Alice, Bob}", "{Alice, Bob, Carol}"),
+        )
+        .expect("write .axi v2");
+        crate::accepted_plane::promote_reviewed_module(
+            &axi_path,
+            dir,
+            Some("test: promote v2"),
+            "off",
+        )
+        .expect("promote v2");
+    }
+
+    /// Pull from `master` into `replica` in deltas of at most `max_events`.
+    fn replicate(master: &Path, replica: &Path, max_events: usize) -> (usize, usize) {
+        let (mut rounds, mut fetched) = (0, 0);
+        loop {
+            let since = store_version(replica).expect("replica version");
+            let tips = log_tips(replica).expect("replica tips");
+            let delta = build_delta(master, since, &tips, max_events).expect("build delta");
+            if delta.is_empty() {
+                return (rounds, fetched);
+            }
+            let to = apply_delta(replica, &delta, &mut |path| {
+                fetched += 1;
+                read_object(master, path)
+            })
+            .expect("apply delta");
+            assert_eq!(to, delta.to);
+            rounds += 1;
+        }
+    }
+
+    #[test]
+    fn deltas_bring_an_empty_replica_to_the_master_head() {
+        let tmp = TempDirGuard::new("axiograph_replication_catch_up_test");
+        let (master, replica) = (tmp.path.join("master"), tmp.path.join("replica"));
+        seed_master(&master, &tmp.path);
+        let head = store_version(&master).expect("master version");
+        assert_eq!(
+            head,
+            VersionVector {
+                accepted: 2,
+                pathdb: 1
+            }
+        );
+
+        let (rounds, fetched) = replicate(&master, &replica, 1);
+        assert_eq!(rounds, 2, "max_events=1 splits the two accepted events");
+        assert!(fetched > 0);
+        assert_eq!(store_version(&replica).expect("replica version"), head);
+        for log in [
+            crate::accepted_plane::log_path(&master),
+            crate::pathdb_wal::log_path(&master),
+        ] {
+            let rel = log.strip_prefix(&master).expect("relative log path");
+            assert_eq!(
+                fs::read_to_string(&log).expect("master log"),
+                fs::read_to_string(replica.join(rel)).expect("replica log")
+            );
+        }
+        assert_eq!(log_tips(&replica).unwrap(), log_tips(&master).unwrap());
+
+        // The replica rebuilds the PathDB head from shipped objects alone.
+        let snap = crate::pathdb_wal::read_pathdb_snapshot_for_cli(&replica, "head")
+            .expect("replica pathdb head");
+        let out = tmp.path.join("replica.axpd");
+        crate::pathdb_wal::build_pathdb_from_pathdb_snapshot(&replica, &snap.snapshot_id, &out)
+            .expect("build replica pathdb");
+        let db = axiograph_pathdb::PathDB::from_bytes(&fs::read(&out).unwrap()).unwrap();
+        assert!(db.find_by_type("DocChunk").is_some_and(|c| !c.is_empty()));
+
+        // The PathDB head derives from the first promotion.
+        let loaded = snapshot_version(
+            &replica,
+            Some(&snap.accepted_snapshot_id),
+            Some(&snap.snapshot_id),
+        )
+        .expect("snapshot version");
+        assert_eq!(
+            loaded,
+            VersionVector {
+                accepted: 1,
+                pathdb: 1
+            }
+        );
+
+        // Caught up: nothing more to ship, and re-syncing fetches nothing.
+        assert_eq!(replicate(&master, &replica, 8), (0, 0));
+    }
+
+    #[test]
+    fn divergence_corruption_and_foreign_paths_are_rejected() {
+        let tmp = TempDirGuard::new("axiograph_replication_reject_test");
+        let (master, replica) = (tmp.path.join("master"), tmp.path.join("replica"));
+        seed_master(&master, &tmp.path);
+
+        // A corrupted object aborts the apply without advancing the replica.
+        let delta = build_delta(&master, VersionVector::default(), &LogTips::default(), 8)
+            .expect("build delta");
+        let err = apply_delta(&replica, &delta, &mut |path| {
+            let mut bytes = read_object(&master, path)?;
+            bytes.push(b'\n');
+            Ok(bytes)
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("integrity"), "{err}");
+        assert_eq!(store_version(&replica).unwrap(), VersionVector::default());
+
+        replicate(&master, &replica, 8);
+        let head = store_version(&replica).unwrap();
+
+        // Stale deltas, foreign tips and replicas ahead of upstream.
+        let stale = build_delta(&master, VersionVector::default(), &LogTips::default(), 8)
+            .expect("build delta");
+        assert!(apply_delta(&replica, &stale, &mut |p| read_object(&master, p)).is_err());
+        let foreign = LogTips {
+            accepted: Some("fnv1a64:not_ours".to_string()),
+            pathdb: None,
+        };
+        assert!(build_delta(&master, head, &foreign, 8)
+            .unwrap_err()
+            .to_string()
+            .contains("diverged"));
+        let ahead = VersionVector {
+            accepted: head.accepted + 1,
+            pathdb: head.pathdb,
+        };
+        assert!(build_delta(&master, ahead, &LogTips::default(), 8).is_err());
+
+        for path in [
+            "../master/HEAD",
+            "/etc/passwd",
+            "HEAD",
+            "accepted_plane.log.jsonl",
+            "pathdb/HEAD",
+            "pathdb/checkpoints/x.axpd",
+        ] {
+            assert!(read_object(&master, path).is_err(), "{path}");
+        }
+    }
+
+    #[test]
+    fn version_vectors_parse_compare_and_render() {
+        let v = VersionVector::parse("accepted=3, pathdb=7").unwrap();
+        assert_eq!(
+            v,
+            VersionVector {
+                accepted: 3,
+                pathdb: 7
+            }
+        );
+        assert_eq!(v.to_string(), "accepted=3,pathdb=7");
+        assert_eq!(VersionVector::parse(&v.to_string()).unwrap(), v);
+        assert_eq!(
+            VersionVector::parse("pathdb=2").unwrap(),
+            VersionVector {
+                accepted: 0,
+                pathdb: 2
+            }
+        );
+        assert!(VersionVector::parse("pathdb").is_err());
+        assert!(VersionVector::parse("wal=1").is_err());
+
+        let behind = VersionVector {
+            accepted: 3,
+            pathdb: 5,
+        };
+        assert!(v.dominates(&behind) && !behind.dominates(&v));
+        assert_eq!(
+            behind.lag_behind(&v),
+            VersionVector {
+                accepted: 0,
+                pathdb: 2
+            }
+        );
+        assert_eq!(v.lag_behind(&behind), VersionVector::default());
+    }
+}
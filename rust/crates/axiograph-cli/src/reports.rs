@@ -0,0 +1,715 @@
+//! Scheduled reports over saved queries (`axiograph db report`).
+//!
+//! A report plan (`report_plan_v1`) names a set of reports; each report is a
+//! list of AxQL queries. A query may be a saved query reference
+//! (`view open_payments`) resolved against the snapshot's `.axi` meta-plane.
+//!
+//! Each pass loads one snapshot (from a snapshot store or a `.axpd` file) and
+//! runs the reports that are **due**:
+//!
+//! - never run before,
+//! - the snapshot version changed since their last run (the trigger), or
+//! - their `every` schedule elapsed (`@hourly`, `@daily`, `@weekly`, `6h`, …).
+//!
+//! A run renders a Markdown and a JSON artifact to
+//! `<out_dir>/<report>/<snapshot_id>.{md,json}`: every result row with the
+//! evidence chunks its bound entities cite, plus the snapshot version it ran
+//! against. Run state (`report_state_v1`) lives in `<out_dir>/report_state.json`.
+//! `--watch` keeps polling for new snapshots and due schedules.
+
+use anyhow::{anyhow, Context, Result};
+use axiograph_pathdb::axi_semantics::MetaPlaneIndex;
+use axiograph_pathdb::PathDB;
+use clap::Args;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::ingest_refresh::{parse_schedule, unix_now};
+
+pub const REPORT_PLAN_VERSION_V1: &str = "report_plan_v1";
+pub const REPORT_STATE_VERSION_V1: &str = "report_state_v1";
+pub const REPORT_VERSION_V1: &str = "report_v1";
+
+#[derive(Args, Debug, Clone)]
+pub struct ReportArgs {
+    /// Report plan JSON (`report_plan_v1`).
+    #[arg(long)]
+    pub plan: PathBuf,
+
+    /// Snapshot store directory (accepted plane + PathDB WAL).
+    #[arg(long, conflicts_with = "axpd")]
+    pub dir: Option<PathBuf>,
+
+    /// Which store layer to report on: `pathdb` (WAL head) or `accepted` (canonical head).
+    #[arg(long, default_value = "pathdb")]
+    pub layer: String,
+
+    /// Snapshot id (or `head`/`latest`) when loading from `--dir`.
+    #[arg(long, default_value = "head")]
+    pub snapshot: String,
+
+    /// Report on a `.axpd` snapshot file; its content digest is the snapshot version.
+    #[arg(long)]
+    pub axpd: Option<PathBuf>,
+
+    /// Artifact directory (default: `<dir>/reports`; required with `--axpd`).
+    #[arg(long)]
+    pub out_dir: Option<PathBuf>,
+
+    /// Run every report now, ignoring schedules and triggers.
+    #[arg(long)]
+    pub force: bool,
+
+    /// Keep running, re-checking the snapshot and schedules.
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Polling interval for `--watch`.
+    #[arg(long, default_value_t = 30)]
+    pub poll_interval_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportPlanV1 {
+    pub version: String,
+    pub reports: Vec<ReportDefV1>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportDefV1 {
+    pub name: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Optional schedule (`@daily`, `6h`, …). Without one, the report runs
+    /// only when the snapshot version changes.
+    #[serde(default)]
+    pub every: Option<String>,
+    pub queries: Vec<ReportQueryV1>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportQueryV1 {
+    pub name: String,
+    /// AxQL text, or `view <name>` for a saved query.
+    pub query: String,
+}
+
+impl ReportPlanV1 {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read report plan {}", path.display()))?;
+        let plan: Self = serde_json::from_str(&text)
+            .with_context(|| format!("failed to parse report plan {}", path.display()))?;
+        plan.validate()?;
+        Ok(plan)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.version != REPORT_PLAN_VERSION_V1 {
+            return Err(anyhow!(
+                "unsupported report plan version `{}` (expected `{REPORT_PLAN_VERSION_V1}`)",
+                self.version
+            ));
+        }
+        let mut names = std::collections::BTreeSet::new();
+        for report in &self.reports {
+            if report.name.is_empty()
+                || !report
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(anyhow!(
+                    "invalid report name `{}` (use letters, digits, `_` or `-`)",
+                    report.name
+                ));
+            }
+            if !names.insert(report.name.as_str()) {
+                return Err(anyhow!("duplicate report `{}`", report.name));
+            }
+            if report.queries.is_empty() {
+                return Err(anyhow!("report `{}` has no queries", report.name));
+            }
+            if let Some(every) = &report.every {
+                parse_schedule(every)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Last run of one report.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportRunState {
+    /// Unix seconds.
+    pub last_run: u64,
+    /// Snapshot version the last run used.
+    pub snapshot: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportStateV1 {
+    pub version: String,
+    /// Keyed by report name.
+    #[serde(default)]
+    pub reports: BTreeMap<String, ReportRunState>,
+}
+
+impl Default for ReportStateV1 {
+    fn default() -> Self {
+        Self {
+            version: REPORT_STATE_VERSION_V1.to_string(),
+            reports: BTreeMap::new(),
+        }
+    }
+}
+
+impl ReportStateV1 {
+    pub fn load_or_default(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read report state {}", path.display()))?;
+        let state: Self = serde_json::from_str(&text)
+            .with_context(|| format!("failed to parse report state {}", path.display()))?;
+        if state.version != REPORT_STATE_VERSION_V1 {
+            return Err(anyhow!(
+                "unsupported report state version `{}` (expected `{REPORT_STATE_VERSION_V1}`)",
+                state.version
+            ));
+        }
+        Ok(state)
+    }
+}
+
+/// A loaded snapshot and the version reports are stored under.
+pub struct ReportSnapshot {
+    /// Store snapshot id, or the `.axpd` content digest.
+    pub id: String,
+    /// `pathdb`, `accepted`, or `axpd`.
+    pub layer: String,
+    pub db: PathDB,
+    pub meta: Option<MetaPlaneIndex>,
+}
+
+/// One rendered report run (the JSON artifact).
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportV1 {
+    pub version: String,
+    pub report: String,
+    pub title: String,
+    pub generated_at: u64,
+    pub snapshot: String,
+    pub layer: String,
+    pub queries: Vec<ReportQueryResultV1>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportQueryResultV1 {
+    pub name: String,
+    pub query: String,
+    /// The executed AxQL, when `query` was a saved query reference.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_query: Option<String>,
+    pub vars: Vec<String>,
+    pub rows: Vec<ReportRowV1>,
+    pub truncated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportRowV1 {
+    pub bindings: BTreeMap<String, ReportEntityV1>,
+    /// Evidence chunk ids cited by the bound entities.
+    pub evidence: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportEntityV1 {
+    pub id: u32,
+    pub entity_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Whether `report` should run against `snapshot` at `now`.
+fn is_due(
+    report: &ReportDefV1,
+    last: Option<&ReportRunState>,
+    snapshot: &str,
+    now: u64,
+) -> Result<bool> {
+    let Some(last) = last else {
+        return Ok(true);
+    };
+    if last.snapshot != snapshot {
+        return Ok(true);
+    }
+    let Some(every) = &report.every else {
+        return Ok(false);
+    };
+    Ok(now
+        >= last
+            .last_run
+            .saturating_add(parse_schedule(every)?.as_secs()))
+}
+
+/// Run one report query; a failing query is recorded, not fatal.
+fn run_query(snapshot: &ReportSnapshot, q: &ReportQueryV1) -> ReportQueryResultV1 {
+    let mut out = ReportQueryResultV1 {
+        name: q.name.clone(),
+        query: q.query.clone(),
+        resolved_query: None,
+        vars: Vec::new(),
+        rows: Vec::new(),
+        truncated: false,
+        error: None,
+    };
+    let db = &snapshot.db;
+    let meta = snapshot.meta.as_ref();
+    let result = crate::axql::resolve_axql_view_reference(meta, &q.query).and_then(|text| {
+        if text != q.query {
+            out.resolved_query = Some(text.clone());
+        }
+        let query = crate::axql::parse_axql_query(&text)?;
+        crate::axql::prepare_axql_query_with_meta(db, &query, meta)?.execute(db, meta)
+    });
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => {
+            out.error = Some(e.to_string());
+            return out;
+        }
+    };
+
+    out.truncated = result.truncated;
+    out.vars = result.selected_vars.clone();
+    for row in &result.rows {
+        let mut bindings = BTreeMap::new();
+        let mut evidence = std::collections::BTreeSet::new();
+        for (var, &id) in row {
+            if !out.vars.is_empty() && !out.vars.contains(var) {
+                continue;
+            }
+            let view = db.get_entity(id);
+            bindings.insert(
+                var.clone(),
+                ReportEntityV1 {
+                    id,
+                    entity_type: view
+                        .as_ref()
+                        .map(|v| v.entity_type.clone())
+                        .unwrap_or_default(),
+                    name: view.and_then(|v| v.attrs.get("name").cloned()),
+                },
+            );
+            evidence.extend(db.entity_evidence(id));
+        }
+        out.rows.push(ReportRowV1 {
+            bindings,
+            evidence: evidence.into_iter().collect(),
+        });
+    }
+    if out.vars.is_empty() {
+        out.vars = out
+            .rows
+            .first()
+            .map(|r| r.bindings.keys().cloned().collect())
+            .unwrap_or_default();
+    }
+    out
+}
+
+/// Run every due report in `plan` against `snapshot` at `now` (unix
+/// seconds), recording the runs in `state`.
+pub fn run_due_reports(
+    plan: &ReportPlanV1,
+    snapshot: &ReportSnapshot,
+    state: &mut ReportStateV1,
+    now: u64,
+    force: bool,
+) -> Result<Vec<ReportV1>> {
+    let mut out = Vec::new();
+    for report in &plan.reports {
+        if !force && !is_due(report, state.reports.get(&report.name), &snapshot.id, now)? {
+            continue;
+        }
+        out.push(ReportV1 {
+            version: REPORT_VERSION_V1.to_string(),
+            report: report.name.clone(),
+            title: report.title.clone().unwrap_or_else(|| report.name.clone()),
+            generated_at: now,
+            snapshot: snapshot.id.clone(),
+            layer: snapshot.layer.clone(),
+            queries: report
+                .queries
+                .iter()
+                .map(|q| run_query(snapshot, q))
+                .collect(),
+        });
+        state.reports.insert(
+            report.name.clone(),
+            ReportRunState {
+                last_run: now,
+                snapshot: snapshot.id.clone(),
+            },
+        );
+    }
+    Ok(out)
+}
+
+fn md_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Markdown rendering: one table per query, evidence cited by footnote
+/// number, the cited chunk ids listed at the end.
+pub fn render_report_markdown(report: &ReportV1) -> String {
+    let mut md = String::new();
+    let mut citations: Vec<&str> = Vec::new();
+    let _ = writeln!(md, "# {}\n", report.title);
+    let _ = writeln!(md, "- report: `{}`", report.report);
+    let _ = writeln!(md, "- snapshot: `{}` ({})", report.snapshot, report.layer);
+    let _ = writeln!(md, "- generated at: {} (unix seconds)", report.generated_at);
+
+    for q in &report.queries {
+        let _ = writeln!(md, "\n## {}\n", q.name);
+        let _ = writeln!(md, "`{}`", md_cell(&q.query));
+        if let Some(resolved) = &q.resolved_query {
+            let _ = writeln!(md, "\n→ `{}`", md_cell(resolved));
+        }
+        if let Some(error) = &q.error {
+            let _ = writeln!(md, "\n**error:** {}", md_cell(error));
+            continue;
+        }
+        if q.rows.is_empty() {
+            let _ = writeln!(md, "\n_no rows_");
+            continue;
+        }
+        let _ = writeln!(md, "\n| {} | evidence |", q.vars.join(" | "));
+        let _ = writeln!(md, "|{}---|", "---|".repeat(q.vars.len()));
+        for row in &q.rows {
+            let mut cells: Vec<String> = q
+                .vars
+                .iter()
+                .map(|var| match row.bindings.get(var) {
+                    Some(e) => md_cell(&match &e.name {
+                        Some(name) => format!("{name} ({})", e.entity_type),
+                        None => format!("#{} ({})", e.id, e.entity_type),
+                    }),
+                    None => String::new(),
+                })
+                .collect();
+            let refs: Vec<String> = row
+                .evidence
+                .iter()
+                .map(|chunk| {
+                    let n = match citations.iter().position(|c| c == chunk) {
+                        Some(i) => i + 1,
+                        None => {
+                            citations.push(chunk);
+                            citations.len()
+                        }
+                    };
+                    format!("[{n}]")
+                })
+                .collect();
+            cells.push(refs.join(" "));
+            let _ = writeln!(md, "| {} |", cells.join(" | "));
+        }
+        let _ = writeln!(
+            md,
+            "\n{} row(s){}",
+            q.rows.len(),
+            if q.truncated {
+                " (truncated by limit)"
+            } else {
+                ""
+            }
+        );
+    }
+
+    if !citations.is_empty() {
+        let _ = writeln!(md, "\n## Evidence\n");
+        for (i, chunk) in citations.iter().enumerate() {
+            let _ = writeln!(md, "[{}] `{}`", i + 1, md_cell(chunk));
+        }
+    }
+    md
+}
+
+/// Write `<out_dir>/<report>/<snapshot>.{md,json}`; returns the Markdown path.
+pub fn write_report(out_dir: &Path, report: &ReportV1) -> Result<PathBuf> {
+    let dir = out_dir.join(&report.report);
+    fs::create_dir_all(&dir)?;
+    let stem = crate::snapshot_id_filename(&report.snapshot);
+    let md = dir.join(format!("{stem}.md"));
+    fs::write(&md, render_report_markdown(report))?;
+    fs::write(
+        dir.join(format!("{stem}.json")),
+        serde_json::to_string_pretty(report)?,
+    )?;
+    Ok(md)
+}
+
+fn load_snapshot(args: &ReportArgs) -> Result<ReportSnapshot> {
+    let (id, layer, bytes) = if let Some(path) = &args.axpd {
+        let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        let id = axiograph_dsl::digest::fnv1a64_digest_bytes(&bytes);
+        (id, "axpd".to_string(), bytes)
+    } else {
+        let dir = args
+            .dir
+            .as_ref()
+            .ok_or_else(|| anyhow!("db report: pass `--dir <store>` or `--axpd <file>`"))?;
+        let tmp_dir = dir.join("pathdb").join("tmp");
+        fs::create_dir_all(&tmp_dir)?;
+        let tmp = tmp_dir.join("report_tmp.axpd");
+        let id = match args.layer.trim().to_ascii_lowercase().as_str() {
+            "accepted" => {
+                let id = crate::accepted_plane::resolve_snapshot_id_for_cli(dir, &args.snapshot)?;
+                crate::accepted_plane::build_pathdb_from_snapshot(dir, &id, &tmp)?;
+                id
+            }
+            "pathdb" => {
+                let snap = crate::pathdb_wal::read_pathdb_snapshot_for_cli(dir, &args.snapshot)?;
+                crate::pathdb_wal::build_pathdb_from_pathdb_snapshot(dir, &snap.snapshot_id, &tmp)?;
+                snap.snapshot_id
+            }
+            other => {
+                return Err(anyhow!(
+                    "unknown --layer `{other}` (expected accepted|pathdb)"
+                ))
+            }
+        };
+        let bytes = fs::read(&tmp)?;
+        let _ = fs::remove_file(&tmp);
+        (id, args.layer.trim().to_ascii_lowercase(), bytes)
+    };
+    let db = PathDB::from_bytes(&bytes)?;
+    let meta = MetaPlaneIndex::from_db(&db).ok();
+    Ok(ReportSnapshot {
+        id,
+        layer,
+        db,
+        meta,
+    })
+}
+
+/// Snapshot version `args` points at, without loading it.
+fn current_snapshot_id(args: &ReportArgs) -> Result<String> {
+    if let Some(path) = &args.axpd {
+        let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        return Ok(axiograph_dsl::digest::fnv1a64_digest_bytes(&bytes));
+    }
+    let dir = args
+        .dir
+        .as_ref()
+        .ok_or_else(|| anyhow!("db report: pass `--dir <store>` or `--axpd <file>`"))?;
+    if args.layer.trim().eq_ignore_ascii_case("accepted") {
+        crate::accepted_plane::resolve_snapshot_id_for_cli(dir, &args.snapshot)
+    } else {
+        Ok(crate::pathdb_wal::read_pathdb_snapshot_for_cli(dir, &args.snapshot)?.snapshot_id)
+    }
+}
+
+pub fn cmd_db_report(args: &ReportArgs) -> Result<()> {
+    let plan = ReportPlanV1::load(&args.plan)?;
+    let out_dir = match (&args.out_dir, &args.dir) {
+        (Some(out), _) => out.clone(),
+        (None, Some(dir)) => dir.join("reports"),
+        (None, None) => return Err(anyhow!("db report: `--out-dir` is required with `--axpd`")),
+    };
+    let state_path = out_dir.join("report_state.json");
+    let mut state = ReportStateV1::load_or_default(&state_path)?;
+
+    let mut force = args.force;
+    let mut loaded: Option<ReportSnapshot> = None;
+    loop {
+        let now = unix_now();
+        let id = current_snapshot_id(args)?;
+        if loaded.as_ref().is_none_or(|s| s.id != id) {
+            loaded = Some(load_snapshot(args)?);
+        }
+        let snapshot = loaded.as_ref().expect("snapshot loaded");
+
+        let reports = run_due_reports(&plan, snapshot, &mut state, now, force)?;
+        if !reports.is_empty() || !args.watch {
+            println!(
+                "{} {} of {} report(s) against {}",
+                "Reporting".green().bold(),
+                reports.len(),
+                plan.reports.len(),
+                snapshot.id
+            );
+        }
+        for report in &reports {
+            let path = write_report(&out_dir, report)?;
+            let failed = report.queries.iter().filter(|q| q.error.is_some()).count();
+            if failed > 0 {
+                println!(
+                    "  {} {} ({failed} query error(s))",
+                    "→".cyan(),
+                    path.display()
+                );
+            } else {
+                println!("  {} {}", "→".cyan(), path.display());
+            }
+        }
+        fs::create_dir_all(&out_dir)?;
+        fs::write(&state_path, serde_json::to_string_pretty(&state)?)?;
+
+        if !args.watch {
+            return Ok(());
+        }
+        force = false;
+        std::thread::sleep(Duration::from_secs(args.poll_interval_secs.max(1)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEDGER: &str = r#"
+module Payments
+
+schema Ledger:
+  object Payment
+  object Status
+  relation PaymentStatus(payment: Payment, status: Status)
+
+theory Reports on Ledger:
+  view open_payments = select ?p where ?f = PaymentStatus(payment=?p, status=open) limit 10
+
+instance I of Ledger:
+  Payment = {p1, p2}
+  Status = {open, settled}
+  PaymentStatus = {(payment=p1, status=open), (payment=p2, status=settled)}
+"#;
+
+    fn snapshot(id: &str) -> ReportSnapshot {
+        let mut db = PathDB::new();
+        axiograph_pathdb::axi_module_import::import_axi_schema_v1_into_pathdb(&mut db, LEDGER)
+            .expect("import ledger");
+        let p1 = db.find_by_type("Payment").unwrap().min().unwrap();
+        db.upsert_entity_attr(p1, "evidence_0_chunk_id", "doc_7#chunk_2")
+            .unwrap();
+        db.build_indexes();
+        let meta = MetaPlaneIndex::from_db(&db).ok();
+        ReportSnapshot {
+            id: id.to_string(),
+            layer: "pathdb".to_string(),
+            db,
+            meta,
+        }
+    }
+
+    fn plan() -> ReportPlanV1 {
+        serde_json::from_value(serde_json::json!({
+            "version": REPORT_PLAN_VERSION_V1,
+            "reports": [
+                {
+                    "name": "payments",
+                    "title": "Open payments",
+                    "every": "1h",
+                    "queries": [
+                        {"name": "open", "query": "view open_payments"},
+                        {"name": "broken", "query": "view missing"}
+                    ]
+                },
+                {
+                    "name": "statuses",
+                    "queries": [{"name": "all", "query": "select ?s where ?s is Status"}]
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn reports_run_on_new_snapshots_and_schedule() {
+        let plan = plan();
+        plan.validate().unwrap();
+        let mut state = ReportStateV1::default();
+
+        let s1 = snapshot("snap_1");
+        let runs = run_due_reports(&plan, &s1, &mut state, 1_000, false).unwrap();
+        assert_eq!(runs.len(), 2);
+
+        // Same snapshot, before the hourly schedule: nothing is due.
+        assert!(run_due_reports(&plan, &s1, &mut state, 2_000, false)
+            .unwrap()
+            .is_empty());
+
+        // Schedule elapsed: only the scheduled report runs.
+        let runs = run_due_reports(&plan, &s1, &mut state, 4_600, false).unwrap();
+        let names: Vec<&str> = runs.iter().map(|r| r.report.as_str()).collect();
+        assert_eq!(names, vec!["payments"]);
+
+        // A new snapshot triggers every report.
+        let s2 = snapshot("snap_2");
+        let runs = run_due_reports(&plan, &s2, &mut state, 4_700, false).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert!(runs.iter().all(|r| r.snapshot == "snap_2"));
+        assert_eq!(state.reports["statuses"].snapshot, "snap_2");
+    }
+
+    #[test]
+    fn report_artifacts_cite_evidence_and_snapshot() {
+        let s = snapshot("fnv1a64:abc");
+        let mut state = ReportStateV1::default();
+        let runs = run_due_reports(&plan(), &s, &mut state, 1_000, true).unwrap();
+        let report = &runs[0];
+
+        let open = &report.queries[0];
+        assert!(open.error.is_none(), "{:?}", open.error);
+        assert!(open
+            .resolved_query
+            .as_deref()
+            .unwrap()
+            .starts_with("select ?p"));
+        assert_eq!(open.rows.len(), 1);
+        assert_eq!(open.rows[0].bindings["?p"].name.as_deref(), Some("p1"));
+        assert_eq!(open.rows[0].evidence, vec!["doc_7#chunk_2".to_string()]);
+        assert!(report.queries[1].error.is_some());
+
+        let md = render_report_markdown(report);
+        assert!(md.starts_with("# Open payments"), "{md}");
+        assert!(md.contains("- snapshot: `fnv1a64:abc` (pathdb)"), "{md}");
+        assert!(md.contains("| p1 (Payment) | [1] |"), "{md}");
+        assert!(md.contains("[1] `doc_7#chunk_2`"), "{md}");
+        assert!(md.contains("**error:** unknown view `missing`"), "{md}");
+
+        let dir = std::env::temp_dir().join(format!("axiograph_reports_{}", std::process::id()));
+        let path = write_report(&dir, report).unwrap();
+        assert_eq!(path, dir.join("payments").join("fnv1a64_abc.md"));
+        let json: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(dir.join("payments").join("fnv1a64_abc.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json["snapshot"], "fnv1a64:abc");
+        assert_eq!(json["version"], REPORT_VERSION_V1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn plans_reject_bad_names_and_schedules() {
+        let mut plan = plan();
+        plan.reports[1].name = "payments".to_string();
+        assert!(plan.validate().is_err());
+        let mut plan = self::plan();
+        plan.reports[0].every = Some("soon".to_string());
+        assert!(plan.validate().is_err());
+        let mut plan = self::plan();
+        plan.reports[0].name = "../x".to_string();
+        assert!(plan.validate().is_err());
+    }
+}
@@ -8,8 +8,11 @@
 //! - a “write master / read replicas” deployment using rsync/NFS/object-store sync
 //! - offline replication by copying a directory
 //!
+//! For replicas without a shared filesystem, `db serve --replicate-from` ships
+//! log deltas over HTTP instead (see `replication.rs`).
+//!
 //! Future directions:
-//! - HTTP/object-store sync (`accept pull`, `accept serve`)
+//! - object-store sync
 //! - authenticated membership proofs for offline verification
 
 use anyhow::{anyhow, Context, Result};
@@ -0,0 +1,146 @@
+//! `axiograph db verify`: record that a reviewer checked a stored fact (see
+//! `axiograph_storage::verification`).
+//!
+//! The verification raises the fact's confidence by the configured rule,
+//! stamps the reviewer's id on a `UserEdit` change, and exempts the fact from
+//! confidence decay.
+
+use anyhow::{anyhow, Result};
+use axiograph_storage::{DeletionTarget, StorageConfig, UnifiedStorage, VerificationRule};
+use clap::Args;
+use colored::Colorize;
+use std::path::PathBuf;
+
+#[derive(Args, Debug, Clone)]
+pub struct VerifyArgs {
+    /// Knowledge directory (`.axi` files, `knowledge.axpd`, `changelog.json`).
+    pub dir: PathBuf,
+
+    /// Relation to verify, endpoints by name.
+    #[arg(
+        long,
+        num_args = 3,
+        value_names = ["REL", "SOURCE", "TARGET"],
+        conflicts_with = "entity",
+        required_unless_present = "entity"
+    )]
+    pub relation: Option<Vec<String>>,
+
+    /// Named fact to verify (entity or tacit-knowledge rule).
+    #[arg(long)]
+    pub entity: Option<String>,
+
+    /// Reviewer identity recorded on the change.
+    #[arg(long = "by")]
+    pub verifier: String,
+
+    /// Optional review note.
+    #[arg(long)]
+    pub note: Option<String>,
+
+    /// Confidence rule: `corroborate:W`, `at-least:C` or `set:C`
+    /// (default: `corroborate:0.9`).
+    #[arg(long, value_parser = parse_rule)]
+    pub rule: Option<VerificationRule>,
+}
+
+fn parse_rule(s: &str) -> Result<VerificationRule, String> {
+    let (kind, value) = s
+        .split_once(':')
+        .ok_or_else(|| format!("expected `<rule>:<value>`, got `{s}`"))?;
+    let value: f32 = value
+        .parse()
+        .map_err(|e| format!("invalid value `{value}`: {e}"))?;
+    if !(0.0..=1.0).contains(&value) {
+        return Err(format!("value {value} outside [0, 1]"));
+    }
+    match kind {
+        "corroborate" => Ok(VerificationRule::Corroborate { weight: value }),
+        "at-least" => Ok(VerificationRule::AtLeast { confidence: value }),
+        "set" => Ok(VerificationRule::Set { confidence: value }),
+        other => Err(format!(
+            "unknown rule `{other}` (expected corroborate, at-least or set)"
+        )),
+    }
+}
+
+fn target(args: &VerifyArgs) -> Result<DeletionTarget> {
+    match (&args.relation, &args.entity) {
+        (Some(parts), _) => match &parts[..] {
+            [rel_type, source, target] => Ok(DeletionTarget::Relation {
+                rel_type: rel_type.clone(),
+                source: source.clone(),
+                target: target.clone(),
+            }),
+            _ => Err(anyhow!("--relation takes REL SOURCE TARGET")),
+        },
+        (None, Some(name)) => Ok(DeletionTarget::Entity { name: name.clone() }),
+        (None, None) => Err(anyhow!("either --relation or --entity is required")),
+    }
+}
+
+pub fn cmd_verify(args: &VerifyArgs) -> Result<()> {
+    let target = target(args)?;
+    let mut config = StorageConfig {
+        axi_dir: args.dir.clone(),
+        pathdb_path: args.dir.join("knowledge.axpd"),
+        changelog_path: args.dir.join("changelog.json"),
+        watch_files: false,
+        ..Default::default()
+    };
+    if let Some(rule) = args.rule {
+        config.verification = rule;
+    }
+    let storage = UnifiedStorage::new(config)?;
+    let record = storage.verify_fact(target, &args.verifier, args.note.as_deref())?;
+
+    let from = record
+        .from
+        .map_or_else(|| "-".to_string(), |c| format!("{c:.3}"));
+    println!(
+        "{} {} by {}",
+        "Verified".green().bold(),
+        record.target,
+        record.verifier
+    );
+    println!("  confidence {from} → {:.3}", record.to);
+    println!("  {} change {}", "→".cyan(), record.change_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rules_and_targets() {
+        assert_eq!(
+            parse_rule("corroborate:0.8"),
+            Ok(VerificationRule::Corroborate { weight: 0.8 })
+        );
+        assert_eq!(
+            parse_rule("at-least:0.95"),
+            Ok(VerificationRule::AtLeast { confidence: 0.95 })
+        );
+        assert!(parse_rule("set:1.5").is_err());
+        assert!(parse_rule("bump:0.5").is_err());
+        assert!(parse_rule("0.5").is_err());
+
+        let args = VerifyArgs {
+            dir: PathBuf::from("knowledge"),
+            relation: Some(vec!["usesTool".into(), "Roughing".into(), "EndMill".into()]),
+            entity: None,
+            verifier: "ana".into(),
+            note: None,
+            rule: None,
+        };
+        assert_eq!(
+            target(&args).unwrap(),
+            DeletionTarget::Relation {
+                rel_type: "usesTool".into(),
+                source: "Roughing".into(),
+                target: "EndMill".into(),
+            }
+        );
+    }
+}
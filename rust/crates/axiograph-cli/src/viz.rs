@@ -12,6 +12,8 @@
 //! - Graphviz DOT (best-in-class layout, external tooling)
 //! - HTML explorer (built from frontend/viz/dist)
 //! - JSON (for custom frontends)
+//! - Evidence explorer (self-contained HTML: the subgraph plus the evidence
+//!   chunks its nodes/edges cite, for reviewing a proposal's neighborhood)
 
 #![allow(dead_code)]
 
@@ -35,6 +37,7 @@ pub enum VizFormat {
     Dot,
     Html,
     Json,
+    Evidence,
 }
 
 impl VizFormat {
@@ -43,8 +46,9 @@ impl VizFormat {
             "dot" => Ok(Self::Dot),
             "html" | "htm" => Ok(Self::Html),
             "json" => Ok(Self::Json),
+            "evidence" => Ok(Self::Evidence),
             other => Err(anyhow!(
-                "unknown viz format `{other}` (expected dot|html|json)"
+                "unknown viz format `{other}` (expected dot|html|json|evidence)"
             )),
         }
     }
@@ -237,6 +241,19 @@ pub fn resolve_focus_by_name_and_type(
     Ok(ids.iter().next())
 }
 
+/// Entities carrying `proposal_id = <proposal_id>` (imported proposal
+/// entities and facts), in id order.
+pub fn resolve_focus_by_proposal_id(db: &PathDB, proposal_id: &str) -> Vec<u32> {
+    match (db.interner.id_of("proposal_id"), db.interner.id_of(proposal_id)) {
+        (Some(key_id), Some(value_id)) => db
+            .entities
+            .entities_with_attr_value(key_id, value_id)
+            .iter()
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 fn is_meta_plane_entity(entity_type: &str) -> bool {
     entity_type.starts_with("AxiMeta")
 }
@@ -271,6 +288,8 @@ fn node_plane(entity_type: &str, kind: &str, attrs: &BTreeMap<String, String>) -
     if entity_type == "DocChunk"
         || entity_type == "Document"
         || entity_type == "ProposalRun"
+        || entity_type == crate::ingest_provenance::TYPE_INGEST_SOURCE
+        || entity_type == crate::ingest_provenance::TYPE_INGEST_TOOL
         || attrs.contains_key("proposal_id")
         || attrs.contains_key("proposals_digest")
         || attrs.contains_key("chunk_id")
@@ -305,16 +324,10 @@ fn schema_for_entity<'a>(
 }
 
 fn db_entity_short_label(db: &PathDB, id: u32) -> String {
-    let Some(view) = db.get_entity(id) else {
-        return id.to_string();
-    };
-    if let Some(name) = view.attrs.get("name") {
-        let name = name.trim();
-        if !name.is_empty() {
-            return name.to_string();
-        }
+    match db.get_entity(id) {
+        Some(view) => view.display_label(),
+        None => id.to_string(),
     }
-    format!("{}#{}", view.entity_type, id)
 }
 
 fn type_label_for_node(entity_type: &str, kind: &str, attrs: &BTreeMap<String, String>) -> Option<String> {
@@ -1034,8 +1047,7 @@ pub fn render_dot(db: &PathDB, g: &VizGraph) -> String {
     }
 
     fn entity_name(db: &PathDB, id: u32) -> Option<String> {
-        let view = db.get_entity(id)?;
-        view.attrs.get("name").cloned()
+        db.entity_label(id)
     }
 
     let mut out = String::new();
@@ -1475,6 +1487,363 @@ pub fn render_html(db: &PathDB, g: &VizGraph) -> Result<String> {
     Ok(html)
 }
 
+// =============================================================================
+// Evidence explorer
+// =============================================================================
+
+/// An evidence chunk cited by a node or edge of an evidence export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VizEvidenceChunk {
+    pub chunk_id: String,
+    /// `DocChunk` entity id, when the chunk is imported into the snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub document_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// A viz subgraph plus the evidence its nodes and edges cite.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VizEvidence {
+    pub graph: VizGraph,
+    /// Node id -> cited chunk ids.
+    pub node_evidence: BTreeMap<u32, Vec<String>>,
+    /// Index into `graph.edges` -> cited chunk ids.
+    pub edge_evidence: BTreeMap<usize, Vec<String>>,
+    /// Chunk id -> chunk details (text etc. only for chunks in the snapshot).
+    pub chunks: BTreeMap<String, VizEvidenceChunk>,
+}
+
+pub fn extract_viz_evidence(db: &PathDB, g: &VizGraph) -> VizEvidence {
+    let mut node_evidence: BTreeMap<u32, Vec<String>> = BTreeMap::new();
+    for node in &g.nodes {
+        let chunks = db.entity_evidence(node.id);
+        if !chunks.is_empty() {
+            node_evidence.insert(node.id, chunks);
+        }
+    }
+    let mut edge_evidence: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for (index, edge) in g.edges.iter().enumerate() {
+        let Some(relation_id) = edge.relation_id else {
+            continue;
+        };
+        let chunks = db.relation_evidence(relation_id);
+        if !chunks.is_empty() {
+            edge_evidence.insert(index, chunks);
+        }
+    }
+
+    let chunk_key = db.interner.id_of("chunk_id");
+    let doc_chunks = db.find_by_type("DocChunk");
+    let mut chunks: BTreeMap<String, VizEvidenceChunk> = BTreeMap::new();
+    for chunk_id in node_evidence.values().chain(edge_evidence.values()).flatten() {
+        if chunks.contains_key(chunk_id) {
+            continue;
+        }
+        let entity = chunk_key
+            .zip(db.interner.id_of(chunk_id))
+            .and_then(|(key, value)| {
+                db.entities
+                    .entities_with_attr_value(key, value)
+                    .iter()
+                    .find(|id| doc_chunks.as_ref().is_some_and(|bm| bm.contains(*id)))
+            });
+        let view = entity.and_then(|id| db.get_entity(id));
+        let attr = |key: &str| view.as_ref().and_then(|v| v.attrs.get(key).cloned());
+        chunks.insert(
+            chunk_id.clone(),
+            VizEvidenceChunk {
+                chunk_id: chunk_id.clone(),
+                entity,
+                document_id: attr("document_id"),
+                span_id: attr("span_id"),
+                page: attr("page"),
+                text: attr("text"),
+            },
+        );
+    }
+
+    VizEvidence {
+        graph: g.clone(),
+        node_evidence,
+        edge_evidence,
+        chunks,
+    }
+}
+
+/// Self-contained evidence explorer page (no external assets): the graph is
+/// laid out in the browser, and selecting a node or edge lists its
+/// confidence, attributes and evidence chunks.
+pub fn render_evidence_html(db: &PathDB, g: &VizGraph) -> Result<String> {
+    let evidence = extract_viz_evidence(db, g);
+    let json = serde_json::to_string(&evidence)?.replace("</", "<\\/");
+    Ok(EVIDENCE_HTML_TEMPLATE
+        .replace("{{NODES_COUNT}}", &g.nodes.len().to_string())
+        .replace("{{EDGES_COUNT}}", &g.edges.len().to_string())
+        .replace("{{CHUNKS_COUNT}}", &evidence.chunks.len().to_string())
+        .replace("{{EVIDENCE_JSON}}", &json))
+}
+
+const EVIDENCE_HTML_TEMPLATE: &str = r##"<!doctype html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Axiograph evidence explorer</title>
+<style>
+  body { margin: 0; font: 13px system-ui, sans-serif; display: flex; flex-direction: column; height: 100vh; }
+  header { padding: 6px 10px; border-bottom: 1px solid #ccc; display: flex; gap: 12px; align-items: center; }
+  main { flex: 1; display: flex; min-height: 0; }
+  svg { flex: 1; background: #fafafa; cursor: grab; }
+  aside { width: 380px; overflow: auto; border-left: 1px solid #ccc; padding: 8px 12px; }
+  .edge { stroke: #555; }
+  .edge.unsupported { stroke-dasharray: 4 3; }
+  .edge.selected, .node.selected circle { stroke: #d33; stroke-width: 3; }
+  .node circle { stroke: #333; stroke-width: 1; cursor: pointer; }
+  .node.cited circle { stroke: #2a7; stroke-width: 3; }
+  .node.focus circle { fill: #fd5; }
+  .node.dim { opacity: 0.2; }
+  .node text { font-size: 11px; pointer-events: none; }
+  .edge-label { font-size: 10px; fill: #666; pointer-events: none; }
+  table { border-collapse: collapse; width: 100%; }
+  td { border-top: 1px solid #eee; padding: 2px 4px; vertical-align: top; word-break: break-word; }
+  .chunk { border: 1px solid #ddd; border-radius: 4px; padding: 4px 6px; margin: 6px 0; }
+  .chunk pre { white-space: pre-wrap; max-height: 240px; overflow: auto; background: #f4f4f4; padding: 4px; }
+  .muted { color: #888; }
+</style>
+</head>
+<body>
+<header>
+  <strong>Evidence explorer</strong>
+  <span class="muted">nodes={{NODES_COUNT}} edges={{EDGES_COUNT}} cited chunks={{CHUNKS_COUNT}}</span>
+  <input id="search" placeholder="filter by name" size="24">
+  <span class="muted">green ring: cites evidence · dashed edge: no evidence · edge width: confidence</span>
+</header>
+<main>
+  <svg id="graph"></svg>
+  <aside id="panel"><p class="muted">Select a node or an edge.</p></aside>
+</main>
+<script id="axiograph_evidence" type="application/json">{{EVIDENCE_JSON}}</script>
+<script>
+(function () {
+  const data = JSON.parse(document.getElementById("axiograph_evidence").textContent);
+  const g = data.graph;
+  const svg = document.getElementById("graph");
+  const panel = document.getElementById("panel");
+  const NS = "http://www.w3.org/2000/svg";
+  const focus = new Set(g.summary.focus_ids);
+  const label = (n) => n.display_name || n.name || (n.entity_type + "#" + n.id);
+
+  // Force-directed layout, computed once.
+  const pos = new Map();
+  g.nodes.forEach((n, i) => {
+    const a = (2 * Math.PI * i) / Math.max(1, g.nodes.length);
+    pos.set(n.id, { x: 400 * Math.cos(a), y: 400 * Math.sin(a) });
+  });
+  for (let iter = 0; iter < 300; iter++) {
+    const force = new Map(g.nodes.map((n) => [n.id, { x: 0, y: 0 }]));
+    for (let i = 0; i < g.nodes.length; i++) {
+      for (let j = i + 1; j < g.nodes.length; j++) {
+        const a = pos.get(g.nodes[i].id), b = pos.get(g.nodes[j].id);
+        const dx = a.x - b.x, dy = a.y - b.y;
+        const d2 = Math.max(dx * dx + dy * dy, 1);
+        const f = 4000 / d2;
+        force.get(g.nodes[i].id).x += dx * f; force.get(g.nodes[i].id).y += dy * f;
+        force.get(g.nodes[j].id).x -= dx * f; force.get(g.nodes[j].id).y -= dy * f;
+      }
+    }
+    for (const e of g.edges) {
+      const a = pos.get(e.source), b = pos.get(e.target);
+      if (!a || !b) continue;
+      const dx = b.x - a.x, dy = b.y - a.y;
+      force.get(e.source).x += dx * 0.02; force.get(e.source).y += dy * 0.02;
+      force.get(e.target).x -= dx * 0.02; force.get(e.target).y -= dy * 0.02;
+    }
+    const step = 1 - iter / 300;
+    for (const n of g.nodes) {
+      const p = pos.get(n.id), f = force.get(n.id);
+      p.x += Math.max(-20, Math.min(20, f.x - p.x * 0.01)) * step;
+      p.y += Math.max(-20, Math.min(20, f.y - p.y * 0.01)) * step;
+    }
+  }
+
+  const el = (tag, attrs, text) => {
+    const node = document.createElementNS(NS, tag);
+    for (const [k, v] of Object.entries(attrs || {})) node.setAttribute(k, v);
+    if (text !== undefined) node.textContent = text;
+    return node;
+  };
+  const html = (tag, text, cls) => {
+    const node = document.createElement(tag);
+    if (text !== undefined) node.textContent = text;
+    if (cls) node.className = cls;
+    return node;
+  };
+
+  const root = el("g");
+  svg.appendChild(root);
+  const edgeEls = [], nodeEls = new Map();
+  g.edges.forEach((e, i) => {
+    const a = pos.get(e.source), b = pos.get(e.target);
+    if (!a || !b) return;
+    const cited = data.edge_evidence[i] !== undefined;
+    const conf = e.confidence === undefined || e.confidence === null ? 1 : e.confidence;
+    const line = el("line", {
+      x1: a.x, y1: a.y, x2: b.x, y2: b.y,
+      class: "edge" + (cited ? "" : " unsupported"),
+      "stroke-width": 1 + 3 * conf, "stroke-opacity": 0.3 + 0.7 * conf,
+    });
+    line.style.cursor = "pointer";
+    line.addEventListener("click", (ev) => { ev.stopPropagation(); selectEdge(i); });
+    root.appendChild(line);
+    const text = e.label + (e.confidence === undefined || e.confidence === null ? "" : " " + e.confidence.toFixed(2));
+    root.appendChild(el("text", { x: (a.x + b.x) / 2, y: (a.y + b.y) / 2, class: "edge-label" }, text));
+    edgeEls[i] = line;
+  });
+  for (const n of g.nodes) {
+    const p = pos.get(n.id);
+    const cls = ["node"];
+    if (data.node_evidence[n.id] !== undefined) cls.push("cited");
+    if (focus.has(n.id)) cls.push("focus");
+    const group = el("g", { class: cls.join(" "), transform: "translate(" + p.x + "," + p.y + ")" });
+    group.appendChild(el("circle", { r: 8, fill: n.kind === "fact" ? "#9cf" : n.entity_type === "DocChunk" ? "#cfc" : "#ddd" }));
+    group.appendChild(el("text", { x: 11, y: 4 }, label(n)));
+    group.addEventListener("click", (ev) => { ev.stopPropagation(); selectNode(n.id); });
+    root.appendChild(group);
+    nodeEls.set(n.id, group);
+  }
+
+  // Pan and zoom.
+  let view = { x: -600, y: -450, w: 1200, h: 900 };
+  const applyView = () => svg.setAttribute("viewBox", [view.x, view.y, view.w, view.h].join(" "));
+  applyView();
+  svg.addEventListener("wheel", (ev) => {
+    ev.preventDefault();
+    const k = ev.deltaY > 0 ? 1.1 : 1 / 1.1;
+    view = { x: view.x + (view.w * (1 - k)) / 2, y: view.y + (view.h * (1 - k)) / 2, w: view.w * k, h: view.h * k };
+    applyView();
+  });
+  let drag = null;
+  svg.addEventListener("mousedown", (ev) => { drag = { x: ev.clientX, y: ev.clientY }; });
+  window.addEventListener("mouseup", () => { drag = null; });
+  window.addEventListener("mousemove", (ev) => {
+    if (!drag) return;
+    const s = view.w / svg.clientWidth;
+    view.x -= (ev.clientX - drag.x) * s; view.y -= (ev.clientY - drag.y) * s;
+    drag = { x: ev.clientX, y: ev.clientY };
+    applyView();
+  });
+
+  const clearSelection = () => {
+    for (const e of edgeEls) if (e) e.classList.remove("selected");
+    for (const n of nodeEls.values()) n.classList.remove("selected");
+  };
+  const attrTable = (rows) => {
+    const table = html("table");
+    for (const [k, v] of rows) {
+      const tr = html("tr");
+      tr.appendChild(html("td", k, "muted"));
+      tr.appendChild(html("td", String(v)));
+      table.appendChild(tr);
+    }
+    return table;
+  };
+  const evidenceList = (ids) => {
+    const box = html("div");
+    box.appendChild(html("h4", "Evidence (" + (ids ? ids.length : 0) + ")"));
+    if (!ids || ids.length === 0) {
+      box.appendChild(html("p", "No evidence cited.", "muted"));
+      return box;
+    }
+    for (const id of ids) {
+      const c = data.chunks[id] || { chunk_id: id };
+      const div = html("div", undefined, "chunk");
+      div.appendChild(html("strong", c.chunk_id));
+      const where = [c.document_id, c.span_id, c.page ? "page " + c.page : null].filter(Boolean).join(" · ");
+      if (where) div.appendChild(html("div", where, "muted"));
+      if (c.text) div.appendChild(html("pre", c.text));
+      else div.appendChild(html("div", "(chunk text not in this snapshot)", "muted"));
+      if (c.entity !== undefined && nodeEls.has(c.entity)) {
+        const a = html("a", "show chunk node");
+        a.href = "#";
+        a.addEventListener("click", (ev) => { ev.preventDefault(); selectNode(c.entity); });
+        div.appendChild(a);
+      }
+      box.appendChild(div);
+    }
+    return box;
+  };
+  const nodeById = new Map(g.nodes.map((n) => [n.id, n]));
+  const nodeLink = (id) => {
+    const n = nodeById.get(id);
+    const a = html("a", n ? label(n) : "#" + id);
+    a.href = "#";
+    a.addEventListener("click", (ev) => { ev.preventDefault(); if (n) selectNode(id); });
+    return a;
+  };
+
+  function selectNode(id) {
+    const n = nodeById.get(id);
+    clearSelection();
+    nodeEls.get(id).classList.add("selected");
+    panel.replaceChildren();
+    panel.appendChild(html("h3", label(n)));
+    panel.appendChild(attrTable([["id", n.id], ["type", n.entity_type], ["kind", n.kind], ["plane", n.plane]]));
+    panel.appendChild(evidenceList(data.node_evidence[id]));
+    panel.appendChild(html("h4", "Edges"));
+    g.edges.forEach((e, i) => {
+      if (e.source !== id && e.target !== id) return;
+      const row = html("div");
+      const a = html("a", (e.source === id ? "→ " : "← ") + e.label);
+      a.href = "#";
+      a.addEventListener("click", (ev) => { ev.preventDefault(); selectEdge(i); });
+      row.appendChild(a);
+      row.appendChild(html("span", " "));
+      row.appendChild(nodeLink(e.source === id ? e.target : e.source));
+      panel.appendChild(row);
+    });
+    panel.appendChild(html("h4", "Attributes"));
+    panel.appendChild(attrTable(Object.entries(n.attrs)));
+  }
+
+  function selectEdge(i) {
+    const e = g.edges[i];
+    clearSelection();
+    if (edgeEls[i]) edgeEls[i].classList.add("selected");
+    panel.replaceChildren();
+    panel.appendChild(html("h3", e.label));
+    const ends = html("p");
+    ends.appendChild(nodeLink(e.source));
+    ends.appendChild(html("span", " → "));
+    ends.appendChild(nodeLink(e.target));
+    panel.appendChild(ends);
+    const rows = [["kind", e.kind], ["confidence", e.confidence === undefined || e.confidence === null ? "—" : e.confidence.toFixed(3)]];
+    if (e.relation_id !== undefined && e.relation_id !== null) rows.push(["relation id", e.relation_id]);
+    panel.appendChild(attrTable(rows));
+    panel.appendChild(evidenceList(data.edge_evidence[i]));
+  }
+
+  document.getElementById("search").addEventListener("input", (ev) => {
+    const q = ev.target.value.trim().toLowerCase();
+    for (const n of g.nodes) {
+      nodeEls.get(n.id).classList.toggle("dim", q !== "" && !label(n).toLowerCase().includes(q));
+    }
+  });
+  svg.addEventListener("click", () => { clearSelection(); });
+  if (g.summary.focus_ids.length > 0 && nodeById.has(g.summary.focus_ids[0])) {
+    selectNode(g.summary.focus_ids[0]);
+  }
+})();
+</script>
+</body>
+</html>
+"##;
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -1562,4 +1931,46 @@ instance DemoInst of Demo:
             .contains("Node"));
         Ok(())
     }
+
+    #[test]
+    fn viz_evidence_export_embeds_cited_chunks() -> Result<()> {
+        let mut db = axiograph_pathdb::PathDB::new();
+        let a = db.add_entity("Supplier", vec![("name", "a"), ("proposal_id", "p1")]);
+        let b = db.add_entity("Supplier", vec![("name", "b")]);
+        db.add_entity(
+            "DocChunk",
+            vec![("chunk_id", "c1"), ("document_id", "d1"), ("text", "a ships to b")],
+        );
+        db.add_relation("Flow", a, b, 0.7, vec![("evidence_0_chunk_id", "c1")]);
+        db.add_relation("Flow", b, a, 0.4, Vec::new());
+        db.build_indexes();
+
+        assert_eq!(resolve_focus_by_proposal_id(&db, "p1"), vec![a]);
+        assert!(resolve_focus_by_proposal_id(&db, "missing").is_empty());
+
+        let options = VizOptions {
+            focus_ids: vec![a],
+            hops: 1,
+            include_equivalences: false,
+            ..VizOptions::default()
+        };
+        let g = extract_viz_graph(&db, &options)?;
+        let evidence = extract_viz_evidence(&db, &g);
+        let cited = g
+            .edges
+            .iter()
+            .position(|e| e.source == a && e.target == b)
+            .expect("a -> b edge");
+        assert_eq!(evidence.edge_evidence.get(&cited), Some(&vec!["c1".to_string()]));
+        assert_eq!(evidence.edge_evidence.len(), 1);
+        let chunk = evidence.chunks.get("c1").expect("chunk details");
+        assert_eq!(chunk.document_id.as_deref(), Some("d1"));
+        assert_eq!(chunk.text.as_deref(), Some("a ships to b"));
+
+        let html = render_evidence_html(&db, &g)?;
+        assert!(html.contains("id=\"axiograph_evidence\""));
+        assert!(html.contains("a ships to b"));
+        assert!(!html.contains("{{EVIDENCE_JSON}}"));
+        Ok(())
+    }
 }
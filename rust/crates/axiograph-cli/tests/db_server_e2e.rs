@@ -279,6 +279,47 @@ fn db_serve_query_smoke() {
         response.get("inferred_types").is_some(),
         "expected inferred_types when show_elaboration=true"
     );
+    assert!(
+        response.get("profile").is_none(),
+        "profile is only returned for explain_analyze=true"
+    );
+
+    let analyze_query = serde_json::json!({
+        "query": "select ?gc where name(\"Alice\") -Grandparent-> ?gc limit 10",
+        "explain_analyze": true,
+    });
+    let (analyze_status, analyzed) = http_post_json(addr, "/query", &analyze_query);
+    assert_eq!(analyze_status, 200, "explain_analyze query failed: {analyzed}");
+    assert_eq!(analyzed["rows"].as_array().map(|r| r.len()), Some(rows.len()));
+    let profile = analyzed["profile"].as_array().cloned().unwrap_or_default();
+    assert!(
+        profile
+            .iter()
+            .any(|l| l.as_str().is_some_and(|l| l.starts_with("indexes used: "))),
+        "expected an index usage summary: {analyzed}"
+    );
+
+    // Paging: one row per page, following `next_cursor` to the end.
+    let mut paged_rows = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut page_query = serde_json::json!({
+            "query": "select ?gc where name(\"Alice\") -Grandparent-> ?gc limit 10",
+            "limit": 1,
+        });
+        if let Some(c) = &cursor {
+            page_query["cursor"] = serde_json::json!(c);
+        }
+        let (page_status, page) = http_post_json(addr, "/query", &page_query);
+        assert_eq!(page_status, 200, "paged query failed: {page}");
+        assert_eq!(page["total_rows"].as_u64(), Some(rows.len() as u64), "{page}");
+        paged_rows.extend(page["rows"].as_array().cloned().unwrap_or_default());
+        match page["next_cursor"].as_str() {
+            Some(next) => cursor = Some(next.to_string()),
+            None => break,
+        }
+    }
+    assert_eq!(paged_rows, rows);
 
     let query_cert = serde_json::json!({
         "query": "select ?gc where name(\"Alice\") -Grandparent-> ?gc limit 10",
@@ -470,6 +511,8 @@ fn db_serve_llm_agent_smoke() {
 
     let (status_code, status_json) = http_get_json(addr, "/status");
     assert_eq!(status_code, 200, "expected 200, got {status_code}: {status_json}");
+    // Standalone servers are read-only: adjacency is frozen into CSR layout.
+    assert_eq!(status_json["snapshot"]["adjacency"], "csr", "{status_json}");
     assert!(
         status_json
             .get("llm")
@@ -689,3 +732,321 @@ fn db_serve_llm_agent_auto_commit_smoke() {
         "expected at least one Parent edge from Jamison after auto-commit: {q_json}"
     );
 }
+
+/// GET with an optional extra header; returns (status, raw headers, JSON body).
+fn http_get_json_with_header(
+    addr: &str,
+    path_and_query: &str,
+    header: Option<(&str, &str)>,
+) -> (u16, String, serde_json::Value) {
+    let mut stream = TcpStream::connect(addr).expect("connect");
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .ok();
+
+    let mut request = format!("GET {path_and_query} HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n");
+    if let Some((name, value)) = header {
+        request.push_str(&format!("{name}: {value}\r\n"));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).expect("write request");
+    stream.flush().ok();
+
+    let mut response_bytes = Vec::new();
+    stream
+        .read_to_end(&mut response_bytes)
+        .expect("read response");
+    let response = String::from_utf8_lossy(&response_bytes).to_string();
+    let (head, body_text) = response
+        .split_once("\r\n\r\n")
+        .unwrap_or(("", response.as_str()));
+    let status = head
+        .lines()
+        .next()
+        .and_then(|l| l.split_whitespace().nth(1))
+        .and_then(|s| s.parse::<u16>().ok())
+        .unwrap_or(0);
+    let json: serde_json::Value = serde_json::from_str(body_text).expect("parse JSON response");
+    (status, head.to_ascii_lowercase(), json)
+}
+
+fn wait_for_ready_addr(ready_file: &Path) -> String {
+    let deadline = std::time::Instant::now() + Duration::from_secs(20);
+    while !ready_file.exists() && std::time::Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    assert!(ready_file.exists(), "server did not write ready file");
+    let ready_text = fs::read_to_string(ready_file).expect("read ready file");
+    let ready_json: serde_json::Value = serde_json::from_str(&ready_text).expect("parse ready json");
+    ready_json["addr"]
+        .as_str()
+        .expect("ready.addr is string")
+        .to_string()
+}
+
+#[test]
+fn db_serve_replica_ships_deltas_and_serves_min_version_reads() {
+    let repo_root = repo_root();
+    let bin = axiograph_bin();
+    let run_dir = unique_run_dir(&repo_root, "db_serve_replication");
+
+    let master_dir = run_dir.join("build/master");
+    let replica_dir = run_dir.join("build/replica");
+    let input = repo_root.join("examples/ontology/OntologyRewrites.axi");
+
+    // 1) Master store: accepted snapshot + initial PathDB WAL head.
+    let status = Command::new(&bin)
+        .current_dir(&run_dir)
+        .args(["db", "accept", "promote"])
+        .arg(&input)
+        .arg("--dir")
+        .arg(&master_dir)
+        .args(["--message", "e2e: replication promote"])
+        .status()
+        .expect("run axiograph db accept promote");
+    assert!(status.success(), "accept promote failed");
+
+    let chunks_path = run_dir.join("build/init_chunks.json");
+    let chunk = |id: &str, text: &str| {
+        serde_json::json!({
+            "chunk_id": id,
+            "document_id": "replication",
+            "page": null,
+            "span_id": "span0",
+            "text": text,
+            "bbox": null,
+            "metadata": {}
+        })
+    };
+    fs::write(
+        &chunks_path,
+        serde_json::to_string_pretty(&serde_json::json!([chunk("init_chunk_0", "init")]))
+            .expect("serialize init chunks"),
+    )
+    .expect("write init_chunks.json");
+    let status = Command::new(&bin)
+        .current_dir(&run_dir)
+        .args(["db", "accept", "pathdb-commit", "--dir"])
+        .arg(&master_dir)
+        .args(["--accepted-snapshot", "latest", "--chunks"])
+        .arg(&chunks_path)
+        .status()
+        .expect("run axiograph db accept pathdb-commit");
+    assert!(status.success(), "accept pathdb-commit failed");
+
+    // 2) Master + replica (separate directories, replica starts empty).
+    let master_ready = run_dir.join("build/master_ready.json");
+    let master = Command::new(&bin)
+        .current_dir(&run_dir)
+        .args(["db", "serve", "--role", "master", "--listen", "127.0.0.1:0", "--dir"])
+        .arg(&master_dir)
+        .arg("--ready-file")
+        .arg(&master_ready)
+        .spawn()
+        .expect("spawn master");
+    let _master_guard = ChildGuard { child: master };
+    let master_addr = wait_for_ready_addr(&master_ready);
+
+    let replica_ready = run_dir.join("build/replica_ready.json");
+    let replica = Command::new(&bin)
+        .current_dir(&run_dir)
+        .args(["db", "serve", "--role", "replica", "--listen", "127.0.0.1:0", "--dir"])
+        .arg(&replica_dir)
+        .arg("--replicate-from")
+        .arg(format!("http://{master_addr}"))
+        .args(["--poll-interval-secs", "1", "--ready-file"])
+        .arg(&replica_ready)
+        .spawn()
+        .expect("spawn replica");
+    let _replica_guard = ChildGuard { child: replica };
+    let replica_addr = wait_for_ready_addr(&replica_ready);
+
+    let (_, _, master_status) = http_get_json_with_header(&master_addr, "/status", None);
+    let (code, headers, replica_status) = http_get_json_with_header(&replica_addr, "/status", None);
+    assert_eq!(code, 200, "{replica_status}");
+    assert_eq!(
+        replica_status["snapshot"]["pathdb_snapshot_id"],
+        master_status["snapshot"]["pathdb_snapshot_id"]
+    );
+    assert_eq!(
+        replica_status["snapshot"]["version"],
+        serde_json::json!({"accepted": 1, "pathdb": 1})
+    );
+    assert_eq!(
+        replica_status["replication"]["lag"],
+        serde_json::json!({"accepted": 0, "pathdb": 0}),
+        "{replica_status}"
+    );
+    assert!(
+        headers.contains("x-axiograph-version: accepted=1,pathdb=1"),
+        "{headers}"
+    );
+
+    // 3) Reads ahead of the replica are refused with Retry-After.
+    let (code, headers, body) = http_get_json_with_header(
+        &replica_addr,
+        "/status",
+        Some(("X-Axiograph-Min-Version", "pathdb=99")),
+    );
+    assert_eq!(code, 503, "{body}");
+    assert!(headers.contains("retry-after:"), "{headers}");
+
+    // 4) A master commit reaches the replica; "at least" reads then succeed.
+    let (code, commit) = http_post_json(
+        &master_addr,
+        "/admin/accept/pathdb-commit",
+        &serde_json::json!({
+            "chunks": [chunk("replicated_chunk_0", "shipped to the replica")],
+            "message": "e2e: replicated commit",
+        }),
+    );
+    assert_eq!(code, 200, "{commit}");
+    assert_eq!(commit["version"], serde_json::json!({"accepted": 1, "pathdb": 2}));
+
+    let deadline = std::time::Instant::now() + Duration::from_secs(20);
+    let replica_status = loop {
+        let (code, _, body) = http_get_json_with_header(
+            &replica_addr,
+            "/status",
+            Some(("X-Axiograph-Min-Version", "accepted=1,pathdb=2")),
+        );
+        if code == 200 {
+            break body;
+        }
+        assert_eq!(code, 503, "{body}");
+        assert!(
+            std::time::Instant::now() < deadline,
+            "replica did not catch up: {body}"
+        );
+        std::thread::sleep(Duration::from_millis(200));
+    };
+    assert_eq!(
+        replica_status["snapshot"]["pathdb_snapshot_id"],
+        commit["snapshot_id"]
+    );
+    assert!(
+        replica_status["replication"]["events_applied"]
+            .as_u64()
+            .unwrap_or(0)
+            >= 3,
+        "{replica_status}"
+    );
+}
+
+#[test]
+fn db_serve_verify_integrity_refuses_inconsistent_snapshot() {
+    let repo_root = repo_root();
+    let bin = axiograph_bin();
+    let run_dir = unique_run_dir(&repo_root, "db_serve_verify_integrity");
+
+    let axpd = run_dir.join("build/server.axpd");
+    let input = repo_root.join("examples/ontology/OntologyRewrites.axi");
+    let status = Command::new(&bin)
+        .current_dir(&run_dir)
+        .args(["db", "pathdb", "import-axi"])
+        .arg(&input)
+        .arg("--out")
+        .arg(&axpd)
+        .status()
+        .expect("import .axi into .axpd");
+    assert!(status.success(), "db pathdb import-axi failed");
+
+    // A consistent snapshot is served as usual.
+    let ready_file = run_dir.join("build/ready.json");
+    let child = Command::new(&bin)
+        .current_dir(&run_dir)
+        .args(["db", "serve", "--verify-integrity", "--listen", "127.0.0.1:0"])
+        .arg("--axpd")
+        .arg(&axpd)
+        .arg("--ready-file")
+        .arg(&ready_file)
+        .spawn()
+        .expect("spawn db serve");
+    let guard = ChildGuard { child };
+    let addr = wait_for_ready_addr(&ready_file);
+    let (status_code, status_json) = http_get_json(&addr, "/status");
+    assert_eq!(status_code, 200, "{status_json}");
+    drop(guard);
+
+    // The confidence index is the last snapshot section: make its final entry
+    // disagree with its relation. The structural load checks still pass.
+    let mut bytes = fs::read(&axpd).expect("read .axpd");
+    let len = bytes.len();
+    bytes[len - 4..].copy_from_slice(&0.123f32.to_le_bytes());
+    let corrupt = run_dir.join("build/corrupt.axpd");
+    fs::write(&corrupt, bytes).expect("write corrupt .axpd");
+
+    let output = Command::new(&bin)
+        .current_dir(&run_dir)
+        .args(["db", "serve", "--verify-integrity", "--listen", "127.0.0.1:0"])
+        .arg("--axpd")
+        .arg(&corrupt)
+        .output()
+        .expect("run db serve");
+    assert!(!output.status.success(), "corrupt snapshot was served");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("failed integrity verification") && stderr.contains("confidence_index"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn db_serve_access_role_hides_denied_types() {
+    let repo_root = repo_root();
+    let bin = axiograph_bin();
+    let run_dir = unique_run_dir(&repo_root, "db_serve_access_role");
+
+    let axpd = run_dir.join("build/server.axpd");
+    let input = repo_root.join("examples/ontology/OntologyRewrites.axi");
+    let status = Command::new(&bin)
+        .current_dir(&run_dir)
+        .args(["db", "pathdb", "import-axi"])
+        .arg(&input)
+        .arg("--out")
+        .arg(&axpd)
+        .status()
+        .expect("import .axi into .axpd");
+    assert!(status.success(), "db pathdb import-axi failed");
+
+    let policy = run_dir.join("build/policy.json");
+    fs::write(
+        &policy,
+        r#"{"roles": {"analyst": {"deny_types": ["Grandparent"]}}}"#,
+    )
+    .expect("write policy");
+
+    let ready_file = run_dir.join("build/ready.json");
+    let child = Command::new(&bin)
+        .current_dir(&run_dir)
+        .args(["db", "serve", "--listen", "127.0.0.1:0"])
+        .arg("--axpd")
+        .arg(&axpd)
+        .arg("--ready-file")
+        .arg(&ready_file)
+        .arg("--access-policy")
+        .arg(&policy)
+        .args(["--access-role", "analyst"])
+        .spawn()
+        .expect("spawn db serve");
+    let _guard = ChildGuard { child };
+    let addr = wait_for_ready_addr(&ready_file);
+
+    let query = serde_json::json!({
+        "query": "select ?gc where name(\"Alice\") -Grandparent-> ?gc limit 10",
+        "lang": "axql",
+    });
+    let (status_code, response) = http_post_json(&addr, "/query", &query);
+    assert_eq!(status_code, 200, "expected 200, got {status_code}: {response}");
+    let rows = response["rows"].as_array().cloned().unwrap_or_default();
+    assert!(rows.is_empty(), "denied relation type leaked: {rows:?}");
+
+    let query = serde_json::json!({
+        "query": "select ?c where name(\"Alice\") -Parent-> ?c limit 10",
+        "lang": "axql",
+    });
+    let (status_code, response) = http_post_json(&addr, "/query", &query);
+    assert_eq!(status_code, 200, "expected 200, got {status_code}: {response}");
+    let rows = response["rows"].as_array().cloned().unwrap_or_default();
+    assert!(!rows.is_empty(), "expected the allowed `Parent` edge");
+}
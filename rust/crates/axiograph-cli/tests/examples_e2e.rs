@@ -1408,3 +1408,141 @@ fn doc_to_proposals_to_candidate_axi_smoke() {
         status.code().unwrap_or(-1)
     );
 }
+
+#[test]
+fn access_role_restricts_exports_and_repl() {
+    let repo_root = repo_root();
+    let bin = axiograph_bin();
+    let run_dir = unique_run_dir(&repo_root, "access_role");
+    let out_dir = run_dir.join("build");
+
+    let mut db = axiograph_pathdb::PathDB::new();
+    let user = db.add_entity(
+        "User",
+        vec![("name", "alice"), ("email", "alice@example.com")],
+    );
+    let secret = db.add_entity("Secret", vec![("name", "launch_codes")]);
+    let vault = db.add_entity("Vault", vec![("name", "east_vault")]);
+    db.add_relation("owns", user, secret, 1.0, vec![]);
+    db.add_relation("stored_in", secret, vault, 1.0, vec![]);
+    db.build_indexes();
+    let axpd = out_dir.join("access.axpd");
+    fs::write(&axpd, db.to_bytes().expect("encode .axpd")).expect("write .axpd");
+
+    let policy = out_dir.join("policy.json");
+    fs::write(
+        &policy,
+        r#"{"roles": {"analyst": {"deny_types": ["Secret"], "redact_attrs": ["email"]}}}"#,
+    )
+    .expect("write policy");
+
+    // Snapshot exports spell strings as `StrUtf8Hex_<hex>`.
+    let exported_string = |s: &str| {
+        let hex: String = s.bytes().map(|b| format!("{b:02x}")).collect();
+        format!("StrUtf8Hex_{hex}")
+    };
+    let check_export = |axi: &Path| {
+        let text = fs::read_to_string(axi).expect("read export");
+        for (value, visible) in [
+            ("east_vault", true),
+            ("launch_codes", false),
+            ("alice@example.com", false),
+        ] {
+            assert_eq!(
+                text.contains(&exported_string(value)),
+                visible,
+                "{value} in {}",
+                axi.display()
+            );
+        }
+    };
+
+    let exported = out_dir.join("analyst_export_v1.axi");
+    let status = Command::new(&bin)
+        .current_dir(&run_dir)
+        .args(["db", "pathdb", "export-axi"])
+        .arg(&axpd)
+        .arg("--out")
+        .arg(&exported)
+        .arg("--access-policy")
+        .arg(&policy)
+        .args(["--access-role", "analyst"])
+        .status()
+        .expect("run axiograph db pathdb export-axi");
+    assert!(status.success());
+    check_export(&exported);
+
+    let repl_exported = out_dir.join("analyst_repl_export_v1.axi");
+    let status = Command::new(&bin)
+        .current_dir(&run_dir)
+        .arg("repl")
+        .arg("--axpd")
+        .arg(&axpd)
+        .arg("--access-policy")
+        .arg(&policy)
+        .args(["--access-role", "analyst", "--quiet", "--cmd"])
+        .arg(format!("export_axi {}", repl_exported.display()))
+        .status()
+        .expect("run axiograph repl");
+    assert!(status.success());
+    check_export(&repl_exported);
+
+    // Unknown roles are rejected rather than silently unrestricted.
+    let status = Command::new(&bin)
+        .current_dir(&run_dir)
+        .args(["db", "pathdb", "export-axi"])
+        .arg(&axpd)
+        .arg("--out")
+        .arg(out_dir.join("unused.axi"))
+        .arg("--access-policy")
+        .arg(&policy)
+        .args(["--access-role", "admin"])
+        .status()
+        .expect("run axiograph db pathdb export-axi");
+    assert!(!status.success());
+}
+
+#[test]
+fn repl_queries_are_recorded_in_the_audit_log() {
+    let repo_root = repo_root();
+    let bin = axiograph_bin();
+    let run_dir = unique_run_dir(&repo_root, "repl_audit");
+    let out_dir = run_dir.join("build");
+
+    let mut db = axiograph_pathdb::PathDB::new();
+    let user = db.add_entity("User", vec![("name", "alice")]);
+    let vault = db.add_entity("Vault", vec![("name", "east_vault")]);
+    db.add_relation("owns", user, vault, 1.0, vec![]);
+    db.build_indexes();
+    let axpd = out_dir.join("audit.axpd");
+    fs::write(&axpd, db.to_bytes().expect("encode .axpd")).expect("write .axpd");
+
+    let log = out_dir.join("audit.jsonl");
+    let axql = r#"select ?v where name("alice") -owns-> ?v"#;
+    let status = Command::new(&bin)
+        .current_dir(&run_dir)
+        .arg("repl")
+        .arg("--axpd")
+        .arg(&axpd)
+        .arg("--audit-log")
+        .arg(&log)
+        .args(["--audit-principal", "auditor", "--quiet", "--cmd"])
+        .arg(format!("q {axql}"))
+        .status()
+        .expect("run axiograph repl");
+    assert!(status.success());
+
+    let config = axiograph_storage::QueryAuditConfig::new(&log);
+    let records = axiograph_storage::QueryAuditLog::open(config)
+        .and_then(|log| log.records())
+        .expect("read audit log");
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].principal, "auditor");
+    assert_eq!(records[0].result_count, Some(1));
+    assert_eq!(
+        records[0].query,
+        axiograph_storage::audit::AuditedQuery::Axql {
+            query: axql.to_string()
+        }
+    );
+}
@@ -0,0 +1,732 @@
+//! Semantic diff of two `.axi` modules.
+//!
+//! A text diff of `.axi` is noise as soon as declarations are reordered or
+//! reformatted. [`diff_axi`] parses both sides and compares declarations by
+//! identity instead of by position:
+//!
+//! - imports, schemas, theories, instances: by name;
+//! - objects, relations, equations, rewrite rules, views, assignments: by name
+//!   within their schema / theory / instance;
+//! - subtypes: by `Sub < Sup`;
+//! - constraints: by canonical text. A removed and an added constraint of the
+//!   same kind on the same relation (or a named block with the same name) are
+//!   reported as one `changed` constraint;
+//! - instance assignments: by member, as a set (member order is irrelevant).
+//!
+//! An object that disappears while another appears in the same schema, used in
+//! exactly the same fields of the relations both sides declare (and the same
+//! subtype edges), is reported as `renamed`; the follow-on edits (relation field types, rewrite variable
+//! types, instance assignments of that object) are folded into the rename.
+//!
+//! Changes come out in a stable order (scope, item kind, name, op), so the
+//! same two inputs always produce the same list, whatever their layout. Each
+//! [`AxiChange`] is plain data (serde) for the CLI (`axiograph diff`) and for
+//! review tooling.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::schema_v1::{
+    format_constraint_v1, parse_schema_v1, ConstraintV1, RewriteOrientationV1, RewriteRuleV1,
+    RewriteVarTypeV1, SchemaV1Instance, SchemaV1Module, SchemaV1ParseError, SchemaV1Schema,
+    SchemaV1Theory, SetItemV1,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeOp {
+    Added,
+    Removed,
+    Changed,
+    Renamed,
+}
+
+/// What kind of declaration a change is about (stable, machine-readable).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AxiItem {
+    Module,
+    Import,
+    Schema,
+    Object,
+    Subtype,
+    Relation,
+    Theory,
+    Constraint,
+    Equation,
+    RewriteRule,
+    View,
+    Instance,
+    Assignment,
+    /// One member of an instance assignment.
+    Member,
+}
+
+impl AxiItem {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AxiItem::Module => "module",
+            AxiItem::Import => "import",
+            AxiItem::Schema => "schema",
+            AxiItem::Object => "object",
+            AxiItem::Subtype => "subtype",
+            AxiItem::Relation => "relation",
+            AxiItem::Theory => "theory",
+            AxiItem::Constraint => "constraint",
+            AxiItem::Equation => "equation",
+            AxiItem::RewriteRule => "rewrite_rule",
+            AxiItem::View => "view",
+            AxiItem::Instance => "instance",
+            AxiItem::Assignment => "assignment",
+            AxiItem::Member => "member",
+        }
+    }
+}
+
+/// One semantic change. Field order is the sort order.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AxiChange {
+    /// Enclosing declaration (`schema S`, `theory T`, `instance I`); `None`
+    /// for module-level items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    pub item: AxiItem,
+    /// Identity of the item (for a rename: the old name).
+    pub name: String,
+    pub op: ChangeOp,
+    /// One-line rendering of the old item (`removed`, `changed`, `renamed`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// One-line rendering of the new item (`added`, `changed`, `renamed`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+impl std::fmt::Display for AxiChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(scope) = &self.scope {
+            write!(f, "{scope}: ")?;
+        }
+        let item = self.item.as_str();
+        let before = self.before.as_deref().unwrap_or(&self.name);
+        let after = self.after.as_deref().unwrap_or(&self.name);
+        match self.op {
+            ChangeOp::Added => write!(f, "+ {item} {after}"),
+            ChangeOp::Removed => write!(f, "- {item} {before}"),
+            ChangeOp::Changed => write!(f, "~ {item} {before} => {after}"),
+            ChangeOp::Renamed => write!(f, "~ {item} {before} renamed to {after}"),
+        }
+    }
+}
+
+/// Result of [`diff_axi`] / [`diff_modules`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AxiDiff {
+    pub changes: Vec<AxiChange>,
+}
+
+impl AxiDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Number of changes with `op`.
+    pub fn count(&self, op: ChangeOp) -> usize {
+        self.changes.iter().filter(|c| c.op == op).count()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AxiDiffError {
+    #[error("old module: {0}")]
+    Old(SchemaV1ParseError),
+    #[error("new module: {0}")]
+    New(SchemaV1ParseError),
+}
+
+/// Parse both texts and diff them (see the module docs).
+pub fn diff_axi(old: &str, new: &str) -> Result<AxiDiff, AxiDiffError> {
+    let old = parse_schema_v1(old).map_err(AxiDiffError::Old)?;
+    let new = parse_schema_v1(new).map_err(AxiDiffError::New)?;
+    Ok(diff_modules(&old, &new))
+}
+
+/// Diff two parsed modules (see the module docs).
+pub fn diff_modules(old: &SchemaV1Module, new: &SchemaV1Module) -> AxiDiff {
+    let mut out = Changes::default();
+
+    // Detect renames against the original old module, then fold them into a
+    // copy so the rest of the diff only sees what the rename does not explain.
+    let renames = detect_object_renames(old, new);
+    let mut old = old.clone();
+    for (schema, from, to) in &renames {
+        apply_object_rename(&mut old, schema, from, to);
+        out.push(
+            Some(format!("schema {schema}")),
+            AxiItem::Object,
+            from,
+            ChangeOp::Renamed,
+            Some(from.clone()),
+            Some(to.clone()),
+        );
+    }
+
+    if old.module_name != new.module_name {
+        out.push(
+            None,
+            AxiItem::Module,
+            &old.module_name,
+            ChangeOp::Changed,
+            Some(old.module_name.clone()),
+            Some(new.module_name.clone()),
+        );
+    }
+    out.keyed(
+        None,
+        AxiItem::Import,
+        &old.imports,
+        &new.imports,
+        |i| i.clone(),
+        |i| i.clone(),
+    );
+
+    for (name, (o, n)) in pair_by_name(&old.schemas, &new.schemas, |s| &s.name) {
+        diff_schema(&mut out, name, o, n);
+    }
+    for (name, (o, n)) in pair_by_name(&old.theories, &new.theories, |t| &t.name) {
+        diff_theory(&mut out, name, o, n);
+    }
+    for (name, (o, n)) in pair_by_name(&old.instances, &new.instances, |i| &i.name) {
+        diff_instance(&mut out, name, o, n);
+    }
+
+    let mut changes = out.0;
+    changes.sort();
+    changes.dedup();
+    AxiDiff { changes }
+}
+
+// ============================================================================
+// Per-declaration diffs
+// ============================================================================
+
+#[derive(Default)]
+struct Changes(Vec<AxiChange>);
+
+impl Changes {
+    fn push(
+        &mut self,
+        scope: Option<String>,
+        item: AxiItem,
+        name: &str,
+        op: ChangeOp,
+        before: Option<String>,
+        after: Option<String>,
+    ) {
+        self.0.push(AxiChange {
+            scope,
+            item,
+            name: name.to_string(),
+            op,
+            before,
+            after,
+        });
+    }
+
+    /// Added/removed/changed for items identified by `key` and compared by
+    /// their rendering.
+    fn keyed<T>(
+        &mut self,
+        scope: Option<&str>,
+        item: AxiItem,
+        old: &[T],
+        new: &[T],
+        key: impl Fn(&T) -> String,
+        render: impl Fn(&T) -> String,
+    ) {
+        let old: BTreeMap<String, String> = old.iter().map(|t| (key(t), render(t))).collect();
+        let new: BTreeMap<String, String> = new.iter().map(|t| (key(t), render(t))).collect();
+        let scope = scope.map(str::to_string);
+        for (name, before) in &old {
+            match new.get(name) {
+                None => self.push(
+                    scope.clone(),
+                    item,
+                    name,
+                    ChangeOp::Removed,
+                    Some(before.clone()),
+                    None,
+                ),
+                Some(after) if after != before => self.push(
+                    scope.clone(),
+                    item,
+                    name,
+                    ChangeOp::Changed,
+                    Some(before.clone()),
+                    Some(after.clone()),
+                ),
+                Some(_) => {}
+            }
+        }
+        for (name, after) in &new {
+            if !old.contains_key(name) {
+                self.push(
+                    scope.clone(),
+                    item,
+                    name,
+                    ChangeOp::Added,
+                    None,
+                    Some(after.clone()),
+                );
+            }
+        }
+    }
+
+    /// Added/removed for a whole declaration; `None` on both sides never
+    /// happens.
+    fn presence(&mut self, item: AxiItem, name: &str, old: Option<String>, new: Option<String>) {
+        match (old, new) {
+            (Some(before), None) => {
+                self.push(None, item, name, ChangeOp::Removed, Some(before), None)
+            }
+            (None, Some(after)) => self.push(None, item, name, ChangeOp::Added, None, Some(after)),
+            (Some(before), Some(after)) if before != after => self.push(
+                None,
+                item,
+                name,
+                ChangeOp::Changed,
+                Some(before),
+                Some(after),
+            ),
+            _ => {}
+        }
+    }
+}
+
+type Pair<'a, T> = (Option<&'a T>, Option<&'a T>);
+
+fn pair_by_name<'a, T>(
+    old: &'a [T],
+    new: &'a [T],
+    name: impl Fn(&T) -> &String,
+) -> BTreeMap<&'a str, Pair<'a, T>> {
+    let mut pairs: BTreeMap<&str, Pair<'a, T>> = BTreeMap::new();
+    for t in old {
+        pairs.entry(name(t).as_str()).or_default().0 = Some(t);
+    }
+    for t in new {
+        pairs.entry(name(t).as_str()).or_default().1 = Some(t);
+    }
+    pairs
+}
+
+/// The `items` of a declaration that may be missing on one side.
+fn items<'a, D, T>(decl: Option<&'a D>, field: impl Fn(&'a D) -> &'a Vec<T>) -> &'a [T] {
+    decl.map_or(&[], |d| field(d).as_slice())
+}
+
+/// A missing side diffs as an empty declaration, so an added schema also
+/// lists what it adds.
+fn diff_schema(
+    out: &mut Changes,
+    name: &str,
+    old: Option<&SchemaV1Schema>,
+    new: Option<&SchemaV1Schema>,
+) {
+    out.presence(
+        AxiItem::Schema,
+        name,
+        old.map(|_| name.to_string()),
+        new.map(|_| name.to_string()),
+    );
+    let scope = format!("schema {name}");
+    let scope = Some(scope.as_str());
+
+    out.keyed(
+        scope,
+        AxiItem::Object,
+        items(old, |s| &s.objects),
+        items(new, |s| &s.objects),
+        Clone::clone,
+        Clone::clone,
+    );
+    out.keyed(
+        scope,
+        AxiItem::Subtype,
+        items(old, |s| &s.subtypes),
+        items(new, |s| &s.subtypes),
+        |s| format!("{} < {}", s.sub, s.sup),
+        |s| match &s.inclusion {
+            Some(inclusion) => format!("{} < {} as {inclusion}", s.sub, s.sup),
+            None => format!("{} < {}", s.sub, s.sup),
+        },
+    );
+    out.keyed(
+        scope,
+        AxiItem::Relation,
+        items(old, |s| &s.relations),
+        items(new, |s| &s.relations),
+        |r| r.name.clone(),
+        |r| {
+            let fields = r
+                .fields
+                .iter()
+                .map(|f| format!("{}: {}", f.field, f.ty))
+                .collect::<Vec<_>>();
+            format!("{}({})", r.name, fields.join(", "))
+        },
+    );
+}
+
+fn diff_theory(
+    out: &mut Changes,
+    name: &str,
+    old: Option<&SchemaV1Theory>,
+    new: Option<&SchemaV1Theory>,
+) {
+    let header = |t: &SchemaV1Theory| format!("{} on {}", t.name, t.schema);
+    out.presence(AxiItem::Theory, name, old.map(header), new.map(header));
+    let scope = format!("theory {name}");
+
+    diff_constraints(
+        out,
+        &scope,
+        items(old, |t| &t.constraints),
+        items(new, |t| &t.constraints),
+    );
+    let scope = Some(scope.as_str());
+    out.keyed(
+        scope,
+        AxiItem::Equation,
+        items(old, |t| &t.equations),
+        items(new, |t| &t.equations),
+        |e| e.name.clone(),
+        |e| format!("{}: {} = {}", e.name, e.lhs, e.rhs),
+    );
+    out.keyed(
+        scope,
+        AxiItem::RewriteRule,
+        items(old, |t| &t.rewrite_rules),
+        items(new, |t| &t.rewrite_rules),
+        |r| r.name.clone(),
+        render_rewrite_rule,
+    );
+    out.keyed(
+        scope,
+        AxiItem::View,
+        items(old, |t| &t.views),
+        items(new, |t| &t.views),
+        |v| v.name.clone(),
+        |v| format!("{} = {}", v.name, v.query),
+    );
+}
+
+fn diff_instance(
+    out: &mut Changes,
+    name: &str,
+    old: Option<&SchemaV1Instance>,
+    new: Option<&SchemaV1Instance>,
+) {
+    let header = |i: &SchemaV1Instance| format!("{} of {}", i.name, i.schema);
+    out.presence(AxiItem::Instance, name, old.map(header), new.map(header));
+    let scope = format!("instance {name}");
+
+    let (old, new) = (assignment_members(old), assignment_members(new));
+    let render = |name: &str, items: &BTreeSet<String>| {
+        let items = items.iter().cloned().collect::<Vec<_>>();
+        format!("{name} = {{{}}}", items.join(", "))
+    };
+    for name in old.keys().chain(new.keys()).collect::<BTreeSet<_>>() {
+        let (before, after) = (old.get(name), new.get(name));
+        match (before, after) {
+            (Some(items), None) => out.push(
+                Some(scope.clone()),
+                AxiItem::Assignment,
+                name,
+                ChangeOp::Removed,
+                Some(render(name, items)),
+                None,
+            ),
+            (None, Some(items)) => out.push(
+                Some(scope.clone()),
+                AxiItem::Assignment,
+                name,
+                ChangeOp::Added,
+                None,
+                Some(render(name, items)),
+            ),
+            _ => {}
+        }
+        // Members of an assignment present on both sides are listed one by
+        // one; a whole new or removed assignment is listed once above.
+        let (Some(before), Some(after)) = (before, after) else {
+            continue;
+        };
+        for member in before.difference(after) {
+            out.push(
+                Some(scope.clone()),
+                AxiItem::Member,
+                name,
+                ChangeOp::Removed,
+                Some(format!("{name}: {member}")),
+                None,
+            );
+        }
+        for member in after.difference(before) {
+            out.push(
+                Some(scope.clone()),
+                AxiItem::Member,
+                name,
+                ChangeOp::Added,
+                None,
+                Some(format!("{name}: {member}")),
+            );
+        }
+    }
+}
+
+/// Rendered members per assignment name (repeated assignments are merged).
+fn assignment_members(instance: Option<&SchemaV1Instance>) -> BTreeMap<&str, BTreeSet<String>> {
+    let mut members: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    for assignment in instance.iter().flat_map(|i| &i.assignments) {
+        members
+            .entry(assignment.name.as_str())
+            .or_default()
+            .extend(assignment.value.items.iter().map(render_set_item));
+    }
+    members
+}
+
+/// Constraints have no names: compare canonical texts as sets, then report a
+/// lone removed/added pair with the same identity (kind + relation, or block
+/// name) as one change.
+fn diff_constraints(out: &mut Changes, scope: &str, old: &[ConstraintV1], new: &[ConstraintV1]) {
+    let rendered = |cs: &[ConstraintV1]| -> BTreeMap<String, Option<String>> {
+        cs.iter()
+            .map(|c| (render_constraint(c), constraint_identity(c)))
+            .collect()
+    };
+    let (old, new) = (rendered(old), rendered(new));
+    let removed: Vec<(&String, &Option<String>)> =
+        old.iter().filter(|(c, _)| !new.contains_key(*c)).collect();
+    let added: Vec<(&String, &Option<String>)> =
+        new.iter().filter(|(c, _)| !old.contains_key(*c)).collect();
+
+    let lone = |side: &[(&String, &Option<String>)], id: &String| {
+        side.iter()
+            .filter(|(_, other)| other.as_ref() == Some(id))
+            .count()
+            == 1
+    };
+    let mut paired = BTreeSet::new();
+    for (before, id) in &removed {
+        let Some(id) = id else { continue };
+        if !lone(&removed, id) || !lone(&added, id) {
+            continue;
+        }
+        let (after, _) = added
+            .iter()
+            .find(|(_, other)| other.as_ref() == Some(id))
+            .expect("lone added constraint");
+        paired.insert((*before).clone());
+        paired.insert((*after).clone());
+        out.push(
+            Some(scope.to_string()),
+            AxiItem::Constraint,
+            id,
+            ChangeOp::Changed,
+            Some((*before).clone()),
+            Some((*after).clone()),
+        );
+    }
+    for (before, id) in removed {
+        if !paired.contains(before) {
+            out.push(
+                Some(scope.to_string()),
+                AxiItem::Constraint,
+                id.as_deref().unwrap_or(before),
+                ChangeOp::Removed,
+                Some(before.clone()),
+                None,
+            );
+        }
+    }
+    for (after, id) in added {
+        if !paired.contains(after) {
+            out.push(
+                Some(scope.to_string()),
+                AxiItem::Constraint,
+                id.as_deref().unwrap_or(after),
+                ChangeOp::Added,
+                None,
+                Some(after.clone()),
+            );
+        }
+    }
+}
+
+// ============================================================================
+// Object renames
+// ============================================================================
+
+/// Where an object is used in `schema`: fields of the relations `other` also
+/// declares, and subtype edges to objects `other` also declares.
+fn object_usage(schema: &SchemaV1Schema, other: &SchemaV1Schema, object: &str) -> BTreeSet<String> {
+    let mut usage = BTreeSet::new();
+    for relation in &schema.relations {
+        if !other.relations.iter().any(|r| r.name == relation.name) {
+            continue;
+        }
+        for field in relation.fields.iter().filter(|f| f.ty == object) {
+            usage.insert(format!("{}.{}", relation.name, field.field));
+        }
+    }
+    for subtype in &schema.subtypes {
+        if subtype.sub == object && other.objects.contains(&subtype.sup) {
+            usage.insert(format!("< {}", subtype.sup));
+        }
+        if subtype.sup == object && other.objects.contains(&subtype.sub) {
+            usage.insert(format!("> {}", subtype.sub));
+        }
+    }
+    usage
+}
+
+/// `(schema, from, to)` for every removed object with exactly one added
+/// counterpart of identical (non-empty) usage among the declarations both
+/// sides share, and vice versa.
+fn detect_object_renames(
+    old: &SchemaV1Module,
+    new: &SchemaV1Module,
+) -> Vec<(String, String, String)> {
+    let mut renames = Vec::new();
+    for (name, pair) in pair_by_name(&old.schemas, &new.schemas, |s| &s.name) {
+        let (Some(old), Some(new)) = pair else {
+            continue;
+        };
+        let usages = |schema: &SchemaV1Schema, other: &SchemaV1Schema| {
+            schema
+                .objects
+                .iter()
+                .filter(|o| !other.objects.contains(o))
+                .map(|o| (o.clone(), object_usage(schema, other, o)))
+                .filter(|(_, usage)| !usage.is_empty())
+                .collect::<Vec<_>>()
+        };
+        let (removed, added) = (usages(old, new), usages(new, old));
+        for (from, usage) in &removed {
+            let matches = |side: &[(String, BTreeSet<String>)]| {
+                side.iter()
+                    .filter(|(_, u)| u == usage)
+                    .map(|(o, _)| o.clone())
+                    .collect::<Vec<_>>()
+            };
+            if let ([to], [_]) = (matches(&added).as_slice(), matches(&removed).as_slice()) {
+                renames.push((name.to_string(), from.clone(), to.clone()));
+            }
+        }
+    }
+    renames
+}
+
+/// Rewrite `from` to `to` wherever it names the object of `schema`.
+fn apply_object_rename(module: &mut SchemaV1Module, schema: &str, from: &str, to: &str) {
+    let rename = |name: &mut String| {
+        if name == from {
+            *name = to.to_string();
+        }
+    };
+    for s in module.schemas.iter_mut().filter(|s| s.name == schema) {
+        s.objects.iter_mut().for_each(rename);
+        for subtype in &mut s.subtypes {
+            rename(&mut subtype.sub);
+            rename(&mut subtype.sup);
+        }
+        for relation in &mut s.relations {
+            relation.fields.iter_mut().for_each(|f| rename(&mut f.ty));
+        }
+    }
+    for theory in module.theories.iter_mut().filter(|t| t.schema == schema) {
+        for var in theory.rewrite_rules.iter_mut().flat_map(|r| &mut r.vars) {
+            match &mut var.ty {
+                RewriteVarTypeV1::Object { ty } => rename(ty),
+                RewriteVarTypeV1::Path { from, to } => {
+                    rename(from);
+                    rename(to);
+                }
+            }
+        }
+    }
+    for instance in module.instances.iter_mut().filter(|i| i.schema == schema) {
+        instance
+            .assignments
+            .iter_mut()
+            .for_each(|a| rename(&mut a.name));
+    }
+}
+
+// ============================================================================
+// One-line renderings
+// ============================================================================
+
+fn render_constraint(constraint: &ConstraintV1) -> String {
+    match constraint {
+        ConstraintV1::NamedBlock { name, body } => format!("{name}: {}", body.join(" ")),
+        ConstraintV1::Unknown { text } => text.clone(),
+        other => match format_constraint_v1(other) {
+            Ok(text) => text.trim_start_matches("constraint ").to_string(),
+            Err(_) => format!("{other:?}"),
+        },
+    }
+}
+
+/// Identity used to pair a removed and an added constraint.
+fn constraint_identity(constraint: &ConstraintV1) -> Option<String> {
+    let (kind, relation) = match constraint {
+        ConstraintV1::Functional { relation, .. } => ("functional", relation),
+        ConstraintV1::AtMost { relation, .. } => ("at_most", relation),
+        ConstraintV1::Typing { relation, .. } => ("typing", relation),
+        ConstraintV1::SymmetricWhereIn { relation, .. }
+        | ConstraintV1::Symmetric { relation, .. } => ("symmetric", relation),
+        ConstraintV1::Transitive { relation, .. } => ("transitive", relation),
+        ConstraintV1::Key { relation, .. } => ("key", relation),
+        ConstraintV1::NamedBlock { name, .. } => return Some(name.clone()),
+        ConstraintV1::Unknown { .. } => return None,
+    };
+    Some(format!("{kind} {relation}"))
+}
+
+fn render_rewrite_rule(rule: &RewriteRuleV1) -> String {
+    let arrow = match rule.orientation {
+        RewriteOrientationV1::Forward => "=>",
+        RewriteOrientationV1::Backward => "<=",
+        RewriteOrientationV1::Bidirectional => "<=>",
+    };
+    let mut out = format!("{}: {} {arrow} {}", rule.name, rule.lhs, rule.rhs);
+    if !rule.vars.is_empty() {
+        let vars = rule
+            .vars
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        out.push_str(&format!(" (vars: {})", vars.join(", ")));
+    }
+    out
+}
+
+/// Tuple fields are sorted: `(a=x, b=y)` and `(b=y, a=x)` are one member.
+fn render_set_item(item: &SetItemV1) -> String {
+    match item {
+        SetItemV1::Ident { name } => name.clone(),
+        SetItemV1::Tuple { fields } => {
+            let mut fields = fields
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect::<Vec<_>>();
+            fields.sort();
+            format!("({})", fields.join(", "))
+        }
+    }
+}
@@ -8,5 +8,10 @@
 //! auto-detects the dialect for the canonical corpus.
 
 pub mod axi_v1;
+pub mod diff;
 pub mod digest;
+pub mod lint;
+pub mod printer;
+pub mod regen;
 pub mod schema_v1;
+pub mod template;
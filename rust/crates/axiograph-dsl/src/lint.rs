@@ -0,0 +1,841 @@
+//! `.axi` formatter and linter (editor / CI tooling).
+//!
+//! - [`format_axi`] is *surgical*: it keeps comments and layout, canonicalizes
+//!   single-line theory constraints, strips trailing whitespace and collapses
+//!   runs of blank lines. It never fails; text it cannot improve is kept
+//!   verbatim (the linter reports it instead).
+//! - [`lint_axi`] parses the module and reports unknown types, duplicate names,
+//!   unused objects and suspicious constraints, with 1-based line numbers.
+//!   Theories and instances of a module that declares no schemas at all
+//!   (e.g. storage output against a shared schema) or that has `import`s are
+//!   not checked against a missing schema; the module graph resolves those.
+//!
+//! For a full canonical rewrite (comments dropped) see
+//! `printer::canonicalize_axi_v1`.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::schema_v1::{
+    format_constraint_v1, parse_constraint_v1, parse_schema_v1, strip_comment, CarrierFieldsV1,
+    ConstraintV1, PathExprV3, RewriteVarTypeV1, SchemaV1Module, SchemaV1ParseError, SchemaV1Schema,
+    SetItemV1,
+};
+
+// ============================================================================
+// Diagnostics
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// What a diagnostic is about (stable, machine-readable).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintCode {
+    ParseError,
+    UnknownType,
+    DuplicateName,
+    UnusedObject,
+    SuspiciousConstraint,
+}
+
+impl LintCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LintCode::ParseError => "parse_error",
+            LintCode::UnknownType => "unknown_type",
+            LintCode::DuplicateName => "duplicate_name",
+            LintCode::UnusedObject => "unused_object",
+            LintCode::SuspiciousConstraint => "suspicious_constraint",
+        }
+    }
+
+    fn severity(self) -> Severity {
+        match self {
+            LintCode::ParseError | LintCode::UnknownType | LintCode::DuplicateName => {
+                Severity::Error
+            }
+            LintCode::UnusedObject | LintCode::SuspiciousConstraint => Severity::Warning,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: LintCode,
+    /// 1-based line, when the finding can be pinned to one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn new(code: LintCode, line: Option<usize>, message: String) -> Self {
+        Self {
+            severity: code.severity(),
+            code,
+            line,
+            message,
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        if let Some(line) = self.line {
+            write!(f, "line {line}: ")?;
+        }
+        write!(f, "{severity}[{}]: {}", self.code.as_str(), self.message)
+    }
+}
+
+// ============================================================================
+// Formatter
+// ============================================================================
+
+pub(crate) fn split_line_comment(line: &str) -> (&str, Option<&str>) {
+    // `.axi` uses `--` comments (Idris/Lean style).
+    match line.find("--") {
+        Some(idx) => (&line[..idx], Some(&line[idx..])),
+        None => (line, None),
+    }
+}
+
+pub(crate) fn indent_of(s: &str) -> usize {
+    s.chars().take_while(|c| c.is_whitespace()).count()
+}
+
+fn is_top_level_keyword(trimmed: &str) -> bool {
+    [
+        "schema ",
+        "theory ",
+        "instance ",
+        "module ",
+        "constraint ",
+        "equation ",
+        "rewrite ",
+    ]
+    .iter()
+    .any(|k| trimmed.starts_with(k))
+}
+
+/// Rewrite the readable shorthand `symmetric Rel where field in {A, B}` into
+/// the canonical `symmetric Rel where Rel.field in {A, B}`.
+fn fix_symmetric_where_shorthand(rest: &str) -> String {
+    let rest = rest.trim();
+    let Some(after) = rest.strip_prefix("symmetric ").map(str::trim) else {
+        return rest.to_string();
+    };
+    let Some((relation, guard)) = after.split_once(" where ") else {
+        return rest.to_string();
+    };
+    let relation = relation.trim();
+    let Some((lhs, rhs)) = guard.trim().split_once(" in ") else {
+        return rest.to_string();
+    };
+    let (lhs, rhs) = (lhs.trim(), rhs.trim());
+    if lhs.contains('.') || relation.is_empty() {
+        return rest.to_string();
+    }
+    format!("symmetric {relation} where {relation}.{lhs} in {rhs}")
+}
+
+/// Canonical form of a `constraint ...` statement, or `None` when it is a
+/// named block, malformed, or outside the canonical subset.
+fn canonical_constraint(rest: &str) -> Option<String> {
+    let rest = fix_symmetric_where_shorthand(rest);
+    match parse_constraint_v1(&rest).ok()? {
+        ConstraintV1::Unknown { .. } | ConstraintV1::NamedBlock { .. } => None,
+        constraint => format_constraint_v1(&constraint).ok(),
+    }
+}
+
+/// Tidy `.axi` text without changing its meaning or dropping comments.
+///
+/// Idempotent: `format_axi(&format_axi(t)) == format_axi(t)`.
+pub fn format_axi(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut out: Vec<String> = Vec::with_capacity(lines.len());
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i].trim_end();
+        let (code, comment) = split_line_comment(line);
+        let code = code.trim_end();
+        let trimmed = code.trim_start();
+
+        if let Some(rest) = trimmed.strip_prefix("constraint ") {
+            let indent_len = indent_of(code);
+            // Fold indented continuation lines (never blank lines, never a
+            // following statement) into the constraint.
+            let mut parts = vec![rest.trim().to_string()];
+            let mut j = i + 1;
+            while !rest.trim().ends_with(':') && j < lines.len() {
+                let (next, next_comment) = split_line_comment(lines[j]);
+                let next = next.trim_end();
+                if next.trim().is_empty()
+                    || next_comment.is_some()
+                    || indent_of(next) <= indent_len
+                    || is_top_level_keyword(next.trim())
+                {
+                    break;
+                }
+                parts.push(next.trim().to_string());
+                j += 1;
+            }
+            if let Some(formatted) = canonical_constraint(&parts.join(" ")) {
+                let mut rendered = format!("{}{}", &code[..indent_len], formatted.trim_end());
+                if let Some(c) = comment {
+                    rendered.push(' ');
+                    rendered.push_str(c);
+                }
+                out.push(rendered);
+                i = j;
+                continue;
+            }
+        }
+
+        let blank = line.trim().is_empty();
+        let previous_blank = out.last().is_none_or(|l| l.is_empty());
+        if !(blank && previous_blank) {
+            out.push(if blank {
+                String::new()
+            } else {
+                line.to_string()
+            });
+        }
+        i += 1;
+    }
+    while out.last().is_some_and(|l| l.is_empty()) {
+        out.pop();
+    }
+
+    let mut rendered = out.join("\n");
+    if !rendered.is_empty() {
+        rendered.push('\n');
+    }
+    rendered
+}
+
+// ============================================================================
+// Line index
+// ============================================================================
+
+/// Source lines of the declarations the parser collected, in the same order
+/// as the AST vectors (so `objects[k]` was declared on `objects[k]`'s line).
+#[derive(Default)]
+struct SectionLines {
+    header: Option<usize>,
+    objects: Vec<usize>,
+    subtypes: Vec<usize>,
+    relations: Vec<usize>,
+    constraints: Vec<usize>,
+    assignments: Vec<usize>,
+}
+
+#[derive(Default)]
+struct LineIndex {
+    schemas: Vec<SectionLines>,
+    theories: Vec<SectionLines>,
+    instances: Vec<SectionLines>,
+}
+
+fn starts_assignment(code: &str) -> bool {
+    let Some((name, _)) = code.split_once('=') else {
+        return false;
+    };
+    let name = name.trim();
+    !name.is_empty() && !name.contains(['(', ')', ',', '{', '}'])
+}
+
+impl LineIndex {
+    fn build(text: &str) -> Self {
+        enum Current {
+            None,
+            Schema,
+            Theory,
+            Instance,
+        }
+        let mut index = LineIndex::default();
+        let mut current = Current::None;
+        for (i, raw) in text.lines().enumerate() {
+            let line_no = i + 1;
+            let code = strip_comment(raw).trim();
+            let header = SectionLines {
+                header: Some(line_no),
+                ..SectionLines::default()
+            };
+            if code.starts_with("schema ") {
+                index.schemas.push(header);
+                current = Current::Schema;
+                continue;
+            }
+            if code.starts_with("theory ") {
+                index.theories.push(header);
+                current = Current::Theory;
+                continue;
+            }
+            if code.starts_with("instance ") {
+                index.instances.push(header);
+                current = Current::Instance;
+                continue;
+            }
+            match current {
+                Current::Schema => {
+                    let Some(s) = index.schemas.last_mut() else {
+                        continue;
+                    };
+                    if code.starts_with("object ") {
+                        s.objects.push(line_no);
+                    } else if code.starts_with("subtype ") {
+                        s.subtypes.push(line_no);
+                    } else if code.starts_with("relation ") {
+                        s.relations.push(line_no);
+                    }
+                }
+                Current::Theory => {
+                    if code.starts_with("constraint ") {
+                        if let Some(t) = index.theories.last_mut() {
+                            t.constraints.push(line_no);
+                        }
+                    }
+                }
+                Current::Instance => {
+                    if starts_assignment(code) {
+                        if let Some(inst) = index.instances.last_mut() {
+                            inst.assignments.push(line_no);
+                        }
+                    }
+                }
+                Current::None => {}
+            }
+        }
+        index
+    }
+}
+
+fn at(
+    lines: Option<&SectionLines>,
+    pick: impl Fn(&SectionLines) -> Option<usize>,
+) -> Option<usize> {
+    lines.and_then(pick)
+}
+
+// ============================================================================
+// Linter
+// ============================================================================
+
+fn constraint_relation(c: &ConstraintV1) -> Option<&str> {
+    match c {
+        ConstraintV1::Functional { relation, .. }
+        | ConstraintV1::AtMost { relation, .. }
+        | ConstraintV1::Typing { relation, .. }
+        | ConstraintV1::SymmetricWhereIn { relation, .. }
+        | ConstraintV1::Symmetric { relation, .. }
+        | ConstraintV1::Transitive { relation, .. }
+        | ConstraintV1::Key { relation, .. } => Some(relation),
+        ConstraintV1::NamedBlock { .. } | ConstraintV1::Unknown { .. } => None,
+    }
+}
+
+fn closure_fields<'c>(
+    carriers: &'c Option<CarrierFieldsV1>,
+    params: &'c Option<Vec<String>>,
+    out: &mut Vec<&'c str>,
+) {
+    if let Some(c) = carriers {
+        out.push(&c.left_field);
+        out.push(&c.right_field);
+    }
+    out.extend(params.iter().flatten().map(String::as_str));
+}
+
+/// Fields a constraint refers to (carriers, params, keys, guards).
+fn constraint_fields(c: &ConstraintV1) -> Vec<&str> {
+    let mut fields: Vec<&str> = Vec::new();
+    match c {
+        ConstraintV1::Functional {
+            src_field,
+            dst_field,
+            ..
+        } => return vec![src_field, dst_field],
+        ConstraintV1::AtMost {
+            src_field,
+            dst_field,
+            params,
+            ..
+        } => {
+            closure_fields(&None, params, &mut fields);
+            fields.extend([src_field.as_str(), dst_field.as_str()]);
+        }
+        ConstraintV1::SymmetricWhereIn {
+            field,
+            carriers,
+            params,
+            ..
+        } => {
+            closure_fields(carriers, params, &mut fields);
+            fields.push(field);
+        }
+        ConstraintV1::Symmetric {
+            carriers, params, ..
+        }
+        | ConstraintV1::Transitive {
+            carriers, params, ..
+        } => closure_fields(carriers, params, &mut fields),
+        ConstraintV1::Key { fields: keys, .. } => return keys.iter().map(String::as_str).collect(),
+        ConstraintV1::Typing { .. }
+        | ConstraintV1::NamedBlock { .. }
+        | ConstraintV1::Unknown { .. } => {}
+    }
+    fields
+}
+
+fn path_types<'a>(expr: &'a PathExprV3, out: &mut BTreeSet<&'a str>) {
+    match expr {
+        PathExprV3::Var { .. } => {}
+        PathExprV3::Reflexive { entity } => {
+            out.insert(entity);
+        }
+        PathExprV3::Step { from, to, .. } => {
+            out.insert(from);
+            out.insert(to);
+        }
+        PathExprV3::Trans { left, right } => {
+            path_types(left, out);
+            path_types(right, out);
+        }
+        PathExprV3::Inv { path } => path_types(path, out),
+    }
+}
+
+fn duplicates<'a>(names: impl IntoIterator<Item = &'a str>) -> Vec<(usize, &'a str)> {
+    let mut seen = BTreeSet::new();
+    names
+        .into_iter()
+        .enumerate()
+        .filter(|(_, n)| !seen.insert(*n))
+        .collect()
+}
+
+struct Linter<'a> {
+    module: &'a SchemaV1Module,
+    lines: &'a LineIndex,
+    out: Vec<Diagnostic>,
+}
+
+impl<'a> Linter<'a> {
+    fn push(&mut self, code: LintCode, line: Option<usize>, message: String) {
+        self.out.push(Diagnostic::new(code, line, message));
+    }
+
+    fn schema(&self, name: &str) -> Option<&'a SchemaV1Schema> {
+        self.module.schemas.iter().find(|s| s.name == name)
+    }
+
+    fn module_names(&mut self) {
+        let (module, index) = (self.module, self.lines);
+        for (kind, names, lines) in [
+            (
+                "schema",
+                module
+                    .schemas
+                    .iter()
+                    .map(|s| s.name.as_str())
+                    .collect::<Vec<_>>(),
+                &index.schemas,
+            ),
+            (
+                "theory",
+                module.theories.iter().map(|t| t.name.as_str()).collect(),
+                &index.theories,
+            ),
+            (
+                "instance",
+                module.instances.iter().map(|i| i.name.as_str()).collect(),
+                &index.instances,
+            ),
+        ] {
+            let found: Vec<_> = duplicates(names)
+                .into_iter()
+                .map(|(k, name)| (lines.get(k).and_then(|l| l.header), name))
+                .collect();
+            for (line, name) in found {
+                self.push(
+                    LintCode::DuplicateName,
+                    line,
+                    format!("{kind} `{name}` is declared more than once"),
+                );
+            }
+        }
+    }
+
+    fn schemas(&mut self) {
+        let index = self.lines;
+        for (s, schema) in self.module.schemas.iter().enumerate() {
+            let lines = index.schemas.get(s);
+            let objects: BTreeSet<&str> = schema.objects.iter().map(String::as_str).collect();
+            let relations: BTreeSet<&str> =
+                schema.relations.iter().map(|r| r.name.as_str()).collect();
+            let mut found = Vec::new();
+
+            for (k, name) in duplicates(schema.objects.iter().map(String::as_str)) {
+                found.push(Diagnostic::new(
+                    LintCode::DuplicateName,
+                    at(lines, |l| l.objects.get(k).copied()),
+                    format!(
+                        "object `{name}` is declared more than once in schema `{}`",
+                        schema.name
+                    ),
+                ));
+            }
+            for (k, name) in duplicates(schema.relations.iter().map(|r| r.name.as_str())) {
+                found.push(Diagnostic::new(
+                    LintCode::DuplicateName,
+                    at(lines, |l| l.relations.get(k).copied()),
+                    format!(
+                        "relation `{name}` is declared more than once in schema `{}`",
+                        schema.name
+                    ),
+                ));
+            }
+            for (k, subtype) in schema.subtypes.iter().enumerate() {
+                let line = at(lines, |l| l.subtypes.get(k).copied());
+                for ty in [&subtype.sub, &subtype.sup] {
+                    if !objects.contains(ty.as_str()) {
+                        found.push(Diagnostic::new(
+                            LintCode::UnknownType,
+                            line,
+                            format!("subtype refers to undeclared object `{ty}`"),
+                        ));
+                    }
+                }
+            }
+            for (k, relation) in schema.relations.iter().enumerate() {
+                let line = at(lines, |l| l.relations.get(k).copied());
+                if objects.contains(relation.name.as_str()) {
+                    // Legal, but instance assignments to the name are ambiguous.
+                    found.push(Diagnostic {
+                        severity: Severity::Warning,
+                        ..Diagnostic::new(
+                            LintCode::DuplicateName,
+                            line,
+                            format!(
+                                "relation `{}` has the same name as an object",
+                                relation.name
+                            ),
+                        )
+                    });
+                }
+                for (_, field) in duplicates(relation.fields.iter().map(|f| f.field.as_str())) {
+                    found.push(Diagnostic::new(
+                        LintCode::DuplicateName,
+                        line,
+                        format!("relation `{}` has field `{field}` twice", relation.name),
+                    ));
+                }
+                for field in &relation.fields {
+                    // Relations are types too (reified tuples).
+                    if !objects.contains(field.ty.as_str())
+                        && !relations.contains(field.ty.as_str())
+                    {
+                        found.push(Diagnostic::new(
+                            LintCode::UnknownType,
+                            line,
+                            format!(
+                                "field `{}.{}` has undeclared type `{}`",
+                                relation.name, field.field, field.ty
+                            ),
+                        ));
+                    }
+                }
+            }
+
+            // Objects nothing refers to: no field, subtype, rewrite variable,
+            // rewrite path or instance assignment mentions them.
+            let mut used: BTreeSet<&str> = BTreeSet::new();
+            for relation in &schema.relations {
+                used.extend(relation.fields.iter().map(|f| f.ty.as_str()));
+            }
+            for subtype in &schema.subtypes {
+                used.insert(&subtype.sub);
+                used.insert(&subtype.sup);
+            }
+            for theory in self
+                .module
+                .theories
+                .iter()
+                .filter(|t| t.schema == schema.name)
+            {
+                for rule in &theory.rewrite_rules {
+                    for var in &rule.vars {
+                        match &var.ty {
+                            RewriteVarTypeV1::Object { ty } => {
+                                used.insert(ty);
+                            }
+                            RewriteVarTypeV1::Path { from, to } => {
+                                used.insert(from);
+                                used.insert(to);
+                            }
+                        }
+                    }
+                    path_types(&rule.lhs, &mut used);
+                    path_types(&rule.rhs, &mut used);
+                }
+            }
+            for instance in self
+                .module
+                .instances
+                .iter()
+                .filter(|i| i.schema == schema.name)
+            {
+                used.extend(instance.assignments.iter().map(|a| a.name.as_str()));
+            }
+            let mut reported = BTreeSet::new();
+            for (k, object) in schema.objects.iter().enumerate() {
+                if !used.contains(object.as_str()) && reported.insert(object) {
+                    found.push(Diagnostic::new(
+                        LintCode::UnusedObject,
+                        at(lines, |l| l.objects.get(k).copied()),
+                        format!(
+                            "object `{object}` is not used by any relation, subtype, rule or instance"
+                        ),
+                    ));
+                }
+            }
+
+            self.out.extend(found);
+        }
+    }
+
+    /// Schemas this module does not declare may live elsewhere.
+    fn schema_may_be_external(&self) -> bool {
+        self.module.schemas.is_empty() || !self.module.imports.is_empty()
+    }
+
+    fn theories(&mut self) {
+        let index = self.lines;
+        for (t, theory) in self.module.theories.iter().enumerate() {
+            let lines = index.theories.get(t);
+            let header = at(lines, |l| l.header);
+            let Some(schema) = self.schema(&theory.schema) else {
+                if self.schema_may_be_external() {
+                    continue;
+                }
+                self.push(
+                    LintCode::UnknownType,
+                    header,
+                    format!(
+                        "theory `{}` is on unknown schema `{}`",
+                        theory.name, theory.schema
+                    ),
+                );
+                continue;
+            };
+
+            let named = theory.constraints.iter().filter_map(|c| match c {
+                ConstraintV1::NamedBlock { name, .. } => Some(name.as_str()),
+                _ => None,
+            });
+            let names = named
+                .chain(theory.equations.iter().map(|e| e.name.as_str()))
+                .chain(theory.rewrite_rules.iter().map(|r| r.name.as_str()))
+                .chain(theory.views.iter().map(|v| v.name.as_str()));
+            let found: Vec<_> = duplicates(names).into_iter().map(|(_, n)| n).collect();
+            for name in found {
+                self.push(
+                    LintCode::DuplicateName,
+                    header,
+                    format!(
+                        "`{name}` is declared more than once in theory `{}`",
+                        theory.name
+                    ),
+                );
+            }
+
+            let mut seen: Vec<&ConstraintV1> = Vec::new();
+            for (k, constraint) in theory.constraints.iter().enumerate() {
+                let line = at(lines, |l| l.constraints.get(k).copied());
+                for message in suspicious_constraint(schema, constraint, &seen) {
+                    self.push(LintCode::SuspiciousConstraint, line, message);
+                }
+                seen.push(constraint);
+            }
+        }
+    }
+
+    fn instances(&mut self) {
+        let index = self.lines;
+        for (i, instance) in self.module.instances.iter().enumerate() {
+            let lines = index.instances.get(i);
+            let Some(schema) = self.schema(&instance.schema) else {
+                if self.schema_may_be_external() {
+                    continue;
+                }
+                self.push(
+                    LintCode::UnknownType,
+                    at(lines, |l| l.header),
+                    format!(
+                        "instance `{}` is of unknown schema `{}`",
+                        instance.name, instance.schema
+                    ),
+                );
+                continue;
+            };
+            for (k, assignment) in instance.assignments.iter().enumerate() {
+                let line = at(lines, |l| l.assignments.get(k).copied());
+                let relation = schema.relations.iter().find(|r| r.name == assignment.name);
+                let is_object = schema.objects.contains(&assignment.name);
+                if relation.is_none() && !is_object {
+                    self.push(
+                        LintCode::UnknownType,
+                        line,
+                        format!(
+                            "`{}` is neither an object nor a relation of schema `{}`",
+                            assignment.name, schema.name
+                        ),
+                    );
+                    continue;
+                }
+                let Some(relation) = relation else {
+                    continue;
+                };
+                let declared: BTreeSet<&str> =
+                    relation.fields.iter().map(|f| f.field.as_str()).collect();
+                let mut unknown: BTreeSet<&str> = BTreeSet::new();
+                for item in &assignment.value.items {
+                    if let SetItemV1::Tuple { fields } = item {
+                        unknown.extend(
+                            fields
+                                .iter()
+                                .map(|(f, _)| f.as_str())
+                                .filter(|f| !declared.contains(f)),
+                        );
+                    }
+                }
+                for field in unknown {
+                    self.push(
+                        LintCode::UnknownType,
+                        line,
+                        format!("relation `{}` has no field `{field}`", relation.name),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Warnings for one theory constraint (`earlier` are the ones before it).
+fn suspicious_constraint(
+    schema: &SchemaV1Schema,
+    constraint: &ConstraintV1,
+    earlier: &[&ConstraintV1],
+) -> Vec<String> {
+    let mut out = Vec::new();
+    if let ConstraintV1::Unknown { text } = constraint {
+        out.push(format!(
+            "constraint `{text}` is outside the canonical subset (not checkable or certifiable)"
+        ));
+        return out;
+    }
+    if earlier.contains(&constraint) {
+        out.push("constraint repeats an earlier one".to_string());
+    }
+    let Some(name) = constraint_relation(constraint) else {
+        return out;
+    };
+    let Some(relation) = schema.relations.iter().find(|r| r.name == name) else {
+        out.push(format!(
+            "constraint refers to relation `{name}`, which schema `{}` does not declare",
+            schema.name
+        ));
+        return out;
+    };
+    let declared: BTreeSet<&str> = relation.fields.iter().map(|f| f.field.as_str()).collect();
+    let missing: BTreeSet<&str> = constraint_fields(constraint)
+        .into_iter()
+        .filter(|f| !declared.contains(f))
+        .collect();
+    for field in missing {
+        out.push(format!("relation `{name}` has no field `{field}`"));
+    }
+    match constraint {
+        ConstraintV1::Functional {
+            src_field,
+            dst_field,
+            ..
+        }
+        | ConstraintV1::AtMost {
+            src_field,
+            dst_field,
+            ..
+        } if src_field == dst_field => {
+            out.push(format!("`{name}.{src_field}` constrains itself"));
+        }
+        ConstraintV1::AtMost { max: 0, .. } => {
+            out.push(format!("`at_most 0` forbids every `{name}` tuple"));
+        }
+        ConstraintV1::Key { fields, .. } if fields.is_empty() => {
+            out.push(format!("key on `{name}` has no fields"));
+        }
+        ConstraintV1::Symmetric { carriers: None, .. }
+        | ConstraintV1::Transitive { carriers: None, .. }
+        | ConstraintV1::SymmetricWhereIn { carriers: None, .. } => {
+            // Default carriers are the first two fields; closure needs them to
+            // share a type.
+            match relation.fields.as_slice() {
+                [a, b, ..] if a.ty != b.ty => out.push(format!(
+                    "closure over `{name}` pairs `{}: {}` with `{}: {}` (different types)",
+                    a.field, a.ty, b.field, b.ty
+                )),
+                [_] | [] => out.push(format!("closure over `{name}` needs two fields")),
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+    out
+}
+
+/// Lint `.axi` text. A parse error is reported alone (nothing else can be
+/// checked); otherwise diagnostics are sorted by line.
+pub fn lint_axi(text: &str) -> Vec<Diagnostic> {
+    let module = match parse_schema_v1(text) {
+        Ok(module) => module,
+        Err(SchemaV1ParseError::Line { line, message }) => {
+            return vec![Diagnostic::new(LintCode::ParseError, Some(line), message)];
+        }
+    };
+    let mut linter = Linter {
+        module: &module,
+        lines: &LineIndex::build(text),
+        out: Vec::new(),
+    };
+    linter.module_names();
+    linter.schemas();
+    linter.theories();
+    linter.instances();
+
+    let mut out = linter.out;
+    out.sort_by_key(|d| (d.line.unwrap_or(0), d.severity, d.code));
+    out
+}
+
+/// `(errors, warnings)` in `diagnostics`.
+pub fn count_diagnostics(diagnostics: &[Diagnostic]) -> (usize, usize) {
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    (errors, diagnostics.len() - errors)
+}
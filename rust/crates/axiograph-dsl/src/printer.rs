@@ -0,0 +1,376 @@
+//! Canonical `axi_schema_v1` printer.
+//!
+//! `print_axi_v1` renders a parsed module back to text with one fixed layout
+//! (two-space indentation, one blank line between sections, sets wrapped one
+//! item per line once they get long), so:
+//!
+//! ```text
+//!   parse_axi_v1(print_axi_v1(m)) == m
+//! ```
+//!
+//! for every module it accepts. Anything that would re-parse differently (a
+//! set item containing `,`, an identifier with spaces, a comment marker, ...)
+//! is rejected with an `AxiPrintError` instead of being written out.
+//!
+//! The surface syntax has no string quoting, so tools that generate `.axi`
+//! from arbitrary text (names, attribute values) go through `escape_axi_value`
+//! first; `unescape_axi_value` recovers the original text.
+
+use crate::schema_v1::{
+    format_constraint_v1, parse_schema_v1, ConstraintV1, InstanceAssignmentV1, RewriteRuleV1,
+    SchemaV1Instance, SchemaV1Module, SchemaV1Schema, SchemaV1Theory, SetItemV1,
+};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum AxiPrintError {
+    #[error("{context}: `{text}` cannot be printed as `.axi` ({reason})")]
+    Unprintable {
+        context: String,
+        text: String,
+        reason: &'static str,
+    },
+    #[error("printed `.axi` does not re-parse to the same module: {0}")]
+    RoundTrip(String),
+    #[error("failed to parse `.axi`: {0}")]
+    Parse(String),
+}
+
+fn unprintable(context: &str, text: &str, reason: &'static str) -> AxiPrintError {
+    AxiPrintError::Unprintable {
+        context: context.to_string(),
+        text: text.to_string(),
+        reason,
+    }
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn ident<'a>(context: &str, s: &'a str) -> Result<&'a str, AxiPrintError> {
+    if is_ident(s) {
+        Ok(s)
+    } else {
+        Err(unprintable(context, s, "expected an identifier"))
+    }
+}
+
+/// Free text on a single line (object names, equation sides, block bodies).
+fn text<'a>(context: &str, s: &'a str) -> Result<&'a str, AxiPrintError> {
+    if s.is_empty() || s.trim() != s {
+        return Err(unprintable(context, s, "empty or surrounded by whitespace"));
+    }
+    if s.contains(['\n', '\r', '#']) || s.contains("--") {
+        return Err(unprintable(
+            context,
+            s,
+            "contains a newline or comment marker",
+        ));
+    }
+    Ok(s)
+}
+
+/// A set item or tuple field: free text without set/tuple punctuation.
+fn token<'a>(context: &str, s: &'a str) -> Result<&'a str, AxiPrintError> {
+    let s = text(context, s)?;
+    if s.contains([',', '(', ')', '{', '}', '=']) {
+        return Err(unprintable(
+            context,
+            s,
+            "contains set/tuple punctuation (use `escape_axi_value`)",
+        ));
+    }
+    Ok(s)
+}
+
+/// Characters `escape_axi_value` always percent-encodes.
+const ESCAPED: &[char] = &['%', ',', '(', ')', '{', '}', '=', '#', '"'];
+
+/// Encode arbitrary text as a printable set item / tuple field value.
+///
+/// Set/tuple punctuation, comment markers, quotes, control characters and
+/// surrounding whitespace are percent-encoded (`a, b` → `a%2C b`); the empty
+/// string becomes `""`. Text that needs no escaping is returned unchanged.
+pub fn escape_axi_value(s: &str) -> String {
+    if s.is_empty() {
+        return "\"\"".to_string();
+    }
+    let last = s.chars().count() - 1;
+    let mut out = String::with_capacity(s.len());
+    let mut prev = None;
+    for (i, c) in s.chars().enumerate() {
+        let edge_space = c.is_whitespace() && (i == 0 || i == last);
+        if ESCAPED.contains(&c) || c.is_control() || edge_space || (c == '-' && prev == Some('-')) {
+            let mut buf = [0u8; 4];
+            for b in c.encode_utf8(&mut buf).bytes() {
+                out.push_str(&format!("%{b:02X}"));
+            }
+        } else {
+            out.push(c);
+        }
+        prev = Some(c);
+    }
+    out
+}
+
+/// Inverse of `escape_axi_value`.
+pub fn unescape_axi_value(s: &str) -> String {
+    if s == "\"\"" {
+        return String::new();
+    }
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let decoded = (bytes[i] == b'%')
+            .then(|| s.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match decoded {
+            Some(b) => {
+                out.push(b);
+                i += 3;
+            }
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(out).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
+
+/// Turn arbitrary text into an identifier (`Cutting Tool` → `Cutting_Tool`).
+pub fn sanitize_axi_ident(s: &str) -> String {
+    let mut out = String::new();
+    for c in s.trim().chars() {
+        let c = if c.is_ascii_alphanumeric() { c } else { '_' };
+        if !(c == '_' && out.ends_with('_')) {
+            out.push(c);
+        }
+    }
+    let out = out.trim_matches('_');
+    match out.chars().next() {
+        None => "_".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("_{out}"),
+        Some(_) => out.to_string(),
+    }
+}
+
+/// Render a set literal, wrapping long sets one item per line.
+fn print_set(context: &str, items: &[SetItemV1]) -> Result<String, AxiPrintError> {
+    let mut rendered = Vec::with_capacity(items.len());
+    let mut tuples = false;
+    for item in items {
+        rendered.push(match item {
+            SetItemV1::Ident { name } => token(context, name)?.to_string(),
+            SetItemV1::Tuple { fields } => {
+                tuples = true;
+                let fields = fields
+                    .iter()
+                    .map(|(k, v)| Ok(format!("{}={}", token(context, k)?, token(context, v)?)))
+                    .collect::<Result<Vec<_>, AxiPrintError>>()?;
+                format!("({})", fields.join(", "))
+            }
+        });
+    }
+
+    let inline = format!("{{{}}}", rendered.join(", "));
+    let (max_len, max_items) = if tuples { (120, 3) } else { (100, 6) };
+    if rendered.is_empty() || (inline.len() <= max_len && rendered.len() <= max_items) {
+        return Ok(inline);
+    }
+    let mut out = String::from("{\n");
+    for (i, item) in rendered.iter().enumerate() {
+        let comma = if i + 1 == rendered.len() { "" } else { "," };
+        out.push_str(&format!("    {item}{comma}\n"));
+    }
+    out.push_str("  }");
+    Ok(out)
+}
+
+/// One instance assignment line (`  Name = {...}`), as inside `print_instance_v1`.
+pub fn print_assignment_v1(assignment: &InstanceAssignmentV1) -> Result<String, AxiPrintError> {
+    let context = format!("assignment `{}`", assignment.name);
+    let name = token(&context, &assignment.name)?;
+    let set = print_set(&context, &assignment.value.items)?;
+    Ok(format!("  {name} = {set}\n"))
+}
+
+/// One theory constraint (indented, possibly multi-line), as inside `print_theory_v1`.
+pub fn print_constraint_v1(constraint: &ConstraintV1) -> Result<String, AxiPrintError> {
+    match constraint {
+        ConstraintV1::NamedBlock { name, body } => {
+            let context = format!("constraint `{name}`");
+            let mut out = format!("  constraint {}:\n", text(&context, name)?);
+            for line in body {
+                out.push_str(&format!("    {}\n", text(&context, line)?));
+            }
+            Ok(out)
+        }
+        other => {
+            let line = format_constraint_v1(other)
+                .map_err(|reason| AxiPrintError::RoundTrip(reason.to_string()))?;
+            Ok(format!("  {}\n", text("constraint", &line)?))
+        }
+    }
+}
+
+fn print_rewrite_rule(rule: &RewriteRuleV1) -> Result<String, AxiPrintError> {
+    let context = format!("rewrite `{}`", rule.name);
+    let orientation = match rule.orientation {
+        crate::schema_v1::RewriteOrientationV1::Forward => "forward",
+        crate::schema_v1::RewriteOrientationV1::Backward => "backward",
+        crate::schema_v1::RewriteOrientationV1::Bidirectional => "bidirectional",
+    };
+    let mut out = format!("  rewrite {}:\n", text(&context, &rule.name)?);
+    out.push_str(&format!("    orientation: {orientation}\n"));
+    if !rule.vars.is_empty() {
+        let vars = rule
+            .vars
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        out.push_str(&format!("    vars: {}\n", vars.join(", ")));
+    }
+    out.push_str(&format!("    lhs: {}\n", rule.lhs));
+    out.push_str(&format!("    rhs: {}\n", rule.rhs));
+    Ok(out)
+}
+
+pub fn print_schema_v1(schema: &SchemaV1Schema) -> Result<String, AxiPrintError> {
+    let context = format!("schema `{}`", schema.name);
+    let mut out = format!("schema {}:\n", ident(&context, &schema.name)?);
+    for object in &schema.objects {
+        out.push_str(&format!("  object {}\n", text(&context, object)?));
+    }
+    for subtype in &schema.subtypes {
+        out.push_str(&format!(
+            "  subtype {} < {}",
+            ident(&context, &subtype.sub)?,
+            ident(&context, &subtype.sup)?
+        ));
+        if let Some(inclusion) = &subtype.inclusion {
+            out.push_str(&format!(" as {}", ident(&context, inclusion)?));
+        }
+        out.push('\n');
+    }
+    for relation in &schema.relations {
+        let fields = relation
+            .fields
+            .iter()
+            .map(|f| {
+                Ok(format!(
+                    "{}: {}",
+                    ident(&context, &f.field)?,
+                    ident(&context, &f.ty)?
+                ))
+            })
+            .collect::<Result<Vec<_>, AxiPrintError>>()?;
+        out.push_str(&format!(
+            "  relation {}({})\n",
+            ident(&context, &relation.name)?,
+            fields.join(", ")
+        ));
+    }
+    Ok(out)
+}
+
+pub fn print_theory_v1(theory: &SchemaV1Theory) -> Result<String, AxiPrintError> {
+    let context = format!("theory `{}`", theory.name);
+    let mut out = format!(
+        "theory {} on {}:\n",
+        ident(&context, &theory.name)?,
+        ident(&context, &theory.schema)?
+    );
+    for constraint in &theory.constraints {
+        out.push_str(&print_constraint_v1(constraint)?);
+    }
+    for equation in &theory.equations {
+        let context = format!("equation `{}`", equation.name);
+        if equation.lhs.contains('=') {
+            return Err(unprintable(&context, &equation.lhs, "lhs contains `=`"));
+        }
+        out.push_str(&format!(
+            "  equation {}:\n    {} = {}\n",
+            text(&context, &equation.name)?,
+            text(&context, &equation.lhs)?,
+            text(&context, &equation.rhs)?
+        ));
+    }
+    for rule in &theory.rewrite_rules {
+        out.push_str(&print_rewrite_rule(rule)?);
+    }
+    for view in &theory.views {
+        let context = format!("view `{}`", view.name);
+        out.push_str(&format!(
+            "  view {} = {}\n",
+            ident(&context, &view.name)?,
+            text(&context, &view.query)?
+        ));
+    }
+    Ok(out)
+}
+
+pub fn print_instance_v1(instance: &SchemaV1Instance) -> Result<String, AxiPrintError> {
+    let context = format!("instance `{}`", instance.name);
+    let mut out = format!(
+        "instance {} of {}:\n",
+        ident(&context, &instance.name)?,
+        ident(&context, &instance.schema)?
+    );
+    for assignment in &instance.assignments {
+        out.push_str(&print_assignment_v1(assignment)?);
+    }
+    Ok(out)
+}
+
+/// `-- ...` comment lines (one per input line).
+pub fn print_comment(comment: &str) -> String {
+    comment
+        .lines()
+        .map(|line| format!("-- {line}").trim_end().to_string() + "\n")
+        .collect()
+}
+
+/// Print `module` canonically; the result re-parses to exactly `module`.
+pub fn print_axi_v1(module: &SchemaV1Module) -> Result<String, AxiPrintError> {
+    let mut sections = Vec::new();
+    for schema in &module.schemas {
+        sections.push(print_schema_v1(schema)?);
+    }
+    for theory in &module.theories {
+        sections.push(print_theory_v1(theory)?);
+    }
+    for instance in &module.instances {
+        sections.push(print_instance_v1(instance)?);
+    }
+
+    let mut out = format!("module {}\n", text("module", &module.module_name)?);
+    for import in &module.imports {
+        out.push_str(&format!("import {}\n", text("import", import)?));
+    }
+    for section in sections {
+        out.push('\n');
+        out.push_str(&section);
+    }
+
+    match parse_schema_v1(&out) {
+        Ok(reparsed) if reparsed == *module => Ok(out),
+        Ok(_) => Err(AxiPrintError::RoundTrip(
+            "the re-parsed module differs (a name collides with surface syntax)".to_string(),
+        )),
+        Err(e) => Err(AxiPrintError::RoundTrip(e.to_string())),
+    }
+}
+
+/// Parse `text` and print it canonically (comments are not preserved).
+pub fn canonicalize_axi_v1(text: &str) -> Result<String, AxiPrintError> {
+    let module = parse_schema_v1(text).map_err(|e| AxiPrintError::Parse(e.to_string()))?;
+    print_axi_v1(&module)
+}
@@ -0,0 +1,439 @@
+//! Splice regenerated declarations into a hand-authored `.axi` file.
+//!
+//! When the graph is edited through the API, the `.axi` file it came from
+//! drifts. Re-exporting the whole module (`axi_module_export`) fixes the
+//! content but throws away the author's comments and layout. [`splice_axi`]
+//! instead replaces only the selected top-level `schema` / `theory` /
+//! `instance` blocks of the existing file with their regenerated text and
+//! leaves everything else (module header, imports, templates, unselected
+//! blocks, comments between blocks) byte-for-byte.
+//!
+//! Comments inside a replaced block are kept by *anchoring*: a run of comment
+//! lines is anchored to the declaration that follows it (`object Firm`,
+//! `relation Flow`, a constraint, `equation name`, `Flow = ...`), and a
+//! trailing `-- ...` to the declaration on its line. The anchors are looked
+//! up by the same keys in the regenerated block and the comments re-inserted
+//! there. Comments whose declaration no longer exists (and comments at the
+//! end of a block) are appended at the end of the block; the former are
+//! counted in [`AxiRegen::orphaned_comments`].
+//!
+//! Selected declarations missing from the existing file are appended.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::lint::{indent_of, split_line_comment};
+use crate::schema_v1::{format_constraint_v1, parse_constraint_v1, parse_schema_v1};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeclKind {
+    Schema,
+    Theory,
+    Instance,
+}
+
+impl DeclKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DeclKind::Schema => "schema",
+            DeclKind::Theory => "theory",
+            DeclKind::Instance => "instance",
+        }
+    }
+}
+
+/// A top-level declaration to regenerate: `schema:Name`, `theory:Name`,
+/// `instance:Name`, or a bare `Name` (any kind).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeclRef {
+    pub kind: Option<DeclKind>,
+    pub name: String,
+}
+
+impl DeclRef {
+    fn matches(&self, kind: DeclKind, name: &str) -> bool {
+        self.name == name && self.kind.is_none_or(|k| k == kind)
+    }
+}
+
+impl FromStr for DeclRef {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, name) = match s.split_once(':') {
+            Some(("schema", name)) => (Some(DeclKind::Schema), name),
+            Some(("theory", name)) => (Some(DeclKind::Theory), name),
+            Some(("instance", name)) => (Some(DeclKind::Instance), name),
+            Some((other, _)) => {
+                return Err(format!(
+                    "unknown declaration kind `{other}` (expected schema|theory|instance)"
+                ))
+            }
+            None => (None, s),
+        };
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(format!("missing declaration name in `{s}`"));
+        }
+        Ok(Self {
+            kind,
+            name: name.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for DeclRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            Some(kind) => write!(f, "{} {}", kind.as_str(), self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// Result of [`splice_axi`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AxiRegen {
+    /// The updated file.
+    pub text: String,
+    /// Blocks replaced in place (`schema Name`, ...).
+    pub replaced: Vec<String>,
+    /// Blocks appended because the file did not have them.
+    pub added: Vec<String>,
+    /// Comment lines whose declaration is gone (kept at the end of the block).
+    pub orphaned_comments: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum AxiRegenError {
+    #[error("`{0}` is not in the regenerated module")]
+    NotRegenerated(String),
+    #[error("spliced module does not parse: {0}")]
+    Parse(#[from] crate::schema_v1::SchemaV1ParseError),
+}
+
+/// Replace the `select`ed blocks of `existing` with their counterparts in
+/// `regenerated` (see the module docs). An empty `select` takes every block
+/// of `regenerated`. The result is checked to parse.
+pub fn splice_axi(
+    existing: &str,
+    regenerated: &str,
+    select: &[DeclRef],
+) -> Result<AxiRegen, AxiRegenError> {
+    let regen_lines: Vec<&str> = regenerated.lines().collect();
+    let regen_blocks = blocks(&regen_lines);
+    for decl in select {
+        if !regen_blocks.iter().any(|b| decl.matches(b.kind, &b.name)) {
+            return Err(AxiRegenError::NotRegenerated(decl.to_string()));
+        }
+    }
+    let selected =
+        |b: &Block| select.is_empty() || select.iter().any(|d| d.matches(b.kind, &b.name));
+
+    let lines: Vec<&str> = existing.lines().collect();
+    let existing_blocks = blocks(&lines);
+    let mut out: Vec<String> = Vec::with_capacity(lines.len());
+    let mut report = AxiRegen {
+        text: String::new(),
+        replaced: Vec::new(),
+        added: Vec::new(),
+        orphaned_comments: 0,
+    };
+
+    let mut i = 0;
+    for block in &existing_blocks {
+        let Some(regen) = regen_blocks
+            .iter()
+            .find(|r| r.kind == block.kind && r.name == block.name && selected(r))
+        else {
+            continue;
+        };
+        out.extend(lines[i..block.start].iter().map(|l| l.to_string()));
+        let anchors = Anchors::collect(&lines[block.start..block.end]);
+        out.extend(anchors.render(&regen_lines[regen.start..regen.end], &mut report));
+        report.replaced.push(block.label());
+        i = block.end;
+    }
+    out.extend(lines[i..].iter().map(|l| l.to_string()));
+
+    for regen in regen_blocks.iter().filter(|r| selected(r)) {
+        if existing_blocks
+            .iter()
+            .any(|b| b.kind == regen.kind && b.name == regen.name)
+        {
+            continue;
+        }
+        while out.last().is_some_and(|l| l.trim().is_empty()) {
+            out.pop();
+        }
+        if !out.is_empty() {
+            out.push(String::new());
+        }
+        out.extend(
+            regen_lines[regen.start..regen.end]
+                .iter()
+                .map(|l| l.to_string()),
+        );
+        report.added.push(regen.label());
+    }
+
+    let mut text = out.join("\n");
+    text.push('\n');
+    parse_schema_v1(&text)?;
+    report.text = text;
+    Ok(report)
+}
+
+// ============================================================================
+// Blocks
+// ============================================================================
+
+/// A top-level declaration: its header line through its last indented line.
+struct Block {
+    kind: DeclKind,
+    name: String,
+    start: usize,
+    end: usize,
+}
+
+impl Block {
+    fn label(&self) -> String {
+        format!("{} {}", self.kind.as_str(), self.name)
+    }
+}
+
+fn blocks(lines: &[&str]) -> Vec<Block> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let Some((kind, name)) = block_header(lines[i]) else {
+            i += 1;
+            continue;
+        };
+        let start = i;
+        let mut end = i + 1;
+        i += 1;
+        while i < lines.len() {
+            let line = lines[i];
+            if !line.trim().is_empty() {
+                if indent_of(line) == 0 {
+                    break;
+                }
+                end = i + 1;
+            }
+            i += 1;
+        }
+        out.push(Block {
+            kind,
+            name,
+            start,
+            end,
+        });
+        i = end;
+    }
+    out
+}
+
+fn block_header(line: &str) -> Option<(DeclKind, String)> {
+    if indent_of(line) > 0 {
+        return None;
+    }
+    let code = split_line_comment(line).0.trim();
+    let code = code.strip_suffix(':')?;
+    let (kind, rest, stop) = if let Some(rest) = code.strip_prefix("schema ") {
+        (DeclKind::Schema, rest, None)
+    } else if let Some(rest) = code.strip_prefix("theory ") {
+        (DeclKind::Theory, rest, Some(" on "))
+    } else if let Some(rest) = code.strip_prefix("instance ") {
+        (DeclKind::Instance, rest, Some(" of "))
+    } else {
+        return None;
+    };
+    let name = match stop {
+        Some(stop) => rest.split(stop).next()?,
+        None => rest,
+    };
+    Some((kind, name.trim().to_string()))
+}
+
+// ============================================================================
+// Comment anchors
+// ============================================================================
+
+/// Identity of a declaration line inside a block (see the module docs).
+fn member_key(code: &str) -> String {
+    let code = code.split_whitespace().collect::<Vec<_>>().join(" ");
+    if let Some(rest) = code.strip_prefix("constraint ") {
+        return parse_constraint_v1(rest)
+            .ok()
+            .and_then(|c| format_constraint_v1(&c).ok())
+            .unwrap_or(code);
+    }
+    for (prefix, stop) in [
+        ("relation ", '('),
+        ("equation ", ':'),
+        ("rewrite ", ':'),
+        ("view ", '='),
+    ] {
+        if code.starts_with(prefix) {
+            return code.split(stop).next().unwrap_or(&code).trim().to_string();
+        }
+    }
+    if code.starts_with("object ") || code.starts_with("subtype ") {
+        return code;
+    }
+    match code.split_once('=') {
+        Some((name, _)) => format!("{} =", name.trim()),
+        None => code,
+    }
+}
+
+/// A declaration line starts with an identifier at the block's member indent;
+/// everything else (field lists, set literal lines, closing braces, equation
+/// bodies) continues the previous declaration.
+fn is_member_line(code: &str, member_indent: usize) -> bool {
+    indent_of(code) <= member_indent
+        && code
+            .trim_start()
+            .starts_with(|c: char| c.is_alphanumeric() || c == '_')
+}
+
+fn member_indent(body: &[&str]) -> usize {
+    body.iter()
+        .map(|l| split_line_comment(l).0)
+        .find(|code| !code.trim().is_empty())
+        .map(indent_of)
+        .unwrap_or(2)
+}
+
+struct CommentGroup {
+    /// `None`: at the end of the block.
+    key: Option<String>,
+    blank_before: bool,
+    lines: Vec<String>,
+}
+
+struct Anchors {
+    header_comment: Option<String>,
+    groups: Vec<CommentGroup>,
+    trailing: Vec<(String, String)>,
+}
+
+impl Anchors {
+    fn collect(block: &[&str]) -> Self {
+        let header_comment = split_line_comment(block[0]).1.map(str::to_string);
+        let body = &block[1..];
+        let indent = member_indent(body);
+        let mut groups = Vec::new();
+        let mut trailing = Vec::new();
+        let mut pending: Vec<String> = Vec::new();
+        let (mut saw_blank, mut blank_before) = (false, false);
+        let mut current: Option<String> = None;
+
+        for line in body {
+            if line.trim().is_empty() {
+                saw_blank = true;
+                continue;
+            }
+            let (code, comment) = split_line_comment(line);
+            if code.trim().is_empty() {
+                if pending.is_empty() {
+                    blank_before = saw_blank;
+                }
+                pending.extend(comment.map(|c| c.trim().to_string()));
+                saw_blank = false;
+                continue;
+            }
+            saw_blank = false;
+            if is_member_line(code, indent) {
+                let key = member_key(code);
+                if !pending.is_empty() {
+                    groups.push(CommentGroup {
+                        key: Some(key.clone()),
+                        blank_before,
+                        lines: std::mem::take(&mut pending),
+                    });
+                }
+                if let Some(comment) = comment {
+                    trailing.push((key.clone(), comment.trim().to_string()));
+                }
+                current = Some(key);
+            } else if let (Some(comment), Some(key)) = (comment, &current) {
+                // A comment on a continuation line moves to its declaration.
+                trailing.push((key.clone(), comment.trim().to_string()));
+            }
+        }
+        if !pending.is_empty() {
+            groups.push(CommentGroup {
+                key: None,
+                blank_before,
+                lines: pending,
+            });
+        }
+        Self {
+            header_comment,
+            groups,
+            trailing,
+        }
+    }
+
+    /// `regen` with the comments re-inserted at their anchors.
+    fn render(mut self, regen: &[&str], report: &mut AxiRegen) -> Vec<String> {
+        let mut out = Vec::with_capacity(regen.len());
+        let mut header = regen[0].trim_end().to_string();
+        if let Some(comment) = self.header_comment.take() {
+            header.push(' ');
+            header.push_str(&comment);
+        }
+        out.push(header);
+
+        let body = &regen[1..];
+        let indent = member_indent(body);
+        let pad = " ".repeat(indent);
+        for line in body {
+            let line = line.trim_end();
+            if line.is_empty() || !is_member_line(line, indent) {
+                out.push(line.to_string());
+                continue;
+            }
+            let key = member_key(line);
+            let (groups, rest): (Vec<_>, Vec<_>) = std::mem::take(&mut self.groups)
+                .into_iter()
+                .partition(|g| g.key.as_ref() == Some(&key));
+            self.groups = rest;
+            for group in groups {
+                if group.blank_before && out.len() > 1 && out.last().is_some_and(|l| !l.is_empty())
+                {
+                    out.push(String::new());
+                }
+                out.extend(group.lines.iter().map(|c| format!("{pad}{c}")));
+            }
+            let mut line = line.to_string();
+            for (_, comment) in self.trailing.iter().filter(|(k, _)| *k == key) {
+                line.push(' ');
+                line.push_str(comment);
+            }
+            self.trailing.retain(|(k, _)| *k != key);
+            out.push(line);
+        }
+        while out.last().is_some_and(|l| l.is_empty()) {
+            out.pop();
+        }
+
+        // Orphans and end-of-block comments go last.
+        for group in self.groups {
+            if group.key.is_some() {
+                report.orphaned_comments += group.lines.len();
+            }
+            out.extend(group.lines.iter().map(|c| format!("{pad}{c}")));
+        }
+        for (_, comment) in self.trailing {
+            report.orphaned_comments += 1;
+            out.push(format!("{pad}{comment}"));
+        }
+        out
+    }
+}
@@ -29,6 +29,12 @@ pub type Name = String;
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SchemaV1Module {
     pub module_name: Name,
+    /// `import <Module>` header lines, in source order.
+    ///
+    /// The parser only records them; resolution (cycles, visibility,
+    /// cross-module schema references) is `axiograph_pathdb::axi_module_graph`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub imports: Vec<Name>,
     pub schemas: Vec<SchemaV1Schema>,
     pub theories: Vec<SchemaV1Theory>,
     pub instances: Vec<SchemaV1Instance>,
@@ -85,6 +91,9 @@ pub struct SchemaV1Theory {
     pub constraints: Vec<ConstraintV1>,
     pub equations: Vec<EquationV1>,
     pub rewrite_rules: Vec<RewriteRuleV1>,
+    /// Named AxQL queries (`view name = <query>`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub views: Vec<ViewV1>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -206,6 +215,16 @@ pub struct EquationV1 {
     pub rhs: String,
 }
 
+/// A saved query: `view open_payments = select ?p where ?p is Payment`.
+///
+/// The query is AxQL text. The parser only stores it (joined onto one line);
+/// AxQL parsing happens when the view is run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ViewV1 {
+    pub name: Name,
+    pub query: String,
+}
+
 /// Orientation of a rewrite rule.
 ///
 /// For `axi_v1`, rewrite rules are stored as *directed* rules at first.
@@ -350,9 +369,17 @@ enum Section {
     Instance(usize),
 }
 
+/// Parse a module. `template`/`use` blocks are expanded first (see
+/// `crate::template`); errors inside an expansion point at the call site.
 pub fn parse_schema_v1(text: &str) -> Result<SchemaV1Module, SchemaV1ParseError> {
+    let expanded = crate::template::expand_templates_v1(text)?;
+    parse_expanded(&expanded.text).map_err(|e| expanded.map_error(e))
+}
+
+fn parse_expanded(text: &str) -> Result<SchemaV1Module, SchemaV1ParseError> {
     let mut module = SchemaV1Module {
         module_name: "Unnamed".to_string(),
+        imports: vec![],
         schemas: vec![],
         theories: vec![],
         instances: vec![],
@@ -384,6 +411,27 @@ pub fn parse_schema_v1(text: &str) -> Result<SchemaV1Module, SchemaV1ParseError>
             continue;
         }
 
+        if let Some(rest) = line.strip_prefix("import ").map(str::trim) {
+            // `import M` is a header line; inside a section `import = {...}`
+            // is still an ordinary assignment.
+            if section == Section::None {
+                let name = parse_import(rest).map_err(|message| SchemaV1ParseError::Line {
+                    line: line_no,
+                    message,
+                })?;
+                module.imports.push(name);
+                i += 1;
+                continue;
+            }
+            if !rest.contains('=') {
+                return Err(SchemaV1ParseError::Line {
+                    line: line_no,
+                    message: "`import` must come before the first schema/theory/instance"
+                        .to_string(),
+                });
+            }
+        }
+
         if let Some(rest) = line.strip_prefix("schema ").map(str::trim) {
             let name = rest.trim_end_matches(':').trim();
             if name.is_empty() {
@@ -415,6 +463,7 @@ pub fn parse_schema_v1(text: &str) -> Result<SchemaV1Module, SchemaV1ParseError>
                 constraints: vec![],
                 equations: vec![],
                 rewrite_rules: vec![],
+                views: vec![],
             });
             section = Section::Theory(module.theories.len() - 1);
             i += 1;
@@ -589,6 +638,24 @@ pub fn parse_schema_v1(text: &str) -> Result<SchemaV1Module, SchemaV1ParseError>
                     continue;
                 }
 
+                if let Some(rest) = line.strip_prefix("view ").map(str::trim) {
+                    // The query may continue on the following (indented) lines.
+                    let (extra, next_index) = collect_indented_block(lines.as_slice(), i + 1);
+                    let combined = if extra.is_empty() {
+                        rest.to_string()
+                    } else {
+                        format!("{rest} {extra}")
+                    };
+                    let view =
+                        parse_view(&combined).map_err(|message| SchemaV1ParseError::Line {
+                            line: line_no,
+                            message,
+                        })?;
+                    module.theories[theory_index].views.push(view);
+                    i = if extra.is_empty() { i + 1 } else { next_index };
+                    continue;
+                }
+
                 return Err(SchemaV1ParseError::Line {
                     line: line_no,
                     message: format!("unrecognized theory line: {line}"),
@@ -637,7 +704,8 @@ pub fn parse_schema_v1(text: &str) -> Result<SchemaV1Module, SchemaV1ParseError>
     Ok(module)
 }
 
-fn strip_comment(line: &str) -> &str {
+/// Drop a trailing `#` or `--` comment from one source line.
+pub fn strip_comment(line: &str) -> &str {
     if let Some((before, _)) = line.split_once('#') {
         return before;
     }
@@ -659,6 +727,12 @@ fn parse_ident(input: &str) -> IResult<&str, &str> {
     )))(input)
 }
 
+fn parse_import(rest: &str) -> Result<Name, String> {
+    all_consuming(parse_ident)(rest.trim())
+        .map(|(_, name)| name.to_string())
+        .map_err(|_| "import expects: `import <Module>`".to_string())
+}
+
 fn parse_theory_header(rest: &str) -> Result<(Name, Name), String> {
     fn parser(input: &str) -> IResult<&str, (Name, Name)> {
         let (input, name) = parse_ident(input)?;
@@ -1327,9 +1401,30 @@ fn is_top_level_keyword(trimmed: &str) -> bool {
             || s.starts_with("constraint ")
             || s.starts_with("equation ")
             || s.starts_with("rewrite ")
+            || s.starts_with("view ")
     )
 }
 
+/// `name = <AxQL query>`.
+fn parse_view(text: &str) -> Result<ViewV1, String> {
+    let Some((name, query)) = text.split_once('=') else {
+        return Err("view must have the form `view name = <AxQL query>`".to_string());
+    };
+    let name = name.trim();
+    let query = query.trim();
+    if name.is_empty() || !name.starts_with(is_ident_start) || !name.chars().all(is_ident_continue)
+    {
+        return Err(format!("invalid view name `{name}`"));
+    }
+    if query.is_empty() {
+        return Err(format!("view `{name}` has an empty query"));
+    }
+    Ok(ViewV1 {
+        name: name.to_string(),
+        query: query.to_string(),
+    })
+}
+
 fn split_equation(equation_text: &str) -> Result<(String, String), String> {
     let Some((lhs, rhs)) = equation_text.split_once('=') else {
         return Err("equation body must contain `=`".to_string());
@@ -1779,4 +1874,25 @@ mod tests {
         assert!(module.schemas.iter().any(|s| s.name == "OntologyMeta"));
         assert!(module.instances.iter().any(|i| i.name == "ProductCatalog"));
     }
+
+    #[test]
+    fn parses_import_headers() {
+        let text = [
+            "module App",
+            "import Core",
+            "import Geo # shared regions",
+            "",
+            "instance I of S:",
+            "  import = {x}",
+        ]
+        .join("\n");
+        let module = parse_schema_v1(&text).expect("parse imports");
+        assert_eq!(module.imports, vec!["Core".to_string(), "Geo".to_string()]);
+        assert_eq!(module.instances[0].assignments[0].name, "import");
+
+        let late = "module App\nschema S:\n  object X\nimport Core\n";
+        let err = parse_schema_v1(late).unwrap_err();
+        assert!(err.to_string().contains("line 4"), "{err}");
+        assert!(parse_schema_v1("module App\nimport a.b\n").is_err());
+    }
 }
@@ -0,0 +1,460 @@
+//! `.axi` templates: parameterized line blocks expanded before parsing.
+//!
+//! Surface syntax (top level, anywhere in the module):
+//!
+//! ```text
+//! template DrilledHole(part, face, dia) {
+//!   Hole = {$hole}
+//!   HoleOn = {(hole=$hole, part=$part, face=$face)}
+//!   HoleDia = {(hole=$hole, dia=$dia)}
+//! }
+//!
+//! instance Bracket of Machining:
+//!   use DrilledHole(BracketA, TopFace, D10)
+//!   use DrilledHole(BracketA, SideFace, D6)
+//! ```
+//!
+//! Expansion is *hygienic*: a body only touches `$`-prefixed names.
+//! - `$param` is replaced by the argument, in a single pass (an argument is
+//!   never re-substituted), and never inside string literals or comments;
+//! - any other `$name` is template-local, renamed per call to
+//!   `name_<Template>_<n>` so two calls never share it;
+//! - plain identifiers (relation, field and type names) are left alone, so a
+//!   parameter named like a field (`part=$part`) cannot capture it.
+//!
+//! Bodies are re-indented to the call site and may call other templates
+//! (recursion is rejected). Definitions are blanked out and every expanded
+//! line keeps its provenance (`LineOrigin`), so parse errors inside an
+//! expansion are reported at the call site, with the template line(s) the
+//! text came from.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::schema_v1::{strip_comment, Name, SchemaV1ParseError};
+
+/// Nested `use` calls deeper than this are reported as recursion.
+const MAX_EXPANSION_DEPTH: usize = 32;
+
+/// A `template Name(params) { ... }` definition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateDefV1 {
+    pub name: Name,
+    pub params: Vec<Name>,
+    /// 1-based source line of the `template` header.
+    pub line: usize,
+    /// Body lines with their 1-based source line.
+    pub body: Vec<(usize, String)>,
+}
+
+/// One `use Template(args)` call and the expanded lines it produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpansionV1 {
+    pub template: Name,
+    pub args: Vec<String>,
+    /// 1-based source line of the outermost call.
+    pub call_line: usize,
+    /// 1-based line range in the expanded text.
+    pub lines: Range<usize>,
+}
+
+/// Where an expanded line came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineOrigin {
+    /// 1-based source line (the outermost call site for expanded lines).
+    pub line: usize,
+    /// Template frames, outermost first: `(template, template source line)`.
+    pub via: Vec<(Name, usize)>,
+}
+
+/// Template-free text plus provenance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpandedAxi {
+    pub text: String,
+    /// One entry per line of `text`.
+    pub origins: Vec<LineOrigin>,
+    pub templates: Vec<TemplateDefV1>,
+    pub expansions: Vec<ExpansionV1>,
+}
+
+impl ExpandedAxi {
+    /// Provenance of 1-based `line` of the expanded text.
+    pub fn origin(&self, line: usize) -> Option<&LineOrigin> {
+        line.checked_sub(1).and_then(|i| self.origins.get(i))
+    }
+
+    /// Re-point a parse error on the expanded text at the source.
+    pub fn map_error(&self, err: SchemaV1ParseError) -> SchemaV1ParseError {
+        let SchemaV1ParseError::Line { line, message } = err;
+        match self.origin(line) {
+            Some(origin) if origin.via.is_empty() => SchemaV1ParseError::Line {
+                line: origin.line,
+                message,
+            },
+            Some(origin) => SchemaV1ParseError::Line {
+                line: origin.line,
+                message: format!(
+                    "{message} (in expansion of {})",
+                    describe_frames(&origin.via)
+                ),
+            },
+            None => SchemaV1ParseError::Line { line, message },
+        }
+    }
+}
+
+fn describe_frames(via: &[(Name, usize)]) -> String {
+    via.iter()
+        .map(|(template, line)| format!("template `{template}` line {line}"))
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+fn error(line: usize, message: impl Into<String>) -> SchemaV1ParseError {
+    SchemaV1ParseError::Line {
+        line,
+        message: message.into(),
+    }
+}
+
+/// Expand every template call in `text`.
+///
+/// Text without `template`/`use` lines expands to itself (with identity
+/// provenance).
+pub fn expand_templates_v1(text: &str) -> Result<ExpandedAxi, SchemaV1ParseError> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut templates: BTreeMap<Name, TemplateDefV1> = BTreeMap::new();
+    let mut kept: Vec<(usize, &str)> = Vec::with_capacity(lines.len());
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line_no = i + 1;
+        let code = strip_comment(lines[i]).trim();
+        let Some(rest) = code.strip_prefix("template ") else {
+            kept.push((line_no, lines[i]));
+            i += 1;
+            continue;
+        };
+
+        let (name, params) = parse_template_header(rest).map_err(|m| error(line_no, m))?;
+        let mut body = Vec::new();
+        let mut depth = 1usize;
+        i += 1;
+        loop {
+            let Some(raw) = lines.get(i) else {
+                return Err(error(
+                    line_no,
+                    format!("template `{name}` is missing its closing `}}`"),
+                ));
+            };
+            let code = strip_comment(raw).trim();
+            if code.starts_with("template ") {
+                return Err(error(
+                    i + 1,
+                    "templates cannot be defined inside a template",
+                ));
+            }
+            depth += code.matches('{').count();
+            depth = depth.saturating_sub(code.matches('}').count());
+            if depth == 0 {
+                if code != "}" {
+                    return Err(error(
+                        i + 1,
+                        "a template's closing `}` must be on its own line",
+                    ));
+                }
+                break;
+            }
+            body.push((i + 1, raw.to_string()));
+            i += 1;
+        }
+        // Keep line numbers stable for the rest of the file.
+        for blank in line_no..=i + 1 {
+            kept.push((blank, ""));
+        }
+        i += 1;
+
+        if templates.contains_key(&name) {
+            return Err(error(
+                line_no,
+                format!("template `{name}` is defined more than once"),
+            ));
+        }
+        templates.insert(
+            name.clone(),
+            TemplateDefV1 {
+                name,
+                params,
+                line: line_no,
+                body,
+            },
+        );
+    }
+
+    let mut out = Expander {
+        templates: &templates,
+        lines: Vec::with_capacity(kept.len()),
+        origins: Vec::with_capacity(kept.len()),
+        expansions: Vec::new(),
+        counter: 0,
+    };
+    for (line_no, raw) in kept {
+        match parse_call(raw).map_err(|m| error(line_no, m))? {
+            Some(call) => out.expand(&call, line_no, &[], 0)?,
+            None => out.push(raw.to_string(), line_no, &[]),
+        }
+    }
+
+    let Expander {
+        lines: expanded,
+        origins,
+        expansions,
+        ..
+    } = out;
+    let mut text_out = expanded.join("\n");
+    if text.ends_with('\n') && !text_out.is_empty() {
+        text_out.push('\n');
+    }
+    Ok(ExpandedAxi {
+        text: text_out,
+        origins,
+        templates: templates.into_values().collect(),
+        expansions,
+    })
+}
+
+struct Call {
+    indent: String,
+    template: Name,
+    args: Vec<String>,
+}
+
+struct Expander<'a> {
+    templates: &'a BTreeMap<Name, TemplateDefV1>,
+    lines: Vec<String>,
+    origins: Vec<LineOrigin>,
+    expansions: Vec<ExpansionV1>,
+    counter: usize,
+}
+
+impl Expander<'_> {
+    fn push(&mut self, line: String, source_line: usize, via: &[(Name, usize)]) {
+        self.lines.push(line);
+        self.origins.push(LineOrigin {
+            line: source_line,
+            via: via.to_vec(),
+        });
+    }
+
+    /// Expand `call` (written at the end of the `via` chain) in place.
+    fn expand(
+        &mut self,
+        call: &Call,
+        call_line: usize,
+        via: &[(Name, usize)],
+        depth: usize,
+    ) -> Result<(), SchemaV1ParseError> {
+        let site = |message: String| {
+            let message = if via.is_empty() {
+                message
+            } else {
+                format!("{message} (in expansion of {})", describe_frames(via))
+            };
+            error(call_line, message)
+        };
+        let Some(template) = self.templates.get(&call.template) else {
+            return Err(site(format!("unknown template `{}`", call.template)));
+        };
+        if depth >= MAX_EXPANSION_DEPTH {
+            return Err(site(format!(
+                "template `{}` expands recursively (depth > {MAX_EXPANSION_DEPTH})",
+                call.template
+            )));
+        }
+        if template.params.len() != call.args.len() {
+            return Err(site(format!(
+                "template `{}` takes {} argument(s) ({}), got {}",
+                template.name,
+                template.params.len(),
+                template.params.join(", "),
+                call.args.len()
+            )));
+        }
+
+        self.counter += 1;
+        let fresh_suffix = format!("{}_{}", template.name, self.counter);
+        let bindings: BTreeMap<&str, &str> = template
+            .params
+            .iter()
+            .map(String::as_str)
+            .zip(call.args.iter().map(String::as_str))
+            .collect();
+        let common = template
+            .body
+            .iter()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(_, l)| l.len() - l.trim_start().len())
+            .min()
+            .unwrap_or(0);
+
+        let start = self.lines.len() + 1;
+        for (body_line, raw) in &template.body {
+            let mut frames = via.to_vec();
+            frames.push((template.name.clone(), *body_line));
+            let stripped = raw.get(common..).unwrap_or_else(|| raw.trim_start());
+            let line = if stripped.trim().is_empty() {
+                String::new()
+            } else {
+                format!(
+                    "{}{}",
+                    call.indent,
+                    substitute(stripped, &bindings, &fresh_suffix)
+                )
+            };
+            match parse_call(&line).map_err(|m| site(format!("{m} (template line {body_line})")))? {
+                Some(inner) => self.expand(&inner, call_line, &frames, depth + 1)?,
+                None => self.push(line, call_line, &frames),
+            }
+        }
+        self.expansions.push(ExpansionV1 {
+            template: template.name.clone(),
+            args: call.args.clone(),
+            call_line,
+            lines: start..self.lines.len() + 1,
+        });
+        Ok(())
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_ident_continue(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(is_ident_start) && chars.all(is_ident_continue)
+}
+
+/// `Name(p1, p2) {`
+fn parse_template_header(rest: &str) -> Result<(Name, Vec<Name>), String> {
+    const EXPECTED: &str = "template header expects: `template <Name>(<param>, ...) {`";
+    let rest = rest
+        .trim()
+        .strip_suffix('{')
+        .ok_or_else(|| EXPECTED.to_string())?
+        .trim_end();
+    let (name, params) = rest.split_once('(').ok_or_else(|| EXPECTED.to_string())?;
+    let params = params
+        .strip_suffix(')')
+        .ok_or_else(|| EXPECTED.to_string())?;
+    let name = name.trim();
+    if !is_ident(name) {
+        return Err(EXPECTED.to_string());
+    }
+    let params: Vec<Name> = if params.trim().is_empty() {
+        Vec::new()
+    } else {
+        params.split(',').map(|p| p.trim().to_string()).collect()
+    };
+    for (k, param) in params.iter().enumerate() {
+        if !is_ident(param) {
+            return Err(format!("template `{name}`: bad parameter name `{param}`"));
+        }
+        if params[..k].contains(param) {
+            return Err(format!("template `{name}`: duplicate parameter `{param}`"));
+        }
+    }
+    Ok((name.to_string(), params))
+}
+
+/// `use Name(arg, ...)` on its own line; `Ok(None)` for any other line
+/// (including the assignment `use = {...}`).
+fn parse_call(raw: &str) -> Result<Option<Call>, String> {
+    let code = strip_comment(raw).trim();
+    let Some(rest) = code.strip_prefix("use ") else {
+        return Ok(None);
+    };
+    let rest = rest.trim_start();
+    if rest.starts_with('=') {
+        return Ok(None);
+    }
+    const EXPECTED: &str = "template call expects: `use <Template>(<arg>, ...)`";
+    let (name, args) = rest.split_once('(').ok_or_else(|| EXPECTED.to_string())?;
+    let args = args
+        .trim_end()
+        .strip_suffix(')')
+        .ok_or_else(|| EXPECTED.to_string())?;
+    let name = name.trim();
+    if !is_ident(name) {
+        return Err(EXPECTED.to_string());
+    }
+    let args: Vec<String> = if args.trim().is_empty() {
+        Vec::new()
+    } else {
+        args.split(',').map(|a| a.trim().to_string()).collect()
+    };
+    if args
+        .iter()
+        .any(|a| a.is_empty() || a.contains(['(', ')', '{', '}']))
+    {
+        return Err(format!(
+            "template call `{name}`: arguments must be plain values"
+        ));
+    }
+    Ok(Some(Call {
+        indent: raw[..raw.len() - raw.trim_start().len()].to_string(),
+        template: name.to_string(),
+        args,
+    }))
+}
+
+/// Single-pass `$name` substitution outside strings and comments: parameters
+/// become their argument, other names become `name_<suffix>`.
+fn substitute(line: &str, bindings: &BTreeMap<&str, &str>, fresh_suffix: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        if c == '#' || rest.starts_with("--") {
+            out.push_str(rest);
+            break;
+        }
+        if c == '"' {
+            let mut end = rest.len();
+            let mut escaped = false;
+            for (i, ch) in rest.char_indices().skip(1) {
+                match ch {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => {
+                        end = i + 1;
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            out.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+        if c == '$' && rest[1..].starts_with(is_ident_start) {
+            let body = &rest[1..];
+            let len = body
+                .find(|ch: char| !is_ident_continue(ch))
+                .unwrap_or(body.len());
+            let ident = &body[..len];
+            match bindings.get(ident) {
+                Some(arg) => out.push_str(arg),
+                None => out.push_str(&format!("{ident}_{fresh_suffix}")),
+            }
+            rest = &body[len..];
+            continue;
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
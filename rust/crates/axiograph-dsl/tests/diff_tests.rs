@@ -0,0 +1,114 @@
+use axiograph_dsl::diff::{diff_axi, AxiItem, ChangeOp};
+
+const OLD: &str = r#"module Supply
+
+schema Supply:
+  object Firm
+  object Good
+  object Region
+  relation Supplies(from: Firm, to: Firm, good: Good)
+  relation Produces(firm: Firm, good: Good)
+
+theory SupplyRules on Supply:
+  constraint functional Produces.firm -> Produces.good
+  constraint symmetric Supplies
+  equation idem:
+    Supplies = Supplies
+
+instance Q1 of Supply:
+  Firm = {acme, bolt}
+  Good = {steel}
+  Supplies = {(from=acme, to=bolt, good=steel)}
+"#;
+
+#[test]
+fn reordering_and_reformatting_is_not_a_change() {
+    let reordered = r#"module Supply
+
+schema Supply:
+  relation Produces(firm: Firm, good: Good)
+  object Region
+  object Good
+  object Firm
+  relation Supplies(from: Firm, to: Firm, good: Good)
+
+instance Q1 of Supply:
+  Supplies = {(good=steel, to=bolt, from=acme)}
+  Good = {steel}
+  Firm = {bolt, acme}
+
+theory SupplyRules on Supply:
+  equation idem:
+    Supplies = Supplies
+  constraint symmetric Supplies
+  constraint functional Produces.firm -> Produces.good
+"#;
+    let diff = diff_axi(OLD, reordered).unwrap();
+    assert!(diff.is_empty(), "{:#?}", diff.changes);
+}
+
+#[test]
+fn reports_semantic_changes_in_stable_order() {
+    let new = r#"module Supply
+
+schema Supply:
+  object Company
+  object Good
+  object Region
+  relation Supplies(from: Company, to: Company, good: Good)
+  relation Produces(firm: Company, good: Good, region: Region)
+  relation Located(firm: Company, region: Region)
+
+theory SupplyRules on Supply:
+  constraint functional Produces.firm -> Produces.region
+  constraint symmetric Supplies
+
+instance Q1 of Supply:
+  Company = {acme, bolt, cog}
+  Good = {steel}
+  Supplies = {(from=acme, to=bolt, good=steel)}
+"#;
+    let diff = diff_axi(OLD, new).unwrap();
+    let lines: Vec<String> = diff.changes.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "instance Q1: + member Company: cog",
+            "schema Supply: ~ object Firm renamed to Company",
+            "schema Supply: + relation Located(firm: Company, region: Region)",
+            "schema Supply: ~ relation Produces(firm: Company, good: Good) => \
+             Produces(firm: Company, good: Good, region: Region)",
+            "theory SupplyRules: ~ constraint functional Produces.firm -> Produces.good => \
+             functional Produces.firm -> Produces.region",
+            "theory SupplyRules: - equation idem: Supplies = Supplies",
+        ]
+    );
+    assert_eq!(diff.count(ChangeOp::Renamed), 1);
+
+    assert_eq!(diff_axi(OLD, new).unwrap(), diff);
+    let rename = &diff.changes[1];
+    assert_eq!(rename.scope.as_deref(), Some("schema Supply"));
+    assert_eq!(
+        (rename.name.as_str(), rename.after.as_deref()),
+        ("Firm", Some("Company"))
+    );
+}
+
+#[test]
+fn added_declarations_list_their_contents() {
+    let new = format!(
+        "{OLD}\nschema Audit:\n  object Auditor\n  relation Audits(who: Auditor, firm: Auditor)\n"
+    );
+    let diff = diff_axi(OLD, &new).unwrap();
+    let kinds: Vec<(AxiItem, ChangeOp)> = diff.changes.iter().map(|c| (c.item, c.op)).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (AxiItem::Schema, ChangeOp::Added),
+            (AxiItem::Object, ChangeOp::Added),
+            (AxiItem::Relation, ChangeOp::Added),
+        ]
+    );
+
+    assert!(diff_axi("schema", OLD).is_err());
+}
@@ -0,0 +1,128 @@
+use axiograph_dsl::lint::{count_diagnostics, format_axi, lint_axi, LintCode, Severity};
+use axiograph_dsl::schema_v1::parse_schema_v1;
+use std::path::{Path, PathBuf};
+
+fn axi_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    for entry in std::fs::read_dir(dir).expect("read examples dir") {
+        let path = entry.expect("dir entry").path();
+        if path.is_dir() {
+            axi_files(&path, out);
+        } else if path.extension().is_some_and(|e| e == "axi") {
+            out.push(path);
+        }
+    }
+}
+
+#[test]
+fn corpus_lints_without_errors_and_formats_stably() {
+    let examples = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../../examples");
+    let mut files = Vec::new();
+    axi_files(&examples, &mut files);
+    assert!(!files.is_empty());
+
+    for path in files {
+        let text = std::fs::read_to_string(&path).unwrap();
+        let diagnostics = lint_axi(&text);
+        let (errors, _) = count_diagnostics(&diagnostics);
+        assert_eq!(errors, 0, "{}: {diagnostics:#?}", path.display());
+
+        let formatted = format_axi(&text);
+        assert_eq!(format_axi(&formatted), formatted, "{}", path.display());
+        assert_eq!(
+            parse_schema_v1(&formatted).unwrap(),
+            parse_schema_v1(&text).unwrap(),
+            "{}: formatting changed meaning",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn lint_reports_each_kind_with_lines() {
+    let text = "\
+module Bad
+
+schema S:
+  object A
+  object A
+  object Unused
+  relation r(x: A, y: Missing)
+  relation sym(a: A, b: r)
+
+theory T on S:
+  constraint functional r.x -> r.z
+  constraint symmetric sym
+  constraint whatever goes here
+
+instance I of S:
+  A = {a}
+  q = {b}
+  r = {(x=a, w=b)}
+";
+    let diagnostics = lint_axi(text);
+    let found: Vec<(Option<usize>, LintCode)> =
+        diagnostics.iter().map(|d| (d.line, d.code)).collect();
+    assert_eq!(
+        found,
+        vec![
+            (Some(5), LintCode::DuplicateName),
+            (Some(6), LintCode::UnusedObject),
+            (Some(7), LintCode::UnknownType),
+            (Some(11), LintCode::SuspiciousConstraint),
+            (Some(12), LintCode::SuspiciousConstraint),
+            (Some(13), LintCode::SuspiciousConstraint),
+            (Some(17), LintCode::UnknownType),
+            (Some(18), LintCode::UnknownType),
+        ],
+        "{diagnostics:#?}"
+    );
+    assert_eq!(count_diagnostics(&diagnostics), (4, 4));
+    assert!(diagnostics[2].message.contains("`Missing`"));
+    assert!(diagnostics[4].message.contains("different types"));
+
+    let parse = lint_axi("schema S:\n  relation r(x A)\n");
+    assert_eq!(parse.len(), 1);
+    assert_eq!(parse[0].code, LintCode::ParseError);
+    assert_eq!(parse[0].severity, Severity::Error);
+    assert_eq!(parse[0].line, Some(2));
+}
+
+#[test]
+fn format_canonicalizes_constraints_and_keeps_comments() {
+    let text = [
+        "module M   ",
+        "-- header comment",
+        "",
+        "",
+        "schema S:",
+        "  object A",
+        "  relation r(x: A, y: A, k: A)",
+        "theory T on S:",
+        "  constraint symmetric r where k in {A}   -- keep me",
+        "  constraint functional   r.x ->",
+        "      r.y",
+        "  constraint Block:",
+        "    anything   goes",
+        "",
+        "",
+    ]
+    .join("\n");
+    let formatted = format_axi(&text);
+    assert_eq!(
+        formatted,
+        "\
+module M
+-- header comment
+
+schema S:
+  object A
+  relation r(x: A, y: A, k: A)
+theory T on S:
+  constraint symmetric r where r.k in {A} -- keep me
+  constraint functional r.x -> r.y
+  constraint Block:
+    anything   goes
+"
+    );
+    assert_eq!(format_axi(&formatted), formatted);
+}
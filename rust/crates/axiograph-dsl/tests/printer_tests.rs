@@ -0,0 +1,87 @@
+use axiograph_dsl::axi_v1::parse_axi_v1;
+use axiograph_dsl::printer::{
+    canonicalize_axi_v1, escape_axi_value, print_axi_v1, unescape_axi_value, AxiPrintError,
+};
+use axiograph_dsl::schema_v1::SetItemV1;
+use proptest::prelude::*;
+use std::path::{Path, PathBuf};
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../..")
+        .canonicalize()
+        .expect("canonicalize repo root")
+}
+
+fn axi_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    for entry in std::fs::read_dir(dir).expect("read dir") {
+        let path = entry.expect("dir entry").path();
+        if path.is_dir() {
+            axi_files(&path, out);
+        } else if path.extension().is_some_and(|e| e == "axi") {
+            out.push(path);
+        }
+    }
+}
+
+#[test]
+fn canonical_corpus_round_trips_through_the_printer() {
+    let mut files = Vec::new();
+    axi_files(&repo_root().join("examples"), &mut files);
+    files.sort();
+
+    let mut printed = 0;
+    for path in files {
+        let text = std::fs::read_to_string(&path).expect("read .axi");
+        let Ok(module) = parse_axi_v1(&text) else {
+            continue;
+        };
+        let out = print_axi_v1(&module).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+        assert_eq!(parse_axi_v1(&out).unwrap(), module, "{}", path.display());
+        assert_eq!(
+            canonicalize_axi_v1(&out).unwrap(),
+            out,
+            "{}",
+            path.display()
+        );
+        printed += 1;
+    }
+    assert!(printed > 10, "expected the example corpus to parse");
+}
+
+#[test]
+fn printer_rejects_text_that_would_reparse_differently() {
+    let mut module = parse_axi_v1(
+        "module M\n\nschema S:\n  object Person\n\ninstance I of S:\n  Person = {Alice}\n",
+    )
+    .unwrap();
+    module.instances[0].assignments[0].value.items[0] = SetItemV1::Ident {
+        name: "Smith, Alice".to_string(),
+    };
+    let err = print_axi_v1(&module).unwrap_err();
+    assert!(matches!(err, AxiPrintError::Unprintable { .. }), "{err}");
+
+    module.instances[0].assignments[0].value.items[0] = SetItemV1::Ident {
+        name: escape_axi_value("Smith, Alice"),
+    };
+    let out = print_axi_v1(&module).unwrap();
+    assert!(out.contains("Person = {Smith%2C Alice}"), "{out}");
+}
+
+proptest! {
+    #[test]
+    fn escaped_values_survive_printing(value in ".{0,24}") {
+        let escaped = escape_axi_value(&value);
+        prop_assert_eq!(unescape_axi_value(&escaped), value.clone());
+
+        let text = format!(
+            "module M\n\nschema S:\n  object Thing\n\ninstance I of S:\n  Thing = {{X}}\n  note = {{(of=X, text={escaped})}}\n"
+        );
+        let module = parse_axi_v1(&text).unwrap();
+        let SetItemV1::Tuple { fields } = &module.instances[0].assignments[1].value.items[0] else {
+            panic!("expected a tuple");
+        };
+        prop_assert_eq!(unescape_axi_value(&fields[1].1), value);
+        prop_assert!(print_axi_v1(&module).is_ok());
+    }
+}
@@ -0,0 +1,86 @@
+use axiograph_dsl::regen::{splice_axi, AxiRegenError, DeclKind, DeclRef};
+
+const EXISTING: &str = r#"module Shop
+
+-- Catalog schema.
+schema Catalog: -- owned by the catalog team
+  object Product
+  -- Prices are in cents.
+  object Price
+  -- Legacy; to be removed.
+  object Sku
+  relation HasPrice(
+    product: Product,
+    price: Price
+  )
+  -- end of catalog
+
+instance Demo of Catalog:
+  Product = {widget}
+"#;
+
+const REGENERATED: &str = r#"module Shop
+
+schema Catalog:
+  object Currency
+  object Price
+  object Product
+  relation HasPrice(product: Product, price: Price, currency: Currency)
+
+theory Pricing on Catalog:
+  constraint functional HasPrice.product -> HasPrice.price
+
+instance Demo of Catalog:
+  Product = {gadget, widget}
+"#;
+
+#[test]
+fn replaces_selected_blocks_and_reanchors_comments() {
+    let select = vec![
+        "schema:Catalog".parse().unwrap(),
+        "Pricing".parse().unwrap(),
+    ];
+    let regen = splice_axi(EXISTING, REGENERATED, &select).unwrap();
+    assert_eq!(regen.replaced, vec!["schema Catalog"]);
+    assert_eq!(regen.added, vec!["theory Pricing"]);
+    // `-- Legacy; to be removed.` lost `object Sku`.
+    assert_eq!(regen.orphaned_comments, 1);
+
+    let expected = r#"module Shop
+
+-- Catalog schema.
+schema Catalog: -- owned by the catalog team
+  object Currency
+  -- Prices are in cents.
+  object Price
+  object Product
+  relation HasPrice(product: Product, price: Price, currency: Currency)
+  -- Legacy; to be removed.
+  -- end of catalog
+
+instance Demo of Catalog:
+  Product = {widget}
+
+theory Pricing on Catalog:
+  constraint functional HasPrice.product -> HasPrice.price
+"#;
+    assert_eq!(regen.text, expected);
+
+    // Splicing the result again changes nothing but keeps the orphan.
+    let again = splice_axi(&regen.text, REGENERATED, &select).unwrap();
+    assert_eq!(again.text, regen.text);
+}
+
+#[test]
+fn rejects_unknown_selections() {
+    let missing = vec!["instance:Other".parse().unwrap()];
+    assert!(matches!(
+        splice_axi(EXISTING, REGENERATED, &missing),
+        Err(AxiRegenError::NotRegenerated(name)) if name == "instance Other"
+    ));
+
+    let decl: DeclRef = "theory:Pricing".parse().unwrap();
+    assert_eq!(decl.kind, Some(DeclKind::Theory));
+    assert!("view:Pricing".parse::<DeclRef>().is_err());
+    assert!("schema:".parse::<DeclRef>().is_err());
+}
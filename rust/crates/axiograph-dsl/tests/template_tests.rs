@@ -0,0 +1,213 @@
+use axiograph_dsl::lint::{count_diagnostics, lint_axi};
+use axiograph_dsl::schema_v1::{parse_schema_v1, SetItemV1};
+use axiograph_dsl::template::expand_templates_v1;
+
+fn machining() -> String {
+    [
+        "module Holes",
+        "",
+        "schema Machining:",
+        "  object Part",
+        "  object Face",
+        "  object Hole",
+        "  object Diameter",
+        "  relation HoleOn(hole: Hole, part: Part, face: Face)",
+        "  relation HoleDia(hole: Hole, dia: Diameter)",
+        "",
+        "template DrilledHole(part, face, dia) {",
+        "  # `$face` in this comment and in \"$face\" stays put.",
+        "  Hole = {$hole}",
+        "  HoleOn = {(hole=$hole, part=$part, face=$face)}",
+        "  HoleDia = {(hole=$hole, dia=$dia)}",
+        "}",
+        "",
+        "instance Bracket of Machining:",
+        "  Part = {BracketA}",
+        "  use DrilledHole(BracketA, TopFace, D10)",
+        "  use DrilledHole(BracketA, SideFace, D6)",
+        "",
+    ]
+    .join("\n")
+}
+
+fn tuples(
+    module: &axiograph_dsl::schema_v1::SchemaV1Module,
+    relation: &str,
+) -> Vec<Vec<(String, String)>> {
+    module.instances[0]
+        .assignments
+        .iter()
+        .filter(|a| a.name == relation)
+        .flat_map(|a| a.value.items.iter())
+        .filter_map(|item| match item {
+            SetItemV1::Tuple { fields } => Some(fields.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+    items
+        .iter()
+        .map(|(a, b)| (a.to_string(), b.to_string()))
+        .collect()
+}
+
+#[test]
+fn expands_calls_hygienically() {
+    let module = parse_schema_v1(&machining()).expect("parse templated module");
+    assert_eq!(
+        tuples(&module, "HoleOn"),
+        vec![
+            pairs(&[
+                ("hole", "hole_DrilledHole_1"),
+                ("part", "BracketA"),
+                ("face", "TopFace")
+            ]),
+            pairs(&[
+                ("hole", "hole_DrilledHole_2"),
+                ("part", "BracketA"),
+                ("face", "SideFace")
+            ]),
+        ]
+    );
+    assert_eq!(
+        tuples(&module, "HoleDia"),
+        vec![
+            pairs(&[("hole", "hole_DrilledHole_1"), ("dia", "D10")]),
+            pairs(&[("hole", "hole_DrilledHole_2"), ("dia", "D6")]),
+        ]
+    );
+
+    let expanded = expand_templates_v1(&machining()).unwrap();
+    assert!(expanded
+        .text
+        .contains("# `$face` in this comment and in \"$face\""));
+    assert_eq!(expanded.templates.len(), 1);
+    assert_eq!(expanded.templates[0].params, ["part", "face", "dia"]);
+
+    // Provenance: each call and the lines it produced.
+    assert_eq!(expanded.expansions.len(), 2);
+    let second = &expanded.expansions[1];
+    assert_eq!(second.template, "DrilledHole");
+    assert_eq!(second.args, ["BracketA", "SideFace", "D6"]);
+    assert_eq!(second.call_line, 21);
+    let first_line = expanded.origin(second.lines.start).unwrap();
+    assert_eq!(first_line.line, 21);
+    assert_eq!(first_line.via, vec![("DrilledHole".to_string(), 12)]);
+    // Text outside templates keeps its line numbers.
+    assert_eq!(expanded.origin(19).unwrap().line, 19);
+    assert!(expanded.origin(19).unwrap().via.is_empty());
+
+    // The templated module lints without errors.
+    let (errors, _) = count_diagnostics(&lint_axi(&machining()));
+    assert_eq!(errors, 0);
+}
+
+#[test]
+fn nested_templates_keep_relative_indentation() {
+    let text = [
+        "module Rules",
+        "",
+        "schema S:",
+        "  object P",
+        "  relation Edge(from: P, to: P)",
+        "",
+        "template Closed(rel) {",
+        "  constraint symmetric $rel",
+        "  use Keyed($rel)",
+        "}",
+        "",
+        "template Keyed(rel) {",
+        "  constraint key $rel(from, to)",
+        "  constraint Named_$block:",
+        "    $rel.from <> $rel.to",
+        "}",
+        "",
+        "theory T on S:",
+        "  use Closed(Edge)",
+        "",
+    ]
+    .join("\n");
+    let module = parse_schema_v1(&text).expect("parse");
+    let constraints = &module.theories[0].constraints;
+    assert_eq!(constraints.len(), 3, "{constraints:?}");
+
+    let expanded = expand_templates_v1(&text).unwrap();
+    assert!(expanded
+        .text
+        .contains("  constraint Named_block_Keyed_2:\n    Edge.from <> Edge.to"));
+    // Inner call recorded (outermost call line), then the outer one.
+    let calls: Vec<_> = expanded
+        .expansions
+        .iter()
+        .map(|e| (e.template.as_str(), e.call_line))
+        .collect();
+    assert_eq!(calls, [("Keyed", 19), ("Closed", 19)]);
+}
+
+#[test]
+fn errors_point_at_the_call_site() {
+    // A bad body line is reported at the call, naming the template line.
+    let text = machining().replace(
+        "  HoleDia = {(hole=$hole, dia=$dia)}",
+        "  HoleDia {(hole=$hole, dia=$dia)}",
+    );
+    let err = parse_schema_v1(&text).unwrap_err().to_string();
+    assert!(err.starts_with("parse error on line 20:"), "{err}");
+    assert!(
+        err.contains("(in expansion of template `DrilledHole` line 15)"),
+        "{err}"
+    );
+
+    let err = parse_schema_v1(&machining().replace(
+        "DrilledHole(BracketA, SideFace, D6)",
+        "DrilledHole(BracketA)",
+    ))
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("line 21"), "{err}");
+    assert!(
+        err.contains("takes 3 argument(s) (part, face, dia), got 1"),
+        "{err}"
+    );
+
+    let err = parse_schema_v1(&machining().replace(
+        "use DrilledHole(BracketA, TopFace",
+        "use Drilled(BracketA, TopFace",
+    ))
+    .unwrap_err()
+    .to_string();
+    assert!(
+        err.contains("line 20") && err.contains("unknown template `Drilled`"),
+        "{err}"
+    );
+
+    let recursive = "module R\ntemplate Loop(x) {\n  use Loop(x)\n}\nschema S:\n  use Loop(a)\n";
+    let err = parse_schema_v1(recursive).unwrap_err().to_string();
+    assert!(
+        err.contains("line 6") && err.contains("expands recursively"),
+        "{err}"
+    );
+
+    let unterminated = "module R\ntemplate Open(x) {\n  object x\n";
+    let err = parse_schema_v1(unterminated).unwrap_err().to_string();
+    assert!(
+        err.contains("line 2") && err.contains("missing its closing"),
+        "{err}"
+    );
+
+    let twice = "module R\ntemplate T() {\n}\ntemplate T() {\n}\n";
+    let err = parse_schema_v1(twice).unwrap_err().to_string();
+    assert!(
+        err.contains("line 4") && err.contains("defined more than once"),
+        "{err}"
+    );
+
+    // `use = {...}` is still an ordinary assignment.
+    let plain = "module R\nschema S:\n  object use\ninstance I of S:\n  use = {a}\n";
+    assert_eq!(
+        parse_schema_v1(plain).unwrap().instances[0].assignments[0].name,
+        "use"
+    );
+}
@@ -18,8 +18,8 @@
 //! LLM-driven augmentation is intentionally implemented at the CLI layer so this crate
 //! stays usable in restricted environments.
 
+use crate::IngestResult;
 use crate::{EvidencePointer, ProposalMetaV1, ProposalSourceV1, ProposalV1, ProposalsFileV1};
-use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
@@ -95,7 +95,7 @@ pub fn augment_proposals_v1(
     trace_id: String,
     generated_at: String,
     options: &AugmentOptionsV1,
-) -> Result<(ProposalsFileV1, ProposalsAugmentTraceV1)> {
+) -> IngestResult<(ProposalsFileV1, ProposalsAugmentTraceV1)> {
     let mut out = input.clone();
     out.generated_at = generated_at.clone();
 
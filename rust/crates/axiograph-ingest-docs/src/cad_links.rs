@@ -0,0 +1,268 @@
+//! Cross-modal linking: document claims → CAD features.
+//!
+//! Machinist notes talk about geometry ("the 10mm bore on bracket B-12"),
+//! while STEP ingestion produces `Face`/`FeatureTag` entities with no prose
+//! attached. This pass matches extracted facts to [`CadFeatureV1`]s and emits
+//! `describes_feature` relation proposals from the fact's claim
+//! (`claim::<fact_id>`, as created by [`crate::proposals_from_extracted_facts_v1`])
+//! to the feature entity, so tacit knowledge lands on the geometry it is about.
+//!
+//! Matching is deterministic:
+//!
+//! 1. **Part number** (required): a fact is only linked to features of parts
+//!    it names. Part numbers compare case-insensitively, ignoring `-`, `_`
+//!    and spaces (`B-12` = `b12`).
+//! 2. **Dimensions**: lengths in the text (`10mm`, `0.5 inch`, `Ø12`) match a
+//!    feature dimension within [`CadLinkOptionsV1::dimension_tolerance_mm`].
+//! 3. **Kind**: a feature word in the text (`bore`, `hole`, `pocket`, ...)
+//!    that fits the feature kind.
+//!
+//! A part-only match is not enough. If several features tie for the best
+//! score, each link's confidence is divided among them.
+
+use crate::{sanitize_id, EvidencePointer, ExtractedFact, ProposalMetaV1, ProposalV1};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Relation type of the emitted proposals.
+pub const DESCRIBES_FEATURE_REL: &str = "describes_feature";
+
+/// A named dimension of a feature, in millimetres.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CadDimensionV1 {
+    /// e.g. `diameter`, `depth`, `width`
+    pub name: String,
+    pub value_mm: f64,
+}
+
+/// A CAD feature to link against (typically a STEP-derived `Face` with a
+/// `FeatureTag` such as `HoleOrBoss:Cylindrical`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CadFeatureV1 {
+    /// Entity id of the face/feature (the relation target).
+    pub entity_id: String,
+    /// Part number of the owning product.
+    pub part_number: String,
+    /// Feature kind or tag, e.g. `hole`, `HoleOrBoss:Cylindrical`.
+    pub feature_kind: String,
+    #[serde(default)]
+    pub dimensions: Vec<CadDimensionV1>,
+}
+
+/// Options for [`link_facts_to_cad_features_v1`].
+#[derive(Debug, Clone)]
+pub struct CadLinkOptionsV1 {
+    /// Largest difference between a mentioned and a modelled length.
+    pub dimension_tolerance_mm: f64,
+    /// Links below this confidence are dropped.
+    pub min_confidence: f64,
+    pub evidence_locator: Option<String>,
+    pub schema_hint: Option<String>,
+}
+
+impl Default for CadLinkOptionsV1 {
+    fn default() -> Self {
+        Self {
+            dimension_tolerance_mm: 0.05,
+            min_confidence: 0.2,
+            evidence_locator: None,
+            schema_hint: None,
+        }
+    }
+}
+
+/// Text words that name a feature, and the kind word they imply.
+const KIND_WORDS: &[(&str, &str)] = &[
+    ("hole", "hole"),
+    ("holes", "hole"),
+    ("bore", "hole"),
+    ("bores", "hole"),
+    ("counterbore", "hole"),
+    ("drilled", "hole"),
+    ("boss", "boss"),
+    ("pocket", "pocket"),
+    ("pockets", "pocket"),
+    ("slot", "slot"),
+    ("slots", "slot"),
+    ("face", "face"),
+    ("chamfer", "chamfer"),
+    ("fillet", "fillet"),
+];
+
+fn normalize_part_number(s: &str) -> String {
+    s.chars()
+        .filter(|c| !matches!(c, '-' | '_' | ' '))
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+fn dimension_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"(?i)(?:(Ø|⌀)\s*)?(\d+(?:\.\d+)?)\s*(mm\b|cm\b|inch(?:es)?\b|")?"#).unwrap()
+    })
+}
+
+/// Lengths mentioned in `text`, in millimetres. Bare numbers only count
+/// after a diameter sign (`Ø12`), which implies millimetres.
+pub fn mentioned_lengths_mm(text: &str) -> Vec<f64> {
+    dimension_regex()
+        .captures_iter(text)
+        .filter_map(|caps| {
+            let value: f64 = caps[2].parse().ok()?;
+            let unit = caps.get(3).map(|m| m.as_str().to_ascii_lowercase());
+            let scale = match unit.as_deref() {
+                Some("mm") => 1.0,
+                Some("cm") => 10.0,
+                Some(_) => 25.4,
+                None if caps.get(1).is_some() => 1.0,
+                None => return None,
+            };
+            Some(value * scale)
+        })
+        .collect()
+}
+
+/// Kind words (`hole`, `pocket`, ...) mentioned in `text`.
+fn mentioned_kinds(text: &str) -> Vec<&'static str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter_map(|word| {
+            let word = word.to_ascii_lowercase();
+            KIND_WORDS
+                .iter()
+                .find(|(w, _)| *w == word)
+                .map(|(_, kind)| *kind)
+        })
+        .collect()
+}
+
+fn fact_text(fact: &ExtractedFact) -> String {
+    let mut text = format!("{} {}", fact.statement, fact.evidence_span);
+    let mut fields: Vec<_> = fact.extracted_entities.values().collect();
+    fields.sort();
+    for value in fields {
+        text.push(' ');
+        text.push_str(value);
+    }
+    text
+}
+
+/// How well a feature fits a fact, with the reasons (for the rationale).
+fn match_score(
+    feature: &CadFeatureV1,
+    lengths: &[f64],
+    kinds: &[&str],
+    tolerance: f64,
+) -> Option<(f64, Vec<String>)> {
+    let mut reasons = vec![format!("part {}", feature.part_number)];
+    let dimension = feature
+        .dimensions
+        .iter()
+        .find(|d| lengths.iter().any(|l| (l - d.value_mm).abs() <= tolerance));
+    let feature_kind = feature.feature_kind.to_ascii_lowercase();
+    let kind = kinds.iter().find(|k| feature_kind.contains(*k));
+
+    if let Some(d) = dimension {
+        reasons.push(format!("{} {}mm", d.name, d.value_mm));
+    }
+    if let Some(k) = kind {
+        reasons.push(format!("kind `{k}`"));
+    }
+    let score = match (dimension.is_some(), kind.is_some()) {
+        (true, true) => 0.95,
+        (true, false) => 0.85,
+        (false, true) => 0.6,
+        (false, false) => return None,
+    };
+    Some((score, reasons))
+}
+
+/// Link extracted facts to the CAD features they describe; emits one
+/// `describes_feature` relation proposal per link (see the module docs).
+pub fn link_facts_to_cad_features_v1(
+    facts: &[ExtractedFact],
+    features: &[CadFeatureV1],
+    options: &CadLinkOptionsV1,
+) -> Vec<ProposalV1> {
+    let mut by_part: HashMap<String, Vec<&CadFeatureV1>> = HashMap::new();
+    for feature in features {
+        by_part
+            .entry(normalize_part_number(&feature.part_number))
+            .or_default()
+            .push(feature);
+    }
+
+    let mut out = Vec::new();
+    for fact in facts {
+        let text = fact_text(fact);
+        let tokens: Vec<String> = text
+            .split(|c: char| !(c.is_alphanumeric() || matches!(c, '-' | '_')))
+            .map(normalize_part_number)
+            .filter(|t| !t.is_empty())
+            .collect();
+        let lengths = mentioned_lengths_mm(&text);
+        let kinds = mentioned_kinds(&text);
+
+        let mut candidates: Vec<(&CadFeatureV1, f64, Vec<String>)> = Vec::new();
+        for (part, part_features) in &by_part {
+            if !tokens.iter().any(|t| t == part) {
+                continue;
+            }
+            for feature in part_features {
+                if let Some((score, reasons)) =
+                    match_score(feature, &lengths, &kinds, options.dimension_tolerance_mm)
+                {
+                    candidates.push((feature, score, reasons));
+                }
+            }
+        }
+        let Some(best) = candidates.iter().map(|(_, s, _)| *s).reduce(f64::max) else {
+            continue;
+        };
+        candidates.retain(|(_, s, _)| *s == best);
+        candidates.sort_by(|a, b| a.0.entity_id.cmp(&b.0.entity_id));
+        let confidence = fact.confidence * best / candidates.len() as f64;
+        if confidence < options.min_confidence {
+            continue;
+        }
+
+        let claim_id = format!("claim::{}", sanitize_id(&fact.fact_id));
+        for (feature, _, reasons) in candidates {
+            let relation_id = format!(
+                "rel::describes_feature::{}::{}",
+                sanitize_id(&claim_id),
+                sanitize_id(&feature.entity_id)
+            );
+            let mut attributes = HashMap::new();
+            attributes.insert("part_number".to_string(), feature.part_number.clone());
+            attributes.insert("feature_kind".to_string(), feature.feature_kind.clone());
+            attributes.insert("matched_on".to_string(), reasons.join(", "));
+            out.push(ProposalV1::Relation {
+                meta: ProposalMetaV1 {
+                    proposal_id: relation_id.clone(),
+                    confidence,
+                    evidence: vec![EvidencePointer {
+                        chunk_id: fact.source_chunk_id.clone(),
+                        locator: options.evidence_locator.clone(),
+                        span_id: None,
+                    }],
+                    public_rationale: format!(
+                        "Claim matches {} on {}.",
+                        feature.entity_id,
+                        reasons.join(", ")
+                    ),
+                    metadata: HashMap::new(),
+                    schema_hint: options.schema_hint.clone(),
+                },
+                relation_id,
+                rel_type: DESCRIBES_FEATURE_REL.to_string(),
+                source: claim_id.clone(),
+                target: feature.entity_id.clone(),
+                attributes,
+            });
+        }
+    }
+    out
+}
@@ -6,7 +6,7 @@
 //! - Structured content (tables, lists, code blocks)
 //! - Page hierarchy and links
 
-use anyhow::Result;
+use crate::IngestResult;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -63,7 +63,11 @@ pub struct PageLink {
 }
 
 /// Parse Confluence HTML export
-pub fn parse_confluence_html(html: &str, page_id: &str, space: &str) -> Result<ConfluencePage> {
+pub fn parse_confluence_html(
+    html: &str,
+    page_id: &str,
+    space: &str,
+) -> IngestResult<ConfluencePage> {
     // Extract title from <title> or <h1>
     let title_re = Regex::new(r"<title>([^<]+)</title>").unwrap();
     let h1_re = Regex::new(r"<h1[^>]*>([^<]+)</h1>").unwrap();
@@ -8,7 +8,6 @@
 
 #![allow(unused_imports)]
 
-use anyhow::Result;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
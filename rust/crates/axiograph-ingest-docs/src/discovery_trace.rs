@@ -13,7 +13,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::{Chunk, EvidencePointer, RepoEdgeV1};
+use crate::{Chunk, EvidencePointer, IngestResult, RepoEdgeV1};
 
 /// A structured proposal produced by discovery (heuristics or LLMs).
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,7 +66,7 @@ pub fn suggest_mentions_symbol_trace_v1(
     max_proposals: usize,
     trace_id: String,
     generated_at: String,
-) -> anyhow::Result<DiscoveryTraceV1> {
+) -> IngestResult<DiscoveryTraceV1> {
     use std::collections::{BTreeMap, BTreeSet};
 
     // Build symbol -> (defining file, kind) index from edges.
@@ -85,7 +85,7 @@ pub fn suggest_mentions_symbol_trace_v1(
         }
     }
 
-    let ident_re = regex::Regex::new(r"\b[A-Za-z_][A-Za-z0-9_]*\b")?;
+    let ident_re = regex::Regex::new(r"\b[A-Za-z_][A-Za-z0-9_]*\b").unwrap();
 
     // (from_file, symbol) -> (confidence, evidence pointers, rationale, metadata)
     let mut proposals: BTreeMap<
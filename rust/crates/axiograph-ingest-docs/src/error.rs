@@ -0,0 +1,49 @@
+//! Structured errors shared by the ingestion crates.
+//!
+//! `axiograph-ingest-docs` is the common dependency of the ingest adapters
+//! (RDF/OWL, proto, SQL), so their public entry points all return
+//! [`IngestError`], as does every entry point of this crate. Adapter
+//! internals that still use `anyhow` convert through [`IngestError::Other`].
+//! The STEP and IGES parsers do not depend on this crate and still return
+//! `anyhow`.
+
+use thiserror::Error;
+
+/// Errors returned by ingestion entry points.
+#[derive(Debug, Error)]
+pub enum IngestError {
+    /// The input format (usually a file extension) is not supported.
+    #[error("unsupported {kind} format: {format}")]
+    UnsupportedFormat { kind: &'static str, format: String },
+
+    /// The input could not be parsed as `format`.
+    #[error("failed to parse {format}: {message}")]
+    Parse {
+        format: &'static str,
+        message: String,
+    },
+
+    /// The requested extractor was compiled out.
+    #[error("{0} feature not enabled")]
+    FeatureNotEnabled(&'static str),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl IngestError {
+    pub fn parse(format: &'static str, err: impl std::fmt::Display) -> Self {
+        IngestError::Parse {
+            format,
+            message: err.to_string(),
+        }
+    }
+}
+
+pub type IngestResult<T> = std::result::Result<T, IngestError>;
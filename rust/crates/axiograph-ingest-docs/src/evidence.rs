@@ -3,10 +3,11 @@
 //! These are used throughout the ingestion pipeline to link any extracted proposal back to
 //! the concrete evidence that supports it (document chunk ids, file paths, etc).
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// A pointer to evidence supporting a proposal.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EvidencePointer {
     /// The chunk id (see `Chunk.chunk_id`) that contains supporting evidence.
     pub chunk_id: String,
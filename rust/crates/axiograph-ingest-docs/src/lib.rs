@@ -12,35 +12,44 @@
 //! - JSON chunks file for RAG/vector search
 //! - Extracted facts with confidence scores
 //! - `proposals.json` (Evidence/Proposals schema) for explicit promotion into canonical `.axi`
+//! - `describes_feature` links from claims to CAD features (see `cad_links`)
+//! - Ordered `ProcessPlan` proposals from extracted procedure steps (see `process_plans`)
 //!
 //! **Untrusted boundary**: this crate is heavy IO/parsing; semantic meaning is defined
 //! in Lean and enforced via certificates (Rust computes, Lean verifies).
 
-use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 
 pub mod augment;
+pub mod cad_links;
 pub mod confluence;
 pub mod conversations;
 pub mod discovery_trace;
+pub mod error;
 pub mod evidence;
 pub mod fact_extraction;
 pub mod pdf;
+pub mod process_plans;
 pub mod promotion;
+pub mod proposal_schema;
 pub mod proposals;
 pub mod readings;
 pub mod repo;
 
 pub use augment::*;
+pub use cad_links::*;
 pub use confluence::*;
 pub use conversations::*;
 pub use discovery_trace::*;
+pub use error::{IngestError, IngestResult};
 pub use evidence::*;
 pub use fact_extraction::*;
 pub use pdf::{PdfDocument, PdfError, PdfParser};
+pub use process_plans::*;
 pub use promotion::*;
+pub use proposal_schema::*;
 pub use proposals::*;
 pub use readings::*;
 pub use repo::*;
@@ -174,11 +183,11 @@ pub fn extract_markdown(text: &str, doc_id: &str) -> DocumentExtraction {
 // ============================================================================
 
 #[cfg(feature = "pdf")]
-pub fn extract_pdf(path: &Path) -> Result<DocumentExtraction> {
+pub fn extract_pdf(path: &Path) -> IngestResult<DocumentExtraction> {
     use pdf_extract::extract_text_from_mem;
 
     let bytes = std::fs::read(path)?;
-    let text = extract_text_from_mem(&bytes)?;
+    let text = extract_text_from_mem(&bytes).map_err(|e| IngestError::parse("PDF", e))?;
 
     let doc_id = path
         .file_stem()
@@ -192,12 +201,12 @@ pub fn extract_pdf(path: &Path) -> Result<DocumentExtraction> {
 }
 
 #[cfg(not(feature = "pdf"))]
-pub fn extract_pdf(_path: &Path) -> Result<DocumentExtraction> {
-    Err(anyhow!("PDF feature not enabled"))
+pub fn extract_pdf(_path: &Path) -> IngestResult<DocumentExtraction> {
+    Err(IngestError::FeatureNotEnabled("PDF"))
 }
 
 /// Output chunks as JSON
-pub fn chunks_to_json(extraction: &DocumentExtraction) -> Result<String> {
+pub fn chunks_to_json(extraction: &DocumentExtraction) -> IngestResult<String> {
     Ok(serde_json::to_string_pretty(&extraction.chunks)?)
 }
 
@@ -339,7 +348,7 @@ pub fn extract_knowledge_from_confluence(
     html: &str,
     page_id: &str,
     space: &str,
-) -> Result<KnowledgeExtractionResult> {
+) -> IngestResult<KnowledgeExtractionResult> {
     let page = parse_confluence_html(html, page_id, space)?;
     let extraction = confluence_to_extraction(&page);
 
@@ -0,0 +1,396 @@
+//! Process-plan assembly from extracted machining facts.
+//!
+//! Fact extraction yields isolated claims: a `procedure_step` ("first, face
+//! the stock ..."), a speed ("run 400 sfm"), a tool ("use TiAlN coated
+//! carbide"). This pass groups them per document into an ordered
+//! [`ProcessPlanV1`], the way the proto ingester groups RPCs into an
+//! `ApiWorkflow`:
+//!
+//! 1. Procedure facts become steps, located by their evidence span in the
+//!    chunk text. Steps are ordered by sequence word: if every step is
+//!    numbered (`step 2`), by number; otherwise `first` steps lead, `finally`
+//!    steps trail, and the rest keep their text order.
+//! 2. Parameter facts (speed, feed, depth of cut, coolant, material, tool)
+//!    between a step and the next one in the text attach to that step;
+//!    parameter facts before the first step describe the whole plan.
+//! 3. A document yields a plan only if it has at least two steps.
+//!
+//! [`proposals_from_process_plans_v1`] emits the plans as reviewable
+//! `ProcessPlan`/`ProcessStep` entity proposals with `process_plan_has_step`,
+//! `process_step_precedes` and `supported_by_claim` relations (the latter to
+//! the `claim::<fact_id>` entities of [`crate::proposals_from_extracted_facts_v1`]).
+
+use crate::{
+    sanitize_id, truncate_for_name, Chunk, EvidencePointer, ExtractedFact, FactType,
+    ProposalMetaV1, ProposalV1,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// One step of a process plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessStepV1 {
+    pub step_id: String,
+    /// 1-based position in the plan.
+    pub index: usize,
+    /// `first`, `then`, `next`, `finally` or `step N`.
+    pub sequence_word: String,
+    pub text: String,
+    /// The `procedure_step` fact this step came from.
+    pub fact_id: String,
+    pub source_chunk_id: String,
+    pub confidence: f64,
+    /// e.g. `speed` = `400 sfm`, `tool_material` = `carbide`.
+    #[serde(default)]
+    pub parameters: BTreeMap<String, String>,
+    /// Facts the parameters came from.
+    #[serde(default)]
+    pub parameter_fact_ids: Vec<String>,
+}
+
+/// An ordered process plan assembled from one document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessPlanV1 {
+    pub plan_id: String,
+    pub document_id: String,
+    /// Parameters stated before the first step (typically the material).
+    #[serde(default)]
+    pub parameters: BTreeMap<String, String>,
+    #[serde(default)]
+    pub parameter_fact_ids: Vec<String>,
+    pub steps: Vec<ProcessStepV1>,
+}
+
+/// Sequence word at the start of a procedure fact, with its step number.
+fn sequence_word(statement: &str) -> (String, Option<u32>) {
+    let lower = statement.trim_start().to_ascii_lowercase();
+    if let Some(rest) = lower.strip_prefix("step") {
+        let digits: String = rest
+            .trim_start()
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect();
+        if let Ok(n) = digits.parse() {
+            return (format!("step {n}"), Some(n));
+        }
+    }
+    let word = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default();
+    (word.to_string(), None)
+}
+
+/// Process parameters stated by a fact.
+fn parameters_of(fact: &ExtractedFact) -> Vec<(String, String)> {
+    let get = |k: &str| fact.extracted_entities.get(k).map(|v| v.trim().to_string());
+    let with_unit = |v: String| match get("unit") {
+        Some(unit) => format!("{v} {unit}"),
+        None => v,
+    };
+    let mut out = Vec::new();
+    if let Some(speed) = get("speed") {
+        out.push(("speed".to_string(), with_unit(speed)));
+    }
+    if let Some(feed) = get("feed") {
+        out.push(("feed".to_string(), with_unit(feed)));
+    }
+    if let Some(depth) = get("depth") {
+        let mut depth = with_unit(depth);
+        if let Some(direction) = get("direction") {
+            depth = format!("{depth} {direction}");
+        }
+        out.push(("depth_of_cut".to_string(), depth));
+    }
+    for key in ["coolant", "material", "tool_material", "coating"] {
+        if let Some(v) = get(key) {
+            out.push((key.to_string(), v.to_ascii_lowercase()));
+        }
+    }
+    out
+}
+
+/// Where a fact sits: (chunk position, byte offset of its span in the chunk).
+type Position = (usize, usize);
+
+/// Parameters gathered for a plan or step, with the facts they came from.
+type Parameters = (BTreeMap<String, String>, Vec<String>);
+
+/// Assemble ordered process plans from `facts` extracted from `chunks` (see
+/// the module docs). Facts whose chunk is not in `chunks` are ignored.
+pub fn build_process_plans_v1(chunks: &[Chunk], facts: &[ExtractedFact]) -> Vec<ProcessPlanV1> {
+    let chunk_pos: HashMap<&str, (usize, &Chunk)> = chunks
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (c.chunk_id.as_str(), (i, c)))
+        .collect();
+
+    // document id -> located facts, documents in chunk order.
+    let mut documents: Vec<&str> = Vec::new();
+    let mut by_document: HashMap<&str, Vec<(Position, &ExtractedFact)>> = HashMap::new();
+    for fact in facts {
+        let Some(&(i, chunk)) = chunk_pos.get(fact.source_chunk_id.as_str()) else {
+            continue;
+        };
+        let offset = chunk.text.find(&fact.evidence_span).unwrap_or(0);
+        let doc = chunk.document_id.as_str();
+        by_document
+            .entry(doc)
+            .or_insert_with(|| {
+                documents.push(doc);
+                Vec::new()
+            })
+            .push(((i, offset), fact));
+    }
+    documents.sort_by_key(|doc| by_document[*doc].iter().map(|(p, _)| *p).min());
+
+    let mut plans = Vec::new();
+    for doc in documents {
+        let mut located = by_document.remove(doc).unwrap_or_default();
+        // A step and a parameter at the same offset: the step comes first.
+        located.sort_by_key(|(pos, fact)| (*pos, !matches!(fact.fact_type, FactType::Procedure)));
+        if let Some(plan) = assemble_plan(doc, &located) {
+            plans.push(plan);
+        }
+    }
+    plans
+}
+
+fn assemble_plan(
+    document_id: &str,
+    located: &[(Position, &ExtractedFact)],
+) -> Option<ProcessPlanV1> {
+    let doc_key = sanitize_id(document_id);
+    let mut plan_params: Parameters = (BTreeMap::new(), Vec::new());
+    // Steps in text order, each with the parameters that follow it.
+    let mut steps: Vec<(&ExtractedFact, Parameters)> = Vec::new();
+    for (_, fact) in located {
+        if matches!(fact.fact_type, FactType::Procedure) {
+            steps.push((fact, (BTreeMap::new(), Vec::new())));
+            continue;
+        }
+        let params = parameters_of(fact);
+        if params.is_empty() {
+            continue;
+        }
+        let (target, fact_ids) = match steps.last_mut() {
+            Some((_, slot)) => (&mut slot.0, &mut slot.1),
+            None => (&mut plan_params.0, &mut plan_params.1),
+        };
+        for (k, v) in params {
+            target.entry(k).or_insert(v);
+        }
+        fact_ids.push(fact.fact_id.clone());
+    }
+    if steps.len() < 2 {
+        return None;
+    }
+
+    let mut ordered: Vec<(usize, String, Option<u32>)> = steps
+        .iter()
+        .enumerate()
+        .map(|(i, (fact, _))| {
+            let (word, n) = sequence_word(&fact.statement);
+            (i, word, n)
+        })
+        .collect();
+    if ordered.iter().all(|(_, _, n)| n.is_some()) {
+        ordered.sort_by_key(|(i, _, n)| (*n, *i));
+    } else {
+        ordered.sort_by_key(|(i, word, _)| {
+            let bucket = match word.as_str() {
+                "first" => 0,
+                "finally" => 2,
+                _ => 1,
+            };
+            (bucket, *i)
+        });
+    }
+
+    let steps = ordered
+        .into_iter()
+        .enumerate()
+        .map(|(k, (i, sequence_word, _))| {
+            let (fact, (parameters, parameter_fact_ids)) = &steps[i];
+            ProcessStepV1 {
+                step_id: format!("process_step::{doc_key}::{}", k + 1),
+                index: k + 1,
+                sequence_word,
+                text: fact
+                    .extracted_entities
+                    .get("step")
+                    .unwrap_or(&fact.statement)
+                    .trim()
+                    .to_string(),
+                fact_id: fact.fact_id.clone(),
+                source_chunk_id: fact.source_chunk_id.clone(),
+                confidence: fact.confidence,
+                parameters: parameters.clone(),
+                parameter_fact_ids: parameter_fact_ids.clone(),
+            }
+        })
+        .collect();
+
+    Some(ProcessPlanV1 {
+        plan_id: format!("process_plan::{doc_key}"),
+        document_id: document_id.to_string(),
+        parameters: plan_params.0,
+        parameter_fact_ids: plan_params.1,
+        steps,
+    })
+}
+
+fn claim_id(fact_id: &str) -> String {
+    format!("claim::{}", sanitize_id(fact_id))
+}
+
+/// Emit process plans as reviewable proposals.
+pub fn proposals_from_process_plans_v1(
+    plans: &[ProcessPlanV1],
+    evidence_locator: Option<String>,
+    schema_hint: Option<String>,
+) -> Vec<ProposalV1> {
+    let evidence = |chunk_id: &str| EvidencePointer {
+        chunk_id: chunk_id.to_string(),
+        locator: evidence_locator.clone(),
+        span_id: None,
+    };
+    let meta = |proposal_id: &str, confidence: f64, evidence: Vec<EvidencePointer>, why: String| {
+        ProposalMetaV1 {
+            proposal_id: proposal_id.to_string(),
+            confidence,
+            evidence,
+            public_rationale: why,
+            metadata: HashMap::new(),
+            schema_hint: schema_hint.clone(),
+        }
+    };
+    let relation = |rel_type: &str,
+                    source: &str,
+                    target: &str,
+                    confidence: f64,
+                    evidence: Vec<EvidencePointer>,
+                    attributes: HashMap<String, String>,
+                    why: &str| {
+        let relation_id = format!(
+            "rel::{rel_type}::{}::{}",
+            sanitize_id(source),
+            sanitize_id(target)
+        );
+        ProposalV1::Relation {
+            meta: meta(&relation_id, confidence, evidence, why.to_string()),
+            relation_id,
+            rel_type: rel_type.to_string(),
+            source: source.to_string(),
+            target: target.to_string(),
+            attributes,
+        }
+    };
+
+    let mut out = Vec::new();
+    for plan in plans {
+        let mut chunk_ids: Vec<&str> = plan
+            .steps
+            .iter()
+            .map(|s| s.source_chunk_id.as_str())
+            .collect();
+        chunk_ids.dedup();
+        let mut attributes: HashMap<String, String> = plan
+            .parameters
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        attributes.insert("document_id".to_string(), plan.document_id.clone());
+        attributes.insert("step_count".to_string(), plan.steps.len().to_string());
+        out.push(ProposalV1::Entity {
+            meta: meta(
+                &plan.plan_id,
+                0.60,
+                chunk_ids.iter().map(|c| evidence(c)).collect(),
+                "Heuristic grouping of procedure steps into a process plan (tacit).".to_string(),
+            ),
+            entity_id: plan.plan_id.clone(),
+            entity_type: "ProcessPlan".to_string(),
+            name: format!("Process plan: {}", plan.document_id),
+            attributes,
+            description: None,
+        });
+        for fact_id in &plan.parameter_fact_ids {
+            out.push(relation(
+                "supported_by_claim",
+                &plan.plan_id,
+                &claim_id(fact_id),
+                0.60,
+                Vec::new(),
+                HashMap::from([("role".to_string(), "parameter".to_string())]),
+                "Parameter stated before the first step applies to the plan.",
+            ));
+        }
+
+        for step in &plan.steps {
+            let mut attributes: HashMap<String, String> = step
+                .parameters
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+            attributes.insert("index".to_string(), step.index.to_string());
+            attributes.insert("sequence_word".to_string(), step.sequence_word.clone());
+            attributes.insert("text".to_string(), step.text.clone());
+            out.push(ProposalV1::Entity {
+                meta: meta(
+                    &step.step_id,
+                    step.confidence,
+                    vec![evidence(&step.source_chunk_id)],
+                    format!("Procedure step `{}`.", step.sequence_word),
+                ),
+                entity_id: step.step_id.clone(),
+                entity_type: "ProcessStep".to_string(),
+                name: truncate_for_name(&step.text, 80),
+                attributes,
+                description: None,
+            });
+            out.push(relation(
+                "process_plan_has_step",
+                &plan.plan_id,
+                &step.step_id,
+                0.60,
+                vec![evidence(&step.source_chunk_id)],
+                HashMap::from([("index".to_string(), step.index.to_string())]),
+                "Procedure step is part of the document's process plan.",
+            ));
+            out.push(relation(
+                "supported_by_claim",
+                &step.step_id,
+                &claim_id(&step.fact_id),
+                step.confidence,
+                vec![evidence(&step.source_chunk_id)],
+                HashMap::from([("role".to_string(), "step".to_string())]),
+                "Step was extracted from this claim.",
+            ));
+            for fact_id in &step.parameter_fact_ids {
+                out.push(relation(
+                    "supported_by_claim",
+                    &step.step_id,
+                    &claim_id(fact_id),
+                    0.55,
+                    vec![evidence(&step.source_chunk_id)],
+                    HashMap::from([("role".to_string(), "parameter".to_string())]),
+                    "Parameter stated after this step (before the next) applies to it.",
+                ));
+            }
+        }
+
+        for pair in plan.steps.windows(2) {
+            out.push(relation(
+                "process_step_precedes",
+                &pair[0].step_id,
+                &pair[1].step_id,
+                0.55,
+                Vec::new(),
+                HashMap::new(),
+                "Heuristic step ordering by sequence words.",
+            ));
+        }
+    }
+    out
+}
@@ -17,8 +17,8 @@
 
 #![allow(unused_imports)]
 
+use crate::IngestResult;
 use crate::{EvidencePointer, ProposalMetaV1, ProposalSourceV1, ProposalV1, ProposalsFileV1};
-use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
@@ -139,7 +139,7 @@ pub struct UnmappedProposalV1 {
 pub fn promote_proposals_to_candidates_v1(
     file: &ProposalsFileV1,
     options: &PromoteOptionsV1,
-) -> Result<PromoteResultV1> {
+) -> IngestResult<PromoteResultV1> {
     let mut candidates: BTreeMap<PromotionDomainV1, String> = BTreeMap::new();
     let mut domain_summaries: Vec<DomainSummaryV1> = Vec::new();
     let mut conflicts: Vec<EntityConflictV1> = Vec::new();
@@ -417,7 +417,7 @@ fn promote_domain(
     domain: PromotionDomainV1,
     proposals: &[ProposalV1],
     file: &ProposalsFileV1,
-) -> Result<(
+) -> IngestResult<(
     String,
     DomainEmitSummary,
     Vec<EntityConflictV1>,
@@ -652,7 +652,7 @@ fn emit_machinist_learning(
     file: &ProposalsFileV1,
     proposals: &[ProposalV1],
     resolved: &ResolutionIndex,
-) -> Result<(String, usize, Vec<UnmappedProposalV1>)> {
+) -> IngestResult<(String, usize, Vec<UnmappedProposalV1>)> {
     let mut builder = SchemaInstanceBuilder::new(
         PromotionDomainV1::MachinistLearning.candidate_module_name(),
         "MachiningLearning",
@@ -1014,7 +1014,7 @@ fn emit_economic_flows(
     proposals: &[ProposalV1],
     resolved: &ResolutionIndex,
     fallback_by_name: &BTreeMap<String, String>,
-) -> Result<(String, usize, Vec<UnmappedProposalV1>)> {
+) -> IngestResult<(String, usize, Vec<UnmappedProposalV1>)> {
     let mut builder = SchemaInstanceBuilder::new(
         PromotionDomainV1::EconomicFlows.candidate_module_name(),
         "Economy",
@@ -1269,7 +1269,7 @@ fn emit_schema_evolution(
     proposals: &[ProposalV1],
     resolved: &ResolutionIndex,
     fallback_by_name: &BTreeMap<String, String>,
-) -> Result<(String, usize, Vec<UnmappedProposalV1>)> {
+) -> IngestResult<(String, usize, Vec<UnmappedProposalV1>)> {
     let mut builder = SchemaInstanceBuilder::new(
         PromotionDomainV1::SchemaEvolution.candidate_module_name(),
         "OntologyMeta",
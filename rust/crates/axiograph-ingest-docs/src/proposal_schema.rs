@@ -0,0 +1,444 @@
+//! Strict validation and published JSON Schema for `proposals.json`.
+//!
+//! `serde` deserialization of [`ProposalsFileV1`] is lenient: unknown fields
+//! are ignored and values like `confidence: 7` or an empty `entity_id` are
+//! accepted. Files produced by other teams' tools go through
+//! [`validate_proposals_json`] / [`validate_proposals_ndjson`] instead, which
+//! report **every** problem with a JSON path (`proposals[3].confidence`), so
+//! the producer can fix them in one pass.
+//!
+//! The JSON Schema ([`proposals_file_json_schema`]) is generated from the Rust
+//! types; the copy checked in at `docs/reference/schemas/proposals_v1.schema.json` is
+//! kept in sync by a test.
+//!
+//! NDJSON input is one [`ProposalV1`] object per line (blank lines ignored),
+//! which lets producers stream proposals without building one large document.
+//!
+//! [`screen_proposals_json`] / [`screen_proposals_ndjson`] run the same checks
+//! per record instead of per batch: valid proposals are kept, and each invalid
+//! one comes back as a [`RejectedProposal`] (raw JSON + issues) for the caller
+//! to quarantine. Only document-level problems fail the whole input.
+//! [`screen_proposal_line`] screens a single NDJSON line.
+
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::{ProposalV1, ProposalsFileV1, PROPOSALS_VERSION_V1};
+
+/// One validation problem, located by a JSON path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProposalIssue {
+    /// `proposals[3].confidence`, `line 12: evidence[0].chunk_id`, or `.` for
+    /// the document root.
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ProposalIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// A proposal that failed strict validation, with its input as written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RejectedProposal {
+    /// `proposals[3]` or `line 12`.
+    pub path: String,
+    /// The record as it appeared in the input (a JSON string if the line did
+    /// not parse as JSON).
+    pub raw: Value,
+    pub issues: Vec<ProposalIssue>,
+}
+
+/// Per-record outcome of screening a batch.
+#[derive(Debug, Clone, Default)]
+pub struct ScreenedProposals {
+    pub accepted: Vec<ProposalV1>,
+    pub rejected: Vec<RejectedProposal>,
+}
+
+fn issue(path: impl Into<String>, message: impl Into<String>) -> ProposalIssue {
+    ProposalIssue {
+        path: path.into(),
+        message: message.into(),
+    }
+}
+
+/// JSON Schema (draft-07) for a whole `proposals.json` file.
+pub fn proposals_file_json_schema() -> Value {
+    serde_json::to_value(schemars::schema_for!(ProposalsFileV1)).expect("JSON Schema serializes")
+}
+
+/// JSON Schema (draft-07) for a single proposal (one NDJSON line).
+pub fn proposal_json_schema() -> Value {
+    serde_json::to_value(schemars::schema_for!(ProposalV1)).expect("JSON Schema serializes")
+}
+
+/// `.ndjson` / `.jsonl` files hold one proposal per line.
+pub fn is_ndjson_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("ndjson") || e.eq_ignore_ascii_case("jsonl"))
+}
+
+/// Strictly parse and validate a `proposals.json` document.
+pub fn validate_proposals_json(text: &str) -> Result<ProposalsFileV1, Vec<ProposalIssue>> {
+    let raw: Value = serde_json::from_str(text).map_err(|e| vec![issue(".", e.to_string())])?;
+    let file: ProposalsFileV1 = deserialize_at(&raw, "")?;
+
+    let mut issues = Vec::new();
+    unknown_fields(
+        &raw,
+        &serde_json::to_value(&file).unwrap_or_default(),
+        "",
+        &mut issues,
+    );
+    if file.version != PROPOSALS_VERSION_V1 {
+        issues.push(issue(
+            "version",
+            format!(
+                "unsupported version {} (expected {PROPOSALS_VERSION_V1})",
+                file.version
+            ),
+        ));
+    }
+    let mut seen = HashSet::new();
+    for (i, proposal) in file.proposals.iter().enumerate() {
+        let path = format!("proposals[{i}]");
+        check_proposal(proposal, &path, &mut issues);
+        check_duplicate(proposal, &path, &mut seen, &mut issues);
+    }
+
+    if issues.is_empty() {
+        Ok(file)
+    } else {
+        Err(issues)
+    }
+}
+
+/// Strictly parse and validate NDJSON proposals (one [`ProposalV1`] per line).
+pub fn validate_proposals_ndjson(
+    reader: impl BufRead,
+) -> Result<Vec<ProposalV1>, Vec<ProposalIssue>> {
+    let mut proposals = Vec::new();
+    let mut issues = Vec::new();
+    let mut seen = HashSet::new();
+    for item in NdjsonProposals::new(reader) {
+        match item {
+            Ok((line, proposal)) => {
+                check_duplicate(&proposal, &format!("line {line}"), &mut seen, &mut issues);
+                proposals.push(proposal);
+            }
+            Err(mut errs) => issues.append(&mut errs),
+        }
+    }
+    if issues.is_empty() {
+        Ok(proposals)
+    } else {
+        Err(issues)
+    }
+}
+
+/// Screen a `proposals.json` document record by record.
+///
+/// Returns the file with only the valid proposals, plus the rejected ones.
+/// Fails as a whole only if the document itself is unusable (not JSON, no
+/// `proposals` array, or a bad header/version).
+pub fn screen_proposals_json(
+    text: &str,
+) -> Result<(ProposalsFileV1, Vec<RejectedProposal>), Vec<ProposalIssue>> {
+    let mut raw: Value = serde_json::from_str(text).map_err(|e| vec![issue(".", e.to_string())])?;
+    let records = match raw.get_mut("proposals").map(Value::take) {
+        Some(Value::Array(records)) => records,
+        Some(_) => return Err(vec![issue("proposals", "expected an array")]),
+        None => return Err(vec![issue(".", "missing field `proposals`")]),
+    };
+    raw["proposals"] = Value::Array(Vec::new());
+    let mut file: ProposalsFileV1 = deserialize_at(&raw, "")?;
+    let mut issues = Vec::new();
+    unknown_fields(
+        &raw,
+        &serde_json::to_value(&file).unwrap_or_default(),
+        "",
+        &mut issues,
+    );
+    if file.version != PROPOSALS_VERSION_V1 {
+        issues.push(issue(
+            "version",
+            format!(
+                "unsupported version {} (expected {PROPOSALS_VERSION_V1})",
+                file.version
+            ),
+        ));
+    }
+    if !issues.is_empty() {
+        return Err(issues);
+    }
+
+    let mut screened = ScreenedProposals::default();
+    let mut seen = HashSet::new();
+    for (i, record) in records.into_iter().enumerate() {
+        screen_record(record, format!("proposals[{i}]"), &mut seen, &mut screened);
+    }
+    file.proposals = screened.accepted;
+    Ok((file, screened.rejected))
+}
+
+/// Screen NDJSON proposals line by line (see [`screen_proposals_json`]).
+pub fn screen_proposals_ndjson(
+    reader: impl BufRead,
+) -> Result<ScreenedProposals, Vec<ProposalIssue>> {
+    let mut screened = ScreenedProposals::default();
+    let mut seen = HashSet::new();
+    for (i, text) in reader.lines().enumerate() {
+        let path = format!("line {}", i + 1);
+        let text = text.map_err(|e| vec![issue(&path, e.to_string())])?;
+        if text.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&text) {
+            Ok(record) => screen_record(record, path, &mut seen, &mut screened),
+            Err(e) => screened.rejected.push(RejectedProposal {
+                issues: vec![issue(&path, e.to_string())],
+                path,
+                raw: Value::String(text),
+            }),
+        }
+    }
+    Ok(screened)
+}
+
+/// Screen one NDJSON line (`line` is 1-based), for callers that read the
+/// stream themselves. Duplicate ids across lines are not checked.
+pub fn screen_proposal_line(line: usize, text: &str) -> Result<ProposalV1, RejectedProposal> {
+    let path = format!("line {line}");
+    let record: Value = match serde_json::from_str(text) {
+        Ok(record) => record,
+        Err(e) => {
+            return Err(RejectedProposal {
+                issues: vec![issue(&path, e.to_string())],
+                path,
+                raw: Value::String(text.to_string()),
+            })
+        }
+    };
+    parse_value(&record, &path).map_err(|issues| RejectedProposal {
+        path,
+        raw: record,
+        issues,
+    })
+}
+
+fn screen_record(
+    record: Value,
+    path: String,
+    seen: &mut HashSet<String>,
+    out: &mut ScreenedProposals,
+) {
+    let issues = match parse_value(&record, &path) {
+        Ok(proposal) => {
+            let mut issues = Vec::new();
+            check_duplicate(&proposal, &path, seen, &mut issues);
+            if issues.is_empty() {
+                out.accepted.push(proposal);
+                return;
+            }
+            issues
+        }
+        Err(issues) => issues,
+    };
+    out.rejected.push(RejectedProposal {
+        path,
+        raw: record,
+        issues,
+    });
+}
+
+/// Streaming NDJSON reader: yields `(line_number, proposal)` one line at a
+/// time, with the same strict checks on each line as
+/// [`validate_proposals_json`] (except cross-line duplicate detection).
+pub struct NdjsonProposals<R> {
+    lines: std::io::Lines<R>,
+    line: usize,
+}
+
+impl<R: BufRead> NdjsonProposals<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            line: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for NdjsonProposals<R> {
+    type Item = Result<(usize, ProposalV1), Vec<ProposalIssue>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let text = match self.lines.next()? {
+                Ok(text) => text,
+                Err(e) => {
+                    return Some(Err(vec![issue(
+                        format!("line {}", self.line + 1),
+                        e.to_string(),
+                    )]))
+                }
+            };
+            self.line += 1;
+            if text.trim().is_empty() {
+                continue;
+            }
+            let prefix = format!("line {}", self.line);
+            return Some(parse_line(&text, &prefix).map(|p| (self.line, p)));
+        }
+    }
+}
+
+fn parse_line(text: &str, prefix: &str) -> Result<ProposalV1, Vec<ProposalIssue>> {
+    let raw: Value = serde_json::from_str(text).map_err(|e| vec![issue(prefix, e.to_string())])?;
+    parse_value(&raw, prefix)
+}
+
+fn parse_value(raw: &Value, prefix: &str) -> Result<ProposalV1, Vec<ProposalIssue>> {
+    let proposal: ProposalV1 = deserialize_at(raw, prefix)?;
+    let mut issues = Vec::new();
+    unknown_fields(
+        raw,
+        &serde_json::to_value(&proposal).unwrap_or_default(),
+        prefix,
+        &mut issues,
+    );
+    check_proposal(&proposal, prefix, &mut issues);
+    if issues.is_empty() {
+        Ok(proposal)
+    } else {
+        Err(issues)
+    }
+}
+
+fn join(prefix: &str, rest: &str) -> String {
+    match (prefix.is_empty(), rest.is_empty() || rest == ".") {
+        (true, true) => ".".to_string(),
+        (true, false) => rest.to_string(),
+        (false, true) => prefix.to_string(),
+        (false, false) if rest.starts_with('[') => format!("{prefix}{rest}"),
+        // NDJSON line prefix: `line 3: evidence[0].chunk_id`.
+        (false, false) if prefix.starts_with("line ") && !prefix.contains(": ") => {
+            format!("{prefix}: {rest}")
+        }
+        (false, false) => format!("{prefix}.{rest}"),
+    }
+}
+
+fn deserialize_at<T: serde::de::DeserializeOwned>(
+    raw: &Value,
+    prefix: &str,
+) -> Result<T, Vec<ProposalIssue>> {
+    serde_path_to_error::deserialize(raw).map_err(|e| {
+        let path = e.path().to_string();
+        vec![issue(join(prefix, &path), e.into_inner().to_string())]
+    })
+}
+
+/// Report keys present in the input but dropped by deserialization. Explicit
+/// `null`s are allowed (they mean "absent" for optional fields).
+fn unknown_fields(raw: &Value, parsed: &Value, path: &str, out: &mut Vec<ProposalIssue>) {
+    match (raw, parsed) {
+        (Value::Object(raw), Value::Object(parsed)) => {
+            for (key, value) in raw {
+                let child = join(path, key);
+                match parsed.get(key) {
+                    Some(p) => unknown_fields(value, p, &child, out),
+                    None if value.is_null() => {}
+                    None => out.push(issue(child, format!("unknown field `{key}`"))),
+                }
+            }
+        }
+        (Value::Array(raw), Value::Array(parsed)) => {
+            for (i, (r, p)) in raw.iter().zip(parsed).enumerate() {
+                unknown_fields(r, p, &join(path, &format!("[{i}]")), out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn check_proposal(proposal: &ProposalV1, path: &str, out: &mut Vec<ProposalIssue>) {
+    let (meta, required): (_, &[(&str, &String)]) = match proposal {
+        ProposalV1::Entity {
+            meta,
+            entity_id,
+            entity_type,
+            name,
+            ..
+        } => (
+            meta,
+            &[
+                ("entity_id", entity_id),
+                ("entity_type", entity_type),
+                ("name", name),
+            ],
+        ),
+        ProposalV1::Relation {
+            meta,
+            relation_id,
+            rel_type,
+            source,
+            target,
+            ..
+        } => (
+            meta,
+            &[
+                ("relation_id", relation_id),
+                ("rel_type", rel_type),
+                ("source", source),
+                ("target", target),
+            ],
+        ),
+    };
+    for (field, value) in required.iter().chain([&("proposal_id", &meta.proposal_id)]) {
+        if value.trim().is_empty() {
+            out.push(issue(join(path, field), "must not be empty"));
+        }
+    }
+    if !(0.0..=1.0).contains(&meta.confidence) {
+        out.push(issue(
+            join(path, "confidence"),
+            format!("{} is outside [0, 1]", meta.confidence),
+        ));
+    }
+    for (i, evidence) in meta.evidence.iter().enumerate() {
+        if evidence.chunk_id.trim().is_empty() {
+            out.push(issue(
+                join(path, &format!("evidence[{i}].chunk_id")),
+                "must not be empty",
+            ));
+        }
+    }
+}
+
+fn check_duplicate(
+    proposal: &ProposalV1,
+    path: &str,
+    seen: &mut HashSet<String>,
+    out: &mut Vec<ProposalIssue>,
+) {
+    let id = &proposal_meta(proposal).proposal_id;
+    if !id.is_empty() && !seen.insert(id.clone()) {
+        out.push(issue(
+            join(path, "proposal_id"),
+            format!("duplicate proposal_id `{id}`"),
+        ));
+    }
+}
+
+fn proposal_meta(proposal: &ProposalV1) -> &crate::ProposalMetaV1 {
+    match proposal {
+        ProposalV1::Entity { meta, .. } | ProposalV1::Relation { meta, .. } => meta,
+    }
+}
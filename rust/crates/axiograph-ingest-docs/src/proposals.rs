@@ -12,13 +12,14 @@
 //! proposal shapes without knowing the domain schema.
 
 use crate::{EvidencePointer, ExtractedFact, FactType, RepoEdgeV1};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
 pub const PROPOSALS_VERSION_V1: u32 = 1;
 
 /// Top-level proposals file.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProposalsFileV1 {
     pub version: u32,
     /// ISO-8601 timestamp (recommended) or unix seconds as string (prototype).
@@ -30,7 +31,7 @@ pub struct ProposalsFileV1 {
     pub proposals: Vec<ProposalV1>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProposalSourceV1 {
     /// e.g. `doc`, `confluence`, `conversation`, `repo`, `ingest_dir`
     pub source_type: String,
@@ -38,9 +39,10 @@ pub struct ProposalSourceV1 {
     pub locator: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProposalMetaV1 {
     pub proposal_id: String,
+    #[schemars(range(min = 0.0, max = 1.0))]
     pub confidence: f64,
     pub evidence: Vec<EvidencePointer>,
     /// Short public rationale, not raw model hidden reasoning.
@@ -52,7 +54,7 @@ pub struct ProposalMetaV1 {
     pub schema_hint: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "kind")]
 pub enum ProposalV1 {
     Entity {
@@ -477,7 +479,7 @@ fn fact_type_to_string(ft: &FactType) -> String {
     .to_string()
 }
 
-fn sanitize_id(s: &str) -> String {
+pub(crate) fn sanitize_id(s: &str) -> String {
     s.chars()
         .map(|c| {
             if c.is_alphanumeric() || c == '_' {
@@ -503,7 +505,7 @@ fn sanitize_key(s: &str) -> String {
         .collect()
 }
 
-fn truncate_for_name(s: &str, max: usize) -> String {
+pub(crate) fn truncate_for_name(s: &str, max: usize) -> String {
     let s = s.trim();
     if s.len() <= max {
         return s.to_string();
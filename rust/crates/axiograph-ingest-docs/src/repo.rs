@@ -12,8 +12,8 @@
 //! - It is designed to be replaced or upgraded (e.g. tree-sitter) without changing the
 //!   downstream artifact shape.
 
+use crate::IngestResult;
 use crate::{extract_markdown, extract_text, Chunk, DocumentExtraction};
-use anyhow::Result;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeSet, HashMap};
@@ -115,7 +115,7 @@ pub struct RepoIndexResult {
 }
 
 /// Index a repository directory into chunks and lightweight structured edges.
-pub fn index_repo(root: &Path, options: &RepoIndexOptions) -> Result<RepoIndexResult> {
+pub fn index_repo(root: &Path, options: &RepoIndexOptions) -> IngestResult<RepoIndexResult> {
     let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
     let root_display = root.to_string_lossy().to_string();
 
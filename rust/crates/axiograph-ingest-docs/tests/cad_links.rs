@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use axiograph_ingest_docs::{
+    link_facts_to_cad_features_v1, mentioned_lengths_mm, CadDimensionV1, CadFeatureV1,
+    CadLinkOptionsV1, ExtractedFact, FactType, ProposalV1,
+};
+
+fn fact(id: &str, statement: &str) -> ExtractedFact {
+    ExtractedFact {
+        fact_id: id.to_string(),
+        domain: "machining".to_string(),
+        statement: statement.to_string(),
+        fact_type: FactType::Observation,
+        confidence: 0.8,
+        source_chunk_id: format!("chunk_{id}"),
+        evidence_span: statement.to_string(),
+        extracted_entities: HashMap::new(),
+    }
+}
+
+fn feature(entity_id: &str, part: &str, kind: &str, diameter: f64) -> CadFeatureV1 {
+    CadFeatureV1 {
+        entity_id: entity_id.to_string(),
+        part_number: part.to_string(),
+        feature_kind: kind.to_string(),
+        dimensions: vec![CadDimensionV1 {
+            name: "diameter".to_string(),
+            value_mm: diameter,
+        }],
+    }
+}
+
+fn links(proposals: &[ProposalV1]) -> Vec<(String, String, f64)> {
+    proposals
+        .iter()
+        .map(|p| match p {
+            ProposalV1::Relation {
+                meta,
+                rel_type,
+                source,
+                target,
+                ..
+            } => {
+                assert_eq!(rel_type, "describes_feature");
+                (source.clone(), target.clone(), meta.confidence)
+            }
+            other => panic!("expected a relation, got {other:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn notes_link_to_features_by_part_number_and_dimension() {
+    let features = vec![
+        feature("F101", "B12", "HoleOrBoss:Cylindrical", 10.0),
+        feature("F102", "B12", "HoleOrBoss:Cylindrical", 6.0),
+        feature("F201", "B13", "HoleOrBoss:Cylindrical", 10.0),
+        feature("F301", "B12", "PocketCandidate:PlanarWithInnerLoop", 40.0),
+    ];
+    let facts = vec![
+        fact("f1", "The 10mm bore on bracket B-12 chatters at full depth"),
+        // Inches and a diameter sign.
+        fact("f2", "Ream the 0.236\" hole on b12 after drilling"),
+        // Kind only: both B-12 holes tie.
+        fact("f3", "Deburr every hole on B-12"),
+        // No part number: never linked.
+        fact("f4", "The 10mm bore chatters"),
+        // Part number but nothing else to go on.
+        fact("f5", "Bracket B-12 ships in batches of 50"),
+    ];
+    let proposals = link_facts_to_cad_features_v1(&facts, &features, &CadLinkOptionsV1::default());
+    let links = links(&proposals);
+
+    let targets = |claim: &str| -> Vec<&str> {
+        links
+            .iter()
+            .filter(|(s, _, _)| s == claim)
+            .map(|(_, t, _)| t.as_str())
+            .collect()
+    };
+    assert_eq!(targets("claim::f1"), vec!["F101"]);
+    assert_eq!(targets("claim::f2"), vec!["F102"]);
+    assert_eq!(targets("claim::f3"), vec!["F101", "F102"]);
+    assert!(targets("claim::f4").is_empty());
+    assert!(targets("claim::f5").is_empty());
+
+    let confidence = |claim: &str| links.iter().find(|(s, _, _)| s == claim).unwrap().2;
+    assert!((confidence("claim::f1") - 0.8 * 0.95).abs() < 1e-9);
+    // Ties share the confidence.
+    assert!((confidence("claim::f3") - 0.8 * 0.6 / 2.0).abs() < 1e-9);
+
+    match &proposals[0] {
+        ProposalV1::Relation {
+            meta, attributes, ..
+        } => {
+            assert_eq!(meta.evidence[0].chunk_id, "chunk_f1");
+            assert_eq!(attributes["part_number"], "B12");
+            assert!(attributes["matched_on"].contains("diameter 10mm"));
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn lengths_are_read_in_millimetres() {
+    assert_eq!(mentioned_lengths_mm("a 10mm bore"), vec![10.0]);
+    assert_eq!(mentioned_lengths_mm("Ø12 through"), vec![12.0]);
+    assert_eq!(mentioned_lengths_mm("1.5 cm deep"), vec![15.0]);
+    assert_eq!(mentioned_lengths_mm("a 0.5 inch slot"), vec![12.7]);
+    assert!(mentioned_lengths_mm("bracket B-12, batch 50").is_empty());
+}
@@ -0,0 +1,137 @@
+use axiograph_ingest_docs::{
+    build_process_plans_v1, extract_knowledge_full, proposals_from_process_plans_v1, Chunk,
+    ExtractedFact, FactType, ProposalV1,
+};
+use std::collections::HashMap;
+
+const NOTES: &str = "\
+Bracket roughing notes. For titanium we always use carbide end mills.
+Finally: deburr all edges and inspect the bore with a pin gauge.
+First: face the stock flat and clamp it low in the vise.
+Then rough the pocket at 120 sfm with flood coolant on.
+Next: drill the bore using 0.004 ipt and peck every 2mm.
+";
+
+#[test]
+fn machining_notes_become_an_ordered_process_plan() {
+    let result = extract_knowledge_full(NOTES, "bracket_notes", "machining");
+    let plans = build_process_plans_v1(&result.extraction.chunks, &result.facts);
+    assert_eq!(plans.len(), 1, "{plans:#?}");
+    let plan = &plans[0];
+
+    let words: Vec<&str> = plan
+        .steps
+        .iter()
+        .map(|s| s.sequence_word.as_str())
+        .collect();
+    assert_eq!(words, ["first", "then", "next", "finally"]);
+    assert!(plan.steps[0].text.starts_with("face the stock"));
+    assert_eq!(
+        plan.parameters.get("material").map(String::as_str),
+        Some("titanium")
+    );
+    assert_eq!(
+        plan.parameters.get("tool_material").map(String::as_str),
+        Some("carbide")
+    );
+    assert_eq!(
+        plan.steps[1].parameters.get("speed").map(String::as_str),
+        Some("120 sfm")
+    );
+    assert_eq!(
+        plan.steps[1].parameters.get("coolant").map(String::as_str),
+        Some("flood coolant")
+    );
+    assert_eq!(
+        plan.steps[2].parameters.get("feed").map(String::as_str),
+        Some("0.004 ipt")
+    );
+    assert!(plan.steps[3].parameters.is_empty());
+
+    let proposals = proposals_from_process_plans_v1(&plans, None, Some("machining".to_string()));
+    let mut order = Vec::new();
+    let mut has_step = 0;
+    for p in &proposals {
+        match p {
+            ProposalV1::Entity {
+                entity_type,
+                attributes,
+                ..
+            } if entity_type == "ProcessPlan" => {
+                assert_eq!(attributes["step_count"], "4");
+                assert_eq!(attributes["material"], "titanium");
+            }
+            ProposalV1::Relation {
+                rel_type,
+                source,
+                target,
+                ..
+            } => match rel_type.as_str() {
+                "process_step_precedes" => order.push((source.clone(), target.clone())),
+                "process_plan_has_step" => has_step += 1,
+                "supported_by_claim" => assert!(target.starts_with("claim::")),
+                other => panic!("unexpected relation {other}"),
+            },
+            _ => {}
+        }
+    }
+    assert_eq!(has_step, 4);
+    assert_eq!(
+        order,
+        (1..4)
+            .map(|i| (
+                format!("process_step::{}::{i}", plan.document_id),
+                format!("process_step::{}::{}", plan.document_id, i + 1)
+            ))
+            .collect::<Vec<_>>()
+    );
+}
+
+fn procedure(id: &str, chunk: &str, statement: &str) -> ExtractedFact {
+    ExtractedFact {
+        fact_id: id.to_string(),
+        domain: "general".to_string(),
+        statement: statement.to_string(),
+        fact_type: FactType::Procedure,
+        confidence: 0.55,
+        source_chunk_id: chunk.to_string(),
+        evidence_span: statement.to_string(),
+        extracted_entities: HashMap::new(),
+    }
+}
+
+fn chunk(id: &str, doc: &str, text: &str) -> Chunk {
+    Chunk {
+        chunk_id: id.to_string(),
+        document_id: doc.to_string(),
+        page: None,
+        span_id: id.to_string(),
+        text: text.to_string(),
+        bbox: None,
+        metadata: HashMap::new(),
+    }
+}
+
+#[test]
+fn numbered_steps_follow_their_numbers_and_single_steps_form_no_plan() {
+    let chunks = vec![
+        chunk(
+            "a_0",
+            "a",
+            "Step 2: finish the walls. Step 1: rough the pocket.",
+        ),
+        chunk("a_1", "a", "Step 3: chamfer the top edge."),
+        chunk("b_0", "b", "First, check the fixture for chips."),
+    ];
+    let facts = vec![
+        procedure("a_1_0", "a_1", "Step 3: chamfer the top edge."),
+        procedure("a_0_1", "a_0", "Step 1: rough the pocket."),
+        procedure("a_0_0", "a_0", "Step 2: finish the walls."),
+        procedure("b_0_0", "b_0", "First, check the fixture for chips."),
+    ];
+    let plans = build_process_plans_v1(&chunks, &facts);
+    assert_eq!(plans.len(), 1);
+    let facts: Vec<&str> = plans[0].steps.iter().map(|s| s.fact_id.as_str()).collect();
+    assert_eq!(facts, ["a_0_1", "a_0_0", "a_1_0"]);
+    assert_eq!(plans[0].plan_id, "process_plan::a");
+}
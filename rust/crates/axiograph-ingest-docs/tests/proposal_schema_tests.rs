@@ -0,0 +1,169 @@
+use axiograph_ingest_docs::{
+    proposal_json_schema, proposals_file_json_schema, screen_proposal_line, screen_proposals_json,
+    screen_proposals_ndjson, validate_proposals_json, validate_proposals_ndjson, ProposalIssue,
+};
+use std::path::PathBuf;
+
+fn schema_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../../docs/reference/schemas")
+}
+
+fn paths(issues: &[ProposalIssue]) -> Vec<&str> {
+    issues.iter().map(|i| i.path.as_str()).collect()
+}
+
+const ENTITY: &str = r#"{"kind":"Entity","proposal_id":"p1","confidence":0.9,"evidence":[],"public_rationale":"","entity_id":"e1","entity_type":"Person","name":"Ada"}"#;
+
+#[test]
+fn valid_file_round_trips() {
+    let text = format!(
+        r#"{{"version":1,"generated_at":"0","source":{{"source_type":"t","locator":"x"}},"proposals":[{ENTITY}]}}"#
+    );
+    let file = validate_proposals_json(&text).unwrap();
+    assert_eq!(file.proposals.len(), 1);
+}
+
+#[test]
+fn reports_every_problem_with_a_path() {
+    let text = r#"{
+      "version": 2,
+      "generated_at": "0",
+      "source": { "source_type": "t", "locator": "x", "extra": 1 },
+      "proposals": [
+        { "kind": "Entity", "proposal_id": "p1", "confidence": 1.5, "evidence": [{"chunk_id": ""}],
+          "public_rationale": "", "entity_id": "", "entity_type": "T", "name": "n", "colour": "red" },
+        { "kind": "Relation", "proposal_id": "p1", "confidence": 0.5, "evidence": [],
+          "public_rationale": "", "relation_id": "r1", "rel_type": "R", "source": "e1", "target": "" }
+      ]
+    }"#;
+    let issues = validate_proposals_json(text).unwrap_err();
+    assert_eq!(
+        paths(&issues),
+        vec![
+            "proposals[0].colour",
+            "source.extra",
+            "version",
+            "proposals[0].entity_id",
+            "proposals[0].confidence",
+            "proposals[0].evidence[0].chunk_id",
+            "proposals[1].target",
+            "proposals[1].proposal_id",
+        ]
+    );
+    assert!(issues[0].message.contains("unknown field `colour`"));
+    assert!(issues[7].message.contains("duplicate proposal_id `p1`"));
+}
+
+#[test]
+fn type_errors_point_at_the_field() {
+    let text = r#"{"version":1,"generated_at":"0","source":{"source_type":"t","locator":"x"},
+      "proposals":[{"kind":"Relation","proposal_id":"p","confidence":"high","evidence":[],
+      "public_rationale":"","relation_id":"r","rel_type":"R","source":"a","target":"b"}]}"#;
+    let issues = validate_proposals_json(text).unwrap_err();
+    assert_eq!(issues.len(), 1);
+    assert!(issues[0].path.starts_with("proposals[0]"), "{}", issues[0]);
+    assert!(issues[0].message.contains("high"), "{}", issues[0]);
+}
+
+#[test]
+fn ndjson_is_validated_line_by_line() {
+    let good = ENTITY;
+    let dup = ENTITY;
+    let bad = ENTITY.replace("\"Ada\"", "\"\"").replace("p1", "p2");
+    let text = format!("{good}\n\n{dup}\n{bad}\nnot json\n");
+    let issues = validate_proposals_ndjson(text.as_bytes()).unwrap_err();
+    assert_eq!(
+        paths(&issues),
+        vec!["line 3: proposal_id", "line 4: name", "line 5"]
+    );
+
+    let ok = validate_proposals_ndjson(format!("{good}\n").as_bytes()).unwrap();
+    assert_eq!(ok.len(), 1);
+}
+
+#[test]
+fn published_schemas_match_the_types() {
+    for (file, schema) in [
+        ("proposals_v1.schema.json", proposals_file_json_schema()),
+        ("proposal_v1.schema.json", proposal_json_schema()),
+    ] {
+        let path = schema_dir().join(file);
+        let published: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            published,
+            schema,
+            "{} is stale; regenerate with `axiograph check proposals-schema`",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn screening_keeps_valid_records_and_rejects_the_rest() {
+    let bad_conf = ENTITY.replace("\"p1\"", "\"p2\"").replace("0.9", "1.5");
+    let text = format!(
+        r#"{{"version":1,"generated_at":"0","source":{{"source_type":"t","locator":"x"}},
+          "proposals":[{ENTITY},{bad_conf},{ENTITY},"junk"]}}"#
+    );
+    let (file, rejected) = screen_proposals_json(&text).unwrap();
+    assert_eq!(file.proposals.len(), 1);
+    assert_eq!(file.source.locator, "x");
+    assert_eq!(
+        rejected.iter().map(|r| r.path.as_str()).collect::<Vec<_>>(),
+        vec!["proposals[1]", "proposals[2]", "proposals[3]"]
+    );
+    assert_eq!(paths(&rejected[0].issues), vec!["proposals[1].confidence"]);
+    assert!(rejected[1].issues[0]
+        .message
+        .contains("duplicate proposal_id"));
+    assert_eq!(rejected[2].raw, serde_json::json!("junk"));
+
+    // Document-level problems still fail the whole input.
+    let issues = screen_proposals_json(
+        r#"{"version":2,"generated_at":"0","source":{"source_type":"t","locator":"x"},"proposals":[]}"#,
+    )
+    .unwrap_err();
+    assert_eq!(paths(&issues), vec!["version"]);
+    assert!(screen_proposals_json(r#"{"version":1}"#).is_err());
+}
+
+#[test]
+fn ndjson_screening_reports_lines() {
+    let text = format!(
+        "{ENTITY}\n\nnot json\n{}\n",
+        ENTITY.replace("\"e1\"", "\"\"")
+    );
+    let screened = screen_proposals_ndjson(text.as_bytes()).unwrap();
+    assert_eq!(screened.accepted.len(), 1);
+    assert_eq!(
+        screened
+            .rejected
+            .iter()
+            .map(|r| r.path.as_str())
+            .collect::<Vec<_>>(),
+        vec!["line 3", "line 4"]
+    );
+    assert_eq!(screened.rejected[0].raw, serde_json::json!("not json"));
+    assert_eq!(
+        paths(&screened.rejected[1].issues),
+        vec!["line 4: entity_id"]
+    );
+}
+
+#[test]
+fn single_line_screening_matches_ndjson_screening() {
+    assert!(matches!(
+        screen_proposal_line(7, ENTITY),
+        Ok(axiograph_ingest_docs::ProposalV1::Entity { entity_id, .. }) if entity_id == "e1"
+    ));
+
+    let rejected = screen_proposal_line(7, &ENTITY.replace("0.9", "1.5")).unwrap_err();
+    assert_eq!(rejected.path, "line 7");
+    assert_eq!(rejected.raw["proposal_id"], "p1");
+    assert_eq!(paths(&rejected.issues), vec!["line 7: confidence"]);
+
+    let rejected = screen_proposal_line(8, "not json").unwrap_err();
+    assert_eq!(rejected.raw, serde_json::json!("not json"));
+    assert_eq!(paths(&rejected.issues), vec!["line 8"]);
+}
@@ -3,8 +3,11 @@
 //! Parses IGES (Initial Graphics Exchange Specification) files
 //! and extracts entity structure for knowledge graphs.
 
-use anyhow::{anyhow, Result};
-use axiograph_dsl as dsl;
+use anyhow::Result;
+use axiograph_dsl::schema_v1::{
+    FieldDeclV1, InstanceAssignmentV1, RelationDeclV1, SchemaV1Instance, SchemaV1Module,
+    SchemaV1Schema, SetItemV1, SetLiteralV1, SubtypeDeclV1,
+};
 use std::collections::HashMap;
 
 /// IGES entity types we care about
@@ -129,64 +132,37 @@ pub fn parse_iges(content: &str) -> Result<IgesFile> {
 }
 
 /// Convert IGES file to Axiograph module
-pub fn iges_to_module(iges: &IgesFile, module_name: &str) -> dsl::Module {
-    let mut module = dsl::Module::new(module_name);
-
-    let schema = dsl::Schema {
+pub fn iges_to_module(iges: &IgesFile, module_name: &str) -> SchemaV1Module {
+    let field = |field: &str, ty: &str| FieldDeclV1 {
+        field: field.to_string(),
+        ty: ty.to_string(),
+    };
+    let subtype = |sub: &str, inclusion: &str| SubtypeDeclV1 {
+        sub: sub.to_string(),
+        sup: "Entity".to_string(),
+        inclusion: Some(inclusion.to_string()),
+    };
+    let schema = SchemaV1Schema {
         name: "IGES".to_string(),
-        objects: vec![
-            "Entity".to_string(),
-            "EntityType".to_string(),
-            "Point".to_string(),
-            "Curve".to_string(),
-            "Surface".to_string(),
-        ],
-        arrows: vec![
-            dsl::ArrowDecl {
-                name: "entityType".to_string(),
-                src: "Entity".to_string(),
-                dst: "EntityType".to_string(),
-            },
-        ],
+        objects: ["Entity", "EntityType", "Point", "Curve", "Surface"]
+            .map(String::from)
+            .to_vec(),
         subtypes: vec![
-            dsl::SubtypeDecl {
-                sub: "Point".to_string(),
-                sup: "Entity".to_string(),
-                incl: "pointIncl".to_string(),
-            },
-            dsl::SubtypeDecl {
-                sub: "Curve".to_string(),
-                sup: "Entity".to_string(),
-                incl: "curveIncl".to_string(),
-            },
-            dsl::SubtypeDecl {
-                sub: "Surface".to_string(),
-                sup: "Entity".to_string(),
-                incl: "surfaceIncl".to_string(),
-            },
+            subtype("Point", "pointIncl"),
+            subtype("Curve", "curveIncl"),
+            subtype("Surface", "surfaceIncl"),
         ],
         relations: vec![
-            dsl::RelationDecl {
+            // Schema v1 has no arrows: `entityType` is a functional relation.
+            RelationDeclV1 {
+                name: "entityType".to_string(),
+                fields: vec![field("entity", "Entity"), field("type", "EntityType")],
+            },
+            RelationDeclV1 {
                 name: "References".to_string(),
-                fields: vec![
-                    dsl::FieldDecl { field: "from".to_string(), ty: "Entity".to_string() },
-                    dsl::FieldDecl { field: "to".to_string(), ty: "Entity".to_string() },
-                ],
-                context: None,
-                temporal: None,
+                fields: vec![field("from", "Entity"), field("to", "Entity")],
             },
         ],
-        equations: vec![],
-    };
-    module.schemas.push(schema);
-
-    // Create instance
-    let mut instance = dsl::Instance {
-        name: "FromIGES".to_string(),
-        schema: "IGES".to_string(),
-        objects: vec![],
-        arrows: vec![],
-        relations: vec![],
     };
 
     // Entity types
@@ -195,27 +171,50 @@ pub fn iges_to_module(iges: &IgesFile, module_name: &str) -> dsl::Module {
         entity_types.insert(format!("Type_{}", ent.type_code));
     }
 
-    instance.objects.push(dsl::ObjElems {
-        obj: "Entity".to_string(),
-        elems: iges.entities.keys().map(|id| format!("E{}", id)).collect(),
-    });
-
-    instance.objects.push(dsl::ObjElems {
-        obj: "EntityType".to_string(),
-        elems: entity_types.into_iter().collect(),
-    });
-
-    // Arrow mappings
-    instance.arrows.push(dsl::ArrowMapEntry {
-        arrow: "entityType".to_string(),
-        pairs: iges
-            .entities
-            .iter()
-            .map(|(id, ent)| (format!("E{}", id), format!("Type_{}", ent.type_code)))
-            .collect(),
-    });
-
-    module.instances.push(instance);
-    module
-}
+    let assignment = |name: &str, items: Vec<SetItemV1>| InstanceAssignmentV1 {
+        name: name.to_string(),
+        value: SetLiteralV1 { items },
+    };
+    let instance = SchemaV1Instance {
+        name: "FromIGES".to_string(),
+        schema: "IGES".to_string(),
+        assignments: vec![
+            assignment(
+                "Entity",
+                iges.entities
+                    .keys()
+                    .map(|id| SetItemV1::Ident {
+                        name: format!("E{}", id),
+                    })
+                    .collect(),
+            ),
+            assignment(
+                "EntityType",
+                entity_types
+                    .into_iter()
+                    .map(|name| SetItemV1::Ident { name })
+                    .collect(),
+            ),
+            assignment(
+                "entityType",
+                iges.entities
+                    .iter()
+                    .map(|(id, ent)| SetItemV1::Tuple {
+                        fields: vec![
+                            ("entity".to_string(), format!("E{}", id)),
+                            ("type".to_string(), format!("Type_{}", ent.type_code)),
+                        ],
+                    })
+                    .collect(),
+            ),
+        ],
+    };
 
+    SchemaV1Module {
+        module_name: module_name.to_string(),
+        imports: vec![],
+        schemas: vec![schema],
+        theories: vec![],
+        instances: vec![instance],
+    }
+}
@@ -25,8 +25,10 @@
 
 #![allow(unused_variables, dead_code)]
 
-use anyhow::{anyhow, Result};
-use axiograph_ingest_docs::{Chunk, EvidencePointer, ProposalMetaV1, ProposalV1};
+use anyhow::Result;
+use axiograph_ingest_docs::{
+    Chunk, EvidencePointer, IngestError, IngestResult, ProposalMetaV1, ProposalV1,
+};
 use serde::Deserialize;
 use serde_json::Value;
 use sha2::{Digest as _, Sha256};
@@ -129,9 +131,10 @@ pub fn ingest_descriptor_set_json(
     text: &str,
     evidence_locator: Option<String>,
     schema_hint: Option<String>,
-) -> Result<ProtoIngestResultV1> {
-    let set: FileDescriptorSetJson = serde_json::from_str(text)
-        .map_err(|e| anyhow!("failed to parse descriptor set JSON: {e}"))?;
+) -> IngestResult<ProtoIngestResultV1> {
+    let _span = tracing::info_span!("ingest.proto", bytes = text.len()).entered();
+    let set: FileDescriptorSetJson =
+        serde_json::from_str(text).map_err(|e| IngestError::parse("descriptor set JSON", e))?;
 
     let mut proposals: Vec<ProposalV1> = Vec::new();
     let mut chunks: Vec<Chunk> = Vec::new();
@@ -13,15 +13,21 @@
 //! - TriG (`.trig`)
 //! - RDF/XML (`.rdf`, `.owl`, `.xml`)
 //!
+//! Cross-vocabulary alignment (`align`) proposes `equivalent_class` /
+//! `equivalent_property` correspondences between two parsed ontologies.
+//!
 //! Roadmap:
 //! - Add SHACL-like validation as a certificate-checked ingestion gate.
 //! - Add named-graph / provenance exports (PROV-inspired) as a boundary layer.
 
+pub mod align;
 pub mod owl;
 
 use anyhow::{anyhow, Result};
 use axiograph_dsl::digest::fnv1a64_digest_bytes;
-use axiograph_ingest_docs::{EvidencePointer, ProposalMetaV1, ProposalV1};
+use axiograph_ingest_docs::{
+    EvidencePointer, IngestError, IngestResult, ProposalMetaV1, ProposalV1,
+};
 use sophia::api::prelude::*;
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
@@ -287,7 +293,7 @@ fn push_attr_value(attrs: &mut HashMap<String, String>, key: String, value: Stri
 fn parse_rdf_statements_from_bytes_v1(
     bytes: &[u8],
     format: RdfFormatV1,
-) -> Result<Vec<RdfStatement>> {
+) -> IngestResult<Vec<RdfStatement>> {
     let cursor = std::io::Cursor::new(bytes);
     let reader = std::io::BufReader::new(cursor);
 
@@ -316,7 +322,7 @@ fn parse_rdf_statements_from_bytes_v1(
                     });
                     Ok(())
                 })
-                .map_err(|e| anyhow!("failed to parse N-Triples: {e}"))?;
+                .map_err(|e| IngestError::parse("N-Triples", e))?;
             Ok(out)
         }
         RdfFormatV1::Turtle => {
@@ -343,7 +349,7 @@ fn parse_rdf_statements_from_bytes_v1(
                     });
                     Ok(())
                 })
-                .map_err(|e| anyhow!("failed to parse Turtle: {e}"))?;
+                .map_err(|e| IngestError::parse("Turtle", e))?;
             Ok(out)
         }
         RdfFormatV1::NQuads => {
@@ -377,7 +383,7 @@ fn parse_rdf_statements_from_bytes_v1(
                     });
                     Ok(())
                 })
-                .map_err(|e| anyhow!("failed to parse N-Quads: {e}"))?;
+                .map_err(|e| IngestError::parse("N-Quads", e))?;
             Ok(out)
         }
         RdfFormatV1::TriG => {
@@ -411,7 +417,7 @@ fn parse_rdf_statements_from_bytes_v1(
                     });
                     Ok(())
                 })
-                .map_err(|e| anyhow!("failed to parse TriG: {e}"))?;
+                .map_err(|e| IngestError::parse("TriG", e))?;
             Ok(out)
         }
         RdfFormatV1::RdfXml => {
@@ -438,7 +444,7 @@ fn parse_rdf_statements_from_bytes_v1(
                     });
                     Ok(())
                 })
-                .map_err(|e| anyhow!("failed to parse RDF/XML: {e}"))?;
+                .map_err(|e| IngestError::parse("RDF/XML", e))?;
             Ok(out)
         }
     }
@@ -454,7 +460,7 @@ pub fn proposals_from_ntriples_v1(
     text: &str,
     evidence_locator: Option<String>,
     schema_hint: Option<String>,
-) -> Result<Vec<ProposalV1>> {
+) -> IngestResult<Vec<ProposalV1>> {
     proposals_from_rdf_v1(
         text.as_bytes(),
         RdfFormatV1::NTriples,
@@ -467,7 +473,7 @@ pub fn proposals_from_rdf_file_v1(
     path: &Path,
     evidence_locator: Option<String>,
     schema_hint: Option<String>,
-) -> Result<Vec<ProposalV1>> {
+) -> IngestResult<Vec<ProposalV1>> {
     let bytes = std::fs::read(path)?;
     let ext = path
         .extension()
@@ -480,7 +486,12 @@ pub fn proposals_from_rdf_file_v1(
         "nq" | "nquads" => RdfFormatV1::NQuads,
         "trig" => RdfFormatV1::TriG,
         "rdf" | "owl" | "xml" => RdfFormatV1::RdfXml,
-        other => return Err(anyhow!("unsupported RDF format: .{other}")),
+        other => {
+            return Err(IngestError::UnsupportedFormat {
+                kind: "RDF",
+                format: format!(".{other}"),
+            })
+        }
     };
 
     proposals_from_rdf_v1(&bytes, format, evidence_locator, schema_hint)
@@ -491,11 +502,19 @@ pub fn proposals_from_rdf_v1(
     format: RdfFormatV1,
     evidence_locator: Option<String>,
     schema_hint: Option<String>,
-) -> Result<Vec<ProposalV1>> {
+) -> IngestResult<Vec<ProposalV1>> {
     let evidence_locator = evidence_locator.unwrap_or_else(|| "<memory>".to_string());
+    let _span = tracing::info_span!(
+        "ingest.rdf",
+        ?format,
+        bytes = bytes.len(),
+        locator = %evidence_locator
+    )
+    .entered();
     let context_id = rdf_context_id(&evidence_locator);
 
     let statements = parse_rdf_statements_from_bytes_v1(bytes, format)?;
+    tracing::debug!(statements = statements.len(), "parsed RDF statements");
 
     // Collect resources, types, attributes and edges.
     let mut resources: HashSet<RdfNode> = HashSet::new();
@@ -637,14 +656,17 @@ pub fn proposals_from_rdf_v1(
         if let Some(attr_map) = attrs_by_resource.get(node) {
             for (k, vals) in attr_map {
                 for lit in vals {
+                    // Language-tagged literals go under `key@lang` (PathDB
+                    // stores them as language-tagged attributes on import).
+                    let key = match &lit.language {
+                        Some(lang) => format!("{k}@{lang}"),
+                        None => k.clone(),
+                    };
                     let mut v = lit.lexical.clone();
-                    if let Some(lang) = &lit.language {
-                        v.push_str(&format!("@{lang}"));
-                    }
                     if let Some(dt) = &lit.datatype {
                         v.push_str(&format!("^^{dt}"));
                     }
-                    push_attr_value(&mut attributes, k.clone(), v);
+                    push_attr_value(&mut attributes, key, v);
                 }
             }
         }
@@ -832,6 +854,38 @@ ex:a ex:label "Alice"@en .
         )));
     }
 
+    #[test]
+    fn language_tagged_literals_use_lang_keys() {
+        let turtle = r#"
+@prefix ex: <http://example.org/> .
+ex:a ex:label "Alice"@en .
+ex:a ex:label "Alicia"@es .
+ex:a ex:label "alice" .
+"#;
+
+        let proposals = proposals_from_rdf_v1(turtle.as_bytes(), RdfFormatV1::Turtle, None, None)
+            .expect("turtle proposals");
+        let attributes = proposals
+            .iter()
+            .find_map(|p| match p {
+                ProposalV1::Entity {
+                    name, attributes, ..
+                } if name == "a" => Some(attributes),
+                _ => None,
+            })
+            .expect("resource entity");
+
+        assert_eq!(attributes.get("label").map(String::as_str), Some("alice"));
+        assert_eq!(
+            attributes.get("label@en").map(String::as_str),
+            Some("Alice")
+        );
+        assert_eq!(
+            attributes.get("label@es").map(String::as_str),
+            Some("Alicia")
+        );
+    }
+
     #[test]
     fn ingests_local_shacl_fixture() -> Result<()> {
         use std::path::PathBuf;
@@ -879,4 +933,28 @@ ex:a ex:label "Alice"@en .
 
         Ok(())
     }
+
+    #[test]
+    fn ingest_errors_are_structured() {
+        let err =
+            proposals_from_ntriples_v1("<http://example.org/a> <broken", None, None).unwrap_err();
+        assert!(matches!(
+            err,
+            IngestError::Parse {
+                format: "N-Triples",
+                ..
+            }
+        ));
+
+        let dir = std::env::temp_dir().join(format!("axiograph_rdf_err_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("data.csv");
+        std::fs::write(&path, "a,b\n").unwrap();
+        let err = proposals_from_rdf_file_v1(&path, None, None).unwrap_err();
+        assert!(matches!(
+            err,
+            IngestError::UnsupportedFormat { kind: "RDF", ref format } if format == ".csv"
+        ));
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -0,0 +1,60 @@
+//! Robustness properties for the RDF/OWL boundary parsers.
+//!
+//! These parsers read untrusted files; malformed input must surface as an
+//! error (or be skipped), never as a panic.
+
+use axiograph_ingest_rdfowl::owl::OwlParser;
+use axiograph_ingest_rdfowl::{proposals_from_rdf_v1, RdfFormatV1};
+use proptest::prelude::*;
+
+/// Line fragments biased towards N-Triples/Turtle syntax.
+fn rdfish_line() -> impl Strategy<Value = String> {
+    let token = prop_oneof![
+        Just("<http://example.org/a>".to_string()),
+        Just("<".to_string()),
+        Just(">".to_string()),
+        Just("\"".to_string()),
+        Just("\"lit\"@en".to_string()),
+        Just("\"1\"^^<http://www.w3.org/2001/XMLSchema#int>".to_string()),
+        Just("_:b0".to_string()),
+        Just("rdf:type".to_string()),
+        Just("owl:Class".to_string()),
+        Just("rdfs:label".to_string()),
+        Just("@prefix".to_string()),
+        Just(".".to_string()),
+        Just(";".to_string()),
+        "[^\\n]{0,6}",
+    ];
+    prop::collection::vec(token, 0..6).prop_map(|tokens| tokens.join(" "))
+}
+
+fn rdfish_doc() -> impl Strategy<Value = String> {
+    prop::collection::vec(rdfish_line(), 0..8).prop_map(|lines| lines.join("\n"))
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 256,
+        failure_persistence: None,
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn owl_ntriples_parser_never_panics(doc in rdfish_doc()) {
+        let _ = OwlParser::new().parse_ntriples(&doc);
+    }
+
+    #[test]
+    fn rdf_proposal_import_never_panics(doc in rdfish_doc()) {
+        for format in [RdfFormatV1::NTriples, RdfFormatV1::Turtle, RdfFormatV1::NQuads] {
+            let _ = proposals_from_rdf_v1(doc.as_bytes(), format, None, None);
+        }
+    }
+
+    #[test]
+    fn rdf_import_of_arbitrary_bytes_never_panics(bytes in prop::collection::vec(any::<u8>(), 0..256)) {
+        for format in [RdfFormatV1::NTriples, RdfFormatV1::RdfXml] {
+            let _ = proposals_from_rdf_v1(&bytes, format, None, None);
+        }
+    }
+}
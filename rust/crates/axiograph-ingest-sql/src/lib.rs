@@ -6,7 +6,7 @@
 //! - Unique constraints -> key constraints
 //! - Check constraints -> (heuristic mapping)
 
-use anyhow::Result;
+use axiograph_ingest_docs::{IngestError, IngestResult};
 use sqlparser::ast::*;
 use sqlparser::dialect::GenericDialect;
 use sqlparser::parser::Parser;
@@ -48,9 +48,11 @@ pub struct UniqueKey {
 }
 
 /// Parse SQL DDL and extract schema
-pub fn parse_sql_ddl(sql: &str) -> Result<SqlSchema> {
+pub fn parse_sql_ddl(sql: &str) -> IngestResult<SqlSchema> {
+    let _span = tracing::info_span!("ingest.sql", bytes = sql.len()).entered();
     let dialect = GenericDialect {};
-    let statements = Parser::parse_sql(&dialect, sql)?;
+    let statements =
+        Parser::parse_sql(&dialect, sql).map_err(|e| IngestError::parse("SQL DDL", e))?;
 
     let mut schema = SqlSchema::default();
 
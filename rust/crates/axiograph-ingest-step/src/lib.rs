@@ -8,7 +8,10 @@
 //! **FFI Policy**: This is heavy IO/parsing. Semantics live in Idris.
 
 use anyhow::{anyhow, Result};
-use axiograph_dsl as dsl;
+use axiograph_dsl::schema_v1::{
+    FieldDeclV1, InstanceAssignmentV1, RelationDeclV1, SchemaV1Instance, SchemaV1Module,
+    SchemaV1Schema, SetItemV1, SetLiteralV1,
+};
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
 
@@ -312,206 +315,158 @@ pub fn lift_brep(entities: &HashMap<u32, StepEntity>) -> BrepLift {
 // Convert to Axiograph module
 // ============================================================================
 
+fn relation(name: &str, fields: &[(&str, &str)]) -> RelationDeclV1 {
+    RelationDeclV1 {
+        name: name.to_string(),
+        fields: fields
+            .iter()
+            .map(|(field, ty)| FieldDeclV1 {
+                field: field.to_string(),
+                ty: ty.to_string(),
+            })
+            .collect(),
+    }
+}
+
+/// `name = {a, b, ...}`
+fn idents(name: &str, elems: impl IntoIterator<Item = String>) -> InstanceAssignmentV1 {
+    InstanceAssignmentV1 {
+        name: name.to_string(),
+        value: SetLiteralV1 {
+            items: elems
+                .into_iter()
+                .map(|name| SetItemV1::Ident { name })
+                .collect(),
+        },
+    }
+}
+
+/// `name = {(field=value, ...), ...}`
+fn tuples(
+    name: &str,
+    rows: impl IntoIterator<Item = Vec<(String, String)>>,
+) -> InstanceAssignmentV1 {
+    InstanceAssignmentV1 {
+        name: name.to_string(),
+        value: SetLiteralV1 {
+            items: rows
+                .into_iter()
+                .map(|fields| SetItemV1::Tuple { fields })
+                .collect(),
+        },
+    }
+}
+
 /// Convert STEP file to Axiograph module
-pub fn step_to_module(step_text: &str, module_name: &str) -> Result<dsl::Module> {
+pub fn step_to_module(step_text: &str, module_name: &str) -> Result<SchemaV1Module> {
     let entities = parse_step(step_text)?;
     let brep = lift_brep(&entities);
 
-    let mut module = dsl::Module::new(module_name);
-
     // Create B-Rep schema
-    let schema = dsl::Schema {
+    let schema = SchemaV1Schema {
         name: "BRep".to_string(),
-        objects: vec![
-            "Solid".to_string(),
-            "Shell".to_string(),
-            "Face".to_string(),
-            "Edge".to_string(),
-            "Vertex".to_string(),
-            "FeatureTag".to_string(),
-            "ProductDef".to_string(),
-        ],
-        arrows: vec![],
+        objects: [
+            "Solid",
+            "Shell",
+            "Face",
+            "Edge",
+            "Vertex",
+            "FeatureTag",
+            "ProductDef",
+        ]
+        .map(String::from)
+        .to_vec(),
         subtypes: vec![],
         relations: vec![
-            dsl::RelationDecl {
-                name: "SolidShell".to_string(),
-                fields: vec![
-                    dsl::FieldDecl { field: "solid".to_string(), ty: "Solid".to_string() },
-                    dsl::FieldDecl { field: "shell".to_string(), ty: "Shell".to_string() },
-                ],
-                context: None,
-                temporal: None,
-            },
-            dsl::RelationDecl {
-                name: "ShellFace".to_string(),
-                fields: vec![
-                    dsl::FieldDecl { field: "shell".to_string(), ty: "Shell".to_string() },
-                    dsl::FieldDecl { field: "face".to_string(), ty: "Face".to_string() },
-                ],
-                context: None,
-                temporal: None,
-            },
-            dsl::RelationDecl {
-                name: "FaceEdge".to_string(),
-                fields: vec![
-                    dsl::FieldDecl { field: "face".to_string(), ty: "Face".to_string() },
-                    dsl::FieldDecl { field: "edge".to_string(), ty: "Edge".to_string() },
-                ],
-                context: None,
-                temporal: None,
-            },
-            dsl::RelationDecl {
-                name: "EdgeVertex".to_string(),
-                fields: vec![
-                    dsl::FieldDecl { field: "edge".to_string(), ty: "Edge".to_string() },
-                    dsl::FieldDecl { field: "vStart".to_string(), ty: "Vertex".to_string() },
-                    dsl::FieldDecl { field: "vEnd".to_string(), ty: "Vertex".to_string() },
-                ],
-                context: None,
-                temporal: None,
-            },
-            dsl::RelationDecl {
-                name: "FaceFeature".to_string(),
-                fields: vec![
-                    dsl::FieldDecl { field: "face".to_string(), ty: "Face".to_string() },
-                    dsl::FieldDecl { field: "tag".to_string(), ty: "FeatureTag".to_string() },
-                ],
-                context: None,
-                temporal: None,
-            },
-            dsl::RelationDecl {
-                name: "AssemblyUses".to_string(),
-                fields: vec![
-                    dsl::FieldDecl { field: "parent".to_string(), ty: "ProductDef".to_string() },
-                    dsl::FieldDecl { field: "child".to_string(), ty: "ProductDef".to_string() },
-                ],
-                context: None,
-                temporal: None,
-            },
+            relation("SolidShell", &[("solid", "Solid"), ("shell", "Shell")]),
+            relation("ShellFace", &[("shell", "Shell"), ("face", "Face")]),
+            relation("FaceEdge", &[("face", "Face"), ("edge", "Edge")]),
+            relation(
+                "EdgeVertex",
+                &[("edge", "Edge"), ("vStart", "Vertex"), ("vEnd", "Vertex")],
+            ),
+            relation("FaceFeature", &[("face", "Face"), ("tag", "FeatureTag")]),
+            relation(
+                "AssemblyUses",
+                &[("parent", "ProductDef"), ("child", "ProductDef")],
+            ),
         ],
-        equations: vec![],
     };
-    module.schemas.push(schema);
+
+    let feature_tags: HashSet<String> = brep.features.iter().map(|(t, _)| t.clone()).collect();
+    let field = |name: &str, value: String| (name.to_string(), value);
 
     // Create instance with data
-    let mut instance = dsl::Instance {
+    let instance = SchemaV1Instance {
         name: "FromSTEP".to_string(),
         schema: "BRep".to_string(),
-        objects: vec![],
-        arrows: vec![],
-        relations: vec![],
+        assignments: vec![
+            // Object carriers
+            idents("Solid", brep.solids.iter().map(|id| format!("S{}", id))),
+            idents("Shell", brep.shells.iter().map(|id| format!("Sh{}", id))),
+            idents("Face", brep.faces.iter().map(|id| format!("F{}", id))),
+            idents("Edge", brep.edges.iter().map(|id| format!("E{}", id))),
+            idents("Vertex", brep.vertices.iter().map(|id| format!("V{}", id))),
+            idents("FeatureTag", feature_tags),
+            idents(
+                "ProductDef",
+                brep.product_defs.iter().map(|id| format!("PD{}", id)),
+            ),
+            // Relation tuples
+            tuples(
+                "SolidShell",
+                brep.solid_shell.iter().map(|(s, sh)| {
+                    vec![
+                        field("solid", format!("S{}", s)),
+                        field("shell", format!("Sh{}", sh)),
+                    ]
+                }),
+            ),
+            tuples(
+                "ShellFace",
+                brep.shell_face.iter().map(|(sh, f)| {
+                    vec![
+                        field("shell", format!("Sh{}", sh)),
+                        field("face", format!("F{}", f)),
+                    ]
+                }),
+            ),
+            tuples(
+                "EdgeVertex",
+                brep.edge_vertex.iter().map(|(e, v1, v2)| {
+                    vec![
+                        field("edge", format!("E{}", e)),
+                        field("vStart", format!("V{}", v1)),
+                        field("vEnd", format!("V{}", v2)),
+                    ]
+                }),
+            ),
+            tuples(
+                "FaceFeature",
+                brep.features.iter().map(|(tag, fid)| {
+                    vec![
+                        field("face", format!("F{}", fid)),
+                        field("tag", tag.clone()),
+                    ]
+                }),
+            ),
+            tuples(
+                "AssemblyUses",
+                brep.assembly_uses.iter().map(|(p, c)| {
+                    vec![
+                        field("parent", format!("PD{}", p)),
+                        field("child", format!("PD{}", c)),
+                    ]
+                }),
+            ),
+        ],
     };
 
-    // Populate object carriers
-    instance.objects.push(dsl::ObjElems {
-        obj: "Solid".to_string(),
-        elems: brep.solids.iter().map(|id| format!("S{}", id)).collect(),
-    });
-    instance.objects.push(dsl::ObjElems {
-        obj: "Shell".to_string(),
-        elems: brep.shells.iter().map(|id| format!("Sh{}", id)).collect(),
-    });
-    instance.objects.push(dsl::ObjElems {
-        obj: "Face".to_string(),
-        elems: brep.faces.iter().map(|id| format!("F{}", id)).collect(),
-    });
-    instance.objects.push(dsl::ObjElems {
-        obj: "Edge".to_string(),
-        elems: brep.edges.iter().map(|id| format!("E{}", id)).collect(),
-    });
-    instance.objects.push(dsl::ObjElems {
-        obj: "Vertex".to_string(),
-        elems: brep.vertices.iter().map(|id| format!("V{}", id)).collect(),
-    });
-
-    // Feature tags
-    let feature_tags: HashSet<String> = brep.features.iter().map(|(t, _)| t.clone()).collect();
-    instance.objects.push(dsl::ObjElems {
-        obj: "FeatureTag".to_string(),
-        elems: feature_tags.into_iter().collect(),
-    });
-
-    // Product definitions
-    instance.objects.push(dsl::ObjElems {
-        obj: "ProductDef".to_string(),
-        elems: brep.product_defs.iter().map(|id| format!("PD{}", id)).collect(),
-    });
-
-    // Relation tuples
-    instance.relations.push(dsl::RelInstanceEntry {
-        rel: "SolidShell".to_string(),
-        tuples: brep
-            .solid_shell
-            .iter()
-            .map(|(s, sh)| dsl::RelTuple {
-                fields: vec![
-                    ("solid".to_string(), format!("S{}", s)),
-                    ("shell".to_string(), format!("Sh{}", sh)),
-                ],
-            })
-            .collect(),
-    });
-
-    instance.relations.push(dsl::RelInstanceEntry {
-        rel: "ShellFace".to_string(),
-        tuples: brep
-            .shell_face
-            .iter()
-            .map(|(sh, f)| dsl::RelTuple {
-                fields: vec![
-                    ("shell".to_string(), format!("Sh{}", sh)),
-                    ("face".to_string(), format!("F{}", f)),
-                ],
-            })
-            .collect(),
-    });
-
-    instance.relations.push(dsl::RelInstanceEntry {
-        rel: "EdgeVertex".to_string(),
-        tuples: brep
-            .edge_vertex
-            .iter()
-            .map(|(e, v1, v2)| dsl::RelTuple {
-                fields: vec![
-                    ("edge".to_string(), format!("E{}", e)),
-                    ("vStart".to_string(), format!("V{}", v1)),
-                    ("vEnd".to_string(), format!("V{}", v2)),
-                ],
-            })
-            .collect(),
-    });
-
-    instance.relations.push(dsl::RelInstanceEntry {
-        rel: "FaceFeature".to_string(),
-        tuples: brep
-            .features
-            .iter()
-            .map(|(tag, fid)| dsl::RelTuple {
-                fields: vec![
-                    ("face".to_string(), format!("F{}", fid)),
-                    ("tag".to_string(), tag.clone()),
-                ],
-            })
-            .collect(),
-    });
-
-    instance.relations.push(dsl::RelInstanceEntry {
-        rel: "AssemblyUses".to_string(),
-        tuples: brep
-            .assembly_uses
-            .iter()
-            .map(|(p, c)| dsl::RelTuple {
-                fields: vec![
-                    ("parent".to_string(), format!("PD{}", p)),
-                    ("child".to_string(), format!("PD{}", c)),
-                ],
-            })
-            .collect(),
-    });
-
-    module.instances.push(instance);
-
-    Ok(module)
+    Ok(SchemaV1Module {
+        module_name: module_name.to_string(),
+        imports: vec![],
+        schemas: vec![schema],
+        theories: vec![],
+        instances: vec![instance],
+    })
 }
-
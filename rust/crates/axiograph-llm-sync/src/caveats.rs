@@ -0,0 +1,231 @@
+//! Caveats for grounded answers.
+//!
+//! A grounded response is only as trustworthy as the facts it cites. Before
+//! a response is returned, [`CaveatScanner`] checks every cited fact for open
+//! conflicts (recorded in the sync state, or pending extracted facts marked
+//! `Conflicting`) and for guardrail violations, and attaches a structured
+//! [`Caveat`] for each, so callers can flag disputed claims next to the
+//! answer instead of presenting them as settled.
+//!
+//! A fact counts as cited when the response mentions it as `[id]`, `[#id]`,
+//! `[fact:id]` or by one of its citation strings (`PathDB:Entity:3`). A
+//! response with no inline citations is treated as citing every fact in its
+//! context.
+
+use axiograph_pathdb::guardrails::{CheckContext, GuardrailEngine, Severity};
+use axiograph_pathdb::PathDB;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Conflict, ConflictType, ExtractedFact, FactId, FactStatus, GroundedFact, GroundingContext,
+    StructuredFact,
+};
+
+/// Why a cited fact needs a caveat.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CaveatKind {
+    /// An unresolved conflict involving the fact.
+    Conflict { conflict_type: ConflictType },
+    /// A pending extracted fact about the same entity is marked `Conflicting`.
+    PendingConflict { fact_id: FactId },
+    /// A guardrail rule is violated by the fact's entity.
+    Guardrail { rule_id: String },
+}
+
+/// A structured caveat on one cited fact.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Caveat {
+    /// Id of the cited fact (PathDB entity).
+    pub fact_id: u32,
+    pub kind: CaveatKind,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// A generated response together with the facts it cites and their caveats.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnotatedResponse {
+    pub text: String,
+    pub cited: Vec<u32>,
+    /// Most severe first.
+    pub caveats: Vec<Caveat>,
+}
+
+impl AnnotatedResponse {
+    pub fn has_caveats(&self) -> bool {
+        !self.caveats.is_empty()
+    }
+
+    /// The response text followed by a `Caveats:` list (if any).
+    pub fn render(&self) -> String {
+        let mut out = self.text.clone();
+        if self.has_caveats() {
+            out.push_str("\n\nCaveats:");
+            for caveat in &self.caveats {
+                out.push_str(&format!(
+                    "\n- [{}] {:?}: {}",
+                    caveat.fact_id, caveat.severity, caveat.message
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// Scans the facts a response cites for conflict and violation markers.
+pub struct CaveatScanner<'a> {
+    db: &'a PathDB,
+    conflicts: &'a [Conflict],
+    pending: &'a [ExtractedFact],
+    guardrails: Option<(&'a GuardrailEngine, CheckContext)>,
+}
+
+impl<'a> CaveatScanner<'a> {
+    pub fn new(db: &'a PathDB) -> Self {
+        Self {
+            db,
+            conflicts: &[],
+            pending: &[],
+            guardrails: None,
+        }
+    }
+
+    /// Unresolved conflicts (e.g. `SyncState::conflicts`).
+    pub fn conflicts(mut self, conflicts: &'a [Conflict]) -> Self {
+        self.conflicts = conflicts;
+        self
+    }
+
+    /// Facts awaiting review (e.g. `SyncState::pending_facts`).
+    pub fn pending(mut self, pending: &'a [ExtractedFact]) -> Self {
+        self.pending = pending;
+        self
+    }
+
+    /// Check cited entities against `engine`.
+    pub fn guardrails(mut self, engine: &'a GuardrailEngine, context: CheckContext) -> Self {
+        self.guardrails = Some((engine, context));
+        self
+    }
+
+    /// Annotate `text`, generated from `context`, with caveats for the
+    /// facts it cites.
+    pub fn annotate(&self, text: &str, context: &GroundingContext) -> AnnotatedResponse {
+        let cited = cited_facts(text, &context.facts);
+        let mut caveats = Vec::new();
+        for &id in &cited {
+            self.scan(id, &mut caveats);
+        }
+        caveats.sort_by_key(|c| std::cmp::Reverse(c.severity));
+        AnnotatedResponse {
+            text: text.to_string(),
+            cited,
+            caveats,
+        }
+    }
+
+    fn scan(&self, id: u32, caveats: &mut Vec<Caveat>) {
+        for conflict in self.conflicts {
+            if conflict.existing_facts.contains(&id) {
+                caveats.push(Caveat {
+                    fact_id: id,
+                    severity: match conflict.conflict_type {
+                        ConflictType::Contradiction | ConflictType::SchemaViolation => {
+                            Severity::Warning
+                        }
+                        ConflictType::AttributeMismatch | ConflictType::ConfidenceConflict => {
+                            Severity::Advisory
+                        }
+                    },
+                    message: format!(
+                        "{:?} with unresolved claim \"{}\"",
+                        conflict.conflict_type, conflict.new_fact.claim
+                    ),
+                    kind: CaveatKind::Conflict {
+                        conflict_type: conflict.conflict_type.clone(),
+                    },
+                });
+            }
+        }
+
+        let Some(entity) = self.db.get_entity(id) else {
+            return;
+        };
+
+        if let Some(name) = entity.attrs.get("name") {
+            for fact in self.pending {
+                if matches!(fact.status, FactStatus::Conflicting { .. })
+                    && mentions(&fact.structured, name)
+                {
+                    caveats.push(Caveat {
+                        fact_id: id,
+                        kind: CaveatKind::PendingConflict { fact_id: fact.id },
+                        severity: Severity::Warning,
+                        message: format!("disputed by pending claim \"{}\"", fact.claim),
+                    });
+                }
+            }
+        }
+
+        if let Some((engine, check)) = &self.guardrails {
+            for violation in engine.check_entity(self.db, id, &entity.entity_type, check) {
+                caveats.push(Caveat {
+                    fact_id: id,
+                    kind: CaveatKind::Guardrail {
+                        rule_id: violation.rule_id,
+                    },
+                    severity: violation.severity,
+                    message: violation.explanation,
+                });
+            }
+        }
+    }
+}
+
+/// Ids of the facts `text` cites, in context order (all of them if it cites
+/// none explicitly).
+pub fn cited_facts(text: &str, facts: &[GroundedFact]) -> Vec<u32> {
+    let mut ids: Vec<u32> = Vec::new();
+    let mut all: Vec<u32> = Vec::new();
+    for fact in facts {
+        if !all.contains(&fact.id) {
+            all.push(fact.id);
+        }
+        let cited = contains_token(text, &format!("[{}]", fact.id))
+            || contains_token(text, &format!("[#{}]", fact.id))
+            || contains_token(text, &format!("[fact:{}]", fact.id))
+            || fact.citation.iter().any(|c| contains_token(text, c));
+        if cited && !ids.contains(&fact.id) {
+            ids.push(fact.id);
+        }
+    }
+    if ids.is_empty() {
+        all
+    } else {
+        ids
+    }
+}
+
+/// `needle` occurs in `text` not directly followed by an alphanumeric
+/// character (so `PathDB:Entity:1` does not match `PathDB:Entity:12`).
+fn contains_token(text: &str, needle: &str) -> bool {
+    !needle.is_empty()
+        && text.match_indices(needle).any(|(at, _)| {
+            !text[at + needle.len()..]
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphanumeric())
+        })
+}
+
+/// Whether `fact` is about the entity called `name`.
+fn mentions(fact: &StructuredFact, name: &str) -> bool {
+    match fact {
+        StructuredFact::Entity { name: n, .. } => n.eq_ignore_ascii_case(name),
+        StructuredFact::Relation { source, target, .. }
+        | StructuredFact::Retraction { source, target, .. } => {
+            source.eq_ignore_ascii_case(name) || target.eq_ignore_ascii_case(name)
+        }
+        StructuredFact::Constraint { .. } | StructuredFact::TacitKnowledge { .. } => false,
+    }
+}
@@ -0,0 +1,224 @@
+//! Graph digest: a compact, type-level summary of the graph for system prompts.
+//!
+//! Grounding retrieves facts for one question; a model also benefits from
+//! knowing what the graph contains at all. [`GraphDigest`] summarizes the
+//! data plane as a short text block: each entity type with its instance
+//! count, its most frequent outgoing relations (with target types), and a
+//! few exemplar entity names. Types are listed most populous first and the
+//! text stops at [`DigestConfig::max_tokens`] (estimated as 4 chars/token),
+//! noting how many types were left out.
+//!
+//! Building a digest scans every entity and relation, so
+//! [`SyncManager::graph_digest`](crate::SyncManager::graph_digest) caches it
+//! against the storage snapshot version and rebuilds only after a change is
+//! applied or rolled back.
+
+use std::collections::{BTreeMap, HashMap};
+
+use axiograph_pathdb::{PathDB, StrId};
+use serde::{Deserialize, Serialize};
+
+/// Size limits for a [`GraphDigest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DigestConfig {
+    /// Token budget for the rendered text.
+    pub max_tokens: usize,
+    /// Outgoing relations listed per type.
+    pub top_relations: usize,
+    /// Exemplar entities listed per type.
+    pub exemplars: usize,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            max_tokens: 600,
+            top_relations: 3,
+            exemplars: 3,
+        }
+    }
+}
+
+/// An outgoing relation of a type, grouped by target type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelationDigest {
+    pub rel_type: String,
+    pub target_type: String,
+    pub count: usize,
+}
+
+/// One entity type in the digest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeDigest {
+    pub name: String,
+    pub count: usize,
+    /// Most frequent first.
+    pub relations: Vec<RelationDigest>,
+    /// Names (or `#id` when unnamed) of the lowest-id instances.
+    pub exemplars: Vec<String>,
+}
+
+/// A token-bounded summary of the graph.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphDigest {
+    /// Snapshot version the digest was built from.
+    pub snapshot: String,
+    pub config: DigestConfig,
+    pub entity_count: usize,
+    pub relation_count: usize,
+    /// Every data-plane type, most populous first.
+    pub types: Vec<TypeDigest>,
+    /// The rendered digest, within `config.max_tokens`.
+    pub text: String,
+    /// Types that did not fit in `text`.
+    pub omitted_types: usize,
+}
+
+impl GraphDigest {
+    /// Summarize `db` (see the module docs). Meta-plane (`AxiMeta*`) entities
+    /// and their edges are skipped.
+    pub fn build(db: &PathDB, snapshot: impl Into<String>, config: &DigestConfig) -> Self {
+        let type_of = |id: u32| -> Option<StrId> {
+            let t = db.entities.get_type(id)?;
+            (!db.interner.lookup(t)?.starts_with("AxiMeta")).then_some(t)
+        };
+
+        let mut members: HashMap<StrId, Vec<u32>> = HashMap::new();
+        for id in 0..db.entities.len() as u32 {
+            if let Some(t) = type_of(id) {
+                members.entry(t).or_default().push(id);
+            }
+        }
+        let mut edges: HashMap<StrId, BTreeMap<(StrId, StrId), usize>> = HashMap::new();
+        let mut relation_count = 0;
+        for id in 0..db.relations.len() as u32 {
+            let Some(rel) = db.relations.get_relation(id) else {
+                continue;
+            };
+            let (Some(s), Some(t)) = (type_of(rel.source), type_of(rel.target)) else {
+                continue;
+            };
+            relation_count += 1;
+            *edges
+                .entry(s)
+                .or_default()
+                .entry((rel.rel_type, t))
+                .or_default() += 1;
+        }
+
+        let name = |id: StrId| db.interner.lookup(id).unwrap_or_default();
+        let mut types: Vec<TypeDigest> = members
+            .iter()
+            .map(|(&t, ids)| {
+                let mut relations: Vec<RelationDigest> = edges
+                    .get(&t)
+                    .into_iter()
+                    .flatten()
+                    .map(|(&(rel_type, target), &count)| RelationDigest {
+                        rel_type: name(rel_type),
+                        target_type: name(target),
+                        count,
+                    })
+                    .collect();
+                relations.sort_by(|a, b| {
+                    b.count
+                        .cmp(&a.count)
+                        .then_with(|| a.rel_type.cmp(&b.rel_type))
+                        .then_with(|| a.target_type.cmp(&b.target_type))
+                });
+                relations.truncate(config.top_relations);
+                let exemplars = ids
+                    .iter()
+                    .take(config.exemplars)
+                    .map(|&id| {
+                        db.get_entity(id)
+                            .and_then(|e| e.attrs.get("name").cloned())
+                            .unwrap_or_else(|| format!("#{id}"))
+                    })
+                    .collect();
+                TypeDigest {
+                    name: name(t),
+                    count: ids.len(),
+                    relations,
+                    exemplars,
+                }
+            })
+            .collect();
+        types.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+
+        let mut digest = Self {
+            snapshot: snapshot.into(),
+            config: config.clone(),
+            entity_count: types.iter().map(|t| t.count).sum(),
+            relation_count,
+            types,
+            text: String::new(),
+            omitted_types: 0,
+        };
+        digest.render();
+        digest
+    }
+
+    /// Estimated token count of `text`.
+    pub fn estimated_tokens(text: &str) -> usize {
+        text.chars().count().div_ceil(4)
+    }
+
+    fn render(&mut self) {
+        let budget = self.config.max_tokens.saturating_mul(4);
+        let mut text = format!(
+            "Knowledge graph (snapshot {}): {} entities, {} relations, {} types.",
+            self.snapshot,
+            self.entity_count,
+            self.relation_count,
+            self.types.len()
+        );
+        let mut shown = 0;
+        for (i, t) in self.types.iter().enumerate() {
+            let line = format!("\n{}", type_line(t));
+            let left = self.types.len() - i - 1;
+            // Keep room for the "more types" line unless this is the last type.
+            let reserve = if left == 0 {
+                0
+            } else {
+                more_line(left).chars().count()
+            };
+            if text.chars().count() + line.chars().count() + reserve > budget {
+                break;
+            }
+            text.push_str(&line);
+            shown += 1;
+        }
+        self.omitted_types = self.types.len() - shown;
+        if self.omitted_types > 0 {
+            let more = more_line(self.omitted_types);
+            if text.chars().count() + more.chars().count() <= budget {
+                text.push_str(&more);
+            }
+        }
+        if text.chars().count() > budget {
+            text = text.chars().take(budget).collect();
+        }
+        self.text = text;
+    }
+}
+
+fn type_line(t: &TypeDigest) -> String {
+    let mut line = format!("- {} ({})", t.name, t.count);
+    if !t.relations.is_empty() {
+        let rels: Vec<String> = t
+            .relations
+            .iter()
+            .map(|r| format!("{} -> {} ({})", r.rel_type, r.target_type, r.count))
+            .collect();
+        line.push_str(&format!(": {}", rels.join(", ")));
+    }
+    if !t.exemplars.is_empty() {
+        line.push_str(&format!("; e.g. {}", t.exemplars.join(", ")));
+    }
+    line
+}
+
+fn more_line(n: usize) -> String {
+    format!("\n- ... and {n} more types")
+}
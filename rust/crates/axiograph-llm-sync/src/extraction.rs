@@ -70,6 +70,24 @@ impl PatternExtractor {
                     },
                     confidence: 0.75,
                 },
+                // "X doesn't [verb] Y" (retraction of an existing relation)
+                ExtractionPattern {
+                    name: "retraction",
+                    regex: Regex::new(
+                        r"(?i)(\w+)\s+(?:does\s+not|doesn't|no\s+longer)\s+(require|produce|use|contain|include)s?\s+(?:the\s+|an?\s+)?(\w+)",
+                    )
+                    .unwrap(),
+                    extract: |cap| {
+                        Some(StructuredFact::Retraction {
+                            rel_type: format!("{}s", cap[2].to_lowercase()),
+                            source: cap[1].to_string(),
+                            target: cap[3].to_string(),
+                            confidence: None,
+                            reason: cap[0].trim().to_string(),
+                        })
+                    },
+                    confidence: 0.8,
+                },
                 // "always/never/should X when Y"
                 ExtractionPattern {
                     name: "tacit_rule",
@@ -190,6 +208,22 @@ impl PatternExtractor {
             StructuredFact::TacitKnowledge { rule, .. } => {
                 format!("Rule: {}", rule)
             }
+            StructuredFact::Retraction {
+                rel_type,
+                source,
+                target,
+                confidence,
+                ..
+            } => match confidence {
+                Some(c) => format!(
+                    "Doubt: {} {} {} (confidence: {:.0}%)",
+                    source,
+                    rel_type,
+                    target,
+                    c * 100.0
+                ),
+                None => format!("Retract: {} {} {}", source, rel_type, target),
+            },
         }
     }
 }
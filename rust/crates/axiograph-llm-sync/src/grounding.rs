@@ -120,11 +120,7 @@ impl<'a> GroundingEngine<'a> {
     }
 
     fn entity_to_natural(&self, entity: &axiograph_pathdb::EntityView) -> String {
-        let name = entity
-            .attrs
-            .get("name")
-            .map(|s| s.as_str())
-            .unwrap_or("entity");
+        let name = entity.label.as_deref().unwrap_or("entity");
 
         let attrs: Vec<String> = entity
             .attrs
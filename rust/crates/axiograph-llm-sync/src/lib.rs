@@ -51,10 +51,14 @@
 
 #![allow(dead_code)]
 
+pub mod caveats;
+pub mod digest;
 pub mod extraction;
 pub mod format;
 pub mod grounding;
 pub mod llm;
+pub mod mention_linking;
+pub mod nl_query;
 pub mod path_optimized;
 pub mod path_verification;
 pub mod probabilistic;
@@ -62,6 +66,7 @@ pub mod protocol;
 pub mod providers;
 pub mod reconciliation;
 pub mod reconciliation_format;
+pub mod sessions;
 pub mod sync;
 
 use axiograph_pathdb::PathDB;
@@ -72,7 +77,8 @@ use uuid::Uuid;
 
 // Re-export storage for convenience
 pub use axiograph_storage::{
-    Change, ChangeSource, ChangeStatus, StorableFact, StorageConfig, UnifiedStorage,
+    Change, ChangeEvent, ChangeFilter, ChangeSource, ChangeStatus, StorableFact, StorageConfig,
+    SubscriptionId, UnifiedStorage,
 };
 
 // ============================================================================
@@ -154,6 +160,15 @@ pub enum StructuredFact {
         confidence: f32,
         domain: String,
     },
+    /// Retraction of an existing relation ("that's wrong, ...")
+    Retraction {
+        rel_type: String,
+        source: String,
+        target: String,
+        /// Downgraded confidence; `None` retracts the relation outright
+        confidence: Option<f32>,
+        reason: String,
+    },
 }
 
 impl StructuredFact {
@@ -163,6 +178,7 @@ impl StructuredFact {
             StructuredFact::Relation { rel_type, .. } => rel_type.clone(),
             StructuredFact::Constraint { .. } => "Constraint".to_string(),
             StructuredFact::TacitKnowledge { .. } => "TacitKnowledge".to_string(),
+            StructuredFact::Retraction { .. } => "Retraction".to_string(),
         }
     }
 }
@@ -336,6 +352,9 @@ pub struct SyncConfig {
     pub track_provenance: bool,
     /// Enable conflict auto-resolution
     pub auto_resolve_conflicts: bool,
+    /// Apply retractions that delete a fact without human review
+    #[serde(default)]
+    pub auto_apply_retractions: bool,
 }
 
 impl Default for SyncConfig {
@@ -346,6 +365,7 @@ impl Default for SyncConfig {
             human_review_constraints: true,
             track_provenance: true,
             auto_resolve_conflicts: false,
+            auto_apply_retractions: false,
         }
     }
 }
@@ -431,9 +451,14 @@ pub trait LLMInterface: Send + Sync {
 // Re-exports
 // ============================================================================
 
+pub use caveats::{AnnotatedResponse, Caveat, CaveatKind, CaveatScanner};
+pub use digest::{DigestConfig, GraphDigest};
+pub use mention_linking::{LinkMethod, MentionLink, MentionLinker};
+pub use nl_query::{NlAnswer, NlQueryTranslator, QueryCandidate};
 pub use reconciliation::{
     Evidence, EvidenceType, ReconciliationAction, ReconciliationConfig, ReconciliationEngine,
     ReconciliationResult, ResolvedConflict, SourceCredibility, TrackRecord, Weight, WeightedFact,
 };
 pub use reconciliation_format::ReconciliationState;
+pub use sessions::{CommitReport, CrossSessionConflict, SessionStage};
 pub use sync::{SyncEvent, SyncManager, SyncResult, SyncStats};
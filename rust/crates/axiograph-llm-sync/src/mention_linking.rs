@@ -0,0 +1,268 @@
+//! Mention linking: resolve conversational mentions to existing entities.
+//!
+//! During a sync session, extracted facts refer to things the way people
+//! talk about them ("the spindle", "that customer", "acme"). A
+//! [`MentionLinker`] resolves each mention to a PathDB entity and remembers
+//! the answer for the rest of the session, so follow-up facts attach to the
+//! same node instead of minting duplicates.
+//!
+//! Resolution order (first hit wins):
+//!
+//! 1. the session alias table (earlier links and explicit [`MentionLinker::alias`]),
+//! 2. exact `name` match (case-insensitive),
+//! 3. type anaphora — "the spindle" with `Spindle` a type: the most recently
+//!    linked `Spindle` in this session, else the only/newest one,
+//! 4. full-text match on `name` (all tokens),
+//! 5. fuzzy `name` match (edit distance scaled by length).
+//!
+//! Leading determiners ("the", "that", "our", ...) are ignored. Ambiguous
+//! candidates prefer entities already mentioned in the session, then the
+//! newest. Every link is recorded with its method and score.
+
+use std::collections::HashMap;
+
+use axiograph_pathdb::PathDB;
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use crate::{SessionId, StructuredFact};
+
+/// Words dropped from the front of a mention.
+const DETERMINERS: &[&str] = &[
+    "the", "that", "this", "those", "these", "a", "an", "our", "my", "your", "their", "its",
+];
+
+/// How a mention was resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinkMethod {
+    Alias,
+    ExactName,
+    TypeAnaphora,
+    FullText,
+    Fuzzy,
+}
+
+impl LinkMethod {
+    /// Confidence attached to links made this way.
+    pub fn score(self) -> f32 {
+        match self {
+            LinkMethod::Alias | LinkMethod::ExactName => 1.0,
+            LinkMethod::TypeAnaphora => 0.8,
+            LinkMethod::FullText => 0.7,
+            LinkMethod::Fuzzy => 0.6,
+        }
+    }
+}
+
+/// A resolved mention.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MentionLink {
+    pub mention: String,
+    pub entity_id: u32,
+    /// Canonical `name` of the entity (what stored facts should use).
+    pub name: String,
+    pub entity_type: String,
+    pub method: LinkMethod,
+    pub score: f32,
+    /// Conversation turn the mention came from, if known.
+    pub turn: Option<usize>,
+}
+
+/// Per-session alias table and link log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MentionLinker {
+    pub session_id: SessionId,
+    /// Normalized mention → entity id.
+    aliases: HashMap<String, u32>,
+    links: Vec<MentionLink>,
+    /// Entities linked this session, most recent last.
+    recent: Vec<u32>,
+}
+
+impl MentionLinker {
+    pub fn new(session_id: SessionId) -> Self {
+        Self {
+            session_id,
+            aliases: HashMap::new(),
+            links: Vec::new(),
+            recent: Vec::new(),
+        }
+    }
+
+    /// Links made so far, in order.
+    pub fn links(&self) -> &[MentionLink] {
+        &self.links
+    }
+
+    /// The entity `mention` is currently aliased to.
+    pub fn alias_of(&self, mention: &str) -> Option<u32> {
+        self.aliases.get(&normalize(mention)).copied()
+    }
+
+    /// Pin `mention` to `entity_id` for the rest of the session.
+    pub fn alias(&mut self, mention: &str, entity_id: u32) {
+        self.aliases.insert(normalize(mention), entity_id);
+        self.touch(entity_id);
+    }
+
+    /// Resolve `mention`, optionally restricted to `expected_type`, and record
+    /// the link.
+    pub fn resolve(
+        &mut self,
+        db: &PathDB,
+        mention: &str,
+        expected_type: Option<&str>,
+        turn: Option<usize>,
+    ) -> Option<MentionLink> {
+        self.resolve_with(db, mention, expected_type, turn, false)
+    }
+
+    /// `exact_only` stops after the alias table and exact name match.
+    fn resolve_with(
+        &mut self,
+        db: &PathDB,
+        mention: &str,
+        expected_type: Option<&str>,
+        turn: Option<usize>,
+        exact_only: bool,
+    ) -> Option<MentionLink> {
+        let key = normalize(mention);
+        if key.is_empty() {
+            return None;
+        }
+        let type_ok = |id: u32| {
+            expected_type.is_none_or(|t| db.find_by_type(t).is_some_and(|ids| ids.contains(id)))
+        };
+
+        let (entity_id, method) = match self.aliases.get(&key) {
+            Some(&id) if type_ok(id) => (id, LinkMethod::Alias),
+            _ => {
+                let methods: &[LinkMethod] = if exact_only {
+                    &[LinkMethod::ExactName]
+                } else {
+                    &[
+                        LinkMethod::ExactName,
+                        LinkMethod::TypeAnaphora,
+                        LinkMethod::FullText,
+                        LinkMethod::Fuzzy,
+                    ]
+                };
+                let max_dist = (key.chars().count() / 4).clamp(1, 3);
+                methods.iter().find_map(|&method| {
+                    let ids = match method {
+                        LinkMethod::Alias => RoaringBitmap::new(),
+                        LinkMethod::ExactName => db.entities_with_attr_fuzzy("name", &key, 0),
+                        LinkMethod::TypeAnaphora => type_named(db, &key).unwrap_or_default(),
+                        LinkMethod::FullText => db.entities_with_attr_fts("name", &key),
+                        LinkMethod::Fuzzy => db.entities_with_attr_fuzzy("name", &key, max_dist),
+                    };
+                    let ids: RoaringBitmap = ids.iter().filter(|&id| type_ok(id)).collect();
+                    Some((self.pick(&ids)?, method))
+                })?
+            }
+        };
+
+        let entity = db.get_entity(entity_id)?;
+        let link = MentionLink {
+            mention: mention.to_string(),
+            entity_id,
+            name: entity
+                .attrs
+                .get("name")
+                .cloned()
+                .unwrap_or_else(|| entity_id.to_string()),
+            entity_type: entity.entity_type,
+            method,
+            score: method.score(),
+            turn,
+        };
+        self.aliases.insert(key, entity_id);
+        self.touch(entity_id);
+        self.links.push(link.clone());
+        Some(link)
+    }
+
+    /// Rewrite the entity references in `fact` to canonical names of the
+    /// entities they resolve to; returns the links made. Unresolved mentions
+    /// are left alone (they become new entities on integration). An entity
+    /// definition only links to an alias or an exact name match, so a new
+    /// `Spindle3` is not folded into an existing `Spindle1`.
+    pub fn link_fact(
+        &mut self,
+        db: &PathDB,
+        fact: &mut StructuredFact,
+        turn: Option<usize>,
+    ) -> Vec<MentionLink> {
+        let mut links = Vec::new();
+        match fact {
+            StructuredFact::Relation { source, target, .. }
+            | StructuredFact::Retraction { source, target, .. } => {
+                for end in [source, target] {
+                    if let Some(link) = self.resolve(db, end, None, turn) {
+                        *end = link.name.clone();
+                        links.push(link);
+                    }
+                }
+            }
+            StructuredFact::Entity {
+                entity_type, name, ..
+            } => {
+                if let Some(link) = self.resolve_with(db, name, Some(entity_type), turn, true) {
+                    *name = link.name.clone();
+                    links.push(link);
+                }
+            }
+            StructuredFact::Constraint { .. } | StructuredFact::TacitKnowledge { .. } => {}
+        }
+        links
+    }
+
+    /// Most recently mentioned candidate, else the newest.
+    fn pick(&self, ids: &RoaringBitmap) -> Option<u32> {
+        self.recent
+            .iter()
+            .rev()
+            .copied()
+            .find(|&id| ids.contains(id))
+            .or_else(|| ids.max())
+    }
+
+    fn touch(&mut self, entity_id: u32) {
+        self.recent.retain(|&id| id != entity_id);
+        self.recent.push(entity_id);
+    }
+}
+
+/// Lowercased mention without leading determiners or surrounding punctuation.
+fn normalize(mention: &str) -> String {
+    let words: Vec<String> = mention
+        .split_whitespace()
+        .map(|w| {
+            w.trim_matches(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .to_lowercase()
+        })
+        .filter(|w| !w.is_empty())
+        .collect();
+    let start = words
+        .iter()
+        .position(|w| !DETERMINERS.contains(&w.as_str()))
+        .unwrap_or(words.len());
+    words[start..].join(" ")
+}
+
+/// Entities of the type a mention names ("machine tool" → `MachineTool`).
+fn type_named(db: &PathDB, key: &str) -> Option<RoaringBitmap> {
+    let pascal: String = key
+        .split_whitespace()
+        .map(|w| {
+            let mut chars = w.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect();
+    db.find_by_type(&pascal)
+        .or_else(|| db.find_by_type(key))
+        .cloned()
+}
@@ -0,0 +1,373 @@
+//! Natural language → AxQL / PathQuery translation with schema grounding.
+//!
+//! Questions are grounded against the [`SchemaContext`] and the graph:
+//!
+//! - entity types mentioned in the question ("which **tools** …"),
+//! - relation types whose name words all occur ("… does op1 **use** as **tool**"
+//!   grounds `usesTool`), in order of mention,
+//! - entities whose `name` matches a word (case-insensitive).
+//!
+//! Each grounding yields a ranked [`QueryCandidate`]: a `PathQuery` plus the
+//! equivalent AxQL text, so the answer can show (and the user can re-run) the
+//! exact query that produced it. [`NlQueryTranslator::answer`] executes the
+//! candidates and keeps the best one that returns results.
+//!
+//! This is deterministic grounding, not a parser: LLM-assisted layers can
+//! propose AxQL directly and use the same response shape.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{GroundedFact, GroundingContext, SchemaContext};
+use axiograph_pathdb::{tokenize_fts_query, PathDB, PathQuery};
+
+/// Relation-name words that carry no meaning on their own (`hasMaterial`).
+const FILLER_WORDS: &[&str] = &["has", "have", "is", "of", "to", "by", "in", "on", "for"];
+
+/// A grounded reading of the question.
+#[derive(Debug, Clone)]
+pub struct QueryCandidate {
+    pub query: PathQuery,
+    /// Equivalent AxQL (`select ?x where … limit N`).
+    pub axql: String,
+    /// Grounding score in `[0, 1]`; higher uses more of the question.
+    pub score: f32,
+    /// What was grounded, e.g. `type Tool; relation usesTool; entity op1 (#0)`.
+    pub rationale: String,
+}
+
+/// The executed translation of a question.
+#[derive(Debug, Clone)]
+pub struct NlAnswer {
+    pub question: String,
+    pub candidates: Vec<QueryCandidate>,
+    /// Index into `candidates` of the query that was answered.
+    pub chosen: Option<usize>,
+    pub results: Vec<u32>,
+    pub facts: Vec<GroundedFact>,
+}
+
+impl NlAnswer {
+    /// AxQL of the chosen query, for display and re-use.
+    pub fn query_text(&self) -> Option<&str> {
+        self.chosen.map(|i| self.candidates[i].axql.as_str())
+    }
+
+    /// Grounding context for generation: the result facts, with the chosen
+    /// query first among the suggested queries.
+    pub fn grounding_context(&self, schema: &SchemaContext) -> GroundingContext {
+        GroundingContext {
+            facts: self.facts.clone(),
+            schema_context: Some(schema.clone()),
+            active_guardrails: vec![],
+            suggested_queries: self.query_text().map(str::to_string).into_iter().collect(),
+        }
+    }
+
+    /// Human-readable answer that shows the query it came from.
+    pub fn render(&self) -> String {
+        let Some(query) = self.query_text() else {
+            return format!("No query could be grounded for: {}", self.question);
+        };
+        let mut out = format!("Query: {query}\nResults ({}):", self.results.len());
+        for fact in &self.facts {
+            out.push_str("\n- ");
+            out.push_str(&fact.natural);
+        }
+        out
+    }
+}
+
+/// A mention grounded in the question, with the word position it starts at.
+#[derive(Debug, Clone)]
+struct Grounded {
+    position: usize,
+    name: String,
+}
+
+/// Translates questions into queries grounded in a schema and a graph.
+pub struct NlQueryTranslator<'a> {
+    pathdb: &'a PathDB,
+    schema: &'a SchemaContext,
+    limit: usize,
+    max_candidates: usize,
+}
+
+impl<'a> NlQueryTranslator<'a> {
+    pub fn new(pathdb: &'a PathDB, schema: &'a SchemaContext) -> Self {
+        Self {
+            pathdb,
+            schema,
+            limit: 20,
+            max_candidates: 5,
+        }
+    }
+
+    /// Maximum number of results returned (and the AxQL `limit`).
+    pub fn limit(mut self, n: usize) -> Self {
+        self.limit = n;
+        self
+    }
+
+    pub fn max_candidates(mut self, n: usize) -> Self {
+        self.max_candidates = n;
+        self
+    }
+
+    /// Candidate queries, best first.
+    pub fn translate(&self, question: &str) -> Vec<QueryCandidate> {
+        let words = tokenize_fts_query(question);
+        let stems: Vec<String> = words.iter().map(|w| stem(w)).collect();
+        let types = self.ground_types(&stems);
+        let relations = self.ground_relations(&stems);
+        let entities = self.ground_entities(question);
+
+        let mut candidates = Vec::new();
+        if let Some(&start_id) = entities.first() {
+            let mut paths: Vec<Vec<&Grounded>> = Vec::new();
+            if relations.len() > 1 {
+                paths.push(relations.iter().take(3).collect());
+            }
+            paths.extend(relations.iter().map(|r| vec![r]));
+            for path in paths {
+                let path_names: Vec<String> = path.iter().map(|r| r.name.clone()).collect();
+                let hop = match path_names.as_slice() {
+                    [rel] => PathQuery::SelectRelated(start_id, rel.clone()),
+                    _ => PathQuery::FollowPath {
+                        start: start_id,
+                        path: path_names.clone(),
+                    },
+                };
+                let mut rationale = vec![format!("entity {}", self.label(start_id))];
+                rationale.extend(path_names.iter().map(|r| format!("relation {r}")));
+                // The last hop's target type narrows the answer.
+                let typed = types.iter().find(|t| {
+                    self.pathdb
+                        .find_by_type(&t.name)
+                        .is_some_and(|ids| !(ids & &self.pathdb.execute(&hop)).is_empty())
+                });
+                let mut used = 1 + path_names.len();
+                let query = match typed {
+                    Some(t) => {
+                        used += 1;
+                        rationale.insert(0, format!("type {}", t.name));
+                        PathQuery::Join(
+                            Box::new(PathQuery::SelectByType(t.name.clone())),
+                            Box::new(hop),
+                        )
+                    }
+                    None => hop,
+                };
+                let mentioned = 1 + relations.len() + types.len();
+                candidates.push(self.candidate(query, used as f32 / mentioned as f32, rationale));
+            }
+        }
+        for t in &types {
+            let score = 0.5 / (1 + relations.len() + entities.len()) as f32;
+            candidates.push(self.candidate(
+                PathQuery::SelectByType(t.name.clone()),
+                score,
+                vec![format!("type {}", t.name)],
+            ));
+        }
+
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        candidates.truncate(self.max_candidates);
+        candidates
+    }
+
+    /// Translate, execute, and keep the best candidate with results (or the
+    /// best candidate, if none has any).
+    pub fn answer(&self, question: &str) -> NlAnswer {
+        let candidates = self.translate(question);
+        let mut chosen = None;
+        let mut results = Vec::new();
+        for (i, candidate) in candidates.iter().enumerate() {
+            let found: Vec<u32> = self
+                .pathdb
+                .execute(&candidate.query)
+                .iter()
+                .take(self.limit)
+                .collect();
+            if chosen.is_none() || (results.is_empty() && !found.is_empty()) {
+                chosen = Some(i);
+                results = found;
+            }
+            if !results.is_empty() {
+                break;
+            }
+        }
+
+        let citation = chosen.map(|i| format!("AxQL: {}", candidates[i].axql));
+        let facts = results
+            .iter()
+            .filter_map(|&id| {
+                let entity = self.pathdb.get_entity(id)?;
+                let name = entity.label.as_deref().unwrap_or("entity");
+                Some(GroundedFact {
+                    id,
+                    natural: format!("{name} is a {}", entity.entity_type),
+                    structured: format!("Entity(id={id}, type={})", entity.entity_type),
+                    confidence: 1.0,
+                    citation: citation
+                        .iter()
+                        .cloned()
+                        .chain([format!("PathDB:Entity:{id}")])
+                        .collect(),
+                    related: vec![],
+                })
+            })
+            .collect();
+
+        NlAnswer {
+            question: question.to_string(),
+            candidates,
+            chosen,
+            results,
+            facts,
+        }
+    }
+
+    fn candidate(&self, query: PathQuery, score: f32, rationale: Vec<String>) -> QueryCandidate {
+        let atoms = self.axql_atoms(&query).unwrap_or_default();
+        QueryCandidate {
+            axql: format!("select ?x where {} limit {}", atoms.join(", "), self.limit),
+            query,
+            score: score.clamp(0.0, 1.0),
+            rationale: rationale.join("; "),
+        }
+    }
+
+    /// AxQL atoms binding `?x` to the answers of `query` (for the shapes
+    /// `translate` produces).
+    fn axql_atoms(&self, query: &PathQuery) -> Option<Vec<String>> {
+        match query {
+            PathQuery::SelectByType(t) => Some(vec![format!("?x : {t}")]),
+            PathQuery::SelectRelated(source, rel) => {
+                Some(vec![format!("{} -{rel}-> ?x", self.axql_entity(*source))])
+            }
+            PathQuery::FollowPath { start, path } => Some(vec![format!(
+                "{} -{}-> ?x",
+                self.axql_entity(*start),
+                path.join("/")
+            )]),
+            PathQuery::Join(left, right) => {
+                let mut atoms = self.axql_atoms(left)?;
+                atoms.extend(self.axql_atoms(right)?);
+                Some(atoms)
+            }
+            _ => None,
+        }
+    }
+
+    /// `name("…")` when the entity is named, else its id.
+    fn axql_entity(&self, id: u32) -> String {
+        match self.entity_name(id) {
+            Some(name) => format!("name({name:?})"),
+            None => id.to_string(),
+        }
+    }
+
+    fn entity_name(&self, id: u32) -> Option<String> {
+        self.pathdb.get_entity(id)?.attrs.get("name").cloned()
+    }
+
+    fn label(&self, id: u32) -> String {
+        match self.pathdb.entity_label(id) {
+            Some(label) => format!("{label} (#{id})"),
+            None => format!("#{id}"),
+        }
+    }
+
+    fn ground_types(&self, stems: &[String]) -> Vec<Grounded> {
+        let mut out: Vec<Grounded> = self
+            .schema
+            .entity_types
+            .iter()
+            .filter_map(|t| {
+                let words: Vec<String> = tokenize_fts_query(t).iter().map(|w| stem(w)).collect();
+                Some(Grounded {
+                    position: find_words(stems, &words)?,
+                    name: t.clone(),
+                })
+            })
+            .collect();
+        out.sort_by_key(|g| g.position);
+        out
+    }
+
+    fn ground_relations(&self, stems: &[String]) -> Vec<Grounded> {
+        let mut out: Vec<Grounded> = self
+            .schema
+            .relation_types
+            .iter()
+            .filter_map(|r| {
+                let words: Vec<String> = tokenize_fts_query(r)
+                    .iter()
+                    .filter(|w| !FILLER_WORDS.contains(&w.as_str()))
+                    .map(|w| stem(w))
+                    .collect();
+                Some(Grounded {
+                    position: find_words(stems, &words)?,
+                    name: r.clone(),
+                })
+            })
+            .collect();
+        out.sort_by_key(|g| g.position);
+        out
+    }
+
+    /// Entities named by a word of the question, in order of mention.
+    fn ground_entities(&self, question: &str) -> Vec<u32> {
+        let types: HashSet<String> = self
+            .schema
+            .entity_types
+            .iter()
+            .map(|t| t.to_ascii_lowercase())
+            .collect();
+        let mut found: BTreeMap<u32, usize> = BTreeMap::new();
+        let words = question
+            .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-' || c == '.'))
+            .map(|w| w.trim_matches(|c| c == '.' || c == '-'))
+            .filter(|w| w.len() > 1 && !types.contains(&w.to_ascii_lowercase()));
+        for (position, word) in words.enumerate() {
+            for id in self.pathdb.entities_with_attr_fuzzy("name", word, 0).iter() {
+                // Meta-plane nodes share names with types and relations.
+                let is_data = self
+                    .pathdb
+                    .get_entity(id)
+                    .is_some_and(|e| !e.entity_type.starts_with("AxiMeta"));
+                if is_data {
+                    found.entry(id).or_insert(position);
+                }
+            }
+        }
+        let mut out: Vec<(u32, usize)> = found.into_iter().collect();
+        out.sort_by_key(|&(_, position)| position);
+        out.into_iter().map(|(id, _)| id).collect()
+    }
+}
+
+/// Crude plural/verb stem, so "tools" grounds `Tool` and "uses" grounds `usesTool`.
+fn stem(word: &str) -> String {
+    let word = word.to_ascii_lowercase();
+    if let Some(base) = word.strip_suffix("ies").filter(|b| b.len() > 2) {
+        return format!("{base}y");
+    }
+    match word.strip_suffix('s') {
+        Some(base) if base.len() > 2 && !base.ends_with('s') => base.to_string(),
+        _ => word,
+    }
+}
+
+/// Position of the first of `words` in `stems` if all of them occur.
+fn find_words(stems: &[String], words: &[String]) -> Option<usize> {
+    if words.is_empty() {
+        return None;
+    }
+    words
+        .iter()
+        .map(|w| stems.iter().position(|s| s == w))
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .min()
+}
@@ -105,7 +105,7 @@ Output format (JSON):
 {
   "facts": [
     {
-      "type": "Entity|Relation|Constraint|TacitKnowledge",
+      "type": "Entity|Relation|Constraint|TacitKnowledge|Retraction",
       "content": { ... type-specific fields ... },
       "confidence": 0.0-1.0,
       "source_quote": "relevant text span"
@@ -117,6 +117,10 @@ Entity fields: entity_type, name, attributes
 Relation fields: rel_type, source, target, attributes
 Constraint fields: name, condition, severity
 TacitKnowledge fields: rule, confidence, domain
+Retraction fields: rel_type, source, target, confidence (null to retract outright), reason
+
+When the user corrects a stated fact ("that's wrong, X is actually Y"), emit a
+Retraction for the old relation and a Relation for the corrected one.
 
 Be precise. Only extract what is explicitly stated or strongly implied."#
     }
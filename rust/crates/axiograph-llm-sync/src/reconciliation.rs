@@ -29,6 +29,7 @@
 #![allow(unused_imports)]
 
 use crate::{Conflict, ConflictType, ExtractedFact, FactId, Resolution, StructuredFact};
+use axiograph_pathdb::ConfidenceCombiner;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -56,6 +57,11 @@ impl Weight {
         Weight::new(self.0 * other.0)
     }
 
+    /// Combine weights under an explicit policy
+    pub fn combine_with(&self, other: Weight, combiner: ConfidenceCombiner) -> Weight {
+        Weight::new(combiner.combine(self.0, other.0))
+    }
+
     /// Bayesian update: P(H|E) = P(E|H) * P(H) / P(E)
     pub fn bayesian_update(&self, likelihood: f32, prior_evidence: f32) -> Weight {
         if prior_evidence <= 0.0 {
@@ -283,7 +289,8 @@ impl WeightedFact {
     fn infer_domain(&self) -> String {
         match &self.content {
             StructuredFact::Entity { entity_type, .. } => entity_type.to_lowercase(),
-            StructuredFact::Relation { rel_type, .. } => rel_type.to_lowercase(),
+            StructuredFact::Relation { rel_type, .. }
+            | StructuredFact::Retraction { rel_type, .. } => rel_type.to_lowercase(),
             StructuredFact::Constraint { .. } => "constraints".to_string(),
             StructuredFact::TacitKnowledge { domain, .. } => domain.clone(),
         }
@@ -309,6 +316,9 @@ pub struct ReconciliationConfig {
     pub expert_override: bool,
     /// Domains where expert override applies
     pub expert_domains: Vec<String>,
+    /// How a fact's confidence is combined with its source's credibility
+    #[serde(default)]
+    pub confidence_combiner: ConfidenceCombiner,
 }
 
 impl Default for ReconciliationConfig {
@@ -320,6 +330,7 @@ impl Default for ReconciliationConfig {
             human_review_threshold: 0.7,
             expert_override: true,
             expert_domains: vec!["safety".to_string(), "constraints".to_string()],
+            confidence_combiner: ConfidenceCombiner::Product,
         }
     }
 }
@@ -378,6 +389,7 @@ impl ReconciliationEngine {
                 weight: Weight::new(new_fact.confidence),
                 conflicts_resolved: vec![],
                 requires_review: false,
+                combiner: self.config.confidence_combiner,
             };
         }
 
@@ -483,7 +495,10 @@ impl ReconciliationEngine {
     ) -> ReconciliationResult {
         let source = self.get_source(&new_fact.source.provider.to_string());
         let domain = self.infer_domain(&new_fact.structured);
-        let new_weight = Weight::new(new_fact.confidence).combine(source.credibility_for(&domain));
+        let new_weight = Weight::new(new_fact.confidence).combine_with(
+            source.credibility_for(&domain),
+            self.config.confidence_combiner,
+        );
 
         let mut resolutions = Vec::new();
         let mut requires_review = false;
@@ -535,6 +550,7 @@ impl ReconciliationEngine {
                 weight: new_weight,
                 conflicts_resolved: resolutions,
                 requires_review: true,
+                combiner: self.config.confidence_combiner,
             };
         }
 
@@ -556,6 +572,7 @@ impl ReconciliationEngine {
                 weight: new_weight,
                 conflicts_resolved: resolutions,
                 requires_review: false,
+                combiner: self.config.confidence_combiner,
             }
         } else {
             ReconciliationResult {
@@ -564,6 +581,7 @@ impl ReconciliationEngine {
                 weight: new_weight,
                 conflicts_resolved: resolutions,
                 requires_review: false,
+                combiner: self.config.confidence_combiner,
             }
         }
     }
@@ -653,7 +671,8 @@ impl ReconciliationEngine {
     fn infer_domain(&self, fact: &StructuredFact) -> String {
         match fact {
             StructuredFact::Entity { entity_type, .. } => entity_type.to_lowercase(),
-            StructuredFact::Relation { rel_type, .. } => rel_type.to_lowercase(),
+            StructuredFact::Relation { rel_type, .. }
+            | StructuredFact::Retraction { rel_type, .. } => rel_type.to_lowercase(),
             StructuredFact::Constraint { .. } => "constraints".to_string(),
             StructuredFact::TacitKnowledge { domain, .. } => domain.clone(),
         }
@@ -850,6 +869,9 @@ pub struct ReconciliationResult {
     pub weight: Weight,
     pub conflicts_resolved: Vec<ResolvedConflict>,
     pub requires_review: bool,
+    /// Policy used to combine confidence with source credibility
+    #[serde(default)]
+    pub combiner: ConfidenceCombiner,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
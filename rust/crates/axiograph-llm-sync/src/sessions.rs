@@ -0,0 +1,221 @@
+//! Concurrent sync sessions with merge-on-commit.
+//!
+//! Several assistants can extract facts at the same time without sharing a
+//! `SyncState`: each opens a session with [`SyncManager::open_session`],
+//! stages conversations into it, and commits when done. A session stages into
+//! its own graph — a snapshot of the store taken when it was opened, plus its
+//! own facts — so it reads its own writes and nobody else's uncommitted ones.
+//!
+//! Commit merges the staged facts through a shared
+//! [`ReconciliationEngine`] that holds every fact committed so far. When a
+//! staged fact clashes with one committed by another session, the engine's
+//! policy decides (replace, keep, merge or review) and the clash is reported
+//! as a [`CrossSessionConflict`]. Facts sent to review land in the shared
+//! review queue with `Conflicting` status.
+//!
+//! [`SyncManager::open_session`]: crate::SyncManager::open_session
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axiograph_pathdb::PathDB;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use crate::mention_linking::MentionLinker;
+use crate::reconciliation::{ReconciliationAction, ReconciliationEngine};
+use crate::{
+    ConflictType, ExtractedFact, FactId, FactStatus, LLMProvider, Resolution, SessionId,
+    StructuredFact,
+};
+
+/// An open session: its staging graph and the facts waiting for commit.
+pub struct SessionStage {
+    pub session_id: SessionId,
+    pub provider: LLMProvider,
+    pub opened_at: DateTime<Utc>,
+    /// Validated facts, merged on commit
+    pub staged: Vec<ExtractedFact>,
+    /// Facts that go to review on commit regardless of merging
+    pub needs_review: Vec<ExtractedFact>,
+    pub(crate) graph: Arc<RwLock<PathDB>>,
+    pub(crate) mentions: MentionLinker,
+}
+
+impl SessionStage {
+    pub(crate) fn new(session_id: SessionId, provider: LLMProvider, snapshot: PathDB) -> Self {
+        Self {
+            session_id,
+            provider,
+            opened_at: Utc::now(),
+            staged: Vec::new(),
+            needs_review: Vec::new(),
+            graph: Arc::new(RwLock::new(snapshot)),
+            mentions: MentionLinker::new(session_id),
+        }
+    }
+}
+
+/// A staged fact that clashed with a fact committed by another session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossSessionConflict {
+    pub fact_id: FactId,
+    pub claim: String,
+    pub other_session: SessionId,
+    pub other_fact_id: FactId,
+    pub conflict_type: ConflictType,
+    pub resolution: Resolution,
+}
+
+/// Outcome of committing a session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitReport {
+    pub session_id: SessionId,
+    /// Facts written to storage
+    pub integrated: Vec<FactId>,
+    /// Facts dropped in favour of existing knowledge
+    pub discarded: Vec<FactId>,
+    /// Facts moved to the shared review queue
+    pub pending_review: Vec<FactId>,
+    /// PathDB ids assigned to integrated facts
+    pub pathdb_ids: Vec<u32>,
+    pub conflicts: Vec<CrossSessionConflict>,
+}
+
+/// Facts committed so far, across sessions.
+pub(crate) struct CommitLog {
+    pub(crate) engine: ReconciliationEngine,
+    committed_by: HashMap<FactId, SessionId>,
+}
+
+impl CommitLog {
+    pub(crate) fn new(engine: ReconciliationEngine) -> Self {
+        Self {
+            engine,
+            committed_by: HashMap::new(),
+        }
+    }
+
+    /// Reconcile `facts` from `session_id` against everything committed so
+    /// far. Returns the report plus the facts to integrate and to review.
+    pub(crate) fn merge(
+        &mut self,
+        session_id: SessionId,
+        facts: Vec<ExtractedFact>,
+    ) -> (CommitReport, Vec<ExtractedFact>, Vec<ExtractedFact>) {
+        let mut report = CommitReport {
+            session_id,
+            integrated: Vec::new(),
+            discarded: Vec::new(),
+            pending_review: Vec::new(),
+            pathdb_ids: Vec::new(),
+            conflicts: Vec::new(),
+        };
+        let mut accepted = Vec::new();
+        let mut review = Vec::new();
+
+        for mut fact in facts {
+            let result = self.engine.reconcile(fact.clone());
+            for resolved in &result.conflicts_resolved {
+                match self.committed_by.get(&resolved.existing_fact_id) {
+                    Some(&other) if other != session_id => {
+                        report.conflicts.push(CrossSessionConflict {
+                            fact_id: fact.id,
+                            claim: fact.claim.clone(),
+                            other_session: other,
+                            other_fact_id: resolved.existing_fact_id,
+                            conflict_type: resolved.conflict_type.clone(),
+                            resolution: resolved.resolution.clone(),
+                        });
+                    }
+                    _ => {}
+                }
+            }
+
+            match result.action {
+                ReconciliationAction::Integrated | ReconciliationAction::Merged => {
+                    self.committed_by.insert(fact.id, session_id);
+                    report.integrated.push(fact.id);
+                    accepted.push(fact);
+                }
+                ReconciliationAction::Discarded => report.discarded.push(fact.id),
+                ReconciliationAction::PendingReview => {
+                    fact.status = FactStatus::Conflicting {
+                        conflicts_with: result
+                            .conflicts_resolved
+                            .iter()
+                            .map(|c| c.existing_fact_id)
+                            .collect(),
+                    };
+                    report.pending_review.push(fact.id);
+                    review.push(fact);
+                }
+            }
+        }
+
+        (report, accepted, review)
+    }
+}
+
+/// Apply `fact` to a staging graph. Relation endpoints are matched by `name`
+/// (newest entity wins); relations with an unknown endpoint are skipped.
+pub(crate) fn stage_fact(db: &mut PathDB, fact: &StructuredFact) {
+    let named = |db: &PathDB, name: &str| db.entities_with_attr_fuzzy("name", name, 0).max();
+    match fact {
+        StructuredFact::Entity {
+            entity_type,
+            name,
+            attributes,
+        } => {
+            let mut attrs = vec![("name", name.as_str())];
+            attrs.extend(attributes.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+            db.add_entity(entity_type, attrs);
+        }
+        StructuredFact::Relation {
+            rel_type,
+            source,
+            target,
+            attributes,
+        } => {
+            if let (Some(s), Some(t)) = (named(db, source), named(db, target)) {
+                let attrs = attributes
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+                    .collect();
+                db.add_relation(rel_type, s, t, 1.0, attrs);
+            }
+        }
+        StructuredFact::Retraction {
+            rel_type,
+            source,
+            target,
+            confidence,
+            ..
+        } => {
+            if let (Some(s), Some(t)) = (named(db, source), named(db, target)) {
+                match confidence {
+                    Some(c) => db.set_relation_confidence(s, rel_type, t, *c),
+                    None => db.remove_relation(s, rel_type, t),
+                };
+            }
+        }
+        StructuredFact::TacitKnowledge {
+            rule,
+            confidence,
+            domain,
+        } => {
+            let confidence = confidence.to_string();
+            db.add_entity(
+                "TacitKnowledge",
+                vec![
+                    ("rule", rule.as_str()),
+                    ("domain", domain.as_str()),
+                    ("confidence", confidence.as_str()),
+                ],
+            );
+        }
+        // Constraints are interpreted at query time, not stored as nodes.
+        StructuredFact::Constraint { .. } => {}
+    }
+}
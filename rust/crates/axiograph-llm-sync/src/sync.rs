@@ -9,17 +9,28 @@
 
 #![allow(unused_imports, unused_mut, unused_variables)]
 
+use crate::caveats::{AnnotatedResponse, CaveatScanner};
+use crate::digest::{DigestConfig, GraphDigest};
+use crate::mention_linking::{MentionLink, MentionLinker};
+use crate::nl_query::{NlAnswer, NlQueryTranslator};
+use crate::protocol::PromptTemplates;
+use crate::reconciliation::{ReconciliationConfig, ReconciliationEngine, SourceCredibility};
+use crate::sessions::{stage_fact, CommitLog, CommitReport, SessionStage};
 use crate::{
     Conflict, ConflictResolver, ConflictType, ConversationTurn, ExtractedFact, FactExtractor,
     FactId, FactSource, FactStatus, FactValidator, GroundedFact, GroundingContext,
-    GuardrailContext, LLMProvider, Resolution, SchemaContext, SessionId, StructuredFact,
-    SyncConfig, SyncState, ValidationResult,
+    GuardrailContext, LLMInterface, LLMProvider, Resolution, SchemaContext, SessionId,
+    StructuredFact, SyncConfig, SyncState, ValidationResult,
 };
+use axiograph_pathdb::guardrails::{CheckContext, GuardrailEngine};
 use axiograph_pathdb::PathDB;
-use axiograph_storage::{Change, ChangeSource, StorableFact, UnifiedStorage};
+use axiograph_storage::{
+    Change, ChangeEvent, ChangeFilter, ChangeSource, StorableFact, SubscriptionId, UnifiedStorage,
+};
 use chrono::Utc;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -58,10 +69,29 @@ pub enum SyncEvent {
         to_version: u64,
         facts_removed: usize,
     },
+    /// Facts changed in storage (see [`SyncManager::subscribe_changes`])
+    FactsChanged {
+        /// Changelog position (replay cursor)
+        seq: u64,
+        change_id: Uuid,
+        source: String,
+        count: usize,
+    },
     /// Error during sync
     SyncError { message: String },
 }
 
+impl From<&ChangeEvent> for SyncEvent {
+    fn from(event: &ChangeEvent) -> Self {
+        SyncEvent::FactsChanged {
+            seq: event.seq,
+            change_id: event.change_id,
+            source: event.source_key.clone(),
+            count: event.facts.len(),
+        }
+    }
+}
+
 /// Callback for sync events
 pub type SyncEventHandler = Box<dyn Fn(SyncEvent) + Send + Sync>;
 
@@ -81,6 +111,16 @@ pub struct SyncManager {
     event_handlers: Vec<SyncEventHandler>,
     /// Default LLM provider
     default_provider: LLMProvider,
+    /// Mention → entity links for the current session
+    mentions: Arc<RwLock<MentionLinker>>,
+    /// Guardrails checked against facts cited in grounded answers
+    guardrails: Option<Arc<GuardrailEngine>>,
+    /// Concurrent sessions staging facts for commit
+    sessions: Arc<RwLock<HashMap<SessionId, SessionStage>>>,
+    /// Facts committed by sessions, for cross-session reconciliation
+    commits: Arc<Mutex<CommitLog>>,
+    /// Last graph digest built, reused while the snapshot is unchanged
+    digest: Arc<RwLock<Option<Arc<GraphDigest>>>>,
 }
 
 impl SyncManager {
@@ -90,8 +130,9 @@ impl SyncManager {
         config: SyncConfig,
         default_provider: LLMProvider,
     ) -> Self {
+        let session_id = Uuid::new_v4();
         let state = SyncState {
-            session_id: Uuid::new_v4(),
+            session_id,
             last_sync: Utc::now(),
             pending_facts: Vec::new(),
             recent_integrations: Vec::new(),
@@ -105,14 +146,55 @@ impl SyncManager {
             config,
             event_handlers: Vec::new(),
             default_provider,
+            mentions: Arc::new(RwLock::new(MentionLinker::new(session_id))),
+            guardrails: None,
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+            commits: Arc::new(Mutex::new(CommitLog::new(ReconciliationEngine::new(
+                ReconciliationConfig::default(),
+            )))),
+            digest: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Check facts cited by grounded answers against `engine`.
+    pub fn set_guardrails(&mut self, engine: GuardrailEngine) {
+        self.guardrails = Some(Arc::new(engine));
+    }
+
+    /// Reconcile session commits with `config` (forgets earlier commits).
+    pub fn set_reconciliation_config(&mut self, config: ReconciliationConfig) {
+        self.commits = Arc::new(Mutex::new(CommitLog::new(ReconciliationEngine::new(
+            config,
+        ))));
+    }
+
+    /// Set how much facts from a provider are trusted when sessions merge.
+    /// `source.source_id` is the provider's display form (`anthropic:<model>`).
+    pub fn register_source(&self, source: SourceCredibility) {
+        self.commits.lock().engine.register_source(source);
+    }
+
     /// Add an event handler
     pub fn on_event(&mut self, handler: SyncEventHandler) {
         self.event_handlers.push(handler);
     }
 
+    /// Forward storage changes matching `filter` — from any writer, not just
+    /// this manager — to `handler` as [`SyncEvent::FactsChanged`].
+    pub fn subscribe_changes(
+        &self,
+        filter: ChangeFilter,
+        handler: SyncEventHandler,
+    ) -> SubscriptionId {
+        self.storage.subscribe(
+            filter,
+            move |event: &ChangeEvent| -> axiograph_storage::Result<()> {
+                handler(SyncEvent::from(event));
+                Ok(())
+            },
+        )
+    }
+
     /// Emit an event to all handlers
     fn emit(&self, event: SyncEvent) {
         for handler in &self.event_handlers {
@@ -134,7 +216,20 @@ impl SyncManager {
         let session_id = self.state.read().session_id;
 
         // Step 1: Extract facts
-        let extracted = self.extract_facts(conversation, &provider).await?;
+        let mut extracted = self
+            .extract_facts(conversation, &provider, session_id)
+            .await?;
+
+        // Step 1b: Link mentions ("the spindle") to existing entities
+        {
+            let pathdb = self.storage.pathdb();
+            let db = pathdb.read();
+            let mut mentions = self.mentions.write();
+            for fact in &mut extracted {
+                let turn = fact.source.conversation_turns.first().copied();
+                mentions.link_fact(&db, &mut fact.structured, turn);
+            }
+        }
 
         self.emit(SyncEvent::FactsExtracted {
             session_id,
@@ -204,6 +299,7 @@ impl SyncManager {
         &self,
         conversation: &[ConversationTurn],
         _provider: &LLMProvider,
+        session_id: SessionId,
     ) -> anyhow::Result<Vec<ExtractedFact>> {
         let mut facts = Vec::new();
 
@@ -218,7 +314,7 @@ impl SyncManager {
                     structured,
                     confidence: 0.85, // Would come from LLM
                     source: FactSource {
-                        session_id: self.state.read().session_id,
+                        session_id,
                         provider: _provider.clone(),
                         conversation_turns: vec![idx],
                         extraction_timestamp: Utc::now(),
@@ -258,6 +354,34 @@ impl SyncManager {
             });
         }
 
+        // Pattern: "X doesn't use Y" (retract an existing relation)
+        let retract_re = regex::Regex::new(
+            r"(?i)(\w+)\s+(?:does\s+not|doesn't|no\s+longer)\s+(require|produce|use|contain|include)s?\s+(?:the\s+|an?\s+)?(\w+)",
+        )?;
+        for cap in retract_re.captures_iter(text) {
+            facts.push(StructuredFact::Retraction {
+                rel_type: format!("{}s", cap[2].to_lowercase()),
+                source: cap[1].to_string(),
+                target: cap[3].to_string(),
+                confidence: None,
+                reason: cap[0].trim().to_string(),
+            });
+        }
+
+        // Pattern: "I doubt X uses Y" (downgrade an existing relation)
+        let doubt_re = regex::Regex::new(
+            r"(?i)(?:doubt|not\s+sure)\s+(?:that\s+)?(\w+)\s+(require|produce|use|contain|include)s?\s+(?:the\s+|an?\s+)?(\w+)",
+        )?;
+        for cap in doubt_re.captures_iter(text) {
+            facts.push(StructuredFact::Retraction {
+                rel_type: format!("{}s", cap[2].to_lowercase()),
+                source: cap[1].to_string(),
+                target: cap[3].to_string(),
+                confidence: Some(0.5),
+                reason: cap[0].trim().to_string(),
+            });
+        }
+
         // Pattern: "always/never/should X when Y"
         let rule_re = regex::Regex::new(r"(?i)(always|never|should)\s+(.+?)\s+when\s+(.+)")?;
         for cap in rule_re.captures_iter(text) {
@@ -314,6 +438,22 @@ impl SyncManager {
                     confidence * 100.0
                 )
             }
+            StructuredFact::Retraction {
+                rel_type,
+                source,
+                target,
+                confidence,
+                ..
+            } => match confidence {
+                Some(c) => format!(
+                    "Doubt: {} {} {} (confidence: {:.0}%)",
+                    source,
+                    rel_type,
+                    target,
+                    c * 100.0
+                ),
+                None => format!("Retract: {} {} {}", source, rel_type, target),
+            },
         }
     }
 
@@ -330,6 +470,17 @@ impl SyncManager {
         let db = pathdb.read();
 
         for fact in facts {
+            // Retractions are validated against the fact they target
+            let fact = &match self.check_retraction(fact, &db) {
+                Ok(fact) => fact,
+                Err(reason) => {
+                    let mut rejected = fact.clone();
+                    rejected.status = FactStatus::Rejected { reason };
+                    invalid.push(rejected);
+                    continue;
+                }
+            };
+
             // Check schema validity
             let schema_valid = self.check_schema_validity(&fact.structured, &db);
 
@@ -362,6 +513,23 @@ impl SyncManager {
                 continue;
             }
 
+            // Check if retraction deletes a fact (requires human review)
+            if matches!(
+                fact.structured,
+                StructuredFact::Retraction {
+                    confidence: None,
+                    ..
+                }
+            ) && !self.config.auto_apply_retractions
+            {
+                let mut retraction = fact.clone();
+                retraction.status = FactStatus::NeedsReview {
+                    reason: "Retractions require human review".to_string(),
+                };
+                needs_review.push(retraction);
+                continue;
+            }
+
             // Valid
             let mut validated = fact.clone();
             validated.status = FactStatus::Validated;
@@ -371,6 +539,74 @@ impl SyncManager {
         Ok((valid, invalid, needs_review))
     }
 
+    /// Check a retraction against the relation it targets, rewriting its
+    /// relation type to the stored one ("uses" → `usesTool`) when the match
+    /// is unambiguous. Other facts pass through unchanged.
+    fn check_retraction(&self, fact: &ExtractedFact, db: &PathDB) -> Result<ExtractedFact, String> {
+        let StructuredFact::Retraction {
+            rel_type,
+            source,
+            target,
+            confidence,
+            ..
+        } = &fact.structured
+        else {
+            return Ok(fact.clone());
+        };
+
+        let sources = db.entities_with_attr_fuzzy("name", source, 0);
+        let targets = db.entities_with_attr_fuzzy("name", target, 0);
+        // Stored relation type → highest confidence among matching edges
+        let mut matches: BTreeMap<String, f32> = BTreeMap::new();
+        for s in &sources {
+            for rel in db.relations.outgoing_any(s) {
+                if !targets.contains(rel.target) {
+                    continue;
+                }
+                let Some(name) = db.interner.lookup(rel.rel_type) else {
+                    continue;
+                };
+                if name
+                    .to_ascii_lowercase()
+                    .starts_with(&rel_type.to_ascii_lowercase())
+                {
+                    let best = matches.entry(name).or_insert(rel.confidence);
+                    *best = best.max(rel.confidence);
+                }
+            }
+        }
+        let (stored, current) = match (matches.get(rel_type), matches.len()) {
+            (Some(c), _) => (rel_type.clone(), *c),
+            (None, 1) => matches.into_iter().next().unwrap(),
+            (None, 0) => {
+                return Err(format!(
+                    "No {} relation from {} to {} to retract",
+                    rel_type, source, target
+                ))
+            }
+            (None, _) => {
+                return Err(format!(
+                    "Ambiguous retraction: several relations from {} to {} match {}",
+                    source, target, rel_type
+                ))
+            }
+        };
+        if let Some(c) = confidence {
+            if *c >= current {
+                return Err(format!(
+                    "Retraction confidence {:.2} does not lower current {:.2}",
+                    c, current
+                ));
+            }
+        }
+
+        let mut checked = fact.clone();
+        if let StructuredFact::Retraction { rel_type, .. } = &mut checked.structured {
+            *rel_type = stored;
+        }
+        Ok(checked)
+    }
+
     /// Check if fact matches schema
     fn check_schema_validity(&self, fact: &StructuredFact, _db: &PathDB) -> bool {
         // Simplified - would check against actual schema
@@ -545,6 +781,19 @@ impl SyncManager {
                 domain: domain.clone(),
                 source: "LLM extraction".to_string(),
             }),
+            StructuredFact::Retraction {
+                rel_type,
+                source,
+                target,
+                confidence,
+                reason,
+            } => Some(StorableFact::Retraction {
+                rel_type: rel_type.clone(),
+                source: source.clone(),
+                target: target.clone(),
+                confidence: *confidence,
+                reason: reason.clone(),
+            }),
         }
     }
 
@@ -592,13 +841,7 @@ impl SyncManager {
         }
 
         // Build schema context
-        let schema = self.storage.schema();
-        let schema_module = schema.read();
-        let schema_context = SchemaContext {
-            entity_types: schema_module.entity_types.clone(),
-            relation_types: schema_module.relation_types.clone(),
-            constraints: schema_module.constraints.clone(),
-        };
+        let schema_context = self.schema_context();
 
         // Get applicable guardrails
         let guardrails = self.get_applicable_guardrails(&keywords);
@@ -611,6 +854,84 @@ impl SyncManager {
         })
     }
 
+    /// Generate a grounded response with `llm` and annotate it with caveats
+    /// for cited facts that are disputed or violate a guardrail.
+    pub async fn generate_grounded(
+        &self,
+        llm: &dyn LLMInterface,
+        prompt: &str,
+        max_facts: usize,
+    ) -> anyhow::Result<AnnotatedResponse> {
+        let context = self.build_grounding_context(prompt, max_facts)?;
+        let text = llm.generate_grounded(prompt, &context).await?;
+        Ok(self.annotate_response(&text, &context))
+    }
+
+    /// Scan the facts `text` cites for open conflicts and guardrail
+    /// violations (see [`crate::caveats`]).
+    pub fn annotate_response(&self, text: &str, context: &GroundingContext) -> AnnotatedResponse {
+        let pathdb = self.storage.pathdb();
+        let db = pathdb.read();
+        let state = self.state.read();
+        let mut scanner = CaveatScanner::new(&db)
+            .conflicts(&state.conflicts)
+            .pending(&state.pending_facts);
+        if let Some(engine) = &self.guardrails {
+            scanner = scanner.guardrails(engine, CheckContext::default());
+        }
+        scanner.annotate(text, context)
+    }
+
+    /// Answer a question by translating it into a schema-grounded query (see
+    /// [`crate::nl_query`]) and executing it. The answer carries the AxQL it
+    /// ran, for transparency and re-use.
+    pub fn answer_question(&self, question: &str, limit: usize) -> NlAnswer {
+        let pathdb = self.storage.pathdb();
+        let db = pathdb.read();
+        let schema = self.schema_context();
+        NlQueryTranslator::new(&db, &schema)
+            .limit(limit)
+            .answer(question)
+    }
+
+    /// Entity/relation types and constraints known to the storage schema.
+    pub fn schema_context(&self) -> SchemaContext {
+        let schema = self.storage.schema();
+        let schema_module = schema.read();
+        SchemaContext {
+            entity_types: schema_module.entity_types.clone(),
+            relation_types: schema_module.relation_types.clone(),
+            constraints: schema_module.constraints.clone(),
+        }
+    }
+
+    /// A type-level digest of the graph for system prompts.
+    ///
+    /// Cached against the storage snapshot version: the digest is rebuilt
+    /// only when a change has been applied or rolled back since the last
+    /// call, or when `config` differs.
+    pub fn graph_digest(&self, config: &DigestConfig) -> Arc<GraphDigest> {
+        let snapshot = self.storage.snapshot_version();
+        if let Some(cached) = self.digest.read().as_ref() {
+            if cached.snapshot == snapshot && cached.config == *config {
+                return Arc::clone(cached);
+            }
+        }
+        let pathdb = self.storage.pathdb();
+        let digest = Arc::new(GraphDigest::build(&pathdb.read(), snapshot, config));
+        *self.digest.write() = Some(Arc::clone(&digest));
+        digest
+    }
+
+    /// The grounded-generation system prompt followed by the graph digest.
+    pub fn grounded_system_prompt(&self, config: &DigestConfig) -> String {
+        format!(
+            "{}\n\n{}",
+            PromptTemplates::grounded_generation(),
+            self.graph_digest(config).text
+        )
+    }
+
     /// Extract keywords from query
     fn extract_keywords(&self, query: &str) -> Vec<String> {
         // Simple keyword extraction (would use NLP in production)
@@ -694,6 +1015,52 @@ impl SyncManager {
         Ok(())
     }
 
+    /// Verify a pending fact as `verifier`: store it as their edit, raise its
+    /// confidence by the storage's verification rule, and return it marked
+    /// `human_verified`.
+    pub fn verify_fact(
+        &self,
+        fact_id: FactId,
+        verifier: &str,
+        note: Option<&str>,
+    ) -> anyhow::Result<ExtractedFact> {
+        let mut fact = {
+            let mut state = self.state.write();
+            let idx = state
+                .pending_facts
+                .iter()
+                .position(|f| f.id == fact_id)
+                .ok_or_else(|| anyhow::anyhow!("No pending fact {}", fact_id))?;
+            state.pending_facts.remove(idx)
+        };
+        let storable = self
+            .to_storable(&fact.structured)
+            .ok_or_else(|| anyhow::anyhow!("Fact {} cannot be stored", fact_id))?;
+        let target = storable
+            .target()
+            .ok_or_else(|| anyhow::anyhow!("Fact {} cannot be verified", fact_id))?;
+
+        self.storage.add_facts(
+            vec![storable],
+            ChangeSource::UserEdit {
+                user_id: Some(verifier.to_string()),
+            },
+        )?;
+        let results = self.storage.flush()?;
+        let record = self.storage.verify_fact(target, verifier, note)?;
+
+        fact.confidence = record.to;
+        fact.source.human_verified = true;
+        fact.status = FactStatus::Integrated {
+            entity_ids: results
+                .iter()
+                .flat_map(|r| r.pathdb_ids.iter().chain(&r.duplicates).copied())
+                .collect(),
+        };
+        self.state.write().recent_integrations.push(fact.id);
+        Ok(fact)
+    }
+
     /// Resolve a conflict
     pub fn resolve_conflict(
         &self,
@@ -750,9 +1117,192 @@ impl SyncManager {
     pub fn new_session(&self) -> SessionId {
         let mut state = self.state.write();
         state.session_id = Uuid::new_v4();
+        *self.mentions.write() = MentionLinker::new(state.session_id);
         state.session_id
     }
 
+    // ========================================================================
+    // Conversation Memory
+    // ========================================================================
+
+    /// Resolve a mention against the graph and this session's alias table.
+    pub fn link_mention(&self, mention: &str, expected_type: Option<&str>) -> Option<MentionLink> {
+        let pathdb = self.storage.pathdb();
+        let db = pathdb.read();
+        self.mentions
+            .write()
+            .resolve(&db, mention, expected_type, None)
+    }
+
+    /// Pin `mention` to `entity_id` for the rest of the session.
+    pub fn alias_mention(&self, mention: &str, entity_id: u32) {
+        self.mentions.write().alias(mention, entity_id);
+    }
+
+    /// Mention links recorded in the current session.
+    pub fn mention_links(&self) -> Vec<MentionLink> {
+        self.mentions.read().links().to_vec()
+    }
+
+    // ========================================================================
+    // Concurrent Sessions
+    // ========================================================================
+
+    /// Open an isolated session staged against a snapshot of the store.
+    pub fn open_session(&self, provider: Option<LLMProvider>) -> anyhow::Result<SessionId> {
+        let provider = provider.unwrap_or_else(|| self.default_provider.clone());
+        let snapshot = {
+            let pathdb = self.storage.pathdb();
+            let bytes = pathdb.read().to_bytes()?;
+            PathDB::from_bytes(&bytes)?
+        };
+        let session_id = Uuid::new_v4();
+        self.sessions.write().insert(
+            session_id,
+            SessionStage::new(session_id, provider, snapshot),
+        );
+        Ok(session_id)
+    }
+
+    /// Extract and validate facts from `conversation` into a session's
+    /// staging graph. Returns how many facts were staged.
+    pub async fn stage_conversation(
+        &self,
+        session_id: SessionId,
+        conversation: &[ConversationTurn],
+    ) -> anyhow::Result<usize> {
+        let provider = self
+            .sessions
+            .read()
+            .get(&session_id)
+            .map(|s| s.provider.clone())
+            .ok_or_else(|| anyhow::anyhow!("No open session {}", session_id))?;
+
+        let mut extracted = self
+            .extract_facts(conversation, &provider, session_id)
+            .await?;
+
+        let mut sessions = self.sessions.write();
+        let stage = sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| anyhow::anyhow!("Session {} closed while staging", session_id))?;
+        {
+            let graph = stage.graph.read();
+            for fact in &mut extracted {
+                let turn = fact.source.conversation_turns.first().copied();
+                stage.mentions.link_fact(&graph, &mut fact.structured, turn);
+            }
+        }
+
+        self.emit(SyncEvent::FactsExtracted {
+            session_id,
+            count: extracted.len(),
+            source: format!("{:?}", provider),
+        });
+
+        let (valid, invalid, needs_review) = self.validate_facts(&extracted)?;
+
+        self.emit(SyncEvent::FactsValidated {
+            valid: valid.len(),
+            invalid: invalid.len(),
+            needs_review: needs_review.len(),
+        });
+
+        {
+            let mut graph = stage.graph.write();
+            for fact in &valid {
+                stage_fact(&mut graph, &fact.structured);
+            }
+        }
+        let staged = valid.len();
+        stage.staged.extend(valid);
+        stage.needs_review.extend(needs_review);
+        Ok(staged)
+    }
+
+    /// A session's staging graph: the store as of `open_session` plus the
+    /// session's staged facts.
+    pub fn session_pathdb(&self, session_id: SessionId) -> Option<Arc<RwLock<PathDB>>> {
+        self.sessions
+            .read()
+            .get(&session_id)
+            .map(|s| s.graph.clone())
+    }
+
+    /// Facts staged in a session and not yet committed.
+    pub fn staged_facts(&self, session_id: SessionId) -> Vec<ExtractedFact> {
+        self.sessions
+            .read()
+            .get(&session_id)
+            .map(|s| s.staged.clone())
+            .unwrap_or_default()
+    }
+
+    /// Ids of open sessions.
+    pub fn open_sessions(&self) -> Vec<SessionId> {
+        self.sessions.read().keys().copied().collect()
+    }
+
+    /// Drop a session and everything it staged.
+    pub fn discard_session(&self, session_id: SessionId) -> bool {
+        self.sessions.write().remove(&session_id).is_some()
+    }
+
+    /// Merge a session's staged facts into storage and close it.
+    ///
+    /// Staged facts are reconciled against facts committed by every session
+    /// so far; clashes with other sessions are reported in the result.
+    pub fn commit_session(&self, session_id: SessionId) -> anyhow::Result<CommitReport> {
+        let stage = self
+            .sessions
+            .write()
+            .remove(&session_id)
+            .ok_or_else(|| anyhow::anyhow!("No open session {}", session_id))?;
+
+        let (mut report, accepted, mut review) =
+            self.commits.lock().merge(session_id, stage.staged);
+
+        if !report.conflicts.is_empty() {
+            self.emit(SyncEvent::ConflictsDetected {
+                count: report.conflicts.len(),
+                types: report
+                    .conflicts
+                    .iter()
+                    .map(|c| c.conflict_type.clone())
+                    .collect(),
+            });
+        }
+
+        let integrated = self.integrate_facts(accepted, &stage.provider, session_id)?;
+        report.pathdb_ids = integrated
+            .iter()
+            .flat_map(|f| match &f.status {
+                FactStatus::Integrated { entity_ids } => entity_ids.clone(),
+                _ => Vec::new(),
+            })
+            .collect();
+        report.pathdb_ids.dedup();
+
+        self.emit(SyncEvent::FactsIntegrated {
+            count: integrated.len(),
+            axi_files: vec!["llm_extracted.axi".to_string()],
+            pathdb_ids: report.pathdb_ids.clone(),
+        });
+
+        review.extend(stage.needs_review);
+        {
+            let mut state = self.state.write();
+            state.pending_facts.extend(review);
+            state
+                .recent_integrations
+                .extend(integrated.iter().map(|f| f.id));
+            state.last_sync = Utc::now();
+            state.graph_version += 1;
+        }
+
+        Ok(report)
+    }
+
     /// Get statistics
     pub fn stats(&self) -> SyncStats {
         let state = self.state.read();
@@ -0,0 +1,160 @@
+use axiograph_llm_sync::caveats::cited_facts;
+use axiograph_llm_sync::{
+    CaveatKind, CaveatScanner, Conflict, ConflictType, ExtractedFact, FactSource, FactStatus,
+    GroundedFact, GroundingContext, LLMProvider, Resolution, StructuredFact,
+};
+use axiograph_pathdb::guardrails::{CheckContext, GuardrailEngine, GuardrailRule, Severity};
+use axiograph_pathdb::PathDB;
+use chrono::Utc;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn grounded(id: u32, natural: &str) -> GroundedFact {
+    GroundedFact {
+        id,
+        natural: natural.to_string(),
+        structured: format!("Entity({id})"),
+        confidence: 1.0,
+        citation: vec![format!("PathDB:Entity:{id}")],
+        related: vec![],
+    }
+}
+
+fn context(facts: Vec<GroundedFact>) -> GroundingContext {
+    GroundingContext {
+        facts,
+        schema_context: None,
+        active_guardrails: vec![],
+        suggested_queries: vec![],
+    }
+}
+
+fn extracted(claim: &str, structured: StructuredFact, status: FactStatus) -> ExtractedFact {
+    ExtractedFact {
+        id: Uuid::new_v4(),
+        claim: claim.to_string(),
+        structured,
+        confidence: 0.7,
+        source: FactSource {
+            session_id: Uuid::new_v4(),
+            provider: LLMProvider::Custom {
+                name: "test".to_string(),
+                endpoint: "http://localhost".to_string(),
+            },
+            conversation_turns: vec![0],
+            extraction_timestamp: Utc::now(),
+            human_verified: false,
+        },
+        status,
+    }
+}
+
+#[test]
+fn cited_facts_with_conflicts_and_violations_get_caveats() {
+    let mut db = PathDB::new();
+    let op = db.add_entity("MachiningOperation", vec![("name", "Roughing")]);
+    let ti = db.add_entity("Material", vec![("name", "Titanium")]);
+    let steel = db.add_entity("Material", vec![("name", "Steel")]);
+    db.add_relation("hasMaterial", op, ti, 1.0, vec![]);
+
+    let conflicts = vec![Conflict {
+        new_fact: extracted(
+            "Titanium is not a Material",
+            StructuredFact::Entity {
+                entity_type: "Alloy".to_string(),
+                name: "Titanium".to_string(),
+                attributes: HashMap::new(),
+            },
+            FactStatus::Validated,
+        ),
+        existing_facts: vec![ti],
+        conflict_type: ConflictType::Contradiction,
+        suggested_resolution: Resolution::HumanReview,
+    }];
+    let pending = vec![extracted(
+        "Roughing uses Steel",
+        StructuredFact::Relation {
+            rel_type: "hasMaterial".to_string(),
+            source: "roughing".to_string(),
+            target: "Steel".to_string(),
+            attributes: HashMap::new(),
+        },
+        FactStatus::Conflicting {
+            conflicts_with: vec![Uuid::new_v4()],
+        },
+    )];
+    let engine = GuardrailEngine::new(vec![GuardrailRule {
+        id: "MACH-010".to_string(),
+        name: "Missing tool".to_string(),
+        description: "Operations must name a tool".to_string(),
+        severity: Severity::Critical,
+        domain: "machining".to_string(),
+        applicable_types: vec!["MachiningOperation".to_string()],
+        violation_pattern: None,
+        required_relations: vec!["usesTool".to_string()],
+        forbidden_relations: vec![],
+        min_confidence: 0.8,
+    }]);
+
+    let ctx = context(vec![
+        grounded(op, "Roughing is a MachiningOperation"),
+        grounded(ti, "Titanium is a Material"),
+        grounded(steel, "Steel is a Material"),
+    ]);
+    let text = format!("Roughing [{op}] cuts Titanium (PathDB:Entity:{ti}).");
+    let response = CaveatScanner::new(&db)
+        .conflicts(&conflicts)
+        .pending(&pending)
+        .guardrails(&engine, CheckContext::default())
+        .annotate(&text, &ctx);
+
+    assert_eq!(response.cited, vec![op, ti]);
+    let kinds: Vec<(u32, Severity)> = response
+        .caveats
+        .iter()
+        .map(|c| (c.fact_id, c.severity))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (op, Severity::Critical),
+            (op, Severity::Warning),
+            (ti, Severity::Warning)
+        ]
+    );
+    assert!(
+        matches!(&response.caveats[0].kind, CaveatKind::Guardrail { rule_id } if rule_id == "MACH-010")
+    );
+    assert!(matches!(
+        response.caveats[1].kind,
+        CaveatKind::PendingConflict { .. }
+    ));
+    assert!(matches!(
+        response.caveats[2].kind,
+        CaveatKind::Conflict {
+            conflict_type: ConflictType::Contradiction
+        }
+    ));
+
+    let rendered = response.render();
+    assert!(rendered.starts_with(&text));
+    assert!(rendered.contains(&format!(
+        "\n- [{ti}] Warning: Contradiction with unresolved claim \"Titanium is not a Material\""
+    )));
+
+    // Uncited facts carry no caveats.
+    let clean = CaveatScanner::new(&db)
+        .conflicts(&conflicts)
+        .annotate(&format!("Steel [{steel}] is common."), &ctx);
+    assert!(!clean.has_caveats());
+    assert_eq!(clean.render(), format!("Steel [{steel}] is common."));
+}
+
+#[test]
+fn uncited_responses_cover_the_whole_context() {
+    let ctx = context(vec![grounded(1, "a"), grounded(12, "b"), grounded(1, "a")]);
+    assert_eq!(cited_facts("no citations here", &ctx.facts), vec![1, 12]);
+    assert_eq!(cited_facts("see PathDB:Entity:12", &ctx.facts), vec![12]);
+    assert_eq!(cited_facts("see [#1]", &ctx.facts), vec![1]);
+    assert_eq!(cited_facts("see [fact:12]", &ctx.facts), vec![12]);
+}
@@ -0,0 +1,116 @@
+use axiograph_llm_sync::{DigestConfig, GraphDigest, LLMProvider, SyncConfig, SyncManager};
+use axiograph_pathdb::PathDB;
+use axiograph_storage::{ChangeSource, StorableFact, StorageConfig, UnifiedStorage};
+use std::sync::Arc;
+use tempfile::tempdir;
+
+fn entity(name: &str, entity_type: &str) -> StorableFact {
+    StorableFact::Entity {
+        name: name.to_string(),
+        entity_type: entity_type.to_string(),
+        attributes: vec![],
+    }
+}
+
+#[test]
+fn digest_lists_types_relations_and_exemplars_within_budget() {
+    let mut db = PathDB::new();
+    let people: Vec<u32> = ["alice", "bob", "carol", "dave"]
+        .iter()
+        .map(|n| db.add_entity("Person", vec![("name", n)]))
+        .collect();
+    let acme = db.add_entity("Company", vec![("name", "acme")]);
+    let paris = db.add_entity("City", vec![]);
+    for &p in &people[..3] {
+        db.add_relation("worksAt", p, acme, 1.0, vec![]);
+    }
+    db.add_relation("knows", people[0], people[1], 1.0, vec![]);
+    db.add_relation("locatedIn", acme, paris, 1.0, vec![]);
+    db.build_indexes();
+
+    let config = DigestConfig {
+        exemplars: 2,
+        top_relations: 1,
+        ..Default::default()
+    };
+    let digest = GraphDigest::build(&db, "v1", &config);
+    assert_eq!((digest.entity_count, digest.relation_count), (6, 5));
+    assert_eq!(
+        digest
+            .types
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Person", "City", "Company"]
+    );
+    let person = &digest.types[0];
+    assert_eq!(person.exemplars, vec!["alice", "bob"]);
+    assert_eq!(
+        (
+            person.relations.len(),
+            person.relations[0].rel_type.as_str()
+        ),
+        (1, "worksAt")
+    );
+    assert_eq!(digest.types[1].exemplars, vec![format!("#{paris}")]);
+    assert_eq!(digest.omitted_types, 0);
+    assert!(digest
+        .text
+        .contains("- Person (4): worksAt -> Company (3); e.g. alice, bob"));
+
+    // A tight budget drops the least populous types and says so.
+    let tight = DigestConfig {
+        max_tokens: 40,
+        ..config
+    };
+    let digest = GraphDigest::build(&db, "v1", &tight);
+    assert!(digest.omitted_types > 0);
+    assert!(GraphDigest::estimated_tokens(&digest.text) <= 40);
+    assert!(digest.text.contains("more types"));
+}
+
+#[test]
+fn sync_manager_caches_the_digest_per_snapshot() {
+    let dir = tempdir().unwrap();
+    let storage = Arc::new(
+        UnifiedStorage::new(StorageConfig {
+            axi_dir: dir.path().to_path_buf(),
+            pathdb_path: dir.path().join("test.axpd"),
+            changelog_path: dir.path().join("changelog.json"),
+            watch_files: false,
+            ..Default::default()
+        })
+        .unwrap(),
+    );
+    let sync = SyncManager::new(
+        Arc::clone(&storage),
+        SyncConfig::default(),
+        LLMProvider::Custom {
+            name: "test".to_string(),
+            endpoint: "local".to_string(),
+        },
+    );
+    let source = ChangeSource::UserEdit { user_id: None };
+    storage
+        .add_facts(vec![entity("Titanium", "Material")], source.clone())
+        .unwrap();
+    storage.flush().unwrap();
+
+    let config = DigestConfig::default();
+    let first = sync.graph_digest(&config);
+    assert_eq!(first.snapshot, storage.snapshot_version());
+    assert!(first.text.contains("Material (1)"));
+    assert!(Arc::ptr_eq(&first, &sync.graph_digest(&config)));
+    assert!(sync
+        .grounded_system_prompt(&config)
+        .ends_with(first.text.as_str()));
+
+    // A new snapshot invalidates the cache.
+    storage
+        .add_facts(vec![entity("Steel", "Material")], source)
+        .unwrap();
+    storage.flush().unwrap();
+    let second = sync.graph_digest(&config);
+    assert!(!Arc::ptr_eq(&first, &second));
+    assert!(second.text.contains("Material (2)"));
+}
@@ -33,6 +33,7 @@ fn test_env() -> (Arc<UnifiedStorage>, SyncManager, tempfile::TempDir) {
         human_review_constraints: true,
         track_provenance: true,
         auto_resolve_conflicts: false,
+        auto_apply_retractions: false,
     };
 
     let manager = SyncManager::new(
@@ -394,6 +395,119 @@ async fn test_reject_fact() {
     }
 }
 
+#[tokio::test]
+async fn test_verify_fact_records_reviewer_and_bumps_confidence() {
+    let dir = tempdir().unwrap();
+    let config = StorageConfig {
+        axi_dir: dir.path().to_path_buf(),
+        pathdb_path: dir.path().join("test.axpd"),
+        changelog_path: dir.path().join("changelog.json"),
+        watch_files: false,
+        ..Default::default()
+    };
+    let storage = Arc::new(UnifiedStorage::new(config).unwrap());
+    let sync = SyncManager::new(
+        Arc::clone(&storage),
+        SyncConfig {
+            auto_integrate_threshold: 0.99,
+            ..Default::default()
+        },
+        LLMProvider::Custom {
+            name: "test".to_string(),
+            endpoint: "local".to_string(),
+        },
+    );
+    sync.sync_from_conversation(&machinist_conversation(), None)
+        .await
+        .unwrap();
+
+    let rule = sync
+        .pending_review()
+        .into_iter()
+        .find(|f| matches!(f.structured, StructuredFact::TacitKnowledge { .. }))
+        .expect("the coolant rule waits for review");
+    assert!(!rule.source.human_verified);
+
+    let verified = sync
+        .verify_fact(rule.id, "ana", Some("matches the shop manual"))
+        .unwrap();
+    assert!(verified.source.human_verified);
+    assert!(matches!(verified.status, FactStatus::Integrated { .. }));
+    // Default rule: corroborate with weight 0.9.
+    assert!((verified.confidence - 0.98).abs() < 0.001);
+    assert!(sync.pending_review().iter().all(|f| f.id != rule.id));
+
+    let changelog = storage.changelog();
+    let last = changelog.last().unwrap();
+    assert!(matches!(
+        &last.source,
+        ChangeSource::UserEdit { user_id: Some(id) } if id == "ana"
+    ));
+    assert!(matches!(
+        &last.facts[..],
+        [StorableFact::Verification { verifier, note: Some(note), .. }]
+            if verifier == "ana" && note == "matches the shop manual"
+    ));
+
+    assert!(sync.verify_fact(rule.id, "ana", None).is_err());
+}
+
+#[tokio::test]
+async fn test_retractions_from_conversation() {
+    let (storage, sync, _dir) = test_env();
+    let (roughing, drill, endmill) = {
+        let pathdb = storage.pathdb();
+        let mut db = pathdb.write();
+        let roughing = db.add_entity("Operation", vec![("name", "Roughing")]);
+        let drill = db.add_entity("Tool", vec![("name", "Drill")]);
+        let endmill = db.add_entity("Tool", vec![("name", "Endmill")]);
+        db.add_relation("usesTool", roughing, drill, 0.9, vec![]);
+        db.add_relation("usesTool", roughing, endmill, 0.9, vec![]);
+        (roughing, drill, endmill)
+    };
+
+    let conversation = vec![ConversationTurn {
+        role: Role::User,
+        content: "That's wrong: roughing doesn't use the drill. I doubt roughing uses Endmill \
+                  either, and Roughing doesn't use Saw."
+            .to_string(),
+        timestamp: Utc::now(),
+        metadata: Default::default(),
+    }];
+    let result = sync
+        .sync_from_conversation(&conversation, None)
+        .await
+        .unwrap();
+
+    // The downgrade applies; the unknown target is rejected; the delete waits.
+    assert_eq!(result.integrated_count, 1);
+    assert_eq!(result.invalid_count, 1);
+    let confidence = |target: u32| {
+        let pathdb = storage.pathdb();
+        let db = pathdb.read();
+        db.relations
+            .outgoing_any(roughing)
+            .iter()
+            .find(|r| r.target == target)
+            .map(|r| r.confidence)
+    };
+    assert_eq!(confidence(endmill), Some(0.5));
+    assert_eq!(confidence(drill), Some(0.9));
+
+    let pending = sync.pending_review();
+    assert_eq!(pending.len(), 1);
+    assert!(matches!(
+        &pending[0].structured,
+        StructuredFact::Retraction { rel_type, source, target, confidence: None, .. }
+            if rel_type == "usesTool" && source == "Roughing" && target == "Drill"
+    ));
+    assert!(matches!(pending[0].status, FactStatus::NeedsReview { .. }));
+
+    sync.approve_fact(pending[0].id).unwrap();
+    assert_eq!(confidence(drill), None);
+    assert_eq!(confidence(endmill), Some(0.5));
+}
+
 // ============================================================================
 // Conflict Detection Tests
 // ============================================================================
@@ -652,3 +766,56 @@ async fn test_full_roundtrip() {
         "Should have processed at least one fact"
     );
 }
+
+#[tokio::test]
+async fn test_storage_changes_forwarded_as_sync_events() {
+    let (storage, sync, _dir) = test_env();
+    let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = events.clone();
+    sync.subscribe_changes(
+        ChangeFilter::new().entity_type("Material"),
+        Box::new(move |e| sink.lock().unwrap().push(e)),
+    );
+
+    // Writes from outside the sync manager are forwarded too.
+    storage
+        .add_facts(
+            vec![StorableFact::Entity {
+                name: "Inconel".to_string(),
+                entity_type: "Material".to_string(),
+                attributes: vec![],
+            }],
+            ChangeSource::API {
+                client_id: "erp".to_string(),
+            },
+        )
+        .unwrap();
+    storage.flush().unwrap();
+    sync.sync_from_conversation(
+        &[ConversationTurn {
+            role: Role::Assistant,
+            content: "Steel is a Material. Endmill is a Tool.".to_string(),
+            timestamp: Utc::now(),
+            metadata: Default::default(),
+        }],
+        None,
+    )
+    .await
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    let changes: Vec<(u64, &str, usize)> = events
+        .iter()
+        .map(|e| match e {
+            SyncEvent::FactsChanged {
+                seq, source, count, ..
+            } => (*seq, source.as_str(), *count),
+            other => panic!("unexpected event {other:?}"),
+        })
+        .collect();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0], (0, "api:erp", 1));
+    // Only the Material from the conversation matches the filter.
+    assert!(changes[1].1.starts_with("llm:"));
+    assert_eq!((changes[1].0, changes[1].2), (1, 1));
+}
@@ -0,0 +1,87 @@
+use axiograph_llm_sync::{LinkMethod, MentionLinker, StructuredFact};
+use axiograph_pathdb::PathDB;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn shop() -> (PathDB, u32, u32, u32) {
+    let mut db = PathDB::new();
+    let s1 = db.add_entity("Spindle", vec![("name", "Spindle1")]);
+    let s2 = db.add_entity("Spindle", vec![("name", "Spindle2")]);
+    let acme = db.add_entity("Customer", vec![("name", "Acme Tooling")]);
+    (db, s1, s2, acme)
+}
+
+#[test]
+fn mentions_resolve_by_name_type_and_spelling() {
+    let (db, s1, s2, acme) = shop();
+    let mut linker = MentionLinker::new(Uuid::new_v4());
+
+    let link = linker.resolve(&db, "spindle1", None, Some(0)).unwrap();
+    assert_eq!(
+        (link.entity_id, link.method, link.name.as_str()),
+        (s1, LinkMethod::ExactName, "Spindle1")
+    );
+
+    // "the spindle" prefers the spindle already discussed this session.
+    let link = linker.resolve(&db, "the spindle", None, Some(1)).unwrap();
+    assert_eq!(
+        (link.entity_id, link.method),
+        (s1, LinkMethod::TypeAnaphora)
+    );
+    assert_eq!(link.score, 0.8);
+
+    let link = linker.resolve(&db, "acme", None, Some(2)).unwrap();
+    assert_eq!((link.entity_id, link.method), (acme, LinkMethod::FullText));
+    let link = linker.resolve(&db, "Spindel2", None, Some(3)).unwrap();
+    assert_eq!((link.entity_id, link.method), (s2, LinkMethod::Fuzzy));
+
+    // Earlier links are remembered; type restrictions still apply.
+    let link = linker.resolve(&db, "Acme", None, Some(4)).unwrap();
+    assert_eq!((link.entity_id, link.method), (acme, LinkMethod::Alias));
+    assert!(linker
+        .resolve(&db, "spindle1", Some("Customer"), None)
+        .is_none());
+    assert!(linker.resolve(&db, "gearbox", None, None).is_none());
+    assert_eq!(linker.links().len(), 5);
+
+    linker.alias("that big one", s2);
+    assert_eq!(linker.alias_of("The big one"), Some(s2));
+}
+
+#[test]
+fn linking_facts_rewrites_entity_references() {
+    let (db, s1, s2, acme) = shop();
+    let mut linker = MentionLinker::new(Uuid::new_v4());
+
+    let mut fact = StructuredFact::Relation {
+        rel_type: "ownedBy".to_string(),
+        source: "the spindle".to_string(),
+        target: "acme".to_string(),
+        attributes: HashMap::new(),
+    };
+    let links = linker.link_fact(&db, &mut fact, Some(7));
+    let ids: Vec<u32> = links.iter().map(|l| l.entity_id).collect();
+    assert_eq!(ids, vec![s2, acme]);
+    match &fact {
+        StructuredFact::Relation { source, target, .. } => {
+            assert_eq!(source, "Spindle2");
+            assert_eq!(target, "Acme Tooling");
+        }
+        other => panic!("expected a relation, got {other:?}"),
+    }
+    assert!(links.iter().all(|l| l.turn == Some(7)));
+
+    // Entity definitions only merge into exact matches.
+    let mut new_spindle = StructuredFact::Entity {
+        entity_type: "Spindle".to_string(),
+        name: "Spindle3".to_string(),
+        attributes: HashMap::new(),
+    };
+    assert!(linker.link_fact(&db, &mut new_spindle, None).is_empty());
+    let mut known = StructuredFact::Entity {
+        entity_type: "Spindle".to_string(),
+        name: "spindle1".to_string(),
+        attributes: HashMap::new(),
+    };
+    assert_eq!(linker.link_fact(&db, &mut known, None)[0].entity_id, s1);
+}
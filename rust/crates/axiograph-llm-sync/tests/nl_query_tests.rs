@@ -0,0 +1,82 @@
+use axiograph_llm_sync::{NlQueryTranslator, SchemaContext};
+use axiograph_pathdb::PathDB;
+
+fn machining() -> (PathDB, SchemaContext) {
+    let mut db = PathDB::new();
+    let op = db.add_entity("Operation", vec![("name", "Roughing")]);
+    let ti = db.add_entity("Material", vec![("name", "Titanium")]);
+    let endmill = db.add_entity("Tool", vec![("name", "Endmill")]);
+    let drill = db.add_entity("Tool", vec![("name", "Drill")]);
+    let carbide = db.add_entity("Material", vec![("name", "Carbide")]);
+    db.add_relation("hasMaterial", op, ti, 1.0, vec![]);
+    db.add_relation("usesTool", op, endmill, 0.9, vec![]);
+    db.add_relation("usesTool", op, drill, 0.8, vec![]);
+    db.add_relation("madeOf", endmill, carbide, 1.0, vec![]);
+
+    let schema = SchemaContext {
+        entity_types: vec![
+            "Operation".to_string(),
+            "Material".to_string(),
+            "Tool".to_string(),
+        ],
+        relation_types: vec![
+            "hasMaterial".to_string(),
+            "usesTool".to_string(),
+            "madeOf".to_string(),
+        ],
+        constraints: vec![],
+    };
+    (db, schema)
+}
+
+#[test]
+fn question_grounds_to_typed_relation_query() {
+    let (db, schema) = machining();
+    let answer = NlQueryTranslator::new(&db, &schema).answer("Which tools does roughing use?");
+
+    assert_eq!(
+        answer.query_text(),
+        Some(r#"select ?x where ?x : Tool, name("Roughing") -usesTool-> ?x limit 20"#)
+    );
+    assert_eq!(answer.results, vec![2, 3]);
+    let chosen = &answer.candidates[answer.chosen.unwrap()];
+    assert_eq!(
+        chosen.rationale,
+        "type Tool; entity Roughing (#0); relation usesTool"
+    );
+    assert!(answer.facts[0]
+        .citation
+        .contains(&format!("AxQL: {}", chosen.axql)));
+
+    let context = answer.grounding_context(&schema);
+    assert_eq!(
+        context.suggested_queries,
+        vec![answer.query_text().unwrap().to_string()]
+    );
+    assert!(answer.render().starts_with("Query: select ?x where"));
+    assert!(answer.render().contains("- Drill is a Tool"));
+}
+
+#[test]
+fn relation_chains_follow_mention_order() {
+    let (db, schema) = machining();
+    let translator = NlQueryTranslator::new(&db, &schema).limit(5);
+    let answer = translator.answer("What are the tools Roughing uses made of?");
+    assert_eq!(
+        answer.query_text(),
+        Some(r#"select ?x where name("Roughing") -usesTool/madeOf-> ?x limit 5"#)
+    );
+    assert_eq!(answer.results, vec![4]);
+
+    // Type-only questions fall back to listing the type.
+    let answer = translator.answer("list all materials");
+    assert_eq!(
+        answer.query_text(),
+        Some("select ?x where ?x : Material limit 5")
+    );
+    assert_eq!(answer.results, vec![1, 4]);
+
+    let answer = translator.answer("hello there");
+    assert!(answer.candidates.is_empty());
+    assert_eq!(answer.query_text(), None);
+}
@@ -2,6 +2,7 @@
 
 use axiograph_llm_sync::reconciliation::*;
 use axiograph_llm_sync::*;
+use axiograph_pathdb::ConfidenceCombiner;
 use chrono::Utc;
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -399,6 +400,49 @@ fn test_decay_all() {
     assert!((initial - after).abs() < 0.01);
 }
 
+#[test]
+fn test_confidence_combiner_is_configurable_and_recorded() {
+    let conflicting = |name: &str| {
+        let mut fact = make_fact(name, 0.8);
+        fact.structured = StructuredFact::Entity {
+            entity_type: "Material".to_string(),
+            name: name.to_string(),
+            attributes: [("hardness".to_string(), "50".to_string())]
+                .into_iter()
+                .collect(),
+        };
+        fact
+    };
+    let mut weights = Vec::new();
+    for combiner in [
+        ConfidenceCombiner::Product,
+        ConfidenceCombiner::DempsterShafer,
+    ] {
+        let mut engine = ReconciliationEngine::new(ReconciliationConfig {
+            confidence_combiner: combiner,
+            ..Default::default()
+        });
+        let mut first = make_fact("Brass", 0.5);
+        first.structured = StructuredFact::Entity {
+            entity_type: "Material".to_string(),
+            name: "Brass".to_string(),
+            attributes: [("hardness".to_string(), "40".to_string())]
+                .into_iter()
+                .collect(),
+        };
+        engine.reconcile(first);
+
+        let result = engine.reconcile(conflicting("Brass"));
+        assert_eq!(result.combiner, combiner);
+        weights.push(result.weight.value());
+    }
+
+    // Unknown source credibility is 0.5 * 0.5 = 0.25.
+    assert!((weights[0] - 0.2).abs() < 1e-6);
+    // 0.2 / (0.2 + 0.2 * 0.75)
+    assert!((weights[1] - 0.2 / 0.35).abs() < 1e-6);
+}
+
 #[test]
 fn test_prune_dead_facts() {
     let config = ReconciliationConfig {
@@ -0,0 +1,144 @@
+use axiograph_llm_sync::*;
+use axiograph_storage::{StorageConfig, UnifiedStorage};
+use chrono::Utc;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+fn test_env() -> (Arc<UnifiedStorage>, SyncManager, tempfile::TempDir) {
+    let dir = tempdir().unwrap();
+    let config = StorageConfig {
+        axi_dir: dir.path().to_path_buf(),
+        pathdb_path: dir.path().join("test.axpd"),
+        changelog_path: dir.path().join("changelog.json"),
+        watch_files: false,
+        ..Default::default()
+    };
+    let storage = Arc::new(UnifiedStorage::new(config).unwrap());
+    let sync_config = SyncConfig {
+        auto_integrate_threshold: 0.8,
+        ..Default::default()
+    };
+    let manager = SyncManager::new(
+        Arc::clone(&storage),
+        sync_config,
+        LLMProvider::Anthropic {
+            model: "a".to_string(),
+        },
+    );
+    (storage, manager, dir)
+}
+
+fn said(content: &str) -> Vec<ConversationTurn> {
+    vec![ConversationTurn {
+        role: Role::Assistant,
+        content: content.to_string(),
+        timestamp: Utc::now(),
+        metadata: Default::default(),
+    }]
+}
+
+fn hardness(db: &axiograph_pathdb::PathDB) -> Vec<String> {
+    db.find_by_type("Unknown")
+        .into_iter()
+        .flatten()
+        .filter_map(|id| db.get_entity(id)?.attrs.get("hardness").cloned())
+        .collect()
+}
+
+#[tokio::test]
+async fn sessions_stage_in_isolation_and_reconcile_on_commit() {
+    let (storage, sync, _dir) = test_env();
+    let a = sync.open_session(None).unwrap();
+    let b = sync
+        .open_session(Some(LLMProvider::OpenAI {
+            model: "b".to_string(),
+        }))
+        .unwrap();
+
+    let turns_a = said("Titanium has hardness of 36");
+    let turns_b = said("Titanium has hardness of 40. Steel is a Material");
+    let (staged_a, staged_b) = tokio::join!(
+        sync.stage_conversation(a, &turns_a),
+        sync.stage_conversation(b, &turns_b),
+    );
+    assert_eq!((staged_a.unwrap(), staged_b.unwrap()), (1, 2));
+
+    // Each session sees only its own staged facts; the store sees neither.
+    assert_eq!(
+        hardness(&sync.session_pathdb(a).unwrap().read()),
+        vec!["36"]
+    );
+    assert_eq!(
+        hardness(&sync.session_pathdb(b).unwrap().read()),
+        vec!["40"]
+    );
+    assert!(hardness(&storage.pathdb().read()).is_empty());
+    assert!(sync.state().recent_integrations.is_empty());
+
+    let report_a = sync.commit_session(a).unwrap();
+    assert_eq!(report_a.integrated.len(), 1);
+    assert!(report_a.conflicts.is_empty());
+    assert_eq!(hardness(&storage.pathdb().read()), vec!["36"]);
+
+    // B's Titanium clashes with A's and loses on weight; Steel goes through.
+    let report_b = sync.commit_session(b).unwrap();
+    assert_eq!(report_b.integrated.len(), 1);
+    assert_eq!(report_b.discarded.len(), 1);
+    assert_eq!(report_b.conflicts.len(), 1);
+    let conflict = &report_b.conflicts[0];
+    assert_eq!(conflict.other_session, a);
+    assert_eq!(conflict.other_fact_id, report_a.integrated[0]);
+    assert!(matches!(
+        conflict.conflict_type,
+        ConflictType::AttributeMismatch
+    ));
+    assert!(matches!(conflict.resolution, Resolution::KeepOld));
+    assert_eq!(hardness(&storage.pathdb().read()), vec!["36"]);
+
+    assert!(sync.open_sessions().is_empty());
+    assert!(sync.commit_session(b).is_err());
+}
+
+#[tokio::test]
+async fn trusted_sessions_merge_and_discarded_sessions_vanish() {
+    let (storage, sync, _dir) = test_env();
+    let expert = LLMProvider::Custom {
+        name: "metallurgist".to_string(),
+        endpoint: "local".to_string(),
+    };
+    let mut credibility = SourceCredibility::new("custom:metallurgist", 1.0);
+    credibility
+        .domain_expertise
+        .insert("unknown".to_string(), Weight::new(1.0));
+    sync.register_source(credibility);
+
+    let a = sync.open_session(None).unwrap();
+    let b = sync.open_session(Some(expert)).unwrap();
+    sync.stage_conversation(a, &said("Titanium has hardness of 36"))
+        .await
+        .unwrap();
+    sync.stage_conversation(b, &said("Titanium has hardness of 40"))
+        .await
+        .unwrap();
+    sync.commit_session(a).unwrap();
+
+    // Equal weights: both readings are kept, and the clash is still reported.
+    let report = sync.commit_session(b).unwrap();
+    assert_eq!(report.integrated.len(), 1);
+    assert!(matches!(
+        report.conflicts[0].resolution,
+        Resolution::Merge { .. }
+    ));
+    let mut readings = hardness(&storage.pathdb().read());
+    readings.sort();
+    assert_eq!(readings, vec!["36", "40"]);
+
+    let c = sync.open_session(None).unwrap();
+    sync.stage_conversation(c, &said("Titanium has hardness of 50"))
+        .await
+        .unwrap();
+    assert_eq!(sync.staged_facts(c).len(), 1);
+    assert!(sync.discard_session(c));
+    assert!(sync.staged_facts(c).is_empty());
+    assert_eq!(hardness(&storage.pathdb().read()).len(), 2);
+}
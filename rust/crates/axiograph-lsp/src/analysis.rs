@@ -0,0 +1,704 @@
+//! Editor analysis of one `.axi` document.
+//!
+//! The index is built line-by-line and tolerates text that does not parse
+//! (the common case while typing): declarations, relation signatures and
+//! section spans are recovered from whatever lines are well-formed.
+//! Diagnostics come from the real parser, `axiograph_dsl::lint` and
+//! `axi_module_typecheck`.
+//!
+//! Positions are LSP positions: 0-based lines, UTF-16 columns.
+
+use axiograph_dsl::lint::{lint_axi, LintCode, Severity};
+use axiograph_dsl::schema_v1::{parse_schema_v1, strip_comment};
+use axiograph_pathdb::axi_module_typecheck::typecheck_axi_v1_module;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolKind {
+    Schema,
+    Object,
+    Relation,
+    Theory,
+    Instance,
+    Constraint,
+    Equation,
+    Rewrite,
+}
+
+/// A declaration and where its name is written.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Symbol {
+    pub kind: SymbolKind,
+    pub name: String,
+    /// Schema the declaration belongs to (objects, relations) or is on
+    /// (theories, instances and their contents).
+    pub schema: Option<String>,
+    pub range: Range,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelationSig {
+    pub schema: String,
+    pub name: String,
+    pub fields: Vec<(String, String)>,
+}
+
+impl RelationSig {
+    fn signature(&self) -> String {
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|(f, t)| format!("{f}: {t}"))
+            .collect();
+        format!("relation {}({})", self.name, fields.join(", "))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SectionKind {
+    Schema,
+    Theory,
+    Instance,
+}
+
+#[derive(Debug, Clone)]
+struct Section {
+    kind: SectionKind,
+    schema: String,
+    start: usize,
+}
+
+/// LSP `DiagnosticSeverity`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    Error = 1,
+    Warning = 2,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LspDiagnostic {
+    pub range: Range,
+    pub severity: u8,
+    pub code: String,
+    pub source: &'static str,
+    pub message: String,
+}
+
+/// LSP `CompletionItemKind` values used here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionKind {
+    Field = 5,
+    Class = 7,
+    Function = 3,
+    Keyword = 14,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Completion {
+    pub label: String,
+    pub kind: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(rename = "insertText", skip_serializing_if = "Option::is_none")]
+    pub insert_text: Option<String>,
+}
+
+const TOP_KEYWORDS: &[&str] = &["module", "schema", "theory", "instance"];
+const SCHEMA_KEYWORDS: &[&str] = &["object", "subtype", "relation"];
+const THEORY_KEYWORDS: &[&str] = &["constraint", "equation", "rewrite"];
+const CONSTRAINT_KEYWORDS: &[&str] = &[
+    "functional",
+    "at_most",
+    "symmetric",
+    "transitive",
+    "key",
+    "typing",
+];
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn utf16_len(s: &str) -> u32 {
+    s.encode_utf16().count() as u32
+}
+
+/// Byte offset of UTF-16 column `character` in `line` (clamped to the end).
+fn byte_offset(line: &str, character: u32) -> usize {
+    let mut units = 0u32;
+    for (i, c) in line.char_indices() {
+        if units >= character {
+            return i;
+        }
+        units += c.len_utf16() as u32;
+    }
+    line.len()
+}
+
+fn range_on(line: usize, text: &str, start_byte: usize, end_byte: usize) -> Range {
+    Range {
+        start: Position {
+            line: line as u32,
+            character: utf16_len(&text[..start_byte]),
+        },
+        end: Position {
+            line: line as u32,
+            character: utf16_len(&text[..end_byte]),
+        },
+    }
+}
+
+/// The code of `line` (without indentation or comment).
+fn line_range(line: usize, text: &str) -> Range {
+    let code = strip_comment(text).trim_end();
+    let start = code.len() - code.trim_start().len();
+    range_on(line, text, start, code.len().max(start))
+}
+
+fn contains_word(text: &str, word: &str) -> bool {
+    text.split(|c: char| !is_ident_char(c)).any(|w| w == word)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DocumentIndex {
+    lines: Vec<String>,
+    symbols: Vec<Symbol>,
+    relations: Vec<RelationSig>,
+    sections: Vec<Section>,
+}
+
+impl DocumentIndex {
+    pub fn build(text: &str) -> Self {
+        let lines: Vec<String> = text.lines().map(str::to_string).collect();
+        let mut index = DocumentIndex {
+            lines,
+            ..DocumentIndex::default()
+        };
+        for i in 0..index.lines.len() {
+            index.scan_line(i);
+        }
+        index
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    fn push_symbol(&mut self, i: usize, kind: SymbolKind, keyword: &str, name: &str, schema: &str) {
+        let raw = &self.lines[i];
+        let Some(kw) = raw.find(keyword) else {
+            return;
+        };
+        let after = kw + keyword.len();
+        let Some(offset) = raw[after..].find(name) else {
+            return;
+        };
+        let start = after + offset;
+        let range = range_on(i, raw, start, start + name.len());
+        self.symbols.push(Symbol {
+            kind,
+            name: name.to_string(),
+            schema: Some(schema.to_string()),
+            range,
+        });
+    }
+
+    fn scan_line(&mut self, i: usize) {
+        let code = strip_comment(&self.lines[i]).trim().to_string();
+        let header = |rest: &str, sep: &str| -> Option<(String, String)> {
+            let (name, schema) = rest.trim_end_matches(':').split_once(sep)?;
+            Some((name.trim().to_string(), schema.trim().to_string()))
+        };
+
+        if let Some(rest) = code.strip_prefix("schema ") {
+            let name = rest.trim_end_matches(':').trim().to_string();
+            self.sections.push(Section {
+                kind: SectionKind::Schema,
+                schema: name.clone(),
+                start: i,
+            });
+            self.push_symbol(i, SymbolKind::Schema, "schema", &name, &name);
+            return;
+        }
+        if let Some((name, schema)) = code.strip_prefix("theory ").and_then(|r| header(r, " on ")) {
+            self.sections.push(Section {
+                kind: SectionKind::Theory,
+                schema: schema.clone(),
+                start: i,
+            });
+            self.push_symbol(i, SymbolKind::Theory, "theory", &name, &schema);
+            return;
+        }
+        if let Some((name, schema)) = code
+            .strip_prefix("instance ")
+            .and_then(|r| header(r, " of "))
+        {
+            self.sections.push(Section {
+                kind: SectionKind::Instance,
+                schema: schema.clone(),
+                start: i,
+            });
+            self.push_symbol(i, SymbolKind::Instance, "instance", &name, &schema);
+            return;
+        }
+
+        let Some(section) = self.sections.last().cloned() else {
+            return;
+        };
+        let schema = section.schema.as_str();
+        match section.kind {
+            SectionKind::Schema => {
+                if let Some(name) = code.strip_prefix("object ").map(str::trim) {
+                    self.push_symbol(i, SymbolKind::Object, "object", name, schema);
+                } else if let Some(rest) = code.strip_prefix("relation ") {
+                    let name = rest
+                        .split('(')
+                        .next()
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string();
+                    if name.is_empty() {
+                        return;
+                    }
+                    let fields = self.relation_fields(i);
+                    self.push_symbol(i, SymbolKind::Relation, "relation", &name, schema);
+                    self.relations.push(RelationSig {
+                        schema: schema.to_string(),
+                        name,
+                        fields,
+                    });
+                }
+            }
+            SectionKind::Theory => {
+                for (keyword, kind) in [
+                    ("constraint ", SymbolKind::Constraint),
+                    ("equation ", SymbolKind::Equation),
+                    ("rewrite ", SymbolKind::Rewrite),
+                ] {
+                    let Some(rest) = code.strip_prefix(keyword) else {
+                        continue;
+                    };
+                    // Only named blocks (`constraint Name:`) declare a name.
+                    let Some(name) = rest.trim().strip_suffix(':').map(str::trim) else {
+                        continue;
+                    };
+                    if !name.is_empty() && name.chars().all(is_ident_char) {
+                        self.push_symbol(i, kind, keyword.trim_end(), name, schema);
+                    }
+                }
+            }
+            SectionKind::Instance => {}
+        }
+    }
+
+    /// Fields of the relation declared at line `i` (the declaration may span
+    /// lines until its parentheses balance).
+    fn relation_fields(&self, i: usize) -> Vec<(String, String)> {
+        let mut text = String::new();
+        for line in &self.lines[i..] {
+            text.push_str(strip_comment(line));
+            text.push(' ');
+            // Stop once balanced, or right away for a paren-less (partial) line.
+            if !text.contains('(') || text.matches('(').count() <= text.matches(')').count() {
+                break;
+            }
+        }
+        let Some(inner) = text
+            .split_once('(')
+            .and_then(|(_, rest)| rest.rsplit_once(')'))
+            .map(|(inner, _)| inner)
+        else {
+            return Vec::new();
+        };
+        inner
+            .split(',')
+            .filter_map(|field| {
+                let (name, ty) = field.split_once(':')?;
+                let (name, ty) = (name.trim(), ty.trim());
+                (!name.is_empty() && !ty.is_empty()).then(|| (name.to_string(), ty.to_string()))
+            })
+            .collect()
+    }
+
+    fn section_at(&self, line: usize) -> Option<&Section> {
+        self.sections.iter().rev().find(|s| s.start <= line)
+    }
+
+    /// The identifier under (or just before) `pos`.
+    pub fn word_at(&self, pos: Position) -> Option<String> {
+        let line = self.lines.get(pos.line as usize)?;
+        let at = byte_offset(line, pos.character);
+        let start = line[..at]
+            .rfind(|c: char| !is_ident_char(c))
+            .map_or(0, |i| i + 1);
+        let end = line[at..]
+            .find(|c: char| !is_ident_char(c))
+            .map_or(line.len(), |i| at + i);
+        (start < end).then(|| line[start..end].to_string())
+    }
+
+    fn schema_at(&self, line: u32) -> Option<&str> {
+        self.section_at(line as usize).map(|s| s.schema.as_str())
+    }
+
+    // ------------------------------------------------------------------
+    // Diagnostics
+    // ------------------------------------------------------------------
+
+    /// Parse, lint and typecheck diagnostics for `text` (this index's text).
+    pub fn diagnostics(&self, text: &str) -> Vec<LspDiagnostic> {
+        let mut out = Vec::new();
+        let mut parsed = true;
+        for d in lint_axi(text) {
+            parsed &= d.code != LintCode::ParseError;
+            let line = d.line.map_or(0, |l| l.saturating_sub(1));
+            out.push(LspDiagnostic {
+                range: self.range_of_line(line),
+                severity: match d.severity {
+                    Severity::Error => DiagnosticSeverity::Error as u8,
+                    Severity::Warning => DiagnosticSeverity::Warning as u8,
+                },
+                code: d.code.as_str().to_string(),
+                source: "axiograph",
+                message: d.message,
+            });
+        }
+        if !parsed {
+            return out;
+        }
+        let Ok(module) = parse_schema_v1(text) else {
+            return out;
+        };
+        if let Err(err) = typecheck_axi_v1_module(&module) {
+            let message = err.to_string();
+            // Pin it to the instance it names, else the first instance.
+            let instances = || {
+                self.symbols
+                    .iter()
+                    .filter(|s| s.kind == SymbolKind::Instance)
+            };
+            let line = instances()
+                .find(|s| message.contains(&format!("`{}`", s.name)))
+                .or_else(|| instances().next())
+                .map_or(0, |s| s.range.start.line as usize);
+            out.push(LspDiagnostic {
+                range: self.range_of_line(line),
+                severity: DiagnosticSeverity::Error as u8,
+                code: "typecheck".to_string(),
+                source: "axiograph",
+                message,
+            });
+        }
+        out
+    }
+
+    fn range_of_line(&self, line: usize) -> Range {
+        match self.lines.get(line) {
+            Some(text) => line_range(line, text),
+            None => range_on(line, "", 0, 0),
+        }
+    }
+
+    // ------------------------------------------------------------------
+    // Definition / hover / completion
+    // ------------------------------------------------------------------
+
+    /// Declaration of `name`, preferring one in `schema`.
+    fn find_symbol(&self, name: &str, schema: Option<&str>) -> Option<&Symbol> {
+        let mut candidates = self.symbols.iter().filter(|s| s.name == name);
+        let first = candidates.next()?;
+        if schema.is_none() || first.schema.as_deref() == schema {
+            return Some(first);
+        }
+        candidates
+            .find(|s| s.schema.as_deref() == schema)
+            .or(Some(first))
+    }
+
+    fn relation(&self, name: &str, schema: Option<&str>) -> Option<&RelationSig> {
+        let mut matching = self.relations.iter().filter(|r| r.name == name);
+        let first = matching.next()?;
+        if schema.is_none() || Some(first.schema.as_str()) == schema {
+            return Some(first);
+        }
+        matching
+            .find(|r| Some(r.schema.as_str()) == schema)
+            .or(Some(first))
+    }
+
+    /// Declaration of the name under `pos`, looked up here first, then in
+    /// `others` (the other open documents). Returns the index that holds it.
+    pub fn definition<'a>(
+        &'a self,
+        pos: Position,
+        others: &[&'a DocumentIndex],
+    ) -> Option<(&'a DocumentIndex, &'a Symbol)> {
+        let word = self.word_at(pos)?;
+        let schema = self.schema_at(pos.line);
+        std::iter::once(self)
+            .chain(others.iter().copied())
+            .find_map(|doc| doc.find_symbol(&word, schema).map(|s| (doc, s)))
+    }
+
+    /// Markdown hover for the name under `pos`.
+    pub fn hover(&self, pos: Position, others: &[&DocumentIndex]) -> Option<String> {
+        let (doc, symbol) = self.definition(pos, others)?;
+        let schema = symbol.schema.as_deref();
+        let docs = || std::iter::once(self).chain(others.iter().copied());
+        let mut out = String::new();
+        match symbol.kind {
+            SymbolKind::Relation => {
+                let sig = doc.relation(&symbol.name, schema)?;
+                out.push_str(&format!(
+                    "```axi\n{}\n```\nschema `{}`",
+                    sig.signature(),
+                    sig.schema
+                ));
+                let constraints: Vec<String> = docs()
+                    .flat_map(|d| d.constraints_mentioning(&symbol.name, schema))
+                    .collect();
+                if !constraints.is_empty() {
+                    out.push_str("\n\n**Constraints**\n");
+                    for c in constraints {
+                        out.push_str(&format!("- `{c}`\n"));
+                    }
+                }
+            }
+            SymbolKind::Object => {
+                out.push_str(&format!(
+                    "```axi\nobject {}\n```\nschema `{}`",
+                    symbol.name,
+                    schema.unwrap_or_default()
+                ));
+                let used: Vec<String> = docs()
+                    .flat_map(|d| d.relations.iter())
+                    .filter(|r| Some(r.schema.as_str()) == schema)
+                    .flat_map(|r| {
+                        r.fields
+                            .iter()
+                            .filter(|(_, ty)| *ty == symbol.name)
+                            .map(move |(f, _)| format!("{}.{f}", r.name))
+                    })
+                    .collect();
+                if !used.is_empty() {
+                    out.push_str(&format!("\n\nUsed by: `{}`", used.join("`, `")));
+                }
+            }
+            SymbolKind::Constraint | SymbolKind::Equation | SymbolKind::Rewrite => {
+                let block = doc.block_at(symbol.range.start.line as usize);
+                out.push_str(&format!("```axi\n{}\n```", block.join("\n")));
+            }
+            SymbolKind::Schema | SymbolKind::Theory | SymbolKind::Instance => {
+                let header = doc.lines[symbol.range.start.line as usize].trim();
+                out.push_str(&format!("```axi\n{header}\n```"));
+            }
+        }
+        Some(out)
+    }
+
+    /// The line at `start` plus the more-indented lines that follow it.
+    fn block_at(&self, start: usize) -> Vec<&str> {
+        let indent = |s: &str| s.len() - s.trim_start().len();
+        let base = indent(&self.lines[start]);
+        let mut out = vec![self.lines[start].trim()];
+        for line in &self.lines[start + 1..] {
+            if line.trim().is_empty() || indent(line) <= base {
+                break;
+            }
+            out.push(line.trim());
+        }
+        out
+    }
+
+    /// First lines of theory constraints (on `schema`) that mention `relation`.
+    fn constraints_mentioning(&self, relation: &str, schema: Option<&str>) -> Vec<String> {
+        let mut out = Vec::new();
+        for (i, line) in self.lines.iter().enumerate() {
+            let Some(section) = self.section_at(i) else {
+                continue;
+            };
+            if section.kind != SectionKind::Theory
+                || schema.is_some_and(|s| s != section.schema)
+                || !strip_comment(line).trim().starts_with("constraint ")
+            {
+                continue;
+            }
+            let block = self.block_at(i);
+            if block
+                .iter()
+                .any(|l| contains_word(strip_comment(l), relation))
+            {
+                out.push(strip_comment(block[0]).trim().to_string());
+            }
+        }
+        out
+    }
+
+    /// Completions at `pos`, from the schema index of this and `others`.
+    pub fn completions(&self, pos: Position, others: &[&DocumentIndex]) -> Vec<Completion> {
+        let line_no = pos.line as usize;
+        let Some(line) = self.lines.get(line_no) else {
+            return keywords(TOP_KEYWORDS);
+        };
+        let prefix = &line[..byte_offset(line, pos.character)];
+        let docs: Vec<&DocumentIndex> = std::iter::once(self)
+            .chain(others.iter().copied())
+            .collect();
+        let Some(section) = self.section_at(line_no).filter(|s| s.start != line_no) else {
+            return keywords(TOP_KEYWORDS);
+        };
+        let schema = section.schema.as_str();
+        let relation = |name: &str| docs.iter().find_map(|d| d.relation(name, Some(schema)));
+
+        // `Rel.` → fields of `Rel`.
+        let word_start = prefix
+            .rfind(|c: char| !is_ident_char(c))
+            .map_or(0, |i| i + 1);
+        if let Some(rel) = prefix[..word_start]
+            .strip_suffix('.')
+            .map(|p| &p[p.rfind(|c: char| !is_ident_char(c)).map_or(0, |i| i + 1)..])
+        {
+            return relation(rel).map(fields).unwrap_or_default();
+        }
+
+        match section.kind {
+            SectionKind::Schema => {
+                if prefix.contains(':') || prefix.trim_start().starts_with("subtype ") {
+                    names(&docs, schema, true)
+                } else {
+                    keywords(SCHEMA_KEYWORDS)
+                }
+            }
+            SectionKind::Theory => {
+                let code = prefix.trim_start();
+                match code.strip_prefix("constraint ") {
+                    Some(rest) if !rest.contains(' ') => {
+                        let mut out = keywords(CONSTRAINT_KEYWORDS);
+                        out.extend(names(&docs, schema, false));
+                        out
+                    }
+                    Some(_) => names(&docs, schema, false),
+                    None => keywords(THEORY_KEYWORDS),
+                }
+            }
+            SectionKind::Instance => {
+                // Inside a tuple of `Rel = {...}`: complete `Rel`'s fields
+                // until this field has its `=`.
+                if let Some(rel) = self.assignment_of(line_no, section.start) {
+                    let open = prefix.matches('(').count() > prefix.matches(')').count()
+                        || (!prefix.contains('=') && prefix.trim_start().starts_with('('));
+                    let field = prefix.rsplit(['(', ',']).next().unwrap_or_default();
+                    if open && !field.contains('=') {
+                        return relation(&rel)
+                            .map(|r| fields(r).into_iter().map(with_equals).collect())
+                            .unwrap_or_default();
+                    }
+                    if prefix.contains('=') || rel_line_continues(prefix) {
+                        return Vec::new();
+                    }
+                }
+                names(&docs, schema, true)
+            }
+        }
+    }
+
+    /// Name assigned by the `Name = {` line at or above `line` (within the
+    /// instance starting at `start`).
+    fn assignment_of(&self, line: usize, start: usize) -> Option<String> {
+        for i in (start + 1..=line).rev() {
+            let code = strip_comment(&self.lines[i]).trim();
+            if let Some((name, _)) = code.split_once('=') {
+                let name = name.trim();
+                if !name.is_empty() && name.chars().all(is_ident_char) {
+                    return Some(name.to_string());
+                }
+            }
+            if code.ends_with('}') && i != line {
+                return None;
+            }
+        }
+        None
+    }
+}
+
+fn rel_line_continues(prefix: &str) -> bool {
+    prefix.trim_start().starts_with(['(', '{'])
+}
+
+fn keywords(words: &[&str]) -> Vec<Completion> {
+    words
+        .iter()
+        .map(|w| Completion {
+            label: w.to_string(),
+            kind: CompletionKind::Keyword as u8,
+            detail: None,
+            insert_text: None,
+        })
+        .collect()
+}
+
+fn fields(relation: &RelationSig) -> Vec<Completion> {
+    relation
+        .fields
+        .iter()
+        .map(|(f, ty)| Completion {
+            label: f.clone(),
+            kind: CompletionKind::Field as u8,
+            detail: Some(format!("{}.{f}: {ty}", relation.name)),
+            insert_text: None,
+        })
+        .collect()
+}
+
+fn with_equals(mut c: Completion) -> Completion {
+    c.insert_text = Some(format!("{}=", c.label));
+    c
+}
+
+/// Relations (and, with `objects`, object types) of `schema` across `docs`.
+fn names(docs: &[&DocumentIndex], schema: &str, objects: bool) -> Vec<Completion> {
+    let mut out: Vec<Completion> = Vec::new();
+    for doc in docs {
+        for symbol in &doc.symbols {
+            if symbol.schema.as_deref() != Some(schema)
+                || out.iter().any(|c| c.label == symbol.name)
+            {
+                continue;
+            }
+            match symbol.kind {
+                SymbolKind::Object if objects => out.push(Completion {
+                    label: symbol.name.clone(),
+                    kind: CompletionKind::Class as u8,
+                    detail: Some(format!("object ({schema})")),
+                    insert_text: None,
+                }),
+                SymbolKind::Relation => {
+                    let detail = doc
+                        .relation(&symbol.name, Some(schema))
+                        .map(RelationSig::signature);
+                    out.push(Completion {
+                        label: symbol.name.clone(),
+                        kind: CompletionKind::Function as u8,
+                        detail,
+                        insert_text: None,
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,19 @@
+//! Axiograph language server for `.axi` modules.
+//!
+//! Speaks LSP over stdio (see `src/main.rs`) and provides:
+//! - diagnostics: parse errors and lint findings (`axiograph_dsl::lint`) plus
+//!   typecheck errors (`axi_module_typecheck`),
+//! - go-to-definition for schemas, objects, relations, theories, instances and
+//!   named constraint blocks,
+//! - completion from the schema index (objects, relations, tuple fields,
+//!   constraint keywords),
+//! - hover showing a relation's signature and the theory constraints on it.
+//!
+//! The protocol layer is hand-rolled JSON-RPC over `serde_json`; `Server` is
+//! transport-agnostic so tests drive it with plain JSON values.
+
+pub mod analysis;
+pub mod protocol;
+mod server;
+
+pub use server::Server;
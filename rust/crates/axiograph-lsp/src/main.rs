@@ -0,0 +1,22 @@
+//! `axiograph-lsp`: language server for `.axi` over stdio.
+
+use std::io::{self, BufReader};
+
+use axiograph_lsp::protocol::{read_message, write_message};
+use axiograph_lsp::Server;
+
+fn main() -> anyhow::Result<()> {
+    let mut input = BufReader::new(io::stdin().lock());
+    let mut output = io::stdout().lock();
+    let mut server = Server::new();
+
+    while let Some(message) = read_message(&mut input)? {
+        for reply in server.handle(message) {
+            write_message(&mut output, &reply)?;
+        }
+        if server.exited() {
+            break;
+        }
+    }
+    std::process::exit(server.exit_code());
+}
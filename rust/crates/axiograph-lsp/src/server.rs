@@ -0,0 +1,198 @@
+//! Request/notification dispatch over open documents.
+
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+
+use crate::analysis::{DocumentIndex, Position};
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// A JSON-RPC result or `(error code, message)`.
+type RpcResult<T> = Result<T, (i64, String)>;
+
+/// The document a request targets, its cursor and the other open documents.
+type Located<'a> = (&'a str, &'a DocumentIndex, Position, Vec<&'a DocumentIndex>);
+
+struct Document {
+    text: String,
+    index: DocumentIndex,
+}
+
+/// LSP server state. Feed it decoded messages; it returns the messages to
+/// send back (responses and `publishDiagnostics` notifications).
+#[derive(Default)]
+pub struct Server {
+    documents: BTreeMap<String, Document>,
+    shutdown: bool,
+    exit: bool,
+}
+
+impl Server {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the client sent `exit`.
+    pub fn exited(&self) -> bool {
+        self.exit
+    }
+
+    /// Process exit code per the spec: 0 only after `shutdown`.
+    pub fn exit_code(&self) -> i32 {
+        if self.shutdown {
+            0
+        } else {
+            1
+        }
+    }
+
+    pub fn handle(&mut self, message: Value) -> Vec<Value> {
+        let method = message["method"].as_str().unwrap_or_default().to_string();
+        let params = &message["params"];
+        let Some(id) = message.get("id").cloned() else {
+            return self.notification(&method, params);
+        };
+
+        let result = match method.as_str() {
+            "initialize" => Ok(initialize_result()),
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            "textDocument/definition" => self.definition(params),
+            "textDocument/hover" => self.hover(params),
+            "textDocument/completion" => self.completion(params),
+            _ => Err((METHOD_NOT_FOUND, format!("unsupported method `{method}`"))),
+        };
+        vec![match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": message },
+            }),
+        }]
+    }
+
+    fn notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        match method {
+            "exit" => {
+                self.exit = true;
+                Vec::new()
+            }
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.open(uri, text.to_string())
+            }
+            "textDocument/didChange" => {
+                // Full sync: the last change carries the whole text.
+                let Some(text) = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                else {
+                    return Vec::new();
+                };
+                self.open(uri, text.to_string())
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                vec![publish_diagnostics(&uri, Value::Array(Vec::new()))]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn open(&mut self, uri: String, text: String) -> Vec<Value> {
+        let index = DocumentIndex::build(&text);
+        let diagnostics = serde_json::to_value(index.diagnostics(&text)).unwrap_or_default();
+        self.documents.insert(uri.clone(), Document { text, index });
+        vec![publish_diagnostics(&uri, diagnostics)]
+    }
+
+    /// The document at `params.textDocument.uri`, the cursor position and
+    /// the other open documents (for cross-file lookups).
+    fn locate(&self, params: &Value) -> RpcResult<Located<'_>> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        let (uri, document) = self
+            .documents
+            .get_key_value(uri)
+            .ok_or_else(|| (INVALID_PARAMS, format!("document `{uri}` is not open")))?;
+        let position = Position {
+            line: params["position"]["line"].as_u64().unwrap_or(0) as u32,
+            character: params["position"]["character"].as_u64().unwrap_or(0) as u32,
+        };
+        let others = self
+            .documents
+            .iter()
+            .filter(|(other, _)| *other != uri)
+            .map(|(_, doc)| &doc.index)
+            .collect();
+        Ok((uri.as_str(), &document.index, position, others))
+    }
+
+    fn definition(&self, params: &Value) -> RpcResult<Value> {
+        let (uri, index, position, others) = self.locate(params)?;
+        let Some((target, symbol)) = index.definition(position, &others) else {
+            return Ok(Value::Null);
+        };
+        let target_uri = self
+            .documents
+            .iter()
+            .find(|(_, doc)| std::ptr::eq(&doc.index, target))
+            .map_or(uri, |(u, _)| u.as_str());
+        Ok(json!({ "uri": target_uri, "range": symbol.range }))
+    }
+
+    fn hover(&self, params: &Value) -> RpcResult<Value> {
+        let (_, index, position, others) = self.locate(params)?;
+        Ok(match index.hover(position, &others) {
+            Some(markdown) => json!({
+                "contents": { "kind": "markdown", "value": markdown },
+            }),
+            None => Value::Null,
+        })
+    }
+
+    fn completion(&self, params: &Value) -> RpcResult<Value> {
+        let (_, index, position, others) = self.locate(params)?;
+        let items = index.completions(position, &others);
+        Ok(json!({ "isIncomplete": false, "items": items }))
+    }
+
+    /// Current text of an open document.
+    pub fn text(&self, uri: &str) -> Option<&str> {
+        self.documents.get(uri).map(|d| d.text.as_str())
+    }
+}
+
+fn initialize_result() -> Value {
+    json!({
+        "capabilities": {
+            "textDocumentSync": 1,
+            "definitionProvider": true,
+            "hoverProvider": true,
+            "completionProvider": {
+                "triggerCharacters": ["(", ",", ".", " "],
+            },
+        },
+        "serverInfo": {
+            "name": "axiograph-lsp",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+    })
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    })
+}
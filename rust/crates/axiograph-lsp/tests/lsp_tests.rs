@@ -0,0 +1,249 @@
+use std::io::Cursor;
+
+use axiograph_lsp::protocol::{read_message, write_message};
+use axiograph_lsp::Server;
+use serde_json::{json, Value};
+
+const URI: &str = "file:///tmp/Family.axi";
+
+fn family_text() -> String {
+    [
+        "module Family",
+        "",
+        "schema Fam:",
+        "  object Person",
+        "  object Place",
+        "  relation Parent(child: Person, parent: Person)",
+        "  relation LivesIn(",
+        "    who: Person,",
+        "    where: Place",
+        "  )",
+        "",
+        "theory FamRules on Fam:",
+        "  constraint functional LivesIn.who -> LivesIn.where",
+        "  constraint key Parent(child, parent)",
+        "",
+        "instance Smiths of Fam:",
+        "  Person = {Alice, Bob}",
+        "  Place = {Paris}",
+        "  Parent = {(child=Bob, parent=Alice)}",
+        "  LivesIn = {(who=Alice, where=Paris)}",
+        "",
+    ]
+    .join("\n")
+}
+
+fn open(server: &mut Server, uri: &str, text: &str) -> Vec<Value> {
+    server.handle(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": { "textDocument": { "uri": uri, "languageId": "axi", "version": 1, "text": text } },
+    }))
+}
+
+fn request(server: &mut Server, method: &str, line: u32, character: u32) -> Value {
+    let mut replies = server.handle(json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": method,
+        "params": {
+            "textDocument": { "uri": URI },
+            "position": { "line": line, "character": character },
+        },
+    }));
+    assert_eq!(replies.len(), 1);
+    let reply = replies.remove(0);
+    assert_eq!(reply["id"], 7);
+    reply["result"].clone()
+}
+
+fn labels(completion: &Value) -> Vec<String> {
+    completion["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn initialize_advertises_capabilities_and_unknown_requests_error() {
+    let mut server = Server::new();
+    let replies =
+        server.handle(json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }));
+    let caps = &replies[0]["result"]["capabilities"];
+    assert_eq!(caps["textDocumentSync"], 1);
+    assert_eq!(caps["definitionProvider"], true);
+    assert_eq!(caps["hoverProvider"], true);
+    assert!(caps["completionProvider"].is_object());
+
+    let replies = server.handle(
+        json!({ "jsonrpc": "2.0", "id": 2, "method": "textDocument/rename", "params": {} }),
+    );
+    assert_eq!(replies[0]["error"]["code"], -32601);
+
+    assert!(server
+        .handle(json!({ "jsonrpc": "2.0", "method": "$/cancelRequest", "params": {} }))
+        .is_empty());
+
+    server.handle(json!({ "jsonrpc": "2.0", "id": 3, "method": "shutdown" }));
+    server.handle(json!({ "jsonrpc": "2.0", "method": "exit" }));
+    assert!(server.exited());
+    assert_eq!(server.exit_code(), 0);
+}
+
+#[test]
+fn diagnostics_cover_clean_parse_lint_and_typecheck() {
+    let mut server = Server::new();
+    let published = open(&mut server, URI, &family_text());
+    assert_eq!(published[0]["method"], "textDocument/publishDiagnostics");
+    assert_eq!(published[0]["params"]["diagnostics"], json!([]));
+
+    // Parse error.
+    let broken = family_text().replace("relation Parent(", "relation Parent((");
+    let published = server.handle(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didChange",
+        "params": {
+            "textDocument": { "uri": URI, "version": 2 },
+            "contentChanges": [{ "text": broken }],
+        },
+    }));
+    let diagnostics = published[0]["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["code"], "parse_error");
+    assert_eq!(diagnostics[0]["severity"], 1);
+
+    // Lint: unknown field type, reported on its line.
+    let unknown = family_text().replace("parent: Person)", "parent: Persn)");
+    let published = open(&mut server, URI, &unknown);
+    let diagnostics = published[0]["params"]["diagnostics"].as_array().unwrap();
+    assert!(diagnostics
+        .iter()
+        .any(|d| d["code"] == "unknown_type" && d["range"]["start"]["line"] == 5));
+
+    // Typecheck: tuple missing a field, pinned to the instance header.
+    let missing = family_text().replace("(child=Bob, parent=Alice)", "(child=Bob)");
+    let published = open(&mut server, URI, &missing);
+    let diagnostics = published[0]["params"]["diagnostics"].as_array().unwrap();
+    let typecheck = diagnostics
+        .iter()
+        .find(|d| d["code"] == "typecheck")
+        .expect("typecheck diagnostic");
+    assert_eq!(typecheck["range"]["start"]["line"], 15);
+    assert!(typecheck["message"]
+        .as_str()
+        .unwrap()
+        .contains("missing field `parent`"));
+
+    // Closing clears diagnostics.
+    let published = server.handle(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didClose",
+        "params": { "textDocument": { "uri": URI } },
+    }));
+    assert_eq!(published[0]["params"]["diagnostics"], json!([]));
+}
+
+#[test]
+fn definition_resolves_locally_and_across_documents() {
+    let mut server = Server::new();
+    open(&mut server, URI, &family_text());
+
+    // `Parent` in the instance → relation declaration.
+    let def = request(&mut server, "textDocument/definition", 18, 4);
+    assert_eq!(def["uri"], URI);
+    assert_eq!(def["range"]["start"], json!({ "line": 5, "character": 11 }));
+    assert_eq!(def["range"]["end"], json!({ "line": 5, "character": 17 }));
+
+    // `Place` as a field type → object declaration.
+    let def = request(&mut server, "textDocument/definition", 8, 12);
+    assert_eq!(def["range"]["start"]["line"], 4);
+
+    // Whitespace resolves to nothing.
+    assert_eq!(
+        request(&mut server, "textDocument/definition", 1, 0),
+        Value::Null
+    );
+
+    // A name declared only in another open document.
+    let other = "file:///tmp/Rules.axi";
+    open(
+        &mut server,
+        other,
+        "module Rules\n\nschema Geo:\n  object Region\n",
+    );
+    let text = family_text().replace("  Place = {Paris}", "  Place = {Paris}\n  -- see Region");
+    open(&mut server, URI, &text);
+    let def = request(&mut server, "textDocument/definition", 18, 11);
+    assert_eq!(def["uri"], other);
+    assert_eq!(def["range"]["start"]["line"], 3);
+}
+
+#[test]
+fn hover_shows_signature_and_constraints() {
+    let mut server = Server::new();
+    open(&mut server, URI, &family_text());
+
+    let hover = request(&mut server, "textDocument/hover", 19, 3);
+    let value = hover["contents"]["value"].as_str().unwrap();
+    assert!(value.contains("relation LivesIn(who: Person, where: Place)"));
+    assert!(value.contains("constraint functional LivesIn.who -> LivesIn.where"));
+    assert!(!value.contains("constraint key Parent"));
+
+    let hover = request(&mut server, "textDocument/hover", 3, 10);
+    let value = hover["contents"]["value"].as_str().unwrap();
+    assert!(value.contains("object Person"));
+    assert!(value.contains("Parent.child"));
+    assert!(value.contains("LivesIn.who"));
+}
+
+#[test]
+fn completion_uses_the_schema_index() {
+    let mut server = Server::new();
+    let text = family_text().replace(
+        "  LivesIn = {(who=Alice, where=Paris)}",
+        "  LivesIn = {(who=Alice, \n  Pa\n",
+    );
+    open(&mut server, URI, &text);
+
+    // Inside a tuple: remaining fields of the relation.
+    let items = request(&mut server, "textDocument/completion", 19, 26);
+    assert_eq!(labels(&items), ["who", "where"]);
+    assert_eq!(items["items"][1]["insertText"], "where=");
+
+    // Start of an instance line: objects and relations of the schema.
+    let items = request(&mut server, "textDocument/completion", 20, 4);
+    let names = labels(&items);
+    for name in ["Person", "Place", "Parent", "LivesIn"] {
+        assert!(names.contains(&name.to_string()), "{names:?}");
+    }
+
+    // Theory constraint: constraint kinds and relations (no objects).
+    let items = request(&mut server, "textDocument/completion", 12, 13);
+    let names = labels(&items);
+    assert!(names.contains(&"functional".to_string()));
+    assert!(names.contains(&"LivesIn".to_string()));
+    assert!(!names.contains(&"Person".to_string()));
+
+    // `Rel.` → fields.
+    let items = request(&mut server, "textDocument/completion", 12, 34);
+    assert_eq!(labels(&items), ["who", "where"]);
+
+    // Top level.
+    let items = request(&mut server, "textDocument/completion", 1, 0);
+    assert!(labels(&items).contains(&"schema".to_string()));
+}
+
+#[test]
+fn protocol_frames_round_trip() {
+    let message = json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} });
+    let mut bytes = Vec::new();
+    write_message(&mut bytes, &message).unwrap();
+    write_message(&mut bytes, &message).unwrap();
+
+    let mut input = Cursor::new(bytes);
+    assert_eq!(read_message(&mut input).unwrap(), Some(message.clone()));
+    assert_eq!(read_message(&mut input).unwrap(), Some(message));
+    assert_eq!(read_message(&mut input).unwrap(), None);
+}
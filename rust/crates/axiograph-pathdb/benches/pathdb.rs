@@ -0,0 +1,262 @@
+//! PathDB micro-benchmarks (criterion).
+//!
+//! Run:
+//!   cargo bench -p axiograph-pathdb --bench pathdb
+//!
+//! Regression tracking against the committed baseline:
+//!   ./scripts/bench_regression.sh            # compare
+//!   ./scripts/bench_regression.sh --update   # refresh baseline JSON
+//!
+//! Graphs are synthetic and seeded, so runs are comparable across machines
+//! only in relative terms; baselines are per-hardware.
+
+use std::hint::black_box;
+
+use axiograph_pathdb::{PathDB, StringInterner};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use roaring::RoaringBitmap;
+
+const ENTITIES: u32 = 5_000;
+const EDGES_PER_ENTITY: u32 = 4;
+const REL_TYPES: [&str; 3] = ["knows", "worksWith", "reportsTo"];
+const WORDS: [&str; 12] = [
+    "graph",
+    "path",
+    "index",
+    "query",
+    "proof",
+    "schema",
+    "entity",
+    "relation",
+    "context",
+    "snapshot",
+    "ontology",
+    "certificate",
+];
+
+/// Small deterministic PRNG (xorshift) so benches don't depend on `rand`.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 32) as u32
+    }
+}
+
+fn description(rng: &mut XorShift) -> String {
+    (0..6)
+        .map(|_| WORDS[rng.next() as usize % WORDS.len()])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn add_entities(db: &mut PathDB, rng: &mut XorShift) {
+    for i in 0..ENTITIES {
+        let name = format!("entity_{i}");
+        let desc = description(rng);
+        db.add_entity(
+            "Node",
+            vec![("name", name.as_str()), ("description", desc.as_str())],
+        );
+    }
+}
+
+fn add_edges(db: &mut PathDB, rng: &mut XorShift) {
+    for src in 0..ENTITIES {
+        for k in 0..EDGES_PER_ENTITY {
+            let dst = rng.next() % ENTITIES;
+            let rel = REL_TYPES[(k as usize) % REL_TYPES.len()];
+            db.add_relation(rel, src, dst, 0.9, vec![]);
+        }
+    }
+}
+
+fn synthetic_db() -> PathDB {
+    let mut rng = XorShift(0x5eed);
+    let mut db = PathDB::new();
+    add_entities(&mut db, &mut rng);
+    add_edges(&mut db, &mut rng);
+    db.build_indexes();
+    db
+}
+
+fn bench_interning(c: &mut Criterion) {
+    let strings: Vec<String> = (0..10_000).map(|i| format!("symbol_{i}")).collect();
+    let mut group = c.benchmark_group("interning");
+    group.throughput(Throughput::Elements(strings.len() as u64));
+    group.bench_function("fresh", |b| {
+        b.iter(|| {
+            let interner = StringInterner::new();
+            for s in &strings {
+                black_box(interner.intern(s));
+            }
+        })
+    });
+    let warm = StringInterner::new();
+    for s in &strings {
+        warm.intern(s);
+    }
+    group.bench_function("existing", |b| {
+        b.iter(|| {
+            for s in &strings {
+                black_box(warm.intern(s));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_bulk_insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bulk_insert");
+    group.sample_size(20);
+    group.throughput(Throughput::Elements((ENTITIES * EDGES_PER_ENTITY) as u64));
+    group.bench_function("relations", |b| {
+        b.iter_batched(
+            || {
+                let mut rng = XorShift(0x5eed);
+                let mut db = PathDB::new();
+                add_entities(&mut db, &mut rng);
+                (db, rng)
+            },
+            |(mut db, mut rng)| {
+                add_edges(&mut db, &mut rng);
+                db
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_traversal(c: &mut Criterion) {
+    let db = synthetic_db();
+    let mut group = c.benchmark_group("traversal");
+    for hops in 1..=3 {
+        let path = &REL_TYPES[..hops];
+        group.bench_with_input(BenchmarkId::new("follow_path", hops), &path, |b, path| {
+            let mut start = 0u32;
+            b.iter(|| {
+                start = (start + 1) % ENTITIES;
+                black_box(db.follow_path(start, path))
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Multi-start 2–4 hop expansion: one `targets()` per frontier entity versus
+/// the batch frontier kernel, on mutable and frozen (CSR) adjacency.
+fn bench_frontier(c: &mut Criterion) {
+    let db = synthetic_db();
+    let mut frozen = synthetic_db();
+    frozen.freeze_adjacency();
+    let frontier: RoaringBitmap = (0..ENTITIES).step_by(8).collect();
+    let mut group = c.benchmark_group("frontier");
+    for hops in 2..=4 {
+        let rel_ids: Vec<_> = (0..hops)
+            .map(|hop| db.interner.id_of(REL_TYPES[hop % REL_TYPES.len()]).unwrap())
+            .collect();
+        group.bench_with_input(
+            BenchmarkId::new("per_entity", hops),
+            &rel_ids,
+            |b, rel_ids| {
+                b.iter(|| {
+                    let mut current = frontier.clone();
+                    for &rel in rel_ids {
+                        let mut next = RoaringBitmap::new();
+                        for entity in current.iter() {
+                            next |= db.relations.targets(entity, rel);
+                        }
+                        current = next;
+                    }
+                    black_box(current)
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("batch", hops), &rel_ids, |b, rel_ids| {
+            b.iter(|| {
+                let mut current = frontier.clone();
+                for &rel in rel_ids {
+                    current = db.relations.expand_frontier(&current, rel);
+                }
+                black_box(current)
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("batch_csr", hops),
+            &rel_ids,
+            |b, rel_ids| {
+                b.iter(|| {
+                    let mut current = frontier.clone();
+                    for &rel in rel_ids {
+                        current = frozen.relations.expand_frontier(&current, rel);
+                    }
+                    black_box(current)
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Existence checks for random `(source, target)` pairs, nearly all absent
+/// (the reconciliation workload the edge Bloom filters target).
+fn bench_edge_probe(c: &mut Criterion) {
+    let db = synthetic_db();
+    let rel = db.interner.id_of(REL_TYPES[0]).unwrap();
+    let mut rng = XorShift(0xb10f);
+    let pairs: Vec<(u32, u32)> = (0..10_000)
+        .map(|_| (rng.next() % ENTITIES, rng.next() % ENTITIES))
+        .collect();
+    let mut group = c.benchmark_group("edge_probe");
+    group.throughput(Throughput::Elements(pairs.len() as u64));
+    group.bench_function("has_edge", |b| {
+        b.iter(|| {
+            pairs
+                .iter()
+                .filter(|&&(s, t)| db.relations.has_edge(s, rel, t))
+                .count()
+        })
+    });
+    group.finish();
+}
+
+fn bench_fts(c: &mut Criterion) {
+    let db = synthetic_db();
+    // Warm the lazily built inverted index so we measure queries, not builds.
+    db.entities_with_attr_fts("description", "graph");
+    let mut group = c.benchmark_group("fts");
+    group.bench_function("all_tokens", |b| {
+        b.iter(|| black_box(db.entities_with_attr_fts("description", "proof schema")))
+    });
+    group.bench_function("any_tokens", |b| {
+        b.iter(|| black_box(db.entities_with_attr_fts_any("description", "ontology snapshot")))
+    });
+    group.finish();
+}
+
+fn bench_snapshot_load(c: &mut Criterion) {
+    let bytes = synthetic_db().to_bytes().expect("serialize synthetic db");
+    let mut group = c.benchmark_group("snapshot");
+    group.sample_size(20);
+    group.throughput(Throughput::Bytes(bytes.len() as u64));
+    group.bench_function("from_bytes", |b| {
+        b.iter(|| black_box(PathDB::from_bytes(&bytes).expect("load snapshot")))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_interning,
+    bench_bulk_insert,
+    bench_traversal,
+    bench_frontier,
+    bench_edge_probe,
+    bench_fts,
+    bench_snapshot_load
+);
+criterion_main!(benches);
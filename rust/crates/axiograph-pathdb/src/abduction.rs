@@ -0,0 +1,266 @@
+//! Abduction: which missing edges would make a reachability query true?
+//!
+//! "Why isn't `to` reachable from `from`?" has a constructive answer: the
+//! smallest set of edges that, if added, would connect them.
+//! [`PathDB::abduce_reachability`] searches paths that mix existing edges
+//! (free) with hypothetical ones (one unit each), breadth-first by cost, so
+//! the first path that reaches `to` uses the fewest missing edges.
+//!
+//! Hypothetical edges are limited to **plausible** relation types: `a -r-> b`
+//! may be proposed only if the graph already has at least
+//! [`AbductionConfig::min_support`] `r` edges from an entity of `a`'s type to
+//! one of `b`'s type. Meta-plane entities (`AxiMeta*`) are neither traversed
+//! nor used to learn these signatures.
+//!
+//! The result is a hypothesis: proposed edges carry the low
+//! [`AbductionConfig::confidence`] and are never written to the graph; they
+//! are meant to go through the proposals review flow.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{PathDB, StrId};
+
+/// Limits and confidence for [`PathDB::abduce_reachability`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AbductionConfig {
+    /// Relation types to traverse and propose (empty: all).
+    #[serde(default)]
+    pub relations: Vec<String>,
+    /// Give up when more than this many edges would be missing.
+    pub max_missing: usize,
+    /// Confidence attached to proposed edges.
+    pub confidence: f32,
+    /// Existing edges needed with a `(source type, relation, target type)`
+    /// signature before it is plausible.
+    pub min_support: u32,
+}
+
+impl Default for AbductionConfig {
+    fn default() -> Self {
+        Self {
+            relations: Vec::new(),
+            max_missing: 2,
+            confidence: 0.2,
+            min_support: 1,
+        }
+    }
+}
+
+/// A missing edge the abduction proposes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposedEdge {
+    pub source: u32,
+    pub rel_type: String,
+    pub target: u32,
+    pub confidence: f32,
+    /// Existing edges with the same type signature.
+    pub support: u32,
+}
+
+/// One edge of the witness path: existing, or proposed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AbductionStep {
+    pub source: u32,
+    pub rel_type: String,
+    pub target: u32,
+    pub confidence: f32,
+    pub proposed: bool,
+}
+
+/// Result of [`PathDB::abduce_reachability`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Abduction {
+    pub from: u32,
+    pub to: u32,
+    /// The path from `from` to `to` once the proposals are added.
+    pub path: Vec<AbductionStep>,
+    /// The proposed edges on `path`, in order (empty if `to` is already
+    /// reachable).
+    pub proposed: Vec<ProposedEdge>,
+}
+
+impl Abduction {
+    pub fn already_reachable(&self) -> bool {
+        self.proposed.is_empty()
+    }
+}
+
+/// How the search reached an entity.
+#[derive(Clone, Copy)]
+struct Pred {
+    source: u32,
+    rel_type: StrId,
+    confidence: f32,
+    /// Support of the signature, for proposed edges.
+    support: Option<u32>,
+}
+
+impl PathDB {
+    /// The smallest set of plausible missing edges that would make `to`
+    /// reachable from `from` (see the module docs), or `None` if more than
+    /// `config.max_missing` would be needed or either entity is unknown.
+    pub fn abduce_reachability(
+        &self,
+        from: u32,
+        to: u32,
+        config: &AbductionConfig,
+    ) -> Option<Abduction> {
+        self.entities.get_type(from)?;
+        self.entities.get_type(to)?;
+        let allowed: Option<HashSet<StrId>> = (!config.relations.is_empty()).then(|| {
+            config
+                .relations
+                .iter()
+                .filter_map(|r| self.interner.id_of(r))
+                .collect()
+        });
+        let allowed = |rel: StrId| allowed.as_ref().is_none_or(|a| a.contains(&rel));
+        let signatures = self.plausible_signatures(&allowed, config.min_support);
+
+        let mut cost: HashMap<u32, usize> = HashMap::from([(from, 0)]);
+        let mut pred: HashMap<u32, Pred> = HashMap::new();
+        // A `(relation, target type)` family only needs expanding once: the
+        // first expansion happens at the lowest cost any later one could.
+        let mut expanded: HashSet<(StrId, StrId)> = HashSet::new();
+        let mut queue = VecDeque::from([(from, 0usize)]);
+        while let Some((entity, d)) = queue.pop_front() {
+            if cost.get(&entity) != Some(&d) {
+                continue;
+            }
+            if entity == to {
+                break;
+            }
+            for rel in self.relations.outgoing_any(entity) {
+                if !allowed(rel.rel_type) || self.is_meta_entity(rel.target) {
+                    continue;
+                }
+                if cost.get(&rel.target).is_none_or(|&c| c > d) {
+                    cost.insert(rel.target, d);
+                    pred.insert(
+                        rel.target,
+                        Pred {
+                            source: entity,
+                            rel_type: rel.rel_type,
+                            confidence: rel.confidence,
+                            support: None,
+                        },
+                    );
+                    queue.push_front((rel.target, d));
+                }
+            }
+            if d >= config.max_missing {
+                continue;
+            }
+            let Some(families) = self
+                .entities
+                .get_type(entity)
+                .and_then(|t| signatures.get(&t))
+            else {
+                continue;
+            };
+            for (&(rel_type, target_type), &support) in families {
+                if !expanded.insert((rel_type, target_type)) {
+                    continue;
+                }
+                let Some(targets) = self.entities.by_type(target_type) else {
+                    continue;
+                };
+                for target in targets.iter() {
+                    if target == entity || cost.get(&target).is_some_and(|&c| c <= d + 1) {
+                        continue;
+                    }
+                    cost.insert(target, d + 1);
+                    pred.insert(
+                        target,
+                        Pred {
+                            source: entity,
+                            rel_type,
+                            confidence: config.confidence,
+                            support: Some(support),
+                        },
+                    );
+                    queue.push_back((target, d + 1));
+                }
+            }
+        }
+        cost.get(&to)?;
+
+        let mut path = Vec::new();
+        let mut proposed = Vec::new();
+        let mut at = to;
+        while at != from {
+            let p = pred[&at];
+            let rel_type = self.interner.lookup(p.rel_type).unwrap_or_default();
+            if let Some(support) = p.support {
+                proposed.push(ProposedEdge {
+                    source: p.source,
+                    rel_type: rel_type.clone(),
+                    target: at,
+                    confidence: p.confidence,
+                    support,
+                });
+            }
+            path.push(AbductionStep {
+                source: p.source,
+                rel_type,
+                target: at,
+                confidence: p.confidence,
+                proposed: p.support.is_some(),
+            });
+            at = p.source;
+        }
+        path.reverse();
+        proposed.reverse();
+        Some(Abduction {
+            from,
+            to,
+            path,
+            proposed,
+        })
+    }
+
+    /// `source type -> (relation, target type) -> edge count` over data-plane
+    /// edges of allowed relation types, keeping signatures with enough support.
+    fn plausible_signatures(
+        &self,
+        allowed: &dyn Fn(StrId) -> bool,
+        min_support: u32,
+    ) -> HashMap<StrId, BTreeMap<(StrId, StrId), u32>> {
+        let mut counts: HashMap<StrId, BTreeMap<(StrId, StrId), u32>> = HashMap::new();
+        for id in 0..self.relations.len() as u32 {
+            let Some(rel) = self.relations.get_relation(id) else {
+                continue;
+            };
+            if !allowed(rel.rel_type)
+                || self.is_meta_entity(rel.source)
+                || self.is_meta_entity(rel.target)
+            {
+                continue;
+            }
+            let (Some(s), Some(t)) = (
+                self.entities.get_type(rel.source),
+                self.entities.get_type(rel.target),
+            ) else {
+                continue;
+            };
+            *counts
+                .entry(s)
+                .or_default()
+                .entry((rel.rel_type, t))
+                .or_default() += 1;
+        }
+        for families in counts.values_mut() {
+            families.retain(|_, n| *n >= min_support);
+        }
+        counts
+    }
+
+    fn is_meta_entity(&self, entity: u32) -> bool {
+        self.entities
+            .get_type(entity)
+            .and_then(|t| self.interner.lookup(t))
+            .is_some_and(|t| t.starts_with("AxiMeta"))
+    }
+}
@@ -0,0 +1,341 @@
+//! Graph analytics over the relation store: centrality and communities.
+//!
+//! - personalized PageRank (confidence-weighted, directed),
+//! - approximate betweenness (Brandes from a deterministic pivot sample),
+//! - label-propagation communities (undirected, deterministic tie-breaks).
+//!
+//! Results are plain per-entity vectors (indexed by entity id) and can be
+//! written back onto entities as attributes ([`write_analytics_attrs`]) so
+//! grounding can prefer central entities and the UI can color communities.
+//!
+//! These are **heuristics** for ranking/discovery; they are not part of the
+//! certified query core.
+
+use crate::error::Result;
+use crate::{PathDB, StrId};
+use roaring::RoaringBitmap;
+use std::collections::{BTreeMap, VecDeque};
+
+pub const ATTR_PAGERANK: &str = "analytics_pagerank";
+pub const ATTR_BETWEENNESS: &str = "analytics_betweenness";
+pub const ATTR_COMMUNITY: &str = "analytics_community";
+
+/// Weighted adjacency snapshot used by the analytics passes.
+#[derive(Debug, Clone)]
+pub struct AnalyticsGraph {
+    /// Outgoing `(target, weight)` lists, indexed by entity id.
+    out_edges: Vec<Vec<(u32, f64)>>,
+    /// Entities incident to at least one selected edge.
+    active: RoaringBitmap,
+}
+
+impl AnalyticsGraph {
+    /// Build from `db`, optionally restricted to `rel_types`.
+    ///
+    /// Edge weights are relation confidences (clamped to `[0, 1]`); parallel
+    /// edges accumulate.
+    pub fn from_db(db: &PathDB, rel_types: Option<&[&str]>) -> Self {
+        let filter: Option<Vec<StrId>> =
+            rel_types.map(|names| names.iter().filter_map(|n| db.interner.id_of(n)).collect());
+
+        let n = db.entities.len();
+        let mut out: Vec<BTreeMap<u32, f64>> = vec![BTreeMap::new(); n];
+        let mut active = RoaringBitmap::new();
+        for rel in &db.relations.relations {
+            if let Some(f) = &filter {
+                if !f.contains(&rel.rel_type) {
+                    continue;
+                }
+            }
+            if rel.source as usize >= n || rel.target as usize >= n {
+                continue;
+            }
+            *out[rel.source as usize].entry(rel.target).or_insert(0.0) +=
+                f64::from(rel.confidence.clamp(0.0, 1.0));
+            active.insert(rel.source);
+            active.insert(rel.target);
+        }
+
+        Self {
+            out_edges: out.into_iter().map(|m| m.into_iter().collect()).collect(),
+            active,
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.out_edges.len()
+    }
+
+    pub fn active_nodes(&self) -> &RoaringBitmap {
+        &self.active
+    }
+
+    fn undirected_neighbors(&self) -> Vec<Vec<(u32, f64)>> {
+        let mut adj: Vec<BTreeMap<u32, f64>> = vec![BTreeMap::new(); self.node_count()];
+        for (s, edges) in self.out_edges.iter().enumerate() {
+            for &(t, w) in edges {
+                if s as u32 == t {
+                    continue;
+                }
+                *adj[s].entry(t).or_insert(0.0) += w;
+                *adj[t as usize].entry(s as u32).or_insert(0.0) += w;
+            }
+        }
+        adj.into_iter().map(|m| m.into_iter().collect()).collect()
+    }
+}
+
+/// PageRank configuration.
+#[derive(Debug, Clone)]
+pub struct PageRankConfig {
+    pub damping: f64,
+    pub max_iterations: usize,
+    /// L1 convergence threshold.
+    pub tolerance: f64,
+    /// Teleport set for personalized PageRank (uniform over active nodes if empty).
+    pub personalization: Vec<u32>,
+}
+
+impl Default for PageRankConfig {
+    fn default() -> Self {
+        Self {
+            damping: 0.85,
+            max_iterations: 100,
+            tolerance: 1e-9,
+            personalization: Vec::new(),
+        }
+    }
+}
+
+/// (Personalized) PageRank over active nodes. Inactive entities score `0.0`.
+pub fn pagerank(graph: &AnalyticsGraph, config: &PageRankConfig) -> Vec<f64> {
+    let n = graph.node_count();
+    let mut teleport = vec![0.0f64; n];
+    let seeds: Vec<u32> = if config.personalization.is_empty() {
+        graph.active.iter().collect()
+    } else {
+        config
+            .personalization
+            .iter()
+            .copied()
+            .filter(|&id| (id as usize) < n)
+            .collect()
+    };
+    if seeds.is_empty() {
+        return teleport;
+    }
+    for &s in &seeds {
+        teleport[s as usize] += 1.0 / seeds.len() as f64;
+    }
+
+    let out_weight: Vec<f64> = graph
+        .out_edges
+        .iter()
+        .map(|es| es.iter().map(|(_, w)| w).sum())
+        .collect();
+
+    let mut rank = teleport.clone();
+    for _ in 0..config.max_iterations {
+        let mut next = vec![0.0f64; n];
+        let mut dangling = 0.0;
+        for (s, edges) in graph.out_edges.iter().enumerate() {
+            if rank[s] == 0.0 {
+                continue;
+            }
+            if out_weight[s] <= 0.0 {
+                dangling += rank[s];
+                continue;
+            }
+            for &(t, w) in edges {
+                next[t as usize] += config.damping * rank[s] * w / out_weight[s];
+            }
+        }
+        let teleport_mass = (1.0 - config.damping) + config.damping * dangling;
+        for (v, p) in next.iter_mut().zip(&teleport) {
+            *v += teleport_mass * p;
+        }
+
+        let delta: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if delta < config.tolerance {
+            break;
+        }
+    }
+    rank
+}
+
+/// Approximate (directed, unweighted) betweenness centrality.
+///
+/// Runs Brandes' accumulation from `samples` pivots spread evenly over the
+/// active nodes and rescales by `active / samples`. With `samples >= active`
+/// this is exact.
+pub fn betweenness_approx(graph: &AnalyticsGraph, samples: usize) -> Vec<f64> {
+    let n = graph.node_count();
+    let mut centrality = vec![0.0f64; n];
+    let active: Vec<u32> = graph.active.iter().collect();
+    if active.is_empty() || samples == 0 {
+        return centrality;
+    }
+
+    let k = samples.min(active.len());
+    let pivots: Vec<u32> = (0..k).map(|i| active[i * active.len() / k]).collect();
+
+    let mut sigma = vec![0.0f64; n];
+    let mut dist = vec![-1i64; n];
+    let mut delta = vec![0.0f64; n];
+    let mut preds: Vec<Vec<u32>> = vec![Vec::new(); n];
+
+    for &s in &pivots {
+        for &v in &active {
+            let v = v as usize;
+            sigma[v] = 0.0;
+            dist[v] = -1;
+            delta[v] = 0.0;
+            preds[v].clear();
+        }
+        sigma[s as usize] = 1.0;
+        dist[s as usize] = 0;
+
+        let mut stack: Vec<u32> = Vec::new();
+        let mut queue: VecDeque<u32> = VecDeque::from([s]);
+        while let Some(v) = queue.pop_front() {
+            stack.push(v);
+            for &(w, _) in &graph.out_edges[v as usize] {
+                let (vi, wi) = (v as usize, w as usize);
+                if dist[wi] < 0 {
+                    dist[wi] = dist[vi] + 1;
+                    queue.push_back(w);
+                }
+                if dist[wi] == dist[vi] + 1 {
+                    sigma[wi] += sigma[vi];
+                    preds[wi].push(v);
+                }
+            }
+        }
+
+        while let Some(w) = stack.pop() {
+            let wi = w as usize;
+            for &v in &preds[wi] {
+                let vi = v as usize;
+                delta[vi] += sigma[vi] / sigma[wi] * (1.0 + delta[wi]);
+            }
+            if w != s {
+                centrality[wi] += delta[wi];
+            }
+        }
+    }
+
+    let scale = active.len() as f64 / k as f64;
+    for c in &mut centrality {
+        *c *= scale;
+    }
+    centrality
+}
+
+/// Label-propagation communities (undirected, confidence-weighted).
+///
+/// Nodes are visited in id order and adopt the heaviest neighbouring label;
+/// ties keep the current label if it is among the heaviest, otherwise pick the
+/// smallest, so results are deterministic. Returns the
+/// community id per entity (`None` for inactive entities). Community ids are
+/// the smallest member entity id.
+pub fn label_propagation(graph: &AnalyticsGraph, max_iterations: usize) -> Vec<Option<u32>> {
+    let n = graph.node_count();
+    let adj = graph.undirected_neighbors();
+    let mut labels: Vec<u32> = (0..n as u32).collect();
+
+    for _ in 0..max_iterations {
+        let mut changed = false;
+        for v in graph.active.iter() {
+            let neighbors = &adj[v as usize];
+            if neighbors.is_empty() {
+                continue;
+            }
+            let mut weights: BTreeMap<u32, f64> = BTreeMap::new();
+            for &(u, w) in neighbors {
+                *weights.entry(labels[u as usize]).or_insert(0.0) += w;
+            }
+            let current = labels[v as usize];
+            let max_w = weights.values().cloned().fold(0.0, f64::max);
+            let best = if weights.get(&current) == Some(&max_w) {
+                current
+            } else {
+                // BTreeMap order: the first heaviest label is the smallest.
+                weights
+                    .iter()
+                    .find(|(_, &w)| w == max_w)
+                    .map(|(&label, _)| label)
+                    .unwrap_or(current)
+            };
+            if best != current {
+                labels[v as usize] = best;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Canonicalize: community id = smallest member id.
+    let mut canon: BTreeMap<u32, u32> = BTreeMap::new();
+    for v in graph.active.iter() {
+        let e = canon.entry(labels[v as usize]).or_insert(v);
+        *e = (*e).min(v);
+    }
+    (0..n as u32)
+        .map(|v| graph.active.contains(v).then(|| canon[&labels[v as usize]]))
+        .collect()
+}
+
+/// Combined analytics output.
+#[derive(Debug, Clone, Default)]
+pub struct AnalyticsReport {
+    pub pagerank: Vec<f64>,
+    pub betweenness: Vec<f64>,
+    pub communities: Vec<Option<u32>>,
+}
+
+impl AnalyticsReport {
+    /// Run all three passes with default parameters.
+    pub fn compute(graph: &AnalyticsGraph, betweenness_samples: usize) -> Self {
+        Self {
+            pagerank: pagerank(graph, &PageRankConfig::default()),
+            betweenness: betweenness_approx(graph, betweenness_samples),
+            communities: label_propagation(graph, 50),
+        }
+    }
+
+    /// Number of distinct communities.
+    pub fn community_count(&self) -> usize {
+        let mut ids: Vec<u32> = self.communities.iter().flatten().copied().collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids.len()
+    }
+}
+
+/// Write analytics back as entity attributes (`analytics_*`).
+///
+/// Only entities that participate in the analysed graph are annotated.
+pub fn write_analytics_attrs(
+    db: &mut PathDB,
+    graph: &AnalyticsGraph,
+    report: &AnalyticsReport,
+) -> Result<usize> {
+    let mut written = 0;
+    for v in graph.active.iter() {
+        let i = v as usize;
+        if let Some(pr) = report.pagerank.get(i) {
+            db.upsert_entity_attr(v, ATTR_PAGERANK, &format!("{pr:.6}"))?;
+        }
+        if let Some(b) = report.betweenness.get(i) {
+            db.upsert_entity_attr(v, ATTR_BETWEENNESS, &format!("{b:.3}"))?;
+        }
+        if let Some(Some(c)) = report.communities.get(i) {
+            db.upsert_entity_attr(v, ATTR_COMMUNITY, &c.to_string())?;
+        }
+        written += 1;
+    }
+    Ok(written)
+}
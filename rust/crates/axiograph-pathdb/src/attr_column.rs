@@ -0,0 +1,206 @@
+//! Sorted attribute columns.
+//!
+//! `EntityStore.attrs` used to be a `HashMap<u32, StrId>` per attribute key:
+//! with hashbrown's control bytes, load factor and per-column growth slack
+//! that is several times the 8 bytes of payload per `(entity, value)` pair,
+//! which dominated memory on large snapshots (60M attributes). An
+//! [`AttrColumn`] keeps the pairs as two parallel vectors sorted by entity
+//! ID and binary-searches them instead:
+//!
+//! - lookups are `O(log n)` over a dense `u32` slice;
+//! - inserts in entity-ID order (the common case: `add_entity` hands out
+//!   increasing IDs) are appends; out-of-order inserts shift the tail;
+//! - iteration is in entity order, so scans are sequential and snapshots are
+//!   deterministic without sorting.
+//!
+//! The API mirrors the `HashMap` subset the stores use, and the serialized
+//! form is the same serde map as before (entries in key order), so `.axpd`
+//! bytes are unchanged.
+//!
+//! The value type is a parameter (default [`StrId`]): list columns
+//! (`list_attrs`) and language-tagged columns (`lang_attrs`) use the same
+//! layout with a `Vec` of items or `(lang, value)` pairs per entity.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use crate::StrId;
+
+/// One attribute column: `entity_id -> value`, sorted by entity ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttrColumn<V = StrId> {
+    entities: Vec<u32>,
+    values: Vec<V>,
+}
+
+impl<V> Default for AttrColumn<V> {
+    fn default() -> Self {
+        Self {
+            entities: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+}
+
+impl<V> AttrColumn<V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    fn position(&self, entity_id: u32) -> Result<usize, usize> {
+        // Fast path for the most recently added entity.
+        match self.entities.last() {
+            Some(&last) if last == entity_id => Ok(self.entities.len() - 1),
+            Some(&last) if last < entity_id => Err(self.entities.len()),
+            _ => self.entities.binary_search(&entity_id),
+        }
+    }
+
+    pub fn get(&self, entity_id: &u32) -> Option<&V> {
+        let i = self.position(*entity_id).ok()?;
+        self.values.get(i)
+    }
+
+    pub fn get_mut(&mut self, entity_id: &u32) -> Option<&mut V> {
+        let i = self.position(*entity_id).ok()?;
+        self.values.get_mut(i)
+    }
+
+    pub fn contains_key(&self, entity_id: &u32) -> bool {
+        self.position(*entity_id).is_ok()
+    }
+
+    /// Set `entity_id`'s value; returns the previous one.
+    pub fn insert(&mut self, entity_id: u32, value: V) -> Option<V> {
+        match self.position(entity_id) {
+            Ok(i) => Some(std::mem::replace(&mut self.values[i], value)),
+            Err(i) => {
+                self.entities.insert(i, entity_id);
+                self.values.insert(i, value);
+                None
+            }
+        }
+    }
+
+    /// `entity_id`'s value, inserting `V::default()` first if it has none.
+    pub fn get_or_insert_default(&mut self, entity_id: u32) -> &mut V
+    where
+        V: Default,
+    {
+        let i = match self.position(entity_id) {
+            Ok(i) => i,
+            Err(i) => {
+                self.entities.insert(i, entity_id);
+                self.values.insert(i, V::default());
+                i
+            }
+        };
+        &mut self.values[i]
+    }
+
+    pub fn remove(&mut self, entity_id: &u32) -> Option<V> {
+        let i = self.position(*entity_id).ok()?;
+        self.entities.remove(i);
+        Some(self.values.remove(i))
+    }
+
+    /// `(entity, value)` pairs in entity order.
+    pub fn iter(&self) -> impl Iterator<Item = (&u32, &V)> + '_ {
+        self.entities.iter().zip(&self.values)
+    }
+
+    /// Entity IDs in order.
+    pub fn keys(&self) -> impl Iterator<Item = &u32> + '_ {
+        self.entities.iter()
+    }
+
+    /// Values in entity order.
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.values.iter()
+    }
+
+    /// Bytes held by the column's own buffers (not by heap-allocated values).
+    pub fn heap_bytes(&self) -> usize {
+        self.entities.capacity() * std::mem::size_of::<u32>()
+            + self.values.capacity() * std::mem::size_of::<V>()
+    }
+}
+
+impl<'a, V> IntoIterator for &'a AttrColumn<V> {
+    type Item = (&'a u32, &'a V);
+    type IntoIter = std::iter::Zip<std::slice::Iter<'a, u32>, std::slice::Iter<'a, V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entities.iter().zip(&self.values)
+    }
+}
+
+impl<V> FromIterator<(u32, V)> for AttrColumn<V> {
+    /// Later pairs win for repeated entities.
+    fn from_iter<I: IntoIterator<Item = (u32, V)>>(iter: I) -> Self {
+        let mut pairs: Vec<(u32, V)> = iter.into_iter().collect();
+        // Stable, so the last of equal entities stays last.
+        pairs.sort_by_key(|&(entity, _)| entity);
+        let mut column = Self {
+            entities: Vec::with_capacity(pairs.len()),
+            values: Vec::with_capacity(pairs.len()),
+        };
+        for (entity, value) in pairs {
+            if column.entities.last() == Some(&entity) {
+                *column.values.last_mut().expect("parallel vectors") = value;
+            } else {
+                column.entities.push(entity);
+                column.values.push(value);
+            }
+        }
+        column
+    }
+}
+
+impl<V: Serialize> Serialize for AttrColumn<V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (entity, value) in self {
+            map.serialize_entry(entity, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, V: Deserialize<'de>> Deserialize<'de> for AttrColumn<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ColumnVisitor<V>(PhantomData<V>);
+
+        impl<'de, V: Deserialize<'de>> Visitor<'de> for ColumnVisitor<V> {
+            type Value = AttrColumn<V>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map from entity id to attribute value")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut access: A,
+            ) -> Result<AttrColumn<V>, A::Error> {
+                let mut pairs = Vec::with_capacity(access.size_hint().unwrap_or(0).min(1 << 16));
+                while let Some(pair) = access.next_entry::<u32, V>()? {
+                    pairs.push(pair);
+                }
+                Ok(pairs.into_iter().collect())
+            }
+        }
+
+        deserializer.deserialize_map(ColumnVisitor(PhantomData))
+    }
+}
@@ -0,0 +1,408 @@
+//! Statistics-driven path index selection.
+//!
+//! The path index used to precompute every signature up to `max_depth` (the
+//! "depth-3 cube"), which is `O(rel_types^3)` signatures and explodes on
+//! graphs with many relation types or high fan-out. Instead, traversals now
+//! feed a [`QueryStats`] log:
+//!
+//! - per relation type: how often queries step along it, and the fan-out
+//!   observed when the traversal actually expanded a frontier;
+//! - per path signature: how often `follow_path` / `FollowPath` asked for it.
+//!
+//! [`PathDB::build_indexes`] then picks signatures under
+//! [`PathIndexPolicy::Auto`] (the default): every single-hop signature, every
+//! logged signature (up to `max_logged_depth`, so hot paths deeper than the
+//! cube get indexed too), and cube signatures ranked by how often their
+//! relation types are queried, while the estimated number of indexed
+//! `(start, target)` pairs stays under `max_entries`. Fan-out comes from the
+//! runtime observations when there are any and from the cardinality sketches
+//! otherwise. [`PathIndexPolicy::Cube`] keeps the old behavior, and
+//! [`PathIndexPolicy::SafeCube`] builds the deepest cube a sampled estimate
+//! says fits.
+//!
+//! The log lives in memory only; [`QueryStatsSnapshot`] can be persisted and
+//! merged back in with [`PathDB::merge_query_stats`].
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use dashmap::mapref::one::Ref;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::cardinality::Direction;
+use crate::index_estimate::SafeCubeConfig;
+use crate::{PathDB, PathSig, StrId};
+
+/// How `build_indexes` chooses path signatures.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "policy", rename_all = "snake_case")]
+pub enum PathIndexPolicy {
+    /// Every signature up to the index depth.
+    Cube,
+    /// Signatures chosen from query statistics under a size budget.
+    Auto(AutoIndexConfig),
+    /// Every signature up to the deepest depth whose sampled size estimate
+    /// fits a budget (see [`crate::index_estimate`]).
+    SafeCube(SafeCubeConfig),
+}
+
+impl Default for PathIndexPolicy {
+    fn default() -> Self {
+        Self::Auto(AutoIndexConfig::default())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutoIndexConfig {
+    /// Budget on the estimated number of indexed `(start, target)` pairs
+    /// (single-hop signatures are always indexed and not counted).
+    pub max_entries: u64,
+    /// Logged signatures need at least this many queries to be indexed.
+    pub min_path_hits: u64,
+    /// Longest logged signature worth indexing (may exceed the cube depth).
+    pub max_logged_depth: usize,
+}
+
+impl Default for AutoIndexConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 5_000_000,
+            min_path_hits: 2,
+            max_logged_depth: 6,
+        }
+    }
+}
+
+/// Runtime counters for one relation type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelTypeStats {
+    /// Query steps along this relation type.
+    pub queries: u64,
+    /// Entities expanded along it by non-indexed traversals.
+    pub expanded: u64,
+    /// Targets those expansions produced.
+    pub targets: u64,
+}
+
+impl RelTypeStats {
+    /// Observed targets per expanded entity, if anything was expanded.
+    pub fn observed_fanout(&self) -> Option<f64> {
+        (self.expanded > 0).then(|| self.targets as f64 / self.expanded as f64)
+    }
+}
+
+/// A serializable copy of the query log, keyed by relation names.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QueryStatsSnapshot {
+    pub rel_types: BTreeMap<String, RelTypeStats>,
+    /// Path signatures (relation names) -> query count.
+    pub paths: Vec<(Vec<String>, u64)>,
+}
+
+/// Query log shared by concurrent readers of a [`PathDB`].
+///
+/// Sharded maps of atomic counters: recording a step only takes a shard read
+/// lock, unless the relation type or signature is new.
+#[derive(Debug, Default)]
+pub(crate) struct QueryStats {
+    rel_types: DashMap<StrId, RelTypeCounters>,
+    paths: DashMap<PathSig, AtomicU64>,
+}
+
+#[derive(Debug, Default)]
+struct RelTypeCounters {
+    queries: AtomicU64,
+    expanded: AtomicU64,
+    targets: AtomicU64,
+}
+
+impl RelTypeCounters {
+    fn load(&self) -> RelTypeStats {
+        RelTypeStats {
+            queries: self.queries.load(Ordering::Relaxed),
+            expanded: self.expanded.load(Ordering::Relaxed),
+            targets: self.targets.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A point-in-time copy of [`QueryStats`].
+#[derive(Debug, Default, Clone)]
+struct QueryStatsData {
+    rel_types: HashMap<StrId, RelTypeStats>,
+    paths: HashMap<PathSig, u64>,
+}
+
+impl QueryStats {
+    fn rel_type(&self, rel_type: StrId) -> Ref<'_, StrId, RelTypeCounters> {
+        if let Some(counters) = self.rel_types.get(&rel_type) {
+            return counters;
+        }
+        self.rel_types.entry(rel_type).or_default().downgrade()
+    }
+
+    fn add_path_hits(&self, path: &PathSig, hits: u64) {
+        if let Some(count) = self.paths.get(path) {
+            count.fetch_add(hits, Ordering::Relaxed);
+            return;
+        }
+        self.paths
+            .entry(path.clone())
+            .or_default()
+            .fetch_add(hits, Ordering::Relaxed);
+    }
+
+    /// One query for `path` (a single-hop lookup is a path of length 1).
+    pub(crate) fn record_path(&self, path: &PathSig) {
+        for &rel_type in &path.0 {
+            self.rel_type(rel_type)
+                .queries
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        if path.len() > 1 {
+            self.add_path_hits(path, 1);
+        }
+    }
+
+    /// A traversal expanded `expanded` entities along `rel_type` into
+    /// `targets` entities.
+    pub(crate) fn record_expansion(&self, rel_type: StrId, expanded: u64, targets: u64) {
+        let counters = self.rel_type(rel_type);
+        counters.expanded.fetch_add(expanded, Ordering::Relaxed);
+        counters.targets.fetch_add(targets, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> QueryStatsData {
+        QueryStatsData {
+            rel_types: self
+                .rel_types
+                .iter()
+                .map(|entry| (*entry.key(), entry.value().load()))
+                .collect(),
+            paths: self
+                .paths
+                .iter()
+                .map(|entry| (entry.key().clone(), entry.value().load(Ordering::Relaxed)))
+                .collect(),
+        }
+    }
+
+    fn clear(&self) {
+        self.rel_types.clear();
+        self.paths.clear();
+    }
+}
+
+/// Why a signature was picked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexReason {
+    SingleHop,
+    Logged,
+    Cube,
+}
+
+/// One signature chosen for the path index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedSignature {
+    pub path: Vec<String>,
+    pub estimated_entries: u64,
+    pub reason: IndexReason,
+}
+
+/// Signatures `build_indexes` will precompute under the auto policy.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IndexPlan {
+    pub signatures: Vec<PlannedSignature>,
+    /// Candidates dropped because they did not fit `max_entries`.
+    pub skipped: usize,
+}
+
+impl PathDB {
+    /// Snapshot of the runtime query log.
+    pub fn query_stats(&self) -> QueryStatsSnapshot {
+        let inner = self.query_stats.snapshot();
+        let name = |id: &StrId| self.interner.lookup(*id).unwrap_or_default();
+        let mut paths: Vec<(Vec<String>, u64)> = inner
+            .paths
+            .iter()
+            .map(|(sig, &hits)| (sig.0.iter().map(name).collect(), hits))
+            .collect();
+        paths.sort();
+        QueryStatsSnapshot {
+            rel_types: inner
+                .rel_types
+                .iter()
+                .map(|(id, stats)| (name(id), *stats))
+                .collect(),
+            paths,
+        }
+    }
+
+    /// Add a (persisted) snapshot to the query log. Unknown relation names
+    /// are ignored.
+    pub fn merge_query_stats(&self, snapshot: &QueryStatsSnapshot) {
+        for (name, stats) in &snapshot.rel_types {
+            let Some(id) = self.interner.id_of(name) else {
+                continue;
+            };
+            let counters = self.query_stats.rel_type(id);
+            counters.queries.fetch_add(stats.queries, Ordering::Relaxed);
+            counters
+                .expanded
+                .fetch_add(stats.expanded, Ordering::Relaxed);
+            counters.targets.fetch_add(stats.targets, Ordering::Relaxed);
+        }
+        for (path, hits) in &snapshot.paths {
+            let ids: Option<Vec<StrId>> = path.iter().map(|n| self.interner.id_of(n)).collect();
+            if let Some(ids) = ids {
+                self.query_stats.add_path_hits(&PathSig::new(ids), *hits);
+            }
+        }
+    }
+
+    pub fn reset_query_stats(&self) {
+        self.query_stats.clear();
+    }
+
+    pub fn path_index_policy(&self) -> &PathIndexPolicy {
+        &self.index_policy
+    }
+
+    /// Takes effect on the next [`Self::build_indexes`].
+    pub fn set_path_index_policy(&mut self, policy: PathIndexPolicy) {
+        self.index_policy = policy;
+    }
+
+    /// The signatures an auto-policy `build_indexes` would precompute with
+    /// `config`, given the current statistics.
+    pub fn plan_path_index(&self, config: &AutoIndexConfig) -> IndexPlan {
+        let stats = self.query_stats.snapshot();
+        let max_depth = self.path_index.max_depth();
+        let mut plan = IndexPlan::default();
+        if max_depth == 0 {
+            return plan;
+        }
+        let rel_types: Vec<StrId> = {
+            let mut ids: Vec<StrId> = self.relations.type_index.keys().copied().collect();
+            ids.sort();
+            ids
+        };
+        let name = |id: StrId| self.interner.lookup(id).unwrap_or_default();
+        let fanout: HashMap<StrId, f64> = rel_types
+            .iter()
+            .map(|&id| {
+                let observed = stats
+                    .rel_types
+                    .get(&id)
+                    .and_then(RelTypeStats::observed_fanout);
+                let fanout =
+                    observed.unwrap_or_else(|| self.estimate_fanout(&name(id), Direction::Forward));
+                (id, fanout)
+            })
+            .collect();
+        let sources: HashMap<StrId, f64> = rel_types
+            .iter()
+            .map(|&id| {
+                let count = self.estimate_endpoint_count(&name(id), Direction::Forward);
+                (id, count as f64)
+            })
+            .collect();
+        let entities = self.entities.len().max(1) as f64;
+        let estimate = |sig: &[StrId]| -> u64 {
+            let sources = sources.get(&sig[0]).copied().unwrap_or(0.0);
+            let pairs = sig.iter().fold(sources, |acc, id| {
+                acc * fanout.get(id).copied().unwrap_or(0.0)
+            });
+            pairs.min(sources * entities).ceil() as u64
+        };
+        let queries = |sig: &[StrId]| -> u64 {
+            sig.iter()
+                .map(|id| stats.rel_types.get(id).map_or(0, |s| s.queries))
+                .sum()
+        };
+
+        for &id in &rel_types {
+            plan.signatures.push(PlannedSignature {
+                path: vec![name(id)],
+                estimated_entries: estimate(&[id]),
+                reason: IndexReason::SingleHop,
+            });
+        }
+
+        // Logged signatures first (most queried first), then the cube ranked
+        // by how hot its relation types are; cheaper wins ties.
+        let mut candidates: Vec<(IndexReason, u64, u64, Vec<StrId>)> = Vec::new();
+        let mut seen: HashSet<Vec<StrId>> = HashSet::new();
+        for (sig, &hits) in &stats.paths {
+            let known = sig.0.iter().all(|id| fanout.contains_key(id));
+            if hits >= config.min_path_hits
+                && known
+                && (2..=config.max_logged_depth).contains(&sig.len())
+                && seen.insert(sig.0.clone())
+            {
+                candidates.push((IndexReason::Logged, hits, estimate(&sig.0), sig.0.clone()));
+            }
+        }
+        let mut frontier: Vec<Vec<StrId>> = rel_types.iter().map(|&id| vec![id]).collect();
+        for _ in 2..=max_depth {
+            let mut next = Vec::new();
+            for prefix in &frontier {
+                for &id in &rel_types {
+                    let mut sig = prefix.clone();
+                    sig.push(id);
+                    let cost = estimate(&sig);
+                    if cost == 0 || cost > config.max_entries {
+                        if cost > 0 {
+                            plan.skipped += 1;
+                        }
+                        continue;
+                    }
+                    if seen.insert(sig.clone()) {
+                        candidates.push((IndexReason::Cube, queries(&sig), cost, sig.clone()));
+                    }
+                    next.push(sig);
+                }
+            }
+            frontier = next;
+        }
+        candidates.sort_by(|a, b| {
+            (a.0 == IndexReason::Cube)
+                .cmp(&(b.0 == IndexReason::Cube))
+                .then(b.1.cmp(&a.1))
+                .then(a.2.cmp(&b.2))
+                .then(a.3.cmp(&b.3))
+        });
+
+        let mut used = 0_u64;
+        for (reason, _, cost, sig) in candidates {
+            if used.saturating_add(cost) > config.max_entries {
+                plan.skipped += 1;
+                continue;
+            }
+            used += cost;
+            plan.signatures.push(PlannedSignature {
+                path: sig.into_iter().map(name).collect(),
+                estimated_entries: cost,
+                reason,
+            });
+        }
+        plan
+    }
+
+    /// `(rel_type ids)` of every planned signature.
+    pub(crate) fn planned_signatures(&self, plan: &IndexPlan) -> Vec<PathSig> {
+        plan.signatures
+            .iter()
+            .filter_map(|planned| {
+                planned
+                    .path
+                    .iter()
+                    .map(|name| self.interner.id_of(name))
+                    .collect::<Option<Vec<_>>>()
+                    .map(PathSig::new)
+            })
+            .collect()
+    }
+}
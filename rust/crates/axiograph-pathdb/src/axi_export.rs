@@ -8,6 +8,8 @@
 //! - preserves **entity ids**, **relation ids**, and **string ids**,
 //! - avoids floats by storing confidences as **IEEE-754 bit patterns**,
 //! - stores interned strings as **UTF-8 hex** (so it can represent any text),
+//! - records the typed reading (`TypedLiteralV1`) of numeric/boolean attribute
+//!   values, so the snapshot digest pins those literals too,
 //! - is intended as an *engineering interchange* format (not a user-facing DSL).
 //!
 //! The goal is faithful round-tripping:
@@ -16,15 +18,26 @@
 //!   PathDB (.axpd) → export (.axi) → import (.axi) → PathDB (.axpd)
 //! ```
 //!
+//! The one exception: instance assignments are sets, so repeated identical
+//! equivalence entries come back as one (see
+//! `tests/format_roundtrip_property_tests.rs`).
+//!
 //! NOTE: This export format is distinct from the domain `.axi` examples (like
 //! `EconomicFlows.axi`). Those files are canonical *source*; this schema is a
 //! stable "snapshot rendering" of the derived PathDB state.
 
+use crate::certificate::TypedLiteralV1;
+use crate::error::{PathDbError, Result};
 use crate::{PathDB, StrId, StringInterner};
-use anyhow::{anyhow, Result};
 use axiograph_dsl::schema_v1::{parse_schema_v1, SchemaV1Instance, SchemaV1Module, SetItemV1};
-use std::collections::BTreeSet;
-use std::sync::atomic::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A [`PathDbError::InvalidExport`] with a formatted reason.
+macro_rules! invalid {
+    ($($arg:tt)*) => {
+        PathDbError::InvalidExport(format!($($arg)*))
+    };
+}
 
 pub const PATHDB_EXPORT_MODULE_NAME_V1: &str = "PathDBExport";
 pub const PATHDB_EXPORT_SCHEMA_NAME_V1: &str = "PathDBExportV1";
@@ -36,6 +49,9 @@ const OBJ_RELATION: &str = "Relation";
 const OBJ_INTERNED_STRING_ID: &str = "InternedStringId";
 const OBJ_UTF8_STRING: &str = "Utf8String";
 const OBJ_FLOAT32_BITS: &str = "Float32Bits";
+const OBJ_TYPED_LITERAL: &str = "TypedLiteral";
+const OBJ_LIST_POSITION: &str = "ListPosition";
+const OBJ_BLOB_BYTES: &str = "BlobBytes";
 
 // Relations
 const REL_INTERNED_STRING: &str = "interned_string";
@@ -44,6 +60,10 @@ const REL_ENTITY_ATTRIBUTE: &str = "entity_attribute";
 const REL_RELATION_INFO: &str = "relation_info";
 const REL_RELATION_ATTRIBUTE: &str = "relation_attribute";
 const REL_EQUIVALENCE: &str = "equivalence";
+const REL_TYPED_VALUE: &str = "typed_value";
+const REL_ENTITY_LIST_ITEM: &str = "entity_list_item";
+const REL_ENTITY_LANG_ATTRIBUTE: &str = "entity_lang_attribute";
+const REL_BLOB_CONTENT: &str = "blob_content";
 
 // Token prefixes
 const PREFIX_ENTITY: &str = "Entity_";
@@ -51,6 +71,11 @@ const PREFIX_RELATION: &str = "Relation_";
 const PREFIX_STRING_ID: &str = "StringId_";
 const PREFIX_STR_UTF8_HEX: &str = "StrUtf8Hex_";
 const PREFIX_F32_HEX: &str = "F32Hex_";
+const PREFIX_LIST_POSITION: &str = "Pos_";
+const PREFIX_BLOB_HEX: &str = "BlobHex_";
+const PREFIX_LIT_INT: &str = "LitInt_";
+const PREFIX_LIT_DEC: &str = "LitDec_";
+const PREFIX_LIT_BOOL: &str = "LitBool_";
 
 fn token_u32(prefix: &str, value: u32) -> String {
     format!("{prefix}{value}")
@@ -59,37 +84,47 @@ fn token_u32(prefix: &str, value: u32) -> String {
 fn parse_token_u32(prefix: &str, token: &str) -> Result<u32> {
     let rest = token
         .strip_prefix(prefix)
-        .ok_or_else(|| anyhow!("expected token prefix `{prefix}`, got `{token}`"))?;
+        .ok_or_else(|| invalid!("expected token prefix `{prefix}`, got `{token}`"))?;
     rest.parse::<u32>()
-        .map_err(|e| anyhow!("invalid u32 in token `{token}`: {e}"))
+        .map_err(|e| invalid!("invalid u32 in token `{token}`: {e}"))
 }
 
 fn encode_utf8_hex(s: &str) -> String {
-    let mut hex = String::with_capacity(s.len() * 2);
-    for b in s.as_bytes() {
+    encode_hex(PREFIX_STR_UTF8_HEX, s.as_bytes())
+}
+
+fn decode_utf8_hex(token: &str) -> Result<String> {
+    let bytes = decode_hex(PREFIX_STR_UTF8_HEX, token)?;
+    String::from_utf8(bytes).map_err(|e| invalid!("invalid UTF-8 in `{token}`: {e}"))
+}
+
+fn encode_hex(prefix: &str, bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(prefix.len() + bytes.len() * 2);
+    hex.push_str(prefix);
+    for b in bytes {
         use std::fmt::Write as _;
         let _ = write!(&mut hex, "{:02x}", b);
     }
-    format!("{PREFIX_STR_UTF8_HEX}{hex}")
+    hex
 }
 
-fn decode_utf8_hex(token: &str) -> Result<String> {
+fn decode_hex(prefix: &str, token: &str) -> Result<Vec<u8>> {
     let hex = token
-        .strip_prefix(PREFIX_STR_UTF8_HEX)
-        .ok_or_else(|| anyhow!("expected `{PREFIX_STR_UTF8_HEX}...`, got `{token}`"))?;
+        .strip_prefix(prefix)
+        .ok_or_else(|| invalid!("expected `{prefix}...`, got `{token}`"))?;
     if hex.len() % 2 != 0 {
-        return Err(anyhow!("utf8 hex token has odd length: `{token}`"));
+        return Err(invalid!("hex token has odd length: `{token}`"));
     }
     let mut bytes = Vec::with_capacity(hex.len() / 2);
     let mut i = 0usize;
     while i < hex.len() {
         let chunk = &hex[i..i + 2];
         let b = u8::from_str_radix(chunk, 16)
-            .map_err(|e| anyhow!("invalid hex byte `{chunk}` in `{token}`: {e}"))?;
+            .map_err(|e| invalid!("invalid hex byte `{chunk}` in `{token}`: {e}"))?;
         bytes.push(b);
         i += 2;
     }
-    String::from_utf8(bytes).map_err(|e| anyhow!("invalid UTF-8 in `{token}`: {e}"))
+    Ok(bytes)
 }
 
 fn encode_f32_bits(x: f32) -> String {
@@ -99,18 +134,100 @@ fn encode_f32_bits(x: f32) -> String {
 fn decode_f32_bits(token: &str) -> Result<f32> {
     let hex = token
         .strip_prefix(PREFIX_F32_HEX)
-        .ok_or_else(|| anyhow!("expected `{PREFIX_F32_HEX}...`, got `{token}`"))?;
+        .ok_or_else(|| invalid!("expected `{PREFIX_F32_HEX}...`, got `{token}`"))?;
     if hex.len() != 8 {
-        return Err(anyhow!(
+        return Err(invalid!(
             "expected 8 hex digits for f32 bits, got {} in `{token}`",
             hex.len()
         ));
     }
     let bits =
-        u32::from_str_radix(hex, 16).map_err(|e| anyhow!("invalid f32 bits `{token}`: {e}"))?;
+        u32::from_str_radix(hex, 16).map_err(|e| invalid!("invalid f32 bits `{token}`: {e}"))?;
     Ok(f32::from_bits(bits))
 }
 
+/// Signed integers in tokens: `m` marks negatives (`-7` → `m7`).
+fn encode_signed(n: i64) -> String {
+    if n < 0 {
+        format!("m{}", n.unsigned_abs())
+    } else {
+        n.to_string()
+    }
+}
+
+fn decode_signed(s: &str, token: &str) -> Result<i64> {
+    let parsed = match s.strip_prefix('m') {
+        Some(rest) => rest.parse::<i64>().map(|n| -n),
+        None => s.parse::<i64>(),
+    };
+    parsed.map_err(|e| invalid!("invalid integer in token `{token}`: {e}"))
+}
+
+/// `TypedLiteral` token plus its unit (empty when unitless).
+///
+/// Ints are `LitInt_<n>`, decimals `LitDec_<mantissa>_<exponent>`, booleans
+/// `LitBool_true`/`LitBool_false`. String literals are never tokenized.
+fn encode_typed_literal(lit: &TypedLiteralV1) -> Option<(String, String)> {
+    let token = match lit {
+        TypedLiteralV1::Int { value, .. } => format!("{PREFIX_LIT_INT}{}", encode_signed(*value)),
+        TypedLiteralV1::Float {
+            mantissa, exponent, ..
+        } => format!(
+            "{PREFIX_LIT_DEC}{}_{}",
+            encode_signed(*mantissa),
+            encode_signed(*exponent as i64)
+        ),
+        TypedLiteralV1::Bool { value } => format!("{PREFIX_LIT_BOOL}{value}"),
+        TypedLiteralV1::String { .. } => return None,
+    };
+    Some((token, encode_utf8_hex(lit.unit().unwrap_or(""))))
+}
+
+fn decode_typed_literal(token: &str, unit_tok: &str) -> Result<TypedLiteralV1> {
+    let unit = Some(decode_utf8_hex(unit_tok)?).filter(|u| !u.is_empty());
+    if let Some(n) = token.strip_prefix(PREFIX_LIT_INT) {
+        return Ok(TypedLiteralV1::Int {
+            value: decode_signed(n, token)?,
+            unit,
+        });
+    }
+    if let Some(rest) = token.strip_prefix(PREFIX_LIT_DEC) {
+        let (mantissa, exponent) = rest.split_once('_').ok_or_else(|| {
+            invalid!("expected `{PREFIX_LIT_DEC}<mantissa>_<exponent>`, got `{token}`")
+        })?;
+        let exponent = i32::try_from(decode_signed(exponent, token)?)
+            .map_err(|e| invalid!("exponent out of range in `{token}`: {e}"))?;
+        return Ok(TypedLiteralV1::Float {
+            mantissa: decode_signed(mantissa, token)?,
+            exponent,
+            unit,
+        });
+    }
+    match token.strip_prefix(PREFIX_LIT_BOOL) {
+        Some("true") if unit.is_none() => Ok(TypedLiteralV1::Bool { value: true }),
+        Some("false") if unit.is_none() => Ok(TypedLiteralV1::Bool { value: false }),
+        _ => Err(invalid!("invalid typed literal token `{token}`")),
+    }
+}
+
+/// Attribute value ids whose typed reading is not a plain string.
+fn typed_value_rows(db: &PathDB, strings: &[String]) -> BTreeSet<(u32, String, String)> {
+    let entity_values = db.entities.attrs.values().flat_map(|col| col.values());
+    let relation_values = db
+        .relations
+        .relations
+        .iter()
+        .flat_map(|rel| rel.attrs.iter().map(|(_, v)| v));
+    entity_values
+        .chain(relation_values)
+        .filter_map(|value_id| {
+            let raw = strings.get(value_id.raw() as usize)?;
+            let (token, unit) = encode_typed_literal(&TypedLiteralV1::infer(raw))?;
+            Some((value_id.raw(), token, unit))
+        })
+        .collect()
+}
+
 fn format_set(name: &str, items: &[String]) -> String {
     if items.is_empty() {
         return format!("  {name} = {{}}\n");
@@ -151,7 +268,7 @@ fn find_export_instance<'a>(m: &'a SchemaV1Module) -> Result<&'a SchemaV1Instanc
         .iter()
         .find(|i| i.schema == PATHDB_EXPORT_SCHEMA_NAME_V1)
         .ok_or_else(|| {
-            anyhow!(
+            invalid!(
                 "no instance `... of {}` found (this importer expects the PathDB export schema)",
                 PATHDB_EXPORT_SCHEMA_NAME_V1
             )
@@ -163,7 +280,7 @@ fn get_assignment<'a>(inst: &'a SchemaV1Instance, name: &str) -> Result<&'a [Set
         .assignments
         .iter()
         .find(|a| a.name == name)
-        .ok_or_else(|| anyhow!("missing assignment `{name} = {{...}}` in export instance"))?;
+        .ok_or_else(|| invalid!("missing assignment `{name} = {{...}}` in export instance"))?;
     Ok(&assignment.value.items)
 }
 
@@ -173,7 +290,7 @@ fn parse_ident_set(items: &[SetItemV1]) -> Result<Vec<String>> {
         match item {
             SetItemV1::Ident { name } => out.push(name.clone()),
             SetItemV1::Tuple { .. } => {
-                return Err(anyhow!("expected identifier set items, found tuple"));
+                return Err(invalid!("expected identifier set items, found tuple"));
             }
         }
     }
@@ -186,7 +303,7 @@ fn parse_tuple_set(items: &[SetItemV1]) -> Result<Vec<Vec<(String, String)>>> {
         match item {
             SetItemV1::Tuple { fields } => out.push(fields.clone()),
             SetItemV1::Ident { .. } => {
-                return Err(anyhow!("expected tuple set items, found identifier"));
+                return Err(invalid!("expected tuple set items, found identifier"));
             }
         }
     }
@@ -197,7 +314,7 @@ fn tuple_field<'a>(fields: &'a [(String, String)], key: &str) -> Result<&'a str>
     fields
         .iter()
         .find_map(|(k, v)| (k == key).then_some(v.as_str()))
-        .ok_or_else(|| anyhow!("tuple missing field `{key}`"))
+        .ok_or_else(|| invalid!("tuple missing field `{key}`"))
 }
 
 fn require_contiguous_ids(prefix: &str, tokens: &[String]) -> Result<u32> {
@@ -211,11 +328,11 @@ fn require_contiguous_ids(prefix: &str, tokens: &[String]) -> Result<u32> {
         return Ok(0);
     }
     if ids[0] != 0 {
-        return Err(anyhow!("expected `{prefix}0` to be present"));
+        return Err(invalid!("expected `{prefix}0` to be present"));
     }
     for (expected, got) in (0u32..).zip(ids.iter().copied()) {
         if expected != got {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "expected contiguous ids `{prefix}0..{prefix}N`, missing `{prefix}{expected}`"
             ));
         }
@@ -227,7 +344,7 @@ fn add_interned_strings(interner: &StringInterner, strings: &[String]) -> Result
     for (expected, s) in strings.iter().enumerate() {
         let got = interner.intern(s);
         if got.raw() != expected as u32 {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "interner id mismatch: expected {expected}, got {} for string `{s}`",
                 got.raw()
             ));
@@ -238,17 +355,24 @@ fn add_interned_strings(interner: &StringInterner, strings: &[String]) -> Result
 
 /// Export a PathDB snapshot as `.axi` (schema_v1) using the `PathDBExportV1` schema.
 pub fn export_pathdb_to_axi_v1(db: &PathDB) -> Result<String> {
+    export_pathdb_to_axi_v1_impl(db, false)
+}
+
+/// `export_pathdb_to_axi_v1` plus a `blob_content` table carrying the
+/// payload of every referenced blob in the sidecar (see `blob_store`).
+pub fn export_pathdb_to_axi_v1_with_blobs(db: &PathDB) -> Result<String> {
+    export_pathdb_to_axi_v1_impl(db, true)
+}
+
+fn export_pathdb_to_axi_v1_impl(db: &PathDB, include_blobs: bool) -> Result<String> {
     // Strings in stable id order (0..next_id).
-    let max = db.interner.next_id.load(Ordering::SeqCst);
+    let max = db.interner.len() as u32;
     let mut strings: Vec<String> = Vec::with_capacity(max as usize);
     for raw in 0..max {
-        let Some(value) = db
-            .interner
-            .id_to_str
-            .get(&StrId::new(raw))
-            .map(|s| s.clone())
-        else {
-            return Err(anyhow!("missing interned string for id {raw}"));
+        let Some(value) = db.interner.lookup(StrId::new(raw)) else {
+            return Err(PathDbError::Corrupt(format!(
+                "missing interned string for id {raw}"
+            )));
         };
         strings.push(value);
     }
@@ -285,7 +409,9 @@ pub fn export_pathdb_to_axi_v1(db: &PathDB) -> Result<String> {
     let mut entity_type_tuples: Vec<String> = Vec::with_capacity(entity_count as usize);
     for entity_id in 0..entity_count {
         let Some(type_id) = db.entities.types.get(entity_id as usize).copied() else {
-            return Err(anyhow!("missing type for entity id {entity_id}"));
+            return Err(PathDbError::Corrupt(format!(
+                "missing type for entity id {entity_id}"
+            )));
         };
         entity_type_tuples.push(tuple(&[
             ("entity", token_u32(PREFIX_ENTITY, entity_id)),
@@ -312,6 +438,88 @@ pub fn export_pathdb_to_axi_v1(db: &PathDB) -> Result<String> {
         })
         .collect();
 
+    // Relations: entity_list_item (only emitted when some entity has a list
+    // attribute, so exports without lists are unchanged).
+    let mut list_item_rows: Vec<(u32, u32, u32, u32)> = Vec::new();
+    for (key_id, rows) in db.entities.list_section() {
+        for (entity_id, values) in rows {
+            for (position, value_id) in values.into_iter().enumerate() {
+                list_item_rows.push((entity_id, key_id.raw(), position as u32, value_id.raw()));
+            }
+        }
+    }
+    list_item_rows.sort_unstable();
+    let max_position = list_item_rows
+        .iter()
+        .map(|row| row.2 + 1)
+        .max()
+        .unwrap_or(0);
+    let position_tokens: Vec<String> = (0..max_position)
+        .map(|i| token_u32(PREFIX_LIST_POSITION, i))
+        .collect();
+    let entity_list_item_tuples: Vec<String> = list_item_rows
+        .into_iter()
+        .map(|(entity_id, key_id, position, value_id)| {
+            tuple(&[
+                ("entity", token_u32(PREFIX_ENTITY, entity_id)),
+                ("key_id", token_u32(PREFIX_STRING_ID, key_id)),
+                ("position", token_u32(PREFIX_LIST_POSITION, position)),
+                ("value_id", token_u32(PREFIX_STRING_ID, value_id)),
+            ])
+        })
+        .collect();
+    let has_lists = !entity_list_item_tuples.is_empty();
+
+    // Relations: entity_lang_attribute (only emitted when some entity has a
+    // language-tagged attribute).
+    let mut lang_rows: Vec<(u32, u32, u32, u32)> = Vec::new();
+    for (key_id, rows) in db.entities.lang_section() {
+        for (entity_id, values) in rows {
+            for (lang_id, value_id) in values {
+                lang_rows.push((entity_id, key_id.raw(), lang_id.raw(), value_id.raw()));
+            }
+        }
+    }
+    lang_rows.sort_unstable();
+    let entity_lang_attribute_tuples: Vec<String> = lang_rows
+        .into_iter()
+        .map(|(entity_id, key_id, lang_id, value_id)| {
+            tuple(&[
+                ("entity", token_u32(PREFIX_ENTITY, entity_id)),
+                ("key_id", token_u32(PREFIX_STRING_ID, key_id)),
+                ("lang_id", token_u32(PREFIX_STRING_ID, lang_id)),
+                ("value_id", token_u32(PREFIX_STRING_ID, value_id)),
+            ])
+        })
+        .collect();
+    let has_langs = !entity_lang_attribute_tuples.is_empty();
+
+    // Relations: blob_content (only with `include_blobs`; references are
+    // interned attribute values, payloads are hex tokens).
+    let mut blob_tokens: Vec<String> = Vec::new();
+    let mut blob_content_tuples: Vec<String> = Vec::new();
+    if include_blobs {
+        for reference in db.referenced_blobs() {
+            let (Some(bytes), Some(reference_id)) =
+                (db.blob(&reference), db.interner.id_of(&reference))
+            else {
+                continue;
+            };
+            let token = encode_hex(PREFIX_BLOB_HEX, bytes);
+            blob_content_tuples.push(tuple(&[
+                (
+                    "reference_id",
+                    token_u32(PREFIX_STRING_ID, reference_id.raw()),
+                ),
+                ("bytes", token.clone()),
+            ]));
+            blob_tokens.push(token);
+        }
+        blob_tokens.sort();
+        blob_tokens.dedup();
+    }
+    let has_blobs = !blob_content_tuples.is_empty();
+
     // Relations: relation_info + relation_attribute
     let mut relation_info_tuples: Vec<String> = Vec::with_capacity(relation_count as usize);
     let mut relation_attr_rows: Vec<(u32, u32, u32)> = Vec::new();
@@ -364,6 +572,31 @@ pub fn export_pathdb_to_axi_v1(db: &PathDB) -> Result<String> {
         })
         .collect();
 
+    // Relations: typed_value (derived from the attribute values above; it is
+    // part of the text, so the snapshot digest covers the typed readings).
+    let typed_rows = typed_value_rows(db, &strings);
+    let literal_tokens: BTreeSet<String> = typed_rows.iter().map(|(_, t, _)| t.clone()).collect();
+    // Units are `Utf8String`s too; add the ones that are not interned values.
+    let known: BTreeSet<&String> = string_value_tokens.iter().collect();
+    let unit_tokens: BTreeSet<String> = typed_rows
+        .iter()
+        .map(|(_, _, u)| u)
+        .filter(|u| !known.contains(u))
+        .cloned()
+        .collect();
+    let mut utf8_tokens = string_value_tokens.clone();
+    utf8_tokens.extend(unit_tokens);
+    let typed_value_tuples: Vec<String> = typed_rows
+        .into_iter()
+        .map(|(value_id, literal, unit)| {
+            tuple(&[
+                ("value_id", token_u32(PREFIX_STRING_ID, value_id)),
+                ("literal", literal),
+                ("unit", unit),
+            ])
+        })
+        .collect();
+
     // ---------------------------------------------------------------------
     // Emit `.axi` text
     // ---------------------------------------------------------------------
@@ -375,6 +608,7 @@ pub fn export_pathdb_to_axi_v1(db: &PathDB) -> Result<String> {
     out.push_str(&format!("  object {OBJ_INTERNED_STRING_ID}\n"));
     out.push_str(&format!("  object {OBJ_UTF8_STRING}\n"));
     out.push_str(&format!("  object {OBJ_FLOAT32_BITS}\n"));
+    out.push_str(&format!("  object {OBJ_TYPED_LITERAL}\n"));
     out.push_str(&format!(
         "  relation {REL_INTERNED_STRING}(interned_id: {OBJ_INTERNED_STRING_ID}, value: {OBJ_UTF8_STRING})\n"
     ));
@@ -393,6 +627,26 @@ pub fn export_pathdb_to_axi_v1(db: &PathDB) -> Result<String> {
     out.push_str(&format!(
         "  relation {REL_EQUIVALENCE}(entity: {OBJ_ENTITY}, other: {OBJ_ENTITY}, equiv_type_id: {OBJ_INTERNED_STRING_ID})\n"
     ));
+    out.push_str(&format!(
+        "  relation {REL_TYPED_VALUE}(value_id: {OBJ_INTERNED_STRING_ID}, literal: {OBJ_TYPED_LITERAL}, unit: {OBJ_UTF8_STRING})\n"
+    ));
+    if has_blobs {
+        out.push_str(&format!("  object {OBJ_BLOB_BYTES}\n"));
+        out.push_str(&format!(
+            "  relation {REL_BLOB_CONTENT}(reference_id: {OBJ_INTERNED_STRING_ID}, bytes: {OBJ_BLOB_BYTES})\n"
+        ));
+    }
+    if has_lists {
+        out.push_str(&format!("  object {OBJ_LIST_POSITION}\n"));
+        out.push_str(&format!(
+            "  relation {REL_ENTITY_LIST_ITEM}(entity: {OBJ_ENTITY}, key_id: {OBJ_INTERNED_STRING_ID}, position: {OBJ_LIST_POSITION}, value_id: {OBJ_INTERNED_STRING_ID})\n"
+        ));
+    }
+    if has_langs {
+        out.push_str(&format!(
+            "  relation {REL_ENTITY_LANG_ATTRIBUTE}(entity: {OBJ_ENTITY}, key_id: {OBJ_INTERNED_STRING_ID}, lang_id: {OBJ_INTERNED_STRING_ID}, value_id: {OBJ_INTERNED_STRING_ID})\n"
+        ));
+    }
     out.push('\n');
 
     out.push_str(&format!(
@@ -401,11 +655,21 @@ pub fn export_pathdb_to_axi_v1(db: &PathDB) -> Result<String> {
     out.push_str(&format_set(OBJ_ENTITY, &entity_tokens));
     out.push_str(&format_set(OBJ_RELATION, &relation_tokens));
     out.push_str(&format_set(OBJ_INTERNED_STRING_ID, &string_id_tokens));
-    out.push_str(&format_set(OBJ_UTF8_STRING, &string_value_tokens));
+    out.push_str(&format_set(OBJ_UTF8_STRING, &utf8_tokens));
     out.push_str(&format_set(
         OBJ_FLOAT32_BITS,
         &float_tokens.into_iter().collect::<Vec<_>>(),
     ));
+    out.push_str(&format_set(
+        OBJ_TYPED_LITERAL,
+        &literal_tokens.into_iter().collect::<Vec<_>>(),
+    ));
+    if has_lists {
+        out.push_str(&format_set(OBJ_LIST_POSITION, &position_tokens));
+    }
+    if has_blobs {
+        out.push_str(&format_set(OBJ_BLOB_BYTES, &blob_tokens));
+    }
     out.push('\n');
     out.push_str(&format_tuple_set(
         REL_INTERNED_STRING,
@@ -422,6 +686,22 @@ pub fn export_pathdb_to_axi_v1(db: &PathDB) -> Result<String> {
         &relation_attribute_tuples,
     ));
     out.push_str(&format_tuple_set(REL_EQUIVALENCE, &equivalence_tuples));
+    out.push_str(&format_tuple_set(REL_TYPED_VALUE, &typed_value_tuples));
+    if has_lists {
+        out.push_str(&format_tuple_set(
+            REL_ENTITY_LIST_ITEM,
+            &entity_list_item_tuples,
+        ));
+    }
+    if has_langs {
+        out.push_str(&format_tuple_set(
+            REL_ENTITY_LANG_ATTRIBUTE,
+            &entity_lang_attribute_tuples,
+        ));
+    }
+    if has_blobs {
+        out.push_str(&format_tuple_set(REL_BLOB_CONTENT, &blob_content_tuples));
+    }
 
     Ok(out)
 }
@@ -429,7 +709,7 @@ pub fn export_pathdb_to_axi_v1(db: &PathDB) -> Result<String> {
 /// Import a PathDB snapshot from `.axi` (schema_v1) in the `PathDBExportV1` schema.
 pub fn import_pathdb_from_axi_v1(text: &str) -> Result<PathDB> {
     let module =
-        parse_schema_v1(text).map_err(|e| anyhow!("failed to parse axi_schema_v1 export: {e}"))?;
+        parse_schema_v1(text).map_err(|e| invalid!("failed to parse axi_schema_v1 export: {e}"))?;
     import_pathdb_from_axi_v1_module(&module)
 }
 
@@ -449,7 +729,7 @@ pub fn import_pathdb_from_axi_v1_module(module: &SchemaV1Module) -> Result<PathD
         let value_tok = tuple_field(&fields, "value")?;
         let sid = parse_token_u32(PREFIX_STRING_ID, sid_tok)?;
         if sid >= string_count {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "interned_string references out-of-range string id {sid} (count={string_count})"
             ));
         }
@@ -459,7 +739,7 @@ pub fn import_pathdb_from_axi_v1_module(module: &SchemaV1Module) -> Result<PathD
     let mut strings: Vec<String> = Vec::with_capacity(string_count as usize);
     for (i, opt) in strings_by_id.into_iter().enumerate() {
         let Some(s) = opt else {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "missing interned_string mapping for {PREFIX_STRING_ID}{i}"
             ));
         };
@@ -480,12 +760,12 @@ pub fn import_pathdb_from_axi_v1_module(module: &SchemaV1Module) -> Result<PathD
         let entity_id = parse_token_u32(PREFIX_ENTITY, entity_tok)?;
         let type_id = parse_token_u32(PREFIX_STRING_ID, type_tok)?;
         if entity_id >= entity_count {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "entity_type references out-of-range entity id {entity_id} (count={entity_count})"
             ));
         }
         if type_id >= string_count {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "entity_type references out-of-range string id {type_id} (count={string_count})"
             ));
         }
@@ -502,12 +782,12 @@ pub fn import_pathdb_from_axi_v1_module(module: &SchemaV1Module) -> Result<PathD
         let key_id = parse_token_u32(PREFIX_STRING_ID, key_tok)?;
         let value_id = parse_token_u32(PREFIX_STRING_ID, value_tok)?;
         if entity_id >= entity_count {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "entity_attribute references out-of-range entity id {entity_id} (count={entity_count})"
             ));
         }
         if key_id >= string_count || value_id >= string_count {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "entity_attribute references out-of-range string id (key={key_id}, value={value_id}, count={string_count})"
             ));
         }
@@ -536,17 +816,17 @@ pub fn import_pathdb_from_axi_v1_module(module: &SchemaV1Module) -> Result<PathD
         let confidence = decode_f32_bits(conf_tok)?;
 
         if rel_id >= relation_count {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "relation_info references out-of-range relation id {rel_id} (count={relation_count})"
             ));
         }
         if rel_type_id >= string_count {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "relation_info references out-of-range string id {rel_type_id} (count={string_count})"
             ));
         }
         if source >= entity_count || target >= entity_count {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "relation_info references out-of-range entity id (source={source}, target={target}, entity_count={entity_count})"
             ));
         }
@@ -564,12 +844,12 @@ pub fn import_pathdb_from_axi_v1_module(module: &SchemaV1Module) -> Result<PathD
         let key_id = parse_token_u32(PREFIX_STRING_ID, key_tok)?;
         let value_id = parse_token_u32(PREFIX_STRING_ID, value_tok)?;
         if rel_id >= relation_count {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "relation_attribute references out-of-range relation id {rel_id} (count={relation_count})"
             ));
         }
         if key_id >= string_count || value_id >= string_count {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "relation_attribute references out-of-range string id (key={key_id}, value={value_id}, count={string_count})"
             ));
         }
@@ -586,12 +866,12 @@ pub fn import_pathdb_from_axi_v1_module(module: &SchemaV1Module) -> Result<PathD
         let e2 = parse_token_u32(PREFIX_ENTITY, other_tok)?;
         let t = parse_token_u32(PREFIX_STRING_ID, equiv_type_tok)?;
         if e1 >= entity_count || e2 >= entity_count {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "equivalence references out-of-range entity id (e1={e1}, e2={e2}, entity_count={entity_count})"
             ));
         }
         if t >= string_count {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "equivalence references out-of-range string id {t} (count={string_count})"
             ));
         }
@@ -606,29 +886,29 @@ pub fn import_pathdb_from_axi_v1_module(module: &SchemaV1Module) -> Result<PathD
 
     for entity_id in 0..entity_count {
         let Some(type_id) = entity_type[entity_id as usize] else {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "missing entity_type row for {PREFIX_ENTITY}{entity_id}"
             ));
         };
         let type_name = strings
             .get(type_id as usize)
-            .ok_or_else(|| anyhow!("missing string for type_id {type_id}"))?;
+            .ok_or_else(|| invalid!("missing string for type_id {type_id}"))?;
 
         let attrs = &entity_attrs[entity_id as usize];
         let mut attrs_str: Vec<(&str, &str)> = Vec::with_capacity(attrs.len());
         for (k, v) in attrs {
             let k = strings
                 .get(*k as usize)
-                .ok_or_else(|| anyhow!("missing string for key_id {k}"))?;
+                .ok_or_else(|| invalid!("missing string for key_id {k}"))?;
             let v = strings
                 .get(*v as usize)
-                .ok_or_else(|| anyhow!("missing string for value_id {v}"))?;
+                .ok_or_else(|| invalid!("missing string for value_id {v}"))?;
             attrs_str.push((k.as_str(), v.as_str()));
         }
 
         let got = db.add_entity(type_name.as_str(), attrs_str);
         if got != entity_id {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "entity id mismatch: expected {entity_id}, got {got} (input must be contiguous and ordered)"
             ));
         }
@@ -636,29 +916,29 @@ pub fn import_pathdb_from_axi_v1_module(module: &SchemaV1Module) -> Result<PathD
 
     for rel_id in 0..relation_count {
         let Some((rel_type_id, source, target, confidence)) = relation_info[rel_id as usize] else {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "missing relation_info row for {PREFIX_RELATION}{rel_id}"
             ));
         };
         let rel_type = strings
             .get(rel_type_id as usize)
-            .ok_or_else(|| anyhow!("missing string for rel_type_id {rel_type_id}"))?;
+            .ok_or_else(|| invalid!("missing string for rel_type_id {rel_type_id}"))?;
 
         let attrs = &relation_attrs[rel_id as usize];
         let mut attrs_str: Vec<(&str, &str)> = Vec::with_capacity(attrs.len());
         for (k, v) in attrs {
             let k = strings
                 .get(*k as usize)
-                .ok_or_else(|| anyhow!("missing string for key_id {k}"))?;
+                .ok_or_else(|| invalid!("missing string for key_id {k}"))?;
             let v = strings
                 .get(*v as usize)
-                .ok_or_else(|| anyhow!("missing string for value_id {v}"))?;
+                .ok_or_else(|| invalid!("missing string for value_id {v}"))?;
             attrs_str.push((k.as_str(), v.as_str()));
         }
 
         let got = db.add_relation(rel_type.as_str(), source, target, confidence, attrs_str);
         if got != rel_id {
-            return Err(anyhow!(
+            return Err(invalid!(
                 "relation id mismatch: expected {rel_id}, got {got} (input must be contiguous and ordered)"
             ));
         }
@@ -674,10 +954,133 @@ pub fn import_pathdb_from_axi_v1_module(module: &SchemaV1Module) -> Result<PathD
         }
         let equiv_type = strings
             .get(t as usize)
-            .ok_or_else(|| anyhow!("missing string for equiv_type_id {t}"))?;
+            .ok_or_else(|| invalid!("missing string for equiv_type_id {t}"))?;
         db.add_equivalence(a, b, equiv_type.as_str());
     }
 
+    // List attributes (absent from exports without lists).
+    if inst
+        .assignments
+        .iter()
+        .any(|a| a.name == REL_ENTITY_LIST_ITEM)
+    {
+        let mut items: BTreeSet<(u32, u32, u32, u32)> = BTreeSet::new();
+        for fields in parse_tuple_set(get_assignment(inst, REL_ENTITY_LIST_ITEM)?)? {
+            let entity_id = parse_token_u32(PREFIX_ENTITY, tuple_field(&fields, "entity")?)?;
+            let key_id = parse_token_u32(PREFIX_STRING_ID, tuple_field(&fields, "key_id")?)?;
+            let position =
+                parse_token_u32(PREFIX_LIST_POSITION, tuple_field(&fields, "position")?)?;
+            let value_id = parse_token_u32(PREFIX_STRING_ID, tuple_field(&fields, "value_id")?)?;
+            if entity_id >= entity_count {
+                return Err(invalid!(
+                    "entity_list_item references out-of-range entity id {entity_id} (count={entity_count})"
+                ));
+            }
+            if key_id >= string_count || value_id >= string_count {
+                return Err(invalid!(
+                    "entity_list_item references out-of-range string id (key={key_id}, value={value_id}, count={string_count})"
+                ));
+            }
+            items.insert((entity_id, key_id, position, value_id));
+        }
+        let mut lists: BTreeMap<(u32, u32), Vec<&str>> = BTreeMap::new();
+        for (entity_id, key_id, position, value_id) in items {
+            let list = lists.entry((entity_id, key_id)).or_default();
+            if position as usize != list.len() {
+                return Err(invalid!(
+                    "entity_list_item positions for {PREFIX_ENTITY}{entity_id} / {PREFIX_STRING_ID}{key_id} are not contiguous"
+                ));
+            }
+            list.push(strings[value_id as usize].as_str());
+        }
+        for ((entity_id, key_id), values) in lists {
+            db.set_list_attr(entity_id, &strings[key_id as usize], &values)?;
+        }
+    }
+
+    // Language-tagged attributes (absent from exports without them).
+    if inst
+        .assignments
+        .iter()
+        .any(|a| a.name == REL_ENTITY_LANG_ATTRIBUTE)
+    {
+        let mut rows: BTreeSet<(u32, u32, u32, u32)> = BTreeSet::new();
+        for fields in parse_tuple_set(get_assignment(inst, REL_ENTITY_LANG_ATTRIBUTE)?)? {
+            let entity_id = parse_token_u32(PREFIX_ENTITY, tuple_field(&fields, "entity")?)?;
+            let key_id = parse_token_u32(PREFIX_STRING_ID, tuple_field(&fields, "key_id")?)?;
+            let lang_id = parse_token_u32(PREFIX_STRING_ID, tuple_field(&fields, "lang_id")?)?;
+            let value_id = parse_token_u32(PREFIX_STRING_ID, tuple_field(&fields, "value_id")?)?;
+            if entity_id >= entity_count {
+                return Err(invalid!(
+                    "entity_lang_attribute references out-of-range entity id {entity_id} (count={entity_count})"
+                ));
+            }
+            if key_id >= string_count || lang_id >= string_count || value_id >= string_count {
+                return Err(invalid!(
+                    "entity_lang_attribute references out-of-range string id (key={key_id}, lang={lang_id}, value={value_id}, count={string_count})"
+                ));
+            }
+            rows.insert((entity_id, key_id, lang_id, value_id));
+        }
+        for (entity_id, key_id, lang_id, value_id) in rows {
+            db.set_attr_lang(
+                entity_id,
+                &strings[key_id as usize],
+                &strings[lang_id as usize],
+                &strings[value_id as usize],
+            )?;
+        }
+    }
+
+    // Blob payloads (only in exports made with blobs); each must hash to
+    // its reference.
+    if inst.assignments.iter().any(|a| a.name == REL_BLOB_CONTENT) {
+        for fields in parse_tuple_set(get_assignment(inst, REL_BLOB_CONTENT)?)? {
+            let reference_id =
+                parse_token_u32(PREFIX_STRING_ID, tuple_field(&fields, "reference_id")?)?;
+            let bytes = decode_hex(PREFIX_BLOB_HEX, tuple_field(&fields, "bytes")?)?;
+            let Some(reference) = strings.get(reference_id as usize) else {
+                return Err(invalid!(
+                    "blob_content references out-of-range string id {reference_id} (count={string_count})"
+                ));
+            };
+            if db.put_blob(&bytes)? != *reference {
+                return Err(invalid!(
+                    "blob_content payload does not hash to `{reference}`"
+                ));
+            }
+        }
+    }
+
+    // Typed readings are derived data: when present (exports predating them
+    // omit the table), they must match the attribute values exactly.
+    if inst.assignments.iter().any(|a| a.name == REL_TYPED_VALUE) {
+        let mut rows: BTreeSet<(u32, String, String)> = BTreeSet::new();
+        for fields in parse_tuple_set(get_assignment(inst, REL_TYPED_VALUE)?)? {
+            let value_id = parse_token_u32(PREFIX_STRING_ID, tuple_field(&fields, "value_id")?)?;
+            let literal_tok = tuple_field(&fields, "literal")?;
+            let unit_tok = tuple_field(&fields, "unit")?;
+            let literal = decode_typed_literal(literal_tok, unit_tok)?;
+            let Some(raw) = strings.get(value_id as usize) else {
+                return Err(invalid!(
+                    "typed_value references out-of-range string id {value_id} (count={string_count})"
+                ));
+            };
+            if TypedLiteralV1::infer(raw) != literal {
+                return Err(invalid!(
+                    "typed_value for {PREFIX_STRING_ID}{value_id} is `{literal}`, but `{raw}` reads as `{}`",
+                    TypedLiteralV1::infer(raw)
+                ));
+            }
+            rows.insert((value_id, literal_tok.to_string(), unit_tok.to_string()));
+        }
+        if rows != typed_value_rows(&db, &strings) {
+            return Err(invalid!(
+                "typed_value table does not cover exactly the typed attribute values"
+            ));
+        }
+    }
+
     db.build_indexes();
     Ok(db)
 }
@@ -25,7 +25,12 @@ pub const META_TYPE_THEORY: &str = "AxiMetaTheory";
 pub const META_TYPE_CONSTRAINT: &str = "AxiMetaConstraint";
 pub const META_TYPE_EQUATION: &str = "AxiMetaEquation";
 pub const META_TYPE_REWRITE_RULE: &str = "AxiMetaRewriteRule";
+pub const META_TYPE_VIEW: &str = "AxiMetaView";
 pub const META_TYPE_INSTANCE: &str = "AxiMetaInstance";
+pub const META_TYPE_RELATION_INVERSE: &str = "AxiMetaRelationInverse";
+pub const META_TYPE_VIRTUAL_RELATION: &str = "AxiMetaVirtualRelation";
+pub const META_TYPE_COMPOSITE_INDEX: &str = "AxiMetaCompositeIndex";
+pub const META_TYPE_RELATION_MULTIPLICITY: &str = "AxiMetaRelationMultiplicity";
 
 // -----------------------------------------------------------------------------
 // Meta relations (edge labels)
@@ -40,6 +45,7 @@ pub const META_REL_SCHEMA_HAS_THEORY: &str = "axi_schema_has_theory";
 pub const META_REL_THEORY_HAS_CONSTRAINT: &str = "axi_theory_has_constraint";
 pub const META_REL_THEORY_HAS_EQUATION: &str = "axi_theory_has_equation";
 pub const META_REL_THEORY_HAS_REWRITE_RULE: &str = "axi_theory_has_rewrite_rule";
+pub const META_REL_THEORY_HAS_VIEW: &str = "axi_theory_has_view";
 pub const META_REL_HAS_INSTANCE: &str = "axi_has_instance";
 
 /// Subtype relation between object type declarations (sub → sup).
@@ -56,6 +62,11 @@ pub const META_REL_FACT_OF: &str = "axi_fact_of";
 /// depending on any special “DB semantics”.
 pub const REL_AXI_FACT_IN_CONTEXT: &str = "axi_fact_in_context";
 
+/// Context inheritance: `child -axi_context_inherits-> parent` makes every fact
+/// asserted in `parent` visible in `child` (transitively) for context-scoped
+/// query execution.
+pub const REL_AXI_CONTEXT_INHERITS: &str = "axi_context_inherits";
+
 // -----------------------------------------------------------------------------
 // Common attributes
 // -----------------------------------------------------------------------------
@@ -80,6 +91,8 @@ pub const ATTR_AXI_INSTANCE: &str = "axi_instance";
 /// Attached to tuple entities imported from relation assignments.
 pub const ATTR_AXI_RELATION: &str = "axi_relation";
 pub const ATTR_AXI_FACT_ID: &str = "axi_fact_id";
+/// On a fact node retired by a key conflict: the `axi_fact_id` that replaced it.
+pub const ATTR_AXI_SUPERSEDED_BY: &str = "axi_superseded_by";
 
 // Field decl attrs
 pub const ATTR_FIELD_NAME: &str = "axi_field";
@@ -91,6 +104,25 @@ pub const ATTR_SUBTYPE_SUB: &str = "axi_sub";
 pub const ATTR_SUBTYPE_SUP: &str = "axi_sup";
 pub const ATTR_SUBTYPE_INCLUSION: &str = "axi_inclusion";
 
+// Relation inverse attrs
+pub const ATTR_INVERSE_RELATION: &str = "axi_inverse_relation";
+pub const ATTR_INVERSE_OF: &str = "axi_inverse_of";
+
+// Virtual relation attrs (attribute-equality joins)
+pub const ATTR_VIRTUAL_SOURCE_TYPE: &str = "axi_virtual_source_type";
+pub const ATTR_VIRTUAL_SOURCE_ATTR: &str = "axi_virtual_source_attr";
+pub const ATTR_VIRTUAL_TARGET_TYPE: &str = "axi_virtual_target_type";
+pub const ATTR_VIRTUAL_TARGET_ATTR: &str = "axi_virtual_target_attr";
+
+// Composite index attrs
+pub const ATTR_COMPOSITE_TYPE: &str = "axi_composite_type";
+pub const ATTR_COMPOSITE_ATTR: &str = "axi_composite_attr";
+
+// Relation multiplicity attrs
+pub const ATTR_MULTIPLICITY_RELATION: &str = "axi_multiplicity_relation";
+pub const ATTR_MULTIPLICITY_MAX_TARGETS: &str = "axi_multiplicity_max_targets";
+pub const ATTR_MULTIPLICITY_MAX_SOURCES: &str = "axi_multiplicity_max_sources";
+
 // Constraint attrs
 pub const ATTR_CONSTRAINT_KIND: &str = "axi_constraint_kind";
 pub const ATTR_CONSTRAINT_RELATION: &str = "axi_constraint_relation";
@@ -117,6 +149,10 @@ pub const ATTR_REWRITE_RULE_LHS: &str = "axi_rewrite_rule_lhs";
 pub const ATTR_REWRITE_RULE_RHS: &str = "axi_rewrite_rule_rhs";
 pub const ATTR_REWRITE_RULE_INDEX: &str = "axi_rewrite_rule_index";
 
+// View (saved query) attrs
+pub const ATTR_VIEW_QUERY: &str = "axi_view_query";
+pub const ATTR_VIEW_INDEX: &str = "axi_view_index";
+
 // Instance decl attrs
 pub const ATTR_INSTANCE_SCHEMA: &str = "axi_instance_schema";
 
@@ -169,6 +205,10 @@ pub fn meta_id_rewrite_rule(module_name: &str, theory_name: &str, rule_name: &st
     format!("axi_meta_rewrite_rule:{module_name}:{theory_name}:{rule_name}")
 }
 
+pub fn meta_id_view(module_name: &str, theory_name: &str, view_name: &str) -> String {
+    format!("axi_meta_view:{module_name}:{theory_name}:{view_name}")
+}
+
 pub fn meta_id_instance(module_name: &str, instance_name: &str) -> String {
     format!("axi_meta_instance:{module_name}:{instance_name}")
 }
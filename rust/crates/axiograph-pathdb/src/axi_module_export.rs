@@ -4,9 +4,16 @@
 //! - `axi_export` round-trips PathDB *snapshots* via `PathDBExportV1`
 //! - this module round-trips *canonical schema modules* (`schema/theory/instance`)
 //!   when PathDB contains the meta-plane produced by `axi_module_import`
+//!
+//! [`regenerate_axi_from_pathdb`] is the round-trip authoring path: it
+//! re-renders selected declarations into an existing hand-authored file
+//! (`axiograph_dsl::regen`), keeping its comments, so graph edits made through
+//! the API flow back into `.axi`.
 
 use anyhow::{anyhow, Result};
 
+use axiograph_dsl::regen::{splice_axi, AxiRegen, DeclRef};
+
 use crate::axi_meta::*;
 use crate::PathDB;
 
@@ -180,6 +187,24 @@ pub fn export_axi_schema_v1_module_from_pathdb(db: &PathDB, module_name: &str) -
                 out.push_str(&format!("    rhs: {rhs}\n"));
             }
 
+            // Views (stable by `axi_view_index`).
+            let view_ids = follow_ids(db, theory_id, META_REL_THEORY_HAS_VIEW);
+            let mut views: Vec<(usize, u32)> = Vec::new();
+            for vid in view_ids {
+                let idx = entity_attr(db, vid, ATTR_VIEW_INDEX)
+                    .and_then(|s| s.parse::<usize>().ok())
+                    .unwrap_or(usize::MAX);
+                views.push((idx, vid));
+            }
+            views.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+            for (_idx, vid) in views {
+                let vname =
+                    entity_attr(db, vid, META_ATTR_NAME).unwrap_or_else(|| "view".to_string());
+                let query = entity_attr(db, vid, ATTR_VIEW_QUERY).unwrap_or_default();
+                out.push_str(&format!("  view {vname} = {query}\n"));
+            }
+
             out.push('\n');
         }
     }
@@ -272,6 +297,20 @@ pub fn export_axi_schema_v1_module_from_pathdb(db: &PathDB, module_name: &str) -
     Ok(out)
 }
 
+/// Re-render the `select`ed declarations of `existing` (an `.axi` file of
+/// module `module_name`) from PathDB; an empty `select` regenerates every
+/// declaration of the module. Everything not selected, and the comments of
+/// the selected blocks, are kept (see `axiograph_dsl::regen`).
+pub fn regenerate_axi_from_pathdb(
+    db: &PathDB,
+    module_name: &str,
+    existing: &str,
+    select: &[DeclRef],
+) -> Result<AxiRegen> {
+    let regenerated = export_axi_schema_v1_module_from_pathdb(db, module_name)?;
+    Ok(splice_axi(existing, &regenerated, select)?)
+}
+
 // =============================================================================
 // Helpers
 // =============================================================================
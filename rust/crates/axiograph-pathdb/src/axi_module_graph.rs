@@ -0,0 +1,505 @@
+//! Multi-file `.axi` workspaces: `import` resolution and combined typechecking.
+//!
+//! A module names its dependencies with `import <Module>` header lines (see
+//! `axiograph_dsl::schema_v1`). `ModuleGraph` loads a workspace of modules,
+//! resolves each import to a module, rejects cycles, and checks cross-module
+//! schema references:
+//!
+//! - **visibility**: a theory or instance may use a schema declared in its own
+//!   module or in a module it imports *directly*; imports are not re-exported,
+//! - schema names are global once modules are combined, so two modules may
+//!   not declare the same schema (which also rules out ambiguous references).
+//!
+//! `combined()` flattens the graph in dependency order (imports before
+//! importers) into a single `SchemaV1Module`, and `typecheck()` runs that
+//! through `axi_module_typecheck`. Certificates are anchored to the combined
+//! module; the Lean parser skips `import` lines accordingly.
+//!
+//! Loading:
+//! - `load(entry, search_paths)` follows imports from one file, looking for
+//!   `<Module>.axi` next to the importing file, then under each search path;
+//! - `load_dir(dir)` takes every `.axi` file in a directory as the workspace.
+//!
+//! Loading and resolution fail with a [`ModuleGraphError`]; `typecheck()`
+//! reports through `axi_module_typecheck`.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use axiograph_dsl::schema_v1::{parse_schema_v1, SchemaV1Module};
+
+use crate::axi_module_typecheck::TypedAxiV1Module;
+
+/// Why a module graph could not be loaded or a schema reference resolved.
+#[derive(Debug, Error)]
+pub enum ModuleGraphError {
+    #[error("failed to read {path}: {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("{path}: {message}")]
+    Parse { path: PathBuf, message: String },
+
+    /// No `<import>.axi` next to the importer or under a search path.
+    #[error(
+        "module `{importer}` imports `{import}`, but no `{import}.axi` was found in: {}",
+        display_dirs(searched)
+    )]
+    ImportNotFound {
+        importer: String,
+        import: String,
+        searched: Vec<PathBuf>,
+    },
+
+    /// `<import>.axi` declares a different module name.
+    #[error(
+        "{} declares module `{declared}`, but `{importer}` imports it as `{import}`",
+        path.display()
+    )]
+    ModuleNameMismatch {
+        path: PathBuf,
+        declared: String,
+        importer: String,
+        import: String,
+    },
+
+    /// Two modules with one name; `first`/`second` are their files (or
+    /// `<in-memory>`).
+    #[error("module `{name}` is declared twice ({first} and {second})")]
+    DuplicateModule {
+        name: String,
+        first: String,
+        second: String,
+    },
+
+    #[error("module `{name}` imports itself")]
+    SelfImport { name: String },
+
+    #[error("module `{module}` imports `{import}`, which is not in the workspace")]
+    MissingImport { module: String, import: String },
+
+    /// Module names along the cycle, the first repeated at the end.
+    #[error("import cycle: {}", cycle.join(" -> "))]
+    ImportCycle { cycle: Vec<String> },
+
+    #[error("unknown module `{name}`")]
+    UnknownModule { name: String },
+
+    #[error("schema `{schema}` is declared in both `{first}` and `{second}`")]
+    DuplicateSchema {
+        schema: String,
+        first: String,
+        second: String,
+    },
+
+    #[error("schema `{schema}` is declared in `{declared_in}`, which `{module}` does not import")]
+    SchemaNotImported {
+        schema: String,
+        declared_in: String,
+        module: String,
+    },
+
+    #[error("schema `{schema}` is not declared in the workspace")]
+    UndeclaredSchema { schema: String },
+
+    /// A theory or instance whose schema does not resolve; `cause` is the
+    /// resolution error.
+    #[error("{kind} `{item}` in module `{module}` cannot use schema `{schema}`: {cause}")]
+    SchemaReference {
+        kind: &'static str,
+        item: String,
+        module: String,
+        schema: String,
+        cause: Box<ModuleGraphError>,
+    },
+}
+
+pub type Result<T, E = ModuleGraphError> = std::result::Result<T, E>;
+
+fn display_dirs(dirs: &[PathBuf]) -> String {
+    dirs.iter()
+        .map(|d| d.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Module name used by `combined()` when the graph has no single entry.
+pub const WORKSPACE_MODULE_NAME: &str = "Workspace";
+
+/// One module of the graph and the file it came from (if any).
+#[derive(Debug, Clone)]
+pub struct ModuleNode {
+    pub path: Option<PathBuf>,
+    pub module: SchemaV1Module,
+}
+
+/// A resolved, acyclic set of `.axi` modules.
+#[derive(Debug, Clone)]
+pub struct ModuleGraph {
+    modules: BTreeMap<String, ModuleNode>,
+    /// Dependency order: every module comes after the modules it imports.
+    order: Vec<String>,
+    entry: Option<String>,
+}
+
+impl ModuleGraph {
+    /// Build a graph from already-parsed modules (none of them is the entry).
+    pub fn from_modules(modules: impl IntoIterator<Item = SchemaV1Module>) -> Result<Self> {
+        Self::from_nodes(
+            modules
+                .into_iter()
+                .map(|module| ModuleNode { path: None, module }),
+            None,
+        )
+    }
+
+    /// Load `entry` and, transitively, every module it imports.
+    pub fn load(entry: &Path, search_paths: &[PathBuf]) -> Result<Self> {
+        let root = read_module(entry)?;
+        let entry_name = root.module.module_name.clone();
+
+        let mut nodes: BTreeMap<String, ModuleNode> = BTreeMap::new();
+        let mut pending = vec![root];
+        while let Some(node) = pending.pop() {
+            let name = node.module.module_name.clone();
+            if let Some(existing) = nodes.get(&name) {
+                if existing.path != node.path {
+                    return Err(duplicate_module(&name, existing, &node));
+                }
+                continue;
+            }
+            for import in &node.module.imports {
+                if nodes.contains_key(import)
+                    || pending.iter().any(|p| &p.module.module_name == import)
+                {
+                    continue;
+                }
+                let path = find_import(&node, import, search_paths)?;
+                let imported = read_module(&path)?;
+                if &imported.module.module_name != import {
+                    return Err(ModuleGraphError::ModuleNameMismatch {
+                        declared: imported.module.module_name,
+                        path,
+                        importer: name,
+                        import: import.clone(),
+                    });
+                }
+                pending.push(imported);
+            }
+            nodes.insert(name, node);
+        }
+
+        Self::from_nodes(nodes.into_values(), Some(entry_name))
+    }
+
+    /// Take every `.axi` file directly under `dir` as the workspace.
+    pub fn load_dir(dir: &Path) -> Result<Self> {
+        let mut paths = Vec::new();
+        if dir.exists() {
+            let read = |source| ModuleGraphError::Read {
+                path: dir.to_path_buf(),
+                source,
+            };
+            for entry in std::fs::read_dir(dir).map_err(read)? {
+                let path = entry.map_err(read)?.path();
+                if path.extension().is_some_and(|e| e == "axi") {
+                    paths.push(path);
+                }
+            }
+        }
+        paths.sort();
+        let nodes = paths
+            .iter()
+            .map(|p| read_module(p))
+            .collect::<Result<Vec<_>>>()?;
+        Self::from_nodes(nodes, None)
+    }
+
+    fn from_nodes(
+        nodes: impl IntoIterator<Item = ModuleNode>,
+        entry: Option<String>,
+    ) -> Result<Self> {
+        let mut modules: BTreeMap<String, ModuleNode> = BTreeMap::new();
+        for node in nodes {
+            let name = node.module.module_name.clone();
+            if let Some(existing) = modules.get(&name) {
+                return Err(duplicate_module(&name, existing, &node));
+            }
+            modules.insert(name, node);
+        }
+
+        for (name, node) in &modules {
+            for import in &node.module.imports {
+                if import == name {
+                    return Err(ModuleGraphError::SelfImport { name: name.clone() });
+                }
+                if !modules.contains_key(import) {
+                    return Err(ModuleGraphError::MissingImport {
+                        module: name.clone(),
+                        import: import.clone(),
+                    });
+                }
+            }
+        }
+
+        let order = dependency_order(&modules)?;
+        let graph = Self {
+            modules,
+            order,
+            entry,
+        };
+        graph.check_schema_references()?;
+        Ok(graph)
+    }
+
+    /// Duplicate schema declarations and theory/instance schema visibility.
+    fn check_schema_references(&self) -> Result<()> {
+        let mut declared_in: BTreeMap<&str, &str> = BTreeMap::new();
+        for name in &self.order {
+            for schema in &self.modules[name].module.schemas {
+                if let Some(other) = declared_in.insert(&schema.name, name) {
+                    return Err(ModuleGraphError::DuplicateSchema {
+                        schema: schema.name.clone(),
+                        first: other.to_string(),
+                        second: name.clone(),
+                    });
+                }
+            }
+        }
+
+        for name in &self.order {
+            let module = &self.modules[name].module;
+            // Schema-less fragments (e.g. storage change logs written against a
+            // shared schema) are left to `typecheck()`.
+            if module.schemas.is_empty() && module.imports.is_empty() {
+                continue;
+            }
+            let references = module
+                .theories
+                .iter()
+                .map(|t| ("theory", &t.name, &t.schema))
+                .chain(
+                    module
+                        .instances
+                        .iter()
+                        .map(|i| ("instance", &i.name, &i.schema)),
+                );
+            for (kind, item, schema) in references {
+                self.resolve_schema(name, schema).map_err(|cause| {
+                    ModuleGraphError::SchemaReference {
+                        kind,
+                        item: item.clone(),
+                        module: name.clone(),
+                        schema: schema.clone(),
+                        cause: Box::new(cause),
+                    }
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The module declaring `schema` as seen from `module` (itself or a
+    /// direct import).
+    pub fn resolve_schema(&self, module: &str, schema: &str) -> Result<&str> {
+        let node =
+            self.modules
+                .get_key_value(module)
+                .ok_or_else(|| ModuleGraphError::UnknownModule {
+                    name: module.to_string(),
+                })?;
+        let declares = |m: &SchemaV1Module| m.schemas.iter().any(|s| s.name == schema);
+        if declares(&node.1.module) {
+            return Ok(node.0);
+        }
+
+        // Schema names are unique across the graph, so at most one direct
+        // import can declare it.
+        if let Some(import) = node
+            .1
+            .module
+            .imports
+            .iter()
+            .find(|i| declares(&self.modules[*i].module))
+        {
+            return Ok(import);
+        }
+        let elsewhere = self
+            .modules
+            .iter()
+            .find(|(_, n)| declares(&n.module))
+            .map(|(n, _)| n.as_str());
+        Err(match elsewhere {
+            Some(other) => ModuleGraphError::SchemaNotImported {
+                schema: schema.to_string(),
+                declared_in: other.to_string(),
+                module: module.to_string(),
+            },
+            None => ModuleGraphError::UndeclaredSchema {
+                schema: schema.to_string(),
+            },
+        })
+    }
+
+    pub fn entry(&self) -> Option<&str> {
+        self.entry.as_deref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ModuleNode> {
+        self.modules.get(name)
+    }
+
+    /// Module names in dependency order (imports first).
+    pub fn order(&self) -> &[String] {
+        &self.order
+    }
+
+    /// Modules in dependency order (imports first).
+    pub fn modules(&self) -> impl Iterator<Item = &ModuleNode> {
+        self.order.iter().map(|name| &self.modules[name])
+    }
+
+    /// Consume the graph, yielding its modules in dependency order.
+    pub fn into_modules(mut self) -> Vec<SchemaV1Module> {
+        self.order
+            .iter()
+            .filter_map(|name| self.modules.remove(name))
+            .map(|node| node.module)
+            .collect()
+    }
+
+    /// Flatten every module into one (named after the entry module, else
+    /// [`WORKSPACE_MODULE_NAME`]), in dependency order.
+    pub fn combined(&self) -> SchemaV1Module {
+        let mut out = SchemaV1Module {
+            module_name: self
+                .entry
+                .clone()
+                .unwrap_or_else(|| WORKSPACE_MODULE_NAME.to_string()),
+            imports: vec![],
+            schemas: vec![],
+            theories: vec![],
+            instances: vec![],
+        };
+        for node in self.modules() {
+            out.schemas.extend(node.module.schemas.iter().cloned());
+            out.theories.extend(node.module.theories.iter().cloned());
+            out.instances.extend(node.module.instances.iter().cloned());
+        }
+        out
+    }
+
+    /// Typecheck the combined module.
+    pub fn typecheck(&self) -> anyhow::Result<TypedAxiV1Module> {
+        TypedAxiV1Module::new(self.combined())
+    }
+}
+
+fn read_module(path: &Path) -> Result<ModuleNode> {
+    let text = std::fs::read_to_string(path).map_err(|source| ModuleGraphError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let module = parse_schema_v1(&text).map_err(|e| ModuleGraphError::Parse {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    Ok(ModuleNode {
+        path: Some(path.to_path_buf()),
+        module,
+    })
+}
+
+fn find_import(importer: &ModuleNode, import: &str, search_paths: &[PathBuf]) -> Result<PathBuf> {
+    let file = format!("{import}.axi");
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if let Some(dir) = importer.path.as_deref().and_then(Path::parent) {
+        // `App.axi` has the empty path as parent.
+        if dir.as_os_str().is_empty() {
+            dirs.push(PathBuf::from("."));
+        } else {
+            dirs.push(dir.to_path_buf());
+        }
+    }
+    dirs.extend(search_paths.iter().cloned());
+
+    dirs.iter()
+        .map(|dir| dir.join(&file))
+        .find(|candidate| candidate.is_file())
+        .ok_or_else(|| ModuleGraphError::ImportNotFound {
+            importer: importer.module.module_name.clone(),
+            import: import.to_string(),
+            searched: dirs.clone(),
+        })
+}
+
+fn duplicate_module(name: &str, a: &ModuleNode, b: &ModuleNode) -> ModuleGraphError {
+    let show = |n: &ModuleNode| {
+        n.path
+            .as_ref()
+            .map_or_else(|| "<in-memory>".to_string(), |p| p.display().to_string())
+    };
+    ModuleGraphError::DuplicateModule {
+        name: name.to_string(),
+        first: show(a),
+        second: show(b),
+    }
+}
+
+/// Depth-first topological order; reports the first cycle found.
+fn dependency_order(modules: &BTreeMap<String, ModuleNode>) -> Result<Vec<String>> {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Mark {
+        Visiting,
+        Done,
+    }
+
+    fn visit<'a>(
+        name: &'a str,
+        modules: &'a BTreeMap<String, ModuleNode>,
+        marks: &mut BTreeMap<&'a str, Mark>,
+        stack: &mut Vec<&'a str>,
+        order: &mut Vec<String>,
+    ) -> Result<()> {
+        match marks.get(name) {
+            Some(Mark::Done) => return Ok(()),
+            Some(Mark::Visiting) => {
+                let start = stack.iter().position(|n| *n == name).unwrap_or(0);
+                let mut cycle: Vec<&str> = stack[start..].to_vec();
+                cycle.push(name);
+                return Err(ModuleGraphError::ImportCycle {
+                    cycle: cycle.into_iter().map(str::to_string).collect(),
+                });
+            }
+            None => {}
+        }
+        marks.insert(name, Mark::Visiting);
+        stack.push(name);
+        for import in &modules[name].module.imports {
+            visit(import, modules, marks, stack, order)?;
+        }
+        stack.pop();
+        marks.insert(name, Mark::Done);
+        order.push(name.to_string());
+        Ok(())
+    }
+
+    let mut marks = BTreeMap::new();
+    let mut order = Vec::with_capacity(modules.len());
+    for name in modules.keys() {
+        visit(name, modules, &mut marks, &mut Vec::new(), &mut order)?;
+    }
+    Ok(order)
+}
@@ -124,6 +124,9 @@ pub fn import_axi_schema_v1_module_into_pathdb(
         summary.entity_type_upgrades += ctx.summary.entity_type_upgrades;
     }
 
+    // Enforce the module's functional/at_most constraints on later inserts.
+    db.adopt_axi_multiplicities();
+
     Ok(summary)
 }
 
@@ -261,6 +264,7 @@ impl<'a> MetaImportContext<'a> {
                     .get(&st.sup)
                     .ok_or_else(|| anyhow!("missing meta object type for `{}`", st.sup))?;
                 self.add_meta_edge_if_missing(META_REL_SUBTYPE_OF, sub_id, sup_id)?;
+                self.db.declare_subtype(&st.sub, &st.sup);
             }
 
             let mut relation_ids: HashMap<String, u32> = HashMap::new();
@@ -434,6 +438,26 @@ impl<'a> MetaImportContext<'a> {
                     rule_entity,
                 )?;
             }
+
+            for (index, v) in theory.views.iter().enumerate() {
+                let attrs = vec![
+                    (META_ATTR_NAME.to_string(), v.name.clone()),
+                    (ATTR_AXI_MODULE.to_string(), module_name.to_string()),
+                    (ATTR_AXI_SCHEMA.to_string(), theory.schema.clone()),
+                    (ATTR_VIEW_QUERY.to_string(), v.query.clone()),
+                    (ATTR_VIEW_INDEX.to_string(), index.to_string()),
+                ];
+                let view_entity = self.get_or_create_meta_entity(
+                    META_TYPE_VIEW,
+                    &meta_id_view(module_name, &theory.name, &v.name),
+                    attrs,
+                )?;
+                self.add_meta_edge_if_missing(
+                    META_REL_THEORY_HAS_VIEW,
+                    theory_entity,
+                    view_entity,
+                )?;
+            }
         }
 
         // Instances.
@@ -1044,6 +1068,7 @@ impl<'a> InstanceImportContext<'a> {
                 .or_insert_with(roaring::RoaringBitmap::new)
                 .insert(entity_id);
         }
+        self.db.reindex_composite(entity_id);
     }
 
     fn import_object_assignment(&mut self, name: &str, items: &[SetItemV1]) -> Result<()> {
@@ -1309,6 +1334,7 @@ impl<'a> InstanceImportContext<'a> {
             .entry(type_id)
             .or_insert_with(roaring::RoaringBitmap::new)
             .insert(entity_id);
+        self.db.reindex_composite(entity_id);
     }
 
     fn add_edge_if_missing_with_attrs(
@@ -72,9 +72,26 @@ pub struct SchemaIndex {
     /// These are preserved as structured data so they can be reviewed in REPL/UI,
     /// even when they are not yet executable or certifiable.
     pub named_block_constraints_by_theory: HashMap<String, Vec<NamedBlockConstraintDecl>>,
+    /// Saved queries (`view name = <AxQL>`) declared in theories attached to
+    /// this schema, by view name.
+    ///
+    /// These come from `AxiMetaView` nodes linked under:
+    /// `schema -> axi_schema_has_theory -> axi_theory_has_view`.
+    pub views: HashMap<String, ViewDecl>,
     pub supertypes_of: HashMap<String, HashSet<String>>,
 }
 
+#[derive(Debug, Clone)]
+pub struct ViewDecl {
+    pub view_entity: u32,
+    pub schema_name: String,
+    pub theory_name: String,
+    pub name: String,
+    /// AxQL query text (parsed when the view is run).
+    pub query: String,
+    pub index: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct RewriteRuleDecl {
     pub rule_entity: u32,
@@ -198,7 +215,7 @@ impl SchemaIndex {
 }
 
 impl MetaPlaneIndex {
-    pub fn from_db(db: &PathDB) -> Result<Self> {
+    pub fn from_db(db: &PathDB) -> crate::error::Result<Self> {
         let mut out = MetaPlaneIndex::default();
 
         let Some(schema_ids) = db.find_by_type(META_TYPE_SCHEMA) else {
@@ -284,6 +301,7 @@ impl MetaPlaneIndex {
             let mut rewrite_rules_by_theory: HashMap<String, Vec<RewriteRuleDecl>> = HashMap::new();
             let mut named_block_constraints_by_theory: HashMap<String, Vec<NamedBlockConstraintDecl>> =
                 HashMap::new();
+            let mut views: HashMap<String, ViewDecl> = HashMap::new();
             for theory_id in db
                 .follow_one(schema_entity, META_REL_SCHEMA_HAS_THEORY)
                 .iter()
@@ -557,6 +575,28 @@ impl MetaPlaneIndex {
                             index,
                         });
                 }
+
+                // Views (saved AxQL queries).
+                for vid in db.follow_one(theory_id, META_REL_THEORY_HAS_VIEW).iter() {
+                    let Some(name) = entity_attr_string(db, vid, META_ATTR_NAME) else {
+                        continue;
+                    };
+                    let query = entity_attr_string(db, vid, ATTR_VIEW_QUERY).unwrap_or_default();
+                    let index = entity_attr_string(db, vid, ATTR_VIEW_INDEX)
+                        .and_then(|s| s.parse::<usize>().ok())
+                        .unwrap_or(usize::MAX);
+                    views.insert(
+                        name.clone(),
+                        ViewDecl {
+                            view_entity: vid,
+                            schema_name: schema_name.clone(),
+                            theory_name: theory_name.clone(),
+                            name,
+                            query,
+                            index,
+                        },
+                    );
+                }
             }
 
             let supertypes_of = compute_supertypes_closure(&object_types, &subtype_decls);
@@ -569,6 +609,7 @@ impl MetaPlaneIndex {
                 constraints_by_relation,
                 rewrite_rules_by_theory,
                 named_block_constraints_by_theory,
+                views,
                 supertypes_of,
             };
 
@@ -585,6 +626,53 @@ impl MetaPlaneIndex {
         Ok(out)
     }
 
+    /// Resolve a saved query by name. `Schema.view` picks the schema; a bare
+    /// name must be declared in exactly one schema.
+    pub fn view(&self, name: &str) -> Result<&ViewDecl> {
+        if let Some((schema, view)) = name.split_once('.') {
+            return self
+                .schemas
+                .get(schema)
+                .and_then(|s| s.views.get(view))
+                .ok_or_else(|| anyhow::anyhow!("unknown view `{view}` in schema `{schema}`"));
+        }
+        let mut found: Vec<&ViewDecl> = self
+            .schemas
+            .values()
+            .filter_map(|s| s.views.get(name))
+            .collect();
+        match found.len() {
+            0 => Err(anyhow::anyhow!("unknown view `{name}`")),
+            1 => Ok(found.remove(0)),
+            _ => {
+                let mut schemas: Vec<&str> = found.iter().map(|v| v.schema_name.as_str()).collect();
+                schemas.sort_unstable();
+                Err(anyhow::anyhow!(
+                    "view `{name}` is declared in several schemas ({}); qualify it as `Schema.{name}`",
+                    schemas.join(", ")
+                ))
+            }
+        }
+    }
+
+    /// Every saved query, ordered by schema, theory and declaration order.
+    pub fn views(&self) -> Vec<&ViewDecl> {
+        let mut out: Vec<&ViewDecl> = self
+            .schemas
+            .values()
+            .flat_map(|s| s.views.values())
+            .collect();
+        out.sort_by(|a, b| {
+            (&a.schema_name, &a.theory_name, a.index, &a.name).cmp(&(
+                &b.schema_name,
+                &b.theory_name,
+                b.index,
+                &b.name,
+            ))
+        });
+        out
+    }
+
     pub fn typecheck_axi_facts(&self, db: &PathDB) -> AxiTypeCheckReport {
         let mut report = AxiTypeCheckReport::default();
 
@@ -898,6 +986,10 @@ fn merge_schema_index(target: &mut SchemaIndex, incoming: SchemaIndex) {
             .append(&mut blocks);
     }
 
+    for (name, view) in incoming.views {
+        target.views.entry(name).or_insert(view);
+    }
+
     target.supertypes_of =
         compute_supertypes_closure(&target.object_types, &target.subtype_decls);
 }
@@ -65,7 +65,7 @@ pub struct TypingEnv {
 }
 
 impl TypingEnv {
-    pub fn from_db(db: &PathDB) -> anyhow::Result<Self> {
+    pub fn from_db(db: &PathDB) -> crate::error::Result<Self> {
         Ok(Self {
             meta: MetaPlaneIndex::from_db(db)?,
         })
@@ -0,0 +1,206 @@
+//! Content-addressed blob sidecar.
+//!
+//! Small binary payloads (thumbnails, serialized geometry digests) do not
+//! belong in the string interner: base64 text bloats it and every interned
+//! string lives forever. Instead the bytes go into a [`BlobStore`] keyed by
+//! their SHA-256, and the entity attribute holds only the short reference
+//! `blob:sha256:<hex>`:
+//!
+//! ```text
+//! let reference = db.attach_blob(part, "thumbnail", &png_bytes)?;
+//! db.entity_blob(part, "thumbnail"); // Some(&png_bytes)
+//! ```
+//!
+//! Identical payloads are stored once. References are ordinary attribute
+//! values, so queries, exports and certificates see (and pin) the digest
+//! without the bytes.
+//!
+//! Blobs are optional in every format: `to_bytes` writes them as a trailing
+//! `.axpd` section (`to_bytes_without_blobs` leaves them out), and
+//! `PathDBExportV1` carries them only when exported with
+//! `export_pathdb_to_axi_v1_with_blobs`. A snapshot loaded without its blobs
+//! keeps the references; [`PathDB::missing_blobs`] lists the ones that cannot
+//! be resolved, and [`PathDB::import_blobs`] fills them in from another store.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+use sha2::{Digest as _, Sha256};
+
+use crate::error::Result;
+use crate::{PathDB, PathDbError};
+
+/// Prefix of blob reference attribute values.
+pub const BLOB_REF_PREFIX: &str = "blob:sha256:";
+
+/// Largest payload `put_blob` accepts.
+pub const MAX_BLOB_BYTES: usize = 1 << 20;
+
+/// `blob:sha256:<hex>` of `bytes`.
+pub fn blob_ref(bytes: &[u8]) -> String {
+    let digest = Sha256::digest(bytes);
+    let mut out = String::with_capacity(BLOB_REF_PREFIX.len() + 64);
+    out.push_str(BLOB_REF_PREFIX);
+    for b in digest.iter() {
+        let _ = write!(&mut out, "{:02x}", b);
+    }
+    out
+}
+
+/// Whether an attribute value is a blob reference.
+pub fn is_blob_ref(value: &str) -> bool {
+    value
+        .strip_prefix(BLOB_REF_PREFIX)
+        .is_some_and(|hex| hex.len() == 64 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// Blob payloads keyed by reference.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlobStore {
+    blobs: BTreeMap<String, Vec<u8>>,
+}
+
+impl BlobStore {
+    pub fn len(&self) -> usize {
+        self.blobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blobs.is_empty()
+    }
+
+    pub fn get(&self, reference: &str) -> Option<&[u8]> {
+        self.blobs.get(reference).map(Vec::as_slice)
+    }
+
+    pub fn contains(&self, reference: &str) -> bool {
+        self.blobs.contains_key(reference)
+    }
+
+    /// Total payload size.
+    pub fn total_bytes(&self) -> usize {
+        self.blobs.values().map(Vec::len).sum()
+    }
+
+    /// `(reference, bytes)` in reference order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.blobs.iter().map(|(r, b)| (r.as_str(), b.as_slice()))
+    }
+
+    /// Store `bytes`; returns its reference.
+    pub fn insert(&mut self, bytes: &[u8]) -> Result<String> {
+        if bytes.len() > MAX_BLOB_BYTES {
+            return Err(PathDbError::BlobTooLarge {
+                size: bytes.len(),
+                limit: MAX_BLOB_BYTES,
+            });
+        }
+        let reference = blob_ref(bytes);
+        self.blobs
+            .entry(reference.clone())
+            .or_insert_with(|| bytes.to_vec());
+        Ok(reference)
+    }
+
+    /// The snapshot section: `(reference, bytes)` in reference order.
+    pub(crate) fn section(&self) -> Vec<(&str, &[u8])> {
+        self.iter().collect()
+    }
+
+    /// Load a snapshot section, rejecting payloads that do not hash to their
+    /// reference.
+    pub(crate) fn from_section(section: Vec<(String, Vec<u8>)>) -> Result<Self> {
+        let mut store = Self::default();
+        for (reference, bytes) in section {
+            if blob_ref(&bytes) != reference {
+                return Err(PathDbError::Corrupt(format!(
+                    "blob payload does not match `{reference}`"
+                )));
+            }
+            store.blobs.insert(reference, bytes);
+        }
+        Ok(store)
+    }
+}
+
+impl PathDB {
+    /// The blob sidecar.
+    pub fn blobs(&self) -> &BlobStore {
+        &self.blobs
+    }
+
+    /// Store `bytes` in the sidecar; returns its `blob:sha256:` reference.
+    pub fn put_blob(&mut self, bytes: &[u8]) -> Result<String> {
+        self.blobs.insert(bytes)
+    }
+
+    /// Store `bytes` and point `entity_id`'s attribute `key` at them.
+    pub fn attach_blob(&mut self, entity_id: u32, key: &str, bytes: &[u8]) -> Result<String> {
+        if entity_id as usize >= self.entities.types.len() {
+            return Err(PathDbError::UnknownEntity(entity_id));
+        }
+        let reference = self.blobs.insert(bytes)?;
+        self.upsert_entity_attr(entity_id, key, &reference)?;
+        Ok(reference)
+    }
+
+    /// Payload of a `blob:sha256:` reference, if it is in the sidecar.
+    pub fn blob(&self, reference: &str) -> Option<&[u8]> {
+        self.blobs.get(reference)
+    }
+
+    /// Payload referenced by `entity_id`'s attribute `key`.
+    pub fn entity_blob(&self, entity_id: u32, key: &str) -> Option<&[u8]> {
+        let key_id = self.interner.id_of(key)?;
+        let value = self.entities.get_attr(entity_id, key_id)?;
+        self.blobs.get(&self.interner.lookup(value)?)
+    }
+
+    /// Every blob reference used by an entity or relation attribute.
+    pub fn referenced_blobs(&self) -> BTreeSet<String> {
+        let mut values = BTreeSet::new();
+        for col in self.entities.attrs.values() {
+            values.extend(col.values().copied());
+        }
+        for rel in &self.relations.relations {
+            values.extend(rel.attrs.iter().map(|(_, v)| *v));
+        }
+        values
+            .into_iter()
+            .filter_map(|id| self.interner.lookup(id))
+            .filter(|value| is_blob_ref(value))
+            .collect()
+    }
+
+    /// References whose payload is not in the sidecar (e.g. after loading a
+    /// snapshot saved without blobs).
+    pub fn missing_blobs(&self) -> Vec<String> {
+        self.referenced_blobs()
+            .into_iter()
+            .filter(|reference| !self.blobs.contains(reference))
+            .collect()
+    }
+
+    /// Copy the payloads this database references from `other`; returns how
+    /// many were added.
+    pub fn import_blobs(&mut self, other: &BlobStore) -> usize {
+        let mut added = 0;
+        for reference in self.missing_blobs() {
+            if let Some(bytes) = other.get(&reference) {
+                self.blobs.blobs.insert(reference, bytes.to_vec());
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Drop payloads no attribute references; returns how many.
+    pub fn gc_blobs(&mut self) -> usize {
+        let referenced = self.referenced_blobs();
+        let before = self.blobs.len();
+        self.blobs
+            .blobs
+            .retain(|reference, _| referenced.contains(reference));
+        before - self.blobs.len()
+    }
+}
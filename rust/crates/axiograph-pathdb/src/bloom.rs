@@ -0,0 +1,170 @@
+//! Bloom-filter negative caches for edge existence checks.
+//!
+//! Reconciliation workloads probe many edges that do not exist. Answering
+//! `has_edge(source, rel_type, target)` from the forward index means hashing
+//! into it and walking the `(source, rel_type)` list, plus a relation record
+//! read per candidate. Each relation type instead keeps a Bloom filter over its
+//! `(source, target)` pairs: a miss is a definite "no edge" without touching
+//! the index, a hit falls through to the exact lookup.
+//!
+//! Filters are derived state: maintained by `RelationStore::add`, rebuilt on
+//! load, and regrown (rebuilt at twice the capacity from the type index) when
+//! a relation type outgrows its filter, which keeps the false-positive rate
+//! near [`TARGET_FALSE_POSITIVE_RATE`]. Removals rebuild the relation store,
+//! and with it the filters.
+
+use crate::cardinality::mix64;
+use crate::{RelationStore, StrId};
+
+/// Minimum filter bits per expected element (~1% false positives with 7
+/// hashes); sizes round up to a power of two.
+const BITS_PER_ELEMENT: usize = 10;
+const HASHES: u32 = 7;
+/// Smallest filter capacity, in elements.
+const MIN_CAPACITY: usize = 64;
+
+/// Approximate false-positive rate of a filter at capacity.
+pub const TARGET_FALSE_POSITIVE_RATE: f64 = 0.01;
+
+/// Fixed-size Bloom filter over `u64` keys.
+#[derive(Debug, Clone)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    capacity: usize,
+    len: usize,
+}
+
+impl BloomFilter {
+    /// A filter sized for `capacity` elements at the target false-positive
+    /// rate.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(MIN_CAPACITY);
+        // A power of two, so probes reduce with a mask.
+        let words = (capacity * BITS_PER_ELEMENT)
+            .div_ceil(64)
+            .next_power_of_two();
+        Self {
+            bits: vec![0; words],
+            capacity,
+            len: 0,
+        }
+    }
+
+    /// Elements inserted (with multiplicity).
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn insert(&mut self, key: u64) {
+        for bit in probes(key, self.mask()) {
+            self.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+        }
+        self.len += 1;
+    }
+
+    fn mask(&self) -> u64 {
+        self.bits.len() as u64 * 64 - 1
+    }
+
+    /// `false` means `key` was definitely never inserted.
+    pub fn may_contain(&self, key: u64) -> bool {
+        probes(key, self.mask()).all(|bit| self.bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+    }
+}
+
+/// Kirsch–Mitzenmacher double hashing: `h1 + i * h2` for `i in 0..HASHES`.
+fn probes(key: u64, mask: u64) -> impl Iterator<Item = u64> {
+    let h1 = mix64(key);
+    let h2 = h1.rotate_left(32) | 1;
+    (0..u64::from(HASHES)).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) & mask)
+}
+
+fn edge_key(source: u32, target: u32) -> u64 {
+    (u64::from(source) << 32) | u64::from(target)
+}
+
+/// One filter per relation type over its `(source, target)` pairs, indexed
+/// by the interned relation type id (dense, so no hashing on the probe path).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EdgeFilters {
+    filters: Vec<Option<BloomFilter>>,
+}
+
+impl EdgeFilters {
+    /// `false` means there is definitely no `source -rel_type-> target` edge.
+    pub(crate) fn may_contain(&self, rel_type: StrId, source: u32, target: u32) -> bool {
+        self.get(rel_type)
+            .is_some_and(|filter| filter.may_contain(edge_key(source, target)))
+    }
+
+    pub(crate) fn get(&self, rel_type: StrId) -> Option<&BloomFilter> {
+        self.filters.get(rel_type.0 as usize)?.as_ref()
+    }
+
+    fn get_mut(&mut self, rel_type: StrId) -> Option<&mut BloomFilter> {
+        self.filters.get_mut(rel_type.0 as usize)?.as_mut()
+    }
+
+    fn insert(&mut self, rel_type: StrId, filter: BloomFilter) {
+        let index = rel_type.0 as usize;
+        if index >= self.filters.len() {
+            self.filters.resize(index + 1, None);
+        }
+        self.filters[index] = Some(filter);
+    }
+}
+
+impl RelationStore {
+    /// Add a just-stored relation to its type's filter, regrowing it if full.
+    pub(crate) fn note_edge_filter(&mut self, rel_id: u32) {
+        let Some(rel) = self.relations.get(rel_id as usize) else {
+            return;
+        };
+        let rel_type = rel.rel_type;
+        let full = self
+            .edge_filters
+            .get(rel_type)
+            .is_none_or(|filter| filter.len() >= filter.capacity());
+        if full {
+            let filter = self.build_edge_filter(rel_type);
+            self.edge_filters.insert(rel_type, filter);
+        } else if let Some(filter) = self.edge_filters.get_mut(rel_type) {
+            filter.insert(edge_key(rel.source, rel.target));
+        }
+    }
+
+    /// The negative-cache filter of `rel_type`, if it has any edges.
+    pub fn edge_filter(&self, rel_type: StrId) -> Option<&BloomFilter> {
+        self.edge_filters.get(rel_type)
+    }
+
+    /// Recompute every filter (after load).
+    pub(crate) fn rebuild_edge_filters(&mut self) {
+        let rel_types: Vec<StrId> = self.type_index.keys().copied().collect();
+        self.edge_filters = EdgeFilters::default();
+        for rel_type in rel_types {
+            let filter = self.build_edge_filter(rel_type);
+            self.edge_filters.insert(rel_type, filter);
+        }
+    }
+
+    /// A filter over every current `rel_type` edge, with room to double.
+    fn build_edge_filter(&self, rel_type: StrId) -> BloomFilter {
+        let count = self.rel_type_count(rel_type);
+        let mut filter = BloomFilter::with_capacity(count * 2);
+        if let Some(ids) = self.type_index.get(&rel_type) {
+            for rel in ids.iter().filter_map(|id| self.relations.get(id as usize)) {
+                filter.insert(edge_key(rel.source, rel.target));
+            }
+        }
+        filter
+    }
+}
@@ -0,0 +1,190 @@
+//! Time/work budgets for query execution.
+//!
+//! Some traversals (notably `find_paths` on dense graphs) can run for a very
+//! long time. A [`QueryBudget`] bounds them by wall-clock time, by the number of
+//! entities expanded, and/or by an external [`CancelToken`]. When a budget is
+//! exhausted the executor stops early and returns whatever it has so far,
+//! tagged with [`QueryStatus::BudgetExceeded`].
+//!
+//! Partial results are always a **subset** of the complete answer (sound but
+//! possibly incomplete): traversals only report targets they actually reached
+//! at full path length, and joins/unions of subsets stay subsets.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Cooperative cancellation flag shared between a query and its caller.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation; running queries stop at their next check.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Limits applied to a single query execution. `None` means unlimited.
+#[derive(Debug, Clone, Default)]
+pub struct QueryBudget {
+    /// Wall-clock limit in milliseconds.
+    pub max_ms: Option<u64>,
+    /// Maximum number of entities expanded during traversal (for
+    /// `find_paths`, edges followed count too).
+    pub max_visited: Option<u64>,
+    /// External cancellation.
+    pub cancel_token: Option<CancelToken>,
+}
+
+impl QueryBudget {
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_ms(mut self, max_ms: u64) -> Self {
+        self.max_ms = Some(max_ms);
+        self
+    }
+
+    pub fn with_max_visited(mut self, max_visited: u64) -> Self {
+        self.max_visited = Some(max_visited);
+        self
+    }
+
+    pub fn with_cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel_token = Some(token);
+        self
+    }
+}
+
+/// Which limit stopped the query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum BudgetExceeded {
+    Timeout { max_ms: u64 },
+    MaxVisited { max_visited: u64 },
+    Cancelled,
+}
+
+impl std::fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BudgetExceeded::Timeout { max_ms } => write!(f, "query exceeded {max_ms}ms budget"),
+            BudgetExceeded::MaxVisited { max_visited } => {
+                write!(f, "query exceeded {max_visited} visited entities")
+            }
+            BudgetExceeded::Cancelled => write!(f, "query cancelled"),
+        }
+    }
+}
+
+/// Completion status of a budgeted query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum QueryStatus {
+    Complete,
+    BudgetExceeded { exceeded: BudgetExceeded },
+}
+
+/// A (possibly partial) result plus how it was produced.
+#[derive(Debug, Clone)]
+pub struct Budgeted<T> {
+    pub value: T,
+    pub status: QueryStatus,
+    /// Entities expanded while producing `value` (`find_paths` also counts
+    /// each edge it follows).
+    pub visited: u64,
+    pub elapsed: Duration,
+}
+
+impl<T> Budgeted<T> {
+    pub fn is_complete(&self) -> bool {
+        self.status == QueryStatus::Complete
+    }
+}
+
+/// Running budget state threaded through the executor.
+pub(crate) struct BudgetTracker {
+    started: Instant,
+    max_duration: Option<Duration>,
+    max_ms: u64,
+    max_visited: Option<u64>,
+    cancel_token: Option<CancelToken>,
+    visited: u64,
+    exceeded: Option<BudgetExceeded>,
+}
+
+impl BudgetTracker {
+    pub(crate) fn new(budget: &QueryBudget) -> Self {
+        Self {
+            started: Instant::now(),
+            max_duration: budget.max_ms.map(Duration::from_millis),
+            max_ms: budget.max_ms.unwrap_or(0),
+            max_visited: budget.max_visited,
+            cancel_token: budget.cancel_token.clone(),
+            visited: 0,
+            exceeded: None,
+        }
+    }
+
+    pub(crate) fn unlimited() -> Self {
+        Self::new(&QueryBudget::unlimited())
+    }
+
+    /// Account for expanding one entity. Once this returns `Err`, every later
+    /// call does too, so nested traversals unwind without extra plumbing.
+    pub(crate) fn visit(&mut self) -> Result<(), BudgetExceeded> {
+        if let Some(exceeded) = self.exceeded {
+            return Err(exceeded);
+        }
+        if let Some(max_visited) = self.max_visited {
+            if self.visited >= max_visited {
+                return Err(*self
+                    .exceeded
+                    .insert(BudgetExceeded::MaxVisited { max_visited }));
+            }
+        }
+        if self
+            .cancel_token
+            .as_ref()
+            .is_some_and(CancelToken::is_cancelled)
+        {
+            return Err(*self.exceeded.insert(BudgetExceeded::Cancelled));
+        }
+        if let Some(max) = self.max_duration {
+            if self.started.elapsed() > max {
+                return Err(*self.exceeded.insert(BudgetExceeded::Timeout {
+                    max_ms: self.max_ms,
+                }));
+            }
+        }
+        self.visited += 1;
+        Ok(())
+    }
+
+    pub(crate) fn exceeded(&self) -> Option<BudgetExceeded> {
+        self.exceeded
+    }
+
+    pub(crate) fn finish<T>(self, value: T) -> Budgeted<T> {
+        Budgeted {
+            value,
+            status: match self.exceeded {
+                None => QueryStatus::Complete,
+                Some(exceeded) => QueryStatus::BudgetExceeded { exceeded },
+            },
+            visited: self.visited,
+            elapsed: self.started.elapsed(),
+        }
+    }
+}
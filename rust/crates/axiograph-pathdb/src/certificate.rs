@@ -3,7 +3,10 @@
 //! This module defines a minimal, versioned JSON shape intended to be consumed
 //! by a trusted checker (Lean during migration).
 
+use crate::collapse::RelationCollapseProofV1;
+use crate::confidence::ConfidenceCombiner;
 use crate::migration::DeltaFMigrationProofV1;
+use crate::partition::ConfidencePartitionProofV1;
 use crate::ReachabilityProof;
 use axiograph_dsl::schema_v1::PathExprV3 as AxiPathExprV3;
 use serde::{Deserialize, Serialize};
@@ -226,6 +229,19 @@ impl ReachabilityProofV2 {
             } => rel_confidence_fp.mul(rest.path_confidence()),
         }
     }
+
+    /// Path confidence under an explicit combination policy
+    /// (`path_confidence` is the `Product` case).
+    pub fn path_confidence_with(&self, combiner: ConfidenceCombiner) -> FixedPointProbability {
+        match self {
+            ReachabilityProofV2::Reflexive { .. } => combiner.identity_fp(),
+            ReachabilityProofV2::Step {
+                rel_confidence_fp,
+                rest,
+                ..
+            } => combiner.combine_fp(*rel_confidence_fp, rest.path_confidence_with(combiner)),
+        }
+    }
 }
 
 /// Versioned wrapper for v2 certificates (fixed-point probabilities).
@@ -310,6 +326,10 @@ pub enum CertificatePayloadV2 {
     },
     ReachabilityV2 {
         proof: ReachabilityProofV2,
+        /// Policy used to combine edge confidences into the path confidence.
+        /// Omitted for `product`, the policy the checker recomputes.
+        #[serde(default, skip_serializing_if = "ConfidenceCombiner::is_product")]
+        combiner: ConfidenceCombiner,
     },
     ResolutionV2 {
         proof: ResolutionProofV2,
@@ -342,6 +362,14 @@ pub enum CertificatePayloadV2 {
     DeltaFMigrationV1 {
         proof: DeltaFMigrationProofV1,
     },
+    #[serde(rename = "relation_collapse_v1")]
+    RelationCollapseV1 {
+        proof: RelationCollapseProofV1,
+    },
+    #[serde(rename = "confidence_partition_v1")]
+    ConfidencePartitionV1 {
+        proof: ConfidencePartitionProofV1,
+    },
 }
 
 impl CertificateV2 {
@@ -365,7 +393,22 @@ impl CertificateV2 {
         Self {
             version: CERTIFICATE_VERSION_V2,
             anchor: None,
-            payload: CertificatePayloadV2::ReachabilityV2 { proof },
+            payload: CertificatePayloadV2::ReachabilityV2 {
+                proof,
+                combiner: ConfidenceCombiner::Product,
+            },
+        }
+    }
+
+    /// Reachability certificate recording a non-default combination policy.
+    pub fn reachability_with_combiner(
+        proof: ReachabilityProofV2,
+        combiner: ConfidenceCombiner,
+    ) -> Self {
+        Self {
+            version: CERTIFICATE_VERSION_V2,
+            anchor: None,
+            payload: CertificatePayloadV2::ReachabilityV2 { proof, combiner },
         }
     }
 
@@ -441,6 +484,22 @@ impl CertificateV2 {
         }
     }
 
+    pub fn relation_collapse_v1(proof: RelationCollapseProofV1) -> Self {
+        Self {
+            version: CERTIFICATE_VERSION_V2,
+            anchor: None,
+            payload: CertificatePayloadV2::RelationCollapseV1 { proof },
+        }
+    }
+
+    pub fn confidence_partition_v1(proof: ConfidencePartitionProofV1) -> Self {
+        Self {
+            version: CERTIFICATE_VERSION_V2,
+            anchor: None,
+            payload: CertificatePayloadV2::ConfidencePartitionV1 { proof },
+        }
+    }
+
     pub fn with_anchor(mut self, anchor: AxiAnchorV1) -> Self {
         self.anchor = Some(anchor);
         self
@@ -1008,6 +1067,224 @@ pub struct PathEquivProofV2 {
     pub right_derivation: Option<Vec<PathRewriteStepV2>>,
 }
 
+// =============================================================================
+// Typed attribute literals (exact numerics; no floats in the trusted checker)
+// =============================================================================
+
+/// Typed reading of an attribute value.
+///
+/// PathDB stores every attribute value as an interned string. Certificates and
+/// `PathDBExportV1` snapshots additionally carry this typed reading so numeric
+/// obligations (`weight ≥ 10 kg`) do not depend on string comparison.
+///
+/// Decimals are exact: `mantissa × 10^exponent`, normalized so the mantissa
+/// has no trailing zeros (`2.50` and `2.5` read the same).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TypedLiteralV1 {
+    Int {
+        value: i64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        unit: Option<String>,
+    },
+    Float {
+        mantissa: i64,
+        exponent: i32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        unit: Option<String>,
+    },
+    Bool {
+        value: bool,
+    },
+    String {
+        value: String,
+    },
+}
+
+impl TypedLiteralV1 {
+    /// Deterministically read a stored attribute value.
+    ///
+    /// Recognized (exactly, no surrounding whitespace):
+    /// - `true` / `false`,
+    /// - integers (`42`, `-7`; no leading zeros, must fit `i64`),
+    /// - decimals (`2.5`, `-0.125`, `6.02e23`),
+    /// - either numeric form followed by one space and a unit (`12 kg`, `99.5 %`).
+    ///
+    /// Everything else (including `007` and `2024-01-01`) stays a string.
+    pub fn infer(raw: &str) -> Self {
+        match raw {
+            "true" => return Self::Bool { value: true },
+            "false" => return Self::Bool { value: false },
+            _ => {}
+        }
+        let (number, unit) = match raw.split_once(' ') {
+            Some((number, unit)) if is_unit(unit) => (number, Some(unit.to_string())),
+            Some(_) => (raw, None),
+            None => (raw, None),
+        };
+        if !number.contains(['.', 'e', 'E']) {
+            if let Some(value) = parse_int(number) {
+                return Self::Int { value, unit };
+            }
+        } else if let Some((mantissa, exponent)) = parse_decimal(number) {
+            return Self::Float {
+                mantissa,
+                exponent,
+                unit,
+            };
+        }
+        Self::String {
+            value: raw.to_string(),
+        }
+    }
+
+    /// `infer(raw)`, unless it is just a string: what witnesses and exports carry.
+    pub fn typed(raw: &str) -> Option<Self> {
+        Some(Self::infer(raw)).filter(|lit| !lit.is_string())
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(self, Self::String { .. })
+    }
+
+    pub fn unit(&self) -> Option<&str> {
+        match self {
+            Self::Int { unit, .. } | Self::Float { unit, .. } => unit.as_deref(),
+            Self::Bool { .. } | Self::String { .. } => None,
+        }
+    }
+
+    /// `(mantissa, exponent)` for numeric literals.
+    fn decimal(&self) -> Option<(i64, i32)> {
+        match self {
+            Self::Int { value, .. } => Some((*value, 0)),
+            Self::Float {
+                mantissa, exponent, ..
+            } => Some((*mantissa, *exponent)),
+            Self::Bool { .. } | Self::String { .. } => None,
+        }
+    }
+
+    /// Compare two literals of the same kind (and unit, for numerics).
+    ///
+    /// Ints and floats compare exactly with each other; mismatched units or
+    /// kinds are incomparable (`None`).
+    pub fn compare(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (Self::Bool { value: a }, Self::Bool { value: b }) => Some(a.cmp(b)),
+            (Self::String { value: a }, Self::String { value: b }) => Some(a.cmp(b)),
+            _ => {
+                if self.unit() != other.unit() {
+                    return None;
+                }
+                Some(compare_decimal(self.decimal()?, other.decimal()?))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for TypedLiteralV1 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int { value, .. } => write!(f, "{value}")?,
+            Self::Float {
+                mantissa, exponent, ..
+            } => write!(f, "{mantissa}e{exponent}")?,
+            Self::Bool { value } => return write!(f, "{value}"),
+            Self::String { value } => return write!(f, "{value:?}"),
+        }
+        match self.unit() {
+            Some(unit) => write!(f, " {unit}"),
+            None => Ok(()),
+        }
+    }
+}
+
+fn is_unit(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_alphabetic() || "%°$€£µ".contains(c))
+        && s.len() <= 16
+        && !s.contains(char::is_whitespace)
+}
+
+fn parse_int(s: &str) -> Option<i64> {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    let canonical = !digits.is_empty()
+        && digits.bytes().all(|b| b.is_ascii_digit())
+        && (digits == "0" || !digits.starts_with('0'))
+        && s != "-0";
+    canonical.then(|| s.parse().ok()).flatten()
+}
+
+/// Parse `-?int(.digits)?([eE][+-]?digits)?` into a normalized decimal.
+fn parse_decimal(s: &str) -> Option<(i64, i32)> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let (significand, exponent) = match s.split_once(['e', 'E']) {
+        Some((significand, exp)) => {
+            let digits = exp.strip_prefix(['+', '-']).unwrap_or(exp);
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            (significand, exp.parse::<i32>().ok()?)
+        }
+        None => (s, 0),
+    };
+    let (int_part, frac_part) = match significand.split_once('.') {
+        Some((i, f)) if !f.is_empty() => (i, f),
+        Some(_) => return None,
+        None => (significand, ""),
+    };
+    let all_digits = |p: &str| p.bytes().all(|b| b.is_ascii_digit());
+    if int_part.is_empty() || !all_digits(int_part) || !all_digits(frac_part) {
+        return None;
+    }
+    if int_part.len() > 1 && int_part.starts_with('0') {
+        return None;
+    }
+
+    let digits = format!("{int_part}{frac_part}");
+    let digits = digits.trim_start_matches('0');
+    let trimmed = digits.trim_end_matches('0');
+    let mut exponent = exponent.checked_sub(i32::try_from(frac_part.len()).ok()?)?;
+    exponent = exponent.checked_add(i32::try_from(digits.len() - trimmed.len()).ok()?)?;
+    if trimmed.is_empty() {
+        // Zero: `-0` and `0.000` are both plain zero.
+        return (!negative || significand != "0").then_some((0, 0));
+    }
+    let magnitude = trimmed.parse::<i64>().ok()?;
+    Some((if negative { -magnitude } else { magnitude }, exponent))
+}
+
+fn compare_decimal((am, ae): (i64, i32), (bm, be): (i64, i32)) -> std::cmp::Ordering {
+    let sign = |m: i64| m.signum();
+    if sign(am) != sign(bm) || am == 0 {
+        return sign(am).cmp(&sign(bm));
+    }
+    // Same non-zero sign: compare magnitudes by order of magnitude first, then
+    // exactly once the exponents are close enough to align in an i128.
+    let digits = |m: i64| m.unsigned_abs().to_string().len() as i64;
+    let (a_mag, b_mag) = (digits(am) + ae as i64, digits(bm) + be as i64);
+    let magnitude = if a_mag != b_mag {
+        a_mag.cmp(&b_mag)
+    } else {
+        let shift = (ae as i64 - be as i64).unsigned_abs() as u32;
+        let (a, b) = (am.unsigned_abs() as i128, bm.unsigned_abs() as i128);
+        if ae >= be {
+            (a * 10i128.pow(shift)).cmp(&b)
+        } else {
+            a.cmp(&(b * 10i128.pow(shift)))
+        }
+    };
+    if am > 0 {
+        magnitude
+    } else {
+        magnitude.reverse()
+    }
+}
+
 // =============================================================================
 // Query result certificates (conjunctive queries; AxQL / SQL-ish)
 // =============================================================================
@@ -1087,6 +1364,9 @@ pub enum QueryAtomWitnessV1 {
         entity: u32,
         key_id: u32,
         value_id: u32,
+        /// Typed reading of the value (`TypedLiteralV1::infer`), when not a plain string.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        literal: Option<TypedLiteralV1>,
     },
     /// A path witness (as a reachability proof).
     ///
@@ -1251,6 +1531,9 @@ pub enum QueryAtomWitnessV3 {
         entity: String,
         key: String,
         value: String,
+        /// Typed reading of the value (`TypedLiteralV1::infer`), when not a plain string.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        literal: Option<TypedLiteralV1>,
     },
     Path {
         proof: ReachabilityProofV3,
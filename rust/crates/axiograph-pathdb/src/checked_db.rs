@@ -19,9 +19,16 @@ use crate::axi_meta::{ATTR_AXI_SCHEMA, META_REL_FACT_OF, REL_AXI_FACT_IN_CONTEXT
 use crate::axi_semantics::{AxiTypeCheckReport, MetaPlaneIndex, RelationDecl, SchemaIndex};
 use crate::axi_type::TypingEnv;
 use crate::PathDB;
-use anyhow::{anyhow, Result};
+use crate::error::{PathDbError, Result};
 use std::collections::HashMap;
 
+fn invalid_edge(relation: &str, reason: String) -> PathDbError {
+    PathDbError::InvalidEdge {
+        relation: relation.to_string(),
+        reason,
+    }
+}
+
 fn entity_attr_string(db: &PathDB, entity: u32, key: &str) -> Option<String> {
     let key_id = db.interner.id_of(key)?;
     let value_id = db.entities.get_attr(entity, key_id)?;
@@ -105,8 +112,8 @@ impl<'db> CheckedDb<'db> {
     pub fn new(db: &'db PathDB) -> Result<Self> {
         let env = TypingEnv::from_db(db)?;
         if env.meta.schemas.is_empty() {
-            return Err(anyhow!(
-                "cannot construct CheckedDb: no `.axi` schemas present in meta-plane"
+            return Err(PathDbError::CheckFailed(
+                "cannot construct CheckedDb: no `.axi` schemas present in meta-plane".to_string(),
             ));
         }
 
@@ -130,7 +137,7 @@ impl<'db> CheckedDb<'db> {
             } else if let Some(first) = report.modal_invariants.errors.first() {
                 msg.push_str(&format!("\nfirst modal/approx invariant error: {first}"));
             }
-            return Err(anyhow!(msg));
+            return Err(PathDbError::CheckFailed(msg));
         }
 
         Ok(Self { db, env })
@@ -666,8 +673,9 @@ impl<'db> CheckedDbMut<'db> {
             TypingEnv::from_db(view)?
         };
         if env.meta.schemas.is_empty() {
-            return Err(anyhow!(
+            return Err(PathDbError::CheckFailed(
                 "cannot construct CheckedDbMut: no `.axi` schemas present in meta-plane"
+                    .to_string(),
             ));
         }
 
@@ -687,7 +695,7 @@ impl<'db> CheckedDbMut<'db> {
             .meta
             .schemas
             .get(schema_name)
-            .ok_or_else(|| anyhow!("unknown schema `{schema_name}`"))
+            .ok_or_else(|| PathDbError::UnknownSchema(schema_name.to_string()))
     }
 
     pub fn relation_decl(&self, schema_name: &str, relation_name: &str) -> Result<&RelationDecl> {
@@ -695,7 +703,10 @@ impl<'db> CheckedDbMut<'db> {
         schema
             .relation_decls
             .get(relation_name)
-            .ok_or_else(|| anyhow!("unknown relation `{relation_name}` in schema `{schema_name}`"))
+            .ok_or_else(|| PathDbError::UnknownRelation {
+                schema: schema_name.to_string(),
+                relation: relation_name.to_string(),
+            })
     }
 
     /// Start constructing a schema-scoped object entity.
@@ -712,15 +723,14 @@ impl<'db> CheckedDbMut<'db> {
             .meta
             .schemas
             .get(schema_name)
-            .ok_or_else(|| anyhow!("unknown schema `{schema_name}`"))?
+            .ok_or_else(|| PathDbError::UnknownSchema(schema_name.to_string()))?
             .clone();
 
         if !schema.object_types.contains(type_name) {
-            return Err(anyhow!(
-                "unknown object type `{}` in schema `{}`",
-                type_name,
-                schema_name
-            ));
+            return Err(PathDbError::UnknownObjectType {
+                schema: schema_name.to_string(),
+                type_name: type_name.to_string(),
+            });
         }
 
         Ok(TypedEntityBuilder {
@@ -745,33 +755,38 @@ impl<'db> CheckedDbMut<'db> {
         attrs: Vec<(&str, &str)>,
     ) -> Result<bool> {
         if self.db.get_entity(source).is_none() {
-            return Err(anyhow!("add_edge: missing source entity {source}"));
+            return Err(PathDbError::UnknownEntity(source));
         }
         if self.db.get_entity(target).is_none() {
-            return Err(anyhow!("add_edge: missing target entity {target}"));
+            return Err(PathDbError::UnknownEntity(target));
         }
         if !confidence.is_finite() || !(0.0..=1.0).contains(&confidence) {
-            return Err(anyhow!(
-                "add_edge: confidence must be a finite number in [0,1] (got {confidence})"
+            return Err(invalid_edge(
+                rel_type,
+                format!("confidence must be a finite number in [0,1] (got {confidence})"),
             ));
         }
 
         // Enforce a core invariant: `axi_fact_in_context` must target a Context.
         if rel_type == REL_AXI_FACT_IN_CONTEXT {
             let Some(type_id) = self.db.entities.get_type(target) else {
-                return Err(anyhow!(
-                    "add_edge: `{REL_AXI_FACT_IN_CONTEXT}` target entity {target} has missing type"
+                return Err(invalid_edge(
+                    rel_type,
+                    format!("target entity {target} has missing type"),
                 ));
             };
             let Some(type_name) = self.db.interner.lookup(type_id) else {
-                return Err(anyhow!(
-                    "add_edge: `{REL_AXI_FACT_IN_CONTEXT}` target entity {target} has unknown type id {}",
-                    type_id.raw()
+                return Err(invalid_edge(
+                    rel_type,
+                    format!("target entity {target} has unknown type id {}", type_id.raw()),
                 ));
             };
             if type_name != "Context" && type_name != "World" {
-                return Err(anyhow!(
-                    "add_edge: `{REL_AXI_FACT_IN_CONTEXT}` target must be a Context/World (got `{type_name}` for entity {target})"
+                return Err(invalid_edge(
+                    rel_type,
+                    format!(
+                        "target must be a Context/World (got `{type_name}` for entity {target})"
+                    ),
                 ));
             }
         }
@@ -796,12 +811,15 @@ impl<'db> CheckedDbMut<'db> {
             .meta
             .schemas
             .get(schema_name)
-            .ok_or_else(|| anyhow!("unknown schema `{schema_name}`"))?
+            .ok_or_else(|| PathDbError::UnknownSchema(schema_name.to_string()))?
             .clone();
         let rel_decl = schema
             .relation_decls
             .get(relation_name)
-            .ok_or_else(|| anyhow!("unknown relation `{relation_name}` in schema `{schema_name}`"))?
+            .ok_or_else(|| PathDbError::UnknownRelation {
+                schema: schema_name.to_string(),
+                relation: relation_name.to_string(),
+            })?
             .clone();
 
         Ok(TypedFactBuilder {
@@ -868,6 +886,13 @@ pub struct TypedFactBuilder<'db> {
 }
 
 impl<'db> TypedFactBuilder<'db> {
+    fn invalid(&self, reason: String) -> PathDbError {
+        PathDbError::InvalidFact {
+            relation: self.relation.clone(),
+            reason,
+        }
+    }
+
     /// Add an attribute to the fact node (e.g. `name`, `axi_fact_id`, provenance pointers).
     pub fn with_attr(mut self, key: &str, value: &str) -> Self {
         self.fact_attrs.push((key.to_string(), value.to_string()));
@@ -885,11 +910,10 @@ impl<'db> TypedFactBuilder<'db> {
     /// - the value entity is schema-scoped and has an allowed type (with subtyping).
     pub fn set_field(&mut self, field: &str, value: u32) -> Result<()> {
         let Some(field_decl) = self.decl.fields.iter().find(|f| f.field_name == field) else {
-            return Err(anyhow!(
-                "unknown field `{field}` for relation `{}` (schema `{}`)",
-                self.relation,
+            return Err(self.invalid(format!(
+                "unknown field `{field}` (schema `{}`)",
                 self.schema_name
-            ));
+            )));
         };
 
         // Adopt schema scoping for previously-unscoped entities (e.g. evidence-plane stubs).
@@ -904,35 +928,31 @@ impl<'db> TypedFactBuilder<'db> {
             }
         };
         if actual_schema != self.schema_name {
-            return Err(anyhow!(
-                "field `{field}` for relation `{}`: schema mismatch (expected `{}`, got `{}` for entity {value})",
-                self.relation,
+            return Err(self.invalid(format!(
+                "field `{field}`: schema mismatch (expected `{}`, got `{}` for entity {value})",
                 self.schema_name,
                 actual_schema
-            ));
+            )));
         }
 
         let Some(type_id) = self.db.entities.get_type(value) else {
-            return Err(anyhow!(
-                "field `{field}` for relation `{}`: value entity {value} has missing type",
-                self.relation
-            ));
+            return Err(self.invalid(format!(
+                "field `{field}`: value entity {value} has missing type"
+            )));
         };
         let Some(actual_type) = self.db.interner.lookup(type_id) else {
-            return Err(anyhow!(
-                "field `{field}` for relation `{}`: value entity {value} has unknown type id {}",
-                self.relation,
+            return Err(self.invalid(format!(
+                "field `{field}`: value entity {value} has unknown type id {}",
                 type_id.raw()
-            ));
+            )));
         };
 
         if !self.schema.is_subtype(&actual_type, &field_decl.field_type) {
-            return Err(anyhow!(
-                "field `{field}` for relation `{}`: expected `{}` but got `{}` (entity {value})",
-                self.relation,
+            return Err(self.invalid(format!(
+                "field `{field}`: expected `{}` but got `{}` (entity {value})",
                 field_decl.field_type,
                 actual_type
-            ));
+            )));
         }
 
         if self
@@ -940,10 +960,9 @@ impl<'db> TypedFactBuilder<'db> {
             .insert(field.to_string(), value)
             .is_some()
         {
-            return Err(anyhow!(
-                "duplicate assignment for field `{field}` in relation `{}`",
-                self.relation
-            ));
+            return Err(self.invalid(format!(
+                "duplicate assignment for field `{field}`"
+            )));
         }
 
         Ok(())
@@ -954,12 +973,11 @@ impl<'db> TypedFactBuilder<'db> {
         // Ensure all declared fields are present.
         for f in &self.decl.fields {
             if !self.field_values.contains_key(&f.field_name) {
-                return Err(anyhow!(
-                    "missing field `{}` for relation `{}` (schema `{}`)",
+                return Err(self.invalid(format!(
+                    "missing field `{}` (schema `{}`)",
                     f.field_name,
-                    self.relation,
                     self.schema_name
-                ));
+                )));
             }
         }
 
@@ -1032,23 +1050,22 @@ impl<'db> TypedFactBuilder<'db> {
         // Ensure all declared fields are present.
         for f in &self.decl.fields {
             if !self.field_values.contains_key(&f.field_name) {
-                return Err(anyhow!(
-                    "missing field `{}` for relation `{}` (schema `{}`)",
+                return Err(self.invalid(format!(
+                    "missing field `{}` (schema `{}`)",
                     f.field_name,
-                    self.relation,
                     self.schema_name
-                ));
+                )));
             }
         }
 
         // Ensure required meta attrs are present and consistent.
         if let Some(existing_schema) = entity_attr_string(self.db, fact_id, ATTR_AXI_SCHEMA) {
             if existing_schema != self.schema_name {
-                return Err(anyhow!(
+                return Err(self.invalid(format!(
                     "fact {fact_id}: schema mismatch (expected `{}`, got `{}`)",
                     self.schema_name,
                     existing_schema
-                ));
+                )));
             }
         } else {
             self.db
@@ -1058,11 +1075,11 @@ impl<'db> TypedFactBuilder<'db> {
             entity_attr_string(self.db, fact_id, crate::axi_meta::ATTR_AXI_RELATION)
         {
             if existing_rel != self.relation {
-                return Err(anyhow!(
+                return Err(self.invalid(format!(
                     "fact {fact_id}: relation mismatch (expected `{}`, got `{}`)",
                     self.relation,
                     existing_rel
-                ));
+                )));
             }
         } else {
             self.db.upsert_entity_attr(
@@ -1105,10 +1122,10 @@ impl<'db> TypedFactBuilder<'db> {
                 .copied()
                 .expect("checked above");
             let Some(field_rel_id) = self.db.interner.id_of(&f.field_name) else {
-                return Err(anyhow!(
+                return Err(self.invalid(format!(
                     "fact {fact_id}: missing interned relation id for field `{}`",
                     f.field_name
-                ));
+                )));
             };
             let outgoing = self.db.relations.outgoing(fact_id, field_rel_id);
             match outgoing.len() {
@@ -1123,27 +1140,27 @@ impl<'db> TypedFactBuilder<'db> {
                 }
                 1 => {
                     if outgoing[0].target != value {
-                        return Err(anyhow!(
+                        return Err(self.invalid(format!(
                             "fact {fact_id}: conflicting value for field `{}` (existing={}, new={})",
                             f.field_name,
                             outgoing[0].target,
                             value
-                        ));
+                        )));
                     }
                 }
                 _ => {
-                    return Err(anyhow!(
+                    return Err(self.invalid(format!(
                         "fact {fact_id}: multiple values already present for field `{}`",
                         f.field_name
-                    ));
+                    )));
                 }
             }
 
             if f.field_name == "ctx" {
                 let Some(scope_rel_id) = self.db.interner.id_of(REL_AXI_FACT_IN_CONTEXT) else {
-                    return Err(anyhow!(
+                    return Err(self.invalid(format!(
                         "fact {fact_id}: missing interned relation id for `{REL_AXI_FACT_IN_CONTEXT}`"
-                    ));
+                    )));
                 };
                 let scopes = self.db.relations.outgoing(fact_id, scope_rel_id);
                 match scopes.len() {
@@ -1158,17 +1175,17 @@ impl<'db> TypedFactBuilder<'db> {
                     }
                     1 => {
                         if scopes[0].target != value {
-                            return Err(anyhow!(
+                            return Err(self.invalid(format!(
                                 "fact {fact_id}: `{REL_AXI_FACT_IN_CONTEXT}` mismatch (ctx={}, axi_fact_in_context={})",
                                 value,
                                 scopes[0].target
-                            ));
+                            )));
                         }
                     }
                     _ => {
-                        return Err(anyhow!(
+                        return Err(self.invalid(format!(
                             "fact {fact_id}: multiple `{REL_AXI_FACT_IN_CONTEXT}` edges present"
-                        ));
+                        )));
                     }
                 }
             }
@@ -1181,6 +1198,7 @@ impl<'db> TypedFactBuilder<'db> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use anyhow::{anyhow, Result};
 
     #[test]
     fn typed_fact_builder_requires_all_fields_and_schema_scoping() -> Result<()> {
@@ -1342,8 +1360,15 @@ instance I of S:
 
         match checked.entity_builder("S", "NotAType") {
             Ok(_) => return Err(anyhow!("expected unknown object type to be rejected")),
-            Err(e) => assert!(e.to_string().contains("unknown object type")),
+            Err(e) => assert!(matches!(
+                e,
+                PathDbError::UnknownObjectType { ref type_name, .. } if type_name == "NotAType"
+            )),
         }
+        assert!(matches!(
+            checked.entity_builder("Nope", "Person"),
+            Err(PathDbError::UnknownSchema(ref s)) if s == "Nope"
+        ));
         Ok(())
     }
 
@@ -0,0 +1,296 @@
+//! Duplicate-relation collapse.
+//!
+//! Repeated ingest runs leave parallel edges: the same `source -rel-> target`
+//! asserted several times with different attributes and confidences.
+//! [`PathDB::collapse_duplicate_relations`] merges each such group into its
+//! first (lowest-id) edge:
+//!
+//! - the merged confidence combines every duplicate's confidence with the
+//!   configured [`ConfidenceCombiner`] (noisy-or by default: repeated
+//!   assertions are corroborating evidence), in fixed point so a checker can
+//!   replay it;
+//! - the merged attributes are the union of the duplicates' `(key, value)`
+//!   pairs, so multi-valued provenance (`axi_fact_id`, `source`, ...) is kept,
+//!   plus [`ATTR_MERGED_COUNT`];
+//! - each duplicate's original confidence and attributes are returned in the
+//!   [`CollapsedEdge`] provenance list.
+//!
+//! Relation ids are compacted afterwards. The emitted
+//! [`RelationCollapseProofV1`] (`relation_collapse_v1` certificate) maps every
+//! old relation id to its new one and records the confidences that were
+//! combined, so certificates citing the old edges can be rewritten and the
+//! merged confidences re-checked.
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::certificate::{CertificateV2, FixedPointProbability};
+use crate::confidence::ConfidenceCombiner;
+use crate::{PathDB, StrId};
+
+/// Relation attribute holding how many edges were merged into one.
+pub const ATTR_MERGED_COUNT: &str = "axi_merged_count";
+
+/// Options for [`PathDB::collapse_duplicate_relations`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollapseConfig {
+    /// How duplicate confidences are combined.
+    pub combiner: ConfidenceCombiner,
+    /// Relation types to collapse (empty: all).
+    #[serde(default)]
+    pub relations: Vec<String>,
+}
+
+impl Default for CollapseConfig {
+    fn default() -> Self {
+        Self {
+            combiner: ConfidenceCombiner::NoisyOr,
+            relations: Vec::new(),
+        }
+    }
+}
+
+/// One duplicate as it was before the collapse.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EdgeProvenance {
+    /// Relation id before the collapse.
+    pub relation_id: u32,
+    pub confidence: f32,
+    pub attrs: Vec<(String, String)>,
+}
+
+/// A group of parallel edges merged into one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollapsedEdge {
+    pub source: u32,
+    pub rel_type: String,
+    pub target: u32,
+    /// Relation id of the merged edge after the collapse.
+    pub relation_id: u32,
+    pub confidence: f32,
+    /// The merged duplicates, in relation-id order.
+    pub provenance: Vec<EdgeProvenance>,
+}
+
+/// Result of [`PathDB::collapse_duplicate_relations`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelationCollapse {
+    pub edges: Vec<CollapsedEdge>,
+    /// Relations removed (merged into another).
+    pub removed: usize,
+    pub proof: RelationCollapseProofV1,
+}
+
+impl RelationCollapse {
+    pub fn certificate(&self) -> CertificateV2 {
+        CertificateV2::relation_collapse_v1(self.proof.clone())
+    }
+}
+
+/// One merge in a [`RelationCollapseProofV1`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollapsedEdgeV1 {
+    pub source: u32,
+    pub rel_type: u32,
+    pub target: u32,
+    /// Old relation ids merged, ascending; the first one was kept.
+    pub relation_ids: Vec<u32>,
+    /// Their confidences, in the same order.
+    pub confidences_fp: Vec<FixedPointProbability>,
+    pub merged_relation_id: u32,
+    pub merged_confidence_fp: FixedPointProbability,
+}
+
+/// Rewrite proof for a duplicate-relation collapse.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelationCollapseProofV1 {
+    pub combiner: ConfidenceCombiner,
+    /// New relation id of every old relation id (indexed by old id).
+    pub relation_id_map: Vec<u32>,
+    pub edges: Vec<CollapsedEdgeV1>,
+}
+
+impl RelationCollapseProofV1 {
+    /// New relation id for an old one.
+    pub fn map_relation_id(&self, old: u32) -> Option<u32> {
+        self.relation_id_map.get(old as usize).copied()
+    }
+
+    /// Replay the proof: merged confidences recombine from their parts, merged
+    /// ids map to the merged edge, and every other id keeps its relative order.
+    pub fn check(&self) -> std::result::Result<(), String> {
+        let mut merged_away = HashSet::new();
+        for (i, edge) in self.edges.iter().enumerate() {
+            if edge.relation_ids.len() < 2 || edge.relation_ids.len() != edge.confidences_fp.len() {
+                return Err(format!("edge {i}: needs two or more ids with confidences"));
+            }
+            if !edge.relation_ids.windows(2).all(|w| w[0] < w[1]) {
+                return Err(format!("edge {i}: relation ids are not ascending"));
+            }
+            if edge
+                .relation_ids
+                .iter()
+                .any(|&id| self.map_relation_id(id) != Some(edge.merged_relation_id))
+            {
+                return Err(format!(
+                    "edge {i}: a merged id does not map to {}",
+                    edge.merged_relation_id
+                ));
+            }
+            let replayed = edge
+                .confidences_fp
+                .iter()
+                .fold(self.combiner.identity_fp(), |acc, &c| {
+                    self.combiner.combine_fp(acc, c)
+                });
+            if replayed != edge.merged_confidence_fp {
+                return Err(format!(
+                    "edge {i}: merged confidence {} does not replay ({})",
+                    edge.merged_confidence_fp.numerator(),
+                    replayed.numerator()
+                ));
+            }
+            merged_away.extend(edge.relation_ids[1..].iter().copied());
+        }
+        let mut next = 0;
+        for (old, &new) in self.relation_id_map.iter().enumerate() {
+            if merged_away.contains(&(old as u32)) {
+                continue;
+            }
+            if new != next {
+                return Err(format!("relation {old} maps to {new}, expected {next}"));
+            }
+            next += 1;
+        }
+        Ok(())
+    }
+}
+
+impl PathDB {
+    /// Merge parallel edges (same source, relation type and target) into one
+    /// (see the module docs). Relation ids are compacted.
+    pub fn collapse_duplicate_relations(&mut self, config: &CollapseConfig) -> RelationCollapse {
+        let allowed: Option<HashSet<StrId>> = (!config.relations.is_empty()).then(|| {
+            config
+                .relations
+                .iter()
+                .filter_map(|r| self.interner.id_of(r))
+                .collect()
+        });
+        let mut groups: BTreeMap<(u32, StrId, u32), Vec<u32>> = BTreeMap::new();
+        for id in 0..self.relations.len() as u32 {
+            let Some(rel) = self.relations.get_relation(id) else {
+                continue;
+            };
+            if allowed.as_ref().is_none_or(|a| a.contains(&rel.rel_type)) {
+                groups
+                    .entry((rel.source, rel.rel_type, rel.target))
+                    .or_default()
+                    .push(id);
+            }
+        }
+        let mut groups: Vec<_> = groups
+            .into_iter()
+            .filter(|(_, ids)| ids.len() > 1)
+            .collect();
+        groups.sort_by_key(|(_, ids)| ids[0]);
+
+        let merged_count = self.interner.intern(ATTR_MERGED_COUNT);
+        let mut merged_away = HashSet::new();
+        let mut edges = Vec::new();
+        let mut proof_edges = Vec::new();
+        for ((source, rel_type, target), ids) in groups {
+            let mut provenance = Vec::new();
+            let mut confidences_fp = Vec::new();
+            let mut attrs: Vec<(StrId, StrId)> = Vec::new();
+            for &id in &ids {
+                let rel = &self.relations.relations[id as usize];
+                confidences_fp.push(FixedPointProbability::from_f32(rel.confidence));
+                provenance.push(EdgeProvenance {
+                    relation_id: id,
+                    confidence: rel.confidence,
+                    attrs: rel
+                        .attrs
+                        .iter()
+                        .map(|&(k, v)| {
+                            (
+                                self.interner.lookup(k).unwrap_or_default(),
+                                self.interner.lookup(v).unwrap_or_default(),
+                            )
+                        })
+                        .collect(),
+                });
+                for &pair in &rel.attrs {
+                    if pair.0 != merged_count && !attrs.contains(&pair) {
+                        attrs.push(pair);
+                    }
+                }
+            }
+            attrs.push((merged_count, self.interner.intern(&ids.len().to_string())));
+            let merged_fp = confidences_fp
+                .iter()
+                .fold(config.combiner.identity_fp(), |acc, &c| {
+                    config.combiner.combine_fp(acc, c)
+                });
+            let kept = &mut self.relations.relations[ids[0] as usize];
+            kept.confidence = merged_fp.to_f32();
+            kept.attrs = attrs;
+
+            merged_away.extend(ids[1..].iter().copied());
+            edges.push(CollapsedEdge {
+                source,
+                rel_type: self.interner.lookup(rel_type).unwrap_or_default(),
+                target,
+                relation_id: ids[0],
+                confidence: merged_fp.to_f32(),
+                provenance,
+            });
+            proof_edges.push(CollapsedEdgeV1 {
+                source,
+                rel_type: rel_type.raw(),
+                target,
+                merged_relation_id: ids[0],
+                relation_ids: ids,
+                confidences_fp,
+                merged_confidence_fp: merged_fp,
+            });
+        }
+
+        // Compact: surviving ids shift down past the merged-away ones.
+        let mut relation_id_map = Vec::with_capacity(self.relations.len());
+        let mut next = 0;
+        for old in 0..self.relations.len() as u32 {
+            if merged_away.contains(&old) {
+                relation_id_map.push(u32::MAX);
+            } else {
+                relation_id_map.push(next);
+                next += 1;
+            }
+        }
+        for (edge, proof_edge) in edges.iter_mut().zip(&mut proof_edges) {
+            let new_id = relation_id_map[edge.relation_id as usize];
+            edge.relation_id = new_id;
+            proof_edge.merged_relation_id = new_id;
+            for &old in &proof_edge.relation_ids {
+                relation_id_map[old as usize] = new_id;
+            }
+        }
+        let mut old = 0u32;
+        let removed = self.retain_relations(|_| {
+            let keep = !merged_away.contains(&old);
+            old += 1;
+            keep
+        });
+
+        RelationCollapse {
+            edges,
+            removed,
+            proof: RelationCollapseProofV1 {
+                combiner: config.combiner,
+                relation_id_map,
+                edges: proof_edges,
+            },
+        }
+    }
+}
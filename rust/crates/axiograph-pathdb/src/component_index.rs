@@ -0,0 +1,140 @@
+//! ComponentIndex: incrementally maintained connected components.
+//!
+//! Guardrail and dedup checks frequently ask "are these two entities in the
+//! same component?" over some subset of relation types (e.g. `sameAs` +
+//! `equivalent_class`). Re-running a BFS per check is wasteful, so we keep a
+//! union-find per requested relation-type set:
+//!
+//! - The first `same_component(a, b, rel_set)` for a set builds its
+//!   union-find from the relation store (O(E α(N))).
+//! - Subsequent `add_relation` calls union endpoints into every registered
+//!   set containing the relation type, so later checks are ~O(1).
+//!
+//! Components are **undirected** (weak connectivity). Entities added after a
+//! set was built are singletons until a relation connects them.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::{PathDB, StrId};
+
+/// Union-find with path halving and union by size.
+#[derive(Debug, Clone, Default)]
+pub struct UnionFind {
+    parent: Vec<u32>,
+    size: Vec<u32>,
+}
+
+impl UnionFind {
+    pub fn with_len(n: usize) -> Self {
+        Self {
+            parent: (0..n as u32).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    fn ensure(&mut self, x: u32) {
+        let needed = x as usize + 1;
+        if self.parent.len() < needed {
+            let start = self.parent.len() as u32;
+            self.parent.extend(start..needed as u32);
+            self.size.resize(needed, 1);
+        }
+    }
+
+    pub fn find(&mut self, mut x: u32) -> u32 {
+        self.ensure(x);
+        while self.parent[x as usize] != x {
+            let grandparent = self.parent[self.parent[x as usize] as usize];
+            self.parent[x as usize] = grandparent;
+            x = grandparent;
+        }
+        x
+    }
+
+    /// Merge the components of `a` and `b`. Returns `true` if they were distinct.
+    pub fn union(&mut self, a: u32, b: u32) -> bool {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return false;
+        }
+        let (big, small) = if self.size[ra as usize] >= self.size[rb as usize] {
+            (ra, rb)
+        } else {
+            (rb, ra)
+        };
+        self.parent[small as usize] = big;
+        self.size[big as usize] += self.size[small as usize];
+        true
+    }
+
+    /// Size of the component containing `x`.
+    pub fn component_size(&mut self, x: u32) -> u32 {
+        let r = self.find(x);
+        self.size[r as usize]
+    }
+}
+
+/// Canonical key for a relation-type set: sorted, deduplicated interned ids.
+fn rel_set_key(rel_types: &[StrId]) -> Vec<u32> {
+    let mut key: Vec<u32> = rel_types.iter().map(|id| id.raw()).collect();
+    key.sort_unstable();
+    key.dedup();
+    key
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ComponentIndexCache {
+    sets: RwLock<HashMap<Vec<u32>, UnionFind>>,
+}
+
+impl ComponentIndexCache {
+    /// Drop every registered set (a union-find cannot forget an edge).
+    pub(crate) fn invalidate(&mut self) {
+        self.sets
+            .get_mut()
+            .expect("component index poisoned")
+            .clear();
+    }
+
+    /// Incrementally apply a newly inserted relation to every registered set.
+    pub(crate) fn on_relation_added(&mut self, rel_type: StrId, source: u32, target: u32) {
+        let sets = self.sets.get_mut().expect("component index poisoned");
+        for (key, uf) in sets.iter_mut() {
+            if key.binary_search(&rel_type.raw()).is_ok() {
+                uf.union(source, target);
+            }
+        }
+    }
+
+    pub(crate) fn same_component(&self, db: &PathDB, a: u32, b: u32, rel_types: &[StrId]) -> bool {
+        if a == b {
+            return true;
+        }
+        self.with_set(db, rel_types, |uf| uf.find(a) == uf.find(b))
+    }
+
+    pub(crate) fn component_size(&self, db: &PathDB, x: u32, rel_types: &[StrId]) -> u32 {
+        self.with_set(db, rel_types, |uf| uf.component_size(x))
+    }
+
+    fn with_set<R>(
+        &self,
+        db: &PathDB,
+        rel_types: &[StrId],
+        f: impl FnOnce(&mut UnionFind) -> R,
+    ) -> R {
+        let key = rel_set_key(rel_types);
+        let mut sets = self.sets.write().expect("component index poisoned");
+        let uf = sets.entry(key).or_insert_with_key(|key| {
+            let mut uf = UnionFind::with_len(db.entities.len());
+            for rel in &db.relations.relations {
+                if key.binary_search(&rel.rel_type.raw()).is_ok() {
+                    uf.union(rel.source, rel.target);
+                }
+            }
+            uf
+        });
+        f(uf)
+    }
+}
@@ -0,0 +1,255 @@
+//! Composite `(type, attribute)` indexes.
+//!
+//! "`Person` with `name = X`" normally intersects the `Person` type bitmap
+//! with a scan of the whole `name` column. A composite index declared with
+//! [`PathDB::declare_composite_index`] maps each value of the attribute,
+//! restricted to entities of the type (subtypes and virtual types included,
+//! i.e. the members of `find_by_type`), straight to an entity bitmap.
+//!
+//! Indexes are maintained on every insert (`add_entity`, attribute upserts,
+//! virtual types, subtype declarations, overlay removals), so a lookup is
+//! always exact. [`PathDB::entities_with_type_attr`] uses one when it is
+//! declared and falls back to the intersection otherwise; the AxQL planner
+//! does the same for `?x is T, attr(?x, k, v)`.
+//!
+//! Declarations live in the meta plane (`AxiMetaCompositeIndex` entities), so
+//! they survive snapshots; the index data is rebuilt on load.
+
+use std::collections::{BTreeMap, HashMap};
+
+use roaring::RoaringBitmap;
+
+use crate::axi_meta::{
+    ATTR_COMPOSITE_ATTR, ATTR_COMPOSITE_TYPE, META_ATTR_NAME, META_TYPE_COMPOSITE_INDEX,
+};
+use crate::{PathDB, StrId};
+
+/// One `(type, attr)` index: value -> entities.
+#[derive(Debug, Clone, Default)]
+struct CompositeIndex {
+    by_value: HashMap<StrId, RoaringBitmap>,
+    /// The value each indexed entity is listed under.
+    entries: HashMap<u32, StrId>,
+}
+
+impl CompositeIndex {
+    fn set(&mut self, entity: u32, value: Option<StrId>) {
+        let old = match value {
+            Some(value) => self.entries.insert(entity, value),
+            None => self.entries.remove(&entity),
+        };
+        if old == value {
+            return;
+        }
+        if let Some(old) = old {
+            if let Some(ids) = self.by_value.get_mut(&old) {
+                ids.remove(entity);
+                if ids.is_empty() {
+                    self.by_value.remove(&old);
+                }
+            }
+        }
+        if let Some(value) = value {
+            self.by_value.entry(value).or_default().insert(entity);
+        }
+    }
+}
+
+/// Declared composite indexes, keyed by `(type, attr)`.
+#[derive(Debug, Clone, Default)]
+pub struct CompositeIndexes {
+    indexes: BTreeMap<(StrId, StrId), CompositeIndex>,
+}
+
+impl CompositeIndexes {
+    pub fn is_empty(&self) -> bool {
+        self.indexes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.indexes.len()
+    }
+
+    pub fn contains(&self, type_id: StrId, attr: StrId) -> bool {
+        self.indexes.contains_key(&(type_id, attr))
+    }
+
+    /// Entities of `type_id` with `attr = value`; `None` if `(type_id, attr)`
+    /// is not indexed.
+    pub fn lookup(&self, type_id: StrId, attr: StrId, value: StrId) -> Option<RoaringBitmap> {
+        let index = self.indexes.get(&(type_id, attr))?;
+        Some(index.by_value.get(&value).cloned().unwrap_or_default())
+    }
+
+    /// Number of distinct values in `(type_id, attr)`.
+    pub fn distinct_values(&self, type_id: StrId, attr: StrId) -> Option<usize> {
+        self.indexes
+            .get(&(type_id, attr))
+            .map(|index| index.by_value.len())
+    }
+
+    fn remove_entity(&mut self, entity: u32) {
+        for index in self.indexes.values_mut() {
+            index.set(entity, None);
+        }
+    }
+}
+
+impl PathDB {
+    /// Declared composite indexes.
+    pub fn composite_indexes(&self) -> &CompositeIndexes {
+        &self.composite_indexes
+    }
+
+    /// `(type, attr)` names of every declared composite index.
+    pub fn composite_index_names(&self) -> Vec<(String, String)> {
+        self.composite_indexes
+            .indexes
+            .keys()
+            .map(|&(type_id, attr)| {
+                (
+                    self.interner.lookup(type_id).unwrap_or_default(),
+                    self.interner.lookup(attr).unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    /// Declare a composite index on `(type_name, attr)` and build it from the
+    /// current snapshot. Returns false if it was already declared.
+    pub fn declare_composite_index(&mut self, type_name: &str, attr: &str) -> bool {
+        let type_id = self.interner.intern(type_name);
+        let attr_id = self.interner.intern(attr);
+        if self.composite_indexes.contains(type_id, attr_id) {
+            return false;
+        }
+        self.add_entity(
+            META_TYPE_COMPOSITE_INDEX,
+            vec![
+                (META_ATTR_NAME, &format!("{type_name}.{attr}")),
+                (ATTR_COMPOSITE_TYPE, type_name),
+                (ATTR_COMPOSITE_ATTR, attr),
+            ],
+        );
+        self.build_composite_index(type_id, attr_id);
+        true
+    }
+
+    /// Whether `(type_name, attr)` has a composite index.
+    pub fn has_composite_index(&self, type_name: &str, attr: &str) -> bool {
+        match (self.interner.id_of(type_name), self.interner.id_of(attr)) {
+            (Some(type_id), Some(attr_id)) => self.composite_indexes.contains(type_id, attr_id),
+            _ => false,
+        }
+    }
+
+    /// Composite-index lookup: `None` when `(type_name, attr)` is not indexed.
+    pub fn composite_lookup(
+        &self,
+        type_name: &str,
+        attr: &str,
+        value: &str,
+    ) -> Option<RoaringBitmap> {
+        let type_id = self.interner.id_of(type_name)?;
+        let attr_id = self.interner.id_of(attr)?;
+        if !self.composite_indexes.contains(type_id, attr_id) {
+            return None;
+        }
+        match self.interner.id_of(value) {
+            Some(value_id) => self.composite_indexes.lookup(type_id, attr_id, value_id),
+            None => Some(RoaringBitmap::new()),
+        }
+    }
+
+    /// Entities of `type_name` (subtypes included) with `attr = value`, via
+    /// the composite index when one is declared.
+    pub fn entities_with_type_attr(
+        &self,
+        type_name: &str,
+        attr: &str,
+        value: &str,
+    ) -> RoaringBitmap {
+        if let Some(ids) = self.composite_lookup(type_name, attr, value) {
+            return ids;
+        }
+        let (Some(of_type), Some(attr_id), Some(value_id)) = (
+            self.find_by_type(type_name),
+            self.interner.id_of(attr),
+            self.interner.id_of(value),
+        ) else {
+            return RoaringBitmap::new();
+        };
+        self.entities.entities_with_attr_value(attr_id, value_id) & of_type
+    }
+
+    fn build_composite_index(&mut self, type_id: StrId, attr_id: StrId) {
+        let mut index = CompositeIndex::default();
+        if let (Some(members), Some(col)) = (
+            self.entities.by_type(type_id),
+            self.entities.attrs.get(&attr_id),
+        ) {
+            for entity in members {
+                if let Some(&value) = col.get(&entity) {
+                    index.set(entity, Some(value));
+                }
+            }
+        }
+        self.composite_indexes
+            .indexes
+            .insert((type_id, attr_id), index);
+    }
+
+    /// Bring `entity`'s entries up to date after its types or attributes
+    /// changed.
+    pub(crate) fn reindex_composite(&mut self, entity: u32) {
+        if self.composite_indexes.is_empty() {
+            return;
+        }
+        for (&(type_id, attr_id), index) in &mut self.composite_indexes.indexes {
+            let is_member = self
+                .entities
+                .by_type(type_id)
+                .is_some_and(|ids| ids.contains(entity));
+            let value = is_member
+                .then(|| self.entities.get_attr(entity, attr_id))
+                .flatten();
+            index.set(entity, value);
+        }
+    }
+
+    /// Drop `entity` from every composite index (it left every type).
+    pub(crate) fn unindex_composite(&mut self, entity: u32) {
+        self.composite_indexes.remove_entity(entity);
+    }
+
+    /// Rebuild every declared index from scratch (after bulk type changes).
+    pub(crate) fn rebuild_composite_data(&mut self) {
+        let keys: Vec<(StrId, StrId)> = self.composite_indexes.indexes.keys().copied().collect();
+        for (type_id, attr_id) in keys {
+            self.build_composite_index(type_id, attr_id);
+        }
+    }
+
+    /// Re-declare every meta-plane composite index (after loading a
+    /// snapshot).
+    pub(crate) fn rebuild_composite_indexes(&mut self) {
+        let Some(decls) = self.find_by_type(META_TYPE_COMPOSITE_INDEX).cloned() else {
+            return;
+        };
+        let (Some(type_key), Some(attr_key)) = (
+            self.interner.id_of(ATTR_COMPOSITE_TYPE),
+            self.interner.id_of(ATTR_COMPOSITE_ATTR),
+        ) else {
+            return;
+        };
+        for decl in &decls {
+            let type_id = self.entities.get_attr(decl, type_key);
+            let attr_id = self.entities.get_attr(decl, attr_key);
+            if let (Some(type_id), Some(attr_id)) = (type_id, attr_id) {
+                if !self.composite_indexes.contains(type_id, attr_id) {
+                    self.build_composite_index(type_id, attr_id);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Confidence-combination policies.
+//!
+//! Path confidence has historically been the product of edge confidences
+//! (independent conjunction). That is one choice among several, and the right
+//! one depends on what the numbers mean:
+//!
+//! - [`ConfidenceCombiner::Min`]: a chain is as strong as its weakest link
+//!   (Gödel t-norm). Robust when edge confidences are correlated.
+//! - [`ConfidenceCombiner::Product`]: independent conjunction (the default,
+//!   and what the Lean checker recomputes for reachability certificates).
+//! - [`ConfidenceCombiner::NoisyOr`]: independent disjunction,
+//!   `1 - (1 - a)(1 - b)`. Suited to corroborating evidence for one fact.
+//! - [`ConfidenceCombiner::DempsterShafer`]: Dempster's rule for two Bayesian
+//!   mass functions on `{true, false}`, `ab / (ab + (1 - a)(1 - b))`. `0.5` is
+//!   neutral; total conflict (`1` vs `0`) yields `0.5`.
+//!
+//! Every combiner is commutative, associative and monotone in both arguments,
+//! and has an [`identity`](ConfidenceCombiner::identity), so a single edge
+//! always combines to its own confidence.
+
+use serde::{Deserialize, Serialize};
+
+use crate::certificate::{FixedPointProbability, FIXED_POINT_DENOMINATOR};
+
+/// How two confidences are combined into one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfidenceCombiner {
+    Min,
+    #[default]
+    Product,
+    NoisyOr,
+    DempsterShafer,
+}
+
+impl ConfidenceCombiner {
+    pub const ALL: [ConfidenceCombiner; 4] = [
+        ConfidenceCombiner::Min,
+        ConfidenceCombiner::Product,
+        ConfidenceCombiner::NoisyOr,
+        ConfidenceCombiner::DempsterShafer,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ConfidenceCombiner::Min => "min",
+            ConfidenceCombiner::Product => "product",
+            ConfidenceCombiner::NoisyOr => "noisy_or",
+            ConfidenceCombiner::DempsterShafer => "dempster_shafer",
+        }
+    }
+
+    /// For `skip_serializing_if`: the default policy is not written out.
+    pub fn is_product(&self) -> bool {
+        *self == ConfidenceCombiner::Product
+    }
+
+    /// Neutral element: `combine(identity, c) == c`.
+    pub fn identity(self) -> f32 {
+        match self {
+            ConfidenceCombiner::Min | ConfidenceCombiner::Product => 1.0,
+            ConfidenceCombiner::NoisyOr => 0.0,
+            ConfidenceCombiner::DempsterShafer => 0.5,
+        }
+    }
+
+    /// Combine two confidences (inputs are clamped to `[0, 1]`).
+    pub fn combine(self, a: f32, b: f32) -> f32 {
+        let a = a.clamp(0.0, 1.0);
+        let b = b.clamp(0.0, 1.0);
+        match self {
+            ConfidenceCombiner::Min => a.min(b),
+            ConfidenceCombiner::Product => a * b,
+            ConfidenceCombiner::NoisyOr => 1.0 - (1.0 - a) * (1.0 - b),
+            ConfidenceCombiner::DempsterShafer => {
+                let agree = a * b;
+                let norm = agree + (1.0 - a) * (1.0 - b);
+                if norm > 0.0 {
+                    agree / norm
+                } else {
+                    0.5
+                }
+            }
+        }
+    }
+
+    /// Fold a sequence of confidences, starting from [`Self::identity`].
+    pub fn combine_all(self, confidences: impl IntoIterator<Item = f32>) -> f32 {
+        confidences
+            .into_iter()
+            .fold(self.identity(), |acc, c| self.combine(acc, c))
+    }
+
+    /// Fixed-point neutral element (see [`Self::identity`]).
+    pub fn identity_fp(self) -> FixedPointProbability {
+        FixedPointProbability::new_unchecked(match self {
+            ConfidenceCombiner::Min | ConfidenceCombiner::Product => FIXED_POINT_DENOMINATOR,
+            ConfidenceCombiner::NoisyOr => 0,
+            ConfidenceCombiner::DempsterShafer => FIXED_POINT_DENOMINATOR / 2,
+        })
+    }
+
+    /// Fixed-point combination (integer arithmetic only, divisions round
+    /// down), for certificates.
+    pub fn combine_fp(
+        self,
+        a: FixedPointProbability,
+        b: FixedPointProbability,
+    ) -> FixedPointProbability {
+        let d = FIXED_POINT_DENOMINATOR as u64;
+        let (x, y) = (a.numerator() as u64, b.numerator() as u64);
+        let numerator = match self {
+            ConfidenceCombiner::Min => x.min(y),
+            ConfidenceCombiner::Product => return a.mul(b),
+            ConfidenceCombiner::NoisyOr => x + y - (x * y) / d,
+            ConfidenceCombiner::DempsterShafer => {
+                let agree = x * y;
+                let norm = agree + (d - x) * (d - y);
+                (agree * d).checked_div(norm).unwrap_or(d / 2)
+            }
+        };
+        FixedPointProbability::new_unchecked(numerator.min(d) as u32)
+    }
+}
+
+impl std::fmt::Display for ConfidenceCombiner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ConfidenceCombiner {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "min" => Ok(ConfidenceCombiner::Min),
+            "product" | "prod" => Ok(ConfidenceCombiner::Product),
+            "noisy_or" | "noisyor" => Ok(ConfidenceCombiner::NoisyOr),
+            "dempster_shafer" | "ds" => Ok(ConfidenceCombiner::DempsterShafer),
+            other => Err(format!(
+                "unknown confidence combiner `{other}` (expected min, product, noisy_or or dempster_shafer)"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combiners_match_their_definitions() {
+        let (a, b) = (0.8, 0.6);
+        assert_eq!(ConfidenceCombiner::Min.combine(a, b), 0.6);
+        assert!((ConfidenceCombiner::Product.combine(a, b) - 0.48).abs() < 1e-6);
+        assert!((ConfidenceCombiner::NoisyOr.combine(a, b) - 0.92).abs() < 1e-6);
+        // 0.48 / (0.48 + 0.08)
+        assert!((ConfidenceCombiner::DempsterShafer.combine(a, b) - 0.857_142_9).abs() < 1e-6);
+        assert_eq!(ConfidenceCombiner::DempsterShafer.combine(1.0, 0.0), 0.5);
+    }
+
+    #[test]
+    fn identity_is_neutral_and_fixed_point_agrees() {
+        for combiner in ConfidenceCombiner::ALL {
+            for c in [0.0, 0.25, 0.9, 1.0] {
+                assert!((combiner.combine(combiner.identity(), c) - c).abs() < 1e-6);
+                let fp = FixedPointProbability::from_f32(c);
+                assert_eq!(combiner.combine_fp(combiner.identity_fp(), fp), fp);
+            }
+            let fp = combiner.combine_fp(
+                FixedPointProbability::from_f32(0.8),
+                FixedPointProbability::from_f32(0.6),
+            );
+            assert!((fp.to_f32() - combiner.combine(0.8, 0.6)).abs() < 1e-5);
+            assert_eq!(
+                combiner.as_str().parse::<ConfidenceCombiner>(),
+                Ok(combiner)
+            );
+        }
+    }
+
+    #[test]
+    fn reachability_certificate_records_non_default_combiner() {
+        use crate::certificate::{CertificateV2, ReachabilityProofV2};
+
+        let proof = ReachabilityProofV2::Reflexive { entity: 1 };
+        let product = serde_json::to_value(CertificateV2::reachability(proof.clone())).unwrap();
+        assert!(product.get("combiner").is_none());
+
+        let cert = CertificateV2::reachability_with_combiner(proof, ConfidenceCombiner::NoisyOr);
+        let json = serde_json::to_value(&cert).unwrap();
+        assert_eq!(json["combiner"], "noisy_or");
+        let back: CertificateV2 = serde_json::from_value(json).unwrap();
+        assert!(matches!(
+            back.payload,
+            crate::certificate::CertificatePayloadV2::ReachabilityV2 {
+                combiner: ConfidenceCombiner::NoisyOr,
+                ..
+            }
+        ));
+    }
+}
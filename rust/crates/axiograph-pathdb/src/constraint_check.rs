@@ -0,0 +1,581 @@
+//! Counterexample search for proposed theory constraints.
+//!
+//! Before a constraint is adopted into a `.axi` theory,
+//! [`check_constraint_against_db`] evaluates it against the fact nodes a
+//! snapshot already holds and answers "what would break?":
+//!
+//! - [`ConstraintCheck::Violated`] lists up to `limit` [`Counterexample`]s,
+//!   each with the fact nodes it follows from and the conflicting tuples;
+//! - [`ConstraintCheck::Satisfied`] carries a [`ConstraintSatisfied`]
+//!   certificate recording what was checked.
+//!
+//! Semantics follow `axi_constraints_ok_v1` (see `axi_module_constraints`):
+//!
+//! - `key`, `functional` and `at_most` are checked directly on the relation's
+//!   fact nodes;
+//! - `symmetric` and `transitive` are open-world closure constraints: they do
+//!   not require inverse/composed tuples to be stored. Instead the closure,
+//!   projected to the carrier and `param` fields, is checked against the
+//!   `key`/`functional` constraints the schema already declares for the
+//!   relation (those mentioning other fields cannot be evaluated on derived
+//!   tuples and are skipped);
+//! - `typing` rules and named blocks are not executable and are rejected.
+//!
+//! Field values are entity ids. A fact node missing a field the check needs
+//! is skipped, as in [`crate::overlay::constraint_violations`].
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use axiograph_dsl::schema_v1::{format_constraint_v1, CarrierFieldsV1, ConstraintV1};
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use crate::axi_semantics::{ConstraintDecl, MetaPlaneIndex};
+use crate::error::Result;
+use crate::{PathDB, PathDbError};
+
+/// One way the proposed constraint fails on the snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Counterexample {
+    /// Fact nodes the violation follows from (for closure constraints, every
+    /// fact used to derive the conflicting tuples).
+    pub facts: Vec<u32>,
+    /// The conflicting tuples as `(field, entity)` bindings. For closure
+    /// constraints these may be derived rather than stored.
+    pub tuples: Vec<Vec<(String, u32)>>,
+    pub reason: String,
+}
+
+/// Certificate that a constraint holds on the snapshot it was checked on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstraintSatisfied {
+    pub schema: String,
+    pub relation: String,
+    /// The constraint in canonical `.axi` syntax.
+    pub constraint: String,
+    /// Fact nodes of the relation that had every field the check needs.
+    pub facts_checked: u64,
+    /// Tuples checked: the stored ones, or the closure for closure constraints.
+    pub tuples_checked: u64,
+    /// Declared constraints the closure was checked against (closure
+    /// constraints only).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub against: Vec<String>,
+    /// Size of the snapshot checked.
+    pub entity_count: usize,
+    pub relation_count: usize,
+}
+
+/// Result of [`check_constraint_against_db`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum ConstraintCheck {
+    Satisfied(ConstraintSatisfied),
+    Violated {
+        counterexamples: Vec<Counterexample>,
+        /// More counterexamples exist than `limit`.
+        truncated: bool,
+    },
+}
+
+impl ConstraintCheck {
+    pub fn is_satisfied(&self) -> bool {
+        matches!(self, ConstraintCheck::Satisfied(_))
+    }
+}
+
+/// A tuple of field values (in a check-specific field order) and the fact
+/// nodes it comes from.
+#[derive(Debug, Clone)]
+struct Tuple {
+    values: Vec<u32>,
+    facts: Vec<u32>,
+}
+
+/// Bounded counterexample collection.
+struct Search<'a> {
+    fields: &'a [String],
+    limit: usize,
+    found: Vec<Counterexample>,
+    truncated: bool,
+}
+
+impl Search<'_> {
+    /// Record a violation between `a` and `b`; false once the limit is hit.
+    fn push(&mut self, a: &Tuple, b: &Tuple, reason: String) -> bool {
+        if self.found.len() >= self.limit {
+            self.truncated = true;
+            return false;
+        }
+        let facts: BTreeSet<u32> = a.facts.iter().chain(&b.facts).copied().collect();
+        let bindings = |t: &Tuple| {
+            self.fields
+                .iter()
+                .cloned()
+                .zip(t.values.iter().copied())
+                .collect::<Vec<_>>()
+        };
+        self.found.push(Counterexample {
+            facts: facts.into_iter().collect(),
+            tuples: vec![bindings(a), bindings(b)],
+            reason,
+        });
+        true
+    }
+}
+
+/// Search `db` for tuples of `schema` that violate `constraint`, returning at
+/// most `limit` counterexamples or a certificate that none exist.
+pub fn check_constraint_against_db(
+    db: &PathDB,
+    schema: &str,
+    constraint: &ConstraintV1,
+    limit: usize,
+) -> Result<ConstraintCheck> {
+    let text = format_constraint_v1(constraint).unwrap_or_else(|_| format!("{constraint:?}"));
+    let invalid = |reason: String| PathDbError::InvalidConstraint {
+        constraint: text.clone(),
+        reason,
+    };
+    let unsupported =
+        || PathDbError::Unsupported(format!("`{text}` is not an executable constraint"));
+    let relation = match constraint {
+        ConstraintV1::Key { relation, .. }
+        | ConstraintV1::Functional { relation, .. }
+        | ConstraintV1::AtMost { relation, .. }
+        | ConstraintV1::Symmetric { relation, .. }
+        | ConstraintV1::SymmetricWhereIn { relation, .. }
+        | ConstraintV1::Transitive { relation, .. } => relation,
+        ConstraintV1::Typing { .. }
+        | ConstraintV1::NamedBlock { .. }
+        | ConstraintV1::Unknown { .. } => return Err(unsupported()),
+    };
+
+    let meta = MetaPlaneIndex::from_db(db)?;
+    let schema_index = meta
+        .schemas
+        .get(schema)
+        .ok_or_else(|| invalid(format!("unknown schema `{schema}`")))?;
+    let decl = schema_index
+        .relation_decls
+        .get(relation)
+        .ok_or_else(|| invalid(format!("`{schema}` declares no relation `{relation}`")))?;
+    let mut declared = decl.fields.clone();
+    declared.sort_by_key(|f| f.field_index);
+    let declared: Vec<String> = declared.into_iter().map(|f| f.field_name).collect();
+    let require = |field: &str| {
+        if declared.iter().any(|f| f == field) {
+            Ok(())
+        } else {
+            Err(invalid(format!("`{relation}` has no field `{field}`")))
+        }
+    };
+
+    // The tuples checked (stored, or the closure).
+    let tuples: Vec<Tuple>;
+    let facts = db.fact_nodes_by_axi_schema_relation(schema, relation);
+    let mut against = Vec::new();
+    let mut found = Vec::new();
+    let mut truncated = false;
+
+    match constraint {
+        ConstraintV1::Key {
+            fields: key_fields, ..
+        } => {
+            for f in key_fields {
+                require(f)?;
+            }
+            tuples = stored_tuples(db, &facts, key_fields);
+            let mut search = Search::new(key_fields, limit);
+            let all: Vec<usize> = (0..key_fields.len()).collect();
+            check_key(&tuples, &all, &text, &mut search);
+            (found, truncated) = (search.found, search.truncated);
+        }
+        ConstraintV1::Functional {
+            src_field,
+            dst_field,
+            ..
+        } => {
+            require(src_field)?;
+            require(dst_field)?;
+            let fields = [src_field.clone(), dst_field.clone()];
+            tuples = stored_tuples(db, &facts, &fields);
+            let mut search = Search::new(&fields, limit);
+            check_functional(&tuples, 0, 1, &text, &mut search);
+            (found, truncated) = (search.found, search.truncated);
+        }
+        ConstraintV1::AtMost {
+            src_field,
+            dst_field,
+            max,
+            params,
+            ..
+        } => {
+            require(src_field)?;
+            require(dst_field)?;
+            let params = params.as_deref().unwrap_or_default();
+            for p in params {
+                require(p)?;
+            }
+            let fields: Vec<String> = [src_field.clone(), dst_field.clone()]
+                .into_iter()
+                .chain(params.iter().cloned())
+                .collect();
+            tuples = stored_tuples(db, &facts, &fields);
+            // (src, params...) -> first tuple per distinct dst
+            let mut groups: HashMap<Vec<u32>, Vec<&Tuple>> = HashMap::new();
+            for t in &tuples {
+                let mut key = vec![t.values[0]];
+                key.extend_from_slice(&t.values[2..]);
+                let group = groups.entry(key).or_default();
+                if !group.iter().any(|g| g.values[1] == t.values[1]) {
+                    group.push(t);
+                }
+            }
+            let mut over: Vec<_> = groups
+                .into_values()
+                .filter(|g| g.len() > *max as usize)
+                .collect();
+            over.sort_by_key(|g| g[0].values.clone());
+            for group in over {
+                if found.len() >= limit {
+                    truncated = true;
+                    break;
+                }
+                found.push(Counterexample {
+                    facts: group.iter().flat_map(|t| t.facts.iter().copied()).collect(),
+                    tuples: group
+                        .iter()
+                        .map(|t| fields.iter().cloned().zip(t.values.iter().copied()).collect())
+                        .collect(),
+                    reason: format!(
+                        "`{src_field}` maps to {} distinct `{dst_field}` values; `{text}` allows {max}",
+                        group.len()
+                    ),
+                });
+            }
+        }
+        ConstraintV1::Symmetric {
+            carriers, params, ..
+        }
+        | ConstraintV1::Transitive {
+            carriers, params, ..
+        }
+        | ConstraintV1::SymmetricWhereIn {
+            carriers, params, ..
+        } => {
+            let (left, right) = carrier_fields(&declared, carriers.as_ref())
+                .ok_or_else(|| invalid("closure constraints need two carrier fields".into()))?;
+            require(&left)?;
+            require(&right)?;
+            if left == right {
+                return Err(invalid(format!("carrier field `{left}` used twice")));
+            }
+            let params = params.as_deref().unwrap_or_default();
+            for p in params {
+                require(p)?;
+                if *p == left || *p == right {
+                    return Err(invalid(format!("param field `{p}` is a carrier field")));
+                }
+            }
+            let projection: Vec<String> = [left, right]
+                .into_iter()
+                .chain(params.iter().cloned())
+                .collect();
+            let guard = match constraint {
+                ConstraintV1::SymmetricWhereIn { field, values, .. } => {
+                    require(field)?;
+                    Some((field, values))
+                }
+                _ => None,
+            };
+            // The guard field is read but not part of the closure projection.
+            let mut read = projection.clone();
+            if let Some((field, _)) = guard {
+                read.push(field.clone());
+            }
+            let stored = stored_tuples(db, &facts, &read);
+            let closure = match guard {
+                Some((_, values)) => {
+                    let allowed = entities_named(db, values);
+                    let guard_idx = read.len() - 1;
+                    let applies: Vec<bool> = stored
+                        .iter()
+                        .map(|t| allowed.contains(t.values[guard_idx]))
+                        .collect();
+                    let projected = stored.into_iter().map(|mut t| {
+                        t.values.truncate(guard_idx);
+                        t
+                    });
+                    symmetric_closure(projected.zip(applies))
+                }
+                None if matches!(constraint, ConstraintV1::Transitive { .. }) => {
+                    transitive_closure(stored)
+                }
+                None => symmetric_closure(stored.into_iter().map(|t| (t, true))),
+            };
+
+            let kind = match constraint {
+                ConstraintV1::Transitive { .. } => "transitive",
+                _ => "symmetric",
+            };
+            let mut search = Search::new(&projection, limit);
+            let position = |f: &str| projection.iter().position(|p| p == f);
+            for decl in schema_index
+                .constraints_by_relation
+                .get(relation)
+                .into_iter()
+                .flatten()
+            {
+                match decl {
+                    ConstraintDecl::Key { fields: key, .. } if !key.is_empty() => {
+                        let Some(idx) = key.iter().map(|f| position(f)).collect::<Option<Vec<_>>>()
+                        else {
+                            continue;
+                        };
+                        let name = format!("constraint key {relation}({})", key.join(", "));
+                        check_key(
+                            &closure,
+                            &idx,
+                            &format!("{kind} closure of `{name}`"),
+                            &mut search,
+                        );
+                        against.push(name);
+                    }
+                    ConstraintDecl::Functional {
+                        src_field,
+                        dst_field,
+                        ..
+                    } => {
+                        let (Some(src), Some(dst)) = (position(src_field), position(dst_field))
+                        else {
+                            continue;
+                        };
+                        let name =
+                            format!("constraint functional {relation}.{src_field} -> {relation}.{dst_field}");
+                        check_functional(
+                            &closure,
+                            src,
+                            dst,
+                            &format!("{kind} closure of `{name}`"),
+                            &mut search,
+                        );
+                        against.push(name);
+                    }
+                    _ => {}
+                }
+            }
+            (found, truncated) = (search.found, search.truncated);
+            tuples = closure;
+        }
+        ConstraintV1::Typing { .. }
+        | ConstraintV1::NamedBlock { .. }
+        | ConstraintV1::Unknown { .. } => return Err(unsupported()),
+    }
+
+    if !found.is_empty() || truncated {
+        return Ok(ConstraintCheck::Violated {
+            counterexamples: found,
+            truncated,
+        });
+    }
+    let facts_checked = tuples
+        .iter()
+        .flat_map(|t| t.facts.iter().copied())
+        .collect::<RoaringBitmap>()
+        .len();
+    Ok(ConstraintCheck::Satisfied(ConstraintSatisfied {
+        schema: schema.to_string(),
+        relation: relation.clone(),
+        constraint: text,
+        facts_checked,
+        tuples_checked: tuples.len() as u64,
+        against,
+        entity_count: db.entities.len(),
+        relation_count: db.relations.len(),
+    }))
+}
+
+impl<'a> Search<'a> {
+    fn new(fields: &'a [String], limit: usize) -> Self {
+        Self {
+            fields,
+            limit,
+            found: Vec::new(),
+            truncated: false,
+        }
+    }
+}
+
+/// Values of `fields` (in order) for every fact node that has them all.
+fn stored_tuples(db: &PathDB, facts: &RoaringBitmap, fields: &[String]) -> Vec<Tuple> {
+    let Some(ids) = fields
+        .iter()
+        .map(|f| db.interner.id_of(f))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Vec::new();
+    };
+    facts
+        .iter()
+        .filter_map(|fact| {
+            let values = ids
+                .iter()
+                .map(|&id| db.relations.targets(fact, id).min())
+                .collect::<Option<Vec<_>>>()?;
+            Some(Tuple {
+                values,
+                facts: vec![fact],
+            })
+        })
+        .collect()
+}
+
+/// Entities whose `name` is one of `names`.
+fn entities_named(db: &PathDB, names: &[String]) -> RoaringBitmap {
+    let Some(key) = db.interner.id_of("name") else {
+        return RoaringBitmap::new();
+    };
+    names
+        .iter()
+        .filter_map(|n| db.interner.id_of(n))
+        .map(|value| db.entities.entities_with_attr_value(key, value))
+        .fold(RoaringBitmap::new(), |acc, ids| acc | ids)
+}
+
+/// Explicit carriers, else the first two declared fields.
+fn carrier_fields(
+    declared: &[String],
+    carriers: Option<&CarrierFieldsV1>,
+) -> Option<(String, String)> {
+    match carriers {
+        Some(c) => Some((c.left_field.clone(), c.right_field.clone())),
+        None => Some((declared.first()?.clone(), declared.get(1)?.clone())),
+    }
+}
+
+/// Two tuples agreeing on `key` (positions into `values`).
+fn check_key(tuples: &[Tuple], key: &[usize], what: &str, search: &mut Search<'_>) {
+    let mut seen: HashMap<Vec<u32>, usize> = HashMap::new();
+    for (i, t) in tuples.iter().enumerate() {
+        let k: Vec<u32> = key.iter().map(|&p| t.values[p]).collect();
+        match seen.get(&k) {
+            Some(&first) => {
+                if !search.push(
+                    &tuples[first],
+                    t,
+                    format!("two tuples share a key under `{what}`"),
+                ) {
+                    return;
+                }
+            }
+            None => {
+                seen.insert(k, i);
+            }
+        }
+    }
+}
+
+/// Two tuples with the same `src` value and different `dst` values.
+fn check_functional(tuples: &[Tuple], src: usize, dst: usize, what: &str, search: &mut Search<'_>) {
+    let mut seen: HashMap<u32, usize> = HashMap::new();
+    for (i, t) in tuples.iter().enumerate() {
+        match seen.get(&t.values[src]) {
+            Some(&first) if tuples[first].values[dst] != t.values[dst] => {
+                let reason = format!("one source maps to two targets under `{what}`");
+                if !search.push(&tuples[first], t, reason) {
+                    return;
+                }
+            }
+            Some(_) => {}
+            None => {
+                seen.insert(t.values[src], i);
+            }
+        }
+    }
+}
+
+/// Distinct tuples plus the swap of positions 0 and 1 for those flagged.
+fn symmetric_closure(tuples: impl IntoIterator<Item = (Tuple, bool)>) -> Vec<Tuple> {
+    let mut seen: HashSet<Vec<u32>> = HashSet::new();
+    let mut out = Vec::new();
+    for (t, swap) in tuples {
+        let swapped = swap.then(|| {
+            let mut s = t.clone();
+            s.values.swap(0, 1);
+            s
+        });
+        for t in std::iter::once(t).chain(swapped) {
+            if seen.insert(t.values.clone()) {
+                out.push(t);
+            }
+        }
+    }
+    out
+}
+
+/// Transitive closure on positions 0 and 1, within equal values at the
+/// remaining (parameter) positions.
+fn transitive_closure(stored: Vec<Tuple>) -> Vec<Tuple> {
+    type Key = (u32, Vec<u32>);
+    let mut out: Vec<Tuple> = Vec::new();
+    let mut seen: HashSet<Vec<u32>> = HashSet::new();
+    let mut by_left: HashMap<Key, Vec<usize>> = HashMap::new();
+    let mut by_right: HashMap<Key, Vec<usize>> = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    let mut add = |t: Tuple,
+                   out: &mut Vec<Tuple>,
+                   by_left: &mut HashMap<Key, Vec<usize>>,
+                   by_right: &mut HashMap<Key, Vec<usize>>,
+                   queue: &mut VecDeque<usize>| {
+        if !seen.insert(t.values.clone()) {
+            return;
+        }
+        let i = out.len();
+        let params = t.values[2..].to_vec();
+        by_left
+            .entry((t.values[0], params.clone()))
+            .or_default()
+            .push(i);
+        by_right.entry((t.values[1], params)).or_default().push(i);
+        out.push(t);
+        queue.push_back(i);
+    };
+    for t in stored {
+        add(t, &mut out, &mut by_left, &mut by_right, &mut queue);
+    }
+    let compose = |a: &Tuple, b: &Tuple| {
+        let mut values = a.values.clone();
+        values[1] = b.values[1];
+        let facts: BTreeSet<u32> = a.facts.iter().chain(&b.facts).copied().collect();
+        Tuple {
+            values,
+            facts: facts.into_iter().collect(),
+        }
+    };
+    while let Some(i) = queue.pop_front() {
+        let params = out[i].values[2..].to_vec();
+        let mut derived = Vec::new();
+        // out[i] ; next
+        for &j in by_left
+            .get(&(out[i].values[1], params.clone()))
+            .into_iter()
+            .flatten()
+        {
+            derived.push(compose(&out[i], &out[j]));
+        }
+        // prev ; out[i]
+        for &j in by_right
+            .get(&(out[i].values[0], params))
+            .into_iter()
+            .flatten()
+        {
+            derived.push(compose(&out[j], &out[i]));
+        }
+        for t in derived {
+            add(t, &mut out, &mut by_left, &mut by_right, &mut queue);
+        }
+    }
+    out
+}
@@ -0,0 +1,666 @@
+//! Context/world-scoped query execution.
+//!
+//! `.axi` facts may carry a context (`ctx` field → `axi_fact_in_context` edge),
+//! but ordinary traversal sees every edge regardless of where it was asserted.
+//! A [`QueryContext`] with a `world` restricts execution to the facts that
+//! hold in that world, matching RDF named-graph and modal-world semantics:
+//!
+//! - a fact is visible when its fact node is in the world, or in a context the
+//!   world inherits from (`world -axi_context_inherits-> parent`, transitive);
+//! - an edge is visible when at least one of its `axi_fact_id` provenance
+//!   values names a visible fact; edges with no fact provenance and facts with
+//!   no context are **not** visible (unscoped ≠ asserted everywhere);
+//! - an entity is visible when it is a visible fact node or a field value of
+//!   one (the "nodes of the named graph").
+//!
+//! `QueryContext::default()` (no world) is the unscoped behaviour.
+//!
+//! ## Context lattice
+//!
+//! Contexts form a DAG under `axi_context_inherits`, rooted at the **default
+//! world** ([`DEFAULT_CONTEXT_NAME`]): [`PathDB::add_context`] creates a named
+//! context whose parent defaults to it, and [`PathDB::add_context_parent`]
+//! refuses edges that would create a cycle.
+//!
+//! A child context **overrides** its ancestors: when a relation has a key
+//! (a `constraint key` without `ctx`, else the source of a `constraint
+//! functional`), an inherited fact is hidden by a fact with the same key and
+//! different values asserted in a nearer context ([`PathDB::effective_facts`]).
+//! Relations without a key are sets and only accumulate. Two facts with the same
+//! key and different values in contexts where neither inherits from the other
+//! are a contradiction ([`PathDB::context_contradictions`]).
+//!
+//! ## Entity scopes
+//!
+//! The same per-hop filter backs scopes that are not worlds:
+//! [`ContextScope::over`] sees exactly a set of entities, and an edge only
+//! when both of its endpoints are in it (optionally also requiring an edge
+//! attribute or hiding edge types or single edges). Namespaces, role policies
+//! and what-if overlays use it through [`PathDB::execute_within`], so a
+//! traversal can never step through an entity the scope hides.
+
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use crate::axi_meta::{
+    ATTR_AXI_FACT_ID, ATTR_AXI_RELATION, ATTR_AXI_SCHEMA, META_ATTR_NAME, REL_AXI_CONTEXT_INHERITS,
+    REL_AXI_FACT_IN_CONTEXT,
+};
+use crate::axi_semantics::{ConstraintDecl, MetaPlaneIndex};
+use crate::error::Result;
+use crate::supernode::HubPolicy;
+use crate::{ExecScope, PathDB, PathDbError, Relation, StrId};
+
+/// Entity type of contexts created through [`PathDB::add_context`].
+pub const CONTEXT_TYPE: &str = "Context";
+
+/// Name of the default world, the root of the context lattice.
+pub const DEFAULT_CONTEXT_NAME: &str = "default";
+
+/// Field linking a fact to its context (what `@context` expands to in `.axi`).
+pub const CONTEXT_FIELD: &str = "ctx";
+
+/// Execution parameters shared by a whole query.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryContext {
+    /// Context/world entity id; `None` sees every fact.
+    pub world: Option<u32>,
+    /// How traversals treat supernodes (see [`crate::supernode`]).
+    #[serde(default)]
+    pub hubs: HubPolicy,
+}
+
+impl QueryContext {
+    pub fn world(world: u32) -> Self {
+        Self {
+            world: Some(world),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_hubs(mut self, hubs: HubPolicy) -> Self {
+        self.hubs = hubs;
+        self
+    }
+}
+
+/// Facts and entities visible in one world (see the module docs).
+#[derive(Debug, Clone, Default)]
+pub struct ContextScope {
+    /// The world and every context it inherits from.
+    pub worlds: RoaringBitmap,
+    /// Visible fact nodes.
+    pub facts: RoaringBitmap,
+    /// Visible fact nodes and their field values.
+    pub entities: RoaringBitmap,
+    fact_key: Option<StrId>,
+    fact_ids: HashSet<StrId>,
+    /// Not a world: edges need no visible fact.
+    worldless: bool,
+    /// Both endpoints of a visible edge must be in `entities`.
+    closed: bool,
+    hidden_rel_types: HashSet<StrId>,
+    /// Hidden `(source, rel_type, target)` edges.
+    hidden_edges: HashSet<(u32, StrId, u32)>,
+    /// Visible edges must carry this `(key, value)` attribute.
+    edge_attr: Option<(StrId, StrId)>,
+}
+
+impl ContextScope {
+    /// A scope seeing exactly `entities`, and the edges between them (see
+    /// the module docs).
+    pub fn over(entities: RoaringBitmap) -> Self {
+        Self {
+            entities,
+            worldless: true,
+            closed: true,
+            ..Self::default()
+        }
+    }
+
+    /// Narrow the scope to `entities`; edges leaving it become invisible.
+    pub fn restrict_entities(mut self, entities: &RoaringBitmap) -> Self {
+        self.entities &= entities;
+        self.closed = true;
+        self
+    }
+
+    /// Hide every edge of type `rel_type`.
+    pub fn hide_relation_type(mut self, rel_type: StrId) -> Self {
+        self.hidden_rel_types.insert(rel_type);
+        self
+    }
+
+    /// Hide every `source -rel_type-> target` edge.
+    pub fn hide_edge(mut self, source: u32, rel_type: StrId, target: u32) -> Self {
+        self.hidden_edges.insert((source, rel_type, target));
+        self
+    }
+
+    /// Only see edges carrying the attribute `key = value`.
+    pub fn require_edge_attr(mut self, key: StrId, value: StrId) -> Self {
+        self.edge_attr = Some((key, value));
+        self
+    }
+
+    pub fn contains_entity(&self, entity: u32) -> bool {
+        self.entities.contains(entity)
+    }
+
+    /// True when `rel` is visible in this scope: asserted by a visible fact
+    /// (worlds), between visible entities (closed scopes), and not filtered
+    /// out by type or edge attribute.
+    pub fn allows_relation(&self, rel: &Relation) -> bool {
+        if self.hidden_rel_types.contains(&rel.rel_type)
+            || self
+                .hidden_edges
+                .contains(&(rel.source, rel.rel_type, rel.target))
+            || (self.closed
+                && !(self.entities.contains(rel.source) && self.entities.contains(rel.target)))
+            || self
+                .edge_attr
+                .is_some_and(|attr| !rel.attrs.contains(&attr))
+        {
+            return false;
+        }
+        if self.worldless {
+            return true;
+        }
+        let Some(key) = self.fact_key else {
+            return false;
+        };
+        rel.attrs
+            .iter()
+            .any(|(k, v)| *k == key && self.fact_ids.contains(v))
+    }
+
+    /// `targets` without the entities a closed scope hides (for computed
+    /// targets that have no stored edge to check).
+    pub(crate) fn clip(&self, mut targets: RoaringBitmap) -> RoaringBitmap {
+        if self.closed {
+            targets &= &self.entities;
+        }
+        targets
+    }
+}
+
+impl PathDB {
+    /// `world` plus every context it (transitively) inherits from.
+    pub fn context_closure(&self, world: u32) -> RoaringBitmap {
+        self.context_depths(world).into_keys().collect()
+    }
+
+    /// Inheritance distance from `world` to each context in its closure
+    /// (`world` itself is 0; shortest path wins).
+    pub fn context_depths(&self, world: u32) -> BTreeMap<u32, usize> {
+        let mut out = BTreeMap::from([(world, 0)]);
+        let Some(inherits) = self.interner.id_of(REL_AXI_CONTEXT_INHERITS) else {
+            return out;
+        };
+        let mut queue = VecDeque::from([world]);
+        while let Some(ctx) = queue.pop_front() {
+            let depth = out[&ctx] + 1;
+            for parent in self.relations.targets(ctx, inherits).iter() {
+                if let std::collections::btree_map::Entry::Vacant(e) = out.entry(parent) {
+                    e.insert(depth);
+                    queue.push_back(parent);
+                }
+            }
+        }
+        out
+    }
+
+    /// Resolve the facts and entities visible in `world`.
+    pub fn context_scope(&self, world: u32) -> ContextScope {
+        let worlds = self.context_closure(world);
+        let facts = self.effective_facts(world).facts;
+
+        let fact_key = self.interner.id_of(ATTR_AXI_FACT_ID);
+        let mut fact_ids = HashSet::new();
+        let mut entities = facts.clone();
+        for fact in facts.iter() {
+            if let Some(id) = fact_key.and_then(|k| self.entities.get_attr(fact, k)) {
+                fact_ids.insert(id);
+            }
+        }
+        if let Some(key) = fact_key {
+            // Field edges (`fact -field-> value`) carry the fact's id.
+            for fact in facts.iter() {
+                for rel in self.relations.outgoing_any(fact) {
+                    if rel
+                        .attrs
+                        .iter()
+                        .any(|(k, v)| *k == key && fact_ids.contains(v))
+                    {
+                        entities.insert(rel.target);
+                    }
+                }
+            }
+        }
+
+        ContextScope {
+            worlds,
+            facts,
+            entities,
+            fact_key,
+            fact_ids,
+            ..ContextScope::default()
+        }
+    }
+
+    pub(crate) fn resolve_context(&self, ctx: &QueryContext) -> Option<ContextScope> {
+        ctx.world.map(|world| self.context_scope(world))
+    }
+
+    /// Entities of `type_name` visible in `ctx`.
+    pub fn find_by_type_in(&self, type_name: &str, ctx: &QueryContext) -> RoaringBitmap {
+        let all = self.find_by_type(type_name).cloned().unwrap_or_default();
+        match self.resolve_context(ctx) {
+            None => all,
+            Some(scope) => all & &scope.entities,
+        }
+    }
+
+    /// Follow a path of relations using only edges visible in `ctx`.
+    pub fn follow_path_in(&self, start: u32, path: &[&str], ctx: &QueryContext) -> RoaringBitmap {
+        let scope = self.resolve_context(ctx);
+        self.follow_path_budgeted(
+            start,
+            path,
+            ExecScope {
+                context: scope.as_ref(),
+                hubs: ctx.hubs,
+                ..ExecScope::default()
+            },
+            &mut crate::budget::BudgetTracker::unlimited(),
+        )
+    }
+}
+
+/// A fact node and the context it was asserted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextFact {
+    pub fact: u32,
+    pub context: u32,
+}
+
+/// An inherited fact hidden by a nearer fact with the same key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FactOverride {
+    pub overridden: ContextFact,
+    pub by: ContextFact,
+}
+
+/// The facts that hold in one world once overrides are applied.
+#[derive(Debug, Clone, Default)]
+pub struct EffectiveFacts {
+    pub world: u32,
+    pub facts: RoaringBitmap,
+    /// Sorted by overridden fact id.
+    pub overrides: Vec<FactOverride>,
+}
+
+/// Two facts with the same key and different values, asserted in contexts
+/// where neither inherits from the other.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextContradiction {
+    pub schema: String,
+    pub relation: String,
+    /// Key field values, in key order.
+    pub key: Vec<(String, u32)>,
+    pub left: ContextFact,
+    pub right: ContextFact,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct FactKey {
+    schema: String,
+    relation: String,
+    values: Vec<Option<u32>>,
+}
+
+/// Non-key field values of a keyed fact, in declaration order.
+type FactValues = Vec<Option<u32>>;
+
+struct RelationKey {
+    key_fields: Vec<String>,
+    value_fields: Vec<String>,
+}
+
+/// Override keys per `(schema, relation)`, read from the meta-plane.
+#[derive(Default)]
+struct RelationKeys {
+    by_relation: HashMap<(String, String), RelationKey>,
+}
+
+impl RelationKeys {
+    fn from_db(db: &PathDB) -> Self {
+        let Ok(meta) = MetaPlaneIndex::from_db(db) else {
+            return Self::default();
+        };
+        let mut by_relation = HashMap::new();
+        for (schema_name, schema) in &meta.schemas {
+            for (relation, decl) in &schema.relation_decls {
+                let constraints = schema
+                    .constraints_by_relation
+                    .get(relation)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let key = constraints
+                    .iter()
+                    .find_map(|c| match c {
+                        ConstraintDecl::Key { fields, .. } => Some(
+                            fields
+                                .iter()
+                                .filter(|f| *f != CONTEXT_FIELD)
+                                .cloned()
+                                .collect::<Vec<_>>(),
+                        ),
+                        _ => None,
+                    })
+                    .filter(|key| !key.is_empty())
+                    .or_else(|| {
+                        constraints.iter().find_map(|c| match c {
+                            ConstraintDecl::Functional { src_field, .. } => {
+                                Some(vec![src_field.clone()])
+                            }
+                            _ => None,
+                        })
+                    });
+                let Some(key_fields) = key else {
+                    continue;
+                };
+                let mut fields: Vec<_> = decl.fields.iter().collect();
+                fields.sort_by_key(|f| f.field_index);
+                let value_fields = fields
+                    .into_iter()
+                    .map(|f| f.field_name.clone())
+                    .filter(|f| f != CONTEXT_FIELD && !key_fields.contains(f))
+                    .collect();
+                by_relation.insert(
+                    (schema_name.clone(), relation.clone()),
+                    RelationKey {
+                        key_fields,
+                        value_fields,
+                    },
+                );
+            }
+        }
+        Self { by_relation }
+    }
+
+    /// `(key, values)` of a fact node whose relation has a key.
+    fn keyed_fact(&self, db: &PathDB, fact: u32) -> Option<(FactKey, FactValues)> {
+        if self.by_relation.is_empty() {
+            return None;
+        }
+        let schema = entity_attr(db, fact, ATTR_AXI_SCHEMA)?;
+        let relation = entity_attr(db, fact, ATTR_AXI_RELATION)?;
+        let rk = self.by_relation.get(&(schema.clone(), relation.clone()))?;
+        let field = |name: &String| {
+            db.interner
+                .id_of(name)
+                .and_then(|id| db.relations.targets(fact, id).min())
+        };
+        let key = FactKey {
+            schema,
+            relation,
+            values: rk.key_fields.iter().map(field).collect(),
+        };
+        Some((key, rk.value_fields.iter().map(field).collect()))
+    }
+}
+
+fn entity_attr(db: &PathDB, entity: u32, key: &str) -> Option<String> {
+    let value = db.entities.get_attr(entity, db.interner.id_of(key)?)?;
+    db.interner.lookup(value)
+}
+
+impl PathDB {
+    fn check_entity(&self, entity_id: u32) -> Result<()> {
+        if entity_id as usize >= self.entities.types.len() {
+            return Err(PathDbError::UnknownEntity(entity_id));
+        }
+        Ok(())
+    }
+
+    /// The `Context` entity named `name`, if any.
+    pub fn context_by_name(&self, name: &str) -> Option<u32> {
+        let contexts = self.find_by_type(CONTEXT_TYPE)?;
+        let key = self.interner.id_of(META_ATTR_NAME)?;
+        let value = self.interner.id_of(name)?;
+        (self.entities.entities_with_attr_value(key, value) & contexts).min()
+    }
+
+    /// The default world, if it has been created.
+    pub fn default_context(&self) -> Option<u32> {
+        self.context_by_name(DEFAULT_CONTEXT_NAME)
+    }
+
+    /// The default world, created on first use.
+    pub fn ensure_default_context(&mut self) -> u32 {
+        match self.default_context() {
+            Some(ctx) => ctx,
+            None => self.add_entity(CONTEXT_TYPE, vec![(META_ATTR_NAME, DEFAULT_CONTEXT_NAME)]),
+        }
+    }
+
+    /// Create a named context inheriting from `parent` (default: the default
+    /// world).
+    pub fn add_context(&mut self, name: &str, parent: Option<u32>) -> Result<u32> {
+        let parent = match parent {
+            Some(parent) => {
+                self.check_entity(parent)?;
+                parent
+            }
+            None => self.ensure_default_context(),
+        };
+        let ctx = self.add_entity(CONTEXT_TYPE, vec![(META_ATTR_NAME, name)]);
+        self.add_context_parent(ctx, parent)?;
+        Ok(ctx)
+    }
+
+    /// Make `child` inherit every fact of `parent` (idempotent). Fails if
+    /// `parent` already inherits from `child`.
+    pub fn add_context_parent(&mut self, child: u32, parent: u32) -> Result<()> {
+        self.check_entity(child)?;
+        self.check_entity(parent)?;
+        if self.context_closure(parent).contains(child) {
+            return Err(PathDbError::ContextCycle { child, parent });
+        }
+        let inherits = self.interner.intern(REL_AXI_CONTEXT_INHERITS);
+        if !self.relations.has_edge(child, inherits, parent) {
+            self.add_relation(REL_AXI_CONTEXT_INHERITS, child, parent, 1.0, Vec::new());
+        }
+        Ok(())
+    }
+
+    /// Direct parents of a context.
+    pub fn context_parents(&self, ctx: u32) -> RoaringBitmap {
+        self.interner
+            .id_of(REL_AXI_CONTEXT_INHERITS)
+            .map(|inherits| self.relations.targets(ctx, inherits))
+            .unwrap_or_default()
+    }
+
+    /// Assert `relation(fields..)` in `context` (default: the default world).
+    ///
+    /// The fact node has the shape the `.axi` importer produces: field edges
+    /// plus a `ctx` field, the `axi_fact_in_context` edge and, for binary
+    /// relations, the derived `source -relation-> target` edge, all tagged with
+    /// the fact id. Asserting the same tuple twice returns the existing fact.
+    pub fn assert_fact_in_context(
+        &mut self,
+        context: Option<u32>,
+        schema: &str,
+        relation: &str,
+        fields: &[(&str, u32)],
+    ) -> Result<u32> {
+        for &(_, value) in fields {
+            self.check_entity(value)?;
+        }
+        let context = match context {
+            Some(ctx) => {
+                self.check_entity(ctx)?;
+                ctx
+            }
+            None => self.ensure_default_context(),
+        };
+
+        let values: Vec<(&str, String)> = fields
+            .iter()
+            .map(|&(field, value)| (field, value.to_string()))
+            .chain([(CONTEXT_FIELD, context.to_string())])
+            .collect();
+        let values: Vec<(&str, &str)> = values.iter().map(|(f, v)| (*f, v.as_str())).collect();
+        let fact_id = axiograph_dsl::digest::axi_fact_id_v1("", schema, "", relation, &values);
+        if let (Some(key), Some(value)) = (
+            self.interner.id_of(ATTR_AXI_FACT_ID),
+            self.interner.id_of(&fact_id),
+        ) {
+            if let Some(existing) = self.entities.entities_with_attr_value(key, value).min() {
+                return Ok(existing);
+            }
+        }
+
+        let name = format!(
+            "{relation}_fact_{}",
+            fact_id
+                .strip_prefix(axiograph_dsl::digest::AXI_FACT_ID_V1_PREFIX)
+                .unwrap_or(&fact_id)
+        );
+        let fact = self.add_entity(
+            relation,
+            vec![
+                (META_ATTR_NAME, name.as_str()),
+                (ATTR_AXI_SCHEMA, schema),
+                (ATTR_AXI_RELATION, relation),
+                (ATTR_AXI_FACT_ID, fact_id.as_str()),
+            ],
+        );
+        let tag = || vec![(ATTR_AXI_FACT_ID, fact_id.as_str())];
+        for &(field, value) in fields {
+            self.add_relation(field, fact, value, 1.0, tag());
+        }
+        self.add_relation(CONTEXT_FIELD, fact, context, 1.0, tag());
+        self.add_relation(REL_AXI_FACT_IN_CONTEXT, fact, context, 1.0, tag());
+        if let [(_, source), (_, target)] = fields {
+            self.add_relation(relation, *source, *target, 1.0, tag());
+        }
+        Ok(fact)
+    }
+
+    /// Facts that hold in `world`: everything asserted in its closure, minus
+    /// inherited keyed facts overridden by a nearer context.
+    pub fn effective_facts(&self, world: u32) -> EffectiveFacts {
+        let keys = RelationKeys::from_db(self);
+        let mut facts = RoaringBitmap::new();
+        type Candidate = (usize, ContextFact, FactValues);
+        let mut keyed: HashMap<FactKey, Vec<Candidate>> = HashMap::new();
+        for (&context, &depth) in &self.context_depths(world) {
+            for fact in self.fact_nodes_by_context(context).iter() {
+                match keys.keyed_fact(self, fact) {
+                    None => {
+                        facts.insert(fact);
+                    }
+                    Some((key, values)) => keyed.entry(key).or_default().push((
+                        depth,
+                        ContextFact { fact, context },
+                        values,
+                    )),
+                }
+            }
+        }
+
+        let mut overrides = Vec::new();
+        for mut group in keyed.into_values() {
+            group.sort_by_key(|(depth, cf, _)| (*depth, cf.fact));
+            let mut seen = HashSet::new();
+            group.retain(|(_, cf, _)| seen.insert(cf.fact));
+            let nearest = group[0].0;
+            let (winners, inherited): (Vec<_>, Vec<_>) =
+                group.iter().partition(|(depth, _, _)| *depth == nearest);
+            facts.extend(winners.iter().map(|(_, cf, _)| cf.fact));
+            for (_, cf, values) in inherited {
+                // Restating a winner's values is not an override.
+                if winners.iter().any(|(_, _, v)| v == values) {
+                    facts.insert(cf.fact);
+                } else {
+                    overrides.push(FactOverride {
+                        overridden: *cf,
+                        by: winners[0].1,
+                    });
+                }
+            }
+        }
+        overrides.sort_by_key(|o| (o.overridden.fact, o.by.fact));
+
+        EffectiveFacts {
+            world,
+            facts,
+            overrides,
+        }
+    }
+
+    /// Keyed facts that disagree across contexts not related by inheritance
+    /// (sorted by fact ids).
+    pub fn context_contradictions(&self) -> Vec<ContextContradiction> {
+        let Some(in_context) = self.interner.id_of(REL_AXI_FACT_IN_CONTEXT) else {
+            return Vec::new();
+        };
+        let keys = RelationKeys::from_db(self);
+        let mut groups: HashMap<FactKey, Vec<(ContextFact, FactValues)>> = HashMap::new();
+        for rel in &self.relations.relations {
+            if rel.rel_type != in_context {
+                continue;
+            }
+            if let Some((key, values)) = keys.keyed_fact(self, rel.source) {
+                let cf = ContextFact {
+                    fact: rel.source,
+                    context: rel.target,
+                };
+                groups.entry(key).or_default().push((cf, values));
+            }
+        }
+
+        let mut closures: HashMap<u32, RoaringBitmap> = HashMap::new();
+        let mut out = Vec::new();
+        for (key, mut group) in groups {
+            group.sort_by_key(|(cf, _)| (cf.fact, cf.context));
+            for (i, (left, left_values)) in group.iter().enumerate() {
+                for (right, right_values) in &group[i + 1..] {
+                    if left_values == right_values || left.context == right.context {
+                        continue;
+                    }
+                    let related = [(left.context, right.context), (right.context, left.context)]
+                        .into_iter()
+                        .any(|(a, b)| {
+                            closures
+                                .entry(a)
+                                .or_insert_with(|| self.context_closure(a))
+                                .contains(b)
+                        });
+                    if related {
+                        continue;
+                    }
+                    let rk = &keys.by_relation[&(key.schema.clone(), key.relation.clone())];
+                    out.push(ContextContradiction {
+                        schema: key.schema.clone(),
+                        relation: key.relation.clone(),
+                        key: rk
+                            .key_fields
+                            .iter()
+                            .zip(&key.values)
+                            .filter_map(|(f, v)| v.map(|v| (f.clone(), v)))
+                            .collect(),
+                        left: *left,
+                        right: *right,
+                    });
+                }
+            }
+        }
+        out.sort_by_key(|c| (c.left.fact, c.right.fact));
+        out
+    }
+}
@@ -0,0 +1,259 @@
+//! Compressed sparse row (CSR) adjacency for read-mostly deployments.
+//!
+//! The ingest tier keeps adjacency as `(entity, rel_type) -> Vec<relation id>`
+//! hash maps: cheap appends, but one heap allocation per list and scattered
+//! reads. Read replicas and `db serve` never append, so at snapshot-freeze time
+//! [`RelationStore::freeze`] rewrites both indexes into CSR form:
+//!
+//! - `rows`: entity `e`'s lists are `keys[rows[e]..rows[e + 1]]`,
+//! - `keys`: every `(entity, rel_type)` pair, sorted,
+//! - `offsets`: list `i` is `offsets[i]..offsets[i + 1]`,
+//! - `rel_ids` / `endpoints`: relation ids and the far endpoint of each edge,
+//!   laid out back to back in key order.
+//!
+//! Lookups index the entity's row and search its few relation types; all
+//! lists of one entity are adjacent, and
+//! neighbour scans read `endpoints` sequentially without touching relation
+//! records. The serialized form is the same sorted map either way, so frozen
+//! and mutable stores produce identical snapshot bytes.
+//!
+//! Appending to a frozen store converts it back to the mutable layout first.
+
+use std::collections::HashMap;
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use crate::{ordered, PathDB, Relation, RelationStore, StrId};
+
+/// Adjacency-list key: an entity and a relation type.
+pub(crate) type AdjacencyKey = (u32, StrId);
+
+/// Physical layout of a [`RelationStore`]'s adjacency indexes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdjacencyLayout {
+    /// Per-key hash-map lists (ingest tier).
+    Mutable,
+    /// Frozen compressed sparse rows.
+    Csr,
+}
+
+/// Frozen adjacency lists, sorted by key.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CsrAdjacency {
+    rows: Vec<u32>,
+    keys: Vec<AdjacencyKey>,
+    offsets: Vec<u32>,
+    rel_ids: Vec<u32>,
+    endpoints: Vec<u32>,
+}
+
+impl CsrAdjacency {
+    /// Build from hash-map lists; `forward` picks targets (else sources) as
+    /// the far endpoints.
+    fn build(
+        lists: &HashMap<AdjacencyKey, Vec<u32>>,
+        relations: &[Relation],
+        forward: bool,
+    ) -> Self {
+        let mut keys: Vec<AdjacencyKey> = lists.keys().copied().collect();
+        keys.sort_unstable();
+        let edges = lists.values().map(Vec::len).sum();
+        let entities = keys.last().map_or(0, |&(e, _)| e as usize + 1);
+        let mut rows = Vec::with_capacity(entities + 1);
+        for entity in 0..=entities as u32 {
+            rows.push(keys.partition_point(|&(e, _)| e < entity) as u32);
+        }
+        let mut csr = Self {
+            rows,
+            offsets: Vec::with_capacity(keys.len() + 1),
+            rel_ids: Vec::with_capacity(edges),
+            endpoints: Vec::with_capacity(edges),
+            keys,
+        };
+        csr.offsets.push(0);
+        for key in &csr.keys {
+            for &id in &lists[key] {
+                let Some(rel) = relations.get(id as usize) else {
+                    continue;
+                };
+                csr.rel_ids.push(id);
+                csr.endpoints
+                    .push(if forward { rel.target } else { rel.source });
+            }
+            csr.offsets.push(csr.rel_ids.len() as u32);
+        }
+        csr
+    }
+
+    fn range(&self, index: usize) -> std::ops::Range<usize> {
+        self.offsets[index] as usize..self.offsets[index + 1] as usize
+    }
+
+    /// Range of `keys` holding `entity`'s lists.
+    fn row(&self, entity: u32) -> std::ops::Range<usize> {
+        let entity = entity as usize;
+        if entity + 1 >= self.rows.len() {
+            return 0..0;
+        }
+        self.rows[entity] as usize..self.rows[entity + 1] as usize
+    }
+
+    fn position(&self, key: &AdjacencyKey) -> Option<usize> {
+        let row = self.row(key.0);
+        let start = row.start;
+        self.keys[row]
+            .iter()
+            .position(|k| k.1 == key.1)
+            .map(|i| start + i)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (AdjacencyKey, &[u32])> + '_ {
+        self.keys
+            .iter()
+            .enumerate()
+            .map(|(i, &key)| (key, &self.rel_ids[self.range(i)]))
+    }
+
+    fn to_lists(&self) -> HashMap<AdjacencyKey, Vec<u32>> {
+        self.iter().map(|(key, ids)| (key, ids.to_vec())).collect()
+    }
+}
+
+/// One adjacency index (forward or backward) in either layout.
+#[derive(Debug)]
+pub(crate) enum Adjacency {
+    Mutable(HashMap<AdjacencyKey, Vec<u32>>),
+    Csr(CsrAdjacency),
+}
+
+impl Default for Adjacency {
+    fn default() -> Self {
+        Adjacency::Mutable(HashMap::new())
+    }
+}
+
+impl Adjacency {
+    pub(crate) fn layout(&self) -> AdjacencyLayout {
+        match self {
+            Adjacency::Mutable(_) => AdjacencyLayout::Mutable,
+            Adjacency::Csr(_) => AdjacencyLayout::Csr,
+        }
+    }
+
+    /// Relation ids of one adjacency list.
+    pub(crate) fn get(&self, key: &AdjacencyKey) -> Option<&[u32]> {
+        match self {
+            Adjacency::Mutable(lists) => lists.get(key).map(Vec::as_slice),
+            Adjacency::Csr(csr) => csr.position(key).map(|i| &csr.rel_ids[csr.range(i)]),
+        }
+    }
+
+    /// Far endpoints of one adjacency list, parallel to [`Adjacency::get`].
+    /// Only the CSR layout stores them.
+    pub(crate) fn endpoints(&self, key: &AdjacencyKey) -> Option<&[u32]> {
+        match self {
+            Adjacency::Mutable(_) => None,
+            Adjacency::Csr(csr) => csr.position(key).map(|i| &csr.endpoints[csr.range(i)]),
+        }
+    }
+
+    /// Every adjacency list (in key order for the CSR layout).
+    pub(crate) fn iter(&self) -> Box<dyn Iterator<Item = (AdjacencyKey, &[u32])> + '_> {
+        match self {
+            Adjacency::Mutable(lists) => {
+                Box::new(lists.iter().map(|(&key, ids)| (key, ids.as_slice())))
+            }
+            Adjacency::Csr(csr) => Box::new(csr.iter()),
+        }
+    }
+
+    /// Relation ids of every list of `entity`, any relation type.
+    pub(crate) fn entity_rel_ids(&self, entity: u32) -> Vec<u32> {
+        match self {
+            Adjacency::Mutable(lists) => lists
+                .iter()
+                .filter(|((e, _), _)| *e == entity)
+                .flat_map(|(_, ids)| ids.iter().copied())
+                .collect(),
+            Adjacency::Csr(csr) => {
+                let row = csr.row(entity);
+                if row.is_empty() {
+                    return Vec::new();
+                }
+                csr.rel_ids[csr.offsets[row.start] as usize..csr.offsets[row.end] as usize].to_vec()
+            }
+        }
+    }
+
+    /// Mutable lists, converting a frozen index back first.
+    pub(crate) fn lists_mut(&mut self) -> &mut HashMap<AdjacencyKey, Vec<u32>> {
+        if let Adjacency::Csr(csr) = self {
+            *self = Adjacency::Mutable(csr.to_lists());
+        }
+        match self {
+            Adjacency::Mutable(lists) => lists,
+            Adjacency::Csr(_) => unreachable!("thawed above"),
+        }
+    }
+
+    fn freeze(&mut self, relations: &[Relation], forward: bool) {
+        if let Adjacency::Mutable(lists) = self {
+            *self = Adjacency::Csr(CsrAdjacency::build(lists, relations, forward));
+        }
+    }
+}
+
+impl Serialize for Adjacency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Adjacency::Mutable(lists) => ordered::sorted_map(lists, serializer),
+            Adjacency::Csr(csr) => {
+                let mut map = serializer.serialize_map(Some(csr.keys.len()))?;
+                for (key, ids) in csr.iter() {
+                    map.serialize_entry(&key, ids)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Adjacency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        HashMap::deserialize(deserializer).map(Adjacency::Mutable)
+    }
+}
+
+impl RelationStore {
+    /// Rewrite both adjacency indexes into CSR form (snapshot-freeze time).
+    ///
+    /// Lookups stay valid; the next [`RelationStore::add`] converts back.
+    pub fn freeze(&mut self) {
+        self.forward_index.freeze(&self.relations, true);
+        self.backward_index.freeze(&self.relations, false);
+    }
+
+    /// Convert frozen indexes back to the mutable layout.
+    pub fn thaw(&mut self) {
+        self.forward_index.lists_mut();
+        self.backward_index.lists_mut();
+    }
+
+    pub fn adjacency_layout(&self) -> AdjacencyLayout {
+        self.forward_index.layout()
+    }
+}
+
+impl PathDB {
+    /// Freeze relation adjacency into CSR layout for a read-mostly deployment
+    /// (see [`crate::csr`]). Query results are unchanged.
+    pub fn freeze_adjacency(&mut self) {
+        self.relations.freeze();
+    }
+
+    pub fn adjacency_layout(&self) -> AdjacencyLayout {
+        self.relations.adjacency_layout()
+    }
+}
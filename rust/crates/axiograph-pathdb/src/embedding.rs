@@ -0,0 +1,525 @@
+//! Shallow knowledge-graph embeddings (TransE / RotatE) for link prediction.
+//!
+//! This is **evidence-plane tooling**: embeddings are trained over the PathDB
+//! relation store and used to *suggest* missing edges. Suggestions are returned
+//! as low-confidence [`LinkPredictionV1`] records meant to be reviewed (and, if
+//! accepted, promoted through the normal proposal → `.axi` path). Nothing here
+//! mutates the snapshot or participates in certificates.
+//!
+//! Training is deterministic for a fixed [`EmbeddingConfigV1::seed`] and
+//! snapshot, so repeated runs produce identical vectors and predictions.
+//!
+//! Learned vectors can be persisted next to the `.axpd` snapshot
+//! (see [`embeddings_path_for_axpd`]) and reloaded without retraining.
+
+use crate::error::{PathDbError, Result};
+use crate::{PathDB, StrId};
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const KG_EMBEDDINGS_VERSION_V1: &str = "pathdb_kg_embeddings_v1";
+
+/// Embedding model family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbeddingModelV1 {
+    /// `h + r ≈ t` in real space.
+    TransE,
+    /// `h ∘ r ≈ t` in complex space, with `|r_k| = 1` (rotation).
+    RotatE,
+}
+
+/// Training configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingConfigV1 {
+    pub model: EmbeddingModelV1,
+    /// Embedding dimension (complex dimension for RotatE).
+    pub dim: usize,
+    pub epochs: usize,
+    pub learning_rate: f32,
+    /// Margin for the pairwise ranking loss.
+    pub margin: f32,
+    /// Corrupted triples sampled per positive triple.
+    pub negatives_per_positive: usize,
+    pub seed: u64,
+    /// Restrict training to these relation types (default: all relation types).
+    #[serde(default)]
+    pub rel_types: Option<Vec<String>>,
+    /// Upper bound for the confidence attached to predicted links.
+    pub max_prediction_confidence: f32,
+}
+
+impl Default for EmbeddingConfigV1 {
+    fn default() -> Self {
+        Self {
+            model: EmbeddingModelV1::TransE,
+            dim: 32,
+            epochs: 200,
+            learning_rate: 0.05,
+            margin: 1.0,
+            negatives_per_positive: 2,
+            seed: 0x5eed,
+            rel_types: None,
+            max_prediction_confidence: 0.3,
+        }
+    }
+}
+
+/// A suggested (missing) edge.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkPredictionV1 {
+    pub source: u32,
+    pub rel_type: String,
+    pub target: u32,
+    /// Model distance `d(h, r, t)` (lower is better).
+    pub distance: f32,
+    /// Review confidence in `[0, max_prediction_confidence]`.
+    pub confidence: f32,
+}
+
+/// Trained entity/relation vectors for one snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KgEmbeddingsV1 {
+    pub version: String,
+    #[serde(default)]
+    pub snapshot_id: Option<String>,
+    pub model: EmbeddingModelV1,
+    pub dim: usize,
+    pub margin: f32,
+    pub max_prediction_confidence: f32,
+    /// Row-major entity vectors (`width()` floats per entity id).
+    entity_vectors: Vec<f32>,
+    /// Relation vectors keyed by relation type name.
+    relation_vectors: BTreeMap<String, Vec<f32>>,
+    /// Entities that occurred in at least one training triple.
+    trained_entities: RoaringBitmap,
+    /// Final mean margin loss (diagnostic).
+    pub final_loss: f32,
+}
+
+impl KgEmbeddingsV1 {
+    /// Train embeddings over the relations of `db`.
+    pub fn train(db: &PathDB, config: &EmbeddingConfigV1) -> Result<Self> {
+        if config.dim == 0 {
+            return Err(PathDbError::InvalidEmbedding("dim must be > 0".to_string()));
+        }
+
+        let rel_filter: Option<Vec<StrId>> = config
+            .rel_types
+            .as_ref()
+            .map(|names| names.iter().filter_map(|n| db.interner.id_of(n)).collect());
+
+        let mut rel_names: BTreeMap<u32, String> = BTreeMap::new();
+        let mut triples: Vec<(u32, StrId, u32)> = Vec::new();
+        let mut trained_entities = RoaringBitmap::new();
+        for rel in &db.relations.relations {
+            if let Some(filter) = &rel_filter {
+                if !filter.contains(&rel.rel_type) {
+                    continue;
+                }
+            }
+            if let std::collections::btree_map::Entry::Vacant(e) =
+                rel_names.entry(rel.rel_type.raw())
+            {
+                let name = db.interner.lookup(rel.rel_type).ok_or_else(|| {
+                    PathDbError::Corrupt(format!("missing relation name for {:?}", rel.rel_type))
+                })?;
+                e.insert(name);
+            }
+            triples.push((rel.source, rel.rel_type, rel.target));
+            trained_entities.insert(rel.source);
+            trained_entities.insert(rel.target);
+        }
+        if triples.is_empty() {
+            return Err(PathDbError::InvalidEmbedding(
+                "no relations to train on".to_string(),
+            ));
+        }
+
+        let mut rng = SplitMix64::new(config.seed);
+        let width = model_width(config.model, config.dim);
+        let n_entities = db.entities.len();
+        let init = 1.0 / (config.dim as f32).sqrt();
+
+        let mut entity_vectors = vec![0.0f32; n_entities * width];
+        for id in trained_entities.iter() {
+            let row = &mut entity_vectors[id as usize * width..(id as usize + 1) * width];
+            for x in row.iter_mut() {
+                *x = rng.next_symmetric() * init;
+            }
+            normalize(row);
+        }
+
+        let mut relation_vectors: BTreeMap<u32, Vec<f32>> = BTreeMap::new();
+        for &rel in rel_names.keys() {
+            let v: Vec<f32> = match config.model {
+                EmbeddingModelV1::TransE => (0..config.dim)
+                    .map(|_| rng.next_symmetric() * init)
+                    .collect(),
+                // Relation phases in [-π, π).
+                EmbeddingModelV1::RotatE => (0..config.dim)
+                    .map(|_| rng.next_symmetric() * std::f32::consts::PI)
+                    .collect(),
+            };
+            relation_vectors.insert(rel, v);
+        }
+
+        let candidates: Vec<u32> = trained_entities.iter().collect();
+        let mut order: Vec<usize> = (0..triples.len()).collect();
+        let mut final_loss = 0.0f32;
+
+        for _epoch in 0..config.epochs {
+            rng.shuffle(&mut order);
+            let mut epoch_loss = 0.0f32;
+            let mut samples = 0usize;
+
+            for &i in &order {
+                let (h, r, t) = triples[i];
+                for _ in 0..config.negatives_per_positive.max(1) {
+                    let corrupt = candidates[rng.next_below(candidates.len())];
+                    let (nh, nt) = if rng.next_u64() & 1 == 0 {
+                        (corrupt, t)
+                    } else {
+                        (h, corrupt)
+                    };
+                    if db.relations.has_edge(nh, r, nt) {
+                        continue;
+                    }
+
+                    let rel_vec = relation_vectors.get_mut(&r.raw()).expect("relation vector");
+                    let pos = distance(
+                        config.model,
+                        config.dim,
+                        row(&entity_vectors, width, h),
+                        rel_vec,
+                        row(&entity_vectors, width, t),
+                    );
+                    let neg = distance(
+                        config.model,
+                        config.dim,
+                        row(&entity_vectors, width, nh),
+                        rel_vec,
+                        row(&entity_vectors, width, nt),
+                    );
+                    let loss = config.margin + pos - neg;
+                    samples += 1;
+                    if loss <= 0.0 {
+                        continue;
+                    }
+                    epoch_loss += loss;
+
+                    // Descend on the positive triple, ascend on the negative one.
+                    sgd_step(
+                        config.model,
+                        config.dim,
+                        &mut entity_vectors,
+                        width,
+                        rel_vec,
+                        (h, t),
+                        config.learning_rate,
+                    );
+                    sgd_step(
+                        config.model,
+                        config.dim,
+                        &mut entity_vectors,
+                        width,
+                        rel_vec,
+                        (nh, nt),
+                        -config.learning_rate,
+                    );
+                }
+            }
+
+            for id in trained_entities.iter() {
+                normalize(&mut entity_vectors[id as usize * width..(id as usize + 1) * width]);
+            }
+            final_loss = if samples > 0 {
+                epoch_loss / samples as f32
+            } else {
+                0.0
+            };
+        }
+
+        let relation_vectors = relation_vectors
+            .into_iter()
+            .map(|(id, v)| (rel_names[&id].clone(), v))
+            .collect();
+
+        Ok(Self {
+            version: KG_EMBEDDINGS_VERSION_V1.to_string(),
+            snapshot_id: None,
+            model: config.model,
+            dim: config.dim,
+            margin: config.margin,
+            max_prediction_confidence: config.max_prediction_confidence.clamp(0.0, 1.0),
+            entity_vectors,
+            relation_vectors,
+            trained_entities,
+            final_loss,
+        })
+    }
+
+    /// Floats stored per entity.
+    pub fn width(&self) -> usize {
+        model_width(self.model, self.dim)
+    }
+
+    pub fn entity_vector(&self, entity_id: u32) -> Option<&[f32]> {
+        if !self.trained_entities.contains(entity_id) {
+            return None;
+        }
+        Some(row(&self.entity_vectors, self.width(), entity_id))
+    }
+
+    pub fn relation_vector(&self, rel_type: &str) -> Option<&[f32]> {
+        self.relation_vectors.get(rel_type).map(|v| v.as_slice())
+    }
+
+    /// Model distance for a triple (lower means more plausible).
+    pub fn distance(&self, source: u32, rel_type: &str, target: u32) -> Option<f32> {
+        let r = self.relation_vector(rel_type)?;
+        let h = self.entity_vector(source)?;
+        let t = self.entity_vector(target)?;
+        Some(distance(self.model, self.dim, h, r, t))
+    }
+
+    /// Suggest the `top_k` most plausible `rel_type` edges missing from `db`.
+    ///
+    /// Candidates are drawn from entities that already occur as a source
+    /// (resp. target) of `rel_type`, which keeps predictions type-plausible
+    /// without consulting the schema. Cost is `O(|sources| · |targets|)`.
+    pub fn predict_links(
+        &self,
+        db: &PathDB,
+        rel_type: &str,
+        top_k: usize,
+    ) -> Vec<LinkPredictionV1> {
+        if top_k == 0 {
+            return Vec::new();
+        }
+        let (Some(r), Some(rel_id)) = (self.relation_vector(rel_type), db.interner.id_of(rel_type))
+        else {
+            return Vec::new();
+        };
+
+        let mut sources = RoaringBitmap::new();
+        let mut targets = RoaringBitmap::new();
+        if let Some(ids) = db.relations.type_index.get(&rel_id) {
+            for rid in ids.iter() {
+                if let Some(rel) = db.relations.get_relation(rid) {
+                    sources.insert(rel.source);
+                    targets.insert(rel.target);
+                }
+            }
+        }
+        sources &= &self.trained_entities;
+        targets &= &self.trained_entities;
+
+        let mut scored: Vec<(f32, u32, u32)> = Vec::new();
+        for s in sources.iter() {
+            let h = row(&self.entity_vectors, self.width(), s);
+            for t in targets.iter() {
+                if s == t || db.relations.has_edge(s, rel_id, t) {
+                    continue;
+                }
+                let d = distance(
+                    self.model,
+                    self.dim,
+                    h,
+                    r,
+                    row(&self.entity_vectors, self.width(), t),
+                );
+                scored.push((d, s, t));
+            }
+        }
+        scored.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+        scored.truncate(top_k);
+
+        scored
+            .into_iter()
+            .map(|(d, s, t)| LinkPredictionV1 {
+                source: s,
+                rel_type: rel_type.to_string(),
+                target: t,
+                distance: d,
+                confidence: self.max_prediction_confidence * sigmoid(self.margin - d),
+            })
+            .collect()
+    }
+
+    pub fn write_file(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("cbor.tmp");
+        let mut f = fs::File::create(&tmp)?;
+        ciborium::ser::into_writer(self, &mut f)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    pub fn read_file(path: &Path) -> Result<Self> {
+        let f = fs::File::open(path)?;
+        let out: Self = ciborium::de::from_reader(f)?;
+        if out.version != KG_EMBEDDINGS_VERSION_V1 {
+            return Err(PathDbError::Corrupt(format!(
+                "unsupported embeddings version: {}",
+                out.version
+            )));
+        }
+        Ok(out)
+    }
+}
+
+/// Conventional embeddings location next to an `.axpd` snapshot.
+pub fn embeddings_path_for_axpd(path: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.emb.cbor", path.display()))
+}
+
+// ============================================================================
+// Model math
+// ============================================================================
+
+fn model_width(model: EmbeddingModelV1, dim: usize) -> usize {
+    match model {
+        EmbeddingModelV1::TransE => dim,
+        EmbeddingModelV1::RotatE => 2 * dim,
+    }
+}
+
+fn row(v: &[f32], width: usize, id: u32) -> &[f32] {
+    &v[id as usize * width..(id as usize + 1) * width]
+}
+
+fn normalize(v: &mut [f32]) {
+    let n = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if n > 1.0 {
+        for x in v.iter_mut() {
+            *x /= n;
+        }
+    }
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// Residual `h ⊕ r − t` (per model), written into `out`.
+fn residual(
+    model: EmbeddingModelV1,
+    dim: usize,
+    h: &[f32],
+    r: &[f32],
+    t: &[f32],
+    out: &mut Vec<f32>,
+) {
+    out.clear();
+    match model {
+        EmbeddingModelV1::TransE => {
+            out.extend((0..dim).map(|k| h[k] + r[k] - t[k]));
+        }
+        EmbeddingModelV1::RotatE => {
+            // Entity layout: [re_0..re_{d-1}, im_0..im_{d-1}].
+            out.resize(2 * dim, 0.0);
+            for k in 0..dim {
+                let (c, s) = (r[k].cos(), r[k].sin());
+                let (a, b) = (h[k], h[dim + k]);
+                out[k] = a * c - b * s - t[k];
+                out[dim + k] = a * s + b * c - t[dim + k];
+            }
+        }
+    }
+}
+
+/// Squared L2 distance of the residual.
+fn distance(model: EmbeddingModelV1, dim: usize, h: &[f32], r: &[f32], t: &[f32]) -> f32 {
+    let mut res = Vec::with_capacity(model_width(model, dim));
+    residual(model, dim, h, r, t, &mut res);
+    res.iter().map(|x| x * x).sum()
+}
+
+/// One gradient step on `d(h, r, t)`; a negative `lr` ascends.
+fn sgd_step(
+    model: EmbeddingModelV1,
+    dim: usize,
+    entities: &mut [f32],
+    width: usize,
+    r: &mut [f32],
+    (h_id, t_id): (u32, u32),
+    lr: f32,
+) {
+    let h: Vec<f32> = row(entities, width, h_id).to_vec();
+    let t: Vec<f32> = row(entities, width, t_id).to_vec();
+    let mut res = Vec::with_capacity(width);
+    residual(model, dim, &h, r, &t, &mut res);
+
+    let mut grad_h = vec![0.0f32; width];
+    let mut grad_t = vec![0.0f32; width];
+    match model {
+        EmbeddingModelV1::TransE => {
+            for k in 0..dim {
+                grad_h[k] = 2.0 * res[k];
+                grad_t[k] = -2.0 * res[k];
+                r[k] -= lr * 2.0 * res[k];
+            }
+        }
+        EmbeddingModelV1::RotatE => {
+            for k in 0..dim {
+                let (c, s) = (r[k].cos(), r[k].sin());
+                let (a, b) = (h[k], h[dim + k]);
+                let (dx, dy) = (res[k], res[dim + k]);
+                grad_h[k] = 2.0 * (dx * c + dy * s);
+                grad_h[dim + k] = 2.0 * (-dx * s + dy * c);
+                grad_t[k] = -2.0 * dx;
+                grad_t[dim + k] = -2.0 * dy;
+                let grad_theta = 2.0 * (dx * (-a * s - b * c) + dy * (a * c - b * s));
+                r[k] -= lr * grad_theta;
+            }
+        }
+    }
+
+    let h_start = h_id as usize * width;
+    for k in 0..width {
+        entities[h_start + k] -= lr * grad_h[k];
+    }
+    let t_start = t_id as usize * width;
+    for k in 0..width {
+        entities[t_start + k] -= lr * grad_t[k];
+    }
+}
+
+/// Small deterministic PRNG (SplitMix64); avoids pulling in `rand`.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[-1, 1)`.
+    fn next_symmetric(&mut self) -> f32 {
+        ((self.next_u64() >> 40) as f32 / (1u64 << 24) as f32) * 2.0 - 1.0
+    }
+
+    fn next_below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    fn shuffle<T>(&mut self, xs: &mut [T]) {
+        for i in (1..xs.len()).rev() {
+            let j = self.next_below(i + 1);
+            xs.swap(i, j);
+        }
+    }
+}
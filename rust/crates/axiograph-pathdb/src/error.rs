@@ -0,0 +1,189 @@
+//! Structured errors for PathDB.
+//!
+//! Snapshot load/save, entity mutation, index sidecars, the `.axi` snapshot
+//! export, embeddings, schema-morphism composition, overlays, the checked
+//! builders in `checked_db`, `Revalidator::new` and the analytics write-back
+//! return [`PathDbError`] so callers can tell a corrupt file from an unknown
+//! entity without string matching.
+//!
+//! Module-graph loading has its own
+//! [`ModuleGraphError`](crate::axi_module_graph::ModuleGraphError).
+//!
+//! The migration is not finished. Still on `anyhow`, and left for follow-up
+//! work: most of the `.axi` module layer (`axi_module_typecheck`,
+//! `axi_semantics`, `axi_typed`), `optimizer`, `witness`, `learning` and
+//! `typestate`. Their errors convert losslessly through
+//! [`PathDbError::Other`], and `PathDbError` converts into `anyhow::Error`
+//! for binaries.
+
+use thiserror::Error;
+
+/// Errors returned by PathDB core APIs.
+#[derive(Debug, Error)]
+pub enum PathDbError {
+    /// The bytes are not a PathDB snapshot (bad magic / header).
+    #[error("Invalid PathDB file")]
+    InvalidFormat,
+
+    #[error("Unsupported PathDB version: {0}")]
+    UnsupportedVersion(u32),
+
+    /// A length-prefixed section runs past the end of the input.
+    #[error("Truncated PathDB file ({section} section)")]
+    Truncated { section: String },
+
+    /// The snapshot decoded but violates a structural invariant.
+    #[error("Corrupt PathDB snapshot: {0}")]
+    Corrupt(String),
+
+    #[error("unknown entity id {0}")]
+    UnknownEntity(u32),
+
+    #[error("entity {entity} is in namespace {actual:?}, not `{expected}`")]
+    NamespaceMismatch {
+        entity: u32,
+        expected: String,
+        actual: Option<String>,
+    },
+
+    /// Adding `child -axi_context_inherits-> parent` would close a cycle.
+    #[error("context {parent} already inherits from {child}; refusing cycle")]
+    ContextCycle { child: u32, parent: u32 },
+
+    /// A relation is already registered as the inverse of another relation.
+    #[error("relation `{relation}` is already the inverse of `{existing}`")]
+    InverseConflict { relation: String, existing: String },
+
+    /// A name cannot be registered as a virtual relation.
+    #[error("cannot register virtual relation `{relation}`: {reason}")]
+    VirtualRelationConflict { relation: String, reason: String },
+
+    /// `add_fact` arguments do not fit the relation's declared fields.
+    #[error("invalid `{relation}` fact: {reason}")]
+    InvalidFact { relation: String, reason: String },
+
+    /// An edge that fails [`crate::checked_db::CheckedDbMut::add_edge_checked`].
+    #[error("invalid `{relation}` edge: {reason}")]
+    InvalidEdge { relation: String, reason: String },
+
+    /// A schema name that is not declared in the meta-plane.
+    #[error("unknown schema `{0}`")]
+    UnknownSchema(String),
+
+    #[error("unknown relation `{relation}` in schema `{schema}`")]
+    UnknownRelation { schema: String, relation: String },
+
+    #[error("unknown object type `{type_name}` in schema `{schema}`")]
+    UnknownObjectType { schema: String, type_name: String },
+
+    /// A snapshot that fails the Rust-side checks behind
+    /// [`crate::checked_db::CheckedDb`].
+    #[error("{0}")]
+    CheckFailed(String),
+
+    /// A fact would share a declared key with an existing fact.
+    #[error("{0}")]
+    KeyViolation(Box<crate::facts::KeyViolation>),
+
+    /// An edge would exceed its relation's declared multiplicity.
+    #[error("{0}")]
+    MultiplicityViolation(Box<crate::multiplicity::MultiplicityViolation>),
+
+    /// A proposed constraint that does not fit the schema it is checked on.
+    #[error("invalid constraint `{constraint}`: {reason}")]
+    InvalidConstraint { constraint: String, reason: String },
+
+    /// A federation member prefix that is not registered.
+    #[error("unknown federation member `{0}`")]
+    UnknownMember(String),
+
+    /// A federation member that cannot be registered under `prefix`.
+    #[error("cannot register federation member `{prefix}`: {reason}")]
+    InvalidMember { prefix: String, reason: String },
+
+    /// A page cursor string that was not produced by [`crate::pagination`].
+    #[error("invalid page cursor `{0}`")]
+    InvalidCursor(String),
+
+    /// A blob payload above `blob_store::MAX_BLOB_BYTES`.
+    #[error("blob of {size} bytes exceeds the {limit}-byte limit")]
+    BlobTooLarge { size: usize, limit: usize },
+
+    /// A JSON query (see [`crate::query_json`]) that does not parse or
+    /// validate.
+    #[error("invalid query: {0}")]
+    InvalidQuery(String),
+
+    /// A language tag that is not BCP 47-shaped (see [`crate::lang_attrs`]).
+    #[error("invalid language tag `{0}`")]
+    InvalidLanguageTag(String),
+
+    /// A confidence threshold outside `[0, 1]`.
+    #[error("confidence threshold {0} is not in [0, 1]")]
+    InvalidThreshold(f32),
+
+    /// A query shape the chosen execution path cannot answer.
+    #[error("unsupported: {0}")]
+    Unsupported(String),
+
+    /// A `.axi` snapshot export that does not decode (bad token, missing
+    /// assignment, dangling id, ...).
+    #[error("invalid PathDB .axi export: {0}")]
+    InvalidExport(String),
+
+    /// Two schema morphisms that do not compose.
+    #[error("cannot compose schema morphisms: {0}")]
+    MorphismMismatch(String),
+
+    /// Embedding settings or data that cannot produce a model.
+    #[error("cannot train embeddings: {0}")]
+    InvalidEmbedding(String),
+
+    #[error("serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl PathDbError {
+    /// Whether this error means the input bytes/file are unusable (as opposed
+    /// to a bad request against a healthy database).
+    pub fn is_corrupt_input(&self) -> bool {
+        matches!(
+            self,
+            PathDbError::InvalidFormat
+                | PathDbError::UnsupportedVersion(_)
+                | PathDbError::Truncated { .. }
+                | PathDbError::Corrupt(_)
+                | PathDbError::InvalidExport(_)
+                | PathDbError::Serialization(_)
+        )
+    }
+}
+
+/// CBOR sidecars (index sidecar, embeddings): I/O stays I/O, a value that
+/// cannot be encoded is unsupported.
+impl From<ciborium::ser::Error<std::io::Error>> for PathDbError {
+    fn from(err: ciborium::ser::Error<std::io::Error>) -> Self {
+        match err {
+            ciborium::ser::Error::Io(err) => PathDbError::Io(err),
+            ciborium::ser::Error::Value(msg) => PathDbError::Unsupported(msg),
+        }
+    }
+}
+
+/// CBOR sidecars: I/O stays I/O, anything else is a corrupt file.
+impl From<ciborium::de::Error<std::io::Error>> for PathDbError {
+    fn from(err: ciborium::de::Error<std::io::Error>) -> Self {
+        match err {
+            ciborium::de::Error::Io(err) => PathDbError::Io(err),
+            err => PathDbError::Corrupt(err.to_string()),
+        }
+    }
+}
+
+pub type Result<T, E = PathDbError> = std::result::Result<T, E>;
@@ -0,0 +1,560 @@
+//! Explanations for query answers.
+//!
+//! [`PathDB::explain`] answers "why is this entity in the result of this
+//! query?" with a tree that mirrors the query: type selections, the concrete
+//! edges (with confidences and evidence chunk ids) and paths that were
+//! followed, and — for edges derived by [`crate::inference`] — the rule and
+//! the premise edges it fired on, recursively.
+//!
+//! The witness is the one `execute` would accept under the same filters
+//! (per-edge thresholds, path-confidence policies, context scoping); for
+//! paths it is the best-scoring one. The tree is serializable for UIs, and
+//! [`Explanation::render`] turns it into an indented Markdown list for LLM
+//! responses.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+
+use crate::axi_meta::ATTR_AXI_FACT_ID;
+use crate::confidence::ConfidenceCombiner;
+use crate::context::QueryContext;
+use crate::inference::derived_provenance;
+use crate::{edge_visible, ExecScope, PathConfidenceFilter, PathDB, PathQuery, Relation};
+
+/// Relation from a fact/proposal node to a `DocChunk`.
+pub const REL_HAS_EVIDENCE_CHUNK: &str = "has_evidence_chunk";
+
+/// One node of an explanation tree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Explanation {
+    pub step: ExplanationStep,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Explanation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExplanationStep {
+    /// Root: `entity` is an answer (in `world`, if the query was scoped).
+    Answer {
+        entity: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        world: Option<u32>,
+    },
+    TypeSelection {
+        entity: u32,
+        type_name: String,
+    },
+    Edge(EdgeExplanation),
+    /// A path from `start`; children are its edges in order.
+    Path {
+        start: u32,
+        path: Vec<String>,
+        confidence: f32,
+        combiner: ConfidenceCombiner,
+    },
+    /// Children: one explanation per side.
+    Join,
+    /// Children: the alternative that holds.
+    Union,
+    WithConfidence {
+        min_confidence: f32,
+    },
+    WithPathConfidence {
+        min_path_confidence: f32,
+        combiner: ConfidenceCombiner,
+    },
+    /// The parent edge was derived by `rule_id`; children are its premises.
+    Rule {
+        rule_id: String,
+    },
+}
+
+/// A concrete edge used by the witness.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EdgeExplanation {
+    pub relation_id: u32,
+    pub source: u32,
+    pub rel_type: String,
+    pub target: u32,
+    pub confidence: f32,
+    /// `DocChunk` ids backing the edge or its `.axi` fact node.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub evidence: Vec<String>,
+}
+
+impl Explanation {
+    fn leaf(step: ExplanationStep) -> Self {
+        Self {
+            step,
+            children: Vec::new(),
+        }
+    }
+
+    /// Indented Markdown list, one line per step.
+    pub fn render(&self, db: &PathDB) -> String {
+        let mut out = String::new();
+        self.render_into(db, 0, &mut out);
+        out
+    }
+
+    fn render_into(&self, db: &PathDB, depth: usize, out: &mut String) {
+        let label = |entity: u32| entity_label(db, entity);
+        let line = match &self.step {
+            ExplanationStep::Answer { entity, world } => match world {
+                Some(world) => format!(
+                    "{} is an answer in context {}",
+                    label(*entity),
+                    label(*world)
+                ),
+                None => format!("{} is an answer", label(*entity)),
+            },
+            ExplanationStep::TypeSelection { entity, type_name } => {
+                format!("{} has type `{type_name}`", label(*entity))
+            }
+            ExplanationStep::Edge(edge) => {
+                let mut line = format!(
+                    "{} -{}-> {} (confidence {:.2}",
+                    label(edge.source),
+                    edge.rel_type,
+                    label(edge.target),
+                    edge.confidence
+                );
+                if !edge.evidence.is_empty() {
+                    let _ = write!(line, "; evidence: {}", edge.evidence.join(", "));
+                }
+                line.push(')');
+                line
+            }
+            ExplanationStep::Path {
+                start,
+                path,
+                confidence,
+                combiner,
+            } => format!(
+                "path from {} via {} (confidence {confidence:.2} under {})",
+                label(*start),
+                path.join("/"),
+                combiner.as_str()
+            ),
+            ExplanationStep::Join => "all of:".to_string(),
+            ExplanationStep::Union => "one alternative of a union:".to_string(),
+            ExplanationStep::WithConfidence { min_confidence } => {
+                format!("every edge has confidence >= {min_confidence:.2}:")
+            }
+            ExplanationStep::WithPathConfidence {
+                min_path_confidence,
+                combiner,
+            } => format!(
+                "path confidence ({}) >= {min_path_confidence:.2}:",
+                combiner.as_str()
+            ),
+            ExplanationStep::Rule { rule_id } => format!("derived by rule `{rule_id}` from:"),
+        };
+        let _ = writeln!(out, "{}- {line}", "  ".repeat(depth));
+        for child in &self.children {
+            child.render_into(db, depth + 1, out);
+        }
+    }
+}
+
+/// `` `label` (#id) `` or `` `Type`#id `` for unlabeled entities.
+fn entity_label(db: &PathDB, entity: u32) -> String {
+    match db.entity_label(entity) {
+        Some(label) => format!("`{label}` (#{entity})"),
+        None => {
+            let ty = db
+                .entities
+                .get_type(entity)
+                .and_then(|t| db.interner.lookup(t))
+                .unwrap_or_default();
+            format!("`{ty}`#{entity}")
+        }
+    }
+}
+
+fn is_evidence_chunk_key(key: &str) -> bool {
+    key == "chunk_id" || (key.starts_with("evidence_") && key.ends_with("_chunk_id"))
+}
+
+impl PathDB {
+    /// Why `entity` is in `self.execute(query)`; `None` if it is not.
+    pub fn explain(&self, query: &PathQuery, entity: u32) -> Option<Explanation> {
+        self.explain_in(query, &QueryContext::default(), entity)
+    }
+
+    /// [`Self::explain`] for [`Self::execute_in`].
+    pub fn explain_in(
+        &self,
+        query: &PathQuery,
+        ctx: &QueryContext,
+        entity: u32,
+    ) -> Option<Explanation> {
+        let context = self.resolve_context(ctx);
+        let scope = ExecScope {
+            context: context.as_ref(),
+            ..ExecScope::default()
+        };
+        let why = self.explain_scoped(query, entity, scope)?;
+        Some(Explanation {
+            step: ExplanationStep::Answer {
+                entity,
+                world: ctx.world,
+            },
+            children: vec![why],
+        })
+    }
+
+    /// Mirrors `execute_scoped`.
+    fn explain_scoped(
+        &self,
+        query: &PathQuery,
+        entity: u32,
+        scope: ExecScope<'_>,
+    ) -> Option<Explanation> {
+        match query {
+            PathQuery::SelectByType(type_name) => {
+                let visible = self.find_by_type(type_name)?.contains(entity)
+                    && scope.context.is_none_or(|c| c.entities.contains(entity));
+                visible.then(|| {
+                    Explanation::leaf(ExplanationStep::TypeSelection {
+                        entity,
+                        type_name: type_name.clone(),
+                    })
+                })
+            }
+            PathQuery::SelectRelated(source, rel_type) => {
+                let min_confidence = match (scope.min_confidence, scope.path_filter) {
+                    (edge, None) => edge,
+                    (None, Some(f)) => Some(f.min),
+                    (Some(edge), Some(f)) => Some(edge.max(f.min)),
+                };
+                let rel_type_id = self.interner.id_of(rel_type)?;
+                let best = self
+                    .relations
+                    .outgoing_relation_ids(*source, rel_type_id)
+                    .iter()
+                    .copied()
+                    .filter_map(|id| Some((id, self.relations.get_relation(id)?)))
+                    .filter(|(_, rel)| {
+                        rel.target == entity && edge_visible(rel, min_confidence, scope.context)
+                    })
+                    .max_by(|a, b| a.1.confidence.total_cmp(&b.1.confidence))?;
+                self.explain_edge(best.0, &mut HashSet::new())
+            }
+            PathQuery::FollowPath { start, path } => {
+                let (confidence, edges) = self.best_path(*start, path, entity, scope)?;
+                let combiner = scope
+                    .path_filter
+                    .map_or(ConfidenceCombiner::Product, |f| f.combiner);
+                Some(self.explain_path(*start, path.clone(), confidence, combiner, &edges))
+            }
+            PathQuery::FindPaths {
+                from,
+                to,
+                max_depth,
+            } => {
+                if *to != entity {
+                    return None;
+                }
+                let (confidence, edges) = self.first_path(*from, *to, *max_depth, scope)?;
+                let path = edges
+                    .iter()
+                    .filter_map(|&id| self.relations.get_relation(id))
+                    .map(|rel| self.interner.lookup(rel.rel_type).unwrap_or_default())
+                    .collect();
+                let combiner = scope
+                    .path_filter
+                    .map_or(ConfidenceCombiner::Product, |f| f.combiner);
+                Some(self.explain_path(*from, path, confidence, combiner, &edges))
+            }
+            PathQuery::Join(left, right) => Some(Explanation {
+                step: ExplanationStep::Join,
+                children: vec![
+                    self.explain_scoped(left, entity, scope)?,
+                    self.explain_scoped(right, entity, scope)?,
+                ],
+            }),
+            PathQuery::Union(left, right) => {
+                let branch = self
+                    .explain_scoped(left, entity, scope)
+                    .or_else(|| self.explain_scoped(right, entity, scope))?;
+                Some(Explanation {
+                    step: ExplanationStep::Union,
+                    children: vec![branch],
+                })
+            }
+            PathQuery::WithConfidence {
+                base,
+                min_confidence,
+            } => {
+                let scope = ExecScope {
+                    min_confidence: Some(
+                        scope
+                            .min_confidence
+                            .map_or(*min_confidence, |prev| prev.max(*min_confidence)),
+                    ),
+                    ..scope
+                };
+                Some(Explanation {
+                    step: ExplanationStep::WithConfidence {
+                        min_confidence: *min_confidence,
+                    },
+                    children: vec![self.explain_scoped(base, entity, scope)?],
+                })
+            }
+            PathQuery::WithPathConfidence {
+                base,
+                min_path_confidence,
+                combiner,
+            } => {
+                let scope = ExecScope {
+                    path_filter: Some(PathConfidenceFilter {
+                        combiner: *combiner,
+                        min: min_path_confidence.clamp(0.0, 1.0),
+                    }),
+                    ..scope
+                };
+                Some(Explanation {
+                    step: ExplanationStep::WithPathConfidence {
+                        min_path_confidence: *min_path_confidence,
+                        combiner: *combiner,
+                    },
+                    children: vec![self.explain_scoped(base, entity, scope)?],
+                })
+            }
+        }
+    }
+
+    fn explain_path(
+        &self,
+        start: u32,
+        path: Vec<String>,
+        confidence: f32,
+        combiner: ConfidenceCombiner,
+        edges: &[u32],
+    ) -> Explanation {
+        Explanation {
+            step: ExplanationStep::Path {
+                start,
+                path,
+                confidence,
+                combiner,
+            },
+            children: edges
+                .iter()
+                .filter_map(|&id| self.explain_edge(id, &mut HashSet::new()))
+                .collect(),
+        }
+    }
+
+    /// The edge, and for derived edges the rule and (recursively) its
+    /// premises. `seen` guards against cyclic provenance.
+    fn explain_edge(&self, relation_id: u32, seen: &mut HashSet<u32>) -> Option<Explanation> {
+        let rel = self.relations.get_relation(relation_id)?;
+        let mut node = Explanation::leaf(ExplanationStep::Edge(EdgeExplanation {
+            relation_id,
+            source: rel.source,
+            rel_type: self.interner.lookup(rel.rel_type).unwrap_or_default(),
+            target: rel.target,
+            confidence: rel.confidence,
+            evidence: self.edge_evidence(rel),
+        }));
+        if !seen.insert(relation_id) {
+            return Some(node);
+        }
+        if let Some(provenance) = derived_provenance(self, relation_id) {
+            let premises = provenance
+                .premises
+                .iter()
+                .filter_map(|key| self.relation_by_key(key))
+                .filter_map(|id| self.explain_edge(id, seen))
+                .collect();
+            node.children.push(Explanation {
+                step: ExplanationStep::Rule {
+                    rule_id: provenance.rule_id,
+                },
+                children: premises,
+            });
+        }
+        seen.remove(&relation_id);
+        Some(node)
+    }
+
+    /// Resolve an `inference::edge_key` (`source-rel->target`).
+    fn relation_by_key(&self, key: &str) -> Option<u32> {
+        let (source, rest) = key.split_once('-')?;
+        let (rel_type, target) = rest.rsplit_once("->")?;
+        let (source, target) = (source.parse::<u32>().ok()?, target.parse::<u32>().ok()?);
+        let rel_type = self.interner.id_of(rel_type)?;
+        self.relations
+            .outgoing_relation_ids(source, rel_type)
+            .iter()
+            .copied()
+            .find(|&id| {
+                self.relations
+                    .get_relation(id)
+                    .is_some_and(|rel| rel.target == target)
+            })
+    }
+
+    /// Evidence chunk ids cited by `entity` (typically a fact or proposal
+    /// node): its `chunk_id` / `evidence_*_chunk_id` attributes and the
+    /// `chunk_id` of every chunk it reaches by `has_evidence_chunk`.
+    pub fn entity_evidence(&self, entity: u32) -> Vec<String> {
+        let mut chunks = BTreeSet::new();
+        self.collect_entity_evidence(entity, &mut chunks);
+        chunks.into_iter().collect()
+    }
+
+    /// Evidence chunk ids cited by the edge `relation_id`, as shown in
+    /// [`Self::explain`] trees; empty for unknown ids.
+    pub fn relation_evidence(&self, relation_id: u32) -> Vec<String> {
+        self.relations
+            .get_relation(relation_id)
+            .map(|rel| self.edge_evidence(rel))
+            .unwrap_or_default()
+    }
+
+    /// Evidence chunk ids: `chunk_id` / `evidence_*_chunk_id` attributes on the
+    /// edge, and on (plus `has_evidence_chunk` edges from) its fact node.
+    fn edge_evidence(&self, rel: &Relation) -> Vec<String> {
+        let mut chunks = BTreeSet::new();
+        let mut fact_id = None;
+        for &(key, value) in &rel.attrs {
+            let Some(key) = self.interner.lookup(key) else {
+                continue;
+            };
+            if is_evidence_chunk_key(&key) {
+                chunks.extend(self.interner.lookup(value));
+            } else if key == ATTR_AXI_FACT_ID {
+                fact_id = Some(value);
+            }
+        }
+
+        let facts = match (fact_id, self.interner.id_of(ATTR_AXI_FACT_ID)) {
+            (Some(value), Some(key)) => self.entities.entities_with_attr_value(key, value),
+            _ => Default::default(),
+        };
+        for fact in facts.iter() {
+            self.collect_entity_evidence(fact, &mut chunks);
+        }
+        chunks.into_iter().collect()
+    }
+
+    fn collect_entity_evidence(&self, entity: u32, chunks: &mut BTreeSet<String>) {
+        for (key, column) in &self.entities.attrs {
+            let Some(value) = column.get(&entity) else {
+                continue;
+            };
+            if self
+                .interner
+                .lookup(*key)
+                .is_some_and(|k| is_evidence_chunk_key(&k))
+            {
+                chunks.extend(self.interner.lookup(*value));
+            }
+        }
+        let chunk_id_key = self.interner.id_of("chunk_id");
+        for chunk in self.follow_one(entity, REL_HAS_EVIDENCE_CHUNK).iter() {
+            let id = chunk_id_key
+                .and_then(|key| self.entities.get_attr(chunk, key))
+                .and_then(|value| self.interner.lookup(value));
+            chunks.insert(id.unwrap_or_else(|| chunk.to_string()));
+        }
+    }
+
+    /// Best-scoring `start -path-> target` under `scope` (the score
+    /// `follow_path_scored` would report), as relation ids.
+    fn best_path(
+        &self,
+        start: u32,
+        path: &[String],
+        target: u32,
+        scope: ExecScope<'_>,
+    ) -> Option<(f32, Vec<u32>)> {
+        let min_confidence = scope.min_confidence.map(|c| c.clamp(0.0, 1.0));
+        let combiner = scope
+            .path_filter
+            .map_or(ConfidenceCombiner::Product, |f| f.combiner);
+        // Per hop: entity -> (best score, relation id that reached it).
+        let mut layers: Vec<BTreeMap<u32, (f32, Option<u32>)>> =
+            vec![BTreeMap::from([(start, (combiner.identity(), None))])];
+        for rel in path {
+            let rel_type = self.interner.id_of(rel)?;
+            let mut next: BTreeMap<u32, (f32, Option<u32>)> = BTreeMap::new();
+            for (&entity, &(score, _)) in layers.last()? {
+                for &id in self.relations.outgoing_relation_ids(entity, rel_type) {
+                    let edge = self.relations.get_relation(id)?;
+                    if !edge_visible(edge, min_confidence, scope.context) {
+                        continue;
+                    }
+                    let combined = combiner.combine(score, edge.confidence);
+                    let best = next.entry(edge.target).or_insert((combined, Some(id)));
+                    if combined > best.0 {
+                        *best = (combined, Some(id));
+                    }
+                }
+            }
+            layers.push(next);
+        }
+
+        let &(score, _) = layers.last()?.get(&target)?;
+        if scope.path_filter.is_some_and(|f| score < f.min) {
+            return None;
+        }
+        let mut edges = Vec::with_capacity(path.len());
+        let mut at = target;
+        for layer in layers.iter().skip(1).rev() {
+            let id = layer.get(&at)?.1?;
+            edges.push(id);
+            at = self.relations.get_relation(id)?.source;
+        }
+        edges.reverse();
+        Some((score, edges))
+    }
+
+    /// The first path `find_paths` accepts, as relation ids.
+    fn first_path(
+        &self,
+        from: u32,
+        to: u32,
+        max_depth: usize,
+        scope: ExecScope<'_>,
+    ) -> Option<(f32, Vec<u32>)> {
+        let min_confidence = scope.min_confidence.map(|c| c.clamp(0.0, 1.0));
+        let combiner = scope
+            .path_filter
+            .map_or(ConfidenceCombiner::Product, |f| f.combiner);
+        let mut queue: Vec<(u32, Vec<u32>, f32)> = vec![(from, vec![], combiner.identity())];
+        let mut visited = roaring::RoaringBitmap::new();
+        visited.insert(from);
+        while let Some((current, path, path_confidence)) = queue.pop() {
+            if path.len() >= max_depth {
+                continue;
+            }
+            for (id, rel) in self.relations.relations.iter().enumerate() {
+                if rel.source != current
+                    || visited.contains(rel.target)
+                    || !edge_visible(rel, min_confidence, scope.context)
+                {
+                    continue;
+                }
+                let mut new_path = path.clone();
+                new_path.push(id as u32);
+                let confidence = combiner.combine(path_confidence, rel.confidence);
+                if rel.target == to {
+                    if scope.path_filter.is_none_or(|f| confidence >= f.min) {
+                        return Some((confidence, new_path));
+                    }
+                } else {
+                    visited.insert(rel.target);
+                    queue.push((rel.target, new_path, confidence));
+                }
+            }
+        }
+        None
+    }
+}
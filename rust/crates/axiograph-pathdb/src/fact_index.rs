@@ -53,6 +53,9 @@ pub struct FactIndex {
 
 impl FactIndex {
     pub(crate) fn build(db: &PathDB) -> Self {
+        let _span = tracing::debug_span!("pathdb.fact_index.build").entered();
+        let build_seconds = crate::metrics::index_build_seconds("fact");
+        let _timer = build_seconds.start_timer();
         let mut out = FactIndex::default();
 
         let Some(relation_key_id) = db.interner.id_of(ATTR_AXI_RELATION) else {
@@ -354,9 +357,11 @@ impl FactIndexCache {
     ) -> R {
         let gen = self.generation.load(Ordering::SeqCst);
         if self.built_generation.load(Ordering::SeqCst) == gen {
+            crate::metrics::record_cache("fact_index", true);
             let guard = self.index.read().expect("fact index lock poisoned");
             return f(&guard);
         }
+        crate::metrics::record_cache("fact_index", false);
 
         if self.schedule_build_async(gen) {
             return fallback(db);
@@ -0,0 +1,528 @@
+//! Building and reading n-ary fact nodes.
+//!
+//! A fact node reifies one relation tuple, the same shape `.axi` instance
+//! import produces (see [`crate::fact_index`]):
+//!
+//! - an entity of the relation's tuple type (`Relation`, or `RelationFact`
+//!   when the schema also declares an object named `Relation`) with
+//!   `axi_schema`, `axi_relation` and `axi_fact_id` attributes;
+//! - one role edge `fact -field-> value` per field;
+//! - `fact -axi_fact_of-> decl` when the schema's meta plane declares the
+//!   relation, and `fact -axi_fact_in_context-> ctx` when the fact is scoped.
+//!
+//! [`PathDB::add_fact`] writes all of that in one call. When the relation is
+//! declared, the fields must match its declaration, so the key indexes built
+//! from meta-plane `key` constraints pick the fact up like an imported one.
+//! Facts added this way belong to no instance (`axi_fact_id` is computed with
+//! an empty instance name); adding the same tuple twice returns the existing
+//! node.
+//!
+//! Declared keys (`constraint key Relation(fields...)` in a theory on the
+//! schema) are enforced on insertion. A new tuple whose key fields match an
+//! existing fact is a [`KeyViolation`]; [`OnKeyConflict`] picks what happens
+//! next. `add_fact` rejects, [`PathDB::insert_fact`] takes the policy and
+//! reports the violations it resolved.
+
+use roaring::RoaringBitmap;
+
+use crate::axi_meta::{
+    ATTR_AXI_FACT_ID, ATTR_AXI_MODULE, ATTR_AXI_RELATION, ATTR_AXI_SCHEMA, ATTR_AXI_SUPERSEDED_BY,
+    ATTR_CONSTRAINT_FIELDS, ATTR_CONSTRAINT_KIND, ATTR_CONSTRAINT_RELATION, ATTR_FIELD_INDEX,
+    ATTR_FIELD_NAME, META_ATTR_NAME, META_REL_FACT_OF, META_REL_RELATION_HAS_FIELD,
+    META_TYPE_CONSTRAINT, META_TYPE_OBJECT_TYPE, META_TYPE_RELATION_DECL, REL_AXI_FACT_IN_CONTEXT,
+};
+use crate::error::Result;
+use crate::{PathDB, PathDbError};
+
+/// Field that `.axi` `@context` annotations expand into.
+const CONTEXT_FIELD: &str = "ctx";
+
+/// Typed view of a fact node (see [`PathDB::get_fact`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FactView {
+    pub id: u32,
+    pub schema: Option<String>,
+    pub relation: String,
+    pub fact_id: Option<String>,
+    /// `(field, value entity)`, in declaration order when the relation is
+    /// declared.
+    pub fields: Vec<(String, u32)>,
+    pub context: Option<u32>,
+    /// Lowest confidence among the role edges (1.0 without any).
+    pub confidence: f32,
+}
+
+impl FactView {
+    /// The value of `field`, if the fact has it.
+    pub fn field(&self, field: &str) -> Option<u32> {
+        self.fields
+            .iter()
+            .find(|(name, _)| name == field)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// What [`PathDB::insert_fact`] does when a new tuple repeats a declared key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OnKeyConflict {
+    /// Fail with [`PathDbError::KeyViolation`]; nothing is written.
+    #[default]
+    Reject,
+    /// Insert the new fact and retire the conflicting ones: their outgoing
+    /// edges are dropped, they lose `axi_relation` (so fact lookups skip
+    /// them) and gain `axi_superseded_by`. Incoming edges are kept.
+    Supersede,
+    /// Keep the existing fact and overwrite its non-key fields with the new
+    /// values (rewritten role edges carry the new confidence). Its
+    /// `axi_fact_id` still names the tuple it was inserted with.
+    Merge,
+}
+
+/// A new tuple whose key fields match an existing fact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyViolation {
+    pub schema: String,
+    pub relation: String,
+    pub key_fields: Vec<String>,
+    pub key_values: Vec<u32>,
+    /// The fact node already holding the key.
+    pub existing: u32,
+}
+
+impl std::fmt::Display for KeyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let key: Vec<String> = self
+            .key_fields
+            .iter()
+            .zip(&self.key_values)
+            .map(|(field, value)| format!("{field}={value}"))
+            .collect();
+        write!(
+            f,
+            "key ({}) of `{}.{}` is already held by fact {}",
+            key.join(", "),
+            self.schema,
+            self.relation,
+            self.existing
+        )
+    }
+}
+
+/// Result of [`PathDB::insert_fact`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FactInsert {
+    /// The new fact node, or the existing one for a duplicate or a merge.
+    pub fact: u32,
+    /// Key conflicts resolved by the policy (empty without conflicts).
+    pub violations: Vec<KeyViolation>,
+}
+
+/// A relation declaration from the meta plane.
+struct DeclaredRelation {
+    entity: u32,
+    module: Option<String>,
+    fields: Vec<String>,
+}
+
+impl PathDB {
+    /// Add a fact node for `relation(fields...)` in `schema`.
+    ///
+    /// Role edges carry `confidence`. `context` scopes the fact to a
+    /// context/world entity; a `ctx` field does the same, and a declared `ctx`
+    /// field is filled from `context` when omitted. Returns the fact node,
+    /// which is the existing one if the same tuple was already added.
+    ///
+    /// A tuple repeating a declared key is rejected; see [`Self::insert_fact`]
+    /// for the other policies.
+    pub fn add_fact(
+        &mut self,
+        schema: &str,
+        relation: &str,
+        fields: &[(&str, u32)],
+        confidence: f32,
+        context: Option<u32>,
+    ) -> Result<u32> {
+        self.insert_fact(
+            schema,
+            relation,
+            fields,
+            confidence,
+            context,
+            OnKeyConflict::Reject,
+        )
+        .map(|insert| insert.fact)
+    }
+
+    /// [`Self::add_fact`] with an explicit key-conflict policy.
+    pub fn insert_fact(
+        &mut self,
+        schema: &str,
+        relation: &str,
+        fields: &[(&str, u32)],
+        confidence: f32,
+        context: Option<u32>,
+        on_conflict: OnKeyConflict,
+    ) -> Result<FactInsert> {
+        let invalid = |reason: String| PathDbError::InvalidFact {
+            relation: relation.to_string(),
+            reason,
+        };
+        let entity_count = self.entities.types.len() as u32;
+        for &id in fields.iter().map(|(_, id)| id).chain(context.iter()) {
+            if id >= entity_count {
+                return Err(PathDbError::UnknownEntity(id));
+            }
+        }
+
+        let mut fields: Vec<(&str, u32)> = fields.to_vec();
+        for (i, (name, _)) in fields.iter().enumerate() {
+            if fields[..i].iter().any(|(other, _)| other == name) {
+                return Err(invalid(format!("field `{name}` given twice")));
+            }
+        }
+        let context_field = fields
+            .iter()
+            .find(|(name, _)| *name == CONTEXT_FIELD)
+            .map(|(_, id)| *id);
+        let context = match (context, context_field) {
+            (Some(a), Some(b)) if a != b => {
+                return Err(invalid(format!(
+                    "context {a} disagrees with the `ctx` field ({b})"
+                )))
+            }
+            (c, f) => c.or(f),
+        };
+
+        let decl = self.declared_relation(schema, relation);
+        if let Some(decl) = &decl {
+            if let (Some(ctx), None) = (context, context_field) {
+                if decl.fields.iter().any(|f| f == CONTEXT_FIELD) {
+                    fields.push((CONTEXT_FIELD, ctx));
+                }
+            }
+            if let Some((name, _)) = fields
+                .iter()
+                .find(|(name, _)| !decl.fields.iter().any(|f| f == name))
+            {
+                return Err(invalid(format!("`{name}` is not a declared field")));
+            }
+            if let Some(missing) = decl
+                .fields
+                .iter()
+                .find(|f| !fields.iter().any(|(name, _)| name == *f))
+            {
+                return Err(invalid(format!("missing field `{missing}`")));
+            }
+            fields.sort_by_key(|(name, _)| decl.fields.iter().position(|f| f == name));
+        } else if fields.is_empty() {
+            return Err(invalid("a fact needs at least one field".to_string()));
+        }
+
+        // The context is part of the tuple's identity even when it is not a
+        // field.
+        let mut identity = fields.clone();
+        if let (Some(ctx), false) = (context, fields.iter().any(|(n, _)| *n == CONTEXT_FIELD)) {
+            identity.push((CONTEXT_FIELD, ctx));
+        }
+        let value_names: Vec<String> = identity
+            .iter()
+            .map(|&(_, id)| {
+                self.attr_string(id, META_ATTR_NAME)
+                    .unwrap_or_else(|| format!("#{id}"))
+            })
+            .collect();
+        let ordered: Vec<(&str, &str)> = identity
+            .iter()
+            .zip(&value_names)
+            .map(|(&(name, _), value)| (name, value.as_str()))
+            .collect();
+        let module = decl.as_ref().and_then(|d| d.module.as_deref());
+        let fact_id = axiograph_dsl::digest::axi_fact_id_v1(
+            module.unwrap_or_default(),
+            schema,
+            "",
+            relation,
+            &ordered,
+        );
+
+        let tuple_type = if self.declared_in_schema(META_TYPE_OBJECT_TYPE, schema, relation) {
+            format!("{relation}Fact")
+        } else {
+            relation.to_string()
+        };
+        if let Some(existing) = self.fact_with_id(&tuple_type, &fact_id) {
+            return Ok(FactInsert {
+                fact: existing,
+                violations: Vec::new(),
+            });
+        }
+
+        let violations = self.key_violations(schema, relation, &fields);
+        if let Some(first) = violations.first() {
+            match on_conflict {
+                OnKeyConflict::Reject => {
+                    return Err(PathDbError::KeyViolation(Box::new(first.clone())));
+                }
+                OnKeyConflict::Merge => {
+                    // Merging into one fact must not leave another key violated.
+                    if let Some(other) = violations.iter().find(|v| v.existing != first.existing) {
+                        return Err(PathDbError::KeyViolation(Box::new(other.clone())));
+                    }
+                    let keyed: Vec<&str> = violations
+                        .iter()
+                        .flat_map(|v| v.key_fields.iter().map(String::as_str))
+                        .collect();
+                    self.merge_fields(first.existing, &fields, &keyed, confidence);
+                    return Ok(FactInsert {
+                        fact: first.existing,
+                        violations,
+                    });
+                }
+                OnKeyConflict::Supersede => {
+                    for violation in &violations {
+                        self.retire_fact(violation.existing, &fact_id);
+                    }
+                }
+            }
+        }
+
+        let tuple_name = format!(
+            "{relation}_fact_{}",
+            fact_id
+                .strip_prefix(axiograph_dsl::digest::AXI_FACT_ID_V1_PREFIX)
+                .unwrap_or(&fact_id)
+        );
+        let mut attrs = vec![
+            (META_ATTR_NAME, tuple_name.as_str()),
+            (ATTR_AXI_SCHEMA, schema),
+            (ATTR_AXI_RELATION, relation),
+            (ATTR_AXI_FACT_ID, fact_id.as_str()),
+        ];
+        if let Some(module) = module {
+            attrs.push((ATTR_AXI_MODULE, module));
+        }
+        let fact = self.add_entity(&tuple_type, attrs);
+
+        let edge_attrs = || vec![(ATTR_AXI_FACT_ID, fact_id.as_str())];
+        if let Some(decl) = &decl {
+            self.add_relation(META_REL_FACT_OF, fact, decl.entity, 1.0, edge_attrs());
+        }
+        for &(name, value) in &fields {
+            self.add_relation(name, fact, value, confidence, edge_attrs());
+        }
+        if let Some(ctx) = context {
+            self.add_relation(REL_AXI_FACT_IN_CONTEXT, fact, ctx, 1.0, edge_attrs());
+        }
+        Ok(FactInsert { fact, violations })
+    }
+
+    /// Typed view of a fact node, or `None` if `fact` is not one.
+    ///
+    /// Fields follow the meta-plane declaration when there is one; otherwise
+    /// every outgoing edge except the context and `axi_fact_of` edges is a
+    /// field.
+    pub fn get_fact(&self, fact: u32) -> Option<FactView> {
+        let relation = self.attr_string(fact, ATTR_AXI_RELATION)?;
+        let schema = self.attr_string(fact, ATTR_AXI_SCHEMA);
+        let decl = schema
+            .as_deref()
+            .and_then(|schema| self.declared_relation(schema, &relation));
+
+        let mut fields = Vec::new();
+        let mut confidence = 1.0f32;
+        match &decl {
+            Some(decl) => {
+                for name in &decl.fields {
+                    let Some(rel) = self
+                        .interner
+                        .id_of(name)
+                        .and_then(|id| self.relations.outgoing(fact, id).first().copied())
+                    else {
+                        continue;
+                    };
+                    confidence = confidence.min(rel.confidence);
+                    fields.push((name.clone(), rel.target));
+                }
+            }
+            None => {
+                let skip =
+                    [REL_AXI_FACT_IN_CONTEXT, META_REL_FACT_OF].map(|r| self.interner.id_of(r));
+                for rel in self.relations.outgoing_any(fact) {
+                    if skip.contains(&Some(rel.rel_type)) {
+                        continue;
+                    }
+                    let Some(name) = self.interner.lookup(rel.rel_type) else {
+                        continue;
+                    };
+                    confidence = confidence.min(rel.confidence);
+                    fields.push((name, rel.target));
+                }
+            }
+        }
+        let context = self
+            .interner
+            .id_of(REL_AXI_FACT_IN_CONTEXT)
+            .and_then(|id| self.relations.targets(fact, id).min());
+
+        Some(FactView {
+            id: fact,
+            schema,
+            relation,
+            fact_id: self.attr_string(fact, ATTR_AXI_FACT_ID),
+            fields,
+            context,
+            confidence,
+        })
+    }
+
+    pub(crate) fn attr_string(&self, entity: u32, key: &str) -> Option<String> {
+        let key = self.interner.id_of(key)?;
+        self.interner.lookup(self.entities.get_attr(entity, key)?)
+    }
+
+    /// Meta-plane entities of `meta_type` named `name` in `schema`.
+    fn schema_decls(&self, meta_type: &str, schema: &str, name: &str) -> RoaringBitmap {
+        let Some(of_type) = self.find_by_type(meta_type) else {
+            return RoaringBitmap::new();
+        };
+        let with =
+            |key: &str, value: &str| match (self.interner.id_of(key), self.interner.id_of(value)) {
+                (Some(key), Some(value)) => self.entities.entities_with_attr_value(key, value),
+                _ => RoaringBitmap::new(),
+            };
+        of_type & with(META_ATTR_NAME, name) & with(ATTR_AXI_SCHEMA, schema)
+    }
+
+    fn declared_in_schema(&self, meta_type: &str, schema: &str, name: &str) -> bool {
+        !self.schema_decls(meta_type, schema, name).is_empty()
+    }
+
+    fn declared_relation(&self, schema: &str, relation: &str) -> Option<DeclaredRelation> {
+        let entity = self
+            .schema_decls(META_TYPE_RELATION_DECL, schema, relation)
+            .min()?;
+        let mut fields: Vec<(usize, String)> = self
+            .interner
+            .id_of(META_REL_RELATION_HAS_FIELD)
+            .map(|rel| self.relations.targets(entity, rel))
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|field| {
+                let name = self.attr_string(field, ATTR_FIELD_NAME)?;
+                let index = self.attr_string(field, ATTR_FIELD_INDEX)?.parse().ok()?;
+                Some((index, name))
+            })
+            .collect();
+        fields.sort();
+        Some(DeclaredRelation {
+            entity,
+            module: self.attr_string(entity, ATTR_AXI_MODULE),
+            fields: fields.into_iter().map(|(_, name)| name).collect(),
+        })
+    }
+
+    /// Existing facts sharing a declared key with `fields`.
+    fn key_violations(
+        &self,
+        schema: &str,
+        relation: &str,
+        fields: &[(&str, u32)],
+    ) -> Vec<KeyViolation> {
+        let attr_is =
+            |key: &str, value: &str| match (self.interner.id_of(key), self.interner.id_of(value)) {
+                (Some(key), Some(value)) => self.entities.entities_with_attr_value(key, value),
+                _ => RoaringBitmap::new(),
+            };
+        let Some(constraints) = self.find_by_type(META_TYPE_CONSTRAINT) else {
+            return Vec::new();
+        };
+        let keys = constraints
+            & attr_is(ATTR_AXI_SCHEMA, schema)
+            & attr_is(ATTR_CONSTRAINT_KIND, "key")
+            & attr_is(ATTR_CONSTRAINT_RELATION, relation);
+        let facts = attr_is(ATTR_AXI_RELATION, relation) & attr_is(ATTR_AXI_SCHEMA, schema);
+
+        let mut out = Vec::new();
+        for key in &keys {
+            let Some(csv) = self.attr_string(key, ATTR_CONSTRAINT_FIELDS) else {
+                continue;
+            };
+            let key_fields: Vec<String> = csv
+                .split(',')
+                .map(str::trim)
+                .filter(|f| !f.is_empty())
+                .map(str::to_string)
+                .collect();
+            let mut key_values = Vec::with_capacity(key_fields.len());
+            let mut holders = facts.clone();
+            for field in &key_fields {
+                let value = fields
+                    .iter()
+                    .find(|(name, _)| name == field)
+                    .map(|(_, v)| *v);
+                let (Some(value), Some(field_id)) = (value, self.interner.id_of(field)) else {
+                    holders.clear();
+                    break;
+                };
+                key_values.push(value);
+                holders &= self.relations.sources(value, field_id);
+            }
+            if key_fields.is_empty() {
+                continue;
+            }
+            for existing in &holders {
+                out.push(KeyViolation {
+                    schema: schema.to_string(),
+                    relation: relation.to_string(),
+                    key_fields: key_fields.clone(),
+                    key_values: key_values.clone(),
+                    existing,
+                });
+            }
+        }
+        out
+    }
+
+    /// Point the non-key role edges of `fact` at the values in `fields`.
+    fn merge_fields(&mut self, fact: u32, fields: &[(&str, u32)], keyed: &[&str], confidence: f32) {
+        let fact_id = self.attr_string(fact, ATTR_AXI_FACT_ID).unwrap_or_default();
+        for &(name, value) in fields {
+            if keyed.contains(&name) {
+                continue;
+            }
+            let field_id = self.interner.intern(name);
+            if self.relations.has_edge(fact, field_id, value) {
+                continue;
+            }
+            let context_id = self.interner.intern(REL_AXI_FACT_IN_CONTEXT);
+            let is_context = name == CONTEXT_FIELD;
+            self.retain_relations(|r| {
+                r.source != fact
+                    || (r.rel_type != field_id && !(is_context && r.rel_type == context_id))
+            });
+            let attrs = || vec![(ATTR_AXI_FACT_ID, fact_id.as_str())];
+            self.add_relation(name, fact, value, confidence, attrs());
+            if is_context {
+                self.add_relation(REL_AXI_FACT_IN_CONTEXT, fact, value, 1.0, attrs());
+            }
+        }
+    }
+
+    /// Take `fact` out of the fact plane in favour of `superseded_by`.
+    fn retire_fact(&mut self, fact: u32, superseded_by: &str) {
+        self.retain_relations(|r| r.source != fact);
+        if let Some(key) = self.interner.id_of(ATTR_AXI_RELATION) {
+            self.entities.remove_attr(fact, key);
+        }
+        // `fact` came from the key lookup, so it is always in range.
+        let _ = self.upsert_entity_attr(fact, ATTR_AXI_SUPERSEDED_BY, superseded_by);
+    }
+
+    /// Existing fact node of `tuple_type` with `axi_fact_id = fact_id`.
+    fn fact_with_id(&self, tuple_type: &str, fact_id: &str) -> Option<u32> {
+        let of_type = self.find_by_type(tuple_type)?;
+        let key = self.interner.id_of(ATTR_AXI_FACT_ID)?;
+        let value = self.interner.id_of(fact_id)?;
+        (self.entities.entities_with_attr_value(key, value) & of_type).min()
+    }
+}
@@ -0,0 +1,288 @@
+//! Federated queries over several PathDBs.
+//!
+//! Teams often keep separate graphs (an API graph, a CAD graph, a docs graph)
+//! that describe overlapping things. A [`Federation`] registers each graph
+//! under a namespace prefix and answers [`FederatedQuery`]s that span them,
+//! without copying any data:
+//!
+//! - entities are addressed as [`FederatedRef`]s, written `prefix:id`;
+//! - graphs are joined only through **equivalence links** between entities of
+//!   different members, added with [`Federation::link`] (e.g. from a
+//!   reconciliation run) or [`Federation::reconcile_by_attr`] (same type and
+//!   same attribute value, such as a shared `name`);
+//! - every hop of a path starts from its frontier closed under equivalence,
+//!   so a path can continue in another graph wherever an entity is linked;
+//! - `Join` keeps left answers equivalent to some right answer.
+//!
+//! Members are shared, read-only handles: queries never mutate them and
+//! relation types are matched by name in each member's own interner.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PathDbError, Result};
+use crate::{EntityView, PathDB, PathQuery};
+
+/// An entity of one federation member, written `prefix:id`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct FederatedRef {
+    pub graph: String,
+    pub entity: u32,
+}
+
+impl FederatedRef {
+    pub fn new(graph: impl Into<String>, entity: u32) -> Self {
+        Self {
+            graph: graph.into(),
+            entity,
+        }
+    }
+}
+
+impl fmt::Display for FederatedRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.graph, self.entity)
+    }
+}
+
+impl FromStr for FederatedRef {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (graph, entity) = s
+            .rsplit_once(':')
+            .ok_or_else(|| format!("expected `prefix:id`, got `{s}`"))?;
+        let entity = entity
+            .parse()
+            .map_err(|_| format!("invalid entity id in `{s}`"))?;
+        Ok(Self::new(graph, entity))
+    }
+}
+
+/// A query over the federation.
+#[derive(Debug, Clone)]
+pub enum FederatedQuery {
+    /// Entities of a type, in every member.
+    SelectByType {
+        type_name: String,
+    },
+    /// Follow relation types from `start`, crossing equivalence links.
+    FollowPath {
+        start: FederatedRef,
+        path: Vec<String>,
+    },
+    /// A local query run in one member.
+    In {
+        graph: String,
+        query: PathQuery,
+    },
+    /// Left answers equivalent to some right answer.
+    Join(Box<FederatedQuery>, Box<FederatedQuery>),
+    Union(Box<FederatedQuery>, Box<FederatedQuery>),
+}
+
+/// Member index and local entity id.
+type Node = (usize, u32);
+
+/// A set of registered PathDBs joined by equivalence links.
+#[derive(Default)]
+pub struct Federation {
+    prefixes: Vec<String>,
+    members: Vec<Arc<PathDB>>,
+    links: HashMap<Node, BTreeSet<Node>>,
+}
+
+impl Federation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `db` under `prefix` (non-empty, without `:`).
+    pub fn register(&mut self, prefix: &str, db: Arc<PathDB>) -> Result<()> {
+        let reason = if prefix.is_empty() || prefix.contains(':') {
+            Some("prefixes must be non-empty and must not contain `:`")
+        } else if self.prefixes.iter().any(|p| p == prefix) {
+            Some("already registered")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return Err(PathDbError::InvalidMember {
+                prefix: prefix.to_string(),
+                reason: reason.to_string(),
+            });
+        }
+        self.prefixes.push(prefix.to_string());
+        self.members.push(db);
+        Ok(())
+    }
+
+    /// Registered prefixes, in registration order.
+    pub fn prefixes(&self) -> &[String] {
+        &self.prefixes
+    }
+
+    pub fn member(&self, prefix: &str) -> Option<&Arc<PathDB>> {
+        self.index(prefix).ok().map(|i| &self.members[i])
+    }
+
+    /// The entity `r` names, if its member and id exist.
+    pub fn entity(&self, r: &FederatedRef) -> Option<EntityView> {
+        self.member(&r.graph)?.get_entity(r.entity)
+    }
+
+    /// Record that `a` and `b` are the same thing (symmetric).
+    pub fn link(&mut self, a: &FederatedRef, b: &FederatedRef) -> Result<()> {
+        let (a, b) = (self.node(a)?, self.node(b)?);
+        if a != b {
+            self.links.entry(a).or_default().insert(b);
+            self.links.entry(b).or_default().insert(a);
+        }
+        Ok(())
+    }
+
+    /// Link entities of different members that have the same type and the
+    /// same `attr` value. Returns how many new links were added.
+    pub fn reconcile_by_attr(&mut self, attr: &str) -> usize {
+        let mut by_key: BTreeMap<(String, String), Vec<Node>> = BTreeMap::new();
+        for (g, db) in self.members.iter().enumerate() {
+            let Some(attr_id) = db.interner.id_of(attr) else {
+                continue;
+            };
+            for entity in 0..db.entities.len() as u32 {
+                let (Some(t), Some(v)) = (
+                    db.entities.get_type(entity),
+                    db.entities.get_attr(entity, attr_id),
+                ) else {
+                    continue;
+                };
+                let (Some(t), Some(v)) = (db.interner.lookup(t), db.interner.lookup(v)) else {
+                    continue;
+                };
+                by_key.entry((t, v)).or_default().push((g, entity));
+            }
+        }
+        let mut added = 0;
+        for nodes in by_key.values() {
+            for (i, &a) in nodes.iter().enumerate() {
+                for &b in &nodes[i + 1..] {
+                    if a.0 != b.0 && self.links.entry(a).or_default().insert(b) {
+                        self.links.entry(b).or_default().insert(a);
+                        added += 1;
+                    }
+                }
+            }
+        }
+        added
+    }
+
+    /// Everything linked to `r`, transitively (including `r`).
+    pub fn equivalents(&self, r: &FederatedRef) -> Result<BTreeSet<FederatedRef>> {
+        let node = self.node(r)?;
+        Ok(self
+            .close([node].into())
+            .into_iter()
+            .map(|n| self.to_ref(n))
+            .collect())
+    }
+
+    /// Execute `query` (see the module docs).
+    pub fn execute(&self, query: &FederatedQuery) -> Result<BTreeSet<FederatedRef>> {
+        Ok(self
+            .run(query)?
+            .into_iter()
+            .map(|n| self.to_ref(n))
+            .collect())
+    }
+
+    fn run(&self, query: &FederatedQuery) -> Result<BTreeSet<Node>> {
+        Ok(match query {
+            FederatedQuery::SelectByType { type_name } => self
+                .members
+                .iter()
+                .enumerate()
+                .flat_map(|(g, db)| {
+                    db.find_by_type(type_name)
+                        .into_iter()
+                        .flat_map(move |ids| ids.iter().map(move |e| (g, e)))
+                })
+                .collect(),
+            FederatedQuery::FollowPath { start, path } => {
+                let mut frontier = BTreeSet::from([self.node(start)?]);
+                for rel_type in path {
+                    if frontier.is_empty() {
+                        break;
+                    }
+                    frontier = self
+                        .close(frontier)
+                        .into_iter()
+                        .flat_map(|(g, e)| {
+                            self.members[g]
+                                .follow_one(e, rel_type)
+                                .into_iter()
+                                .map(move |t| (g, t))
+                        })
+                        .collect();
+                }
+                frontier
+            }
+            FederatedQuery::In { graph, query } => {
+                let g = self.index(graph)?;
+                self.members[g]
+                    .execute(query)
+                    .iter()
+                    .map(|e| (g, e))
+                    .collect()
+            }
+            FederatedQuery::Join(left, right) => {
+                let right = self.close(self.run(right)?);
+                self.run(left)?
+                    .into_iter()
+                    .filter(|&n| right.contains(&n))
+                    .collect()
+            }
+            FederatedQuery::Union(left, right) => {
+                let mut out = self.run(left)?;
+                out.extend(self.run(right)?);
+                out
+            }
+        })
+    }
+
+    /// `nodes` plus everything reachable over equivalence links.
+    fn close(&self, nodes: BTreeSet<Node>) -> BTreeSet<Node> {
+        let mut queue: VecDeque<Node> = nodes.iter().copied().collect();
+        let mut seen = nodes;
+        while let Some(n) = queue.pop_front() {
+            for &m in self.links.get(&n).into_iter().flatten() {
+                if seen.insert(m) {
+                    queue.push_back(m);
+                }
+            }
+        }
+        seen
+    }
+
+    fn index(&self, prefix: &str) -> Result<usize> {
+        self.prefixes
+            .iter()
+            .position(|p| p == prefix)
+            .ok_or_else(|| PathDbError::UnknownMember(prefix.to_string()))
+    }
+
+    fn node(&self, r: &FederatedRef) -> Result<Node> {
+        let g = self.index(&r.graph)?;
+        if r.entity as usize >= self.members[g].entities.len() {
+            return Err(PathDbError::UnknownEntity(r.entity));
+        }
+        Ok((g, r.entity))
+    }
+
+    fn to_ref(&self, (g, entity): Node) -> FederatedRef {
+        FederatedRef::new(self.prefixes[g].clone(), entity)
+    }
+}
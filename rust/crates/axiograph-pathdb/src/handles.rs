@@ -0,0 +1,153 @@
+//! Pre-resolved query handles for hot loops.
+//!
+//! The string-keyed APIs (`follow_one(source, "knows")`, `get_entity`) hash
+//! every relation/attribute name through the interner on every call, and
+//! `StringInterner::lookup` copies each string out. Batch pipelines that ask
+//! the same question for many entities can resolve names once instead:
+//!
+//! ```ignore
+//! let knows = db.rel_type_handle("knows");
+//! let email = db.attr_key_handle("email");
+//! for entity in batch {
+//!     let friends = db.follow_one_handle(entity, knows)?;
+//!     let address = db.attr_value(entity, email)?; // Cow<str>
+//! }
+//! ```
+//!
+//! A handle is resolved against one `PathDB` instance and carries its
+//! [`DbToken`]; using it with another instance fails with
+//! [`DbTokenMismatch`] (the IDs would mean different strings there).
+//! Resolving a name the DB has never seen yields a handle that matches
+//! nothing, so lookups with it are empty rather than errors, exactly like the
+//! string-keyed APIs. Such a handle stays empty even if the name is added
+//! later, so resolve handles after loading.
+//!
+//! Attribute values come back as `Cow<str>`: borrowed (no allocation) once
+//! the interner is frozen (see [`StringInterner::freeze`]), copied otherwise.
+//!
+//! [`StringInterner::freeze`]: crate::StringInterner::freeze
+
+use std::borrow::Cow;
+
+use roaring::RoaringBitmap;
+
+use crate::budget::BudgetTracker;
+use crate::{DbToken, DbTokenMismatch, ExecScope, PathDB, StrId};
+
+/// A relation type resolved against one `PathDB`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RelTypeHandle {
+    db_token: DbToken,
+    id: Option<StrId>,
+}
+
+impl RelTypeHandle {
+    /// Interned id of the relation type (`None`: never seen by the DB).
+    pub fn id(self) -> Option<StrId> {
+        self.id
+    }
+}
+
+/// An attribute key resolved against one `PathDB`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AttrKeyHandle {
+    db_token: DbToken,
+    id: Option<StrId>,
+}
+
+impl AttrKeyHandle {
+    /// Interned id of the attribute key (`None`: never seen by the DB).
+    pub fn id(self) -> Option<StrId> {
+        self.id
+    }
+}
+
+impl PathDB {
+    fn check_token(&self, token: DbToken) -> Result<(), DbTokenMismatch> {
+        if token == self.db_token() {
+            Ok(())
+        } else {
+            Err(DbTokenMismatch {
+                expected: token,
+                actual: self.db_token(),
+            })
+        }
+    }
+
+    /// Resolve a relation type once for [`Self::follow_one_handle`] and
+    /// [`Self::follow_path_handles`].
+    pub fn rel_type_handle(&self, rel_type: &str) -> RelTypeHandle {
+        RelTypeHandle {
+            db_token: self.db_token(),
+            id: self.interner.id_of(rel_type),
+        }
+    }
+
+    /// Resolve an attribute key once for [`Self::attr_value_id`] and
+    /// [`Self::attr_value`].
+    pub fn attr_key_handle(&self, key: &str) -> AttrKeyHandle {
+        AttrKeyHandle {
+            db_token: self.db_token(),
+            id: self.interner.id_of(key),
+        }
+    }
+
+    /// [`Self::follow_one`] with a pre-resolved relation type.
+    pub fn follow_one_handle(
+        &self,
+        source: u32,
+        rel_type: RelTypeHandle,
+    ) -> Result<RoaringBitmap, DbTokenMismatch> {
+        self.check_token(rel_type.db_token)?;
+        let Some(id) = rel_type.id else {
+            return Ok(RoaringBitmap::new());
+        };
+        Ok(self.relations.targets(source, id)
+            | self.inverse_targets_by_id(source, id, None, None)
+            | self.virtual_targets_by_id(source, id))
+    }
+
+    /// [`Self::follow_path`] with pre-resolved relation types.
+    pub fn follow_path_handles(
+        &self,
+        start: u32,
+        path: &[RelTypeHandle],
+    ) -> Result<RoaringBitmap, DbTokenMismatch> {
+        let mut rel_ids = Vec::with_capacity(path.len());
+        for handle in path {
+            self.check_token(handle.db_token)?;
+            let Some(id) = handle.id else {
+                return Ok(RoaringBitmap::new());
+            };
+            rel_ids.push(id);
+        }
+        Ok(self.follow_path_ids_budgeted(
+            start,
+            rel_ids,
+            ExecScope::default(),
+            &mut BudgetTracker::unlimited(),
+        ))
+    }
+
+    /// Interned value of `entity`'s attribute `key`, if set.
+    pub fn attr_value_id(
+        &self,
+        entity: u32,
+        key: AttrKeyHandle,
+    ) -> Result<Option<StrId>, DbTokenMismatch> {
+        self.check_token(key.db_token)?;
+        Ok(key.id.and_then(|id| self.entities.get_attr(entity, id)))
+    }
+
+    /// Value of `entity`'s attribute `key`, if set; borrowed when the
+    /// interner is frozen.
+    pub fn attr_value(
+        &self,
+        entity: u32,
+        key: AttrKeyHandle,
+    ) -> Result<Option<Cow<'_, str>>, DbTokenMismatch> {
+        Ok(self
+            .attr_value_id(entity, key)?
+            .and_then(|value| self.interner.lookup_ref(value)))
+    }
+}
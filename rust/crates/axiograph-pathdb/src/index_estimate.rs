@@ -0,0 +1,254 @@
+//! Sampled build-cost estimates for the path index.
+//!
+//! A depth-`d` cube index stores, for every signature of length `<= d` and
+//! every start entity, the set of entities the signature reaches. On a new
+//! dataset its size is hard to guess: one dense relation type can turn depth
+//! 3 from megabytes into the whole heap. [`PathDB::estimate_path_index`]
+//! predicts it before anything is built:
+//!
+//! 1. pick `sample_starts` start entities (those with outgoing edges),
+//!    uniformly with a seeded hash, so the estimate is deterministic;
+//! 2. run the cube construction for those starts only, depth by depth;
+//! 3. scale the sampled entries, bitmaps and build time by
+//!    `sources / sampled`.
+//!
+//! The sample itself stops after `max_expansions` frontier steps, so
+//! estimating a hopeless depth stays cheap; depths past that point are
+//! reported as truncated and never considered safe.
+//!
+//! [`PathIndexPolicy::SafeCube`](crate::PathIndexPolicy::SafeCube) uses the
+//! estimate to pick the deepest cube that fits a budget, and
+//! [`PathIndexPolicy::Cube`](crate::PathIndexPolicy::Cube) logs a warning
+//! when the requested depth would not fit the default one.
+//!
+//! Like [`crate::sampling`], these are evidence-plane numbers: they steer
+//! index construction and never feed certificates.
+
+use std::time::Instant;
+
+use ahash::AHashMap;
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use crate::cardinality::mix64;
+use crate::{PathDB, StrId};
+
+/// Rough heap cost of one indexed `(start, target)` pair (roaring array
+/// container).
+const BYTES_PER_ENTRY: u64 = 2;
+/// Rough heap cost of one `(signature, start)` bitmap, map slot included.
+const BYTES_PER_BITMAP: u64 = 64;
+
+/// Sampling settings for [`PathDB::estimate_path_index`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexEstimateConfig {
+    /// Start entities to sample (all of them when there are fewer).
+    pub sample_starts: usize,
+    pub seed: u64,
+    /// Stop sampling after this many frontier steps.
+    pub max_expansions: u64,
+}
+
+impl Default for IndexEstimateConfig {
+    fn default() -> Self {
+        Self {
+            sample_starts: 256,
+            seed: 0,
+            max_expansions: 1_000_000,
+        }
+    }
+}
+
+/// [`PathIndexPolicy::SafeCube`](crate::PathIndexPolicy::SafeCube) settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SafeCubeConfig {
+    /// Deepest index wanted; `build_indexes` sets the index depth to the
+    /// deepest depth up to this one that fits.
+    pub max_depth: usize,
+    /// Budget on the estimated multi-hop `(start, target)` pairs.
+    pub max_entries: u64,
+    pub sample: IndexEstimateConfig,
+}
+
+impl Default for SafeCubeConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            max_entries: 5_000_000,
+            sample: IndexEstimateConfig::default(),
+        }
+    }
+}
+
+/// Predicted cost of one depth of the cube (signatures of exactly this
+/// length).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DepthEstimate {
+    pub depth: usize,
+    /// Non-empty signatures seen in the sample (a lower bound).
+    pub signatures: u64,
+    /// Indexed `(start, target)` pairs.
+    pub entries: u64,
+    /// Approximate heap size.
+    pub bytes: u64,
+    /// Build time, scaled from the time the sample took.
+    pub seconds: f64,
+}
+
+/// Result of [`PathDB::estimate_path_index`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PathIndexEstimate {
+    /// Entities with at least one outgoing edge.
+    pub sources: u64,
+    pub sampled: u64,
+    /// Depths `1..`, up to the requested one; stops early at the first
+    /// empty depth or when the sample is truncated.
+    pub depths: Vec<DepthEstimate>,
+    /// The sample ran out of `max_expansions`; depths after the last entry
+    /// were not estimated.
+    pub truncated: bool,
+    /// Always false: estimates are evidence-plane, never certified.
+    pub certified: bool,
+}
+
+impl PathIndexEstimate {
+    /// Entries of signatures of length `2..=depth` (single-hop signatures
+    /// are always indexed, as under the auto policy).
+    pub fn multi_hop_entries(&self, depth: usize) -> u64 {
+        self.depths
+            .iter()
+            .filter(|d| (2..=depth).contains(&d.depth))
+            .map(|d| d.entries)
+            .sum()
+    }
+
+    /// The deepest depth up to `max_depth` whose multi-hop entries fit
+    /// `max_entries`. Depths past a truncated sample never qualify; the
+    /// answer is at least 1 unless `max_depth` is 0.
+    pub fn safe_depth(&self, max_depth: usize, max_entries: u64) -> usize {
+        let limit = if self.truncated {
+            self.depths.last().map_or(1, |d| d.depth)
+        } else {
+            max_depth
+        };
+        (1..=max_depth.min(limit))
+            .rev()
+            .find(|&depth| self.multi_hop_entries(depth) <= max_entries)
+            .unwrap_or(max_depth.min(1))
+    }
+}
+
+impl PathDB {
+    /// Predict the size and build time of a depth-`max_depth` cube index
+    /// from a sample of start entities (see the module docs).
+    pub fn estimate_path_index(
+        &self,
+        max_depth: usize,
+        config: &IndexEstimateConfig,
+    ) -> PathIndexEstimate {
+        let _span = tracing::debug_span!(
+            "pathdb.estimate_path_index",
+            max_depth,
+            sample = config.sample_starts
+        )
+        .entered();
+        let mut sources: Vec<u32> = {
+            let mut all = RoaringBitmap::new();
+            all.extend(self.relations.relations.iter().map(|rel| rel.source));
+            all.iter().collect()
+        };
+        let mut estimate = PathIndexEstimate {
+            sources: sources.len() as u64,
+            sampled: 0,
+            depths: Vec::new(),
+            truncated: false,
+            certified: false,
+        };
+        if max_depth == 0 || sources.is_empty() {
+            return estimate;
+        }
+        sources.sort_by_key(|&id| (mix64(u64::from(id) ^ config.seed), id));
+        sources.truncate(config.sample_starts.max(1));
+        estimate.sampled = sources.len() as u64;
+        let scale = estimate.sources as f64 / estimate.sampled as f64;
+
+        let mut rel_types: Vec<StrId> = self.relations.type_index.keys().copied().collect();
+        rel_types.sort();
+
+        // Per sampled start: signature -> reached entities at the current depth.
+        let timer = Instant::now();
+        let mut layer: Vec<AHashMap<Vec<StrId>, RoaringBitmap>> = sources
+            .iter()
+            .map(|&start| {
+                let mut reach: AHashMap<Vec<StrId>, RoaringBitmap> = AHashMap::new();
+                for rel in self.relations.outgoing_any(start) {
+                    reach
+                        .entry(vec![rel.rel_type])
+                        .or_default()
+                        .insert(rel.target);
+                }
+                reach
+            })
+            .collect();
+        let mut seconds = timer.elapsed().as_secs_f64();
+        let mut expansions = 0_u64;
+
+        for depth in 1..=max_depth {
+            if depth > 1 {
+                let timer = Instant::now();
+                let mut next_layer = Vec::with_capacity(layer.len());
+                for reach in &layer {
+                    let mut next: AHashMap<Vec<StrId>, RoaringBitmap> = AHashMap::new();
+                    for (sig, frontier) in reach {
+                        for &rel_type in &rel_types {
+                            let (targets, complete) = self.relations.expand_frontier_budgeted(
+                                frontier,
+                                rel_type,
+                                true,
+                                &mut || {
+                                    expansions += 1;
+                                    expansions <= config.max_expansions
+                                },
+                            );
+                            if !complete {
+                                estimate.truncated = true;
+                                return estimate;
+                            }
+                            if !targets.is_empty() {
+                                let mut sig = sig.clone();
+                                sig.push(rel_type);
+                                next.insert(sig, targets);
+                            }
+                        }
+                    }
+                    next_layer.push(next);
+                }
+                layer = next_layer;
+                seconds = timer.elapsed().as_secs_f64();
+            }
+
+            let mut signatures: Vec<&Vec<StrId>> = layer.iter().flat_map(|r| r.keys()).collect();
+            signatures.sort();
+            signatures.dedup();
+            let bitmaps: u64 = layer.iter().map(|r| r.len() as u64).sum();
+            let entries: u64 = layer
+                .iter()
+                .flat_map(|r| r.values())
+                .map(RoaringBitmap::len)
+                .sum();
+            if entries == 0 {
+                break;
+            }
+            let entries = (entries as f64 * scale).ceil() as u64;
+            let bitmaps = (bitmaps as f64 * scale).ceil() as u64;
+            estimate.depths.push(DepthEstimate {
+                depth,
+                signatures: signatures.len() as u64,
+                entries,
+                bytes: entries * BYTES_PER_ENTRY + bitmaps * BYTES_PER_BITMAP,
+                seconds: seconds * scale,
+            });
+        }
+        estimate
+    }
+}
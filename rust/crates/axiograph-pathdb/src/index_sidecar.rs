@@ -5,11 +5,11 @@ use std::path::{Path, PathBuf};
 use std::sync::{mpsc, Weak};
 use std::time::Duration;
 
-use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
 use ahash::AHashMap;
 
+use crate::error::Result;
 use crate::fact_index::FactIndex;
 use crate::text_index::InvertedIndex;
 use crate::{PathDB, PathSig, StrId};
@@ -0,0 +1,541 @@
+//! Forward-chaining rule inference with provenance-tagged derived facts.
+//!
+//! Rules are Horn clauses over PathDB edges and types:
+//!
+//! ```text
+//! hasMaterial(op, m) && Titanium(m) -> preferredSpeed(op, "LowSpeed")
+//! ```
+//!
+//! - a binary atom `Rel(a, b)` matches an edge `a -Rel-> b`;
+//! - a unary atom `T(x)` matches an entity of type `T` (including virtual types);
+//! - identifiers are variables, `"quoted"` arguments name an entity (`name`
+//!   attribute); `&&` / `∧` separate premises, `->` / `=>` introduces the head;
+//! - the head must be a binary atom whose variables all occur in the premises.
+//!
+//! Rules are read from `.axi` ([`RuleSet::from_db`]): each line containing
+//! `->` in a named theory block (`constraint Name:` + indented body), and the
+//! `rule` attribute of `TacitKnowledge` entities (with their `confidence`).
+//!
+//! Every derived edge is tagged `derived=true`, with the rule id and its
+//! premises (as stable `source-rel->target` keys, since relation ids are
+//! compacted on retraction). Its confidence is the product of the premise
+//! edge confidences and the rule's confidence.
+//!
+//! Evaluation is semi-naive: [`RuleSet::run_since`] only joins against edges
+//! added after a given relation id. [`RuleSet::retract`] removes edges, then
+//! every derived edge that (transitively) depended on them, then re-derives
+//! whatever still follows from other premises (delete/re-derive). A changed
+//! premise is a retraction followed by an addition.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use crate::axi_meta::META_ATTR_NAME;
+use crate::axi_semantics::MetaPlaneIndex;
+use crate::{PathDB, Relation};
+
+/// Relation attribute marking an inferred edge.
+pub const ATTR_DERIVED: &str = "derived";
+/// Relation attribute naming the rule that derived an edge.
+pub const ATTR_RULE_ID: &str = "axi_rule_id";
+/// Relation attribute listing an inferred edge's premises (`;`-separated keys).
+pub const ATTR_PREMISES: &str = "axi_premises";
+
+/// Entity type whose `rule` attribute holds tacit rule text.
+pub const TACIT_KNOWLEDGE_TYPE: &str = "TacitKnowledge";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum Term {
+    Var(String),
+    /// Entity named by its `name` attribute.
+    Const(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Atom {
+    pub predicate: String,
+    pub args: Vec<Term>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rule {
+    pub id: String,
+    pub premises: Vec<Atom>,
+    pub head: Atom,
+    pub confidence: f32,
+}
+
+impl Rule {
+    /// Parse `p(x, y) && q(y) -> r(x, y)`.
+    pub fn parse(id: &str, text: &str) -> Result<Self, String> {
+        let (body, head) = text
+            .split_once("->")
+            .or_else(|| text.split_once("=>"))
+            .ok_or_else(|| "missing `->`".to_string())?;
+        let premises = body
+            .split("&&")
+            .flat_map(|part| part.split('∧'))
+            .map(parse_atom)
+            .collect::<Result<Vec<_>, _>>()?;
+        let head = parse_atom(head)?;
+        if head.args.len() != 2 {
+            return Err(format!(
+                "head `{}` must be binary (only relations are derived)",
+                head.predicate
+            ));
+        }
+        if premises
+            .iter()
+            .any(|a| a.args.is_empty() || a.args.len() > 2)
+        {
+            return Err("premises must be unary or binary atoms".to_string());
+        }
+        let bound: HashSet<&str> = premises
+            .iter()
+            .flat_map(|a| &a.args)
+            .filter_map(|t| match t {
+                Term::Var(v) => Some(v.as_str()),
+                Term::Const(_) => None,
+            })
+            .collect();
+        for term in &head.args {
+            if let Term::Var(v) = term {
+                if !bound.contains(v.as_str()) {
+                    return Err(format!("head variable `{v}` does not occur in a premise"));
+                }
+            }
+        }
+        Ok(Self {
+            id: id.to_string(),
+            premises,
+            head,
+            confidence: 1.0,
+        })
+    }
+}
+
+fn parse_atom(text: &str) -> Result<Atom, String> {
+    let text = text.trim();
+    let (predicate, rest) = text
+        .split_once('(')
+        .ok_or_else(|| format!("expected `Pred(args)`, got `{text}`"))?;
+    let args = rest
+        .trim_end()
+        .strip_suffix(')')
+        .ok_or_else(|| format!("missing `)` in `{text}`"))?;
+    let predicate = predicate.trim();
+    let is_ident = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+    };
+    if !is_ident(predicate) {
+        return Err(format!("invalid predicate `{predicate}`"));
+    }
+    let args = args
+        .split(',')
+        .map(|arg| {
+            let arg = arg.trim();
+            match arg.strip_prefix('"').and_then(|a| a.strip_suffix('"')) {
+                Some(name) => Ok(Term::Const(name.to_string())),
+                None if is_ident(arg) => Ok(Term::Var(arg.to_string())),
+                None => Err(format!("invalid argument `{arg}` in `{text}`")),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Atom {
+        predicate: predicate.to_string(),
+        args,
+    })
+}
+
+/// Outcome of a derivation or retraction pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InferenceReport {
+    pub rounds: usize,
+    /// Edges added (or re-added after a retraction).
+    pub derived: usize,
+    /// Edges removed because the caller retracted them.
+    pub retracted: usize,
+    /// Derived edges removed because a premise was retracted.
+    pub overdeleted: usize,
+}
+
+/// Provenance of a derived edge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DerivedProvenance {
+    pub rule_id: String,
+    /// Premise keys (`source-rel->target`).
+    pub premises: Vec<String>,
+}
+
+/// Parsed rules, plus the rule texts that could not be used.
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    pub rules: Vec<Rule>,
+    /// `(rule id, error)` for rules that failed to parse.
+    pub skipped: Vec<(String, String)>,
+}
+
+/// Rule text of a named theory block: comments and `key: value` metadata
+/// lines (`message:`, `severity:`, ...) are dropped, the remaining lines are
+/// joined and a leading `forall x : T, ... .` binder is stripped.
+fn rule_text(body: &str) -> String {
+    let text = body
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with("--"))
+        .filter(|l| {
+            !l.split_once(':')
+                .is_some_and(|(key, _)| key.chars().all(|c| c.is_alphanumeric() || c == '_'))
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    match text.strip_prefix("forall ") {
+        Some(rest) => rest
+            .split_once(". ")
+            .map_or(rest, |(_, body)| body)
+            .to_string(),
+        None => text,
+    }
+}
+
+/// Stable key of an edge: `source-rel->target`.
+pub fn edge_key(db: &PathDB, rel: &Relation) -> String {
+    let rel_type = db.interner.lookup(rel.rel_type).unwrap_or_default();
+    format!("{}-{rel_type}->{}", rel.source, rel.target)
+}
+
+/// Provenance of `relation_id` if it was derived by a rule.
+pub fn derived_provenance(db: &PathDB, relation_id: u32) -> Option<DerivedProvenance> {
+    let rel = db.relations.get_relation(relation_id)?;
+    let attr = |key: &str| {
+        let key = db.interner.id_of(key)?;
+        let (_, value) = rel.attrs.iter().find(|(k, _)| *k == key)?;
+        db.interner.lookup(*value)
+    };
+    if attr(ATTR_DERIVED).as_deref() != Some("true") {
+        return None;
+    }
+    Some(DerivedProvenance {
+        rule_id: attr(ATTR_RULE_ID).unwrap_or_default(),
+        premises: attr(ATTR_PREMISES)
+            .map(|p| p.split(';').map(str::to_string).collect())
+            .unwrap_or_default(),
+    })
+}
+
+type Bindings = HashMap<String, u32>;
+
+/// A partial match: variable bindings, premise relation ids, confidence.
+type Partial = (Bindings, Vec<u32>, f32);
+
+struct Derivation {
+    rule: usize,
+    source: u32,
+    target: u32,
+    premises: Vec<u32>,
+    confidence: f32,
+}
+
+impl RuleSet {
+    pub fn new(rules: Vec<Rule>) -> Self {
+        Self {
+            rules,
+            skipped: Vec::new(),
+        }
+    }
+
+    /// Rules from `.axi` named theory blocks and `TacitKnowledge` entities.
+    pub fn from_db(db: &PathDB) -> Self {
+        let mut out = Self::default();
+        if let Ok(meta) = MetaPlaneIndex::from_db(db) {
+            let mut blocks: Vec<_> = meta
+                .schemas
+                .values()
+                .flat_map(|s| s.named_block_constraints_by_theory.values().flatten())
+                .collect();
+            blocks.sort_by(|a, b| (&a.theory_name, a.index).cmp(&(&b.theory_name, b.index)));
+            for block in blocks {
+                if !block.body.contains("->") {
+                    continue;
+                }
+                let id = format!("{}.{}", block.theory_name, block.name);
+                out.push_parsed(&id, &rule_text(&block.body), 1.0);
+            }
+        }
+
+        if let Some(tacit) = db.find_by_type(TACIT_KNOWLEDGE_TYPE) {
+            let attr = |entity: u32, key: &str| {
+                db.entities
+                    .get_attr(entity, db.interner.id_of(key)?)
+                    .and_then(|v| db.interner.lookup(v))
+            };
+            for entity in tacit.iter() {
+                let Some(text) = attr(entity, "rule") else {
+                    continue;
+                };
+                let id = attr(entity, META_ATTR_NAME).unwrap_or_else(|| format!("tacit_{entity}"));
+                let confidence = attr(entity, "confidence")
+                    .and_then(|c| c.parse::<f32>().ok())
+                    .or_else(|| {
+                        // `.axi` imports: `tacitConfidence(tacit, value=Conf_0_95)`.
+                        let value = db.follow_one(entity, "tacitConfidence").min()?;
+                        let name = attr(value, META_ATTR_NAME)?;
+                        name.strip_prefix("Conf_")?
+                            .replacen('_', ".", 1)
+                            .parse()
+                            .ok()
+                    })
+                    .unwrap_or(1.0);
+                out.push_parsed(&id, &text, confidence);
+            }
+        }
+        out
+    }
+
+    fn push_parsed(&mut self, id: &str, text: &str, confidence: f32) {
+        match Rule::parse(id, text) {
+            Ok(rule) => self.rules.push(Rule {
+                confidence: confidence.clamp(0.0, 1.0),
+                ..rule
+            }),
+            Err(e) => self.skipped.push((id.to_string(), e)),
+        }
+    }
+
+    /// Derive to a fixpoint over the whole DB.
+    pub fn run(&self, db: &mut PathDB) -> InferenceReport {
+        self.run_since(db, 0)
+    }
+
+    /// Derive to a fixpoint, joining only against edges with id
+    /// `>= first_new_relation` in the first round (semi-naive). Unary premises
+    /// are matched against current types but only edges drive new rounds.
+    pub fn run_since(&self, db: &mut PathDB, first_new_relation: u32) -> InferenceReport {
+        let mut report = InferenceReport::default();
+        let mut delta: RoaringBitmap = (first_new_relation..db.relations.len() as u32).collect();
+        let mut full = first_new_relation == 0;
+        loop {
+            let mut found = Vec::new();
+            for (index, rule) in self.rules.iter().enumerate() {
+                let binary: Vec<usize> = (0..rule.premises.len())
+                    .filter(|&i| rule.premises[i].args.len() == 2)
+                    .collect();
+                if full && binary.is_empty() {
+                    found.extend(evaluate(db, index, rule, None));
+                }
+                for i in binary {
+                    found.extend(evaluate(db, index, rule, Some((i, &delta))));
+                }
+            }
+            report.rounds += 1;
+
+            let mut added = RoaringBitmap::new();
+            for d in found {
+                let rule = &self.rules[d.rule];
+                let rel_type = db.interner.intern(&rule.head.predicate);
+                if db.relations.has_edge(d.source, rel_type, d.target) {
+                    continue;
+                }
+                let premises = d
+                    .premises
+                    .iter()
+                    .filter_map(|&id| db.relations.get_relation(id))
+                    .map(|rel| edge_key(db, rel))
+                    .collect::<Vec<_>>()
+                    .join(";");
+                let id = db.add_relation(
+                    &rule.head.predicate,
+                    d.source,
+                    d.target,
+                    d.confidence * rule.confidence,
+                    vec![
+                        (ATTR_DERIVED, "true"),
+                        (ATTR_RULE_ID, rule.id.as_str()),
+                        (ATTR_PREMISES, premises.as_str()),
+                    ],
+                );
+                added.insert(id);
+            }
+            report.derived += added.len() as usize;
+            if added.is_empty() {
+                break;
+            }
+            delta = added;
+            full = false;
+        }
+        report
+    }
+
+    /// Remove every `source -rel-> target` edge in `edges`, every derived edge
+    /// depending on them (transitively), then re-derive what still holds.
+    pub fn retract(&self, db: &mut PathDB, edges: &[(u32, &str, u32)]) -> InferenceReport {
+        let mut removed: HashSet<String> = edges
+            .iter()
+            .map(|(s, rel, t)| format!("{s}-{rel}->{t}"))
+            .collect();
+        let retracted = db
+            .relations
+            .relations
+            .iter()
+            .filter(|rel| removed.contains(&edge_key(db, rel)))
+            .count();
+
+        let derived: Vec<(String, BTreeSet<String>)> = (0..db.relations.len() as u32)
+            .filter_map(|id| {
+                let provenance = derived_provenance(db, id)?;
+                let rel = db.relations.get_relation(id)?;
+                Some((edge_key(db, rel), provenance.premises.into_iter().collect()))
+            })
+            .collect();
+        let mut overdeleted = HashSet::new();
+        loop {
+            let before = overdeleted.len();
+            for (key, premises) in &derived {
+                if !removed.contains(key) && premises.iter().any(|p| removed.contains(p)) {
+                    overdeleted.insert(key.clone());
+                }
+            }
+            removed.extend(overdeleted.iter().cloned());
+            if overdeleted.len() == before {
+                break;
+            }
+        }
+
+        let keep: Vec<bool> = db
+            .relations
+            .relations
+            .iter()
+            .map(|rel| !removed.contains(&edge_key(db, rel)))
+            .collect();
+        let mut keep = keep.into_iter();
+        let dropped = db.retain_relations(|_| keep.next().unwrap_or(true));
+
+        let mut report = self.run(db);
+        report.retracted = retracted;
+        report.overdeleted = dropped - retracted;
+        report
+    }
+}
+
+/// Entity id of a `"quoted"` constant.
+fn constant(db: &PathDB, name: &str) -> Option<u32> {
+    let key = db.interner.id_of(META_ATTR_NAME)?;
+    let value = db.interner.id_of(name)?;
+    db.entities.entities_with_attr_value(key, value).min()
+}
+
+/// Unify `term` with `entity` under `bindings`.
+fn unify(db: &PathDB, term: &Term, entity: u32, bindings: &mut Bindings) -> bool {
+    match term {
+        Term::Const(name) => constant(db, name) == Some(entity),
+        Term::Var(v) => *bindings.entry(v.clone()).or_insert(entity) == entity,
+    }
+}
+
+fn resolve(db: &PathDB, term: &Term, bindings: &Bindings) -> Option<u32> {
+    match term {
+        Term::Const(name) => constant(db, name),
+        Term::Var(v) => bindings.get(v).copied(),
+    }
+}
+
+/// All matches of `rule`; with `delta = (i, ids)`, premise `i` only matches
+/// edges in `ids` (semi-naive).
+fn evaluate(
+    db: &PathDB,
+    index: usize,
+    rule: &Rule,
+    delta: Option<(usize, &RoaringBitmap)>,
+) -> Vec<Derivation> {
+    let mut order: Vec<usize> = (0..rule.premises.len()).collect();
+    if let Some((first, _)) = delta {
+        order.retain(|&i| i != first);
+        order.insert(0, first);
+    }
+
+    let mut partials: Vec<Partial> = vec![(Bindings::new(), Vec::new(), 1.0)];
+    for i in order {
+        let atom = &rule.premises[i];
+        let only = delta.filter(|(d, _)| *d == i).map(|(_, ids)| ids);
+        let mut next = Vec::new();
+        for (bindings, premises, confidence) in partials {
+            match atom.args.as_slice() {
+                [term] => {
+                    let Some(entities) = db.find_by_type(&atom.predicate) else {
+                        continue;
+                    };
+                    let candidates: Vec<u32> = match resolve(db, term, &bindings) {
+                        Some(e) => entities.contains(e).then_some(e).into_iter().collect(),
+                        None => entities.iter().collect(),
+                    };
+                    for entity in candidates {
+                        let mut b = bindings.clone();
+                        if unify(db, term, entity, &mut b) {
+                            next.push((b, premises.clone(), confidence));
+                        }
+                    }
+                }
+                [a, b] => {
+                    let Some(rel_type) = db.interner.id_of(&atom.predicate) else {
+                        continue;
+                    };
+                    let ids: Vec<u32> = match (only, resolve(db, a, &bindings)) {
+                        (Some(ids), _) => ids
+                            .iter()
+                            .filter(|&id| {
+                                db.relations
+                                    .get_relation(id)
+                                    .is_some_and(|r| r.rel_type == rel_type)
+                            })
+                            .collect(),
+                        (None, Some(source)) => db
+                            .relations
+                            .outgoing_relation_ids(source, rel_type)
+                            .to_vec(),
+                        (None, None) => db
+                            .relations
+                            .type_index
+                            .get(&rel_type)
+                            .map(|ids| ids.iter().collect())
+                            .unwrap_or_default(),
+                    };
+                    for id in ids {
+                        let Some(rel) = db.relations.get_relation(id) else {
+                            continue;
+                        };
+                        let mut bound = bindings.clone();
+                        if unify(db, a, rel.source, &mut bound)
+                            && unify(db, b, rel.target, &mut bound)
+                        {
+                            let mut p = premises.clone();
+                            p.push(id);
+                            next.push((bound, p, confidence * rel.confidence));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        partials = next;
+    }
+
+    partials
+        .into_iter()
+        .filter_map(|(bindings, premises, confidence)| {
+            let source = resolve(db, &rule.head.args[0], &bindings)?;
+            let target = resolve(db, &rule.head.args[1], &bindings)?;
+            Some(Derivation {
+                rule: index,
+                source,
+                target,
+                premises,
+                confidence,
+            })
+        })
+        .collect()
+}
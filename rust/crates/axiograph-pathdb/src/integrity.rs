@@ -0,0 +1,375 @@
+//! Health check: index/primary consistency verification.
+//!
+//! [`PathDB::from_bytes`] only checks that ids are *in range* (so corrupt files
+//! cannot panic queries). [`PathDB::verify_integrity`] goes further and checks
+//! that the redundant structures agree with the primary columns:
+//!
+//! - every interned id resolves, and both interner directions agree;
+//! - `type_index` lists every entity under its canonical type (virtual types
+//!   may add further memberships) and nothing but existing entities;
+//! - the forward and backward adjacency indexes each list every relation
+//!   exactly once, under its own `(endpoint, rel_type)` key;
+//! - the relation type index covers every relation under its own type;
+//! - `confidence_index` has one entry per relation, equal to the relation's
+//!   confidence;
+//! - equivalences reference existing entities.
+//!
+//! It is a full scan (linear in entities + relations + index entries), meant
+//! for load time and operator health checks rather than the query path.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{PathDB, StrId};
+
+/// Sample messages kept per check (the count is always exact).
+const MAX_SAMPLES_PER_CHECK: usize = 8;
+
+/// The consistency checks run by [`PathDB::verify_integrity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityCheck {
+    /// Interned ids resolve, and `str -> id` / `id -> str` agree.
+    Interner,
+    /// Entity type column vs `type_index` bitmaps; attribute columns.
+    EntityIndex,
+    /// Forward adjacency `(source, rel_type) -> relation ids`.
+    ForwardIndex,
+    /// Backward adjacency `(target, rel_type) -> relation ids`.
+    BackwardIndex,
+    /// Relation type index `rel_type -> relation ids`.
+    RelationTypeIndex,
+    /// `confidence_index` vs relation confidences.
+    ConfidenceIndex,
+    /// Equivalence endpoints and types.
+    Equivalences,
+}
+
+impl IntegrityCheck {
+    pub const ALL: [IntegrityCheck; 7] = [
+        IntegrityCheck::Interner,
+        IntegrityCheck::EntityIndex,
+        IntegrityCheck::ForwardIndex,
+        IntegrityCheck::BackwardIndex,
+        IntegrityCheck::RelationTypeIndex,
+        IntegrityCheck::ConfidenceIndex,
+        IntegrityCheck::Equivalences,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IntegrityCheck::Interner => "interner",
+            IntegrityCheck::EntityIndex => "entity_index",
+            IntegrityCheck::ForwardIndex => "forward_index",
+            IntegrityCheck::BackwardIndex => "backward_index",
+            IntegrityCheck::RelationTypeIndex => "relation_type_index",
+            IntegrityCheck::ConfidenceIndex => "confidence_index",
+            IntegrityCheck::Equivalences => "equivalences",
+        }
+    }
+}
+
+/// Failures of one check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityIssue {
+    pub check: IntegrityCheck,
+    /// Number of inconsistencies found.
+    pub count: usize,
+    /// The first few, human-readable.
+    pub samples: Vec<String>,
+}
+
+/// Result of [`PathDB::verify_integrity`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub entities: usize,
+    pub relations: usize,
+    pub interned_strings: usize,
+    /// One entry per failing check (empty when consistent).
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Total inconsistencies across all checks.
+    pub fn issue_count(&self) -> usize {
+        self.issues.iter().map(|i| i.count).sum()
+    }
+
+    pub fn issue(&self, check: IntegrityCheck) -> Option<&IntegrityIssue> {
+        self.issues.iter().find(|i| i.check == check)
+    }
+}
+
+impl fmt::Display for IntegrityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_ok() {
+            return write!(
+                f,
+                "ok ({} entities, {} relations)",
+                self.entities, self.relations
+            );
+        }
+        write!(f, "{} inconsistencies:", self.issue_count())?;
+        for issue in &self.issues {
+            write!(f, " {} ({})", issue.check.as_str(), issue.count)?;
+            if let Some(first) = issue.samples.first() {
+                write!(f, " e.g. {first};")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct Findings {
+    by_check: HashMap<IntegrityCheck, IntegrityIssue>,
+}
+
+impl Findings {
+    fn report(&mut self, check: IntegrityCheck, message: impl FnOnce() -> String) {
+        let issue = self
+            .by_check
+            .entry(check)
+            .or_insert_with(|| IntegrityIssue {
+                check,
+                count: 0,
+                samples: Vec::new(),
+            });
+        issue.count += 1;
+        if issue.samples.len() < MAX_SAMPLES_PER_CHECK {
+            issue.samples.push(message());
+        }
+    }
+
+    fn into_issues(mut self) -> Vec<IntegrityIssue> {
+        IntegrityCheck::ALL
+            .iter()
+            .filter_map(|check| self.by_check.remove(check))
+            .collect()
+    }
+}
+
+impl PathDB {
+    /// Check that every index agrees with the primary entity/relation columns.
+    ///
+    /// Never fails: inconsistencies are returned in the report (see
+    /// [`IntegrityReport::is_ok`]).
+    pub fn verify_integrity(&self) -> IntegrityReport {
+        let _span = tracing::info_span!(
+            "pathdb.verify_integrity",
+            entities = self.entities.len(),
+            relations = self.relations.len()
+        )
+        .entered();
+        let mut findings = Findings::default();
+        self.verify_interner(&mut findings);
+        self.verify_entity_index(&mut findings);
+        self.verify_adjacency(&mut findings);
+        self.verify_relation_type_index(&mut findings);
+        self.verify_confidence_index(&mut findings);
+        self.verify_equivalences(&mut findings);
+        IntegrityReport {
+            entities: self.entities.len(),
+            relations: self.relations.len(),
+            interned_strings: self.interner.reverse_len(),
+            issues: findings.into_issues(),
+        }
+    }
+
+    fn verify_interner(&self, findings: &mut Findings) {
+        let check = IntegrityCheck::Interner;
+        let next_id = self.interner.len() as u32;
+        self.interner.for_each_entry(|id, s| {
+            if id.0 >= next_id {
+                findings.report(check, || format!("id {} >= next id {next_id}", id.0));
+            }
+            if self.interner.id_of(s) != Some(id) {
+                findings.report(check, || format!("id {} -> {s:?} does not map back", id.0));
+            }
+        });
+        let (forward, reverse) = (self.interner.forward_len(), self.interner.reverse_len());
+        if forward != reverse {
+            findings.report(check, || format!("{forward} strings but {reverse} ids"));
+        }
+
+        let mut unresolved = |what: &str, id: StrId| {
+            if !self.interner.contains_id(id) {
+                findings.report(check, || format!("{what} id {} does not resolve", id.0));
+            }
+        };
+        for &type_id in &self.entities.types {
+            unresolved("entity type", type_id);
+        }
+        for (&key, col) in &self.entities.attrs {
+            unresolved("attribute key", key);
+            for &value in col.values() {
+                unresolved("attribute value", value);
+            }
+        }
+        for rel in &self.relations.relations {
+            unresolved("relation type", rel.rel_type);
+            for &(k, v) in &rel.attrs {
+                unresolved("relation attribute key", k);
+                unresolved("relation attribute value", v);
+            }
+        }
+        for equivs in self.equivalences.values() {
+            for &(_, equiv_type) in equivs {
+                unresolved("equivalence type", equiv_type);
+            }
+        }
+    }
+
+    fn verify_entity_index(&self, findings: &mut Findings) {
+        let check = IntegrityCheck::EntityIndex;
+        let n = self.entities.len();
+        if self.entities.types.len() != n {
+            findings.report(check, || {
+                format!(
+                    "type column has {} rows for {n} entities",
+                    self.entities.types.len()
+                )
+            });
+        }
+        for (id, &type_id) in self.entities.types.iter().enumerate().take(n) {
+            let indexed = self
+                .entities
+                .type_index
+                .get(&type_id)
+                .is_some_and(|ids| ids.contains(id as u32));
+            if !indexed {
+                findings.report(check, || {
+                    format!("entity {id} missing from type_index[{}]", type_id.0)
+                });
+            }
+        }
+        for (&type_id, ids) in &self.entities.type_index {
+            if let Some(max) = ids.max().filter(|&m| m as usize >= n) {
+                findings.report(check, || {
+                    format!("type_index[{}] lists missing entity {max}", type_id.0)
+                });
+            }
+        }
+        for (&key, col) in &self.entities.attrs {
+            for &entity in col.keys().filter(|&&e| e as usize >= n) {
+                findings.report(check, || {
+                    format!("attribute {} set on missing entity {entity}", key.0)
+                });
+            }
+        }
+    }
+
+    fn verify_adjacency(&self, findings: &mut Findings) {
+        let relations = &self.relations.relations;
+        for (check, index) in [
+            (IntegrityCheck::ForwardIndex, &self.relations.forward_index),
+            (
+                IntegrityCheck::BackwardIndex,
+                &self.relations.backward_index,
+            ),
+        ] {
+            let forward = check == IntegrityCheck::ForwardIndex;
+            let mut seen = vec![0u32; relations.len()];
+            for ((entity, rel_type), ids) in index.iter() {
+                for &id in ids {
+                    let Some(rel) = relations.get(id as usize) else {
+                        findings.report(check, || format!("missing relation {id}"));
+                        continue;
+                    };
+                    seen[id as usize] += 1;
+                    let endpoint = if forward { rel.source } else { rel.target };
+                    if endpoint != entity || rel.rel_type != rel_type {
+                        findings.report(check, || {
+                            format!("relation {id} listed under ({entity}, {})", rel_type.0)
+                        });
+                    }
+                }
+            }
+            for (id, &times) in seen.iter().enumerate().filter(|(_, &t)| t != 1) {
+                findings.report(check, || format!("relation {id} listed {times} times"));
+            }
+            for (id, rel) in relations.iter().enumerate() {
+                let endpoint = if forward { rel.source } else { rel.target };
+                if endpoint as usize >= self.entities.len() {
+                    findings.report(check, || {
+                        format!("relation {id} endpoint {endpoint} is not an entity")
+                    });
+                }
+            }
+        }
+    }
+
+    fn verify_relation_type_index(&self, findings: &mut Findings) {
+        let check = IntegrityCheck::RelationTypeIndex;
+        let relations = &self.relations.relations;
+        for (id, rel) in relations.iter().enumerate() {
+            let indexed = self
+                .relations
+                .type_index
+                .get(&rel.rel_type)
+                .is_some_and(|ids| ids.contains(id as u32));
+            if !indexed {
+                findings.report(check, || {
+                    format!("relation {id} missing from type_index[{}]", rel.rel_type.0)
+                });
+            }
+        }
+        for (&rel_type, ids) in &self.relations.type_index {
+            for id in ids.iter() {
+                if relations.get(id as usize).map(|r| r.rel_type) != Some(rel_type) {
+                    findings.report(check, || {
+                        format!(
+                            "type_index[{}] lists relation {id} of another type",
+                            rel_type.0
+                        )
+                    });
+                }
+            }
+        }
+    }
+
+    fn verify_confidence_index(&self, findings: &mut Findings) {
+        let check = IntegrityCheck::ConfidenceIndex;
+        let relations = &self.relations.relations;
+        if self.confidence_index.len() != relations.len() {
+            findings.report(check, || {
+                format!(
+                    "{} entries for {} relations",
+                    self.confidence_index.len(),
+                    relations.len()
+                )
+            });
+        }
+        for (id, (rel, &indexed)) in relations.iter().zip(&self.confidence_index).enumerate() {
+            if rel.confidence.to_bits() != indexed.to_bits() {
+                findings.report(check, || {
+                    format!(
+                        "relation {id}: index {indexed} != relation {}",
+                        rel.confidence
+                    )
+                });
+            }
+        }
+    }
+
+    fn verify_equivalences(&self, findings: &mut Findings) {
+        let check = IntegrityCheck::Equivalences;
+        let n = self.entities.len();
+        for (&entity, equivs) in &self.equivalences {
+            if entity as usize >= n {
+                findings.report(check, || format!("equivalence on missing entity {entity}"));
+            }
+            for &(other, _) in equivs.iter().filter(|(e, _)| *e as usize >= n) {
+                findings.report(check, || {
+                    format!("entity {entity} equivalent to missing entity {other}")
+                });
+            }
+        }
+    }
+}
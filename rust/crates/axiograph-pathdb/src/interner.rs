@@ -0,0 +1,354 @@
+//! Sharded string interner with inline small strings.
+//!
+//! The interner used to be a pair of `DashMap`s (`String -> StrId` and
+//! `StrId -> String`), which contended heavily under parallel ingest and paid
+//! two heap allocations per string. It is now:
+//!
+//! - **sharded**: `str -> id` lives in [`SHARDS`] hash-partitioned tables, and
+//!   `id -> str` in [`SHARDS`] id-partitioned vectors, each behind its own
+//!   `RwLock`. Interning locks one table of each kind; lookups take read
+//!   locks only.
+//! - **inline for short strings**: strings of up to [`INLINE_CAP`] bytes
+//!   (most attribute keys, enum values, small numbers) are stored in place,
+//!   without a heap allocation. IDs stay dense `u32`s: snapshots,
+//!   `PathDBExportV1` string tables and certificates index strings by ID, so
+//!   the inlining happens in the entry, not in the ID.
+//! - **freezable**: [`StringInterner::freeze`] moves every string into a
+//!   plain, lock-free table. Query-only deployments (read replicas,
+//!   `db serve` without a write master) freeze once after loading and never
+//!   touch a lock for known strings. Interning a new string afterwards still
+//!   works: it lands in the sharded tables on top of the frozen base.
+//!
+//! Serialization is unchanged (strings in ID order), so frozen and sharded
+//! interners produce identical snapshot bytes.
+
+use std::borrow::Cow;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use ahash::{AHashMap, RandomState};
+use bincode::Options as _;
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::{bounded_bincode_options, StrId};
+
+/// Number of shards (a power of two).
+pub const SHARDS: usize = 16;
+
+/// Longest string stored inline.
+pub const INLINE_CAP: usize = 12;
+
+/// An interned string: inline up to [`INLINE_CAP`] bytes, boxed otherwise.
+#[derive(Clone)]
+enum SmallStr {
+    Inline { len: u8, bytes: [u8; INLINE_CAP] },
+    Heap(Box<str>),
+}
+
+impl SmallStr {
+    fn new(s: &str) -> Self {
+        if s.len() <= INLINE_CAP {
+            let mut bytes = [0; INLINE_CAP];
+            bytes[..s.len()].copy_from_slice(s.as_bytes());
+            Self::Inline {
+                len: s.len() as u8,
+                bytes,
+            }
+        } else {
+            Self::Heap(s.into())
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        match self {
+            // Built from a `&str` and never mutated.
+            Self::Inline { len, bytes } => {
+                std::str::from_utf8(&bytes[..*len as usize]).unwrap_or_default()
+            }
+            Self::Heap(s) => s,
+        }
+    }
+
+    fn is_inline(&self) -> bool {
+        matches!(self, Self::Inline { .. })
+    }
+}
+
+impl PartialEq for SmallStr {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for SmallStr {}
+
+// Must agree with `str`'s `Hash` for `Borrow<str>` lookups.
+impl Hash for SmallStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl std::borrow::Borrow<str> for SmallStr {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// Lock-free base table built by [`StringInterner::freeze`]: IDs
+/// `0..strings.len()`.
+#[derive(Default)]
+struct FrozenTable {
+    by_str: AHashMap<SmallStr, StrId>,
+    strings: Vec<SmallStr>,
+}
+
+/// Memory/layout summary of an interner.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct InternerStats {
+    pub strings: usize,
+    /// Strings stored inline (no heap allocation).
+    pub inline: usize,
+    /// Heap bytes held by the longer strings (one copy).
+    pub heap_bytes: usize,
+    /// Strings in the frozen (lock-free) table.
+    pub frozen: usize,
+}
+
+/// String interner: maps strings to compact IDs
+pub struct StringInterner {
+    frozen: FrozenTable,
+    /// `str -> id` for strings interned since the last freeze, by hash.
+    by_str: Box<[RwLock<AHashMap<SmallStr, StrId>>]>,
+    /// `id -> str` for the same strings: ID `base + i` is slot `i / SHARDS`
+    /// of shard `i % SHARDS`, where `base` is the frozen table's length.
+    by_id: Box<[RwLock<Vec<Option<SmallStr>>>]>,
+    hasher: RandomState,
+    /// Next available ID
+    next_id: AtomicU32,
+}
+
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl StringInterner {
+    pub fn new() -> Self {
+        Self {
+            frozen: FrozenTable::default(),
+            by_str: (0..SHARDS).map(|_| RwLock::default()).collect(),
+            by_id: (0..SHARDS).map(|_| RwLock::default()).collect(),
+            hasher: RandomState::new(),
+            next_id: AtomicU32::new(0),
+        }
+    }
+
+    fn str_shard(&self, s: &str) -> &RwLock<AHashMap<SmallStr, StrId>> {
+        &self.by_str[self.hasher.hash_one(s) as usize % SHARDS]
+    }
+
+    /// `(shard, slot)` of a non-frozen ID.
+    fn id_slot(&self, id: StrId) -> Option<(usize, usize)> {
+        let offset = (id.raw() as usize).checked_sub(self.frozen.strings.len())?;
+        Some((offset % SHARDS, offset / SHARDS))
+    }
+
+    /// Intern a string, returning its ID
+    pub fn intern(&self, s: &str) -> StrId {
+        if let Some(&id) = self.frozen.by_str.get(s) {
+            return id;
+        }
+        let shard = self.str_shard(s);
+        if let Some(&id) = read(shard).get(s) {
+            return id;
+        }
+
+        let mut by_str = write(shard);
+        if let Some(&id) = by_str.get(s) {
+            return id;
+        }
+        let id = StrId::new(self.next_id.fetch_add(1, Ordering::SeqCst));
+        let entry = SmallStr::new(s);
+        // Publish the reverse entry before the forward one is visible, so any
+        // ID handed out can be looked up.
+        if let Some((shard, slot)) = self.id_slot(id) {
+            let mut by_id = write(&self.by_id[shard]);
+            if by_id.len() <= slot {
+                by_id.resize(slot + 1, None);
+            }
+            by_id[slot] = Some(entry.clone());
+        }
+        by_str.insert(entry, id);
+        id
+    }
+
+    /// Look up an existing ID for a string without inserting.
+    pub fn id_of(&self, s: &str) -> Option<StrId> {
+        if let Some(&id) = self.frozen.by_str.get(s) {
+            return Some(id);
+        }
+        read(self.str_shard(s)).get(s).copied()
+    }
+
+    /// Whether `id` was issued by this interner.
+    pub fn contains_id(&self, id: StrId) -> bool {
+        self.with_str(id, |_| ()).is_some()
+    }
+
+    /// Look up string by ID
+    pub fn lookup(&self, id: StrId) -> Option<String> {
+        self.with_str(id, str::to_string)
+    }
+
+    /// Look up string by ID, borrowing it when it is in the frozen table
+    /// (no allocation) and copying it out of the sharded tables otherwise.
+    pub fn lookup_ref(&self, id: StrId) -> Option<Cow<'_, str>> {
+        if let Some(s) = self.frozen.strings.get(id.raw() as usize) {
+            return Some(Cow::Borrowed(s.as_str()));
+        }
+        self.with_str(id, |s| Cow::Owned(s.to_string()))
+    }
+
+    /// Apply `f` to the string of `id` without copying it out.
+    pub fn with_str<R>(&self, id: StrId, f: impl FnOnce(&str) -> R) -> Option<R> {
+        if let Some(s) = self.frozen.strings.get(id.raw() as usize) {
+            return Some(f(s.as_str()));
+        }
+        let (shard, slot) = self.id_slot(id)?;
+        let by_id = read(&self.by_id[shard]);
+        by_id.get(slot)?.as_ref().map(|s| f(s.as_str()))
+    }
+
+    /// Number of interned strings.
+    pub fn len(&self) -> usize {
+        self.next_id.load(Ordering::SeqCst) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Move every string into the lock-free frozen table. Later lookups of
+    /// these strings take no locks; later interns go to the sharded tables.
+    pub fn freeze(&mut self) {
+        let len = self.len();
+        let base = self.frozen.strings.len();
+        if len == base {
+            return;
+        }
+        let mut shards: Vec<Vec<Option<SmallStr>>> = self
+            .by_id
+            .iter_mut()
+            .map(|shard| std::mem::take(shard.get_mut().unwrap_or_else(|p| p.into_inner())))
+            .collect();
+        for shard in self.by_str.iter_mut() {
+            shard.get_mut().unwrap_or_else(|p| p.into_inner()).clear();
+        }
+        self.frozen.strings.reserve(len - base);
+        self.frozen.by_str.reserve(len - base);
+        for offset in 0..len - base {
+            let entry = shards[offset % SHARDS]
+                .get_mut(offset / SHARDS)
+                .and_then(Option::take)
+                // `&mut self`: every issued ID has been published.
+                .unwrap_or_else(|| SmallStr::new(""));
+            let id = StrId::new((base + offset) as u32);
+            self.frozen.by_str.entry(entry.clone()).or_insert(id);
+            self.frozen.strings.push(entry);
+        }
+    }
+
+    /// Whether every string is in the frozen table.
+    pub fn is_frozen(&self) -> bool {
+        !self.is_empty() && self.frozen.strings.len() == self.len()
+    }
+
+    pub fn stats(&self) -> InternerStats {
+        let mut stats = InternerStats {
+            strings: self.len(),
+            frozen: self.frozen.strings.len(),
+            ..InternerStats::default()
+        };
+        let mut count = |s: &SmallStr| {
+            if s.is_inline() {
+                stats.inline += 1;
+            } else {
+                stats.heap_bytes += s.as_str().len();
+            }
+        };
+        self.frozen.strings.iter().for_each(&mut count);
+        for shard in self.by_id.iter() {
+            read(shard).iter().flatten().for_each(&mut count);
+        }
+        stats
+    }
+
+    /// Every `(id, string)` entry, frozen table first (for integrity checks).
+    pub(crate) fn for_each_entry(&self, mut f: impl FnMut(StrId, &str)) {
+        for (i, s) in self.frozen.strings.iter().enumerate() {
+            f(StrId::new(i as u32), s.as_str());
+        }
+        let base = self.frozen.strings.len();
+        for (shard, entries) in self.by_id.iter().enumerate() {
+            for (slot, s) in read(entries).iter().enumerate() {
+                if let Some(s) = s {
+                    f(
+                        StrId::new((base + slot * SHARDS + shard) as u32),
+                        s.as_str(),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Number of `str -> id` entries.
+    pub(crate) fn forward_len(&self) -> usize {
+        self.frozen.by_str.len()
+            + self
+                .by_str
+                .iter()
+                .map(|shard| read(shard).len())
+                .sum::<usize>()
+    }
+
+    /// Number of `id -> str` entries.
+    pub(crate) fn reverse_len(&self) -> usize {
+        self.frozen.strings.len()
+            + self
+                .by_id
+                .iter()
+                .map(|shard| read(shard).iter().flatten().count())
+                .sum::<usize>()
+    }
+
+    /// Serialize to bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let strings: Vec<String> = (0..self.next_id.load(Ordering::SeqCst))
+            .filter_map(|i| self.lookup(StrId::new(i)))
+            .collect();
+        bincode::serialize(&strings).unwrap_or_default()
+    }
+
+    /// Deserialize from bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let strings: Vec<String> = bounded_bincode_options(bytes.len()).deserialize(bytes)?;
+        let interner = Self::new();
+        for s in strings {
+            interner.intern(&s);
+        }
+        Ok(interner)
+    }
+}
+
+impl Default for StringInterner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,325 @@
+//! Relation inverses.
+//!
+//! Many relations come in pairs (`proto_service_has_rpc` / `rpc_in_service`).
+//! Registering a pair with [`PathDB::register_inverse`] lets traversals use
+//! either name without storing both edges. A step along `name` follows the
+//! stored `name` edges forward *and* the stored edges of its inverse backward.
+//! This covers `follow_one`, `follow_path` and `SelectRelated`/`FollowPath`
+//! queries.
+//!
+//! Paths with an inverse step skip the path index and hub limiting and are
+//! walked edge by edge. Confidence and context filters still apply.
+//!
+//! Registrations live in the meta plane (`AxiMetaRelationInverse` entities),
+//! so they survive snapshots. [`PathDB::materialize_inverses`] rewrites the
+//! edges of every registered pair into one direction (or both) before an
+//! export.
+
+use std::collections::HashMap;
+
+use roaring::RoaringBitmap;
+
+use crate::axi_meta::{
+    ATTR_INVERSE_OF, ATTR_INVERSE_RELATION, META_ATTR_NAME, META_TYPE_RELATION_INVERSE,
+};
+use crate::error::Result;
+use crate::{edge_visible, BudgetTracker, ContextScope, PathDB, PathDbError, StrId};
+
+/// Registered `relation <-> inverse` pairs.
+#[derive(Debug, Clone, Default)]
+pub struct InverseRegistry {
+    /// `(relation, inverse)` in registration order.
+    pairs: Vec<(StrId, StrId)>,
+    /// Both directions of every pair.
+    inverse: HashMap<StrId, StrId>,
+}
+
+impl InverseRegistry {
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// The registered inverse of `rel_type` (either side of a pair).
+    pub fn inverse(&self, rel_type: StrId) -> Option<StrId> {
+        self.inverse.get(&rel_type).copied()
+    }
+
+    /// `(relation, inverse)` pairs in registration order.
+    pub fn pairs(&self) -> &[(StrId, StrId)] {
+        &self.pairs
+    }
+
+    fn insert(&mut self, relation: StrId, inverse: StrId) {
+        self.pairs.push((relation, inverse));
+        self.inverse.insert(relation, inverse);
+        self.inverse.insert(inverse, relation);
+    }
+}
+
+/// Which direction `materialize_inverses` keeps for each registered pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InverseDirection {
+    /// Only `relation` edges (inverse edges are flipped).
+    Relation,
+    /// Only `inverse` edges.
+    Inverse,
+    /// Both: every edge gets its mirror.
+    Both,
+}
+
+impl std::str::FromStr for InverseDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "relation" | "forward" => Ok(InverseDirection::Relation),
+            "inverse" | "backward" => Ok(InverseDirection::Inverse),
+            "both" => Ok(InverseDirection::Both),
+            other => Err(format!(
+                "unknown inverse direction `{other}` (expected relation, inverse or both)"
+            )),
+        }
+    }
+}
+
+/// One traversal step: stored edges to follow forward and backward.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct InverseStep {
+    forward: StrId,
+    backward: Option<StrId>,
+}
+
+impl PathDB {
+    /// Register `inverse` as the inverse of `relation` (idempotent).
+    ///
+    /// A relation may be its own inverse (symmetric relations). Pairing a
+    /// name that already has a different inverse is an error.
+    pub fn register_inverse(&mut self, relation: &str, inverse: &str) -> Result<()> {
+        let rel = self.interner.intern(relation);
+        let inv = self.interner.intern(inverse);
+        for (a, b) in [(rel, inv), (inv, rel)] {
+            if let Some(existing) = self.inverses.inverse(a) {
+                if existing == b {
+                    return Ok(());
+                }
+                return Err(PathDbError::InverseConflict {
+                    relation: self.interner.lookup(a).unwrap_or_default(),
+                    existing: self.interner.lookup(existing).unwrap_or_default(),
+                });
+            }
+        }
+        self.inverses.insert(rel, inv);
+        self.add_entity(
+            META_TYPE_RELATION_INVERSE,
+            vec![
+                (META_ATTR_NAME, &format!("{relation}~{inverse}")),
+                (ATTR_INVERSE_RELATION, relation),
+                (ATTR_INVERSE_OF, inverse),
+            ],
+        );
+        Ok(())
+    }
+
+    /// The registered inverse of `rel_type`, if any.
+    pub fn inverse_of(&self, rel_type: &str) -> Option<String> {
+        let id = self.interner.id_of(rel_type)?;
+        self.interner.lookup(self.inverses.inverse(id)?)
+    }
+
+    pub fn inverse_registry(&self) -> &InverseRegistry {
+        &self.inverses
+    }
+
+    /// Sources of stored `inverse(rel_type)` edges into `entity`, i.e. the
+    /// extra targets of a `rel_type` step from `entity`.
+    pub(crate) fn inverse_targets(
+        &self,
+        entity: u32,
+        rel_type: &str,
+        min_confidence: Option<f32>,
+        context: Option<&ContextScope>,
+    ) -> RoaringBitmap {
+        match self.interner.id_of(rel_type) {
+            Some(id) => self.inverse_targets_by_id(entity, id, min_confidence, context),
+            None => RoaringBitmap::new(),
+        }
+    }
+
+    /// [`Self::inverse_targets`] for an already-resolved relation type.
+    pub(crate) fn inverse_targets_by_id(
+        &self,
+        entity: u32,
+        rel_type: StrId,
+        min_confidence: Option<f32>,
+        context: Option<&ContextScope>,
+    ) -> RoaringBitmap {
+        let Some(inv) = self.inverses.inverse(rel_type) else {
+            return RoaringBitmap::new();
+        };
+        self.relations
+            .incoming(entity, inv)
+            .into_iter()
+            .filter(|rel| edge_visible(rel, min_confidence, context))
+            .map(|rel| rel.source)
+            .collect()
+    }
+
+    /// Steps for `path` if any of them has a registered inverse.
+    pub(crate) fn inverse_steps(&self, path: &[StrId]) -> Option<Vec<InverseStep>> {
+        if self.inverses.is_empty() {
+            return None;
+        }
+        let steps: Vec<InverseStep> = path
+            .iter()
+            .map(|&id| InverseStep {
+                forward: id,
+                backward: self.inverses.inverse(id),
+            })
+            .collect();
+        steps.iter().any(|s| s.backward.is_some()).then_some(steps)
+    }
+
+    /// Edge-by-edge walk of `steps` (see the module docs).
+    pub(crate) fn follow_inverse_path(
+        &self,
+        start: u32,
+        steps: &[InverseStep],
+        min_confidence: Option<f32>,
+        context: Option<&ContextScope>,
+        tracker: &mut BudgetTracker,
+    ) -> RoaringBitmap {
+        let mut current = RoaringBitmap::new();
+        current.insert(start);
+        for (hop, step) in steps.iter().enumerate() {
+            let last_hop = hop + 1 == steps.len();
+            let mut next = RoaringBitmap::new();
+            for entity in current.iter() {
+                if tracker.visit().is_err() {
+                    return if last_hop { next } else { RoaringBitmap::new() };
+                }
+                next.extend(
+                    self.relations
+                        .outgoing(entity, step.forward)
+                        .into_iter()
+                        .filter(|r| edge_visible(r, min_confidence, context))
+                        .map(|r| r.target),
+                );
+                if let Some(inv) = step.backward {
+                    next.extend(
+                        self.relations
+                            .incoming(entity, inv)
+                            .into_iter()
+                            .filter(|r| edge_visible(r, min_confidence, context))
+                            .map(|r| r.source),
+                    );
+                }
+            }
+            current = next;
+            if current.is_empty() {
+                break;
+            }
+        }
+        current
+    }
+
+    /// Rewrite the edges of every registered pair into `direction`; returns
+    /// how many edges were added. Mirrors keep the confidence and attributes
+    /// of the edge they mirror, and edges that already have a mirror are not
+    /// duplicated. Relation ids are compacted when edges are dropped.
+    pub fn materialize_inverses(&mut self, direction: InverseDirection) -> usize {
+        let mut mirrors = Vec::new();
+        let mut dropped: Vec<StrId> = Vec::new();
+        for &(rel, inv) in self.inverses.pairs() {
+            if rel == inv {
+                // Symmetric: only `Both` changes anything.
+                if direction == InverseDirection::Both {
+                    mirrors.extend(self.missing_mirrors(rel, rel));
+                }
+                continue;
+            }
+            match direction {
+                InverseDirection::Relation => {
+                    mirrors.extend(self.missing_mirrors(inv, rel));
+                    dropped.push(inv);
+                }
+                InverseDirection::Inverse => {
+                    mirrors.extend(self.missing_mirrors(rel, inv));
+                    dropped.push(rel);
+                }
+                InverseDirection::Both => {
+                    mirrors.extend(self.missing_mirrors(rel, inv));
+                    mirrors.extend(self.missing_mirrors(inv, rel));
+                }
+            }
+        }
+        if !dropped.is_empty() {
+            self.retain_relations(|r| !dropped.contains(&r.rel_type));
+        }
+        let added = mirrors.len();
+        for (rel_type, source, target, confidence, attrs) in mirrors {
+            let attrs: Vec<(&str, &str)> = attrs
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect();
+            self.add_relation(&rel_type, source, target, confidence, attrs);
+        }
+        added
+    }
+
+    /// `to` edges mirroring stored `from` edges that have no `to` mirror yet.
+    #[allow(clippy::type_complexity)]
+    fn missing_mirrors(
+        &self,
+        from: StrId,
+        to: StrId,
+    ) -> Vec<(String, u32, u32, f32, Vec<(String, String)>)> {
+        let Some(ids) = self.relations.type_index.get(&from) else {
+            return Vec::new();
+        };
+        let Some(to_name) = self.interner.lookup(to) else {
+            return Vec::new();
+        };
+        let mut out: Vec<(String, u32, u32, f32, Vec<(String, String)>)> = Vec::new();
+        for id in ids {
+            let Some(rel) = self.relations.get_relation(id) else {
+                continue;
+            };
+            let (source, target) = (rel.target, rel.source);
+            let mirrored = self.relations.has_edge(source, to, target)
+                || out.iter().any(|m| m.1 == source && m.2 == target);
+            if mirrored {
+                continue;
+            }
+            let attrs = rel
+                .attrs
+                .iter()
+                .filter_map(|(k, v)| Some((self.interner.lookup(*k)?, self.interner.lookup(*v)?)))
+                .collect();
+            out.push((to_name.clone(), source, target, rel.confidence, attrs));
+        }
+        out
+    }
+
+    /// Re-register every meta-plane inverse declaration (after loading a
+    /// snapshot).
+    pub(crate) fn rebuild_inverses(&mut self) {
+        let Some(decls) = self.find_by_type(META_TYPE_RELATION_INVERSE).cloned() else {
+            return;
+        };
+        let (Some(rel_key), Some(inv_key)) = (
+            self.interner.id_of(ATTR_INVERSE_RELATION),
+            self.interner.id_of(ATTR_INVERSE_OF),
+        ) else {
+            return;
+        };
+        for decl in &decls {
+            let rel = self.entities.get_attr(decl, rel_key);
+            let inv = self.entities.get_attr(decl, inv_key);
+            if let (Some(rel), Some(inv)) = (rel, inv) {
+                if self.inverses.inverse(rel).is_none() && self.inverses.inverse(inv).is_none() {
+                    self.inverses.insert(rel, inv);
+                }
+            }
+        }
+    }
+}
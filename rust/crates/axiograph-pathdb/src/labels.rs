@@ -0,0 +1,160 @@
+//! Display labels for entities.
+//!
+//! Sources store human-readable names under different attribute keys: `.axi`
+//! modules and most ingesters use `name`, RDF/OWL brings `label`
+//! (`rdfs:label`) and `prefLabel` (`skos:prefLabel`), and some exports
+//! flatten the prefix into `rdfs_label`. A [`LabelPolicy`] picks one display
+//! label per entity:
+//!
+//! - **Key precedence**: the first key in [`LabelPolicy::keys`] with a
+//!   non-blank value wins.
+//! - **Language preference**: within the winning key, a value tagged with
+//!   one of [`LabelPolicy::languages`] (in order; `en` also matches `en-GB`)
+//!   is preferred, then an untagged value, then the first tagged value.
+//!   Tagged values come from the key's language columns (see
+//!   [`crate::lang_attrs`]) or, in older snapshots, from scalar values
+//!   written as `lexical@lang` / `lexical^^datatype` (newline-separated when
+//!   there are several); suffixes are stripped from the result.
+//!
+//! Each `PathDB` carries a policy ([`PathDB::label_policy`], the default
+//! unless replaced); [`PathDB::get_entity`] resolves
+//! [`EntityView::label`](crate::EntityView::label) with it, and
+//! [`PathDB::display_label`] falls back to `Type#id` for unlabeled entities.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::lang_attrs::is_language_tag;
+use crate::{PathDB, StrId};
+
+/// Which attributes name an entity, and which languages to prefer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelPolicy {
+    /// Attribute keys, highest precedence first.
+    pub keys: Vec<String>,
+    /// Preferred language tags, most preferred first.
+    pub languages: Vec<String>,
+}
+
+impl Default for LabelPolicy {
+    fn default() -> Self {
+        Self {
+            keys: ["name", "label", "prefLabel", "rdfs_label"]
+                .map(String::from)
+                .to_vec(),
+            languages: vec!["en".to_string()],
+        }
+    }
+}
+
+impl LabelPolicy {
+    /// Label from already-resolved attributes (e.g. an `EntityView`).
+    pub fn resolve(&self, attrs: &BTreeMap<String, String>) -> Option<String> {
+        self.resolve_with(|key| attrs.get(key).cloned())
+    }
+
+    /// Label from an attribute getter.
+    pub fn resolve_with(&self, mut attr: impl FnMut(&str) -> Option<String>) -> Option<String> {
+        self.keys
+            .iter()
+            .find_map(|key| attr(key).and_then(|raw| self.pick_value(&raw)))
+    }
+
+    /// Best value of one (possibly multi-valued, language-tagged) attribute.
+    fn pick_value(&self, raw: &str) -> Option<String> {
+        let values: Vec<(&str, Option<&str>)> = raw
+            .lines()
+            .map(parse_literal)
+            .filter(|(text, _)| !text.trim().is_empty())
+            .collect();
+        let preferred = self.languages.iter().find_map(|wanted| {
+            values
+                .iter()
+                .find(|(_, lang)| lang.is_some_and(|lang| language_matches(lang, wanted)))
+        });
+        preferred
+            .or_else(|| values.iter().find(|(_, lang)| lang.is_none()))
+            .or_else(|| values.first())
+            .map(|(text, _)| text.trim().to_string())
+    }
+}
+
+/// Split `lexical@lang` / `lexical^^datatype` into text and language tag.
+/// Anything that does not look like a tag (e.g. the domain of an email
+/// address) stays part of the text.
+fn parse_literal(value: &str) -> (&str, Option<&str>) {
+    let value = match value.rsplit_once("^^") {
+        Some((lexical, datatype)) if !datatype.is_empty() && !datatype.contains(' ') => lexical,
+        _ => value,
+    };
+    match value.rsplit_once('@') {
+        Some((lexical, tag)) if is_language_tag(tag) => (lexical, Some(tag)),
+        _ => (value, None),
+    }
+}
+
+fn language_matches(tag: &str, wanted: &str) -> bool {
+    match tag.get(..wanted.len()) {
+        Some(prefix) if prefix.eq_ignore_ascii_case(wanted) => {
+            matches!(tag.as_bytes().get(wanted.len()), None | Some(b'-'))
+        }
+        _ => false,
+    }
+}
+
+impl PathDB {
+    pub fn label_policy(&self) -> &LabelPolicy {
+        &self.label_policy
+    }
+
+    pub fn set_label_policy(&mut self, policy: LabelPolicy) {
+        self.label_policy = policy;
+    }
+
+    /// `entity`'s label under the DB's policy, if it has one.
+    pub fn entity_label(&self, entity: u32) -> Option<String> {
+        let policy = &self.label_policy;
+        policy.keys.iter().find_map(|key| {
+            let key = self.interner.id_of(key)?;
+            let tagged: Vec<(String, String)> = self
+                .entities
+                .get_lang_values(entity, key)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|&(lang, value)| self.lang_pair(lang, value))
+                .filter(|(_, value)| !value.trim().is_empty())
+                .collect();
+            let preferred = policy.languages.iter().find_map(|wanted| {
+                tagged
+                    .iter()
+                    .find(|(lang, _)| language_matches(lang, wanted))
+            });
+            if let Some((_, value)) = preferred {
+                return Some(value.trim().to_string());
+            }
+            self.entities
+                .get_attr(entity, key)
+                .and_then(|value| self.interner.lookup(value))
+                .and_then(|raw| policy.pick_value(&raw))
+                .or_else(|| tagged.first().map(|(_, value)| value.trim().to_string()))
+        })
+    }
+
+    fn lang_pair(&self, lang: StrId, value: StrId) -> Option<(String, String)> {
+        Some((self.interner.lookup(lang)?, self.interner.lookup(value)?))
+    }
+
+    /// [`Self::entity_label`], or `Type#id` (`#id` for unknown entities).
+    pub fn display_label(&self, entity: u32) -> String {
+        if let Some(label) = self.entity_label(entity) {
+            return label;
+        }
+        let ty = self
+            .entities
+            .get_type(entity)
+            .and_then(|t| self.interner.lookup(t))
+            .unwrap_or_default();
+        format!("{ty}#{entity}")
+    }
+}
@@ -0,0 +1,215 @@
+//! Language-tagged string attributes.
+//!
+//! RDF literals such as `"Alice"@en` carry a language tag. Ordinary
+//! attributes hold one untagged string per `(entity, key)`, so the tag used
+//! to end up as a `@en` suffix on the value. Language-tagged attributes keep
+//! one value per `(entity, key, language)` instead, in their own columns next
+//! to the scalar ones (the untagged value, if any, stays the scalar
+//! attribute):
+//!
+//! - [`PathDB::set_attr_lang`] / [`PathDB::attr_langs`] store and list values;
+//! - [`PathDB::get_attr_lang`] and [`PathDB::get_attr_lang_chain`] look one up
+//!   through a fallback chain: each requested language, then its
+//!   less specific forms (`en-GB` → `en`, BCP 47 lookup), then the untagged
+//!   scalar attribute.
+//!
+//! Tags are compared case-insensitively and stored lowercased. The columns
+//! are stored in an optional trailing `.axpd` section (omitted when there are
+//! none) and as `entity_lang_attribute` rows in `PathDBExportV1`.
+//!
+//! Proposals carry tagged values under `key@lang` attribute keys (see
+//! [`split_lang_key`]); the proposals importer stores them here.
+
+use crate::error::Result;
+use crate::{AttrColumn, EntityStore, PathDB, PathDbError, StrId};
+
+/// Serialized language columns: `(key, [(entity, [(lang, value)])])`, sorted.
+pub(crate) type LangSection = Vec<(StrId, Vec<(u32, Vec<(StrId, StrId)>)>)>;
+
+/// A value found by [`PathDB::get_attr_lang_chain`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LangValue {
+    pub value: String,
+    /// The tag of the matched value (`None`: the untagged scalar attribute).
+    pub lang: Option<String>,
+}
+
+/// Whether `tag` has BCP 47 shape: `xx` or `xx-YY-…`, with alphanumeric
+/// subtags of 1–8 characters and an alphabetic primary subtag.
+pub fn is_language_tag(tag: &str) -> bool {
+    let mut subtags = tag.split('-');
+    let primary = subtags.next().unwrap_or_default();
+    (1..=8).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags
+            .all(|s| (1..=8).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// Split a `key@lang` proposal attribute key; `None` for ordinary keys.
+pub fn split_lang_key(key: &str) -> Option<(&str, &str)> {
+    let (key, lang) = key.rsplit_once('@')?;
+    (!key.is_empty() && is_language_tag(lang)).then_some((key, lang))
+}
+
+/// `lang` and its less specific forms, most specific first
+/// (`zh-hant-tw`, `zh-hant`, `zh`).
+pub(crate) fn lookup_chain(lang: &str) -> impl Iterator<Item = &str> {
+    let mut next = Some(lang);
+    std::iter::from_fn(move || {
+        let current = next?;
+        next = current.rsplit_once('-').map(|(parent, _)| parent);
+        Some(current)
+    })
+}
+
+impl EntityStore {
+    /// The `(lang, value)` pairs stored under `attr_name`, in insertion order.
+    pub fn get_lang_values(&self, entity_id: u32, attr_name: StrId) -> Option<&[(StrId, StrId)]> {
+        self.lang_strings
+            .get(&attr_name)?
+            .get(&entity_id)
+            .map(Vec::as_slice)
+    }
+
+    pub(crate) fn set_lang_value(
+        &mut self,
+        entity_id: u32,
+        attr_name: StrId,
+        lang: StrId,
+        value: StrId,
+    ) {
+        let values = self
+            .lang_strings
+            .entry(attr_name)
+            .or_default()
+            .get_or_insert_default(entity_id);
+        match values.iter_mut().find(|(l, _)| *l == lang) {
+            Some(slot) => slot.1 = value,
+            None => values.push((lang, value)),
+        }
+    }
+
+    pub(crate) fn lang_section(&self) -> LangSection {
+        let mut section: LangSection = self
+            .lang_strings
+            .iter()
+            .map(|(&key, col)| {
+                let rows = col.iter().map(|(&e, values)| (e, values.clone()));
+                (key, rows.collect())
+            })
+            .collect();
+        section.sort_unstable_by_key(|(key, _)| *key);
+        section
+    }
+
+    pub(crate) fn load_lang_section(&mut self, section: LangSection) {
+        self.lang_strings = section
+            .into_iter()
+            .map(|(key, rows)| (key, rows.into_iter().collect::<AttrColumn<_>>()))
+            .collect();
+    }
+}
+
+impl PathDB {
+    /// Set (replace) `entity_id`'s `key` value in language `lang`.
+    pub fn set_attr_lang(
+        &mut self,
+        entity_id: u32,
+        key: &str,
+        lang: &str,
+        value: &str,
+    ) -> Result<()> {
+        if entity_id as usize >= self.entities.types.len() {
+            return Err(PathDbError::UnknownEntity(entity_id));
+        }
+        if !is_language_tag(lang) {
+            return Err(PathDbError::InvalidLanguageTag(lang.to_string()));
+        }
+        let key_id = self.interner.intern(key);
+        let lang_id = self.interner.intern(&lang.to_ascii_lowercase());
+        let value_id = self.interner.intern(value);
+        self.entities
+            .set_lang_value(entity_id, key_id, lang_id, value_id);
+        Ok(())
+    }
+
+    /// Remove `entity_id`'s `key` value in language `lang`; returns whether
+    /// there was one.
+    pub fn remove_attr_lang(&mut self, entity_id: u32, key: &str, lang: &str) -> bool {
+        let (Some(key_id), Some(lang_id)) = (
+            self.interner.id_of(key),
+            self.interner.id_of(&lang.to_ascii_lowercase()),
+        ) else {
+            return false;
+        };
+        let Some(col) = self.entities.lang_strings.get_mut(&key_id) else {
+            return false;
+        };
+        let Some(values) = col.get_mut(&entity_id) else {
+            return false;
+        };
+        let before = values.len();
+        values.retain(|(l, _)| *l != lang_id);
+        let removed = values.len() != before;
+        if values.is_empty() {
+            col.remove(&entity_id);
+        }
+        removed
+    }
+
+    /// All tagged values of `entity_id`'s `key` as `(lang, value)`, in
+    /// insertion order.
+    pub fn attr_langs(&self, entity_id: u32, key: &str) -> Vec<(String, String)> {
+        let Some(key_id) = self.interner.id_of(key) else {
+            return Vec::new();
+        };
+        self.entities
+            .get_lang_values(entity_id, key_id)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|&(lang, value)| {
+                Some((self.interner.lookup(lang)?, self.interner.lookup(value)?))
+            })
+            .collect()
+    }
+
+    /// `entity_id`'s `key` in `lang`, falling back to less specific forms of
+    /// `lang` and then to the untagged attribute.
+    pub fn get_attr_lang(&self, entity_id: u32, key: &str, lang: &str) -> Option<String> {
+        self.get_attr_lang_chain(entity_id, key, &[lang])
+            .map(|found| found.value)
+    }
+
+    /// `entity_id`'s `key` in the first of `langs` that has a value (each
+    /// tried with its less specific forms), falling back to the untagged
+    /// attribute.
+    pub fn get_attr_lang_chain(
+        &self,
+        entity_id: u32,
+        key: &str,
+        langs: &[&str],
+    ) -> Option<LangValue> {
+        let key_id = self.interner.id_of(key)?;
+        if let Some(tagged) = self.entities.get_lang_values(entity_id, key_id) {
+            for wanted in langs {
+                let wanted = wanted.to_ascii_lowercase();
+                for candidate in lookup_chain(&wanted) {
+                    let Some(lang_id) = self.interner.id_of(candidate) else {
+                        continue;
+                    };
+                    if let Some(&(_, value)) = tagged.iter().find(|(l, _)| *l == lang_id) {
+                        return Some(LangValue {
+                            value: self.interner.lookup(value)?,
+                            lang: Some(candidate.to_string()),
+                        });
+                    }
+                }
+            }
+        }
+        let value = self.entities.get_attr(entity_id, key_id)?;
+        Some(LangValue {
+            value: self.interner.lookup(value)?,
+            lang: None,
+        })
+    }
+}
@@ -28,57 +28,147 @@
 
 #![allow(unused_variables)]
 
+pub mod abduction;
+pub mod analytics;
+pub mod attr_column;
+pub mod auto_index;
 pub mod axi_export;
 pub mod axi_meta;
 pub mod axi_module_constraints;
 pub mod axi_module_export;
+pub mod axi_module_graph;
 pub mod axi_module_import;
 pub mod axi_module_typecheck;
 pub mod axi_semantics;
 pub mod axi_type;
 pub mod axi_typed;
+pub mod blob_store;
+pub mod bloom;
 pub mod branding;
+pub mod budget;
+pub mod cardinality;
 pub mod checked_db;
 pub mod certificate;
+pub mod collapse;
+pub mod component_index;
+pub mod composite_index;
+pub mod confidence;
+pub mod constraint_check;
+pub mod context;
+pub mod csr;
+pub mod embedding;
+pub mod error;
+pub mod explain;
 pub mod fact_index;
+pub mod facts;
+pub mod federation;
+pub mod frontier;
 mod index_sidecar;
 pub mod guardrails;
+pub mod handles;
+pub mod index_estimate;
+pub mod inference;
+pub mod integrity;
+pub mod interner;
+pub mod inverses;
+pub mod labels;
+pub mod lang_attrs;
 pub mod learning;
+pub mod list_attrs;
+pub mod metrics;
 pub mod migration;
 pub mod modal;
+pub mod multiplicity;
+pub mod namespace;
 pub mod optimizer;
+mod ordered;
+pub mod overlay;
+pub mod pagination;
+pub mod partition;
 pub mod proof_mode;
+pub mod query_json;
+pub mod revalidation;
+pub mod sampling;
+pub mod shard;
+pub mod subscription;
+pub mod supernode;
+pub mod testing;
 pub mod text_index;
+pub mod type_lattice;
 pub mod typestate;
 pub mod verified;
+pub mod virtual_relations;
 pub mod witness;
 
-use ahash::AHashMap;
-use anyhow::Result;
+pub use error::PathDbError;
+
+use budget::{BudgetTracker, Budgeted, QueryBudget};
+use error::Result;
+
+use ahash::{AHashMap, AHashSet};
 use dashmap::DashMap;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, VecDeque};
-use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{mpsc, Arc, Mutex, Weak};
 use std::time::Duration;
 
 // Re-export key types
+pub use abduction::{Abduction, AbductionConfig, AbductionStep, ProposedEdge};
+pub use attr_column::AttrColumn;
+pub use blob_store::BlobStore;
 pub use branding::{DbBranded, DbToken, DbTokenMismatch};
 pub use certificate::{
     AxiAnchorV1, AxiConstraintsOkProofV1, AxiWellTypedProofV1, Certificate, CertificateV2,
     FixedPointProbability, FixedProb, NormalizePathProofV2, PathEquivProofV2, PathExprV2,
     PathRewriteStepV3, ReachabilityProofV2, ResolutionDecisionV2, ResolutionProofV2,
-    RewriteDerivationProofV2, RewriteDerivationProofV3, VProb, CERTIFICATE_VERSION,
+    RewriteDerivationProofV2, RewriteDerivationProofV3, TypedLiteralV1, VProb, CERTIFICATE_VERSION,
     CERTIFICATE_VERSION_V2, FIXED_POINT_DENOMINATOR, FIXED_PROB_PRECISION,
 };
+pub use auto_index::{
+    AutoIndexConfig, IndexPlan, IndexReason, PathIndexPolicy, PlannedSignature, QueryStatsSnapshot,
+    RelTypeStats,
+};
 pub use axi_type::{AxiType, TypingEnv};
 pub use index_sidecar::{
     read_sidecar_file, write_sidecar_file, IndexSidecarWriter, LruSnapshot, PathDbIndexSidecarV1,
     PATHDB_INDEX_SIDECAR_VERSION_V1,
 };
+pub use composite_index::CompositeIndexes;
+pub use interner::{InternerStats, StringInterner};
+pub use labels::LabelPolicy;
+pub use lang_attrs::LangValue;
 pub use checked_db::{CheckedDb, CheckedDbMut, CheckedDbReport, TypedFactBuilder};
+pub use collapse::{
+    CollapseConfig, CollapsedEdge, CollapsedEdgeV1, EdgeProvenance, RelationCollapse,
+    RelationCollapseProofV1,
+};
+pub use confidence::ConfidenceCombiner;
+pub use constraint_check::{
+    check_constraint_against_db, ConstraintCheck, ConstraintSatisfied, Counterexample,
+};
+pub use context::{
+    ContextContradiction, ContextFact, ContextScope, EffectiveFacts, FactOverride, QueryContext,
+};
+pub use embedding::{
+    embeddings_path_for_axpd, EmbeddingConfigV1, EmbeddingModelV1, KgEmbeddingsV1, LinkPredictionV1,
+};
+pub use explain::{EdgeExplanation, Explanation, ExplanationStep};
+pub use facts::{FactInsert, FactView, KeyViolation, OnKeyConflict};
+pub use federation::{FederatedQuery, FederatedRef, Federation};
 pub use guardrails::{GuardrailEngine, GuardrailRule, GuardrailViolation, Severity};
+pub use handles::{AttrKeyHandle, RelTypeHandle};
+pub use index_estimate::{
+    DepthEstimate, IndexEstimateConfig, PathIndexEstimate, SafeCubeConfig,
+};
+pub use inference::{DerivedProvenance, InferenceReport, Rule, RuleSet};
+pub use integrity::{IntegrityCheck, IntegrityIssue, IntegrityReport};
+pub use inverses::{InverseDirection, InverseRegistry};
+pub use multiplicity::{
+    Multiplicity, MultiplicityBound, MultiplicityRegistry, MultiplicityViolation,
+    OnMultiplicityConflict, RelationInsert,
+};
 pub use migration::{
     ArrowDeclV1, ArrowMapV1, ArrowMappingV1, DeltaFMigrationProofV1, InstanceV1, Name,
     ObjectElementsV1, ObjectMappingV1, SchemaMorphismV1, SchemaV1, SigmaFMigrationProofV1,
@@ -86,11 +176,24 @@ pub use migration::{
 };
 pub use modal::{ModalFrame, ModalPathDB, ModalWorld, Modality};
 pub use optimizer::{MigrationOperatorV1, OptimizerRuleV1, ProofProducingOptimizer};
+pub use overlay::{ConstraintViolation, Overlay, OverlayCheck, ReachabilityDiff, StagedChange};
+pub use partition::{ConfidencePartition, ConfidencePartitionProofV1, PartitionSideV1};
 pub use proof_mode::{NoProof, ProofJournal, ProofMode, Proved, WithProof};
+pub use query_json::{PathQueryRequest, PATH_QUERY_JSON_VERSION};
+pub use revalidation::{Revalidation, RevalidationStatus, Revalidator};
+pub use sampling::{Estimate, NeighborhoodSample, PathCountSample, SamplingConfig};
+pub use subscription::{SubscriptionDelta, SubscriptionId, SubscriptionStats, Subscriptions};
+pub use type_lattice::TypeLattice;
 pub use typestate::{NormalizedPathExprV2, UnnormalizedPathExprV2};
 pub use verified::{BinaryHeader, ReachabilityProof, VerifiedPathSig, VerifiedProb};
+pub use virtual_relations::{
+    AttrEqualsDef, VirtualPredicate, VirtualRelationDef, VirtualRelationRegistry,
+};
 
+use bincode::Options as _;
 use fact_index::FactIndexCache;
+use cardinality::CardinalityCache;
+use component_index::ComponentIndexCache;
 use text_index::TextIndexCache;
 
 /// Tokenize a string using the same rules as PathDB's `fts` query operators.
@@ -106,7 +209,7 @@ pub fn tokenize_fts_query(query: &str) -> Vec<String> {
 // ============================================================================
 
 /// Interned string ID (4 bytes instead of 24+ for String)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[repr(transparent)]
 pub struct StrId(u32);
 
@@ -120,72 +223,6 @@ impl StrId {
     }
 }
 
-/// String interner: maps strings to compact IDs
-pub struct StringInterner {
-    /// String to ID mapping
-    str_to_id: DashMap<String, StrId>,
-    /// ID to string mapping (for reverse lookup)
-    id_to_str: DashMap<StrId, String>,
-    /// Next available ID
-    next_id: AtomicU32,
-}
-
-impl StringInterner {
-    pub fn new() -> Self {
-        Self {
-            str_to_id: DashMap::new(),
-            id_to_str: DashMap::new(),
-            next_id: AtomicU32::new(0),
-        }
-    }
-
-    /// Intern a string, returning its ID
-    pub fn intern(&self, s: &str) -> StrId {
-        if let Some(id) = self.str_to_id.get(s) {
-            return *id;
-        }
-
-        let id = StrId(self.next_id.fetch_add(1, Ordering::SeqCst));
-        self.str_to_id.insert(s.to_string(), id);
-        self.id_to_str.insert(id, s.to_string());
-        id
-    }
-
-    /// Look up an existing ID for a string without inserting.
-    pub fn id_of(&self, s: &str) -> Option<StrId> {
-        self.str_to_id.get(s).map(|id| *id)
-    }
-
-    /// Look up string by ID
-    pub fn lookup(&self, id: StrId) -> Option<String> {
-        self.id_to_str.get(&id).map(|s| s.clone())
-    }
-
-    /// Serialize to bytes
-    pub fn to_bytes(&self) -> Vec<u8> {
-        let strings: Vec<String> = (0..self.next_id.load(Ordering::SeqCst))
-            .filter_map(|i| self.id_to_str.get(&StrId(i)).map(|s| s.clone()))
-            .collect();
-        bincode::serialize(&strings).unwrap_or_default()
-    }
-
-    /// Deserialize from bytes
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
-        let strings: Vec<String> = bincode::deserialize(bytes)?;
-        let interner = Self::new();
-        for s in strings {
-            interner.intern(&s);
-        }
-        Ok(interner)
-    }
-}
-
-impl Default for StringInterner {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 // ============================================================================
 // Entity Storage (Columnar)
 // ============================================================================
@@ -203,7 +240,20 @@ pub struct Entity {
 pub struct EntityView {
     pub id: u32,
     pub entity_type: String,
-    pub attrs: HashMap<String, String>,
+    /// Attributes in key order.
+    pub attrs: BTreeMap<String, String>,
+    /// Display label under the DB's [`LabelPolicy`], if any attribute names
+    /// the entity.
+    pub label: Option<String>,
+}
+
+impl EntityView {
+    /// [`Self::label`], or `Type#id` for unlabeled entities.
+    pub fn display_label(&self) -> String {
+        self.label
+            .clone()
+            .unwrap_or_else(|| format!("{}#{}", self.entity_type, self.id))
+    }
 }
 
 /// Columnar entity storage
@@ -211,12 +261,27 @@ pub struct EntityView {
 pub struct EntityStore {
     /// Type column: entity_id -> type_id
     types: Vec<StrId>,
-    /// Attribute columns: attr_name -> (entity_id -> value)
-    attrs: HashMap<StrId, HashMap<u32, StrId>>,
+    /// Attribute columns: attr_name -> (entity_id -> value), sorted by
+    /// entity (see `attr_column`)
+    #[serde(serialize_with = "ordered::sorted_map")]
+    attrs: HashMap<StrId, AttrColumn>,
     /// Type index: type_id -> bitmap of entity IDs
+    #[serde(serialize_with = "ordered::sorted_map")]
     type_index: HashMap<StrId, RoaringBitmap>,
     /// Next entity ID
     next_id: u32,
+    /// List-valued attribute columns: attr_name -> (entity_id -> values),
+    /// sorted by entity (an optional trailing snapshot section, see `list_attrs`)
+    #[serde(skip)]
+    lists: HashMap<StrId, AttrColumn<Vec<StrId>>>,
+    /// Language-tagged string columns: attr_name -> (entity_id -> [(lang, value)]),
+    /// sorted by entity (an optional trailing snapshot section, see `lang_attrs`)
+    #[serde(skip)]
+    lang_strings: HashMap<StrId, AttrColumn<Vec<(StrId, StrId)>>>,
+    /// Value indexes for declared attributes: attr_name -> (value -> entity
+    /// bitmap). Runtime-only, see [`EntityStore::index_attr_values`].
+    #[serde(skip)]
+    value_index: HashMap<StrId, HashMap<StrId, RoaringBitmap>>,
 }
 
 impl EntityStore {
@@ -247,15 +312,12 @@ impl EntityStore {
         // Update type index
         self.type_index
             .entry(type_id)
-            .or_insert_with(RoaringBitmap::new)
+            .or_default()
             .insert(id);
 
         // Store attributes
         for (attr_name, attr_value) in attrs {
-            self.attrs
-                .entry(attr_name)
-                .or_insert_with(HashMap::new)
-                .insert(id, attr_value);
+            self.set_attr(id, attr_name, attr_value);
         }
 
         id
@@ -276,8 +338,76 @@ impl EntityStore {
         self.attrs.get(&attr_name)?.get(&entity_id).copied()
     }
 
-    /// Find all entities where `attr_name == value`.
+    /// The attribute column `attr_name`, in entity order.
+    pub fn attr_column(&self, attr_name: StrId) -> Option<&AttrColumn> {
+        self.attrs.get(&attr_name)
+    }
+
+    /// Bytes held by the attribute columns' buffers.
+    pub fn attr_heap_bytes(&self) -> usize {
+        self.attrs.values().map(AttrColumn::heap_bytes).sum()
+    }
+
+    /// Set `attr_name = value` on `entity_id`, keeping any value index in
+    /// step. Returns the previous value.
+    pub(crate) fn set_attr(
+        &mut self,
+        entity_id: u32,
+        attr_name: StrId,
+        value: StrId,
+    ) -> Option<StrId> {
+        let old = self.attrs.entry(attr_name).or_default().insert(entity_id, value);
+        if let Some(index) = self.value_index.get_mut(&attr_name) {
+            reindex_value(index, entity_id, old, Some(value));
+        }
+        old
+    }
+
+    /// Drop `attr_name` from `entity_id`, keeping any value index in step.
+    pub(crate) fn remove_attr(&mut self, entity_id: u32, attr_name: StrId) -> Option<StrId> {
+        let old = self.attrs.get_mut(&attr_name)?.remove(&entity_id);
+        if let Some(index) = self.value_index.get_mut(&attr_name) {
+            reindex_value(index, entity_id, old, None);
+        }
+        old
+    }
+
+    /// Maintain a value -> entities index for `attr_name`, so
+    /// [`Self::entities_with_attr_value`] becomes a lookup instead of a
+    /// column scan. The index is not persisted; declare it again after
+    /// loading. Returns `false` if `attr_name` was already indexed.
+    pub fn index_attr_values(&mut self, attr_name: StrId) -> bool {
+        if self.value_index.contains_key(&attr_name) {
+            return false;
+        }
+        let mut index: HashMap<StrId, RoaringBitmap> = HashMap::new();
+        if let Some(col) = self.attrs.get(&attr_name) {
+            for (&entity_id, &value) in col {
+                index.entry(value).or_default().insert(entity_id);
+            }
+        }
+        self.value_index.insert(attr_name, index);
+        true
+    }
+
+    /// Attributes with a value index, in no particular order.
+    pub fn value_indexed_attrs(&self) -> impl Iterator<Item = StrId> + '_ {
+        self.value_index.keys().copied()
+    }
+
+    /// Entities where `attr_name == value`, answered from the value index
+    /// only; `None` if `attr_name` is not indexed.
+    pub fn indexed_attr_value(&self, attr_name: StrId, value: StrId) -> Option<RoaringBitmap> {
+        let index = self.value_index.get(&attr_name)?;
+        Some(index.get(&value).cloned().unwrap_or_default())
+    }
+
+    /// Find all entities where `attr_name == value`. Uses the value index
+    /// when `attr_name` has one, otherwise scans the column.
     pub fn entities_with_attr_value(&self, attr_name: StrId, value: StrId) -> RoaringBitmap {
+        if let Some(hit) = self.indexed_attr_value(attr_name, value) {
+            return hit;
+        }
         let mut out = RoaringBitmap::new();
         let Some(col) = self.attrs.get(&attr_name) else {
             return out;
@@ -291,6 +421,26 @@ impl EntityStore {
     }
 }
 
+/// Move `entity_id` from `old`'s bitmap to `new`'s in a value index.
+fn reindex_value(
+    index: &mut HashMap<StrId, RoaringBitmap>,
+    entity_id: u32,
+    old: Option<StrId>,
+    new: Option<StrId>,
+) {
+    if let Some(old) = old {
+        if let Some(bits) = index.get_mut(&old) {
+            bits.remove(entity_id);
+            if bits.is_empty() {
+                index.remove(&old);
+            }
+        }
+    }
+    if let Some(new) = new {
+        index.entry(new).or_default().insert(entity_id);
+    }
+}
+
 // ============================================================================
 // Relation Storage (Edge-List with Indexes)
 // ============================================================================
@@ -311,11 +461,18 @@ pub struct RelationStore {
     /// All relations
     relations: Vec<Relation>,
     /// Forward index: (source, rel_type) -> relation IDs
-    forward_index: HashMap<(u32, StrId), Vec<u32>>,
+    forward_index: csr::Adjacency,
     /// Backward index: (target, rel_type) -> relation IDs
-    backward_index: HashMap<(u32, StrId), Vec<u32>>,
+    backward_index: csr::Adjacency,
     /// Type index: rel_type -> relation IDs
+    #[serde(serialize_with = "ordered::sorted_map")]
     type_index: HashMap<StrId, RoaringBitmap>,
+    /// Neighbour bitmaps for supernode adjacency lists (derived, rebuilt on load).
+    #[serde(skip)]
+    supernodes: supernode::SupernodeIndex,
+    /// Per-rel_type Bloom filters over `(source, target)` (derived, rebuilt on load).
+    #[serde(skip)]
+    edge_filters: bloom::EdgeFilters,
 }
 
 impl RelationStore {
@@ -346,24 +503,43 @@ impl RelationStore {
 
         // Update indexes
         self.forward_index
+            .lists_mut()
             .entry((rel.source, rel.rel_type))
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(id);
 
         self.backward_index
+            .lists_mut()
             .entry((rel.target, rel.rel_type))
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(id);
 
         self.type_index
             .entry(rel.rel_type)
-            .or_insert_with(RoaringBitmap::new)
+            .or_default()
             .insert(id);
 
         self.relations.push(rel);
+        self.note_supernode_edge(id);
+        self.note_edge_filter(id);
         id
     }
 
+    /// Keep only the relations `keep` accepts; returns how many were dropped.
+    ///
+    /// Relation ids are renumbered: the survivors are compacted in their
+    /// original order, so an id held across this call may name another
+    /// relation, or none.
+    pub(crate) fn retain(&mut self, mut keep: impl FnMut(&Relation) -> bool) -> usize {
+        let old = std::mem::take(self);
+        let before = old.len();
+        self.set_supernode_threshold(old.supernode_threshold());
+        for rel in old.relations.into_iter().filter(|rel| keep(rel)) {
+            self.add(rel);
+        }
+        before - self.len()
+    }
+
     /// Get outgoing relations from source with given type
     pub fn outgoing(&self, source: u32, rel_type: StrId) -> Vec<&Relation> {
         self.forward_index
@@ -380,31 +556,40 @@ impl RelationStore {
     ///
     /// This is primarily intended for lightweight tooling (FFI, debugging).
     /// Performance-sensitive callers should use `outgoing(source, rel_type)` or
-    /// a query plan that fixes `rel_type`.
+    /// a query plan that fixes `rel_type`. Results are in relation id order.
     pub fn outgoing_any(&self, source: u32) -> Vec<&Relation> {
-        let mut out = Vec::new();
-        for ((src, _), ids) in &self.forward_index {
-            if *src != source {
-                continue;
-            }
-            out.extend(ids.iter().filter_map(|&id| self.relations.get(id as usize)));
-        }
-        out
+        self.relations_by_id(self.forward_index.entity_rel_ids(source))
     }
 
     /// Get incoming relations to target (any type).
     ///
     /// This is primarily intended for lightweight tooling (REPL, debugging).
     /// Performance-sensitive callers should fix `rel_type` and use `incoming(...)`.
+    /// Results are in relation id order.
     pub fn incoming_any(&self, target: u32) -> Vec<&Relation> {
-        let mut out = Vec::new();
-        for ((dst, _), ids) in &self.backward_index {
-            if *dst != target {
-                continue;
-            }
-            out.extend(ids.iter().filter_map(|&id| self.relations.get(id as usize)));
-        }
-        out
+        self.relations_by_id(self.backward_index.entity_rel_ids(target))
+    }
+
+    /// Resolve relation ids in ascending id (insertion) order.
+    /// Relations out of `source` (any type) in relation id order, found with
+    /// one forward-index probe per relation type. Unlike
+    /// [`Self::outgoing_any`] this never walks the whole mutable index.
+    pub(crate) fn outgoing_by_type_probe(&self, source: u32) -> Vec<&Relation> {
+        let ids = self
+            .type_index
+            .keys()
+            .filter_map(|&rel_type| self.forward_index.get(&(source, rel_type)))
+            .flatten()
+            .copied()
+            .collect();
+        self.relations_by_id(ids)
+    }
+
+    fn relations_by_id(&self, mut ids: Vec<u32>) -> Vec<&Relation> {
+        ids.sort_unstable();
+        ids.into_iter()
+            .filter_map(|id| self.relations.get(id as usize))
+            .collect()
     }
 
     /// Get incoming relations to target with given type
@@ -421,6 +606,9 @@ impl RelationStore {
 
     /// Get all targets reachable from source via rel_type
     pub fn targets(&self, source: u32, rel_type: StrId) -> RoaringBitmap {
+        if let Some(targets) = self.supernodes.targets(source, rel_type) {
+            return targets.clone();
+        }
         let mut result = RoaringBitmap::new();
         self.targets_into(source, rel_type, &mut result);
         result
@@ -428,6 +616,14 @@ impl RelationStore {
 
     /// Fill `out` with all targets reachable from source via rel_type.
     pub fn targets_into(&self, source: u32, rel_type: StrId, out: &mut RoaringBitmap) {
+        if let Some(targets) = self.supernodes.targets(source, rel_type) {
+            *out |= targets;
+            return;
+        }
+        if let Some(targets) = self.forward_index.endpoints(&(source, rel_type)) {
+            out.extend(targets.iter().copied());
+            return;
+        }
         let Some(ids) = self.forward_index.get(&(source, rel_type)) else {
             return;
         };
@@ -464,6 +660,12 @@ impl RelationStore {
 
     /// Get all sources that reach `target` via `rel_type`.
     pub fn sources(&self, target: u32, rel_type: StrId) -> RoaringBitmap {
+        if let Some(sources) = self.supernodes.sources(target, rel_type) {
+            return sources.clone();
+        }
+        if let Some(sources) = self.backward_index.endpoints(&(target, rel_type)) {
+            return sources.iter().copied().collect();
+        }
         let mut result = RoaringBitmap::new();
         if let Some(ids) = self.backward_index.get(&(target, rel_type)) {
             for &id in ids {
@@ -501,6 +703,12 @@ impl RelationStore {
 
     /// Check whether an edge exists: `source -[rel_type]-> target`.
     pub fn has_edge(&self, source: u32, rel_type: StrId, target: u32) -> bool {
+        if !self.edge_filters.may_contain(rel_type, source, target) {
+            return false;
+        }
+        if let Some(targets) = self.supernodes.targets(source, rel_type) {
+            return targets.contains(target);
+        }
         let Some(ids) = self.forward_index.get(&(source, rel_type)) else {
             return false;
         };
@@ -538,7 +746,6 @@ impl RelationStore {
     pub fn outgoing_relation_ids(&self, source: u32, rel_type: StrId) -> &[u32] {
         self.forward_index
             .get(&(source, rel_type))
-            .map(|ids| ids.as_slice())
             .unwrap_or(&[])
     }
 
@@ -547,6 +754,9 @@ impl RelationStore {
     /// Note: PathDB may contain multiple edges with the same endpoints and label
     /// (e.g. differing attributes/confidence). For certificates we only need one.
     pub fn edge_relation_id(&self, source: u32, rel_type: StrId, target: u32) -> Option<u32> {
+        if !self.edge_filters.may_contain(rel_type, source, target) {
+            return None;
+        }
         let ids = self.forward_index.get(&(source, rel_type))?;
         for &id in ids {
             let rel = self.relations.get(id as usize)?;
@@ -569,6 +779,9 @@ impl RelationStore {
         target: u32,
         min_confidence: f32,
     ) -> Option<u32> {
+        if !self.edge_filters.may_contain(rel_type, source, target) {
+            return None;
+        }
         let min_confidence = min_confidence.clamp(0.0, 1.0);
         let ids = self.forward_index.get(&(source, rel_type))?;
 
@@ -601,7 +814,7 @@ impl RelationStore {
 // ============================================================================
 
 /// A path signature: sequence of relation types
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct PathSig(Vec<StrId>);
 
 impl PathSig {
@@ -689,7 +902,7 @@ impl LruWorkerState {
             return;
         }
         {
-            let mut entry = entries.entry(sig.clone()).or_insert_with(AHashMap::new);
+            let mut entry = entries.entry(sig.clone()).or_default();
             entry.insert(start, targets);
         }
         self.touch(&sig);
@@ -727,6 +940,7 @@ impl LruWorkerState {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PathIndex {
     /// path_sig -> (start_entity -> reachable_entities)
+    #[serde(serialize_with = "ordered::sorted_nested_ahash_map")]
     index: AHashMap<PathSig, AHashMap<u32, RoaringBitmap>>,
     /// Maximum indexed path length
     max_depth: usize,
@@ -1015,9 +1229,9 @@ impl PathIndex {
             let sig = PathSig::new(vec![rel.rel_type]);
             self.index
                 .entry(sig)
-                .or_insert_with(AHashMap::new)
+                .or_default()
                 .entry(rel.source)
-                .or_insert_with(RoaringBitmap::new)
+                .or_default()
                 .insert(rel.target);
         }
 
@@ -1049,10 +1263,7 @@ impl PathIndex {
                     // Compute reachability
                     let mut new_reach: AHashMap<u32, RoaringBitmap> = AHashMap::new();
                     for (&start, intermediates) in prev_reach {
-                        let mut targets = RoaringBitmap::new();
-                        for intermediate in intermediates.iter() {
-                            relations.targets_into(intermediate, *rel_type, &mut targets);
-                        }
+                        let targets = relations.expand_frontier(intermediates, *rel_type);
                         if !targets.is_empty() {
                             new_reach.insert(start, targets);
                         }
@@ -1072,6 +1283,67 @@ impl PathIndex {
         }
     }
 
+    /// Build only `signatures` (plus the single-hop index every traversal
+    /// can use). Prefixes are computed on the way but not kept.
+    pub fn build_signatures(&mut self, relations: &RelationStore, signatures: &[PathSig]) {
+        self.index.clear();
+        self.clear_lru();
+        if self.max_depth == 0 {
+            return;
+        }
+
+        for rel in &relations.relations {
+            let sig = PathSig::new(vec![rel.rel_type]);
+            self.index
+                .entry(sig)
+                .or_default()
+                .entry(rel.source)
+                .or_default()
+                .insert(rel.target);
+        }
+
+        let wanted: AHashSet<&PathSig> = signatures.iter().collect();
+        let mut needed: Vec<PathSig> = signatures
+            .iter()
+            .flat_map(|sig| (2..=sig.len()).map(|len| PathSig::new(sig.0[..len].to_vec())))
+            .collect();
+        needed.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        needed.dedup();
+
+        let mut prefixes: Vec<PathSig> = Vec::new();
+        for sig in needed {
+            let prefix = PathSig::new(sig.0[..sig.len() - 1].to_vec());
+            let Some(prev_reach) = self.index.get(&prefix) else {
+                continue;
+            };
+            let rel_type = sig.0[sig.len() - 1];
+            let mut reach: AHashMap<u32, RoaringBitmap> = AHashMap::new();
+            for (&start, intermediates) in prev_reach {
+                let targets = relations.expand_frontier(intermediates, rel_type);
+                if !targets.is_empty() {
+                    reach.insert(start, targets);
+                }
+            }
+            if reach.is_empty() {
+                continue;
+            }
+            if !wanted.contains(&sig) {
+                prefixes.push(sig.clone());
+            }
+            self.index.insert(sig, reach);
+        }
+        for prefix in prefixes {
+            self.index.remove(&prefix);
+        }
+    }
+
+    /// Indexed signatures (diagnostic/testing).
+    pub fn signatures(&self) -> Vec<PathSig> {
+        let mut sigs: Vec<PathSig> = self.index.keys().cloned().collect();
+        sigs.sort();
+        sigs
+    }
+
     pub fn invalidate(&mut self) {
         self.index.clear();
         self.clear_lru();
@@ -1125,6 +1397,40 @@ pub struct PathDB {
     /// Cached inverted indexes for attribute full-text search (rebuilt on demand).
     #[serde(skip)]
     text_index: TextIndexCache,
+    /// Incremental union-find per requested relation-type set.
+    #[serde(skip)]
+    component_index: ComponentIndexCache,
+    /// Approximate cardinality sketches (built on first estimate).
+    #[serde(skip)]
+    cardinality: CardinalityCache,
+    /// Declared subtypes (rebuilt from the meta plane on load).
+    #[serde(skip)]
+    type_lattice: TypeLattice,
+    /// Registered relation inverses (rebuilt from the meta plane on load).
+    #[serde(skip)]
+    inverses: InverseRegistry,
+    /// Virtual relations (attribute equalities rebuilt from the meta plane on
+    /// load; predicates are session-only).
+    #[serde(skip)]
+    virtual_relations: VirtualRelationRegistry,
+    /// Declared relation multiplicities (rebuilt from the meta plane on load).
+    #[serde(skip)]
+    multiplicities: MultiplicityRegistry,
+    /// Declared `(type, attr)` indexes (rebuilt from the meta plane on load).
+    #[serde(skip)]
+    composite_indexes: CompositeIndexes,
+    /// Content-addressed blob payloads (an optional trailing snapshot section).
+    #[serde(skip)]
+    blobs: BlobStore,
+    /// Runtime query log (per relation type / path signature).
+    #[serde(skip)]
+    query_stats: auto_index::QueryStats,
+    /// How `build_indexes` picks path signatures.
+    #[serde(skip)]
+    index_policy: PathIndexPolicy,
+    /// Which attributes name entities for display.
+    #[serde(skip)]
+    label_policy: LabelPolicy,
     /// Optional writer for durable index sidecars.
     #[serde(skip)]
     index_sidecar: Mutex<Option<Arc<IndexSidecarWriter>>>,
@@ -1142,6 +1448,17 @@ impl PathDB {
             confidence_index: Vec::new(),
             fact_index: FactIndexCache::default(),
             text_index: TextIndexCache::default(),
+            component_index: ComponentIndexCache::default(),
+            cardinality: CardinalityCache::default(),
+            type_lattice: TypeLattice::default(),
+            inverses: InverseRegistry::default(),
+            virtual_relations: VirtualRelationRegistry::default(),
+            multiplicities: MultiplicityRegistry::default(),
+            composite_indexes: CompositeIndexes::default(),
+            blobs: BlobStore::default(),
+            query_stats: auto_index::QueryStats::default(),
+            index_policy: PathIndexPolicy::default(),
+            label_policy: LabelPolicy::default(),
             index_sidecar: Mutex::new(None),
         }
     }
@@ -1150,6 +1467,61 @@ impl PathDB {
         self.db_token
     }
 
+    /// An empty PathDB with this one's runtime declarations: subtypes,
+    /// inverses, multiplicities, virtual relations (predicates included),
+    /// composite and attribute-value indexes, and the label and path-index
+    /// policies. For rebuilding the data, e.g. by replaying a log, without
+    /// dropping what was declared on the live database.
+    pub fn empty_with_declarations(&self) -> PathDB {
+        let name = |id: StrId| self.interner.lookup(id).unwrap_or_default();
+        let mut db = PathDB::new();
+        db.index_policy = self.index_policy.clone();
+        db.label_policy = self.label_policy.clone();
+
+        let mut subtypes: Vec<(String, String)> = self
+            .type_lattice
+            .declared()
+            .map(|(sub, sup)| (name(sub), name(sup)))
+            .collect();
+        subtypes.sort();
+        for (sub, sup) in &subtypes {
+            db.declare_subtype(sub, sup);
+        }
+        // The registries below were consistent here, and `db` has no edges,
+        // so none of the re-registrations can conflict.
+        for &(relation, inverse) in self.inverses.pairs() {
+            let _ = db.register_inverse(&name(relation), &name(inverse));
+        }
+        let mut multiplicities: Vec<(String, Multiplicity)> = self
+            .multiplicities
+            .iter()
+            .map(|(rel, m)| (name(rel), m))
+            .collect();
+        multiplicities.sort_by(|a, b| a.0.cmp(&b.0));
+        for (relation, multiplicity) in multiplicities {
+            db.declare_multiplicity(&relation, multiplicity);
+        }
+        let mut virtuals: Vec<(String, VirtualRelationDef)> = self
+            .virtual_relations
+            .iter()
+            .map(|(rel, def)| (name(rel), def.clone()))
+            .collect();
+        virtuals.sort_by(|a, b| a.0.cmp(&b.0));
+        for (relation, def) in virtuals {
+            let _ = db.register_virtual_relation(&relation, def);
+        }
+        for (type_name, attr) in self.composite_index_names() {
+            db.declare_composite_index(&type_name, &attr);
+        }
+        let mut value_indexed: Vec<String> =
+            self.entities.value_indexed_attrs().map(name).collect();
+        value_indexed.sort();
+        for attr in &value_indexed {
+            db.index_attr_values(attr);
+        }
+        db
+    }
+
     /// Add an entity
     pub fn add_entity(&mut self, type_name: &str, attrs: Vec<(&str, &str)>) -> u32 {
         self.fact_index.invalidate();
@@ -1160,7 +1532,11 @@ impl PathDB {
             .into_iter()
             .map(|(k, v)| (self.interner.intern(k), self.interner.intern(v)))
             .collect();
-        self.entities.add(type_id, interned_attrs)
+        let id = self.entities.add(type_id, interned_attrs);
+        self.cardinality.on_entity_added(type_id, id);
+        self.index_supertypes(type_id, id);
+        self.reindex_composite(id);
+        id
     }
 
     /// Upsert a single entity attribute (extension-layer convenience).
@@ -1172,7 +1548,7 @@ impl PathDB {
     /// Note: this mutates the snapshot and invalidates dependent caches.
     pub fn upsert_entity_attr(&mut self, entity_id: u32, key: &str, value: &str) -> Result<()> {
         if entity_id as usize >= self.entities.types.len() {
-            return Err(anyhow::anyhow!("unknown entity id {entity_id}"));
+            return Err(PathDbError::UnknownEntity(entity_id));
         }
 
         self.fact_index.invalidate();
@@ -1181,14 +1557,20 @@ impl PathDB {
 
         let key_id = self.interner.intern(key);
         let value_id = self.interner.intern(value);
-        self.entities
-            .attrs
-            .entry(key_id)
-            .or_insert_with(HashMap::new)
-            .insert(entity_id, value_id);
+        self.entities.set_attr(entity_id, key_id, value_id);
+        self.reindex_composite(entity_id);
         Ok(())
     }
 
+    /// Keep a value -> entities index for `attr` (across all types), so
+    /// exact-value lookups on it stop scanning the column. Runtime-only: it
+    /// is not written to `.axpd`, so callers declare it after every load.
+    /// Returns false if `attr` was already indexed.
+    pub fn index_attr_values(&mut self, attr: &str) -> bool {
+        let attr_id = self.interner.intern(attr);
+        self.entities.index_attr_values(attr_id)
+    }
+
     /// Mark an entity as belonging to an additional type set (a "virtual type").
     ///
     /// PathDB stores a single canonical type per entity, but many workflows want
@@ -1197,7 +1579,7 @@ impl PathDB {
     /// allowing queries like `?x is T` to match it.
     pub fn mark_virtual_type(&mut self, entity_id: u32, type_name: &str) -> Result<()> {
         if entity_id as usize >= self.entities.types.len() {
-            return Err(anyhow::anyhow!("unknown entity id {entity_id}"));
+            return Err(PathDbError::UnknownEntity(entity_id));
         }
 
         self.fact_index.invalidate();
@@ -1206,11 +1588,31 @@ impl PathDB {
         self.entities
             .type_index
             .entry(type_id)
-            .or_insert_with(RoaringBitmap::new)
+            .or_default()
             .insert(entity_id);
+        self.cardinality.on_entity_added(type_id, entity_id);
+        self.index_supertypes(type_id, entity_id);
+        self.reindex_composite(entity_id);
         Ok(())
     }
 
+    /// Types `entity_id` is indexed under besides its own: marks from
+    /// [`PathDB::mark_virtual_type`] and indexed supertypes, sorted by name.
+    pub fn virtual_types_of(&self, entity_id: u32) -> Vec<String> {
+        let Some(own) = self.entities.get_type(entity_id) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = self
+            .entities
+            .type_index
+            .iter()
+            .filter(|(&type_id, ids)| type_id != own && ids.contains(entity_id))
+            .filter_map(|(&type_id, _)| self.interner.lookup(type_id))
+            .collect();
+        names.sort();
+        names
+    }
+
     /// Add a relation
     pub fn add_relation(
         &mut self,
@@ -1237,9 +1639,76 @@ impl PathDB {
         };
 
         self.confidence_index.push(confidence);
+        self.component_index.on_relation_added(rel_type_id, source, target);
+        self.cardinality.on_relation_added(rel_type_id, source, target);
         self.relations.add(rel)
     }
 
+    /// Remove every `source -rel_type-> target` edge. Returns how many were
+    /// removed; relation ids are renumbered (see [`RelationStore::retain`]).
+    pub fn remove_relation(&mut self, source: u32, rel_type: &str, target: u32) -> usize {
+        let Some(rel_type_id) = self.interner.id_of(rel_type) else {
+            return 0;
+        };
+        self.retain_relations(|rel| {
+            !(rel.source == source && rel.rel_type == rel_type_id && rel.target == target)
+        })
+    }
+
+    /// Keep only the relations for which `keep` returns true; returns how
+    /// many were dropped. Relation ids are renumbered (see
+    /// [`RelationStore::retain`]).
+    pub(crate) fn retain_relations(&mut self, keep: impl FnMut(&Relation) -> bool) -> usize {
+        let removed = self.relations.retain(keep);
+        if removed > 0 {
+            self.confidence_index = self
+                .relations
+                .relations
+                .iter()
+                .map(|rel| rel.confidence)
+                .collect();
+            self.fact_index.invalidate();
+            self.path_index.invalidate();
+            self.component_index.invalidate();
+            self.cardinality.invalidate();
+        }
+        removed
+    }
+
+    /// Set the confidence of every `source -rel_type-> target` edge. Returns
+    /// how many were updated.
+    pub fn set_relation_confidence(
+        &mut self,
+        source: u32,
+        rel_type: &str,
+        target: u32,
+        confidence: f32,
+    ) -> usize {
+        let Some(rel_type_id) = self.interner.id_of(rel_type) else {
+            return 0;
+        };
+        let ids = self
+            .relations
+            .forward_index
+            .get(&(source, rel_type_id))
+            .map(<[u32]>::to_vec)
+            .unwrap_or_default();
+        let mut updated = 0;
+        for id in ids {
+            let rel = &mut self.relations.relations[id as usize];
+            if rel.target == target {
+                rel.confidence = confidence;
+                self.confidence_index[id as usize] = confidence;
+                updated += 1;
+            }
+        }
+        if updated > 0 {
+            self.fact_index.invalidate();
+            self.path_index.invalidate();
+        }
+        updated
+    }
+
     /// Add an equivalence
     pub fn add_equivalence(&mut self, e1: u32, e2: u32, equiv_type: &str) {
         // Equivalences don't affect fact-node lookup, but we treat this as a DB mutation
@@ -1249,25 +1718,78 @@ impl PathDB {
         let equiv_type_id = self.interner.intern(equiv_type);
         self.equivalences
             .entry(e1)
-            .or_insert_with(Vec::new)
+            .or_default()
             .push((e2, equiv_type_id));
         self.equivalences
             .entry(e2)
-            .or_insert_with(Vec::new)
+            .or_default()
             .push((e1, equiv_type_id));
     }
 
     /// Build indexes (call after loading data)
     pub fn build_indexes(&mut self) {
-        self.path_index
-            .build(&self.entities, &self.relations, &self.interner);
+        let _span = tracing::info_span!(
+            "pathdb.build_indexes",
+            entities = self.entities.len(),
+            relations = self.relations.len(),
+            depth = self.path_index.max_depth()
+        )
+        .entered();
+        let build_seconds = metrics::index_build_seconds("path");
+        let _timer = build_seconds.start_timer();
+        match &self.index_policy {
+            PathIndexPolicy::Cube => {
+                let depth = self.path_index.max_depth();
+                if depth > 1 {
+                    let limits = SafeCubeConfig::default();
+                    let estimate = self.estimate_path_index(depth, &limits.sample);
+                    let safe = estimate.safe_depth(depth, limits.max_entries);
+                    if safe < depth {
+                        tracing::warn!(
+                            depth,
+                            safe_depth = safe,
+                            estimated_entries = estimate.multi_hop_entries(depth),
+                            truncated = estimate.truncated,
+                            "path index cube looks too large; consider PathIndexPolicy::SafeCube"
+                        );
+                    }
+                }
+                self.path_index
+                    .build(&self.entities, &self.relations, &self.interner);
+            }
+            PathIndexPolicy::SafeCube(config) => {
+                let estimate = self.estimate_path_index(config.max_depth, &config.sample);
+                let depth = estimate.safe_depth(config.max_depth, config.max_entries);
+                if depth < config.max_depth {
+                    tracing::warn!(
+                        requested = config.max_depth,
+                        depth,
+                        estimated_entries = estimate.multi_hop_entries(config.max_depth),
+                        truncated = estimate.truncated,
+                        "path index depth lowered to fit the estimated budget"
+                    );
+                }
+                self.path_index.set_max_depth(depth);
+                self.path_index
+                    .build(&self.entities, &self.relations, &self.interner);
+            }
+            PathIndexPolicy::Auto(config) => {
+                let plan = self.plan_path_index(config);
+                tracing::debug!(
+                    signatures = plan.signatures.len(),
+                    skipped = plan.skipped,
+                    "auto path index plan"
+                );
+                let signatures = self.planned_signatures(&plan);
+                self.path_index.build_signatures(&self.relations, &signatures);
+            }
+        }
     }
 
     /// Build indexes with a specific path index depth.
     pub fn build_indexes_with_depth(&mut self, depth: usize) {
         self.path_index.set_max_depth(depth);
-        self.path_index
-            .build(&self.entities, &self.relations, &self.interner);
+        self.build_indexes();
     }
 
     /// Attach an async indexing source (used to build fact/text caches off-thread).
@@ -1345,7 +1867,8 @@ impl PathDB {
     // Query Operations
     // ========================================================================
 
-    /// Find entities by type (bitmap result for efficient joins)
+    /// Find entities by type (bitmap result for efficient joins), including
+    /// entities of declared subtypes (see `type_lattice`)
     pub fn find_by_type(&self, type_name: &str) -> Option<&RoaringBitmap> {
         let type_id = self.interner.id_of(type_name)?;
         self.entities.by_type(type_id)
@@ -1400,6 +1923,29 @@ impl PathDB {
         self.text_index.query_all_tokens(self, key_id, &tokens)
     }
 
+    /// Whether `a` and `b` are connected (ignoring direction) via relations whose
+    /// type is in `rel_set`.
+    ///
+    /// Backed by a union-find per relation-type set, built on first use and then
+    /// maintained incrementally by `add_relation`. Unknown relation names are ignored.
+    pub fn same_component(&self, a: u32, b: u32, rel_set: &[&str]) -> bool {
+        let rel_types = self.rel_type_ids(rel_set);
+        self.component_index.same_component(self, a, b, &rel_types)
+    }
+
+    /// Number of entities in `x`'s component over `rel_set` (see `same_component`).
+    pub fn component_size(&self, x: u32, rel_set: &[&str]) -> u32 {
+        let rel_types = self.rel_type_ids(rel_set);
+        self.component_index.component_size(self, x, &rel_types)
+    }
+
+    fn rel_type_ids(&self, rel_set: &[&str]) -> Vec<StrId> {
+        rel_set
+            .iter()
+            .filter_map(|name| self.interner.id_of(name))
+            .collect()
+    }
+
     /// Like `entities_with_attr_fts`, but uses OR semantics (any token match).
     pub fn entities_with_attr_fts_any(&self, key: &str, query: &str) -> RoaringBitmap {
         let Some(key_id) = self.interner.id_of(key) else {
@@ -1453,7 +1999,7 @@ impl PathDB {
         let type_id = self.entities.get_type(entity_id)?;
         let entity_type = self.interner.lookup(type_id)?;
 
-        let mut attrs: HashMap<String, String> = HashMap::new();
+        let mut attrs: BTreeMap<String, String> = BTreeMap::new();
         for (attr_name_id, col) in &self.entities.attrs {
             if let Some(value_id) = col.get(&entity_id) {
                 let Some(name) = self.interner.lookup(*attr_name_id) else {
@@ -1466,19 +2012,96 @@ impl PathDB {
             }
         }
 
+        let label = self.entity_label(entity_id);
         Some(EntityView {
             id: entity_id,
             entity_type,
             attrs,
+            label,
         })
     }
 
-    /// Follow a single relation from source
+    /// Append a copy of `entity_id` to `out` with every attribute column
+    /// (scalar, list and language-tagged), passing each value through
+    /// `rewrite(key, value)`; returns the new id.
+    ///
+    /// Blob payloads are not copied: follow with [`PathDB::import_blobs`],
+    /// which picks up exactly the references that survived `rewrite`.
+    pub fn copy_entity_into(
+        &self,
+        entity_id: u32,
+        out: &mut PathDB,
+        rewrite: impl Fn(&str, String) -> String,
+    ) -> Option<u32> {
+        let view = self.get_entity(entity_id)?;
+        let attrs: Vec<(String, String)> = view
+            .attrs
+            .into_iter()
+            .map(|(k, v)| {
+                let v = rewrite(&k, v);
+                (k, v)
+            })
+            .collect();
+        let new_id = out.add_entity(
+            &view.entity_type,
+            attrs
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect(),
+        );
+
+        let key_name = |key: &StrId| self.interner.lookup(*key);
+        let mut lists: Vec<(String, &Vec<StrId>)> = self
+            .entities
+            .lists
+            .iter()
+            .filter_map(|(key, col)| Some((key_name(key)?, col.get(&entity_id)?)))
+            .collect();
+        lists.sort();
+        for (key, values) in lists {
+            let values: Vec<String> = values
+                .iter()
+                .filter_map(|&v| Some(rewrite(&key, self.interner.lookup(v)?)))
+                .collect();
+            out.entities.set_list(
+                new_id,
+                out.interner.intern(&key),
+                values.iter().map(|v| out.interner.intern(v)).collect(),
+            );
+        }
+
+        let mut tagged: Vec<(String, &Vec<(StrId, StrId)>)> = self
+            .entities
+            .lang_strings
+            .iter()
+            .filter_map(|(key, col)| Some((key_name(key)?, col.get(&entity_id)?)))
+            .collect();
+        tagged.sort();
+        for (key, values) in tagged {
+            let key_id = out.interner.intern(&key);
+            for &(lang, value) in values {
+                let (Some(lang), Some(value)) =
+                    (self.interner.lookup(lang), self.interner.lookup(value))
+                else {
+                    continue;
+                };
+                let lang = out.interner.intern(&lang);
+                let value = out.interner.intern(&rewrite(&key, value));
+                out.entities.set_lang_value(new_id, key_id, lang, value);
+            }
+        }
+        Some(new_id)
+    }
+
+    /// Follow a single relation from source (registered inverses and
+    /// virtual relations included)
     pub fn follow_one(&self, source: u32, rel_type: &str) -> RoaringBitmap {
         let Some(rel_type_id) = self.interner.id_of(rel_type) else {
             return RoaringBitmap::new();
         };
         self.relations.targets(source, rel_type_id)
+            | self.inverse_targets(source, rel_type, None, None)
+            | self.virtual_targets_by_id(source, rel_type_id)
     }
 
     /// Follow a single relation from `source`, counting only edges whose
@@ -1494,10 +2117,49 @@ impl PathDB {
         };
         self.relations
             .targets_with_min_confidence(source, rel_type_id, min_confidence)
+            | self.inverse_targets(source, rel_type, Some(min_confidence), None)
+            | self.virtual_targets_by_id(source, rel_type_id)
     }
 
     /// Follow a path of relations
     pub fn follow_path(&self, start: u32, path: &[&str]) -> RoaringBitmap {
+        self.follow_path_budgeted(
+            start,
+            path,
+            ExecScope::default(),
+            &mut BudgetTracker::unlimited(),
+        )
+    }
+
+    /// Follow a path of relations, counting only edges whose
+    /// `confidence >= min_confidence`.
+    ///
+    /// Note: This intentionally does **not** use the `PathIndex` (which is
+    /// currently confidence-agnostic).
+    pub fn follow_path_with_min_confidence(
+        &self,
+        start: u32,
+        path: &[&str],
+        min_confidence: f32,
+    ) -> RoaringBitmap {
+        self.follow_path_budgeted(
+            start,
+            path,
+            ExecScope {
+                min_confidence: Some(min_confidence),
+                ..ExecScope::default()
+            },
+            &mut BudgetTracker::unlimited(),
+        )
+    }
+
+    pub(crate) fn follow_path_budgeted(
+        &self,
+        start: u32,
+        path: &[&str],
+        scope: ExecScope<'_>,
+        tracker: &mut BudgetTracker,
+    ) -> RoaringBitmap {
         let mut rel_ids = Vec::with_capacity(path.len());
         for rel in path {
             let Some(id) = self.interner.id_of(rel) else {
@@ -1505,19 +2167,52 @@ impl PathDB {
             };
             rel_ids.push(id);
         }
+        self.follow_path_ids_budgeted(start, rel_ids, scope, tracker)
+    }
+
+    /// [`Self::follow_path_budgeted`] for already-resolved relation types.
+    pub(crate) fn follow_path_ids_budgeted(
+        &self,
+        start: u32,
+        rel_ids: Vec<StrId>,
+        scope: ExecScope<'_>,
+        tracker: &mut BudgetTracker,
+    ) -> RoaringBitmap {
+        let ExecScope {
+            min_confidence,
+            context,
+            hubs,
+            ..
+        } = scope;
+        if self.has_virtual_step(&rel_ids) {
+            return self.follow_virtual_path(start, &rel_ids, min_confidence, context, tracker);
+        }
+        if let Some(steps) = self.inverse_steps(&rel_ids) {
+            return self.follow_inverse_path(start, &steps, min_confidence, context, tracker);
+        }
         let path_sig = PathSig::new(rel_ids);
         let path_len = path_sig.len();
         let max_depth = self.path_index.max_depth();
+        self.query_stats.record_path(&path_sig);
+
+        let unfiltered =
+            min_confidence.is_none() && context.is_none() && hubs == supernode::HubPolicy::Expand;
+        if unfiltered {
+            // Try indexed path first
+            if let Some(result) = self.path_index.query(start, &path_sig) {
+                metrics::record_cache("path_index", true);
+                return result.clone();
+            }
 
-        // Try indexed path first
-        if let Some(result) = self.path_index.query(start, &path_sig) {
-            return result.clone();
-        }
-
-        // Try LRU cache for deeper paths
-        if path_len > max_depth {
-            if let Some(result) = self.path_index.query_lru(start, &path_sig) {
-                return result;
+            // Try LRU cache for deeper paths
+            if path_len > max_depth {
+                let cached = self.path_index.query_lru(start, &path_sig);
+                metrics::record_cache("path_lru", cached.is_some());
+                if let Some(result) = cached {
+                    return result;
+                }
+            } else {
+                metrics::record_cache("path_index", false);
             }
         }
 
@@ -1525,88 +2220,148 @@ impl PathDB {
         let mut current = RoaringBitmap::new();
         current.insert(start);
 
-        for rel_type in path {
-            let Some(rel_type_id) = self.interner.id_of(rel_type) else {
-                return RoaringBitmap::new();
-            };
+        for (hop, &rel_type_id) in path_sig.0.iter().enumerate() {
+            let last_hop = hop + 1 == path_len;
+            let expanded = current.len();
             let mut next = RoaringBitmap::new();
+            if min_confidence.is_none() && context.is_none() {
+                // Batch kernel for everything but hubs that need limiting.
+                let mut batch = current.clone();
+                if hubs != supernode::HubPolicy::Expand {
+                    let mut limited = &current & self.supernodes();
+                    limited.remove(start);
+                    batch -= &limited;
+                }
+                let (reached, complete) = self.relations.expand_frontier_budgeted(
+                    &batch,
+                    rel_type_id,
+                    true,
+                    &mut || tracker.visit().is_ok(),
+                );
+                next = reached;
+                if !complete {
+                    return if last_hop { next } else { RoaringBitmap::new() };
+                }
+                current -= &batch;
+            }
             for entity in current.iter() {
-                next |= self.relations.targets(entity, rel_type_id);
+                if tracker.visit().is_err() {
+                    // Only targets reached at full path length are answers.
+                    return if last_hop { next } else { RoaringBitmap::new() };
+                }
+                if let Some(limit) = self.hub_limit(hubs, start, entity) {
+                    if limit > 0 {
+                        let targets = match (min_confidence, context) {
+                            (None, None) => self.relations.targets(entity, rel_type_id),
+                            _ => self
+                                .relations
+                                .outgoing(entity, rel_type_id)
+                                .into_iter()
+                                .filter(|rel| edge_visible(rel, min_confidence, context))
+                                .map(|rel| rel.target)
+                                .collect(),
+                        };
+                        next.extend(targets.iter().take(limit));
+                    }
+                    continue;
+                }
+                next |= match (min_confidence, context) {
+                    (None, None) => self.relations.targets(entity, rel_type_id),
+                    (Some(min), None) => {
+                        self.relations
+                            .targets_with_min_confidence(entity, rel_type_id, min)
+                    }
+                    (min, Some(_)) => self
+                        .relations
+                        .outgoing(entity, rel_type_id)
+                        .into_iter()
+                        .filter(|rel| edge_visible(rel, min, context))
+                        .map(|rel| rel.target)
+                        .collect(),
+                };
             }
+            self.query_stats.record_expansion(rel_type_id, expanded, next.len());
             current = next;
             if current.is_empty() {
                 break;
             }
         }
 
-        if path_len > max_depth && !current.is_empty() {
+        if unfiltered && path_len > max_depth && !current.is_empty() {
             self.path_index.cache_result(path_sig, start, current.clone());
         }
         current
     }
 
-    /// Follow a path of relations, counting only edges whose
-    /// `confidence >= min_confidence`.
+    /// Best combined confidence (under `combiner`) of any walk along `path`
+    /// from `start`, for each target reached.
     ///
-    /// Note: This intentionally does **not** use the `PathIndex` (which is
-    /// currently confidence-agnostic).
-    pub fn follow_path_with_min_confidence(
+    /// Every combiner is monotone, so keeping only the best prefix per entity
+    /// at each hop is exact.
+    pub fn follow_path_scored(
         &self,
         start: u32,
         path: &[&str],
-        min_confidence: f32,
-    ) -> RoaringBitmap {
-        let mut current = RoaringBitmap::new();
-        current.insert(start);
+        combiner: ConfidenceCombiner,
+    ) -> BTreeMap<u32, f32> {
+        self.follow_path_scored_budgeted(
+            start,
+            path,
+            None,
+            None,
+            combiner,
+            &mut BudgetTracker::unlimited(),
+        )
+    }
 
-        for rel_type in path {
-            let Some(rel_type_id) = self.interner.id_of(rel_type) else {
-                return RoaringBitmap::new();
+    fn follow_path_scored_budgeted(
+        &self,
+        start: u32,
+        path: &[&str],
+        min_confidence: Option<f32>,
+        context: Option<&ContextScope>,
+        combiner: ConfidenceCombiner,
+        tracker: &mut BudgetTracker,
+    ) -> BTreeMap<u32, f32> {
+        let min_confidence = min_confidence.map(|c| c.clamp(0.0, 1.0));
+        let mut current = BTreeMap::from([(start, combiner.identity())]);
+        for (hop, rel) in path.iter().enumerate() {
+            let last_hop = hop + 1 == path.len();
+            let Some(rel_type_id) = self.interner.id_of(rel) else {
+                return BTreeMap::new();
             };
-            let mut next = RoaringBitmap::new();
-            for entity in current.iter() {
-                next |=
-                    self.relations
-                        .targets_with_min_confidence(entity, rel_type_id, min_confidence);
+            let mut next: BTreeMap<u32, f32> = BTreeMap::new();
+            for (&entity, &confidence) in &current {
+                if tracker.visit().is_err() {
+                    return if last_hop { next } else { BTreeMap::new() };
+                }
+                for edge in self.relations.outgoing(entity, rel_type_id) {
+                    if !edge_visible(edge, min_confidence, context) {
+                        continue;
+                    }
+                    let combined = combiner.combine(confidence, edge.confidence);
+                    next.entry(edge.target)
+                        .and_modify(|best| *best = best.max(combined))
+                        .or_insert(combined);
+                }
             }
             current = next;
             if current.is_empty() {
                 break;
             }
         }
-
         current
     }
 
     /// Find paths between two entities
     pub fn find_paths(&self, from: u32, to: u32, max_depth: usize) -> Vec<Vec<StrId>> {
-        let mut results = Vec::new();
-        let mut queue: Vec<(u32, Vec<StrId>)> = vec![(from, vec![])];
-        let mut visited = RoaringBitmap::new();
-        visited.insert(from);
-
-        while let Some((current, path)) = queue.pop() {
-            if path.len() >= max_depth {
-                continue;
-            }
-
-            // Check all outgoing relations
-            for rel in &self.relations.relations {
-                if rel.source == current && !visited.contains(rel.target) {
-                    let mut new_path = path.clone();
-                    new_path.push(rel.rel_type);
-
-                    if rel.target == to {
-                        results.push(new_path);
-                    } else {
-                        visited.insert(rel.target);
-                        queue.push((rel.target, new_path));
-                    }
-                }
-            }
-        }
-
-        results
+        self.find_paths_budgeted(
+            from,
+            to,
+            max_depth,
+            ExecScope::default(),
+            &mut BudgetTracker::unlimited(),
+        )
     }
 
     /// Find paths between two entities, using only edges whose
@@ -1618,31 +2373,128 @@ impl PathDB {
         max_depth: usize,
         min_confidence: f32,
     ) -> Vec<Vec<StrId>> {
-        let min_confidence = min_confidence.clamp(0.0, 1.0);
+        self.find_paths_budgeted(
+            from,
+            to,
+            max_depth,
+            ExecScope {
+                min_confidence: Some(min_confidence),
+                ..ExecScope::default()
+            },
+            &mut BudgetTracker::unlimited(),
+        )
+    }
+
+    /// Find paths between two entities whose edge confidences, combined under
+    /// `combiner`, are `>= min_path_confidence`.
+    pub fn find_paths_with_path_confidence(
+        &self,
+        from: u32,
+        to: u32,
+        max_depth: usize,
+        combiner: ConfidenceCombiner,
+        min_path_confidence: f32,
+    ) -> Vec<Vec<StrId>> {
+        self.find_paths_budgeted(
+            from,
+            to,
+            max_depth,
+            ExecScope {
+                path_filter: Some(PathConfidenceFilter {
+                    combiner,
+                    min: min_path_confidence.clamp(0.0, 1.0),
+                }),
+                ..ExecScope::default()
+            },
+            &mut BudgetTracker::unlimited(),
+        )
+    }
+
+    /// Find paths between two entities, stopping early when `budget` runs out.
+    ///
+    /// On `BudgetExceeded` the returned paths are the ones found so far.
+    pub fn find_paths_with_budget(
+        &self,
+        from: u32,
+        to: u32,
+        max_depth: usize,
+        budget: &QueryBudget,
+    ) -> Budgeted<Vec<Vec<StrId>>> {
+        let mut tracker = BudgetTracker::new(budget);
+        let paths =
+            self.find_paths_budgeted(from, to, max_depth, ExecScope::default(), &mut tracker);
+        tracker.finish(paths)
+    }
+
+    fn find_paths_budgeted(
+        &self,
+        from: u32,
+        to: u32,
+        max_depth: usize,
+        scope: ExecScope<'_>,
+        tracker: &mut BudgetTracker,
+    ) -> Vec<Vec<StrId>> {
+        let ExecScope {
+            min_confidence,
+            path_filter,
+            context,
+            hubs,
+        } = scope;
+        let min_confidence = min_confidence.map(|c| c.clamp(0.0, 1.0));
+        let combiner = path_filter.map_or(ConfidenceCombiner::Product, |f| f.combiner);
 
         let mut results = Vec::new();
-        let mut queue: Vec<(u32, Vec<StrId>)> = vec![(from, vec![])];
+        let mut queue: Vec<(u32, Vec<StrId>, f32)> = vec![(from, vec![], combiner.identity())];
         let mut visited = RoaringBitmap::new();
         visited.insert(from);
 
-        while let Some((current, path)) = queue.pop() {
+        while let Some((current, path, path_confidence)) = queue.pop() {
             if path.len() >= max_depth {
                 continue;
             }
+            if tracker.visit().is_err() {
+                break;
+            }
+            // Hubs are cut (or expanded towards a sample of their neighbours).
+            let sample = self.hub_limit(hubs, from, current);
+            if sample == Some(0) {
+                continue;
+            }
+            let mut sampled: HashMap<StrId, RoaringBitmap> = HashMap::new();
 
-            for rel in &self.relations.relations {
-                if rel.confidence < min_confidence {
+            for rel in self.relations.outgoing_by_type_probe(current) {
+                if !edge_visible(rel, min_confidence, context) {
                     continue;
                 }
-                if rel.source == current && !visited.contains(rel.target) {
+                if let Some(limit) = sample {
+                    let allowed = sampled.entry(rel.rel_type).or_insert_with(|| {
+                        self.relations
+                            .targets(current, rel.rel_type)
+                            .iter()
+                            .take(limit)
+                            .collect()
+                    });
+                    if !allowed.contains(rel.target) {
+                        continue;
+                    }
+                }
+                if !visited.contains(rel.target) {
+                    // Each edge is charged too, so one wide node cannot run
+                    // past the budget.
+                    if tracker.visit().is_err() {
+                        return results;
+                    }
                     let mut new_path = path.clone();
                     new_path.push(rel.rel_type);
+                    let new_confidence = combiner.combine(path_confidence, rel.confidence);
 
                     if rel.target == to {
-                        results.push(new_path);
+                        if path_filter.is_none_or(|f| new_confidence >= f.min) {
+                            results.push(new_path);
+                        }
                     } else {
                         visited.insert(rel.target);
-                        queue.push((rel.target, new_path));
+                        queue.push((rel.target, new_path, new_confidence));
                     }
                 }
             }
@@ -1689,12 +2541,22 @@ impl PathDB {
 
     /// Serialize to binary format
     pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        self.encode(true)
+    }
+
+    /// `to_bytes` without the blob sidecar (attribute references are kept;
+    /// see `blob_store`).
+    pub fn to_bytes_without_blobs(&self) -> Result<Vec<u8>> {
+        self.encode(false)
+    }
+
+    fn encode(&self, include_blobs: bool) -> Result<Vec<u8>> {
         let interner_bytes = self.interner.to_bytes();
         let db_bytes = bincode::serialize(&(
             &self.entities,
             &self.relations,
             &self.path_index,
-            &self.equivalences,
+            ordered::SortedMap(&self.equivalences),
             &self.confidence_index,
         ))?;
 
@@ -1711,6 +2573,23 @@ impl PathDB {
         result.extend_from_slice(&(db_bytes.len() as u64).to_le_bytes());
         result.extend_from_slice(&db_bytes);
 
+        // Optional trailing sections: 4-byte tag + length-prefixed payload,
+        // written only when non-empty (older readers ignore them).
+        let mut trailing = |tag: &[u8; 4], payload: Vec<u8>| {
+            result.extend_from_slice(tag);
+            result.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+            result.extend_from_slice(&payload);
+        };
+        if !self.entities.lists.is_empty() {
+            trailing(SECTION_LISTS, bincode::serialize(&self.entities.list_section())?);
+        }
+        if !self.entities.lang_strings.is_empty() {
+            trailing(SECTION_LANG, bincode::serialize(&self.entities.lang_section())?);
+        }
+        if include_blobs && !self.blobs.is_empty() {
+            trailing(SECTION_BLOBS, bincode::serialize(&self.blobs.section())?);
+        }
+
         Ok(result)
     }
 
@@ -1718,34 +2597,50 @@ impl PathDB {
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
         // Check header
         if bytes.len() < 8 || &bytes[0..4] != b"AXPD" {
-            return Err(anyhow::anyhow!("Invalid PathDB file"));
+            return Err(PathDbError::InvalidFormat);
         }
 
-        let version = u32::from_le_bytes(bytes[4..8].try_into()?);
+        let version = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
         if version != 1 {
-            return Err(anyhow::anyhow!("Unsupported PathDB version: {}", version));
+            return Err(PathDbError::UnsupportedVersion(version));
         }
 
         let mut offset = 8;
 
         // Interner
-        let interner_len = u64::from_le_bytes(bytes[offset..offset + 8].try_into()?) as usize;
-        offset += 8;
-        let interner = StringInterner::from_bytes(&bytes[offset..offset + interner_len])?;
-        offset += interner_len;
+        let interner_bytes = read_len_prefixed(bytes, &mut offset, "interner")?;
+        let interner = StringInterner::from_bytes(interner_bytes)?;
 
         // DB
-        let db_len = u64::from_le_bytes(bytes[offset..offset + 8].try_into()?) as usize;
-        offset += 8;
-        let (entities, relations, path_index, equivalences, confidence_index): (
-            EntityStore,
-            RelationStore,
-            PathIndex,
-            HashMap<u32, Vec<(u32, StrId)>>,
-            Vec<f32>,
-        ) = bincode::deserialize(&bytes[offset..offset + db_len])?;
-
-        Ok(Self {
+        let db_bytes = read_len_prefixed(bytes, &mut offset, "db")?;
+        let (mut entities, relations, path_index, equivalences, confidence_index): DbSection =
+            bounded_bincode_options(db_bytes.len()).deserialize(db_bytes)?;
+        let mut blobs = BlobStore::default();
+        while offset < bytes.len() {
+            let tag = bytes
+                .get(offset..offset + 4)
+                .ok_or_else(|| PathDbError::Truncated {
+                    section: "trailing".to_string(),
+                })?;
+            offset += 4;
+            let payload = read_len_prefixed(bytes, &mut offset, "trailing")?;
+            let options = bounded_bincode_options(payload.len());
+            match tag {
+                t if t == SECTION_LISTS => {
+                    entities.load_list_section(options.deserialize(payload)?);
+                }
+                t if t == SECTION_LANG => {
+                    entities.load_lang_section(options.deserialize(payload)?);
+                }
+                t if t == SECTION_BLOBS => {
+                    blobs = BlobStore::from_section(options.deserialize(payload)?)?;
+                }
+                // Sections from newer writers.
+                _ => {}
+            }
+        }
+
+        let mut db = Self {
             db_token: DbToken::new(),
             interner,
             entities,
@@ -1755,11 +2650,148 @@ impl PathDB {
             confidence_index,
             fact_index: FactIndexCache::default(),
             text_index: TextIndexCache::default(),
+            component_index: ComponentIndexCache::default(),
+            cardinality: CardinalityCache::default(),
+            type_lattice: TypeLattice::default(),
+            inverses: InverseRegistry::default(),
+            virtual_relations: VirtualRelationRegistry::default(),
+            multiplicities: MultiplicityRegistry::default(),
+            composite_indexes: CompositeIndexes::default(),
+            blobs,
+            query_stats: auto_index::QueryStats::default(),
+            index_policy: PathIndexPolicy::default(),
+            label_policy: LabelPolicy::default(),
             index_sidecar: Mutex::new(None),
-        })
+        };
+        db.validate_loaded()?;
+        db.rebuild_type_lattice();
+        db.rebuild_inverses();
+        db.rebuild_multiplicities();
+        db.rebuild_composite_indexes();
+        db.rebuild_virtual_relations();
+        db.relations.rebuild_supernodes();
+        db.relations.rebuild_edge_filters();
+        Ok(db)
+    }
+
+    /// Structural checks for a freshly deserialized snapshot.
+    ///
+    /// `.axpd` files are untrusted input: every id the in-memory indexes later
+    /// dereference must be in range, so corrupt files fail here instead of
+    /// panicking during queries.
+    fn validate_loaded(&self) -> Result<()> {
+        let bad = |what: &str| Err(PathDbError::Corrupt(what.to_string()));
+        let n_entities = self.entities.next_id as usize;
+        let n_relations = self.relations.relations.len();
+        let str_ok = |id: &StrId| self.interner.contains_id(*id);
+        let entity_ok = |id: u32| (id as usize) < n_entities;
+
+        if self.entities.types.len() != n_entities {
+            return bad("entity type column length mismatch");
+        }
+        if !self.entities.types.iter().all(str_ok) {
+            return bad("entity type id out of range");
+        }
+        for (key, col) in &self.entities.attrs {
+            if !str_ok(key) || !col.iter().all(|(e, v)| entity_ok(*e) && str_ok(v)) {
+                return bad("entity attribute out of range");
+            }
+        }
+        for (type_id, ids) in &self.entities.type_index {
+            if !str_ok(type_id) || ids.max().is_some_and(|m| !entity_ok(m)) {
+                return bad("entity type index out of range");
+            }
+        }
+        for (key, col) in &self.entities.lists {
+            if !str_ok(key) || !col.iter().all(|(e, vs)| entity_ok(*e) && vs.iter().all(str_ok)) {
+                return bad("entity list attribute out of range");
+            }
+        }
+        for (key, col) in &self.entities.lang_strings {
+            let pairs_ok =
+                |vs: &Vec<(StrId, StrId)>| vs.iter().all(|(l, v)| str_ok(l) && str_ok(v));
+            if !str_ok(key) || !col.iter().all(|(e, vs)| entity_ok(*e) && pairs_ok(vs)) {
+                return bad("entity language attribute out of range");
+            }
+        }
+
+        for rel in &self.relations.relations {
+            if !entity_ok(rel.source) || !entity_ok(rel.target) || !str_ok(&rel.rel_type) {
+                return bad("relation endpoint or type out of range");
+            }
+            if !rel.attrs.iter().all(|(k, v)| str_ok(k) && str_ok(v)) {
+                return bad("relation attribute out of range");
+            }
+        }
+        let rel_ok = |id: &u32| (*id as usize) < n_relations;
+        for index in [&self.relations.forward_index, &self.relations.backward_index] {
+            for ((entity, rel_type), ids) in index.iter() {
+                if !entity_ok(entity) || !str_ok(&rel_type) || !ids.iter().all(rel_ok) {
+                    return bad("relation index out of range");
+                }
+            }
+        }
+        for (rel_type, ids) in &self.relations.type_index {
+            if !str_ok(rel_type) || ids.max().is_some_and(|m| !rel_ok(&m)) {
+                return bad("relation type index out of range");
+            }
+        }
+        if self.confidence_index.len() != n_relations {
+            return bad("confidence index length mismatch");
+        }
+
+        for (entity, equivs) in &self.equivalences {
+            if !entity_ok(*entity) || !equivs.iter().all(|(e, t)| entity_ok(*e) && str_ok(t)) {
+                return bad("equivalence out of range");
+            }
+        }
+        Ok(())
     }
 }
 
+/// Trailing `.axpd` section tags.
+const SECTION_LISTS: &[u8; 4] = b"LIST";
+const SECTION_LANG: &[u8; 4] = b"LANG";
+const SECTION_BLOBS: &[u8; 4] = b"BLOB";
+
+/// Read a `u64`-length-prefixed section, advancing `offset` (bounds-checked).
+fn read_len_prefixed<'a>(bytes: &'a [u8], offset: &mut usize, section: &str) -> Result<&'a [u8]> {
+    let truncated = || PathDbError::Truncated {
+        section: section.to_string(),
+    };
+    let len_bytes: [u8; 8] = bytes
+        .get(*offset..*offset + 8)
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(truncated)?;
+    let len = u64::from_le_bytes(len_bytes);
+    let start = *offset + 8;
+    let end = usize::try_from(len)
+        .ok()
+        .and_then(|len| start.checked_add(len))
+        .filter(|&end| end <= bytes.len())
+        .ok_or_else(truncated)?;
+    *offset = end;
+    Ok(&bytes[start..end])
+}
+
+/// The `db` section of a snapshot, in encoding order (see `PathDB::encode`).
+type DbSection = (
+    EntityStore,
+    RelationStore,
+    PathIndex,
+    HashMap<u32, Vec<(u32, StrId)>>,
+    Vec<f32>,
+);
+
+/// `bincode::deserialize`-compatible options that refuse to read (and so to
+/// allocate) more than `limit` bytes.
+fn bounded_bincode_options(limit: usize) -> impl bincode::Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(limit as u64)
+}
+
 impl Default for PathDB {
     fn default() -> Self {
         Self::new()
@@ -2078,7 +3110,13 @@ fn levenshtein_with_max(value: &str, needle_chars: &[char], max_dist: usize) ->
 // ============================================================================
 
 /// SQL-like query for PathDB
-#[derive(Debug, Clone)]
+///
+/// Serializes as the tagged JSON tree documented in [`query_json`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    into = "query_json::PathQueryJson",
+    try_from = "query_json::PathQueryJson"
+)]
 pub enum PathQuery {
     /// SELECT * FROM entities WHERE type = ?
     SelectByType(String),
@@ -2101,12 +3139,51 @@ pub enum PathQuery {
         base: Box<PathQuery>,
         min_confidence: f32,
     },
+    /// Keep only answers reached by a path whose edge confidences, combined
+    /// under `combiner`, are `>= min_path_confidence`. Applies to
+    /// `SelectRelated`, `FollowPath` and `FindPaths` inside `base`; a nested
+    /// `WithPathConfidence` replaces the outer one.
+    WithPathConfidence {
+        base: Box<PathQuery>,
+        min_path_confidence: f32,
+        combiner: ConfidenceCombiner,
+    },
+}
+
+/// Path-confidence filter in scope during query execution.
+#[derive(Debug, Clone, Copy)]
+struct PathConfidenceFilter {
+    combiner: ConfidenceCombiner,
+    min: f32,
+}
+
+/// Filters in scope while executing a (sub)query.
+#[derive(Debug, Clone, Copy, Default)]
+struct ExecScope<'a> {
+    min_confidence: Option<f32>,
+    path_filter: Option<PathConfidenceFilter>,
+    context: Option<&'a ContextScope>,
+    hubs: supernode::HubPolicy,
+}
+
+/// Edge passes the per-edge confidence threshold and is visible in `context`.
+fn edge_visible(
+    rel: &Relation,
+    min_confidence: Option<f32>,
+    context: Option<&ContextScope>,
+) -> bool {
+    min_confidence.is_none_or(|min| rel.confidence >= min)
+        && context.is_none_or(|scope| scope.allows_relation(rel))
 }
 
 /// Optional execution trace events (recorded only when proofs are enabled).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "op", rename_all = "snake_case")]
 pub enum QueryExecutionEvent {
+    /// Recorded first when the query runs in a context/world.
+    InContext {
+        world: u32,
+    },
     SelectByType {
         type_name: String,
     },
@@ -2128,24 +3205,77 @@ pub enum QueryExecutionEvent {
     WithConfidence {
         min_confidence: f32,
     },
+    WithPathConfidence {
+        min_path_confidence: f32,
+        combiner: ConfidenceCombiner,
+    },
 }
 
 impl PathDB {
     /// Execute a PathQuery
     pub fn execute(&self, query: &PathQuery) -> RoaringBitmap {
+        self.execute_in(query, &QueryContext::default())
+    }
+
+    /// Execute a PathQuery seeing only the facts that hold in `ctx.world`
+    /// (see [`context`]).
+    pub fn execute_in(&self, query: &PathQuery, ctx: &QueryContext) -> RoaringBitmap {
         use crate::proof_mode::{NoProof, ProofJournal};
         let mut journal: ProofJournal<NoProof, QueryExecutionEvent> = ProofJournal::new();
-        self.execute_with_journal(query, &mut journal)
+        self.execute_with_journal(query, ctx, &mut journal, &mut BudgetTracker::unlimited())
+    }
+
+    /// Execute a PathQuery seeing only what `scope` allows, at every hop
+    /// (see [`ContextScope::over`]).
+    pub fn execute_within(&self, query: &PathQuery, scope: &ContextScope) -> RoaringBitmap {
+        use crate::proof_mode::{NoProof, ProofJournal};
+        let mut journal: ProofJournal<NoProof, QueryExecutionEvent> = ProofJournal::new();
+        let scope = ExecScope {
+            context: Some(scope),
+            ..ExecScope::default()
+        };
+        self.execute_scoped(query, &mut journal, scope, &mut BudgetTracker::unlimited())
+    }
+
+    /// Execute a PathQuery under a time/work budget.
+    ///
+    /// When the budget runs out, execution stops and the (partial) result is
+    /// returned with `QueryStatus::BudgetExceeded`; see [`budget`] for the
+    /// partial-result guarantees.
+    pub fn execute_with_budget(
+        &self,
+        query: &PathQuery,
+        budget: &QueryBudget,
+    ) -> Budgeted<RoaringBitmap> {
+        use crate::proof_mode::{NoProof, ProofJournal};
+        let mut journal: ProofJournal<NoProof, QueryExecutionEvent> = ProofJournal::new();
+        let mut tracker = BudgetTracker::new(budget);
+        let result =
+            self.execute_with_journal(query, &QueryContext::default(), &mut journal, &mut tracker);
+        if let Some(exceeded) = tracker.exceeded() {
+            tracing::debug!(%exceeded, "PathQuery stopped early");
+        }
+        tracker.finish(result)
     }
 
     /// Execute a PathQuery and optionally capture a trace (generic over `ProofMode`).
     pub fn execute_with_mode<M: crate::proof_mode::ProofMode>(
         &self,
         query: &PathQuery,
+    ) -> crate::proof_mode::Proved<M, RoaringBitmap, Vec<QueryExecutionEvent>> {
+        self.execute_with_mode_in(query, &QueryContext::default())
+    }
+
+    /// [`Self::execute_with_mode`] scoped to `ctx.world`.
+    pub fn execute_with_mode_in<M: crate::proof_mode::ProofMode>(
+        &self,
+        query: &PathQuery,
+        ctx: &QueryContext,
     ) -> crate::proof_mode::Proved<M, RoaringBitmap, Vec<QueryExecutionEvent>> {
         use crate::proof_mode::{ProofJournal, Proved};
         let mut journal: ProofJournal<M, QueryExecutionEvent> = ProofJournal::new();
-        let result = self.execute_with_journal(query, &mut journal);
+        let result =
+            self.execute_with_journal(query, ctx, &mut journal, &mut BudgetTracker::unlimited());
         Proved {
             value: result,
             proof: journal.into_entries(),
@@ -2155,32 +3285,77 @@ impl PathDB {
     fn execute_with_journal<M: crate::proof_mode::ProofMode>(
         &self,
         query: &PathQuery,
+        ctx: &QueryContext,
         journal: &mut crate::proof_mode::ProofJournal<M, QueryExecutionEvent>,
+        tracker: &mut BudgetTracker,
     ) -> RoaringBitmap {
-        self.execute_with_journal_conf(query, journal, None)
+        let _span = tracing::debug_span!("pathdb.execute", world = ctx.world).entered();
+        let _timer = metrics::query_seconds().start_timer();
+        let context = self.resolve_context(ctx);
+        if let Some(world) = ctx.world {
+            journal.record(|| QueryExecutionEvent::InContext { world });
+        }
+        let scope = ExecScope {
+            context: context.as_ref(),
+            hubs: ctx.hubs,
+            ..ExecScope::default()
+        };
+        self.execute_scoped(query, journal, scope, tracker)
     }
 
-    fn execute_with_journal_conf<M: crate::proof_mode::ProofMode>(
+    fn execute_scoped<M: crate::proof_mode::ProofMode>(
         &self,
         query: &PathQuery,
         journal: &mut crate::proof_mode::ProofJournal<M, QueryExecutionEvent>,
-        min_confidence: Option<f32>,
+        scope: ExecScope<'_>,
+        tracker: &mut BudgetTracker,
     ) -> RoaringBitmap {
         match query {
             PathQuery::SelectByType(type_name) => {
                 journal.record(|| QueryExecutionEvent::SelectByType {
                     type_name: type_name.clone(),
                 });
-                self.find_by_type(type_name).cloned().unwrap_or_default()
+                let all = self.find_by_type(type_name).cloned().unwrap_or_default();
+                match scope.context {
+                    None => all,
+                    Some(context) => all & &context.entities,
+                }
             }
             PathQuery::SelectRelated(source, rel_type) => {
                 journal.record(|| QueryExecutionEvent::SelectRelated {
                     source: *source,
                     rel_type: rel_type.clone(),
                 });
-                match min_confidence {
-                    None => self.follow_one(*source, rel_type),
-                    Some(min) => self.follow_one_with_min_confidence(*source, rel_type, min),
+                if let Some(rel_type_id) = self.interner.id_of(rel_type) {
+                    self.query_stats.record_path(&PathSig::new(vec![rel_type_id]));
+                }
+                // A single edge combines to its own confidence under every
+                // policy, so a path filter is just one more edge threshold.
+                let min_confidence = match (scope.min_confidence, scope.path_filter) {
+                    (edge, None) => edge,
+                    (None, Some(f)) => Some(f.min),
+                    (Some(edge), Some(f)) => Some(edge.max(f.min)),
+                };
+                match (min_confidence, scope.context) {
+                    (None, None) => self.follow_one(*source, rel_type),
+                    (Some(min), None) => {
+                        self.follow_one_with_min_confidence(*source, rel_type, min)
+                    }
+                    (min, Some(context)) => {
+                        let Some(rel_type_id) = self.interner.id_of(rel_type) else {
+                            return RoaringBitmap::new();
+                        };
+                        let forward: RoaringBitmap = self
+                            .relations
+                            .outgoing(*source, rel_type_id)
+                            .into_iter()
+                            .filter(|rel| edge_visible(rel, min, scope.context))
+                            .map(|rel| rel.target)
+                            .collect();
+                        forward
+                            | self.inverse_targets(*source, rel_type, min, scope.context)
+                            | context.clip(self.virtual_targets_by_id(*source, rel_type_id))
+                    }
                 }
             }
             PathQuery::FollowPath { start, path } => {
@@ -2189,9 +3364,21 @@ impl PathDB {
                     path: path.clone(),
                 });
                 let path_refs: Vec<&str> = path.iter().map(|s| s.as_str()).collect();
-                match min_confidence {
-                    None => self.follow_path(*start, &path_refs),
-                    Some(min) => self.follow_path_with_min_confidence(*start, &path_refs, min),
+                match scope.path_filter {
+                    None => self.follow_path_budgeted(*start, &path_refs, scope, tracker),
+                    Some(f) => self
+                        .follow_path_scored_budgeted(
+                            *start,
+                            &path_refs,
+                            scope.min_confidence,
+                            scope.context,
+                            f.combiner,
+                            tracker,
+                        )
+                        .into_iter()
+                        .filter(|&(_, confidence)| confidence >= f.min)
+                        .map(|(target, _)| target)
+                        .collect(),
                 }
             }
             PathQuery::FindPaths {
@@ -2205,10 +3392,7 @@ impl PathDB {
                     max_depth: *max_depth,
                 });
                 // Returns entities at the end of paths (just the target)
-                let paths = match min_confidence {
-                    None => self.find_paths(*from, *to, *max_depth),
-                    Some(min) => self.find_paths_with_min_confidence(*from, *to, *max_depth, min),
-                };
+                let paths = self.find_paths_budgeted(*from, *to, *max_depth, scope, tracker);
                 let mut result = RoaringBitmap::new();
                 if !paths.is_empty() {
                     result.insert(*to);
@@ -2217,14 +3401,14 @@ impl PathDB {
             }
             PathQuery::Join(left, right) => {
                 journal.record(|| QueryExecutionEvent::Join);
-                let left_result = self.execute_with_journal_conf(left, journal, min_confidence);
-                let right_result = self.execute_with_journal_conf(right, journal, min_confidence);
+                let left_result = self.execute_scoped(left, journal, scope, tracker);
+                let right_result = self.execute_scoped(right, journal, scope, tracker);
                 self.join(&left_result, &right_result)
             }
             PathQuery::Union(left, right) => {
                 journal.record(|| QueryExecutionEvent::Union);
-                let left_result = self.execute_with_journal_conf(left, journal, min_confidence);
-                let right_result = self.execute_with_journal_conf(right, journal, min_confidence);
+                let left_result = self.execute_scoped(left, journal, scope, tracker);
+                let right_result = self.execute_scoped(right, journal, scope, tracker);
                 self.union(&left_result, &right_result)
             }
             PathQuery::WithConfidence {
@@ -2234,11 +3418,33 @@ impl PathDB {
                 journal.record(|| QueryExecutionEvent::WithConfidence {
                     min_confidence: *edge_min_confidence,
                 });
-                let next_min = match min_confidence {
+                let next_min = match scope.min_confidence {
                     None => *edge_min_confidence,
                     Some(prev) => prev.max(*edge_min_confidence),
                 };
-                self.execute_with_journal_conf(base, journal, Some(next_min))
+                let scope = ExecScope {
+                    min_confidence: Some(next_min),
+                    ..scope
+                };
+                self.execute_scoped(base, journal, scope, tracker)
+            }
+            PathQuery::WithPathConfidence {
+                base,
+                min_path_confidence,
+                combiner,
+            } => {
+                journal.record(|| QueryExecutionEvent::WithPathConfidence {
+                    min_path_confidence: *min_path_confidence,
+                    combiner: *combiner,
+                });
+                let scope = ExecScope {
+                    path_filter: Some(PathConfidenceFilter {
+                        combiner: *combiner,
+                        min: min_path_confidence.clamp(0.0, 1.0),
+                    }),
+                    ..scope
+                };
+                self.execute_scoped(base, journal, scope, tracker)
             }
         }
     }
@@ -0,0 +1,178 @@
+//! Ordered (list-valued) entity attributes.
+//!
+//! Ordinary attributes hold one interned string per `(entity, key)`, so
+//! ordered data (workflow step order, enum value lists) used to be flattened
+//! into one delimited string or spread over `step_1`, `step_2`, ... keys.
+//! List attributes keep an ordered `Vec` of interned values per
+//! `(entity, key)` instead, in their own columns next to the scalar ones:
+//!
+//! - positional access: [`PathDB::list_attr_item`], [`PathDB::list_attr_position`];
+//! - containment: [`PathDB::entities_with_list_containing`] and
+//!   [`PathDB::entities_with_list_item_at`].
+//!
+//! A key can be scalar on one entity and a list on another; the two columns
+//! are independent. List columns are stored in an optional trailing `.axpd`
+//! section (omitted when there are none, so snapshots without lists keep
+//! their bytes) and as `entity_list_item` rows in `PathDBExportV1`.
+
+use roaring::RoaringBitmap;
+
+use crate::error::Result;
+use crate::{AttrColumn, EntityStore, PathDB, PathDbError, StrId};
+
+/// Serialized list columns: `(key, [(entity, values)])`, sorted.
+pub(crate) type ListSection = Vec<(StrId, Vec<(u32, Vec<StrId>)>)>;
+
+impl EntityStore {
+    /// The list stored under `attr_name`, if any.
+    pub fn get_list(&self, entity_id: u32, attr_name: StrId) -> Option<&[StrId]> {
+        self.lists
+            .get(&attr_name)?
+            .get(&entity_id)
+            .map(Vec::as_slice)
+    }
+
+    /// Item `position` (0-based) of the list stored under `attr_name`.
+    pub fn get_list_item(
+        &self,
+        entity_id: u32,
+        attr_name: StrId,
+        position: usize,
+    ) -> Option<StrId> {
+        self.get_list(entity_id, attr_name)?.get(position).copied()
+    }
+
+    /// Find all entities whose `attr_name` list contains `value`.
+    pub fn entities_with_list_containing(&self, attr_name: StrId, value: StrId) -> RoaringBitmap {
+        let Some(col) = self.lists.get(&attr_name) else {
+            return RoaringBitmap::new();
+        };
+        col.iter()
+            .filter(|(_, values)| values.contains(&value))
+            .map(|(&entity_id, _)| entity_id)
+            .collect()
+    }
+
+    pub(crate) fn set_list(&mut self, entity_id: u32, attr_name: StrId, values: Vec<StrId>) {
+        self.lists
+            .entry(attr_name)
+            .or_default()
+            .insert(entity_id, values);
+    }
+
+    pub(crate) fn list_section(&self) -> ListSection {
+        let mut section: ListSection = self
+            .lists
+            .iter()
+            .map(|(&key, col)| {
+                let rows = col.iter().map(|(&e, values)| (e, values.clone()));
+                (key, rows.collect())
+            })
+            .collect();
+        section.sort_unstable_by_key(|(key, _)| *key);
+        section
+    }
+
+    pub(crate) fn load_list_section(&mut self, section: ListSection) {
+        self.lists = section
+            .into_iter()
+            .map(|(key, rows)| (key, rows.into_iter().collect::<AttrColumn<_>>()))
+            .collect();
+    }
+}
+
+impl PathDB {
+    /// Set (replace) the list attribute `key` of `entity_id`.
+    pub fn set_list_attr(&mut self, entity_id: u32, key: &str, values: &[&str]) -> Result<()> {
+        if entity_id as usize >= self.entities.types.len() {
+            return Err(PathDbError::UnknownEntity(entity_id));
+        }
+        let key_id = self.interner.intern(key);
+        let values = values.iter().map(|v| self.interner.intern(v)).collect();
+        self.entities.set_list(entity_id, key_id, values);
+        Ok(())
+    }
+
+    /// Append `value` to the list attribute `key` (creating it); returns the
+    /// new item's position.
+    pub fn push_list_attr(&mut self, entity_id: u32, key: &str, value: &str) -> Result<usize> {
+        if entity_id as usize >= self.entities.types.len() {
+            return Err(PathDbError::UnknownEntity(entity_id));
+        }
+        let key_id = self.interner.intern(key);
+        let value_id = self.interner.intern(value);
+        let list = self
+            .entities
+            .lists
+            .entry(key_id)
+            .or_default()
+            .get_or_insert_default(entity_id);
+        list.push(value_id);
+        Ok(list.len() - 1)
+    }
+
+    /// Remove the list attribute `key`; returns whether there was one.
+    pub fn remove_list_attr(&mut self, entity_id: u32, key: &str) -> bool {
+        let Some(key_id) = self.interner.id_of(key) else {
+            return false;
+        };
+        self.entities
+            .lists
+            .get_mut(&key_id)
+            .is_some_and(|col| col.remove(&entity_id).is_some())
+    }
+
+    /// The list attribute `key` of `entity_id`, in order.
+    pub fn list_attr(&self, entity_id: u32, key: &str) -> Option<Vec<String>> {
+        let key_id = self.interner.id_of(key)?;
+        let values = self.entities.get_list(entity_id, key_id)?;
+        values.iter().map(|&v| self.interner.lookup(v)).collect()
+    }
+
+    /// Item `position` (0-based) of the list attribute `key`.
+    pub fn list_attr_item(&self, entity_id: u32, key: &str, position: usize) -> Option<String> {
+        let key_id = self.interner.id_of(key)?;
+        let value = self.entities.get_list_item(entity_id, key_id, position)?;
+        self.interner.lookup(value)
+    }
+
+    /// First position of `value` in the list attribute `key`.
+    pub fn list_attr_position(&self, entity_id: u32, key: &str, value: &str) -> Option<usize> {
+        let key_id = self.interner.id_of(key)?;
+        let value_id = self.interner.id_of(value)?;
+        self.entities
+            .get_list(entity_id, key_id)?
+            .iter()
+            .position(|&v| v == value_id)
+    }
+
+    /// Entities whose list attribute `key` contains `value`.
+    pub fn entities_with_list_containing(&self, key: &str, value: &str) -> RoaringBitmap {
+        let (Some(key_id), Some(value_id)) = (self.interner.id_of(key), self.interner.id_of(value))
+        else {
+            return RoaringBitmap::new();
+        };
+        self.entities
+            .entities_with_list_containing(key_id, value_id)
+    }
+
+    /// Entities whose list attribute `key` has `value` at `position`.
+    pub fn entities_with_list_item_at(
+        &self,
+        key: &str,
+        position: usize,
+        value: &str,
+    ) -> RoaringBitmap {
+        let (Some(key_id), Some(value_id)) = (self.interner.id_of(key), self.interner.id_of(value))
+        else {
+            return RoaringBitmap::new();
+        };
+        let Some(col) = self.entities.lists.get(&key_id) else {
+            return RoaringBitmap::new();
+        };
+        col.iter()
+            .filter(|(_, values)| values.get(position) == Some(&value_id))
+            .map(|(&entity_id, _)| entity_id)
+            .collect()
+    }
+}
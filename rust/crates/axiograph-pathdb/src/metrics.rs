@@ -0,0 +1,380 @@
+//! Process-wide metrics in Prometheus text format.
+//!
+//! A deliberately small registry (counters, gauges, histograms) with no
+//! external dependencies: metric handles are atomics, so recording is cheap
+//! enough to leave on unconditionally, and rendering is only paid when a
+//! `/metrics` endpoint is scraped.
+//!
+//! PathDB records query latency, index build durations and cache
+//! hits/misses here; the storage layer and the CLI server register their own
+//! series in the same [`global`] registry so one endpoint exposes everything.
+//!
+//! Cache hit rates are exported as `axiograph_cache_requests_total{cache, result}`
+//! counters (`result` is `hit` or `miss`); compute the ratio in PromQL.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Default latency buckets (seconds).
+pub const LATENCY_BUCKETS: &[f64] = &[
+    0.000_1, 0.000_5, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 30.0,
+];
+
+/// Monotonic counter.
+#[derive(Debug, Default)]
+pub struct Counter(AtomicU64);
+
+impl Counter {
+    pub fn inc(&self) {
+        self.add(1);
+    }
+
+    pub fn add(&self, n: u64) {
+        self.0.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Gauge (value that can go up and down).
+#[derive(Debug, Default)]
+pub struct Gauge(AtomicI64);
+
+impl Gauge {
+    pub fn set(&self, value: i64) {
+        self.0.store(value, Ordering::Relaxed);
+    }
+
+    pub fn add(&self, delta: i64) {
+        self.0.fetch_add(delta, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> i64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Cumulative histogram with fixed bucket bounds.
+#[derive(Debug)]
+pub struct Histogram {
+    bounds: &'static [f64],
+    buckets: Vec<AtomicU64>,
+    count: AtomicU64,
+    /// Sum of observations, stored as `f64` bits.
+    sum_bits: AtomicU64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            buckets: bounds.iter().map(|_| AtomicU64::new(0)).collect(),
+            count: AtomicU64::new(0),
+            sum_bits: AtomicU64::new(0f64.to_bits()),
+        }
+    }
+
+    pub fn observe(&self, value: f64) {
+        if let Some(i) = self.bounds.iter().position(|b| value <= *b) {
+            self.buckets[i].fetch_add(1, Ordering::Relaxed);
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        let mut current = self.sum_bits.load(Ordering::Relaxed);
+        loop {
+            let next = (f64::from_bits(current) + value).to_bits();
+            match self.sum_bits.compare_exchange_weak(
+                current,
+                next,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    pub fn observe_duration(&self, elapsed: Duration) {
+        self.observe(elapsed.as_secs_f64());
+    }
+
+    /// Start a timer that observes the elapsed time when dropped.
+    pub fn start_timer(&self) -> HistogramTimer<'_> {
+        HistogramTimer {
+            histogram: self,
+            start: Instant::now(),
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    pub fn sum(&self) -> f64 {
+        f64::from_bits(self.sum_bits.load(Ordering::Relaxed))
+    }
+}
+
+/// Drop guard returned by [`Histogram::start_timer`].
+pub struct HistogramTimer<'a> {
+    histogram: &'a Histogram,
+    start: Instant,
+}
+
+impl Drop for HistogramTimer<'_> {
+    fn drop(&mut self) {
+        self.histogram.observe_duration(self.start.elapsed());
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Series {
+    Counter(Arc<Counter>),
+    Gauge(Arc<Gauge>),
+    Histogram(Arc<Histogram>),
+}
+
+impl Series {
+    fn kind(&self) -> &'static str {
+        match self {
+            Series::Counter(_) => "counter",
+            Series::Gauge(_) => "gauge",
+            Series::Histogram(_) => "histogram",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Family {
+    help: String,
+    /// Rendered label set (`a="x",b="y"`, possibly empty) -> series.
+    series: BTreeMap<String, Series>,
+}
+
+/// A set of named metric families.
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    families: Mutex<BTreeMap<String, Family>>,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get or register a counter series.
+    pub fn counter(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Arc<Counter> {
+        match self.series(name, help, labels, || {
+            Series::Counter(Arc::new(Counter::default()))
+        }) {
+            Series::Counter(c) => c,
+            other => panic!("metric `{name}` already registered as {}", other.kind()),
+        }
+    }
+
+    /// Get or register a gauge series.
+    pub fn gauge(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Arc<Gauge> {
+        match self.series(name, help, labels, || {
+            Series::Gauge(Arc::new(Gauge::default()))
+        }) {
+            Series::Gauge(g) => g,
+            other => panic!("metric `{name}` already registered as {}", other.kind()),
+        }
+    }
+
+    /// Get or register a histogram series with [`LATENCY_BUCKETS`].
+    pub fn histogram(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Arc<Histogram> {
+        match self.series(name, help, labels, || {
+            Series::Histogram(Arc::new(Histogram::new(LATENCY_BUCKETS)))
+        }) {
+            Series::Histogram(h) => h,
+            other => panic!("metric `{name}` already registered as {}", other.kind()),
+        }
+    }
+
+    fn series(
+        &self,
+        name: &str,
+        help: &str,
+        labels: &[(&str, &str)],
+        make: impl FnOnce() -> Series,
+    ) -> Series {
+        let mut families = self.families.lock().expect("metrics registry poisoned");
+        let family = families.entry(name.to_string()).or_insert_with(|| Family {
+            help: help.to_string(),
+            series: BTreeMap::new(),
+        });
+        family
+            .series
+            .entry(render_labels(labels))
+            .or_insert_with(make)
+            .clone()
+    }
+
+    /// Render every family in the Prometheus text exposition format (v0.0.4).
+    pub fn render_prometheus(&self) -> String {
+        let families = self.families.lock().expect("metrics registry poisoned");
+        let mut out = String::new();
+        for (name, family) in families.iter() {
+            let Some(first) = family.series.values().next() else {
+                continue;
+            };
+            let _ = writeln!(out, "# HELP {name} {}", family.help);
+            let _ = writeln!(out, "# TYPE {name} {}", first.kind());
+            for (labels, series) in &family.series {
+                match series {
+                    Series::Counter(c) => {
+                        let _ = writeln!(out, "{name}{} {}", braced(labels), c.get());
+                    }
+                    Series::Gauge(g) => {
+                        let _ = writeln!(out, "{name}{} {}", braced(labels), g.get());
+                    }
+                    Series::Histogram(h) => render_histogram(&mut out, name, labels, h),
+                }
+            }
+        }
+        out
+    }
+}
+
+fn render_histogram(out: &mut String, name: &str, labels: &str, h: &Histogram) {
+    let mut cumulative = 0u64;
+    for (bound, bucket) in h.bounds.iter().zip(&h.buckets) {
+        cumulative += bucket.load(Ordering::Relaxed);
+        let le = format!("le=\"{bound}\"");
+        let _ = writeln!(
+            out,
+            "{name}_bucket{} {cumulative}",
+            braced(&join_labels(labels, &le))
+        );
+    }
+    let count = h.count();
+    let _ = writeln!(
+        out,
+        "{name}_bucket{} {count}",
+        braced(&join_labels(labels, "le=\"+Inf\""))
+    );
+    let _ = writeln!(out, "{name}_sum{} {}", braced(labels), h.sum());
+    let _ = writeln!(out, "{name}_count{} {count}", braced(labels));
+}
+
+fn render_labels(labels: &[(&str, &str)]) -> String {
+    labels
+        .iter()
+        .map(|(k, v)| format!("{k}=\"{}\"", escape_label_value(v)))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn escape_label_value(v: &str) -> String {
+    v.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn join_labels(a: &str, b: &str) -> String {
+    match (a.is_empty(), b.is_empty()) {
+        (true, _) => b.to_string(),
+        (_, true) => a.to_string(),
+        _ => format!("{a},{b}"),
+    }
+}
+
+fn braced(labels: &str) -> String {
+    if labels.is_empty() {
+        String::new()
+    } else {
+        format!("{{{labels}}}")
+    }
+}
+
+/// The process-wide registry.
+pub fn global() -> &'static MetricsRegistry {
+    static GLOBAL: OnceLock<MetricsRegistry> = OnceLock::new();
+    GLOBAL.get_or_init(MetricsRegistry::new)
+}
+
+/// Caches recorded on hot paths; their counter handles are resolved once.
+const KNOWN_CACHES: [&str; 5] = [
+    "path_index",
+    "path_lru",
+    "fact_index",
+    "text_index",
+    "axql_plan",
+];
+
+/// `hit` / `miss` series of one cache.
+struct CacheCounters {
+    hit: Arc<Counter>,
+    miss: Arc<Counter>,
+}
+
+impl CacheCounters {
+    fn register(cache: &str) -> Self {
+        let counter = |result| {
+            global().counter(
+                "axiograph_cache_requests_total",
+                "Cache lookups by cache and outcome.",
+                &[("cache", cache), ("result", result)],
+            )
+        };
+        Self {
+            hit: counter("hit"),
+            miss: counter("miss"),
+        }
+    }
+
+    fn record(&self, hit: bool) {
+        if hit {
+            self.hit.inc();
+        } else {
+            self.miss.inc();
+        }
+    }
+}
+
+/// Record a cache lookup outcome in `axiograph_cache_requests_total`.
+///
+/// The [`KNOWN_CACHES`] never touch the registry lock after their first
+/// lookup; other names go through it every time.
+pub fn record_cache(cache: &str, hit: bool) {
+    static KNOWN: [OnceLock<CacheCounters>; KNOWN_CACHES.len()] = [
+        OnceLock::new(),
+        OnceLock::new(),
+        OnceLock::new(),
+        OnceLock::new(),
+        OnceLock::new(),
+    ];
+    match KNOWN_CACHES.iter().position(|known| *known == cache) {
+        Some(i) => KNOWN[i]
+            .get_or_init(|| CacheCounters::register(cache))
+            .record(hit),
+        None => CacheCounters::register(cache).record(hit),
+    }
+}
+
+/// `axiograph_pathdb_query_seconds`: PathQuery execution latency.
+pub(crate) fn query_seconds() -> &'static Histogram {
+    static H: OnceLock<Arc<Histogram>> = OnceLock::new();
+    H.get_or_init(|| {
+        global().histogram(
+            "axiograph_pathdb_query_seconds",
+            "PathQuery execution latency in seconds.",
+            &[],
+        )
+    })
+}
+
+/// `axiograph_pathdb_index_build_seconds{index}`: index build durations.
+pub(crate) fn index_build_seconds(index: &str) -> Arc<Histogram> {
+    global().histogram(
+        "axiograph_pathdb_index_build_seconds",
+        "PathDB index build duration in seconds.",
+        &[("index", index)],
+    )
+}
@@ -8,7 +8,7 @@
 //! We keep these types *out of* the optimizer/certificate modules to avoid
 //! circular dependencies: both sides can depend on `migration`.
 
-use anyhow::{anyhow, Result};
+use crate::error::{PathDbError, Result};
 use serde::{Deserialize, Serialize};
 
 pub type Name = String;
@@ -126,20 +126,19 @@ impl SchemaMorphismV1 {
     ///   `(after ∘ self)(f) = p₁ ++ p₂`.
     pub fn then(&self, after: &SchemaMorphismV1) -> Result<SchemaMorphismV1> {
         if self.target_schema != after.source_schema {
-            return Err(anyhow!(
-                "cannot compose schema morphisms: self.target_schema={} but after.source_schema={}",
-                self.target_schema,
-                after.source_schema
-            ));
+            return Err(PathDbError::MorphismMismatch(format!(
+                "self.target_schema={} but after.source_schema={}",
+                self.target_schema, after.source_schema
+            )));
         }
 
         let mut composed_objects: Vec<ObjectMappingV1> = Vec::with_capacity(self.objects.len());
         for mapping in &self.objects {
             let Some(target_object) = after.object_image(mapping.target_object.as_str()) else {
-                return Err(anyhow!(
-                    "cannot compose: missing object mapping for intermediate object `{}`",
+                return Err(PathDbError::MorphismMismatch(format!(
+                    "missing object mapping for intermediate object `{}`",
                     mapping.target_object
-                ));
+                )));
             };
             composed_objects.push(ObjectMappingV1 {
                 source_object: mapping.source_object.clone(),
@@ -152,10 +151,10 @@ impl SchemaMorphismV1 {
             let mut composed_path: Vec<Name> = Vec::new();
             for intermediate_arrow in &mapping.target_path {
                 let Some(after_path) = after.arrow_image(intermediate_arrow.as_str()) else {
-                    return Err(anyhow!(
-                        "cannot compose: missing arrow mapping for intermediate arrow `{}`",
+                    return Err(PathDbError::MorphismMismatch(format!(
+                        "missing arrow mapping for intermediate arrow `{}`",
                         intermediate_arrow
-                    ));
+                    )));
                 };
                 composed_path.extend(after_path.iter().cloned());
             }
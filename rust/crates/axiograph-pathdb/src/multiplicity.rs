@@ -0,0 +1,542 @@
+//! Relation multiplicity constraints, enforced at insert time.
+//!
+//! `add_relation` appends unconditionally, so repeated ingests of the same
+//! source accumulate edges: an RPC ends up with two `proto_rpc_request`
+//! targets even though the schema says it has exactly one. A relation can
+//! now carry a [`Multiplicity`]: at most N targets per source and/or at most
+//! N sources per target (one-to-one is both at 1).
+//!
+//! - [`PathDB::declare_multiplicity`] declares one directly;
+//!   [`PathDB::adopt_axi_multiplicities`] derives them from `.axi`
+//!   `constraint functional R.a -> R.b` and `constraint at_most N R.a -> R.b`
+//!   on relations that are binary once `ctx`/`time` are dropped (the same
+//!   relations that get a derived traversal edge on import, with the same
+//!   edge label). `.axi` module import adopts them automatically.
+//! - [`PathDB::insert_relation`] checks the declaration before adding an
+//!   edge. Re-adding an existing edge returns it; an edge that would exceed a
+//!   bound is a [`MultiplicityViolation`], and [`OnMultiplicityConflict`]
+//!   picks what happens next. [`PathDB::try_add_relation`] rejects.
+//! - [`PathDB::multiplicity_violations`] audits the edges already stored.
+//!
+//! Plain `add_relation` stays unchecked (bulk loaders and imports that have
+//! already validated their input). Declarations survive snapshots: explicit
+//! ones as meta-plane `AxiMetaRelationMultiplicity` entities, `.axi` ones as
+//! the constraint entities they come from; both are re-read on load.
+
+use std::collections::{BTreeMap, HashMap};
+
+use roaring::RoaringBitmap;
+
+use crate::axi_meta::{
+    ATTR_MULTIPLICITY_MAX_SOURCES, ATTR_MULTIPLICITY_MAX_TARGETS, ATTR_MULTIPLICITY_RELATION,
+    META_ATTR_NAME, META_TYPE_CONSTRAINT, META_TYPE_RELATION_MULTIPLICITY,
+};
+use crate::axi_semantics::{ConstraintDecl, MetaPlaneIndex};
+use crate::error::Result;
+use crate::{PathDB, PathDbError, StrId};
+
+/// Bounds on one relation's edges.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Multiplicity {
+    /// Most distinct targets per source.
+    pub max_targets: Option<u32>,
+    /// Most distinct sources per target.
+    pub max_sources: Option<u32>,
+}
+
+impl Multiplicity {
+    /// At most one target per source (`functional R.from -> R.to`).
+    pub const MANY_TO_ONE: Self = Self {
+        max_targets: Some(1),
+        max_sources: None,
+    };
+    /// At most one source per target.
+    pub const ONE_TO_MANY: Self = Self {
+        max_targets: None,
+        max_sources: Some(1),
+    };
+    pub const ONE_TO_ONE: Self = Self {
+        max_targets: Some(1),
+        max_sources: Some(1),
+    };
+
+    pub fn is_unbounded(&self) -> bool {
+        self.max_targets.is_none() && self.max_sources.is_none()
+    }
+
+    /// The tighter of each bound.
+    pub fn meet(self, other: Self) -> Self {
+        let min = |a: Option<u32>, b: Option<u32>| match (a, b) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        Self {
+            max_targets: min(self.max_targets, other.max_targets),
+            max_sources: min(self.max_sources, other.max_sources),
+        }
+    }
+}
+
+impl std::fmt::Display for Multiplicity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bound = |b: Option<u32>| b.map_or("*".to_string(), |n| format!("≤{n}"));
+        write!(
+            f,
+            "sources {} : targets {}",
+            bound(self.max_sources),
+            bound(self.max_targets)
+        )
+    }
+}
+
+/// Which bound a violation exceeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiplicityBound {
+    /// Too many targets for one source.
+    Targets,
+    /// Too many sources for one target.
+    Sources,
+}
+
+/// An edge (new or stored) beyond a declared bound.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiplicityViolation {
+    pub relation: String,
+    pub source: u32,
+    pub target: u32,
+    pub bound: MultiplicityBound,
+    pub max: u32,
+    /// The far endpoints already holding the bound: other targets of
+    /// `source` (`Targets`) or other sources of `target` (`Sources`).
+    pub existing: Vec<u32>,
+}
+
+impl std::fmt::Display for MultiplicityViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let existing: Vec<String> = self.existing.iter().map(u32::to_string).collect();
+        match self.bound {
+            MultiplicityBound::Targets => write!(
+                f,
+                "`{}` allows at most {} target(s) per source: {} -> {} conflicts with {} -> [{}]",
+                self.relation,
+                self.max,
+                self.source,
+                self.target,
+                self.source,
+                existing.join(", ")
+            ),
+            MultiplicityBound::Sources => write!(
+                f,
+                "`{}` allows at most {} source(s) per target: {} -> {} conflicts with [{}] -> {}",
+                self.relation,
+                self.max,
+                self.source,
+                self.target,
+                existing.join(", "),
+                self.target
+            ),
+        }
+    }
+}
+
+/// What [`PathDB::insert_relation`] does with an edge beyond a bound.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OnMultiplicityConflict {
+    /// Fail with [`PathDbError::MultiplicityViolation`]; nothing is written.
+    #[default]
+    Reject,
+    /// Keep the stored edges and drop the new one (reported).
+    Skip,
+    /// Remove the conflicting stored edges, then add the new one. Relation
+    /// ids are compacted.
+    Replace,
+}
+
+/// Result of [`PathDB::insert_relation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelationInsert {
+    /// The new edge, the existing identical edge, or `None` when skipped.
+    pub relation: Option<u32>,
+    /// Whether a new edge was stored.
+    pub added: bool,
+    /// Bounds the new edge exceeded (resolved by the policy).
+    pub violations: Vec<MultiplicityViolation>,
+}
+
+/// Declared multiplicities, keyed by relation type.
+#[derive(Debug, Clone, Default)]
+pub struct MultiplicityRegistry {
+    by_rel: HashMap<StrId, Multiplicity>,
+}
+
+impl MultiplicityRegistry {
+    pub fn is_empty(&self) -> bool {
+        self.by_rel.is_empty()
+    }
+
+    pub fn get(&self, rel_type: StrId) -> Option<Multiplicity> {
+        self.by_rel.get(&rel_type).copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (StrId, Multiplicity)> + '_ {
+        self.by_rel.iter().map(|(&rel, &m)| (rel, m))
+    }
+
+    fn declare(&mut self, rel_type: StrId, multiplicity: Multiplicity) -> Multiplicity {
+        let entry = self.by_rel.entry(rel_type).or_default();
+        *entry = entry.meet(multiplicity);
+        *entry
+    }
+}
+
+impl PathDB {
+    /// Declare (or tighten) the multiplicity of `relation`. Returns false if
+    /// the declaration did not change anything.
+    pub fn declare_multiplicity(&mut self, relation: &str, multiplicity: Multiplicity) -> bool {
+        if multiplicity.is_unbounded() {
+            return false;
+        }
+        let rel = self.interner.intern(relation);
+        let before = self.multiplicities.get(rel);
+        let after = self.multiplicities.declare(rel, multiplicity);
+        if before == Some(after) {
+            return false;
+        }
+        let bound = |b: Option<u32>| b.map(|n| n.to_string()).unwrap_or_default();
+        let (max_targets, max_sources) = (bound(after.max_targets), bound(after.max_sources));
+        let mut attrs = vec![
+            (META_ATTR_NAME, relation),
+            (ATTR_MULTIPLICITY_RELATION, relation),
+        ];
+        if after.max_targets.is_some() {
+            attrs.push((ATTR_MULTIPLICITY_MAX_TARGETS, &max_targets));
+        }
+        if after.max_sources.is_some() {
+            attrs.push((ATTR_MULTIPLICITY_MAX_SOURCES, &max_sources));
+        }
+        self.add_entity(META_TYPE_RELATION_MULTIPLICITY, attrs);
+        true
+    }
+
+    /// The declared multiplicity of `relation`, if any.
+    pub fn multiplicity(&self, relation: &str) -> Option<Multiplicity> {
+        self.multiplicities.get(self.interner.id_of(relation)?)
+    }
+
+    pub fn multiplicity_registry(&self) -> &MultiplicityRegistry {
+        &self.multiplicities
+    }
+
+    /// Enforce the multiplicities implied by the meta plane's `functional`
+    /// and unparameterized `at_most` constraints on binary relations (see the
+    /// module docs). The constraints themselves are the persisted form, so
+    /// nothing is written. Returns how many relations got tighter bounds.
+    pub fn adopt_axi_multiplicities(&mut self) -> usize {
+        if self.find_by_type(META_TYPE_CONSTRAINT).is_none() {
+            return 0;
+        }
+        let Ok(meta) = MetaPlaneIndex::from_db(self) else {
+            return 0;
+        };
+        let mut name_counts: HashMap<&str, usize> = HashMap::new();
+        for schema in meta.schemas.values() {
+            for rel in schema.relation_decls.keys() {
+                *name_counts.entry(rel.as_str()).or_default() += 1;
+            }
+        }
+
+        let mut declared: BTreeMap<String, Multiplicity> = BTreeMap::new();
+        for (schema_name, schema) in &meta.schemas {
+            for (relation, constraints) in &schema.constraints_by_relation {
+                let Some(decl) = schema.relation_decls.get(relation) else {
+                    continue;
+                };
+                let mut fields: Vec<(usize, &str)> = decl
+                    .fields
+                    .iter()
+                    .map(|f| (f.field_index, f.field_name.as_str()))
+                    .filter(|(_, name)| *name != "ctx" && *name != "time")
+                    .collect();
+                fields.sort();
+                let [(_, source_field), (_, target_field)] = fields[..] else {
+                    continue;
+                };
+                let label = if name_counts.get(relation.as_str()).copied().unwrap_or(0) > 1 {
+                    format!("{schema_name}.{relation}")
+                } else {
+                    relation.clone()
+                };
+                for constraint in constraints {
+                    let (src, dst, max) = match constraint {
+                        ConstraintDecl::Functional {
+                            src_field,
+                            dst_field,
+                            ..
+                        } => (src_field, dst_field, 1),
+                        ConstraintDecl::AtMost {
+                            src_field,
+                            dst_field,
+                            max,
+                            params: None,
+                            ..
+                        } => (src_field, dst_field, *max),
+                        _ => continue,
+                    };
+                    let multiplicity =
+                        if (src.as_str(), dst.as_str()) == (source_field, target_field) {
+                            Multiplicity {
+                                max_targets: Some(max),
+                                max_sources: None,
+                            }
+                        } else if (src.as_str(), dst.as_str()) == (target_field, source_field) {
+                            Multiplicity {
+                                max_targets: None,
+                                max_sources: Some(max),
+                            }
+                        } else {
+                            continue;
+                        };
+                    let entry = declared.entry(label.clone()).or_default();
+                    *entry = entry.meet(multiplicity);
+                }
+            }
+        }
+
+        declared
+            .into_iter()
+            .filter(|(label, multiplicity)| {
+                let rel = self.interner.intern(label);
+                let before = self.multiplicities.get(rel);
+                before != Some(self.multiplicities.declare(rel, *multiplicity))
+            })
+            .count()
+    }
+
+    /// Add `source -rel_type-> target` unless it exceeds a declared bound.
+    pub fn try_add_relation(
+        &mut self,
+        rel_type: &str,
+        source: u32,
+        target: u32,
+        confidence: f32,
+        attrs: Vec<(&str, &str)>,
+    ) -> Result<u32> {
+        let insert = self.insert_relation(
+            rel_type,
+            source,
+            target,
+            confidence,
+            attrs,
+            OnMultiplicityConflict::Reject,
+        )?;
+        Ok(insert
+            .relation
+            .expect("rejecting inserts always store or fail"))
+    }
+
+    /// [`Self::add_relation`] checked against the declared multiplicity of
+    /// `rel_type`, with an explicit conflict policy.
+    ///
+    /// For a declared relation an identical stored edge is returned instead
+    /// of adding a duplicate. Undeclared relations are added as-is.
+    pub fn insert_relation(
+        &mut self,
+        rel_type: &str,
+        source: u32,
+        target: u32,
+        confidence: f32,
+        attrs: Vec<(&str, &str)>,
+        on_conflict: OnMultiplicityConflict,
+    ) -> Result<RelationInsert> {
+        let entity_count = self.entities.types.len() as u32;
+        for id in [source, target] {
+            if id >= entity_count {
+                return Err(PathDbError::UnknownEntity(id));
+            }
+        }
+        let declared = self
+            .interner
+            .id_of(rel_type)
+            .and_then(|rel| Some((rel, self.multiplicities.get(rel)?)));
+        let Some((rel, multiplicity)) = declared else {
+            let relation = self.add_relation(rel_type, source, target, confidence, attrs);
+            return Ok(RelationInsert {
+                relation: Some(relation),
+                added: true,
+                violations: Vec::new(),
+            });
+        };
+
+        if let Some(existing) = self.relations.edge_relation_id(source, rel, target) {
+            return Ok(RelationInsert {
+                relation: Some(existing),
+                added: false,
+                violations: Vec::new(),
+            });
+        }
+
+        let violations = self.edge_violations(rel_type, rel, multiplicity, source, target);
+        if let Some(first) = violations.first() {
+            match on_conflict {
+                OnMultiplicityConflict::Reject => {
+                    return Err(PathDbError::MultiplicityViolation(Box::new(first.clone())));
+                }
+                OnMultiplicityConflict::Skip => {
+                    return Ok(RelationInsert {
+                        relation: None,
+                        added: false,
+                        violations,
+                    });
+                }
+                OnMultiplicityConflict::Replace => {
+                    for violation in &violations {
+                        for &other in &violation.existing {
+                            match violation.bound {
+                                MultiplicityBound::Targets => {
+                                    self.remove_relation(source, rel_type, other)
+                                }
+                                MultiplicityBound::Sources => {
+                                    self.remove_relation(other, rel_type, target)
+                                }
+                            };
+                        }
+                    }
+                }
+            }
+        }
+
+        let relation = self.add_relation(rel_type, source, target, confidence, attrs);
+        Ok(RelationInsert {
+            relation: Some(relation),
+            added: true,
+            violations,
+        })
+    }
+
+    /// Stored edges beyond their relation's declared bounds, one violation
+    /// per over-full source (targets) or target (sources).
+    pub fn multiplicity_violations(&self) -> Vec<MultiplicityViolation> {
+        let mut declared: Vec<(StrId, Multiplicity)> = self
+            .multiplicities
+            .by_rel
+            .iter()
+            .map(|(&rel, &m)| (rel, m))
+            .collect();
+        declared.sort_by_key(|(rel, _)| *rel);
+
+        let mut out = Vec::new();
+        for (rel, multiplicity) in declared {
+            let Some(ids) = self.relations.type_index.get(&rel) else {
+                continue;
+            };
+            let name = self.interner.lookup(rel).unwrap_or_default();
+            let mut targets_of: BTreeMap<u32, RoaringBitmap> = BTreeMap::new();
+            let mut sources_of: BTreeMap<u32, RoaringBitmap> = BTreeMap::new();
+            for id in ids {
+                if let Some(r) = self.relations.get_relation(id) {
+                    targets_of.entry(r.source).or_default().insert(r.target);
+                    sources_of.entry(r.target).or_default().insert(r.source);
+                }
+            }
+            let mut report = |bound, max: Option<u32>, groups: BTreeMap<u32, RoaringBitmap>| {
+                let Some(max) = max else {
+                    return;
+                };
+                for (key, far) in groups {
+                    if far.len() <= u64::from(max) {
+                        continue;
+                    }
+                    let mut far: Vec<u32> = far.iter().collect();
+                    let last = far.pop().expect("over a bound, so non-empty");
+                    let (source, target) = match bound {
+                        MultiplicityBound::Targets => (key, last),
+                        MultiplicityBound::Sources => (last, key),
+                    };
+                    out.push(MultiplicityViolation {
+                        relation: name.clone(),
+                        source,
+                        target,
+                        bound,
+                        max,
+                        existing: far,
+                    });
+                }
+            };
+            report(
+                MultiplicityBound::Targets,
+                multiplicity.max_targets,
+                targets_of,
+            );
+            report(
+                MultiplicityBound::Sources,
+                multiplicity.max_sources,
+                sources_of,
+            );
+        }
+        out
+    }
+
+    /// Bounds a new `source -rel-> target` edge would exceed.
+    fn edge_violations(
+        &self,
+        rel_type: &str,
+        rel: StrId,
+        multiplicity: Multiplicity,
+        source: u32,
+        target: u32,
+    ) -> Vec<MultiplicityViolation> {
+        let mut out = Vec::new();
+        let mut check = |bound, max: Option<u32>, far: RoaringBitmap| {
+            if let Some(max) = max {
+                if far.len() >= u64::from(max) {
+                    out.push(MultiplicityViolation {
+                        relation: rel_type.to_string(),
+                        source,
+                        target,
+                        bound,
+                        max,
+                        existing: far.iter().collect(),
+                    });
+                }
+            }
+        };
+        check(
+            MultiplicityBound::Targets,
+            multiplicity.max_targets,
+            self.relations.targets(source, rel),
+        );
+        check(
+            MultiplicityBound::Sources,
+            multiplicity.max_sources,
+            self.relations.sources(target, rel),
+        );
+        out
+    }
+
+    /// Re-declare every meta-plane multiplicity (after loading a snapshot).
+    pub(crate) fn rebuild_multiplicities(&mut self) {
+        self.adopt_axi_multiplicities();
+        let Some(decls) = self.find_by_type(META_TYPE_RELATION_MULTIPLICITY).cloned() else {
+            return;
+        };
+        let Some(rel_key) = self.interner.id_of(ATTR_MULTIPLICITY_RELATION) else {
+            return;
+        };
+        let bound = |db: &Self, decl: u32, key: &str| {
+            db.attr_string(decl, key)
+                .and_then(|value| value.parse::<u32>().ok())
+        };
+        for decl in &decls {
+            let Some(rel) = self.entities.get_attr(decl, rel_key) else {
+                continue;
+            };
+            let multiplicity = Multiplicity {
+                max_targets: bound(self, decl, ATTR_MULTIPLICITY_MAX_TARGETS),
+                max_sources: bound(self, decl, ATTR_MULTIPLICITY_MAX_SOURCES),
+            };
+            if !multiplicity.is_unbounded() {
+                self.multiplicities.declare(rel, multiplicity);
+            }
+        }
+    }
+}
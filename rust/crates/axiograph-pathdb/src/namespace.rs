@@ -0,0 +1,254 @@
+//! Multi-tenant namespaces within one PathDB.
+//!
+//! A namespace is a reserved attribute (`axi_namespace`) on entities and
+//! relations. Its value is interned like any other attribute, so tagging costs
+//! one `StrId` pair per record and scoping is a column scan / bitmap filter.
+//!
+//! Invariants (for data written through this API):
+//! - `add_relation_in` only connects entities of the same namespace.
+//! - `execute_in_namespace` applies the namespace at every hop, so even
+//!   untagged or cross-namespace edges added through `add_relation` cannot
+//!   carry a traversal out of it.
+//! - Untagged entities/relations belong to no namespace and are invisible to
+//!   namespace-scoped reads.
+//!
+//! Exports are filtered by extracting a namespace into a standalone PathDB
+//! (`extract_namespace`), which every existing export path can consume.
+
+use std::collections::{BTreeMap, HashMap};
+
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::{ContextScope, PathDB, PathDbError, PathQuery};
+
+/// Reserved attribute holding an entity's / relation's namespace.
+pub const ATTR_NAMESPACE: &str = "axi_namespace";
+
+/// Per-namespace counts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamespaceStats {
+    pub entities: usize,
+    pub relations: usize,
+    /// Entity counts by (canonical) type name.
+    pub entity_types: BTreeMap<String, usize>,
+    /// Relation counts by relation type name.
+    pub relation_types: BTreeMap<String, usize>,
+}
+
+impl PathDB {
+    /// Add an entity tagged with `namespace`.
+    pub fn add_entity_in<'a>(
+        &mut self,
+        namespace: &'a str,
+        type_name: &str,
+        mut attrs: Vec<(&'a str, &'a str)>,
+    ) -> u32 {
+        attrs.retain(|(k, _)| *k != ATTR_NAMESPACE);
+        attrs.push((ATTR_NAMESPACE, namespace));
+        self.add_entity(type_name, attrs)
+    }
+
+    /// Add a relation tagged with `namespace`. Both endpoints must already be
+    /// in `namespace`.
+    pub fn add_relation_in<'a>(
+        &mut self,
+        namespace: &'a str,
+        rel_type: &str,
+        source: u32,
+        target: u32,
+        confidence: f32,
+        mut attrs: Vec<(&'a str, &'a str)>,
+    ) -> Result<u32> {
+        for endpoint in [source, target] {
+            let actual = self.entity_namespace(endpoint);
+            if actual.as_deref() != Some(namespace) {
+                return Err(PathDbError::NamespaceMismatch {
+                    entity: endpoint,
+                    expected: namespace.to_string(),
+                    actual,
+                });
+            }
+        }
+        attrs.retain(|(k, _)| *k != ATTR_NAMESPACE);
+        attrs.push((ATTR_NAMESPACE, namespace));
+        Ok(self.add_relation(rel_type, source, target, confidence, attrs))
+    }
+
+    /// Namespace of an entity, if tagged.
+    pub fn entity_namespace(&self, entity_id: u32) -> Option<String> {
+        let key = self.interner.id_of(ATTR_NAMESPACE)?;
+        let value = self.entities.get_attr(entity_id, key)?;
+        self.interner.lookup(value)
+    }
+
+    /// Namespace of a relation, if tagged.
+    pub fn relation_namespace(&self, relation_id: u32) -> Option<String> {
+        let key = self.interner.id_of(ATTR_NAMESPACE)?;
+        let rel = self.relations.get_relation(relation_id)?;
+        let (_, value) = rel.attrs.iter().find(|(k, _)| *k == key)?;
+        self.interner.lookup(*value)
+    }
+
+    /// All entities in `namespace`.
+    pub fn entities_in_namespace(&self, namespace: &str) -> RoaringBitmap {
+        let (Some(key), Some(value)) = (
+            self.interner.id_of(ATTR_NAMESPACE),
+            self.interner.id_of(namespace),
+        ) else {
+            return RoaringBitmap::new();
+        };
+        self.entities.entities_with_attr_value(key, value)
+    }
+
+    /// Relation ids tagged with `namespace`.
+    pub fn relations_in_namespace(&self, namespace: &str) -> Vec<u32> {
+        let (Some(key), Some(value)) = (
+            self.interner.id_of(ATTR_NAMESPACE),
+            self.interner.id_of(namespace),
+        ) else {
+            return Vec::new();
+        };
+        self.relations
+            .relations
+            .iter()
+            .enumerate()
+            .filter(|(_, rel)| rel.attrs.contains(&(key, value)))
+            .map(|(id, _)| id as u32)
+            .collect()
+    }
+
+    /// Sorted list of namespaces that tag at least one entity.
+    pub fn namespaces(&self) -> Vec<String> {
+        self.namespace_stats().into_keys().collect()
+    }
+
+    /// Entity/relation counts for every namespace.
+    pub fn namespace_stats(&self) -> BTreeMap<String, NamespaceStats> {
+        let mut out: BTreeMap<String, NamespaceStats> = BTreeMap::new();
+        let Some(key) = self.interner.id_of(ATTR_NAMESPACE) else {
+            return out;
+        };
+
+        let mut names: HashMap<u32, String> = HashMap::new();
+        let mut name_of = |id: crate::StrId| -> String {
+            names
+                .entry(id.raw())
+                .or_insert_with(|| self.interner.lookup(id).unwrap_or_default())
+                .clone()
+        };
+
+        if let Some(col) = self.entities.attrs.get(&key) {
+            for (&entity_id, &ns) in col {
+                let stats = out.entry(name_of(ns)).or_default();
+                stats.entities += 1;
+                if let Some(type_id) = self.entities.get_type(entity_id) {
+                    *stats.entity_types.entry(name_of(type_id)).or_default() += 1;
+                }
+            }
+        }
+        for rel in &self.relations.relations {
+            if let Some((_, ns)) = rel.attrs.iter().find(|(k, _)| *k == key) {
+                let stats = out.entry(name_of(*ns)).or_default();
+                stats.relations += 1;
+                *stats
+                    .relation_types
+                    .entry(name_of(rel.rel_type))
+                    .or_default() += 1;
+            }
+        }
+        out
+    }
+
+    /// The scope of `namespace`: its entities, and the relations tagged with
+    /// it whose endpoints are both in it.
+    pub fn namespace_scope(&self, namespace: &str) -> ContextScope {
+        let scope = ContextScope::over(self.entities_in_namespace(namespace));
+        match (
+            self.interner.id_of(ATTR_NAMESPACE),
+            self.interner.id_of(namespace),
+        ) {
+            (Some(key), Some(value)) => scope.require_edge_attr(key, value),
+            _ => scope,
+        }
+    }
+
+    /// Execute a query scoped to `namespace`.
+    ///
+    /// Every hop only sees [`Self::namespace_scope`], so a path cannot step
+    /// out of the namespace and back in. Queries anchored at an entity
+    /// outside the namespace return nothing.
+    pub fn execute_in_namespace(&self, namespace: &str, query: &PathQuery) -> RoaringBitmap {
+        let scope = self.namespace_scope(namespace);
+        if !query_anchors(query)
+            .iter()
+            .all(|id| scope.contains_entity(*id))
+        {
+            return RoaringBitmap::new();
+        }
+        self.execute_within(query, &scope)
+    }
+
+    /// Copy `namespace` into a standalone PathDB (export filter).
+    ///
+    /// Entity ids are renumbered densely in ascending original-id order; only
+    /// relations tagged with the namespace are kept. Every attribute column
+    /// (scalar, list, language-tagged) is copied, with the blobs the copy
+    /// references. Virtual types are not carried over.
+    pub fn extract_namespace(&self, namespace: &str) -> PathDB {
+        let mut out = PathDB::new();
+        let mut remap: HashMap<u32, u32> = HashMap::new();
+
+        for entity_id in &self.entities_in_namespace(namespace) {
+            if let Some(new_id) = self.copy_entity_into(entity_id, &mut out, |_, v| v) {
+                remap.insert(entity_id, new_id);
+            }
+        }
+
+        for relation_id in self.relations_in_namespace(namespace) {
+            let Some(rel) = self.relations.get_relation(relation_id) else {
+                continue;
+            };
+            let (Some(&source), Some(&target)) = (remap.get(&rel.source), remap.get(&rel.target))
+            else {
+                continue;
+            };
+            let Some(rel_type) = self.interner.lookup(rel.rel_type) else {
+                continue;
+            };
+            let attrs: Vec<(String, String)> = rel
+                .attrs
+                .iter()
+                .filter_map(|(k, v)| Some((self.interner.lookup(*k)?, self.interner.lookup(*v)?)))
+                .collect();
+            let attrs: Vec<(&str, &str)> = attrs
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect();
+            out.add_relation(&rel_type, source, target, rel.confidence, attrs);
+        }
+
+        out.import_blobs(&self.blobs);
+        out.build_indexes();
+        out
+    }
+}
+
+/// Entity ids a query starts from (its traversal anchors).
+fn query_anchors(query: &PathQuery) -> Vec<u32> {
+    match query {
+        PathQuery::SelectByType(_) => Vec::new(),
+        PathQuery::SelectRelated(source, _) => vec![*source],
+        PathQuery::FollowPath { start, .. } => vec![*start],
+        PathQuery::FindPaths { from, to, .. } => vec![*from, *to],
+        PathQuery::Join(a, b) | PathQuery::Union(a, b) => {
+            let mut out = query_anchors(a);
+            out.extend(query_anchors(b));
+            out
+        }
+        PathQuery::WithConfidence { base, .. } | PathQuery::WithPathConfidence { base, .. } => {
+            query_anchors(base)
+        }
+    }
+}
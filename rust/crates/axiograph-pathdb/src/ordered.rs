@@ -0,0 +1,70 @@
+//! Deterministic serialization of hash maps.
+//!
+//! The in-memory stores use `HashMap`/`AHashMap` for O(1) lookups, but their
+//! iteration order depends on a per-process random seed. Serializing them
+//! directly made `.axpd` bytes differ run to run for identical graphs, which
+//! breaks snapshot diffs and golden tests.
+//!
+//! These helpers serialize maps with entries sorted by key. The encoding is a
+//! plain serde map, so the byte layout is unchanged and existing snapshots
+//! still deserialize into the same `HashMap` types.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+/// Serialize-only view of a hash map with entries in key order.
+pub(crate) struct SortedMap<'a, K, V, H>(pub(crate) &'a HashMap<K, V, H>);
+
+impl<K, V, H> Serialize for SortedMap<'_, K, V, H>
+where
+    K: Ord + Serialize,
+    V: Serialize,
+    H: BuildHasher,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<(&K, &V)> = self.0.iter().collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+        for (k, v) in entries {
+            map.serialize_entry(k, v)?;
+        }
+        map.end()
+    }
+}
+
+/// `serialize_with` adapter for `HashMap` fields.
+pub(crate) fn sorted_map<K, V, H, S>(
+    map: &HashMap<K, V, H>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    K: Ord + Serialize,
+    V: Serialize,
+    H: BuildHasher,
+    S: Serializer,
+{
+    SortedMap(map).serialize(serializer)
+}
+
+/// `serialize_with` adapter for `AHashMap` map-of-map fields (both levels sorted).
+pub(crate) fn sorted_nested_ahash_map<K, K2, V, S>(
+    map: &ahash::AHashMap<K, ahash::AHashMap<K2, V>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    K: Ord + Serialize,
+    K2: Ord + Serialize,
+    V: Serialize,
+    S: Serializer,
+{
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    let mut out = serializer.serialize_map(Some(entries.len()))?;
+    for (k, inner) in entries {
+        let inner: &HashMap<K2, V, ahash::RandomState> = inner;
+        out.serialize_entry(k, &SortedMap(inner))?;
+    }
+    out.end()
+}
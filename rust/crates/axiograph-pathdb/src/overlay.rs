@@ -0,0 +1,556 @@
+//! Hypothetical ("what-if") overlays.
+//!
+//! An [`Overlay`] stages entity/relation additions and removals on top of a
+//! base snapshot without mutating it, so analysts can ask "if we add these
+//! facts, what becomes reachable and which constraints break?".
+//!
+//! The overlay borrows the base and keeps only the staged delta, so opening
+//! one costs nothing however large the base is:
+//!
+//! - [`Overlay::follow_one`], [`Overlay::follow_path`],
+//!   [`Overlay::find_by_type`] and [`Overlay::reachability_diff`] read the
+//!   base through the delta: removals hide base entities and edges per hop
+//!   (a [`ContextScope`]), staged edges are followed next to the stored ones
+//!   (registered inverses included; virtual relations only see the base);
+//! - [`Overlay::view`] materializes base plus delta as a [`PathDB`] on first
+//!   use, for the rest of the query API (`execute`, AxQL, composite lookups,
+//!   ...) and for [`Overlay::check`]; later staging keeps it in sync.
+//!
+//! In both:
+//!
+//! - entity ids are stable: base ids name the same entities, staged entities
+//!   get fresh ids after the base's;
+//! - relation ids of the view are **not** stable once a relation is removed
+//!   (the view's relation store is compacted);
+//! - removing an entity detaches it: its incident relations are dropped and it
+//!   leaves every type index, but its id stays allocated.
+//!
+//! [`Overlay::check`] compares Rust-side checks (`CheckedDb`), key/functional
+//! theory constraints and cross-context contradictions between base and view
+//! and reports only what the staged changes introduce.
+
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet};
+
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use crate::axi_semantics::{AxiTypeCheckError, ConstraintDecl, MetaPlaneIndex};
+use crate::checked_db::CheckedDb;
+use crate::context::ContextContradiction;
+use crate::error::Result;
+use crate::{ContextScope, PathDB, PathDbError, PathQuery, StrId};
+
+/// One staged change, in the order it was applied.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum StagedChange {
+    AddEntity {
+        entity: u32,
+        type_name: String,
+        attrs: Vec<(String, String)>,
+    },
+    AddRelation {
+        rel_type: String,
+        source: u32,
+        target: u32,
+        confidence: f32,
+        attrs: Vec<(String, String)>,
+    },
+    /// Removes every `source -rel_type-> target` edge.
+    RemoveRelation {
+        source: u32,
+        rel_type: String,
+        target: u32,
+    },
+    RemoveEntity {
+        entity: u32,
+    },
+}
+
+/// Entities reachable in the view but not in the base (`gained`), and the
+/// other way round (`lost`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReachabilityDiff {
+    pub gained: RoaringBitmap,
+    pub lost: RoaringBitmap,
+}
+
+/// A violated `constraint key` / `constraint functional`, witnessed by two
+/// fact nodes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ConstraintViolation {
+    Key {
+        schema: String,
+        relation: String,
+        fields: Vec<String>,
+        facts: [u32; 2],
+    },
+    Functional {
+        schema: String,
+        relation: String,
+        src_field: String,
+        dst_field: String,
+        facts: [u32; 2],
+    },
+}
+
+/// Problems present in the overlay view but not in its base.
+#[derive(Debug, Clone, Default)]
+pub struct OverlayCheck {
+    pub type_errors: Vec<AxiTypeCheckError>,
+    pub context_errors: Vec<String>,
+    pub modal_errors: Vec<String>,
+    pub violations: Vec<ConstraintViolation>,
+    pub contradictions: Vec<ContextContradiction>,
+}
+
+impl OverlayCheck {
+    /// True when the staged changes introduce no new problem.
+    pub fn ok(&self) -> bool {
+        self.type_errors.is_empty()
+            && self.context_errors.is_empty()
+            && self.modal_errors.is_empty()
+            && self.violations.is_empty()
+            && self.contradictions.is_empty()
+    }
+}
+
+/// A staged edge that is still in effect.
+#[derive(Debug, Clone)]
+struct StagedEdge {
+    rel_type: String,
+    source: u32,
+    target: u32,
+}
+
+/// Staged additions/removals layered over a base snapshot.
+pub struct Overlay<'a> {
+    base: &'a PathDB,
+    changes: Vec<StagedChange>,
+    /// `(type, attrs)` of staged entities, by id minus the base's entity count.
+    entities: Vec<(String, Vec<(String, String)>)>,
+    edges: Vec<StagedEdge>,
+    removed_entities: RoaringBitmap,
+    /// Base edges hidden by `remove_relation`.
+    removed_edges: HashSet<(u32, StrId, u32)>,
+    /// Base plus `changes`, materialized on first use.
+    view: OnceCell<PathDB>,
+}
+
+impl PathDB {
+    /// Start a what-if overlay over this snapshot.
+    pub fn overlay(&self) -> Overlay<'_> {
+        Overlay::new(self)
+    }
+}
+
+impl<'a> Overlay<'a> {
+    pub fn new(base: &'a PathDB) -> Self {
+        Self {
+            base,
+            changes: Vec::new(),
+            entities: Vec::new(),
+            edges: Vec::new(),
+            removed_entities: RoaringBitmap::new(),
+            removed_edges: HashSet::new(),
+            view: OnceCell::new(),
+        }
+    }
+
+    pub fn base(&self) -> &'a PathDB {
+        self.base
+    }
+
+    pub fn changes(&self) -> &[StagedChange] {
+        &self.changes
+    }
+
+    /// Base plus the staged changes as a [`PathDB`], built on first call.
+    pub fn view(&self) -> Result<&PathDB> {
+        if let Some(view) = self.view.get() {
+            return Ok(view);
+        }
+        let view = self.materialize()?;
+        Ok(self.view.get_or_init(|| view))
+    }
+
+    /// The view with the staged changes applied, detached from the base.
+    pub fn into_view(mut self) -> Result<PathDB> {
+        match self.view.take() {
+            Some(view) => Ok(view),
+            None => self.materialize(),
+        }
+    }
+
+    fn materialize(&self) -> Result<PathDB> {
+        let mut view = PathDB::from_bytes(&self.base.to_bytes()?)?;
+        view.path_index
+            .set_max_depth(self.base.path_index.max_depth());
+        view.set_supernode_threshold(self.base.supernode_threshold());
+        for change in &self.changes {
+            apply_change(&mut view, change);
+        }
+        Ok(view)
+    }
+
+    /// Record `change`, keeping a materialized view in sync.
+    fn stage(&mut self, change: StagedChange) {
+        if let Some(view) = self.view.get_mut() {
+            apply_change(view, &change);
+        }
+        self.changes.push(change);
+    }
+
+    fn base_len(&self) -> u32 {
+        self.base.entities.len() as u32
+    }
+
+    fn check_entity(&self, entity: u32) -> Result<()> {
+        if entity >= self.base_len() + self.entities.len() as u32 {
+            return Err(PathDbError::UnknownEntity(entity));
+        }
+        Ok(())
+    }
+
+    pub fn add_entity(&mut self, type_name: &str, attrs: Vec<(&str, &str)>) -> u32 {
+        let entity = self.base_len() + self.entities.len() as u32;
+        let attrs: Vec<(String, String)> = attrs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        self.entities.push((type_name.to_string(), attrs.clone()));
+        self.stage(StagedChange::AddEntity {
+            entity,
+            type_name: type_name.to_string(),
+            attrs,
+        });
+        entity
+    }
+
+    pub fn add_relation(
+        &mut self,
+        rel_type: &str,
+        source: u32,
+        target: u32,
+        confidence: f32,
+        attrs: Vec<(&str, &str)>,
+    ) -> Result<()> {
+        self.check_entity(source)?;
+        self.check_entity(target)?;
+        self.edges.push(StagedEdge {
+            rel_type: rel_type.to_string(),
+            source,
+            target,
+        });
+        self.stage(StagedChange::AddRelation {
+            rel_type: rel_type.to_string(),
+            source,
+            target,
+            confidence,
+            attrs: attrs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        });
+        Ok(())
+    }
+
+    /// Remove every `source -rel_type-> target` edge; returns how many.
+    pub fn remove_relation(&mut self, source: u32, rel_type: &str, target: u32) -> usize {
+        let mut removed = 0;
+        if let Some(rel) = self.base.interner.id_of(rel_type) {
+            let visible = !self.removed_edges.contains(&(source, rel, target))
+                && !self.removed_entities.contains(source)
+                && !self.removed_entities.contains(target);
+            if visible {
+                removed += self
+                    .base
+                    .relations
+                    .outgoing(source, rel)
+                    .iter()
+                    .filter(|r| r.target == target)
+                    .count();
+            }
+            self.removed_edges.insert((source, rel, target));
+        }
+        let staged = self.edges.len();
+        self.edges
+            .retain(|e| !(e.source == source && e.rel_type == rel_type && e.target == target));
+        removed += staged - self.edges.len();
+        if removed > 0 {
+            self.stage(StagedChange::RemoveRelation {
+                source,
+                rel_type: rel_type.to_string(),
+                target,
+            });
+        }
+        removed
+    }
+
+    /// Detach `entity`: drop its incident relations and remove it from every
+    /// type index.
+    pub fn remove_entity(&mut self, entity: u32) -> Result<()> {
+        self.check_entity(entity)?;
+        self.removed_entities.insert(entity);
+        self.edges
+            .retain(|e| e.source != entity && e.target != entity);
+        self.stage(StagedChange::RemoveEntity { entity });
+        Ok(())
+    }
+
+    /// Entities of `type_name` in the view (`None` if there are none).
+    pub fn find_by_type(&self, type_name: &str) -> Option<RoaringBitmap> {
+        let mut out = self
+            .base
+            .find_by_type(type_name)
+            .cloned()
+            .unwrap_or_default();
+        let base_len = self.base_len();
+        out.extend(
+            self.entities
+                .iter()
+                .enumerate()
+                .filter(|(_, (t, _))| t == type_name)
+                .map(|(i, _)| base_len + i as u32),
+        );
+        out -= &self.removed_entities;
+        (!out.is_empty()).then_some(out)
+    }
+
+    /// Targets of `source` along `rel_type` in the view.
+    pub fn follow_one(&self, source: u32, rel_type: &str) -> RoaringBitmap {
+        self.step(&self.base_scope(), source, rel_type)
+    }
+
+    /// Entities reachable from `start` along `path` in the view.
+    pub fn follow_path(&self, start: u32, path: &[&str]) -> RoaringBitmap {
+        let scope = self.base_scope();
+        let mut current = RoaringBitmap::from_iter([start]);
+        for rel_type in path {
+            let mut next = RoaringBitmap::new();
+            for entity in current.iter() {
+                next |= self.step(&scope, entity, rel_type);
+            }
+            current = next;
+            if current.is_empty() {
+                break;
+            }
+        }
+        current
+    }
+
+    /// What of the base a traversal still sees: every base entity but the
+    /// removed ones, minus removed edges.
+    fn base_scope(&self) -> ContextScope {
+        let mut entities = RoaringBitmap::new();
+        entities.insert_range(0..self.base_len());
+        entities -= &self.removed_entities;
+        self.removed_edges
+            .iter()
+            .fold(ContextScope::over(entities), |scope, &(s, rel, t)| {
+                scope.hide_edge(s, rel, t)
+            })
+    }
+
+    /// One hop from `source`: base edges `scope` allows, plus staged edges
+    /// (and staged edges of `rel_type`'s registered inverse, reversed).
+    fn step(&self, scope: &ContextScope, source: u32, rel_type: &str) -> RoaringBitmap {
+        let mut out = if source < self.base_len() {
+            self.base.execute_within(
+                &PathQuery::SelectRelated(source, rel_type.to_string()),
+                scope,
+            )
+        } else {
+            RoaringBitmap::new()
+        };
+        let inverse = self
+            .base
+            .interner
+            .id_of(rel_type)
+            .and_then(|rel| self.base.inverses.inverse(rel))
+            .and_then(|inv| self.base.interner.lookup(inv));
+        for edge in &self.edges {
+            if edge.source == source && edge.rel_type == rel_type {
+                out.insert(edge.target);
+            }
+            if edge.target == source && inverse.as_deref() == Some(edge.rel_type.as_str()) {
+                out.insert(edge.source);
+            }
+        }
+        out
+    }
+
+    /// How `follow_path(start, path)` changes under the staged changes.
+    pub fn reachability_diff(&self, start: u32, path: &[&str]) -> ReachabilityDiff {
+        let before = self.base.follow_path(start, path);
+        let after = self.follow_path(start, path);
+        ReachabilityDiff {
+            gained: &after - &before,
+            lost: &before - &after,
+        }
+    }
+
+    /// Checks that fail in the view but not in the base.
+    pub fn check(&self) -> Result<OverlayCheck> {
+        let view = self.view()?;
+        let base_report = CheckedDb::check(self.base)?;
+        let view_report = CheckedDb::check(view)?;
+        Ok(OverlayCheck {
+            type_errors: new_items(
+                &base_report.axi_fact_typecheck.errors,
+                view_report.axi_fact_typecheck.errors,
+            ),
+            context_errors: new_items(
+                &base_report.context_invariants.errors,
+                view_report.context_invariants.errors,
+            ),
+            modal_errors: new_items(
+                &base_report.modal_invariants.errors,
+                view_report.modal_invariants.errors,
+            ),
+            violations: new_items(
+                &constraint_violations(self.base),
+                constraint_violations(view),
+            ),
+            contradictions: new_items(
+                &self.base.context_contradictions(),
+                view.context_contradictions(),
+            ),
+        })
+    }
+}
+
+/// Apply one staged change to a materialized view.
+fn apply_change(view: &mut PathDB, change: &StagedChange) {
+    match change {
+        StagedChange::AddEntity {
+            type_name, attrs, ..
+        } => {
+            view.add_entity(
+                type_name,
+                attrs
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+                    .collect(),
+            );
+        }
+        StagedChange::AddRelation {
+            rel_type,
+            source,
+            target,
+            confidence,
+            attrs,
+        } => {
+            view.add_relation(
+                rel_type,
+                *source,
+                *target,
+                *confidence,
+                attrs
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+                    .collect(),
+            );
+        }
+        StagedChange::RemoveRelation {
+            source,
+            rel_type,
+            target,
+        } => {
+            view.remove_relation(*source, rel_type, *target);
+        }
+        StagedChange::RemoveEntity { entity } => {
+            let entity = *entity;
+            view.retain_relations(|rel| rel.source != entity && rel.target != entity);
+            for ids in view.entities.type_index.values_mut() {
+                ids.remove(entity);
+            }
+            view.unindex_composite(entity);
+            view.fact_index.invalidate();
+            view.text_index.invalidate();
+            view.path_index.invalidate();
+        }
+    }
+}
+
+/// Items of `after` that are not in `before`.
+fn new_items<T: PartialEq>(before: &[T], after: Vec<T>) -> Vec<T> {
+    after.into_iter().filter(|e| !before.contains(e)).collect()
+}
+
+/// Key and functional constraint violations among imported fact nodes.
+pub fn constraint_violations(db: &PathDB) -> Vec<ConstraintViolation> {
+    let Ok(meta) = MetaPlaneIndex::from_db(db) else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    for (schema_name, schema) in &meta.schemas {
+        for (relation, constraints) in &schema.constraints_by_relation {
+            let facts = db.fact_nodes_by_axi_schema_relation(schema_name, relation);
+            let field = |fact: u32, name: &str| {
+                db.interner
+                    .id_of(name)
+                    .and_then(|id| db.relations.targets(fact, id).min())
+            };
+            for constraint in constraints {
+                match constraint {
+                    ConstraintDecl::Key { fields, .. } if !fields.is_empty() => {
+                        let mut seen: HashMap<Vec<u32>, u32> = HashMap::new();
+                        for fact in facts.iter() {
+                            let Some(key) = fields
+                                .iter()
+                                .map(|f| field(fact, f))
+                                .collect::<Option<Vec<_>>>()
+                            else {
+                                continue;
+                            };
+                            if let Some(&first) = seen.get(&key) {
+                                out.push(ConstraintViolation::Key {
+                                    schema: schema_name.clone(),
+                                    relation: relation.clone(),
+                                    fields: fields.clone(),
+                                    facts: [first, fact],
+                                });
+                            } else {
+                                seen.insert(key, fact);
+                            }
+                        }
+                    }
+                    ConstraintDecl::Functional {
+                        src_field,
+                        dst_field,
+                        ..
+                    } => {
+                        let mut seen: HashMap<u32, (u32, u32)> = HashMap::new();
+                        for fact in facts.iter() {
+                            let (Some(src), Some(dst)) =
+                                (field(fact, src_field), field(fact, dst_field))
+                            else {
+                                continue;
+                            };
+                            match seen.get(&src) {
+                                Some(&(first, first_dst)) if first_dst != dst => {
+                                    out.push(ConstraintViolation::Functional {
+                                        schema: schema_name.clone(),
+                                        relation: relation.clone(),
+                                        src_field: src_field.clone(),
+                                        dst_field: dst_field.clone(),
+                                        facts: [first, fact],
+                                    });
+                                }
+                                Some(_) => {}
+                                None => {
+                                    seen.insert(src, (fact, dst));
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,175 @@
+//! Paginated and streaming query results.
+//!
+//! [`PathDB::execute`] returns a `RoaringBitmap`, which is cheap even for
+//! millions of ids; the expensive part is resolving every id into an
+//! [`EntityView`]. This module lets callers (REST/gRPC handlers, the REPL)
+//! walk a result one page at a time, or lazily, instead of materializing the
+//! whole answer.
+//!
+//! A [`PageCursor`] is an opaque resume point. For bitmap results it records
+//! the smallest id not yet returned, so paging stays correct even if the
+//! result is recomputed between requests (ids are stable; new ids only
+//! append). For row results ([`page_rows`]) it records an offset.
+
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PathDbError, Result};
+use crate::{EntityView, PathDB, PathQuery};
+
+const CURSOR_PREFIX: &str = "pc1.";
+
+/// Opaque resume point for a paged result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PageCursor {
+    start: u64,
+}
+
+impl PageCursor {
+    /// Stable string form, suitable for query strings and JSON.
+    pub fn encode(&self) -> String {
+        format!("{CURSOR_PREFIX}{:x}", self.start)
+    }
+
+    pub fn decode(text: &str) -> Result<Self> {
+        let start = text
+            .trim()
+            .strip_prefix(CURSOR_PREFIX)
+            .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+            .ok_or_else(|| PathDbError::InvalidCursor(text.to_string()))?;
+        Ok(Self { start })
+    }
+}
+
+impl std::fmt::Display for PageCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl std::str::FromStr for PageCursor {
+    type Err = PathDbError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::decode(s)
+    }
+}
+
+/// One page of a larger result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Where the next page starts; `None` on the last page.
+    pub next: Option<PageCursor>,
+    /// Size of the full result (all pages).
+    pub total: u64,
+}
+
+impl<T> Page<T> {
+    pub fn is_last(&self) -> bool {
+        self.next.is_none()
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next: self.next,
+            total: self.total,
+        }
+    }
+}
+
+/// Take up to `limit` ids of `ids`, starting at `cursor`.
+pub fn page_bitmap(ids: &RoaringBitmap, cursor: Option<&PageCursor>, limit: usize) -> Page<u32> {
+    let total = ids.len();
+    let start = cursor.map_or(0, |c| c.start);
+    let Ok(start) = u32::try_from(start) else {
+        return Page {
+            items: Vec::new(),
+            next: None,
+            total,
+        };
+    };
+    let mut rest = ids.clone();
+    rest.remove_range(..start);
+    let items: Vec<u32> = rest.iter().take(limit).collect();
+    let next = ((items.len() as u64) < rest.len()).then(|| PageCursor {
+        start: items
+            .last()
+            .map_or(u64::from(start), |&last| u64::from(last) + 1),
+    });
+    Page { items, next, total }
+}
+
+/// Take up to `limit` rows of `rows`, starting at `cursor` (an offset).
+pub fn page_rows<T: Clone>(rows: &[T], cursor: Option<&PageCursor>, limit: usize) -> Page<T> {
+    let start = cursor.map_or(0, |c| c.start).min(rows.len() as u64) as usize;
+    let end = start.saturating_add(limit).min(rows.len());
+    Page {
+        items: rows[start..end].to_vec(),
+        next: (end < rows.len()).then_some(PageCursor { start: end as u64 }),
+        total: rows.len() as u64,
+    }
+}
+
+/// Lazily resolves ids into [`EntityView`]s; ids with no entity are skipped.
+pub struct EntityViews<'a, I> {
+    db: &'a PathDB,
+    ids: I,
+}
+
+impl<I: Iterator<Item = u32>> Iterator for EntityViews<'_, I> {
+    type Item = EntityView;
+
+    fn next(&mut self) -> Option<EntityView> {
+        self.ids.by_ref().find_map(|id| self.db.get_entity(id))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.ids.size_hint().1)
+    }
+}
+
+impl PathDB {
+    /// Run `query` and return one page of matching ids (ascending).
+    pub fn execute_paged(
+        &self,
+        query: &PathQuery,
+        cursor: Option<&PageCursor>,
+        limit: usize,
+    ) -> Page<u32> {
+        page_bitmap(&self.execute(query), cursor, limit)
+    }
+
+    /// [`Self::execute_paged`] with each id resolved to an [`EntityView`].
+    pub fn execute_paged_views(
+        &self,
+        query: &PathQuery,
+        cursor: Option<&PageCursor>,
+        limit: usize,
+    ) -> Page<EntityView> {
+        let page = self.execute_paged(query, cursor, limit);
+        Page {
+            items: self.entity_views(page.items).collect(),
+            next: page.next,
+            total: page.total,
+        }
+    }
+
+    /// Run `query` and stream its results as [`EntityView`]s, resolving each
+    /// entity only when the iterator reaches it.
+    pub fn execute_iter(&self, query: &PathQuery) -> EntityViews<'_, roaring::bitmap::IntoIter> {
+        self.entity_views(self.execute(query))
+    }
+
+    /// Lazily resolve `ids` into [`EntityView`]s.
+    pub fn entity_views<I: IntoIterator<Item = u32>>(
+        &self,
+        ids: I,
+    ) -> EntityViews<'_, I::IntoIter> {
+        EntityViews {
+            db: self,
+            ids: ids.into_iter(),
+        }
+    }
+}
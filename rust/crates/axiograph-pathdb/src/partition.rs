@@ -0,0 +1,188 @@
+//! Confidence-threshold partition of a snapshot.
+//!
+//! The trusted checker should only see facts above a certified confidence
+//! threshold. [`PathDB::partition_by_confidence`] splits a snapshot into two
+//! `PathDBExportV1` exports:
+//!
+//! - the **certified core**: every relation whose confidence is at or above
+//!   the threshold, and
+//! - the **evidence plane**: every other relation.
+//!
+//! Both keep the full entity table (with its attributes), so entity ids line
+//! up across the two exports and their union is the original snapshot. The
+//! comparison is done in fixed point ([`FixedPointProbability`]), the same
+//! representation the checker uses.
+//!
+//! The [`ConfidencePartitionProofV1`] (`confidence_partition_v1` certificate,
+//! anchored to the core export) records the threshold and the digests and
+//! relation counts of the source snapshot and both sides;
+//! [`ConfidencePartitionProofV1::check`] replays it against the exports.
+
+use serde::{Deserialize, Serialize};
+
+use crate::axi_export::{export_pathdb_to_axi_v1, import_pathdb_from_axi_v1};
+use crate::certificate::{AxiAnchorV1, CertificateV2, FixedPointProbability};
+use crate::error::{PathDbError, Result};
+use crate::PathDB;
+
+/// One side of a [`ConfidencePartitionProofV1`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartitionSideV1 {
+    /// `axi_digest_v1` of the side's `PathDBExportV1` text.
+    pub axi_digest_v1: String,
+    pub relation_count: u32,
+}
+
+/// Proof that a snapshot was split at `threshold_fp`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfidencePartitionProofV1 {
+    /// Relations with `confidence_fp >= threshold_fp` are in the core.
+    pub threshold_fp: FixedPointProbability,
+    /// The unsplit snapshot's export.
+    pub source: PartitionSideV1,
+    pub entity_count: u32,
+    pub core: PartitionSideV1,
+    pub evidence: PartitionSideV1,
+}
+
+/// Result of [`PathDB::partition_by_confidence`].
+#[derive(Debug, Clone)]
+pub struct ConfidencePartition {
+    /// `PathDBExportV1` text of the certified core.
+    pub core_axi: String,
+    /// `PathDBExportV1` text of the evidence plane.
+    pub evidence_axi: String,
+    pub proof: ConfidencePartitionProofV1,
+}
+
+impl ConfidencePartition {
+    /// The `confidence_partition_v1` certificate, anchored to the core export.
+    pub fn certificate(&self) -> CertificateV2 {
+        CertificateV2::confidence_partition_v1(self.proof.clone()).with_anchor(AxiAnchorV1 {
+            axi_digest_v1: self.proof.core.axi_digest_v1.clone(),
+        })
+    }
+}
+
+impl ConfidencePartitionProofV1 {
+    /// Replay the proof against the source snapshot export and the two sides:
+    /// the digests match, every core relation is at or above the threshold,
+    /// every evidence relation below it, the entity tables agree and the
+    /// relation counts add up.
+    pub fn check(
+        &self,
+        source_axi: &str,
+        core_axi: &str,
+        evidence_axi: &str,
+    ) -> std::result::Result<(), String> {
+        let source = check_side("source", &self.source, source_axi)?;
+        let core = check_side("core", &self.core, core_axi)?;
+        let evidence = check_side("evidence", &self.evidence, evidence_axi)?;
+
+        for (name, db) in [
+            ("source", &source),
+            ("core", &core),
+            ("evidence", &evidence),
+        ] {
+            if db.entities.len() as u32 != self.entity_count {
+                return Err(format!(
+                    "{name}: {} entities, expected {}",
+                    db.entities.len(),
+                    self.entity_count
+                ));
+            }
+        }
+        if self.core.relation_count + self.evidence.relation_count != self.source.relation_count {
+            return Err(format!(
+                "core ({}) + evidence ({}) relations do not add up to the source ({})",
+                self.core.relation_count, self.evidence.relation_count, self.source.relation_count
+            ));
+        }
+        if let Some(id) = first_relation(&core, |c| c < self.threshold_fp.numerator()) {
+            return Err(format!("core relation {id} is below the threshold"));
+        }
+        if let Some(id) = first_relation(&evidence, |c| c >= self.threshold_fp.numerator()) {
+            return Err(format!(
+                "evidence relation {id} is at or above the threshold"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Id of the first relation whose fixed-point confidence numerator satisfies
+/// `pred`.
+fn first_relation(db: &PathDB, pred: impl Fn(u32) -> bool) -> Option<u32> {
+    (0..db.relations.len() as u32).find(|&id| {
+        db.relations
+            .get_relation(id)
+            .is_some_and(|rel| pred(FixedPointProbability::from_f32(rel.confidence).numerator()))
+    })
+}
+
+fn check_side(
+    name: &str,
+    side: &PartitionSideV1,
+    text: &str,
+) -> std::result::Result<PathDB, String> {
+    let digest = axiograph_dsl::digest::axi_digest_v1(text);
+    if digest != side.axi_digest_v1 {
+        return Err(format!(
+            "{name}: digest {digest} does not match {}",
+            side.axi_digest_v1
+        ));
+    }
+    let db = import_pathdb_from_axi_v1(text).map_err(|e| format!("{name}: {e}"))?;
+    if db.relations.len() as u32 != side.relation_count {
+        return Err(format!(
+            "{name}: {} relations, expected {}",
+            db.relations.len(),
+            side.relation_count
+        ));
+    }
+    Ok(db)
+}
+
+impl PathDB {
+    /// Split the snapshot at `threshold` into a certified core and an
+    /// evidence plane (see the module docs). `self` is left unchanged.
+    pub fn partition_by_confidence(&self, threshold: f32) -> Result<ConfidencePartition> {
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(PathDbError::InvalidThreshold(threshold));
+        }
+        let threshold_fp = FixedPointProbability::from_f32(threshold);
+        let in_core = |confidence: f32| {
+            FixedPointProbability::from_f32(confidence).numerator() >= threshold_fp.numerator()
+        };
+
+        let bytes = self.to_bytes()?;
+        let mut core = PathDB::from_bytes(&bytes)?;
+        core.retain_relations(|rel| in_core(rel.confidence));
+        let mut evidence = PathDB::from_bytes(&bytes)?;
+        evidence.retain_relations(|rel| !in_core(rel.confidence));
+
+        let side = |db: &PathDB| -> Result<(String, PartitionSideV1)> {
+            let text = export_pathdb_to_axi_v1(db)?;
+            let side = PartitionSideV1 {
+                axi_digest_v1: axiograph_dsl::digest::axi_digest_v1(&text),
+                relation_count: db.relations.len() as u32,
+            };
+            Ok((text, side))
+        };
+        let (_, source) = side(self)?;
+        let (core_axi, core) = side(&core)?;
+        let (evidence_axi, evidence) = side(&evidence)?;
+
+        Ok(ConfidencePartition {
+            core_axi,
+            evidence_axi,
+            proof: ConfidencePartitionProofV1 {
+                threshold_fp,
+                source,
+                entity_count: self.entities.len() as u32,
+                core,
+                evidence,
+            },
+        })
+    }
+}
@@ -0,0 +1,198 @@
+//! JSON bindings for [`PathQuery`].
+//!
+//! Services that do not speak AxQL can send structured queries instead. The
+//! wire shape is a versioned envelope around a tree of operations, each an
+//! object tagged by `op`:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "query": {
+//!     "op": "with_confidence",
+//!     "min_confidence": 0.8,
+//!     "base": { "op": "follow_path", "start": 0, "path": ["hasChild", "hasChild"] }
+//!   }
+//! }
+//! ```
+//!
+//! | `op`                   | fields                                                   |
+//! |------------------------|----------------------------------------------------------|
+//! | `select_by_type`       | `type_name`                                              |
+//! | `select_related`       | `source`, `rel_type`                                     |
+//! | `follow_path`          | `start`, `path`                                          |
+//! | `find_paths`           | `from`, `to`, `max_depth`                                |
+//! | `join`, `union`        | `left`, `right`                                          |
+//! | `with_confidence`      | `base`, `min_confidence`                                 |
+//! | `with_path_confidence` | `base`, `min_path_confidence`, `combiner` (default `product`) |
+//!
+//! Deserialization is strict: unknown `op`s or fields, missing fields,
+//! confidences outside `[0, 1]` and unsupported envelope versions are errors.
+//! `PathQuery` itself (de)serializes as the bare operation tree; the envelope
+//! is [`PathQueryRequest`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PathDbError, Result};
+use crate::{ConfidenceCombiner, PathQuery};
+
+/// Current [`PathQueryRequest::version`].
+pub const PATH_QUERY_JSON_VERSION: u32 = 1;
+
+/// A versioned JSON query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PathQueryRequest {
+    pub version: u32,
+    pub query: PathQuery,
+}
+
+impl PathQueryRequest {
+    pub fn new(query: PathQuery) -> Self {
+        Self {
+            version: PATH_QUERY_JSON_VERSION,
+            query,
+        }
+    }
+
+    /// Parse and validate a request.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let request: Self =
+            serde_json::from_str(json).map_err(|e| PathDbError::InvalidQuery(e.to_string()))?;
+        if request.version != PATH_QUERY_JSON_VERSION {
+            return Err(PathDbError::InvalidQuery(format!(
+                "unsupported query version {} (expected {PATH_QUERY_JSON_VERSION})",
+                request.version
+            )));
+        }
+        Ok(request)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("PathQueryRequest serializes")
+    }
+}
+
+/// Wire form of one [`PathQuery`] node.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case", deny_unknown_fields)]
+pub(crate) enum PathQueryJson {
+    SelectByType {
+        type_name: String,
+    },
+    SelectRelated {
+        source: u32,
+        rel_type: String,
+    },
+    FollowPath {
+        start: u32,
+        path: Vec<String>,
+    },
+    FindPaths {
+        from: u32,
+        to: u32,
+        max_depth: usize,
+    },
+    Join {
+        left: Box<PathQuery>,
+        right: Box<PathQuery>,
+    },
+    Union {
+        left: Box<PathQuery>,
+        right: Box<PathQuery>,
+    },
+    WithConfidence {
+        base: Box<PathQuery>,
+        min_confidence: f32,
+    },
+    WithPathConfidence {
+        base: Box<PathQuery>,
+        min_path_confidence: f32,
+        #[serde(default)]
+        combiner: ConfidenceCombiner,
+    },
+}
+
+impl From<PathQuery> for PathQueryJson {
+    fn from(query: PathQuery) -> Self {
+        match query {
+            PathQuery::SelectByType(type_name) => Self::SelectByType { type_name },
+            PathQuery::SelectRelated(source, rel_type) => Self::SelectRelated { source, rel_type },
+            PathQuery::FollowPath { start, path } => Self::FollowPath { start, path },
+            PathQuery::FindPaths {
+                from,
+                to,
+                max_depth,
+            } => Self::FindPaths {
+                from,
+                to,
+                max_depth,
+            },
+            PathQuery::Join(left, right) => Self::Join { left, right },
+            PathQuery::Union(left, right) => Self::Union { left, right },
+            PathQuery::WithConfidence {
+                base,
+                min_confidence,
+            } => Self::WithConfidence {
+                base,
+                min_confidence,
+            },
+            PathQuery::WithPathConfidence {
+                base,
+                min_path_confidence,
+                combiner,
+            } => Self::WithPathConfidence {
+                base,
+                min_path_confidence,
+                combiner,
+            },
+        }
+    }
+}
+
+impl TryFrom<PathQueryJson> for PathQuery {
+    type Error = String;
+
+    fn try_from(json: PathQueryJson) -> std::result::Result<Self, String> {
+        let check = |field: &str, value: f32| {
+            if (0.0..=1.0).contains(&value) {
+                Ok(value)
+            } else {
+                Err(format!("`{field}` must be in [0, 1], got {value}"))
+            }
+        };
+        Ok(match json {
+            PathQueryJson::SelectByType { type_name } => PathQuery::SelectByType(type_name),
+            PathQueryJson::SelectRelated { source, rel_type } => {
+                PathQuery::SelectRelated(source, rel_type)
+            }
+            PathQueryJson::FollowPath { start, path } => PathQuery::FollowPath { start, path },
+            PathQueryJson::FindPaths {
+                from,
+                to,
+                max_depth,
+            } => PathQuery::FindPaths {
+                from,
+                to,
+                max_depth,
+            },
+            PathQueryJson::Join { left, right } => PathQuery::Join(left, right),
+            PathQueryJson::Union { left, right } => PathQuery::Union(left, right),
+            PathQueryJson::WithConfidence {
+                base,
+                min_confidence,
+            } => PathQuery::WithConfidence {
+                base,
+                min_confidence: check("min_confidence", min_confidence)?,
+            },
+            PathQueryJson::WithPathConfidence {
+                base,
+                min_path_confidence,
+                combiner,
+            } => PathQuery::WithPathConfidence {
+                base,
+                min_path_confidence: check("min_path_confidence", min_path_confidence)?,
+                combiner,
+            },
+        })
+    }
+}
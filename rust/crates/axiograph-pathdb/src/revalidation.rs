@@ -0,0 +1,539 @@
+//! Cross-snapshot certificate revalidation.
+//!
+//! A certificate is anchored to the `.axi` input it was issued against
+//! (`AxiAnchorV1`). When the graph changes, the anchor moves and previously
+//! issued certificates no longer refer to the current snapshot. Rather than
+//! re-running every query, [`Revalidator`] checks each certificate's witnesses
+//! against the new snapshot:
+//!
+//! - certificates whose witnesses still hold are **re-derived**: re-anchored to
+//!   the new digest, with path steps refreshed (relation ids, `axi_fact_id`s
+//!   and confidences as they are in the new snapshot);
+//! - certificates with a witness the delta removed (an entity, type, attribute
+//!   or edge, or an edge now below the query's confidence floor) are
+//!   **invalidated**, with the failing witnesses as reasons;
+//! - module certificates (`axi_well_typed_v1`, `axi_constraints_ok_v1`) are
+//!   re-checked when the new anchor is the same module.
+//!
+//! Re-derived certificates are untrusted output like any other: they still
+//! go through the trusted checker against the new anchor.
+
+use std::collections::HashMap;
+
+use axiograph_dsl::schema_v1::SchemaV1Module;
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use crate::axi_meta::{ATTR_AXI_FACT_ID, META_ATTR_NAME};
+use crate::axi_module_typecheck::TypedAxiV1Module;
+use crate::axi_semantics::MetaPlaneIndex;
+use crate::certificate::{
+    AxiAnchorV1, CertificatePayloadV2, CertificateV2, FixedPointProbability, QueryAtomWitnessV1,
+    QueryAtomWitnessV3, ReachabilityProofV2, ReachabilityProofV3, ResolutionProofV2,
+    RewriteDerivationProofV3, TypedLiteralV1,
+};
+use crate::{PathDB, StrId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevalidationStatus {
+    /// Already anchored to the new snapshot.
+    Current,
+    /// Witnesses still hold; re-anchored to the new snapshot.
+    Rederived,
+    /// A witness no longer holds in the new snapshot.
+    Invalidated,
+    /// Unanchored, or a kind that cannot be revalidated (re-emit it instead).
+    Unsupported,
+}
+
+#[derive(Debug, Clone)]
+pub struct Revalidation {
+    pub status: RevalidationStatus,
+    /// The certificate for the new snapshot (`Current` and `Rederived` only).
+    pub certificate: Option<CertificateV2>,
+    /// Why the certificate was invalidated or not revalidated.
+    pub reasons: Vec<String>,
+}
+
+impl Revalidation {
+    fn rederived(certificate: CertificateV2) -> Self {
+        Self {
+            status: RevalidationStatus::Rederived,
+            certificate: Some(certificate),
+            reasons: Vec::new(),
+        }
+    }
+
+    fn invalidated(reasons: Vec<String>) -> Self {
+        Self {
+            status: RevalidationStatus::Invalidated,
+            certificate: None,
+            reasons,
+        }
+    }
+
+    fn unsupported(reason: impl Into<String>) -> Self {
+        Self {
+            status: RevalidationStatus::Unsupported,
+            certificate: None,
+            reasons: vec![reason.into()],
+        }
+    }
+}
+
+/// Checks certificates against one new snapshot.
+pub struct Revalidator<'a> {
+    anchor: AxiAnchorV1,
+    db: &'a PathDB,
+    module: Option<&'a SchemaV1Module>,
+    meta: MetaPlaneIndex,
+}
+
+impl<'a> Revalidator<'a> {
+    /// `db` is the new snapshot and `anchor` its digest; `module` is its parsed
+    /// `.axi` module, needed to re-check module certificates.
+    pub fn new(
+        anchor: AxiAnchorV1,
+        db: &'a PathDB,
+        module: Option<&'a SchemaV1Module>,
+    ) -> crate::error::Result<Self> {
+        Ok(Self {
+            anchor,
+            db,
+            module,
+            meta: MetaPlaneIndex::from_db(db)?,
+        })
+    }
+
+    pub fn revalidate(&self, cert: &CertificateV2) -> Revalidation {
+        match &cert.anchor {
+            None => return Revalidation::unsupported("certificate has no `.axi` anchor"),
+            Some(anchor) if *anchor == self.anchor => {
+                return Revalidation {
+                    status: RevalidationStatus::Current,
+                    certificate: Some(cert.clone()),
+                    reasons: Vec::new(),
+                }
+            }
+            Some(_) => {}
+        }
+
+        let payload = match self.rederive(&cert.payload) {
+            Ok(payload) => payload,
+            Err(Rejection::Invalid(reasons)) => return Revalidation::invalidated(reasons),
+            Err(Rejection::Unsupported(reason)) => return Revalidation::unsupported(reason),
+        };
+        Revalidation::rederived(CertificateV2 {
+            version: cert.version,
+            anchor: Some(self.anchor.clone()),
+            payload,
+        })
+    }
+
+    fn rederive(&self, payload: &CertificatePayloadV2) -> Result<CertificatePayloadV2, Rejection> {
+        use CertificatePayloadV2 as P;
+        match payload {
+            P::AxiWellTypedV1 { proof } => {
+                let typed = self.typecheck_module(&proof.module_name)?;
+                let (_, proof) = typed.into_parts();
+                Ok(P::AxiWellTypedV1 { proof })
+            }
+            P::AxiConstraintsOkV1 { proof } => {
+                let typed = self.typecheck_module(&proof.module_name)?;
+                let proof =
+                    crate::axi_module_constraints::check_axi_constraints_ok_v1(typed.module())
+                        .map_err(|e| Rejection::one(format!("constraints no longer hold: {e}")))?;
+                Ok(P::AxiConstraintsOkV1 { proof })
+            }
+            P::ReachabilityV2 { proof, combiner } => Ok(P::ReachabilityV2 {
+                proof: self.path_v2(proof, None).map_err(Rejection::one)?,
+                combiner: *combiner,
+            }),
+            P::QueryResultV1 { proof } => {
+                let mut proof = proof.clone();
+                let min = proof.query.min_confidence_fp;
+                let mut reasons = Vec::new();
+                for (i, row) in proof.rows.iter_mut().enumerate() {
+                    self.witnesses_v1(&mut row.witnesses, min, i, &mut reasons);
+                }
+                Rejection::check(reasons)?;
+                Ok(P::QueryResultV1 { proof })
+            }
+            P::QueryResultV2 { proof } => {
+                let mut proof = proof.clone();
+                let min = proof.query.min_confidence_fp;
+                let mut reasons = Vec::new();
+                for (i, row) in proof.rows.iter_mut().enumerate() {
+                    self.witnesses_v1(&mut row.witnesses, min, i, &mut reasons);
+                }
+                Rejection::check(reasons)?;
+                Ok(P::QueryResultV2 { proof })
+            }
+            P::QueryResultV3 { proof } => {
+                let mut proof = proof.clone();
+                let min = proof.query.min_confidence_fp;
+                let mut reasons = Vec::new();
+                for (i, row) in proof.rows.iter_mut().enumerate() {
+                    for witness in &mut row.witnesses {
+                        if let Err(e) = self.witness_v3(witness, min) {
+                            reasons.push(format!("row {i}: {e}"));
+                        }
+                    }
+                }
+                for rewrite in &mut proof.elaboration_rewrites {
+                    if let Err(e) = self.rewrite_v3(rewrite) {
+                        reasons.push(format!("elaboration: {e}"));
+                    }
+                }
+                Rejection::check(reasons)?;
+                Ok(P::QueryResultV3 { proof })
+            }
+            P::RewriteDerivationV3 { proof } => {
+                let mut proof = proof.clone();
+                self.rewrite_v3(&mut proof).map_err(Rejection::one)?;
+                Ok(P::RewriteDerivationV3 { proof })
+            }
+            // Pure arithmetic: independent of the snapshot.
+            P::ResolutionV2 { proof } => {
+                let decided = ResolutionProofV2::decide(
+                    proof.first_confidence_fp,
+                    proof.second_confidence_fp,
+                    proof.threshold_fp,
+                );
+                if decided != *proof {
+                    return Err(Rejection::one("resolution decision does not replay"));
+                }
+                Ok(payload.clone())
+            }
+            P::RelationCollapseV1 { proof } => {
+                proof.check().map_err(Rejection::one)?;
+                Ok(payload.clone())
+            }
+            P::NormalizePathV2 { .. }
+            | P::RewriteDerivationV2 { .. }
+            | P::PathEquivV2 { .. }
+            | P::DeltaFMigrationV1 { .. }
+            | P::ConfidencePartitionV1 { .. } => Err(Rejection::Unsupported(
+                "no witness check for this certificate kind; re-emit it against the new snapshot"
+                    .to_string(),
+            )),
+        }
+    }
+
+    fn typecheck_module(&self, module_name: &str) -> Result<TypedAxiV1Module, Rejection> {
+        let module = self.module.ok_or_else(|| {
+            Rejection::Unsupported("module certificates need the new `.axi` module".to_string())
+        })?;
+        if module.module_name != module_name {
+            return Err(Rejection::one(format!(
+                "module `{module_name}` is not the anchored module (`{}`)",
+                module.module_name
+            )));
+        }
+        TypedAxiV1Module::new(module.clone()).map_err(|e| {
+            Rejection::one(format!("module `{module_name}` no longer typechecks: {e}"))
+        })
+    }
+
+    fn name(&self, id: StrId) -> String {
+        self.db
+            .interner
+            .lookup(id)
+            .unwrap_or_else(|| format!("#{}", id.raw()))
+    }
+
+    fn has_type(&self, entity: u32, type_name: &str) -> bool {
+        let Some(ty) = self.db.entities.get_type(entity) else {
+            return false;
+        };
+        let ty = self.name(ty);
+        ty == type_name
+            || self
+                .meta
+                .schemas
+                .values()
+                .any(|s| s.is_subtype(&ty, type_name))
+    }
+
+    fn witnesses_v1(
+        &self,
+        witnesses: &mut [QueryAtomWitnessV1],
+        min: Option<FixedPointProbability>,
+        row: usize,
+        reasons: &mut Vec<String>,
+    ) {
+        for witness in witnesses {
+            let checked = match witness {
+                QueryAtomWitnessV1::Type { entity, type_id } => {
+                    let type_name = self.name(StrId::new(*type_id));
+                    if self.has_type(*entity, &type_name) {
+                        Ok(())
+                    } else {
+                        Err(format!("entity {entity} is no longer a `{type_name}`"))
+                    }
+                }
+                QueryAtomWitnessV1::AttrEq {
+                    entity,
+                    key_id,
+                    value_id,
+                    literal,
+                } => {
+                    let key = StrId::new(*key_id);
+                    if self.db.entities.get_attr(*entity, key) == Some(StrId::new(*value_id)) {
+                        *literal = TypedLiteralV1::typed(&self.name(StrId::new(*value_id)));
+                        Ok(())
+                    } else {
+                        Err(format!(
+                            "entity {entity} no longer has {} = {}",
+                            self.name(key),
+                            self.name(StrId::new(*value_id))
+                        ))
+                    }
+                }
+                QueryAtomWitnessV1::Path { proof } => self.path_v2(proof, min).map(|p| *proof = p),
+            };
+            if let Err(e) = checked {
+                reasons.push(format!("row {row}: {e}"));
+            }
+        }
+    }
+
+    /// The proof's path in the new snapshot, or the first step that is gone.
+    fn path_v2(
+        &self,
+        proof: &ReachabilityProofV2,
+        min: Option<FixedPointProbability>,
+    ) -> Result<ReachabilityProofV2, String> {
+        match proof {
+            ReachabilityProofV2::Reflexive { entity } => {
+                if self.db.entities.get_type(*entity).is_none() {
+                    return Err(format!("entity {entity} is no longer in the snapshot"));
+                }
+                Ok(proof.clone())
+            }
+            ReachabilityProofV2::Step {
+                from,
+                rel_type,
+                to,
+                relation_id,
+                rest,
+                ..
+            } => {
+                let rel_type_id = StrId::new(*rel_type);
+                let same = |id: &u32| {
+                    self.db.relations.get_relation(*id).is_some_and(|r| {
+                        r.source == *from && r.target == *to && r.rel_type == rel_type_id
+                    })
+                };
+                let id = relation_id
+                    .filter(same)
+                    .or_else(|| self.db.relations.edge_relation_id(*from, rel_type_id, *to))
+                    .ok_or_else(|| {
+                        format!(
+                            "edge {from} -{}-> {to} is no longer in the snapshot",
+                            self.name(rel_type_id)
+                        )
+                    })?;
+                let confidence = self.step_confidence(id, min, || {
+                    format!("{from} -{}-> {to}", self.name(rel_type_id))
+                })?;
+                Ok(ReachabilityProofV2::Step {
+                    from: *from,
+                    rel_type: *rel_type,
+                    to: *to,
+                    rel_confidence_fp: confidence,
+                    relation_id: relation_id.map(|_| id),
+                    rest: Box::new(self.path_v2(rest, min)?),
+                })
+            }
+        }
+    }
+
+    fn step_confidence(
+        &self,
+        relation_id: u32,
+        min: Option<FixedPointProbability>,
+        edge: impl Fn() -> String,
+    ) -> Result<FixedPointProbability, String> {
+        let confidence = self
+            .db
+            .relations
+            .get_relation(relation_id)
+            .map(|r| FixedPointProbability::from_f32(r.confidence))
+            .ok_or_else(|| format!("relation {relation_id} is missing"))?;
+        match min {
+            Some(min) if confidence.numerator() < min.numerator() => Err(format!(
+                "edge {} fell below the confidence floor ({} < {})",
+                edge(),
+                confidence.numerator(),
+                min.numerator()
+            )),
+            _ => Ok(confidence),
+        }
+    }
+
+    /// Entities a name-based (v3) id refers to: a tuple fact id, else a name.
+    fn entities_v3(&self, id: &str) -> RoaringBitmap {
+        let key = if id.starts_with(axiograph_dsl::digest::AXI_FACT_ID_V1_PREFIX) {
+            ATTR_AXI_FACT_ID
+        } else {
+            META_ATTR_NAME
+        };
+        match (self.db.interner.id_of(key), self.db.interner.id_of(id)) {
+            (Some(key), Some(value)) => self.db.entities.entities_with_attr_value(key, value),
+            _ => RoaringBitmap::new(),
+        }
+    }
+
+    fn witness_v3(
+        &self,
+        witness: &mut QueryAtomWitnessV3,
+        min: Option<FixedPointProbability>,
+    ) -> Result<(), String> {
+        match witness {
+            QueryAtomWitnessV3::Type { entity, type_name } => {
+                if self
+                    .entities_v3(entity)
+                    .iter()
+                    .any(|e| self.has_type(e, type_name))
+                {
+                    Ok(())
+                } else {
+                    Err(format!("`{entity}` is no longer a `{type_name}`"))
+                }
+            }
+            QueryAtomWitnessV3::AttrEq {
+                entity,
+                key,
+                value,
+                literal,
+            } => {
+                let holds = match (self.db.interner.id_of(key), self.db.interner.id_of(value)) {
+                    (Some(k), Some(v)) => self
+                        .entities_v3(entity)
+                        .iter()
+                        .any(|e| self.db.entities.get_attr(e, k) == Some(v)),
+                    _ => false,
+                };
+                if holds {
+                    *literal = TypedLiteralV1::typed(value);
+                    Ok(())
+                } else {
+                    Err(format!("`{entity}` no longer has {key} = {value}"))
+                }
+            }
+            QueryAtomWitnessV3::Path { proof } => {
+                *proof = self.path_v3(proof, min)?;
+                Ok(())
+            }
+        }
+    }
+
+    fn path_v3(
+        &self,
+        proof: &ReachabilityProofV3,
+        min: Option<FixedPointProbability>,
+    ) -> Result<ReachabilityProofV3, String> {
+        match proof {
+            ReachabilityProofV3::Reflexive { entity } => {
+                if self.entities_v3(entity).is_empty() {
+                    return Err(format!("`{entity}` is no longer in the snapshot"));
+                }
+                Ok(proof.clone())
+            }
+            ReachabilityProofV3::Step {
+                from,
+                rel,
+                to,
+                axi_fact_id,
+                rest,
+                ..
+            } => {
+                let gone = || format!("edge `{from}` -{rel}-> `{to}` is no longer in the snapshot");
+                let rel_type = self.db.interner.id_of(rel).ok_or_else(gone)?;
+                let targets = self.entities_v3(to);
+                let mut found: Option<(u32, String)> = None;
+                for source in &self.entities_v3(from) {
+                    for target in &targets {
+                        let Some(id) = self.db.relations.edge_relation_id(source, rel_type, target)
+                        else {
+                            continue;
+                        };
+                        let fact_id = crate::witness::relation_axi_fact_id_v1(self.db, id)
+                            .map_err(|e| format!("edge `{from}` -{rel}-> `{to}`: {e}"))?;
+                        let exact = fact_id == *axi_fact_id;
+                        if found.is_none() || exact {
+                            found = Some((id, fact_id));
+                        }
+                        if exact {
+                            break;
+                        }
+                    }
+                }
+                let (id, fact_id) = found.ok_or_else(gone)?;
+                let confidence =
+                    self.step_confidence(id, min, || format!("`{from}` -{rel}-> `{to}`"))?;
+                Ok(ReachabilityProofV3::Step {
+                    from: from.clone(),
+                    rel: rel.clone(),
+                    to: to.clone(),
+                    rel_confidence_fp: confidence,
+                    axi_fact_id: fact_id,
+                    rest: Box::new(self.path_v3(rest, min)?),
+                })
+            }
+        }
+    }
+
+    /// Re-anchor `axi:<digest>:<theory>:<rule>` rule references; the rule must
+    /// still be declared in the new snapshot.
+    fn rewrite_v3(&self, proof: &mut RewriteDerivationProofV3) -> Result<(), String> {
+        let mut rules: HashMap<&str, Vec<&str>> = HashMap::new();
+        for schema in self.meta.schemas.values() {
+            for (theory, decls) in &schema.rewrite_rules_by_theory {
+                rules
+                    .entry(theory.as_str())
+                    .or_default()
+                    .extend(decls.iter().map(|d| d.name.as_str()));
+            }
+        }
+        for step in &mut proof.derivation {
+            let Some(rest) = step.rule_ref.strip_prefix("axi:") else {
+                continue;
+            };
+            let mut parts = rest.rsplitn(3, ':');
+            let (Some(rule), Some(theory), Some(_digest)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                return Err(format!("malformed rule reference `{}`", step.rule_ref));
+            };
+            if !rules.get(theory).is_some_and(|r| r.contains(&rule)) {
+                return Err(format!(
+                    "rewrite rule `{theory}.{rule}` is no longer declared"
+                ));
+            }
+            step.rule_ref = format!("axi:{}:{theory}:{rule}", self.anchor.axi_digest_v1);
+        }
+        Ok(())
+    }
+}
+
+enum Rejection {
+    Invalid(Vec<String>),
+    Unsupported(String),
+}
+
+impl Rejection {
+    fn one(reason: impl Into<String>) -> Self {
+        Self::Invalid(vec![reason.into()])
+    }
+
+    fn check(reasons: Vec<String>) -> Result<(), Self> {
+        if reasons.is_empty() {
+            Ok(())
+        } else {
+            Err(Self::Invalid(reasons))
+        }
+    }
+}
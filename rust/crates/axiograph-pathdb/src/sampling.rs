@@ -0,0 +1,283 @@
+//! Sampled (approximate) traversal for exploratory analytics.
+//!
+//! On huge graphs an exact `follow_path` / neighborhood expansion can be far
+//! too expensive for a quick "roughly how big is this?" question. The
+//! operations here run random walks under a [`QueryBudget`] and return
+//! estimates with error bounds instead:
+//!
+//! - [`PathDB::sample_path_count`]: Knuth's estimator for the number of
+//!   distinct-target walks along a relation path (unbiased; Chebyshev
+//!   interval from the sample variance);
+//! - [`PathDB::sample_neighborhood`]: random walks of 1..=`hops` steps; the
+//!   entities seen are a subset of the exact neighborhood, its size is
+//!   estimated with Chao1, and the Good–Turing / McAllester–Schapire bound
+//!   says how much of the walk distribution went unseen.
+//!
+//! Walks are seeded (deterministic for a given seed and snapshot). When the
+//! budget runs out, the walks completed so far are used and the status says
+//! so; bounds simply widen.
+//!
+//! These are **evidence-plane** operations: every result carries
+//! `certified: false` and must never feed certificates or be promoted as
+//! query answers.
+
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::budget::{BudgetTracker, Budgeted, QueryBudget};
+use crate::cardinality::mix64;
+use crate::PathDB;
+
+/// McAllester–Schapire constant for the missing-mass deviation bound.
+const MISSING_MASS_C: f64 = 2.0 * std::f64::consts::SQRT_2 + 1.732_050_807_568_877_2;
+
+/// Random-walk settings.
+#[derive(Debug, Clone)]
+pub struct SamplingConfig {
+    /// Maximum number of walks (fewer if the budget runs out first).
+    pub walks: usize,
+    pub seed: u64,
+    /// Failure probability of the reported bounds (`0.05` = 95%).
+    pub delta: f64,
+    /// Only walk edges with `confidence >= min_confidence`.
+    pub min_confidence: Option<f32>,
+    /// Every walk step counts as one visited entity.
+    pub budget: QueryBudget,
+}
+
+impl Default for SamplingConfig {
+    fn default() -> Self {
+        Self {
+            walks: 1000,
+            seed: 0,
+            delta: 0.05,
+            min_confidence: None,
+            budget: QueryBudget::unlimited(),
+        }
+    }
+}
+
+/// A point estimate with a `1 - delta` interval.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Estimate {
+    pub value: f64,
+    pub lower: f64,
+    /// `None` when the samples do not bound the value from above.
+    pub upper: Option<f64>,
+    /// `1 - delta`.
+    pub confidence: f64,
+}
+
+/// Result of [`PathDB::sample_path_count`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathCountSample {
+    /// Number of distinct-target walks along the path.
+    pub count: Estimate,
+    /// Path endpoints reached by the walks (a subset of `follow_path`).
+    pub reached: RoaringBitmap,
+    pub walks: u64,
+    /// Always false: sampled results are evidence-plane, never certified.
+    pub certified: bool,
+}
+
+/// Result of [`PathDB::sample_neighborhood`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NeighborhoodSample {
+    /// Number of entities within 1..=hops steps of the start.
+    pub size: Estimate,
+    /// Entities visited by the walks (a subset of the exact neighborhood).
+    pub reached: RoaringBitmap,
+    /// Walks that took at least one step.
+    pub walks: u64,
+    /// With probability `1 - delta`, walks ending outside `reached` have at
+    /// most this probability.
+    pub missing_mass: f64,
+    /// Always false: sampled results are evidence-plane, never certified.
+    pub certified: bool,
+}
+
+/// Counter-based SplitMix64 stream.
+struct WalkRng(u64);
+
+impl WalkRng {
+    fn new(seed: u64) -> Self {
+        Self(mix64(seed))
+    }
+
+    /// Uniform in `0..n` (`n > 0`).
+    fn below(&mut self, n: u64) -> u64 {
+        self.0 = self.0.wrapping_add(1);
+        ((u128::from(mix64(self.0)) * u128::from(n)) >> 64) as u64
+    }
+}
+
+impl PathDB {
+    /// Estimate how many distinct-target walks follow `path` from `start`
+    /// (the product of per-hop branching, summed over walks).
+    pub fn sample_path_count(
+        &self,
+        start: u32,
+        path: &[&str],
+        config: &SamplingConfig,
+    ) -> Budgeted<PathCountSample> {
+        let _span = tracing::debug_span!(
+            "pathdb.sample_path_count",
+            start,
+            hops = path.len(),
+            walks = config.walks
+        )
+        .entered();
+        let mut tracker = BudgetTracker::new(&config.budget);
+        let mut rng = WalkRng::new(config.seed);
+        let mut weights = Vec::with_capacity(config.walks);
+        let mut reached = RoaringBitmap::new();
+        'walks: for _ in 0..config.walks {
+            let mut entity = start;
+            let mut weight = 1.0_f64;
+            for rel_type in path {
+                if tracker.visit().is_err() {
+                    break 'walks;
+                }
+                let next = match config.min_confidence {
+                    None => self.follow_one(entity, rel_type),
+                    Some(min) => self.follow_one_with_min_confidence(entity, rel_type, min),
+                };
+                let degree = next.len();
+                if degree == 0 {
+                    weight = 0.0;
+                    break;
+                }
+                weight *= degree as f64;
+                entity = next
+                    .select(rng.below(degree) as u32)
+                    .expect("index below bitmap length");
+            }
+            if weight > 0.0 {
+                reached.insert(entity);
+            }
+            weights.push(weight);
+        }
+
+        let count = chebyshev_interval(&weights, config.delta, reached.len() as f64);
+        tracker.finish(PathCountSample {
+            count,
+            reached,
+            walks: weights.len() as u64,
+            certified: false,
+        })
+    }
+
+    /// Estimate the size of the `hops`-step outgoing neighborhood of `start`
+    /// over `rel_types` (every type when `None`).
+    pub fn sample_neighborhood(
+        &self,
+        start: u32,
+        rel_types: Option<&[&str]>,
+        hops: usize,
+        config: &SamplingConfig,
+    ) -> Budgeted<NeighborhoodSample> {
+        let _span = tracing::debug_span!(
+            "pathdb.sample_neighborhood",
+            start,
+            hops,
+            walks = config.walks
+        )
+        .entered();
+        let allowed: Option<Vec<_>> = rel_types.map(|names| {
+            names
+                .iter()
+                .filter_map(|name| self.interner.id_of(name))
+                .collect()
+        });
+        let mut tracker = BudgetTracker::new(&config.budget);
+        let mut rng = WalkRng::new(config.seed);
+        let mut reached = RoaringBitmap::new();
+        let mut endpoints: HashMap<u32, u64> = HashMap::new();
+        let mut walks = 0_u64;
+        'walks: for _ in 0..config.walks {
+            if hops == 0 {
+                break;
+            }
+            let length = 1 + rng.below(hops as u64);
+            let mut entity = start;
+            let mut steps = 0;
+            while steps < length {
+                if tracker.visit().is_err() {
+                    break 'walks;
+                }
+                let edges: Vec<_> = self
+                    .relations
+                    .outgoing_any(entity)
+                    .into_iter()
+                    .filter(|rel| {
+                        allowed
+                            .as_ref()
+                            .is_none_or(|ids| ids.contains(&rel.rel_type))
+                            && config
+                                .min_confidence
+                                .is_none_or(|min| rel.confidence >= min)
+                    })
+                    .collect();
+                if edges.is_empty() {
+                    break;
+                }
+                entity = edges[rng.below(edges.len() as u64) as usize].target;
+                reached.insert(entity);
+                steps += 1;
+            }
+            if steps > 0 {
+                walks += 1;
+                *endpoints.entry(entity).or_default() += 1;
+            }
+        }
+
+        let f1 = endpoints.values().filter(|&&n| n == 1).count() as f64;
+        let f2 = endpoints.values().filter(|&&n| n == 2).count() as f64;
+        let observed = reached.len() as f64;
+        let chao1 = endpoints.len() as f64 + f1 * (f1 - 1.0).max(0.0) / (2.0 * (f2 + 1.0));
+        let missing_mass = if walks == 0 {
+            1.0
+        } else {
+            let n = walks as f64;
+            (f1 / n + MISSING_MASS_C * ((3.0 / config.delta).ln() / n).sqrt()).min(1.0)
+        };
+        tracker.finish(NeighborhoodSample {
+            size: Estimate {
+                value: chao1.max(observed),
+                lower: observed,
+                upper: None,
+                confidence: 1.0 - config.delta,
+            },
+            reached,
+            walks,
+            missing_mass,
+            certified: false,
+        })
+    }
+}
+
+/// Mean of `samples` with a distribution-free Chebyshev interval (using the
+/// sample variance), clamped below by `floor`.
+fn chebyshev_interval(samples: &[f64], delta: f64, floor: f64) -> Estimate {
+    let confidence = 1.0 - delta;
+    let n = samples.len() as f64;
+    if samples.len() < 2 {
+        let value = samples.first().copied().unwrap_or(0.0).max(floor);
+        return Estimate {
+            value,
+            lower: floor,
+            upper: None,
+            confidence,
+        };
+    }
+    let mean = samples.iter().sum::<f64>() / n;
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    let half_width = (variance / n).sqrt() / delta.sqrt();
+    Estimate {
+        value: mean.max(floor),
+        lower: (mean - half_width).max(floor),
+        upper: Some((mean + half_width).max(floor)),
+        confidence,
+    }
+}
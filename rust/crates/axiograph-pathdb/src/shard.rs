@@ -0,0 +1,535 @@
+//! Graph partitioning and sharded `PathQuery` execution.
+//!
+//! A first step toward distributed deployments: split one [`PathDB`] into
+//! shards by entity, then answer queries with a coordinator that runs each
+//! step on every shard (in parallel) and merges the bitmap results.
+//!
+//! - **Partitioning.** Every entity is owned by exactly one shard, chosen by
+//!   [`PartitionStrategy::Hash`] (uniform, no locality) or
+//!   [`PartitionStrategy::Greedy`] (streaming linear deterministic greedy:
+//!   each entity joins the shard holding most of its already-placed
+//!   neighbours, damped by shard fill; a METIS-style edge-cut heuristic
+//!   without the multilevel machinery).
+//! - **Shards.** A shard is an ordinary `PathDB` with local ids. It holds its
+//!   entities (type, virtual types and attributes) and every relation whose
+//!   source it owns. Targets owned elsewhere appear as attribute-less
+//!   [`GHOST_TYPE`] entities, so edges never dangle; ghosts are never returned
+//!   by type selection.
+//! - **Coordinator.** [`ShardedDb::execute`] keeps frontiers in global ids.
+//!   Each hop splits the frontier by owner, expands it on the owning shards
+//!   and unions the results, so cross-shard paths cost one exchange per hop.
+//!
+//! Query contexts, hub policies, budgets and proof journals are single-node
+//! features and are not available here. `FindPaths` answers exact
+//! depth-bounded reachability, and is not supported under
+//! `WithPathConfidence`.
+
+use std::collections::{BTreeMap, HashMap};
+
+use rayon::prelude::*;
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use crate::cardinality::mix64;
+use crate::error::{PathDbError, Result};
+use crate::{edge_visible, ExecScope, PathConfidenceFilter, PathDB, PathQuery};
+
+/// Entity type of the placeholders standing in for remote relation targets.
+pub const GHOST_TYPE: &str = "axiograph::shard_ghost";
+
+/// Headroom over a perfectly even split that greedy partitioning allows.
+const GREEDY_SLACK: f64 = 1.1;
+
+/// How entities are assigned to shards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartitionStrategy {
+    /// Hash of the entity id.
+    #[default]
+    Hash,
+    /// Linear deterministic greedy: keep neighbours together.
+    Greedy,
+}
+
+/// Entity-to-shard assignment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Partitioning {
+    shards: usize,
+    assignment: Vec<u32>,
+}
+
+impl Partitioning {
+    /// Assign every entity of `db` to one of `shards` (at least 1) shards.
+    pub fn compute(db: &PathDB, strategy: PartitionStrategy, shards: usize) -> Self {
+        let shards = shards.max(1);
+        let n = db.entities.len();
+        let assignment = match strategy {
+            PartitionStrategy::Hash => (0..n as u64)
+                .map(|id| (mix64(id) % shards as u64) as u32)
+                .collect(),
+            PartitionStrategy::Greedy => greedy_assignment(db, shards),
+        };
+        Self { shards, assignment }
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shards
+    }
+
+    /// Owning shard of `entity`.
+    pub fn shard_of(&self, entity: u32) -> Option<usize> {
+        self.assignment.get(entity as usize).map(|&s| s as usize)
+    }
+
+    /// Number of entities owned by each shard.
+    pub fn shard_sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.shards];
+        for &shard in &self.assignment {
+            sizes[shard as usize] += 1;
+        }
+        sizes
+    }
+
+    /// Relations of `db` whose endpoints live on different shards.
+    pub fn edge_cut(&self, db: &PathDB) -> usize {
+        db.relations
+            .relations
+            .iter()
+            .filter(|rel| self.shard_of(rel.source) != self.shard_of(rel.target))
+            .count()
+    }
+}
+
+fn greedy_assignment(db: &PathDB, shards: usize) -> Vec<u32> {
+    let n = db.entities.len();
+    let mut neighbours: Vec<Vec<u32>> = vec![Vec::new(); n];
+    for rel in &db.relations.relations {
+        if rel.source != rel.target {
+            neighbours[rel.source as usize].push(rel.target);
+            neighbours[rel.target as usize].push(rel.source);
+        }
+    }
+    let capacity = (n.div_ceil(shards) as f64 * GREEDY_SLACK).floor().max(1.0);
+    let mut assignment = vec![u32::MAX; n];
+    let mut sizes = vec![0usize; shards];
+    let mut placed = vec![0usize; shards];
+    for entity in 0..n {
+        placed.iter_mut().for_each(|count| *count = 0);
+        for &other in &neighbours[entity] {
+            if let Some(&shard) = assignment.get(other as usize).filter(|&&s| s != u32::MAX) {
+                placed[shard as usize] += 1;
+            }
+        }
+        let score = |shard: usize| placed[shard] as f64 * (1.0 - sizes[shard] as f64 / capacity);
+        let best = (0..shards)
+            .filter(|&shard| (sizes[shard] as f64) < capacity)
+            .max_by(|&a, &b| {
+                score(a)
+                    .total_cmp(&score(b))
+                    .then(sizes[b].cmp(&sizes[a]))
+                    .then(b.cmp(&a))
+            })
+            .unwrap_or_else(|| (0..shards).min_by_key(|&s| sizes[s]).unwrap_or(0));
+        assignment[entity] = best as u32;
+        sizes[best] += 1;
+    }
+    assignment
+}
+
+/// One shard: a `PathDB` over the entities it owns plus ghost targets.
+pub struct Shard {
+    db: PathDB,
+    local_to_global: Vec<u32>,
+    global_to_local: HashMap<u32, u32>,
+    /// Local ids of owned (non-ghost) entities.
+    owned: RoaringBitmap,
+}
+
+impl Shard {
+    fn new() -> Self {
+        Self {
+            db: PathDB::new(),
+            local_to_global: Vec::new(),
+            global_to_local: HashMap::new(),
+            owned: RoaringBitmap::new(),
+        }
+    }
+
+    /// The shard's database (local ids).
+    pub fn db(&self) -> &PathDB {
+        &self.db
+    }
+
+    /// Number of entities this shard owns.
+    pub fn owned_len(&self) -> u64 {
+        self.owned.len()
+    }
+
+    /// Number of ghost entities standing in for remote targets.
+    pub fn ghost_len(&self) -> u64 {
+        self.local_to_global.len() as u64 - self.owned.len()
+    }
+
+    pub fn to_global(&self, local: u32) -> Option<u32> {
+        self.local_to_global.get(local as usize).copied()
+    }
+
+    pub fn to_local(&self, global: u32) -> Option<u32> {
+        self.global_to_local.get(&global).copied()
+    }
+
+    fn add(&mut self, global: u32, local: u32) {
+        debug_assert_eq!(local as usize, self.local_to_global.len());
+        self.local_to_global.push(global);
+        self.global_to_local.insert(global, local);
+    }
+
+    /// Local id of `global`, adding a ghost entity if the shard lacks it.
+    fn local_or_ghost(&mut self, global: u32) -> u32 {
+        if let Some(local) = self.to_local(global) {
+            return local;
+        }
+        let local = self.db.add_entity(GHOST_TYPE, vec![]);
+        self.add(global, local);
+        local
+    }
+
+    fn globals(&self, local: &RoaringBitmap) -> RoaringBitmap {
+        local.iter().filter_map(|id| self.to_global(id)).collect()
+    }
+
+    fn locals(&self, global: &RoaringBitmap) -> RoaringBitmap {
+        global.iter().filter_map(|id| self.to_local(id)).collect()
+    }
+
+    /// Targets of one `rel_type` hop from the owned entities in `frontier`.
+    fn expand(&self, frontier: &RoaringBitmap, rel_type: &str, min: Option<f32>) -> RoaringBitmap {
+        let Some(rel_id) = self.db.interner.id_of(rel_type) else {
+            return RoaringBitmap::new();
+        };
+        let local = self.locals(frontier);
+        let reached = match min {
+            None => self.db.relations.expand_frontier(&local, rel_id),
+            Some(min) => local
+                .iter()
+                .map(|e| {
+                    self.db
+                        .relations
+                        .targets_with_min_confidence(e, rel_id, min)
+                })
+                .fold(RoaringBitmap::new(), |acc, t| acc | t),
+        };
+        self.globals(&reached)
+    }
+
+    /// Targets of one hop along any relation type.
+    fn expand_any(&self, frontier: &RoaringBitmap, min: Option<f32>) -> RoaringBitmap {
+        let mut reached = RoaringBitmap::new();
+        for entity in self.locals(frontier).iter() {
+            for rel in self.db.relations.outgoing_any(entity) {
+                if edge_visible(rel, min, None) {
+                    reached.insert(rel.target);
+                }
+            }
+        }
+        self.globals(&reached)
+    }
+
+    /// Best combined confidence per target of one scored hop.
+    fn expand_scored(
+        &self,
+        frontier: &BTreeMap<u32, f32>,
+        rel_type: &str,
+        min: Option<f32>,
+        filter: PathConfidenceFilter,
+    ) -> BTreeMap<u32, f32> {
+        let mut next = BTreeMap::new();
+        let Some(rel_id) = self.db.interner.id_of(rel_type) else {
+            return next;
+        };
+        for (&global, &confidence) in frontier {
+            let Some(local) = self.to_local(global) else {
+                continue;
+            };
+            for edge in self.db.relations.outgoing(local, rel_id) {
+                if !edge_visible(edge, min, None) {
+                    continue;
+                }
+                let Some(target) = self.to_global(edge.target) else {
+                    continue;
+                };
+                let combined = filter.combiner.combine(confidence, edge.confidence);
+                keep_best(&mut next, target, combined);
+            }
+        }
+        next
+    }
+}
+
+fn keep_best(scores: &mut BTreeMap<u32, f32>, entity: u32, confidence: f32) {
+    scores
+        .entry(entity)
+        .and_modify(|best| *best = best.max(confidence))
+        .or_insert(confidence);
+}
+
+/// A database split into shards, with a query coordinator.
+pub struct ShardedDb {
+    partitioning: Partitioning,
+    shards: Vec<Shard>,
+}
+
+impl ShardedDb {
+    /// Partition `db` into `shards` shards.
+    pub fn partition(db: &PathDB, strategy: PartitionStrategy, shards: usize) -> Self {
+        Self::with_partitioning(db, Partitioning::compute(db, strategy, shards))
+    }
+
+    /// Build shards for a precomputed assignment.
+    pub fn with_partitioning(db: &PathDB, partitioning: Partitioning) -> Self {
+        let mut shards: Vec<Shard> = (0..partitioning.shard_count())
+            .map(|_| Shard::new())
+            .collect();
+        for entity in 0..db.entities.len() as u32 {
+            let (Some(owner), Some(view)) = (partitioning.shard_of(entity), db.get_entity(entity))
+            else {
+                continue;
+            };
+            let shard = &mut shards[owner];
+            let attrs: Vec<(&str, &str)> = view
+                .attrs
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect();
+            let local = shard.db.add_entity(&view.entity_type, attrs);
+            shard.add(entity, local);
+            shard.owned.insert(local);
+        }
+        for (&type_id, ids) in &db.entities.type_index {
+            let Some(type_name) = db.interner.lookup(type_id) else {
+                continue;
+            };
+            for entity in ids.iter() {
+                if db.entities.get_type(entity) == Some(type_id) {
+                    continue;
+                }
+                if let Some(owner) = partitioning.shard_of(entity) {
+                    let shard = &mut shards[owner];
+                    if let Some(local) = shard.to_local(entity) {
+                        let _ = shard.db.mark_virtual_type(local, &type_name);
+                    }
+                }
+            }
+        }
+        for rel in &db.relations.relations {
+            let Some(owner) = partitioning.shard_of(rel.source) else {
+                continue;
+            };
+            let (Some(rel_type), Some(shard)) =
+                (db.interner.lookup(rel.rel_type), shards.get_mut(owner))
+            else {
+                continue;
+            };
+            let Some(source) = shard.to_local(rel.source) else {
+                continue;
+            };
+            let target = shard.local_or_ghost(rel.target);
+            let attrs: Vec<(String, String)> = rel
+                .attrs
+                .iter()
+                .filter_map(|&(k, v)| Some((db.interner.lookup(k)?, db.interner.lookup(v)?)))
+                .collect();
+            let attrs = attrs
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect();
+            shard
+                .db
+                .add_relation(&rel_type, source, target, rel.confidence, attrs);
+        }
+        for shard in &mut shards {
+            shard.db.build_indexes();
+        }
+        Self {
+            partitioning,
+            shards,
+        }
+    }
+
+    pub fn partitioning(&self) -> &Partitioning {
+        &self.partitioning
+    }
+
+    pub fn shards(&self) -> &[Shard] {
+        &self.shards
+    }
+
+    /// Execute `query` across all shards; results are global entity ids.
+    pub fn execute(&self, query: &PathQuery) -> Result<RoaringBitmap> {
+        self.execute_scoped(query, ExecScope::default())
+    }
+
+    fn execute_scoped(&self, query: &PathQuery, scope: ExecScope<'_>) -> Result<RoaringBitmap> {
+        let min = scope.min_confidence.map(|c| c.clamp(0.0, 1.0));
+        Ok(match query {
+            PathQuery::SelectByType(type_name) => self
+                .shards
+                .par_iter()
+                .map(|shard| match shard.db.find_by_type(type_name) {
+                    Some(ids) => shard.globals(&(ids & &shard.owned)),
+                    None => RoaringBitmap::new(),
+                })
+                .reduce(RoaringBitmap::new, |a, b| a | b),
+            PathQuery::SelectRelated(source, rel_type) => {
+                // One edge combines to its own confidence under every policy.
+                let min = match (min, scope.path_filter) {
+                    (edge, None) => edge,
+                    (None, Some(f)) => Some(f.min),
+                    (Some(edge), Some(f)) => Some(edge.max(f.min)),
+                };
+                let frontier = RoaringBitmap::from_iter([*source]);
+                self.hop(&frontier, |shard, part| shard.expand(part, rel_type, min))
+            }
+            PathQuery::FollowPath { start, path } => match scope.path_filter {
+                None => {
+                    let mut frontier = RoaringBitmap::from_iter([*start]);
+                    for rel_type in path {
+                        if frontier.is_empty() {
+                            break;
+                        }
+                        frontier =
+                            self.hop(&frontier, |shard, part| shard.expand(part, rel_type, min));
+                    }
+                    frontier
+                }
+                Some(filter) => self.follow_scored(*start, path, min, filter),
+            },
+            PathQuery::FindPaths {
+                from,
+                to,
+                max_depth,
+            } => {
+                if scope.path_filter.is_some() {
+                    return Err(PathDbError::Unsupported(
+                        "FindPaths under WithPathConfidence in sharded execution".to_string(),
+                    ));
+                }
+                let mut visited = RoaringBitmap::from_iter([*from]);
+                let mut frontier = visited.clone();
+                let mut result = RoaringBitmap::new();
+                for _ in 0..*max_depth {
+                    let next = self.hop(&frontier, |shard, part| shard.expand_any(part, min));
+                    if next.contains(*to) && *to != *from {
+                        result.insert(*to);
+                        break;
+                    }
+                    frontier = next - &visited;
+                    if frontier.is_empty() {
+                        break;
+                    }
+                    visited |= &frontier;
+                }
+                result
+            }
+            PathQuery::Join(left, right) => {
+                self.execute_scoped(left, scope)? & self.execute_scoped(right, scope)?
+            }
+            PathQuery::Union(left, right) => {
+                self.execute_scoped(left, scope)? | self.execute_scoped(right, scope)?
+            }
+            PathQuery::WithConfidence {
+                base,
+                min_confidence,
+            } => {
+                let next = match scope.min_confidence {
+                    None => *min_confidence,
+                    Some(prev) => prev.max(*min_confidence),
+                };
+                let scope = ExecScope {
+                    min_confidence: Some(next),
+                    ..scope
+                };
+                self.execute_scoped(base, scope)?
+            }
+            PathQuery::WithPathConfidence {
+                base,
+                min_path_confidence,
+                combiner,
+            } => {
+                let scope = ExecScope {
+                    path_filter: Some(PathConfidenceFilter {
+                        combiner: *combiner,
+                        min: min_path_confidence.clamp(0.0, 1.0),
+                    }),
+                    ..scope
+                };
+                self.execute_scoped(base, scope)?
+            }
+        })
+    }
+
+    /// Split `frontier` by owner, run `step` on each shard's part in
+    /// parallel and union the results.
+    fn hop(
+        &self,
+        frontier: &RoaringBitmap,
+        step: impl Fn(&Shard, &RoaringBitmap) -> RoaringBitmap + Sync,
+    ) -> RoaringBitmap {
+        let parts = self.split(frontier);
+        self.shards
+            .par_iter()
+            .zip(parts.par_iter())
+            .filter(|(_, part)| !part.is_empty())
+            .map(|(shard, part)| step(shard, part))
+            .reduce(RoaringBitmap::new, |a, b| a | b)
+    }
+
+    fn split(&self, frontier: &RoaringBitmap) -> Vec<RoaringBitmap> {
+        let mut parts = vec![RoaringBitmap::new(); self.shards.len()];
+        for entity in frontier.iter() {
+            if let Some(owner) = self.partitioning.shard_of(entity) {
+                parts[owner].insert(entity);
+            }
+        }
+        parts
+    }
+
+    /// `FollowPath` keeping the best combined confidence per entity per hop
+    /// (exact for every combiner, which are all monotone).
+    fn follow_scored(
+        &self,
+        start: u32,
+        path: &[String],
+        min: Option<f32>,
+        filter: PathConfidenceFilter,
+    ) -> RoaringBitmap {
+        let mut current = BTreeMap::from([(start, filter.combiner.identity())]);
+        for rel_type in path {
+            let mut parts = vec![BTreeMap::new(); self.shards.len()];
+            for (&entity, &confidence) in &current {
+                if let Some(owner) = self.partitioning.shard_of(entity) {
+                    parts[owner].insert(entity, confidence);
+                }
+            }
+            current = self
+                .shards
+                .par_iter()
+                .zip(parts.par_iter())
+                .map(|(shard, part)| shard.expand_scored(part, rel_type, min, filter))
+                .reduce(BTreeMap::new, |mut a, b| {
+                    for (entity, confidence) in b {
+                        keep_best(&mut a, entity, confidence);
+                    }
+                    a
+                });
+            if current.is_empty() {
+                break;
+            }
+        }
+        current
+            .into_iter()
+            .filter(|&(_, confidence)| confidence >= filter.min)
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+}
@@ -0,0 +1,357 @@
+//! Standing query subscriptions.
+//!
+//! A [`Subscriptions`] registry holds [`PathQuery`]s that clients want kept
+//! up to date ("notify me when a new `ProtoRpc` lacking an auth scope
+//! appears"). Each query is compiled into a small dataflow tree whose nodes
+//! cache their last output:
+//!
+//! - `SelectByType` leaves are maintained from the change log alone (an added
+//!   entity of the type joins, a removed entity leaves);
+//! - traversal leaves (`SelectRelated`, `FollowPath`, `FindPaths`) re-run only
+//!   when a change touches one of the relation types they read (registered
+//!   inverses included) or removes an entity;
+//! - `Join` / `Union` nodes recombine their children's cached bitmaps, and
+//!   only when a child changed.
+//!
+//! `WithConfidence` / `WithPathConfidence` are pushed down onto the traversal
+//! leaves they scope, so the maintained result always equals
+//! [`PathDB::execute`] on the updated snapshot.
+//!
+//! [`Subscriptions::apply`] takes the snapshot *after* the changes plus the
+//! changes themselves (e.g. an [`Overlay`](crate::Overlay) and its
+//! [`changes`](crate::Overlay::changes)) and returns an add/remove delta for
+//! every subscription whose result moved.
+
+use std::collections::{BTreeMap, HashSet};
+
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use crate::{PathDB, PathQuery, StagedChange};
+
+/// Identifier handed out by [`Subscriptions::subscribe`].
+pub type SubscriptionId = u64;
+
+/// How one subscription's result changed under a batch of changes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SubscriptionDelta {
+    pub subscription: SubscriptionId,
+    pub added: RoaringBitmap,
+    pub removed: RoaringBitmap,
+}
+
+impl SubscriptionDelta {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Counters for the last [`Subscriptions::apply`] call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubscriptionStats {
+    /// Type leaves updated from the change log without querying.
+    pub type_updates: usize,
+    /// Traversal leaves re-executed because a change touched them.
+    pub leaf_reruns: usize,
+    /// Traversal leaves left untouched.
+    pub leaf_skips: usize,
+}
+
+/// Registered standing queries and their maintained results.
+#[derive(Debug, Default)]
+pub struct Subscriptions {
+    next_id: SubscriptionId,
+    subscriptions: BTreeMap<SubscriptionId, Subscription>,
+    stats: SubscriptionStats,
+}
+
+#[derive(Debug)]
+struct Subscription {
+    query: PathQuery,
+    root: Node,
+}
+
+/// One dataflow operator plus its cached output.
+#[derive(Debug)]
+struct Node {
+    op: Op,
+    out: RoaringBitmap,
+}
+
+#[derive(Debug)]
+enum Op {
+    Type(String),
+    /// A traversal, already wrapped in the confidence filters in scope.
+    Traversal {
+        query: PathQuery,
+        /// Relation types read by the traversal; `None` reads every type.
+        reads: Option<HashSet<String>>,
+    },
+    Join(Box<Node>, Box<Node>),
+    Union(Box<Node>, Box<Node>),
+}
+
+impl Subscriptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.subscriptions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.subscriptions.is_empty()
+    }
+
+    /// Register `query` against `db`; returns its id and initial result.
+    pub fn subscribe(&mut self, db: &PathDB, query: PathQuery) -> (SubscriptionId, RoaringBitmap) {
+        let root = Node::compile(db, &query, &mut Vec::new());
+        let initial = root.out.clone();
+        let id = self.next_id;
+        self.next_id += 1;
+        self.subscriptions.insert(id, Subscription { query, root });
+        (id, initial)
+    }
+
+    /// Drop a subscription; false if `id` is unknown.
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        self.subscriptions.remove(&id).is_some()
+    }
+
+    pub fn query(&self, id: SubscriptionId) -> Option<&PathQuery> {
+        self.subscriptions.get(&id).map(|sub| &sub.query)
+    }
+
+    /// The maintained result of subscription `id`.
+    pub fn results(&self, id: SubscriptionId) -> Option<&RoaringBitmap> {
+        self.subscriptions.get(&id).map(|sub| &sub.root.out)
+    }
+
+    /// Work done by the last [`Self::apply`].
+    pub fn stats(&self) -> SubscriptionStats {
+        self.stats
+    }
+
+    /// Propagate `changes` (already applied to `db`) through every
+    /// subscription. Returns the non-empty deltas in subscription id order.
+    pub fn apply(&mut self, db: &PathDB, changes: &[StagedChange]) -> Vec<SubscriptionDelta> {
+        let _span = tracing::debug_span!(
+            "pathdb.subscriptions.apply",
+            subscriptions = self.subscriptions.len(),
+            changes = changes.len()
+        )
+        .entered();
+        let touch = Touched::from_changes(db, changes);
+        let mut stats = SubscriptionStats::default();
+        let mut deltas = Vec::new();
+        for (&id, sub) in &mut self.subscriptions {
+            let before = sub.root.out.clone();
+            if !sub.root.update(db, changes, &touch, &mut stats) {
+                continue;
+            }
+            let delta = SubscriptionDelta {
+                subscription: id,
+                added: &sub.root.out - &before,
+                removed: &before - &sub.root.out,
+            };
+            if !delta.is_empty() {
+                deltas.push(delta);
+            }
+        }
+        self.stats = stats;
+        deltas
+    }
+}
+
+/// Confidence filters in scope while compiling, outermost first.
+#[derive(Debug, Clone)]
+enum Filter {
+    Edge(f32),
+    Path(f32, crate::ConfidenceCombiner),
+}
+
+impl Node {
+    fn compile(db: &PathDB, query: &PathQuery, filters: &mut Vec<Filter>) -> Node {
+        let op = match query {
+            PathQuery::SelectByType(type_name) => Op::Type(type_name.clone()),
+            PathQuery::SelectRelated(_, rel_type) => traversal(db, query, filters, [rel_type]),
+            PathQuery::FollowPath { path, .. } => traversal(db, query, filters, path),
+            PathQuery::FindPaths { .. } => Op::Traversal {
+                query: wrap(query, filters),
+                reads: None,
+            },
+            PathQuery::Join(left, right) => Op::Join(
+                Box::new(Node::compile(db, left, filters)),
+                Box::new(Node::compile(db, right, filters)),
+            ),
+            PathQuery::Union(left, right) => Op::Union(
+                Box::new(Node::compile(db, left, filters)),
+                Box::new(Node::compile(db, right, filters)),
+            ),
+            PathQuery::WithConfidence {
+                base,
+                min_confidence,
+            } => {
+                filters.push(Filter::Edge(*min_confidence));
+                let node = Node::compile(db, base, filters);
+                filters.pop();
+                return node;
+            }
+            PathQuery::WithPathConfidence {
+                base,
+                min_path_confidence,
+                combiner,
+            } => {
+                filters.push(Filter::Path(*min_path_confidence, *combiner));
+                let node = Node::compile(db, base, filters);
+                filters.pop();
+                return node;
+            }
+        };
+        let mut node = Node {
+            op,
+            out: RoaringBitmap::new(),
+        };
+        node.out = node.evaluate(db);
+        node
+    }
+
+    /// Output from scratch (leaves) or from the children's cached outputs.
+    fn evaluate(&self, db: &PathDB) -> RoaringBitmap {
+        match &self.op {
+            Op::Type(type_name) => db.find_by_type(type_name).cloned().unwrap_or_default(),
+            Op::Traversal { query, .. } => db.execute(query),
+            Op::Join(left, right) => db.join(&left.out, &right.out),
+            Op::Union(left, right) => db.union(&left.out, &right.out),
+        }
+    }
+
+    /// Bring the cached output up to date; true if the node was recomputed.
+    fn update(
+        &mut self,
+        db: &PathDB,
+        changes: &[StagedChange],
+        touch: &Touched,
+        stats: &mut SubscriptionStats,
+    ) -> bool {
+        match &mut self.op {
+            Op::Type(type_name) => {
+                let mut changed = false;
+                for change in changes {
+                    match change {
+                        StagedChange::AddEntity { entity, .. } => {
+                            let is_member = db
+                                .find_by_type(type_name)
+                                .is_some_and(|ids| ids.contains(*entity));
+                            if is_member {
+                                changed |= self.out.insert(*entity);
+                            }
+                        }
+                        StagedChange::RemoveEntity { entity } => {
+                            changed |= self.out.remove(*entity);
+                        }
+                        StagedChange::AddRelation { .. } | StagedChange::RemoveRelation { .. } => {}
+                    }
+                }
+                if changed {
+                    stats.type_updates += 1;
+                }
+                changed
+            }
+            Op::Traversal { reads, .. } => {
+                if !touch.affects(reads.as_ref()) {
+                    stats.leaf_skips += 1;
+                    return false;
+                }
+                stats.leaf_reruns += 1;
+                self.out = self.evaluate(db);
+                true
+            }
+            Op::Join(left, right) | Op::Union(left, right) => {
+                let left_changed = left.update(db, changes, touch, stats);
+                let right_changed = right.update(db, changes, touch, stats);
+                if !(left_changed || right_changed) {
+                    return false;
+                }
+                self.out = self.evaluate(db);
+                true
+            }
+        }
+    }
+}
+
+fn traversal<'a>(
+    db: &PathDB,
+    query: &PathQuery,
+    filters: &[Filter],
+    rel_types: impl IntoIterator<Item = &'a String>,
+) -> Op {
+    let mut reads = HashSet::new();
+    for rel_type in rel_types {
+        reads.extend(db.inverse_of(rel_type));
+        reads.insert(rel_type.clone());
+    }
+    Op::Traversal {
+        query: wrap(query, filters),
+        reads: Some(reads),
+    }
+}
+
+/// Re-apply the enclosing filters around a leaf (innermost closest, so a
+/// nested `WithPathConfidence` still replaces the outer one).
+fn wrap(query: &PathQuery, filters: &[Filter]) -> PathQuery {
+    filters
+        .iter()
+        .rev()
+        .fold(query.clone(), |base, filter| match *filter {
+            Filter::Edge(min_confidence) => PathQuery::WithConfidence {
+                base: Box::new(base),
+                min_confidence,
+            },
+            Filter::Path(min_path_confidence, combiner) => PathQuery::WithPathConfidence {
+                base: Box::new(base),
+                min_path_confidence,
+                combiner,
+            },
+        })
+}
+
+/// What a batch of changes can have affected.
+struct Touched {
+    /// Relation types added or removed, with their registered inverses.
+    rel_types: HashSet<String>,
+    /// An entity was removed (its incident edges of any type went with it).
+    removed_entity: bool,
+}
+
+impl Touched {
+    fn from_changes(db: &PathDB, changes: &[StagedChange]) -> Self {
+        let mut touched = Touched {
+            rel_types: HashSet::new(),
+            removed_entity: false,
+        };
+        for change in changes {
+            match change {
+                StagedChange::AddRelation { rel_type, .. }
+                | StagedChange::RemoveRelation { rel_type, .. } => {
+                    touched.rel_types.extend(db.inverse_of(rel_type));
+                    touched.rel_types.insert(rel_type.clone());
+                }
+                StagedChange::RemoveEntity { .. } => touched.removed_entity = true,
+                StagedChange::AddEntity { .. } => {}
+            }
+        }
+        touched
+    }
+
+    fn affects(&self, reads: Option<&HashSet<String>>) -> bool {
+        if self.removed_entity {
+            return true;
+        }
+        match reads {
+            None => !self.rel_types.is_empty(),
+            Some(reads) => !reads.is_disjoint(&self.rel_types),
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Supernode (hub) handling.
+//!
+//! A few entities collect an outsized share of the edges — `bool::true` from
+//! the proto ingester, popular tags, shared units. Their per-`(entity,
+//! rel_type)` adjacency lists hold millions of relation ids, so even a single
+//! `targets()` call walks every relation record.
+//!
+//! Two mitigations:
+//!
+//! - **Dedicated bitmaps.** Once an adjacency list reaches the supernode
+//!   threshold (in either direction), [`RelationStore`] also keeps the
+//!   neighbour set as a `RoaringBitmap`. `targets`/`sources`/`has_edge`
+//!   answer from it directly. These bitmaps are derived: they are not
+//!   serialized and are rebuilt on load.
+//! - **Hub policy.** Traversals (`follow_path`, `find_paths`, `PathQuery`
+//!   execution via [`QueryContext::hubs`](crate::context::QueryContext))
+//!   can skip hubs or expand only a sample of their neighbours, see
+//!   [`HubPolicy`]. The traversal's start entity is always expanded in full.
+//!   Confidence-filtered and scored traversals read relation records and do
+//!   not use the bitmaps; explanations ignore the hub policy.
+
+use std::collections::HashMap;
+
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use crate::budget::BudgetTracker;
+use crate::{ExecScope, PathDB, Relation, RelationStore, StrId};
+
+/// Adjacency-list length (edges of one type at one entity, in one direction)
+/// at which an entity becomes a supernode.
+pub const DEFAULT_SUPERNODE_THRESHOLD: usize = 4096;
+
+/// How traversals treat supernodes reached along the way.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HubPolicy {
+    /// Expand hubs like any other entity (exact results).
+    #[default]
+    Expand,
+    /// Reach hubs, but do not expand them further.
+    Skip,
+    /// Expand only the `n` smallest neighbour ids of a hub per relation type.
+    Sample(usize),
+}
+
+impl HubPolicy {
+    /// How many neighbours of `entity` to expand; `None` means all.
+    fn neighbour_limit(self, db: &PathDB, entity: u32) -> Option<usize> {
+        match self {
+            HubPolicy::Expand => None,
+            _ if !db.is_supernode(entity) => None,
+            HubPolicy::Skip => Some(0),
+            HubPolicy::Sample(n) => Some(n),
+        }
+    }
+}
+
+/// Dedicated neighbour bitmaps for adjacency lists over the threshold.
+#[derive(Debug, Clone)]
+pub(crate) struct SupernodeIndex {
+    threshold: usize,
+    /// `(source, rel_type)` -> targets.
+    forward: HashMap<(u32, StrId), RoaringBitmap>,
+    /// `(target, rel_type)` -> sources.
+    backward: HashMap<(u32, StrId), RoaringBitmap>,
+    hubs: RoaringBitmap,
+}
+
+impl Default for SupernodeIndex {
+    fn default() -> Self {
+        Self::with_threshold(DEFAULT_SUPERNODE_THRESHOLD)
+    }
+}
+
+impl SupernodeIndex {
+    fn with_threshold(threshold: usize) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            forward: HashMap::new(),
+            backward: HashMap::new(),
+            hubs: RoaringBitmap::new(),
+        }
+    }
+
+    pub(crate) fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub(crate) fn hubs(&self) -> &RoaringBitmap {
+        &self.hubs
+    }
+
+    pub(crate) fn targets(&self, source: u32, rel_type: StrId) -> Option<&RoaringBitmap> {
+        self.forward.get(&(source, rel_type))
+    }
+
+    pub(crate) fn sources(&self, target: u32, rel_type: StrId) -> Option<&RoaringBitmap> {
+        self.backward.get(&(target, rel_type))
+    }
+}
+
+impl RelationStore {
+    /// Keep the supernode bitmaps in step with a just-added relation.
+    pub(crate) fn note_supernode_edge(&mut self, rel_id: u32) {
+        let Some(rel) = self.relations.get(rel_id as usize) else {
+            return;
+        };
+        let index = &mut self.supernodes;
+        for (key, lists, bitmaps, forward) in [
+            (
+                (rel.source, rel.rel_type),
+                &self.forward_index,
+                &mut index.forward,
+                true,
+            ),
+            (
+                (rel.target, rel.rel_type),
+                &self.backward_index,
+                &mut index.backward,
+                false,
+            ),
+        ] {
+            if let Some(bitmap) = bitmaps.get_mut(&key) {
+                bitmap.insert(if forward { rel.target } else { rel.source });
+            } else if let Some(ids) = lists.get(&key).filter(|ids| ids.len() >= index.threshold) {
+                bitmaps.insert(key, neighbours(&self.relations, ids, forward));
+                index.hubs.insert(key.0);
+            }
+        }
+    }
+
+    /// Recompute every supernode bitmap (after load or a threshold change).
+    pub(crate) fn rebuild_supernodes(&mut self) {
+        let mut index = SupernodeIndex::with_threshold(self.supernodes.threshold);
+        for (lists, bitmaps, forward) in [
+            (&self.forward_index, &mut index.forward, true),
+            (&self.backward_index, &mut index.backward, false),
+        ] {
+            for (key, ids) in lists.iter() {
+                if ids.len() >= index.threshold {
+                    bitmaps.insert(key, neighbours(&self.relations, ids, forward));
+                    index.hubs.insert(key.0);
+                }
+            }
+        }
+        self.supernodes = index;
+    }
+
+    pub fn supernode_threshold(&self) -> usize {
+        self.supernodes.threshold()
+    }
+
+    /// Change the supernode threshold and rebuild the bitmaps.
+    pub fn set_supernode_threshold(&mut self, threshold: usize) {
+        if threshold.max(1) != self.supernodes.threshold {
+            self.supernodes = SupernodeIndex::with_threshold(threshold);
+            self.rebuild_supernodes();
+        }
+    }
+}
+
+/// Far endpoints of `ids` (targets when `forward`, else sources).
+fn neighbours(relations: &[Relation], ids: &[u32], forward: bool) -> RoaringBitmap {
+    ids.iter()
+        .filter_map(|&id| relations.get(id as usize))
+        .map(|rel| if forward { rel.target } else { rel.source })
+        .collect()
+}
+
+impl PathDB {
+    /// Whether some adjacency list of `entity` reached the supernode threshold.
+    pub fn is_supernode(&self, entity: u32) -> bool {
+        self.relations.supernodes.hubs().contains(entity)
+    }
+
+    /// All current supernodes.
+    pub fn supernodes(&self) -> &RoaringBitmap {
+        self.relations.supernodes.hubs()
+    }
+
+    pub fn supernode_threshold(&self) -> usize {
+        self.relations.supernode_threshold()
+    }
+
+    /// Change the adjacency-list length at which entities become supernodes.
+    pub fn set_supernode_threshold(&mut self, threshold: usize) {
+        self.relations.set_supernode_threshold(threshold);
+    }
+
+    /// [`PathDB::follow_path`] under a [`HubPolicy`].
+    pub fn follow_path_with_hubs(
+        &self,
+        start: u32,
+        path: &[&str],
+        hubs: HubPolicy,
+    ) -> RoaringBitmap {
+        let scope = ExecScope {
+            hubs,
+            ..ExecScope::default()
+        };
+        self.follow_path_budgeted(start, path, scope, &mut BudgetTracker::unlimited())
+    }
+
+    /// [`PathDB::find_paths`] under a [`HubPolicy`]: hubs can still be the
+    /// destination, but paths through them are cut or sampled.
+    pub fn find_paths_with_hubs(
+        &self,
+        from: u32,
+        to: u32,
+        max_depth: usize,
+        hubs: HubPolicy,
+    ) -> Vec<Vec<StrId>> {
+        let scope = ExecScope {
+            hubs,
+            ..ExecScope::default()
+        };
+        self.find_paths_budgeted(from, to, max_depth, scope, &mut BudgetTracker::unlimited())
+    }
+
+    /// Neighbour cap for expanding `entity` during a traversal from `start`.
+    pub(crate) fn hub_limit(&self, hubs: HubPolicy, start: u32, entity: u32) -> Option<usize> {
+        if entity == start {
+            None
+        } else {
+            hubs.neighbour_limit(self, entity)
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Synthetic graph generation for benchmarks, fuzzing and examples.
+//!
+//! [`synthetic_graph`] builds a seeded, reproducible PathDB of any size so
+//! that performance work and demos never need a real (proprietary) snapshot:
+//!
+//! - **Scale-free degrees**: entities arrive one at a time and each links to
+//!   [`SyntheticGraphConfig::edges_per_entity`] earlier entities chosen by
+//!   preferential attachment (Barabási–Albert, with every entity starting at
+//!   weight 1), so a few hubs collect most edges and degrees follow a power
+//!   law. Edges point from the newer entity to the older one.
+//! - **Vocabularies**: entity and relation types are drawn from weighted
+//!   [`Vocabulary`]s. Every entity gets a unique `name` attribute
+//!   (`<type>_<id>`, lowercased).
+//! - **Confidences**: edge confidences follow a [`ConfidenceDistribution`].
+//!
+//! The same config (including the seed) always produces the same graph.
+
+use crate::cardinality::mix64;
+use crate::PathDB;
+
+/// A weighted list of type names.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vocabulary {
+    entries: Vec<(String, u32)>,
+    total: u64,
+}
+
+impl Vocabulary {
+    /// Names with relative weights; zero-weight names are never drawn.
+    ///
+    /// # Panics
+    ///
+    /// If no name has a positive weight.
+    pub fn weighted<S: Into<String>>(entries: impl IntoIterator<Item = (S, u32)>) -> Self {
+        let entries: Vec<(String, u32)> = entries
+            .into_iter()
+            .map(|(name, weight)| (name.into(), weight))
+            .collect();
+        let total = entries.iter().map(|(_, w)| u64::from(*w)).sum();
+        assert!(total > 0, "vocabulary needs a name with positive weight");
+        Self { entries, total }
+    }
+
+    /// Names drawn with equal probability.
+    pub fn uniform<S: Into<String>>(names: impl IntoIterator<Item = S>) -> Self {
+        Self::weighted(names.into_iter().map(|name| (name, 1)))
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(name, _)| name.as_str())
+    }
+
+    fn draw(&self, rng: &mut SplitMix) -> &str {
+        let mut pick = rng.below(self.total);
+        for (name, weight) in &self.entries {
+            let weight = u64::from(*weight);
+            if pick < weight {
+                return name;
+            }
+            pick -= weight;
+        }
+        unreachable!("pick is below the total weight")
+    }
+}
+
+/// How edge confidences are distributed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfidenceDistribution {
+    Fixed(f32),
+    /// Uniform in `[min, max]`.
+    Uniform {
+        min: f32,
+        max: f32,
+    },
+    /// In `[min, max]`, skewed toward `max` (`min + (max - min) * u^(1/exponent)`
+    /// for uniform `u`): most edges are confident, a tail is not.
+    /// `exponent = 1` is uniform.
+    SkewedHigh {
+        min: f32,
+        max: f32,
+        exponent: f32,
+    },
+}
+
+impl ConfidenceDistribution {
+    fn draw(&self, rng: &mut SplitMix) -> f32 {
+        match *self {
+            Self::Fixed(c) => c,
+            Self::Uniform { min, max } => min + (max - min) * rng.unit(),
+            Self::SkewedHigh { min, max, exponent } => {
+                min + (max - min) * rng.unit().powf(1.0 / exponent.max(f32::EPSILON))
+            }
+        }
+    }
+}
+
+/// Parameters of [`synthetic_graph`].
+#[derive(Debug, Clone)]
+pub struct SyntheticGraphConfig {
+    pub entities: u32,
+    /// Edges each new entity adds (fewer for the first entities, and when
+    /// two draws pick the same target).
+    pub edges_per_entity: u32,
+    pub entity_types: Vocabulary,
+    pub relation_types: Vocabulary,
+    pub confidence: ConfidenceDistribution,
+    pub seed: u64,
+    /// Build indexes before returning.
+    pub build_indexes: bool,
+}
+
+impl Default for SyntheticGraphConfig {
+    fn default() -> Self {
+        Self {
+            entities: 1_000,
+            edges_per_entity: 3,
+            entity_types: Vocabulary::weighted([("Person", 5), ("Project", 3), ("Document", 2)]),
+            relation_types: Vocabulary::weighted([("knows", 5), ("worksOn", 3), ("cites", 2)]),
+            confidence: ConfidenceDistribution::SkewedHigh {
+                min: 0.5,
+                max: 1.0,
+                exponent: 3.0,
+            },
+            seed: 0,
+            build_indexes: true,
+        }
+    }
+}
+
+/// Counter-based SplitMix64 stream (no `rand` dependency).
+struct SplitMix(u64);
+
+impl SplitMix {
+    fn new(seed: u64) -> Self {
+        Self(mix64(seed))
+    }
+
+    /// Uniform in `0..n` (`n > 0`).
+    fn below(&mut self, n: u64) -> u64 {
+        self.0 = self.0.wrapping_add(1);
+        ((u128::from(mix64(self.0)) * u128::from(n)) >> 64) as u64
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn unit(&mut self) -> f32 {
+        self.below(1 << 24) as f32 / (1 << 24) as f32
+    }
+}
+
+/// Generate a graph per `config` (see the [module docs](self)).
+pub fn synthetic_graph(config: &SyntheticGraphConfig) -> PathDB {
+    let mut rng = SplitMix::new(config.seed);
+    let mut db = PathDB::new();
+
+    // Each entity appears once, plus once per edge endpoint: a uniform pick
+    // from this list is a pick proportional to degree + 1.
+    let mut endpoints: Vec<u32> = Vec::new();
+    let mut targets: Vec<u32> = Vec::with_capacity(config.edges_per_entity as usize);
+    for i in 0..config.entities {
+        let entity_type = config.entity_types.draw(&mut rng);
+        let name = format!("{}_{i}", entity_type.to_ascii_lowercase());
+        let id = db.add_entity(entity_type, vec![("name", name.as_str())]);
+
+        targets.clear();
+        if !endpoints.is_empty() {
+            for _ in 0..config.edges_per_entity {
+                let target = endpoints[rng.below(endpoints.len() as u64) as usize];
+                if !targets.contains(&target) {
+                    targets.push(target);
+                }
+            }
+        }
+        for &target in &targets {
+            let rel_type = config.relation_types.draw(&mut rng);
+            let confidence = config.confidence.draw(&mut rng);
+            db.add_relation(rel_type, id, target, confidence, Vec::new());
+            endpoints.push(target);
+            endpoints.push(id);
+        }
+        endpoints.push(id);
+    }
+
+    if config.build_indexes {
+        db.build_indexes();
+    }
+    db
+}
@@ -81,7 +81,9 @@ impl TextIndexCache {
             return RoaringBitmap::new();
         }
         let gen = self.generation.load(Ordering::SeqCst);
-        if self.is_ready(attr_key_id, gen) {
+        let ready = self.is_ready(attr_key_id, gen);
+        crate::metrics::record_cache("text_index", ready);
+        if ready {
             let guard = self.indexes.read().expect("text index lock poisoned");
             let Some((_, index)) = guard.get(&attr_key_id) else {
                 return RoaringBitmap::new();
@@ -109,7 +111,9 @@ impl TextIndexCache {
             return RoaringBitmap::new();
         }
         let gen = self.generation.load(Ordering::SeqCst);
-        if self.is_ready(attr_key_id, gen) {
+        let ready = self.is_ready(attr_key_id, gen);
+        crate::metrics::record_cache("text_index", ready);
+        if ready {
             let guard = self.indexes.read().expect("text index lock poisoned");
             let Some((_, index)) = guard.get(&attr_key_id) else {
                 return RoaringBitmap::new();
@@ -279,6 +283,9 @@ fn fallback_all(db: &PathDB, attr_key_id: StrId, tokens: &[String]) -> RoaringBi
 }
 
 fn build_inverted_index(db: &PathDB, attr_key_id: StrId) -> InvertedIndex {
+    let _span = tracing::debug_span!("pathdb.text_index.build", attr_key = attr_key_id.raw()).entered();
+    let build_seconds = crate::metrics::index_build_seconds("text");
+    let _timer = build_seconds.start_timer();
     let mut out = InvertedIndex::default();
 
     let Some(col) = db.entities.attrs.get(&attr_key_id) else {
@@ -0,0 +1,179 @@
+//! Entity type hierarchy (the subtype lattice).
+//!
+//! `.axi` schemas declare subtypes (`subtype Metal < Material`). PathDB keeps
+//! those declarations in a [`TypeLattice`] and materializes them in the type
+//! index: an entity is listed under its canonical type *and every supertype*,
+//! so `find_by_type("Material")` (and everything built on it: `SelectByType`,
+//! AxQL `?x is Material`) also returns `Metal` and `Steel` entities.
+//! [`PathDB::find_by_exact_type`] is the exact-type variant.
+//!
+//! The lattice is not part of the snapshot format. Module import feeds it,
+//! and it is rebuilt from the meta-plane subtype declarations
+//! (`AxiMetaSubtypeDecl`) when a snapshot is loaded.
+//!
+//! Declarations are global: `subtype Metal < Material` in one schema makes
+//! every `Metal` entity a `Material`, whatever schema it came from.
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use roaring::RoaringBitmap;
+
+use crate::axi_meta::{ATTR_SUBTYPE_SUB, ATTR_SUBTYPE_SUP, META_TYPE_SUBTYPE_DECL};
+use crate::{PathDB, StrId};
+
+/// Declared `sub < sup` edges between entity types.
+#[derive(Debug, Clone, Default)]
+pub struct TypeLattice {
+    /// Direct supertypes of each type.
+    supertypes: HashMap<StrId, BTreeSet<StrId>>,
+}
+
+impl TypeLattice {
+    pub fn is_empty(&self) -> bool {
+        self.supertypes.is_empty()
+    }
+
+    /// Record `sub < sup`; returns false if it was already declared.
+    pub fn declare(&mut self, sub: StrId, sup: StrId) -> bool {
+        sub != sup && self.supertypes.entry(sub).or_default().insert(sup)
+    }
+
+    /// Every declared `(sub, sup)` pair (direct edges only).
+    pub fn declared(&self) -> impl Iterator<Item = (StrId, StrId)> + '_ {
+        self.supertypes
+            .iter()
+            .flat_map(|(&sub, sups)| sups.iter().map(move |&sup| (sub, sup)))
+    }
+
+    /// Strict supertypes of `ty`, transitively (cycles are tolerated).
+    pub fn supertypes(&self, ty: StrId) -> Vec<StrId> {
+        self.closure(ty, |t| {
+            self.supertypes
+                .get(&t)
+                .into_iter()
+                .flatten()
+                .copied()
+                .collect()
+        })
+    }
+
+    /// Strict subtypes of `ty`, transitively.
+    pub fn subtypes(&self, ty: StrId) -> Vec<StrId> {
+        self.closure(ty, |t| {
+            self.supertypes
+                .iter()
+                .filter(|(_, sups)| sups.contains(&t))
+                .map(|(&sub, _)| sub)
+                .collect()
+        })
+    }
+
+    /// `sub <: sup` (reflexive).
+    pub fn is_subtype(&self, sub: StrId, sup: StrId) -> bool {
+        sub == sup || self.supertypes(sub).contains(&sup)
+    }
+
+    fn closure(&self, start: StrId, next: impl Fn(StrId) -> Vec<StrId>) -> Vec<StrId> {
+        let mut seen = BTreeSet::from([start]);
+        let mut out = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(t) = queue.pop_front() {
+            for n in next(t) {
+                if seen.insert(n) {
+                    out.push(n);
+                    queue.push_back(n);
+                }
+            }
+        }
+        out
+    }
+}
+
+impl PathDB {
+    /// The declared subtype lattice.
+    pub fn type_lattice(&self) -> &TypeLattice {
+        &self.type_lattice
+    }
+
+    /// Declare `sub < sup` and list every `sub` entity (subtypes included)
+    /// under `sup` and its supertypes.
+    pub fn declare_subtype(&mut self, sub: &str, sup: &str) {
+        let sub_id = self.interner.intern(sub);
+        let sup_id = self.interner.intern(sup);
+        if !self.type_lattice.declare(sub_id, sup_id) {
+            return;
+        }
+        let Some(members) = self.entities.type_index.get(&sub_id).cloned() else {
+            return;
+        };
+        for ty in std::iter::once(sup_id).chain(self.type_lattice.supertypes(sup_id)) {
+            *self.entities.type_index.entry(ty).or_default() |= &members;
+        }
+        self.rebuild_composite_data();
+        self.fact_index.invalidate();
+        self.path_index.invalidate();
+        self.cardinality.invalidate();
+    }
+
+    /// Entities whose type is exactly `type_name`: those of `find_by_type`
+    /// minus the ones listed under a declared proper subtype.
+    pub fn find_by_exact_type(&self, type_name: &str) -> RoaringBitmap {
+        let Some(type_id) = self.interner.id_of(type_name) else {
+            return RoaringBitmap::new();
+        };
+        let Some(all) = self.entities.by_type(type_id) else {
+            return RoaringBitmap::new();
+        };
+        let mut exact = all.clone();
+        for sub in self.type_lattice.subtypes(type_id) {
+            if let Some(ids) = self.entities.by_type(sub) {
+                exact -= ids;
+            }
+        }
+        exact
+    }
+
+    /// List a newly typed entity under the supertypes of `type_id`.
+    pub(crate) fn index_supertypes(&mut self, type_id: StrId, entity_id: u32) {
+        if self.type_lattice.is_empty() {
+            return;
+        }
+        for sup in self.type_lattice.supertypes(type_id) {
+            self.entities
+                .type_index
+                .entry(sup)
+                .or_default()
+                .insert(entity_id);
+            self.cardinality.on_entity_added(sup, entity_id);
+        }
+    }
+
+    /// Re-declare every meta-plane subtype declaration (after loading a
+    /// snapshot).
+    pub(crate) fn rebuild_type_lattice(&mut self) {
+        let Some(decls) = self.find_by_type(META_TYPE_SUBTYPE_DECL).cloned() else {
+            return;
+        };
+        let (Some(sub_key), Some(sup_key)) = (
+            self.interner.id_of(ATTR_SUBTYPE_SUB),
+            self.interner.id_of(ATTR_SUBTYPE_SUP),
+        ) else {
+            return;
+        };
+        let mut pairs = Vec::new();
+        for decl in &decls {
+            let sub = self.entities.get_attr(decl, sub_key);
+            let sup = self.entities.get_attr(decl, sup_key);
+            if let (Some(sub), Some(sup)) = (sub, sup) {
+                if let (Some(sub), Some(sup)) =
+                    (self.interner.lookup(sub), self.interner.lookup(sup))
+                {
+                    pairs.push((sub, sup));
+                }
+            }
+        }
+        for (sub, sup) in pairs {
+            self.declare_subtype(&sub, &sup);
+        }
+    }
+}
@@ -696,6 +696,21 @@ impl ReachabilityProof {
         }
     }
 
+    /// Combined confidence along path under an explicit policy
+    pub fn path_confidence_with(&self, combiner: crate::ConfidenceCombiner) -> VerifiedProb {
+        match self {
+            ReachabilityProof::Reflexive { .. } => VerifiedProb::new(combiner.identity()),
+            ReachabilityProof::Step {
+                rel_confidence,
+                rest,
+                ..
+            } => VerifiedProb::new(combiner.combine(
+                rel_confidence.value(),
+                rest.path_confidence_with(combiner).value(),
+            )),
+        }
+    }
+
     /// Extract path signature from proof
     pub fn to_path_sig(&self) -> VerifiedPathSig {
         let mut rel_types = Vec::new();
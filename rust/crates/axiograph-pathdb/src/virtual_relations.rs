@@ -0,0 +1,311 @@
+//! Virtual relations: edges computed at query time.
+//!
+//! Many joins are "entities whose `part_number` matches" rather than stored
+//! edges; materializing them costs one edge per matching pair. A virtual
+//! relation is a named rule instead, registered with
+//! [`PathDB::register_virtual_relation`] and evaluated when a traversal steps
+//! along its name:
+//!
+//! - [`VirtualRelationDef::AttrEquals`]: `source -name-> target` when
+//!   `source.source_attr == target.target_attr`, optionally restricted to a
+//!   source and/or target type. With a target type, registering declares the
+//!   `(target_type, target_attr)` composite index, so each step is one index
+//!   lookup; without one, it scans the `target_attr` column.
+//! - [`VirtualRelationDef::Predicate`]: `source -name-> target` for every
+//!   entity of `target_type` a Rust predicate accepts. This tests every
+//!   entity of the type per step; keep the type small.
+//!
+//! An entity is never related to itself. Virtual edges have confidence 1.0
+//! and belong to no context, so confidence and context filters never hide
+//! them; entity scopes ([`ContextScope::over`]) still hide their targets.
+//!
+//! A step along a virtual name covers `follow_one`, `follow_path` and
+//! `SelectRelated`/`FollowPath` queries, and mixes freely with stored and
+//! inverse steps. Paths with a virtual step skip the path index and are
+//! walked entity by entity under the query budget.
+//!
+//! Attribute-equality definitions live in the meta plane
+//! (`AxiMetaVirtualRelation` entities), so they survive snapshots.
+//! Predicates are closures: they are not persisted and must be registered
+//! again after loading.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use roaring::RoaringBitmap;
+
+use crate::axi_meta::{
+    ATTR_VIRTUAL_SOURCE_ATTR, ATTR_VIRTUAL_SOURCE_TYPE, ATTR_VIRTUAL_TARGET_ATTR,
+    ATTR_VIRTUAL_TARGET_TYPE, META_ATTR_NAME, META_TYPE_VIRTUAL_RELATION,
+};
+use crate::error::Result;
+use crate::{edge_visible, BudgetTracker, ContextScope, PathDB, PathDbError, StrId};
+
+/// `source.source_attr == target.target_attr`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttrEqualsDef {
+    pub source_type: Option<String>,
+    pub source_attr: String,
+    pub target_type: Option<String>,
+    pub target_attr: String,
+}
+
+impl AttrEqualsDef {
+    /// Entities of `entity_type` sharing a value of `attr`.
+    pub fn same(entity_type: &str, attr: &str) -> Self {
+        Self {
+            source_type: Some(entity_type.to_string()),
+            source_attr: attr.to_string(),
+            target_type: Some(entity_type.to_string()),
+            target_attr: attr.to_string(),
+        }
+    }
+}
+
+/// `(db, source, target)`: whether `source -name-> target` holds.
+pub type VirtualPredicate = Arc<dyn Fn(&PathDB, u32, u32) -> bool + Send + Sync>;
+
+#[derive(Clone)]
+pub enum VirtualRelationDef {
+    AttrEquals(AttrEqualsDef),
+    Predicate {
+        target_type: String,
+        predicate: VirtualPredicate,
+    },
+}
+
+impl std::fmt::Debug for VirtualRelationDef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VirtualRelationDef::AttrEquals(def) => f.debug_tuple("AttrEquals").field(def).finish(),
+            VirtualRelationDef::Predicate { target_type, .. } => f
+                .debug_struct("Predicate")
+                .field("target_type", target_type)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Registered virtual relations, by name.
+#[derive(Debug, Clone, Default)]
+pub struct VirtualRelationRegistry {
+    defs: HashMap<StrId, VirtualRelationDef>,
+}
+
+impl VirtualRelationRegistry {
+    pub fn is_empty(&self) -> bool {
+        self.defs.is_empty()
+    }
+
+    pub fn get(&self, rel_type: StrId) -> Option<&VirtualRelationDef> {
+        self.defs.get(&rel_type)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (StrId, &VirtualRelationDef)> {
+        self.defs.iter().map(|(&rel, def)| (rel, def))
+    }
+}
+
+impl PathDB {
+    /// Register `name` as a virtual relation (see the module docs).
+    ///
+    /// Registering the same attribute-equality definition again is a no-op.
+    /// A name that already has stored edges, a registered inverse or a
+    /// different definition is an error.
+    pub fn register_virtual_relation(&mut self, name: &str, def: VirtualRelationDef) -> Result<()> {
+        let rel = self.interner.intern(name);
+        let conflict = |reason: &str| PathDbError::VirtualRelationConflict {
+            relation: name.to_string(),
+            reason: reason.to_string(),
+        };
+        match (self.virtual_relations.get(rel), &def) {
+            (
+                Some(VirtualRelationDef::AttrEquals(existing)),
+                VirtualRelationDef::AttrEquals(new),
+            ) if existing == new => return Ok(()),
+            (Some(_), _) => return Err(conflict("it is already a virtual relation")),
+            (None, _) => {}
+        }
+        if self.relations.rel_type_count(rel) > 0 {
+            return Err(conflict("it has stored edges"));
+        }
+        if self.inverses.inverse(rel).is_some() {
+            return Err(conflict("it has a registered inverse"));
+        }
+
+        if let VirtualRelationDef::AttrEquals(attr_eq) = &def {
+            if let Some(target_type) = &attr_eq.target_type {
+                self.declare_composite_index(target_type, &attr_eq.target_attr);
+            }
+            let mut attrs = vec![
+                (META_ATTR_NAME, name),
+                (ATTR_VIRTUAL_SOURCE_ATTR, attr_eq.source_attr.as_str()),
+                (ATTR_VIRTUAL_TARGET_ATTR, attr_eq.target_attr.as_str()),
+            ];
+            if let Some(t) = &attr_eq.source_type {
+                attrs.push((ATTR_VIRTUAL_SOURCE_TYPE, t));
+            }
+            if let Some(t) = &attr_eq.target_type {
+                attrs.push((ATTR_VIRTUAL_TARGET_TYPE, t));
+            }
+            self.add_entity(META_TYPE_VIRTUAL_RELATION, attrs);
+        }
+        self.virtual_relations.defs.insert(rel, def);
+        Ok(())
+    }
+
+    /// The definition of virtual relation `name`, if registered.
+    pub fn virtual_relation(&self, name: &str) -> Option<&VirtualRelationDef> {
+        self.virtual_relations.get(self.interner.id_of(name)?)
+    }
+
+    /// Names of every registered virtual relation, sorted.
+    pub fn virtual_relation_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .virtual_relations
+            .defs
+            .keys()
+            .filter_map(|&id| self.interner.lookup(id))
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Targets of `entity` along virtual relation `rel_type` (empty if it is
+    /// not virtual).
+    pub(crate) fn virtual_targets_by_id(&self, entity: u32, rel_type: StrId) -> RoaringBitmap {
+        let mut out = match self.virtual_relations.get(rel_type) {
+            None => return RoaringBitmap::new(),
+            Some(VirtualRelationDef::AttrEquals(def)) => self.attr_equals_targets(entity, def),
+            Some(VirtualRelationDef::Predicate {
+                target_type,
+                predicate,
+            }) => self
+                .find_by_type(target_type)
+                .map(|candidates| {
+                    candidates
+                        .iter()
+                        .filter(|&target| predicate(self, entity, target))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+        out.remove(entity);
+        out
+    }
+
+    fn attr_equals_targets(&self, entity: u32, def: &AttrEqualsDef) -> RoaringBitmap {
+        if let Some(source_type) = &def.source_type {
+            if !self
+                .find_by_type(source_type)
+                .is_some_and(|ids| ids.contains(entity))
+            {
+                return RoaringBitmap::new();
+            }
+        }
+        let value = self
+            .interner
+            .id_of(&def.source_attr)
+            .and_then(|key| self.entities.get_attr(entity, key))
+            .and_then(|value| self.interner.lookup(value));
+        let Some(value) = value else {
+            return RoaringBitmap::new();
+        };
+        match &def.target_type {
+            Some(target_type) => {
+                self.entities_with_type_attr(target_type, &def.target_attr, &value)
+            }
+            None => match (
+                self.interner.id_of(&def.target_attr),
+                self.interner.id_of(&value),
+            ) {
+                (Some(attr), Some(value)) => self.entities.entities_with_attr_value(attr, value),
+                _ => RoaringBitmap::new(),
+            },
+        }
+    }
+
+    /// Whether any step of `path` is a virtual relation.
+    pub(crate) fn has_virtual_step(&self, path: &[StrId]) -> bool {
+        !self.virtual_relations.is_empty()
+            && path
+                .iter()
+                .any(|&id| self.virtual_relations.get(id).is_some())
+    }
+
+    /// Entity-by-entity walk of `path` where each step follows stored edges,
+    /// registered inverses and virtual relations (see the module docs).
+    pub(crate) fn follow_virtual_path(
+        &self,
+        start: u32,
+        path: &[StrId],
+        min_confidence: Option<f32>,
+        context: Option<&ContextScope>,
+        tracker: &mut BudgetTracker,
+    ) -> RoaringBitmap {
+        let mut current = RoaringBitmap::new();
+        current.insert(start);
+        for (hop, &rel_type) in path.iter().enumerate() {
+            let last_hop = hop + 1 == path.len();
+            let mut next = RoaringBitmap::new();
+            for entity in current.iter() {
+                if tracker.visit().is_err() {
+                    return if last_hop { next } else { RoaringBitmap::new() };
+                }
+                if self.virtual_relations.get(rel_type).is_some() {
+                    let targets = self.virtual_targets_by_id(entity, rel_type);
+                    next |= match context {
+                        Some(scope) => scope.clip(targets),
+                        None => targets,
+                    };
+                    continue;
+                }
+                next.extend(
+                    self.relations
+                        .outgoing(entity, rel_type)
+                        .into_iter()
+                        .filter(|r| edge_visible(r, min_confidence, context))
+                        .map(|r| r.target),
+                );
+                next |= self.inverse_targets_by_id(entity, rel_type, min_confidence, context);
+            }
+            current = next;
+            if current.is_empty() {
+                break;
+            }
+        }
+        current
+    }
+
+    /// Re-register every meta-plane attribute-equality definition (after
+    /// loading a snapshot).
+    pub(crate) fn rebuild_virtual_relations(&mut self) {
+        let Some(decls) = self.find_by_type(META_TYPE_VIRTUAL_RELATION).cloned() else {
+            return;
+        };
+        let attr = |db: &PathDB, entity: u32, key: &str| -> Option<String> {
+            let key = db.interner.id_of(key)?;
+            db.interner.lookup(db.entities.get_attr(entity, key)?)
+        };
+        for decl in &decls {
+            let (Some(name), Some(source_attr), Some(target_attr)) = (
+                attr(self, decl, META_ATTR_NAME),
+                attr(self, decl, ATTR_VIRTUAL_SOURCE_ATTR),
+                attr(self, decl, ATTR_VIRTUAL_TARGET_ATTR),
+            ) else {
+                continue;
+            };
+            let def = AttrEqualsDef {
+                source_type: attr(self, decl, ATTR_VIRTUAL_SOURCE_TYPE),
+                source_attr,
+                target_type: attr(self, decl, ATTR_VIRTUAL_TARGET_TYPE),
+                target_attr,
+            };
+            let rel = self.interner.intern(&name);
+            self.virtual_relations
+                .defs
+                .entry(rel)
+                .or_insert(VirtualRelationDef::AttrEquals(def));
+        }
+    }
+}
@@ -93,7 +93,7 @@ pub fn stable_entity_id_v1(db: &PathDB, entity_id: u32) -> Result<String> {
         .ok_or_else(|| anyhow!("internal error: missing string interner entry {name_val:?}"))
 }
 
-fn relation_axi_fact_id_v1(db: &PathDB, rel_id: u32) -> Result<String> {
+pub(crate) fn relation_axi_fact_id_v1(db: &PathDB, rel_id: u32) -> Result<String> {
     let rel = db
         .relations
         .get_relation(rel_id)
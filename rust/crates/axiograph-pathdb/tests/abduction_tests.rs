@@ -0,0 +1,80 @@
+use axiograph_pathdb::{AbductionConfig, PathDB};
+
+/// alice -worksAt-> acme -locatedIn-> paris; bob works nowhere; globex is in
+/// berlin.
+fn db() -> (PathDB, [u32; 6]) {
+    let mut db = PathDB::new();
+    let alice = db.add_entity("Person", vec![("name", "alice")]);
+    let bob = db.add_entity("Person", vec![("name", "bob")]);
+    let acme = db.add_entity("Company", vec![("name", "acme")]);
+    let globex = db.add_entity("Company", vec![("name", "globex")]);
+    let paris = db.add_entity("City", vec![("name", "paris")]);
+    let berlin = db.add_entity("City", vec![("name", "berlin")]);
+    db.add_relation("worksAt", alice, acme, 1.0, Vec::new());
+    db.add_relation("locatedIn", acme, paris, 0.9, Vec::new());
+    db.add_relation("locatedIn", globex, berlin, 0.9, Vec::new());
+    db.build_indexes();
+    (db, [alice, bob, acme, globex, paris, berlin])
+}
+
+#[test]
+fn proposes_the_fewest_plausible_missing_edges() {
+    let (db, [alice, bob, acme, _, paris, berlin]) = db();
+    let config = AbductionConfig::default();
+
+    // Already reachable: nothing to propose.
+    let found = db.abduce_reachability(alice, paris, &config).unwrap();
+    assert!(found.already_reachable());
+    assert_eq!(found.path.len(), 2);
+
+    // bob needs an employer in paris. Person -> City has no plausible
+    // relation, so the proposal goes through a company.
+    let found = db.abduce_reachability(bob, paris, &config).unwrap();
+    assert_eq!(found.proposed.len(), 1);
+    let edge = &found.proposed[0];
+    assert_eq!(
+        (edge.source, edge.rel_type.as_str(), edge.target),
+        (bob, "worksAt", acme)
+    );
+    assert_eq!(edge.support, 1);
+    assert_eq!(edge.confidence, config.confidence);
+    assert_eq!(
+        found
+            .path
+            .iter()
+            .map(|s| (s.rel_type.as_str(), s.proposed))
+            .collect::<Vec<_>>(),
+        vec![("worksAt", true), ("locatedIn", false)]
+    );
+
+    // paris -> berlin needs an unsupported City edge: no plausible answer.
+    assert!(db.abduce_reachability(paris, berlin, &config).is_none());
+}
+
+#[test]
+fn limits_and_relation_filters_bound_the_search() {
+    let (db, [_, bob, _, _, paris, _]) = db();
+
+    let none_missing = AbductionConfig {
+        max_missing: 0,
+        ..Default::default()
+    };
+    assert!(db.abduce_reachability(bob, paris, &none_missing).is_none());
+
+    let only_located = AbductionConfig {
+        relations: vec!["locatedIn".to_string()],
+        ..Default::default()
+    };
+    assert!(db.abduce_reachability(bob, paris, &only_located).is_none());
+
+    let well_supported = AbductionConfig {
+        min_support: 2,
+        ..Default::default()
+    };
+    assert!(db
+        .abduce_reachability(bob, paris, &well_supported)
+        .is_none());
+    assert!(db
+        .abduce_reachability(bob, 999, &AbductionConfig::default())
+        .is_none());
+}
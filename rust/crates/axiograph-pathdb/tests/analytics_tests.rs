@@ -0,0 +1,74 @@
+use axiograph_pathdb::analytics::{
+    betweenness_approx, label_propagation, pagerank, write_analytics_attrs, AnalyticsGraph,
+    AnalyticsReport, PageRankConfig, ATTR_COMMUNITY, ATTR_PAGERANK,
+};
+use axiograph_pathdb::PathDB;
+
+/// Two triangles joined by a single weak bridge edge `a2 -> b0`.
+fn two_triangles() -> (PathDB, Vec<u32>, Vec<u32>) {
+    let mut db = PathDB::new();
+    let a: Vec<u32> = (0..3)
+        .map(|i| db.add_entity("Node", vec![("name", format!("a{i}").as_str())]))
+        .collect();
+    let b: Vec<u32> = (0..3)
+        .map(|i| db.add_entity("Node", vec![("name", format!("b{i}").as_str())]))
+        .collect();
+    for group in [&a, &b] {
+        for i in 0..3 {
+            db.add_relation("link", group[i], group[(i + 1) % 3], 1.0, vec![]);
+        }
+    }
+    db.add_relation("link", a[2], b[0], 0.2, vec![]);
+    (db, a, b)
+}
+
+#[test]
+fn pagerank_sums_to_one_and_respects_personalization() {
+    let (db, a, b) = two_triangles();
+    let graph = AnalyticsGraph::from_db(&db, None);
+
+    let pr = pagerank(&graph, &PageRankConfig::default());
+    let total: f64 = pr.iter().sum();
+    assert!((total - 1.0).abs() < 1e-6, "total = {total}");
+
+    let personalized = pagerank(
+        &graph,
+        &PageRankConfig {
+            personalization: vec![b[1]],
+            ..Default::default()
+        },
+    );
+    // Mass teleports into the `b` triangle and never flows back to `a`.
+    assert!(a.iter().all(|&x| personalized[x as usize] < 1e-9));
+    assert!(personalized[b[1] as usize] > personalized[b[0] as usize]);
+}
+
+#[test]
+fn bridge_endpoints_have_highest_betweenness() {
+    let (db, a, b) = two_triangles();
+    let graph = AnalyticsGraph::from_db(&db, None);
+
+    let exact = betweenness_approx(&graph, usize::MAX);
+    let max = exact.iter().cloned().fold(0.0, f64::max);
+    assert_eq!(exact[a[2] as usize], max);
+    assert!(exact[b[0] as usize] > exact[b[1] as usize]);
+    assert!(exact[a[0] as usize] < exact[a[2] as usize]);
+}
+
+#[test]
+fn label_propagation_finds_both_triangles_and_writes_attrs() {
+    let (mut db, a, b) = two_triangles();
+    let graph = AnalyticsGraph::from_db(&db, Some(&["link"]));
+
+    let communities = label_propagation(&graph, 50);
+    assert!(a.iter().all(|&x| communities[x as usize] == Some(a[0])));
+    assert!(b.iter().all(|&x| communities[x as usize] == Some(b[0])));
+
+    let report = AnalyticsReport::compute(&graph, 8);
+    assert_eq!(report.community_count(), 2);
+    assert_eq!(write_analytics_attrs(&mut db, &graph, &report).unwrap(), 6);
+
+    let view = db.get_entity(b[2]).unwrap();
+    assert_eq!(view.attrs.get(ATTR_COMMUNITY), Some(&b[0].to_string()));
+    assert!(view.attrs.contains_key(ATTR_PAGERANK));
+}
@@ -0,0 +1,124 @@
+use axiograph_pathdb::{AttrColumn, PathDB, StrId};
+
+#[test]
+fn attr_column_behaves_like_a_map_in_entity_order() {
+    let mut col = AttrColumn::new();
+    assert_eq!(col.insert(5, StrId::new(50)), None);
+    assert_eq!(col.insert(1, StrId::new(10)), None);
+    assert_eq!(col.insert(9, StrId::new(90)), None);
+    assert_eq!(col.insert(3, StrId::new(30)), None);
+    assert_eq!(col.insert(5, StrId::new(55)), Some(StrId::new(50)));
+
+    assert_eq!(col.len(), 4);
+    assert_eq!(col.get(&5), Some(&StrId::new(55)));
+    assert_eq!(col.get(&4), None);
+    assert!(col.contains_key(&9));
+    assert_eq!(col.keys().copied().collect::<Vec<_>>(), vec![1, 3, 5, 9]);
+
+    *col.get_mut(&1).unwrap() = StrId::new(11);
+    assert_eq!(col.remove(&3), Some(StrId::new(30)));
+    assert_eq!(col.remove(&3), None);
+    let pairs: Vec<(u32, u32)> = col.iter().map(|(e, v)| (*e, v.raw())).collect();
+    assert_eq!(pairs, vec![(1, 11), (5, 55), (9, 90)]);
+
+    let collected: AttrColumn = [(7, StrId::new(1)), (2, StrId::new(2)), (7, StrId::new(3))]
+        .into_iter()
+        .collect();
+    let pairs: Vec<(u32, u32)> = collected.iter().map(|(e, v)| (*e, v.raw())).collect();
+    assert_eq!(pairs, vec![(2, 2), (7, 3)]);
+}
+
+#[test]
+fn attr_columns_hold_non_scalar_values() {
+    let mut col: AttrColumn<Vec<u32>> = AttrColumn::new();
+    col.get_or_insert_default(8).push(1);
+    col.get_or_insert_default(2).push(2);
+    col.get_or_insert_default(8).push(3);
+    let rows: Vec<(u32, Vec<u32>)> = col.iter().map(|(e, v)| (*e, v.clone())).collect();
+    assert_eq!(rows, vec![(2, vec![2]), (8, vec![1, 3])]);
+}
+
+#[test]
+fn attribute_columns_stay_compact_and_round_trip() {
+    let mut db = PathDB::new();
+    let n = 20_000u32;
+    for i in 0..n {
+        db.add_entity(
+            "Item",
+            vec![("name", format!("item_{i}").as_str()), ("kind", "widget")],
+        );
+    }
+    // Out-of-order attribute writes land in place.
+    for i in (0..n).step_by(97).rev() {
+        db.upsert_entity_attr(i, "tag", "sampled").unwrap();
+    }
+
+    let name = db.interner.id_of("name").unwrap();
+    let tag = db.interner.id_of("tag").unwrap();
+    let tags = db.entities.attr_column(tag).unwrap();
+    assert!(tags.keys().zip(tags.keys().skip(1)).all(|(a, b)| a < b));
+    assert_eq!(db.entities.get_attr(97, tag), db.interner.id_of("sampled"));
+
+    // 8 bytes per (entity, value) pair plus `Vec` growth slack.
+    let pairs = db.entities.attr_column(name).unwrap().len() * 2 + tags.len();
+    assert!(db.entities.attr_heap_bytes() <= pairs * 8 * 2);
+
+    let bytes = db.to_bytes().unwrap();
+    let loaded = PathDB::from_bytes(&bytes).unwrap();
+    assert_eq!(loaded.to_bytes().unwrap(), bytes);
+    let item = loaded.interner.id_of("item_1234").unwrap();
+    let name = loaded.interner.id_of("name").unwrap();
+    assert_eq!(
+        loaded
+            .entities
+            .entities_with_attr_value(name, item)
+            .iter()
+            .collect::<Vec<_>>(),
+        vec![1234]
+    );
+}
+
+#[test]
+fn value_index_tracks_inserts_and_upserts_and_is_runtime_only() {
+    let mut db = PathDB::new();
+    let a = db.add_entity("Person", vec![("external_id", "p-1")]);
+    let b = db.add_entity("Robot", vec![("external_id", "p-2")]);
+    let ext = db.interner.id_of("external_id").unwrap();
+    let p1 = db.interner.id_of("p-1").unwrap();
+    assert_eq!(db.entities.indexed_attr_value(ext, p1), None);
+
+    assert!(db.index_attr_values("external_id"));
+    assert!(!db.index_attr_values("external_id"));
+    let ids = |bits: Option<roaring::RoaringBitmap>| bits.map(|b| b.iter().collect::<Vec<_>>());
+    assert_eq!(ids(db.entities.indexed_attr_value(ext, p1)), Some(vec![a]));
+
+    let c = db.add_entity("Person", vec![("external_id", "p-1")]);
+    db.upsert_entity_attr(b, "external_id", "p-1").unwrap();
+    db.upsert_entity_attr(a, "external_id", "p-3").unwrap();
+    let p2 = db.interner.id_of("p-2").unwrap();
+    let p3 = db.interner.id_of("p-3").unwrap();
+    assert_eq!(
+        ids(db.entities.indexed_attr_value(ext, p1)),
+        Some(vec![b, c])
+    );
+    assert_eq!(ids(db.entities.indexed_attr_value(ext, p2)), Some(vec![]));
+    assert_eq!(ids(db.entities.indexed_attr_value(ext, p3)), Some(vec![a]));
+    assert_eq!(
+        db.entities
+            .entities_with_attr_value(ext, p1)
+            .iter()
+            .collect::<Vec<_>>(),
+        vec![b, c]
+    );
+
+    // Not persisted: a loaded snapshot scans until the index is declared again.
+    let bytes = db.to_bytes().unwrap();
+    let mut loaded = PathDB::from_bytes(&bytes).unwrap();
+    assert_eq!(loaded.to_bytes().unwrap(), bytes);
+    assert_eq!(loaded.entities.indexed_attr_value(ext, p1), None);
+    assert!(loaded.index_attr_values("external_id"));
+    assert_eq!(
+        ids(loaded.entities.indexed_attr_value(ext, p1)),
+        Some(vec![b, c])
+    );
+}
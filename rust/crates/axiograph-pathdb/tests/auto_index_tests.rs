@@ -0,0 +1,109 @@
+//! Statistics-driven path index selection.
+
+use axiograph_pathdb::{AutoIndexConfig, IndexReason, PathDB, PathIndexPolicy, PathSig};
+
+/// A chain `n0 -r-> n1 -r-> ...` with `s` / `t` shortcuts, plus a dense
+/// `hub` relation fanning out from every node.
+fn chain_db(len: u32) -> PathDB {
+    let mut db = PathDB::new();
+    let nodes: Vec<u32> = (0..len)
+        .map(|i| db.add_entity("Node", vec![("name", &format!("n{i}"))]))
+        .collect();
+    for w in nodes.windows(2) {
+        db.add_relation("r", w[0], w[1], 1.0, vec![]);
+        db.add_relation("s", w[0], w[1], 1.0, vec![]);
+        db.add_relation("t", w[1], w[0], 1.0, vec![]);
+    }
+    for &a in &nodes {
+        for &b in &nodes {
+            db.add_relation("hub", a, b, 1.0, vec![]);
+        }
+    }
+    db
+}
+
+fn sig(db: &PathDB, rels: &[&str]) -> PathSig {
+    PathSig::new(rels.iter().map(|r| db.interner.id_of(r).unwrap()).collect())
+}
+
+#[test]
+fn auto_policy_matches_the_cube_when_the_budget_allows() {
+    let mut cube = chain_db(6);
+    cube.set_path_index_policy(PathIndexPolicy::Cube);
+    cube.build_indexes();
+    let mut auto = chain_db(6);
+    assert!(matches!(auto.path_index_policy(), PathIndexPolicy::Auto(_)));
+    auto.build_indexes();
+    assert_eq!(auto.path_index.signatures(), cube.path_index.signatures());
+}
+
+#[test]
+fn auto_policy_skips_dense_signatures_and_indexes_logged_paths() {
+    let mut db = chain_db(20);
+    db.set_path_index_policy(PathIndexPolicy::Auto(AutoIndexConfig {
+        max_entries: 200,
+        ..AutoIndexConfig::default()
+    }));
+    let start = 0;
+
+    // Nothing logged yet: only cheap cube signatures fit.
+    db.build_indexes();
+    assert!(db
+        .path_index
+        .query(start, &sig(&db, &["hub", "hub"]))
+        .is_none());
+    assert!(db.path_index.query(start, &sig(&db, &["r", "r"])).is_some());
+    assert!(db.path_index.query(start, &sig(&db, &["hub"])).is_some());
+
+    // A hot 4-hop path (deeper than the cube) shows up in the log.
+    let hot = ["r", "s", "r", "s"];
+    let expected = db.follow_path(start, &hot);
+    db.follow_path(start, &hot);
+    db.follow_path(start, &["t"]);
+    let stats = db.query_stats();
+    assert_eq!(stats.paths, vec![(hot.map(String::from).to_vec(), 2)]);
+    assert_eq!(stats.rel_types["r"].queries, 4);
+    assert_eq!(stats.rel_types["t"].queries, 1);
+    assert_eq!(stats.rel_types["r"].observed_fanout(), Some(1.0));
+
+    let plan = db.plan_path_index(&AutoIndexConfig {
+        max_entries: 200,
+        ..AutoIndexConfig::default()
+    });
+    let logged: Vec<_> = plan
+        .signatures
+        .iter()
+        .filter(|s| s.reason == IndexReason::Logged)
+        .map(|s| s.path.join("/"))
+        .collect();
+    assert_eq!(logged, vec!["r/s/r/s"]);
+    assert!(plan.skipped > 0);
+
+    db.build_indexes();
+    assert_eq!(db.path_index.query(start, &sig(&db, &hot)), Some(&expected));
+    // The log can be persisted and merged into a fresh snapshot.
+    let snapshot = db.query_stats();
+    db.reset_query_stats();
+    assert!(db.query_stats().paths.is_empty());
+    db.merge_query_stats(&snapshot);
+    assert_eq!(db.query_stats(), snapshot);
+}
+
+#[test]
+fn concurrent_readers_all_land_in_the_query_log() {
+    let db = chain_db(10);
+    let path = ["r", "s", "r", "s"];
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..25 {
+                    db.follow_path(0, &path);
+                }
+            });
+        }
+    });
+    let stats = db.query_stats();
+    assert_eq!(stats.paths, vec![(path.map(String::from).to_vec(), 100)]);
+    assert_eq!(stats.rel_types["r"].queries, 200);
+    assert_eq!(stats.rel_types["s"].expanded, 200);
+}
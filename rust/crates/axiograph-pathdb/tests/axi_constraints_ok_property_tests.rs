@@ -39,6 +39,7 @@ fn build_single_relation_module(
         constraints,
         equations: Vec::new(),
         rewrite_rules: Vec::new(),
+        views: Vec::new(),
     };
 
     // (Optional) declare the used `Atom` values so the module is readable.
@@ -85,6 +86,7 @@ fn build_single_relation_module(
 
     SchemaV1Module {
         module_name: "PropTest".to_string(),
+        imports: vec![],
         schemas: vec![schema],
         theories: vec![theory],
         instances: vec![inst],
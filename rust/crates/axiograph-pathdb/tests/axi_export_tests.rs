@@ -39,3 +39,39 @@ fn pathdb_export_v1_roundtrip_is_deterministic_and_reversible() {
         "expected `.axi` export to be stable across a round-trip"
     );
 }
+
+#[test]
+fn pathdb_export_v1_records_typed_attribute_values() {
+    let mut db = PathDB::new();
+    db.add_entity(
+        "Shipment",
+        vec![
+            ("weight", "12.50 kg"),
+            ("count", "-3"),
+            ("fragile", "true"),
+            ("zip", "02139"),
+        ],
+    );
+    db.build_indexes();
+
+    let axi = export_pathdb_to_axi_v1(&db).expect("export to axi");
+    assert!(axi.contains("literal=LitDec_125_m1"), "{axi}");
+    assert!(axi.contains("literal=LitInt_m3"), "{axi}");
+    assert!(axi.contains("literal=LitBool_true"), "{axi}");
+    assert!(!axi.contains("LitInt_2139"), "leading zeros stay strings");
+
+    let imported = import_pathdb_from_axi_v1(&axi).expect("import from axi");
+    assert_eq!(export_pathdb_to_axi_v1(&imported).unwrap(), axi);
+
+    // A typed reading that disagrees with the stored string is rejected.
+    let tampered = axi.replace("LitInt_m3", "LitInt_3");
+    let Err(err) = import_pathdb_from_axi_v1(&tampered) else {
+        panic!("expected the tampered typed_value row to be rejected");
+    };
+    assert!(err.to_string().contains("reads as"), "{err}");
+
+    // Exports without the table (older snapshots) still import.
+    let start = axi.find("  typed_value = ").unwrap();
+    let legacy = &axi[..start];
+    assert!(import_pathdb_from_axi_v1(legacy).is_ok());
+}
@@ -0,0 +1,184 @@
+use std::path::{Path, PathBuf};
+
+use axiograph_dsl::axi_v1::parse_axi_v1;
+use axiograph_pathdb::axi_module_graph::{ModuleGraph, ModuleGraphError, WORKSPACE_MODULE_NAME};
+
+const CORE: &str = r#"
+module Core
+
+schema People:
+  object Person
+  relation Parent(child: Person, parent: Person)
+"#;
+
+const RULES: &str = r#"
+module Rules
+import Core
+
+theory FamilyRules on People:
+  constraint key Parent(child, parent)
+"#;
+
+const APP: &str = r#"
+module App
+import Core
+import Rules
+
+instance Smiths of People:
+  Person = {Alice, Bob}
+  Parent = {(child=Bob, parent=Alice)}
+"#;
+
+fn write(dir: &Path, name: &str, text: &str) -> PathBuf {
+    let path = dir.join(format!("{name}.axi"));
+    std::fs::write(&path, text).expect("write .axi");
+    path
+}
+
+fn graph_of(modules: &[&str]) -> Result<ModuleGraph, ModuleGraphError> {
+    ModuleGraph::from_modules(modules.iter().map(|m| parse_axi_v1(m).expect("parse")))
+}
+
+#[test]
+fn load_follows_imports_and_typechecks_combined_module() {
+    let dir = tempfile::tempdir().unwrap();
+    let lib = tempfile::tempdir().unwrap();
+    write(dir.path(), "Rules", RULES);
+    let app = write(dir.path(), "App", APP);
+    // Found via the search path, not next to the importer.
+    write(lib.path(), "Core", CORE);
+
+    assert!(matches!(
+        ModuleGraph::load(&app, &[]),
+        Err(ModuleGraphError::ImportNotFound { import, .. }) if import == "Core"
+    ));
+
+    let graph = ModuleGraph::load(&app, &[lib.path().to_path_buf()]).expect("load");
+    assert_eq!(graph.entry(), Some("App"));
+    assert_eq!(graph.order(), ["Core", "Rules", "App"]);
+    assert_eq!(
+        graph.get("Core").unwrap().path.as_deref(),
+        Some(lib.path().join("Core.axi").as_path())
+    );
+
+    let typed = graph.typecheck().expect("typecheck combined");
+    let combined = typed.module();
+    assert_eq!(combined.module_name, "App");
+    assert!(combined.imports.is_empty());
+    assert_eq!(combined.schemas.len(), 1);
+    assert_eq!(combined.theories.len(), 1);
+    assert_eq!(typed.proof().tuple_count, 1);
+
+    // The combined module prints and re-parses without imports.
+    let printed = axiograph_dsl::printer::print_axi_v1(combined).expect("print");
+    assert_eq!(parse_axi_v1(&printed).unwrap(), *combined);
+}
+
+#[test]
+fn load_dir_takes_the_whole_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "Core", CORE);
+    write(dir.path(), "Rules", RULES);
+    write(dir.path(), "App", APP);
+
+    let graph = ModuleGraph::load_dir(dir.path()).expect("load dir");
+    assert_eq!(graph.len(), 3);
+    assert_eq!(graph.entry(), None);
+    assert_eq!(graph.combined().module_name, WORKSPACE_MODULE_NAME);
+    assert_eq!(
+        graph
+            .into_modules()
+            .iter()
+            .map(|m| m.module_name.as_str())
+            .collect::<Vec<_>>(),
+        ["Core", "Rules", "App"]
+    );
+
+    let missing = tempfile::tempdir().unwrap();
+    assert!(ModuleGraph::load_dir(&missing.path().join("nope"))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn rejects_cycles_and_missing_or_duplicate_modules() {
+    let a = "module A\nimport B\n";
+    let b = "module B\nimport C\n";
+    let c = "module C\nimport A\n";
+    match graph_of(&[a, b, c]) {
+        Err(ModuleGraphError::ImportCycle { cycle }) => assert_eq!(cycle, ["A", "B", "C", "A"]),
+        other => panic!("expected an import cycle, got {other:?}"),
+    }
+
+    assert!(matches!(
+        graph_of(&["module A\nimport A\n"]),
+        Err(ModuleGraphError::SelfImport { name }) if name == "A"
+    ));
+    assert!(matches!(
+        graph_of(&[RULES]),
+        Err(ModuleGraphError::MissingImport { module, import })
+            if module == "Rules" && import == "Core"
+    ));
+    assert!(matches!(
+        graph_of(&[CORE, CORE]),
+        Err(ModuleGraphError::DuplicateModule { name, .. }) if name == "Core"
+    ));
+
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "Core", "module NotCore\n");
+    let app = write(dir.path(), "App", "module App\nimport Core\n");
+    assert!(matches!(
+        ModuleGraph::load(&app, &[]),
+        Err(ModuleGraphError::ModuleNameMismatch { declared, import, .. })
+            if declared == "NotCore" && import == "Core"
+    ));
+}
+
+#[test]
+fn schema_visibility_is_limited_to_direct_imports() {
+    // `Outer` imports `Rules`, which imports `Core`: `People` is not re-exported.
+    let outer = "module Outer\nimport Rules\n\ninstance I of People:\n  Person = {Alice}\n";
+    match graph_of(&[CORE, RULES, outer]) {
+        Err(ModuleGraphError::SchemaReference {
+            kind,
+            item,
+            module,
+            cause,
+            ..
+        }) => {
+            assert_eq!(
+                (kind, item.as_str(), module.as_str()),
+                ("instance", "I", "Outer")
+            );
+            assert!(matches!(
+                *cause,
+                ModuleGraphError::SchemaNotImported { ref declared_in, .. } if declared_in == "Core"
+            ));
+        }
+        other => panic!("expected a schema reference error, got {other:?}"),
+    }
+
+    // The same schema declared by two modules.
+    let other = "module Other\n\nschema People:\n  object Person\n";
+    let both = "module Both\nimport Core\nimport Other\n";
+    assert!(matches!(
+        graph_of(&[CORE, other, both]),
+        Err(ModuleGraphError::DuplicateSchema { schema, .. }) if schema == "People"
+    ));
+
+    let graph = graph_of(&[CORE, RULES]).unwrap();
+    assert_eq!(graph.resolve_schema("Rules", "People").unwrap(), "Core");
+    assert_eq!(graph.resolve_schema("Core", "People").unwrap(), "Core");
+    assert!(matches!(
+        graph.resolve_schema("Rules", "Nope"),
+        Err(ModuleGraphError::UndeclaredSchema { .. })
+    ));
+    assert!(matches!(
+        graph.resolve_schema("Nope", "People"),
+        Err(ModuleGraphError::UnknownModule { name }) if name == "Nope"
+    ));
+
+    // Schema-less fragments (storage change logs) are not resolved here.
+    let fragment = "module Change\n\ninstance C of Knowledge:\n  Thing = {x}\n";
+    assert!(graph_of(&[CORE, fragment]).is_ok());
+}
@@ -0,0 +1,95 @@
+use anyhow::Result;
+use axiograph_dsl::regen::DeclRef;
+use axiograph_pathdb::axi_meta::{
+    ATTR_AXI_INSTANCE, ATTR_AXI_MODULE, ATTR_AXI_SCHEMA, META_ATTR_NAME,
+};
+use axiograph_pathdb::axi_module_export::regenerate_axi_from_pathdb;
+use axiograph_pathdb::axi_module_import::import_axi_schema_v1_module_into_pathdb;
+use axiograph_pathdb::PathDB;
+
+const LEDGER: &str = r#"-- Payments ledger (hand-authored).
+module Payments
+
+schema Ledger:
+  -- Money movements.
+  object Payment
+  object Status -- open | settled
+  relation PaymentStatus(payment: Payment, status: Status)
+
+theory Reports on Ledger:
+  view open_payments = select ?p where ?p is Payment
+
+-- Sample data.
+instance I of Ledger:
+  -- Keep this list short.
+  Payment = {p1, p2}
+  Status = {open, settled}
+  PaymentStatus = {(payment=p1, status=open), (payment=p2, status=settled)}
+"#;
+
+fn import(text: &str) -> Result<PathDB> {
+    let m = axiograph_dsl::axi_v1::parse_axi_v1(text)?;
+    let mut db = PathDB::new();
+    import_axi_schema_v1_module_into_pathdb(&mut db, &m)?;
+    Ok(db)
+}
+
+#[test]
+fn regenerated_instance_picks_up_graph_edits_and_keeps_comments() -> Result<()> {
+    let mut db = import(LEDGER)?;
+    // An edit made through the API, not through the file.
+    db.add_entity(
+        "Payment",
+        vec![
+            (META_ATTR_NAME, "p3"),
+            (ATTR_AXI_MODULE, "Payments"),
+            (ATTR_AXI_SCHEMA, "Ledger"),
+            (ATTR_AXI_INSTANCE, "I"),
+        ],
+    );
+
+    let select: Vec<DeclRef> = vec!["instance:I".parse().unwrap()];
+    let regen = regenerate_axi_from_pathdb(&db, "Payments", LEDGER, &select)?;
+    assert_eq!(regen.replaced, vec!["instance I"]);
+    assert!(regen.added.is_empty());
+    assert_eq!(regen.orphaned_comments, 0);
+
+    let text = &regen.text;
+    assert!(text.contains("  -- Keep this list short.\n  Payment = {p1, p2, p3}\n"));
+    // Unselected blocks and everything between blocks are untouched.
+    let (head, _) = LEDGER.split_once("instance I").unwrap();
+    assert!(text.starts_with(head), "{text}");
+
+    // The file now agrees with the graph.
+    let diff = axiograph_dsl::diff::diff_axi(LEDGER, text)?;
+    let changes: Vec<String> = diff.changes.iter().map(ToString::to_string).collect();
+    assert_eq!(changes, vec!["instance I: + member Payment: p3"]);
+
+    // Regenerating again is a no-op.
+    let again = regenerate_axi_from_pathdb(&db, "Payments", text, &select)?;
+    assert_eq!(&again.text, text);
+    Ok(())
+}
+
+#[test]
+fn regenerating_everything_keeps_anchored_comments() -> Result<()> {
+    let db = import(LEDGER)?;
+    let regen = regenerate_axi_from_pathdb(&db, "Payments", LEDGER, &[])?;
+    assert_eq!(
+        regen.replaced,
+        vec!["schema Ledger", "theory Reports", "instance I"]
+    );
+    for comment in [
+        "-- Payments ledger (hand-authored).",
+        "  -- Money movements.\n  object Payment\n",
+        "  object Status -- open | settled\n",
+        "-- Sample data.\ninstance I of Ledger:",
+    ] {
+        assert!(regen.text.contains(comment), "{comment}\n{}", regen.text);
+    }
+    assert!(axiograph_dsl::diff::diff_axi(LEDGER, &regen.text)?.is_empty());
+
+    let missing: DeclRef = "schema:Missing".parse().unwrap();
+    assert!(regenerate_axi_from_pathdb(&db, "Payments", LEDGER, &[missing]).is_err());
+    Ok(())
+}
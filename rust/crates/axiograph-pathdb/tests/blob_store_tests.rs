@@ -0,0 +1,68 @@
+use anyhow::Result;
+use axiograph_pathdb::axi_export::{
+    export_pathdb_to_axi_v1, export_pathdb_to_axi_v1_with_blobs, import_pathdb_from_axi_v1,
+};
+use axiograph_pathdb::blob_store::{blob_ref, MAX_BLOB_BYTES};
+use axiograph_pathdb::{PathDB, PathDbError};
+
+const THUMBNAIL: &[u8] = &[0x89, b'P', b'N', b'G', 0, 1, 2, 255];
+
+#[test]
+fn blobs_are_content_addressed_and_referenced_from_attributes() -> Result<()> {
+    let mut db = PathDB::new();
+    let part = db.add_entity("Part", vec![("name", "bracket")]);
+    let copy = db.add_entity("Part", vec![("name", "bracket-copy")]);
+    let reference = db.attach_blob(part, "thumbnail", THUMBNAIL)?;
+    assert_eq!(reference, blob_ref(THUMBNAIL));
+    assert_eq!(db.attach_blob(copy, "thumbnail", THUMBNAIL)?, reference);
+    assert_eq!(db.blobs().len(), 1);
+
+    assert_eq!(db.entity_blob(part, "thumbnail"), Some(THUMBNAIL));
+    assert_eq!(
+        db.get_entity(part).unwrap().attrs.get("thumbnail"),
+        Some(&reference)
+    );
+
+    let orphan = db.put_blob(b"unused")?;
+    assert_eq!(db.blob(&orphan), Some(&b"unused"[..]));
+    assert_eq!(db.gc_blobs(), 1);
+    assert_eq!(db.blob(&orphan), None);
+
+    let too_big = vec![0u8; MAX_BLOB_BYTES + 1];
+    assert!(matches!(
+        db.put_blob(&too_big),
+        Err(PathDbError::BlobTooLarge { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn blobs_are_optional_in_snapshots_and_exports() -> Result<()> {
+    let mut db = PathDB::new();
+    let part = db.add_entity("Part", vec![("name", "bracket")]);
+    let reference = db.attach_blob(part, "thumbnail", THUMBNAIL)?;
+
+    let full = PathDB::from_bytes(&db.to_bytes()?)?;
+    assert_eq!(full.entity_blob(part, "thumbnail"), Some(THUMBNAIL));
+
+    let mut slim = PathDB::from_bytes(&db.to_bytes_without_blobs()?)?;
+    assert_eq!(slim.entity_blob(part, "thumbnail"), None);
+    assert_eq!(slim.missing_blobs(), vec![reference.clone()]);
+    assert_eq!(slim.import_blobs(db.blobs()), 1);
+    assert!(slim.missing_blobs().is_empty());
+
+    let plain = export_pathdb_to_axi_v1(&db)?;
+    assert!(!plain.contains("blob_content"));
+    assert!(import_pathdb_from_axi_v1(&plain)?
+        .entity_blob(part, "thumbnail")
+        .is_none());
+
+    let with_blobs = export_pathdb_to_axi_v1_with_blobs(&db)?;
+    let imported = import_pathdb_from_axi_v1(&with_blobs)?;
+    assert_eq!(imported.entity_blob(part, "thumbnail"), Some(THUMBNAIL));
+    assert_eq!(export_pathdb_to_axi_v1_with_blobs(&imported)?, with_blobs);
+
+    let tampered = with_blobs.replace("BlobHex_89504e47", "BlobHex_89504e48");
+    assert!(import_pathdb_from_axi_v1(&tampered).is_err());
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use axiograph_pathdb::bloom::{BloomFilter, TARGET_FALSE_POSITIVE_RATE};
+use axiograph_pathdb::PathDB;
+
+/// `n` nodes with `link` edges `i -> (i * 31 + 7) % n`.
+fn linked(n: u32) -> PathDB {
+    let mut db = PathDB::new();
+    for i in 0..n {
+        db.add_entity("Node", vec![("name", format!("n{i}").as_str())]);
+    }
+    for i in 0..n {
+        db.add_relation("link", i, (i * 31 + 7) % n, 0.8, vec![]);
+    }
+    db
+}
+
+#[test]
+fn filter_has_no_false_negatives_and_few_false_positives() {
+    let mut filter = BloomFilter::with_capacity(10_000);
+    for key in 0..10_000u64 {
+        filter.insert(key * 3);
+    }
+    assert!((0..10_000u64).all(|key| filter.may_contain(key * 3)));
+    let false_positives = (0..10_000u64)
+        .filter(|key| filter.may_contain(key * 3 + 1))
+        .count();
+    assert!(
+        (false_positives as f64) < 10_000.0 * TARGET_FALSE_POSITIVE_RATE * 2.0,
+        "{false_positives}"
+    );
+}
+
+#[test]
+fn edge_checks_agree_with_the_forward_index() {
+    let n = 2_000;
+    let db = linked(n);
+    let rel = db.interner.id_of("link").unwrap();
+    for source in (0..n).step_by(7) {
+        let target = (source * 31 + 7) % n;
+        assert!(db.relations.has_edge(source, rel, target));
+        assert!(db.relations.edge_relation_id(source, rel, target).is_some());
+        assert!(db
+            .relations
+            .has_edge_with_min_confidence(source, rel, target, 0.5));
+        assert!(!db
+            .relations
+            .has_edge_with_min_confidence(source, rel, target, 0.9));
+        let missing = (target + 1) % n;
+        assert!(!db.relations.has_edge(source, rel, missing));
+        assert!(db
+            .relations
+            .edge_relation_id(source, rel, missing)
+            .is_none());
+    }
+    // Endpoints with no edges at all.
+    assert!(!db.relations.has_edge(n + 5, rel, 0));
+    let filter = db.relations.edge_filter(rel).expect("link filter");
+    assert_eq!(filter.len(), n as usize);
+    assert!(filter.capacity() >= filter.len());
+}
+
+#[test]
+fn filters_survive_growth_load_and_removal() {
+    let mut db = linked(100);
+    let rel = db.interner.id_of("link").unwrap();
+    // Grows past several regrowths.
+    for i in 0..1_000 {
+        db.add_relation("link", i % 100, (i * 13) % 100, 1.0, vec![]);
+    }
+    assert!(db.relations.has_edge(5, rel, 65));
+    assert_eq!(db.relations.edge_filter(rel).unwrap().len(), 1_100);
+
+    let loaded = PathDB::from_bytes(&db.to_bytes().unwrap()).unwrap();
+    assert!(loaded.relations.has_edge(5, rel, 65));
+    assert!(loaded.relations.has_edge(0, rel, 7));
+
+    db.remove_relation(0, "link", 7);
+    assert!(!db.relations.has_edge(0, rel, 7));
+    assert!(db.relations.has_edge(5, rel, 65));
+}
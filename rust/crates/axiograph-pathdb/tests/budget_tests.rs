@@ -0,0 +1,160 @@
+use axiograph_pathdb::budget::{BudgetExceeded, CancelToken, QueryBudget, QueryStatus};
+use axiograph_pathdb::{PathDB, PathQuery};
+
+/// Complete directed graph on `n` nodes: `find_paths` explores a lot of it.
+fn dense_graph(n: usize) -> (PathDB, Vec<u32>) {
+    let mut db = PathDB::new();
+    let ids: Vec<u32> = (0..n)
+        .map(|i| db.add_entity("Node", vec![("name", format!("n{i}").as_str())]))
+        .collect();
+    for &a in &ids {
+        for &b in &ids {
+            if a != b {
+                db.add_relation("edge", a, b, 1.0, vec![]);
+            }
+        }
+    }
+    db.build_indexes();
+    (db, ids)
+}
+
+#[test]
+fn unlimited_budget_matches_plain_execution() {
+    let (db, ids) = dense_graph(6);
+    let query = PathQuery::FindPaths {
+        from: ids[0],
+        to: ids[5],
+        max_depth: 3,
+    };
+    let out = db.execute_with_budget(&query, &QueryBudget::unlimited());
+    assert!(out.is_complete());
+    assert_eq!(out.value, db.execute(&query));
+
+    let paths = db.find_paths_with_budget(ids[0], ids[5], 3, &QueryBudget::unlimited());
+    assert!(paths.is_complete());
+    assert_eq!(paths.value, db.find_paths(ids[0], ids[5], 3));
+}
+
+#[test]
+fn visit_limit_stops_find_paths_with_partial_results() {
+    let (db, ids) = dense_graph(40);
+    let full = db.find_paths(ids[0], ids[39], 6);
+    // `find_paths` charges every edge it follows: the first node alone
+    // costs 40 of these.
+    let budget = QueryBudget::unlimited().with_max_visited(60);
+    let out = db.find_paths_with_budget(ids[0], ids[39], 6, &budget);
+
+    assert_eq!(
+        out.status,
+        QueryStatus::BudgetExceeded {
+            exceeded: BudgetExceeded::MaxVisited { max_visited: 60 }
+        }
+    );
+    assert_eq!(out.visited, 60);
+    assert!(!out.value.is_empty());
+    assert!(out.value.iter().all(|p| full.contains(p)));
+}
+
+#[test]
+fn visit_limit_trips_inside_one_nodes_expansion() {
+    // One wide node whose last edge reaches the goal: the budget has to stop
+    // the scan of its edges, not wait for the next node.
+    let mut db = PathDB::new();
+    let hub = db.add_entity("Node", vec![("name", "hub")]);
+    let leaves: Vec<u32> = (0..100)
+        .map(|i| db.add_entity("Node", vec![("name", format!("leaf{i}").as_str())]))
+        .collect();
+    for &leaf in &leaves {
+        db.add_relation("edge", hub, leaf, 1.0, vec![]);
+    }
+
+    let budget = QueryBudget::unlimited().with_max_visited(5);
+    let out = db.find_paths_with_budget(hub, leaves[99], 1, &budget);
+    assert!(out.value.is_empty());
+    assert_eq!(out.visited, 5);
+    assert_eq!(
+        out.status,
+        QueryStatus::BudgetExceeded {
+            exceeded: BudgetExceeded::MaxVisited { max_visited: 5 }
+        }
+    );
+
+    let out = db.find_paths_with_budget(hub, leaves[99], 1, &QueryBudget::unlimited());
+    assert_eq!(out.value.len(), 1);
+}
+
+#[test]
+fn cancelled_query_returns_immediately() {
+    let (db, ids) = dense_graph(20);
+    let token = CancelToken::new();
+    token.cancel();
+    let budget = QueryBudget::unlimited().with_cancel_token(token);
+    let out = db.execute_with_budget(
+        &PathQuery::FindPaths {
+            from: ids[0],
+            to: ids[19],
+            max_depth: 8,
+        },
+        &budget,
+    );
+    assert_eq!(
+        out.status,
+        QueryStatus::BudgetExceeded {
+            exceeded: BudgetExceeded::Cancelled
+        }
+    );
+    assert_eq!(out.visited, 0);
+    assert!(out.value.is_empty());
+}
+
+#[test]
+fn timeout_is_reported() {
+    let (db, ids) = dense_graph(60);
+    let budget = QueryBudget::unlimited().with_max_ms(0);
+    let out = db.find_paths_with_budget(ids[0], ids[59], 10, &budget);
+    assert!(matches!(
+        out.status,
+        QueryStatus::BudgetExceeded {
+            exceeded: BudgetExceeded::Timeout { max_ms: 0 }
+        }
+    ));
+}
+
+#[test]
+fn partial_follow_path_never_reports_intermediate_hops() {
+    let (db, ids) = dense_graph(10);
+    let query = PathQuery::WithConfidence {
+        base: Box::new(PathQuery::FollowPath {
+            start: ids[0],
+            path: vec!["edge".to_string(), "edge".to_string()],
+        }),
+        min_confidence: 0.5,
+    };
+    let full = db.execute(&query);
+
+    // One hop worth of budget: the second hop never completes.
+    let out = db.execute_with_budget(&query, &QueryBudget::unlimited().with_max_visited(1));
+    assert!(!out.is_complete());
+    assert!(out.value.is_empty());
+
+    // Enough for the first hop and part of the second: a strict subset.
+    let out = db.execute_with_budget(&query, &QueryBudget::unlimited().with_max_visited(3));
+    assert!(!out.is_complete());
+    assert!(!out.value.is_empty());
+    assert!(out.value.is_subset(&full));
+}
+
+#[test]
+fn status_serializes_with_reason() {
+    let status = QueryStatus::BudgetExceeded {
+        exceeded: BudgetExceeded::MaxVisited { max_visited: 10 },
+    };
+    let json = serde_json::to_value(status).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "status": "budget_exceeded",
+            "exceeded": { "reason": "max_visited", "max_visited": 10 }
+        })
+    );
+}
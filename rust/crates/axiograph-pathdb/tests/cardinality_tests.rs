@@ -0,0 +1,96 @@
+use axiograph_pathdb::cardinality::{Direction, HyperLogLog};
+use axiograph_pathdb::{PathDB, PathQuery};
+
+fn within(estimate: u64, exact: u64, tolerance: f64) -> bool {
+    (estimate as f64 - exact as f64).abs() <= exact as f64 * tolerance
+}
+
+/// `people` persons, each owning `per_person` books.
+fn library(people: u32, per_person: u32) -> PathDB {
+    let mut db = PathDB::new();
+    for p in 0..people {
+        let person = db.add_entity("Person", vec![("name", format!("p{p}").as_str())]);
+        for b in 0..per_person {
+            let book = db.add_entity("Book", vec![("title", format!("b{p}_{b}").as_str())]);
+            db.add_relation("owns", person, book, 1.0, vec![]);
+            db.add_relation("ownedBy", book, person, 1.0, vec![]);
+        }
+    }
+    db.build_indexes();
+    db
+}
+
+#[test]
+fn sketch_estimates_distinct_counts() {
+    for n in [0u32, 1, 10, 500, 5_000, 100_000] {
+        let mut hll = HyperLogLog::default();
+        for id in 0..n {
+            hll.insert(id);
+            // Duplicates do not count.
+            hll.insert(id);
+        }
+        assert!(
+            within(hll.estimate(), u64::from(n), 0.06),
+            "{n}: {}",
+            hll.estimate()
+        );
+    }
+
+    let mut evens = HyperLogLog::default();
+    let mut odds = HyperLogLog::default();
+    (0..20_000u32).step_by(2).for_each(|id| evens.insert(id));
+    (1..20_000u32).step_by(2).for_each(|id| odds.insert(id));
+    assert!(evens.merge(&odds));
+    assert!(within(evens.estimate(), 20_000, 0.06));
+    assert!(!evens.merge(&HyperLogLog::new(12)));
+}
+
+#[test]
+fn type_and_fanout_estimates() {
+    let db = library(2_000, 3);
+    assert!(within(db.estimate_type_count("Person"), 2_000, 0.06));
+    assert!(within(db.estimate_type_count("Book"), 6_000, 0.06));
+    assert_eq!(db.estimate_type_count("Nope"), 0);
+
+    assert!(within(
+        db.estimate_endpoint_count("owns", Direction::Forward),
+        2_000,
+        0.06
+    ));
+    let fanout = db.estimate_fanout("owns", Direction::Forward);
+    assert!((fanout - 3.0).abs() < 0.3, "{fanout}");
+    assert!((db.estimate_fanout("owns", Direction::Backward) - 1.0).abs() < 0.1);
+    assert_eq!(db.estimate_fanout("nope", Direction::Forward), 0.0);
+
+    // owns then ownedBy: 3 books, each back to the one owner.
+    let round_trip = PathQuery::FollowPath {
+        start: 0,
+        path: vec!["owns".to_string(), "ownedBy".to_string()],
+    };
+    assert_eq!(db.estimate_query(&round_trip), 3);
+    let persons = PathQuery::SelectByType("Person".to_string());
+    let books = PathQuery::SelectByType("Book".to_string());
+    let join = PathQuery::Join(Box::new(persons.clone()), Box::new(books.clone()));
+    assert_eq!(db.estimate_query(&join), db.estimate_type_count("Person"));
+    let union = PathQuery::Union(Box::new(persons), Box::new(books));
+    assert!(db.estimate_query(&union) <= db.entities.len() as u64);
+}
+
+#[test]
+fn sketches_track_mutations() {
+    let mut db = library(10, 1);
+    assert_eq!(db.estimate_type_count("Person"), 10);
+
+    // Maintained incrementally once built.
+    let extra = db.add_entity("Person", vec![("name", "late")]);
+    assert_eq!(db.estimate_type_count("Person"), 11);
+    db.mark_virtual_type(extra, "Agent").unwrap();
+    assert_eq!(db.estimate_type_count("Agent"), 1);
+    let book = db.add_entity("Book", vec![]);
+    db.add_relation("owns", extra, book, 1.0, vec![]);
+    assert_eq!(db.estimate_endpoint_count("owns", Direction::Forward), 11);
+
+    // Removals rebuild from scratch.
+    db.remove_relation(extra, "owns", book);
+    assert_eq!(db.estimate_endpoint_count("owns", Direction::Forward), 10);
+}
@@ -0,0 +1,105 @@
+use axiograph_pathdb::collapse::ATTR_MERGED_COUNT;
+use axiograph_pathdb::{CollapseConfig, ConfidenceCombiner, FixedPointProbability, PathDB};
+
+/// Three ingest runs of `a -uses-> b` (one with a different source), one
+/// `a -uses-> c` and two `b -uses-> c`.
+fn db() -> (PathDB, [u32; 3]) {
+    let mut db = PathDB::new();
+    let a = db.add_entity("Tool", vec![("name", "a")]);
+    let b = db.add_entity("Tool", vec![("name", "b")]);
+    let c = db.add_entity("Tool", vec![("name", "c")]);
+    db.add_relation("uses", a, b, 0.5, vec![("source", "run1")]); // 0
+    db.add_relation("uses", a, c, 0.9, vec![]); // 1
+    db.add_relation("uses", a, b, 0.5, vec![("source", "run2")]); // 2
+    db.add_relation("uses", b, c, 0.6, vec![("source", "run1")]); // 3
+    db.add_relation("uses", a, b, 0.5, vec![("source", "run1")]); // 4
+    db.add_relation("uses", b, c, 0.7, vec![("source", "run2")]); // 5
+    db.build_indexes();
+    (db, [a, b, c])
+}
+
+#[test]
+fn parallel_edges_merge_with_combined_confidence_and_provenance() {
+    let (mut db, [a, b, c]) = db();
+    let collapse = db.collapse_duplicate_relations(&CollapseConfig::default());
+
+    assert_eq!(collapse.removed, 3);
+    assert_eq!(db.relations.len(), 3);
+    assert_eq!(collapse.proof.relation_id_map, vec![0, 1, 0, 2, 0, 2]);
+    assert!(collapse.proof.check().is_ok());
+
+    let ab = &collapse.edges[0];
+    assert_eq!((ab.source, ab.target, ab.relation_id), (a, b, 0));
+    assert_eq!(
+        ab.provenance
+            .iter()
+            .map(|p| p.relation_id)
+            .collect::<Vec<_>>(),
+        vec![0, 2, 4]
+    );
+    // noisy-or of three 0.5s.
+    assert!((ab.confidence - 0.875).abs() < 1e-6);
+
+    let merged = db.relations.get_relation(0).unwrap();
+    assert_eq!(merged.confidence, ab.confidence);
+    let attrs: Vec<(String, String)> = merged
+        .attrs
+        .iter()
+        .map(|&(k, v)| {
+            (
+                db.interner.lookup(k).unwrap(),
+                db.interner.lookup(v).unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        attrs,
+        vec![
+            ("source".to_string(), "run1".to_string()),
+            ("source".to_string(), "run2".to_string()),
+            (ATTR_MERGED_COUNT.to_string(), "3".to_string()),
+        ]
+    );
+    // Indexes follow the compacted ids.
+    assert_eq!(
+        db.follow_one(a, "uses").iter().collect::<Vec<_>>(),
+        vec![b, c]
+    );
+    assert_eq!(db.follow_one(b, "uses").iter().collect::<Vec<_>>(), vec![c]);
+
+    // Collapsing again is a no-op.
+    let again = db.collapse_duplicate_relations(&CollapseConfig::default());
+    assert_eq!((again.removed, again.edges.len()), (0, 0));
+}
+
+#[test]
+fn the_rewrite_proof_replays_and_rejects_tampering() {
+    let (mut graph, _) = db();
+    let config = CollapseConfig {
+        combiner: ConfidenceCombiner::Min,
+        relations: vec!["uses".to_string()],
+    };
+    let collapse = graph.collapse_duplicate_relations(&config);
+    assert_eq!(collapse.edges[1].confidence, 0.6);
+
+    let json = serde_json::to_value(collapse.certificate()).unwrap();
+    assert_eq!(json["kind"], "relation_collapse_v1");
+    assert_eq!(json["proof"]["combiner"], "min");
+
+    let mut tampered = collapse.proof.clone();
+    tampered.edges[1].merged_confidence_fp = FixedPointProbability::from_f32(0.7);
+    assert!(tampered.check().unwrap_err().contains("does not replay"));
+
+    let mut tampered = collapse.proof.clone();
+    tampered.relation_id_map[1] = 2;
+    assert!(tampered.check().is_err());
+
+    // Relation filter: nothing of another type is touched.
+    let (mut db, _) = db();
+    let other = CollapseConfig {
+        relations: vec!["partOf".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(db.collapse_duplicate_relations(&other).removed, 0);
+    assert_eq!(db.relations.len(), 6);
+}
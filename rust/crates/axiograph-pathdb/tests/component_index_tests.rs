@@ -0,0 +1,46 @@
+use axiograph_pathdb::PathDB;
+
+fn person(db: &mut PathDB, name: &str) -> u32 {
+    db.add_entity("Person", vec![("name", name)])
+}
+
+#[test]
+fn same_component_is_scoped_to_relation_set() {
+    let mut db = PathDB::new();
+    let a = person(&mut db, "a");
+    let b = person(&mut db, "b");
+    let c = person(&mut db, "c");
+    db.add_relation("sameAs", a, b, 1.0, vec![]);
+    db.add_relation("knows", b, c, 1.0, vec![]);
+
+    assert!(db.same_component(a, b, &["sameAs"]));
+    // Direction is ignored.
+    assert!(db.same_component(b, a, &["sameAs"]));
+    assert!(!db.same_component(a, c, &["sameAs"]));
+    assert!(db.same_component(a, c, &["sameAs", "knows"]));
+    // Unknown relation names contribute no edges.
+    assert!(!db.same_component(a, b, &["missing"]));
+    assert!(db.same_component(a, a, &[]));
+}
+
+#[test]
+fn same_component_updates_incrementally_on_insert() {
+    let mut db = PathDB::new();
+    let a = person(&mut db, "a");
+    let b = person(&mut db, "b");
+    assert!(!db.same_component(a, b, &["sameAs"]));
+
+    // Entities added after the set was built start as singletons.
+    let c = person(&mut db, "c");
+    assert_eq!(db.component_size(c, &["sameAs"]), 1);
+
+    db.add_relation("sameAs", a, c, 1.0, vec![]);
+    db.add_relation("sameAs", c, b, 1.0, vec![]);
+    db.add_relation("knows", a, b, 1.0, vec![]);
+
+    assert!(db.same_component(a, b, &["sameAs"]));
+    assert_eq!(db.component_size(b, &["sameAs"]), 3);
+    // A set registered after the inserts is built from the store.
+    assert!(db.same_component(a, b, &["knows"]));
+    assert!(!db.same_component(a, c, &["knows"]));
+}
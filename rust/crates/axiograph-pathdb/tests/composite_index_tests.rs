@@ -0,0 +1,77 @@
+use anyhow::Result;
+use axiograph_pathdb::PathDB;
+use roaring::RoaringBitmap;
+
+#[test]
+fn composite_index_is_maintained_on_insert_and_upsert() -> Result<()> {
+    let mut db = PathDB::new();
+    let alice = db.add_entity("Person", vec![("name", "Alice")]);
+    let _robot = db.add_entity("Robot", vec![("name", "Alice")]);
+    assert!(db.composite_lookup("Person", "name", "Alice").is_none());
+
+    assert!(db.declare_composite_index("Person", "name"));
+    assert!(!db.declare_composite_index("Person", "name"));
+    assert_eq!(
+        db.composite_lookup("Person", "name", "Alice"),
+        Some(RoaringBitmap::from_iter([alice]))
+    );
+
+    let bob = db.add_entity("Person", vec![("name", "Bob")]);
+    let alice2 = db.add_entity("Person", vec![("name", "Alice")]);
+    assert_eq!(
+        db.entities_with_type_attr("Person", "name", "Alice"),
+        RoaringBitmap::from_iter([alice, alice2])
+    );
+
+    db.upsert_entity_attr(bob, "name", "Alice")?;
+    assert_eq!(
+        db.composite_lookup("Person", "name", "Alice"),
+        Some(RoaringBitmap::from_iter([alice, bob, alice2]))
+    );
+    assert_eq!(
+        db.composite_lookup("Person", "name", "Bob"),
+        Some(RoaringBitmap::new())
+    );
+
+    // Entities that join the type later are picked up too.
+    let robot2 = db.add_entity("Robot", vec![("name", "Alice")]);
+    db.mark_virtual_type(robot2, "Person")?;
+    assert!(db
+        .composite_lookup("Person", "name", "Alice")
+        .is_some_and(|ids| ids.contains(robot2)));
+    Ok(())
+}
+
+#[test]
+fn composite_index_covers_subtypes_and_survives_snapshots() -> Result<()> {
+    let mut db = PathDB::new();
+    db.declare_composite_index("Material", "grade");
+    let steel = db.add_entity("Steel", vec![("grade", "A")]);
+    let wood = db.add_entity("Material", vec![("grade", "A")]);
+    db.add_entity("Material", vec![("grade", "B")]);
+    db.declare_subtype("Steel", "Material");
+    let expected = RoaringBitmap::from_iter([steel, wood]);
+    assert_eq!(
+        db.composite_lookup("Material", "grade", "A"),
+        Some(expected.clone())
+    );
+
+    let loaded = PathDB::from_bytes(&db.to_bytes()?)?;
+    assert!(loaded.has_composite_index("Material", "grade"));
+    assert_eq!(
+        loaded.composite_index_names(),
+        vec![("Material".to_string(), "grade".to_string())]
+    );
+    assert_eq!(
+        loaded.composite_lookup("Material", "grade", "A"),
+        Some(expected.clone())
+    );
+
+    let mut overlay = loaded.overlay();
+    overlay.remove_entity(wood)?;
+    assert_eq!(
+        overlay.view()?.composite_lookup("Material", "grade", "A"),
+        Some(RoaringBitmap::from_iter([steel]))
+    );
+    Ok(())
+}
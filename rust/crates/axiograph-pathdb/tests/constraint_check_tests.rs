@@ -0,0 +1,181 @@
+use anyhow::Result;
+use axiograph_dsl::schema_v1::parse_constraint_v1;
+use axiograph_pathdb::{check_constraint_against_db, ConstraintCheck, PathDB, PathDbError};
+
+const MODULE: &str = r#"
+module ConstraintCheckTest
+
+schema S:
+  object Person
+  object Kind
+  relation ReportsTo(employee: Person, manager: Person)
+  relation Knows(from: Person, to: Person, kind: Kind)
+  relation Parent(child: Person, parent: Person)
+
+theory T on S:
+  constraint functional Parent.child -> Parent.parent
+
+instance I of S:
+  Person = {alice, bob, carol, dave}
+  Kind = {Friend, Colleague}
+  ReportsTo = {
+    (employee=alice, manager=bob),
+    (employee=alice, manager=carol),
+    (employee=bob, manager=carol)
+  }
+  Knows = {
+    (from=alice, to=bob, kind=Friend),
+    (from=alice, to=carol, kind=Colleague)
+  }
+  Parent = {
+    (child=alice, parent=bob),
+    (child=bob, parent=carol)
+  }
+"#;
+
+fn db() -> Result<PathDB> {
+    let m = axiograph_dsl::axi_v1::parse_axi_v1(MODULE)?;
+    let mut db = PathDB::new();
+    axiograph_pathdb::axi_module_import::import_axi_schema_v1_module_into_pathdb(&mut db, &m)?;
+    Ok(db)
+}
+
+fn check(db: &PathDB, constraint: &str, limit: usize) -> Result<ConstraintCheck> {
+    let constraint = parse_constraint_v1(constraint).map_err(anyhow::Error::msg)?;
+    Ok(check_constraint_against_db(db, "S", &constraint, limit)?)
+}
+
+fn id(db: &PathDB, name: &str) -> u32 {
+    let key = db.interner.id_of("name").unwrap();
+    let value = db.interner.id_of(name).unwrap();
+    db.entities
+        .entities_with_attr_value(key, value)
+        .min()
+        .unwrap()
+}
+
+#[test]
+fn direct_constraints_report_bounded_counterexamples() -> Result<()> {
+    let db = db()?;
+    let reports_to = db.fact_nodes_by_axi_schema_relation("S", "ReportsTo");
+
+    let ConstraintCheck::Violated {
+        counterexamples,
+        truncated,
+    } = check(
+        &db,
+        "functional ReportsTo.employee -> ReportsTo.manager",
+        10,
+    )?
+    else {
+        panic!("alice has two managers");
+    };
+    assert!(!truncated);
+    assert_eq!(counterexamples.len(), 1);
+    let cx = &counterexamples[0];
+    assert_eq!(cx.facts.len(), 2);
+    assert!(cx.facts.iter().all(|f| reports_to.contains(*f)));
+    assert!(cx
+        .tuples
+        .iter()
+        .all(|t| t[0] == ("employee".to_string(), id(&db, "alice"))));
+
+    // at_most 2 holds, at_most 1 does not.
+    assert!(check(&db, "at_most 2 ReportsTo.employee -> ReportsTo.manager", 10)?.is_satisfied());
+    let ConstraintCheck::Violated {
+        counterexamples, ..
+    } = check(&db, "at_most 1 ReportsTo.employee -> ReportsTo.manager", 10)?
+    else {
+        panic!("expected a violation");
+    };
+    assert_eq!(counterexamples[0].tuples.len(), 2);
+
+    // Two facts share a manager; the limit bounds the search.
+    let ConstraintCheck::Violated {
+        counterexamples,
+        truncated,
+    } = check(&db, "key ReportsTo(manager)", 0)?
+    else {
+        panic!("carol manages two employees");
+    };
+    assert!(counterexamples.is_empty());
+    assert!(truncated);
+
+    let ConstraintCheck::Satisfied(cert) = check(&db, "key ReportsTo(employee, manager)", 10)?
+    else {
+        panic!("tuples are distinct");
+    };
+    assert_eq!(
+        cert.constraint,
+        "constraint key ReportsTo(employee, manager)"
+    );
+    assert_eq!((cert.facts_checked, cert.tuples_checked), (3, 3));
+    assert_eq!(cert.entity_count, db.entities.len());
+    Ok(())
+}
+
+#[test]
+fn closure_constraints_are_checked_against_declared_functionals() -> Result<()> {
+    let db = db()?;
+
+    // alice -> bob -> carol closes to alice -> carol, but Parent is
+    // functional on child.
+    let ConstraintCheck::Violated {
+        counterexamples, ..
+    } = check(&db, "transitive Parent", 10)?
+    else {
+        panic!("closure breaks the functional dependency");
+    };
+    assert_eq!(counterexamples.len(), 1);
+    assert!(counterexamples[0]
+        .reason
+        .contains("functional Parent.child"));
+    // Both stored facts are evidence for the derived tuple.
+    assert_eq!(
+        counterexamples[0].facts,
+        db.fact_nodes_by_axi_schema_relation("S", "Parent")
+            .iter()
+            .collect::<Vec<_>>()
+    );
+
+    // Symmetry adds bob -> alice and carol -> bob: bob gets two parents.
+    assert!(!check(&db, "symmetric Parent", 10)?.is_satisfied());
+
+    // No declared key/functional on Knows, so symmetry is compatible.
+    let ConstraintCheck::Satisfied(cert) =
+        check(&db, "symmetric Knows where Knows.kind in {Friend}", 10)?
+    else {
+        panic!("nothing to break");
+    };
+    assert_eq!(cert.tuples_checked, 3);
+    assert!(cert.against.is_empty());
+    Ok(())
+}
+
+#[test]
+fn unknown_fields_and_non_executable_constraints_are_rejected() -> Result<()> {
+    let db = db()?;
+    assert!(matches!(
+        check(&db, "key ReportsTo(boss)", 10)
+            .unwrap_err()
+            .downcast::<PathDbError>(),
+        Ok(PathDbError::InvalidConstraint { .. })
+    ));
+    assert!(matches!(
+        check(&db, "key Missing(a)", 10)
+            .unwrap_err()
+            .downcast::<PathDbError>(),
+        Ok(PathDbError::InvalidConstraint { .. })
+    ));
+    assert!(matches!(
+        check(
+            &db,
+            "typing ReportsTo: preserves_manifold_and_increments_degree",
+            10
+        )
+        .unwrap_err()
+        .downcast::<PathDbError>(),
+        Ok(PathDbError::Unsupported(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,255 @@
+use anyhow::Result;
+use axiograph_pathdb::axi_meta::REL_AXI_CONTEXT_INHERITS;
+use axiograph_pathdb::proof_mode::WithProof;
+use axiograph_pathdb::{
+    ContextFact, FactOverride, PathDB, PathDbError, PathQuery, QueryContext, QueryExecutionEvent,
+};
+use roaring::RoaringBitmap;
+
+const MODULE: &str = r#"
+module ContextScopeTest
+
+schema S:
+  object Node
+  object Context
+  relation Flow(from: Node, to: Node) @context Context
+
+instance I of S:
+  Node = {a, b, c, d}
+  Context = {Accepted, Evidence, Draft}
+  Flow = {
+    (from=a, to=b, ctx=Accepted),
+    (from=a, to=c, ctx=Evidence),
+    (from=b, to=d, ctx=Draft)
+  }
+"#;
+
+fn entity_id_by_name(db: &PathDB, name: &str) -> Result<u32> {
+    let key = db
+        .interner
+        .id_of("name")
+        .ok_or_else(|| anyhow::anyhow!("missing `name` attr key in interner"))?;
+    let value = db
+        .interner
+        .id_of(name)
+        .ok_or_else(|| anyhow::anyhow!("missing `{name}` value in interner"))?;
+    db.entities
+        .entities_with_attr_value(key, value)
+        .iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("no entity with name `{name}`"))
+}
+
+fn import() -> Result<PathDB> {
+    let m = axiograph_dsl::axi_v1::parse_axi_v1(MODULE)?;
+    let mut db = PathDB::new();
+    axiograph_pathdb::axi_module_import::import_axi_schema_v1_module_into_pathdb(&mut db, &m)?;
+    Ok(db)
+}
+
+fn ids(db: &PathDB, names: &[&str]) -> Result<RoaringBitmap> {
+    names.iter().map(|n| entity_id_by_name(db, n)).collect()
+}
+
+#[test]
+fn follow_path_only_sees_facts_asserted_in_the_world() -> Result<()> {
+    let mut db = import()?;
+    let [a, accepted, evidence, draft] =
+        ["a", "Accepted", "Evidence", "Draft"].map(|n| entity_id_by_name(&db, n).unwrap());
+    // Draft inherits everything accepted.
+    db.add_relation(REL_AXI_CONTEXT_INHERITS, draft, accepted, 1.0, Vec::new());
+    db.build_indexes();
+
+    assert_eq!(db.follow_path(a, &["Flow"]), ids(&db, &["b", "c"])?);
+    assert_eq!(
+        db.follow_path_in(a, &["Flow"], &QueryContext::default()),
+        ids(&db, &["b", "c"])?
+    );
+    assert_eq!(
+        db.follow_path_in(a, &["Flow"], &QueryContext::world(accepted)),
+        ids(&db, &["b"])?
+    );
+    assert_eq!(
+        db.follow_path_in(a, &["Flow"], &QueryContext::world(evidence)),
+        ids(&db, &["c"])?
+    );
+    // a -Flow-> b comes from Accepted, b -Flow-> d from Draft itself.
+    assert_eq!(
+        db.follow_path_in(a, &["Flow", "Flow"], &QueryContext::world(draft)),
+        ids(&db, &["d"])?
+    );
+    assert!(db
+        .follow_path_in(a, &["Flow", "Flow"], &QueryContext::world(accepted))
+        .is_empty());
+
+    // Inheritance is one-way.
+    assert_eq!(db.context_closure(draft), ids(&db, &["Draft", "Accepted"])?);
+    assert_eq!(db.context_closure(accepted), ids(&db, &["Accepted"])?);
+    Ok(())
+}
+
+#[test]
+fn type_selection_and_execute_are_scoped_by_query_context() -> Result<()> {
+    let db = import()?;
+    let accepted = entity_id_by_name(&db, "Accepted")?;
+    let evidence = entity_id_by_name(&db, "Evidence")?;
+
+    assert_eq!(
+        db.find_by_type_in("Node", &QueryContext::default()).len(),
+        4
+    );
+    assert_eq!(
+        db.find_by_type_in("Node", &QueryContext::world(evidence)),
+        ids(&db, &["a", "c"])?
+    );
+
+    let query = PathQuery::Union(
+        Box::new(PathQuery::SelectByType("Node".to_string())),
+        Box::new(PathQuery::SelectRelated(
+            entity_id_by_name(&db, "a")?,
+            "Flow".to_string(),
+        )),
+    );
+    assert_eq!(db.execute(&query).len(), 4);
+    assert_eq!(
+        db.execute_in(&query, &QueryContext::world(accepted)),
+        ids(&db, &["a", "b"])?
+    );
+
+    let proved = db.execute_with_mode_in::<WithProof>(&query, &QueryContext::world(accepted));
+    assert_eq!(proved.value, ids(&db, &["a", "b"])?);
+    assert_eq!(
+        proved.proof.first(),
+        Some(&QueryExecutionEvent::InContext { world: accepted })
+    );
+    Ok(())
+}
+
+const PRICES: &str = r#"
+module ContextLatticeTest
+
+schema S:
+  object Node
+  object Context
+  relation Price(item: Node, value: Node) @context Context
+
+theory T on S:
+  constraint key Price(item, ctx)
+
+instance I of S:
+  Node = {widget, v1, v2, v3}
+  Context = {Base}
+  Price = {
+    (item=widget, value=v1, ctx=Base)
+  }
+"#;
+
+#[test]
+fn child_contexts_override_keyed_facts_and_unrelated_contexts_contradict() -> Result<()> {
+    let m = axiograph_dsl::axi_v1::parse_axi_v1(PRICES)?;
+    let mut db = PathDB::new();
+    axiograph_pathdb::axi_module_import::import_axi_schema_v1_module_into_pathdb(&mut db, &m)?;
+    let [widget, v2, v3, base] =
+        ["widget", "v2", "v3", "Base"].map(|n| entity_id_by_name(&db, n).unwrap());
+    let base_fact = db.fact_nodes_by_context(base).min().unwrap();
+
+    let child = db.add_context("Child", Some(base))?;
+    let child_fact = db.assert_fact_in_context(
+        Some(child),
+        "S",
+        "Price",
+        &[("item", widget), ("value", v2)],
+    )?;
+    assert_eq!(
+        db.assert_fact_in_context(
+            Some(child),
+            "S",
+            "Price",
+            &[("item", widget), ("value", v2)]
+        )?,
+        child_fact
+    );
+    db.build_indexes();
+
+    let effective = db.effective_facts(child);
+    assert!(effective.facts.contains(child_fact));
+    assert!(!effective.facts.contains(base_fact));
+    assert_eq!(
+        effective.overrides,
+        vec![FactOverride {
+            overridden: ContextFact {
+                fact: base_fact,
+                context: base,
+            },
+            by: ContextFact {
+                fact: child_fact,
+                context: child,
+            },
+        }]
+    );
+    assert_eq!(
+        db.follow_path_in(widget, &["Price"], &QueryContext::world(child)),
+        ids(&db, &["v2"])?
+    );
+    assert_eq!(
+        db.follow_path_in(widget, &["Price"], &QueryContext::world(base)),
+        ids(&db, &["v1"])?
+    );
+
+    // Overriding a parent is not a contradiction; disagreeing siblings are.
+    assert!(db.context_contradictions().is_empty());
+    let sibling = db.add_context("Sibling", Some(base))?;
+    let sibling_fact = db.assert_fact_in_context(
+        Some(sibling),
+        "S",
+        "Price",
+        &[("item", widget), ("value", v3)],
+    )?;
+    let contradictions = db.context_contradictions();
+    assert_eq!(contradictions.len(), 1);
+    assert_eq!(contradictions[0].relation, "Price");
+    assert_eq!(contradictions[0].key, vec![("item".to_string(), widget)]);
+    assert_eq!(
+        [contradictions[0].left.fact, contradictions[0].right.fact],
+        [child_fact, sibling_fact]
+    );
+    Ok(())
+}
+
+#[test]
+fn context_lattice_has_a_default_root_and_rejects_cycles() -> Result<()> {
+    let mut db = PathDB::new();
+    let a = db.add_entity("Node", vec![("name", "a")]);
+    let b = db.add_entity("Node", vec![("name", "b")]);
+
+    let parent = db.add_context("Parent", None)?;
+    let default = db.default_context().expect("created by add_context");
+    assert_eq!(db.context_by_name("Parent"), Some(parent));
+    assert_eq!(
+        db.context_parents(parent),
+        RoaringBitmap::from_iter([default])
+    );
+    let child = db.add_context("Child", Some(parent))?;
+
+    // Facts asserted without a context land in the default world and are
+    // inherited by every context below it.
+    db.assert_fact_in_context(None, "S", "Edge", &[("from", a), ("to", b)])?;
+    for world in [default, parent, child] {
+        assert_eq!(
+            db.follow_path_in(a, &["Edge"], &QueryContext::world(world)),
+            RoaringBitmap::from_iter([b])
+        );
+    }
+
+    assert!(matches!(
+        db.add_context_parent(default, child),
+        Err(PathDbError::ContextCycle { .. })
+    ));
+    assert!(matches!(
+        db.add_context_parent(child, child),
+        Err(PathDbError::ContextCycle { .. })
+    ));
+    db.add_context_parent(child, default)?;
+    assert_eq!(db.context_parents(child).len(), 2);
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use axiograph_pathdb::csr::AdjacencyLayout;
+use axiograph_pathdb::{PathDB, PathQuery};
+use roaring::RoaringBitmap;
+
+/// A small graph with parallel edges, self-loops, several relation types per
+/// entity and one hub over the supernode threshold.
+fn graph() -> PathDB {
+    let mut db = PathDB::new();
+    db.set_supernode_threshold(8);
+    for i in 0..40 {
+        db.add_entity("Node", vec![("name", format!("n{i}").as_str())]);
+    }
+    for i in 0..40u32 {
+        db.add_relation("next", i, (i + 1) % 40, 0.9, vec![]);
+        db.add_relation("skip", i, (i * 7) % 40, 0.4, vec![]);
+        db.add_relation("hub", i, 0, 1.0, vec![]);
+    }
+    db.add_relation("next", 3, 4, 0.2, vec![]);
+    db.add_relation("next", 5, 5, 1.0, vec![]);
+    db.build_indexes();
+    db
+}
+
+fn snapshot_of(db: &PathDB) -> Vec<String> {
+    let mut lines = Vec::new();
+    for e in 0..db.entities.len() as u32 {
+        for rel in ["next", "skip", "hub", "missing"] {
+            let id = db.interner.id_of(rel);
+            let follow = db.follow_one(e, rel);
+            let (sources, outgoing, incoming) = match id {
+                Some(id) => (
+                    db.relations.sources(e, id),
+                    db.relations
+                        .outgoing(e, id)
+                        .iter()
+                        .map(|r| (r.target, r.confidence.to_bits()))
+                        .collect::<Vec<_>>(),
+                    db.relations.incoming(e, id).len(),
+                ),
+                None => (RoaringBitmap::new(), Vec::new(), 0),
+            };
+            lines.push(format!(
+                "{e} {rel} {follow:?} {sources:?} {outgoing:?} {incoming}"
+            ));
+        }
+        let any: Vec<_> = db
+            .relations
+            .outgoing_any(e)
+            .iter()
+            .map(|r| (r.target, r.rel_type))
+            .collect();
+        lines.push(format!(
+            "{e} any {any:?} {}",
+            db.relations.incoming_any(e).len()
+        ));
+    }
+    lines
+}
+
+#[test]
+fn frozen_adjacency_answers_like_the_mutable_layout() {
+    let mut db = graph();
+    assert_eq!(db.adjacency_layout(), AdjacencyLayout::Mutable);
+    let before = snapshot_of(&db);
+    let bytes = db.to_bytes().unwrap();
+    let path = PathQuery::WithConfidence {
+        base: Box::new(PathQuery::FollowPath {
+            start: 3,
+            path: vec!["next".to_string(), "skip".to_string(), "next".to_string()],
+        }),
+        min_confidence: 0.3,
+    };
+    let answer = db.execute(&path);
+
+    db.freeze_adjacency();
+    assert_eq!(db.adjacency_layout(), AdjacencyLayout::Csr);
+    assert_eq!(snapshot_of(&db), before);
+    assert_eq!(db.execute(&path), answer);
+    assert_eq!(db.find_paths(3, 9, 3), graph().find_paths(3, 9, 3));
+    let rel = db.interner.id_of("next").unwrap();
+    assert!(db.relations.has_edge(5, rel, 5));
+    assert_eq!(db.relations.outgoing_relation_ids(3, rel).len(), 2);
+
+    // Same snapshot bytes; loading gives the mutable layout back.
+    assert_eq!(db.to_bytes().unwrap(), bytes);
+    let loaded = PathDB::from_bytes(&bytes).unwrap();
+    assert_eq!(loaded.adjacency_layout(), AdjacencyLayout::Mutable);
+}
+
+#[test]
+fn appending_to_a_frozen_store_thaws_it() {
+    let mut db = graph();
+    db.freeze_adjacency();
+    db.add_relation("next", 10, 20, 1.0, vec![]);
+    assert_eq!(db.adjacency_layout(), AdjacencyLayout::Mutable);
+    assert!(db.follow_one(10, "next").contains(20));
+    assert!(db.follow_one(10, "next").contains(11));
+
+    db.freeze_adjacency();
+    assert_eq!(db.remove_relation(10, "next", 20), 1);
+    assert!(!db.follow_one(10, "next").contains(20));
+    assert_eq!(snapshot_of(&db), snapshot_of(&graph()));
+}
@@ -0,0 +1,89 @@
+use axiograph_pathdb::axi_export::export_pathdb_to_axi_v1;
+use axiograph_pathdb::PathDB;
+
+/// Build the same graph from scratch. Every call gets fresh hash seeds, so any
+/// iteration-order dependence shows up as differing bytes.
+fn build() -> PathDB {
+    let mut db = PathDB::new();
+    let mut ids = Vec::new();
+    for i in 0..40 {
+        let name = format!("n{i}");
+        let group = format!("g{}", i % 5);
+        let tier = format!("t{}", i % 3);
+        ids.push(db.add_entity(
+            if i % 2 == 0 { "Person" } else { "Team" },
+            vec![
+                ("name", name.as_str()),
+                ("group", group.as_str()),
+                ("tier", tier.as_str()),
+            ],
+        ));
+    }
+    for (i, &src) in ids.iter().enumerate() {
+        for step in [1, 3, 5] {
+            let dst = ids[(i + step) % ids.len()];
+            let rel = ["knows", "reportsTo", "worksWith"][step % 3];
+            db.add_relation(rel, src, dst, 0.5 + (step as f32) / 20.0, vec![]);
+        }
+    }
+    for i in (0..ids.len()).step_by(4) {
+        db.add_equivalence(ids[i], ids[(i + 2) % ids.len()], "sameAs");
+    }
+    db.build_indexes();
+    db
+}
+
+#[test]
+fn serialization_bytes_are_stable_across_builds() {
+    let first = build().to_bytes().unwrap();
+    for _ in 0..5 {
+        assert_eq!(build().to_bytes().unwrap(), first);
+    }
+}
+
+#[test]
+fn serialization_bytes_survive_round_trip() {
+    let bytes = build().to_bytes().unwrap();
+    let reloaded = PathDB::from_bytes(&bytes).unwrap();
+    assert_eq!(reloaded.to_bytes().unwrap(), bytes);
+}
+
+#[test]
+fn axi_export_is_stable_across_builds() {
+    let first = export_pathdb_to_axi_v1(&build()).unwrap();
+    for _ in 0..3 {
+        assert_eq!(export_pathdb_to_axi_v1(&build()).unwrap(), first);
+    }
+}
+
+#[test]
+fn entity_attrs_iterate_in_key_order() {
+    let db = build();
+    let view = db.get_entity(0).unwrap();
+    let keys: Vec<&str> = view.attrs.keys().map(String::as_str).collect();
+    assert_eq!(keys, vec!["group", "name", "tier"]);
+}
+
+#[test]
+fn outgoing_and_incoming_any_are_in_relation_id_order() {
+    let db = build();
+    for entity in [0, 5, 39] {
+        let out = db.relations.outgoing_any(entity);
+        assert_eq!(out.len(), 3);
+        let sources: Vec<u32> = out.iter().map(|r| r.source).collect();
+        assert!(sources.iter().all(|&s| s == entity));
+
+        let incoming = db.relations.incoming_any(entity);
+        assert_eq!(incoming.len(), 3);
+        assert!(incoming.iter().all(|r| r.target == entity));
+    }
+
+    // Insertion order for entity 0 is step 1, 3, 5.
+    let types: Vec<String> = db
+        .relations
+        .outgoing_any(0)
+        .iter()
+        .filter_map(|r| db.interner.lookup(r.rel_type))
+        .collect();
+    assert_eq!(types, vec!["reportsTo", "knows", "worksWith"]);
+}
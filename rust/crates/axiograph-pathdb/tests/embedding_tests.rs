@@ -0,0 +1,88 @@
+use axiograph_pathdb::{
+    embeddings_path_for_axpd, EmbeddingConfigV1, EmbeddingModelV1, KgEmbeddingsV1, PathDB,
+};
+
+/// Two disjoint "clusters" of suppliers/parts; each part in a cluster is
+/// supplied by every supplier in that cluster except for one held-out edge.
+fn clustered_db() -> (PathDB, (u32, u32)) {
+    let mut db = PathDB::new();
+    let mut held_out = None;
+    for cluster in 0..2 {
+        let suppliers: Vec<u32> = (0..3)
+            .map(|i| {
+                let name = format!("S{cluster}{i}");
+                db.add_entity("Supplier", vec![("name", name.as_str())])
+            })
+            .collect();
+        let parts: Vec<u32> = (0..3)
+            .map(|i| {
+                let name = format!("P{cluster}{i}");
+                db.add_entity("Part", vec![("name", name.as_str())])
+            })
+            .collect();
+        for (pi, &p) in parts.iter().enumerate() {
+            for (si, &s) in suppliers.iter().enumerate() {
+                if cluster == 0 && pi == 0 && si == 0 {
+                    held_out = Some((p, s));
+                    continue;
+                }
+                db.add_relation("supplied_by", p, s, 1.0, vec![]);
+            }
+        }
+    }
+    db.build_indexes();
+    (db, held_out.unwrap())
+}
+
+#[test]
+fn transe_training_is_deterministic_and_recovers_held_out_edge() {
+    let (db, (part, supplier)) = clustered_db();
+    let config = EmbeddingConfigV1::default();
+
+    let a = KgEmbeddingsV1::train(&db, &config).unwrap();
+    let b = KgEmbeddingsV1::train(&db, &config).unwrap();
+    assert_eq!(a.entity_vector(part), b.entity_vector(part));
+
+    let preds = a.predict_links(&db, "supplied_by", 1);
+    assert_eq!(preds.len(), 1);
+    assert_eq!((preds[0].source, preds[0].target), (part, supplier));
+    assert!(preds[0].confidence <= config.max_prediction_confidence);
+}
+
+#[test]
+fn rotate_predictions_exclude_existing_edges() {
+    let (db, (part, supplier)) = clustered_db();
+    let config = EmbeddingConfigV1 {
+        model: EmbeddingModelV1::RotatE,
+        dim: 16,
+        ..Default::default()
+    };
+    let emb = KgEmbeddingsV1::train(&db, &config).unwrap();
+
+    let preds = emb.predict_links(&db, "supplied_by", 100);
+    assert!(preds
+        .iter()
+        .any(|p| (p.source, p.target) == (part, supplier)));
+    let rel = db.interner.id_of("supplied_by").unwrap();
+    for p in &preds {
+        assert!(!db.relations.has_edge(p.source, rel, p.target));
+    }
+    assert!(emb.predict_links(&db, "unknown_rel", 5).is_empty());
+}
+
+#[test]
+fn embeddings_roundtrip_through_sidecar_file() {
+    let (db, (part, supplier)) = clustered_db();
+    let emb = KgEmbeddingsV1::train(&db, &EmbeddingConfigV1::default()).unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = embeddings_path_for_axpd(&dir.path().join("snapshot.axpd"));
+    assert!(path.to_string_lossy().ends_with("snapshot.axpd.emb.cbor"));
+    emb.write_file(&path).unwrap();
+
+    let loaded = KgEmbeddingsV1::read_file(&path).unwrap();
+    assert_eq!(
+        loaded.distance(part, "supplied_by", supplier),
+        emb.distance(part, "supplied_by", supplier)
+    );
+}
@@ -0,0 +1,114 @@
+use axiograph_pathdb::{PathDB, PathDbError};
+
+fn snapshot() -> Vec<u8> {
+    let mut db = PathDB::new();
+    let a = db.add_entity("Person", vec![("name", "a")]);
+    let b = db.add_entity("Person", vec![("name", "b")]);
+    db.add_relation("knows", a, b, 1.0, vec![]);
+    db.build_indexes();
+    db.to_bytes().unwrap()
+}
+
+#[test]
+fn bad_magic_is_invalid_format() {
+    let err = PathDB::from_bytes(b"NOPE\x01\0\0\0").err().unwrap();
+    assert!(matches!(err, PathDbError::InvalidFormat));
+    assert!(err.is_corrupt_input());
+}
+
+#[test]
+fn future_version_is_reported() {
+    let mut bytes = snapshot();
+    bytes[4..8].copy_from_slice(&2u32.to_le_bytes());
+    let err = PathDB::from_bytes(&bytes).err().unwrap();
+    assert!(matches!(err, PathDbError::UnsupportedVersion(2)));
+}
+
+#[test]
+fn truncated_file_names_the_section() {
+    let bytes = snapshot();
+    let err = PathDB::from_bytes(&bytes[..bytes.len() - 1]).err().unwrap();
+    match err {
+        PathDbError::Truncated { section } => assert_eq!(section, "db"),
+        other => panic!("expected Truncated, got {other:?}"),
+    }
+}
+
+#[test]
+fn unknown_entity_is_not_corrupt_input() {
+    let mut db = PathDB::new();
+    let err = db.upsert_entity_attr(7, "name", "x").unwrap_err();
+    assert!(matches!(err, PathDbError::UnknownEntity(7)));
+    assert!(!err.is_corrupt_input());
+
+    let err = db.mark_virtual_type(7, "Thing").unwrap_err();
+    assert!(matches!(err, PathDbError::UnknownEntity(7)));
+}
+
+#[test]
+fn namespace_mismatch_is_structured() {
+    let mut db = PathDB::new();
+    let a = db.add_entity_in("tenant_a", "Person", vec![("name", "a")]);
+    let b = db.add_entity_in("tenant_b", "Person", vec![("name", "b")]);
+    let err = db
+        .add_relation_in("tenant_a", "knows", a, b, 1.0, vec![])
+        .unwrap_err();
+    match err {
+        PathDbError::NamespaceMismatch {
+            entity,
+            expected,
+            actual,
+        } => {
+            assert_eq!(entity, b);
+            assert_eq!(expected, "tenant_a");
+            assert_eq!(actual.as_deref(), Some("tenant_b"));
+        }
+        other => panic!("expected NamespaceMismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn errors_survive_anyhow_for_binaries() {
+    let err: anyhow::Error = PathDB::from_bytes(b"junk").err().unwrap().into();
+    assert!(matches!(
+        err.downcast_ref::<PathDbError>(),
+        Some(PathDbError::InvalidFormat)
+    ));
+}
+
+#[test]
+fn bad_axi_export_is_corrupt_input() {
+    let mut db = PathDB::new();
+    db.add_entity("Person", vec![("name", "a")]);
+    let text = axiograph_pathdb::axi_export::export_pathdb_to_axi_v1(&db).unwrap();
+    let broken = text.replacen("StringId_0", "StringId_x", 1);
+    let err = axiograph_pathdb::axi_export::import_pathdb_from_axi_v1(&broken)
+        .err()
+        .unwrap();
+    assert!(matches!(err, PathDbError::InvalidExport(_)), "{err:?}");
+    assert!(err.is_corrupt_input());
+}
+
+#[test]
+fn mismatched_morphisms_do_not_compose() {
+    let morphism = |source: &str, target: &str| axiograph_pathdb::SchemaMorphismV1 {
+        source_schema: source.to_string(),
+        target_schema: target.to_string(),
+        objects: Vec::new(),
+        arrows: Vec::new(),
+    };
+    let err = morphism("S0", "S1")
+        .then(&morphism("S2", "S3"))
+        .unwrap_err();
+    assert!(matches!(err, PathDbError::MorphismMismatch(_)));
+    assert!(!err.is_corrupt_input());
+}
+
+#[test]
+fn garbage_embeddings_file_is_corrupt_input() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("db.axpd.emb.cbor");
+    std::fs::write(&path, b"not cbor").unwrap();
+    let err = axiograph_pathdb::embedding::KgEmbeddingsV1::read_file(&path).unwrap_err();
+    assert!(err.is_corrupt_input(), "{err:?}");
+}
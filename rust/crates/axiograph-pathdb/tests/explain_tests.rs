@@ -0,0 +1,133 @@
+use axiograph_pathdb::inference::{Rule, RuleSet};
+use axiograph_pathdb::{ExplanationStep, PathDB, PathQuery};
+
+fn edge(step: &ExplanationStep) -> (u32, &str, u32) {
+    match step {
+        ExplanationStep::Edge(e) => (e.source, e.rel_type.as_str(), e.target),
+        other => panic!("expected an edge, got {other:?}"),
+    }
+}
+
+#[test]
+fn explanation_follows_paths_into_rule_premises() {
+    let mut db = PathDB::new();
+    let ann = db.add_entity("Person", vec![("name", "ann")]);
+    let bob = db.add_entity("Person", vec![("name", "bob")]);
+    let cat = db.add_entity("Person", vec![("name", "cat")]);
+    db.add_relation(
+        "Parent",
+        ann,
+        bob,
+        0.9,
+        vec![("evidence_0_chunk_id", "doc_1")],
+    );
+    db.add_relation("Parent", bob, cat, 0.8, Vec::new());
+    RuleSet::new(vec![
+        Rule::parse("base", "Parent(x, y) -> Ancestor(x, y)").unwrap(),
+        Rule::parse("step", "Ancestor(x, y) && Parent(y, z) -> Ancestor(x, z)").unwrap(),
+    ])
+    .run(&mut db);
+
+    let query = PathQuery::Join(
+        Box::new(PathQuery::SelectByType("Person".to_string())),
+        Box::new(PathQuery::FollowPath {
+            start: ann,
+            path: vec!["Ancestor".to_string()],
+        }),
+    );
+    assert!(db.explain(&query, ann).is_none());
+
+    let why = db.explain(&query, cat).unwrap();
+    assert_eq!(
+        why.step,
+        ExplanationStep::Answer {
+            entity: cat,
+            world: None
+        }
+    );
+    let join = &why.children[0];
+    assert_eq!(join.step, ExplanationStep::Join);
+    assert_eq!(
+        join.children[0].step,
+        ExplanationStep::TypeSelection {
+            entity: cat,
+            type_name: "Person".to_string()
+        }
+    );
+
+    // ann -Ancestor-> cat <= step(ann -Ancestor-> bob <= base(ann -Parent-> bob), bob -Parent-> cat)
+    let path = &join.children[1];
+    assert!(
+        matches!(&path.step, ExplanationStep::Path { confidence, .. } if (*confidence - 0.72).abs() < 1e-6)
+    );
+    let derived = &path.children[0];
+    assert_eq!(edge(&derived.step), (ann, "Ancestor", cat));
+    let rule = &derived.children[0];
+    assert_eq!(
+        rule.step,
+        ExplanationStep::Rule {
+            rule_id: "step".to_string()
+        }
+    );
+    assert_eq!(edge(&rule.children[0].step), (ann, "Ancestor", bob));
+    assert_eq!(edge(&rule.children[1].step), (bob, "Parent", cat));
+    let base = &rule.children[0].children[0];
+    assert_eq!(
+        base.step,
+        ExplanationStep::Rule {
+            rule_id: "base".to_string()
+        }
+    );
+    match &base.children[0].step {
+        ExplanationStep::Edge(e) => assert_eq!(e.evidence, ["doc_1"]),
+        other => panic!("expected an edge, got {other:?}"),
+    }
+
+    let text = why.render(&db);
+    assert!(text.starts_with("- `cat` (#2) is an answer\n"));
+    assert!(text.contains("      - derived by rule `step` from:\n"));
+    assert!(text.contains("`ann` (#0) -Parent-> `bob` (#1) (confidence 0.90; evidence: doc_1)"));
+
+    // The explanation respects the same filters as `execute`.
+    let strict = PathQuery::WithConfidence {
+        base: Box::new(query),
+        min_confidence: 0.85,
+    };
+    assert!(!db.execute(&strict).contains(cat));
+    assert!(db.explain(&strict, cat).is_none());
+}
+
+#[test]
+fn explanation_collects_evidence_from_fact_nodes() {
+    let mut db = PathDB::new();
+    let a = db.add_entity("Node", vec![("name", "a")]);
+    let b = db.add_entity("Node", vec![("name", "b")]);
+    let fact = db.add_entity("Link", vec![("axi_fact_id", "f1")]);
+    let chunk = db.add_entity("DocChunk", vec![("chunk_id", "doc_7")]);
+    db.add_relation("has_evidence_chunk", fact, chunk, 1.0, Vec::new());
+    db.add_relation("Link", a, b, 0.6, vec![("axi_fact_id", "f1")]);
+    db.add_relation("Link", a, b, 0.4, Vec::new());
+
+    let why = db
+        .explain(&PathQuery::SelectRelated(a, "Link".to_string()), b)
+        .unwrap();
+    match &why.children[0].step {
+        ExplanationStep::Edge(e) => {
+            assert_eq!(e.confidence, 0.6);
+            assert_eq!(e.evidence, ["doc_7"]);
+        }
+        other => panic!("expected an edge, got {other:?}"),
+    }
+
+    let find = PathQuery::FindPaths {
+        from: a,
+        to: b,
+        max_depth: 2,
+    };
+    let why = db.explain(&find, b).unwrap();
+    assert!(matches!(
+        &why.children[0].step,
+        ExplanationStep::Path { start, path, .. } if *start == a && path == &["Link"]
+    ));
+    assert!(db.explain(&find, a).is_none());
+}
@@ -0,0 +1,202 @@
+use anyhow::Result;
+use axiograph_pathdb::axi_module_import::import_axi_schema_v1_module_into_pathdb;
+use axiograph_pathdb::{OnKeyConflict, PathDB, PathDbError};
+
+const FLOWS: &str = r#"
+module Flows
+
+schema S:
+  object Node
+  object Ctx
+  relation Flow(to: Node, from: Node) @context Ctx
+
+theory T on S:
+  constraint key Flow(from, to, ctx)
+
+instance I of S:
+  Node = {a, b}
+  Ctx = {live}
+"#;
+
+fn id(db: &PathDB, name: &str) -> u32 {
+    let key = db.interner.id_of("name").unwrap();
+    let value = db.interner.id_of(name).unwrap();
+    db.entities
+        .entities_with_attr_value(key, value)
+        .min()
+        .unwrap()
+}
+
+#[test]
+fn declared_fact_gets_role_edges_context_and_key_entries() -> Result<()> {
+    let m = axiograph_dsl::axi_v1::parse_axi_v1(FLOWS)?;
+    let mut db = PathDB::new();
+    import_axi_schema_v1_module_into_pathdb(&mut db, &m)?;
+    let (a, b, live) = (id(&db, "a"), id(&db, "b"), id(&db, "live"));
+
+    let fact = db.add_fact("S", "Flow", &[("from", a), ("to", b)], 0.8, Some(live))?;
+    let view = db.get_fact(fact).expect("fact view");
+    assert_eq!(view.relation, "Flow");
+    assert_eq!(view.schema.as_deref(), Some("S"));
+    let fields: Vec<&str> = view.fields.iter().map(|(f, _)| f.as_str()).collect();
+    assert_eq!(fields, vec!["to", "from", "ctx"]);
+    assert_eq!(view.field("from"), Some(a));
+    assert_eq!(view.context, Some(live));
+    assert!((view.confidence - 0.8).abs() < 1e-6);
+
+    assert!(db
+        .fact_nodes_by_axi_schema_relation("S", "Flow")
+        .contains(fact));
+    assert!(db.fact_nodes_by_context(live).contains(fact));
+    let hit = db.fact_nodes_by_axi_key("S", "Flow", &["from", "to", "ctx"], &[a, b, live]);
+    assert_eq!(hit, Some(vec![fact]));
+
+    // Same tuple again: the existing node.
+    let again = db.add_fact("S", "Flow", &[("to", b), ("from", a)], 0.8, Some(live))?;
+    assert_eq!(again, fact);
+    assert!(db.verify_integrity().is_ok());
+    Ok(())
+}
+
+#[test]
+fn declared_fields_are_checked() -> Result<()> {
+    let m = axiograph_dsl::axi_v1::parse_axi_v1(FLOWS)?;
+    let mut db = PathDB::new();
+    import_axi_schema_v1_module_into_pathdb(&mut db, &m)?;
+    let (a, b, live) = (id(&db, "a"), id(&db, "b"), id(&db, "live"));
+
+    let missing = db.add_fact("S", "Flow", &[("from", a)], 1.0, Some(live));
+    assert!(matches!(missing, Err(PathDbError::InvalidFact { .. })));
+    let extra = db.add_fact(
+        "S",
+        "Flow",
+        &[("from", a), ("to", b), ("via", a)],
+        1.0,
+        Some(live),
+    );
+    assert!(matches!(extra, Err(PathDbError::InvalidFact { .. })));
+    let unknown = db.add_fact("S", "Flow", &[("from", a), ("to", 9_999)], 1.0, Some(live));
+    assert!(matches!(unknown, Err(PathDbError::UnknownEntity(9_999))));
+    Ok(())
+}
+
+#[test]
+fn undeclared_relations_build_ad_hoc_fact_nodes() -> Result<()> {
+    let mut db = PathDB::new();
+    let alice = db.add_entity("Person", vec![("name", "alice")]);
+    let acme = db.add_entity("Org", vec![("name", "acme")]);
+    let w1 = db.add_entity("World", vec![("name", "w1")]);
+    let w2 = db.add_entity("World", vec![("name", "w2")]);
+
+    let f1 = db.add_fact(
+        "Hr",
+        "Employment",
+        &[("person", alice), ("org", acme)],
+        0.9,
+        Some(w1),
+    )?;
+    let f2 = db.add_fact(
+        "Hr",
+        "Employment",
+        &[("person", alice), ("org", acme)],
+        0.9,
+        Some(w2),
+    )?;
+    assert_ne!(f1, f2, "the context is part of the tuple's identity");
+    assert_eq!(db.fact_nodes_by_axi_relation("Employment").len(), 2);
+
+    let view = db.get_fact(f2).unwrap();
+    assert_eq!(
+        view.fields,
+        vec![("person".to_string(), alice), ("org".to_string(), acme)]
+    );
+    assert_eq!(view.context, Some(w2));
+    assert!(db.get_fact(alice).is_none());
+    Ok(())
+}
+
+const OWNERS: &str = r#"
+module Owners
+
+schema S:
+  object Node
+  relation Owner(asset: Node, person: Node)
+
+theory T on S:
+  constraint key Owner(asset)
+
+instance I of S:
+  Node = {car, ann, bob}
+  Owner = {(asset=car, person=ann)}
+"#;
+
+fn owners() -> Result<(PathDB, u32, u32, u32, u32)> {
+    let m = axiograph_dsl::axi_v1::parse_axi_v1(OWNERS)?;
+    let mut db = PathDB::new();
+    import_axi_schema_v1_module_into_pathdb(&mut db, &m)?;
+    let imported = db.fact_nodes_by_axi_relation("Owner").min().unwrap();
+    let (car, ann, bob) = (id(&db, "car"), id(&db, "ann"), id(&db, "bob"));
+    Ok((db, imported, car, ann, bob))
+}
+
+#[test]
+fn key_conflicts_are_rejected_by_default() -> Result<()> {
+    let (mut db, imported, car, _, bob) = owners()?;
+    let err = db
+        .add_fact("S", "Owner", &[("asset", car), ("person", bob)], 1.0, None)
+        .unwrap_err();
+    let PathDbError::KeyViolation(violation) = err else {
+        panic!("expected a key violation, got {err:?}");
+    };
+    assert_eq!(violation.existing, imported);
+    assert_eq!(violation.key_fields, vec!["asset".to_string()]);
+    assert_eq!(violation.key_values, vec![car]);
+    assert_eq!(db.fact_nodes_by_axi_relation("Owner").len(), 1);
+    Ok(())
+}
+
+#[test]
+fn supersede_retires_the_conflicting_fact() -> Result<()> {
+    let (mut db, imported, car, _, bob) = owners()?;
+    let insert = db.insert_fact(
+        "S",
+        "Owner",
+        &[("asset", car), ("person", bob)],
+        1.0,
+        None,
+        OnKeyConflict::Supersede,
+    )?;
+    assert_eq!(insert.violations.len(), 1);
+    assert_ne!(insert.fact, imported);
+
+    let owners = db.fact_nodes_by_axi_relation("Owner");
+    assert_eq!(owners.iter().collect::<Vec<_>>(), vec![insert.fact]);
+    assert!(db.get_fact(imported).is_none());
+    assert!(db.follow_one(imported, "person").is_empty());
+    let hit = db.fact_nodes_by_axi_key("S", "Owner", &["asset"], &[car]);
+    assert_eq!(hit, Some(vec![insert.fact]));
+    assert!(db.verify_integrity().is_ok());
+    Ok(())
+}
+
+#[test]
+fn merge_rewrites_non_key_fields_in_place() -> Result<()> {
+    let (mut db, imported, car, ann, bob) = owners()?;
+    let insert = db.insert_fact(
+        "S",
+        "Owner",
+        &[("asset", car), ("person", bob)],
+        0.7,
+        None,
+        OnKeyConflict::Merge,
+    )?;
+    assert_eq!(insert.fact, imported);
+    assert_eq!(insert.violations.len(), 1);
+
+    let view = db.get_fact(imported).unwrap();
+    assert_eq!(view.field("person"), Some(bob));
+    assert_eq!(view.field("asset"), Some(car));
+    assert!(!db.follow_one(imported, "person").contains(ann));
+    assert_eq!(db.fact_nodes_by_axi_relation("Owner").len(), 1);
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use axiograph_pathdb::{FederatedQuery, FederatedRef, Federation, PathDB, PathDbError, PathQuery};
+
+/// api: the `orders` service exposes `/orders`.
+/// docs: page `orders-guide` documents the `orders` service.
+fn federation() -> Federation {
+    let mut api = PathDB::new();
+    let svc = api.add_entity("Service", vec![("name", "orders")]);
+    let ep = api.add_entity("Endpoint", vec![("name", "/orders")]);
+    api.add_entity("Service", vec![("name", "billing")]);
+    api.add_relation("exposes", svc, ep, 1.0, vec![]);
+    api.build_indexes();
+
+    let mut docs = PathDB::new();
+    let page = docs.add_entity("Page", vec![("name", "orders-guide")]);
+    let svc = docs.add_entity("Service", vec![("name", "orders")]);
+    docs.add_relation("documents", page, svc, 1.0, vec![]);
+    docs.build_indexes();
+
+    let mut fed = Federation::new();
+    fed.register("api", Arc::new(api)).unwrap();
+    fed.register("docs", Arc::new(docs)).unwrap();
+    fed
+}
+
+fn refs(items: &[&str]) -> BTreeSet<FederatedRef> {
+    items.iter().map(|s| s.parse().unwrap()).collect()
+}
+
+#[test]
+fn paths_cross_members_through_equivalence_links() {
+    let mut fed = federation();
+    let follow = FederatedQuery::FollowPath {
+        start: "docs:0".parse().unwrap(),
+        path: vec!["documents".to_string(), "exposes".to_string()],
+    };
+    // Without links the path stops at the docs graph's copy of the service.
+    assert!(fed.execute(&follow).unwrap().is_empty());
+
+    // Reconciling on `name` links the two `orders` services (same type), and
+    // nothing else.
+    assert_eq!(fed.reconcile_by_attr("name"), 1);
+    assert_eq!(fed.reconcile_by_attr("name"), 0);
+    assert_eq!(
+        fed.equivalents(&"api:0".parse().unwrap()).unwrap(),
+        refs(&["api:0", "docs:1"])
+    );
+    assert_eq!(fed.execute(&follow).unwrap(), refs(&["api:1"]));
+    assert_eq!(
+        fed.entity(&"api:1".parse().unwrap()).unwrap().attrs["name"],
+        "/orders"
+    );
+
+    // Services that are documented somewhere: a cross-graph join.
+    let documented = FederatedQuery::Join(
+        Box::new(FederatedQuery::In {
+            graph: "api".to_string(),
+            query: PathQuery::SelectByType("Service".to_string()),
+        }),
+        Box::new(FederatedQuery::FollowPath {
+            start: "docs:0".parse().unwrap(),
+            path: vec!["documents".to_string()],
+        }),
+    );
+    assert_eq!(fed.execute(&documented).unwrap(), refs(&["api:0"]));
+    assert_eq!(
+        fed.execute(&FederatedQuery::SelectByType {
+            type_name: "Service".to_string()
+        })
+        .unwrap(),
+        refs(&["api:0", "api:2", "docs:1"])
+    );
+}
+
+#[test]
+fn members_and_refs_are_validated() {
+    let mut fed = federation();
+    assert!(matches!(
+        fed.register("api", Arc::new(PathDB::new())),
+        Err(PathDbError::InvalidMember { .. })
+    ));
+    assert!(matches!(
+        fed.register("a:b", Arc::new(PathDB::new())),
+        Err(PathDbError::InvalidMember { .. })
+    ));
+    assert!(matches!(
+        fed.link(&FederatedRef::new("cad", 0), &FederatedRef::new("api", 0)),
+        Err(PathDbError::UnknownMember(_))
+    ));
+    assert!(matches!(
+        fed.link(&FederatedRef::new("docs", 9), &FederatedRef::new("api", 0)),
+        Err(PathDbError::UnknownEntity(9))
+    ));
+    fed.link(&"api:2".parse().unwrap(), &"docs:0".parse().unwrap())
+        .unwrap();
+    assert_eq!(
+        fed.equivalents(&"docs:0".parse().unwrap()).unwrap(),
+        refs(&["api:2", "docs:0"])
+    );
+    assert!("api".parse::<FederatedRef>().is_err());
+    assert_eq!(FederatedRef::new("api", 3).to_string(), "api:3");
+}
@@ -0,0 +1,247 @@
+//! Export-format conformance: every PathDB interchange format must round-trip
+//! random graphs to an isomorphic graph.
+//!
+//! Each entry of [`formats`] exports a PathDB and re-ingests the result. Both
+//! graphs are reduced to an id-free [`Canonical`] form (multisets of typed,
+//! attributed entities, of edges between such entities, and of equivalences),
+//! so formats are free to renumber entities and relations, but must not drop
+//! or alter anything else unless the format lists it in [`Lossy`].
+//!
+//! Formats covered: `.axpd` (`PathDB::to_bytes`) and the reversible `.axi`
+//! snapshot (`PathDBExportV1`). A new exporter/ingester pair (JSON-LD,
+//! GraphML, Arrow, ...) joins the harness by adding one [`Format`] entry with
+//! its documented lossy fields.
+
+use std::collections::BTreeMap;
+
+use axiograph_pathdb::axi_export::{export_pathdb_to_axi_v1, import_pathdb_from_axi_v1};
+use axiograph_pathdb::PathDB;
+use proptest::prelude::*;
+
+/// What a format is documented to lose; the canonical form ignores it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lossy {
+    /// Confidences survive only to this many decimal places.
+    ConfidenceDecimals(u32),
+    /// Repeated identical equivalence entries collapse to one.
+    DuplicateEquivalences,
+}
+
+struct Format {
+    name: &'static str,
+    roundtrip: fn(&PathDB) -> Result<PathDB, String>,
+    lossy: &'static [Lossy],
+}
+
+fn formats() -> Vec<Format> {
+    vec![
+        Format {
+            name: "axpd",
+            roundtrip: |db| {
+                let bytes = db.to_bytes().map_err(|e| e.to_string())?;
+                PathDB::from_bytes(&bytes).map_err(|e| e.to_string())
+            },
+            lossy: &[],
+        },
+        Format {
+            name: "axi_v1",
+            roundtrip: |db| {
+                let axi = export_pathdb_to_axi_v1(db).map_err(|e| e.to_string())?;
+                import_pathdb_from_axi_v1(&axi).map_err(|e| e.to_string())
+            },
+            // Instance assignments are sets.
+            lossy: &[Lossy::DuplicateEquivalences],
+        },
+    ]
+}
+
+type Attrs = Vec<(String, String)>;
+/// Entity type and attributes: everything but the id.
+type EntitySig = (String, Attrs);
+
+/// Id-free summary of a graph; equal for isomorphic graphs.
+#[derive(Debug, PartialEq)]
+struct Canonical {
+    entities: BTreeMap<EntitySig, usize>,
+    relations: BTreeMap<(EntitySig, String, EntitySig, String, Attrs), usize>,
+    equivalences: BTreeMap<(EntitySig, EntitySig, String), usize>,
+}
+
+fn canonical(db: &PathDB, lossy: &[Lossy]) -> Canonical {
+    let sig = |id: u32| -> EntitySig {
+        let view = db.get_entity(id).expect("entity");
+        (view.entity_type, view.attrs.into_iter().collect())
+    };
+    let lookup = |id| db.interner.lookup(id).expect("interned string");
+    let confidence = |c: f32| match lossy.iter().find_map(|l| match l {
+        Lossy::ConfidenceDecimals(d) => Some(*d as usize),
+        _ => None,
+    }) {
+        Some(decimals) => format!("{c:.decimals$}"),
+        None => format!("{:08x}", c.to_bits()),
+    };
+
+    let mut out = Canonical {
+        entities: BTreeMap::new(),
+        relations: BTreeMap::new(),
+        equivalences: BTreeMap::new(),
+    };
+    for id in 0..db.entities.len() as u32 {
+        *out.entities.entry(sig(id)).or_default() += 1;
+    }
+    for id in 0..db.relations.len() as u32 {
+        let rel = db.relations.get_relation(id).expect("relation");
+        let mut attrs: Attrs = rel
+            .attrs
+            .iter()
+            .map(|&(k, v)| (lookup(k), lookup(v)))
+            .collect();
+        attrs.sort();
+        let key = (
+            sig(rel.source),
+            lookup(rel.rel_type),
+            sig(rel.target),
+            confidence(rel.confidence),
+            attrs,
+        );
+        *out.relations.entry(key).or_default() += 1;
+    }
+    for (&a, targets) in &db.equivalences {
+        for &(b, label) in targets {
+            *out.equivalences
+                .entry((sig(a), sig(b), lookup(label)))
+                .or_default() += 1;
+        }
+    }
+    if lossy.contains(&Lossy::DuplicateEquivalences) {
+        out.equivalences.values_mut().for_each(|n| *n = 1);
+    }
+    out
+}
+
+#[derive(Debug, Clone)]
+struct GraphCase {
+    entities: Vec<(u8, Attrs)>,                // (type_idx, attrs)
+    edges: Vec<(u8, usize, usize, u8, Attrs)>, // (rel_idx, src, dst, conf_pct, attrs)
+    equivalences: Vec<(usize, usize, u8)>,     // (a, b, label_idx)
+}
+
+fn small_string() -> impl Strategy<Value = String> {
+    prop::collection::vec(any::<char>(), 0..=8).prop_map(|chars| chars.into_iter().collect())
+}
+
+/// Attribute values that are sometimes numeric or boolean, so typed-literal
+/// handling is exercised too.
+fn attr_value() -> impl Strategy<Value = String> {
+    prop_oneof![
+        small_string(),
+        any::<i32>().prop_map(|n| n.to_string()),
+        (-1000i32..1000).prop_map(|n| format!("{}.5", n)),
+        any::<bool>().prop_map(|b| b.to_string()),
+    ]
+}
+
+fn kv_pairs(max: usize) -> impl Strategy<Value = Vec<(String, String)>> {
+    prop::collection::vec(("[a-z_]{1,6}", attr_value()), 0..=max)
+}
+
+fn graph_case_strategy() -> impl Strategy<Value = GraphCase> {
+    (1usize..=10).prop_flat_map(|n| {
+        let entities = prop::collection::vec((0u8..3, kv_pairs(3)), n..=n);
+        let edges = prop::collection::vec((0u8..3, 0..n, 0..n, 0u8..=100, kv_pairs(2)), 0..=20);
+        let equivalences = prop::collection::vec((0..n, 0..n, 0u8..2), 0..=4);
+        (entities, edges, equivalences).prop_map(|(entities, edges, equivalences)| GraphCase {
+            entities,
+            edges,
+            equivalences,
+        })
+    })
+}
+
+fn build_db(case: &GraphCase) -> PathDB {
+    let mut db = PathDB::new();
+    let ids: Vec<u32> = case
+        .entities
+        .iter()
+        .map(|(t, attrs)| {
+            db.add_entity(
+                &format!("Type{t}"),
+                attrs
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+                    .collect(),
+            )
+        })
+        .collect();
+    for (r, s, t, conf, attrs) in &case.edges {
+        db.add_relation(
+            &format!("rel_{r}"),
+            ids[*s],
+            ids[*t],
+            f32::from(*conf) / 100.0,
+            attrs
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect(),
+        );
+    }
+    for (a, b, label) in &case.equivalences {
+        db.add_equivalence(ids[*a], ids[*b], &format!("equiv_{label}"));
+    }
+    db.build_indexes();
+    db
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 96,
+        failure_persistence: None,
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn every_format_roundtrips_to_an_isomorphic_graph(case in graph_case_strategy()) {
+        let db = build_db(&case);
+        for format in formats() {
+            let back = (format.roundtrip)(&db)
+                .map_err(|e| TestCaseError::fail(format!("{}: {e}", format.name)))?;
+            prop_assert_eq!(
+                canonical(&db, format.lossy),
+                canonical(&back, format.lossy),
+                "{} changed the graph",
+                format.name
+            );
+        }
+    }
+}
+
+#[test]
+fn the_canonical_form_ignores_ids_but_not_content() {
+    let mut a = PathDB::new();
+    let x = a.add_entity("Tool", vec![("name", "x")]);
+    let y = a.add_entity("Tool", vec![("name", "y")]);
+    a.add_relation("uses", x, y, 0.5, vec![]);
+
+    // Same graph, entities inserted in the other order.
+    let mut b = PathDB::new();
+    let y = b.add_entity("Tool", vec![("name", "y")]);
+    let x = b.add_entity("Tool", vec![("name", "x")]);
+    b.add_relation("uses", x, y, 0.5, vec![]);
+    assert_eq!(canonical(&a, &[]), canonical(&b, &[]));
+
+    // Reversed edge: not isomorphic.
+    let mut c = PathDB::new();
+    let x = c.add_entity("Tool", vec![("name", "x")]);
+    let y = c.add_entity("Tool", vec![("name", "y")]);
+    c.add_relation("uses", y, x, 0.5, vec![]);
+    assert_ne!(canonical(&a, &[]), canonical(&c, &[]));
+
+    // Documented confidence rounding is tolerated, anything finer is not.
+    let mut d = PathDB::new();
+    let x = d.add_entity("Tool", vec![("name", "x")]);
+    let y = d.add_entity("Tool", vec![("name", "y")]);
+    d.add_relation("uses", x, y, 0.5001, vec![]);
+    assert_ne!(canonical(&a, &[]), canonical(&d, &[]));
+    let rounded = [Lossy::ConfidenceDecimals(2)];
+    assert_eq!(canonical(&a, &rounded), canonical(&d, &rounded));
+}
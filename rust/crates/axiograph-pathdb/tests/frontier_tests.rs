@@ -0,0 +1,94 @@
+use axiograph_pathdb::budget::QueryBudget;
+use axiograph_pathdb::frontier::FRONTIER_CHUNK;
+use axiograph_pathdb::{PathDB, PathQuery};
+use roaring::RoaringBitmap;
+
+/// `n` nodes, each with `next` edges to `i+1`, `i+7` and `i*3` (mod `n`),
+/// and a `back` edge to `i/2`.
+fn ring(n: u32) -> PathDB {
+    let mut db = PathDB::new();
+    for i in 0..n {
+        db.add_entity("Node", vec![("name", format!("n{i}").as_str())]);
+    }
+    for i in 0..n {
+        for j in [i + 1, i + 7, i * 3] {
+            db.add_relation("next", i, j % n, 1.0, vec![]);
+        }
+        db.add_relation("back", i, i / 2, 1.0, vec![]);
+    }
+    db.build_indexes();
+    db
+}
+
+fn per_entity(db: &PathDB, frontier: &RoaringBitmap, rel: &str) -> RoaringBitmap {
+    let rel = db.interner.id_of(rel).unwrap();
+    let mut out = RoaringBitmap::new();
+    for entity in frontier.iter() {
+        out |= db.relations.targets(entity, rel);
+    }
+    out
+}
+
+#[test]
+fn batch_expansion_matches_per_entity_union() {
+    // Larger than one chunk, so chunk boundaries are exercised.
+    let db = ring(3 * FRONTIER_CHUNK as u32 + 17);
+    let frontier: RoaringBitmap = (0..db.entities.len() as u32).step_by(3).collect();
+    for rel in ["next", "back"] {
+        assert_eq!(
+            db.follow_frontier(&frontier, rel),
+            per_entity(&db, &frontier, rel)
+        );
+    }
+    assert!(db.follow_frontier(&frontier, "missing").is_empty());
+    assert!(db.follow_frontier(&RoaringBitmap::new(), "next").is_empty());
+
+    let rel = db.interner.id_of("back").unwrap();
+    let mut sources = RoaringBitmap::new();
+    for entity in frontier.iter() {
+        sources |= db.relations.sources(entity, rel);
+    }
+    assert_eq!(
+        db.relations.expand_frontier_reverse(&frontier, rel),
+        sources
+    );
+
+    let any = db.follow_frontier_any(&frontier, &["next", "back", "next", "missing"]);
+    assert_eq!(
+        any,
+        per_entity(&db, &frontier, "next") | per_entity(&db, &frontier, "back")
+    );
+}
+
+#[test]
+fn multi_hop_frontiers_match_follow_path() {
+    let db = ring(500);
+    let path = ["next", "back", "next", "next"];
+    let starts: RoaringBitmap = [3u32, 40, 41, 499].into_iter().collect();
+    let mut expected = RoaringBitmap::new();
+    for start in starts.iter() {
+        expected |= db.follow_path(start, &path);
+    }
+    assert_eq!(db.follow_path_from(&starts, &path), expected);
+    // Deeper than the path index, so this runs the batch traversal.
+    assert_eq!(
+        db.follow_path(3, &path),
+        db.follow_path_from(&RoaringBitmap::from_iter([3]), &path)
+    );
+}
+
+#[test]
+fn budgeted_traversal_stays_a_subset() {
+    let db = ring(2_000);
+    let query = PathQuery::FollowPath {
+        start: 0,
+        path: vec!["next".to_string(); 4],
+    };
+    let full = db.execute(&query);
+    // Runs out partway through the last hop's batch.
+    let out = db.execute_with_budget(&query, &QueryBudget::unlimited().with_max_visited(20));
+    assert!(!out.is_complete());
+    assert!(!out.value.is_empty());
+    assert!(out.value.is_subset(&full));
+    assert!(out.value.len() < full.len());
+}
@@ -0,0 +1,92 @@
+use std::borrow::Cow;
+
+use anyhow::Result;
+use axiograph_pathdb::PathDB;
+
+/// `alice -knows-> bob -knows-> carol`, `alice -worksAt-> acme`.
+fn people() -> (PathDB, [u32; 4]) {
+    let mut db = PathDB::new();
+    let alice = db.add_entity(
+        "Person",
+        vec![("name", "alice"), ("email", "alice@example.com")],
+    );
+    let bob = db.add_entity("Person", vec![("name", "bob")]);
+    let carol = db.add_entity("Person", vec![("name", "carol")]);
+    let acme = db.add_entity("Company", vec![("name", "acme")]);
+    db.add_relation("knows", alice, bob, 0.9, vec![]);
+    db.add_relation("knows", bob, carol, 0.8, vec![]);
+    db.add_relation("worksAt", alice, acme, 1.0, vec![]);
+    db.build_indexes();
+    (db, [alice, bob, carol, acme])
+}
+
+#[test]
+fn handles_answer_like_the_string_apis() -> Result<()> {
+    let (mut db, [alice, bob, carol, _]) = people();
+    db.register_inverse("knows", "knownBy")?;
+
+    let knows = db.rel_type_handle("knows");
+    let known_by = db.rel_type_handle("knownBy");
+    assert_eq!(
+        db.follow_one_handle(alice, knows)?,
+        db.follow_one(alice, "knows")
+    );
+    assert!(db.follow_one_handle(bob, known_by)?.contains(alice));
+    assert_eq!(
+        db.follow_path_handles(alice, &[knows, knows])?,
+        db.follow_path(alice, &["knows", "knows"])
+    );
+    assert!(db
+        .follow_path_handles(carol, &[known_by, known_by])?
+        .contains(alice));
+
+    // Names the DB has never seen resolve to handles that match nothing.
+    let unknown = db.rel_type_handle("manages");
+    assert_eq!(unknown.id(), None);
+    assert!(db.follow_one_handle(alice, unknown)?.is_empty());
+    assert!(db.follow_path_handles(alice, &[knows, unknown])?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn attribute_values_are_borrowed_once_frozen() -> Result<()> {
+    let (mut db, [alice, bob, ..]) = people();
+    let email = db.attr_key_handle("email");
+    assert_eq!(
+        db.attr_value(alice, email)?.as_deref(),
+        Some("alice@example.com")
+    );
+    assert_eq!(db.attr_value(bob, email)?, None);
+    assert_eq!(db.attr_value(alice, db.attr_key_handle("phone"))?, None);
+    let value_id = db.attr_value_id(alice, email)?.expect("email is set");
+    assert_eq!(
+        db.interner.lookup(value_id).as_deref(),
+        Some("alice@example.com")
+    );
+
+    assert!(matches!(db.attr_value(alice, email)?, Some(Cow::Owned(_))));
+    db.interner.freeze();
+    assert!(matches!(
+        db.attr_value(alice, email)?,
+        Some(Cow::Borrowed("alice@example.com"))
+    ));
+    assert!(matches!(
+        db.interner.lookup_ref(value_id),
+        Some(Cow::Borrowed(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn handles_are_scoped_to_their_db() {
+    let (db, [alice, ..]) = people();
+    let (other, _) = people();
+    let knows = other.rel_type_handle("knows");
+    let email = other.attr_key_handle("email");
+
+    let err = db.follow_one_handle(alice, knows).unwrap_err();
+    assert_eq!(err.expected, other.db_token());
+    assert_eq!(err.actual, db.db_token());
+    assert!(db.follow_path_handles(alice, &[knows]).is_err());
+    assert!(db.attr_value(alice, email).is_err());
+}
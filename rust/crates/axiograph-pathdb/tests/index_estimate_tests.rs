@@ -0,0 +1,112 @@
+//! Sampled path-index build-cost estimates.
+
+use axiograph_pathdb::{IndexEstimateConfig, PathDB, PathIndexPolicy, PathSig, SafeCubeConfig};
+
+/// A chain `n0 -r-> n1 -r-> ...` plus a dense `hub` relation fanning out
+/// from every node.
+fn chain_db(len: u32) -> PathDB {
+    let mut db = PathDB::new();
+    let nodes: Vec<u32> = (0..len)
+        .map(|i| db.add_entity("Node", vec![("name", &format!("n{i}"))]))
+        .collect();
+    for w in nodes.windows(2) {
+        db.add_relation("r", w[0], w[1], 1.0, vec![]);
+    }
+    for &a in &nodes {
+        for &b in &nodes {
+            db.add_relation("hub", a, b, 1.0, vec![]);
+        }
+    }
+    db
+}
+
+#[test]
+fn a_full_sample_matches_the_built_cube() {
+    let mut db = chain_db(8);
+    let estimate = db.estimate_path_index(3, &IndexEstimateConfig::default());
+    assert_eq!(estimate.sources, 8);
+    assert_eq!(estimate.sampled, 8);
+    assert!(!estimate.truncated && !estimate.certified);
+    assert_eq!(
+        estimate.depths.iter().map(|d| d.depth).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+
+    db.set_path_index_policy(PathIndexPolicy::Cube);
+    db.build_indexes_with_depth(3);
+    for depth in &estimate.depths {
+        let sigs: Vec<PathSig> = db
+            .path_index
+            .signatures()
+            .into_iter()
+            .filter(|s| s.len() == depth.depth)
+            .collect();
+        let entries: u64 = sigs
+            .iter()
+            .flat_map(|sig| (0..8).filter_map(|start| db.path_index.query(start, sig)))
+            .map(|reach| reach.len())
+            .sum();
+        assert_eq!(depth.signatures, sigs.len() as u64);
+        assert_eq!(depth.entries, entries, "depth {}", depth.depth);
+        assert!(depth.bytes >= depth.entries);
+    }
+}
+
+#[test]
+fn sampling_scales_and_is_deterministic() {
+    let db = chain_db(40);
+    let config = IndexEstimateConfig {
+        sample_starts: 10,
+        ..IndexEstimateConfig::default()
+    };
+    let estimate = db.estimate_path_index(2, &config);
+    assert_eq!(estimate.sampled, 10);
+    let again = db.estimate_path_index(2, &config);
+    assert_eq!(
+        estimate
+            .depths
+            .iter()
+            .map(|d| d.entries)
+            .collect::<Vec<_>>(),
+        again.depths.iter().map(|d| d.entries).collect::<Vec<_>>()
+    );
+    // Every node reaches every node through `hub`, so `hub`-led signatures
+    // are exact under any sample.
+    assert!(estimate.depths[1].entries >= 40 * 40);
+
+    let truncated = db.estimate_path_index(
+        3,
+        &IndexEstimateConfig {
+            max_expansions: 50,
+            ..config
+        },
+    );
+    assert!(truncated.truncated);
+    assert_eq!(truncated.depths.len(), 1);
+    assert_eq!(truncated.safe_depth(3, u64::MAX), 1);
+}
+
+#[test]
+fn safe_cube_lowers_the_depth_to_fit_the_budget() {
+    let mut db = chain_db(20);
+    let estimate = db.estimate_path_index(3, &IndexEstimateConfig::default());
+    let depth_two = estimate.multi_hop_entries(2);
+    assert!(estimate.multi_hop_entries(3) > depth_two);
+    assert_eq!(estimate.safe_depth(3, depth_two), 2);
+    assert_eq!(estimate.safe_depth(3, 0), 1);
+    assert_eq!(estimate.safe_depth(0, 0), 0);
+
+    db.set_path_index_policy(PathIndexPolicy::SafeCube(SafeCubeConfig {
+        max_depth: 3,
+        max_entries: depth_two,
+        ..SafeCubeConfig::default()
+    }));
+    db.build_indexes();
+    assert_eq!(db.path_index.max_depth(), 2);
+    assert!(db.path_index.signatures().iter().all(|s| s.len() <= 2));
+
+    // A roomier budget gets the full depth back.
+    db.set_path_index_policy(PathIndexPolicy::SafeCube(SafeCubeConfig::default()));
+    db.build_indexes();
+    assert_eq!(db.path_index.max_depth(), 3);
+}
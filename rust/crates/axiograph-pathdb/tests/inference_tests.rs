@@ -0,0 +1,184 @@
+use anyhow::Result;
+use axiograph_pathdb::inference::{derived_provenance, Rule, RuleSet};
+use axiograph_pathdb::PathDB;
+use roaring::RoaringBitmap;
+
+fn import(text: &str) -> Result<PathDB> {
+    let m = axiograph_dsl::axi_v1::parse_axi_v1(text)?;
+    let mut db = PathDB::new();
+    axiograph_pathdb::axi_module_import::import_axi_schema_v1_module_into_pathdb(&mut db, &m)?;
+    Ok(db)
+}
+
+fn entity(db: &PathDB, name: &str) -> u32 {
+    let key = db.interner.id_of("name").unwrap();
+    let value = db.interner.id_of(name).unwrap();
+    db.entities
+        .entities_with_attr_value(key, value)
+        .min()
+        .unwrap()
+}
+
+fn edge_id(db: &PathDB, source: u32, rel: &str, target: u32) -> u32 {
+    let rel = db.interner.id_of(rel).unwrap();
+    db.relations
+        .outgoing_relation_ids(source, rel)
+        .iter()
+        .copied()
+        .find(|&id| db.relations.get_relation(id).unwrap().target == target)
+        .unwrap()
+}
+
+const FAMILY: &str = r#"
+module Family
+
+schema Kin:
+  object Person
+  relation Parent(parent: Person, child: Person)
+  relation Ancestor(from: Person, to: Person)
+
+theory KinRules on Kin:
+  constraint AncestorBase:
+    forall x : Person, y : Person .
+      Parent(x, y) -> Ancestor(x, y)
+    message: "parents are ancestors"
+
+  constraint AncestorStep:
+    -- transitive closure
+    Ancestor(x, y) && Parent(y, z) ->
+      Ancestor(x, z)
+
+  constraint NotARule:
+    forall p : Person .
+      exists q : Person . Parent(q, p)
+
+instance Tree of Kin:
+  Person = {ann, bob, cat, dan}
+  Parent = {
+    (parent=ann, child=bob),
+    (parent=bob, child=cat)
+  }
+"#;
+
+#[test]
+fn rules_from_theory_blocks_derive_tagged_edges_to_fixpoint() -> Result<()> {
+    let mut db = import(FAMILY)?;
+    let rules = RuleSet::from_db(&db);
+    let ids: Vec<&str> = rules.rules.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(ids, ["KinRules.AncestorBase", "KinRules.AncestorStep"]);
+    assert!(rules.skipped.is_empty());
+
+    let (ann, bob, cat, dan) = (
+        entity(&db, "ann"),
+        entity(&db, "bob"),
+        entity(&db, "cat"),
+        entity(&db, "dan"),
+    );
+    let report = rules.run(&mut db);
+    assert_eq!(report.derived, 3);
+    assert_eq!(
+        db.follow_path(ann, &["Ancestor"]),
+        RoaringBitmap::from_iter([bob, cat])
+    );
+
+    let step = derived_provenance(&db, edge_id(&db, ann, "Ancestor", cat)).unwrap();
+    assert_eq!(step.rule_id, "KinRules.AncestorStep");
+    assert_eq!(
+        step.premises,
+        [
+            format!("{ann}-Ancestor->{bob}"),
+            format!("{bob}-Parent->{cat}")
+        ]
+    );
+    // Asserted edges carry no provenance; a second run derives nothing.
+    assert!(derived_provenance(&db, edge_id(&db, ann, "Parent", bob)).is_none());
+    assert_eq!(rules.run(&mut db).derived, 0);
+
+    // Incremental: only joins against the new premise.
+    let since = db.relations.len() as u32;
+    db.add_relation("Parent", cat, dan, 0.5, Vec::new());
+    let report = rules.run_since(&mut db, since);
+    assert_eq!(report.derived, 3);
+    assert_eq!(
+        db.follow_path(ann, &["Ancestor"]),
+        RoaringBitmap::from_iter([bob, cat, dan])
+    );
+    let rel = db
+        .relations
+        .get_relation(edge_id(&db, ann, "Ancestor", dan))
+        .unwrap();
+    assert_eq!(rel.confidence, 0.5);
+    Ok(())
+}
+
+#[test]
+fn retraction_removes_dependent_facts_and_rederives_alternatives() -> Result<()> {
+    let mut db = PathDB::new();
+    let a = db.add_entity("Person", vec![("name", "a")]);
+    let b = db.add_entity("Person", vec![("name", "b")]);
+    let c = db.add_entity("Person", vec![("name", "c")]);
+    db.add_relation("Parent", a, b, 1.0, Vec::new());
+    db.add_relation("Parent", b, c, 1.0, Vec::new());
+
+    let rules = RuleSet::new(vec![
+        Rule::parse("base", "Parent(x, y) -> Ancestor(x, y)").unwrap(),
+        Rule::parse("step", "Ancestor(x, y) && Parent(y, z) -> Ancestor(x, z)").unwrap(),
+        Rule::parse("guardian", "Guardian(x, y) ∧ Person(y) => Ancestor(x, y)").unwrap(),
+    ]);
+    rules.run(&mut db);
+    let first = derived_provenance(&db, edge_id(&db, a, "Ancestor", c)).unwrap();
+    assert_eq!(first.rule_id, "step");
+    // A second justification for a→c is not derived twice.
+    let since = db.relations.len() as u32;
+    db.add_relation("Guardian", a, c, 0.8, Vec::new());
+    assert_eq!(rules.run_since(&mut db, since).derived, 0);
+
+    let report = rules.retract(&mut db, &[(b, "Parent", c)]);
+    assert_eq!(report.retracted, 1);
+    // b→c and a→c are gone; a→c comes back through the guardian rule.
+    assert_eq!(report.overdeleted, 2);
+    assert_eq!(report.derived, 1);
+    assert_eq!(
+        db.follow_path(a, &["Ancestor"]),
+        RoaringBitmap::from_iter([b, c])
+    );
+    assert!(db.follow_path(b, &["Ancestor"]).is_empty());
+    let now = derived_provenance(&db, edge_id(&db, a, "Ancestor", c)).unwrap();
+    assert_eq!(now.rule_id, "guardian");
+    assert_eq!(now.premises, [format!("{a}-Guardian->{c}")]);
+
+    assert!(Rule::parse("bad", "Parent(x, y) -> Ancestor(x, w)").is_err());
+    assert!(Rule::parse("bad", "Parent(x, y) -> Tall(x)").is_err());
+    Ok(())
+}
+
+#[test]
+fn tacit_knowledge_rules_carry_their_confidence() {
+    let mut db = PathDB::new();
+    let op = db.add_entity("Operation", vec![("name", "op1")]);
+    let ti = db.add_entity("Titanium", vec![("name", "Ti6Al4V")]);
+    let low = db.add_entity("Speed", vec![("name", "LowSpeed")]);
+    db.add_relation("hasMaterial", op, ti, 0.9, Vec::new());
+    db.add_entity(
+        "TacitKnowledge",
+        vec![
+            ("name", "TitaniumRequiresSlowSpeeds"),
+            (
+                "rule",
+                r#"hasMaterial(op, m) && Titanium(m) -> preferredSpeed(op, "LowSpeed")"#,
+            ),
+            ("confidence", "0.5"),
+        ],
+    );
+
+    let rules = RuleSet::from_db(&db);
+    assert_eq!(rules.rules.len(), 1);
+    assert_eq!(rules.run(&mut db).derived, 1);
+    let id = edge_id(&db, op, "preferredSpeed", low);
+    let rel = db.relations.get_relation(id).unwrap();
+    assert!((rel.confidence - 0.45).abs() < 1e-6);
+    assert_eq!(
+        derived_provenance(&db, id).unwrap().rule_id,
+        "TitaniumRequiresSlowSpeeds"
+    );
+}
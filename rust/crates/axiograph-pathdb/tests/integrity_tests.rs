@@ -0,0 +1,57 @@
+use axiograph_pathdb::{IntegrityCheck, PathDB};
+
+fn small_graph() -> PathDB {
+    let mut db = PathDB::new();
+    let a = db.add_entity("Person", vec![("name", "Alice")]);
+    let b = db.add_entity("Person", vec![("name", "Bob")]);
+    let c = db.add_entity("Team", vec![("name", "Core")]);
+    db.add_relation("knows", a, b, 0.9, vec![]);
+    db.add_relation("member_of", a, c, 1.0, vec![("since", "2020")]);
+    db.add_relation("member_of", b, c, 0.7, vec![]);
+    db.add_equivalence(a, b, "same_as");
+    // Virtual types add type_index memberships beyond the canonical type.
+    db.mark_virtual_type(a, "Agent").unwrap();
+    db
+}
+
+#[test]
+fn consistent_db_verifies_clean() {
+    let mut db = small_graph();
+    let report = db.verify_integrity();
+    assert!(report.is_ok(), "{report}");
+    assert_eq!((report.entities, report.relations), (3, 3));
+
+    // Mutations keep the indexes in step.
+    db.set_relation_confidence(1, "member_of", 2, 0.2);
+    assert_eq!(db.remove_relation(0, "knows", 1), 1);
+    db.freeze_adjacency();
+    assert!(db.verify_integrity().is_ok());
+}
+
+#[test]
+fn snapshot_round_trip_verifies_clean() {
+    let db = PathDB::from_bytes(&small_graph().to_bytes().unwrap()).unwrap();
+    assert!(db.verify_integrity().is_ok());
+}
+
+#[test]
+fn confidence_index_drift_is_reported() {
+    // The confidence index is the last section of the snapshot: overwrite the
+    // final entry so it no longer matches its relation (still in range, so the
+    // load-time structural checks accept it).
+    let mut bytes = small_graph().to_bytes().unwrap();
+    let len = bytes.len();
+    bytes[len - 4..].copy_from_slice(&0.125f32.to_le_bytes());
+    let db = PathDB::from_bytes(&bytes).unwrap();
+
+    let report = db.verify_integrity();
+    assert!(!report.is_ok());
+    assert_eq!(report.issue_count(), 1);
+    let issue = report.issue(IntegrityCheck::ConfidenceIndex).unwrap();
+    assert!(
+        issue.samples[0].contains("relation 2"),
+        "{:?}",
+        issue.samples
+    );
+    assert!(report.to_string().contains("confidence_index"));
+}
@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+
+use axiograph_pathdb::interner::INLINE_CAP;
+use axiograph_pathdb::{PathDB, StrId, StringInterner};
+
+#[test]
+fn parallel_interning_hands_out_dense_unique_ids() {
+    let interner = StringInterner::new();
+    // Every thread interns the same 2000 strings in a different order.
+    let ids: Vec<Vec<(String, StrId)>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|t| {
+                let interner = &interner;
+                scope.spawn(move || {
+                    (0..2000)
+                        .map(|i| {
+                            let s = format!("symbol_{}", (i * 7 + t * 311) % 2000);
+                            let id = interner.intern(&s);
+                            (s, id)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    assert_eq!(interner.len(), 2000);
+    let distinct: HashSet<StrId> = ids.iter().flatten().map(|(_, id)| *id).collect();
+    assert_eq!(distinct.len(), 2000);
+    for (s, id) in ids.iter().flatten() {
+        assert!(id.raw() < 2000);
+        assert_eq!(interner.id_of(s), Some(*id));
+        assert_eq!(interner.lookup(*id).as_deref(), Some(s.as_str()));
+    }
+}
+
+#[test]
+fn short_strings_are_stored_inline() {
+    let interner = StringInterner::new();
+    let short = "a".repeat(INLINE_CAP);
+    let long = "b".repeat(INLINE_CAP + 1);
+    for s in ["", "name", "ü€", short.as_str(), long.as_str()] {
+        let id = interner.intern(s);
+        assert_eq!(interner.lookup(id).as_deref(), Some(s));
+    }
+
+    let stats = interner.stats();
+    assert_eq!(stats.strings, 5);
+    assert_eq!(stats.inline, 4);
+    assert_eq!(stats.heap_bytes, long.len());
+}
+
+#[test]
+fn frozen_interner_keeps_ids_and_still_accepts_new_strings() {
+    let mut interner = StringInterner::new();
+    let before: Vec<StrId> = (0..100)
+        .map(|i| interner.intern(&format!("s{i}")))
+        .collect();
+    let bytes = interner.to_bytes();
+    assert!(!interner.is_frozen());
+
+    interner.freeze();
+    assert!(interner.is_frozen());
+    assert_eq!(interner.stats().frozen, 100);
+    assert_eq!(interner.to_bytes(), bytes);
+    for (i, id) in before.iter().enumerate() {
+        assert_eq!(interner.intern(&format!("s{i}")), *id);
+        assert_eq!(interner.lookup(*id), Some(format!("s{i}")));
+    }
+
+    // New strings go on top of the frozen base.
+    let fresh = interner.intern("a string interned after freezing");
+    assert_eq!(fresh.raw(), 100);
+    assert!(!interner.is_frozen());
+    assert_eq!(
+        interner.lookup(fresh).as_deref(),
+        Some("a string interned after freezing")
+    );
+
+    interner.freeze();
+    assert!(interner.is_frozen());
+    assert_eq!(
+        interner.id_of("a string interned after freezing"),
+        Some(fresh)
+    );
+    assert_eq!(interner.lookup(StrId::new(101)), None);
+}
+
+#[test]
+fn frozen_snapshot_round_trips_and_verifies() {
+    let mut db = PathDB::new();
+    let a = db.add_entity("Person", vec![("name", "Alice")]);
+    let b = db.add_entity("Person", vec![("name", "Bob with a long name")]);
+    db.add_relation("knows", a, b, 1.0, vec![]);
+    let bytes = db.to_bytes().unwrap();
+
+    db.interner.freeze();
+    assert!(db.verify_integrity().is_ok());
+    assert_eq!(db.to_bytes().unwrap(), bytes);
+    db.add_entity("Person", vec![("name", "Carol")]);
+    assert!(db.verify_integrity().is_ok());
+
+    let loaded = PathDB::from_bytes(&db.to_bytes().unwrap()).unwrap();
+    let carol = loaded.interner.id_of("Carol").unwrap();
+    let name = loaded.interner.id_of("name").unwrap();
+    assert_eq!(
+        loaded.entities.entities_with_attr_value(name, carol).len(),
+        1
+    );
+}
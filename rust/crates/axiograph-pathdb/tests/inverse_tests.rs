@@ -0,0 +1,97 @@
+use anyhow::Result;
+use axiograph_pathdb::{InverseDirection, PathDB, PathDbError};
+
+/// `Billing -has_rpc-> Charge`, `Billing -has_rpc-> Refund`, stored one way.
+fn services() -> (PathDB, u32, u32, u32) {
+    let mut db = PathDB::new();
+    let billing = db.add_entity("ProtoService", vec![("name", "Billing")]);
+    let charge = db.add_entity("ProtoRpc", vec![("name", "Charge")]);
+    let refund = db.add_entity("ProtoRpc", vec![("name", "Refund")]);
+    db.add_relation("proto_service_has_rpc", billing, charge, 0.9, vec![]);
+    db.add_relation("proto_service_has_rpc", billing, refund, 0.4, vec![]);
+    db.build_indexes();
+    (db, billing, charge, refund)
+}
+
+#[test]
+fn inverse_names_traverse_stored_edges_backward() -> Result<()> {
+    let (mut db, billing, charge, refund) = services();
+    assert!(db.follow_one(charge, "rpc_in_service").is_empty());
+
+    db.register_inverse("proto_service_has_rpc", "rpc_in_service")?;
+    assert_eq!(
+        db.inverse_of("rpc_in_service").as_deref(),
+        Some("proto_service_has_rpc")
+    );
+    assert!(db.follow_one(charge, "rpc_in_service").contains(billing));
+    assert!(db
+        .follow_one_with_min_confidence(refund, "rpc_in_service", 0.5)
+        .is_empty());
+
+    // Siblings: up through the inverse, back down through the relation.
+    let siblings = db.follow_path(charge, &["rpc_in_service", "proto_service_has_rpc"]);
+    assert_eq!(siblings.iter().collect::<Vec<_>>(), vec![charge, refund]);
+    assert_eq!(
+        db.follow_path_with_min_confidence(
+            charge,
+            &["rpc_in_service", "proto_service_has_rpc"],
+            0.5
+        )
+        .len(),
+        1
+    );
+    Ok(())
+}
+
+#[test]
+fn conflicting_registration_is_rejected() -> Result<()> {
+    let (mut db, ..) = services();
+    db.register_inverse("proto_service_has_rpc", "rpc_in_service")?;
+    db.register_inverse("rpc_in_service", "proto_service_has_rpc")?;
+    let err = db
+        .register_inverse("proto_service_has_rpc", "rpc_of")
+        .unwrap_err();
+    assert!(matches!(err, PathDbError::InverseConflict { .. }));
+    assert_eq!(db.inverse_registry().pairs().len(), 1);
+    Ok(())
+}
+
+#[test]
+fn registrations_survive_snapshot_round_trip() -> Result<()> {
+    let (mut db, billing, charge, _) = services();
+    db.register_inverse("proto_service_has_rpc", "rpc_in_service")?;
+    let loaded = PathDB::from_bytes(&db.to_bytes()?)?;
+    assert!(loaded
+        .follow_one(charge, "rpc_in_service")
+        .contains(billing));
+    Ok(())
+}
+
+#[test]
+fn materialize_rewrites_edges_to_the_chosen_direction() -> Result<()> {
+    let (mut db, billing, charge, refund) = services();
+    db.register_inverse("proto_service_has_rpc", "rpc_in_service")?;
+    let has_rpc = db.interner.id_of("proto_service_has_rpc").unwrap();
+
+    assert_eq!(db.materialize_inverses(InverseDirection::Both), 2);
+    assert_eq!(db.materialize_inverses(InverseDirection::Both), 0);
+
+    assert_eq!(db.materialize_inverses(InverseDirection::Inverse), 0);
+    let rpc_in = db.interner.id_of("rpc_in_service").unwrap();
+    assert!(db.relations.has_edge(refund, rpc_in, billing));
+    assert!(!db.relations.has_edge(billing, has_rpc, charge));
+    // Traversal by either name is unchanged.
+    assert_eq!(db.follow_one(billing, "proto_service_has_rpc").len(), 2);
+
+    assert_eq!(db.materialize_inverses(InverseDirection::Relation), 2);
+    assert!(db.relations.has_edge(billing, has_rpc, charge));
+    assert!(!db.relations.has_edge(charge, rpc_in, billing));
+    assert!(db.verify_integrity().is_ok());
+
+    assert_eq!(
+        "both".parse::<InverseDirection>(),
+        Ok(InverseDirection::Both)
+    );
+    assert!("sideways".parse::<InverseDirection>().is_err());
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use axiograph_pathdb::{LabelPolicy, PathDB};
+
+#[test]
+fn labels_follow_key_precedence_and_fall_back_to_type() {
+    let mut db = PathDB::new();
+    let named = db.add_entity("Person", vec![("name", "alice"), ("label", "Alice A.")]);
+    let labeled = db.add_entity(
+        "Class",
+        vec![("prefLabel", "Steel"), ("rdfs_label", "steel")],
+    );
+    let blank = db.add_entity("Class", vec![("name", "  "), ("rdfs_label", "Alloy")]);
+    let anonymous = db.add_entity("Class", vec![("iri", "http://example.org/x")]);
+
+    assert_eq!(db.entity_label(named).as_deref(), Some("alice"));
+    assert_eq!(db.entity_label(labeled).as_deref(), Some("Steel"));
+    assert_eq!(db.entity_label(blank).as_deref(), Some("Alloy"));
+    assert_eq!(db.entity_label(anonymous), None);
+    assert_eq!(db.display_label(anonymous), format!("Class#{anonymous}"));
+
+    let view = db.get_entity(labeled).unwrap();
+    assert_eq!(view.label.as_deref(), Some("Steel"));
+    assert_eq!(
+        db.get_entity(anonymous).unwrap().display_label(),
+        format!("Class#{anonymous}")
+    );
+
+    db.set_label_policy(LabelPolicy {
+        keys: vec!["rdfs_label".to_string(), "name".to_string()],
+        languages: Vec::new(),
+    });
+    assert_eq!(db.entity_label(labeled).as_deref(), Some("steel"));
+    assert_eq!(db.entity_label(named).as_deref(), Some("alice"));
+    assert_eq!(
+        db.get_entity(labeled).unwrap().label.as_deref(),
+        Some("steel")
+    );
+}
+
+#[test]
+fn labels_prefer_configured_languages() {
+    let mut db = PathDB::new();
+    // RDF ingestion joins repeated literals with newlines and keeps tags.
+    let steel = db.add_entity(
+        "Class",
+        vec![("label", "Stahl@de\nacier@fr\nSteel@en-GB\nsteel")],
+    );
+    let typed = db.add_entity(
+        "Thing",
+        vec![("label", "42^^http://www.w3.org/2001/XMLSchema#int")],
+    );
+    let email = db.add_entity("Person", vec![("name", "bob@example.com")]);
+
+    assert_eq!(db.entity_label(steel).as_deref(), Some("Steel"));
+    assert_eq!(db.entity_label(typed).as_deref(), Some("42"));
+    assert_eq!(db.entity_label(email).as_deref(), Some("bob@example.com"));
+
+    let mut policy = LabelPolicy {
+        languages: vec!["fr".to_string(), "de".to_string()],
+        ..LabelPolicy::default()
+    };
+    db.set_label_policy(policy.clone());
+    assert_eq!(db.entity_label(steel).as_deref(), Some("acier"));
+
+    // No preferred language present: untagged first, then the first value.
+    policy.languages = vec!["ja".to_string()];
+    db.set_label_policy(policy);
+    assert_eq!(db.entity_label(steel).as_deref(), Some("steel"));
+    let tagged_only = db.add_entity("Class", vec![("label", "Stahl@de\nacier@fr")]);
+    assert_eq!(db.entity_label(tagged_only).as_deref(), Some("Stahl"));
+}
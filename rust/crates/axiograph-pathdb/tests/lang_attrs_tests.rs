@@ -0,0 +1,171 @@
+use anyhow::Result;
+use axiograph_pathdb::axi_export::{export_pathdb_to_axi_v1, import_pathdb_from_axi_v1};
+use axiograph_pathdb::lang_attrs::split_lang_key;
+use axiograph_pathdb::{LangValue, PathDB, PathDbError};
+
+fn steel() -> Result<(PathDB, u32)> {
+    let mut db = PathDB::new();
+    let steel = db.add_entity("Material", vec![("name", "steel")]);
+    db.set_attr_lang(steel, "label", "en", "Steel")?;
+    db.set_attr_lang(steel, "label", "de", "Stahl")?;
+    db.set_attr_lang(steel, "label", "pt-BR", "Aço")?;
+    Ok((db, steel))
+}
+
+#[test]
+fn lang_attributes_resolve_through_fallback_chains() -> Result<()> {
+    let (mut db, steel) = steel()?;
+    db.upsert_entity_attr(steel, "label", "steel (untagged)")?;
+
+    assert_eq!(
+        db.get_attr_lang(steel, "label", "de").as_deref(),
+        Some("Stahl")
+    );
+    // Less specific forms of the requested tag, then the untagged value.
+    assert_eq!(
+        db.get_attr_lang(steel, "label", "en-GB").as_deref(),
+        Some("Steel")
+    );
+    assert_eq!(
+        db.get_attr_lang(steel, "label", "PT-br").as_deref(),
+        Some("Aço")
+    );
+    assert_eq!(
+        db.get_attr_lang(steel, "label", "pt").as_deref(),
+        Some("steel (untagged)")
+    );
+    assert_eq!(
+        db.get_attr_lang_chain(steel, "label", &["fr", "de-AT", "en"]),
+        Some(LangValue {
+            value: "Stahl".to_string(),
+            lang: Some("de".to_string()),
+        })
+    );
+    assert_eq!(
+        db.get_attr_lang_chain(steel, "label", &["ja"])
+            .unwrap()
+            .lang,
+        None
+    );
+    assert_eq!(db.get_attr_lang(steel, "comment", "en"), None);
+
+    // Tags do not leak into the scalar column; tagged values are listed apart.
+    assert_eq!(
+        db.get_entity(steel)
+            .unwrap()
+            .attrs
+            .get("label")
+            .map(String::as_str),
+        Some("steel (untagged)")
+    );
+    assert_eq!(
+        db.attr_langs(steel, "label"),
+        vec![
+            ("en".to_string(), "Steel".to_string()),
+            ("de".to_string(), "Stahl".to_string()),
+            ("pt-br".to_string(), "Aço".to_string()),
+        ]
+    );
+
+    db.set_attr_lang(steel, "label", "EN", "Mild steel")?;
+    assert_eq!(
+        db.get_attr_lang(steel, "label", "en").as_deref(),
+        Some("Mild steel")
+    );
+    assert!(db.remove_attr_lang(steel, "label", "de"));
+    assert!(!db.remove_attr_lang(steel, "label", "de"));
+
+    assert!(matches!(
+        db.set_attr_lang(steel, "label", "not a tag", "x"),
+        Err(PathDbError::InvalidLanguageTag(_))
+    ));
+    assert!(matches!(
+        db.set_attr_lang(99, "label", "en", "x"),
+        Err(PathDbError::UnknownEntity(99))
+    ));
+    Ok(())
+}
+
+#[test]
+fn lang_attributes_feed_display_labels() -> Result<()> {
+    let mut db = PathDB::new();
+    let steel = db.add_entity("Material", vec![("iri", "http://example.org/Steel")]);
+    db.set_attr_lang(steel, "label", "de", "Stahl")?;
+    assert_eq!(db.entity_label(steel).as_deref(), Some("Stahl"));
+    db.set_attr_lang(steel, "label", "en-US", "Steel")?;
+    assert_eq!(db.entity_label(steel).as_deref(), Some("Steel"));
+    assert_eq!(
+        db.get_entity(steel).unwrap().label.as_deref(),
+        Some("Steel")
+    );
+    Ok(())
+}
+
+#[test]
+fn lang_attributes_survive_snapshots_and_exports() -> Result<()> {
+    let (db, steel) = steel()?;
+    let loaded = PathDB::from_bytes(&db.to_bytes()?)?;
+    assert_eq!(
+        loaded.attr_langs(steel, "label"),
+        db.attr_langs(steel, "label")
+    );
+
+    let text = export_pathdb_to_axi_v1(&db)?;
+    assert!(text.contains("entity_lang_attribute"));
+    let imported = import_pathdb_from_axi_v1(&text)?;
+    assert_eq!(
+        imported.get_attr_lang(steel, "label", "de").as_deref(),
+        Some("Stahl")
+    );
+    assert_eq!(
+        imported.get_attr_lang(steel, "label", "pt-BR").as_deref(),
+        Some("Aço")
+    );
+    assert_eq!(export_pathdb_to_axi_v1(&imported)?, text);
+
+    // Without tagged values the export is unchanged.
+    let mut plain = PathDB::new();
+    plain.add_entity("Material", vec![("name", "steel")]);
+    assert!(!export_pathdb_to_axi_v1(&plain)?.contains("entity_lang_attribute"));
+    Ok(())
+}
+
+#[test]
+fn lang_columns_accept_out_of_order_writes_and_removals() -> Result<()> {
+    let mut db = PathDB::new();
+    let ids: Vec<u32> = (0..6)
+        .map(|i| db.add_entity("Material", vec![("name", format!("m{i}").as_str())]))
+        .collect();
+    for &id in ids.iter().rev() {
+        db.set_attr_lang(id, "label", "en", &format!("en {id}"))?;
+    }
+    db.set_attr_lang(ids[2], "label", "de", "de 2")?;
+    assert!(db.remove_attr_lang(ids[4], "label", "en"));
+    assert!(!db.remove_attr_lang(ids[4], "label", "en"));
+
+    let loaded = PathDB::from_bytes(&db.to_bytes()?)?;
+    for &id in &ids {
+        assert_eq!(loaded.attr_langs(id, "label"), db.attr_langs(id, "label"));
+    }
+    assert!(loaded.attr_langs(ids[4], "label").is_empty());
+    assert_eq!(
+        loaded.attr_langs(ids[2], "label"),
+        vec![
+            ("en".to_string(), "en 2".to_string()),
+            ("de".to_string(), "de 2".to_string())
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn proposal_keys_split_only_on_language_tags() {
+    assert_eq!(split_lang_key("label@en"), Some(("label", "en")));
+    assert_eq!(
+        split_lang_key("prefLabel@zh-Hant"),
+        Some(("prefLabel", "zh-Hant"))
+    );
+    assert_eq!(split_lang_key("label"), None);
+    assert_eq!(split_lang_key("@en"), None);
+    assert_eq!(split_lang_key("contact@example.com"), None);
+}
@@ -0,0 +1,106 @@
+use anyhow::Result;
+use axiograph_pathdb::axi_export::{export_pathdb_to_axi_v1, import_pathdb_from_axi_v1};
+use axiograph_pathdb::PathDB;
+use roaring::RoaringBitmap;
+
+fn workflows() -> Result<(PathDB, u32, u32)> {
+    let mut db = PathDB::new();
+    let deploy = db.add_entity("Workflow", vec![("name", "deploy")]);
+    let rollback = db.add_entity("Workflow", vec![("name", "rollback")]);
+    db.set_list_attr(deploy, "steps", &["build", "test", "release"])?;
+    db.set_list_attr(rollback, "steps", &["drain", "release"])?;
+    Ok((db, deploy, rollback))
+}
+
+#[test]
+fn list_attributes_support_positional_access_and_containment() -> Result<()> {
+    let (mut db, deploy, rollback) = workflows()?;
+    assert_eq!(
+        db.list_attr(deploy, "steps"),
+        Some(vec![
+            "build".to_string(),
+            "test".to_string(),
+            "release".to_string()
+        ])
+    );
+    assert_eq!(
+        db.list_attr_item(deploy, "steps", 1).as_deref(),
+        Some("test")
+    );
+    assert_eq!(db.list_attr_item(deploy, "steps", 3), None);
+    assert_eq!(db.list_attr_position(rollback, "steps", "release"), Some(1));
+    // Lists live next to the scalar column, not in it.
+    assert_eq!(db.get_entity(deploy).unwrap().attrs.get("steps"), None);
+
+    assert_eq!(
+        db.entities_with_list_containing("steps", "release"),
+        RoaringBitmap::from_iter([deploy, rollback])
+    );
+    assert_eq!(
+        db.entities_with_list_item_at("steps", 0, "drain"),
+        RoaringBitmap::from_iter([rollback])
+    );
+
+    assert_eq!(db.push_list_attr(rollback, "steps", "verify")?, 2);
+    assert_eq!(
+        db.entities_with_list_item_at("steps", 2, "verify"),
+        RoaringBitmap::from_iter([rollback])
+    );
+    assert!(db.remove_list_attr(deploy, "steps"));
+    assert!(!db.remove_list_attr(deploy, "steps"));
+    assert!(db.set_list_attr(99, "steps", &["x"]).is_err());
+    Ok(())
+}
+
+#[test]
+fn list_attributes_survive_snapshots_and_exports() -> Result<()> {
+    let (db, deploy, rollback) = workflows()?;
+    let loaded = PathDB::from_bytes(&db.to_bytes()?)?;
+    assert_eq!(
+        loaded.list_attr(deploy, "steps"),
+        db.list_attr(deploy, "steps")
+    );
+    assert_eq!(
+        loaded.entities_with_list_containing("steps", "drain"),
+        RoaringBitmap::from_iter([rollback])
+    );
+
+    let text = export_pathdb_to_axi_v1(&db)?;
+    assert!(text.contains("entity_list_item"));
+    let imported = import_pathdb_from_axi_v1(&text)?;
+    assert_eq!(
+        imported.list_attr(rollback, "steps"),
+        db.list_attr(rollback, "steps")
+    );
+    assert_eq!(export_pathdb_to_axi_v1(&imported)?, text);
+
+    // Without lists, neither format changes.
+    let mut plain = PathDB::new();
+    plain.add_entity("Workflow", vec![("name", "deploy")]);
+    assert!(!export_pathdb_to_axi_v1(&plain)?.contains("entity_list_item"));
+    Ok(())
+}
+
+#[test]
+fn list_attributes_accept_out_of_order_writes() -> Result<()> {
+    let mut db = PathDB::new();
+    let ids: Vec<u32> = (0..5)
+        .map(|i| db.add_entity("Workflow", vec![("name", format!("w{i}").as_str())]))
+        .collect();
+    for &id in ids.iter().rev() {
+        db.push_list_attr(id, "steps", &format!("step {id}"))?;
+    }
+    db.push_list_attr(ids[1], "steps", "release")?;
+    db.set_list_attr(ids[3], "steps", &["release"])?;
+
+    let loaded = PathDB::from_bytes(&db.to_bytes()?)?;
+    assert_eq!(
+        loaded.list_attr(ids[1], "steps"),
+        Some(vec![format!("step {}", ids[1]), "release".to_string()])
+    );
+    assert_eq!(
+        loaded.entities_with_list_containing("steps", "release"),
+        RoaringBitmap::from_iter([ids[1], ids[3]])
+    );
+    Ok(())
+}
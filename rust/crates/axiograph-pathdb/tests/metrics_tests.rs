@@ -0,0 +1,75 @@
+use axiograph_pathdb::metrics::{self, MetricsRegistry};
+use axiograph_pathdb::{PathDB, PathQuery};
+
+fn cache_count(cache: &str, result: &str) -> u64 {
+    metrics::global()
+        .counter(
+            "axiograph_cache_requests_total",
+            "Cache lookups by cache and outcome.",
+            &[("cache", cache), ("result", result)],
+        )
+        .get()
+}
+
+#[test]
+fn renders_prometheus_text_format() {
+    let registry = MetricsRegistry::new();
+    registry
+        .counter("requests_total", "Requests.", &[("route", "q\"1")])
+        .add(3);
+    registry.gauge("pending", "Pending changes.", &[]).set(-2);
+    let h = registry.histogram("latency_seconds", "Latency.", &[("op", "read")]);
+    h.observe(0.002);
+    h.observe(0.2);
+    h.observe(100.0);
+
+    let text = registry.render_prometheus();
+    assert!(text.contains("# TYPE requests_total counter\n"));
+    assert!(text.contains("requests_total{route=\"q\\\"1\"} 3\n"));
+    assert!(text.contains("# HELP pending Pending changes.\n"));
+    assert!(text.contains("pending -2\n"));
+    assert!(text.contains("# TYPE latency_seconds histogram\n"));
+    assert!(text.contains("latency_seconds_bucket{op=\"read\",le=\"0.001\"} 0\n"));
+    assert!(text.contains("latency_seconds_bucket{op=\"read\",le=\"0.005\"} 1\n"));
+    assert!(text.contains("latency_seconds_bucket{op=\"read\",le=\"0.5\"} 2\n"));
+    assert!(text.contains("latency_seconds_bucket{op=\"read\",le=\"+Inf\"} 3\n"));
+    assert!(text.contains("latency_seconds_count{op=\"read\"} 3\n"));
+    assert!((h.sum() - 100.202).abs() < 1e-9);
+}
+
+#[test]
+fn series_are_shared_by_name_and_labels() {
+    let registry = MetricsRegistry::new();
+    registry.counter("c", "C.", &[("k", "a")]).inc();
+    registry.counter("c", "C.", &[("k", "a")]).inc();
+    registry.counter("c", "C.", &[("k", "b")]).inc();
+    assert_eq!(registry.counter("c", "C.", &[("k", "a")]).get(), 2);
+    assert_eq!(registry.counter("c", "C.", &[("k", "b")]).get(), 1);
+}
+
+#[test]
+#[should_panic(expected = "already registered as counter")]
+fn kind_conflicts_are_rejected() {
+    let registry = MetricsRegistry::new();
+    registry.counter("m", "M.", &[]);
+    registry.gauge("m", "M.", &[]);
+}
+
+#[test]
+fn pathdb_records_index_builds_queries_and_cache_hits() {
+    let mut db = PathDB::new();
+    let a = db.add_entity("Person", vec![("name", "a")]);
+    let b = db.add_entity("Person", vec![("name", "b")]);
+    db.add_relation("knows", a, b, 1.0, vec![]);
+
+    let before_hits = cache_count("path_index", "hit");
+    db.build_indexes();
+    assert!(db.follow_path(a, &["knows"]).contains(b));
+    assert!(cache_count("path_index", "hit") > before_hits);
+
+    db.execute(&PathQuery::SelectByType("Person".to_string()));
+
+    let text = metrics::global().render_prometheus();
+    assert!(text.contains("axiograph_pathdb_index_build_seconds_count{index=\"path\"}"));
+    assert!(text.contains("axiograph_pathdb_query_seconds_count "));
+}
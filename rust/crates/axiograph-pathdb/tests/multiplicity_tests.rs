@@ -0,0 +1,171 @@
+use anyhow::Result;
+use axiograph_pathdb::{
+    Multiplicity, MultiplicityBound, OnMultiplicityConflict, PathDB, PathDbError,
+};
+
+fn rpcs() -> (PathDB, [u32; 4]) {
+    let mut db = PathDB::new();
+    let get = db.add_entity("ProtoRpc", vec![("name", "GetUser")]);
+    let list = db.add_entity("ProtoRpc", vec![("name", "ListUsers")]);
+    let req = db.add_entity("ProtoMessage", vec![("name", "GetUserRequest")]);
+    let req2 = db.add_entity("ProtoMessage", vec![("name", "GetUserRequestV2")]);
+    (db, [get, list, req, req2])
+}
+
+#[test]
+fn declared_multiplicity_rejects_or_resolves_extra_edges() -> Result<()> {
+    let (mut db, [get, list, req, req2]) = rpcs();
+    assert!(db.declare_multiplicity("proto_rpc_request", Multiplicity::MANY_TO_ONE));
+    assert!(!db.declare_multiplicity("proto_rpc_request", Multiplicity::MANY_TO_ONE));
+
+    let edge = db.try_add_relation("proto_rpc_request", get, req, 1.0, vec![])?;
+    // Re-ingesting the same edge returns it instead of duplicating it.
+    assert_eq!(
+        db.try_add_relation("proto_rpc_request", get, req, 1.0, vec![])?,
+        edge
+    );
+    assert_eq!(db.relations.len(), 1);
+    // Many sources may share a target.
+    db.try_add_relation("proto_rpc_request", list, req, 1.0, vec![])?;
+
+    let err = db
+        .try_add_relation("proto_rpc_request", get, req2, 1.0, vec![])
+        .unwrap_err();
+    let PathDbError::MultiplicityViolation(violation) = &err else {
+        panic!("unexpected error: {err}");
+    };
+    assert_eq!(violation.bound, MultiplicityBound::Targets);
+    assert_eq!(violation.existing, vec![req]);
+    assert!(err.to_string().contains("at most 1 target(s) per source"));
+    assert_eq!(db.relations.len(), 2);
+
+    let skipped = db.insert_relation(
+        "proto_rpc_request",
+        get,
+        req2,
+        1.0,
+        vec![],
+        OnMultiplicityConflict::Skip,
+    )?;
+    assert_eq!((skipped.relation, skipped.added), (None, false));
+    assert_eq!(skipped.violations.len(), 1);
+    assert_eq!(db.follow_one(get, "proto_rpc_request").len(), 1);
+
+    let replaced = db.insert_relation(
+        "proto_rpc_request",
+        get,
+        req2,
+        1.0,
+        vec![],
+        OnMultiplicityConflict::Replace,
+    )?;
+    assert!(replaced.added);
+    assert_eq!(
+        db.follow_one(get, "proto_rpc_request")
+            .iter()
+            .collect::<Vec<_>>(),
+        vec![req2]
+    );
+    // Undeclared relations are not constrained.
+    db.try_add_relation("mentions", get, req, 1.0, vec![])?;
+    db.try_add_relation("mentions", get, req2, 1.0, vec![])?;
+    Ok(())
+}
+
+#[test]
+fn stored_violations_are_audited_and_declarations_survive_snapshots() -> Result<()> {
+    let (mut db, [get, list, req, req2]) = rpcs();
+    db.add_relation("paired_with", get, req, 1.0, vec![]);
+    db.add_relation("paired_with", list, req, 1.0, vec![]);
+    db.add_relation("paired_with", get, req2, 1.0, vec![]);
+    db.declare_multiplicity("paired_with", Multiplicity::ONE_TO_ONE);
+
+    let violations = db.multiplicity_violations();
+    assert_eq!(violations.len(), 2);
+    assert!(violations
+        .iter()
+        .any(|v| v.bound == MultiplicityBound::Targets && v.source == get));
+    assert!(violations
+        .iter()
+        .any(|v| v.bound == MultiplicityBound::Sources && v.target == req));
+
+    let loaded = PathDB::from_bytes(&db.to_bytes()?)?;
+    assert_eq!(
+        loaded.multiplicity("paired_with"),
+        Some(Multiplicity::ONE_TO_ONE)
+    );
+    assert_eq!(loaded.multiplicity_violations(), violations);
+    Ok(())
+}
+
+#[test]
+fn axi_functional_constraints_are_enforced_on_insert() -> Result<()> {
+    let text = r#"
+module ProtoMultiplicity
+
+schema Proto:
+  object Rpc
+  object Message
+  object Context
+  relation proto_rpc_request(from: Rpc, to: Message, ctx: Context)
+  relation proto_rpc_response(from: Rpc, to: Message)
+
+theory Shape on Proto:
+  constraint functional proto_rpc_request.from -> proto_rpc_request.to
+  constraint at_most 2 proto_rpc_response.to -> proto_rpc_response.from
+
+instance I of Proto:
+  Rpc = {get_user, list_users, delete_user}
+  Message = {req, req_v2, resp}
+  Context = {ingest}
+  proto_rpc_request = {
+    (from=get_user, to=req, ctx=ingest)
+  }
+  proto_rpc_response = {
+    (from=get_user, to=resp),
+    (from=list_users, to=resp)
+  }
+"#;
+    let m = axiograph_dsl::axi_v1::parse_axi_v1(text)?;
+    let mut db = PathDB::new();
+    axiograph_pathdb::axi_module_import::import_axi_schema_v1_module_into_pathdb(&mut db, &m)?;
+    assert_eq!(
+        db.multiplicity("proto_rpc_request"),
+        Some(Multiplicity::MANY_TO_ONE)
+    );
+    assert_eq!(
+        db.multiplicity("proto_rpc_response"),
+        Some(Multiplicity {
+            max_targets: None,
+            max_sources: Some(2),
+        })
+    );
+
+    let id = |db: &PathDB, name: &str| {
+        let key = db.interner.id_of("name").unwrap();
+        let value = db.interner.id_of(name).unwrap();
+        db.entities
+            .entities_with_attr_value(key, value)
+            .min()
+            .unwrap()
+    };
+    let (get_user, delete_user) = (id(&db, "get_user"), id(&db, "delete_user"));
+    let (req, req_v2, resp) = (id(&db, "req"), id(&db, "req_v2"), id(&db, "resp"));
+    assert!(db.multiplicity_violations().is_empty());
+
+    db.try_add_relation("proto_rpc_request", get_user, req, 1.0, vec![])?;
+    assert!(db
+        .try_add_relation("proto_rpc_request", get_user, req_v2, 1.0, vec![])
+        .is_err());
+    assert!(db
+        .try_add_relation("proto_rpc_response", delete_user, resp, 1.0, vec![])
+        .is_err());
+
+    // Re-derived from the constraint entities on load.
+    let loaded = PathDB::from_bytes(&db.to_bytes()?)?;
+    assert_eq!(
+        loaded.multiplicity("proto_rpc_request"),
+        Some(Multiplicity::MANY_TO_ONE)
+    );
+    Ok(())
+}
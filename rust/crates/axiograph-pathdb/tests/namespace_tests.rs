@@ -0,0 +1,136 @@
+use axiograph_pathdb::namespace::ATTR_NAMESPACE;
+use axiograph_pathdb::{PathDB, PathQuery};
+
+fn two_tenants() -> (PathDB, [u32; 2], [u32; 2]) {
+    let mut db = PathDB::new();
+    let a0 = db.add_entity_in("team_a", "Service", vec![("name", "billing")]);
+    let a1 = db.add_entity_in("team_a", "Service", vec![("name", "ledger")]);
+    let b0 = db.add_entity_in("team_b", "Service", vec![("name", "search")]);
+    let b1 = db.add_entity_in("team_b", "Database", vec![("name", "index")]);
+    db.add_relation_in("team_a", "calls", a0, a1, 1.0, vec![])
+        .unwrap();
+    db.add_relation_in("team_b", "reads", b0, b1, 1.0, vec![])
+        .unwrap();
+    db.build_indexes();
+    (db, [a0, a1], [b0, b1])
+}
+
+#[test]
+fn relations_cannot_cross_namespaces() {
+    let (mut db, a, b) = two_tenants();
+    assert!(db
+        .add_relation_in("team_a", "calls", a[0], b[0], 1.0, vec![])
+        .is_err());
+    assert_eq!(db.entity_namespace(b[1]).as_deref(), Some("team_b"));
+    assert_eq!(db.relation_namespace(0).as_deref(), Some("team_a"));
+    assert_eq!(db.get_entity(a[0]).unwrap().attrs[ATTR_NAMESPACE], "team_a");
+}
+
+#[test]
+fn scoped_queries_and_stats() {
+    let (db, _, b) = two_tenants();
+
+    let services = PathQuery::SelectByType("Service".to_string());
+    assert_eq!(db.execute(&services).len(), 3);
+    assert_eq!(db.execute_in_namespace("team_b", &services).len(), 1);
+
+    let follow = PathQuery::SelectRelated(b[0], "reads".to_string());
+    assert!(db.execute_in_namespace("team_b", &follow).contains(b[1]));
+    // Anchored outside the namespace: nothing is visible.
+    assert!(db.execute_in_namespace("team_a", &follow).is_empty());
+    assert!(db.execute_in_namespace("missing", &services).is_empty());
+
+    let stats = db.namespace_stats();
+    assert_eq!(db.namespaces(), vec!["team_a", "team_b"]);
+    assert_eq!(stats["team_a"].entities, 2);
+    assert_eq!(stats["team_a"].relations, 1);
+    assert_eq!(stats["team_b"].entity_types["Database"], 1);
+    assert_eq!(stats["team_b"].relation_types["reads"], 1);
+}
+
+#[test]
+fn extract_namespace_filters_exports() {
+    let (db, _, _) = two_tenants();
+    let extracted = db.extract_namespace("team_a");
+
+    assert_eq!(extracted.entities.len(), 2);
+    assert_eq!(extracted.relations.len(), 1);
+    assert_eq!(extracted.follow_one(0, "calls").len(), 1);
+    assert!(extracted.find_by_type("Database").is_none());
+
+    assert_eq!(extracted.namespaces(), vec!["team_a"]);
+    assert_eq!(
+        extracted
+            .get_entity(0)
+            .unwrap()
+            .attrs
+            .get("name")
+            .map(String::as_str),
+        Some("billing")
+    );
+    // The filtered snapshot goes through the regular export paths.
+    assert!(axiograph_pathdb::axi_export::export_pathdb_to_axi_v1(&extracted).is_ok());
+}
+
+#[test]
+fn extract_namespace_copies_every_attribute_column() {
+    let (mut db, a, b) = two_tenants();
+    db.set_list_attr(a[0], "steps", &["auth", "charge"])
+        .unwrap();
+    db.set_attr_lang(a[0], "label", "de", "Abrechnung").unwrap();
+    db.attach_blob(a[0], "diagram", b"svg").unwrap();
+    db.attach_blob(b[0], "diagram", b"other").unwrap();
+
+    let extracted = db.extract_namespace("team_a");
+    let loaded = PathDB::from_bytes(&extracted.to_bytes().unwrap()).unwrap();
+    assert_eq!(
+        loaded.list_attr(0, "steps").unwrap(),
+        vec!["auth", "charge"]
+    );
+    assert_eq!(
+        loaded.get_attr_lang(0, "label", "de").as_deref(),
+        Some("Abrechnung")
+    );
+    assert_eq!(loaded.entity_blob(0, "diagram"), Some(&b"svg"[..]));
+    // Only the namespace's own blobs come along.
+    assert_eq!(loaded.blobs().len(), 1);
+}
+
+#[test]
+fn traversals_cannot_hop_through_another_namespace() {
+    let (mut db, a, b) = two_tenants();
+    // a0 (team_a) -> b1 (team_b) -> a1 (team_a), written around
+    // `add_relation_in` and even tagged with team_a.
+    db.add_relation("uses", a[0], b[1], 1.0, vec![(ATTR_NAMESPACE, "team_a")]);
+    db.add_relation("uses", b[1], a[1], 1.0, vec![(ATTR_NAMESPACE, "team_a")]);
+    db.build_indexes();
+
+    let path = PathQuery::FollowPath {
+        start: a[0],
+        path: vec!["uses".to_string(), "uses".to_string()],
+    };
+    assert!(db.execute(&path).contains(a[1]));
+    assert!(db.execute_in_namespace("team_a", &path).is_empty());
+
+    let find = PathQuery::FindPaths {
+        from: a[0],
+        to: a[1],
+        max_depth: 3,
+    };
+    // Only the direct, tagged `calls` edge is left.
+    assert!(db.execute_in_namespace("team_a", &find).contains(a[1]));
+    let into_b = PathQuery::FindPaths {
+        from: a[0],
+        to: b[1],
+        max_depth: 3,
+    };
+    assert!(!db.execute(&into_b).is_empty());
+    assert!(db.execute_in_namespace("team_a", &into_b).is_empty());
+    assert!(db
+        .execute_in_namespace(
+            "team_a",
+            &PathQuery::SelectRelated(a[0], "uses".to_string())
+        )
+        .is_empty());
+    assert!(!db.namespace_scope("team_a").contains_entity(b[0]));
+}
@@ -0,0 +1,172 @@
+use anyhow::Result;
+use axiograph_pathdb::axi_meta::{ATTR_AXI_RELATION, ATTR_AXI_SCHEMA};
+use axiograph_pathdb::{ConstraintViolation, Overlay, PathDB, StagedChange};
+use roaring::RoaringBitmap;
+
+#[test]
+fn overlay_changes_reachability_without_touching_the_base() -> Result<()> {
+    let mut base = PathDB::new();
+    let a = base.add_entity("Node", vec![("name", "a")]);
+    let b = base.add_entity("Node", vec![("name", "b")]);
+    let c = base.add_entity("Node", vec![("name", "c")]);
+    base.add_relation("r", a, b, 1.0, Vec::new());
+    base.add_relation("r", b, c, 1.0, Vec::new());
+    base.build_indexes();
+
+    let mut overlay = base.overlay();
+    let d = overlay.add_entity("Node", vec![("name", "d")]);
+    assert_eq!(d, 3);
+    overlay.add_relation("r", b, d, 0.9, Vec::new())?;
+    assert!(overlay.add_relation("r", b, 99, 1.0, Vec::new()).is_err());
+
+    let diff = overlay.reachability_diff(a, &["r", "r"]);
+    assert_eq!(diff.gained, RoaringBitmap::from_iter([d]));
+    assert!(diff.lost.is_empty());
+    // Layered traversal; the view answers the rest of the query API.
+    assert_eq!(
+        overlay.follow_path(a, &["r", "r"]),
+        RoaringBitmap::from_iter([c, d])
+    );
+    assert_eq!(
+        overlay.view()?.follow_path(a, &["r", "r"]),
+        RoaringBitmap::from_iter([c, d])
+    );
+
+    assert_eq!(overlay.remove_relation(a, "r", b), 1);
+    assert_eq!(overlay.remove_relation(a, "r", b), 0);
+    assert_eq!(
+        overlay.reachability_diff(a, &["r"]).lost,
+        RoaringBitmap::from_iter([b])
+    );
+    overlay.remove_entity(c)?;
+    assert!(!overlay.find_by_type("Node").unwrap().contains(c));
+    assert!(overlay.follow_path(b, &["r"]).contains(d));
+    assert!(!overlay.follow_path(b, &["r"]).contains(c));
+
+    assert_eq!(overlay.changes().len(), 4);
+    assert!(matches!(
+        overlay.changes()[3],
+        StagedChange::RemoveEntity { entity } if entity == c
+    ));
+
+    // Base is unchanged.
+    assert_eq!(base.entities.len(), 3);
+    assert_eq!(base.relations.len(), 2);
+    assert_eq!(
+        base.follow_path(a, &["r", "r"]),
+        RoaringBitmap::from_iter([c])
+    );
+    Ok(())
+}
+
+#[test]
+fn overlay_check_reports_only_newly_broken_constraints() -> Result<()> {
+    let text = r#"
+module OverlayTest
+
+schema S:
+  object Employee
+  relation ReportsTo(employee: Employee, manager: Employee)
+
+theory T on S:
+  constraint functional ReportsTo.employee -> ReportsTo.manager
+
+instance I of S:
+  Employee = {alice, bob, carol}
+  ReportsTo = {
+    (employee=alice, manager=bob)
+  }
+"#;
+    let m = axiograph_dsl::axi_v1::parse_axi_v1(text)?;
+    let mut base = PathDB::new();
+    axiograph_pathdb::axi_module_import::import_axi_schema_v1_module_into_pathdb(&mut base, &m)?;
+    let id = |name: &str| {
+        let key = base.interner.id_of("name").unwrap();
+        let value = base.interner.id_of(name).unwrap();
+        base.entities
+            .entities_with_attr_value(key, value)
+            .min()
+            .unwrap()
+    };
+    let (alice, carol) = (id("alice"), id("carol"));
+    let original = base
+        .fact_nodes_by_axi_schema_relation("S", "ReportsTo")
+        .min()
+        .unwrap();
+
+    let mut overlay = base.overlay();
+    assert!(overlay.check()?.ok());
+
+    // alice reports to carol as well: breaks the functional dependency.
+    let fact = overlay.add_entity(
+        "ReportsTo",
+        vec![
+            ("name", "ReportsTo_fact_whatif"),
+            (ATTR_AXI_SCHEMA, "S"),
+            (ATTR_AXI_RELATION, "ReportsTo"),
+        ],
+    );
+    overlay.add_relation("employee", fact, alice, 1.0, Vec::new())?;
+    overlay.add_relation("manager", fact, carol, 1.0, Vec::new())?;
+
+    let check = overlay.check()?;
+    assert!(!check.ok());
+    assert_eq!(
+        check.violations,
+        vec![ConstraintViolation::Functional {
+            schema: "S".to_string(),
+            relation: "ReportsTo".to_string(),
+            src_field: "employee".to_string(),
+            dst_field: "manager".to_string(),
+            facts: [original, fact],
+        }]
+    );
+    assert!(axiograph_pathdb::overlay::constraint_violations(&base).is_empty());
+    Ok(())
+}
+
+#[test]
+fn layered_reads_agree_with_the_materialized_view() -> Result<()> {
+    let mut base = PathDB::new();
+    let a = base.add_entity("Node", vec![("name", "a")]);
+    let b = base.add_entity("Node", vec![("name", "b")]);
+    let c = base.add_entity("Node", vec![("name", "c")]);
+    base.add_relation("r", a, b, 1.0, Vec::new());
+    base.add_relation("r", a, c, 1.0, Vec::new());
+    base.add_relation("r", b, c, 1.0, Vec::new());
+    base.register_inverse("r", "r_of")?;
+    base.build_indexes();
+
+    let mut overlay = base.overlay();
+    let d = overlay.add_entity("Node", vec![("name", "d")]);
+    overlay.add_relation("r", d, a, 1.0, Vec::new())?;
+    overlay.add_relation("r", c, d, 1.0, Vec::new())?;
+    assert_eq!(overlay.remove_relation(a, "r", c), 1);
+    overlay.remove_entity(b)?;
+    assert_eq!(overlay.follow_one(a, "r_of"), RoaringBitmap::from_iter([d]));
+
+    let agree = |overlay: &Overlay| -> Result<()> {
+        let view = overlay.view()?;
+        for start in [a, b, c, d] {
+            for path in [&["r"][..], &["r", "r"], &["r_of"], &["r_of", "r"]] {
+                assert_eq!(
+                    overlay.follow_path(start, path),
+                    view.follow_path(start, path),
+                    "{start} {path:?}"
+                );
+            }
+        }
+        assert_eq!(
+            overlay.find_by_type("Node"),
+            view.find_by_type("Node").cloned()
+        );
+        Ok(())
+    };
+    agree(&overlay)?;
+    // Staging after the view exists keeps both in step.
+    overlay.add_relation("r", a, c, 1.0, Vec::new())?;
+    overlay.remove_entity(d)?;
+    agree(&overlay)?;
+    assert_eq!(overlay.into_view()?.entities.len() as u32, d + 1);
+    Ok(())
+}
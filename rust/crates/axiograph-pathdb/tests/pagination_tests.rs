@@ -0,0 +1,115 @@
+use axiograph_pathdb::pagination::{page_bitmap, page_rows, PageCursor};
+use axiograph_pathdb::{PathDB, PathDbError, PathQuery};
+use roaring::RoaringBitmap;
+
+/// 10 `Node`s interleaved with 5 `Other`s, so `Node` ids are not contiguous.
+fn mixed_db() -> (PathDB, Vec<u32>) {
+    let mut db = PathDB::new();
+    let mut nodes = Vec::new();
+    for i in 0..10 {
+        nodes.push(db.add_entity("Node", vec![("name", format!("n{i}").as_str())]));
+        if i % 2 == 0 {
+            db.add_entity("Other", vec![]);
+        }
+    }
+    db.build_indexes();
+    (db, nodes)
+}
+
+#[test]
+fn execute_paged_walks_the_whole_result_once() {
+    let (db, nodes) = mixed_db();
+    let query = PathQuery::SelectByType("Node".to_string());
+
+    let mut seen = Vec::new();
+    let mut cursor: Option<PageCursor> = None;
+    let mut pages = 0;
+    loop {
+        let page = db.execute_paged(&query, cursor.as_ref(), 3);
+        assert_eq!(page.total, 10);
+        assert!(page.items.len() <= 3);
+        seen.extend(page.items.iter().copied());
+        pages += 1;
+        match page.next {
+            // Round-trip through the string form, as a REST client would.
+            Some(next) => cursor = Some(PageCursor::decode(&next.encode()).unwrap()),
+            None => break,
+        }
+    }
+    assert_eq!(pages, 4);
+    assert_eq!(seen, nodes);
+
+    // An exact fit ends without an empty trailing page.
+    let first = db.execute_paged(&query, None, 10);
+    assert!(first.is_last());
+    assert_eq!(first.items, nodes);
+
+    let views = db.execute_paged_views(&query, None, 2);
+    assert_eq!(
+        views
+            .items
+            .iter()
+            .map(|v| v.attrs["name"].as_str())
+            .collect::<Vec<_>>(),
+        ["n0", "n1"]
+    );
+}
+
+#[test]
+fn cursors_survive_new_entities() {
+    let (mut db, nodes) = mixed_db();
+    let query = PathQuery::SelectByType("Node".to_string());
+    let first = db.execute_paged(&query, None, 4);
+    let added = db.add_entity("Node", vec![("name", "late")]);
+    db.build_indexes();
+
+    let rest = db.execute_paged(&query, first.next.as_ref(), 100);
+    assert_eq!(rest.total, 11);
+    let mut expected = nodes[4..].to_vec();
+    expected.push(added);
+    assert_eq!(rest.items, expected);
+}
+
+#[test]
+fn execute_iter_resolves_lazily() {
+    let (db, nodes) = mixed_db();
+    let query = PathQuery::SelectByType("Node".to_string());
+    let mut iter = db.execute_iter(&query);
+    assert_eq!(iter.size_hint().1, Some(10));
+    let second = iter.nth(1).expect("second view");
+    assert_eq!(second.id, nodes[1]);
+    assert_eq!(iter.count(), 8);
+
+    // Ids with no entity are skipped.
+    let ids = db.entity_views([nodes[0], 9_999, nodes[2]]);
+    assert_eq!(ids.map(|v| v.id).collect::<Vec<_>>(), [nodes[0], nodes[2]]);
+}
+
+#[test]
+fn page_helpers_and_cursor_encoding() {
+    let ids: RoaringBitmap = [1u32, 5, 9, u32::MAX].into_iter().collect();
+    let page = page_bitmap(&ids, None, 3);
+    assert_eq!(page.items, [1, 5, 9]);
+    let last = page_bitmap(&ids, page.next.as_ref(), 3);
+    assert_eq!(last.items, [u32::MAX]);
+    assert!(last.is_last());
+
+    let rows = ["a", "b", "c", "d", "e"];
+    let page = page_rows(&rows, None, 2);
+    assert_eq!(page.items, ["a", "b"]);
+    let page = page_rows(&rows, page.next.as_ref(), 2);
+    assert_eq!(page.items, ["c", "d"]);
+    let page = page_rows(&rows, page.next.as_ref(), 2);
+    assert_eq!(page.items, ["e"]);
+    assert!(page.is_last());
+    assert_eq!(page.map(str::len).items, [1]);
+
+    let cursor: PageCursor = "pc1.2a".parse().unwrap();
+    assert_eq!(cursor.to_string(), "pc1.2a");
+    for bad in ["", "2a", "pc1.", "pc1.zz", "pc2.2a"] {
+        assert!(
+            matches!(PageCursor::decode(bad), Err(PathDbError::InvalidCursor(_))),
+            "{bad:?}"
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use anyhow::Result;
+use axiograph_pathdb::axi_export::{export_pathdb_to_axi_v1, import_pathdb_from_axi_v1};
+use axiograph_pathdb::certificate::CertificatePayloadV2;
+use axiograph_pathdb::{FixedPointProbability, PathDB, PathDbError};
+
+fn db() -> PathDB {
+    let mut db = PathDB::new();
+    let a = db.add_entity("Tool", vec![("name", "a")]);
+    let b = db.add_entity("Tool", vec![("name", "b")]);
+    let c = db.add_entity("Tool", vec![("name", "c")]);
+    db.add_relation("uses", a, b, 0.95, vec![]);
+    db.add_relation("uses", b, c, 0.4, vec![("source", "llm")]);
+    db.add_relation("uses", a, c, 0.8, vec![]);
+    db.build_indexes();
+    db
+}
+
+fn confidences(db: &PathDB) -> Vec<f32> {
+    (0..db.relations.len() as u32)
+        .map(|id| db.relations.get_relation(id).unwrap().confidence)
+        .collect()
+}
+
+#[test]
+fn partition_splits_relations_at_the_threshold() -> Result<()> {
+    let db = db();
+    let partition = db.partition_by_confidence(0.8)?;
+    let proof = &partition.proof;
+    assert_eq!(proof.threshold_fp, FixedPointProbability::from_f32(0.8));
+    assert_eq!(
+        (
+            proof.source.relation_count,
+            proof.core.relation_count,
+            proof.evidence.relation_count,
+            proof.entity_count
+        ),
+        (3, 2, 1, 3)
+    );
+
+    // The threshold is inclusive; entities are kept on both sides.
+    let core = import_pathdb_from_axi_v1(&partition.core_axi)?;
+    let evidence = import_pathdb_from_axi_v1(&partition.evidence_axi)?;
+    assert_eq!(confidences(&core), vec![0.95, 0.8]);
+    assert_eq!(confidences(&evidence), vec![0.4]);
+    assert_eq!(core.entities.len(), 3);
+    assert_eq!(evidence.entities.len(), 3);
+
+    let source_axi = export_pathdb_to_axi_v1(&db)?;
+    assert_eq!(
+        proof.source.axi_digest_v1,
+        axiograph_dsl::digest::axi_digest_v1(&source_axi)
+    );
+    assert!(proof
+        .check(&source_axi, &partition.core_axi, &partition.evidence_axi)
+        .is_ok());
+
+    let cert = partition.certificate();
+    assert_eq!(
+        cert.anchor.as_ref().map(|a| a.axi_digest_v1.as_str()),
+        Some(proof.core.axi_digest_v1.as_str())
+    );
+    assert!(matches!(
+        cert.payload,
+        CertificatePayloadV2::ConfidencePartitionV1 { .. }
+    ));
+    let json = serde_json::to_value(&cert)?;
+    assert_eq!(json["kind"], "confidence_partition_v1");
+
+    // Partitioning does not touch the input.
+    assert_eq!(confidences(&db), vec![0.95, 0.4, 0.8]);
+    Ok(())
+}
+
+#[test]
+fn partition_check_rejects_tampered_exports() -> Result<()> {
+    let db = db();
+    let partition = db.partition_by_confidence(0.8)?;
+    let source_axi = export_pathdb_to_axi_v1(&db)?;
+
+    // Swapped sides: digests no longer match.
+    let err = partition
+        .proof
+        .check(&source_axi, &partition.evidence_axi, &partition.core_axi)
+        .unwrap_err();
+    assert!(err.contains("digest"), "{err}");
+
+    // A proof whose threshold was raised after the fact.
+    let mut raised = partition.proof.clone();
+    raised.threshold_fp = FixedPointProbability::from_f32(0.9);
+    let err = raised
+        .check(&source_axi, &partition.core_axi, &partition.evidence_axi)
+        .unwrap_err();
+    assert!(err.contains("below the threshold"), "{err}");
+
+    assert!(matches!(
+        db.partition_by_confidence(1.5),
+        Err(PathDbError::InvalidThreshold(_))
+    ));
+    Ok(())
+}
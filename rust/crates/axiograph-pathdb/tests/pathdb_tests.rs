@@ -439,3 +439,53 @@ fn test_large_scale_graph_ingest_and_follow_path() {
         (queries as f64) / dt.as_secs_f64()
     );
 }
+
+#[test]
+fn test_empty_with_declarations_keeps_declarations_not_data() {
+    use std::sync::Arc;
+
+    let mut db = PathDB::new();
+    let bolt = db.add_entity("Bolt", vec![("name", "M6"), ("sku", "b-1")]);
+    let acme = db.add_entity("Supplier", vec![("name", "Acme")]);
+    db.add_relation("supplies", acme, bolt, 1.0, vec![]);
+    db.declare_subtype("Bolt", "Part");
+    db.register_inverse("supplies", "suppliedBy").unwrap();
+    db.declare_multiplicity("supplies", Multiplicity::ONE_TO_MANY);
+    db.register_virtual_relation(
+        "anyPart",
+        VirtualRelationDef::Predicate {
+            target_type: "Part".to_string(),
+            predicate: Arc::new(|_: &PathDB, _: u32, _: u32| true),
+        },
+    )
+    .unwrap();
+    db.declare_composite_index("Part", "sku");
+    db.index_attr_values("sku");
+    let policy = LabelPolicy {
+        keys: vec!["sku".to_string()],
+        languages: vec!["de".to_string()],
+    };
+    db.set_label_policy(policy.clone());
+
+    let mut fresh = db.empty_with_declarations();
+    assert!(fresh.find_by_type("Bolt").is_none());
+    assert_eq!(fresh.relations.len(), 0);
+    assert_eq!(fresh.label_policy(), &policy);
+    assert_eq!(fresh.inverse_of("supplies").as_deref(), Some("suppliedBy"));
+    assert_eq!(
+        fresh.multiplicity("supplies"),
+        Some(Multiplicity::ONE_TO_MANY)
+    );
+    assert_eq!(fresh.virtual_relation_names(), vec!["anyPart"]);
+    assert!(fresh.has_composite_index("Part", "sku"));
+
+    // New data picks the declarations up.
+    let bolt = fresh.add_entity("Bolt", vec![("sku", "b-2")]);
+    let sku = fresh.interner.id_of("sku").unwrap();
+    let b2 = fresh.interner.id_of("b-2").unwrap();
+    assert!(fresh.find_by_type("Part").unwrap().contains(bolt));
+    assert_eq!(
+        fresh.entities.indexed_attr_value(sku, b2).map(|b| b.len()),
+        Some(1)
+    );
+}
@@ -0,0 +1,103 @@
+use axiograph_pathdb::{
+    ConfidenceCombiner, PathDB, PathDbError, PathQuery, PathQueryRequest, PATH_QUERY_JSON_VERSION,
+};
+use serde_json::json;
+
+#[test]
+fn queries_roundtrip_through_the_documented_json_shape() {
+    let query = PathQuery::Union(
+        Box::new(PathQuery::WithConfidence {
+            base: Box::new(PathQuery::FollowPath {
+                start: 0,
+                path: vec!["parentOf".to_string(), "parentOf".to_string()],
+            }),
+            min_confidence: 0.5,
+        }),
+        Box::new(PathQuery::WithPathConfidence {
+            base: Box::new(PathQuery::SelectRelated(1, "parentOf".to_string())),
+            min_path_confidence: 0.25,
+            combiner: ConfidenceCombiner::Min,
+        }),
+    );
+    let value = serde_json::to_value(PathQueryRequest::new(query)).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "version": PATH_QUERY_JSON_VERSION,
+            "query": {
+                "op": "union",
+                "left": {
+                    "op": "with_confidence",
+                    "base": { "op": "follow_path", "start": 0, "path": ["parentOf", "parentOf"] },
+                    "min_confidence": 0.5,
+                },
+                "right": {
+                    "op": "with_path_confidence",
+                    "base": { "op": "select_related", "source": 1, "rel_type": "parentOf" },
+                    "min_path_confidence": 0.25,
+                    "combiner": "min",
+                },
+            },
+        })
+    );
+
+    // A posted query runs like the one built in Rust.
+    let mut db = PathDB::new();
+    let a = db.add_entity("Person", vec![("name", "a")]);
+    let b = db.add_entity("Person", vec![("name", "b")]);
+    let c = db.add_entity("Person", vec![("name", "c")]);
+    db.add_relation("parentOf", a, b, 0.9, vec![]);
+    db.add_relation("parentOf", b, c, 0.9, vec![]);
+    db.build_indexes();
+    let request = PathQueryRequest::from_json(&value.to_string()).unwrap();
+    assert_eq!(
+        db.execute(&request.query).iter().collect::<Vec<_>>(),
+        vec![c]
+    );
+
+    // `combiner` defaults to `product`.
+    let query: PathQuery = serde_json::from_value(json!({
+        "op": "with_path_confidence",
+        "base": { "op": "select_by_type", "type_name": "Person" },
+        "min_path_confidence": 0.1,
+    }))
+    .unwrap();
+    assert!(matches!(
+        query,
+        PathQuery::WithPathConfidence {
+            combiner: ConfidenceCombiner::Product,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn malformed_queries_are_rejected_with_a_reason() {
+    let reject = |value: serde_json::Value| match PathQueryRequest::from_json(&value.to_string()) {
+        Err(PathDbError::InvalidQuery(reason)) => reason,
+        other => panic!("expected InvalidQuery, got {other:?}"),
+    };
+    let select = json!({ "op": "select_by_type", "type_name": "Person" });
+
+    assert!(reject(json!({ "version": 2, "query": select })).contains("unsupported query version"));
+    assert!(reject(json!({ "query": select })).contains("version"));
+    assert!(reject(json!({ "version": 1, "query": select, "limit": 5 })).contains("limit"));
+    assert!(
+        reject(json!({ "version": 1, "query": { "op": "select_all" } })).contains("select_all")
+    );
+    assert!(reject(json!({
+        "version": 1,
+        "query": { "op": "select_by_type", "type_name": "Person", "extra": true },
+    }))
+    .contains("extra"));
+    assert!(reject(json!({
+        "version": 1,
+        "query": { "op": "find_paths", "from": 0, "to": 1 },
+    }))
+    .contains("max_depth"));
+    assert!(reject(json!({
+        "version": 1,
+        "query": { "op": "with_confidence", "base": select, "min_confidence": 1.5 },
+    }))
+    .contains("[0, 1]"));
+}
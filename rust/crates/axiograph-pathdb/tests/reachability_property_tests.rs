@@ -1,4 +1,5 @@
 use axiograph_pathdb::certificate::{FixedPointProbability, ReachabilityProofV2, FIXED_POINT_DENOMINATOR};
+use axiograph_pathdb::ConfidenceCombiner;
 use proptest::prelude::*;
 
 const MAX_PATH_LEN: usize = 12;
@@ -75,4 +76,16 @@ proptest! {
         }
         prop_assert_eq!(p.path_confidence(), expected);
     }
+
+    #[test]
+    fn reachability_proof_v2_confidence_with_combiner_is_right_fold((p, confs, _nodes) in reachability_proof_v2_strategy()) {
+        prop_assert_eq!(p.path_confidence_with(ConfidenceCombiner::Product), p.path_confidence());
+        for combiner in ConfidenceCombiner::ALL {
+            let mut expected = combiner.identity_fp();
+            for c in confs.iter().rev() {
+                expected = combiner.combine_fp(*c, expected);
+            }
+            prop_assert_eq!(p.path_confidence_with(combiner), expected);
+        }
+    }
 }
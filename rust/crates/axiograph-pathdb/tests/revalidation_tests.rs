@@ -0,0 +1,85 @@
+use axiograph_pathdb::certificate::CertificatePayloadV2;
+use axiograph_pathdb::witness::reachability_proof_v2_from_relation_ids;
+use axiograph_pathdb::{
+    AxiAnchorV1, CertificateV2, PathDB, ReachabilityProofV2, RevalidationStatus, Revalidator,
+};
+
+fn anchor(digest: &str) -> AxiAnchorV1 {
+    AxiAnchorV1 {
+        axi_digest_v1: digest.to_string(),
+    }
+}
+
+/// `a -r-> b -r-> c`, plus the certificate for that path.
+fn graph() -> (PathDB, [u32; 3], CertificateV2) {
+    let mut db = PathDB::new();
+    let a = db.add_entity("Node", vec![("name", "a")]);
+    let b = db.add_entity("Node", vec![("name", "b")]);
+    let c = db.add_entity("Node", vec![("name", "c")]);
+    let ab = db.add_relation("r", a, b, 0.9, Vec::new());
+    let bc = db.add_relation("r", b, c, 0.8, Vec::new());
+    db.build_indexes();
+    let proof = reachability_proof_v2_from_relation_ids(&db, a, &[ab, bc])
+        .unwrap()
+        .into_inner_in_db(&db)
+        .unwrap();
+    let cert = CertificateV2::reachability(proof).with_anchor(anchor("fnv1a64:old"));
+    (db, [a, b, c], cert)
+}
+
+fn step_confidences(cert: &CertificateV2) -> Vec<u32> {
+    let CertificatePayloadV2::ReachabilityV2 { proof, .. } = &cert.payload else {
+        panic!("expected a reachability certificate");
+    };
+    let mut out = Vec::new();
+    let mut cur = proof;
+    while let ReachabilityProofV2::Step {
+        rel_confidence_fp,
+        rest,
+        ..
+    } = cur
+    {
+        out.push(rel_confidence_fp.numerator());
+        cur = rest;
+    }
+    out
+}
+
+#[test]
+fn surviving_witnesses_are_reanchored_with_current_confidences() {
+    let (mut db, [_, b, c], cert) = graph();
+    db.set_relation_confidence(b, "r", c, 0.5);
+    let revalidator = Revalidator::new(anchor("fnv1a64:new"), &db, None).unwrap();
+
+    let outcome = revalidator.revalidate(&cert);
+    assert_eq!(outcome.status, RevalidationStatus::Rederived);
+    let rederived = outcome.certificate.unwrap();
+    assert_eq!(rederived.anchor, Some(anchor("fnv1a64:new")));
+    assert_eq!(step_confidences(&rederived), vec![900_000, 500_000]);
+
+    // Already anchored to the new snapshot, or not anchored at all.
+    let current = revalidator.revalidate(&rederived);
+    assert_eq!(current.status, RevalidationStatus::Current);
+    let mut unanchored = cert.clone();
+    unanchored.anchor = None;
+    assert_eq!(
+        revalidator.revalidate(&unanchored).status,
+        RevalidationStatus::Unsupported
+    );
+}
+
+#[test]
+fn removed_edges_invalidate_certificates() {
+    let (mut db, [a, b, _], cert) = graph();
+    assert_eq!(db.remove_relation(a, "r", b), 1);
+    let revalidator = Revalidator::new(anchor("fnv1a64:new"), &db, None).unwrap();
+
+    let outcome = revalidator.revalidate(&cert);
+    assert_eq!(outcome.status, RevalidationStatus::Invalidated);
+    assert!(outcome.certificate.is_none());
+    assert!(
+        outcome.reasons[0].contains("no longer in the snapshot"),
+        "{:?}",
+        outcome.reasons
+    );
+}
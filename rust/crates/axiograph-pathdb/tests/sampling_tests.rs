@@ -0,0 +1,80 @@
+use axiograph_pathdb::budget::QueryBudget;
+use axiograph_pathdb::{PathDB, SamplingConfig};
+
+/// `root` fans out to `width` mids, each of which fans out to `width` leaves.
+fn fan(width: usize) -> (PathDB, u32) {
+    let mut db = PathDB::new();
+    let root = db.add_entity("Node", vec![("name", "root")]);
+    for i in 0..width {
+        let mid = db.add_entity("Node", vec![]);
+        db.add_relation("r", root, mid, 1.0, Vec::new());
+        for _ in 0..=i {
+            let leaf = db.add_entity("Node", vec![]);
+            db.add_relation("s", mid, leaf, 0.4, Vec::new());
+        }
+    }
+    db.build_indexes();
+    (db, root)
+}
+
+#[test]
+fn sampled_path_count_brackets_the_exact_count() {
+    let (db, root) = fan(10);
+    let exact = db.follow_path(root, &["r", "s"]).len() as f64;
+    assert_eq!(exact, 55.0);
+
+    let sample = db.sample_path_count(root, &["r", "s"], &SamplingConfig::default());
+    assert!(sample.is_complete());
+    let value = sample.value;
+    assert!(!value.certified);
+    assert_eq!(value.walks, 1000);
+    let upper = value.count.upper.unwrap();
+    assert!(
+        value.count.lower <= exact && exact <= upper,
+        "{:?}",
+        value.count
+    );
+    assert!((value.count.value - exact).abs() < 0.2 * exact);
+    let exact_targets = db.follow_path(root, &["r", "s"]);
+    assert!(value.reached.is_subset(&exact_targets));
+
+    // Same seed, same walks.
+    let again = db.sample_path_count(root, &["r", "s"], &SamplingConfig::default());
+    assert_eq!(again.value.count, value.count);
+
+    // Confidence filters prune edges before sampling.
+    let strict = SamplingConfig {
+        min_confidence: Some(0.5),
+        ..SamplingConfig::default()
+    };
+    let pruned = db.sample_path_count(root, &["r", "s"], &strict).value;
+    assert_eq!(pruned.count.value, 0.0);
+    assert!(pruned.reached.is_empty());
+}
+
+#[test]
+fn sampled_neighborhood_is_sound_and_respects_the_budget() {
+    let (db, root) = fan(10);
+    let sample = db.sample_neighborhood(root, None, 2, &SamplingConfig::default());
+    let value = sample.value;
+    assert!(!value.certified);
+    let mut exact = db.follow_one(root, "r");
+    for mid in exact.clone() {
+        exact |= db.follow_one(mid, "s");
+    }
+    assert!(value.reached.is_subset(&exact));
+    assert!(value.size.lower <= exact.len() as f64);
+    assert!(value.missing_mass > 0.0 && value.missing_mass <= 1.0);
+
+    let only_r = db.sample_neighborhood(root, Some(&["r"]), 2, &SamplingConfig::default());
+    assert_eq!(only_r.value.reached, db.follow_one(root, "r"));
+
+    let tight = SamplingConfig {
+        budget: QueryBudget::unlimited().with_max_visited(50),
+        ..SamplingConfig::default()
+    };
+    let partial = db.sample_path_count(root, &["r", "s"], &tight);
+    assert!(!partial.is_complete());
+    assert_eq!(partial.value.walks, 25);
+    assert!(partial.value.reached.is_subset(&exact));
+}
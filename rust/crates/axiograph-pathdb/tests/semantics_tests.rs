@@ -147,6 +147,55 @@ fn with_confidence_filters_low_conf_edges() {
     assert!(result.contains(c));
 }
 
+#[test]
+fn with_path_confidence_uses_the_selected_combiner() {
+    let mut db = PathDB::new();
+
+    // a -r-> b -r-> c (0.9, 0.6) and a -r-> d -r-> e (0.7, 0.7)
+    let a = db.add_entity("Thing", vec![("name", "a")]);
+    let b = db.add_entity("Thing", vec![("name", "b")]);
+    let c = db.add_entity("Thing", vec![("name", "c")]);
+    let d = db.add_entity("Thing", vec![("name", "d")]);
+    let e = db.add_entity("Thing", vec![("name", "e")]);
+    db.add_relation("r", a, b, 0.9, vec![]);
+    db.add_relation("r", b, c, 0.6, vec![]);
+    db.add_relation("r", a, d, 0.7, vec![]);
+    db.add_relation("r", d, e, 0.7, vec![]);
+    db.build_indexes();
+
+    let query = |combiner| PathQuery::WithPathConfidence {
+        base: Box::new(PathQuery::FollowPath {
+            start: a,
+            path: vec!["r".to_string(), "r".to_string()],
+        }),
+        min_path_confidence: 0.5,
+        combiner,
+    };
+
+    // product: 0.54 and 0.49; min: 0.6 and 0.7.
+    let product = db.execute(&query(ConfidenceCombiner::Product));
+    assert_eq!(product.iter().collect::<Vec<_>>(), vec![c]);
+    let min = db.execute(&query(ConfidenceCombiner::Min));
+    assert_eq!(min.iter().collect::<Vec<_>>(), vec![c, e]);
+
+    let paths = db.find_paths_with_path_confidence(a, e, 3, ConfidenceCombiner::Product, 0.5);
+    assert!(paths.is_empty());
+    let paths = db.find_paths_with_path_confidence(a, e, 3, ConfidenceCombiner::Min, 0.5);
+    assert_eq!(paths.len(), 1);
+
+    let scored = db.follow_path_scored(a, &["r", "r"], ConfidenceCombiner::NoisyOr);
+    assert!((scored[&c] - 0.96).abs() < 1e-6);
+
+    let proved = db.execute_with_mode::<WithProof>(&query(ConfidenceCombiner::DempsterShafer));
+    assert_eq!(
+        proved.proof[0],
+        QueryExecutionEvent::WithPathConfidence {
+            min_path_confidence: 0.5,
+            combiner: ConfidenceCombiner::DempsterShafer,
+        }
+    );
+}
+
 // =============================================================================
 // Δ_F semantics: functoriality (composition)
 // =============================================================================
@@ -0,0 +1,173 @@
+//! Property tests for the `.axpd` binary format (`PathDB::to_bytes` / `from_bytes`).
+//!
+//! `from_bytes` reads untrusted snapshots, so besides round-trips we check that
+//! truncated / corrupted / arbitrary inputs produce errors rather than panics,
+//! and that anything it accepts is safe to query.
+
+use std::collections::BTreeMap;
+
+use axiograph_pathdb::PathDB;
+use proptest::prelude::*;
+
+#[derive(Debug, Clone)]
+struct DbCase {
+    entities: Vec<(u8, Vec<(String, String)>)>, // (type_idx, attrs)
+    edges: Vec<(u8, usize, usize, u8)>,         // (rel_idx, src, dst, conf_pct)
+    equivalences: Vec<(usize, usize)>,
+}
+
+fn small_string() -> impl Strategy<Value = String> {
+    prop::collection::vec(any::<char>(), 0..=8).prop_map(|chars| chars.into_iter().collect())
+}
+
+fn db_case_strategy() -> impl Strategy<Value = DbCase> {
+    (1usize..=8).prop_flat_map(|n| {
+        let entities = prop::collection::vec(
+            (
+                0u8..3,
+                prop::collection::vec((small_string(), small_string()), 0..=3),
+            ),
+            n..=n,
+        );
+        let edges = prop::collection::vec((0u8..3, 0..n, 0..n, 0u8..=100), 0..=16);
+        let equivalences = prop::collection::vec((0..n, 0..n), 0..=4);
+        (entities, edges, equivalences).prop_map(|(entities, edges, equivalences)| DbCase {
+            entities,
+            edges,
+            equivalences,
+        })
+    })
+}
+
+fn build_db(case: &DbCase) -> PathDB {
+    let mut db = PathDB::new();
+    let ids: Vec<u32> = case
+        .entities
+        .iter()
+        .map(|(t, attrs)| {
+            let attrs: Vec<(&str, &str)> = attrs
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect();
+            db.add_entity(&format!("Type{t}"), attrs)
+        })
+        .collect();
+    for (r, s, t, conf) in &case.edges {
+        db.add_relation(
+            &format!("rel_{r}"),
+            ids[*s],
+            ids[*t],
+            f32::from(*conf) / 100.0,
+            vec![],
+        );
+    }
+    for (a, b) in &case.equivalences {
+        db.add_equivalence(ids[*a], ids[*b], "same");
+    }
+    db.build_indexes();
+    db
+}
+
+/// Order-independent summary of everything `.axpd` persists.
+#[derive(Debug, PartialEq)]
+struct Summary {
+    entities: Vec<(String, BTreeMap<String, String>)>,
+    relations: Vec<(String, u32, u32, u32)>,
+    equivalences: Vec<(u32, Vec<u32>)>,
+}
+
+fn summarize(db: &PathDB) -> Summary {
+    let entities = (0..db.entities.len() as u32)
+        .map(|id| {
+            let view = db.get_entity(id).expect("entity");
+            (view.entity_type, view.attrs.into_iter().collect())
+        })
+        .collect();
+    let relations = (0..db.relations.len() as u32)
+        .map(|id| {
+            let rel = db.relations.get_relation(id).expect("relation");
+            (
+                db.interner.lookup(rel.rel_type).expect("rel type"),
+                rel.source,
+                rel.target,
+                rel.confidence.to_bits(),
+            )
+        })
+        .collect();
+    let mut equivalences: Vec<(u32, Vec<u32>)> = db
+        .equivalences
+        .iter()
+        .map(|(k, v)| {
+            let mut targets: Vec<u32> = v.iter().map(|(e, _)| *e).collect();
+            targets.sort_unstable();
+            (*k, targets)
+        })
+        .collect();
+    equivalences.sort();
+    Summary {
+        entities,
+        relations,
+        equivalences,
+    }
+}
+
+/// Exercise read paths on a loaded snapshot (must not panic).
+fn touch_everything(db: &PathDB) {
+    for id in 0..db.entities.len() as u32 {
+        let _ = db.get_entity(id);
+        let _ = db.relations.outgoing_any(id);
+        let _ = db.relations.incoming_any(id);
+    }
+    for id in 0..db.relations.len() as u32 {
+        let _ = db.relations.get_relation(id);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 128,
+        failure_persistence: None,
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn axpd_roundtrip_preserves_content(case in db_case_strategy()) {
+        let db = build_db(&case);
+        let bytes = db.to_bytes().expect("serialize");
+        let loaded = PathDB::from_bytes(&bytes).expect("deserialize");
+        prop_assert_eq!(summarize(&db), summarize(&loaded));
+    }
+
+    #[test]
+    fn axpd_truncation_is_an_error(case in db_case_strategy(), cut in any::<prop::sample::Index>()) {
+        let bytes = build_db(&case).to_bytes().expect("serialize");
+        let cut = cut.index(bytes.len());
+        prop_assert!(PathDB::from_bytes(&bytes[..cut]).is_err());
+    }
+
+    #[test]
+    fn axpd_corruption_never_panics(
+        case in db_case_strategy(),
+        flips in prop::collection::vec((any::<prop::sample::Index>(), any::<u8>()), 1..=4),
+    ) {
+        let mut bytes = build_db(&case).to_bytes().expect("serialize");
+        for (at, value) in flips {
+            let i = at.index(bytes.len());
+            bytes[i] = value;
+        }
+        if let Ok(db) = PathDB::from_bytes(&bytes) {
+            touch_everything(&db);
+        }
+    }
+
+    #[test]
+    fn axpd_arbitrary_bytes_never_panic(tail in prop::collection::vec(any::<u8>(), 0..256)) {
+        let mut bytes = b"AXPD".to_vec();
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&tail);
+        if let Ok(db) = PathDB::from_bytes(&bytes) {
+            touch_everything(&db);
+        }
+        let _ = PathDB::from_bytes(&tail);
+    }
+}
@@ -0,0 +1,154 @@
+use axiograph_pathdb::confidence::ConfidenceCombiner;
+use axiograph_pathdb::shard::{PartitionStrategy, ShardedDb, GHOST_TYPE};
+use axiograph_pathdb::{PathDB, PathDbError, PathQuery};
+
+/// `clusters` groups of `size` people. Inside a group, `knows` links each
+/// member to the next two; one `bridge` edge joins consecutive groups. Every
+/// fifth person is also a virtual `Author`.
+fn clustered(clusters: u32, size: u32) -> PathDB {
+    let mut db = PathDB::new();
+    for c in 0..clusters {
+        for i in 0..size {
+            let id = db.add_entity(
+                "Person",
+                vec![
+                    ("name", format!("p{c}_{i}").as_str()),
+                    ("cluster", &c.to_string()),
+                ],
+            );
+            if id.is_multiple_of(5) {
+                db.mark_virtual_type(id, "Author").unwrap();
+            }
+        }
+    }
+    for c in 0..clusters {
+        let base = c * size;
+        for i in 0..size {
+            for step in 1..=2 {
+                let confidence = if (i + step) % 3 == 0 { 0.4 } else { 0.9 };
+                db.add_relation(
+                    "knows",
+                    base + i,
+                    base + (i + step) % size,
+                    confidence,
+                    vec![("since", "2020")],
+                );
+            }
+        }
+        db.add_relation("bridge", base, ((c + 1) % clusters) * size, 0.8, vec![]);
+    }
+    db.build_indexes();
+    db
+}
+
+fn queries() -> Vec<PathQuery> {
+    let follow = |start: u32, path: &[&str]| PathQuery::FollowPath {
+        start,
+        path: path.iter().map(|s| s.to_string()).collect(),
+    };
+    vec![
+        PathQuery::SelectByType("Person".to_string()),
+        PathQuery::SelectByType("Author".to_string()),
+        PathQuery::SelectByType(GHOST_TYPE.to_string()),
+        PathQuery::SelectRelated(3, "knows".to_string()),
+        follow(0, &["bridge", "knows", "knows", "bridge", "knows"]),
+        follow(7, &["knows", "knows", "knows"]),
+        PathQuery::WithConfidence {
+            base: Box::new(follow(0, &["knows", "knows", "bridge", "knows"])),
+            min_confidence: 0.5,
+        },
+        PathQuery::WithPathConfidence {
+            base: Box::new(follow(0, &["knows", "bridge", "knows"])),
+            min_path_confidence: 0.6,
+            combiner: ConfidenceCombiner::Product,
+        },
+        PathQuery::WithPathConfidence {
+            base: Box::new(PathQuery::SelectRelated(1, "knows".to_string())),
+            min_path_confidence: 0.5,
+            combiner: ConfidenceCombiner::Min,
+        },
+        PathQuery::Join(
+            Box::new(PathQuery::SelectByType("Author".to_string())),
+            Box::new(follow(0, &["bridge", "knows", "knows"])),
+        ),
+        PathQuery::Union(
+            Box::new(PathQuery::SelectRelated(40, "bridge".to_string())),
+            Box::new(PathQuery::SelectRelated(41, "knows".to_string())),
+        ),
+    ]
+}
+
+#[test]
+fn sharded_execution_matches_single_node() {
+    let db = clustered(6, 20);
+    for strategy in [PartitionStrategy::Hash, PartitionStrategy::Greedy] {
+        for shards in [1, 3, 4] {
+            let sharded = ShardedDb::partition(&db, strategy, shards);
+            assert_eq!(sharded.shards().len(), shards);
+            let owned: u64 = sharded.shards().iter().map(|s| s.owned_len()).sum();
+            assert_eq!(owned, db.entities.len() as u64);
+            for query in queries() {
+                assert_eq!(
+                    sharded.execute(&query).unwrap(),
+                    db.execute(&query),
+                    "{strategy:?} x{shards}: {query:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn greedy_partitioning_cuts_fewer_edges() {
+    let db = clustered(8, 50);
+    let hash = ShardedDb::partition(&db, PartitionStrategy::Hash, 4);
+    let greedy = ShardedDb::partition(&db, PartitionStrategy::Greedy, 4);
+    let hash_cut = hash.partitioning().edge_cut(&db);
+    let greedy_cut = greedy.partitioning().edge_cut(&db);
+    assert!(
+        greedy_cut * 4 < hash_cut,
+        "greedy {greedy_cut} vs hash {hash_cut}"
+    );
+
+    // Balanced within the greedy slack.
+    let sizes = greedy.partitioning().shard_sizes();
+    assert!(sizes.iter().all(|&n| n <= 110), "{sizes:?}");
+
+    // Ghosts stand in for cut targets only.
+    let ghosts: u64 = greedy.shards().iter().map(|s| s.ghost_len()).sum();
+    assert!(ghosts > 0 && ghosts as usize <= greedy_cut);
+    let shard = &greedy.shards()[0];
+    let local = shard.to_local(0).expect("entity 0 on shard 0");
+    assert_eq!(shard.to_global(local), Some(0));
+    assert_eq!(shard.db().get_entity(local).unwrap().attrs["name"], "p0_0");
+}
+
+#[test]
+fn find_paths_is_depth_bounded_reachability() {
+    let db = clustered(4, 10);
+    let sharded = ShardedDb::partition(&db, PartitionStrategy::Hash, 3);
+    let find = |from: u32, to: u32, max_depth: usize| PathQuery::FindPaths {
+        from,
+        to,
+        max_depth,
+    };
+    // 0 -bridge-> 10 -knows-> 11 -knows-> 13.
+    assert_eq!(sharded.execute(&find(0, 13, 3)).unwrap().len(), 1);
+    assert!(sharded.execute(&find(0, 13, 2)).unwrap().is_empty());
+    assert!(sharded.execute(&find(0, 0, 5)).unwrap().is_empty());
+    let low = PathQuery::WithConfidence {
+        base: Box::new(find(0, 12, 2)),
+        min_confidence: 0.95,
+    };
+    assert!(sharded.execute(&low).unwrap().is_empty());
+
+    let scored = PathQuery::WithPathConfidence {
+        base: Box::new(find(0, 13, 3)),
+        min_path_confidence: 0.5,
+        combiner: ConfidenceCombiner::Product,
+    };
+    assert!(matches!(
+        sharded.execute(&scored),
+        Err(PathDbError::Unsupported(_))
+    ));
+}
@@ -0,0 +1,97 @@
+use anyhow::Result;
+use axiograph_pathdb::{PathDB, PathQuery, Subscriptions};
+use roaring::RoaringBitmap;
+
+fn base() -> (PathDB, u32, u32) {
+    let mut db = PathDB::new();
+    let svc = db.add_entity("ProtoService", vec![("name", "billing")]);
+    let rpc = db.add_entity("ProtoRpc", vec![("name", "Charge")]);
+    let scope = db.add_entity("AuthScope", vec![("name", "billing.write")]);
+    db.add_relation("proto_service_has_rpc", svc, rpc, 1.0, Vec::new());
+    db.add_relation("rpc_requires_scope", rpc, scope, 1.0, Vec::new());
+    db.build_indexes();
+    (db, svc, rpc)
+}
+
+#[test]
+fn subscriptions_emit_deltas_for_new_and_removed_answers() -> Result<()> {
+    let (db, svc, rpc) = base();
+    let mut subs = Subscriptions::new();
+    let (rpcs, initial) = subs.subscribe(&db, PathQuery::SelectByType("ProtoRpc".to_string()));
+    assert_eq!(initial, RoaringBitmap::from_iter([rpc]));
+    let (in_service, _) = subs.subscribe(
+        &db,
+        PathQuery::Join(
+            Box::new(PathQuery::SelectByType("ProtoRpc".to_string())),
+            Box::new(PathQuery::SelectRelated(
+                svc,
+                "proto_service_has_rpc".to_string(),
+            )),
+        ),
+    );
+
+    let mut overlay = db.overlay();
+    let refund = overlay.add_entity("ProtoRpc", vec![("name", "Refund")]);
+    let deltas = subs.apply(overlay.view()?, overlay.changes());
+    assert_eq!(deltas.len(), 1);
+    assert_eq!(deltas[0].subscription, rpcs);
+    assert_eq!(deltas[0].added, RoaringBitmap::from_iter([refund]));
+    assert!(deltas[0].removed.is_empty());
+    // No relation changed: the traversal leaf was not re-run.
+    assert_eq!(subs.stats().leaf_reruns, 0);
+    assert_eq!(subs.stats().leaf_skips, 1);
+
+    let db = overlay.into_view()?;
+    let mut overlay = db.overlay();
+    overlay.add_relation("proto_service_has_rpc", svc, refund, 1.0, Vec::new())?;
+    overlay.remove_entity(rpc)?;
+    let deltas = subs.apply(overlay.view()?, overlay.changes());
+    assert_eq!(deltas.len(), 2);
+    assert_eq!(deltas[0].removed, RoaringBitmap::from_iter([rpc]));
+    assert_eq!(deltas[1].subscription, in_service);
+    assert_eq!(deltas[1].added, RoaringBitmap::from_iter([refund]));
+    assert_eq!(deltas[1].removed, RoaringBitmap::from_iter([rpc]));
+
+    for id in [rpcs, in_service] {
+        let query = subs.query(id).unwrap().clone();
+        assert_eq!(subs.results(id), Some(&overlay.view()?.execute(&query)));
+    }
+    assert!(subs.unsubscribe(rpcs));
+    assert!(!subs.unsubscribe(rpcs));
+    assert_eq!(subs.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn subscriptions_track_inverses_and_confidence_filters() -> Result<()> {
+    let (mut db, svc, rpc) = base();
+    db.register_inverse("proto_service_has_rpc", "rpc_in_service")?;
+    let mut subs = Subscriptions::new();
+    let query = PathQuery::WithConfidence {
+        base: Box::new(PathQuery::FollowPath {
+            start: rpc,
+            path: vec!["rpc_in_service".to_string()],
+        }),
+        min_confidence: 0.5,
+    };
+    let (id, initial) = subs.subscribe(&db, query.clone());
+    assert_eq!(initial, RoaringBitmap::from_iter([svc]));
+
+    let mut overlay = db.overlay();
+    let weak = overlay.add_entity("ProtoService", vec![("name", "weak")]);
+    let strong = overlay.add_entity("ProtoService", vec![("name", "strong")]);
+    overlay.add_relation("proto_service_has_rpc", weak, rpc, 0.2, Vec::new())?;
+    overlay.add_relation("proto_service_has_rpc", strong, rpc, 0.9, Vec::new())?;
+    let deltas = subs.apply(overlay.view()?, overlay.changes());
+    assert_eq!(deltas.len(), 1);
+    assert_eq!(deltas[0].added, RoaringBitmap::from_iter([strong]));
+    assert_eq!(subs.results(id), Some(&overlay.view()?.execute(&query)));
+
+    // Unrelated edge types leave the traversal alone.
+    let db = overlay.into_view()?;
+    let mut overlay = db.overlay();
+    overlay.add_relation("rpc_requires_scope", rpc, strong, 1.0, Vec::new())?;
+    assert!(subs.apply(overlay.view()?, overlay.changes()).is_empty());
+    assert_eq!(subs.stats().leaf_skips, 1);
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use axiograph_pathdb::context::QueryContext;
+use axiograph_pathdb::supernode::{HubPolicy, DEFAULT_SUPERNODE_THRESHOLD};
+use axiograph_pathdb::{PathDB, PathQuery};
+use roaring::RoaringBitmap;
+
+/// `items` entities all pointing at one `flag` hub (`hasFlag`), plus a small
+/// `a -link-> b -link-> c` chain where `a` also links to the hub.
+struct Hub {
+    db: PathDB,
+    flag: u32,
+    items: Vec<u32>,
+    a: u32,
+    c: u32,
+}
+
+fn hub(items: usize, threshold: usize) -> Hub {
+    let mut db = PathDB::new();
+    db.set_supernode_threshold(threshold);
+    let flag = db.add_entity("Bool", vec![("name", "true")]);
+    let items: Vec<u32> = (0..items)
+        .map(|i| {
+            let item = db.add_entity("Item", vec![("name", format!("i{i}").as_str())]);
+            db.add_relation("hasFlag", item, flag, 1.0, vec![]);
+            db.add_relation("flagOf", flag, item, 1.0, vec![]);
+            item
+        })
+        .collect();
+    let a = db.add_entity("Node", vec![("name", "a")]);
+    let b = db.add_entity("Node", vec![("name", "b")]);
+    let c = db.add_entity("Node", vec![("name", "c")]);
+    db.add_relation("link", a, b, 1.0, vec![]);
+    db.add_relation("link", b, c, 1.0, vec![]);
+    db.add_relation("link", a, flag, 1.0, vec![]);
+    db.build_indexes();
+    Hub {
+        db,
+        flag,
+        items,
+        a,
+        c,
+    }
+}
+
+#[test]
+fn hub_adjacency_uses_dedicated_bitmaps() {
+    let Hub {
+        mut db,
+        flag,
+        items,
+        a,
+        ..
+    } = hub(50, 16);
+    assert_eq!(db.supernodes().iter().collect::<Vec<_>>(), [flag]);
+    assert!(!db.is_supernode(a));
+
+    let all: RoaringBitmap = items.iter().copied().collect();
+    assert_eq!(db.follow_one(flag, "flagOf"), all);
+    let rel = db.interner.id_of("hasFlag").unwrap();
+    assert_eq!(db.relations.sources(flag, rel), all);
+    let rel = db.interner.id_of("flagOf").unwrap();
+    assert!(db.relations.has_edge(flag, rel, items[7]));
+    assert!(!db.relations.has_edge(flag, rel, a));
+
+    // Maintained on insert, rebuilt on load and on threshold changes.
+    let late = db.add_entity("Item", vec![]);
+    db.add_relation("flagOf", flag, late, 1.0, vec![]);
+    assert!(db.follow_one(flag, "flagOf").contains(late));
+    let loaded = PathDB::from_bytes(&db.to_bytes().unwrap()).unwrap();
+    assert_eq!(loaded.supernode_threshold(), DEFAULT_SUPERNODE_THRESHOLD);
+    assert!(loaded.supernodes().is_empty());
+    db.set_supernode_threshold(1_000);
+    assert!(db.supernodes().is_empty());
+    assert_eq!(db.follow_one(flag, "flagOf").len(), 51);
+}
+
+#[test]
+fn removals_keep_hub_bitmaps_consistent() {
+    let Hub {
+        mut db,
+        flag,
+        items,
+        ..
+    } = hub(20, 16);
+    assert_eq!(db.remove_relation(flag, "flagOf", items[0]), 1);
+    assert!(!db.follow_one(flag, "flagOf").contains(items[0]));
+    assert_eq!(db.supernode_threshold(), 16);
+    assert!(db.is_supernode(flag));
+
+    let mut overlay = db.overlay();
+    overlay.remove_relation(flag, "flagOf", items[1]);
+    assert!(overlay.view().unwrap().is_supernode(flag));
+    assert!(!overlay.follow_one(flag, "flagOf").contains(items[1]));
+}
+
+#[test]
+fn hub_policy_skips_or_samples_hub_expansion() {
+    let Hub {
+        db,
+        flag,
+        items,
+        a,
+        c,
+        ..
+    } = hub(50, 16);
+    let path = ["link", "flagOf"];
+
+    let all = db.follow_path_with_hubs(a, &path, HubPolicy::Expand);
+    assert_eq!(all, db.follow_path(a, &path));
+    assert_eq!(all.len(), 50);
+    assert!(db
+        .follow_path_with_hubs(a, &path, HubPolicy::Skip)
+        .is_empty());
+    let sample = db.follow_path_with_hubs(a, &path, HubPolicy::Sample(3));
+    assert_eq!(sample.iter().collect::<Vec<_>>(), items[..3]);
+
+    // The start entity is always expanded.
+    assert_eq!(
+        db.follow_path_with_hubs(flag, &["flagOf"], HubPolicy::Skip)
+            .len(),
+        50
+    );
+
+    // Through `PathQuery` execution.
+    let query = PathQuery::FollowPath {
+        start: a,
+        path: path.iter().map(|s| s.to_string()).collect(),
+    };
+    let skip = QueryContext::default().with_hubs(HubPolicy::Skip);
+    assert!(db.execute_in(&query, &skip).is_empty());
+    assert_eq!(db.execute_in(&query, &QueryContext::default()).len(), 50);
+
+    // `find_paths` can end at a hub but not pass through one.
+    assert_eq!(db.find_paths_with_hubs(a, c, 3, HubPolicy::Skip).len(), 1);
+    assert_eq!(
+        db.find_paths_with_hubs(a, flag, 3, HubPolicy::Skip).len(),
+        1
+    );
+    assert!(db
+        .find_paths_with_hubs(a, items[40], 3, HubPolicy::Skip)
+        .is_empty());
+    assert!(db
+        .find_paths_with_hubs(a, items[40], 3, HubPolicy::Sample(3))
+        .is_empty());
+    assert_eq!(
+        db.find_paths_with_hubs(a, items[2], 3, HubPolicy::Sample(3))
+            .len(),
+        1
+    );
+    assert_eq!(
+        db.find_paths_with_hubs(a, items[40], 3, HubPolicy::Expand),
+        db.find_paths(a, items[40], 3)
+    );
+}
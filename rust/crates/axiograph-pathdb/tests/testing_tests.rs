@@ -0,0 +1,109 @@
+use axiograph_pathdb::testing::{
+    synthetic_graph, ConfidenceDistribution, SyntheticGraphConfig, Vocabulary,
+};
+use axiograph_pathdb::PathDB;
+
+fn in_degrees(db: &PathDB) -> Vec<usize> {
+    (0..db.entities.len() as u32)
+        .map(|id| db.relations.incoming_any(id).len())
+        .collect()
+}
+
+fn edges(db: &PathDB) -> Vec<(u32, u32, f32)> {
+    (0..db.relations.len() as u32)
+        .filter_map(|id| db.relations.get_relation(id))
+        .map(|r| (r.source, r.target, r.confidence))
+        .collect()
+}
+
+#[test]
+fn same_seed_same_graph_different_seed_different_graph() {
+    let config = SyntheticGraphConfig {
+        entities: 500,
+        ..Default::default()
+    };
+    let a = synthetic_graph(&config);
+    let b = synthetic_graph(&config);
+    assert_eq!(edges(&a), edges(&b));
+
+    let c = synthetic_graph(&SyntheticGraphConfig { seed: 7, ..config });
+    assert_ne!(edges(&a), edges(&c));
+}
+
+#[test]
+fn degrees_are_heavy_tailed() {
+    let db = synthetic_graph(&SyntheticGraphConfig {
+        entities: 5_000,
+        edges_per_entity: 2,
+        ..Default::default()
+    });
+    assert_eq!(db.entities.len(), 5_000);
+    let edge_count = db.relations.len();
+    assert!(
+        edge_count > 9_000 && edge_count <= 2 * 5_000,
+        "{edge_count}"
+    );
+
+    let mut degrees = in_degrees(&db);
+    degrees.sort_unstable_by(|a, b| b.cmp(a));
+    let mean = edge_count as f64 / degrees.len() as f64;
+    // Hubs: the top entity has far more than the mean, and the top 1% hold a
+    // disproportionate share of edges.
+    assert!(
+        degrees[0] as f64 > 20.0 * mean,
+        "max {} mean {mean}",
+        degrees[0]
+    );
+    let top: usize = degrees[..50].iter().sum();
+    assert!(top as f64 > 0.1 * edge_count as f64, "top 1% hold {top}");
+    // No self loops; edges point from newer to older entities.
+    assert!(edges(&db).iter().all(|(s, t, _)| t < s));
+}
+
+#[test]
+fn vocabularies_and_confidences_follow_the_config() {
+    let db = synthetic_graph(&SyntheticGraphConfig {
+        entities: 2_000,
+        entity_types: Vocabulary::weighted([("Machine", 9), ("Part", 1), ("Unused", 0)]),
+        relation_types: Vocabulary::uniform(["feeds", "holds"]),
+        confidence: ConfidenceDistribution::Uniform { min: 0.2, max: 0.4 },
+        ..Default::default()
+    });
+    let count = |t: &str| db.find_by_type(t).map_or(0, |ids| ids.len());
+    let (machines, parts) = (count("Machine"), count("Part"));
+    assert_eq!(machines + parts, 2_000);
+    assert_eq!(count("Unused"), 0);
+    assert!(machines > 6 * parts, "{machines} vs {parts}");
+
+    let name = db
+        .get_entity(0)
+        .unwrap()
+        .attrs
+        .get("name")
+        .cloned()
+        .unwrap();
+    assert!(name.ends_with("_0"), "{name}");
+
+    let edges = edges(&db);
+    assert!(edges.iter().all(|(_, _, c)| (0.2..=0.4).contains(c)));
+    let mean = edges.iter().map(|(_, _, c)| f64::from(*c)).sum::<f64>() / edges.len() as f64;
+    assert!((mean - 0.3).abs() < 0.01, "{mean}");
+
+    let skewed = synthetic_graph(&SyntheticGraphConfig {
+        entities: 2_000,
+        confidence: ConfidenceDistribution::SkewedHigh {
+            min: 0.0,
+            max: 1.0,
+            exponent: 4.0,
+        },
+        ..Default::default()
+    });
+    let edges = self::edges(&skewed);
+    let high = edges.iter().filter(|(_, _, c)| *c >= 0.5).count();
+    // P(u^(1/4) >= 0.5) = 1 - 0.5^4 ≈ 0.94.
+    assert!(
+        high as f64 > 0.9 * edges.len() as f64,
+        "{high}/{}",
+        edges.len()
+    );
+}
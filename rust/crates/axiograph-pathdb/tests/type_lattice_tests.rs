@@ -0,0 +1,78 @@
+use anyhow::Result;
+use axiograph_pathdb::axi_module_import::import_axi_schema_v1_module_into_pathdb;
+use axiograph_pathdb::PathDB;
+
+const MATERIALS: &str = r#"
+module Materials
+
+schema S:
+  object Material
+  object Metal
+  object Steel
+  subtype Metal < Material
+  subtype Steel < Metal
+
+instance I of S:
+  Material = {wood}
+  Metal = {titanium}
+  Steel = {s355}
+"#;
+
+fn imported() -> Result<PathDB> {
+    let m = axiograph_dsl::axi_v1::parse_axi_v1(MATERIALS)?;
+    let mut db = PathDB::new();
+    import_axi_schema_v1_module_into_pathdb(&mut db, &m)?;
+    Ok(db)
+}
+
+fn count(db: &PathDB, ty: &str) -> u64 {
+    db.find_by_type(ty).map_or(0, |ids| ids.len())
+}
+
+#[test]
+fn find_by_type_includes_declared_subtypes() -> Result<()> {
+    let mut db = imported()?;
+    assert_eq!(count(&db, "Material"), 3);
+    assert_eq!(count(&db, "Metal"), 2);
+    assert_eq!(db.find_by_exact_type("Material").len(), 1);
+    assert_eq!(db.find_by_exact_type("Metal").len(), 1);
+
+    // Entities added after the import follow the lattice too.
+    let steel = db.add_entity("Steel", vec![("name", "s235")]);
+    assert!(db.find_by_type("Material").unwrap().contains(steel));
+    assert!(db.find_by_exact_type("Steel").contains(steel));
+    assert!(!db.find_by_exact_type("Metal").contains(steel));
+    assert!(db.verify_integrity().is_ok());
+    Ok(())
+}
+
+#[test]
+fn lattice_survives_snapshot_round_trip() -> Result<()> {
+    let db = imported()?;
+    let mut loaded = PathDB::from_bytes(&db.to_bytes()?)?;
+    let (steel, material) = (
+        loaded.interner.id_of("Steel").unwrap(),
+        loaded.interner.id_of("Material").unwrap(),
+    );
+    assert!(loaded.type_lattice().is_subtype(steel, material));
+
+    let added = loaded.add_entity("Steel", vec![]);
+    assert!(loaded.find_by_type("Material").unwrap().contains(added));
+    Ok(())
+}
+
+#[test]
+fn declare_subtype_indexes_existing_entities() {
+    let mut db = PathDB::new();
+    let steel = db.add_entity("Steel", vec![]);
+    let virtual_metal = db.add_entity("Thing", vec![]);
+    db.mark_virtual_type(virtual_metal, "Metal").unwrap();
+    assert_eq!(count(&db, "Material"), 0);
+
+    db.declare_subtype("Steel", "Metal");
+    db.declare_subtype("Metal", "Material");
+    let materials = db.find_by_type("Material").unwrap();
+    assert!(materials.contains(steel) && materials.contains(virtual_metal));
+    assert_eq!(db.find_by_exact_type("Metal").len(), 1);
+    assert_eq!(db.estimate_type_count("Material"), 2);
+}
@@ -0,0 +1,77 @@
+//! Typed attribute literals carried by certificates and `.axi` exports.
+
+use axiograph_pathdb::TypedLiteralV1;
+use std::cmp::Ordering;
+
+fn int(value: i64, unit: Option<&str>) -> TypedLiteralV1 {
+    TypedLiteralV1::Int {
+        value,
+        unit: unit.map(str::to_string),
+    }
+}
+
+fn dec(mantissa: i64, exponent: i32, unit: Option<&str>) -> TypedLiteralV1 {
+    TypedLiteralV1::Float {
+        mantissa,
+        exponent,
+        unit: unit.map(str::to_string),
+    }
+}
+
+#[test]
+fn infer_reads_numbers_booleans_and_units_exactly() {
+    assert_eq!(TypedLiteralV1::infer("42"), int(42, None));
+    assert_eq!(TypedLiteralV1::infer("-7 kg"), int(-7, Some("kg")));
+    assert_eq!(TypedLiteralV1::infer("1200"), int(1200, None));
+    assert_eq!(TypedLiteralV1::infer("2.50"), dec(25, -1, None));
+    assert_eq!(TypedLiteralV1::infer("2.5"), TypedLiteralV1::infer("2.50"));
+    assert_eq!(TypedLiteralV1::infer("6.02e23"), dec(602, 21, None));
+    assert_eq!(TypedLiteralV1::infer("99.5 %"), dec(995, -1, Some("%")));
+    assert_eq!(
+        TypedLiteralV1::infer("true"),
+        TypedLiteralV1::Bool { value: true }
+    );
+
+    for raw in [
+        "007",
+        "-0",
+        "2024-01-01",
+        "1.",
+        ".5",
+        "NaN",
+        "inf",
+        " 1",
+        "1 2 3",
+        "True",
+    ] {
+        assert!(TypedLiteralV1::infer(raw).is_string(), "{raw}");
+        assert_eq!(TypedLiteralV1::typed(raw), None, "{raw}");
+    }
+}
+
+#[test]
+fn compare_is_exact_and_unit_aware() {
+    let lit = TypedLiteralV1::infer;
+    assert_eq!(
+        lit("10 kg").compare(&lit("9.99 kg")),
+        Some(Ordering::Greater)
+    );
+    assert_eq!(lit("2").compare(&lit("2.000")), Some(Ordering::Equal));
+    assert_eq!(lit("-1.5").compare(&lit("-1.25")), Some(Ordering::Less));
+    assert_eq!(lit("1e30").compare(&lit("999")), Some(Ordering::Greater));
+    assert_eq!(lit("0.1").compare(&lit("-5")), Some(Ordering::Greater));
+    assert_eq!(lit("10 kg").compare(&lit("10 lb")), None);
+    assert_eq!(lit("10 kg").compare(&lit("10")), None);
+    assert_eq!(lit("true").compare(&lit("1")), None);
+}
+
+#[test]
+fn literals_serialize_compactly() {
+    let json = serde_json::to_value(TypedLiteralV1::infer("12.5 kg")).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"type": "float", "mantissa": 125, "exponent": -1, "unit": "kg"})
+    );
+    let json = serde_json::to_value(TypedLiteralV1::infer("3")).unwrap();
+    assert_eq!(json, serde_json::json!({"type": "int", "value": 3}));
+}
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use axiograph_pathdb::axi_module_export::export_axi_schema_v1_module_from_pathdb;
+use axiograph_pathdb::axi_module_import::import_axi_schema_v1_module_into_pathdb;
+use axiograph_pathdb::axi_semantics::MetaPlaneIndex;
+use axiograph_pathdb::PathDB;
+
+const PAYMENTS: &str = r#"
+module Payments
+
+schema Ledger:
+  object Payment
+  object Status
+  relation PaymentStatus(payment: Payment, status: Status)
+
+theory Reports on Ledger:
+  view open_payments = select ?p where ?p is Payment, ?p -PaymentStatus-> ?s, ?s = name("open")
+  view all_payments =
+    select ?p where ?p is Payment
+    limit 50
+
+instance I of Ledger:
+  Payment = {p1, p2}
+  Status = {open, settled}
+  PaymentStatus = {(payment=p1, status=open), (payment=p2, status=settled)}
+"#;
+
+fn import(text: &str) -> Result<PathDB> {
+    let m = axiograph_dsl::axi_v1::parse_axi_v1(text)?;
+    let mut db = PathDB::new();
+    import_axi_schema_v1_module_into_pathdb(&mut db, &m)?;
+    Ok(db)
+}
+
+#[test]
+fn views_parse_with_continuation_lines() -> Result<()> {
+    let m = axiograph_dsl::axi_v1::parse_axi_v1(PAYMENTS)?;
+    let views = &m.theories[0].views;
+    assert_eq!(views.len(), 2);
+    assert_eq!(views[0].name, "open_payments");
+    assert!(views[0].query.starts_with("select ?p where"));
+    assert_eq!(views[1].query, "select ?p where ?p is Payment limit 50");
+
+    let printed = axiograph_dsl::printer::print_axi_v1(&m)?;
+    assert_eq!(axiograph_dsl::axi_v1::parse_axi_v1(&printed)?, m);
+    Ok(())
+}
+
+#[test]
+fn imported_views_are_indexed_by_name() -> Result<()> {
+    let db = import(PAYMENTS)?;
+    let meta = MetaPlaneIndex::from_db(&db)?;
+
+    let view = meta.view("open_payments")?;
+    assert_eq!(view.schema_name, "Ledger");
+    assert_eq!(view.theory_name, "Reports");
+    assert_eq!(meta.view("Ledger.all_payments")?.index, 1);
+
+    let names: Vec<&str> = meta.views().iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(names, vec!["open_payments", "all_payments"]);
+
+    assert!(meta.view("missing").is_err());
+    assert!(meta.view("Other.open_payments").is_err());
+    Ok(())
+}
+
+#[test]
+fn views_survive_module_export() -> Result<()> {
+    let db = import(PAYMENTS)?;
+    let exported = export_axi_schema_v1_module_from_pathdb(&db, "Payments")?;
+    let original = axiograph_dsl::axi_v1::parse_axi_v1(PAYMENTS)?;
+    let reparsed = axiograph_dsl::axi_v1::parse_axi_v1(&exported)?;
+    assert_eq!(reparsed.theories[0].views, original.theories[0].views);
+    Ok(())
+}
+
+#[test]
+fn duplicate_view_names_are_reported_by_lint() -> Result<()> {
+    let text = PAYMENTS.replace("view all_payments", "view open_payments");
+    let report = axiograph_dsl::lint::lint_axi(&text);
+    assert!(
+        report.iter().any(|d| d.message.contains("open_payments")),
+        "{report:?}"
+    );
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axiograph_pathdb::{AttrEqualsDef, PathDB, PathDbError, PathQuery, VirtualRelationDef};
+
+/// Parts and suppliers joined by `part_number`, with no stored join edges.
+/// `Acme -supplies-> bolt_a`.
+fn parts() -> (PathDB, [u32; 4]) {
+    let mut db = PathDB::new();
+    let bolt_a = db.add_entity("Part", vec![("name", "bolt_a"), ("part_number", "B-10")]);
+    let bolt_b = db.add_entity("Part", vec![("name", "bolt_b"), ("part_number", "B-10")]);
+    let nut = db.add_entity("Part", vec![("name", "nut"), ("part_number", "N-4")]);
+    let acme = db.add_entity("Supplier", vec![("name", "acme")]);
+    db.add_relation("supplies", acme, bolt_a, 0.6, vec![]);
+    db.build_indexes();
+    (db, [bolt_a, bolt_b, nut, acme])
+}
+
+#[test]
+fn attr_equality_joins_without_stored_edges() -> Result<()> {
+    let (mut db, [bolt_a, bolt_b, nut, acme]) = parts();
+    db.register_virtual_relation(
+        "same_part",
+        VirtualRelationDef::AttrEquals(AttrEqualsDef::same("Part", "part_number")),
+    )?;
+    let edges_before = db.relations.len();
+
+    assert_eq!(
+        db.follow_one(bolt_a, "same_part")
+            .iter()
+            .collect::<Vec<_>>(),
+        vec![bolt_b]
+    );
+    assert!(db.follow_one(nut, "same_part").is_empty());
+    // The source must be a `Part`.
+    assert!(db.follow_one(acme, "same_part").is_empty());
+
+    // Mixed with a stored step; filters never hide the virtual step.
+    let path = ["supplies", "same_part"];
+    assert_eq!(
+        db.follow_path(acme, &path).iter().collect::<Vec<_>>(),
+        vec![bolt_b]
+    );
+    assert!(db
+        .follow_path_with_min_confidence(acme, &path, 0.9)
+        .is_empty());
+    assert!(db
+        .follow_one_with_min_confidence(bolt_a, "same_part", 0.9)
+        .contains(bolt_b));
+    let query = PathQuery::FollowPath {
+        start: acme,
+        path: path.iter().map(|s| s.to_string()).collect(),
+    };
+    assert!(db.execute(&query).contains(bolt_b));
+    assert!(db
+        .execute(&PathQuery::SelectRelated(bolt_b, "same_part".to_string()))
+        .contains(bolt_a));
+
+    assert_eq!(db.relations.len(), edges_before);
+    assert_eq!(db.virtual_relation_names(), vec!["same_part"]);
+    assert!(db
+        .composite_index_names()
+        .contains(&("Part".to_string(), "part_number".to_string())));
+    Ok(())
+}
+
+#[test]
+fn predicates_and_conflicts() -> Result<()> {
+    let (mut db, [bolt_a, bolt_b, nut, acme]) = parts();
+    // Parts sharing a name prefix.
+    db.register_virtual_relation(
+        "similar",
+        VirtualRelationDef::Predicate {
+            target_type: "Part".to_string(),
+            predicate: Arc::new(|db: &PathDB, a: u32, b: u32| {
+                let name = |id| db.get_entity(id).and_then(|e| e.attrs.get("name").cloned());
+                matches!((name(a), name(b)), (Some(x), Some(y)) if x[..3] == y[..3])
+            }),
+        },
+    )?;
+    assert_eq!(
+        db.follow_one(bolt_b, "similar").iter().collect::<Vec<_>>(),
+        vec![bolt_a]
+    );
+    assert!(db.follow_one(nut, "similar").is_empty());
+    assert!(db
+        .follow_path(acme, &["supplies", "similar"])
+        .contains(bolt_b));
+
+    let same = VirtualRelationDef::AttrEquals(AttrEqualsDef::same("Part", "part_number"));
+    db.register_virtual_relation("same_part", same.clone())?;
+    db.register_virtual_relation("same_part", same.clone())?;
+    for name in ["similar", "supplies"] {
+        let err = db
+            .register_virtual_relation(name, same.clone())
+            .unwrap_err();
+        assert!(matches!(err, PathDbError::VirtualRelationConflict { .. }));
+    }
+    Ok(())
+}
+
+#[test]
+fn attr_equality_definitions_survive_snapshot_round_trip() -> Result<()> {
+    let (mut db, [bolt_a, bolt_b, ..]) = parts();
+    db.register_virtual_relation(
+        "same_part",
+        VirtualRelationDef::AttrEquals(AttrEqualsDef::same("Part", "part_number")),
+    )?;
+    db.register_virtual_relation(
+        "similar",
+        VirtualRelationDef::Predicate {
+            target_type: "Part".to_string(),
+            predicate: Arc::new(|_: &PathDB, _: u32, _: u32| true),
+        },
+    )?;
+    let loaded = PathDB::from_bytes(&db.to_bytes()?)?;
+    assert!(loaded.follow_one(bolt_a, "same_part").contains(bolt_b));
+    // Predicates are session-only.
+    assert_eq!(loaded.virtual_relation_names(), vec!["same_part"]);
+    Ok(())
+}
@@ -0,0 +1,231 @@
+//! Access control: per-role type denial and attribute redaction.
+//!
+//! Some consumers must not see PII or whole entity types. A [`RolePolicy`]
+//! describes what one role may see; an [`AccessView`] applies it to a PathDB:
+//!
+//! - **deny types**: entities of a denied type (canonical or virtual) are
+//!   invisible, as are relations touching them or whose type is denied,
+//! - **redact attributes**: listed attributes are replaced by
+//!   [`REDACTED_VALUE`]; with `redact_pii`, attribute names tagged PII by the
+//!   proto ingester (`proto_field_pii -> Bool(true)`) are redacted as well.
+//!
+//! Enforcement points: `AccessView::get_entity` (the only way the view hands
+//! out `EntityView`s), `AccessView::execute`, which hides denied entities and
+//! relation types at every hop (a path through a denied entity finds
+//! nothing), and `AccessView::redacted_pathdb`, which every export path goes
+//! through. The CLI serves and exports `redacted_pathdb` when given
+//! `--access-policy`/`--access-role` (`db serve`, `repl`, `db pathdb
+//! export-axi`).
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use axiograph_pathdb::axi_export::export_pathdb_to_axi_v1;
+use axiograph_pathdb::pagination::{page_bitmap, Page, PageCursor};
+use axiograph_pathdb::{ContextScope, EntityView, PathDB, PathQuery};
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use crate::redaction::copy_filtered;
+use crate::{Result, StorageError, UnifiedStorage};
+
+/// Replacement value for redacted attributes.
+pub const REDACTED_VALUE: &str = "[redacted]";
+
+/// Relation emitted by the proto ingester for `(pii) = true/false` annotations.
+pub const PROTO_FIELD_PII_REL: &str = "proto_field_pii";
+
+/// Visibility rules for one role.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RolePolicy {
+    /// Entity (or relation) types this role may not see.
+    #[serde(default)]
+    pub deny_types: BTreeSet<String>,
+    /// Attribute names whose values are redacted.
+    #[serde(default)]
+    pub redact_attrs: BTreeSet<String>,
+    /// Also redact attributes whose names are tagged PII in the graph.
+    #[serde(default)]
+    pub redact_pii: bool,
+}
+
+/// Named role policies (e.g. loaded from JSON config).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessPolicy {
+    pub roles: BTreeMap<String, RolePolicy>,
+}
+
+impl AccessPolicy {
+    pub fn role(&self, name: &str) -> Result<&RolePolicy> {
+        self.roles
+            .get(name)
+            .ok_or_else(|| StorageError::UnknownRole(name.to_string()))
+    }
+}
+
+/// Attribute names of proto fields annotated `pii = true`.
+///
+/// Both the field `name` (last path segment) and its `json_name` are returned.
+pub fn pii_attribute_names(db: &PathDB) -> BTreeSet<String> {
+    let mut out = BTreeSet::new();
+    let Some(fields) = db.find_by_type("ProtoField") else {
+        return out;
+    };
+    for field in fields {
+        let is_pii = db
+            .follow_one(field, PROTO_FIELD_PII_REL)
+            .iter()
+            .filter_map(|b| db.get_entity(b))
+            .any(|b| b.attrs.get("name").map(String::as_str) == Some("true"));
+        if !is_pii {
+            continue;
+        }
+        let Some(view) = db.get_entity(field) else {
+            continue;
+        };
+        for key in ["name", "json_name"] {
+            if let Some(v) = view.attrs.get(key) {
+                let leaf = v.rsplit('.').next().unwrap_or(v);
+                out.insert(leaf.to_string());
+            }
+        }
+    }
+    out
+}
+
+/// A role-filtered read view over a PathDB.
+pub struct AccessView<'a> {
+    db: &'a PathDB,
+    denied: RoaringBitmap,
+    deny_types: BTreeSet<String>,
+    redact_attrs: BTreeSet<String>,
+    /// Visible entities and relation types, applied at every query hop.
+    scope: ContextScope,
+}
+
+impl<'a> AccessView<'a> {
+    pub fn new(db: &'a PathDB, policy: &RolePolicy) -> Self {
+        let mut denied = RoaringBitmap::new();
+        for t in &policy.deny_types {
+            if let Some(ids) = db.find_by_type(t) {
+                denied |= ids;
+            }
+        }
+        let mut redact_attrs = policy.redact_attrs.clone();
+        if policy.redact_pii {
+            redact_attrs.extend(pii_attribute_names(db));
+        }
+        let mut visible = RoaringBitmap::new();
+        visible.insert_range(0..db.entities.len() as u32);
+        let mut scope = ContextScope::over(&visible - &denied);
+        for rel_type in policy
+            .deny_types
+            .iter()
+            .filter_map(|t| db.interner.id_of(t))
+        {
+            scope = scope.hide_relation_type(rel_type);
+        }
+        Self {
+            db,
+            denied,
+            deny_types: policy.deny_types.clone(),
+            redact_attrs,
+            scope,
+        }
+    }
+
+    pub fn is_visible(&self, entity_id: u32) -> bool {
+        (entity_id as usize) < self.db.entities.len() && !self.denied.contains(entity_id)
+    }
+
+    /// Attribute names redacted for this role.
+    pub fn redacted_attrs(&self) -> &BTreeSet<String> {
+        &self.redact_attrs
+    }
+
+    /// Policy-enforced entity view (`None` if the entity is denied).
+    pub fn get_entity(&self, entity_id: u32) -> Option<EntityView> {
+        if !self.is_visible(entity_id) {
+            return None;
+        }
+        let mut view = self.db.get_entity(entity_id)?;
+        for (k, v) in view.attrs.iter_mut() {
+            if self.redact_attrs.contains(k) {
+                *v = REDACTED_VALUE.to_string();
+            }
+        }
+        // The label is derived from (possibly redacted) attributes: resolve it
+        // again from what this role may see.
+        let policy = self.db.label_policy();
+        if policy.keys.iter().any(|k| self.redact_attrs.contains(k)) {
+            view.label = policy.resolve(&view.attrs);
+        }
+        Some(view)
+    }
+
+    /// Drop denied entities from a result set.
+    pub fn filter(&self, ids: &RoaringBitmap) -> RoaringBitmap {
+        ids - &self.denied
+    }
+
+    /// Execute a query seeing only what this role may see, at every hop.
+    pub fn execute(&self, query: &PathQuery) -> RoaringBitmap {
+        self.db.execute_within(query, &self.scope)
+    }
+
+    /// One page of [`Self::execute`], resolved to policy-enforced views.
+    pub fn execute_paged(
+        &self,
+        query: &PathQuery,
+        cursor: Option<&PageCursor>,
+        limit: usize,
+    ) -> Page<EntityView> {
+        let page = page_bitmap(&self.execute(query), cursor, limit);
+        Page {
+            items: page
+                .items
+                .iter()
+                .filter_map(|&id| self.get_entity(id))
+                .collect(),
+            next: page.next,
+            total: page.total,
+        }
+    }
+
+    /// Copy of the PathDB with denied entities/relations removed and attributes
+    /// redacted. All exports for this role should be produced from this copy.
+    ///
+    /// Entity ids are renumbered densely in ascending original-id order.
+    pub fn redacted_pathdb(&self) -> PathDB {
+        copy_filtered(
+            self.db,
+            |id| self.is_visible(id),
+            |rel_type| !self.deny_types.contains(rel_type),
+            |k, v| {
+                if self.redact_attrs.contains(k) {
+                    REDACTED_VALUE.to_string()
+                } else {
+                    v
+                }
+            },
+        )
+    }
+
+    /// Canonical `.axi` export of the redacted snapshot.
+    pub fn export_axi_v1(&self) -> Result<String> {
+        Ok(export_pathdb_to_axi_v1(&self.redacted_pathdb())?)
+    }
+
+    /// `.axpd` bytes of the redacted snapshot.
+    pub fn export_axpd(&self) -> Result<Vec<u8>> {
+        Ok(self.redacted_pathdb().to_bytes()?)
+    }
+}
+
+impl UnifiedStorage {
+    /// Run `f` against a role-filtered view of the current PathDB.
+    pub fn with_access_view<R>(&self, policy: &RolePolicy, f: impl FnOnce(&AccessView) -> R) -> R {
+        let db = self.pathdb.read();
+        let view = AccessView::new(&db, policy);
+        f(&view)
+    }
+}
@@ -0,0 +1,240 @@
+//! Append-only audit log of read queries (compliance).
+//!
+//! The changelog records every write; this records reads: who ran which
+//! query against which snapshot version, and how many results came back.
+//! Queries are stored in a stable form: a PathQuery as its JSON encoding,
+//! AxQL and SQL-ish queries as their text. Records are appended as JSON lines
+//! and never rewritten.
+//!
+//! Besides [`UnifiedStorage::execute_audited`], the CLI's query entry points
+//! (`repl`, `db serve`, `query-cert`) record into a log given by
+//! `--audit-log`.
+//!
+//! Sampling is deterministic (every `1 / sample_rate`-th query is kept), so
+//! audit volume is predictable and reproducible in tests. Principals listed in
+//! `always_audit` bypass sampling.
+
+use std::collections::BTreeSet;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use axiograph_pathdb::PathQuery;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use crate::{Result, StorageError, UnifiedStorage};
+
+/// Query audit configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryAuditConfig {
+    /// JSONL file records are appended to.
+    pub path: PathBuf,
+    /// Fraction of queries recorded, in `[0, 1]`.
+    pub sample_rate: f64,
+    /// Principals that are always recorded regardless of sampling.
+    #[serde(default)]
+    pub always_audit: BTreeSet<String>,
+}
+
+impl QueryAuditConfig {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            sample_rate: 1.0,
+            always_audit: BTreeSet::new(),
+        }
+    }
+}
+
+/// The audited query, by language.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditedQuery {
+    PathQuery { query: PathQuery },
+    Axql { query: String },
+    Sql { query: String },
+}
+
+/// One audited read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryAuditRecord {
+    /// Monotonic sequence number over all queries seen (including unsampled).
+    pub seq: u64,
+    pub timestamp: DateTime<Utc>,
+    pub principal: String,
+    pub query: AuditedQuery,
+    /// Snapshot version the query ran against.
+    pub snapshot: String,
+    pub result_count: Option<u64>,
+}
+
+/// Append-only query audit log.
+pub struct QueryAuditLog {
+    config: QueryAuditConfig,
+    seq: Mutex<u64>,
+}
+
+impl QueryAuditLog {
+    pub fn open(config: QueryAuditConfig) -> Result<Self> {
+        if !(0.0..=1.0).contains(&config.sample_rate) {
+            return Err(StorageError::InvalidConfig(format!(
+                "query audit sample_rate must be in [0, 1], got {}",
+                config.sample_rate
+            )));
+        }
+        if let Some(parent) = config.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+        // Continue the sequence across restarts.
+        let seq = Self::read_path(&config.path)?
+            .last()
+            .map_or(0, |r| r.seq + 1);
+        Ok(Self {
+            config,
+            seq: Mutex::new(seq),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.config.path
+    }
+
+    fn sampled(&self, seq: u64, principal: &str) -> bool {
+        if self.config.always_audit.contains(principal) {
+            return true;
+        }
+        let rate = self.config.sample_rate;
+        ((seq + 1) as f64 * rate).floor() > (seq as f64 * rate).floor()
+    }
+
+    /// Record a query (subject to sampling). Returns whether it was written.
+    pub fn record(
+        &self,
+        principal: &str,
+        query: AuditedQuery,
+        snapshot: &str,
+        result_count: Option<u64>,
+    ) -> Result<bool> {
+        let mut seq = self.seq.lock();
+        let current = *seq;
+        *seq += 1;
+        if !self.sampled(current, principal) {
+            return Ok(false);
+        }
+
+        let record = QueryAuditRecord {
+            seq: current,
+            timestamp: Utc::now(),
+            principal: principal.to_string(),
+            query,
+            snapshot: snapshot.to_string(),
+            result_count,
+        };
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.config.path)?;
+        writeln!(file, "{}", serde_json::to_string(&record)?)?;
+        file.sync_data()?;
+        Ok(true)
+    }
+
+    /// All records, oldest first.
+    pub fn records(&self) -> Result<Vec<QueryAuditRecord>> {
+        Self::read_path(&self.config.path)
+    }
+
+    /// Records in `[from, to)` for compliance export.
+    pub fn export_range(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<QueryAuditRecord>> {
+        Ok(self
+            .records()?
+            .into_iter()
+            .filter(|r| r.timestamp >= from && r.timestamp < to)
+            .collect())
+    }
+
+    fn read_path(path: &Path) -> Result<Vec<QueryAuditRecord>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let reader = BufReader::new(std::fs::File::open(path)?);
+        let mut out = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            out.push(serde_json::from_str(&line)?);
+        }
+        Ok(out)
+    }
+}
+
+impl UnifiedStorage {
+    /// Enable read-query auditing for this storage handle.
+    pub fn enable_query_audit(&self, config: QueryAuditConfig) -> Result<()> {
+        *self.query_audit.write() = Some(Arc::new(QueryAuditLog::open(config)?));
+        Ok(())
+    }
+
+    pub fn query_audit(&self) -> Option<Arc<QueryAuditLog>> {
+        self.query_audit.read().clone()
+    }
+
+    /// Version of the current snapshot: the last applied change id (or `genesis`).
+    pub fn snapshot_version(&self) -> String {
+        self.changelog
+            .read()
+            .iter()
+            .rev()
+            .find(|c| matches!(c.status, crate::ChangeStatus::Applied))
+            .map_or_else(|| "genesis".to_string(), |c| c.id.to_string())
+    }
+
+    /// Execute a PathQuery on behalf of `principal`, auditing it if enabled.
+    pub fn execute_audited(&self, principal: &str, query: &PathQuery) -> Result<RoaringBitmap> {
+        let snapshot = self.snapshot_version();
+        let result = self.pathdb.read().execute(query);
+        if let Some(log) = self.query_audit() {
+            log.record(
+                principal,
+                AuditedQuery::PathQuery {
+                    query: query.clone(),
+                },
+                &snapshot,
+                Some(result.len()),
+            )?;
+        }
+        Ok(result)
+    }
+
+    /// Audit an AxQL query executed elsewhere (e.g. by the CLI/REPL).
+    pub fn audit_axql(
+        &self,
+        principal: &str,
+        query: &str,
+        result_count: Option<u64>,
+    ) -> Result<()> {
+        if let Some(log) = self.query_audit() {
+            log.record(
+                principal,
+                AuditedQuery::Axql {
+                    query: query.to_string(),
+                },
+                &self.snapshot_version(),
+                result_count,
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,349 @@
+//! Canonical `.axi` generation for applied changes.
+//!
+//! Every fact is rendered through the `axiograph_dsl::printer`, so the files
+//! under `axi_dir` re-parse with `parse_axi_v1`. Each file starts with a
+//! `module` header; each flushed change appends one block:
+//!
+//! ```text
+//! -- Added at 2024-01-15T10:30:00+00:00
+//! instance Change_<id> of Knowledge:
+//!   Material = {Titanium}
+//!   attribute = {(entity=Titanium, key=hardness, value=36)}
+//!   usedWith = {(source=EndMill, target=Titanium, confidence=0.9)}
+//! theory Change_<id>_rules on Knowledge:
+//!   constraint SpeedLimit:
+//!     severity = warning
+//!     condition = speed <= 60
+//! -- retracted usedWith(EndMill, Titanium): user correction
+//! ```
+//!
+//! Entities are an object assignment plus `attribute` rows sorted by key;
+//! relations are tuples of their endpoints, confidence and (sorted)
+//! attributes. Names and values go through `escape_axi_value`, so arbitrary
+//! text stays printable. Retractions are comments: they are not facts.
+
+use axiograph_dsl::printer::{
+    escape_axi_value, print_assignment_v1, print_comment, print_constraint_v1, print_instance_v1,
+    print_theory_v1, sanitize_axi_ident,
+};
+use axiograph_dsl::schema_v1::{
+    ConstraintV1, InstanceAssignmentV1, SchemaV1Instance, SchemaV1Theory, SetItemV1, SetLiteralV1,
+};
+use chrono::Utc;
+use std::io::Write;
+
+use crate::{ChangeId, ChangeSource, Result, StorableFact, UnifiedStorage};
+
+/// Schema the generated instances and theories are declared against.
+pub const STORAGE_AXI_SCHEMA: &str = "Knowledge";
+
+/// Canonical `.axi` for one fact.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum AxiFragment {
+    Assignments(Vec<InstanceAssignmentV1>),
+    Constraint(ConstraintV1),
+    Comment(String),
+}
+
+impl AxiFragment {
+    /// The fragment as it appears in the file (`ChangePlan::axi_lines`).
+    pub(crate) fn render(&self) -> Result<String> {
+        Ok(match self {
+            AxiFragment::Assignments(assignments) => assignments
+                .iter()
+                .map(print_assignment_v1)
+                .collect::<std::result::Result<String, _>>()?,
+            AxiFragment::Constraint(constraint) => print_constraint_v1(constraint)?,
+            AxiFragment::Comment(comment) => print_comment(comment),
+        })
+    }
+}
+
+fn assignment(name: &str, items: Vec<SetItemV1>) -> InstanceAssignmentV1 {
+    InstanceAssignmentV1 {
+        name: escape_axi_value(name),
+        value: SetLiteralV1 { items },
+    }
+}
+
+fn tuple(fields: &[(&str, &str)]) -> SetItemV1 {
+    SetItemV1::Tuple {
+        fields: fields
+            .iter()
+            .map(|(k, v)| (escape_axi_value(k), escape_axi_value(v)))
+            .collect(),
+    }
+}
+
+fn sorted<'a>(attrs: impl IntoIterator<Item = (&'a str, &'a str)>) -> Vec<(&'a str, &'a str)> {
+    let mut attrs: Vec<_> = attrs.into_iter().collect();
+    attrs.sort();
+    attrs
+}
+
+/// A constraint body value: kept verbatim (conditions like `speed <= 60`
+/// stay readable) unless it would break the line, in which case it is escaped.
+fn body_value(s: &str) -> String {
+    let plain = !s.is_empty()
+        && s.trim() == s
+        && !s.contains(|c: char| c.is_control() || c == '#')
+        && !s.contains("--");
+    if plain {
+        s.to_string()
+    } else {
+        escape_axi_value(s)
+    }
+}
+
+/// `Type = {name}` plus one `attribute` row per (sorted) attribute.
+fn entity<'a>(
+    name: &str,
+    entity_type: &str,
+    attrs: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Vec<InstanceAssignmentV1> {
+    let mut out = vec![assignment(
+        entity_type,
+        vec![SetItemV1::Ident {
+            name: escape_axi_value(name),
+        }],
+    )];
+    let rows: Vec<SetItemV1> = sorted(attrs)
+        .into_iter()
+        .map(|(k, v)| tuple(&[("entity", name), ("key", k), ("value", v)]))
+        .collect();
+    if !rows.is_empty() {
+        out.push(assignment("attribute", rows));
+    }
+    out
+}
+
+/// Canonical `.axi` for `fact`.
+pub(crate) fn fact_fragment(fact: &StorableFact) -> AxiFragment {
+    match fact {
+        StorableFact::Entity {
+            name,
+            entity_type,
+            attributes,
+        } => AxiFragment::Assignments(entity(
+            name,
+            entity_type,
+            attributes.iter().map(|(k, v)| (k.as_str(), v.as_str())),
+        )),
+        StorableFact::Relation {
+            name,
+            rel_type,
+            source,
+            target,
+            confidence,
+            attributes,
+        } => {
+            let confidence = confidence.to_string();
+            let mut fields: Vec<(&str, &str)> = Vec::new();
+            if let Some(name) = name {
+                fields.push(("name", name));
+            }
+            fields.extend([
+                ("source", source.as_str()),
+                ("target", target.as_str()),
+                ("confidence", confidence.as_str()),
+            ]);
+            fields.extend(sorted(
+                attributes.iter().map(|(k, v)| (k.as_str(), v.as_str())),
+            ));
+            AxiFragment::Assignments(vec![assignment(rel_type, vec![tuple(&fields)])])
+        }
+        StorableFact::Constraint {
+            name,
+            condition,
+            severity,
+            message,
+        } => {
+            let mut body = vec![
+                format!("severity = {}", body_value(severity)),
+                format!("condition = {}", body_value(condition)),
+            ];
+            if let Some(message) = message {
+                body.push(format!("message = {}", body_value(message)));
+            }
+            AxiFragment::Constraint(ConstraintV1::NamedBlock {
+                name: escape_axi_value(name),
+                body,
+            })
+        }
+        StorableFact::TacitKnowledge {
+            name,
+            rule,
+            confidence,
+            domain,
+            source,
+        } => AxiFragment::Assignments(entity(
+            name,
+            "TacitKnowledge",
+            [
+                ("rule", rule.as_str()),
+                ("confidence", &confidence.to_string()),
+                ("domain", domain.as_str()),
+                ("source", source.as_str()),
+            ],
+        )),
+        StorableFact::Concept {
+            name,
+            description,
+            difficulty,
+            prerequisites,
+        } => {
+            let mut assignments = entity(
+                name,
+                "Concept",
+                [
+                    ("description", description.as_str()),
+                    ("difficulty", difficulty.as_str()),
+                ],
+            );
+            if !prerequisites.is_empty() {
+                let rows = prerequisites
+                    .iter()
+                    .map(|p| tuple(&[("concept", name), ("requires", p)]))
+                    .collect();
+                assignments.push(assignment("prerequisite", rows));
+            }
+            AxiFragment::Assignments(assignments)
+        }
+        StorableFact::SafetyGuideline {
+            name,
+            title,
+            severity,
+            explanation,
+        } => AxiFragment::Assignments(entity(
+            name,
+            "SafetyGuideline",
+            [
+                ("title", title.as_str()),
+                ("severity", severity.as_str()),
+                ("explanation", explanation.as_str()),
+            ],
+        )),
+        StorableFact::Retraction {
+            rel_type,
+            source,
+            target,
+            confidence,
+            reason,
+        } => AxiFragment::Comment(match confidence {
+            Some(c) => {
+                format!("downgraded {rel_type}({source}, {target}) @confidence({c}): {reason}")
+            }
+            None => format!("retracted {rel_type}({source}, {target}): {reason}"),
+        }),
+        StorableFact::Deletion { target, reason, .. } => {
+            AxiFragment::Comment(format!("deleted {target}: {reason}"))
+        }
+        StorableFact::ConfidenceDecay {
+            target,
+            confidence,
+            half_life_days,
+        } => AxiFragment::Comment(format!(
+            "decayed {target} @confidence({confidence}) (half-life {half_life_days} days)"
+        )),
+        StorableFact::DecayExemption { target, reason } => {
+            AxiFragment::Comment(format!("exempt from decay {target}: {reason}"))
+        }
+        StorableFact::Verification {
+            target,
+            verifier,
+            note,
+            confidence,
+        } => AxiFragment::Comment(match note {
+            Some(note) => {
+                format!("verified {target} @confidence({confidence}) by {verifier}: {note}")
+            }
+            None => format!("verified {target} @confidence({confidence}) by {verifier}"),
+        }),
+    }
+}
+
+/// Render one change: header comments, then its instance block, theory block
+/// and comments (in that order, each in fact order).
+fn render_change(change_id: ChangeId, fragments: &[AxiFragment], header: &str) -> Result<String> {
+    let block = format!("Change_{}", change_id.simple());
+    let mut instance = SchemaV1Instance {
+        name: block.clone(),
+        schema: STORAGE_AXI_SCHEMA.to_string(),
+        assignments: Vec::new(),
+    };
+    let mut theory = SchemaV1Theory {
+        name: format!("{block}_rules"),
+        schema: STORAGE_AXI_SCHEMA.to_string(),
+        constraints: Vec::new(),
+        equations: Vec::new(),
+        rewrite_rules: Vec::new(),
+        views: Vec::new(),
+    };
+    let mut comments = String::new();
+    for fragment in fragments {
+        match fragment {
+            AxiFragment::Assignments(a) => instance.assignments.extend(a.iter().cloned()),
+            AxiFragment::Constraint(c) => theory.constraints.push(c.clone()),
+            AxiFragment::Comment(_) => comments.push_str(&fragment.render()?),
+        }
+    }
+
+    let mut out = format!("\n{}", print_comment(header));
+    if !instance.assignments.is_empty() {
+        out.push_str(&print_instance_v1(&instance)?);
+    }
+    if !theory.constraints.is_empty() {
+        out.push_str(&print_theory_v1(&theory)?);
+    }
+    out.push_str(&comments);
+    Ok(out)
+}
+
+fn source_header(source: &ChangeSource) -> String {
+    let mut header = format!("Added at {}", Utc::now().to_rfc3339());
+    match source {
+        ChangeSource::LLMExtraction {
+            model, confidence, ..
+        } => header.push_str(&format!(
+            "\nSource: LLM extraction (model: {model}, confidence: {confidence:.2})"
+        )),
+        ChangeSource::UserEdit { user_id } => header.push_str(&format!(
+            "\nSource: User edit ({})",
+            user_id.as_deref().unwrap_or("anonymous")
+        )),
+        _ => {}
+    }
+    header
+}
+
+impl UnifiedStorage {
+    /// Append `change_id`'s fragments to the `.axi` file for `source`,
+    /// starting the file with a `module` header if it is new.
+    pub(crate) fn append_to_axi(
+        &self,
+        change_id: ChangeId,
+        fragments: &[AxiFragment],
+        source: &ChangeSource,
+    ) -> Result<()> {
+        let Some(filename) = Self::axi_filename(source) else {
+            return Ok(());
+        };
+        let text = render_change(change_id, fragments, &source_header(source))?;
+
+        let path = self.config.axi_dir.join(filename);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let is_new = std::fs::metadata(&path).map_or(true, |m| m.len() == 0);
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        if is_new {
+            let stem = filename.trim_end_matches(".axi");
+            writeln!(file, "module {}", sanitize_axi_ident(stem))?;
+        }
+        file.write_all(text.as_bytes())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,286 @@
+//! Backup and restore with integrity manifests.
+//!
+//! A backup directory holds everything needed to rebuild a storage:
+//!
+//! ```text
+//! <backup>/
+//!   knowledge.axpd     PathDB snapshot (the live, applied state)
+//!   changelog.json     full change history (incl. rolled-back changes)
+//!   axi/*.axi          the `.axi` files of `axi_dir`
+//!   manifest.json      format version + SHA-256 digest and size of every file
+//! ```
+//!
+//! The manifest is written last, so a directory without one is an incomplete
+//! backup. [`UnifiedStorage::restore`] verifies every digest before writing
+//! anything, then loads the storage through the normal constructor (which
+//! also decodes the snapshot and changelog).
+//!
+//! Pending (unflushed) changes are not part of a backup, and neither are the
+//! periodic `pathdb_as_of` snapshots: they are a cache, and as-of reads fall
+//! back to changelog replay without them.
+
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+
+use crate::{Result, StorageConfig, StorageError, UnifiedStorage};
+
+/// Manifest file name inside a backup directory.
+pub const BACKUP_MANIFEST_FILE: &str = "manifest.json";
+/// Format tag recorded in every manifest.
+pub const BACKUP_FORMAT_VERSION: &str = "axiograph_storage_backup_v1";
+
+const SNAPSHOT_FILE: &str = "knowledge.axpd";
+const CHANGELOG_FILE: &str = "changelog.json";
+const AXI_DIR: &str = "axi";
+
+/// What a backed-up file is restored as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupFileRole {
+    Snapshot,
+    Changelog,
+    Axi,
+}
+
+/// One file of a backup, relative to the backup directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupFile {
+    pub path: String,
+    pub role: BackupFileRole,
+    /// `sha256:<hex>` of the file contents.
+    pub sha256: String,
+    pub size: u64,
+}
+
+/// Contents of `manifest.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub version: String,
+    pub created_at: DateTime<Utc>,
+    /// Changelog entries captured (applied and rolled back).
+    pub changes: usize,
+    pub files: Vec<BackupFile>,
+}
+
+impl BackupManifest {
+    fn file(&self, role: BackupFileRole) -> Option<&BackupFile> {
+        self.files.iter().find(|f| f.role == role)
+    }
+}
+
+/// `sha256:<hex>` of `bytes`.
+fn sha256_digest(bytes: &[u8]) -> String {
+    use std::fmt::Write as _;
+    let digest = Sha256::digest(bytes);
+    let mut out = String::with_capacity(7 + 64);
+    out.push_str("sha256:");
+    for b in digest.iter() {
+        let _ = write!(&mut out, "{:02x}", b);
+    }
+    out
+}
+
+fn integrity(path: &str, reason: impl Into<String>) -> StorageError {
+    StorageError::BackupIntegrity {
+        path: path.to_string(),
+        reason: reason.into(),
+    }
+}
+
+/// Manifest paths must stay inside the backup directory.
+fn backup_path(dir: &Path, rel: &str) -> Result<PathBuf> {
+    let rel_path = Path::new(rel);
+    if rel.is_empty()
+        || !rel_path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+    {
+        return Err(integrity(rel, "path escapes the backup directory"));
+    }
+    Ok(dir.join(rel_path))
+}
+
+/// Top-level `.axi` files of `dir` (the files `UnifiedStorage` indexes), sorted.
+fn axi_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if dir.exists() {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|e| e == "axi") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Check a backup directory against its manifest without restoring it.
+///
+/// Fails on a missing or unknown-version manifest, a missing snapshot or
+/// changelog entry, and any file whose size or SHA-256 digest differs.
+pub fn verify_backup(dir: impl AsRef<Path>) -> Result<BackupManifest> {
+    let dir = dir.as_ref();
+    let manifest_path = dir.join(BACKUP_MANIFEST_FILE);
+    if !manifest_path.exists() {
+        return Err(integrity(
+            BACKUP_MANIFEST_FILE,
+            "missing manifest (incomplete backup?)",
+        ));
+    }
+    let manifest: BackupManifest = serde_json::from_slice(&std::fs::read(&manifest_path)?)?;
+    if manifest.version != BACKUP_FORMAT_VERSION {
+        return Err(integrity(
+            BACKUP_MANIFEST_FILE,
+            format!("unsupported backup version `{}`", manifest.version),
+        ));
+    }
+    for role in [BackupFileRole::Snapshot, BackupFileRole::Changelog] {
+        if manifest.file(role).is_none() {
+            return Err(integrity(
+                BACKUP_MANIFEST_FILE,
+                format!("manifest lists no {role:?} file"),
+            ));
+        }
+    }
+    for file in &manifest.files {
+        let bytes = std::fs::read(backup_path(dir, &file.path)?)
+            .map_err(|e| integrity(&file.path, format!("unreadable: {e}")))?;
+        if bytes.len() as u64 != file.size {
+            return Err(integrity(
+                &file.path,
+                format!("size {} != manifest {}", bytes.len(), file.size),
+            ));
+        }
+        let actual = sha256_digest(&bytes);
+        if actual != file.sha256 {
+            return Err(integrity(
+                &file.path,
+                format!("digest {actual} != manifest {}", file.sha256),
+            ));
+        }
+    }
+    Ok(manifest)
+}
+
+impl UnifiedStorage {
+    /// Write a backup of the applied state to `dir` (created if missing; must
+    /// be empty).
+    ///
+    /// The snapshot and changelog are taken together under read locks, so they
+    /// describe the same point in the history.
+    pub fn backup(&self, dir: impl AsRef<Path>) -> Result<BackupManifest> {
+        let dir = dir.as_ref();
+        if dir.exists() && std::fs::read_dir(dir)?.next().is_some() {
+            return Err(StorageError::InvalidConfig(format!(
+                "backup directory `{}` is not empty",
+                dir.display()
+            )));
+        }
+        std::fs::create_dir_all(dir.join(AXI_DIR))?;
+
+        let (snapshot, changelog, changes) = {
+            let pathdb = self.pathdb.read();
+            let changelog = self.changelog.read();
+            (
+                pathdb.to_bytes()?,
+                serde_json::to_vec_pretty(&*changelog)?,
+                changelog.len(),
+            )
+        };
+
+        let mut files = Vec::new();
+        let mut write = |rel: String, role: BackupFileRole, bytes: &[u8]| -> Result<()> {
+            std::fs::write(dir.join(&rel), bytes)?;
+            files.push(BackupFile {
+                path: rel,
+                role,
+                sha256: sha256_digest(bytes),
+                size: bytes.len() as u64,
+            });
+            Ok(())
+        };
+        write(
+            SNAPSHOT_FILE.to_string(),
+            BackupFileRole::Snapshot,
+            &snapshot,
+        )?;
+        write(
+            CHANGELOG_FILE.to_string(),
+            BackupFileRole::Changelog,
+            &changelog,
+        )?;
+        for path in axi_files(&self.config.axi_dir)? {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let bytes = std::fs::read(&path)?;
+            write(format!("{AXI_DIR}/{name}"), BackupFileRole::Axi, &bytes)?;
+        }
+
+        let manifest = BackupManifest {
+            version: BACKUP_FORMAT_VERSION.to_string(),
+            created_at: Utc::now(),
+            changes,
+            files,
+        };
+        let tmp = dir.join(format!("{BACKUP_MANIFEST_FILE}.tmp"));
+        std::fs::write(&tmp, serde_json::to_vec_pretty(&manifest)?)?;
+        std::fs::rename(&tmp, dir.join(BACKUP_MANIFEST_FILE))?;
+        tracing::info!(
+            dir = %dir.display(),
+            changes,
+            files = manifest.files.len(),
+            "storage backup written"
+        );
+        Ok(manifest)
+    }
+
+    /// Verify the backup in `backup_dir`, copy it to the locations named by
+    /// `config`, and open the restored storage.
+    ///
+    /// Nothing is written unless every digest matches, and existing files are
+    /// never overwritten: restore into a fresh location (or remove the damaged
+    /// files first).
+    pub fn restore(backup_dir: impl AsRef<Path>, config: StorageConfig) -> Result<Self> {
+        let backup_dir = backup_dir.as_ref();
+        let manifest = verify_backup(backup_dir)?;
+
+        let mut plan: Vec<(PathBuf, PathBuf)> = Vec::new();
+        for file in &manifest.files {
+            let target = match file.role {
+                BackupFileRole::Snapshot => config.pathdb_path.clone(),
+                BackupFileRole::Changelog => config.changelog_path.clone(),
+                BackupFileRole::Axi => {
+                    let name = Path::new(&file.path)
+                        .file_name()
+                        .ok_or_else(|| integrity(&file.path, "axi entry has no file name"))?;
+                    config.axi_dir.join(name)
+                }
+            };
+            if target.exists() {
+                return Err(StorageError::InvalidConfig(format!(
+                    "restore target `{}` already exists",
+                    target.display()
+                )));
+            }
+            plan.push((backup_path(backup_dir, &file.path)?, target));
+        }
+
+        std::fs::create_dir_all(&config.axi_dir)?;
+        for (from, to) in &plan {
+            if let Some(parent) = to.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(from, to)?;
+        }
+        tracing::info!(
+            backup = %backup_dir.display(),
+            changes = manifest.changes,
+            files = plan.len(),
+            "storage restored from backup"
+        );
+        Self::new(config)
+    }
+}
@@ -0,0 +1,204 @@
+//! Confidence calibration from human review outcomes.
+//!
+//! Extractors attach confidences that are not calibrated (descriptor-derived
+//! facts all claim 0.98, regex patterns use fixed constants). The changelog
+//! already records what reviewers did with each change, so we can measure how
+//! often facts from a given source at a given confidence were actually kept:
+//!
+//! - `Applied` and never retracted → **accepted**,
+//! - `Rejected` or `Rolled` (rolled back) → **rejected**,
+//! - `Pending` → not reviewed yet, ignored.
+//!
+//! [`CalibrationModel::fit`] groups outcomes per source (see
+//! [`ChangeSource::calibration_key`]) into [`CALIBRATION_BINS`] equal-width
+//! confidence bins. [`CalibrationModel::calibrate`] maps a raw confidence to
+//! the smoothed acceptance rate of its bin, using the raw value as a prior
+//! worth `prior_strength` pseudo-reviews: sparse bins stay close to the
+//! extractor's own estimate, well-reviewed bins converge to the observed rate.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{Change, ChangeSource, ChangeStatus, StorableFact, UnifiedStorage};
+
+pub const CALIBRATION_VERSION_V1: &str = "confidence_calibration_v1";
+
+/// Number of equal-width confidence bins per source.
+pub const CALIBRATION_BINS: usize = 10;
+
+/// Default weight of the raw confidence, in pseudo-reviews.
+pub const DEFAULT_PRIOR_STRENGTH: f32 = 5.0;
+
+/// One reviewed fact.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewOutcome {
+    pub source: String,
+    pub confidence: f32,
+    pub accepted: bool,
+}
+
+impl ChangeSource {
+    /// Key under which facts from this source are calibrated, or `None` for
+    /// human-authored edits (nothing to calibrate).
+    pub fn calibration_key(&self) -> Option<String> {
+        match self {
+            ChangeSource::LLMExtraction { model, .. } => Some(format!("llm:{model}")),
+            ChangeSource::FileImport { path } => Some(format!(
+                "file:{}",
+                path.extension()
+                    .and_then(|e| e.to_str())
+                    .unwrap_or("")
+                    .to_ascii_lowercase()
+            )),
+            ChangeSource::API { client_id } => Some(format!("api:{client_id}")),
+            ChangeSource::System { .. } => Some("system".to_string()),
+            ChangeSource::UserEdit { .. } => None,
+        }
+    }
+}
+
+fn fact_confidence(fact: &StorableFact, source: &ChangeSource) -> Option<f32> {
+    let explicit = match fact {
+        StorableFact::Relation { confidence, .. }
+        | StorableFact::TacitKnowledge { confidence, .. } => Some(*confidence),
+        StorableFact::Entity { attributes, .. } => attributes
+            .iter()
+            .find(|(k, _)| k == "confidence")
+            .and_then(|(_, v)| v.parse().ok()),
+        StorableFact::Constraint { .. }
+        | StorableFact::Concept { .. }
+        | StorableFact::SafetyGuideline { .. }
+        | StorableFact::Retraction { .. }
+        | StorableFact::Deletion { .. }
+        | StorableFact::ConfidenceDecay { .. }
+        | StorableFact::DecayExemption { .. }
+        | StorableFact::Verification { .. } => None,
+    };
+    explicit
+        .or(match source {
+            ChangeSource::LLMExtraction { confidence, .. } => Some(*confidence),
+            _ => None,
+        })
+        .filter(|c| (0.0..=1.0).contains(c))
+}
+
+/// Review outcomes recorded in a changelog, one per fact that carries a
+/// confidence.
+pub fn review_outcomes(changelog: &[Change]) -> Vec<ReviewOutcome> {
+    let mut out = Vec::new();
+    for change in changelog {
+        let accepted = match change.status {
+            ChangeStatus::Applied => change.retracted_at.is_none(),
+            ChangeStatus::Rejected { .. } | ChangeStatus::Rolled { .. } => false,
+            ChangeStatus::Pending => continue,
+        };
+        let Some(source) = change.source.calibration_key() else {
+            continue;
+        };
+        for fact in &change.facts {
+            if let Some(confidence) = fact_confidence(fact, &change.source) {
+                out.push(ReviewOutcome {
+                    source: source.clone(),
+                    confidence,
+                    accepted,
+                });
+            }
+        }
+    }
+    out
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BinStats {
+    pub reviewed: u32,
+    pub accepted: u32,
+}
+
+/// Review statistics for one source.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceCalibration {
+    pub reviewed: u32,
+    pub accepted: u32,
+    /// `bins[i]` covers confidences in `[i/N, (i+1)/N)` (the last bin includes 1.0).
+    pub bins: Vec<BinStats>,
+}
+
+impl Default for SourceCalibration {
+    fn default() -> Self {
+        Self {
+            reviewed: 0,
+            accepted: 0,
+            bins: vec![BinStats::default(); CALIBRATION_BINS],
+        }
+    }
+}
+
+impl SourceCalibration {
+    /// Fraction of reviewed facts that were accepted.
+    pub fn reliability(&self) -> Option<f32> {
+        (self.reviewed > 0).then(|| self.accepted as f32 / self.reviewed as f32)
+    }
+}
+
+fn bin_of(confidence: f32) -> usize {
+    ((confidence * CALIBRATION_BINS as f32) as usize).min(CALIBRATION_BINS - 1)
+}
+
+/// Per-source calibration fitted from review outcomes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationModel {
+    pub version: String,
+    pub prior_strength: f32,
+    pub sources: BTreeMap<String, SourceCalibration>,
+}
+
+impl CalibrationModel {
+    pub fn fit(outcomes: &[ReviewOutcome], prior_strength: f32) -> Self {
+        let mut sources: BTreeMap<String, SourceCalibration> = BTreeMap::new();
+        for outcome in outcomes {
+            let stats = sources.entry(outcome.source.clone()).or_default();
+            let bin = &mut stats.bins[bin_of(outcome.confidence)];
+            stats.reviewed += 1;
+            bin.reviewed += 1;
+            if outcome.accepted {
+                stats.accepted += 1;
+                bin.accepted += 1;
+            }
+        }
+        Self {
+            version: CALIBRATION_VERSION_V1.to_string(),
+            prior_strength,
+            sources,
+        }
+    }
+
+    /// Calibrated confidence for a raw `confidence` from `source`. Unknown
+    /// sources and unreviewed bins return the raw confidence unchanged.
+    pub fn calibrate(&self, source: &str, confidence: f32) -> f32 {
+        let confidence = confidence.clamp(0.0, 1.0);
+        let Some(stats) = self.sources.get(source) else {
+            return confidence;
+        };
+        let bin = stats
+            .bins
+            .get(bin_of(confidence))
+            .copied()
+            .unwrap_or_default();
+        let k = self.prior_strength.max(0.0);
+        if bin.reviewed == 0 && k == 0.0 {
+            return confidence;
+        }
+        (bin.accepted as f32 + k * confidence) / (bin.reviewed as f32 + k)
+    }
+
+    pub fn reliability(&self, source: &str) -> Option<f32> {
+        self.sources.get(source).and_then(|s| s.reliability())
+    }
+}
+
+impl UnifiedStorage {
+    /// Fit a calibration model from this storage's changelog.
+    pub fn fit_calibration(&self, prior_strength: f32) -> CalibrationModel {
+        CalibrationModel::fit(&review_outcomes(&self.changelog.read()), prior_strength)
+    }
+}
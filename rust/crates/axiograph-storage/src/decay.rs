@@ -0,0 +1,346 @@
+//! Confidence decay: knowledge fades unless reaffirmed.
+//!
+//! Tacit knowledge extracted from an old conversation should not keep the
+//! confidence it had the day it was extracted. A [`DecayPolicy`] gives each
+//! source a half-life (keyed like calibration, see
+//! [`ChangeSource::calibration_key`]), and [`UnifiedStorage::decay_confidences`]
+//! is the periodic job that applies it:
+//!
+//! - every relation and tacit-knowledge fact decays from the confidence of
+//!   its **latest affirmation** (the last applied change asserting it, or a
+//!   downgrade): `c(t) = c₀ · 2^(-age / half_life)`, computed with
+//!   [`VerifiedProb`] so the result stays in `[0, 1]`, and never below
+//!   `floor`;
+//! - re-asserting a fact restarts its clock, so the next run restores it;
+//! - facts last affirmed by a human edit never decay, nor do facts marked with
+//!   a [`StorableFact::DecayExemption`] (see [`UnifiedStorage::exempt_from_decay`])
+//!   or verified by a reviewer (see `verification`).
+//!
+//! The job records the new values as [`StorableFact::ConfidenceDecay`] facts in
+//! a `System` change, so rebuilds, rollbacks and `pathdb_as_of` see the same
+//! confidences as the live graph. Values are recomputed from the affirmation
+//! each run, not compounded, so running the job more often does not decay
+//! faster; `min_step` only keeps tiny updates out of the changelog.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use axiograph_pathdb::{PathDB, VerifiedProb};
+
+use crate::{
+    retract_relation, ChangeId, ChangeSource, ChangeStatus, DeletionTarget, Result, StorableFact,
+    UnifiedStorage,
+};
+
+/// Entity attribute holding a tacit-knowledge fact's confidence.
+pub const CONFIDENCE_ATTR: &str = "confidence";
+
+/// How confidences fade with age.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecayPolicy {
+    /// Half-life in days per source: a calibration key (`llm:<model>`,
+    /// `api:<client>`, `file:<ext>`) or just its kind (`llm`, `api`, `file`,
+    /// `system`). The full key wins.
+    #[serde(default)]
+    pub half_life_days: BTreeMap<String, f64>,
+    /// Half-life for sources not listed; `None` leaves them alone.
+    #[serde(default)]
+    pub default_half_life_days: Option<f64>,
+    /// Decay never takes a confidence below this.
+    #[serde(default)]
+    pub floor: f32,
+    /// Smaller updates are skipped.
+    #[serde(default = "default_min_step")]
+    pub min_step: f32,
+}
+
+fn default_min_step() -> f32 {
+    0.01
+}
+
+impl Default for DecayPolicy {
+    /// Nothing decays until a half-life is configured.
+    fn default() -> Self {
+        Self {
+            half_life_days: BTreeMap::new(),
+            default_half_life_days: None,
+            floor: 0.0,
+            min_step: default_min_step(),
+        }
+    }
+}
+
+impl DecayPolicy {
+    /// Half-life for facts from `source`, or `None` if they do not decay
+    /// (human edits always; other sources unless configured).
+    pub fn half_life_for(&self, source: &ChangeSource) -> Option<f64> {
+        let key = source.calibration_key()?;
+        let kind = key.split(':').next().unwrap_or(&key);
+        self.half_life_days
+            .get(&key)
+            .or_else(|| self.half_life_days.get(kind))
+            .copied()
+            .or(self.default_half_life_days)
+            .filter(|days| *days > 0.0)
+    }
+
+    /// `confidence` after `age_days` with the given half-life, or `None` if
+    /// `confidence` is not a probability.
+    pub fn decay(&self, confidence: f32, age_days: f64, half_life_days: f64) -> Option<f32> {
+        let base = VerifiedProb::try_new(confidence)?;
+        let factor = VerifiedProb::try_new(0.5f64.powf(age_days.max(0.0) / half_life_days) as f32)?;
+        let decayed = base.and_independent(&factor).value();
+        Some(decayed.max(self.floor.min(base.value())))
+    }
+}
+
+/// One confidence the decay job changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecayedFact {
+    pub target: DeletionTarget,
+    pub from: f32,
+    pub to: f32,
+    pub half_life_days: f64,
+    /// When the fact was last affirmed.
+    pub affirmed_at: DateTime<Utc>,
+}
+
+/// Result of [`UnifiedStorage::decay_confidences`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DecayReport {
+    pub decayed: Vec<DecayedFact>,
+    /// Facts left alone: exempt, human-authored, or without a half-life.
+    pub exempt: usize,
+    /// The change recording the new confidences (`None` if nothing changed).
+    pub change_id: Option<ChangeId>,
+}
+
+impl StorableFact {
+    /// Mark `target` as exempt from confidence decay.
+    pub fn decay_exemption(target: DeletionTarget, reason: impl Into<String>) -> Self {
+        StorableFact::DecayExemption {
+            target,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// The latest assertion of a decayable fact.
+struct Affirmation {
+    confidence: f32,
+    at: DateTime<Utc>,
+    half_life_days: Option<f64>,
+}
+
+/// Current confidence of `target` in `pathdb`: the highest of its matching
+/// edges, or a named entity's `confidence` attribute.
+pub(crate) fn current_confidence(pathdb: &PathDB, target: &DeletionTarget) -> Option<f32> {
+    match target {
+        DeletionTarget::Relation {
+            rel_type,
+            source,
+            target,
+        } => {
+            let rel = pathdb.interner.id_of(rel_type)?;
+            let sources = pathdb.entities_with_attr_fuzzy("name", source, 0);
+            let targets = pathdb.entities_with_attr_fuzzy("name", target, 0);
+            let mut best: Option<f32> = None;
+            for s in &sources {
+                for t in &targets {
+                    if let Some(r) = pathdb
+                        .relations
+                        .edge_relation_id(s, rel, t)
+                        .and_then(|id| pathdb.relations.get_relation(id))
+                    {
+                        best = Some(best.map_or(r.confidence, |b| b.max(r.confidence)));
+                    }
+                }
+            }
+            best
+        }
+        DeletionTarget::Entity { name } => {
+            let key = pathdb.interner.id_of(CONFIDENCE_ATTR)?;
+            pathdb
+                .entities_with_attr_fuzzy("name", name, 0)
+                .iter()
+                .filter_map(|id| pathdb.entities.get_attr(id, key))
+                .filter_map(|value| pathdb.interner.lookup(value)?.parse::<f32>().ok())
+                .reduce(f32::max)
+        }
+    }
+}
+
+/// Set the confidence of `target` (every matching edge, or the `confidence`
+/// attribute of every entity with that name). Returns how many changed.
+pub(crate) fn set_confidence(
+    pathdb: &mut PathDB,
+    target: &DeletionTarget,
+    confidence: f32,
+) -> usize {
+    match target {
+        DeletionTarget::Relation {
+            rel_type,
+            source,
+            target,
+        } => retract_relation(pathdb, rel_type, source, target, Some(confidence)),
+        DeletionTarget::Entity { name } => {
+            let value = confidence.to_string();
+            pathdb
+                .entities_with_attr_fuzzy("name", name, 0)
+                .iter()
+                // Ids come from the entity store, so they are in range.
+                .filter(|&id| {
+                    pathdb
+                        .upsert_entity_attr(id, CONFIDENCE_ATTR, &value)
+                        .is_ok()
+                })
+                .count()
+        }
+    }
+}
+
+impl UnifiedStorage {
+    /// Periodic decay job: recompute every decayable confidence as of `now`
+    /// and record the ones that moved by at least `min_step` (see the module
+    /// docs). Flushes pending changes.
+    pub fn decay_confidences(&self, now: DateTime<Utc>) -> Result<DecayReport> {
+        let policy = &self.config.decay;
+        let mut latest: BTreeMap<DeletionTarget, Affirmation> = BTreeMap::new();
+        let mut exempt: BTreeSet<DeletionTarget> = BTreeSet::new();
+        for change in self.changelog.read().iter() {
+            if !matches!(change.status, ChangeStatus::Applied) {
+                continue;
+            }
+            let affirmation = |confidence: f32| Affirmation {
+                confidence,
+                at: change.applied_time(),
+                half_life_days: policy.half_life_for(&change.source),
+            };
+            for fact in &change.facts {
+                match fact {
+                    StorableFact::Relation {
+                        rel_type,
+                        source,
+                        target,
+                        confidence,
+                        ..
+                    } => {
+                        let key = DeletionTarget::Relation {
+                            rel_type: rel_type.clone(),
+                            source: source.clone(),
+                            target: target.clone(),
+                        };
+                        latest.insert(key, affirmation(*confidence));
+                    }
+                    StorableFact::TacitKnowledge {
+                        name, confidence, ..
+                    } => {
+                        let key = DeletionTarget::Entity { name: name.clone() };
+                        latest.insert(key, affirmation(*confidence));
+                    }
+                    StorableFact::Retraction {
+                        rel_type,
+                        source,
+                        target,
+                        confidence,
+                        ..
+                    } => {
+                        let key = DeletionTarget::Relation {
+                            rel_type: rel_type.clone(),
+                            source: source.clone(),
+                            target: target.clone(),
+                        };
+                        match confidence {
+                            // A downgrade is a reassessment.
+                            Some(c) => latest.insert(key, affirmation(*c)),
+                            None => latest.remove(&key),
+                        };
+                    }
+                    StorableFact::DecayExemption { target, .. } => {
+                        exempt.insert(target.clone());
+                    }
+                    // A human check is an affirmation that does not decay.
+                    StorableFact::Verification {
+                        target, confidence, ..
+                    } => {
+                        latest.insert(target.clone(), affirmation(*confidence));
+                        exempt.insert(target.clone());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut report = DecayReport::default();
+        {
+            let pathdb = self.pathdb.read();
+            for (target, affirmation) in latest {
+                let half_life = match affirmation.half_life_days {
+                    Some(days) if !exempt.contains(&target) => days,
+                    _ => {
+                        report.exempt += 1;
+                        continue;
+                    }
+                };
+                // Deleted or retracted since.
+                let Some(current) = current_confidence(&pathdb, &target) else {
+                    continue;
+                };
+                let age_days = (now - affirmation.at).num_seconds() as f64 / 86_400.0;
+                let Some(decayed) = policy.decay(affirmation.confidence, age_days, half_life)
+                else {
+                    continue;
+                };
+                if (current - decayed).abs() < policy.min_step {
+                    continue;
+                }
+                report.decayed.push(DecayedFact {
+                    target,
+                    from: current,
+                    to: decayed,
+                    half_life_days: half_life,
+                    affirmed_at: affirmation.at,
+                });
+            }
+        }
+        if report.decayed.is_empty() {
+            return Ok(report);
+        }
+
+        let facts = report
+            .decayed
+            .iter()
+            .map(|d| StorableFact::ConfidenceDecay {
+                target: d.target.clone(),
+                confidence: d.to,
+                half_life_days: d.half_life_days,
+            })
+            .collect();
+        let change_id = self.add_facts(
+            facts,
+            ChangeSource::System {
+                reason: "confidence decay".to_string(),
+            },
+        )?;
+        self.flush()?;
+        report.change_id = Some(change_id);
+        tracing::info!(facts = report.decayed.len(), "decayed confidences");
+        Ok(report)
+    }
+
+    /// Exempt `target` from confidence decay, e.g. after a human verified it.
+    pub fn exempt_from_decay(
+        &self,
+        target: DeletionTarget,
+        reason: &str,
+        user_id: Option<String>,
+    ) -> Result<ChangeId> {
+        let change_id = self.add_facts(
+            vec![StorableFact::decay_exemption(target, reason)],
+            ChangeSource::UserEdit { user_id },
+        )?;
+        self.flush()?;
+        Ok(change_id)
+    }
+}
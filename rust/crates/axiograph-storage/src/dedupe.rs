@@ -0,0 +1,171 @@
+//! Idempotent apply: recognizing facts PathDB already holds.
+//!
+//! Re-applying a changelog or re-ingesting a source must not duplicate the
+//! graph, so every entity and relation write first looks for an existing
+//! match:
+//!
+//! - an entity matches an entity of the same type with the same `external_id`
+//!   attribute or, if the fact has none, the same `name` attribute (entities
+//!   with neither are always inserted);
+//! - a relation matches an edge with the same type and the same endpoints,
+//!   with endpoints resolved by exact `name`; a relation naming an endpoint
+//!   no entity has is not written.
+//!
+//! What a match does is [`StorageConfig::on_duplicate`](crate::StorageConfig).
+//! Changelog replay applies the same policy, so a rebuilt PathDB matches the
+//! live one.
+//!
+//! Both identity attributes carry a PathDB value index (see
+//! [`index_identity_attrs`]), so a lookup costs one hash probe rather than a
+//! scan of the attribute column.
+
+use axiograph_pathdb::PathDB;
+use serde::{Deserialize, Serialize};
+
+/// Attribute that identifies an entity across sources, if present.
+pub const EXTERNAL_ID_ATTR: &str = "external_id";
+const NAME_ATTR: &str = "name";
+
+/// What to do when a write matches a fact PathDB already holds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnDuplicate {
+    /// Keep the existing fact unchanged.
+    #[default]
+    Skip,
+    /// Upsert the incoming attributes onto the existing entity. Relation
+    /// attributes are immutable in PathDB, so duplicate relations are skipped.
+    MergeAttrs,
+    /// Count the duplicate as corroborating evidence: the existing relation's
+    /// confidence becomes `1 - (1 - old) * (1 - new)`. Entities are skipped.
+    BumpConfidence,
+}
+
+/// Result of one deduplicated write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WriteOutcome {
+    Inserted(u32),
+    /// Matched an existing entity or relation (its id).
+    Duplicate(u32),
+    /// A relation endpoint named no entity; nothing was written.
+    Unresolved,
+}
+
+fn borrowed(attributes: &[(String, String)]) -> Vec<(&str, &str)> {
+    attributes
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect()
+}
+
+fn attr<'a>(attributes: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.as_str())
+}
+
+/// Declare the value indexes the lookups below rely on. They are not
+/// persisted, so this runs on every PathDB storage loads or replays into.
+pub(crate) fn index_identity_attrs(pathdb: &mut PathDB) {
+    pathdb.index_attr_values(EXTERNAL_ID_ATTR);
+    pathdb.index_attr_values(NAME_ATTR);
+}
+
+/// Lowest-id entity whose `key` attribute is exactly `value`.
+fn entity_with_attr(pathdb: &PathDB, key: &str, value: &str) -> Option<u32> {
+    let key_id = pathdb.interner.id_of(key)?;
+    let value_id = pathdb.interner.id_of(value)?;
+    pathdb
+        .entities
+        .entities_with_attr_value(key_id, value_id)
+        .min()
+}
+
+/// The entity a source-side id refers to, via the `external_id` index.
+pub(crate) fn entity_by_external_id(pathdb: &PathDB, external_id: &str) -> Option<u32> {
+    entity_with_attr(pathdb, EXTERNAL_ID_ATTR, external_id)
+}
+
+/// The entity a fact names, as relation endpoints are resolved.
+pub(crate) fn entity_by_name(pathdb: &PathDB, name: &str) -> Option<u32> {
+    entity_with_attr(pathdb, NAME_ATTR, name)
+}
+
+/// Existing entity of `entity_type` with the same identity attribute.
+fn existing_entity(
+    pathdb: &PathDB,
+    entity_type: &str,
+    attributes: &[(String, String)],
+) -> Option<u32> {
+    let (key, value) = match attr(attributes, EXTERNAL_ID_ATTR) {
+        Some(v) => (EXTERNAL_ID_ATTR, v),
+        None => (NAME_ATTR, attr(attributes, NAME_ATTR)?),
+    };
+    let type_id = pathdb.interner.id_of(entity_type)?;
+    let key_id = pathdb.interner.id_of(key)?;
+    let value_id = pathdb.interner.id_of(value)?;
+    pathdb
+        .entities
+        .entities_with_attr_value(key_id, value_id)
+        .iter()
+        .find(|&id| pathdb.entities.get_type(id) == Some(type_id))
+}
+
+/// Insert an entity unless an equivalent one exists.
+pub(crate) fn apply_entity(
+    pathdb: &mut PathDB,
+    entity_type: &str,
+    attributes: &[(String, String)],
+    policy: OnDuplicate,
+) -> WriteOutcome {
+    let Some(id) = existing_entity(pathdb, entity_type, attributes) else {
+        return WriteOutcome::Inserted(pathdb.add_entity(entity_type, borrowed(attributes)));
+    };
+    if policy == OnDuplicate::MergeAttrs {
+        for (key, value) in attributes {
+            // `id` came from the entity store, so it is always in range.
+            let _ = pathdb.upsert_entity_attr(id, key, value);
+        }
+    }
+    WriteOutcome::Duplicate(id)
+}
+
+/// Insert a `source -rel_type-> target` relation unless an identical edge
+/// exists.
+///
+/// Endpoints resolve to the lowest-id entity with that exact `name`. If
+/// either does not resolve, nothing is written and the outcome is
+/// [`WriteOutcome::Unresolved`].
+pub(crate) fn apply_relation(
+    pathdb: &mut PathDB,
+    rel_type: &str,
+    source: &str,
+    target: &str,
+    confidence: f32,
+    attributes: &[(String, String)],
+    policy: OnDuplicate,
+) -> WriteOutcome {
+    let (Some(s), Some(t)) = (
+        entity_with_attr(pathdb, NAME_ATTR, source),
+        entity_with_attr(pathdb, NAME_ATTR, target),
+    ) else {
+        return WriteOutcome::Unresolved;
+    };
+    let existing = pathdb
+        .interner
+        .id_of(rel_type)
+        .and_then(|rel| pathdb.relations.edge_relation_id(s, rel, t));
+    if let Some(id) = existing {
+        if policy == OnDuplicate::BumpConfidence {
+            let old = pathdb
+                .relations
+                .get_relation(id)
+                .map_or(0.0, |r| r.confidence);
+            let bumped = 1.0 - (1.0 - old) * (1.0 - confidence);
+            pathdb.set_relation_confidence(s, rel_type, t, bumped.clamp(0.0, 1.0));
+        }
+        return WriteOutcome::Duplicate(id);
+    }
+    WriteOutcome::Inserted(pathdb.add_relation(rel_type, s, t, confidence, borrowed(attributes)))
+}
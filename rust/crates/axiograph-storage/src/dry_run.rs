@@ -0,0 +1,367 @@
+//! Dry-run (simulate) mode for storage writes.
+//!
+//! [`UnifiedStorage::add_facts_dry_run`] runs the same planning step as
+//! `flush` — PathDB inserts, `.axi` lines, review warnings — and validates the
+//! facts against the loaded schema, but mutates nothing: no PathDB inserts, no
+//! `.axi` appends, no pending queue or changelog entries. CI jobs use it to
+//! check what a new source would contribute before it is allowed to write.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::axi_writer::{fact_fragment, AxiFragment};
+use crate::{ChangeSource, DeletionTarget, StorableFact, UnifiedStorage};
+
+/// One PathDB write that applying a change performs, in apply order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PlannedWrite {
+    Entity {
+        entity_type: String,
+        attributes: Vec<(String, String)>,
+    },
+    Relation {
+        rel_type: String,
+        source: String,
+        target: String,
+        confidence: f32,
+        attributes: Vec<(String, String)>,
+    },
+    /// Delete (`confidence: None`) or downgrade an existing relation.
+    Retraction {
+        rel_type: String,
+        source: String,
+        target: String,
+        confidence: Option<f32>,
+    },
+    /// Soft delete, applied by replaying the changelog.
+    Deletion { target: DeletionTarget },
+    /// Set the confidence of existing relations or tacit-knowledge entities.
+    Confidence {
+        target: DeletionTarget,
+        confidence: f32,
+    },
+}
+
+/// A problem with a fact that would not stop it from being stored, but that a
+/// CI check should fail on. `fact` is the index into the submitted facts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Violation {
+    /// Entity type not declared in any loaded `.axi` schema.
+    UnknownEntityType { fact: usize, entity_type: String },
+    /// Relation type not declared in any loaded `.axi` schema.
+    UnknownRelationType { fact: usize, rel_type: String },
+    /// Confidence is NaN or outside `[0, 1]`.
+    ConfidenceOutOfRange { fact: usize, confidence: f32 },
+    /// Confidence below `ReviewPolicy::low_confidence_threshold`.
+    LowConfidence {
+        fact: usize,
+        confidence: f32,
+        threshold: f32,
+    },
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Violation::UnknownEntityType { fact, entity_type } => {
+                write!(f, "fact {fact}: unknown entity type `{entity_type}`")
+            }
+            Violation::UnknownRelationType { fact, rel_type } => {
+                write!(f, "fact {fact}: unknown relation type `{rel_type}`")
+            }
+            Violation::ConfidenceOutOfRange { fact, confidence } => {
+                write!(f, "fact {fact}: confidence {confidence} outside [0, 1]")
+            }
+            Violation::LowConfidence {
+                fact,
+                confidence,
+                threshold,
+            } => write!(
+                f,
+                "fact {fact}: confidence {confidence} below review threshold {threshold}"
+            ),
+        }
+    }
+}
+
+/// Everything applying a change would do.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChangePlan {
+    pub writes: Vec<PlannedWrite>,
+    /// Canonical `.axi` text per fact, as appended to the file.
+    pub axi_lines: Vec<String>,
+    #[serde(skip)]
+    pub(crate) axi_fragments: Vec<AxiFragment>,
+    pub warnings: Vec<String>,
+    pub violations: Vec<Violation>,
+}
+
+/// Result of [`UnifiedStorage::add_facts_dry_run`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunReport {
+    /// `.axi` file the lines would be appended to (`None` for file imports,
+    /// which are already backed by a file).
+    pub axi_file: Option<PathBuf>,
+    #[serde(flatten)]
+    pub plan: ChangePlan,
+}
+
+impl DryRunReport {
+    pub fn entities(&self) -> impl Iterator<Item = &PlannedWrite> {
+        self.plan
+            .writes
+            .iter()
+            .filter(|w| matches!(w, PlannedWrite::Entity { .. }))
+    }
+
+    pub fn relations(&self) -> impl Iterator<Item = &PlannedWrite> {
+        self.plan
+            .writes
+            .iter()
+            .filter(|w| matches!(w, PlannedWrite::Relation { .. }))
+    }
+
+    /// True when no violations were found.
+    pub fn is_clean(&self) -> bool {
+        self.plan.violations.is_empty()
+    }
+}
+
+pub(crate) fn owned_attrs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+impl UnifiedStorage {
+    /// Simulate `add_facts` + `flush` for `facts` without mutating anything.
+    pub fn add_facts_dry_run(&self, facts: &[StorableFact], source: &ChangeSource) -> DryRunReport {
+        let _span = tracing::info_span!("storage.dry_run", facts = facts.len()).entered();
+        DryRunReport {
+            axi_file: Self::axi_filename(source).map(|f| self.config.axi_dir.join(f)),
+            plan: self.plan_facts(facts),
+        }
+    }
+
+    /// Plan a batch of facts. `apply_change` executes exactly this plan.
+    pub(crate) fn plan_facts(&self, facts: &[StorableFact]) -> ChangePlan {
+        let mut plan = ChangePlan::default();
+        for (i, fact) in facts.iter().enumerate() {
+            match fact {
+                StorableFact::Entity {
+                    name,
+                    entity_type,
+                    attributes,
+                } => {
+                    plan.writes.push(PlannedWrite::Entity {
+                        entity_type: entity_type.clone(),
+                        attributes: attributes.clone(),
+                    });
+                }
+
+                StorableFact::Relation {
+                    name,
+                    rel_type,
+                    source,
+                    target,
+                    confidence,
+                    attributes,
+                } => {
+                    plan.writes.push(PlannedWrite::Relation {
+                        rel_type: rel_type.clone(),
+                        source: source.clone(),
+                        target: target.clone(),
+                        confidence: *confidence,
+                        attributes: attributes.clone(),
+                    });
+                }
+
+                StorableFact::Constraint {
+                    name,
+                    condition,
+                    severity,
+                    message,
+                } => {
+                    // Constraints go to .axi only (interpreted at query time)
+                    if self.config.require_review.constraints {
+                        plan.warnings
+                            .push(format!("Constraint '{}' added - requires review", name));
+                    }
+                }
+
+                StorableFact::TacitKnowledge {
+                    name,
+                    rule,
+                    confidence,
+                    domain,
+                    source,
+                } => {
+                    plan.writes.push(PlannedWrite::Entity {
+                        entity_type: "TacitKnowledge".to_string(),
+                        attributes: owned_attrs(&[
+                            ("name", name),
+                            ("rule", rule),
+                            (crate::decay::CONFIDENCE_ATTR, &confidence.to_string()),
+                            ("domain", domain),
+                            ("source", source),
+                        ]),
+                    });
+                }
+
+                StorableFact::Concept {
+                    name,
+                    description,
+                    difficulty,
+                    prerequisites,
+                } => {
+                    plan.writes.push(PlannedWrite::Entity {
+                        entity_type: "Concept".to_string(),
+                        attributes: owned_attrs(&[
+                            ("name", name),
+                            ("description", description),
+                            ("difficulty", difficulty),
+                        ]),
+                    });
+                }
+
+                StorableFact::SafetyGuideline {
+                    name,
+                    title,
+                    severity,
+                    explanation,
+                } => {
+                    plan.writes.push(PlannedWrite::Entity {
+                        entity_type: "SafetyGuideline".to_string(),
+                        attributes: owned_attrs(&[
+                            ("name", name),
+                            ("title", title),
+                            ("severity", severity),
+                        ]),
+                    });
+                }
+
+                StorableFact::Retraction {
+                    rel_type,
+                    source,
+                    target,
+                    confidence,
+                    reason,
+                } => {
+                    plan.writes.push(PlannedWrite::Retraction {
+                        rel_type: rel_type.clone(),
+                        source: source.clone(),
+                        target: target.clone(),
+                        confidence: *confidence,
+                    });
+                }
+
+                StorableFact::Deletion { target, .. } => {
+                    plan.writes.push(PlannedWrite::Deletion {
+                        target: target.clone(),
+                    });
+                }
+
+                StorableFact::ConfidenceDecay {
+                    target, confidence, ..
+                }
+                | StorableFact::Verification {
+                    target, confidence, ..
+                } => {
+                    plan.writes.push(PlannedWrite::Confidence {
+                        target: target.clone(),
+                        confidence: *confidence,
+                    });
+                }
+
+                StorableFact::DecayExemption { .. } => {}
+            }
+            let fragment = fact_fragment(fact);
+            match fragment.render() {
+                Ok(text) => {
+                    plan.axi_lines.push(text);
+                    plan.axi_fragments.push(fragment);
+                }
+                Err(err) => plan
+                    .warnings
+                    .push(format!("fact {i} left out of the .axi file: {err}")),
+            }
+            self.check_fact(i, fact, &mut plan.violations);
+        }
+        plan
+    }
+
+    fn check_fact(&self, i: usize, fact: &StorableFact, out: &mut Vec<Violation>) {
+        let schema = self.schema.read();
+        let confidence = match fact {
+            StorableFact::Entity { entity_type, .. } => {
+                if !schema.entity_types.is_empty() && !schema.entity_types.contains(entity_type) {
+                    out.push(Violation::UnknownEntityType {
+                        fact: i,
+                        entity_type: entity_type.clone(),
+                    });
+                }
+                None
+            }
+            StorableFact::Relation {
+                rel_type,
+                confidence,
+                ..
+            } => {
+                if !schema.relation_types.is_empty() && !schema.relation_types.contains(rel_type) {
+                    out.push(Violation::UnknownRelationType {
+                        fact: i,
+                        rel_type: rel_type.clone(),
+                    });
+                }
+                Some(*confidence)
+            }
+            StorableFact::TacitKnowledge { confidence, .. } => Some(*confidence),
+            // A downgrade is meant to be low; only its range is checked.
+            StorableFact::Retraction { confidence, .. } => {
+                if let Some(confidence) = confidence.filter(|c| !(0.0..=1.0).contains(c)) {
+                    out.push(Violation::ConfidenceOutOfRange {
+                        fact: i,
+                        confidence,
+                    });
+                }
+                None
+            }
+            StorableFact::ConfidenceDecay { confidence, .. }
+            | StorableFact::Verification { confidence, .. } => {
+                if !(0.0..=1.0).contains(confidence) {
+                    out.push(Violation::ConfidenceOutOfRange {
+                        fact: i,
+                        confidence: *confidence,
+                    });
+                }
+                None
+            }
+            StorableFact::Constraint { .. }
+            | StorableFact::Concept { .. }
+            | StorableFact::SafetyGuideline { .. }
+            | StorableFact::Deletion { .. }
+            | StorableFact::DecayExemption { .. } => None,
+        };
+
+        let Some(confidence) = confidence else {
+            return;
+        };
+        if !(0.0..=1.0).contains(&confidence) {
+            out.push(Violation::ConfidenceOutOfRange {
+                fact: i,
+                confidence,
+            });
+        } else if let Some(threshold) = self.config.require_review.low_confidence_threshold {
+            if confidence < threshold {
+                out.push(Violation::LowConfidence {
+                    fact: i,
+                    confidence,
+                    threshold,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Structured errors for the storage layer.
+
+use axiograph_pathdb::PathDbError;
+use thiserror::Error;
+
+use crate::ChangeId;
+
+/// Errors returned by `UnifiedStorage` and its companions (audit, access, export).
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("Change not found: {0}")]
+    ChangeNotFound(ChangeId),
+
+    /// Nothing in the graph matches a fact reference (see `DeletionTarget`).
+    #[error("no fact matches {0}")]
+    FactNotFound(String),
+
+    #[error("unknown access role `{0}`")]
+    UnknownRole(String),
+
+    #[error("invalid storage configuration: {0}")]
+    InvalidConfig(String),
+
+    /// A backup failed verification (missing manifest, bad digest, ...).
+    #[error("backup integrity check failed for `{path}`: {reason}")]
+    BackupIntegrity { path: String, reason: String },
+
+    /// A trash entry is missing, already restored/purged, or past retention.
+    #[error("cannot restore {change_id}#{fact} from the trash: {reason}")]
+    NotRestorable {
+        change_id: ChangeId,
+        fact: usize,
+        reason: String,
+    },
+
+    /// A `ProposalV1` failed validation or names an endpoint that does not
+    /// resolve (see `ProposalConverter`).
+    #[error("invalid proposal `{proposal_id}`: {reason}")]
+    InvalidProposal { proposal_id: String, reason: String },
+
+    /// An `IngestBridge` writer has stopped (after a failed batch); the
+    /// proposal was not queued.
+    #[error("ingest bridge is closed")]
+    BridgeClosed,
+
+    /// A background task (ingest bridge writer or batch) panicked or was
+    /// cancelled.
+    #[error("{task} failed: {reason}")]
+    TaskFailed { task: &'static str, reason: String },
+
+    /// A change subscriber could not take an event; it is retried on the
+    /// next delivery round.
+    #[error("delivery to {target} failed: {reason}")]
+    Delivery { target: String, reason: String },
+
+    /// The PathDB snapshot could not be loaded or saved (see the inner error
+    /// for corrupt input vs. bad request).
+    #[error(transparent)]
+    PathDb(#[from] PathDbError),
+
+    /// Changelog or audit log JSON is malformed.
+    #[error("malformed storage JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// Generated `.axi` text could not be printed canonically.
+    #[error(transparent)]
+    AxiPrint(#[from] axiograph_dsl::printer::AxiPrintError),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+pub type Result<T, E = StorageError> = std::result::Result<T, E>;
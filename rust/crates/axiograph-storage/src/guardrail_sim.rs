@@ -0,0 +1,174 @@
+//! Guardrail simulation: what a candidate rule set would have flagged.
+//!
+//! Before tightening a guardrail, [`UnifiedStorage::simulate_guardrails`]
+//! replays the applied changelog into a scratch PathDB and, after each
+//! change, checks the candidate rules against the entities that change
+//! touched (entities it wrote and the endpoints of relations it wrote). The
+//! live graph is not modified.
+//!
+//! Each check sees the graph as it was right after the change, so a rule that
+//! requires a relation flags an entity whose relation arrived in a later
+//! change, just as an enforced guardrail would have. A change counts as
+//! blocked when one of its violations is at least
+//! [`GuardrailSimulationConfig::block_at`].
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use axiograph_pathdb::guardrails::CheckContext;
+use axiograph_pathdb::{GuardrailEngine, GuardrailRule, GuardrailViolation, PathDB, Severity};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::subscriptions::source_key;
+use crate::{dedupe, ChangeId, ChangeStatus, StorableFact, UnifiedStorage};
+
+/// How a simulation checks and counts.
+#[derive(Debug, Clone)]
+pub struct GuardrailSimulationConfig {
+    /// Context the rules are checked in (its `domain` selects domain rules).
+    pub context: CheckContext,
+    /// Lowest severity that would have blocked a change.
+    pub block_at: Severity,
+}
+
+impl Default for GuardrailSimulationConfig {
+    fn default() -> Self {
+        Self {
+            context: CheckContext::default(),
+            block_at: Severity::Blocking,
+        }
+    }
+}
+
+/// A past change the candidate rules flag.
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulatedChange {
+    /// Position in the changelog.
+    pub seq: usize,
+    pub change_id: ChangeId,
+    pub applied_at: DateTime<Utc>,
+    pub source_key: String,
+    /// Most severe first.
+    pub violations: Vec<GuardrailViolation>,
+    pub blocked: bool,
+}
+
+/// Hypothetical violations of a candidate rule set over the changelog.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GuardrailSimulation {
+    /// Applied changes replayed.
+    pub changes_replayed: usize,
+    /// Changes with at least one violation, in changelog order.
+    pub flagged: Vec<SimulatedChange>,
+    /// Violation counts by severity.
+    pub by_severity: BTreeMap<Severity, usize>,
+    /// Violation counts by change source key (`llm:<model>`, `user`, ...)
+    /// and severity.
+    pub by_source: BTreeMap<String, BTreeMap<Severity, usize>>,
+}
+
+impl GuardrailSimulation {
+    /// Number of changes the rules would have blocked.
+    pub fn blocked(&self) -> usize {
+        self.flagged.iter().filter(|c| c.blocked).count()
+    }
+
+    pub fn violations(&self) -> usize {
+        self.by_severity.values().sum()
+    }
+}
+
+/// Names of the entities a fact writes or connects.
+fn touched_names(fact: &StorableFact) -> Vec<&str> {
+    match fact {
+        StorableFact::Entity { name, .. }
+        | StorableFact::TacitKnowledge { name, .. }
+        | StorableFact::Concept { name, .. }
+        | StorableFact::SafetyGuideline { name, .. } => vec![name],
+        StorableFact::Relation { source, target, .. } => vec![source, target],
+        _ => Vec::new(),
+    }
+}
+
+impl UnifiedStorage {
+    /// Replay applied changes against `rules` and report what they would
+    /// have flagged (see the [module docs](self)).
+    pub fn simulate_guardrails(
+        &self,
+        rules: Vec<GuardrailRule>,
+        config: &GuardrailSimulationConfig,
+    ) -> GuardrailSimulation {
+        let engine = GuardrailEngine::new(rules);
+        let applied = |c: &crate::Change| matches!(c.status, ChangeStatus::Applied);
+        let policy = self.config.on_duplicate;
+        let changelog = self.changelog.read();
+
+        let mut report = GuardrailSimulation::default();
+        let mut scratch = PathDB::new();
+        for (seq, change) in changelog.iter().enumerate() {
+            if !applied(change) {
+                continue;
+            }
+            report.changes_replayed += 1;
+            let deletes = change
+                .facts
+                .iter()
+                .any(|f| matches!(f, StorableFact::Deletion { .. }));
+            if deletes {
+                // Deletions act on earlier facts: rebuild the state as of now.
+                scratch = PathDB::new();
+                crate::replay_changes(
+                    &mut scratch,
+                    &changelog[..=seq],
+                    0,
+                    applied,
+                    Some(change.applied_time()),
+                    policy,
+                );
+            } else {
+                for fact in &change.facts {
+                    crate::replay_fact(&mut scratch, fact, policy);
+                }
+            }
+
+            let entities: BTreeSet<u32> = change
+                .facts
+                .iter()
+                .flat_map(touched_names)
+                .filter_map(|name| dedupe::entity_by_name(&scratch, name))
+                .collect();
+            let mut violations = Vec::new();
+            for id in entities {
+                let Some(view) = scratch.get_entity(id) else {
+                    continue;
+                };
+                violations.extend(engine.check_entity(
+                    &scratch,
+                    id,
+                    &view.entity_type,
+                    &config.context,
+                ));
+            }
+            if violations.is_empty() {
+                continue;
+            }
+
+            violations.sort_by_key(|v| std::cmp::Reverse(v.severity));
+            let source_key = source_key(&change.source);
+            let by_source = report.by_source.entry(source_key.clone()).or_default();
+            for v in &violations {
+                *report.by_severity.entry(v.severity).or_default() += 1;
+                *by_source.entry(v.severity).or_default() += 1;
+            }
+            report.flagged.push(SimulatedChange {
+                seq,
+                change_id: change.id,
+                applied_at: change.applied_time(),
+                source_key,
+                blocked: violations[0].severity >= config.block_at,
+                violations,
+            });
+        }
+        report
+    }
+}
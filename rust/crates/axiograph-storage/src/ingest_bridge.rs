@@ -0,0 +1,224 @@
+//! Backpressure-aware async bridge from ingestion to storage.
+//!
+//! Ingestion can produce proposals faster than [`UnifiedStorage`] applies and
+//! persists them. An [`IngestBridge`] puts a bounded channel between the two:
+//!
+//! - [`IngestBridgeSender::send`] waits while [`IngestBridgeConfig::capacity`]
+//!   proposals are queued, so producers slow down to the storage's pace
+//!   instead of buffering without bound;
+//! - a single writer task converts proposals (like
+//!   [`UnifiedStorage::ingest_proposals_stream`]) and applies them in batches
+//!   of [`IngestBridgeConfig::batch_size`], one [`Change`](crate::Change) per
+//!   batch, persisted as it is applied. A partial batch is applied once its
+//!   first proposal has waited [`IngestBridgeConfig::max_batch_delay_ms`];
+//! - [`IngestBridge::shutdown`] closes the bridge, waits until every queued
+//!   proposal has been applied and persisted, and returns the report.
+//!
+//! A proposal is *accepted* once `send` returns `Ok`. While storage keeps
+//! working, accepted proposals are never dropped: each is applied (or
+//! rejected and reported, like an invalid stream line) before `shutdown`
+//! returns. If applying a batch fails, the writer stops: the proposals still
+//! queued are not applied, later sends fail with
+//! [`StorageError::BridgeClosed`], and `shutdown` returns the error.
+//!
+//! In the report, `line` numbers are 1-based positions in the order the
+//! writer received the proposals.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axiograph_ingest_docs::ProposalV1;
+use axiograph_pathdb::metrics;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::proposal_stream::Batch;
+use crate::proposals::ProposalConverter;
+use crate::{ChangeSource, ProposalStreamReport, Result, StorageError, UnifiedStorage};
+
+/// Options for [`IngestBridge::spawn`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestBridgeConfig {
+    /// Proposals queued before `send` waits.
+    pub capacity: usize,
+    /// Accepted proposals per change.
+    pub batch_size: usize,
+    /// Longest a partial batch waits for more proposals, in milliseconds.
+    pub max_batch_delay_ms: u64,
+    /// Source recorded on every change.
+    pub source: ChangeSource,
+    /// Rejected proposals kept in the report (all are counted).
+    pub max_errors: usize,
+}
+
+impl Default for IngestBridgeConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 4096,
+            batch_size: 1000,
+            max_batch_delay_ms: 250,
+            source: ChangeSource::System {
+                reason: "ingest bridge".to_string(),
+            },
+            max_errors: 100,
+        }
+    }
+}
+
+/// A cloneable handle for producers (see [`IngestBridge::sender`]).
+#[derive(Clone)]
+pub struct IngestBridgeSender {
+    tx: mpsc::Sender<ProposalV1>,
+}
+
+impl IngestBridgeSender {
+    /// Queue `proposal`, waiting while the bridge is full.
+    pub async fn send(&self, proposal: ProposalV1) -> Result<()> {
+        self.tx
+            .send(proposal)
+            .await
+            .map_err(|_| StorageError::BridgeClosed)?;
+        queued_proposals_gauge().add(1);
+        Ok(())
+    }
+
+    /// Queue `proposal` without waiting; gives it back if the bridge is full
+    /// or closed.
+    pub fn try_send(&self, proposal: ProposalV1) -> std::result::Result<(), Box<ProposalV1>> {
+        self.tx.try_send(proposal).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(p) | mpsc::error::TrySendError::Closed(p) => {
+                Box::new(p)
+            }
+        })?;
+        queued_proposals_gauge().add(1);
+        Ok(())
+    }
+
+    /// Proposals queued and not yet taken by the writer.
+    pub fn queued(&self) -> usize {
+        self.tx.max_capacity() - self.tx.capacity()
+    }
+}
+
+/// Bounded, batching writer from ingestion into [`UnifiedStorage`] (see the
+/// module docs).
+pub struct IngestBridge {
+    sender: IngestBridgeSender,
+    writer: JoinHandle<Result<ProposalStreamReport>>,
+}
+
+impl IngestBridge {
+    /// Start the writer task. Must be called from within a tokio runtime.
+    pub fn spawn(storage: Arc<UnifiedStorage>, config: IngestBridgeConfig) -> Self {
+        let (tx, rx) = mpsc::channel(config.capacity.max(1));
+        Self {
+            sender: IngestBridgeSender { tx },
+            writer: tokio::spawn(run_writer(storage, rx, config)),
+        }
+    }
+
+    /// A handle for another producer. The bridge drains once the bridge
+    /// itself and every handle are gone.
+    pub fn sender(&self) -> IngestBridgeSender {
+        self.sender.clone()
+    }
+
+    /// Queue `proposal`, waiting while the bridge is full.
+    pub async fn send(&self, proposal: ProposalV1) -> Result<()> {
+        self.sender.send(proposal).await
+    }
+
+    /// Close the bridge and wait until every accepted proposal is applied and
+    /// persisted. Waits for outstanding [`IngestBridgeSender`]s to be dropped.
+    pub async fn shutdown(self) -> Result<ProposalStreamReport> {
+        drop(self.sender);
+        self.writer.await.map_err(|e| StorageError::TaskFailed {
+            task: "ingest bridge writer",
+            reason: e.to_string(),
+        })?
+    }
+}
+
+async fn run_writer(
+    storage: Arc<UnifiedStorage>,
+    mut rx: mpsc::Receiver<ProposalV1>,
+    config: IngestBridgeConfig,
+) -> Result<ProposalStreamReport> {
+    let batch_size = config.batch_size.max(1);
+    let max_delay = Duration::from_millis(config.max_batch_delay_ms);
+    let mut converter = ProposalConverter::new();
+    let mut report = ProposalStreamReport::default();
+    let mut batch = Batch::default();
+    let mut deadline = None;
+    loop {
+        let received = match deadline {
+            None => rx.recv().await,
+            Some(at) => match tokio::time::timeout_at(at, rx.recv()).await {
+                Ok(received) => received,
+                Err(_) => {
+                    apply(&storage, std::mem::take(&mut batch), &config, &mut report).await?;
+                    deadline = None;
+                    continue;
+                }
+            },
+        };
+        let Some(proposal) = received else {
+            break;
+        };
+        queued_proposals_gauge().add(-1);
+        report.lines += 1;
+        let converted = storage.convert_proposal(&mut converter, &proposal);
+        batch.accept(report.lines, converted, &mut report, config.max_errors);
+        if batch.facts.len() >= batch_size {
+            apply(&storage, std::mem::take(&mut batch), &config, &mut report).await?;
+            deadline = None;
+        } else if deadline.is_none() && !batch.facts.is_empty() {
+            deadline = Some(Instant::now() + max_delay);
+        }
+    }
+    if !batch.facts.is_empty() {
+        apply(&storage, batch, &config, &mut report).await?;
+    }
+    Ok(report)
+}
+
+/// Apply and persist `batch` off the async workers; producers wait on the
+/// full channel meanwhile.
+async fn apply(
+    storage: &Arc<UnifiedStorage>,
+    batch: Batch,
+    config: &IngestBridgeConfig,
+    report: &mut ProposalStreamReport,
+) -> Result<()> {
+    if batch.facts.is_empty() {
+        return Ok(());
+    }
+    let storage = storage.clone();
+    let source = config.source.clone();
+    let stats = tokio::task::spawn_blocking(move || {
+        let applied_before = storage.changelog.read().len();
+        let stats = storage.apply_proposal_batch(batch, &source)?;
+        storage.persist_applied(applied_before)?;
+        Ok::<_, StorageError>(stats)
+    })
+    .await
+    .map_err(|e| StorageError::TaskFailed {
+        task: "ingest bridge batch",
+        reason: e.to_string(),
+    })??;
+    report.batches.push(stats);
+    Ok(())
+}
+
+fn queued_proposals_gauge() -> &'static metrics::Gauge {
+    static GAUGE: std::sync::OnceLock<Arc<metrics::Gauge>> = std::sync::OnceLock::new();
+    GAUGE.get_or_init(|| {
+        metrics::global().gauge(
+            "axiograph_storage_bridge_queued_proposals",
+            "Proposals queued in ingest bridges and not yet taken by the writer.",
+            &[],
+        )
+    })
+}
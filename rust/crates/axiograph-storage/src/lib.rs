@@ -34,21 +34,72 @@
 //! - **Synced**: Hot reload when files change externally
 #![allow(unused_variables)]
 
+pub mod access;
+pub mod audit;
+mod axi_writer;
+pub mod backup;
+pub mod calibration;
+pub mod decay;
+pub mod dedupe;
+pub mod dry_run;
+pub mod error;
+pub mod guardrail_sim;
+pub mod ingest_bridge;
 pub mod persistence;
+pub mod proof_store;
+pub mod proposal_stream;
+pub mod proposals;
+pub mod quality;
+pub mod redaction;
+pub mod sandbox;
+pub mod subscriptions;
+pub mod temporal;
+pub mod trash;
+pub mod verification;
 
 #[cfg(test)]
 mod tests;
 
 use axiograph_dsl as dsl;
-use axiograph_pathdb::PathDB;
+use axiograph_pathdb::axi_module_graph::ModuleGraph;
+use axiograph_pathdb::{metrics, PathDB};
+use dedupe::WriteOutcome;
 use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use uuid::Uuid;
 
+pub use access::{AccessPolicy, AccessView, RolePolicy};
+pub use error::{Result, StorageError};
+pub use audit::{QueryAuditConfig, QueryAuditLog, QueryAuditRecord};
+pub use backup::{verify_backup, BackupFile, BackupFileRole, BackupManifest};
+pub use calibration::{CalibrationModel, ReviewOutcome};
+pub use decay::{DecayPolicy, DecayReport, DecayedFact};
+pub use dedupe::OnDuplicate;
+pub use axi_writer::STORAGE_AXI_SCHEMA;
+pub use dry_run::{ChangePlan, DryRunReport, PlannedWrite, Violation};
+pub use guardrail_sim::{GuardrailSimulation, GuardrailSimulationConfig, SimulatedChange};
+pub use ingest_bridge::{IngestBridge, IngestBridgeConfig, IngestBridgeSender};
+pub use proof_store::{ProofRetention, ProofStore, ProofStoreConfig, StoredProof};
+pub use proposal_stream::{
+    ProposalBatchStats, ProposalLineError, ProposalStreamConfig, ProposalStreamReport,
+};
+pub use proposals::{ConvertedProposal, ProposalConverter, ResolvedEndpoint};
+pub use quality::{QualityHistory, QualityReport, QualityRule, TypeQuality};
+pub use redaction::RedactionPolicy;
+pub use sandbox::{SandboxConfig, SandboxReport};
+#[cfg(feature = "webhooks")]
+pub use subscriptions::Webhook;
+pub use subscriptions::{
+    ChangeEvent, ChangeFilter, ChangeSubscriber, DeliveryReport, SubscriptionId,
+};
+pub use temporal::{AsOf, CHANGELOG_SNAPSHOT_INTERVAL};
+pub use trash::{DeletionTarget, TrashEntry, TrashPolicy};
+pub use verification::{VerificationRecord, VerificationRule};
+
 // ============================================================================
 // Core Types
 // ============================================================================
@@ -103,6 +154,46 @@ pub enum StorableFact {
         severity: String,
         explanation: String,
     },
+    /// Retraction of an existing relation (endpoints matched by name)
+    Retraction {
+        rel_type: String,
+        source: String,
+        target: String,
+        /// Downgraded confidence; `None` deletes the relation
+        confidence: Option<f32>,
+        reason: String,
+    },
+    /// Soft delete of an entity or relation, recoverable from the trash
+    /// until purged (see `trash`)
+    Deletion {
+        target: DeletionTarget,
+        reason: String,
+        /// Set when restored from the trash; the deletion no longer applies
+        #[serde(default)]
+        restored_at: Option<DateTime<Utc>>,
+        /// Set when purged; the facts it covered are gone from the changelog
+        #[serde(default)]
+        purged_at: Option<DateTime<Utc>>,
+    },
+    /// Confidence of a relation or tacit-knowledge fact after decay (see `decay`)
+    ConfidenceDecay {
+        target: DeletionTarget,
+        confidence: f32,
+        half_life_days: f64,
+    },
+    /// Keep a fact's confidence from decaying
+    DecayExemption {
+        target: DeletionTarget,
+        reason: String,
+    },
+    /// A reviewer checked a fact; its confidence after the check (see `verification`)
+    Verification {
+        target: DeletionTarget,
+        verifier: String,
+        #[serde(default)]
+        note: Option<String>,
+        confidence: f32,
+    },
 }
 
 /// Source of a change
@@ -132,6 +223,12 @@ pub struct Change {
     pub source: ChangeSource,
     pub facts: Vec<StorableFact>,
     pub status: ChangeStatus,
+    /// When the change was applied to PathDB (transaction time).
+    #[serde(default)]
+    pub applied_at: Option<DateTime<Utc>>,
+    /// When an applied change stopped being believed (rollback).
+    #[serde(default)]
+    pub retracted_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -150,6 +247,8 @@ pub struct ApplyResult {
     pub pathdb_ids: Vec<u32>,
     /// Lines added to .axi file
     pub axi_lines: Vec<String>,
+    /// Existing PathDB IDs matched instead of inserting (see [`OnDuplicate`])
+    pub duplicates: Vec<u32>,
     /// Any warnings
     pub warnings: Vec<String>,
 }
@@ -163,6 +262,16 @@ pub struct AxiSchemaIndex {
     pub entity_types: Vec<String>,
     pub relation_types: Vec<String>,
     pub constraints: Vec<String>,
+    /// Rules from `quality_*` constraint blocks (see [`quality`]).
+    #[serde(default)]
+    pub quality_rules: Vec<quality::QualityRule>,
+}
+
+impl AxiSchemaIndex {
+    /// Index the `.axi` files in `dir`, as `UnifiedStorage` does on startup.
+    pub fn load_dir(dir: &Path) -> Result<Self> {
+        UnifiedStorage::load_axi_files(&dir.to_path_buf())
+    }
 }
 
 // ============================================================================
@@ -184,6 +293,18 @@ pub struct StorageConfig {
     pub require_review: ReviewPolicy,
     /// Maximum pending changes before force-sync
     pub max_pending: usize,
+    /// What applying a fact PathDB already holds does
+    #[serde(default)]
+    pub on_duplicate: OnDuplicate,
+    /// Soft delete and trash retention
+    #[serde(default)]
+    pub trash: TrashPolicy,
+    /// Confidence half-lives per source
+    #[serde(default)]
+    pub decay: DecayPolicy,
+    /// How a human verification raises confidence
+    #[serde(default)]
+    pub verification: VerificationRule,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -209,6 +330,10 @@ impl Default for StorageConfig {
                 schema_changes: true,
             },
             max_pending: 100,
+            on_duplicate: OnDuplicate::default(),
+            trash: TrashPolicy::default(),
+            decay: DecayPolicy::default(),
+            verification: VerificationRule::default(),
         }
     }
 }
@@ -229,18 +354,27 @@ pub struct UnifiedStorage {
     changelog: Arc<RwLock<Vec<Change>>>,
     /// Current schema index (loaded from `.axi` files)
     schema: Arc<RwLock<AxiSchemaIndex>>,
+    /// Optional read-query audit log
+    query_audit: RwLock<Option<Arc<audit::QueryAuditLog>>>,
+    /// Optional persisted query proofs
+    proof_store: RwLock<Option<Arc<proof_store::ProofStore>>>,
+    /// Change subscribers and their cursors
+    subscriptions: RwLock<Vec<subscriptions::Subscription>>,
+    /// Serializes delivery rounds
+    delivery: parking_lot::Mutex<()>,
 }
 
 impl UnifiedStorage {
     /// Create new storage manager
-    pub fn new(config: StorageConfig) -> anyhow::Result<Self> {
+    pub fn new(config: StorageConfig) -> Result<Self> {
         // Load or create PathDB
-        let pathdb = if config.pathdb_path.exists() {
+        let mut pathdb = if config.pathdb_path.exists() {
             let bytes = std::fs::read(&config.pathdb_path)?;
             PathDB::from_bytes(&bytes)?
         } else {
             PathDB::new()
         };
+        dedupe::index_identity_attrs(&mut pathdb);
 
         // Load changelog if exists
         let changelog = if config.changelog_path.exists() {
@@ -259,6 +393,10 @@ impl UnifiedStorage {
             pending: Arc::new(RwLock::new(Vec::new())),
             changelog: Arc::new(RwLock::new(changelog)),
             schema: Arc::new(RwLock::new(schema)),
+            query_audit: RwLock::new(None),
+            proof_store: RwLock::new(None),
+            subscriptions: RwLock::new(Vec::new()),
+            delivery: parking_lot::Mutex::new(()),
         })
     }
 
@@ -356,11 +494,66 @@ impl UnifiedStorage {
     }
 
     /// Load all .axi files from directory
-    fn load_axi_files(dir: &PathBuf) -> anyhow::Result<AxiSchemaIndex> {
+    ///
+    /// The directory is resolved as one module graph (imports, cycles, schema
+    /// visibility); if that fails, each file is indexed on its own.
+    fn load_axi_files(dir: &PathBuf) -> Result<AxiSchemaIndex> {
         let mut entity_types: BTreeSet<String> = BTreeSet::new();
         let mut relation_types: BTreeSet<String> = BTreeSet::new();
         let mut constraints: BTreeSet<String> = BTreeSet::new();
+        let mut quality_rules: Vec<quality::QualityRule> = Vec::new();
+
+        let modules = match ModuleGraph::load_dir(dir) {
+            Ok(graph) => graph.into_modules(),
+            Err(err) => {
+                tracing::warn!(
+                    dir = %dir.display(),
+                    error = %err,
+                    "failed to resolve .axi module graph; indexing files individually"
+                );
+                Self::parse_axi_dir(dir)?
+            }
+        };
+
+        for module in &modules {
+            for schema in &module.schemas {
+                for obj in &schema.objects {
+                    entity_types.insert(obj.clone());
+                }
+                for rel in &schema.relations {
+                    relation_types.insert(rel.name.clone());
+                }
+                for subtype in &schema.subtypes {
+                    constraints.insert(format!("subtype {} <: {}", subtype.sub, subtype.sup));
+                }
+            }
+
+            for theory in &module.theories {
+                for constraint in &theory.constraints {
+                    if let dsl::schema_v1::ConstraintV1::NamedBlock { name, body } = constraint {
+                        if name.starts_with(quality::QUALITY_BLOCK_PREFIX) {
+                            quality_rules.extend(quality::parse_quality_block(name, body));
+                        }
+                    }
+                    constraints.insert(Self::schema_constraint_display(constraint));
+                }
+                for eq in &theory.equations {
+                    constraints.insert(format!("equation {}", eq.name));
+                }
+            }
+        }
 
+        Ok(AxiSchemaIndex {
+            entity_types: entity_types.into_iter().collect(),
+            relation_types: relation_types.into_iter().collect(),
+            constraints: constraints.into_iter().collect(),
+            quality_rules,
+        })
+    }
+
+    /// Parse each .axi file in `dir` independently, skipping unparseable ones.
+    fn parse_axi_dir(dir: &PathBuf) -> Result<Vec<dsl::axi_v1::AxiV1Module>> {
+        let mut modules = Vec::new();
         if dir.exists() {
             for entry in std::fs::read_dir(dir)? {
                 let entry = entry?;
@@ -371,29 +564,7 @@ impl UnifiedStorage {
 
                 let contents = std::fs::read_to_string(&path)?;
                 match dsl::axi_v1::parse_axi_v1(&contents) {
-                    Ok(module) => {
-                        for schema in &module.schemas {
-                            for obj in &schema.objects {
-                                entity_types.insert(obj.clone());
-                            }
-                            for rel in &schema.relations {
-                                relation_types.insert(rel.name.clone());
-                            }
-                            for subtype in &schema.subtypes {
-                                constraints
-                                    .insert(format!("subtype {} <: {}", subtype.sub, subtype.sup));
-                            }
-                        }
-
-                        for theory in &module.theories {
-                            for constraint in &theory.constraints {
-                                constraints.insert(Self::schema_constraint_display(constraint));
-                            }
-                            for eq in &theory.equations {
-                                constraints.insert(format!("equation {}", eq.name));
-                            }
-                        }
-                    }
+                    Ok(module) => modules.push(module),
                     Err(err) => {
                         tracing::warn!(
                             path = %path.display(),
@@ -404,12 +575,7 @@ impl UnifiedStorage {
                 }
             }
         }
-
-        Ok(AxiSchemaIndex {
-            entity_types: entity_types.into_iter().collect(),
-            relation_types: relation_types.into_iter().collect(),
-            constraints: constraints.into_iter().collect(),
-        })
+        Ok(modules)
     }
 
     // ========================================================================
@@ -421,17 +587,20 @@ impl UnifiedStorage {
         &self,
         facts: Vec<StorableFact>,
         source: ChangeSource,
-    ) -> anyhow::Result<ChangeId> {
+    ) -> Result<ChangeId> {
         let change = Change {
             id: Uuid::new_v4(),
             timestamp: Utc::now(),
             source,
             facts,
             status: ChangeStatus::Pending,
+            applied_at: None,
+            retracted_at: None,
         };
 
         let change_id = change.id;
         self.pending.write().push(change);
+        pending_changes_gauge().add(1);
 
         // Auto-apply if below threshold
         if self.pending.read().len() >= self.config.max_pending {
@@ -442,8 +611,17 @@ impl UnifiedStorage {
     }
 
     /// Apply all pending changes
-    pub fn flush(&self) -> anyhow::Result<Vec<ApplyResult>> {
+    pub fn flush(&self) -> Result<Vec<ApplyResult>> {
         let pending: Vec<Change> = self.pending.write().drain(..).collect();
+        pending_changes_gauge().add(-(pending.len() as i64));
+        let _span = tracing::info_span!("storage.flush", changes = pending.len()).entered();
+        let flush_seconds = metrics::global().histogram(
+            "axiograph_storage_flush_seconds",
+            "Time to apply and persist pending changes, in seconds.",
+            &[],
+        );
+        let _timer = flush_seconds.start_timer();
+        let applied_before = self.changelog.read().len();
         let mut results = Vec::new();
 
         for change in pending {
@@ -451,337 +629,197 @@ impl UnifiedStorage {
             results.push(result);
         }
 
+        self.persist_applied(applied_before)?;
+        Ok(results)
+    }
+
+    /// Persist changes applied since the changelog had `applied_before`
+    /// entries and notify subscribers.
+    fn persist_applied(&self, applied_before: usize) -> Result<()> {
+        // Without soft delete, deletions skip the trash
+        if !self.config.trash.soft_delete {
+            self.purge_where(|_| true)?;
+        }
+
         // Save changelog
         self.save_changelog()?;
 
         // Save PathDB
         self.save_pathdb()?;
 
-        Ok(results)
+        // Periodic snapshot for `pathdb_as_of`
+        self.maybe_snapshot(applied_before)?;
+
+        // Notify subscribers (failures are retried on the next round)
+        self.deliver_events();
+
+        Ok(())
     }
 
     /// Apply a single change
-    fn apply_change(&self, change: &Change) -> anyhow::Result<ApplyResult> {
+    fn apply_change(&self, change: &Change) -> Result<ApplyResult> {
+        let ChangePlan {
+            writes,
+            axi_lines,
+            axi_fragments,
+            mut warnings,
+            ..
+        } = self.plan_facts(&change.facts);
+
+        let policy = self.config.on_duplicate;
         let mut pathdb = self.pathdb.write();
-        let mut pathdb_ids = Vec::new();
-        let mut axi_lines = Vec::new();
-        let mut warnings = Vec::new();
-
-        for fact in &change.facts {
-            match fact {
-                StorableFact::Entity {
-                    name,
+        let mut pathdb_ids = Vec::with_capacity(writes.len());
+        let mut duplicates = Vec::new();
+        let mut rebuild = false;
+        for write in &writes {
+            let outcome = match write {
+                PlannedWrite::Entity {
                     entity_type,
                     attributes,
-                } => {
-                    // Add to PathDB
-                    let attrs: Vec<(&str, &str)> = attributes
-                        .iter()
-                        .map(|(k, v)| (k.as_str(), v.as_str()))
-                        .collect();
-                    let id = pathdb.add_entity(entity_type, attrs);
-                    pathdb_ids.push(id);
-
-                    // Generate .axi line
-                    let axi = self.entity_to_axi(name, entity_type, attributes);
-                    axi_lines.push(axi);
-                }
-
-                StorableFact::Relation {
-                    name,
+                } => dedupe::apply_entity(&mut pathdb, entity_type, attributes, policy),
+                PlannedWrite::Relation {
                     rel_type,
                     source,
                     target,
                     confidence,
                     attributes,
+                } => dedupe::apply_relation(
+                    &mut pathdb,
+                    rel_type,
+                    source,
+                    target,
+                    *confidence,
+                    attributes,
+                    policy,
+                ),
+                PlannedWrite::Retraction {
+                    rel_type,
+                    source,
+                    target,
+                    confidence,
                 } => {
-                    // Resolve source/target to IDs (simplified)
-                    // In production, would look up by name
-                    let source_id = 0; // placeholder
-                    let target_id = 1; // placeholder
-
-                    let attrs: Vec<(&str, &str)> = attributes
-                        .iter()
-                        .map(|(k, v)| (k.as_str(), v.as_str()))
-                        .collect();
-                    let id =
-                        pathdb.add_relation(rel_type, source_id, target_id, *confidence, attrs);
-                    pathdb_ids.push(id);
-
-                    // Generate .axi line
-                    let axi = self.relation_to_axi(
-                        name.as_deref(),
-                        rel_type,
-                        source,
-                        target,
-                        *confidence,
-                    );
-                    axi_lines.push(axi);
-                }
-
-                StorableFact::Constraint {
-                    name,
-                    condition,
-                    severity,
-                    message,
-                } => {
-                    // Constraints go to .axi only (interpreted at query time)
-                    let axi = self.constraint_to_axi(name, condition, severity, message.as_deref());
-                    axi_lines.push(axi);
-
-                    if self.config.require_review.constraints {
-                        warnings.push(format!("Constraint '{}' added - requires review", name));
+                    if retract_relation(&mut pathdb, rel_type, source, target, *confidence) == 0 {
+                        warnings.push(format!(
+                            "Retraction of {}({}, {}) matched no relation",
+                            rel_type, source, target
+                        ));
                     }
+                    continue;
                 }
-
-                StorableFact::TacitKnowledge {
-                    name,
-                    rule,
-                    confidence,
-                    domain,
-                    source,
-                } => {
-                    // Add as special entity in PathDB
-                    let id = pathdb.add_entity(
-                        "TacitKnowledge",
-                        vec![
-                            ("name", name.as_str()),
-                            ("rule", rule.as_str()),
-                            ("domain", domain.as_str()),
-                            ("source", source.as_str()),
-                        ],
-                    );
-                    pathdb_ids.push(id);
-
-                    // Generate .axi
-                    let axi = self.tacit_to_axi(name, rule, *confidence, domain, source);
-                    axi_lines.push(axi);
+                PlannedWrite::Confidence { target, confidence } => {
+                    if decay::set_confidence(&mut pathdb, target, *confidence) == 0 {
+                        warnings.push(format!("Confidence update of {} matched no fact", target));
+                    }
+                    continue;
                 }
-
-                StorableFact::Concept {
-                    name,
-                    description,
-                    difficulty,
-                    prerequisites,
-                } => {
-                    let id = pathdb.add_entity(
-                        "Concept",
-                        vec![
-                            ("name", name.as_str()),
-                            ("description", description.as_str()),
-                            ("difficulty", difficulty.as_str()),
-                        ],
-                    );
-                    pathdb_ids.push(id);
-
-                    let axi = self.concept_to_axi(name, description, difficulty, prerequisites);
-                    axi_lines.push(axi);
+                // Edges go in place; PathDB cannot drop entities, so those
+                // are left out by the replay below (see `trash`).
+                PlannedWrite::Deletion { target } => {
+                    match target {
+                        DeletionTarget::Relation {
+                            rel_type,
+                            source,
+                            target,
+                        } => {
+                            retract_relation(&mut pathdb, rel_type, source, target, None);
+                        }
+                        DeletionTarget::Entity { .. } => rebuild = true,
+                    }
+                    continue;
                 }
-
-                StorableFact::SafetyGuideline {
-                    name,
-                    title,
-                    severity,
-                    explanation,
-                } => {
-                    let id = pathdb.add_entity(
-                        "SafetyGuideline",
-                        vec![
-                            ("name", name.as_str()),
-                            ("title", title.as_str()),
-                            ("severity", severity.as_str()),
-                        ],
-                    );
-                    pathdb_ids.push(id);
-
-                    let axi = self.guideline_to_axi(name, title, severity, explanation);
-                    axi_lines.push(axi);
+            };
+            match outcome {
+                WriteOutcome::Inserted(id) => pathdb_ids.push(id),
+                WriteOutcome::Duplicate(id) => duplicates.push(id),
+                WriteOutcome::Unresolved => {
+                    if let PlannedWrite::Relation {
+                        rel_type,
+                        source,
+                        target,
+                        ..
+                    } = write
+                    {
+                        warnings.push(format!(
+                            "Relation {}({}, {}) names an unknown entity; not written",
+                            rel_type, source, target
+                        ));
+                    }
                 }
             }
         }
+        drop(pathdb);
 
         // Write to .axi file
-        self.append_to_axi(&axi_lines, &change.source)?;
+        self.append_to_axi(change.id, &axi_fragments, &change.source)?;
 
         // Record in changelog
         let mut applied_change = change.clone();
         applied_change.status = ChangeStatus::Applied;
+        applied_change.applied_at = Some(Utc::now());
         self.changelog.write().push(applied_change);
 
+        let deletions: Vec<&DeletionTarget> = writes
+            .iter()
+            .filter_map(|w| match w {
+                PlannedWrite::Deletion { target } => Some(target),
+                _ => None,
+            })
+            .collect();
+        if !deletions.is_empty() {
+            let changelog = self.changelog.read();
+            for target in deletions {
+                let matched = changelog
+                    .iter()
+                    .filter(|c| matches!(c.status, ChangeStatus::Applied))
+                    .flat_map(|c| &c.facts)
+                    .any(|f| target.covers(f));
+                if !matched {
+                    warnings.push(format!("Deletion of {} matched no fact", target));
+                }
+            }
+            drop(changelog);
+        }
+        if rebuild {
+            self.rebuild_from_changelog()?;
+        }
+
         Ok(ApplyResult {
             change_id: change.id,
             pathdb_ids,
             axi_lines,
+            duplicates,
             warnings,
         })
     }
 
     // ========================================================================
-    // .axi Generation
+    // .axi Generation (rendering lives in `axi_writer`)
     // ========================================================================
 
-    fn entity_to_axi(&self, name: &str, entity_type: &str, attrs: &[(String, String)]) -> String {
-        let mut s = format!("{} : {} {{\n", name, entity_type);
-        for (k, v) in attrs {
-            s.push_str(&format!("  {} = \"{}\"\n", k, v));
-        }
-        s.push_str("}\n");
-        s
-    }
-
-    fn relation_to_axi(
-        &self,
-        name: Option<&str>,
-        rel_type: &str,
-        source: &str,
-        target: &str,
-        confidence: f32,
-    ) -> String {
-        if let Some(n) = name {
-            format!(
-                "{} : {}({}, {}) @confidence({})\n",
-                n, rel_type, source, target, confidence
-            )
-        } else {
-            format!(
-                "{}({}, {}) @confidence({})\n",
-                rel_type, source, target, confidence
-            )
-        }
-    }
-
-    fn constraint_to_axi(
-        &self,
-        name: &str,
-        condition: &str,
-        severity: &str,
-        message: Option<&str>,
-    ) -> String {
-        let mut s = format!("constraint {} {{\n", name);
-        s.push_str(&format!("  severity = {}\n", severity));
-        s.push_str(&format!("  condition = \"{}\"\n", condition));
-        if let Some(msg) = message {
-            s.push_str(&format!("  message = \"{}\"\n", msg));
-        }
-        s.push_str("}\n");
-        s
-    }
-
-    fn tacit_to_axi(
-        &self,
-        name: &str,
-        rule: &str,
-        confidence: f32,
-        domain: &str,
-        source: &str,
-    ) -> String {
-        format!(
-            "tacit \"{}\" {{\n  rule: {}\n  confidence: {}\n  domain: \"{}\"\n  source: \"{}\"\n}}\n",
-            name, rule, confidence, domain, source
-        )
-    }
-
-    fn concept_to_axi(
-        &self,
-        name: &str,
-        description: &str,
-        difficulty: &str,
-        prerequisites: &[String],
-    ) -> String {
-        let prereqs = if prerequisites.is_empty() {
-            "[]".to_string()
-        } else {
-            format!("[{}]", prerequisites.join(", "))
-        };
-        format!(
-            "concept {} : Concept {{\n  description = \"\"\"{}\"\"\"\n  difficulty = {}\n  prerequisites = {}\n}}\n",
-            name, description, difficulty, prereqs
-        )
-    }
-
-    fn guideline_to_axi(
-        &self,
-        name: &str,
-        title: &str,
-        severity: &str,
-        explanation: &str,
-    ) -> String {
-        format!(
-            "guideline {} : SafetyGuideline {{\n  title = \"{}\"\n  severity = {}\n  explanation = \"\"\"{}\"\"\"\n}}\n",
-            name, title, severity, explanation
-        )
-    }
-
-    /// Append lines to the appropriate .axi file
-    fn append_to_axi(&self, lines: &[String], source: &ChangeSource) -> anyhow::Result<()> {
-        // Determine file name based on source
-        let filename = match source {
-            ChangeSource::LLMExtraction { .. } => "llm_extracted.axi",
-            ChangeSource::UserEdit { .. } => "user_edits.axi",
-            ChangeSource::FileImport { path } => {
-                return Ok(()); // Already in a file
-            }
-            ChangeSource::API { .. } => "api_additions.axi",
-            ChangeSource::System { .. } => "system_inferred.axi",
-        };
-
-        let path = self.config.axi_dir.join(filename);
-
-        // Create directory if needed
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-
-        // Append to file
-        use std::io::Write;
-        let mut file = std::fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&path)?;
-
-        // Add header comment for this batch
-        writeln!(file, "\n-- Added at {}", Utc::now().to_rfc3339())?;
+    /// `.axi` file a change from `source` is appended to (`None` for file
+    /// imports, which are already in a file).
+    fn axi_filename(source: &ChangeSource) -> Option<&'static str> {
         match source {
-            ChangeSource::LLMExtraction {
-                session_id,
-                model,
-                confidence,
-            } => {
-                writeln!(
-                    file,
-                    "-- Source: LLM extraction (model: {}, confidence: {:.2})",
-                    model, confidence
-                )?;
-            }
-            ChangeSource::UserEdit { user_id } => {
-                writeln!(
-                    file,
-                    "-- Source: User edit ({})",
-                    user_id.as_deref().unwrap_or("anonymous")
-                )?;
-            }
-            _ => {}
+            ChangeSource::LLMExtraction { .. } => Some("llm_extracted.axi"),
+            ChangeSource::UserEdit { .. } => Some("user_edits.axi"),
+            ChangeSource::FileImport { .. } => None,
+            ChangeSource::API { .. } => Some("api_additions.axi"),
+            ChangeSource::System { .. } => Some("system_inferred.axi"),
         }
-
-        for line in lines {
-            writeln!(file, "{}", line)?;
-        }
-
-        Ok(())
     }
 
     // ========================================================================
     // Persistence
     // ========================================================================
 
-    fn save_changelog(&self) -> anyhow::Result<()> {
+    fn save_changelog(&self) -> Result<()> {
         let changelog = self.changelog.read();
         let json = serde_json::to_string_pretty(&*changelog)?;
         std::fs::write(&self.config.changelog_path, json)?;
         Ok(())
     }
 
-    fn save_pathdb(&self) -> anyhow::Result<()> {
+    fn save_pathdb(&self) -> Result<()> {
         let pathdb = self.pathdb.read();
         let bytes = pathdb.to_bytes()?;
         std::fs::write(&self.config.pathdb_path, bytes)?;
@@ -817,18 +855,22 @@ impl UnifiedStorage {
     // ========================================================================
 
     /// Rollback to a specific change
-    pub fn rollback_to(&self, change_id: ChangeId) -> anyhow::Result<()> {
+    pub fn rollback_to(&self, change_id: ChangeId) -> Result<()> {
         // Find the change index
         let changelog = self.changelog.read();
         let idx = changelog
             .iter()
             .position(|c| c.id == change_id)
-            .ok_or_else(|| anyhow::anyhow!("Change not found: {}", change_id))?;
+            .ok_or(StorageError::ChangeNotFound(change_id))?;
 
         // Mark subsequent changes as rolled back
         drop(changelog);
         let mut changelog = self.changelog.write();
+        let now = Utc::now();
         for change in changelog.iter_mut().skip(idx + 1) {
+            if matches!(change.status, ChangeStatus::Applied) {
+                change.retracted_at = Some(now);
+            }
             change.status = ChangeStatus::Rolled {
                 reason: format!("Rolled back to {}", change_id),
             };
@@ -841,64 +883,21 @@ impl UnifiedStorage {
         Ok(())
     }
 
-    /// Rebuild PathDB from changelog (up to Applied changes)
-    fn rebuild_from_changelog(&self) -> anyhow::Result<()> {
+    /// Rebuild PathDB from changelog (up to Applied changes), keeping the
+    /// live database's declarations
+    fn rebuild_from_changelog(&self) -> Result<()> {
         let mut pathdb = self.pathdb.write();
-        *pathdb = PathDB::new();
+        *pathdb = pathdb.empty_with_declarations();
 
         let changelog = self.changelog.read();
-        for change in changelog.iter() {
-            if matches!(change.status, ChangeStatus::Applied) {
-                for fact in &change.facts {
-                    match fact {
-                        StorableFact::Entity {
-                            name,
-                            entity_type,
-                            attributes,
-                        } => {
-                            let attrs: Vec<(&str, &str)> = attributes
-                                .iter()
-                                .map(|(k, v)| (k.as_str(), v.as_str()))
-                                .collect();
-                            pathdb.add_entity(entity_type, attrs);
-                        }
-                        StorableFact::Relation {
-                            rel_type,
-                            source,
-                            target,
-                            confidence,
-                            attributes,
-                            ..
-                        } => {
-                            // Simplified - would need name resolution
-                            let attrs: Vec<(&str, &str)> = attributes
-                                .iter()
-                                .map(|(k, v)| (k.as_str(), v.as_str()))
-                                .collect();
-                            pathdb.add_relation(rel_type, 0, 1, *confidence, attrs);
-                        }
-                        StorableFact::TacitKnowledge {
-                            name,
-                            rule,
-                            domain,
-                            source,
-                            ..
-                        } => {
-                            pathdb.add_entity(
-                                "TacitKnowledge",
-                                vec![
-                                    ("name", name.as_str()),
-                                    ("rule", rule.as_str()),
-                                    ("domain", domain.as_str()),
-                                    ("source", source.as_str()),
-                                ],
-                            );
-                        }
-                        _ => {}
-                    }
-                }
-            }
-        }
+        replay_changes(
+            &mut pathdb,
+            &changelog,
+            0,
+            |c| matches!(c.status, ChangeStatus::Applied),
+            Some(Utc::now()),
+            self.config.on_duplicate,
+        );
 
         // Rebuild indexes
         pathdb.build_indexes();
@@ -911,7 +910,7 @@ impl UnifiedStorage {
     // ========================================================================
 
     /// Reload .axi files and sync to PathDB
-    pub fn sync_from_axi(&self) -> anyhow::Result<usize> {
+    pub fn sync_from_axi(&self) -> Result<usize> {
         // Today we only refresh a lightweight schema index (names of entity/relation
         // types and constraints) to support grounding/validation. Importing full
         // `.axi` instances into PathDB is intentionally deferred until the
@@ -922,12 +921,182 @@ impl UnifiedStorage {
     }
 }
 
+/// `axiograph_storage_pending_changes`: changes queued but not yet flushed
+/// (summed across every storage instance in the process).
+fn pending_changes_gauge() -> &'static metrics::Gauge {
+    static GAUGE: std::sync::OnceLock<Arc<metrics::Gauge>> = std::sync::OnceLock::new();
+    GAUGE.get_or_init(|| {
+        metrics::global().gauge(
+            "axiograph_storage_pending_changes",
+            "Changes queued in UnifiedStorage and not yet flushed.",
+            &[],
+        )
+    })
+}
+
+/// Replay the changes of `changes[start..]` that `believed` accepts, leaving
+/// out facts covered by a later deletion in effect at `deletions_at` (`None`
+/// replays as if nothing had been deleted).
+pub(crate) fn replay_changes(
+    pathdb: &mut PathDB,
+    changes: &[Change],
+    start: usize,
+    believed: impl Fn(&Change) -> bool,
+    deletions_at: Option<DateTime<Utc>>,
+    policy: OnDuplicate,
+) {
+    dedupe::index_identity_attrs(pathdb);
+    let mut deletions: Vec<((usize, usize), &DeletionTarget)> = Vec::new();
+    if let Some(t) = deletions_at {
+        for (i, change) in changes.iter().enumerate().skip(start) {
+            for (f, fact) in change.facts.iter().enumerate() {
+                if let StorableFact::Deletion { target, .. } = fact {
+                    if trash::active_deletion(change, fact, t) {
+                        deletions.push(((i, f), target));
+                    }
+                }
+            }
+        }
+    }
+
+    for (i, change) in changes.iter().enumerate().skip(start) {
+        if !believed(change) {
+            continue;
+        }
+        for (f, fact) in change.facts.iter().enumerate() {
+            let deleted = deletions
+                .iter()
+                .any(|(at, target)| *at > (i, f) && target.covers(fact));
+            if !deleted {
+                replay_fact(pathdb, fact, policy);
+            }
+        }
+    }
+}
+
+/// Apply the PathDB side of a fact (mirrors `apply_change`, without `.axi` output).
+pub(crate) fn replay_fact(pathdb: &mut PathDB, fact: &StorableFact, policy: OnDuplicate) {
+    match fact {
+        StorableFact::Entity {
+            entity_type,
+            attributes,
+            ..
+        } => {
+            dedupe::apply_entity(pathdb, entity_type, attributes, policy);
+        }
+        StorableFact::Relation {
+            rel_type,
+            source,
+            target,
+            confidence,
+            attributes,
+            ..
+        } => {
+            dedupe::apply_relation(
+                pathdb,
+                rel_type,
+                source,
+                target,
+                *confidence,
+                attributes,
+                policy,
+            );
+        }
+        StorableFact::TacitKnowledge {
+            name,
+            rule,
+            confidence,
+            domain,
+            source,
+        } => {
+            let attrs = dry_run::owned_attrs(&[
+                ("name", name),
+                ("rule", rule),
+                (decay::CONFIDENCE_ATTR, &confidence.to_string()),
+                ("domain", domain),
+                ("source", source),
+            ]);
+            dedupe::apply_entity(pathdb, "TacitKnowledge", &attrs, policy);
+        }
+        StorableFact::Concept {
+            name,
+            description,
+            difficulty,
+            ..
+        } => {
+            let attrs = dry_run::owned_attrs(&[
+                ("name", name),
+                ("description", description),
+                ("difficulty", difficulty),
+            ]);
+            dedupe::apply_entity(pathdb, "Concept", &attrs, policy);
+        }
+        StorableFact::SafetyGuideline {
+            name,
+            title,
+            severity,
+            ..
+        } => {
+            let attrs = dry_run::owned_attrs(&[
+                ("name", name),
+                ("title", title),
+                ("severity", severity),
+            ]);
+            dedupe::apply_entity(pathdb, "SafetyGuideline", &attrs, policy);
+        }
+        StorableFact::Retraction {
+            rel_type,
+            source,
+            target,
+            confidence,
+            ..
+        } => {
+            retract_relation(pathdb, rel_type, source, target, *confidence);
+        }
+        StorableFact::ConfidenceDecay {
+            target, confidence, ..
+        }
+        | StorableFact::Verification {
+            target, confidence, ..
+        } => {
+            decay::set_confidence(pathdb, target, *confidence);
+        }
+        // Deletions act through `replay_changes`
+        StorableFact::Constraint { .. }
+        | StorableFact::Deletion { .. }
+        | StorableFact::DecayExemption { .. } => {}
+    }
+}
+
+/// Delete (`confidence: None`) or downgrade every `source -rel_type-> target`
+/// relation, matching endpoints by `name`. Returns how many changed.
+pub(crate) fn retract_relation(
+    pathdb: &mut PathDB,
+    rel_type: &str,
+    source: &str,
+    target: &str,
+    confidence: Option<f32>,
+) -> usize {
+    let sources = pathdb.entities_with_attr_fuzzy("name", source, 0);
+    let targets = pathdb.entities_with_attr_fuzzy("name", target, 0);
+    let mut changed = 0;
+    for s in &sources {
+        for t in &targets {
+            changed += match confidence {
+                Some(c) => pathdb.set_relation_confidence(s, rel_type, t, c),
+                None => pathdb.remove_relation(s, rel_type, t),
+            };
+        }
+    }
+    changed
+}
+
 // ============================================================================
 // Convenience Functions
 // ============================================================================
 
 /// Create storage from common paths
-pub fn open_storage(knowledge_dir: &str) -> anyhow::Result<UnifiedStorage> {
+pub fn open_storage(knowledge_dir: &str) -> Result<UnifiedStorage> {
     let dir = PathBuf::from(knowledge_dir);
     let config = StorageConfig {
         axi_dir: dir.clone(),
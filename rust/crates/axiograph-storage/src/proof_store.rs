@@ -0,0 +1,216 @@
+//! Persisted query proofs.
+//!
+//! `PathDB::execute_with_mode::<WithProof>` returns the execution journal
+//! alongside the result, but it is gone once the caller drops it. With a
+//! proof store enabled, [`UnifiedStorage::execute_with_proof`] writes the
+//! query, its result and the journal to `<dir>/<query_id>.json`, so an
+//! auditor can later fetch exactly what a query did (and re-check it) by id.
+//!
+//! Journals are kept until [`UnifiedStorage::gc_proofs`] drops them per the
+//! configured [`ProofRetention`] (age and/or count).
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use axiograph_pathdb::{PathQuery, QueryContext, QueryExecutionEvent, WithProof};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{Result, StorageError, UnifiedStorage};
+
+/// When stored proofs are garbage-collected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofRetention {
+    /// Days a proof is kept; `None` keeps proofs regardless of age.
+    pub retention_days: Option<u32>,
+    /// Keep at most this many proofs (newest first); `None` is unbounded.
+    pub max_proofs: Option<usize>,
+}
+
+impl Default for ProofRetention {
+    fn default() -> Self {
+        Self {
+            retention_days: Some(30),
+            max_proofs: None,
+        }
+    }
+}
+
+/// Proof store configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofStoreConfig {
+    /// Directory holding one `<query_id>.json` file per proof.
+    pub dir: PathBuf,
+    #[serde(default)]
+    pub retention: ProofRetention,
+}
+
+impl ProofStoreConfig {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            retention: ProofRetention::default(),
+        }
+    }
+}
+
+/// A query run with proofs on, as persisted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredProof {
+    pub query_id: Uuid,
+    pub executed_at: DateTime<Utc>,
+    pub principal: String,
+    pub query: PathQuery,
+    pub context: QueryContext,
+    /// Snapshot version the query ran against.
+    pub snapshot: String,
+    /// Matching entity ids, ascending.
+    pub result: Vec<u32>,
+    /// The `ProofJournal` of the execution.
+    pub journal: Vec<QueryExecutionEvent>,
+}
+
+/// Directory of persisted proofs.
+pub struct ProofStore {
+    config: ProofStoreConfig,
+}
+
+impl ProofStore {
+    pub fn open(config: ProofStoreConfig) -> Result<Self> {
+        if config.retention.max_proofs == Some(0) {
+            return Err(StorageError::InvalidConfig(
+                "proof retention max_proofs must be at least 1".to_string(),
+            ));
+        }
+        std::fs::create_dir_all(&config.dir)?;
+        Ok(Self { config })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.config.dir
+    }
+
+    fn path_for(&self, query_id: Uuid) -> PathBuf {
+        self.config.dir.join(format!("{query_id}.json"))
+    }
+
+    pub fn save(&self, proof: &StoredProof) -> Result<()> {
+        let path = self.path_for(proof.query_id);
+        std::fs::write(&path, serde_json::to_vec_pretty(proof)?)?;
+        Ok(())
+    }
+
+    /// The proof stored for `query_id`, if it exists and was not collected.
+    pub fn get(&self, query_id: Uuid) -> Result<Option<StoredProof>> {
+        let path = self.path_for(query_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_slice(&std::fs::read(path)?)?))
+    }
+
+    /// All stored proofs, oldest first.
+    pub fn list(&self) -> Result<Vec<StoredProof>> {
+        let mut out: Vec<StoredProof> = Vec::new();
+        for entry in std::fs::read_dir(&self.config.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            out.push(serde_json::from_slice(&std::fs::read(&path)?)?);
+        }
+        out.sort_by_key(|p| (p.executed_at, p.query_id));
+        Ok(out)
+    }
+
+    /// Drop proofs outside the retention policy at `now`. Returns the ids
+    /// removed, oldest first.
+    pub fn gc(&self, now: DateTime<Utc>) -> Result<Vec<Uuid>> {
+        let retention = &self.config.retention;
+        let proofs = self.list()?;
+        let expired_before = retention
+            .retention_days
+            .map(|days| now - Duration::days(i64::from(days)));
+        let overflow = retention
+            .max_proofs
+            .map_or(0, |max| proofs.len().saturating_sub(max));
+
+        let mut removed = Vec::new();
+        for (i, proof) in proofs.iter().enumerate() {
+            let expired = expired_before.is_some_and(|cutoff| proof.executed_at <= cutoff);
+            if expired || i < overflow {
+                std::fs::remove_file(self.path_for(proof.query_id))?;
+                removed.push(proof.query_id);
+            }
+        }
+        Ok(removed)
+    }
+}
+
+impl UnifiedStorage {
+    /// Persist proofs from [`Self::execute_with_proof`] under `config.dir`.
+    pub fn enable_proof_store(&self, config: ProofStoreConfig) -> Result<()> {
+        *self.proof_store.write() = Some(Arc::new(ProofStore::open(config)?));
+        Ok(())
+    }
+
+    pub fn proof_store(&self) -> Option<Arc<ProofStore>> {
+        self.proof_store.read().clone()
+    }
+
+    fn require_proof_store(&self) -> Result<Arc<ProofStore>> {
+        self.proof_store()
+            .ok_or_else(|| StorageError::InvalidConfig("proof store not enabled".to_string()))
+    }
+
+    /// Execute a PathQuery with proofs on and persist the journal. The
+    /// returned [`StoredProof::query_id`] retrieves it via [`Self::proof`].
+    ///
+    /// The query is audited like [`Self::execute_audited`] if auditing is on.
+    pub fn execute_with_proof(
+        &self,
+        principal: &str,
+        query: &PathQuery,
+        ctx: &QueryContext,
+    ) -> Result<StoredProof> {
+        let store = self.require_proof_store()?;
+        let snapshot = self.snapshot_version();
+        let proved = self
+            .pathdb
+            .read()
+            .execute_with_mode_in::<WithProof>(query, ctx);
+        let proof = StoredProof {
+            query_id: Uuid::new_v4(),
+            executed_at: Utc::now(),
+            principal: principal.to_string(),
+            query: query.clone(),
+            context: *ctx,
+            snapshot,
+            result: proved.value.iter().collect(),
+            journal: proved.proof,
+        };
+        store.save(&proof)?;
+        if let Some(log) = self.query_audit() {
+            log.record(
+                principal,
+                crate::audit::AuditedQuery::PathQuery {
+                    query: query.clone(),
+                },
+                &proof.snapshot,
+                Some(proof.result.len() as u64),
+            )?;
+        }
+        Ok(proof)
+    }
+
+    /// The persisted proof of an earlier [`Self::execute_with_proof`].
+    pub fn proof(&self, query_id: Uuid) -> Result<Option<StoredProof>> {
+        self.require_proof_store()?.get(query_id)
+    }
+
+    /// Apply the proof retention policy at `now`; returns the ids removed.
+    pub fn gc_proofs(&self, now: DateTime<Utc>) -> Result<Vec<Uuid>> {
+        self.require_proof_store()?.gc(now)
+    }
+}
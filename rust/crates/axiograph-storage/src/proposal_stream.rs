@@ -0,0 +1,268 @@
+//! Streaming NDJSON proposal ingestion.
+//!
+//! Proposal pipelines produce files far too large to load as one
+//! `ProposalsFileV1`. [`UnifiedStorage::ingest_proposals_stream`] reads one
+//! `ProposalV1` JSON object per line instead, validates it, and applies the
+//! accepted proposals in batches of [`ProposalStreamConfig::batch_size`], one
+//! [`Change`] per batch. The input is never held whole, but the stream is not
+//! constant-memory: every applied batch stays in the changelog (in memory,
+//! and written out whole when saved), as does the converter's
+//! `entity_id -> name` map.
+//!
+//! - Each line gets the strict per-record checks of
+//!   [`screen_proposal_line`]: unknown fields, empty ids and confidence
+//!   outside `[0, 1]` are rejected with the field path
+//!   (`line 12: confidence`).
+//! - Proposals are converted by one [`ProposalConverter`] for the whole
+//!   stream, so relation endpoints are entity ids from earlier in the stream
+//!   or `external_id`s already in PathDB; entities must precede the relations
+//!   that use them.
+//! - Invalid lines (bad JSON, failed checks, unknown endpoints) are rejected
+//!   and reported, and never stop the stream.
+//!
+//! Applying follows `flush`: duplicates are handled by
+//! `StorageConfig::on_duplicate`, and the changelog and PathDB are saved once
+//! at the end (also when reading fails part way).
+
+use std::io::BufRead;
+
+use axiograph_ingest_docs::{screen_proposal_line, ProposalV1, RejectedProposal};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::proposals::{ConvertedProposal, ProposalConverter};
+use crate::{
+    Change, ChangeId, ChangeSource, ChangeStatus, Result, StorableFact, StorageError,
+    UnifiedStorage,
+};
+
+/// Options for [`UnifiedStorage::ingest_proposals_stream_with`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalStreamConfig {
+    /// Accepted proposals per change.
+    pub batch_size: usize,
+    /// Source recorded on every change.
+    pub source: ChangeSource,
+    /// Rejected lines kept in the report (all are counted).
+    pub max_errors: usize,
+}
+
+impl Default for ProposalStreamConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 1000,
+            source: ChangeSource::System {
+                reason: "proposal stream".to_string(),
+            },
+            max_errors: 100,
+        }
+    }
+}
+
+/// What one batch (change) applied.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposalBatchStats {
+    pub change_id: ChangeId,
+    /// 1-based input lines of the first and last proposal in the batch.
+    pub first_line: usize,
+    pub last_line: usize,
+    pub entities: usize,
+    pub relations: usize,
+    /// PathDB writes that inserted a new entity or relation.
+    pub inserted: usize,
+    /// Writes that matched an existing fact (see `OnDuplicate`).
+    pub duplicates: usize,
+    pub warnings: Vec<String>,
+}
+
+/// A rejected input line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposalLineError {
+    /// 1-based input line.
+    pub line: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proposal_id: Option<String>,
+    pub message: String,
+}
+
+/// Result of [`UnifiedStorage::ingest_proposals_stream`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProposalStreamReport {
+    /// Non-blank lines read.
+    pub lines: usize,
+    pub accepted: usize,
+    pub rejected: usize,
+    pub batches: Vec<ProposalBatchStats>,
+    /// The first `max_errors` rejections.
+    pub errors: Vec<ProposalLineError>,
+}
+
+/// Accepted proposals not yet applied.
+#[derive(Default)]
+pub(crate) struct Batch {
+    pub(crate) facts: Vec<StorableFact>,
+    first_line: usize,
+    last_line: usize,
+    entities: usize,
+    relations: usize,
+}
+
+impl Batch {
+    /// Add the proposal at (1-based) `line`, or record its rejection.
+    pub(crate) fn accept(
+        &mut self,
+        line: usize,
+        converted: std::result::Result<ConvertedProposal, (Option<String>, String)>,
+        report: &mut ProposalStreamReport,
+        max_errors: usize,
+    ) {
+        match converted {
+            Ok(converted) => {
+                if converted.endpoints.is_some() {
+                    self.relations += 1;
+                } else {
+                    self.entities += 1;
+                }
+                if self.facts.is_empty() {
+                    self.first_line = line;
+                }
+                self.last_line = line;
+                self.facts.push(converted.fact);
+                report.accepted += 1;
+            }
+            Err((proposal_id, message)) => {
+                report.rejected += 1;
+                if report.errors.len() < max_errors {
+                    report.errors.push(ProposalLineError {
+                        line,
+                        proposal_id,
+                        message,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// A screening failure as `(proposal_id, message)`, like conversion errors.
+fn rejection(rejected: RejectedProposal) -> (Option<String>, String) {
+    let proposal_id = rejected
+        .raw
+        .get("proposal_id")
+        .and_then(serde_json::Value::as_str)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string);
+    let message = rejected
+        .issues
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ");
+    (proposal_id, message)
+}
+
+impl UnifiedStorage {
+    /// Ingest newline-delimited `ProposalV1` records with the default
+    /// [`ProposalStreamConfig`] (see the module docs).
+    pub fn ingest_proposals_stream(&self, reader: impl BufRead) -> Result<ProposalStreamReport> {
+        self.ingest_proposals_stream_with(reader, &ProposalStreamConfig::default(), |_| {})
+    }
+
+    /// Ingest newline-delimited `ProposalV1` records, calling `on_batch` after
+    /// each batch is applied.
+    pub fn ingest_proposals_stream_with(
+        &self,
+        reader: impl BufRead,
+        config: &ProposalStreamConfig,
+        mut on_batch: impl FnMut(&ProposalBatchStats),
+    ) -> Result<ProposalStreamReport> {
+        let applied_before = self.changelog.read().len();
+        let mut report = ProposalStreamReport::default();
+        let read = self.read_proposal_stream(reader, config, &mut report, &mut on_batch);
+        if !report.batches.is_empty() {
+            self.persist_applied(applied_before)?;
+        }
+        read.map(|()| report)
+    }
+
+    fn read_proposal_stream(
+        &self,
+        reader: impl BufRead,
+        config: &ProposalStreamConfig,
+        report: &mut ProposalStreamReport,
+        on_batch: &mut impl FnMut(&ProposalBatchStats),
+    ) -> Result<()> {
+        let batch_size = config.batch_size.max(1);
+        let mut converter = ProposalConverter::new();
+        let mut batch = Batch::default();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            report.lines += 1;
+            let converted = screen_proposal_line(i + 1, &line)
+                .map_err(rejection)
+                .and_then(|proposal| self.convert_proposal(&mut converter, &proposal));
+            batch.accept(i + 1, converted, report, config.max_errors);
+            if batch.facts.len() >= batch_size {
+                let stats =
+                    self.apply_proposal_batch(std::mem::take(&mut batch), &config.source)?;
+                on_batch(&stats);
+                report.batches.push(stats);
+            }
+        }
+        if !batch.facts.is_empty() {
+            let stats = self.apply_proposal_batch(batch, &config.source)?;
+            on_batch(&stats);
+            report.batches.push(stats);
+        }
+        Ok(())
+    }
+
+    /// Convert one proposal; a rejection is `(proposal_id, message)`.
+    pub(crate) fn convert_proposal(
+        &self,
+        converter: &mut ProposalConverter,
+        proposal: &ProposalV1,
+    ) -> std::result::Result<ConvertedProposal, (Option<String>, String)> {
+        converter
+            .convert(proposal, &self.pathdb.read())
+            .map_err(|e| match e {
+                StorageError::InvalidProposal {
+                    proposal_id,
+                    reason,
+                } => (Some(proposal_id).filter(|id| !id.is_empty()), reason),
+                other => (None, other.to_string()),
+            })
+    }
+
+    /// Apply `batch` as one change.
+    pub(crate) fn apply_proposal_batch(
+        &self,
+        batch: Batch,
+        source: &ChangeSource,
+    ) -> Result<ProposalBatchStats> {
+        let change = Change {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: source.clone(),
+            facts: batch.facts,
+            status: ChangeStatus::Pending,
+            applied_at: None,
+            retracted_at: None,
+        };
+        let applied = self.apply_change(&change)?;
+        Ok(ProposalBatchStats {
+            change_id: change.id,
+            first_line: batch.first_line,
+            last_line: batch.last_line,
+            entities: batch.entities,
+            relations: batch.relations,
+            inserted: applied.pathdb_ids.len(),
+            duplicates: applied.duplicates.len(),
+            warnings: applied.warnings,
+        })
+    }
+}
@@ -0,0 +1,247 @@
+//! Converting ingester proposals into storable facts.
+//!
+//! Ingesters emit `ProposalV1` records; storage takes [`StorableFact`]s.
+//! [`ProposalConverter`] is the supported mapping between the two, using the
+//! same attribute layout as the CLI's evidence-plane proposal import:
+//!
+//! - entities keep `name`, `external_id` (the proposal's `entity_id`),
+//!   `proposal_id`, `proposal_confidence`, `schema_hint`, `public_rationale`
+//!   and `description`; proposal attributes that collide with these are stored
+//!   as `attr_<key>`, and proposal metadata as `meta_<key>`;
+//! - relations get the same provenance attributes, with the `relation_id` as
+//!   fact name and `external_id`, at the proposal's confidence;
+//! - evidence pointers become `evidence_<i>_chunk_id` / `_locator` /
+//!   `_span_id` attributes on both.
+//!
+//! Relation endpoints are proposal entity ids. They resolve to entities the
+//! converter has already seen (earlier in the same batch) or, through the
+//! `external_id` index, to entities already in PathDB; anything else is an
+//! [`StorageError::InvalidProposal`].
+
+use std::collections::HashMap;
+
+use axiograph_ingest_docs::{ProposalMetaV1, ProposalV1};
+use axiograph_pathdb::PathDB;
+use serde::{Deserialize, Serialize};
+
+use crate::dedupe::{entity_by_external_id, EXTERNAL_ID_ATTR};
+use crate::{Result, StorableFact, StorageError};
+
+/// Attributes the converter sets itself.
+const RESERVED_ATTRS: [&str; 7] = [
+    "name",
+    EXTERNAL_ID_ATTR,
+    "proposal_id",
+    "proposal_confidence",
+    "schema_hint",
+    "public_rationale",
+    "description",
+];
+
+/// Where a relation endpoint resolved to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedEndpoint {
+    /// The proposal's entity id.
+    pub entity_id: String,
+    /// Name the relation fact refers to the entity by.
+    pub name: String,
+    /// The PathDB entity, or `None` if it is only proposed so far.
+    pub pathdb_id: Option<u32>,
+}
+
+/// One converted proposal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvertedProposal {
+    pub proposal_id: String,
+    pub fact: StorableFact,
+    /// Source and target, for relations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoints: Option<(ResolvedEndpoint, ResolvedEndpoint)>,
+}
+
+/// Maps `ProposalV1` records to [`StorableFact`]s (see the module docs).
+///
+/// The converter remembers the entities it converts, so one instance should
+/// see a whole proposal file (or stream) in order.
+#[derive(Debug, Clone, Default)]
+pub struct ProposalConverter {
+    /// Proposal entity id -> entity name.
+    entities: HashMap<String, String>,
+}
+
+impl ProposalConverter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validate and convert one proposal, resolving relation endpoints
+    /// against earlier proposals and then `db`.
+    pub fn convert(&mut self, proposal: &ProposalV1, db: &PathDB) -> Result<ConvertedProposal> {
+        let meta = match proposal {
+            ProposalV1::Entity { meta, .. } | ProposalV1::Relation { meta, .. } => meta,
+        };
+        let invalid = |reason: String| StorageError::InvalidProposal {
+            proposal_id: meta.proposal_id.clone(),
+            reason,
+        };
+        if meta.proposal_id.trim().is_empty() {
+            return Err(invalid("empty proposal_id".to_string()));
+        }
+        if !(0.0..=1.0).contains(&meta.confidence) {
+            return Err(invalid(format!(
+                "confidence {} is outside [0, 1]",
+                meta.confidence
+            )));
+        }
+        match proposal {
+            ProposalV1::Entity {
+                meta,
+                entity_id,
+                entity_type,
+                name,
+                attributes,
+                description,
+            } => {
+                let entity_id = entity_id.trim();
+                if entity_id.is_empty() || entity_type.trim().is_empty() || name.trim().is_empty() {
+                    return Err(invalid(
+                        "entity needs an entity_id, entity_type and name".to_string(),
+                    ));
+                }
+                let mut attrs = vec![("name".to_string(), name.clone())];
+                attrs.extend(provenance_attrs(meta, entity_id));
+                if let Some(description) = description.as_ref().filter(|d| !d.trim().is_empty()) {
+                    attrs.push(("description".to_string(), description.clone()));
+                }
+                push_proposal_attrs(&mut attrs, meta, attributes);
+                self.entities.insert(entity_id.to_string(), name.clone());
+                Ok(ConvertedProposal {
+                    proposal_id: meta.proposal_id.clone(),
+                    fact: StorableFact::Entity {
+                        name: name.clone(),
+                        entity_type: entity_type.clone(),
+                        attributes: attrs,
+                    },
+                    endpoints: None,
+                })
+            }
+            ProposalV1::Relation {
+                meta,
+                relation_id,
+                rel_type,
+                source,
+                target,
+                attributes,
+            } => {
+                if relation_id.trim().is_empty() || rel_type.trim().is_empty() {
+                    return Err(invalid(
+                        "relation needs a relation_id and rel_type".to_string(),
+                    ));
+                }
+                let endpoint = |entity_id: &str| {
+                    self.resolve(entity_id.trim(), db)
+                        .ok_or_else(|| invalid(format!("unknown endpoint `{entity_id}`")))
+                };
+                let (source, target) = (endpoint(source)?, endpoint(target)?);
+                let mut attrs = provenance_attrs(meta, relation_id);
+                push_proposal_attrs(&mut attrs, meta, attributes);
+                Ok(ConvertedProposal {
+                    proposal_id: meta.proposal_id.clone(),
+                    fact: StorableFact::Relation {
+                        name: Some(relation_id.clone()),
+                        rel_type: rel_type.clone(),
+                        source: source.name.clone(),
+                        target: target.name.clone(),
+                        confidence: meta.confidence as f32,
+                        attributes: attrs,
+                    },
+                    endpoints: Some((source, target)),
+                })
+            }
+        }
+    }
+
+    /// Convert `proposals` in order, keeping going past invalid ones.
+    pub fn convert_all<'a>(
+        &mut self,
+        proposals: impl IntoIterator<Item = &'a ProposalV1>,
+        db: &PathDB,
+    ) -> (Vec<ConvertedProposal>, Vec<StorageError>) {
+        let mut converted = Vec::new();
+        let mut errors = Vec::new();
+        for proposal in proposals {
+            match self.convert(proposal, db) {
+                Ok(c) => converted.push(c),
+                Err(e) => errors.push(e),
+            }
+        }
+        (converted, errors)
+    }
+
+    /// An entity id proposed earlier, else one already stored in `db`.
+    fn resolve(&self, entity_id: &str, db: &PathDB) -> Option<ResolvedEndpoint> {
+        let pathdb_id = entity_by_external_id(db, entity_id);
+        let name = match self.entities.get(entity_id) {
+            Some(name) => name.clone(),
+            None => db.get_entity(pathdb_id?)?.attrs.get("name")?.clone(),
+        };
+        Some(ResolvedEndpoint {
+            entity_id: entity_id.to_string(),
+            name,
+            pathdb_id,
+        })
+    }
+}
+
+/// `external_id` and the proposal's provenance attributes.
+fn provenance_attrs(meta: &ProposalMetaV1, external_id: &str) -> Vec<(String, String)> {
+    let mut attrs = vec![
+        (EXTERNAL_ID_ATTR.to_string(), external_id.to_string()),
+        ("proposal_id".to_string(), meta.proposal_id.clone()),
+        (
+            "proposal_confidence".to_string(),
+            meta.confidence.to_string(),
+        ),
+    ];
+    if let Some(hint) = meta.schema_hint.as_ref() {
+        attrs.push(("schema_hint".to_string(), hint.clone()));
+    }
+    if !meta.public_rationale.trim().is_empty() {
+        attrs.push((
+            "public_rationale".to_string(),
+            meta.public_rationale.clone(),
+        ));
+    }
+    attrs
+}
+
+/// Proposal attributes (reserved keys prefixed), metadata and evidence.
+fn push_proposal_attrs(
+    attrs: &mut Vec<(String, String)>,
+    meta: &ProposalMetaV1,
+    attributes: &HashMap<String, String>,
+) {
+    let mut extra: Vec<_> = attributes.iter().collect();
+    extra.sort();
+    for (key, value) in extra {
+        if RESERVED_ATTRS.contains(&key.as_str()) {
+            attrs.push((format!("attr_{key}"), value.clone()));
+        } else {
+            attrs.push((key.clone(), value.clone()));
+        }
+    }
+    let mut metadata: Vec<_> = meta.metadata.iter().collect();
+    metadata.sort();
+    for (key, value) in metadata {
+        attrs.push((format!("meta_{key}"), value.clone()));
+    }
+    for (i, ev) in meta.evidence.iter().enumerate() {
+        attrs.push((format!("evidence_{i}_chunk_id"), ev.chunk_id.clone()));
+        if let Some(locator) = ev.locator.as_ref() {
+            attrs.push((format!("evidence_{i}_locator"), locator.clone()));
+        }
+        if let Some(span) = ev.span_id.as_ref() {
+            attrs.push((format!("evidence_{i}_span_id"), span.clone()));
+        }
+    }
+}
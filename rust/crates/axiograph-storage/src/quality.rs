@@ -0,0 +1,348 @@
+//! Declarative data-quality rules and scoring.
+//!
+//! Quality rules are declared in `.axi` theories as named constraint blocks
+//! whose name starts with `quality_`, one rule per line:
+//!
+//! ```text
+//! theory ServiceQuality on Services:
+//!   constraint quality_endpoints:
+//!     ProtoRpc has http_endpoint   -- attribute or outgoing relation
+//!     Person.name non_empty        -- present and not blank
+//!     Person.email unique          -- no two instances share a value
+//! ```
+//!
+//! They are indexed with the rest of the schema
+//! ([`AxiSchemaIndex::quality_rules`](crate::AxiSchemaIndex::quality_rules)).
+//! [`score_quality`] checks every rule against every instance of its type and
+//! reports, per type, **completeness** (the share of `has` checks that pass)
+//! and **consistency** (the share of `non_empty`/`unique` checks that pass).
+//!
+//! [`UnifiedStorage::run_quality_job`] is the periodic job: it scores the
+//! current snapshot and appends the report to a [`QualityHistory`] (JSON
+//! lines) unless that snapshot version was already scored, so the history
+//! holds one point per snapshot version and [`QualityHistory::trend`] shows
+//! how a type's scores moved.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use axiograph_pathdb::PathDB;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{Result, UnifiedStorage};
+
+/// Named constraint blocks with this prefix hold quality rules.
+pub const QUALITY_BLOCK_PREFIX: &str = "quality_";
+
+/// Failing entity ids kept per rule in a report.
+const MAX_EXAMPLES: usize = 20;
+
+/// One declared quality rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum QualityRule {
+    /// `Type has field`: every instance has a `field` attribute or an
+    /// outgoing `field` relation.
+    Has { entity_type: String, field: String },
+    /// `Type.attr non_empty`: every instance has a non-blank `attr`.
+    NonEmpty { entity_type: String, attr: String },
+    /// `Type.attr unique`: no two instances share an `attr` value.
+    Unique { entity_type: String, attr: String },
+}
+
+/// What a rule measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityDimension {
+    Completeness,
+    Consistency,
+}
+
+impl QualityRule {
+    /// Parse one rule line (without comments).
+    pub fn parse(line: &str) -> std::result::Result<Self, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let attr_of = |subject: &str| {
+            subject
+                .split_once('.')
+                .filter(|(t, a)| !t.is_empty() && !a.is_empty())
+                .map(|(t, a)| (t.to_string(), a.to_string()))
+                .ok_or_else(|| format!("expected `Type.attr`, got `{subject}`"))
+        };
+        match words.as_slice() {
+            [entity_type, "has", field] => Ok(Self::Has {
+                entity_type: entity_type.to_string(),
+                field: field.to_string(),
+            }),
+            [subject, "non_empty"] => {
+                let (entity_type, attr) = attr_of(subject)?;
+                Ok(Self::NonEmpty { entity_type, attr })
+            }
+            [subject, "unique"] => {
+                let (entity_type, attr) = attr_of(subject)?;
+                Ok(Self::Unique { entity_type, attr })
+            }
+            _ => Err(format!(
+                "unrecognized quality rule `{line}` (expected `Type has field`, \
+                 `Type.attr non_empty` or `Type.attr unique`)"
+            )),
+        }
+    }
+
+    pub fn entity_type(&self) -> &str {
+        match self {
+            Self::Has { entity_type, .. }
+            | Self::NonEmpty { entity_type, .. }
+            | Self::Unique { entity_type, .. } => entity_type,
+        }
+    }
+
+    pub fn dimension(&self) -> QualityDimension {
+        match self {
+            Self::Has { .. } => QualityDimension::Completeness,
+            Self::NonEmpty { .. } | Self::Unique { .. } => QualityDimension::Consistency,
+        }
+    }
+}
+
+impl fmt::Display for QualityRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Has { entity_type, field } => write!(f, "{entity_type} has {field}"),
+            Self::NonEmpty { entity_type, attr } => write!(f, "{entity_type}.{attr} non_empty"),
+            Self::Unique { entity_type, attr } => write!(f, "{entity_type}.{attr} unique"),
+        }
+    }
+}
+
+/// Rules of a `quality_*` block body; bad lines are skipped with a warning.
+pub(crate) fn parse_quality_block(name: &str, body: &[String]) -> Vec<QualityRule> {
+    body.iter()
+        .map(|line| line.split("--").next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| match QualityRule::parse(line) {
+            Ok(rule) => Some(rule),
+            Err(error) => {
+                tracing::warn!(block = name, %error, "skipping quality rule");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Outcome of one rule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleScore {
+    pub rule: QualityRule,
+    /// Instances checked.
+    pub checked: usize,
+    pub passed: usize,
+    /// Some failing entity ids (at most 20).
+    pub examples: Vec<u32>,
+}
+
+impl RuleScore {
+    pub fn pass_rate(&self) -> Option<f64> {
+        (self.checked > 0).then(|| self.passed as f64 / self.checked as f64)
+    }
+}
+
+/// Scores of one entity type. A dimension without rules (or instances) is
+/// `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TypeQuality {
+    pub instances: usize,
+    pub completeness: Option<f64>,
+    pub consistency: Option<f64>,
+}
+
+/// One scoring run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityReport {
+    /// Snapshot version scored (see [`UnifiedStorage::snapshot_version`]).
+    pub snapshot: String,
+    pub scored_at: DateTime<Utc>,
+    pub types: BTreeMap<String, TypeQuality>,
+    pub rules: Vec<RuleScore>,
+}
+
+fn score_rule(db: &PathDB, rule: &QualityRule) -> RuleScore {
+    let ids: Vec<u32> = db
+        .find_by_type(rule.entity_type())
+        .map(|ids| ids.iter().collect())
+        .unwrap_or_default();
+    let attr = |id: u32, key: &str| db.get_entity(id).and_then(|e| e.attrs.get(key).cloned());
+    let failing: Vec<u32> = match rule {
+        QualityRule::Has { field, .. } => ids
+            .iter()
+            .copied()
+            .filter(|&id| attr(id, field).is_none() && db.follow_one(id, field).is_empty())
+            .collect(),
+        QualityRule::NonEmpty { attr: key, .. } => ids
+            .iter()
+            .copied()
+            .filter(|&id| attr(id, key).is_none_or(|v| v.trim().is_empty()))
+            .collect(),
+        QualityRule::Unique { attr: key, .. } => {
+            let mut by_value: HashMap<String, Vec<u32>> = HashMap::new();
+            for &id in &ids {
+                if let Some(value) = attr(id, key) {
+                    by_value.entry(value).or_default().push(id);
+                }
+            }
+            let mut failing: Vec<u32> = by_value
+                .into_values()
+                .filter(|ids| ids.len() > 1)
+                .flatten()
+                .collect();
+            failing.sort_unstable();
+            failing
+        }
+    };
+    RuleScore {
+        rule: rule.clone(),
+        checked: ids.len(),
+        passed: ids.len() - failing.len(),
+        examples: failing.into_iter().take(MAX_EXAMPLES).collect(),
+    }
+}
+
+/// Score `rules` over `db`, labelling the report with `snapshot`.
+pub fn score_quality(db: &PathDB, rules: &[QualityRule], snapshot: &str) -> QualityReport {
+    let rules: Vec<RuleScore> = rules.iter().map(|r| score_rule(db, r)).collect();
+    let mut totals: BTreeMap<String, [(usize, usize); 2]> = BTreeMap::new();
+    for score in &rules {
+        let slot = match score.rule.dimension() {
+            QualityDimension::Completeness => 0,
+            QualityDimension::Consistency => 1,
+        };
+        let entry = totals
+            .entry(score.rule.entity_type().to_string())
+            .or_default();
+        entry[slot].0 += score.passed;
+        entry[slot].1 += score.checked;
+    }
+    let rate =
+        |(passed, checked): (usize, usize)| (checked > 0).then(|| passed as f64 / checked as f64);
+    let types = totals
+        .into_iter()
+        .map(|(entity_type, [completeness, consistency])| {
+            let instances = db
+                .find_by_type(&entity_type)
+                .map_or(0, |ids| ids.len() as usize);
+            let quality = TypeQuality {
+                instances,
+                completeness: rate(completeness),
+                consistency: rate(consistency),
+            };
+            (entity_type, quality)
+        })
+        .collect();
+    QualityReport {
+        snapshot: snapshot.to_string(),
+        scored_at: Utc::now(),
+        types,
+        rules,
+    }
+}
+
+/// A type's scores at one snapshot version.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityPoint {
+    pub snapshot: String,
+    pub scored_at: DateTime<Utc>,
+    pub quality: TypeQuality,
+}
+
+/// Append-only history of quality reports (JSON lines).
+pub struct QualityHistory {
+    path: PathBuf,
+}
+
+impl QualityHistory {
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+        Ok(Self { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, report: &QualityReport) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(report)?)?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    /// All reports, oldest first.
+    pub fn reports(&self) -> Result<Vec<QualityReport>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let reader = BufReader::new(std::fs::File::open(&self.path)?);
+        let mut out = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            out.push(serde_json::from_str(&line)?);
+        }
+        Ok(out)
+    }
+
+    /// Scores of `entity_type` across the recorded snapshots, oldest first.
+    pub fn trend(&self, entity_type: &str) -> Result<Vec<QualityPoint>> {
+        Ok(self
+            .reports()?
+            .into_iter()
+            .filter_map(|mut report| {
+                let quality = report.types.remove(entity_type)?;
+                Some(QualityPoint {
+                    snapshot: report.snapshot,
+                    scored_at: report.scored_at,
+                    quality,
+                })
+            })
+            .collect())
+    }
+}
+
+impl UnifiedStorage {
+    /// Score the current snapshot against the `.axi` quality rules.
+    pub fn score_quality(&self) -> QualityReport {
+        let rules = self.schema.read().quality_rules.clone();
+        let snapshot = self.snapshot_version();
+        score_quality(&self.pathdb.read(), &rules, &snapshot)
+    }
+
+    /// Periodic job: score and append to `history`, unless the current
+    /// snapshot version is already its latest entry. Returns the new report.
+    pub fn run_quality_job(&self, history: &QualityHistory) -> Result<Option<QualityReport>> {
+        let snapshot = self.snapshot_version();
+        if history
+            .reports()?
+            .last()
+            .is_some_and(|r| r.snapshot == snapshot)
+        {
+            return Ok(None);
+        }
+        let report = self.score_quality();
+        history.append(&report)?;
+        Ok(Some(report))
+    }
+}
@@ -0,0 +1,193 @@
+//! Redaction-preserving snapshot export for external partners.
+//!
+//! Partners want to run structural analyses (degree distributions, motif
+//! counts, path statistics) without seeing sensitive values. A
+//! [`RedactionPolicy`] replaces selected attribute values with salted SHA-256
+//! hashes, so equal values still hash equal (joins and dedup keep working) but
+//! cannot be read back without the salt. PII-tagged entities can optionally be
+//! dropped entirely.
+//!
+//! Without `drop_pii_entities`, entity ids, types (virtual types included),
+//! relations and equivalences are preserved exactly; only attribute values
+//! change.
+
+use std::collections::{BTreeSet, HashMap};
+
+use axiograph_pathdb::PathDB;
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+
+use crate::access::{pii_attribute_names, PROTO_FIELD_PII_REL};
+use crate::{Result, UnifiedStorage};
+
+/// What to hash/drop in a redacted export.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactionPolicy {
+    /// Attribute names whose values are replaced by salted hashes.
+    #[serde(default)]
+    pub hash_attrs: BTreeSet<String>,
+    /// Also hash attributes whose names are tagged PII in the graph.
+    #[serde(default)]
+    pub hash_pii: bool,
+    /// Drop PII-tagged entities (and their relations) instead of keeping them.
+    #[serde(default)]
+    pub drop_pii_entities: bool,
+    /// Salt mixed into every hash; keep it private to make hashes unlinkable.
+    pub salt: String,
+}
+
+/// `sha256:<hex>` of `salt || 0x00 || value`.
+pub fn salted_hash(salt: &str, value: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(value.as_bytes());
+    let digest = hasher.finalize();
+    let mut out = String::with_capacity(7 + 64);
+    out.push_str("sha256:");
+    for b in digest.iter() {
+        use std::fmt::Write as _;
+        let _ = write!(&mut out, "{:02x}", b);
+    }
+    out
+}
+
+/// Entities tagged PII: the sources of `proto_field_pii -> Bool(true)` edges.
+pub fn pii_tagged_entities(db: &PathDB) -> RoaringBitmap {
+    let mut out = RoaringBitmap::new();
+    for relation_id in 0..db.relations.len() as u32 {
+        let Some(rel) = db.relations.get_relation(relation_id) else {
+            continue;
+        };
+        if db.interner.lookup(rel.rel_type).as_deref() != Some(PROTO_FIELD_PII_REL) {
+            continue;
+        }
+        let is_true = db
+            .get_entity(rel.target)
+            .is_some_and(|b| b.attrs.get("name").map(String::as_str) == Some("true"));
+        if is_true {
+            out.insert(rel.source);
+        }
+    }
+    out
+}
+
+/// Copy `db` keeping only `keep_entity` entities and `keep_rel_type` relations
+/// between them, rewriting attribute values with `rewrite(key, value)`.
+///
+/// Every attribute column is rewritten: scalars, each list item and each
+/// language-tagged value. Blob payloads come along only while some copied
+/// attribute still references them, so a hashed or dropped reference takes
+/// its payload with it.
+///
+/// Kept entities are renumbered densely in ascending original-id order (so ids
+/// are unchanged when nothing is dropped). Virtual types of kept entities and
+/// equivalences between them are carried over.
+pub(crate) fn copy_filtered(
+    db: &PathDB,
+    keep_entity: impl Fn(u32) -> bool,
+    keep_rel_type: impl Fn(&str) -> bool,
+    rewrite: impl Fn(&str, String) -> String,
+) -> PathDB {
+    let mut out = PathDB::new();
+    let mut remap: HashMap<u32, u32> = HashMap::new();
+
+    for entity_id in 0..db.entities.len() as u32 {
+        if !keep_entity(entity_id) {
+            continue;
+        }
+        if let Some(new_id) = db.copy_entity_into(entity_id, &mut out, &rewrite) {
+            for type_name in db.virtual_types_of(entity_id) {
+                // `new_id` was just added, so it is in range.
+                let _ = out.mark_virtual_type(new_id, &type_name);
+            }
+            remap.insert(entity_id, new_id);
+        }
+    }
+
+    // Stored once per direction; copy each pair once, in a stable order.
+    let mut equivalences: BTreeSet<(u32, u32, String)> = BTreeSet::new();
+    for (&e1, list) in &db.equivalences {
+        for &(e2, equiv_type) in list {
+            let (Some(&a), Some(&b)) = (remap.get(&e1), remap.get(&e2)) else {
+                continue;
+            };
+            let Some(equiv_type) = db.interner.lookup(equiv_type) else {
+                continue;
+            };
+            equivalences.insert((a.min(b), a.max(b), equiv_type));
+        }
+    }
+    for (a, b, equiv_type) in equivalences {
+        out.add_equivalence(a, b, &equiv_type);
+    }
+
+    for relation_id in 0..db.relations.len() as u32 {
+        let Some(rel) = db.relations.get_relation(relation_id) else {
+            continue;
+        };
+        let (Some(&source), Some(&target)) = (remap.get(&rel.source), remap.get(&rel.target))
+        else {
+            continue;
+        };
+        let Some(rel_type) = db.interner.lookup(rel.rel_type) else {
+            continue;
+        };
+        if !keep_rel_type(&rel_type) {
+            continue;
+        }
+        let attrs: Vec<(String, String)> = rel
+            .attrs
+            .iter()
+            .filter_map(|(k, v)| {
+                let k = db.interner.lookup(*k)?;
+                let v = rewrite(&k, db.interner.lookup(*v)?);
+                Some((k, v))
+            })
+            .collect();
+        let attrs: Vec<(&str, &str)> = attrs
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        out.add_relation(&rel_type, source, target, rel.confidence, attrs);
+    }
+
+    out.import_blobs(db.blobs());
+    out.build_indexes();
+    out
+}
+
+/// Apply `policy` to `db`, returning the redacted copy.
+pub fn redact_pathdb(db: &PathDB, policy: &RedactionPolicy) -> PathDB {
+    let mut hashed = policy.hash_attrs.clone();
+    if policy.hash_pii {
+        hashed.extend(pii_attribute_names(db));
+    }
+    let dropped = if policy.drop_pii_entities {
+        pii_tagged_entities(db)
+    } else {
+        RoaringBitmap::new()
+    };
+
+    copy_filtered(
+        db,
+        |id| !dropped.contains(id),
+        |_| true,
+        |k, v| {
+            if hashed.contains(k) {
+                salted_hash(&policy.salt, &v)
+            } else {
+                v
+            }
+        },
+    )
+}
+
+impl UnifiedStorage {
+    /// `.axpd` bytes of the current PathDB with `policy` applied.
+    pub fn export_redacted(&self, policy: &RedactionPolicy) -> Result<Vec<u8>> {
+        let db = self.pathdb.read();
+        Ok(redact_pathdb(&db, policy).to_bytes()?)
+    }
+}
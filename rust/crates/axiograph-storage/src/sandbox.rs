@@ -0,0 +1,223 @@
+//! Sandbox validation: how well a new source's proposals fit the schemas.
+//!
+//! Before a source goes live, [`validate_proposals`] cross-references its
+//! `ProposalV1` records against an [`AxiSchemaIndex`] without writing
+//! anything. For entity types and relation types separately it reports:
+//!
+//! - how many proposals use each known type;
+//! - **novel** types (not declared in any `.axi` schema), most used first,
+//!   each with **candidate mappings**: declared types whose names are close
+//!   (same words in a different case/spelling, plural vs. singular, or a
+//!   small edit distance);
+//! - coverage, the percentage of proposals whose type is declared.
+//!
+//! An empty schema index declares nothing, so everything is novel.
+
+use std::collections::BTreeMap;
+
+use axiograph_ingest_docs::ProposalV1;
+use serde::{Deserialize, Serialize};
+
+use crate::{AxiSchemaIndex, UnifiedStorage};
+
+/// Knobs for candidate mappings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SandboxConfig {
+    /// Minimum name similarity (`[0, 1]`) for a candidate mapping.
+    pub min_similarity: f64,
+    /// Candidates kept per novel type.
+    pub max_candidates: usize,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            min_similarity: 0.75,
+            max_candidates: 3,
+        }
+    }
+}
+
+/// A declared type a novel type may correspond to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CandidateMapping {
+    pub schema_type: String,
+    pub similarity: f64,
+}
+
+/// A proposed type no schema declares.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NovelType {
+    pub name: String,
+    /// Proposals using it.
+    pub proposals: usize,
+    /// Best first.
+    pub candidates: Vec<CandidateMapping>,
+}
+
+/// Coverage of one kind of type (entity or relation).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TypeCoverage {
+    /// Proposals of this kind.
+    pub proposals: usize,
+    /// Declared type -> proposals using it.
+    pub known: BTreeMap<String, usize>,
+    /// Most used first.
+    pub novel: Vec<NovelType>,
+}
+
+impl TypeCoverage {
+    /// Proposals whose type is declared.
+    pub fn known_proposals(&self) -> usize {
+        self.known.values().sum()
+    }
+
+    /// Percentage of proposals whose type is declared (100 when there are
+    /// none).
+    pub fn coverage_percent(&self) -> f64 {
+        percent(self.known_proposals(), self.proposals)
+    }
+}
+
+/// Result of [`validate_proposals`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SandboxReport {
+    pub entity_types: TypeCoverage,
+    pub relation_types: TypeCoverage,
+}
+
+impl SandboxReport {
+    /// Percentage of all proposals whose type is declared.
+    pub fn coverage_percent(&self) -> f64 {
+        percent(
+            self.entity_types.known_proposals() + self.relation_types.known_proposals(),
+            self.entity_types.proposals + self.relation_types.proposals,
+        )
+    }
+
+    /// Whether every proposed type is declared.
+    pub fn is_fully_covered(&self) -> bool {
+        self.entity_types.novel.is_empty() && self.relation_types.novel.is_empty()
+    }
+}
+
+/// Cross-reference `proposals` against `schema` (see the module docs).
+pub fn validate_proposals<'a>(
+    proposals: impl IntoIterator<Item = &'a ProposalV1>,
+    schema: &AxiSchemaIndex,
+    config: &SandboxConfig,
+) -> SandboxReport {
+    let mut entity_counts: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut relation_counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for proposal in proposals {
+        match proposal {
+            ProposalV1::Entity { entity_type, .. } => {
+                *entity_counts.entry(entity_type.as_str()).or_default() += 1;
+            }
+            ProposalV1::Relation { rel_type, .. } => {
+                *relation_counts.entry(rel_type.as_str()).or_default() += 1;
+            }
+        }
+    }
+    SandboxReport {
+        entity_types: coverage(entity_counts, &schema.entity_types, config),
+        relation_types: coverage(relation_counts, &schema.relation_types, config),
+    }
+}
+
+impl UnifiedStorage {
+    /// [`validate_proposals`] against this storage's `.axi` schema index.
+    pub fn sandbox_validate<'a>(
+        &self,
+        proposals: impl IntoIterator<Item = &'a ProposalV1>,
+        config: &SandboxConfig,
+    ) -> SandboxReport {
+        validate_proposals(proposals, &self.schema.read(), config)
+    }
+}
+
+fn coverage(
+    counts: BTreeMap<&str, usize>,
+    declared: &[String],
+    config: &SandboxConfig,
+) -> TypeCoverage {
+    let mut out = TypeCoverage::default();
+    for (name, count) in counts {
+        out.proposals += count;
+        if declared.iter().any(|d| d == name) {
+            out.known.insert(name.to_string(), count);
+            continue;
+        }
+        let mut candidates: Vec<CandidateMapping> = declared
+            .iter()
+            .map(|d| CandidateMapping {
+                schema_type: d.clone(),
+                similarity: name_similarity(name, d),
+            })
+            .filter(|c| c.similarity >= config.min_similarity)
+            .collect();
+        candidates.sort_by(|a, b| {
+            b.similarity
+                .total_cmp(&a.similarity)
+                .then_with(|| a.schema_type.cmp(&b.schema_type))
+        });
+        candidates.truncate(config.max_candidates);
+        out.novel.push(NovelType {
+            name: name.to_string(),
+            proposals: count,
+            candidates,
+        });
+    }
+    out.novel.sort_by(|a, b| {
+        b.proposals
+            .cmp(&a.proposals)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    out
+}
+
+fn percent(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        100.0
+    } else {
+        100.0 * part as f64 / whole as f64
+    }
+}
+
+/// Lowercase alphanumerics with a plural `s` dropped, so `work_orders`,
+/// `WorkOrder` and `workOrders` agree.
+fn normalize(name: &str) -> Vec<char> {
+    let mut chars: Vec<char> = name
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if chars.len() > 3 && chars.ends_with(&['s']) && !chars.ends_with(&['s', 's']) {
+        chars.pop();
+    }
+    chars
+}
+
+/// Normalized edit similarity in `[0, 1]`.
+fn name_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (normalize(a), normalize(b));
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 0.0;
+    }
+    1.0 - levenshtein(&a, &b) as f64 / max_len as f64
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
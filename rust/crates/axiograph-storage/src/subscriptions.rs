@@ -0,0 +1,363 @@
+//! Change notifications for downstream systems.
+//!
+//! Subscribers register a [`ChangeFilter`] (entity types, relation types,
+//! sources) and receive a [`ChangeEvent`] for every applied change with
+//! matching facts. Events are numbered by their position in the changelog, so
+//! the changelog doubles as a durable event log: a subscriber that records the
+//! cursor of the last event it handled can resume from it after a restart
+//! with [`UnifiedStorage::subscribe_from`].
+//!
+//! Delivery is at-least-once and in order per subscriber. Each `flush` runs a
+//! delivery round; a subscriber that returns an error keeps its cursor and
+//! gets the same event again on the next round (or [`UnifiedStorage::deliver_events`]).
+//! With the `webhooks` feature, [`Webhook`] posts events as JSON to an HTTP
+//! endpoint.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    Change, ChangeId, ChangeSource, ChangeStatus, DeletionTarget, Result, StorableFact,
+    UnifiedStorage,
+};
+
+/// Identifier of a change subscription.
+pub type SubscriptionId = Uuid;
+
+/// Which changes a subscriber wants. Empty sets match everything.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChangeFilter {
+    pub entity_types: BTreeSet<String>,
+    /// Relation types (also matches retractions of them).
+    pub relation_types: BTreeSet<String>,
+    /// Source keys (`llm:<model>`, `file:<ext>`, `api:<client>`, `system`,
+    /// `user`); a bare kind such as `llm` matches every model.
+    pub sources: BTreeSet<String>,
+}
+
+impl ChangeFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entity_type(mut self, entity_type: &str) -> Self {
+        self.entity_types.insert(entity_type.to_string());
+        self
+    }
+
+    pub fn relation_type(mut self, rel_type: &str) -> Self {
+        self.relation_types.insert(rel_type.to_string());
+        self
+    }
+
+    pub fn source(mut self, source: &str) -> Self {
+        self.sources.insert(source.to_string());
+        self
+    }
+
+    fn matches_source(&self, key: &str) -> bool {
+        self.sources.is_empty()
+            || self.sources.iter().any(|s| {
+                key == s
+                    || key
+                        .strip_prefix(s.as_str())
+                        .is_some_and(|rest| rest.starts_with(':'))
+            })
+    }
+
+    fn matches_fact(&self, fact: &StorableFact) -> bool {
+        if self.entity_types.is_empty() && self.relation_types.is_empty() {
+            return true;
+        }
+        match fact {
+            StorableFact::Entity { entity_type, .. } => self.entity_types.contains(entity_type),
+            StorableFact::Relation { rel_type, .. } | StorableFact::Retraction { rel_type, .. } => {
+                self.relation_types.contains(rel_type)
+            }
+            // Stored as entities of these types.
+            StorableFact::TacitKnowledge { .. } => self.entity_types.contains("TacitKnowledge"),
+            StorableFact::Concept { .. } => self.entity_types.contains("Concept"),
+            StorableFact::SafetyGuideline { .. } => self.entity_types.contains("SafetyGuideline"),
+            StorableFact::Deletion { target, .. }
+            | StorableFact::ConfidenceDecay { target, .. }
+            | StorableFact::DecayExemption { target, .. }
+            | StorableFact::Verification { target, .. } => match target {
+                DeletionTarget::Relation { rel_type, .. } => self.relation_types.contains(rel_type),
+                // Entities are targeted by name; the type is not known here.
+                DeletionTarget::Entity { .. } => false,
+            },
+            StorableFact::Constraint { .. } => false,
+        }
+    }
+
+    /// The event for changelog entry `seq`, restricted to matching facts, or
+    /// `None` if the change is not applied or nothing in it matches.
+    pub fn event(&self, seq: u64, change: &Change) -> Option<ChangeEvent> {
+        if !matches!(change.status, ChangeStatus::Applied) {
+            return None;
+        }
+        let source_key = source_key(&change.source);
+        if !self.matches_source(&source_key) {
+            return None;
+        }
+        let facts: Vec<StorableFact> = change
+            .facts
+            .iter()
+            .filter(|f| self.matches_fact(f))
+            .cloned()
+            .collect();
+        if facts.is_empty() {
+            return None;
+        }
+        Some(ChangeEvent {
+            seq,
+            change_id: change.id,
+            applied_at: change.applied_at.unwrap_or(change.timestamp),
+            source: change.source.clone(),
+            source_key,
+            facts,
+        })
+    }
+}
+
+/// Key a change source is filtered by: its calibration key, or `user` for
+/// human edits.
+pub fn source_key(source: &ChangeSource) -> String {
+    source
+        .calibration_key()
+        .unwrap_or_else(|| "user".to_string())
+}
+
+/// An applied change, as seen by one subscriber.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    /// Position in the changelog; resume after this event from `seq + 1`.
+    pub seq: u64,
+    pub change_id: ChangeId,
+    pub applied_at: DateTime<Utc>,
+    pub source: ChangeSource,
+    pub source_key: String,
+    /// The change's facts that match the subscriber's filter.
+    pub facts: Vec<StorableFact>,
+}
+
+/// Receives change events.
+///
+/// Returning an error leaves the event undelivered; it is retried, before any
+/// later event, on the next delivery round. Handlers should be idempotent.
+pub trait ChangeSubscriber: Send + Sync {
+    fn deliver(&self, event: &ChangeEvent) -> Result<()>;
+}
+
+impl<F> ChangeSubscriber for F
+where
+    F: Fn(&ChangeEvent) -> Result<()> + Send + Sync,
+{
+    fn deliver(&self, event: &ChangeEvent) -> Result<()> {
+        self(event)
+    }
+}
+
+pub(crate) struct Subscription {
+    id: SubscriptionId,
+    filter: ChangeFilter,
+    subscriber: Arc<dyn ChangeSubscriber>,
+    /// Next changelog position to deliver.
+    cursor: u64,
+    last_error: Option<String>,
+}
+
+/// Outcome of a delivery round.
+#[derive(Debug, Clone, Default)]
+pub struct DeliveryReport {
+    pub delivered: usize,
+    /// Subscribers whose delivery failed, with the error; retried next round.
+    pub failed: Vec<(SubscriptionId, String)>,
+}
+
+impl UnifiedStorage {
+    /// Subscribe to changes applied from now on.
+    pub fn subscribe(
+        &self,
+        filter: ChangeFilter,
+        subscriber: impl ChangeSubscriber + 'static,
+    ) -> SubscriptionId {
+        let cursor = self.changelog.read().len() as u64;
+        self.subscribe_from(filter, subscriber, cursor)
+    }
+
+    /// Subscribe starting at changelog position `cursor` (`0` replays the
+    /// whole history). Past events are delivered on the next round.
+    pub fn subscribe_from(
+        &self,
+        filter: ChangeFilter,
+        subscriber: impl ChangeSubscriber + 'static,
+        cursor: u64,
+    ) -> SubscriptionId {
+        let id = Uuid::new_v4();
+        self.subscriptions.write().push(Subscription {
+            id,
+            filter,
+            subscriber: Arc::new(subscriber),
+            cursor,
+            last_error: None,
+        });
+        id
+    }
+
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        let mut subs = self.subscriptions.write();
+        let before = subs.len();
+        subs.retain(|s| s.id != id);
+        subs.len() != before
+    }
+
+    /// Next changelog position `id` will receive (persist this to resume).
+    pub fn subscription_cursor(&self, id: SubscriptionId) -> Option<u64> {
+        self.subscriptions
+            .read()
+            .iter()
+            .find(|s| s.id == id)
+            .map(|s| s.cursor)
+    }
+
+    /// The error from `id`'s last failed delivery, if it has not since succeeded.
+    pub fn subscription_error(&self, id: SubscriptionId) -> Option<String> {
+        self.subscriptions
+            .read()
+            .iter()
+            .find(|s| s.id == id)
+            .and_then(|s| s.last_error.clone())
+    }
+
+    /// Events at changelog positions `>= cursor` that match `filter`.
+    pub fn events_since(&self, cursor: u64, filter: &ChangeFilter) -> Vec<ChangeEvent> {
+        self.changelog
+            .read()
+            .iter()
+            .enumerate()
+            .skip(cursor as usize)
+            .filter_map(|(seq, change)| filter.event(seq as u64, change))
+            .collect()
+    }
+
+    /// Deliver outstanding events to every subscriber. Called by `flush`;
+    /// call it directly to retry failed deliveries sooner.
+    pub fn deliver_events(&self) -> DeliveryReport {
+        // One round at a time, so concurrent flushes don't reorder events.
+        let _round = self.delivery.lock();
+        let mut report = DeliveryReport::default();
+        let pending: Vec<(SubscriptionId, ChangeFilter, Arc<dyn ChangeSubscriber>, u64)> = self
+            .subscriptions
+            .read()
+            .iter()
+            .map(|s| (s.id, s.filter.clone(), s.subscriber.clone(), s.cursor))
+            .collect();
+        let end = self.changelog.read().len() as u64;
+
+        for (id, filter, subscriber, cursor) in pending {
+            // Subscribers run without storage locks held, so they may read it.
+            let mut next = cursor;
+            let mut error = None;
+            for event in self.events_since(cursor, &filter) {
+                if event.seq >= end {
+                    break;
+                }
+                match subscriber.deliver(&event) {
+                    Ok(()) => {
+                        report.delivered += 1;
+                        next = event.seq + 1;
+                    }
+                    Err(e) => {
+                        error = Some(format!("{e:#}"));
+                        break;
+                    }
+                }
+            }
+            if error.is_none() {
+                next = next.max(end);
+            }
+
+            if let Some(e) = &error {
+                tracing::warn!(subscription = %id, seq = next, "change delivery failed: {e}");
+                report.failed.push((id, e.clone()));
+            }
+            if let Some(sub) = self.subscriptions.write().iter_mut().find(|s| s.id == id) {
+                sub.cursor = sub.cursor.max(next);
+                sub.last_error = error;
+            }
+        }
+        report
+    }
+}
+
+/// Posts change events as JSON to an HTTP endpoint; any non-2xx response is
+/// a failed delivery.
+#[cfg(feature = "webhooks")]
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    url: String,
+    headers: Vec<(String, String)>,
+    timeout: std::time::Duration,
+}
+
+#[cfg(feature = "webhooks")]
+impl Webhook {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            headers: Vec::new(),
+            timeout: std::time::Duration::from_secs(10),
+        }
+    }
+
+    /// Extra request header (e.g. `Authorization`).
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[cfg(feature = "webhooks")]
+impl ChangeSubscriber for Webhook {
+    fn deliver(&self, event: &ChangeEvent) -> Result<()> {
+        let body = serde_json::to_vec(event)?;
+        let hook = self.clone();
+        let failed = |reason: String| crate::StorageError::Delivery {
+            target: self.url.clone(),
+            reason,
+        };
+        // The blocking client must not run on an async runtime thread, and
+        // `flush` may be called from one.
+        std::thread::spawn(move || -> std::result::Result<(), String> {
+            let client = reqwest::blocking::Client::builder()
+                .timeout(hook.timeout)
+                .build()
+                .map_err(|e| e.to_string())?;
+            let mut request = client
+                .post(&hook.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body);
+            for (name, value) in &hook.headers {
+                request = request.header(name.as_str(), value.as_str());
+            }
+            let status = request.send().map_err(|e| e.to_string())?.status();
+            if !status.is_success() {
+                return Err(format!("webhook returned {status}"));
+            }
+            Ok(())
+        })
+        .join()
+        .map_err(|_| failed("webhook delivery panicked".to_string()))?
+        .map_err(failed)
+    }
+}
@@ -0,0 +1,198 @@
+//! Bitemporal reads over the changelog ("as of" queries).
+//!
+//! Every applied change records its transaction time (`applied_at`) and, if it
+//! is later rolled back, when it stopped being believed (`retracted_at`). That
+//! is enough to answer "what did the graph look like at time `t`?" even after
+//! rollbacks: a change is part of the state at `t` iff it was applied at or
+//! before `t` and not yet retracted.
+//!
+//! Replaying the whole changelog gets slow as it grows, so `flush` also writes
+//! a PathDB snapshot every [`CHANGELOG_SNAPSHOT_INTERVAL`] applied changes
+//! (`<changelog>.snapshots/<index>.axpd`). `pathdb_as_of` starts from the
+//! newest snapshot whose prefix is believed identically at `t`, then replays
+//! the remaining changes.
+//!
+//! Snapshots are replayed changelog state, never a copy of the live PathDB:
+//! the live graph may also hold data loaded from the `.axpd` file, which the
+//! changelog cannot reproduce, so an "as of" read would otherwise depend on
+//! whether a snapshot happened to cover it.
+//!
+//! Notes:
+//! - Legacy changelog entries without `applied_at` fall back to `timestamp`;
+//!   legacy rolled-back entries without `retracted_at` are treated as never
+//!   believed.
+
+use std::path::PathBuf;
+
+use axiograph_pathdb::PathDB;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    replay_changes, trash, Change, ChangeId, ChangeStatus, Result, StorageError, UnifiedStorage,
+};
+
+/// Number of applied changes between PathDB snapshots.
+pub const CHANGELOG_SNAPSHOT_INTERVAL: usize = 64;
+
+/// Point in transaction time to read at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsOf {
+    /// Everything believed at this instant.
+    Time(DateTime<Utc>),
+    /// Everything believed right after this change was applied.
+    Change(ChangeId),
+}
+
+impl From<DateTime<Utc>> for AsOf {
+    fn from(t: DateTime<Utc>) -> Self {
+        AsOf::Time(t)
+    }
+}
+
+impl From<ChangeId> for AsOf {
+    fn from(id: ChangeId) -> Self {
+        AsOf::Change(id)
+    }
+}
+
+impl Change {
+    /// Transaction time at which this change entered the graph.
+    pub fn applied_time(&self) -> DateTime<Utc> {
+        self.applied_at.unwrap_or(self.timestamp)
+    }
+
+    /// Whether this change was part of the believed graph state at `t`.
+    pub fn believed_at(&self, t: DateTime<Utc>) -> bool {
+        if self.applied_time() > t {
+            return false;
+        }
+        match &self.status {
+            ChangeStatus::Applied => true,
+            ChangeStatus::Rolled { .. } => self.retracted_at.is_some_and(|r| r > t),
+            ChangeStatus::Pending | ChangeStatus::Rejected { .. } => false,
+        }
+    }
+}
+
+impl UnifiedStorage {
+    /// Reconstruct the PathDB as it was believed at `at` (a timestamp or change id).
+    pub fn pathdb_as_of(&self, at: impl Into<AsOf>) -> Result<PathDB> {
+        let changelog = self.changelog.read();
+        let t = match at.into() {
+            AsOf::Time(t) => t,
+            AsOf::Change(id) => changelog
+                .iter()
+                .find(|c| c.id == id)
+                .map(Change::applied_time)
+                .ok_or(StorageError::ChangeNotFound(id))?,
+        };
+
+        let (mut pathdb, start) = match self.best_snapshot(&changelog, t)? {
+            Some((index, db)) => (db, index + 1),
+            None => (PathDB::new(), 0),
+        };
+        replay_changes(
+            &mut pathdb,
+            &changelog,
+            start,
+            |c| c.believed_at(t),
+            Some(t),
+            self.config.on_duplicate,
+        );
+        pathdb.build_indexes();
+        Ok(pathdb)
+    }
+
+    /// Directory holding periodic PathDB snapshots.
+    pub fn snapshot_dir(&self) -> PathBuf {
+        self.config.changelog_path.with_extension("snapshots")
+    }
+
+    /// Snapshot the replayed changelog if the last flush crossed a snapshot
+    /// boundary.
+    pub(crate) fn maybe_snapshot(&self, applied_before: usize) -> Result<()> {
+        let (applied_after, taken_at) = {
+            let changelog = self.changelog.read();
+            match changelog.last() {
+                Some(last) => (changelog.len(), last.applied_time()),
+                None => return Ok(()),
+            }
+        };
+        if applied_after / CHANGELOG_SNAPSHOT_INTERVAL
+            == applied_before / CHANGELOG_SNAPSHOT_INTERVAL
+        {
+            return Ok(());
+        }
+
+        let dir = self.snapshot_dir();
+        std::fs::create_dir_all(&dir)?;
+        let bytes = self.pathdb_as_of(taken_at)?.to_bytes()?;
+        let path = dir.join(format!("{:08}.axpd", applied_after - 1));
+        let tmp = path.with_extension("axpd.tmp");
+        std::fs::write(&tmp, bytes)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    /// Changelog indexes of the snapshots on disk, unordered.
+    fn snapshot_indexes(&self) -> Result<Vec<usize>> {
+        let dir = self.snapshot_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(std::fs::read_dir(&dir)?
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != "axpd" {
+                    return None;
+                }
+                path.file_stem()?.to_str()?.parse().ok()
+            })
+            .collect())
+    }
+
+    /// Delete the snapshots covering `changelog[first]`, i.e. every snapshot
+    /// from index `first` on. Older ones stay valid.
+    pub(crate) fn discard_snapshots_from(&self, first: usize) -> Result<()> {
+        let dir = self.snapshot_dir();
+        for index in self.snapshot_indexes()? {
+            if index >= first {
+                std::fs::remove_file(dir.join(format!("{index:08}.axpd")))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Newest snapshot usable for a read at `t`: taken at or before `t`, and every
+    /// change it covers is believed at `t` exactly as it was when it was taken.
+    fn best_snapshot(
+        &self,
+        changelog: &[Change],
+        t: DateTime<Utc>,
+    ) -> Result<Option<(usize, PathDB)>> {
+        let dir = self.snapshot_dir();
+        let mut indexes: Vec<usize> = self
+            .snapshot_indexes()?
+            .into_iter()
+            .filter(|&i| i < changelog.len())
+            .collect();
+        indexes.sort_unstable_by(|a, b| b.cmp(a));
+
+        for index in indexes {
+            let taken_at = changelog[index].applied_time();
+            if taken_at > t {
+                continue;
+            }
+            let consistent = changelog[..=index]
+                .iter()
+                .all(|c| c.believed_at(taken_at) == c.believed_at(t))
+                && trash::snapshot_reflects_deletions(changelog, index, taken_at, t);
+            if !consistent {
+                continue;
+            }
+            let bytes = std::fs::read(dir.join(format!("{index:08}.axpd")))?;
+            return Ok(Some((index, PathDB::from_bytes(&bytes)?)));
+        }
+        Ok(None)
+    }
+}
@@ -17,6 +17,10 @@ fn test_storage() -> (UnifiedStorage, tempfile::TempDir) {
             schema_changes: false,
         },
         max_pending: 100,
+        on_duplicate: OnDuplicate::Skip,
+        trash: TrashPolicy::default(),
+        decay: DecayPolicy::default(),
+        verification: VerificationRule::default(),
     };
     let storage = UnifiedStorage::new(config).unwrap();
     (storage, dir)
@@ -114,6 +118,92 @@ fn test_relation_lands_in_both_formats() {
     assert!(content.contains("Ti6Al4V"), "Should contain target");
 }
 
+#[test]
+fn test_retraction_downgrades_then_deletes_relation() {
+    let (storage, dir) = test_storage();
+    let named = |name: &str, entity_type: &str| StorableFact::Entity {
+        name: name.to_string(),
+        entity_type: entity_type.to_string(),
+        attributes: vec![("name".to_string(), name.to_string())],
+    };
+    let retraction = |confidence: Option<f32>| StorableFact::Retraction {
+        rel_type: "usedWith".to_string(),
+        source: "endmill".to_string(),
+        target: "Ti6Al4V".to_string(),
+        confidence,
+        reason: "user correction".to_string(),
+    };
+    let source = ChangeSource::UserEdit { user_id: None };
+
+    storage
+        .add_facts(
+            vec![
+                named("EndMill", "Tool"),
+                named("Ti6Al4V", "Material"),
+                StorableFact::Relation {
+                    name: None,
+                    rel_type: "usedWith".to_string(),
+                    source: "EndMill".to_string(),
+                    target: "Ti6Al4V".to_string(),
+                    confidence: 0.9,
+                    attributes: vec![],
+                },
+            ],
+            source.clone(),
+        )
+        .unwrap();
+    storage.flush().unwrap();
+
+    storage
+        .add_facts(vec![retraction(Some(0.2))], source.clone())
+        .unwrap();
+    let results = storage.flush().unwrap();
+    assert!(results[0].warnings.is_empty());
+    assert!(results[0].pathdb_ids.is_empty());
+    assert_eq!(
+        storage
+            .pathdb()
+            .read()
+            .relations
+            .get_relation(0)
+            .unwrap()
+            .confidence,
+        0.2
+    );
+
+    storage
+        .add_facts(vec![retraction(None)], source.clone())
+        .unwrap();
+    storage.flush().unwrap();
+    assert!(storage.pathdb().read().follow_one(0, "usedWith").is_empty());
+
+    // Nothing left to retract.
+    storage.add_facts(vec![retraction(None)], source).unwrap();
+    let results = storage.flush().unwrap();
+    assert_eq!(
+        results[0].warnings,
+        vec!["Retraction of usedWith(endmill, Ti6Al4V) matched no relation"]
+    );
+
+    let axi = std::fs::read_to_string(dir.path().join("user_edits.axi")).unwrap();
+    assert!(
+        axi.contains("-- downgraded usedWith(endmill, Ti6Al4V) @confidence(0.2): user correction")
+    );
+    assert!(axi.contains("-- retracted usedWith(endmill, Ti6Al4V): user correction"));
+
+    let report = storage.add_facts_dry_run(
+        &[retraction(Some(1.5))],
+        &ChangeSource::UserEdit { user_id: None },
+    );
+    assert_eq!(
+        report.plan.violations,
+        vec![Violation::ConfidenceOutOfRange {
+            fact: 0,
+            confidence: 1.5
+        }]
+    );
+}
+
 #[test]
 fn test_tacit_knowledge_storage() {
     let (storage, _dir) = test_storage();
@@ -411,6 +501,2073 @@ fn test_concept_and_guideline_storage() {
 
     // Verify in .axi
     let content = std::fs::read_to_string(dir.path().join("user_edits.axi")).unwrap();
-    assert!(content.contains("concept ChipFormation"));
-    assert!(content.contains("guideline CoolantRequired"));
+    assert!(content.contains("Concept = {ChipFormation}"));
+    assert!(content.contains("(concept=ChipFormation, requires=Mechanics)"));
+    assert!(content.contains("SafetyGuideline = {CoolantRequired}"));
+}
+
+#[test]
+fn test_flushed_axi_reparses_canonically() {
+    let (storage, dir) = test_storage();
+
+    let facts = vec![
+        StorableFact::Entity {
+            name: "Titanium".to_string(),
+            entity_type: "Material".to_string(),
+            attributes: vec![
+                ("hardness".to_string(), "36".to_string()),
+                ("alloy".to_string(), "Ti-6Al-4V, grade 5".to_string()),
+            ],
+        },
+        StorableFact::Relation {
+            name: None,
+            rel_type: "usedWith".to_string(),
+            source: "EndMill".to_string(),
+            target: "Titanium".to_string(),
+            confidence: 0.9,
+            attributes: vec![],
+        },
+        StorableFact::Constraint {
+            name: "SpeedLimit".to_string(),
+            condition: "speed <= 60".to_string(),
+            severity: "warning".to_string(),
+            message: Some("keep it slow -- really".to_string()),
+        },
+    ];
+    for _ in 0..2 {
+        storage
+            .add_facts(facts.clone(), ChangeSource::UserEdit { user_id: None })
+            .unwrap();
+        storage.flush().unwrap();
+    }
+
+    let content = std::fs::read_to_string(dir.path().join("user_edits.axi")).unwrap();
+    let module = dsl::schema_v1::parse_schema_v1(&content).unwrap();
+    assert_eq!(module.module_name, "user_edits");
+    assert_eq!(module.instances.len(), 2);
+    assert_eq!(module.theories.len(), 2);
+    assert!(module
+        .instances
+        .iter()
+        .all(|i| i.schema == STORAGE_AXI_SCHEMA));
+
+    // Attributes are sorted by key; punctuation inside values is escaped.
+    assert!(content.contains(
+        "(entity=Titanium, key=alloy, value=Ti-6Al-4V%2C grade 5), \
+         (entity=Titanium, key=hardness, value=36)"
+    ));
+    assert!(content.contains("condition = speed <= 60"));
+    assert_eq!(
+        dsl::printer::canonicalize_axi_v1(&content).unwrap(),
+        dsl::printer::print_axi_v1(&module).unwrap()
+    );
+}
+
+fn add_test_entity(storage: &UnifiedStorage, name: &str) -> ChangeId {
+    let id = storage
+        .add_facts(
+            vec![StorableFact::Entity {
+                name: name.to_string(),
+                entity_type: "Test".to_string(),
+                attributes: vec![("name".to_string(), name.to_string())],
+            }],
+            ChangeSource::System {
+                reason: "as-of test".to_string(),
+            },
+        )
+        .unwrap();
+    storage.flush().unwrap();
+    id
+}
+
+fn entity_count(db: &PathDB) -> usize {
+    db.find_by_type("Test").map_or(0, |ids| ids.len() as usize)
+}
+
+#[test]
+fn test_pathdb_as_of_survives_rollback() {
+    let (storage, _dir) = test_storage();
+
+    let first = add_test_entity(&storage, "A");
+    let second = add_test_entity(&storage, "B");
+    let before_rollback = Utc::now();
+    std::thread::sleep(std::time::Duration::from_millis(2));
+
+    storage.rollback_to(first).unwrap();
+    assert_eq!(entity_count(&storage.pathdb().read()), 1);
+
+    // What we believed before the rollback still includes B...
+    assert_eq!(
+        entity_count(&storage.pathdb_as_of(before_rollback).unwrap()),
+        2
+    );
+    assert_eq!(entity_count(&storage.pathdb_as_of(second).unwrap()), 2);
+    // ...while "now" and "as of A" do not.
+    assert_eq!(entity_count(&storage.pathdb_as_of(Utc::now()).unwrap()), 1);
+    assert_eq!(entity_count(&storage.pathdb_as_of(first).unwrap()), 1);
+
+    let changelog = storage.changelog();
+    assert!(changelog[1].retracted_at.is_some());
+    assert!(storage.pathdb_as_of(Uuid::new_v4()).is_err());
+}
+
+#[test]
+fn test_pathdb_as_of_uses_snapshots() {
+    let (storage, _dir) = test_storage();
+
+    let ids: Vec<ChangeId> = (0..CHANGELOG_SNAPSHOT_INTERVAL + 2)
+        .map(|i| add_test_entity(&storage, &format!("E{i}")))
+        .collect();
+    let snapshot = storage
+        .snapshot_dir()
+        .join(format!("{:08}.axpd", CHANGELOG_SNAPSHOT_INTERVAL - 1));
+    assert!(snapshot.exists());
+
+    let at_last = storage.pathdb_as_of(*ids.last().unwrap()).unwrap();
+    assert_eq!(entity_count(&at_last), CHANGELOG_SNAPSHOT_INTERVAL + 2);
+    let early = storage.pathdb_as_of(ids[2]).unwrap();
+    assert_eq!(entity_count(&early), 3);
+
+    // Rolling back into the snapshot prefix makes it unusable for "now".
+    storage.rollback_to(ids[0]).unwrap();
+    assert_eq!(entity_count(&storage.pathdb_as_of(Utc::now()).unwrap()), 1);
+}
+
+#[test]
+fn test_snapshots_hold_only_replayed_changelog_state() {
+    let (storage, _dir) = test_storage();
+    // Live-only data, as if loaded from an `.axpd` file.
+    storage
+        .pathdb()
+        .write()
+        .add_entity("Test", vec![("name", "preloaded")]);
+
+    let ids: Vec<ChangeId> = (0..CHANGELOG_SNAPSHOT_INTERVAL)
+        .map(|i| add_test_entity(&storage, &format!("E{i}")))
+        .collect();
+    assert_eq!(
+        entity_count(&storage.pathdb().read()),
+        CHANGELOG_SNAPSHOT_INTERVAL + 1
+    );
+
+    let snapshot = storage
+        .snapshot_dir()
+        .join(format!("{:08}.axpd", CHANGELOG_SNAPSHOT_INTERVAL - 1));
+    let snapshot = PathDB::from_bytes(&std::fs::read(snapshot).unwrap()).unwrap();
+    assert_eq!(entity_count(&snapshot), CHANGELOG_SNAPSHOT_INTERVAL);
+    assert!(snapshot
+        .entities_with_attr_fuzzy("name", "preloaded", 0)
+        .is_empty());
+    assert_eq!(
+        entity_count(&storage.pathdb_as_of(*ids.last().unwrap()).unwrap()),
+        CHANGELOG_SNAPSHOT_INTERVAL
+    );
+}
+
+#[test]
+fn test_replayed_relations_link_their_named_endpoints() {
+    let (storage, _dir) = test_storage();
+    add_test_entity(&storage, "A");
+    add_test_entity(&storage, "B");
+    add_test_entity(&storage, "C");
+    let relation = |source: &str, target: &str| StorableFact::Relation {
+        name: None,
+        rel_type: "next".to_string(),
+        source: source.to_string(),
+        target: target.to_string(),
+        confidence: 1.0,
+        attributes: vec![],
+    };
+    let applied = apply_facts(&storage, vec![relation("B", "C"), relation("C", "Nowhere")]);
+    assert_eq!(applied.pathdb_ids.len(), 1);
+    assert!(applied.warnings.iter().any(|w| w.contains("Nowhere")));
+
+    let replayed = storage.pathdb_as_of(Utc::now()).unwrap();
+    let named = |name: &str| {
+        replayed
+            .entities_with_attr_fuzzy("name", name, 0)
+            .min()
+            .unwrap()
+    };
+    assert_eq!(replayed.relations.len(), 1);
+    assert!(replayed.follow_one(named("A"), "next").is_empty());
+    assert_eq!(
+        replayed
+            .follow_one(named("B"), "next")
+            .iter()
+            .collect::<Vec<_>>(),
+        vec![named("C")]
+    );
+}
+
+/// ProtoField `email` tagged PII, a `User` carrying it, and a `Secret`.
+fn seed_pii_graph(storage: &UnifiedStorage) -> (u32, u32, u32) {
+    let pathdb = storage.pathdb();
+    let mut db = pathdb.write();
+    let field = db.add_entity(
+        "ProtoField",
+        vec![("name", "acme.User.email"), ("json_name", "emailAddress")],
+    );
+    let yes = db.add_entity("Bool", vec![("name", "true")]);
+    db.add_relation("proto_field_pii", field, yes, 0.98, vec![]);
+    let user = db.add_entity(
+        "User",
+        vec![("name", "alice"), ("email", "alice@example.com")],
+    );
+    let secret = db.add_entity("Secret", vec![("name", "launch_codes")]);
+    db.add_relation("owns", user, secret, 1.0, vec![]);
+    db.build_indexes();
+    (field, user, secret)
+}
+
+#[test]
+fn test_access_view_denies_types_and_redacts_pii() {
+    let (storage, _dir) = test_storage();
+    let (_, user, secret) = seed_pii_graph(&storage);
+
+    let policy: AccessPolicy = serde_json::from_str(
+        r#"{"roles": {"analyst": {"deny_types": ["Secret"], "redact_pii": true}}}"#,
+    )
+    .unwrap();
+    let analyst = policy.role("analyst").unwrap();
+    assert!(policy.role("admin").is_err());
+
+    storage.with_access_view(analyst, |view| {
+        assert!(view.redacted_attrs().contains("email"));
+        assert!(view.redacted_attrs().contains("emailAddress"));
+
+        let view_user = view.get_entity(user).unwrap();
+        assert_eq!(view_user.attrs["email"], access::REDACTED_VALUE);
+        assert_eq!(view_user.attrs["name"], "alice");
+        assert!(view.get_entity(secret).is_none());
+
+        let owned = view.execute(&axiograph_pathdb::PathQuery::SelectRelated(
+            user,
+            "owns".to_string(),
+        ));
+        assert!(owned.is_empty());
+
+        let exported = view.redacted_pathdb();
+        assert!(exported.find_by_type("Secret").is_none());
+        assert_eq!(exported.relations.len(), 1);
+        let axi = view.export_axi_v1().unwrap();
+        assert!(!axi.contains("alice@example.com"));
+        assert!(!axi.contains("launch_codes"));
+    });
+
+    // The default role sees everything.
+    storage.with_access_view(&RolePolicy::default(), |view| {
+        assert_eq!(
+            view.get_entity(user).unwrap().attrs["email"],
+            "alice@example.com"
+        );
+    });
+}
+
+#[test]
+fn test_access_view_paths_through_denied_entities_find_nothing() {
+    let (storage, _dir) = test_storage();
+    let (_, user, secret) = seed_pii_graph(&storage);
+    let vault = {
+        let pathdb = storage.pathdb();
+        let mut db = pathdb.write();
+        let vault = db.add_entity("Vault", vec![("name", "east")]);
+        db.add_relation("stored_in", secret, vault, 1.0, vec![]);
+        db.build_indexes();
+        vault
+    };
+    let path = axiograph_pathdb::PathQuery::FollowPath {
+        start: user,
+        path: vec!["owns".to_string(), "stored_in".to_string()],
+    };
+    let find = axiograph_pathdb::PathQuery::FindPaths {
+        from: user,
+        to: vault,
+        max_depth: 3,
+    };
+    storage.with_access_view(&RolePolicy::default(), |view| {
+        assert!(view.execute(&path).contains(vault));
+        assert!(view.execute(&find).contains(vault));
+    });
+
+    // `Vault` itself is visible, but the only way there is through a `Secret`.
+    let policy = RolePolicy {
+        deny_types: ["Secret".to_string()].into(),
+        ..RolePolicy::default()
+    };
+    storage.with_access_view(&policy, |view| {
+        assert!(view.get_entity(vault).is_some());
+        assert!(view.execute(&path).is_empty());
+        assert!(view.execute(&find).is_empty());
+    });
+
+    // Denying a relation type hides its edges the same way.
+    let policy = RolePolicy {
+        deny_types: ["owns".to_string()].into(),
+        ..RolePolicy::default()
+    };
+    storage.with_access_view(&policy, |view| {
+        assert!(view.execute(&path).is_empty());
+    });
+}
+
+#[test]
+fn test_access_view_label_follows_redaction() {
+    let (storage, _dir) = test_storage();
+    let (_, user, _) = seed_pii_graph(&storage);
+    let policy = RolePolicy {
+        redact_attrs: ["name".to_string()].into(),
+        ..RolePolicy::default()
+    };
+    storage.with_access_view(&policy, |view| {
+        let view_user = view.get_entity(user).unwrap();
+        assert_eq!(view_user.attrs["name"], access::REDACTED_VALUE);
+        assert_eq!(view_user.label.as_deref(), Some(access::REDACTED_VALUE));
+    });
+    storage.with_access_view(&RolePolicy::default(), |view| {
+        assert_eq!(
+            view.get_entity(user).unwrap().label.as_deref(),
+            Some("alice")
+        );
+    });
+}
+
+#[test]
+fn test_query_audit_records_sampled_reads() {
+    let (storage, dir) = test_storage();
+    add_test_entity(&storage, "A");
+
+    let mut config = audit::QueryAuditConfig::new(dir.path().join("audit/queries.jsonl"));
+    config.sample_rate = 0.5;
+    config.always_audit.insert("auditor".to_string());
+    storage.enable_query_audit(config.clone()).unwrap();
+
+    let query = axiograph_pathdb::PathQuery::SelectByType("Test".to_string());
+    for _ in 0..4 {
+        let result = storage.execute_audited("analyst", &query).unwrap();
+        assert_eq!(result.len(), 1);
+    }
+    storage.execute_audited("auditor", &query).unwrap();
+    storage
+        .audit_axql("auditor", "select ?x where ?x is Test", Some(1))
+        .unwrap();
+
+    let records = storage.query_audit().unwrap().records().unwrap();
+    let analyst: Vec<_> = records
+        .iter()
+        .filter(|r| r.principal == "analyst")
+        .collect();
+    assert_eq!(analyst.len(), 2);
+    assert_eq!(records.len(), 4);
+    assert!(records
+        .iter()
+        .all(|r| r.snapshot == storage.snapshot_version()));
+    assert!(matches!(
+        records.last().unwrap().query,
+        audit::AuditedQuery::Axql { .. }
+    ));
+
+    // Append-only across reopen: sequence numbers keep increasing.
+    let reopened = audit::QueryAuditLog::open(config).unwrap();
+    reopened
+        .record(
+            "auditor",
+            audit::AuditedQuery::PathQuery {
+                query: query.clone(),
+            },
+            "genesis",
+            None,
+        )
+        .unwrap();
+    let all = reopened.records().unwrap();
+    assert_eq!(all.len(), 5);
+    // PathQueries are stored in their JSON encoding, not `Debug` text.
+    let raw = std::fs::read_to_string(reopened.path()).unwrap();
+    assert!(raw
+        .lines()
+        .last()
+        .unwrap()
+        .contains(r#"{"op":"select_by_type","type_name":"Test"}"#));
+    assert_eq!(
+        all.last().unwrap().query,
+        audit::AuditedQuery::PathQuery { query }
+    );
+    assert!(all.windows(2).all(|w| w[0].seq < w[1].seq));
+    let exported = reopened
+        .export_range(Utc::now() - chrono::Duration::hours(1), Utc::now())
+        .unwrap();
+    assert_eq!(exported.len(), 5);
+}
+
+#[test]
+fn test_export_redacted_hashes_values_and_keeps_structure() {
+    let (storage, _dir) = test_storage();
+    let (field, user, secret) = seed_pii_graph(&storage);
+
+    let policy = RedactionPolicy {
+        hash_attrs: ["name".to_string()].into_iter().collect(),
+        hash_pii: true,
+        drop_pii_entities: false,
+        salt: "s3cret".to_string(),
+    };
+    let bytes = storage.export_redacted(&policy).unwrap();
+    let redacted = PathDB::from_bytes(&bytes).unwrap();
+
+    // Structure is intact: same ids, types and edges.
+    assert_eq!(redacted.entities.len(), 4);
+    assert_eq!(redacted.relations.len(), 2);
+    assert!(redacted.follow_one(user, "owns").contains(secret));
+
+    let view = redacted.get_entity(user).unwrap();
+    assert_eq!(view.entity_type, "User");
+    assert_eq!(
+        view.attrs["email"],
+        redaction::salted_hash("s3cret", "alice@example.com")
+    );
+    assert_eq!(
+        view.attrs["name"],
+        redaction::salted_hash("s3cret", "alice")
+    );
+    assert_ne!(
+        redaction::salted_hash("other", "alice"),
+        redaction::salted_hash("s3cret", "alice")
+    );
+
+    // Dropping PII-tagged entities removes them and their edges.
+    let dropped = redaction::redact_pathdb(
+        &storage.pathdb().read(),
+        &RedactionPolicy {
+            drop_pii_entities: true,
+            ..policy
+        },
+    );
+    assert_eq!(dropped.entities.len(), 3);
+    assert_eq!(dropped.relations.len(), 1);
+    assert!(dropped.find_by_type("ProtoField").is_none());
+    assert!(redaction::pii_tagged_entities(&storage.pathdb().read()).contains(field));
+}
+
+#[test]
+fn test_export_redacted_keeps_virtual_types_and_equivalences() {
+    let (storage, _dir) = test_storage();
+    let (field, user, secret) = seed_pii_graph(&storage);
+    {
+        let pathdb = storage.pathdb();
+        let mut db = pathdb.write();
+        db.mark_virtual_type(user, "Person").unwrap();
+        db.add_equivalence(user, secret, "sameAs");
+        db.add_equivalence(field, user, "mentions");
+    }
+    let policy = RedactionPolicy {
+        hash_attrs: ["name".to_string()].into_iter().collect(),
+        salt: "s3cret".to_string(),
+        ..RedactionPolicy::default()
+    };
+
+    let bytes = storage.export_redacted(&policy).unwrap();
+    let redacted = PathDB::from_bytes(&bytes).unwrap();
+    assert_eq!(redacted.virtual_types_of(user), vec!["Person".to_string()]);
+    assert!(redacted.find_by_type("Person").unwrap().contains(user));
+    let mut equivalents: Vec<u32> = redacted
+        .find_equivalent(user)
+        .into_iter()
+        .map(|(other, _)| other)
+        .collect();
+    equivalents.sort();
+    assert_eq!(equivalents, vec![field, secret]);
+
+    // Equivalences to a dropped entity go with it; the rest are renumbered.
+    let dropped = redaction::redact_pathdb(
+        &storage.pathdb().read(),
+        &RedactionPolicy {
+            drop_pii_entities: true,
+            ..policy
+        },
+    );
+    let new_user = dropped.find_by_type("User").unwrap().min().unwrap();
+    let new_secret = dropped.find_by_type("Secret").unwrap().min().unwrap();
+    assert_eq!(
+        dropped.virtual_types_of(new_user),
+        vec!["Person".to_string()]
+    );
+    let equivalents = dropped.find_equivalent(new_user);
+    assert_eq!(equivalents.len(), 1);
+    assert_eq!(equivalents[0].0, new_secret);
+    assert_eq!(
+        dropped.interner.lookup(equivalents[0].1).as_deref(),
+        Some("sameAs")
+    );
+}
+
+#[test]
+fn test_export_redacted_covers_list_lang_and_blob_columns() {
+    let (storage, _dir) = test_storage();
+    let (field, user, secret) = seed_pii_graph(&storage);
+    {
+        let pathdb = storage.pathdb();
+        let mut db = pathdb.write();
+        db.set_list_attr(user, "email", &["a@example.com", "b@example.com"])
+            .unwrap();
+        db.set_list_attr(user, "tags", &["admin", "ops"]).unwrap();
+        db.set_attr_lang(user, "title", "fr", "Directrice").unwrap();
+        db.set_attr_lang(secret, "name", "fr", "codes").unwrap();
+        db.attach_blob(user, "avatar", b"png").unwrap();
+        db.attach_blob(secret, "name_scan", b"scan").unwrap();
+        db.attach_blob(field, "schema", b"proto").unwrap();
+    }
+
+    let policy = RedactionPolicy {
+        hash_attrs: ["name".to_string(), "name_scan".to_string()]
+            .into_iter()
+            .collect(),
+        hash_pii: true,
+        drop_pii_entities: false,
+        salt: "s3cret".to_string(),
+    };
+    let hash = |value: &str| redaction::salted_hash("s3cret", value);
+    let redacted = PathDB::from_bytes(&storage.export_redacted(&policy).unwrap()).unwrap();
+
+    // Hashed keys are hashed in every column; other keys survive as is.
+    assert_eq!(
+        redacted.list_attr(user, "email").unwrap(),
+        vec![hash("a@example.com"), hash("b@example.com")]
+    );
+    assert_eq!(
+        redacted.list_attr(user, "tags").unwrap(),
+        vec!["admin", "ops"]
+    );
+    assert_eq!(
+        redacted.attr_langs(secret, "name"),
+        vec![("fr".to_string(), hash("codes"))]
+    );
+    assert_eq!(
+        redacted.get_attr_lang(user, "title", "fr").as_deref(),
+        Some("Directrice")
+    );
+    // A blob travels with its reference; a hashed reference leaves it behind.
+    assert_eq!(redacted.entity_blob(user, "avatar"), Some(&b"png"[..]));
+    assert!(redacted.entity_blob(secret, "name_scan").is_none());
+    assert!(redacted.missing_blobs().is_empty());
+    assert_eq!(redacted.blobs().len(), 2);
+
+    // Dropped entities take their blobs with them.
+    let dropped = redaction::redact_pathdb(
+        &storage.pathdb().read(),
+        &RedactionPolicy {
+            drop_pii_entities: true,
+            ..policy
+        },
+    );
+    assert_eq!(dropped.blobs().len(), 1);
+}
+
+#[test]
+fn test_structured_errors() {
+    let (storage, dir) = test_storage();
+
+    let missing = Uuid::new_v4();
+    assert!(matches!(
+        storage.rollback_to(missing),
+        Err(StorageError::ChangeNotFound(id)) if id == missing
+    ));
+    assert!(matches!(
+        AccessPolicy::default().role("auditor"),
+        Err(StorageError::UnknownRole(_))
+    ));
+
+    // A corrupt `.axpd` surfaces as a PathDB corruption, not a generic error.
+    let pathdb_path = dir.path().join("corrupt.axpd");
+    std::fs::write(&pathdb_path, b"AXPD\x01\0\0\0\xff").unwrap();
+    let config = StorageConfig {
+        pathdb_path,
+        ..storage.config.clone()
+    };
+    match UnifiedStorage::new(config) {
+        Err(StorageError::PathDb(err)) => assert!(err.is_corrupt_input()),
+        Err(other) => panic!("expected PathDb error, got {other:?}"),
+        Ok(_) => panic!("corrupt snapshot loaded"),
+    }
+}
+
+#[test]
+fn test_dry_run_reports_plan_without_mutating() {
+    let (storage, dir) = test_storage();
+    *storage.schema.write() = AxiSchemaIndex {
+        entity_types: vec!["Material".to_string(), "Tool".to_string()],
+        relation_types: vec!["usedWith".to_string()],
+        constraints: vec![],
+        quality_rules: vec![],
+    };
+
+    let facts = vec![
+        StorableFact::Entity {
+            name: "Ti6Al4V".to_string(),
+            entity_type: "Material".to_string(),
+            attributes: vec![("density".to_string(), "4.43".to_string())],
+        },
+        StorableFact::Entity {
+            name: "Widget".to_string(),
+            entity_type: "Gadget".to_string(),
+            attributes: vec![],
+        },
+        StorableFact::Relation {
+            name: None,
+            rel_type: "cuts".to_string(),
+            source: "Widget".to_string(),
+            target: "Ti6Al4V".to_string(),
+            confidence: 1.5,
+            attributes: vec![],
+        },
+    ];
+    let source = ChangeSource::API {
+        client_id: "ci".to_string(),
+    };
+
+    let report = storage.add_facts_dry_run(&facts, &source);
+    assert_eq!(report.entities().count(), 2);
+    assert_eq!(report.relations().count(), 1);
+    assert_eq!(report.plan.axi_lines.len(), 3);
+    assert_eq!(report.axi_file, Some(dir.path().join("api_additions.axi")));
+    assert_eq!(
+        report.plan.violations,
+        vec![
+            Violation::UnknownEntityType {
+                fact: 1,
+                entity_type: "Gadget".to_string()
+            },
+            Violation::UnknownRelationType {
+                fact: 2,
+                rel_type: "cuts".to_string()
+            },
+            Violation::ConfidenceOutOfRange {
+                fact: 2,
+                confidence: 1.5
+            },
+        ]
+    );
+    assert!(!report.is_clean());
+
+    // Nothing was written anywhere.
+    assert!(storage.pending().is_empty());
+    assert!(storage.changelog().is_empty());
+    assert_eq!(storage.pathdb().read().entities.len(), 0);
+    assert!(!dir.path().join("api_additions.axi").exists());
+
+    // Applying for real produces exactly the planned `.axi` lines. The
+    // relation's endpoints carry no `name` attribute, so it is not written.
+    storage.add_facts(facts, source).unwrap();
+    let applied = storage.flush().unwrap();
+    assert_eq!(applied[0].axi_lines, report.plan.axi_lines);
+    assert_eq!(applied[0].pathdb_ids.len(), report.plan.writes.len() - 1);
+    assert!(applied[0].warnings.iter().any(|w| w.contains("cuts(Widget")));
+}
+
+#[test]
+fn test_calibration_from_review_outcomes() {
+    let (storage, _dir) = test_storage();
+    let source = ChangeSource::LLMExtraction {
+        session_id: Uuid::new_v4(),
+        model: "extractor".to_string(),
+        confidence: 0.5,
+    };
+    let mut ids = Vec::new();
+    for i in 0..3 {
+        ids.push(
+            storage
+                .add_facts(
+                    vec![StorableFact::Relation {
+                        name: None,
+                        rel_type: "usedWith".to_string(),
+                        source: format!("Tool{i}"),
+                        target: "Ti".to_string(),
+                        confidence: 0.95,
+                        attributes: vec![],
+                    }],
+                    source.clone(),
+                )
+                .unwrap(),
+        );
+        storage.flush().unwrap();
+    }
+    // Human edits carry no extraction confidence to calibrate.
+    storage
+        .add_facts(
+            vec![StorableFact::Entity {
+                name: "Ti".to_string(),
+                entity_type: "Material".to_string(),
+                attributes: vec![],
+            }],
+            ChangeSource::UserEdit { user_id: None },
+        )
+        .unwrap();
+    storage.flush().unwrap();
+    // A reviewer rolls back everything after the first extraction.
+    storage.rollback_to(ids[0]).unwrap();
+
+    let outcomes = calibration::review_outcomes(&storage.changelog());
+    assert_eq!(outcomes.len(), 3);
+    assert_eq!(outcomes.iter().filter(|o| o.accepted).count(), 1);
+
+    let model = storage.fit_calibration(1.0);
+    let stats = &model.sources["llm:extractor"];
+    assert_eq!((stats.reviewed, stats.accepted), (3, 1));
+    assert_eq!(stats.bins[9].reviewed, 3);
+    assert!((model.reliability("llm:extractor").unwrap() - 1.0 / 3.0).abs() < 1e-6);
+
+    // (1 accepted + 1 * 0.95 prior) / (3 reviewed + 1)
+    assert!((model.calibrate("llm:extractor", 0.95) - 0.4875).abs() < 1e-6);
+    // Unreviewed bins and unknown sources keep the raw confidence.
+    assert!((model.calibrate("llm:extractor", 0.3) - 0.3).abs() < 1e-6);
+    assert!((model.calibrate("llm:other", 0.95) - 0.95).abs() < 1e-6);
+}
+
+#[test]
+fn test_change_subscriptions_filter_retry_and_replay() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+
+    let (storage, _dir) = test_storage();
+    let llm = ChangeSource::LLMExtraction {
+        session_id: Uuid::new_v4(),
+        model: "extractor".to_string(),
+        confidence: 0.9,
+    };
+    let material = |name: &str| StorableFact::Entity {
+        name: name.to_string(),
+        entity_type: "Material".to_string(),
+        attributes: vec![],
+    };
+    let tool = StorableFact::Entity {
+        name: "Endmill".to_string(),
+        entity_type: "Tool".to_string(),
+        attributes: vec![],
+    };
+
+    // Applied before anyone subscribed: only visible through replay.
+    storage
+        .add_facts(vec![material("Steel")], llm.clone())
+        .unwrap();
+    storage.flush().unwrap();
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let down = Arc::new(AtomicBool::new(true));
+    let (seen_in, down_in) = (seen.clone(), down.clone());
+    let id = storage.subscribe(
+        ChangeFilter::new().entity_type("Material").source("llm"),
+        move |event: &ChangeEvent| {
+            if down_in.load(Ordering::SeqCst) {
+                return Err(StorageError::Delivery {
+                    target: "test".to_string(),
+                    reason: "endpoint down".to_string(),
+                });
+            }
+            seen_in.lock().unwrap().push(event.clone());
+            Ok(())
+        },
+    );
+    assert_eq!(storage.subscription_cursor(id), Some(1));
+
+    storage
+        .add_facts(vec![material("Titanium"), tool.clone()], llm.clone())
+        .unwrap();
+    storage.flush().unwrap();
+    // Wrong source: filtered out.
+    storage
+        .add_facts(
+            vec![material("Inconel")],
+            ChangeSource::UserEdit { user_id: None },
+        )
+        .unwrap();
+    storage.flush().unwrap();
+
+    // Deliveries failed; the cursor stays on the undelivered event.
+    assert!(seen.lock().unwrap().is_empty());
+    assert_eq!(storage.subscription_cursor(id), Some(1));
+    assert!(storage
+        .subscription_error(id)
+        .unwrap()
+        .contains("endpoint down"));
+
+    down.store(false, Ordering::SeqCst);
+    let report = storage.deliver_events();
+    assert_eq!((report.delivered, report.failed.len()), (1, 0));
+    {
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(
+            (seen[0].seq, seen[0].source_key.as_str()),
+            (1, "llm:extractor")
+        );
+        // Only the facts matching the filter are included.
+        assert!(matches!(
+            &seen[0].facts[..],
+            [StorableFact::Entity { name, .. }] if name == "Titanium"
+        ));
+    }
+    assert_eq!(storage.subscription_cursor(id), Some(3));
+    assert!(storage.subscription_error(id).is_none());
+    assert_eq!(storage.deliver_events().delivered, 0);
+
+    // Replay from the start with a cursor, e.g. after a restart.
+    let replayed = Arc::new(Mutex::new(Vec::new()));
+    let replayed_in = replayed.clone();
+    storage.subscribe_from(
+        ChangeFilter::new().entity_type("Material"),
+        move |event: &ChangeEvent| {
+            replayed_in.lock().unwrap().push(event.seq);
+            Ok(())
+        },
+        0,
+    );
+    storage.deliver_events();
+    assert_eq!(*replayed.lock().unwrap(), vec![0, 1, 2]);
+    assert_eq!(
+        storage
+            .events_since(1, &ChangeFilter::new().entity_type("Tool"))
+            .len(),
+        1
+    );
+
+    assert!(storage.unsubscribe(id));
+    assert!(!storage.unsubscribe(id));
+}
+
+#[cfg(feature = "webhooks")]
+#[test]
+fn test_webhook_posts_change_events() {
+    use std::io::{Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hooks/changes", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        while !String::from_utf8_lossy(&request).contains("\"facts\"") {
+            let n = stream.read(&mut buf).unwrap();
+            request.extend_from_slice(&buf[..n]);
+        }
+        stream
+            .write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n")
+            .unwrap();
+        String::from_utf8_lossy(&request).to_string()
+    });
+
+    let (storage, _dir) = test_storage();
+    let id = storage.subscribe(
+        ChangeFilter::new(),
+        Webhook::new(&url).header("x-token", "secret"),
+    );
+    storage
+        .add_facts(
+            vec![StorableFact::Entity {
+                name: "Titanium".to_string(),
+                entity_type: "Material".to_string(),
+                attributes: vec![],
+            }],
+            ChangeSource::API {
+                client_id: "erp".to_string(),
+            },
+        )
+        .unwrap();
+    storage.flush().unwrap();
+
+    let request = server.join().unwrap();
+    assert!(request.starts_with("POST /hooks/changes"));
+    assert!(request.contains("x-token: secret"));
+    assert!(request.contains("\"source_key\":\"api:erp\""));
+    assert_eq!(storage.subscription_cursor(id), Some(1));
+}
+
+fn restore_config(dir: &std::path::Path) -> StorageConfig {
+    StorageConfig {
+        axi_dir: dir.join("axi"),
+        pathdb_path: dir.join("restored.axpd"),
+        changelog_path: dir.join("changelog.json"),
+        watch_files: false,
+        require_review: ReviewPolicy {
+            constraints: false,
+            low_confidence_threshold: None,
+            schema_changes: false,
+        },
+        max_pending: 100,
+        on_duplicate: OnDuplicate::Skip,
+        trash: TrashPolicy::default(),
+        decay: DecayPolicy::default(),
+        verification: VerificationRule::default(),
+    }
+}
+
+#[test]
+fn test_backup_restore_round_trip() {
+    let (storage, _dir) = test_storage();
+    let first = add_test_entity(&storage, "A");
+    add_test_entity(&storage, "B");
+    add_test_entity(&storage, "C");
+    storage.rollback_to(first).unwrap();
+
+    let backup = tempdir().unwrap();
+    let manifest = storage.backup(backup.path().join("b1")).unwrap();
+    assert_eq!(manifest.changes, 3);
+    assert!(manifest
+        .files
+        .iter()
+        .all(|f| f.sha256.starts_with("sha256:")));
+    assert_eq!(verify_backup(backup.path().join("b1")).unwrap(), manifest);
+
+    let target = tempdir().unwrap();
+    let restored =
+        UnifiedStorage::restore(backup.path().join("b1"), restore_config(target.path())).unwrap();
+    assert_eq!(entity_count(&restored.pathdb().read()), 1);
+    let changelog = restored.changelog();
+    assert_eq!(changelog.len(), 3);
+    assert!(changelog[2].retracted_at.is_some());
+    // History survives the round trip, not just the live state.
+    assert_eq!(
+        entity_count(&restored.pathdb_as_of(changelog[2].id).unwrap()),
+        3
+    );
+}
+
+#[test]
+fn test_restore_rejects_tampered_backup() {
+    let (storage, _dir) = test_storage();
+    add_test_entity(&storage, "A");
+    let backup = tempdir().unwrap();
+    let manifest = storage.backup(backup.path()).unwrap();
+
+    let changelog = manifest
+        .files
+        .iter()
+        .find(|f| f.role == BackupFileRole::Changelog)
+        .unwrap();
+    let path = backup.path().join(&changelog.path);
+    let mut bytes = std::fs::read(&path).unwrap();
+    let last = bytes.len() - 2;
+    bytes[last] ^= 0x01;
+    std::fs::write(&path, bytes).unwrap();
+
+    let target = tempdir().unwrap();
+    let config = restore_config(target.path());
+    let Err(err) = UnifiedStorage::restore(backup.path(), config.clone()) else {
+        panic!("tampered backup restored");
+    };
+    assert!(matches!(err, StorageError::BackupIntegrity { .. }), "{err}");
+    // Verification happens before anything is written.
+    assert!(!config.pathdb_path.exists());
+    assert!(!config.changelog_path.exists());
+
+    std::fs::remove_file(backup.path().join(backup::BACKUP_MANIFEST_FILE)).unwrap();
+    assert!(matches!(
+        verify_backup(backup.path()),
+        Err(StorageError::BackupIntegrity { .. })
+    ));
+}
+
+#[test]
+fn test_backup_and_restore_refuse_to_overwrite() {
+    let (storage, dir) = test_storage();
+    add_test_entity(&storage, "A");
+    let backup = tempdir().unwrap();
+    storage.backup(backup.path()).unwrap();
+    assert!(storage.backup(backup.path()).is_err());
+
+    // Restoring over the live storage's files is refused.
+    let mut config = restore_config(dir.path());
+    config.changelog_path = dir.path().join("changelog.json");
+    assert!(matches!(
+        UnifiedStorage::restore(backup.path(), config),
+        Err(StorageError::InvalidConfig(_))
+    ));
+}
+
+fn dedupe_facts(confidence: f32) -> Vec<StorableFact> {
+    let entity = |name: &str, extra: &[(&str, &str)]| StorableFact::Entity {
+        name: name.to_string(),
+        entity_type: "Material".to_string(),
+        attributes: [("name", name)]
+            .iter()
+            .chain(extra)
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+    };
+    vec![
+        entity("Ti6Al4V", &[("external_id", "mat-1"), ("density", "4.43")]),
+        entity("Steel", &[]),
+        StorableFact::Relation {
+            name: None,
+            rel_type: "harderThan".to_string(),
+            source: "Steel".to_string(),
+            target: "Ti6Al4V".to_string(),
+            confidence,
+            attributes: vec![],
+        },
+    ]
+}
+
+fn apply_facts(storage: &UnifiedStorage, facts: Vec<StorableFact>) -> ApplyResult {
+    storage
+        .add_facts(
+            facts,
+            ChangeSource::System {
+                reason: "dedupe test".to_string(),
+            },
+        )
+        .unwrap();
+    storage.flush().unwrap().pop().unwrap()
+}
+
+fn material_count(db: &PathDB) -> usize {
+    db.find_by_type("Material")
+        .map_or(0, |ids| ids.len() as usize)
+}
+
+#[test]
+fn test_reapplying_facts_is_idempotent() {
+    let (storage, _dir) = test_storage();
+    let first = apply_facts(&storage, dedupe_facts(0.8));
+    assert_eq!(first.pathdb_ids.len(), 3);
+    assert!(first.duplicates.is_empty());
+
+    let second = apply_facts(&storage, dedupe_facts(0.8));
+    assert!(second.pathdb_ids.is_empty());
+    assert_eq!(second.duplicates, first.pathdb_ids);
+    {
+        let db = storage.pathdb();
+        let db = db.read();
+        assert_eq!(material_count(&db), 2);
+        assert_eq!(db.relations.len(), 1);
+        // The relation was attached to the named endpoints.
+        let steel = db.find_by_type("Material").unwrap().iter().max().unwrap();
+        assert_eq!(db.follow_one(steel, "harderThan").len(), 1);
+    }
+
+    // Changelog replay dedupes the same way.
+    let replayed = storage.pathdb_as_of(Utc::now()).unwrap();
+    assert_eq!(material_count(&replayed), 2);
+    assert_eq!(replayed.relations.len(), 1);
+}
+
+#[test]
+fn test_dedupe_lookups_use_the_value_index() {
+    let (storage, _dir) = test_storage();
+    let first = apply_facts(&storage, dedupe_facts(0.8));
+    let (ti, steel) = (first.pathdb_ids[0], first.pathdb_ids[1]);
+
+    // `indexed_attr_value` answers only from the index (`None` means the
+    // lookup would fall back to scanning the column), so every PathDB storage
+    // holds must carry it: live, reopened from disk, and replayed.
+    let check = |db: &PathDB| {
+        let lookup = |key: &str, value: &str| {
+            db.entities.indexed_attr_value(
+                db.interner.id_of(key).unwrap(),
+                db.interner.id_of(value).unwrap(),
+            )
+        };
+        assert_eq!(lookup("external_id", "mat-1").and_then(|b| b.min()), Some(ti));
+        assert_eq!(lookup("name", "Steel").and_then(|b| b.min()), Some(steel));
+    };
+    check(&storage.pathdb().read());
+    check(&storage.pathdb_as_of(Utc::now()).unwrap());
+    let reopened = UnifiedStorage::new(storage.config.clone()).unwrap();
+    check(&reopened.pathdb().read());
+
+    // Writes after the index is declared keep it current.
+    let renamed = vec![StorableFact::Entity {
+        name: "Iron".to_string(),
+        entity_type: "Material".to_string(),
+        attributes: vec![("name".to_string(), "Iron".to_string())],
+    }];
+    let iron = apply_facts(&reopened, renamed).pathdb_ids[0];
+    let db = reopened.pathdb();
+    let db = db.read();
+    let name = db.interner.id_of("name").unwrap();
+    let value = db.interner.id_of("Iron").unwrap();
+    assert_eq!(
+        db.entities.indexed_attr_value(name, value).and_then(|b| b.min()),
+        Some(iron)
+    );
+}
+
+#[test]
+fn test_on_duplicate_merge_attrs_and_bump_confidence() {
+    let (storage, _dir) = test_storage();
+    let mut config = storage.config.clone();
+    config.on_duplicate = OnDuplicate::MergeAttrs;
+    let merging = UnifiedStorage::new(config.clone()).unwrap();
+    apply_facts(&merging, dedupe_facts(0.5));
+
+    // Matched by `external_id` even though the name changed.
+    let renamed = vec![StorableFact::Entity {
+        name: "Ti-6Al-4V".to_string(),
+        entity_type: "Material".to_string(),
+        attributes: vec![
+            ("external_id".to_string(), "mat-1".to_string()),
+            ("grade".to_string(), "5".to_string()),
+        ],
+    }];
+    let result = apply_facts(&merging, renamed);
+    assert_eq!(result.duplicates.len(), 1);
+    {
+        let db = merging.pathdb();
+        let db = db.read();
+        let ti = db.get_entity(result.duplicates[0]).unwrap();
+        assert_eq!(ti.attrs.get("grade").map(String::as_str), Some("5"));
+        assert_eq!(ti.attrs.get("density").map(String::as_str), Some("4.43"));
+        assert_eq!(material_count(&db), 2);
+    }
+    drop(merging);
+
+    config.on_duplicate = OnDuplicate::BumpConfidence;
+    let bumping = UnifiedStorage::new(config).unwrap();
+    apply_facts(&bumping, dedupe_facts(0.5));
+    let db = bumping.pathdb();
+    let db = db.read();
+    assert_eq!(db.relations.len(), 1);
+    let confidence = db.relations.get_relation(0).unwrap().confidence;
+    assert!((confidence - 0.75).abs() < 1e-6, "{confidence}");
+}
+
+fn link(storage: &UnifiedStorage, source: &str, target: &str) {
+    storage
+        .add_facts(
+            vec![StorableFact::Relation {
+                name: None,
+                rel_type: "linksTo".to_string(),
+                source: source.to_string(),
+                target: target.to_string(),
+                confidence: 0.9,
+                attributes: vec![],
+            }],
+            ChangeSource::System {
+                reason: "trash test".to_string(),
+            },
+        )
+        .unwrap();
+    storage.flush().unwrap();
+}
+
+fn delete_entity(storage: &UnifiedStorage, name: &str) -> ChangeId {
+    let target = DeletionTarget::Entity {
+        name: name.to_string(),
+    };
+    let id = storage
+        .add_facts(
+            vec![StorableFact::deletion(target, "trash test")],
+            ChangeSource::UserEdit { user_id: None },
+        )
+        .unwrap();
+    storage.flush().unwrap();
+    id
+}
+
+#[test]
+fn test_soft_delete_is_recoverable() {
+    let (storage, _dir) = test_storage();
+    add_test_entity(&storage, "A");
+    add_test_entity(&storage, "B");
+    link(&storage, "A", "B");
+
+    let deletion = delete_entity(&storage, "B");
+    {
+        let db = storage.pathdb();
+        let db = db.read();
+        assert_eq!(entity_count(&db), 1);
+        assert_eq!(db.relations.len(), 0);
+    }
+    let with_deleted = storage.pathdb_including_deleted();
+    assert_eq!(entity_count(&with_deleted), 2);
+    assert_eq!(with_deleted.relations.len(), 1);
+
+    let trash = storage.trash();
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0].change_id, deletion);
+
+    // Re-adding after the deletion is kept.
+    add_test_entity(&storage, "B");
+    assert_eq!(entity_count(&storage.pathdb().read()), 2);
+
+    storage.restore_from_trash(deletion, 0).unwrap();
+    assert!(storage.trash().is_empty());
+    {
+        let db = storage.pathdb();
+        let db = db.read();
+        assert_eq!(entity_count(&db), 2);
+        assert_eq!(db.relations.len(), 1);
+    }
+    assert!(matches!(
+        storage.restore_from_trash(deletion, 0),
+        Err(StorageError::NotRestorable { .. })
+    ));
+}
+
+#[test]
+fn test_purge_trash_scrubs_expired_deletions() {
+    let (storage, _dir) = test_storage();
+    let mut config = storage.config.clone();
+    config.trash.retention_days = 0;
+    drop(storage);
+    let storage = UnifiedStorage::new(config).unwrap();
+
+    add_test_entity(&storage, "A");
+    let b = add_test_entity(&storage, "B");
+    link(&storage, "A", "B");
+    let deletion = delete_entity(&storage, "B");
+    assert!(matches!(
+        storage.restore_from_trash(deletion, 0),
+        Err(StorageError::NotRestorable { .. })
+    ));
+
+    let purged = storage.purge_trash(Utc::now()).unwrap();
+    assert_eq!(purged.len(), 1);
+    assert!(storage.trash().is_empty());
+    let target = &purged[0].target;
+    assert!(!storage
+        .changelog()
+        .iter()
+        .flat_map(|c| &c.facts)
+        .any(|f| target.covers(f)));
+
+    // Gone from every view, history included.
+    assert_eq!(entity_count(&storage.pathdb().read()), 1);
+    assert_eq!(entity_count(&storage.pathdb_including_deleted()), 1);
+    assert_eq!(entity_count(&storage.pathdb_as_of(b).unwrap()), 1);
+}
+
+#[test]
+fn test_hard_delete_mode_purges_on_flush() {
+    let (storage, _dir) = test_storage();
+    let mut config = storage.config.clone();
+    config.trash.soft_delete = false;
+    drop(storage);
+    let storage = UnifiedStorage::new(config).unwrap();
+
+    add_test_entity(&storage, "A");
+    delete_entity(&storage, "A");
+    assert!(storage.trash().is_empty());
+    assert_eq!(entity_count(&storage.pathdb_including_deleted()), 0);
+    assert_eq!(storage.changelog()[0].facts.len(), 0);
+}
+
+#[test]
+fn test_deletions_keep_pathdb_declarations() {
+    use axiograph_pathdb::Multiplicity;
+
+    let (storage, _dir) = test_storage();
+    add_test_entity(&storage, "A");
+    add_test_entity(&storage, "B");
+    link(&storage, "A", "B");
+    let token = {
+        let db = storage.pathdb();
+        let mut db = db.write();
+        db.declare_composite_index("Test", "name");
+        db.declare_multiplicity("linksTo", Multiplicity::MANY_TO_ONE);
+        db.register_inverse("linksTo", "linkedFrom").unwrap();
+        db.db_token()
+    };
+
+    // A relation deletion removes the edge in place.
+    storage
+        .add_facts(
+            vec![StorableFact::deletion(relation_target("linksTo"), "drop edge")],
+            ChangeSource::UserEdit { user_id: None },
+        )
+        .unwrap();
+    storage.flush().unwrap();
+    {
+        let db = storage.pathdb();
+        let db = db.read();
+        assert_eq!(db.relations.len(), 0);
+        assert_eq!(db.db_token(), token);
+    }
+
+    // An entity deletion replays the changelog into a fresh PathDB that
+    // keeps what was declared on the live one.
+    delete_entity(&storage, "B");
+    let db = storage.pathdb();
+    let db = db.read();
+    assert_ne!(db.db_token(), token);
+    assert_eq!(entity_count(&db), 1);
+    assert!(db.has_composite_index("Test", "name"));
+    assert_eq!(db.multiplicity("linksTo"), Some(Multiplicity::MANY_TO_ONE));
+    assert_eq!(db.inverse_of("linksTo").as_deref(), Some("linkedFrom"));
+}
+
+#[test]
+fn test_purge_discards_only_snapshots_holding_purged_facts() {
+    let (storage, _dir) = test_storage();
+    let mut config = storage.config.clone();
+    config.trash.retention_days = 0;
+    drop(storage);
+    let storage = UnifiedStorage::new(config).unwrap();
+
+    for i in 0..CHANGELOG_SNAPSHOT_INTERVAL {
+        add_test_entity(&storage, &format!("E{i}"));
+    }
+    let snapshot = storage
+        .snapshot_dir()
+        .join(format!("{:08}.axpd", CHANGELOG_SNAPSHOT_INTERVAL - 1));
+    assert!(snapshot.exists());
+
+    // Facts newer than the snapshot: it stays.
+    add_test_entity(&storage, "Z");
+    delete_entity(&storage, "Z");
+    assert_eq!(storage.purge_trash(Utc::now()).unwrap().len(), 1);
+    assert!(snapshot.exists());
+
+    // Facts inside it: it goes.
+    delete_entity(&storage, "E0");
+    assert_eq!(storage.purge_trash(Utc::now()).unwrap().len(), 1);
+    assert!(!snapshot.exists());
+    assert_eq!(
+        entity_count(&storage.pathdb().read()),
+        CHANGELOG_SNAPSHOT_INTERVAL - 1
+    );
+}
+
+fn llm_source() -> ChangeSource {
+    ChangeSource::LLMExtraction {
+        session_id: uuid::Uuid::new_v4(),
+        model: "test-model".to_string(),
+        confidence: 0.8,
+    }
+}
+
+fn relation_fact(rel_type: &str, confidence: f32) -> StorableFact {
+    StorableFact::Relation {
+        name: None,
+        rel_type: rel_type.to_string(),
+        source: "A".to_string(),
+        target: "B".to_string(),
+        confidence,
+        attributes: vec![],
+    }
+}
+
+fn relation_target(rel_type: &str) -> DeletionTarget {
+    DeletionTarget::Relation {
+        rel_type: rel_type.to_string(),
+        source: "A".to_string(),
+        target: "B".to_string(),
+    }
+}
+
+#[test]
+fn test_confidence_decay_per_source_with_exemptions() {
+    let (storage, _dir) = test_storage();
+    let mut config = storage.config.clone();
+    config.decay.half_life_days.insert("llm".to_string(), 30.0);
+    drop(storage);
+    let storage = UnifiedStorage::new(config).unwrap();
+
+    add_test_entity(&storage, "A");
+    add_test_entity(&storage, "B");
+    let tacit = StorableFact::TacitKnowledge {
+        name: "coolant_rule".to_string(),
+        rule: "titanium -> flood coolant".to_string(),
+        confidence: 0.8,
+        domain: "machining".to_string(),
+        source: "conversation".to_string(),
+    };
+    storage
+        .add_facts(vec![relation_fact("linksTo", 0.8), tacit], llm_source())
+        .unwrap();
+    storage
+        .add_facts(
+            vec![relation_fact("usesTool", 0.9)],
+            ChangeSource::UserEdit { user_id: None },
+        )
+        .unwrap();
+    storage.flush().unwrap();
+
+    let tacit_target = DeletionTarget::Entity {
+        name: "coolant_rule".to_string(),
+    };
+    let confidence = |db: &PathDB, target: &DeletionTarget| {
+        decay::current_confidence(db, target).unwrap()
+    };
+    assert_eq!(confidence(&storage.pathdb().read(), &tacit_target), 0.8);
+
+    // One half-life later LLM facts are at half strength; human edits are not.
+    let report = storage
+        .decay_confidences(Utc::now() + chrono::Duration::days(30))
+        .unwrap();
+    assert_eq!(report.decayed.len(), 2);
+    assert_eq!(report.exempt, 1);
+    assert!(report.change_id.is_some());
+    {
+        let db = storage.pathdb();
+        let db = db.read();
+        assert!((confidence(&db, &relation_target("linksTo")) - 0.4).abs() < 0.01);
+        assert!((confidence(&db, &tacit_target) - 0.4).abs() < 0.01);
+        assert_eq!(confidence(&db, &relation_target("usesTool")), 0.9);
+    }
+    // Recorded in the changelog, so replay agrees.
+    let replayed = storage.pathdb_including_deleted();
+    assert!((confidence(&replayed, &relation_target("linksTo")) - 0.4).abs() < 0.01);
+
+    // Recomputed, not compounded.
+    let again = storage
+        .decay_confidences(Utc::now() + chrono::Duration::days(30))
+        .unwrap();
+    assert!(again.decayed.is_empty() && again.change_id.is_none());
+
+    storage
+        .exempt_from_decay(tacit_target.clone(), "verified by reviewer", None)
+        .unwrap();
+    let later = storage
+        .decay_confidences(Utc::now() + chrono::Duration::days(60))
+        .unwrap();
+    assert_eq!(later.decayed.len(), 1);
+    assert_eq!(later.decayed[0].target, relation_target("linksTo"));
+    {
+        let db = storage.pathdb();
+        let db = db.read();
+        assert!((confidence(&db, &relation_target("linksTo")) - 0.2).abs() < 0.01);
+        assert!((confidence(&db, &tacit_target) - 0.4).abs() < 0.01);
+    }
+
+    // Reaffirming restarts the clock.
+    storage
+        .add_facts(vec![relation_fact("linksTo", 0.8)], llm_source())
+        .unwrap();
+    storage.flush().unwrap();
+    let restored = storage.decay_confidences(Utc::now()).unwrap();
+    assert_eq!(restored.decayed.len(), 1);
+    assert!((confidence(&storage.pathdb().read(), &relation_target("linksTo")) - 0.8).abs() < 0.01);
+}
+
+#[test]
+fn test_verify_fact_bumps_confidence_and_stops_decay() {
+    let (storage, _dir) = test_storage();
+    let mut config = storage.config.clone();
+    config.decay.half_life_days.insert("llm".to_string(), 30.0);
+    config.verification = VerificationRule::AtLeast { confidence: 0.95 };
+    drop(storage);
+    let storage = UnifiedStorage::new(config).unwrap();
+
+    add_test_entity(&storage, "A");
+    add_test_entity(&storage, "B");
+    storage
+        .add_facts(vec![relation_fact("linksTo", 0.6)], llm_source())
+        .unwrap();
+    storage.flush().unwrap();
+
+    let record = storage
+        .verify_fact(relation_target("linksTo"), "ana", Some("checked the drawing"))
+        .unwrap();
+    assert_eq!((record.from, record.to), (Some(0.6), 0.95));
+    let change = storage.changelog().pop().unwrap();
+    assert_eq!(change.id, record.change_id);
+    assert!(matches!(
+        &change.source,
+        ChangeSource::UserEdit { user_id: Some(id) } if id == "ana"
+    ));
+    let confidence = |db: &PathDB| decay::current_confidence(db, &relation_target("linksTo"));
+    assert_eq!(confidence(&storage.pathdb().read()), Some(0.95));
+    assert_eq!(confidence(&storage.pathdb_including_deleted()), Some(0.95));
+
+    let report = storage
+        .decay_confidences(Utc::now() + chrono::Duration::days(90))
+        .unwrap();
+    assert!(report.decayed.is_empty());
+    assert_eq!(report.exempt, 1);
+
+    assert!(matches!(
+        storage.verify_fact(relation_target("usesTool"), "ana", None),
+        Err(StorageError::FactNotFound(_))
+    ));
+}
+
+fn entity_line(id: &str, name: &str, confidence: f64) -> String {
+    serde_json::json!({
+        "kind": "Entity",
+        "proposal_id": format!("p-{id}"),
+        "confidence": confidence,
+        "evidence": [],
+        "public_rationale": "",
+        "entity_id": id,
+        "entity_type": "Material",
+        "name": name,
+        "attributes": { "name": "shadowed", "grade": "5" },
+    })
+    .to_string()
+}
+
+fn relation_line(id: &str, source: &str, target: &str) -> String {
+    serde_json::json!({
+        "kind": "Relation",
+        "proposal_id": format!("p-{id}"),
+        "confidence": 0.7,
+        "evidence": [],
+        "public_rationale": "",
+        "relation_id": id,
+        "rel_type": "alloyOf",
+        "source": source,
+        "target": target,
+    })
+    .to_string()
+}
+
+#[test]
+fn test_proposal_stream_batches_and_rejects_lines() {
+    let (storage, _dir) = test_storage();
+    let input = [
+        entity_line("e1", "Ti-6Al-4V", 0.9),
+        String::new(),
+        entity_line("e2", "Titanium", 0.8),
+        "{not json".to_string(),
+        entity_line("e3", "Bogus", 1.5),
+        relation_line("r1", "e1", "e2"),
+        relation_line("r2", "e1", "missing"),
+    ]
+    .join("\n");
+    let config = ProposalStreamConfig {
+        batch_size: 2,
+        ..Default::default()
+    };
+    let mut seen = Vec::new();
+    let report = storage
+        .ingest_proposals_stream_with(input.as_bytes(), &config, |b| seen.push(b.change_id))
+        .unwrap();
+
+    assert_eq!((report.lines, report.accepted, report.rejected), (6, 3, 3));
+    assert_eq!(report.batches.len(), 2);
+    assert_eq!(
+        (report.batches[0].first_line, report.batches[0].last_line),
+        (1, 3)
+    );
+    assert_eq!(
+        (report.batches[1].entities, report.batches[1].relations),
+        (0, 1)
+    );
+    assert_eq!(
+        report.errors.iter().map(|e| e.line).collect::<Vec<_>>(),
+        vec![4, 5, 7]
+    );
+    assert_eq!(report.errors[1].proposal_id.as_deref(), Some("p-e3"));
+    // Screened like `validate_proposals_ndjson`: the field path is reported.
+    assert!(report.errors[1].message.starts_with("line 5: confidence"));
+    assert!(report.errors[2].message.contains("unknown endpoint"));
+
+    // One applied change per batch, reported through the callback.
+    let changelog = storage.changelog();
+    assert_eq!(changelog.iter().map(|c| c.id).collect::<Vec<_>>(), seen);
+    assert!(changelog
+        .iter()
+        .all(|c| matches!(c.status, ChangeStatus::Applied)));
+
+    let db = storage.pathdb();
+    let db = db.read();
+    let alloy = db.find_by_type("Material").unwrap().iter().find(|&id| {
+        db.get_entity(id)
+            .unwrap()
+            .attrs
+            .get("name")
+            .map(String::as_str)
+            == Some("Ti-6Al-4V")
+    });
+    let alloy = db.get_entity(alloy.unwrap()).unwrap();
+    assert_eq!(alloy.attrs["external_id"], "e1");
+    assert_eq!(alloy.attrs["attr_name"], "shadowed");
+    assert_eq!(alloy.attrs["grade"], "5");
+    assert_eq!(db.follow_one(alloy.id, "alloyOf").len(), 1);
+    drop(db);
+
+    // Later streams resolve endpoints against entities already stored.
+    let report = storage
+        .ingest_proposals_stream(relation_line("r3", "e2", "e1").as_bytes())
+        .unwrap();
+    assert_eq!((report.accepted, report.batches.len()), (1, 1));
+    assert_eq!(report.batches[0].inserted, 1);
+}
+
+fn proposal(line: String) -> axiograph_ingest_docs::ProposalV1 {
+    serde_json::from_str(&line).unwrap()
+}
+
+#[tokio::test]
+async fn test_ingest_bridge_bounds_queue_and_drains_on_shutdown() {
+    let (storage, _dir) = test_storage();
+    let storage = Arc::new(storage);
+    let config = IngestBridgeConfig {
+        capacity: 2,
+        batch_size: 2,
+        max_batch_delay_ms: 60_000,
+        ..Default::default()
+    };
+    let bridge = IngestBridge::spawn(storage.clone(), config);
+
+    // The writer has not run yet (single-threaded runtime): the bridge is full.
+    let sender = bridge.sender();
+    sender
+        .try_send(proposal(entity_line("e1", "Ti-6Al-4V", 0.9)))
+        .unwrap();
+    sender
+        .try_send(proposal(entity_line("e2", "Titanium", 0.8)))
+        .unwrap();
+    assert_eq!(sender.queued(), 2);
+    assert!(sender
+        .try_send(proposal(entity_line("e3", "Vanadium", 0.8)))
+        .is_err());
+
+    // Producers wait for room instead of buffering.
+    let producer = tokio::spawn(async move {
+        sender
+            .send(proposal(entity_line("e3", "Vanadium", 0.8)))
+            .await
+            .unwrap();
+        sender
+            .send(proposal(relation_line("r1", "e1", "e2")))
+            .await
+            .unwrap();
+        sender
+            .send(proposal(relation_line("r2", "e1", "missing")))
+            .await
+            .unwrap();
+        sender
+            .send(proposal(entity_line("e4", "Aluminium", 0.9)))
+            .await
+            .unwrap();
+    });
+    producer.await.unwrap();
+
+    // The last batch is partial and its delay far away: shutdown applies it.
+    let report = bridge.shutdown().await.unwrap();
+    assert_eq!((report.lines, report.accepted, report.rejected), (6, 5, 1));
+    assert_eq!(report.batches.len(), 3);
+    assert_eq!(
+        (report.batches[1].entities, report.batches[1].relations),
+        (1, 1)
+    );
+    assert_eq!(
+        (report.batches[2].first_line, report.batches[2].entities),
+        (6, 1)
+    );
+    assert_eq!(report.errors[0].line, 5);
+    assert_eq!(storage.changelog().len(), 3);
+    assert_eq!(
+        storage
+            .pathdb()
+            .read()
+            .find_by_type("Material")
+            .map_or(0, |ids| ids.len()),
+        4
+    );
+
+    // Persisted as applied.
+    let reopened = UnifiedStorage::new(storage.config.clone()).unwrap();
+    assert_eq!(reopened.changelog().len(), 3);
+}
+
+#[tokio::test]
+async fn test_ingest_bridge_applies_partial_batches_after_delay() {
+    let (storage, _dir) = test_storage();
+    let storage = Arc::new(storage);
+    let config = IngestBridgeConfig {
+        batch_size: 100,
+        max_batch_delay_ms: 10,
+        ..Default::default()
+    };
+    let bridge = IngestBridge::spawn(storage.clone(), config);
+    bridge
+        .send(proposal(entity_line("e1", "Ti-6Al-4V", 0.9)))
+        .await
+        .unwrap();
+    for _ in 0..100 {
+        if !storage.changelog().is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert_eq!(storage.changelog().len(), 1);
+
+    let report = bridge.shutdown().await.unwrap();
+    assert_eq!((report.accepted, report.batches.len()), (1, 1));
+}
+
+#[test]
+fn test_proposal_converter_resolves_endpoints_and_keeps_evidence() {
+    let (storage, _dir) = test_storage();
+    let proposal = |value: serde_json::Value| -> axiograph_ingest_docs::ProposalV1 {
+        serde_json::from_value(value).unwrap()
+    };
+    let mut stored = serde_json::json!({
+        "kind": "Entity",
+        "proposal_id": "p-steel",
+        "confidence": 0.9,
+        "evidence": [{ "chunk_id": "doc1#3", "locator": "steel.md" }],
+        "public_rationale": "named in the datasheet",
+        "metadata": { "model": "extractor-v2" },
+        "entity_id": "steel",
+        "entity_type": "Material",
+        "name": "Steel",
+        "attributes": { "proposal_id": "spoofed" },
+    });
+    let mut converter = ProposalConverter::new();
+    let db = PathDB::new();
+    let converted = converter.convert(&proposal(stored.clone()), &db).unwrap();
+    let StorableFact::Entity { attributes, .. } = &converted.fact else {
+        panic!("expected an entity fact");
+    };
+    let attr = |key: &str| {
+        attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    };
+    assert_eq!(attr("external_id"), Some("steel"));
+    assert_eq!(attr("proposal_id"), Some("p-steel"));
+    assert_eq!(attr("attr_proposal_id"), Some("spoofed"));
+    assert_eq!(attr("public_rationale"), Some("named in the datasheet"));
+    assert_eq!(attr("meta_model"), Some("extractor-v2"));
+    assert_eq!(attr("evidence_0_chunk_id"), Some("doc1#3"));
+    assert_eq!(attr("evidence_0_locator"), Some("steel.md"));
+    storage
+        .add_facts(vec![converted.fact], llm_source())
+        .unwrap();
+    storage.flush().unwrap();
+
+    // A fresh converter resolves `steel` through PathDB, `bolt` through the
+    // batch.
+    let pathdb = storage.pathdb();
+    let db = pathdb.read();
+    let mut converter = ProposalConverter::new();
+    stored["proposal_id"] = "p-bolt".into();
+    stored["entity_id"] = "bolt".into();
+    stored["entity_type"] = "Part".into();
+    stored["name"] = "M6 bolt".into();
+    converter.convert(&proposal(stored), &db).unwrap();
+    let relation = |source: &str, target: &str, confidence: f64| {
+        proposal(serde_json::json!({
+            "kind": "Relation",
+            "proposal_id": format!("p-{source}-{target}"),
+            "confidence": confidence,
+            "evidence": [{ "chunk_id": "doc1#4", "span_id": "table 2" }],
+            "public_rationale": "",
+            "relation_id": format!("{source}-{target}"),
+            "rel_type": "madeOf",
+            "source": source,
+            "target": target,
+        }))
+    };
+    let (converted, errors) = converter.convert_all(
+        &[
+            relation("bolt", "steel", 0.8),
+            relation("bolt", "brass", 0.8),
+            relation("bolt", "steel", 1.2),
+        ],
+        &db,
+    );
+    assert_eq!(converted.len(), 1);
+    let (source, target) = converted[0].endpoints.clone().unwrap();
+    assert_eq!((source.name.as_str(), source.pathdb_id), ("M6 bolt", None));
+    assert_eq!(target.name, "Steel");
+    assert!(target.pathdb_id.is_some());
+    let StorableFact::Relation {
+        name,
+        source,
+        target,
+        attributes,
+        ..
+    } = &converted[0].fact
+    else {
+        panic!("expected a relation fact");
+    };
+    assert_eq!(
+        (name.as_deref(), source.as_str(), target.as_str()),
+        (Some("bolt-steel"), "M6 bolt", "Steel")
+    );
+    assert!(attributes.contains(&("evidence_0_span_id".to_string(), "table 2".to_string())));
+    assert!(attributes.contains(&("external_id".to_string(), "bolt-steel".to_string())));
+
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        &errors[0],
+        StorageError::InvalidProposal { proposal_id, reason }
+            if proposal_id == "p-bolt-brass" && reason.contains("unknown endpoint")
+    ));
+    assert!(errors[1].to_string().contains("outside [0, 1]"));
+}
+
+#[test]
+fn test_sandbox_reports_novel_types_candidates_and_coverage() {
+    let (storage, _dir) = test_storage();
+    *storage.schema.write() = AxiSchemaIndex {
+        entity_types: vec!["Material".to_string(), "WorkOrder".to_string()],
+        relation_types: vec!["usedWith".to_string()],
+        constraints: vec![],
+        quality_rules: vec![],
+    };
+    let proposal = |kind: &str, ty: &str| -> axiograph_ingest_docs::ProposalV1 {
+        let mut value = serde_json::json!({
+            "kind": kind,
+            "proposal_id": "p",
+            "confidence": 0.5,
+            "evidence": [],
+            "public_rationale": "",
+        });
+        let fields = if kind == "Entity" {
+            serde_json::json!({ "entity_id": "e", "entity_type": ty, "name": "n" })
+        } else {
+            serde_json::json!({ "relation_id": "r", "rel_type": ty, "source": "a", "target": "b" })
+        };
+        value
+            .as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        serde_json::from_value(value).unwrap()
+    };
+    let proposals = vec![
+        proposal("Entity", "Material"),
+        proposal("Entity", "Material"),
+        proposal("Entity", "work_orders"),
+        proposal("Entity", "work_orders"),
+        proposal("Entity", "Supplier"),
+        proposal("Relation", "usedWith"),
+        proposal("Relation", "suppliedBy"),
+    ];
+
+    let report = storage.sandbox_validate(&proposals, &SandboxConfig::default());
+    let entities = &report.entity_types;
+    assert_eq!((entities.proposals, entities.known_proposals()), (5, 2));
+    assert_eq!(entities.coverage_percent(), 40.0);
+    assert_eq!(
+        entities
+            .novel
+            .iter()
+            .map(|n| n.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["work_orders", "Supplier"]
+    );
+    assert_eq!(entities.novel[0].candidates[0].schema_type, "WorkOrder");
+    assert_eq!(entities.novel[0].candidates[0].similarity, 1.0);
+    assert!(entities.novel[1].candidates.is_empty());
+    assert_eq!(report.relation_types.coverage_percent(), 50.0);
+    assert!((report.coverage_percent() - 300.0 / 7.0).abs() < 1e-9);
+    assert!(!report.is_fully_covered());
+
+    // An empty schema index declares nothing.
+    let bare = sandbox::validate_proposals(
+        &proposals,
+        &AxiSchemaIndex::default(),
+        &SandboxConfig::default(),
+    );
+    assert_eq!(bare.coverage_percent(), 0.0);
+    assert!(
+        sandbox::validate_proposals([], &AxiSchemaIndex::default(), &SandboxConfig::default())
+            .is_fully_covered()
+    );
+}
+
+#[test]
+fn test_proofs_persist_by_query_id_and_are_collected() {
+    let (storage, dir) = test_storage();
+    add_test_entity(&storage, "A");
+    add_test_entity(&storage, "B");
+    let query = axiograph_pathdb::PathQuery::SelectByType("Test".to_string());
+    let ctx = axiograph_pathdb::QueryContext::default();
+    assert!(storage.execute_with_proof("analyst", &query, &ctx).is_err());
+
+    let mut config = proof_store::ProofStoreConfig::new(dir.path().join("proofs"));
+    config.retention.max_proofs = Some(2);
+    storage.enable_proof_store(config).unwrap();
+
+    let first = storage.execute_with_proof("analyst", &query, &ctx).unwrap();
+    assert_eq!(first.result.len(), 2);
+    assert!(!first.journal.is_empty());
+    assert_eq!(first.snapshot, storage.snapshot_version());
+    let stored = storage.proof(first.query_id).unwrap().unwrap();
+    assert_eq!(stored.result, first.result);
+    assert_eq!(stored.journal, first.journal);
+    assert_eq!(stored.principal, "analyst");
+
+    let second = storage.execute_with_proof("analyst", &query, &ctx).unwrap();
+    let third = storage.execute_with_proof("analyst", &query, &ctx).unwrap();
+
+    // Count policy: only the two newest survive.
+    let now = Utc::now();
+    assert_eq!(storage.gc_proofs(now).unwrap(), vec![first.query_id]);
+    assert!(storage.proof(first.query_id).unwrap().is_none());
+    assert!(storage.proof(second.query_id).unwrap().is_some());
+
+    // Age policy: everything is gone after the retention window.
+    let later = now + chrono::Duration::days(31);
+    assert_eq!(
+        storage.gc_proofs(later).unwrap(),
+        vec![second.query_id, third.query_id]
+    );
+    assert!(storage.proof_store().unwrap().list().unwrap().is_empty());
+}
+
+#[test]
+fn test_guardrail_simulation_replays_changelog() {
+    use axiograph_pathdb::{GuardrailRule, Severity};
+    let (storage, _dir) = test_storage();
+    let entity = |name: &str, entity_type: &str| StorableFact::Entity {
+        name: name.to_string(),
+        entity_type: entity_type.to_string(),
+        attributes: vec![("name".to_string(), name.to_string())],
+    };
+    let relation = |rel_type: &str, source: &str, target: &str| StorableFact::Relation {
+        name: None,
+        rel_type: rel_type.to_string(),
+        source: source.to_string(),
+        target: target.to_string(),
+        confidence: 0.9,
+        attributes: vec![],
+    };
+    let user = ChangeSource::UserEdit { user_id: None };
+    let system = ChangeSource::System {
+        reason: "import".to_string(),
+    };
+    storage
+        .add_facts(vec![entity("Milling", "MachiningOperation")], user.clone())
+        .unwrap();
+    storage
+        .add_facts(
+            vec![
+                entity("Ti-6Al-4V", "Material"),
+                relation("hasMaterial", "Milling", "Ti-6Al-4V"),
+            ],
+            user,
+        )
+        .unwrap();
+    storage
+        .add_facts(
+            vec![
+                entity("Turning", "MachiningOperation"),
+                entity("Steel", "Material"),
+                entity("Fast", "Speed"),
+                relation("hasMaterial", "Turning", "Steel"),
+                relation("hasHighSpeed", "Turning", "Fast"),
+            ],
+            system,
+        )
+        .unwrap();
+    storage.flush().unwrap();
+
+    let rule = |id: &str, severity, required: &[&str], forbidden: &[&str]| GuardrailRule {
+        id: id.to_string(),
+        name: id.to_string(),
+        description: id.to_string(),
+        severity,
+        domain: "machining".to_string(),
+        applicable_types: vec!["MachiningOperation".to_string()],
+        violation_pattern: None,
+        required_relations: required.iter().map(|s| s.to_string()).collect(),
+        forbidden_relations: forbidden.iter().map(|s| s.to_string()).collect(),
+        min_confidence: 0.0,
+    };
+    let rules = vec![
+        rule("needs-material", Severity::Blocking, &["hasMaterial"], &[]),
+        rule("no-high-speed", Severity::Warning, &[], &["hasHighSpeed"]),
+    ];
+    let entities_before = storage.pathdb().read().entities.len();
+    let report = storage.simulate_guardrails(rules, &Default::default());
+
+    assert_eq!(report.changes_replayed, 3);
+    assert_eq!(report.violations(), 2);
+    assert_eq!(report.blocked(), 1);
+    assert_eq!(report.flagged.len(), 2);
+    // The operation was added before its material.
+    assert_eq!(report.flagged[0].seq, 0);
+    assert!(report.flagged[0].blocked);
+    assert_eq!(report.flagged[0].violations[0].rule_id, "needs-material");
+    assert_eq!(report.flagged[1].violations[0].rule_id, "no-high-speed");
+    assert!(!report.flagged[1].blocked);
+    assert_eq!(report.by_severity[&Severity::Blocking], 1);
+    assert_eq!(report.by_source["user"][&Severity::Blocking], 1);
+    assert_eq!(report.by_source["system"][&Severity::Warning], 1);
+
+    // Lowering the bar counts the warning too; the live graph is untouched.
+    let config = GuardrailSimulationConfig {
+        block_at: Severity::Warning,
+        ..Default::default()
+    };
+    let rules = vec![rule(
+        "no-high-speed",
+        Severity::Warning,
+        &[],
+        &["hasHighSpeed"],
+    )];
+    assert_eq!(storage.simulate_guardrails(rules, &config).blocked(), 1);
+    assert_eq!(storage.pathdb().read().entities.len(), entities_before);
+}
+
+#[test]
+fn test_quality_rules_from_axi_are_scored_per_snapshot() {
+    let (storage, dir) = test_storage();
+    std::fs::write(
+        dir.path().join("services.axi"),
+        "module Services\n\n\
+         schema Services:\n  object ProtoRpc\n  object Person\n  object Endpoint\n  \
+         relation http_endpoint(rpc: ProtoRpc, endpoint: Endpoint)\n\n\
+         theory ServiceQuality on Services:\n  constraint quality_services:\n    \
+         ProtoRpc has http_endpoint\n    Person.name non_empty -- blank names\n    \
+         Person.email unique\n    not a rule\n",
+    )
+    .unwrap();
+    storage.sync_from_axi().unwrap();
+    assert_eq!(storage.schema().read().quality_rules.len(), 3);
+
+    let entity = |name: &str, entity_type: &str, attrs: &[(&str, &str)]| StorableFact::Entity {
+        name: name.to_string(),
+        entity_type: entity_type.to_string(),
+        attributes: std::iter::once(("name", name))
+            .chain(attrs.iter().copied())
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+    };
+    let system = || ChangeSource::System {
+        reason: "quality test".to_string(),
+    };
+    storage
+        .add_facts(
+            vec![
+                entity("GetUser", "ProtoRpc", &[("http_endpoint", "/users/{id}")]),
+                entity("ListUsers", "ProtoRpc", &[]),
+                entity("ada", "Person", &[("email", "a@x.org")]),
+                entity(" ", "Person", &[("email", "a@x.org")]),
+            ],
+            system(),
+        )
+        .unwrap();
+    storage.flush().unwrap();
+
+    let history = quality::QualityHistory::open(dir.path().join("quality/history.jsonl")).unwrap();
+    let first = storage.run_quality_job(&history).unwrap().unwrap();
+    assert_eq!(first.types["ProtoRpc"].completeness, Some(0.5));
+    assert_eq!(first.types["ProtoRpc"].consistency, None);
+    // One blank name out of two, and both share an email.
+    assert_eq!(first.types["Person"].consistency, Some(0.25));
+    assert_eq!(first.rules[0].examples.len(), 1);
+    // Nothing changed: no new point.
+    assert!(storage.run_quality_job(&history).unwrap().is_none());
+
+    // An endpoint relation completes the second RPC.
+    storage
+        .add_facts(
+            vec![
+                entity("/users", "Endpoint", &[]),
+                StorableFact::Relation {
+                    name: None,
+                    rel_type: "http_endpoint".to_string(),
+                    source: "ListUsers".to_string(),
+                    target: "/users".to_string(),
+                    confidence: 1.0,
+                    attributes: vec![],
+                },
+            ],
+            system(),
+        )
+        .unwrap();
+    storage.flush().unwrap();
+    storage.run_quality_job(&history).unwrap().unwrap();
+
+    let trend = history.trend("ProtoRpc").unwrap();
+    assert_eq!(trend.len(), 2);
+    assert_eq!(trend[0].snapshot, first.snapshot);
+    assert_eq!(trend[1].snapshot, storage.snapshot_version());
+    assert_eq!(trend[1].quality.completeness, Some(1.0));
 }
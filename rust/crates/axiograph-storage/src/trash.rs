@@ -0,0 +1,351 @@
+//! Soft delete: a recoverable trash with a retention policy.
+//!
+//! A [`StorableFact::Deletion`] stays in the changelog, and replay (the
+//! live rebuild, `rollback_to`, `pathdb_as_of`) leaves out every earlier fact
+//! it [covers](DeletionTarget::covers): an entity deletion covers the facts
+//! that created the entity and every relation touching it, and a relation
+//! deletion covers that exact edge. Facts added after the deletion are kept,
+//! so a deleted entity can be added again.
+//!
+//! Until it is purged, a deletion is a [`TrashEntry`]:
+//!
+//! - [`UnifiedStorage::restore_from_trash`] undoes it within the retention
+//!   window;
+//! - [`UnifiedStorage::pathdb_including_deleted`] is the graph with every
+//!   trashed fact still in place;
+//! - [`UnifiedStorage::purge_trash`] is the compaction job: it drops expired
+//!   entries and scrubs the facts they cover from the changelog, after which
+//!   they are gone from every replay, `pathdb_as_of` included. Periodic
+//!   snapshots that hold a scrubbed fact are discarded too; the live graph
+//!   already leaves those facts out, so it is not rebuilt. The append-only
+//!   `.axi` provenance files are not rewritten.
+//!
+//! With `TrashPolicy::soft_delete` off, every flush purges its deletions
+//! right away (a hard delete).
+//!
+//! Applying a relation deletion removes the edges from the live PathDB in
+//! place. PathDB cannot drop entities, so an entity deletion rebuilds the
+//! live graph by replay, keeping its declarations (see
+//! [`PathDB::empty_with_declarations`]).
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use axiograph_pathdb::PathDB;
+
+use crate::{
+    replay_changes, Change, ChangeId, ChangeStatus, Result, StorableFact, StorageError,
+    UnifiedStorage,
+};
+
+/// How deletions are kept.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrashPolicy {
+    /// Keep deletions recoverable; `false` purges them on flush.
+    pub soft_delete: bool,
+    /// Days a deletion stays restorable before `purge_trash` drops it.
+    pub retention_days: u32,
+}
+
+impl Default for TrashPolicy {
+    fn default() -> Self {
+        Self {
+            soft_delete: true,
+            retention_days: 30,
+        }
+    }
+}
+
+/// What a [`StorableFact::Deletion`] removes (endpoints matched by `name`).
+/// Decay facts refer to relations and tacit knowledge the same way.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeletionTarget {
+    /// The entity and every relation touching it.
+    Entity { name: String },
+    /// Every `source -rel_type-> target` edge.
+    Relation {
+        rel_type: String,
+        source: String,
+        target: String,
+    },
+}
+
+impl DeletionTarget {
+    /// Whether `fact` is part of what this deletion removes.
+    pub fn covers(&self, fact: &StorableFact) -> bool {
+        match self {
+            DeletionTarget::Entity { name: deleted } => match fact {
+                StorableFact::Entity {
+                    name, attributes, ..
+                } => name == deleted || attributes.iter().any(|(k, v)| k == "name" && v == deleted),
+                StorableFact::TacitKnowledge { name, .. }
+                | StorableFact::Concept { name, .. }
+                | StorableFact::SafetyGuideline { name, .. } => name == deleted,
+                StorableFact::Relation { source, target, .. }
+                | StorableFact::Retraction { source, target, .. } => {
+                    source == deleted || target == deleted
+                }
+                StorableFact::ConfidenceDecay { target, .. }
+                | StorableFact::DecayExemption { target, .. }
+                | StorableFact::Verification { target, .. } => match target {
+                    DeletionTarget::Entity { name } => name == deleted,
+                    DeletionTarget::Relation { source, target, .. } => {
+                        source == deleted || target == deleted
+                    }
+                },
+                StorableFact::Constraint { .. } | StorableFact::Deletion { .. } => false,
+            },
+            DeletionTarget::Relation {
+                rel_type: deleted_type,
+                source: deleted_source,
+                target: deleted_target,
+            } => match fact {
+                StorableFact::Relation {
+                    rel_type,
+                    source,
+                    target,
+                    ..
+                }
+                | StorableFact::Retraction {
+                    rel_type,
+                    source,
+                    target,
+                    ..
+                } => {
+                    rel_type == deleted_type && source == deleted_source && target == deleted_target
+                }
+                StorableFact::ConfidenceDecay { target, .. }
+                | StorableFact::DecayExemption { target, .. }
+                | StorableFact::Verification { target, .. } => target == self,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for DeletionTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeletionTarget::Entity { name } => write!(f, "entity {name}"),
+            DeletionTarget::Relation {
+                rel_type,
+                source,
+                target,
+            } => write!(f, "{rel_type}({source}, {target})"),
+        }
+    }
+}
+
+impl StorableFact {
+    /// A soft delete of `target`.
+    pub fn deletion(target: DeletionTarget, reason: impl Into<String>) -> Self {
+        StorableFact::Deletion {
+            target,
+            reason: reason.into(),
+            restored_at: None,
+            purged_at: None,
+        }
+    }
+}
+
+/// A deletion that can still be restored (or purged).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub change_id: ChangeId,
+    /// Index of the deletion among the change's facts.
+    pub fact: usize,
+    pub target: DeletionTarget,
+    pub reason: String,
+    pub deleted_at: DateTime<Utc>,
+    /// After this, the entry is no longer restorable and `purge_trash` drops it.
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A deletion fact in effect at `t`: its change is believed and it was not
+/// restored by then.
+pub(crate) fn active_deletion(change: &Change, fact: &StorableFact, t: DateTime<Utc>) -> bool {
+    match fact {
+        StorableFact::Deletion { restored_at, .. } => {
+            change.believed_at(t) && !restored_at.is_some_and(|r| r <= t)
+        }
+        _ => false,
+    }
+}
+
+/// Whether the snapshot of `changelog[..=index]` taken at `taken_at` already
+/// reflects, at `t`, every deletion that replay would apply.
+pub(crate) fn snapshot_reflects_deletions(
+    changelog: &[Change],
+    index: usize,
+    taken_at: DateTime<Utc>,
+    t: DateTime<Utc>,
+) -> bool {
+    changelog.iter().enumerate().all(|(i, change)| {
+        change.facts.iter().all(|fact| {
+            if i > index {
+                !active_deletion(change, fact, t)
+            } else {
+                active_deletion(change, fact, taken_at) == active_deletion(change, fact, t)
+            }
+        })
+    })
+}
+
+impl UnifiedStorage {
+    /// Deletions still in the trash, oldest first.
+    pub fn trash(&self) -> Vec<TrashEntry> {
+        let retention = Duration::days(i64::from(self.config.trash.retention_days));
+        let changelog = self.changelog.read();
+        let mut entries = Vec::new();
+        for change in changelog.iter() {
+            if !matches!(change.status, ChangeStatus::Applied) {
+                continue;
+            }
+            for (fact, f) in change.facts.iter().enumerate() {
+                if let StorableFact::Deletion {
+                    target,
+                    reason,
+                    restored_at: None,
+                    purged_at: None,
+                } = f
+                {
+                    let deleted_at = change.applied_time();
+                    entries.push(TrashEntry {
+                        change_id: change.id,
+                        fact,
+                        target: target.clone(),
+                        reason: reason.clone(),
+                        deleted_at,
+                        expires_at: deleted_at + retention,
+                    });
+                }
+            }
+        }
+        entries
+    }
+
+    /// Undo a deletion that is still within its retention window.
+    pub fn restore_from_trash(&self, change_id: ChangeId, fact: usize) -> Result<TrashEntry> {
+        let now = Utc::now();
+        let entry = self
+            .trash()
+            .into_iter()
+            .find(|e| e.change_id == change_id && e.fact == fact)
+            .ok_or_else(|| StorageError::NotRestorable {
+                change_id,
+                fact,
+                reason: "not in the trash".to_string(),
+            })?;
+        if entry.expires_at <= now {
+            return Err(StorageError::NotRestorable {
+                change_id,
+                fact,
+                reason: format!("retention expired at {}", entry.expires_at),
+            });
+        }
+
+        {
+            let mut changelog = self.changelog.write();
+            if let Some(StorableFact::Deletion { restored_at, .. }) = changelog
+                .iter_mut()
+                .find(|c| c.id == change_id)
+                .and_then(|c| c.facts.get_mut(fact))
+            {
+                *restored_at = Some(now);
+            }
+        }
+        self.rebuild_from_changelog()?;
+        self.save_changelog()?;
+        self.save_pathdb()?;
+        tracing::info!(%change_id, fact, target = %entry.target, "restored from trash");
+        Ok(entry)
+    }
+
+    /// Compaction job: purge every trash entry expired at `now` (see the
+    /// module docs). Returns the purged entries.
+    pub fn purge_trash(&self, now: DateTime<Utc>) -> Result<Vec<TrashEntry>> {
+        let purged = self.purge_where(|entry| entry.expires_at <= now)?;
+        if !purged.is_empty() {
+            self.save_changelog()?;
+            self.save_pathdb()?;
+        }
+        Ok(purged)
+    }
+
+    /// Purge the trash entries `select` picks, without saving.
+    pub(crate) fn purge_where(
+        &self,
+        select: impl Fn(&TrashEntry) -> bool,
+    ) -> Result<Vec<TrashEntry>> {
+        let purged: Vec<TrashEntry> = self.trash().into_iter().filter(|e| select(e)).collect();
+        if purged.is_empty() {
+            return Ok(purged);
+        }
+        let now = Utc::now();
+
+        let mut changelog = self.changelog.write();
+        let mut first_scrubbed: Option<usize> = None;
+        // Mark first: fact indexes are only valid before anything is scrubbed.
+        for entry in &purged {
+            if let Some(StorableFact::Deletion { purged_at, .. }) = changelog
+                .iter_mut()
+                .find(|c| c.id == entry.change_id)
+                .and_then(|c| c.facts.get_mut(entry.fact))
+            {
+                *purged_at = Some(now);
+            }
+        }
+        for entry in &purged {
+            let Some(position) = changelog.iter().position(|c| c.id == entry.change_id) else {
+                continue;
+            };
+            for (index, change) in changelog.iter_mut().take(position + 1).enumerate() {
+                // Deletions are never covered, so the marked one stays put.
+                let mut before_deletion = change.id != entry.change_id;
+                let len = change.facts.len();
+                change.facts.retain(|fact| {
+                    if let StorableFact::Deletion {
+                        target,
+                        purged_at: Some(at),
+                        ..
+                    } = fact
+                    {
+                        if *at == now && *target == entry.target {
+                            before_deletion = false;
+                        }
+                    }
+                    !(before_deletion && entry.target.covers(fact))
+                });
+                if change.facts.len() < len {
+                    first_scrubbed = Some(first_scrubbed.map_or(index, |i| i.min(index)));
+                }
+            }
+        }
+        drop(changelog);
+
+        // Snapshots from the first scrubbed change on still hold what the
+        // purged deletions covered.
+        if let Some(first) = first_scrubbed {
+            self.discard_snapshots_from(first)?;
+        }
+        tracing::info!(entries = purged.len(), "purged trash");
+        Ok(purged)
+    }
+
+    /// The current graph with every trashed (not yet purged) fact in place.
+    pub fn pathdb_including_deleted(&self) -> PathDB {
+        let changelog = self.changelog.read();
+        let mut pathdb = PathDB::new();
+        replay_changes(
+            &mut pathdb,
+            &changelog,
+            0,
+            |c| matches!(c.status, ChangeStatus::Applied),
+            None,
+            self.config.on_duplicate,
+        );
+        pathdb.build_indexes();
+        pathdb
+    }
+}
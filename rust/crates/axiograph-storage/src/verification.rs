@@ -0,0 +1,155 @@
+//! Human verification of stored facts.
+//!
+//! A reviewer who checked a relation or a named fact (tacit knowledge,
+//! entities) records it with [`UnifiedStorage::verify_fact`]. That appends a
+//! [`StorableFact::Verification`] in a `UserEdit` change stamped with the
+//! reviewer's id, so the act is in the changelog with everything else:
+//!
+//! - the fact's confidence is raised by the configured [`VerificationRule`]
+//!   (`StorageConfig::verification`) and set on replay like a decay update;
+//! - verified facts are exempt from confidence decay (see `decay`);
+//! - the reviewer and the optional note travel with the fact.
+
+use serde::{Deserialize, Serialize};
+
+use axiograph_pathdb::VerifiedProb;
+
+use crate::decay::current_confidence;
+use crate::{
+    ChangeId, ChangeSource, DeletionTarget, Result, StorableFact, StorageError, UnifiedStorage,
+};
+
+/// How a verification changes a fact's confidence.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum VerificationRule {
+    /// Set the confidence to `confidence`.
+    Set { confidence: f32 },
+    /// Raise the confidence to at least `confidence`.
+    AtLeast { confidence: f32 },
+    /// Count the review as independent evidence of strength `weight`:
+    /// `1 - (1 - old) * (1 - weight)`.
+    Corroborate { weight: f32 },
+}
+
+impl Default for VerificationRule {
+    fn default() -> Self {
+        VerificationRule::Corroborate { weight: 0.9 }
+    }
+}
+
+impl VerificationRule {
+    /// The confidence after verifying a fact at `old` (`None` when the fact
+    /// carries no confidence; treated as 0). Out-of-range inputs are clamped.
+    pub fn apply(&self, old: Option<f32>) -> f32 {
+        let prob =
+            |p: f32| VerifiedProb::try_new(p.clamp(0.0, 1.0)).unwrap_or(VerifiedProb::IMPOSSIBLE);
+        let old = prob(old.unwrap_or(0.0));
+        match *self {
+            VerificationRule::Set { confidence } => prob(confidence).value(),
+            VerificationRule::AtLeast { confidence } => old.value().max(prob(confidence).value()),
+            VerificationRule::Corroborate { weight } => old.or_independent(&prob(weight)).value(),
+        }
+    }
+}
+
+/// Result of [`UnifiedStorage::verify_fact`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerificationRecord {
+    pub change_id: ChangeId,
+    pub target: DeletionTarget,
+    pub verifier: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Confidence before verifying (`None` if the fact had none).
+    pub from: Option<f32>,
+    pub to: f32,
+}
+
+impl StorableFact {
+    /// The relation or named fact this fact stores, as verification and decay
+    /// address it (`None` for constraints and bookkeeping facts).
+    pub fn target(&self) -> Option<DeletionTarget> {
+        match self {
+            StorableFact::Entity {
+                name, attributes, ..
+            } => {
+                let name = attributes
+                    .iter()
+                    .find(|(k, _)| k == "name")
+                    .map_or(name, |(_, v)| v);
+                Some(DeletionTarget::Entity { name: name.clone() })
+            }
+            StorableFact::TacitKnowledge { name, .. }
+            | StorableFact::Concept { name, .. }
+            | StorableFact::SafetyGuideline { name, .. } => {
+                Some(DeletionTarget::Entity { name: name.clone() })
+            }
+            StorableFact::Relation {
+                rel_type,
+                source,
+                target,
+                ..
+            } => Some(DeletionTarget::Relation {
+                rel_type: rel_type.clone(),
+                source: source.clone(),
+                target: target.clone(),
+            }),
+            StorableFact::Constraint { .. }
+            | StorableFact::Retraction { .. }
+            | StorableFact::Deletion { .. }
+            | StorableFact::ConfidenceDecay { .. }
+            | StorableFact::DecayExemption { .. }
+            | StorableFact::Verification { .. } => None,
+        }
+    }
+}
+
+impl UnifiedStorage {
+    /// Record that `verifier` checked `target`: raise its confidence by
+    /// `StorageConfig::verification` and append the act to the changelog.
+    /// Fails if nothing in the graph matches `target`.
+    pub fn verify_fact(
+        &self,
+        target: DeletionTarget,
+        verifier: &str,
+        note: Option<&str>,
+    ) -> Result<VerificationRecord> {
+        let from = {
+            let pathdb = self.pathdb.read();
+            let exists = match &target {
+                DeletionTarget::Entity { name } => {
+                    !pathdb.entities_with_attr_fuzzy("name", name, 0).is_empty()
+                }
+                DeletionTarget::Relation { .. } => current_confidence(&pathdb, &target).is_some(),
+            };
+            if !exists {
+                return Err(StorageError::FactNotFound(target.to_string()));
+            }
+            current_confidence(&pathdb, &target)
+        };
+        let to = self.config.verification.apply(from);
+        let note = note.map(str::to_string);
+        let change_id = self.add_facts(
+            vec![StorableFact::Verification {
+                target: target.clone(),
+                verifier: verifier.to_string(),
+                note: note.clone(),
+                confidence: to,
+            }],
+            ChangeSource::UserEdit {
+                user_id: Some(verifier.to_string()),
+            },
+        )?;
+        self.flush()?;
+        tracing::info!(%change_id, %target, verifier, "verified fact");
+        Ok(VerificationRecord {
+            change_id,
+            target,
+            verifier: verifier.to_string(),
+            note,
+            from,
+            to,
+        })
+    }
+}
@@ -0,0 +1,114 @@
+//! API graph tutorial: proto descriptors → proposals → PathDB → queries →
+//! certificate.
+//!
+//! Ingests the bundled `examples/proto/large_api/descriptor.json` fixture (a
+//! Buf descriptor set for a small multi-service API), loads the proposals
+//! into a PathDB, runs a few representative traversals, and emits a
+//! reachability certificate for one of the answers.
+//!
+//! Run:
+//!   cd rust && cargo run --example api_graph
+
+mod common;
+
+use anyhow::Result;
+use axiograph_ingest_proto::ingest_descriptor_set_json;
+
+fn main() -> Result<()> {
+    let path = common::fixture("proto/large_api/descriptor.json");
+    println!("━━━ Step 1: Ingest {} ━━━", path.display());
+    let result = ingest_descriptor_set_json(
+        &std::fs::read_to_string(&path)?,
+        Some(path.to_string_lossy().to_string()),
+        Some("proto_api".to_string()),
+    )?;
+    println!(
+        "  {} files, {} services, {} rpcs → {} proposals, {} chunks",
+        result.stats.files,
+        result.stats.services,
+        result.stats.rpcs,
+        result.proposals.len(),
+        result.chunks.len()
+    );
+
+    println!("\n━━━ Step 2: Load into PathDB ━━━");
+    let db = &common::load_proposals(&result.proposals);
+    println!(
+        "  {} entities, {} relations",
+        db.entities.len(),
+        db.relations.len()
+    );
+    for ty in ["ProtoService", "ProtoRpc", "ProtoMessage", "ApiWorkflow"] {
+        let n = db.find_by_type(ty).map_or(0, |ids| ids.len());
+        println!("  {ty:<14} {n}");
+    }
+
+    println!("\n━━━ Step 3: Query ━━━");
+    let services: Vec<u32> = db
+        .find_by_type("ProtoService")
+        .map(|ids| ids.iter().collect())
+        .unwrap_or_default();
+    for &service in &services {
+        let rpcs = db.follow_path(service, &["proto_service_has_rpc"]);
+        let http = rpcs
+            .iter()
+            .filter(|&rpc| {
+                db.get_entity(rpc)
+                    .is_some_and(|e| e.attrs.contains_key("http_method"))
+            })
+            .count();
+        let workflow_rpcs = db.follow_path(
+            service,
+            &["proto_service_has_workflow", "workflow_includes_rpc"],
+        );
+        println!(
+            "  {}: {} rpcs ({} with HTTP bindings), {} in heuristic workflows",
+            common::name_of(db, service),
+            rpcs.len(),
+            http,
+            workflow_rpcs.len()
+        );
+    }
+
+    // Suggested call order within the largest workflow: start at the rpc no
+    // other rpc precedes and follow `workflow_suggests_order`.
+    let largest = db.find_by_type("ApiWorkflow").and_then(|ids| {
+        ids.iter()
+            .max_by_key(|&wf| db.follow_path(wf, &["workflow_includes_rpc"]).len())
+    });
+    if let Some(workflow) = largest {
+        let rpcs = db.follow_path(workflow, &["workflow_includes_rpc"]);
+        let successors = |rpc: u32| db.follow_path(rpc, &["workflow_suggests_order"]) & &rpcs;
+        let first = rpcs
+            .iter()
+            .find(|&rpc| !rpcs.iter().any(|other| successors(other).contains(rpc)));
+        let mut order = Vec::new();
+        let mut current = first;
+        while let Some(rpc) = current.filter(|rpc| !order.contains(rpc)) {
+            order.push(rpc);
+            current = successors(rpc).min();
+        }
+        println!("  {}:", common::name_of(db, workflow));
+        let names: Vec<String> = order.iter().map(|&rpc| common::name_of(db, rpc)).collect();
+        println!("    {}", names.join("\n    → "));
+    }
+
+    println!("\n━━━ Step 4: Certificate ━━━");
+    let path = ["proto_service_has_workflow", "workflow_includes_rpc"];
+    let Some(&service) = services
+        .iter()
+        .find(|&&s| !db.follow_path(s, &path).is_empty())
+    else {
+        println!("  no service → workflow → rpc walk to certify");
+        return Ok(());
+    };
+    let cert = common::reachability_certificate(db, service, &path)?;
+    let out = common::write_certificate("api_graph_reachability_v2.json", &cert)?;
+    println!(
+        "  {} reaches an rpc via {}",
+        common::name_of(db, service),
+        path.join("/")
+    );
+    println!("  → {}", out.display());
+    Ok(())
+}
@@ -0,0 +1,109 @@
+//! Shared helpers for the fixture-driven examples (`api_graph`, `rdf_graph`):
+//! locate the bundled fixtures, load ingestion proposals into a PathDB, and
+//! emit reachability certificates for query results.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use axiograph_ingest_docs::ProposalV1;
+use axiograph_pathdb::witness::reachability_proof_v2_from_relation_ids;
+use axiograph_pathdb::{CertificateV2, PathDB};
+
+/// Repository root (the parent of `rust/`).
+pub fn repo_root() -> PathBuf {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir.parent().unwrap_or(&manifest_dir).to_path_buf()
+}
+
+/// A fixture under the top-level `examples/` directory.
+pub fn fixture(relative: &str) -> PathBuf {
+    repo_root().join("examples").join(relative)
+}
+
+/// Load proposals as plain PathDB entities and relations (the evidence-plane
+/// shape `axiograph db import-proposals` produces, minus provenance nodes).
+/// Relations whose endpoints were not proposed as entities are skipped.
+pub fn load_proposals(proposals: &[ProposalV1]) -> PathDB {
+    let mut db = PathDB::new();
+    let mut ids = HashMap::new();
+    for p in proposals {
+        let ProposalV1::Entity {
+            entity_id,
+            entity_type,
+            name,
+            attributes,
+            ..
+        } = p
+        else {
+            continue;
+        };
+        if ids.contains_key(entity_id) {
+            continue;
+        }
+        let mut attrs = vec![("name", name.as_str()), ("proposal_id", entity_id.as_str())];
+        attrs.extend(
+            attributes
+                .iter()
+                .filter(|(k, _)| k.as_str() != "name")
+                .map(|(k, v)| (k.as_str(), v.as_str())),
+        );
+        ids.insert(entity_id.clone(), db.add_entity(entity_type, attrs));
+    }
+    for p in proposals {
+        let ProposalV1::Relation {
+            meta,
+            rel_type,
+            source,
+            target,
+            ..
+        } = p
+        else {
+            continue;
+        };
+        if let (Some(&s), Some(&t)) = (ids.get(source), ids.get(target)) {
+            db.add_relation(rel_type, s, t, meta.confidence as f32, Vec::new());
+        }
+    }
+    db.build_indexes();
+    db
+}
+
+pub fn name_of(db: &PathDB, id: u32) -> String {
+    db.get_entity(id)
+        .and_then(|e| e.attrs.get("name").cloned())
+        .unwrap_or_else(|| format!("#{id}"))
+}
+
+/// Relation ids of one walk along `path` from `start`, if any exists.
+fn witness_chain(db: &PathDB, start: u32, path: &[&str]) -> Option<Vec<u32>> {
+    let Some((first, rest)) = path.split_first() else {
+        return Some(Vec::new());
+    };
+    let rel_type = db.interner.id_of(first)?;
+    for &rel_id in db.relations.outgoing_relation_ids(start, rel_type) {
+        let target = db.relations.get_relation(rel_id)?.target;
+        if let Some(mut chain) = witness_chain(db, target, rest) {
+            chain.insert(0, rel_id);
+            return Some(chain);
+        }
+    }
+    None
+}
+
+/// A reachability certificate for one walk along `path` from `start`.
+pub fn reachability_certificate(db: &PathDB, start: u32, path: &[&str]) -> Result<CertificateV2> {
+    let chain = witness_chain(db, start, path)
+        .ok_or_else(|| anyhow!("no walk along {path:?} from #{start}"))?;
+    let proof = reachability_proof_v2_from_relation_ids(db, start, &chain)?;
+    Ok(CertificateV2::reachability(proof.into_inner()))
+}
+
+/// Write `cert` to `build/examples/<file_name>` and return the path.
+pub fn write_certificate(file_name: &str, cert: &CertificateV2) -> Result<PathBuf> {
+    let dir = repo_root().join("build/examples");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(file_name);
+    std::fs::write(&path, serde_json::to_string_pretty(cert)?)?;
+    Ok(path)
+}
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        if let Ok(iges) = axiograph_ingest_iges::parse_iges(text) {
+            let _ = axiograph_ingest_iges::iges_to_module(&iges, "Fuzz");
+        }
+    }
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+use axiograph_pathdb::PathDB;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Anything `from_bytes` accepts must be safe to read.
+    if let Ok(db) = PathDB::from_bytes(data) {
+        for id in 0..db.entities.len() as u32 {
+            let _ = db.get_entity(id);
+            let _ = db.relations.outgoing_any(id);
+            let _ = db.relations.incoming_any(id);
+        }
+        let _ = db.to_bytes();
+    }
+});
@@ -0,0 +1,24 @@
+#![no_main]
+
+use axiograph_ingest_rdfowl::owl::OwlParser;
+use axiograph_ingest_rdfowl::{proposals_from_rdf_v1, RdfFormatV1};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // First byte selects the syntax so one corpus covers every reader.
+    let Some((&selector, body)) = data.split_first() else {
+        return;
+    };
+    let format = match selector % 5 {
+        0 => RdfFormatV1::NTriples,
+        1 => RdfFormatV1::Turtle,
+        2 => RdfFormatV1::NQuads,
+        3 => RdfFormatV1::TriG,
+        _ => RdfFormatV1::RdfXml,
+    };
+    let _ = proposals_from_rdf_v1(body, format, None, None);
+
+    if let Ok(text) = std::str::from_utf8(body) {
+        let _ = OwlParser::new().parse_ntriples(text);
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        if let Ok(entities) = axiograph_ingest_step::parse_step(text) {
+            let _ = axiograph_ingest_step::lift_brep(&entities);
+        }
+        let _ = axiograph_ingest_step::step_to_module(text, "Fuzz");
+    }
+});
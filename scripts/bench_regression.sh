@@ -0,0 +1,112 @@
+#!/bin/bash
+set -euo pipefail
+
+# PathDB benchmark regression check (criterion).
+#
+# Runs the `axiograph-pathdb` criterion suite, collects mean estimates from
+# `rust/target/criterion/**/new/estimates.json`, and compares them against the
+# committed baseline JSON. Exits non-zero if any benchmark regressed by more
+# than the threshold.
+#
+# Run:
+#   ./scripts/bench_regression.sh            # compare against baseline
+#   ./scripts/bench_regression.sh --update   # overwrite baseline with this run
+#
+# Tunables (env vars):
+#   BENCH_THRESHOLD=0.15   allowed slowdown (fraction of baseline mean)
+#   BENCH_BASELINE=...     baseline JSON path
+#   BENCH_OUT=...          where to write this run's JSON
+#   BENCH_SKIP_RUN=1       reuse existing criterion output
+
+SCRIPT_DIR="$(cd "$(dirname "$0")" && pwd)"
+ROOT_DIR="$(cd "$SCRIPT_DIR/.." && pwd)"
+cd "$ROOT_DIR"
+
+UPDATE=0
+if [ "${1:-}" = "--update" ]; then
+  UPDATE=1
+fi
+
+BENCH_THRESHOLD="${BENCH_THRESHOLD:-0.15}"
+BENCH_BASELINE="${BENCH_BASELINE:-$ROOT_DIR/rust/crates/axiograph-pathdb/benches/baseline.json}"
+BENCH_OUT="${BENCH_OUT:-$ROOT_DIR/build/bench/pathdb.json}"
+CRITERION_DIR="$ROOT_DIR/rust/target/criterion"
+
+echo "== Axiograph bench regression (pathdb) =="
+
+if [ "${BENCH_SKIP_RUN:-0}" != "1" ]; then
+  echo ""
+  echo "-- cargo bench"
+  (cd rust && cargo bench -p axiograph-pathdb --bench pathdb)
+fi
+
+mkdir -p "$(dirname "$BENCH_OUT")"
+
+python3 - "$CRITERION_DIR" "$BENCH_OUT" <<'PY'
+import json, os, sys
+
+criterion_dir, out_path = sys.argv[1], sys.argv[2]
+benchmarks = {}
+for root, _dirs, files in os.walk(criterion_dir):
+    if os.path.basename(root) != "new" or "estimates.json" not in files:
+        continue
+    with open(os.path.join(root, "benchmark.json")) as f:
+        full_id = json.load(f)["full_id"]
+    with open(os.path.join(root, "estimates.json")) as f:
+        est = json.load(f)
+    benchmarks[full_id] = {
+        "mean_ns": est["mean"]["point_estimate"],
+        "median_ns": est["median"]["point_estimate"],
+    }
+
+if not benchmarks:
+    sys.exit(f"error: no criterion results under {criterion_dir}")
+
+with open(out_path, "w") as f:
+    json.dump(
+        {"version": "axiograph_bench_v1", "suite": "pathdb", "benchmarks": dict(sorted(benchmarks.items()))},
+        f,
+        indent=2,
+    )
+    f.write("\n")
+print(f"wrote {out_path} ({len(benchmarks)} benchmarks)")
+PY
+
+if [ "$UPDATE" = "1" ]; then
+  cp "$BENCH_OUT" "$BENCH_BASELINE"
+  echo "updated baseline: $BENCH_BASELINE"
+  exit 0
+fi
+
+if [ ! -f "$BENCH_BASELINE" ]; then
+  echo "error: missing baseline $BENCH_BASELINE (run with --update)"
+  exit 2
+fi
+
+python3 - "$BENCH_BASELINE" "$BENCH_OUT" "$BENCH_THRESHOLD" <<'PY'
+import json, sys
+
+baseline_path, current_path, threshold = sys.argv[1], sys.argv[2], float(sys.argv[3])
+baseline = json.load(open(baseline_path))["benchmarks"]
+current = json.load(open(current_path))["benchmarks"]
+
+regressions = []
+print(f"{'benchmark':<40} {'baseline':>12} {'current':>12} {'change':>8}")
+for name in sorted(set(baseline) | set(current)):
+    if name not in current:
+        print(f"{name:<40} {'':>12} {'missing':>12}")
+        continue
+    if name not in baseline:
+        print(f"{name:<40} {'new':>12} {current[name]['mean_ns']:>12.0f}")
+        continue
+    base, cur = baseline[name]["mean_ns"], current[name]["mean_ns"]
+    change = (cur - base) / base
+    flag = "  REGRESSION" if change > threshold else ""
+    print(f"{name:<40} {base:>12.0f} {cur:>12.0f} {change:>+7.1%}{flag}")
+    if change > threshold:
+        regressions.append(name)
+
+if regressions:
+    sys.exit(f"\n{len(regressions)} benchmark(s) regressed by more than {threshold:.0%}")
+print(f"\nno regressions above {threshold:.0%}")
+PY